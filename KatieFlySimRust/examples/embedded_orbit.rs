@@ -0,0 +1,75 @@
+// Embedded Orbit - Minimal host program for the embedding API
+// Flies a rocket off the spawn body of a map and draws it; pass --headless to print the flight instead
+
+use macroquad::prelude::*;
+
+use katie_fly_sim_rust::embed::{RocketControls, Simulation};
+use katie_fly_sim_rust::systems::EntityId;
+
+/// Seconds of full-throttle burn before the rocket coasts
+const BURN_TIME: f32 = 8.0;
+
+/// Full throttle for the first few seconds, then coast
+fn controls_at(time: f32) -> RocketControls {
+    RocketControls {
+        thrust: if time < BURN_TIME { 1.0 } else { 0.0 },
+        turn_rate: 0.0,
+    }
+}
+
+fn run_headless(mut simulation: Simulation, rocket_id: EntityId) {
+    for second in 0..30 {
+        simulation.set_controls(rocket_id, controls_at(second as f32));
+        simulation.advance(1.0);
+        if let Some(rocket) = simulation.rocket(rocket_id) {
+            println!(
+                "t={:>5.1}s  pos=({:>9.1}, {:>9.1})  speed={:>7.2}  fuel={:>6.1}",
+                simulation.time(),
+                rocket.position.x,
+                rocket.position.y,
+                rocket.velocity.length(),
+                rocket.fuel,
+            );
+        }
+    }
+}
+
+fn main() {
+    let map_name = std::env::args().skip(1).find(|arg| !arg.starts_with("--")).unwrap_or_else(|| "earth moon".to_string());
+    let mut simulation = match Simulation::from_map_name(&map_name) {
+        Ok(simulation) => simulation,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+    let Some(rocket_id) = simulation.spawn_rocket(Some(0)) else {
+        eprintln!("Map has no spawn body");
+        return;
+    };
+
+    // The simulation itself needs no window
+    if std::env::args().any(|arg| arg == "--headless") {
+        run_headless(simulation, rocket_id);
+    } else {
+        macroquad::Window::new("Embedded Orbit", run_windowed(simulation, rocket_id));
+    }
+}
+
+async fn run_windowed(mut simulation: Simulation, rocket_id: EntityId) {
+    loop {
+        if is_key_pressed(KeyCode::Escape) {
+            break;
+        }
+        simulation.set_controls(rocket_id, controls_at(simulation.time()));
+        simulation.advance(get_frame_time().min(0.1));
+
+        clear_background(BLACK);
+        let center = simulation.rocket(rocket_id).map(|rocket| rocket.position).unwrap_or_default();
+        let camera = Simulation::camera_on(center, 20_000.0, vec2(screen_width(), screen_height()));
+        simulation.render(&camera);
+        draw_text(&format!("{}  t = {:.1}s", simulation.map().name, simulation.time()), 20.0, 30.0, 24.0, WHITE);
+
+        next_frame().await;
+    }
+}
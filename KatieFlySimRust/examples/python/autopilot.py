@@ -0,0 +1,93 @@
+# Autopilot - Script a rocket from Python through the C ABI (ctypes, no extra packages)
+# Build first: cargo rustc --release --lib --crate-type cdylib
+
+import ctypes
+import math
+import os
+import pathlib
+import sys
+
+LIB_NAMES = {"linux": "libkatie_fly_sim_rust.so", "darwin": "libkatie_fly_sim_rust.dylib", "win32": "katie_fly_sim_rust.dll"}
+
+
+class RocketState(ctypes.Structure):
+    _fields_ = [
+        ("id", ctypes.c_uint64),
+        ("player_id", ctypes.c_int32),
+        ("x", ctypes.c_float), ("y", ctypes.c_float),
+        ("vx", ctypes.c_float), ("vy", ctypes.c_float),
+        ("rotation", ctypes.c_float),
+        ("fuel", ctypes.c_float),
+        ("max_fuel", ctypes.c_float),
+        ("landed", ctypes.c_bool),
+    ]
+
+
+class BodyState(ctypes.Structure):
+    _fields_ = [
+        ("id", ctypes.c_uint64),
+        ("x", ctypes.c_float), ("y", ctypes.c_float),
+        ("vx", ctypes.c_float), ("vy", ctypes.c_float),
+        ("radius", ctypes.c_float),
+        ("mass", ctypes.c_float),
+    ]
+
+
+def load_library():
+    # KFS_LIB overrides the library path (e.g. a debug build)
+    root = pathlib.Path(__file__).resolve().parents[2]
+    default = root / "target" / "release" / LIB_NAMES.get(sys.platform, LIB_NAMES["linux"])
+    lib = ctypes.CDLL(os.environ.get("KFS_LIB", str(default)))
+    sim = ctypes.c_void_p
+    lib.kfs_last_error.restype = ctypes.c_char_p
+    lib.kfs_simulation_new.argtypes = [ctypes.c_char_p]
+    lib.kfs_simulation_new.restype = sim
+    lib.kfs_simulation_free.argtypes = [sim]
+    lib.kfs_simulation_spawn_rocket.argtypes = [sim, ctypes.c_int32]
+    lib.kfs_simulation_spawn_rocket.restype = ctypes.c_int64
+    lib.kfs_simulation_set_controls.argtypes = [sim, ctypes.c_uint64, ctypes.c_float, ctypes.c_float]
+    lib.kfs_simulation_set_controls.restype = ctypes.c_bool
+    lib.kfs_simulation_advance.argtypes = [sim, ctypes.c_float]
+    lib.kfs_simulation_advance.restype = ctypes.c_uint32
+    lib.kfs_simulation_time.argtypes = [sim]
+    lib.kfs_simulation_time.restype = ctypes.c_float
+    lib.kfs_simulation_rocket_state.argtypes = [sim, ctypes.c_uint64, ctypes.POINTER(RocketState)]
+    lib.kfs_simulation_rocket_state.restype = ctypes.c_bool
+    lib.kfs_simulation_body_at.argtypes = [sim, ctypes.c_uint32, ctypes.POINTER(BodyState)]
+    lib.kfs_simulation_body_at.restype = ctypes.c_bool
+    return lib
+
+
+def main():
+    lib = load_library()
+    sim = lib.kfs_simulation_new(b"earth moon")
+    if not sim:
+        sys.exit(lib.kfs_last_error().decode())
+    rocket_id = lib.kfs_simulation_spawn_rocket(sim, 0)
+
+    earth = BodyState()
+    lib.kfs_simulation_body_at(sim, 0, ctypes.byref(earth))
+    rocket = RocketState()
+
+    # Turn to face straight away from the body, burn up to the target altitude, then coast
+    target_altitude = 2000.0
+    for _ in range(80):
+        lib.kfs_simulation_rocket_state(sim, rocket_id, ctypes.byref(rocket))
+        dx, dy = rocket.x - earth.x, rocket.y - earth.y
+        altitude = math.hypot(dx, dy) - earth.radius
+
+        # Rotation 0 thrusts towards -y; heading r thrusts along (sin r, -cos r)
+        heading = math.atan2(dx, -dy)
+        error = (heading - rocket.rotation + math.pi) % (2.0 * math.pi) - math.pi
+        turn_rate = max(-3.0, min(3.0, error * 4.0))
+        thrust = 1.0 if altitude < target_altitude and abs(error) < 0.2 else 0.0
+
+        lib.kfs_simulation_set_controls(sim, rocket_id, thrust, turn_rate)
+        lib.kfs_simulation_advance(sim, 0.25)
+        print(f"t={lib.kfs_simulation_time(sim):5.1f}s altitude={altitude:8.1f} fuel={rocket.fuel:6.1f} thrust={thrust:.0f}")
+
+    lib.kfs_simulation_free(sim)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,63 @@
+/* katie_fly_sim.h - C ABI for the headless KatieFlySimRust simulation
+ * Build the library with: cargo rustc --release --lib --crate-type cdylib
+ */
+#ifndef KATIE_FLY_SIM_H
+#define KATIE_FLY_SIM_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct KfsSimulation KfsSimulation;
+
+typedef struct {
+    uint64_t id;
+    int32_t player_id; /* -1 when unowned */
+    float x, y;
+    float vx, vy;
+    float rotation;
+    float fuel;
+    float max_fuel;
+    bool landed;
+} KfsRocketState;
+
+typedef struct {
+    uint64_t id;
+    float x, y;
+    float vx, vy;
+    float radius;
+    float mass;
+} KfsBodyState;
+
+/* Message for the last failed call on this thread, or NULL */
+const char *kfs_last_error(void);
+
+/* Create from a built-in/custom map name or a RON/JSON map file; NULL on failure */
+KfsSimulation *kfs_simulation_new(const char *map_name);
+KfsSimulation *kfs_simulation_from_map_file(const char *path);
+void kfs_simulation_free(KfsSimulation *simulation);
+
+/* Rocket ID, or -1 on failure; player_id < 0 spawns an unowned rocket */
+int64_t kfs_simulation_spawn_rocket(KfsSimulation *simulation, int32_t player_id);
+/* thrust 0-1, turn_rate in radians per second; held until changed */
+bool kfs_simulation_set_controls(KfsSimulation *simulation, uint64_t rocket_id, float thrust, float turn_rate);
+
+void kfs_simulation_set_timestep(KfsSimulation *simulation, float timestep);
+void kfs_simulation_step(KfsSimulation *simulation, uint32_t steps);
+uint32_t kfs_simulation_advance(KfsSimulation *simulation, float seconds);
+float kfs_simulation_time(const KfsSimulation *simulation);
+
+uint32_t kfs_simulation_rocket_count(const KfsSimulation *simulation);
+bool kfs_simulation_rocket_at(const KfsSimulation *simulation, uint32_t index, KfsRocketState *out);
+bool kfs_simulation_rocket_state(const KfsSimulation *simulation, uint64_t rocket_id, KfsRocketState *out);
+uint32_t kfs_simulation_body_count(const KfsSimulation *simulation);
+bool kfs_simulation_body_at(const KfsSimulation *simulation, uint32_t index, KfsBodyState *out);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KATIE_FLY_SIM_H */
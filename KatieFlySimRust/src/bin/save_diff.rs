@@ -0,0 +1,55 @@
+// Save Diff Tool - Compare two saved world states field by field
+// Usage: save_diff <left> <right> [--tolerance T]  (.sav files, desync dumps from saves/desync, or .json schema exports)
+
+use katie_fly_sim_rust::save_system::save_diff::DEFAULT_TOLERANCE;
+use katie_fly_sim_rust::save_system::{GameSaveData, SaveDiff};
+
+const USAGE: &str = "Usage: save_diff <left> <right> [--tolerance T]
+  Lists every entity and field that differs between two saves (numbers within T, default 0.001, count as equal).
+  Files can be .sav saves, host/client desync dumps from saves/desync, or .json schema exports.";
+
+fn run(args: &[String]) -> Result<bool, Box<dyn std::error::Error>> {
+    let mut paths = Vec::new();
+    let mut tolerance = DEFAULT_TOLERANCE;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--tolerance" => {
+                let text = args.next().ok_or("Missing value for --tolerance")?;
+                tolerance = text.parse().map_err(|_| format!("'{}' is not a number", text))?;
+            }
+            "--help" | "-h" => return Err(USAGE.into()),
+            _ => paths.push(arg.as_str()),
+        }
+    }
+    let [left_path, right_path] = paths[..] else {
+        return Err(USAGE.into());
+    };
+
+    let left = GameSaveData::load_from_path(left_path).map_err(|e| format!("{}: {}", left_path, e))?;
+    let right = GameSaveData::load_from_path(right_path).map_err(|e| format!("{}: {}", right_path, e))?;
+    let diff = SaveDiff::compare(&left, &right, tolerance);
+
+    println!("left:  {} (version {}, game time {:.2}s)", left_path, left.version, left.game_time);
+    println!("right: {} (version {}, game time {:.2}s)", right_path, right.version, right.game_time);
+    if diff.origins_differ {
+        println!("note: the floating origins differ, so entity positions are relative to different points");
+    }
+    for change in &diff.changes {
+        println!("{}", change);
+    }
+    println!("{}", if diff.is_empty() { "No differences".to_string() } else { diff.summary() });
+    Ok(diff.is_empty())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1), // Like diff(1): 1 means the saves differ
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,109 @@
+// World DB Tool - Query a host's SQLite world database and roll it back to an earlier snapshot
+// Usage: world_db <database> <command> [args]  (build with --features sqlite)
+
+use katie_fly_sim_rust::save_system::world_database::{format_timestamp, PlayerStat, WorldDatabase};
+
+const USAGE: &str = "Usage: world_db <database> <command>
+Commands:
+  snapshots [N]           List the newest N snapshots (default 20)
+  players                 List known players
+  stats [PLAYER]          One player's stats, or the top players for every stat
+  chat [N]                Show the last N chat lines (default 50)
+  export <ID> <SAVE>      Write a snapshot to saves/multi/<SAVE>.sav for the host to load
+  rollback <ID>           Export a snapshot as rollback_<ID> and drop every newer snapshot";
+
+fn count_arg(arg: Option<&String>, default: usize) -> Result<usize, String> {
+    match arg {
+        Some(text) => text.parse().map_err(|_| format!("'{}' is not a number", text)),
+        None => Ok(default),
+    }
+}
+
+fn snapshot_id_arg(arg: Option<&String>) -> Result<i64, String> {
+    let text = arg.ok_or("Missing snapshot ID")?;
+    text.parse().map_err(|_| format!("'{}' is not a snapshot ID", text))
+}
+
+fn run(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let (Some(path), Some(command)) = (args.first(), args.get(1)) else {
+        return Err(USAGE.into());
+    };
+    if !std::path::Path::new(path).exists() {
+        return Err(format!("Database not found: {}", path).into());
+    }
+    let mut db = WorldDatabase::open(path)?;
+
+    match command.as_str() {
+        "snapshots" => {
+            for snapshot in db.snapshots(count_arg(args.get(2), 20)?)? {
+                println!(
+                    "#{:<6} session {:<4} {}  game time {:>9.1}s  {:<12} {} players  {} KB",
+                    snapshot.id,
+                    snapshot.session_id,
+                    format_timestamp(snapshot.created_at),
+                    snapshot.game_time,
+                    snapshot.map_name.as_deref().unwrap_or("-"),
+                    snapshot.player_count,
+                    snapshot.size / 1024,
+                );
+            }
+        }
+        "players" => {
+            for player in db.players()? {
+                println!(
+                    "{:<20} first seen {}  last seen {}  {} sessions",
+                    player.name,
+                    format_timestamp(player.first_seen),
+                    format_timestamp(player.last_seen),
+                    player.sessions,
+                );
+            }
+        }
+        "stats" => match args.get(2) {
+            Some(player) => {
+                for (stat, value) in db.stats_for(player)? {
+                    println!("{:<20} {}", stat.key(), value);
+                }
+            }
+            None => {
+                for stat in PlayerStat::ALL {
+                    println!("{}:", stat.key());
+                    for (rank, (player, value)) in db.leaderboard(stat, 5)?.into_iter().enumerate() {
+                        println!("  {}. {:<20} {}", rank + 1, player, value);
+                    }
+                }
+            }
+        },
+        "chat" => {
+            for line in db.chat_history(count_arg(args.get(2), 50)?)? {
+                println!("[{}] {}: {}", format_timestamp(line.sent_at), line.player, line.message);
+            }
+        }
+        "export" => {
+            let id = snapshot_id_arg(args.get(2))?;
+            let save_name = args.get(3).ok_or("Missing save name")?;
+            db.load_snapshot(id)?.save_to_multi_file(save_name)?;
+            println!("Snapshot {} written to saves/multi/{}.sav", id, save_name);
+        }
+        "rollback" => {
+            let id = snapshot_id_arg(args.get(2))?;
+            let save_name = format!("rollback_{}", id);
+            db.load_snapshot(id)?.save_to_multi_file(&save_name)?;
+            let dropped = db.roll_back_to(id)?;
+            println!(
+                "Rolled back to snapshot {} ({} newer dropped); load '{}' from the host menu to resume",
+                id, dropped, save_name
+            );
+        }
+        _ => return Err(USAGE.into()),
+    }
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = run(&args) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
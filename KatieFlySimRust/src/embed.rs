@@ -0,0 +1,417 @@
+// Embedding API - Drive the n-body simulation from another crate
+// No menus or main loop: build a world from a map, step it, query it, and draw it into any camera
+
+use macroquad::prelude::*;
+use std::collections::HashMap;
+
+use crate::entities::{Planet, Rocket};
+use crate::game_constants::GameConstants;
+use crate::map_config::{orbit_calculator, MapConfiguration};
+use crate::save_system::{GameSaveData, SavedBullet, SavedPlanet, SavedRocket, SavedSatellite};
+use crate::systems::{ConservationSample, EntityId, LandingEvent, World};
+
+/// Most steps one `advance` call runs; time beyond that is dropped rather than owed
+const MAX_STEPS_PER_ADVANCE: usize = 10_000;
+/// Touchdowns kept for a caller that never takes them (oldest dropped first)
+const MAX_PENDING_LANDINGS: usize = 1024;
+
+/// Throttle and turn rate held by a rocket until changed
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RocketControls {
+    pub thrust: f32,    // 0.0 to 1.0
+    pub turn_rate: f32, // Radians per second (positive = counter-clockwise)
+}
+
+/// A rocket as seen from outside the simulation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RocketState {
+    pub id: EntityId,
+    pub player_id: Option<u32>,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub rotation: f32,
+    pub fuel: f32,
+    pub max_fuel: f32,
+    pub landed: bool,
+}
+
+/// A celestial body as seen from outside the simulation
+#[derive(Debug, Clone, PartialEq)]
+pub struct BodyState {
+    pub id: EntityId,
+    pub name: Option<String>,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub radius: f32,
+    pub mass: f32,
+}
+
+/// A self-contained simulation stepped at a fixed timestep
+pub struct Simulation {
+    world: World,
+    map: MapConfiguration,
+    spawn_planet_id: Option<EntityId>,
+    controls: HashMap<EntityId, RocketControls>,
+    landings: Vec<LandingEvent>, // Touchdowns not yet taken by the caller
+    time: f32,
+    timestep: f32,
+    leftover: f32, // Time passed to `advance` not yet stepped
+}
+
+impl Simulation {
+    /// Build the map's bodies in their starting orbits (no rockets yet)
+    pub fn from_map(map: MapConfiguration) -> Self {
+        let mut world = World::new();
+        let mut tidal_rules = world.tidal_rules();
+        tidal_rules.enabled = map.tidal_breakup;
+        world.set_tidal_rules(tidal_rules);
+        world.gravity_simulator_mut().set_model(map.gravity_model);
+        world.set_bullet_speed(map.tunables.bullet_speed());
+        world.set_arena(map.arena_bounds());
+
+        let initial_states = orbit_calculator::calculate_initial_states(&map, GameConstants::G);
+        let mut spawn_planet_id = None;
+        for (i, body_config) in map.celestial_bodies.iter().enumerate() {
+            let state = &initial_states[i];
+            let mut planet = Planet::new(state.position, body_config.radius, body_config.mass, body_config.color);
+            planet.set_velocity(state.velocity);
+            planet.set_name(body_config.name.clone());
+            planet.set_pinned(body_config.is_pinned);
+            planet.set_terrain(body_config.terrain.as_ref().map(|t| t.to_terrain()));
+
+            let planet_id = world.add_planet(planet);
+            if i == map.player_spawn_body_index {
+                spawn_planet_id = Some(planet_id);
+            }
+        }
+
+        Simulation {
+            world,
+            map,
+            spawn_planet_id,
+            controls: HashMap::new(),
+            landings: Vec::new(),
+            time: 0.0,
+            timestep: 1.0 / GameConstants::DEFAULT_PHYSICS_HZ as f32,
+            leftover: 0.0,
+        }
+    }
+
+    /// Build one of the built-in or custom maps by name (see `MapConfiguration::all_maps`)
+    pub fn from_map_name(name: &str) -> Result<Self, String> {
+        MapConfiguration::all_maps()
+            .into_iter()
+            .find(|map| map.name == name)
+            .map(Self::from_map)
+            .ok_or_else(|| format!("No map named '{}'", name))
+    }
+
+    /// Restore a save or multiplayer snapshot, using its map's physics when the map is known
+    pub fn from_save(save_data: GameSaveData) -> Self {
+        let map = save_data
+            .map_name
+            .as_ref()
+            .and_then(|name| MapConfiguration::all_maps().into_iter().find(|map| &map.name == name))
+            .unwrap_or_else(MapConfiguration::earth_moon);
+        let mut simulation = Self::from_map(map);
+        simulation.world.clear_all_entities();
+        simulation.world.set_origin(save_data.origin.into());
+        simulation.world.set_arena(save_data.arena);
+        simulation.world.set_cosmetic_seed(save_data.cosmetic_seed);
+        simulation.spawn_planet_id = None;
+
+        for saved_planet in save_data.planets {
+            let (id, planet) = saved_planet.to_planet();
+            if simulation.spawn_planet_id.is_none() && planet.name() == Some(simulation.map.get_spawn_body().name.as_str()) {
+                simulation.spawn_planet_id = Some(id);
+            }
+            simulation.world.add_planet_with_id(id, planet);
+        }
+        for saved_rocket in save_data.rockets {
+            let (id, rocket) = saved_rocket.to_rocket();
+            simulation.world.add_rocket_with_id(id, rocket);
+        }
+        for saved_satellite in save_data.satellites {
+            let (id, satellite) = saved_satellite.to_satellite();
+            simulation.world.add_satellite_with_id(id, satellite);
+        }
+        for saved_bullet in save_data.bullets {
+            let (id, bullet) = saved_bullet.to_bullet();
+            simulation.world.add_bullet_with_id(id, bullet);
+        }
+        simulation.world.set_active_rocket(save_data.active_rocket_id);
+        simulation.time = save_data.game_time;
+        simulation
+    }
+
+    pub fn map(&self) -> &MapConfiguration {
+        &self.map
+    }
+
+    /// Simulated seconds so far
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    pub fn timestep(&self) -> f32 {
+        self.timestep
+    }
+
+    /// Seconds per physics step (larger steps run faster but lose accuracy near planets)
+    pub fn set_timestep(&mut self, timestep: f32) {
+        self.timestep = timestep.max(1.0e-4);
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Direct access for anything the API doesn't cover
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// The body rockets spawn on (None for a save whose map isn't known)
+    pub fn spawn_body_id(&self) -> Option<EntityId> {
+        self.spawn_planet_id
+    }
+
+    /// Put a fueled rocket above the map's spawn body, moving with it
+    pub fn spawn_rocket(&mut self, player_id: Option<u32>) -> Option<EntityId> {
+        let planet = self.world.get_planet(self.spawn_planet_id?)?;
+        let tunables = &self.map.tunables;
+        let spawn_distance = planet.surface_radius_at(0.0) + tunables.spawn_altitude();
+        let mut rocket = Rocket::new(
+            planet.position() + Vec2::new(spawn_distance, 0.0),
+            planet.velocity(),
+            WHITE,
+            tunables.rocket_base_mass(),
+        );
+        tunables.apply_to_rocket(&mut rocket);
+        rocket.set_fuel(rocket.max_fuel());
+        rocket.set_player_id(player_id);
+        Some(self.world.add_rocket(rocket))
+    }
+
+    /// Hold these controls on a rocket from the next step on; false if there's no such rocket
+    pub fn set_controls(&mut self, rocket_id: EntityId, controls: RocketControls) -> bool {
+        if self.world.get_rocket(rocket_id).is_none() {
+            return false;
+        }
+        self.controls.insert(rocket_id, controls);
+        true
+    }
+
+    /// Run one physics step
+    pub fn step(&mut self) {
+        let dt = self.timestep;
+        self.controls.retain(|id, _| self.world.get_rocket(*id).is_some());
+        for (id, controls) in &self.controls {
+            if let Some(rocket) = self.world.get_rocket_mut(*id) {
+                if controls.turn_rate != 0.0 {
+                    rocket.rotate(controls.turn_rate * dt);
+                }
+                rocket.set_thrust_level(controls.thrust.clamp(0.0, 1.0));
+            }
+        }
+
+        self.world.update(dt, false);
+
+        // Nobody reads the game-mode event queues here, so keep them from growing
+        self.world.take_destroyed_rockets();
+        self.world.take_fuel_transfer_events();
+        self.world.take_cleared_debris();
+        self.world.take_hit_events();
+        self.world.take_satellite_life_events();
+        self.world.take_network_anomalies();
+        self.landings.extend(self.world.take_landing_events());
+        if self.landings.len() > MAX_PENDING_LANDINGS {
+            let excess = self.landings.len() - MAX_PENDING_LANDINGS;
+            self.landings.drain(..excess);
+        }
+        self.time += dt;
+    }
+
+    /// Run as many whole steps as fit in `seconds` (the remainder carries over); returns the step count
+    /// Negative, NaN and infinite times are ignored, and at most `MAX_STEPS_PER_ADVANCE` steps run per call
+    pub fn advance(&mut self, seconds: f32) -> usize {
+        if !seconds.is_finite() || seconds <= 0.0 {
+            return 0;
+        }
+        self.leftover += seconds;
+        let mut steps = 0;
+        while self.leftover >= self.timestep {
+            if steps == MAX_STEPS_PER_ADVANCE {
+                self.leftover = 0.0;
+                break;
+            }
+            self.leftover -= self.timestep;
+            self.step();
+            steps += 1;
+        }
+        steps
+    }
+
+    /// Touchdowns graded since the last call (only the latest `MAX_PENDING_LANDINGS` are kept)
+    pub fn take_landings(&mut self) -> Vec<LandingEvent> {
+        std::mem::take(&mut self.landings)
+    }
+
+    pub fn rocket(&self, id: EntityId) -> Option<RocketState> {
+        self.world.get_rocket(id).map(|rocket| RocketState {
+            id,
+            player_id: rocket.player_id(),
+            position: rocket.position(),
+            velocity: rocket.velocity(),
+            rotation: rocket.rotation(),
+            fuel: rocket.current_fuel(),
+            max_fuel: rocket.max_fuel(),
+            landed: rocket.is_landed(),
+        })
+    }
+
+    /// Every rocket, lowest ID first
+    pub fn rockets(&self) -> Vec<RocketState> {
+        let mut ids: Vec<EntityId> = self.world.rockets_with_ids().map(|(id, _)| id).collect();
+        ids.sort_unstable();
+        ids.into_iter().filter_map(|id| self.rocket(id)).collect()
+    }
+
+    /// Every celestial body, lowest ID first (so map order for a fresh map)
+    pub fn bodies(&self) -> Vec<BodyState> {
+        let mut bodies: Vec<BodyState> = self
+            .world
+            .planets_with_ids()
+            .map(|(id, planet)| BodyState {
+                id,
+                name: planet.name().map(str::to_string),
+                position: planet.position(),
+                velocity: planet.velocity(),
+                radius: planet.radius(),
+                mass: planet.mass(),
+            })
+            .collect();
+        bodies.sort_unstable_by_key(|body| body.id);
+        bodies
+    }
+
+    /// Total energy and momentum right now, for conservation checks (compare samples from different times)
+    pub fn conservation(&self) -> ConservationSample {
+        ConservationSample::measure(&self.world, self.time)
+    }
+
+    /// The world as a save (loadable by the game, or by `from_save`)
+    pub fn snapshot(&self) -> GameSaveData {
+        let mut save_data = GameSaveData::new();
+        save_data.planets = self.world.planets_with_ids().map(|(id, planet)| SavedPlanet::from_planet(id, planet)).collect();
+        save_data.rockets = self.world.rockets_with_ids().map(|(id, rocket)| SavedRocket::from_rocket(id, rocket)).collect();
+        save_data.satellites = self
+            .world
+            .satellites_with_ids()
+            .map(|(id, satellite)| SavedSatellite::from_satellite(id, satellite))
+            .collect();
+        save_data.bullets = self.world.bullets_with_ids().map(|(id, bullet)| SavedBullet::from_bullet(id, bullet)).collect();
+        save_data.game_time = self.time;
+        save_data.active_rocket_id = self.world.active_rocket_id();
+        save_data.map_name = Some(self.map.name.clone());
+        save_data.origin = self.world.origin().into();
+        save_data.arena = self.world.arena();
+        save_data.cosmetic_seed = self.world.cosmetic_seed();
+        save_data
+    }
+
+    /// Draw the world through `camera` (which may target a render texture); leaves the default camera set
+    pub fn render(&self, camera: &Camera2D) {
+        set_camera(camera);
+        self.world.render();
+        set_default_camera();
+    }
+
+    /// A camera over `center` showing `world_width` units across a `viewport` of pixels
+    pub fn camera_on(center: Vec2, world_width: f32, viewport: Vec2) -> Camera2D {
+        let scale = 2.0 / world_width.max(1.0);
+        Camera2D {
+            target: center,
+            zoom: vec2(scale, -scale * viewport.x / viewport.y.max(1.0)),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_bodies_and_spawned_rocket() {
+        let mut simulation = Simulation::from_map(MapConfiguration::earth_moon());
+        let bodies = simulation.bodies();
+        assert_eq!(bodies.len(), 2);
+        assert_eq!(bodies[0].name.as_deref(), Some(simulation.map().celestial_bodies[0].name.as_str()));
+
+        let rocket_id = simulation.spawn_rocket(Some(3)).unwrap();
+        let rocket = simulation.rocket(rocket_id).unwrap();
+        assert_eq!(rocket.player_id, Some(3));
+        assert_eq!(rocket.fuel, rocket.max_fuel);
+        assert!(Simulation::from_map_name("no such map").is_err());
+    }
+
+    #[test]
+    fn test_advance_steps_and_carries_remainder() {
+        let mut simulation = Simulation::from_map(MapConfiguration::earth_moon());
+        simulation.set_timestep(0.1);
+        assert_eq!(simulation.advance(0.25), 2);
+        assert_eq!(simulation.advance(0.05), 1);
+        assert!((simulation.time() - 0.3).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_advance_ignores_bad_times_and_caps_huge_ones() {
+        let mut simulation = Simulation::from_map(MapConfiguration::earth_moon());
+        simulation.set_timestep(0.1);
+        assert_eq!(simulation.advance(f32::INFINITY), 0);
+        assert_eq!(simulation.advance(f32::NAN), 0);
+        assert_eq!(simulation.advance(-5.0), 0);
+        assert_eq!(simulation.time(), 0.0);
+
+        // A year of catching up runs one capped batch and owes nothing afterwards
+        assert_eq!(simulation.advance(3.15e7), MAX_STEPS_PER_ADVANCE);
+        assert_eq!(simulation.advance(0.05), 0);
+    }
+
+    #[test]
+    fn test_controls_burn_fuel_and_snapshot_round_trips() {
+        let mut simulation = Simulation::from_map(MapConfiguration::earth_moon());
+        let rocket_id = simulation.spawn_rocket(None).unwrap();
+        let start_fuel = simulation.rocket(rocket_id).unwrap().fuel;
+        assert!(simulation.set_controls(rocket_id, RocketControls { thrust: 1.0, turn_rate: 0.5 }));
+        simulation.advance(1.0);
+
+        let rocket = simulation.rocket(rocket_id).unwrap();
+        assert!(rocket.fuel < start_fuel);
+        assert!(rocket.rotation != 0.0);
+
+        let restored = Simulation::from_save(simulation.snapshot());
+        assert_eq!(restored.rocket(rocket_id).unwrap().position, rocket.position);
+        assert_eq!(restored.map().name, "earth moon");
+        assert!((restored.time() - simulation.time()).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_coasting_world_conserves_energy_and_momentum() {
+        // Unpinned, so the Earth answers the Moon's pull and no outside force acts
+        let mut simulation = Simulation::from_map(MapConfiguration::earth_moon());
+        let planet_ids: Vec<EntityId> = simulation.world().planets_with_ids().map(|(id, _)| id).collect();
+        for id in planet_ids {
+            simulation.world_mut().get_planet_mut(id).unwrap().set_pinned(false);
+        }
+        let start = simulation.conservation();
+        simulation.advance(10.0);
+        let end = simulation.conservation();
+
+        let moon = &simulation.bodies()[1];
+        let moon_momentum = (moon.mass * moon.velocity.length()) as f64;
+        assert!(((end.energy() - start.energy()) / start.energy().abs()).abs() < 1e-4);
+        assert!((end.momentum - start.momentum).length() / moon_momentum < 1e-5);
+    }
+}
@@ -4,6 +4,7 @@
 use macroquad::prelude::*;
 
 use super::game_object::{GameObject, GameObjectData};
+use crate::ui::sprite_batch::{Sprite, SpriteBatch};
 
 /// Bullet projectile with mass and lifetime
 #[derive(Debug, Clone)]
@@ -13,6 +14,7 @@ pub struct Bullet {
     lifetime: f32,        // Time since creation in seconds
     max_lifetime: f32,    // Despawn after this many seconds
     size: f32,            // Square size for rendering
+    shooter: Option<usize>, // Rocket that fired it (host-side only; not saved or sent)
 }
 
 impl Bullet {
@@ -24,6 +26,7 @@ impl Bullet {
             lifetime: 0.0,
             max_lifetime: 360.0,  // Bullets last 360 seconds (6 minutes)
             size: 3.0,  // Small square, 3x3 pixels
+            shooter: None,
         }
     }
 
@@ -42,6 +45,35 @@ impl Bullet {
         self.lifetime = lifetime;
     }
 
+    /// Seconds the bullet lives before despawning
+    pub fn max_lifetime(&self) -> f32 {
+        self.max_lifetime
+    }
+
+    /// Set time-to-live (used when restoring from save/network so the host's value applies)
+    pub fn set_max_lifetime(&mut self, max_lifetime: f32) {
+        self.max_lifetime = max_lifetime;
+    }
+
+    /// Rocket that fired this bullet, if known
+    pub fn shooter(&self) -> Option<usize> {
+        self.shooter
+    }
+
+    pub fn set_shooter(&mut self, rocket_id: Option<usize>) {
+        self.shooter = rocket_id;
+    }
+
+    /// Tint the bullet (fired bullets take the shooter's rocket color)
+    pub fn set_color(&mut self, color: Color) {
+        self.data.color = color;
+    }
+
+    /// Move the bullet (floating origin rebase)
+    pub fn set_position(&mut self, position: Vec2) {
+        self.data.position = position;
+    }
+
     /// Check if bullet should be despawned
     pub fn should_despawn(&self) -> bool {
         self.lifetime >= self.max_lifetime
@@ -51,6 +83,11 @@ impl Bullet {
     pub fn size(&self) -> f32 {
         self.size
     }
+
+    /// Far-zoom stand-in for `draw` as one batched sprite
+    pub fn push_sprites(&self, batch: &mut SpriteBatch) {
+        batch.push(Sprite::Square, self.data.position, self.size, self.data.color);
+    }
 }
 
 impl GameObject for Bullet {
@@ -4,25 +4,67 @@
 use macroquad::prelude::*;
 
 use super::rocket_part::{RocketPart, RocketPartData};
+use crate::game_constants::GameConstants;
 use crate::utils::vector_helper;
 
+/// Specific impulse curve of an engine across its throttle range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EngineEfficiency {
+    /// Effective exhaust velocity at full throttle
+    pub full_throttle_isp: f32,
+    /// Effective exhaust velocity at the minimum usable throttle
+    pub min_throttle_isp: f32,
+}
+
+impl Default for EngineEfficiency {
+    fn default() -> Self {
+        EngineEfficiency {
+            full_throttle_isp: GameConstants::ENGINE_SPECIFIC_IMPULSE,
+            min_throttle_isp: GameConstants::ENGINE_MIN_THROTTLE_SPECIFIC_IMPULSE,
+        }
+    }
+}
+
+impl EngineEfficiency {
+    /// Specific impulse at `throttle`, interpolated between the minimum throttle and full throttle
+    pub fn specific_impulse(&self, throttle: f32) -> f32 {
+        let min = GameConstants::FUEL_CONSUMPTION_MIN_THRESHOLD;
+        let t = ((throttle - min) / (1.0 - min)).clamp(0.0, 1.0);
+        self.min_throttle_isp + (self.full_throttle_isp - self.min_throttle_isp) * t
+    }
+
+    /// Fuel burned per second at `throttle` for an engine of `thrust_power`
+    pub fn fuel_flow(&self, throttle: f32, thrust_power: f32) -> f32 {
+        if throttle < GameConstants::FUEL_CONSUMPTION_MIN_THRESHOLD {
+            return 0.0;
+        }
+        throttle * thrust_power / self.specific_impulse(throttle).max(1.0)
+    }
+}
+
 /// Rocket engine providing thrust
 pub struct Engine {
     data: RocketPartData,
     thrust: f32,
+    efficiency: EngineEfficiency,
 }
 
 impl Engine {
-    pub fn new(relative_pos: Vec2, thrust_power: f32, color: Color) -> Self {
+    pub fn new(relative_pos: Vec2, thrust_power: f32, efficiency: EngineEfficiency, color: Color) -> Self {
         Engine {
             data: RocketPartData::new(relative_pos, color),
             thrust: thrust_power,
+            efficiency,
         }
     }
 
     pub fn thrust(&self) -> f32 {
         self.thrust
     }
+
+    pub fn efficiency(&self) -> EngineEfficiency {
+        self.efficiency
+    }
 }
 
 impl RocketPart for Engine {
@@ -65,3 +107,28 @@ impl RocketPart for Engine {
         self.data.color
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_throttle_flow_matches_specific_impulse() {
+        let efficiency = EngineEfficiency::default();
+        let flow = efficiency.fuel_flow(1.0, GameConstants::ENGINE_THRUST_POWER);
+        assert!((flow - GameConstants::ENGINE_THRUST_POWER / GameConstants::ENGINE_SPECIFIC_IMPULSE).abs() < 1e-3);
+        assert_eq!(efficiency.fuel_flow(0.05, GameConstants::ENGINE_THRUST_POWER), 0.0);
+    }
+
+    #[test]
+    fn test_throttle_curve_changes_fuel_per_impulse() {
+        let power = GameConstants::ENGINE_THRUST_POWER;
+        let wasteful = EngineEfficiency { full_throttle_isp: 10000.0, min_throttle_isp: 4000.0 };
+        let frugal = EngineEfficiency { full_throttle_isp: 10000.0, min_throttle_isp: 16000.0 };
+
+        // Fuel needed for the same total impulse at 20% and 100% throttle
+        let per_impulse = |e: &EngineEfficiency, t: f32| e.fuel_flow(t, power) / (t * power);
+        assert!(per_impulse(&wasteful, 0.2) > per_impulse(&wasteful, 1.0));
+        assert!(per_impulse(&frugal, 0.2) < per_impulse(&frugal, 1.0));
+    }
+}
@@ -7,6 +7,8 @@ pub mod satellite;
 pub mod rocket_part;
 pub mod engine;
 pub mod bullet;
+pub mod rover;
+pub mod terrain;
 
 // Re-export commonly used items
 pub use game_object::{GameObject, GameObjectData};
@@ -14,5 +16,7 @@ pub use planet::Planet;
 pub use rocket::Rocket;
 pub use satellite::Satellite;
 pub use rocket_part::{RocketPart, RocketPartData};
-pub use engine::Engine;
+pub use engine::{Engine, EngineEfficiency};
 pub use bullet::Bullet;
+pub use rover::Rover;
+pub use terrain::Terrain;
@@ -4,7 +4,9 @@
 use macroquad::prelude::*;
 
 use super::game_object::{GameObject, GameObjectData};
+use super::terrain::Terrain;
 use crate::game_constants::GameConstants;
+use crate::systems::daily_challenge::SplitMix64;
 
 /// Planet entity with mass, gravity, and fuel storage
 pub struct Planet {
@@ -15,6 +17,8 @@ pub struct Planet {
     initial_radius: f32, // Store starting radius for proportional scaling
     name: Option<String>, // Planet name (e.g., "Earth", "Moon", "Sun")
     is_pinned: bool,     // If true, planet doesn't move (for central bodies like Sun)
+    is_fragment: bool,   // Debris from a tidal breakup (never fragments again)
+    terrain: Option<Terrain>, // Surface heightmap; None = perfect circle
 }
 
 impl Planet {
@@ -27,6 +31,8 @@ impl Planet {
             initial_radius: radius, // for proportional scaling
             name: None,             // No name by default
             is_pinned: false,       // Not pinned by default
+            is_fragment: false,
+            terrain: None,
         }
     }
 
@@ -47,6 +53,8 @@ impl Planet {
             initial_radius,
             name: None,       // No name by default
             is_pinned: false, // Not pinned by default
+            is_fragment: false,
+            terrain: None,
         }
     }
 
@@ -92,6 +100,40 @@ impl Planet {
         self.radius * 1.10
     }
 
+    /// Lighter and darker patches over the surface, picked by the save's cosmetic seed and the planet's ID
+    /// (seed 0 keeps the plain disc; nothing here touches mass, radius or collisions)
+    pub fn draw_surface_pattern(&self, cosmetic_seed: u64, id: usize) {
+        if cosmetic_seed == 0 {
+            return;
+        }
+        let mut rng = SplitMix64(cosmetic_seed ^ (id as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        let center = self.data.position;
+        let base = self.data.color;
+        let shade = |rng: &mut SplitMix64, alpha: f32| {
+            // Mostly the planet's own colour, pushed lighter or darker
+            let tone = if rng.unit() < 0.5 { 0.55 } else { 1.35 };
+            Color::new((base.r * tone).min(1.0), (base.g * tone).min(1.0), (base.b * tone).min(1.0), alpha)
+        };
+
+        // Banding: a few concentric rings read as weather belts or strata
+        for _ in 0..1 + rng.next() % 3 {
+            let band_radius = self.radius * (0.35 + 0.55 * rng.unit());
+            let thickness = self.radius * (0.03 + 0.08 * rng.unit());
+            let color = shade(&mut rng, 0.18);
+            draw_poly_lines(center.x, center.y, 60, band_radius, 0.0, thickness, color);
+        }
+
+        // Patches: craters, seas or storms
+        for _ in 0..3 + rng.next() % 6 {
+            let size = self.radius * (0.06 + 0.2 * rng.unit());
+            let angle = rng.unit() * std::f32::consts::TAU;
+            let distance = (self.radius - size) * rng.unit().sqrt();
+            let spot = center + Vec2::from_angle(angle) * distance;
+            let color = shade(&mut rng, 0.22);
+            draw_poly(spot.x, spot.y, 24, size, 0.0, color);
+        }
+    }
+
     /// Draw fuel collection ring around planet
     pub fn draw_fuel_collection_ring(&self, is_actively_collecting: bool) {
         if !self.can_collect_fuel() {
@@ -152,6 +194,61 @@ impl Planet {
     pub fn set_pinned(&mut self, pinned: bool) {
         self.is_pinned = pinned;
     }
+
+    pub fn is_fragment(&self) -> bool {
+        self.is_fragment
+    }
+
+    pub fn set_fragment(&mut self, fragment: bool) {
+        self.is_fragment = fragment;
+    }
+
+    // === Terrain ===
+
+    pub fn terrain(&self) -> Option<&Terrain> {
+        self.terrain.as_ref()
+    }
+
+    pub fn set_terrain(&mut self, terrain: Option<Terrain>) {
+        self.terrain = terrain;
+    }
+
+    /// Distance from the center to the surface at an angle (includes terrain)
+    pub fn surface_radius_at(&self, angle: f32) -> f32 {
+        match &self.terrain {
+            Some(terrain) => self.radius * (1.0 + terrain.height_at(angle)),
+            None => self.radius,
+        }
+    }
+
+    /// Distance from the center to the surface below a world point
+    pub fn surface_radius_toward(&self, point: Vec2) -> f32 {
+        self.surface_radius_at(Self::surface_angle(self.data.position, point))
+    }
+
+    /// Outward surface normal at an angle (tilted by slopes when there's terrain)
+    pub fn surface_normal_at(&self, angle: f32) -> Vec2 {
+        let radial = Vec2::from_angle(angle);
+        if self.terrain.is_none() {
+            return radial;
+        }
+
+        // Tangent of the surface curve p(a) = r(a) * (cos a, sin a), by central difference
+        let step = 0.001;
+        let slope = (self.surface_radius_at(angle + step) - self.surface_radius_at(angle - step)) / (2.0 * step);
+        let tangent = radial * slope + radial.perp() * self.surface_radius_at(angle);
+        Vec2::new(tangent.y, -tangent.x).try_normalize().unwrap_or(radial)
+    }
+
+    /// Outward surface normal below a world point
+    pub fn surface_normal_toward(&self, point: Vec2) -> Vec2 {
+        self.surface_normal_at(Self::surface_angle(self.data.position, point))
+    }
+
+    fn surface_angle(center: Vec2, point: Vec2) -> f32 {
+        let offset = point - center;
+        offset.y.atan2(offset.x)
+    }
 }
 
 impl GameObject for Planet {
@@ -161,16 +258,29 @@ impl GameObject for Planet {
     }
 
     fn draw(&self) {
-        // Draw the planet as a filled circle with high segment count for smooth edges
-        // Using 60 segments (3x the typical 20) for smoother circles
-        draw_poly(
-            self.data.position.x,
-            self.data.position.y,
-            60, // Triple the typical segment count for smoother circles
-            self.radius,
-            0.0, // rotation
-            self.data.color,
-        );
+        if let Some(terrain) = &self.terrain {
+            // Jagged silhouette: triangle fan through every terrain sample
+            let center = self.data.position;
+            let count = terrain.heights().len();
+            let surface_point = |i: usize| {
+                let angle = i as f32 / count as f32 * std::f32::consts::TAU;
+                center + Vec2::from_angle(angle) * self.surface_radius_at(angle)
+            };
+            for i in 0..count {
+                draw_triangle(center, surface_point(i), surface_point(i + 1), self.data.color);
+            }
+        } else {
+            // Draw the planet as a filled circle with high segment count for smooth edges
+            // Using 60 segments (3x the typical 20) for smoother circles
+            draw_poly(
+                self.data.position.x,
+                self.data.position.y,
+                60, // Triple the typical segment count for smoother circles
+                self.radius,
+                0.0, // rotation
+                self.data.color,
+            );
+        }
 
         // Draw fuel collection range (always visible for planning)
         if self.can_collect_fuel() {
@@ -256,4 +366,22 @@ mod tests {
         );
         assert!(!small_planet.can_collect_fuel());
     }
+
+    #[test]
+    fn test_terrain_surface() {
+        let mut planet = Planet::new(Vec2::new(0.0, 0.0), 100.0, 1000.0, BLUE);
+        assert_eq!(planet.surface_radius_toward(Vec2::new(0.0, 500.0)), 100.0);
+        assert_eq!(planet.surface_normal_at(0.0), Vec2::X);
+
+        // Ramp rising counterclockwise from 0 at angle 0 to 0.1 at angle PI/2
+        planet.set_terrain(Terrain::from_heights(vec![0.0, 0.1, 0.1, 0.0]));
+        let quarter = std::f32::consts::FRAC_PI_2;
+        assert!((planet.surface_radius_toward(Vec2::new(0.0, 500.0)) - 110.0).abs() < 1e-3);
+
+        // On the ramp the normal leans back toward angle 0 (downhill)
+        let normal = planet.surface_normal_at(quarter * 0.5);
+        let radial = Vec2::from_angle(quarter * 0.5);
+        assert!(normal.dot(radial) < 0.999);
+        assert!(normal.dot(radial.perp()) < 0.0);
+    }
 }
@@ -5,7 +5,7 @@ use macroquad::prelude::*;
 
 use super::game_object::{GameObject, GameObjectData};
 use super::rocket_part::RocketPart;
-use super::engine::Engine;
+use super::engine::{Engine, EngineEfficiency};
 use crate::game_constants::GameConstants;
 use crate::utils::vector_helper;
 
@@ -24,6 +24,7 @@ pub struct Rocket {
     // Fuel system
     current_fuel: f32,
     max_fuel: f32,
+    engine_efficiency: EngineEfficiency,
     is_currently_thrusting: bool,
 
     // Manual fuel transfer
@@ -31,6 +32,9 @@ pub struct Rocket {
     is_transferring_fuel_out: bool,
     fuel_transfer_rate: f32,
 
+    // Hull integrity (collision damage; destroyed at zero)
+    hull: f32,
+
     // Landing state
     landed: bool,
     landed_on_planet_id: Option<usize>,
@@ -56,10 +60,12 @@ impl Rocket {
         let mass = base_mass + starting_fuel;
 
         // Create default rocket parts (single engine)
+        let engine_efficiency = EngineEfficiency::default();
         let mut parts: Vec<Box<dyn RocketPart>> = Vec::new();
         parts.push(Box::new(Engine::new(
             Vec2::new(0.0, GameConstants::ROCKET_SIZE * 0.8),
             GameConstants::ENGINE_THRUST_POWER,
+            engine_efficiency,
             Color::new(0.8, 0.2, 0.2, 1.0), // Red engine
         )));
 
@@ -73,10 +79,12 @@ impl Rocket {
             max_mass: GameConstants::ROCKET_MAX_MASS,
             current_fuel: starting_fuel,
             max_fuel,
+            engine_efficiency,
             is_currently_thrusting: false,
             is_transferring_fuel_in: false,
             is_transferring_fuel_out: false,
             fuel_transfer_rate: 0.0,
+            hull: GameConstants::ROCKET_MAX_HULL,
             landed: false,
             landed_on_planet_id: None,
             player_id: None, // Will be set later if needed
@@ -98,6 +106,21 @@ impl Rocket {
         self.player_id = player_id;
     }
 
+    // === Hull ===
+    pub fn hull(&self) -> f32 {
+        self.hull
+    }
+
+    pub fn hull_percentage(&self) -> f32 {
+        self.hull / GameConstants::ROCKET_MAX_HULL * 100.0
+    }
+
+    /// Apply collision damage; returns true if the hull is destroyed
+    pub fn apply_damage(&mut self, damage: f32) -> bool {
+        self.hull = (self.hull - damage).max(0.0);
+        self.hull <= 0.0
+    }
+
     // === Mass System ===
     pub fn mass(&self) -> f32 {
         self.mass
@@ -107,6 +130,11 @@ impl Rocket {
         self.base_mass
     }
 
+    pub fn set_base_mass(&mut self, base_mass: f32) {
+        self.base_mass = base_mass.max(0.01);
+        self.mass = self.base_mass + self.current_fuel;
+    }
+
     pub fn max_mass(&self) -> f32 {
         self.max_mass
     }
@@ -127,6 +155,12 @@ impl Rocket {
         self.max_fuel
     }
 
+    /// Resize the tank, spilling any fuel that no longer fits
+    pub fn set_max_fuel(&mut self, max_fuel: f32) {
+        self.max_fuel = max_fuel.max(0.0);
+        self.set_fuel(self.current_fuel);
+    }
+
     pub fn fuel_percentage(&self) -> f32 {
         if self.max_fuel > 0.0 {
             (self.current_fuel / self.max_fuel) * 100.0
@@ -139,6 +173,11 @@ impl Rocket {
         self.current_fuel > 0.0
     }
 
+    /// Whether the engine fired on the last update
+    pub fn is_thrusting(&self) -> bool {
+        self.is_currently_thrusting
+    }
+
     pub fn set_fuel(&mut self, fuel: f32) {
         self.current_fuel = fuel.clamp(0.0, self.max_fuel);
         self.mass = self.base_mass + self.current_fuel;
@@ -162,12 +201,25 @@ impl Rocket {
     }
 
     fn calculate_fuel_consumption(&self) -> f32 {
-        if self.thrust_level < GameConstants::FUEL_CONSUMPTION_MIN_THRESHOLD {
-            return 0.0;
-        }
+        self.engine_efficiency.fuel_flow(self.thrust_level, GameConstants::ENGINE_THRUST_POWER)
+    }
+
+    /// Instantaneous fuel flow (units per second) at the current thrust level
+    pub fn fuel_flow(&self) -> f32 {
+        self.calculate_fuel_consumption()
+    }
+
+    /// Specific impulse of the engine at the current thrust level
+    pub fn specific_impulse(&self) -> f32 {
+        self.engine_efficiency.specific_impulse(self.thrust_level)
+    }
+
+    pub fn engine_efficiency(&self) -> EngineEfficiency {
+        self.engine_efficiency
+    }
 
-        GameConstants::FUEL_CONSUMPTION_BASE +
-            (self.thrust_level * GameConstants::FUEL_CONSUMPTION_MULTIPLIER)
+    pub fn set_engine_efficiency(&mut self, efficiency: EngineEfficiency) {
+        self.engine_efficiency = efficiency;
     }
 
     // === Thrust and Control ===
@@ -239,6 +291,11 @@ impl Rocket {
         self.is_transferring_fuel_in || self.is_transferring_fuel_out
     }
 
+    /// Giving fuel away (convoy tankers), so satellites don't top it back up
+    pub fn is_transferring_fuel_out(&self) -> bool {
+        self.is_transferring_fuel_out
+    }
+
     pub fn current_transfer_rate(&self) -> f32 {
         self.fuel_transfer_rate
     }
@@ -604,9 +661,12 @@ mod tests {
             GameConstants::ROCKET_BASE_MASS,
         );
 
-        // Rocket starts with ROCKET_STARTING_FUEL, already at max (100)
-        // Adding 50 more should clamp to max
+        // Rocket starts empty, so adding 50 should give exactly 50
         rocket.add_fuel(50.0);
+        assert_relative_eq!(rocket.current_fuel(), 50.0, epsilon = 0.01);
+
+        // Adding past the tank size should clamp to max
+        rocket.add_fuel(GameConstants::ROCKET_MAX_FUEL);
         assert_relative_eq!(rocket.current_fuel(), GameConstants::ROCKET_MAX_FUEL, epsilon = 0.01);
     }
 
@@ -628,6 +688,22 @@ mod tests {
         assert!(rocket.current_fuel() < initial_fuel);
     }
 
+    #[test]
+    fn test_resizing_tank_and_hull() {
+        let mut rocket = Rocket::new(Vec2::ZERO, Vec2::ZERO, WHITE, GameConstants::ROCKET_BASE_MASS);
+        rocket.set_max_fuel(300.0);
+        rocket.set_fuel(250.0);
+        assert_relative_eq!(rocket.current_fuel(), 250.0, epsilon = 0.01);
+
+        rocket.set_base_mass(5.0);
+        assert_relative_eq!(rocket.mass(), 255.0, epsilon = 0.01);
+
+        // Shrinking the tank spills what no longer fits
+        rocket.set_max_fuel(100.0);
+        assert_relative_eq!(rocket.current_fuel(), 100.0, epsilon = 0.01);
+        assert_relative_eq!(rocket.mass(), 105.0, epsilon = 0.01);
+    }
+
     #[test]
     fn test_mass_updates_with_fuel() {
         let mut rocket = Rocket::new(
@@ -3,8 +3,8 @@
 
 use macroquad::prelude::*;
 
-/// Trait for rocket components
-pub trait RocketPart {
+/// Trait for rocket components (Send so whole worlds can step on worker threads)
+pub trait RocketPart: Send {
     /// Draw the part relative to rocket position and rotation
     fn draw(&self, rocket_pos: Vec2, rotation: f32, scale: f32);
 
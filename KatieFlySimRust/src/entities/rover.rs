@@ -0,0 +1,157 @@
+// Rover - Small surface vehicle deployed from a landed rocket
+// Stays attached to the planet surface and drives along its circumference
+
+use macroquad::prelude::*;
+
+use crate::game_constants::GameConstants;
+
+/// Surface rover tied to the rocket that deployed it
+#[derive(Debug, Clone)]
+pub struct Rover {
+    rocket_id: usize,
+    planet_id: usize,
+    angle: f32,           // Position on the surface (radians, same convention as sample sites)
+    drive_input: f32,     // -1.0 to 1.0, positive drives counterclockwise
+    fuel_cargo: f32,
+    samples: Vec<String>, // Sample sites collected by this rover
+}
+
+impl Rover {
+    /// Create a rover on a planet at the given surface angle
+    pub fn new(rocket_id: usize, planet_id: usize, angle: f32) -> Self {
+        Rover {
+            rocket_id,
+            planet_id,
+            angle,
+            drive_input: 0.0,
+            fuel_cargo: 0.0,
+            samples: Vec::new(),
+        }
+    }
+
+    pub fn rocket_id(&self) -> usize {
+        self.rocket_id
+    }
+
+    pub fn planet_id(&self) -> usize {
+        self.planet_id
+    }
+
+    pub fn angle(&self) -> f32 {
+        self.angle
+    }
+
+    pub fn set_drive_input(&mut self, input: f32) {
+        self.drive_input = input.clamp(-1.0, 1.0);
+    }
+
+    pub fn is_driving(&self) -> bool {
+        self.drive_input != 0.0
+    }
+
+    /// Drive along the surface; angular speed shrinks on bigger planets so ground speed stays constant
+    pub fn update(&mut self, delta_time: f32, planet_radius: f32) {
+        if planet_radius <= 0.0 {
+            return;
+        }
+        let angular_speed = GameConstants::ROVER_SPEED / planet_radius;
+        self.angle = (self.angle + self.drive_input * angular_speed * delta_time).rem_euclid(std::f32::consts::TAU);
+    }
+
+    /// World position, riding on top of the surface
+    pub fn position(&self, planet_position: Vec2, planet_radius: f32) -> Vec2 {
+        planet_position + Vec2::from_angle(self.angle) * (planet_radius + GameConstants::ROVER_SIZE * 0.5)
+    }
+
+    // === Cargo ===
+
+    pub fn fuel_cargo(&self) -> f32 {
+        self.fuel_cargo
+    }
+
+    /// Load fuel up to capacity; returns how much was accepted
+    pub fn load_fuel(&mut self, amount: f32) -> f32 {
+        let accepted = amount.min(GameConstants::ROVER_FUEL_CAPACITY - self.fuel_cargo).max(0.0);
+        self.fuel_cargo += accepted;
+        accepted
+    }
+
+    pub fn is_fuel_full(&self) -> bool {
+        self.fuel_cargo >= GameConstants::ROVER_FUEL_CAPACITY
+    }
+
+    /// Unload all fuel (when docking)
+    pub fn take_fuel(&mut self) -> f32 {
+        std::mem::take(&mut self.fuel_cargo)
+    }
+
+    pub fn samples(&self) -> &[String] {
+        &self.samples
+    }
+
+    pub fn add_sample(&mut self, site_name: String) {
+        self.samples.push(site_name);
+    }
+
+    /// Unload all samples (when docking or when the rover is lost)
+    pub fn take_samples(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.samples)
+    }
+
+    /// Draw the rover as a small box with wheels, aligned to the surface
+    pub fn draw(&self, planet_position: Vec2, planet_radius: f32, color: Color) {
+        let up = Vec2::from_angle(self.angle);
+        let along = up.perp();
+        let center = self.position(planet_position, planet_radius);
+        let half = GameConstants::ROVER_SIZE * 0.5;
+
+        // Body
+        let corners = [
+            center + along * half * 1.4 + up * half * 0.6,
+            center - along * half * 1.4 + up * half * 0.6,
+            center - along * half * 1.4 - up * half * 0.4,
+            center + along * half * 1.4 - up * half * 0.4,
+        ];
+        draw_triangle(corners[0], corners[1], corners[2], color);
+        draw_triangle(corners[0], corners[2], corners[3], color);
+
+        // Wheels
+        for side in [-1.0, 1.0] {
+            let wheel = center + along * (side * half) - up * (half * 0.6);
+            draw_circle(wheel.x, wheel.y, half * 0.4, DARKGRAY);
+        }
+
+        // Cargo indicator
+        if self.fuel_cargo > 0.0 || !self.samples.is_empty() {
+            let light = center + up * half;
+            draw_circle(light.x, light.y, half * 0.25, YELLOW);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rover_drives_at_constant_ground_speed() {
+        let mut small = Rover::new(0, 1, 0.0);
+        let mut large = Rover::new(0, 2, 0.0);
+        small.set_drive_input(1.0);
+        large.set_drive_input(1.0);
+        small.update(1.0, 1000.0);
+        large.update(1.0, 4000.0);
+
+        assert!((small.angle() * 1000.0 - GameConstants::ROVER_SPEED).abs() < 1e-3);
+        assert!((large.angle() * 4000.0 - GameConstants::ROVER_SPEED).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_fuel_cargo_capacity() {
+        let mut rover = Rover::new(0, 1, 0.0);
+        assert_eq!(rover.load_fuel(GameConstants::ROVER_FUEL_CAPACITY + 10.0), GameConstants::ROVER_FUEL_CAPACITY);
+        assert!(rover.is_fuel_full());
+        assert_eq!(rover.take_fuel(), GameConstants::ROVER_FUEL_CAPACITY);
+        assert_eq!(rover.fuel_cargo(), 0.0);
+    }
+}
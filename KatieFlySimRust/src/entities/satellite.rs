@@ -5,6 +5,7 @@ use macroquad::prelude::*;
 
 use super::game_object::{GameObject, GameObjectData};
 use crate::game_constants::GameConstants;
+use crate::ui::sprite_batch::{Sprite, SpriteBatch};
 
 /// Satellite for automated fuel collection and orbital maintenance
 pub struct Satellite {
@@ -35,6 +36,16 @@ pub struct Satellite {
     nearby_rockets: Vec<usize>,
     transfer_range: f32,
     is_transferring_fuel: bool,
+
+    // Hull integrity (collision damage; destroyed at zero)
+    hull: f32,
+
+    // Component wear (1.0 = end of life, keeps rising after) and whether the deorbit burn happened
+    wear: f32,
+    deorbiting: bool,
+
+    // Player whose rocket was converted (None in single player or when unknown)
+    owner: Option<u32>,
 }
 
 impl Satellite {
@@ -59,6 +70,10 @@ impl Satellite {
             nearby_rockets: Vec::new(),
             transfer_range: 500.0,
             is_transferring_fuel: false,
+            hull: GameConstants::SATELLITE_MAX_HULL,
+            wear: 0.0,
+            deorbiting: false,
+            owner: None,
         }
     }
 
@@ -92,6 +107,50 @@ impl Satellite {
         self.max_fuel
     }
 
+    pub fn hull(&self) -> f32 {
+        self.hull
+    }
+
+    pub fn wear(&self) -> f32 {
+        self.wear
+    }
+
+    pub fn set_wear(&mut self, wear: f32) {
+        self.wear = wear.max(0.0);
+    }
+
+    /// Worn out: no longer collects or hands out fuel, and waits for retrieval or a deorbit burn
+    pub fn is_end_of_life(&self) -> bool {
+        self.wear >= 1.0
+    }
+
+    pub fn is_deorbiting(&self) -> bool {
+        self.deorbiting
+    }
+
+    pub fn set_deorbiting(&mut self, deorbiting: bool) {
+        self.deorbiting = deorbiting;
+    }
+
+    pub fn owner(&self) -> Option<u32> {
+        self.owner
+    }
+
+    pub fn set_owner(&mut self, owner: Option<u32>) {
+        self.owner = owner;
+    }
+
+    /// Owned satellites only refuel their owner's rockets; unowned ones refuel anyone
+    pub fn can_refuel(&self, player_id: Option<u32>) -> bool {
+        self.owner.is_none() || self.owner == player_id
+    }
+
+    /// Apply collision damage; returns true if the hull is destroyed
+    pub fn apply_damage(&mut self, damage: f32) -> bool {
+        self.hull = (self.hull - damage).max(0.0);
+        self.hull <= 0.0
+    }
+
     pub fn fuel_percentage(&self) -> f32 {
         if self.max_fuel > 0.0 {
             (self.current_fuel / self.max_fuel) * 100.0
@@ -137,10 +196,12 @@ impl Satellite {
         }
     }
 
-    /// Get satellite status color based on fuel level
+    /// Get satellite status color based on fuel level (gray once retired)
     pub fn status_color(&self) -> Color {
         let fuel_percent = self.fuel_percentage();
-        if fuel_percent > GameConstants::SATELLITE_EMERGENCY_FUEL_THRESHOLD * 100.0 {
+        if self.is_end_of_life() {
+            crate::game_constants::colors::SATELLITE_STATUS_END_OF_LIFE
+        } else if fuel_percent > GameConstants::SATELLITE_EMERGENCY_FUEL_THRESHOLD * 100.0 {
             crate::game_constants::colors::SATELLITE_STATUS_ACTIVE
         } else if fuel_percent > GameConstants::SATELLITE_CRITICAL_FUEL_THRESHOLD * 100.0 {
             crate::game_constants::colors::SATELLITE_STATUS_LOW_FUEL
@@ -303,6 +364,24 @@ impl Satellite {
             0.0
         }
     }
+
+    /// Far-zoom stand-in for `draw`: body, owner ring and transfer range as batched sprites
+    /// (panels and the status dot are sub-pixel at this distance)
+    pub fn push_sprites(&self, batch: &mut SpriteBatch) {
+        let position = self.data.position;
+        batch.push(Sprite::Disc, position, GameConstants::SATELLITE_SIZE * 2.0, self.data.color);
+        if let Some(owner) = self.owner {
+            batch.push(Sprite::Ring, position, (GameConstants::SATELLITE_SIZE + 2.0) * 2.0, crate::game_constants::colors::player_color(owner));
+        }
+
+        // The range ring's line thickness scales with the sprite, so big ones stay vector
+        let range_color = Color::new(self.data.color.r, self.data.color.g, self.data.color.b, 0.5);
+        if batch.should_batch(self.transfer_range) {
+            batch.push(Sprite::Ring, position, self.transfer_range * 2.0, range_color);
+        } else {
+            draw_circle_lines(position.x, position.y, self.transfer_range, 2.0, range_color);
+        }
+    }
 }
 
 impl GameObject for Satellite {
@@ -318,6 +397,9 @@ impl GameObject for Satellite {
 
         // Update maintenance time
         self.last_maintenance_time += delta_time;
+
+        // Components wear out with age
+        self.wear += delta_time / GameConstants::SATELLITE_DESIGN_LIFE;
     }
 
     fn draw(&self) {
@@ -329,6 +411,17 @@ impl GameObject for Satellite {
             self.data.color,
         );
 
+        // Owner's color ring (multiplayer)
+        if let Some(owner) = self.owner {
+            draw_circle_lines(
+                self.data.position.x,
+                self.data.position.y,
+                GameConstants::SATELLITE_SIZE + 2.0,
+                1.5,
+                crate::game_constants::colors::player_color(owner),
+            );
+        }
+
         // Draw solar panels (simple rectangles)
         let panel_offset = GameConstants::SATELLITE_SIZE + 2.0;
         let panel_width = GameConstants::SATELLITE_PANEL_SIZE;
@@ -0,0 +1,118 @@
+// Terrain - Radial heightmap for planet surfaces
+// Heights are fractions of the planet's base radius, so terrain scales with the planet
+
+use serde::{Deserialize, Serialize};
+
+/// Number of height samples around the planet for generated terrain
+pub const TERRAIN_SAMPLES: usize = 256;
+
+/// (control points around the circle, weight) for each noise octave
+const OCTAVES: [(usize, f32); 4] = [(4, 1.0), (8, 0.5), (16, 0.25), (32, 0.125)];
+
+/// Evenly spaced surface heights, starting at angle 0 and going counterclockwise
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Terrain {
+    heights: Vec<f32>,
+}
+
+impl Terrain {
+    /// Terrain from authored heights (None if there are too few samples to form a surface)
+    pub fn from_heights(heights: Vec<f32>) -> Option<Self> {
+        if heights.len() < 3 {
+            return None;
+        }
+        Some(Terrain {
+            heights: heights.into_iter().map(|h| h.max(0.0)).collect(),
+        })
+    }
+
+    /// Generate hills and craters from a seed; heights range from 0 to `amplitude`
+    pub fn generate(seed: u64, amplitude: f32) -> Self {
+        let total_weight: f32 = OCTAVES.iter().map(|(_, weight)| weight).sum();
+
+        let heights = (0..TERRAIN_SAMPLES)
+            .map(|i| {
+                let t = i as f32 / TERRAIN_SAMPLES as f32;
+                let noise: f32 = OCTAVES
+                    .iter()
+                    .enumerate()
+                    .map(|(octave, &(points, weight))| {
+                        // Periodic value noise: smoothly blend random values at control points
+                        let position = t * points as f32;
+                        let index = position.floor() as usize;
+                        let blend = position - position.floor();
+                        let blend = (1.0 - (blend * std::f32::consts::PI).cos()) * 0.5;
+                        let a = hash_to_unit(seed, octave as u64, (index % points) as u64);
+                        let b = hash_to_unit(seed, octave as u64, ((index + 1) % points) as u64);
+                        (a + (b - a) * blend) * weight
+                    })
+                    .sum();
+                noise / total_weight * amplitude.max(0.0)
+            })
+            .collect();
+
+        Terrain { heights }
+    }
+
+    pub fn heights(&self) -> &[f32] {
+        &self.heights
+    }
+
+    /// Height (fraction of base radius) at an angle, interpolated between samples
+    pub fn height_at(&self, angle: f32) -> f32 {
+        let count = self.heights.len();
+        let position = angle.rem_euclid(std::f32::consts::TAU) / std::f32::consts::TAU * count as f32;
+        let index = (position.floor() as usize) % count;
+        let blend = position - position.floor();
+        let a = self.heights[index];
+        let b = self.heights[(index + 1) % count];
+        a + (b - a) * blend
+    }
+
+    pub fn max_height(&self) -> f32 {
+        self.heights.iter().copied().fold(0.0, f32::max)
+    }
+}
+
+/// Deterministic pseudo-random value in [0, 1] (splitmix64 finalizer)
+fn hash_to_unit(seed: u64, octave: u64, index: u64) -> f32 {
+    let mut x = seed
+        .wrapping_add(octave.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+        .wrapping_add(index.wrapping_mul(0xBF58_476D_1CE4_E5B9));
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^= x >> 31;
+    (x >> 40) as f32 / (1u64 << 24) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_terrain_is_deterministic_and_bounded() {
+        let a = Terrain::generate(42, 0.05);
+        let b = Terrain::generate(42, 0.05);
+        let c = Terrain::generate(7, 0.05);
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a.heights().len(), TERRAIN_SAMPLES);
+        assert!(a.heights().iter().all(|&h| (0.0..=0.05).contains(&h)));
+        assert!(a.max_height() > 0.0);
+    }
+
+    #[test]
+    fn test_height_interpolates_and_wraps() {
+        let terrain = Terrain::from_heights(vec![0.0, 0.1, 0.0, 0.1]).unwrap();
+        let quarter = std::f32::consts::FRAC_PI_2;
+
+        assert!((terrain.height_at(quarter) - 0.1).abs() < 1e-5);
+        assert!((terrain.height_at(quarter * 0.5) - 0.05).abs() < 1e-5);
+        // Between the last sample and the first one again
+        assert!((terrain.height_at(quarter * 3.5) - 0.05).abs() < 1e-5);
+        assert!((terrain.height_at(-quarter) - 0.1).abs() < 1e-5);
+
+        assert!(Terrain::from_heights(vec![0.1, 0.2]).is_none());
+    }
+}
@@ -0,0 +1,287 @@
+// C ABI - Flat C functions over the embedding API for Python (ctypes/cffi) and other languages
+// Build the shared library with: cargo rustc --release --lib --crate-type cdylib (header in include/katie_fly_sim.h)
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+
+use crate::embed::{RocketControls, Simulation};
+use crate::map_config::MapConfiguration;
+
+thread_local! {
+    /// Why the last call on this thread failed
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    log::warn!("FFI call failed: {}", message);
+    LAST_ERROR.with(|error| *error.borrow_mut() = CString::new(message).ok());
+}
+
+/// Rocket state as a plain C struct
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct KfsRocketState {
+    pub id: u64,
+    pub player_id: i32, // -1 when unowned
+    pub x: f32,
+    pub y: f32,
+    pub vx: f32,
+    pub vy: f32,
+    pub rotation: f32,
+    pub fuel: f32,
+    pub max_fuel: f32,
+    pub landed: bool,
+}
+
+/// Celestial body state as a plain C struct
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct KfsBodyState {
+    pub id: u64,
+    pub x: f32,
+    pub y: f32,
+    pub vx: f32,
+    pub vy: f32,
+    pub radius: f32,
+    pub mass: f32,
+}
+
+/// Read a C string argument, recording an error if it's null or not UTF-8
+///
+/// # Safety
+/// `text` must be null or point to a NUL-terminated string
+unsafe fn read_str<'a>(text: *const c_char) -> Option<&'a str> {
+    if text.is_null() {
+        set_last_error("null string argument".to_string());
+        return None;
+    }
+    match CStr::from_ptr(text).to_str() {
+        Ok(text) => Some(text),
+        Err(e) => {
+            set_last_error(format!("string argument is not UTF-8: {}", e));
+            None
+        }
+    }
+}
+
+/// Message for the last failed call on this thread (null if none); valid until the next failure
+#[no_mangle]
+pub extern "C" fn kfs_last_error() -> *const c_char {
+    LAST_ERROR.with(|error| error.borrow().as_ref().map_or(std::ptr::null(), |message| message.as_ptr()))
+}
+
+/// New simulation on a built-in or custom map by name; null on failure (see `kfs_last_error`)
+///
+/// # Safety
+/// `map_name` must be null or point to a NUL-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn kfs_simulation_new(map_name: *const c_char) -> Option<Box<Simulation>> {
+    match Simulation::from_map_name(read_str(map_name)?) {
+        Ok(simulation) => Some(Box::new(simulation)),
+        Err(e) => {
+            set_last_error(e);
+            None
+        }
+    }
+}
+
+/// New simulation on a RON or JSON map file; null on failure (see `kfs_last_error`)
+///
+/// # Safety
+/// `path` must be null or point to a NUL-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn kfs_simulation_from_map_file(path: *const c_char) -> Option<Box<Simulation>> {
+    match MapConfiguration::load_from_file(read_str(path)?) {
+        Ok(map) => Some(Box::new(Simulation::from_map(map))),
+        Err(e) => {
+            set_last_error(e);
+            None
+        }
+    }
+}
+
+/// Free a simulation (null is ignored)
+#[no_mangle]
+pub extern "C" fn kfs_simulation_free(simulation: Option<Box<Simulation>>) {
+    drop(simulation);
+}
+
+/// Spawn a fueled rocket on the map's spawn body (`player_id` < 0 for none); returns its ID or -1
+#[no_mangle]
+pub extern "C" fn kfs_simulation_spawn_rocket(simulation: Option<&mut Simulation>, player_id: i32) -> i64 {
+    let Some(simulation) = simulation else {
+        set_last_error("null simulation".to_string());
+        return -1;
+    };
+    let owner = u32::try_from(player_id).ok();
+    match simulation.spawn_rocket(owner) {
+        Some(id) => id as i64,
+        None => {
+            set_last_error("map has no spawn body".to_string());
+            -1
+        }
+    }
+}
+
+/// Hold throttle (0-1) and turn rate (radians per second) on a rocket; false if it doesn't exist
+#[no_mangle]
+pub extern "C" fn kfs_simulation_set_controls(
+    simulation: Option<&mut Simulation>,
+    rocket_id: u64,
+    thrust: f32,
+    turn_rate: f32,
+) -> bool {
+    let Some(simulation) = simulation else {
+        return false;
+    };
+    let applied = simulation.set_controls(rocket_id as usize, RocketControls { thrust, turn_rate });
+    if !applied {
+        set_last_error(format!("no rocket {}", rocket_id));
+    }
+    applied
+}
+
+/// Seconds per physics step
+#[no_mangle]
+pub extern "C" fn kfs_simulation_set_timestep(simulation: Option<&mut Simulation>, timestep: f32) {
+    if let Some(simulation) = simulation {
+        simulation.set_timestep(timestep);
+    }
+}
+
+/// Run `steps` physics steps
+#[no_mangle]
+pub extern "C" fn kfs_simulation_step(simulation: Option<&mut Simulation>, steps: u32) {
+    if let Some(simulation) = simulation {
+        for _ in 0..steps {
+            simulation.step();
+        }
+    }
+}
+
+/// Run the steps that fit in `seconds` (the remainder carries over; bad or huge times are ignored or capped); returns how many ran
+#[no_mangle]
+pub extern "C" fn kfs_simulation_advance(simulation: Option<&mut Simulation>, seconds: f32) -> u32 {
+    simulation.map_or(0, |simulation| simulation.advance(seconds) as u32)
+}
+
+/// Simulated seconds so far
+#[no_mangle]
+pub extern "C" fn kfs_simulation_time(simulation: Option<&Simulation>) -> f32 {
+    simulation.map_or(0.0, Simulation::time)
+}
+
+#[no_mangle]
+pub extern "C" fn kfs_simulation_rocket_count(simulation: Option<&Simulation>) -> u32 {
+    simulation.map_or(0, |simulation| simulation.world().rockets().count() as u32)
+}
+
+/// State of the `index`th rocket (0 to count-1); false if out of range
+#[no_mangle]
+pub extern "C" fn kfs_simulation_rocket_at(simulation: Option<&Simulation>, index: u32, out: Option<&mut KfsRocketState>) -> bool {
+    let (Some(simulation), Some(out)) = (simulation, out) else {
+        return false;
+    };
+    match simulation.rockets().get(index as usize) {
+        Some(rocket) => {
+            *out = rocket_state(rocket);
+            true
+        }
+        None => false,
+    }
+}
+
+/// State of the rocket with this ID; false if it no longer exists (converted or destroyed)
+#[no_mangle]
+pub extern "C" fn kfs_simulation_rocket_state(simulation: Option<&Simulation>, rocket_id: u64, out: Option<&mut KfsRocketState>) -> bool {
+    let (Some(simulation), Some(out)) = (simulation, out) else {
+        return false;
+    };
+    match simulation.rocket(rocket_id as usize) {
+        Some(rocket) => {
+            *out = rocket_state(&rocket);
+            true
+        }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn kfs_simulation_body_count(simulation: Option<&Simulation>) -> u32 {
+    simulation.map_or(0, |simulation| simulation.world().planets().count() as u32)
+}
+
+/// State of the `index`th celestial body (0 to count-1); false if out of range
+#[no_mangle]
+pub extern "C" fn kfs_simulation_body_at(simulation: Option<&Simulation>, index: u32, out: Option<&mut KfsBodyState>) -> bool {
+    let (Some(simulation), Some(out)) = (simulation, out) else {
+        return false;
+    };
+    match simulation.bodies().get(index as usize) {
+        Some(body) => {
+            *out = KfsBodyState {
+                id: body.id as u64,
+                x: body.position.x,
+                y: body.position.y,
+                vx: body.velocity.x,
+                vy: body.velocity.y,
+                radius: body.radius,
+                mass: body.mass,
+            };
+            true
+        }
+        None => false,
+    }
+}
+
+fn rocket_state(rocket: &crate::embed::RocketState) -> KfsRocketState {
+    KfsRocketState {
+        id: rocket.id as u64,
+        player_id: rocket.player_id.map_or(-1, |id| id as i32),
+        x: rocket.position.x,
+        y: rocket.position.y,
+        vx: rocket.velocity.x,
+        vy: rocket.velocity.y,
+        rotation: rocket.rotation,
+        fuel: rocket.fuel,
+        max_fuel: rocket.max_fuel,
+        landed: rocket.landed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_through_c_functions() {
+        let name = CString::new("earth moon").unwrap();
+        let mut simulation = unsafe { kfs_simulation_new(name.as_ptr()) }.unwrap();
+        assert_eq!(kfs_simulation_body_count(Some(&simulation)), 2);
+
+        let rocket_id = kfs_simulation_spawn_rocket(Some(&mut simulation), 2);
+        assert!(rocket_id >= 0);
+        assert!(kfs_simulation_set_controls(Some(&mut simulation), rocket_id as u64, 1.0, 0.0));
+        kfs_simulation_set_timestep(Some(&mut simulation), 0.1);
+        kfs_simulation_step(Some(&mut simulation), 5);
+        assert!((kfs_simulation_time(Some(&simulation)) - 0.5).abs() < 1e-5);
+
+        let mut state = KfsRocketState::default();
+        assert!(kfs_simulation_rocket_state(Some(&simulation), rocket_id as u64, Some(&mut state)));
+        assert_eq!(state.player_id, 2);
+        assert!(state.fuel < state.max_fuel);
+        assert!(!kfs_simulation_rocket_at(Some(&simulation), 1, Some(&mut state)));
+        kfs_simulation_free(Some(simulation));
+    }
+
+    #[test]
+    fn test_failures_set_last_error() {
+        let name = CString::new("no such map").unwrap();
+        assert!(unsafe { kfs_simulation_new(name.as_ptr()) }.is_none());
+        let message = unsafe { CStr::from_ptr(kfs_last_error()) }.to_str().unwrap();
+        assert!(message.contains("no such map"));
+
+        assert!(unsafe { kfs_simulation_new(std::ptr::null()) }.is_none());
+        assert_eq!(kfs_simulation_spawn_rocket(None, 0), -1);
+    }
+}
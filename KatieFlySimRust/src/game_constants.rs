@@ -10,7 +10,9 @@ pub struct GameConstants;
 impl GameConstants {
     // ==================== Gravitational Constants ====================
     pub const G: f32 = 100.0;  // Gravitational constant
-    pub const PI: f32 = 3.14159265358979323846;
+    pub const ARCADE_GRAVITY_EXPONENT: f32 = 2.5;   // Arcade gravity falls off as 1/r^2.5
+    pub const ARCADE_GRAVITY_FLAT_BAND: f32 = 1.2;  // Arcade gravity is constant out to this many radii
+    pub const PI: f32 = STD_PI;
 
     // ==================== Mass-Radius Relationship ====================
     pub const BASE_RADIUS_FACTOR: f32 = 260.0;
@@ -39,6 +41,9 @@ impl GameConstants {
     pub const ROCKET_BASE_MASS: f32 = 1.0;
     pub const ROCKET_MAX_MASS: f32 = 101.0;
     pub const ROCKET_SIZE: f32 = 15.0;
+    pub const ROCKET_MAX_HULL: f32 = 100.0;      // Hull integrity (collision damage)
+    pub const ROCKET_COLLISION_RADIUS: f32 = 12.0; // Hitbox used for bullets and vehicle collisions
+    pub const ROCKET_SPAWN_ALTITUDE: f32 = 200.0;  // Height above the spawn body's surface
 
     // ==================== Fuel System Constants ====================
     pub const ROCKET_MAX_FUEL: f32 = 128.0;
@@ -48,9 +53,12 @@ impl GameConstants {
     pub const MANUAL_FUEL_TRANSFER_RATE: f32 = 10.0;
     pub const FUEL_TRANSFER_THRUST_MULTIPLIER: f32 = 0.1;
 
-    // Fuel consumption
-    pub const FUEL_CONSUMPTION_BASE: f32 = 2.0;
-    pub const FUEL_CONSUMPTION_MULTIPLIER: f32 = 8.0;
+    // Rocket-to-rocket transfer (rescuing a stranded rocket)
+    pub const ROCKET_FUEL_TRANSFER_RATE: f32 = 8.0;         // Fuel/sec while the transfer key is held
+    pub const ROCKET_FUEL_TRANSFER_RANGE: f32 = 150.0;      // Rockets must be this close...
+    pub const ROCKET_FUEL_TRANSFER_MAX_SPEED: f32 = 20.0;   // ...and moving this slowly relative to each other
+
+    // Fuel consumption (flow = thrust / specific impulse; no burn below the threshold)
     pub const FUEL_CONSUMPTION_MIN_THRESHOLD: f32 = 0.1;
 
     // Automatic fuel collection (for satellites)
@@ -78,15 +86,44 @@ impl GameConstants {
     // ==================== Engine Parameters ====================
     pub const BASE_THRUST_MULTIPLIER: f32 = 100000.0;
     pub const ENGINE_THRUST_POWER: f32 = Self::BASE_THRUST_MULTIPLIER;
+    pub const ENGINE_SPECIFIC_IMPULSE: f32 = 10000.0;              // Effective exhaust velocity at full throttle
+    pub const ENGINE_MIN_THROTTLE_SPECIFIC_IMPULSE: f32 = 3500.0;  // At the minimum throttle; above full-throttle Isp favours gentle burns
 
     // ==================== Bullet Parameters ====================
     pub const BULLET_SPEED: f32 = 500.0;          // Speed added to bullet when fired
     pub const BULLET_RECOIL_FORCE: f32 = 50.0;    // Recoil force magnitude
     pub const BULLET_RECOIL_MULTIPLIER: f32 = 0.01; // How much recoil affects the rocket
+    pub const BULLET_WARNING_RADIUS: f32 = 100.0;  // Warn when a bullet's path comes this close
+    pub const BULLET_WARNING_HORIZON: f32 = 10.0;  // How far ahead to check bullet paths (seconds)
 
     // ==================== Vehicle Transformation ====================
     pub const TRANSFORM_VELOCITY_FACTOR: f32 = 0.1;
 
+    // ==================== Sample Return ====================
+    pub const SAMPLE_SITE_RANGE: f32 = 500.0;  // Max distance along the surface from a site to collect its sample
+
+    // ==================== Rover ====================
+    pub const ROVER_SPEED: f32 = 80.0;            // Driving speed along the surface (pixels/s)
+    pub const ROVER_SIZE: f32 = 8.0;
+    pub const ROVER_FUEL_CAPACITY: f32 = 30.0;    // Fuel the rover can haul back to the rocket
+    pub const ROVER_EXTRACT_RATE: f32 = 5.0;      // Fuel extracted per second while parked on a deposit
+    pub const ROVER_DOCK_RANGE: f32 = 60.0;       // Max surface distance to the rocket for docking
+    pub const FUEL_DEPOSIT_RANGE: f32 = 150.0;    // Max surface distance from a deposit to extract
+
+    // ==================== Planet Rings ====================
+    pub const RING_HARVEST_MAX_SPEED: f32 = 20.0; // Rockets drifting this slowly with the ring particles scoop fuel
+    pub const RING_HARVEST_RATE: f32 = 4.0;       // Fuel per second scooped from a full-density ring
+    pub const RING_HAZARD_SPEED: f32 = 80.0;      // Relative speed above which ring particles damage the hull
+    pub const RING_DAMAGE_RATE: f32 = 0.5;        // Hull damage per second per unit of speed over the limit (full density)
+
+    // ==================== Simulation & Network Rates ====================
+    pub const DEFAULT_PHYSICS_HZ: u32 = 120;   // Fixed physics step rate
+    pub const MIN_PHYSICS_HZ: u32 = 30;
+    pub const MAX_PHYSICS_HZ: u32 = 240;
+    pub const DEFAULT_SNAPSHOT_HZ: u32 = 60;   // Host snapshot broadcast rate
+    pub const MIN_SNAPSHOT_HZ: u32 = 5;
+    pub const MAX_SNAPSHOT_HZ: u32 = 120;
+
     // ==================== Satellite System Constants ====================
 
     // Basic parameters
@@ -95,6 +132,8 @@ impl GameConstants {
     pub const SATELLITE_MAX_FUEL: f32 = 128.0;
     pub const SATELLITE_STARTING_FUEL: f32 = 0.0;  // Start empty like rockets
     pub const SATELLITE_SIZE: f32 = 12.0;
+    pub const SATELLITE_MAX_HULL: f32 = 60.0;
+    pub const SATELLITE_COLLISION_RADIUS: f32 = 7.0;
 
     // Station-keeping and orbital maintenance
     pub const SATELLITE_MAINTENANCE_FUEL_PERCENT: f32 = 0.2;
@@ -124,6 +163,19 @@ impl GameConstants {
     pub const SATELLITE_CRITICAL_FUEL_THRESHOLD: f32 = 0.05;
     pub const SATELLITE_MINIMUM_MAINTENANCE_FUEL: f32 = 5.0;
 
+    // Network HUD statistics
+    pub const SATELLITE_STATS_WINDOW: f32 = 60.0;  // Seconds of transfer history shown in the HUD
+    pub const SATELLITE_COVERAGE_SHELLS: [f32; 3] = [1000.0, 5000.0, 15000.0];  // Altitudes above the primary body
+
+    // Component wear (1.0 = end of life: the satellite retires, then deorbits unless a rocket retrieves it)
+    pub const SATELLITE_DESIGN_LIFE: f32 = 1200.0;        // Seconds of service before end of life
+    pub const SATELLITE_DEORBIT_WEAR: f32 = 1.1;          // Deorbit burn at this wear (the retrieval window)
+    pub const SATELLITE_DEORBIT_PERIAPSIS: f32 = 0.8;     // Deorbit targets a periapsis this fraction of the planet radius
+    pub const SATELLITE_DEORBIT_FUEL_PER_DV: f32 = 0.25;  // A light, empty-ish satellite burns little per unit of delta-v
+    pub const SATELLITE_RETRIEVAL_RANGE: f32 = 60.0;      // Rocket must be this close...
+    pub const SATELLITE_RETRIEVAL_MAX_SPEED: f32 = 15.0;  // ...and moving this slowly relative to it
+    pub const SATELLITE_RETRIEVAL_REFUND: f32 = 0.5;      // Share of the satellite's remaining fuel the rocket gets
+
     // Visual constants
     pub const SATELLITE_PANEL_SIZE: f32 = 8.0;
     pub const SATELLITE_ORBIT_PATH_THICKNESS: f32 = 2.0;
@@ -215,6 +267,7 @@ pub mod colors {
     pub const SATELLITE_STATUS_LOW_FUEL: Color = rgba(255, 255, 0, 200);
     pub const SATELLITE_STATUS_CRITICAL: Color = rgba(255, 100, 0, 200);
     pub const SATELLITE_STATUS_DEPLETED: Color = rgba(255, 0, 0, 200);
+    pub const SATELLITE_STATUS_END_OF_LIFE: Color = rgba(140, 140, 140, 200);
 
     // Orbit visualization colors
     pub const SATELLITE_ORBIT_PATH_COLOR: Color = rgba(0, 255, 255, 128);
@@ -225,6 +278,19 @@ pub mod colors {
     pub const SATELLITE_CONNECTION_COLOR: Color = rgba(100, 255, 100, 100);
     pub const SATELLITE_TRANSFER_FLOW_COLOR: Color = rgba(255, 255, 100, 200);
     pub const SATELLITE_EMERGENCY_COLOR: Color = rgba(255, 50, 50, 255);
+
+    /// Multiplayer player color (rockets, and the ring on satellites they own)
+    pub fn player_color(player_id: u32) -> Color {
+        match player_id {
+            0 => rgba(255, 100, 100, 255), // Red for host
+            1 => rgba(100, 100, 255, 255), // Blue for player 1
+            2 => rgba(100, 255, 100, 255), // Green for player 2
+            3 => rgba(255, 255, 100, 255), // Yellow for player 3
+            4 => rgba(255, 100, 255, 255), // Magenta for player 4
+            5 => rgba(100, 255, 255, 255), // Cyan for player 5
+            _ => rgba(200, 200, 200, 255), // Gray for others
+        }
+    }
 }
 
 #[cfg(test)]
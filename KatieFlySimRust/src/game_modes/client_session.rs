@@ -0,0 +1,755 @@
+// Client Session - One player's connection to a host, without a window
+// Joins, takes snapshots, predicts between them and sends our input; MultiplayerClient adds the screen and controls on top
+
+use macroquad::prelude::*;
+use std::net::{SocketAddr, UdpSocket};
+use std::collections::HashMap;
+use serde::Serialize;
+
+use crate::entities::{Rocket, Terrain};
+use crate::game_constants::GameConstants;
+use crate::map_config::{MapConfiguration, MapTunables};
+use crate::networking::{ClientInputPacket, Clock};
+use crate::physics::CollisionRules;
+use crate::save_system::save_diff::{self, MAX_DESYNC_DUMPS};
+use crate::save_system::{GameSaveData, SavedPlanet, SavedRocket, SavedSatellite, SavedBullet, SavedWaypoint};
+use crate::systems::{World, EntityId, AuthoritativePose, BulletRemovalPacket, ChatMessage, ChecksumPacket, CrewRole, DeltaDecoder, DesyncMonitor, FloatingOrigin, FuelTransferEvent, HitEvent, KillShot, LandingEvent, OwnershipPacket, PlanetRings, PredictionDebug, RemovedBullets, ServerNotice, SnapshotFragment, SnapshotInterpolator, SnapshotPacket, WaypointPacket, Waypoints};
+use crate::systems::desync::{world_checksum, RESYNC_REQUEST};
+use crate::systems::server_notice::{LEAVE, MAP_REQUEST};
+use crate::systems::snapshot_pacing::ack_packet;
+
+const KEEPALIVE_INTERVAL: f32 = 5.0; // Send keepalive every 5 seconds
+/// Seconds without a snapshot before the host counts as gone
+const CONNECTION_TIMEOUT: f64 = 30.0;
+/// Seconds between requests for a map definition the host's snapshots name but we haven't got
+const MAP_REQUEST_INTERVAL: f32 = 1.0;
+/// Spectators never learn the ID the host gave them; this one matches no rocket or satellite
+pub const SPECTATOR_PLAYER_ID: u32 = u32::MAX;
+/// Altitude above the surface that clears a reported landing (allows the next one to count)
+const LANDING_REPORT_RESET_ALTITUDE: f32 = 50.0;
+
+/// Something the player should see or feel, queued for MultiplayerClient (see `take_events`)
+#[derive(Debug, Clone)]
+pub enum ClientSessionEvent {
+    Toast(String, Color),
+    Chat(ChatMessage),           // A line for the chat box (ours, or relayed by the host)
+    HitLanded(Vec2),             // The host confirmed one of our bullets hit here
+    ShotDown { rocket_id: EntityId, shot: KillShot, impact: Vec2, attacker: String }, // The host says a bullet got us
+    SaveRefused(String),         // The host turned down our quick save
+    Ownership(OwnershipPacket),  // A satellite gift offered to us, or how one turned out
+    Desync,                      // Our world didn't match the host's checksum; a resync is on its way
+    OriginShift(Vec2),           // The host's floating origin moved; shift anything kept in local coordinates
+    Hit(HitEvent),               // Prediction saw a rocket struck
+    Landed(LandingEvent),        // Our rocket touched down (once per planet until we lift off again)
+    Destroyed(Vec2),             // Prediction lost a rocket here (the next snapshot has the host's respawn)
+    MapChanged,                  // The map's physics changed; previews should follow
+}
+
+pub struct ClientSession {
+    world: World,
+    player_id: u32, // Assigned by host
+    player_name: String, // This client's player name
+    crew_role: CrewRole, // Pilot of our own rocket, engineer aboard someone else's, or spectator
+    active_rocket_id: Option<EntityId>, // The rocket we fly or crew
+    clock: Box<dyn Clock>,
+    events: Vec<ClientSessionEvent>,
+    fuel_transfers: Vec<FuelTransferEvent>, // Last step's predicted transfers, for the beam effects
+
+    // Networking
+    socket: UdpSocket,
+    host_addr: SocketAddr,
+    keepalive_timer: f32,
+    last_snapshot_time: f64,
+    mission_time: f32, // Host's game time from the latest snapshot
+    connected: bool,
+    kicked_reason: Option<String>, // Set once the host removes us; we stop talking to it
+    connection_lost: bool, // Host shut down or went silent
+    player_names: HashMap<u32, String>, // Map player IDs to player names
+    desync: DesyncMonitor, // Checks applied snapshots against the host's checksums
+    prediction: PredictionDebug, // Prediction on/off (F9), error history and host ghost for the netcode overlay (F8)
+    interpolation: SnapshotInterpolator, // Draws other craft 100ms in the past, between snapshots
+    removed_bullets: RemovedBullets, // Host removals newer than the last snapshot
+    snapshot_decoder: DeltaDecoder, // Rebuilds snapshots from the host's keyframes and deltas
+    desync_dumps: u32, // World states written for desync reports this session
+    waypoints: Waypoints,    // Ours and shared ones, as last sent by the host
+    map: Option<MapConfiguration>,      // Custom map sent by the host (None = the classic layout)
+    map_terrains: Vec<Option<Terrain>>, // The map's terrain, generated once rather than every snapshot
+    map_request_timer: f32,             // Until the map definition may be asked for again
+    // Planet we last reported a landing on - snapshots don't carry landed state,
+    // so prediction re-detects the touchdown until we actually leave the surface
+    reported_landing_planet: Option<EntityId>,
+}
+
+impl ClientSession {
+    /// Join the host at `host_addr` from `socket` (switched to non-blocking), reading timeouts from `clock`
+    pub fn connect(
+        socket: UdpSocket,
+        host_addr: SocketAddr,
+        player_name: String,
+        crew_role: CrewRole,
+        clock: Box<dyn Clock>,
+    ) -> Result<Self, String> {
+        // Set non-blocking mode
+        socket.set_nonblocking(true)
+            .map_err(|e| format!("Failed to set non-blocking mode: {}", e))?;
+
+        // Send initial join packet with player name and the role picked in the lobby
+        #[derive(Serialize)]
+        struct JoinPacket {
+            player_name: String,
+            crew_role: CrewRole,
+        }
+
+        let join_data = JoinPacket {
+            player_name: player_name.clone(),
+            crew_role,
+        };
+
+        let join_packet = bincode::serialize(&join_data)
+            .map_err(|e| format!("Failed to serialize join packet: {}", e))?;
+        socket.send_to(&join_packet, host_addr)
+            .map_err(|e| format!("Failed to send join packet: {}", e))?;
+
+        // Initialize player names map with this client's name (will be updated with actual player_id later)
+        let mut player_names = HashMap::new();
+        player_names.insert(1, player_name.clone()); // Temporary ID 1, will be updated
+
+        // Vehicle collisions are resolved authoritatively by the host (snapshots carry the result)
+        let mut world = World::new();
+        world.set_collision_rules(CollisionRules::disabled());
+
+        let last_snapshot_time = clock.now();
+        Ok(Self {
+            world,
+            player_id: if crew_role == CrewRole::Spectator { SPECTATOR_PLAYER_ID } else { 1 }, // Pilots learn theirs from a snapshot
+            player_name,
+            crew_role,
+            active_rocket_id: None,
+            clock,
+            events: Vec::new(),
+            fuel_transfers: Vec::new(),
+
+            socket,
+            host_addr,
+            keepalive_timer: 0.0,
+            last_snapshot_time,
+            mission_time: 0.0,
+            connected: false,
+            kicked_reason: None,
+            connection_lost: false,
+            player_names,
+            desync: DesyncMonitor::new(),
+            prediction: PredictionDebug::new(),
+            interpolation: SnapshotInterpolator::new(),
+            removed_bullets: RemovedBullets::new(),
+            snapshot_decoder: DeltaDecoder::new(),
+            desync_dumps: 0,
+            waypoints: Waypoints::new(),
+            map: None,
+            map_terrains: Vec::new(),
+            map_request_timer: 0.0,
+            reported_landing_planet: None,
+        })
+    }
+
+    /// Calculate spawn position for a player based on their player ID
+    /// Same as host - each player at +5 degrees from previous
+    fn calculate_spawn_position(&self, player_id: u32) -> Vec2 {
+        let angle_degrees = player_id as f32 * 5.0;
+        let angle_radians = angle_degrees.to_radians();
+        let spawn_distance = GameConstants::MAIN_PLANET_RADIUS + 200.0;
+
+        let spawn = DVec2::new(
+            (GameConstants::MAIN_PLANET_X + spawn_distance * angle_radians.cos()) as f64,
+            (GameConstants::MAIN_PLANET_Y + spawn_distance * angle_radians.sin()) as f64,
+        );
+        self.world.origin().to_local(spawn)
+    }
+
+    /// Everything MultiplayerClient shows or plays since the last call
+    pub fn take_events(&mut self) -> Vec<ClientSessionEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Predicted fuel transfers during the last step, for the beam effects
+    pub fn take_fuel_transfers(&mut self) -> Vec<FuelTransferEvent> {
+        std::mem::take(&mut self.fuel_transfers)
+    }
+
+    pub fn player_label(&self, player_id: u32) -> String {
+        self.player_names.get(&player_id).cloned().unwrap_or_else(|| format!("Player {}", player_id))
+    }
+
+    /// Send our controls to the host, steering our predicted rocket the same way
+    /// (the snapshot carries the pilot's thrust for engineers)
+    pub fn send_input(&mut self, input: ClientInputPacket) {
+        let predicted = self.active_rocket_id.filter(|_| self.prediction.prediction_enabled() && self.crew_role == CrewRole::Pilot);
+        if let Some(rocket) = predicted.and_then(|id| self.world.get_rocket_mut(id)) {
+            if input.rotation_delta != 0.0 {
+                rocket.rotate(input.rotation_delta * std::f32::consts::PI / 180.0);
+            }
+            rocket.set_thrust_level(input.thrust_level);
+        }
+
+        if let Ok(bytes) = bincode::serialize(&input) {
+            if let Err(e) = self.socket.send_to(&bytes, self.host_addr) {
+                log::warn!("Failed to send input packet: {}", e);
+            }
+        }
+    }
+
+    /// Hands off the controls (while typing in the chat box)
+    pub fn send_idle_input(&self) {
+        let input_packet = ClientInputPacket::idle(self.player_id);
+        if let Ok(bytes) = bincode::serialize(&input_packet) {
+            if let Err(e) = self.socket.send_to(&bytes, self.host_addr) {
+                log::warn!("Failed to send input packet: {}", e);
+            }
+        }
+    }
+
+    /// Send our chat line through the host (it comes back as a chat event for our chat box right away)
+    pub fn send_chat(&mut self, text: &str) {
+        let Some(message) = ChatMessage::new(self.player_id, self.player_name.clone(), text) else {
+            return;
+        };
+        if let Err(e) = self.socket.send_to(&message.to_bytes(), self.host_addr) {
+            log::warn!("Failed to send chat: {}", e);
+        }
+        self.events.push(ClientSessionEvent::Chat(message));
+    }
+
+    /// Offer a satellite, or answer an offer (the host checks ownership)
+    pub fn send_ownership(&self, packet: OwnershipPacket) {
+        if let Err(e) = self.socket.send_to(&packet.to_bytes(), self.host_addr) {
+            log::warn!("Failed to send ownership packet: {}", e);
+        }
+    }
+
+    /// Ask the host to add or remove one of our waypoints
+    pub fn send_waypoint(&self, packet: WaypointPacket) {
+        if let Err(e) = self.socket.send_to(&packet.to_bytes(), self.host_addr) {
+            log::warn!("Failed to send waypoint packet: {}", e);
+        }
+    }
+
+    /// Keep the link alive and take whatever the host sent
+    pub fn receive(&mut self, delta_time: f32) {
+        // Send keepalive packets to host
+        self.keepalive_timer += delta_time;
+        if self.keepalive_timer >= KEEPALIVE_INTERVAL && self.kicked_reason.is_none() {
+            self.send_keepalive();
+            self.keepalive_timer = 0.0;
+        }
+
+        // Receive snapshots from host
+        self.map_request_timer -= delta_time;
+        self.receive_snapshots();
+
+        // Check for connection timeout (no snapshot for 30 seconds)
+        if self.seconds_since_snapshot() > CONNECTION_TIMEOUT && self.connected {
+            log::warn!("Connection to host lost (no snapshots for 30 seconds)");
+            self.connected = false;
+            self.connection_lost = true;
+        }
+    }
+
+    /// Run the local predicted simulation between snapshots (with prediction off the world holds the last snapshot)
+    pub fn simulate(&mut self, delta_time: f32) {
+        if self.prediction.prediction_enabled() {
+            self.world.update(delta_time, false);
+            // Everyone but us is shown between the two snapshots bracketing the render clock
+            self.interpolation.advance(delta_time);
+            self.interpolation.apply(&mut self.world);
+        }
+        // Predicted hits flash right away; hit markers wait for the host's word
+        for hit in self.world.take_hit_events() {
+            self.events.push(ClientSessionEvent::Hit(hit));
+        }
+        self.fuel_transfers = self.world.take_fuel_transfer_events();
+        self.update_landing_reports();
+
+        // Handle rockets destroyed by bullets (respawn like 'C' key, but without satellite)
+        let destroyed_rockets = self.world.take_destroyed_rockets();
+        for destroyed in destroyed_rockets {
+            let player_id = destroyed.player_id.unwrap_or(0);
+            log::info!("Player {} rocket destroyed by bullet, respawning", player_id);
+            self.events.push(ClientSessionEvent::Destroyed(destroyed.position));
+
+            // Spawn new rocket for this player (same as 'C' key respawn logic)
+            let spawn_position = self.calculate_spawn_position(player_id);
+            let mut new_rocket = Rocket::new(
+                spawn_position,
+                Vec2::new(0.0, 0.0),
+                crate::game_constants::colors::player_color(player_id),
+                GameConstants::ROCKET_BASE_MASS,
+            );
+            new_rocket.set_player_id(Some(player_id));
+            let new_rocket_id = self.world.add_rocket(new_rocket);
+
+            // If this was our rocket (this client's player), update active_rocket_id
+            if player_id == self.player_id {
+                self.active_rocket_id = Some(new_rocket_id);
+                self.world.set_active_rocket(Some(new_rocket_id));
+            }
+
+            log::info!("Respawned new rocket {} for player {}", new_rocket_id, player_id);
+        }
+    }
+
+    /// Tell the host we're going, so it drops our rocket now (call before dropping the client)
+    pub fn disconnect(&self) {
+        if self.kicked_reason.is_some() || self.connection_lost {
+            return;
+        }
+        if let Err(e) = self.socket.send_to(LEAVE, self.host_addr) {
+            log::warn!("Failed to send leave packet: {}", e);
+        }
+    }
+
+    /// Send keepalive packet to host
+    fn send_keepalive(&self) {
+        let keepalive_packet = b"KEEPALIVE";
+        if let Err(e) = self.socket.send_to(keepalive_packet, self.host_addr) {
+            log::warn!("Failed to send keepalive: {}", e);
+        }
+    }
+
+    /// Our own touchdowns, once per planet until we lift off again
+    fn update_landing_reports(&mut self) {
+        // Leaving the surface re-arms landing reports
+        if let (Some(planet_id), Some(rocket_id)) = (self.reported_landing_planet, self.active_rocket_id) {
+            if let (Some(planet), Some(rocket)) = (self.world.get_planet(planet_id), self.world.get_rocket(rocket_id)) {
+                let altitude = (rocket.position() - planet.position()).length() - planet.radius();
+                if altitude > LANDING_REPORT_RESET_ALTITUDE {
+                    self.reported_landing_planet = None;
+                }
+            }
+        }
+
+        // Satellite retirement is the host's call (its snapshots carry the wear); drop our predicted events
+        self.world.take_satellite_life_events();
+
+        for event in self.world.take_landing_events() {
+            if Some(event.rocket_id) != self.active_rocket_id
+                || self.reported_landing_planet == Some(event.planet_id)
+            {
+                continue;
+            }
+            self.reported_landing_planet = Some(event.planet_id);
+            self.events.push(ClientSessionEvent::Landed(event));
+        }
+    }
+
+    /// Receive and apply snapshots from host
+    fn receive_snapshots(&mut self) {
+        let mut buf = vec![0u8; 65536]; // Snapshots arrive in pieces of at most 1200 bytes; the rest is headroom
+
+        // Process all available packets
+        loop {
+            match self.socket.recv_from(&mut buf) {
+                Ok((size, _src_addr)) => {
+                    if self.kicked_reason.is_some() {
+                        continue;
+                    }
+
+                    if let Some(notice) = ServerNotice::from_bytes(&buf[..size]) {
+                        self.handle_server_notice(notice);
+                        continue;
+                    }
+
+                    if let Some(packet) = ChecksumPacket::from_bytes(&buf[..size]) {
+                        self.check_desync(packet);
+                        continue;
+                    }
+
+                    if let Some(message) = ChatMessage::from_bytes(&buf[..size]) {
+                        self.events.push(ClientSessionEvent::Chat(message));
+                        continue;
+                    }
+
+                    // One of our bullets connected
+                    if let Some(packet) = crate::systems::HitEventPacket::from_bytes(&buf[..size]) {
+                        self.events.push(ClientSessionEvent::HitLanded(Vec2::new(packet.position.0, packet.position.1)));
+                        continue;
+                    }
+
+                    if let Some(packet) = OwnershipPacket::from_bytes(&buf[..size]) {
+                        self.events.push(ClientSessionEvent::Ownership(packet));
+                        continue;
+                    }
+
+                    // The host removed these bullets; drop them now rather than at the next snapshot
+                    if let Some(packet) = BulletRemovalPacket::from_bytes(&buf[..size]) {
+                        for bullet_id in &packet.bullet_ids {
+                            self.world.remove_bullet(*bullet_id);
+                        }
+                        self.removed_bullets.record(&packet);
+                        continue;
+                    }
+
+                    // A piece of a snapshot too big for one datagram; decoded once the last one arrives
+                    if let Some(fragment) = SnapshotFragment::from_bytes(&buf[..size]) {
+                        if let Some(packet) = self.snapshot_decoder.add_fragment(fragment) {
+                            self.receive_snapshot(packet);
+                        }
+                        continue;
+                    }
+
+                    // Received snapshot from host (a keyframe, or a delta against one we acked)
+                    let Some(packet) = SnapshotPacket::from_bytes(&buf[..size]) else {
+                        log::warn!("Ignoring unrecognised {}-byte packet from host", size);
+                        continue;
+                    };
+                    self.receive_snapshot(packet);
+                }
+                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    // No more packets available
+                    break;
+                }
+                Err(e) => {
+                    log::warn!("Error receiving UDP packet: {}", e);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Rebuild a snapshot packet and apply it, acking it to the host
+    fn receive_snapshot(&mut self, packet: SnapshotPacket) {
+        match self.snapshot_decoder.decode(packet) {
+            Ok(Some(snapshot)) => {
+                // Ack so the host can measure this link, pace our snapshots and diff against this one
+                let ack = ack_packet(snapshot.game_time);
+                self.apply_snapshot(snapshot);
+                if let Err(e) = self.socket.send_to(&ack, self.host_addr) {
+                    log::warn!("Failed to ack snapshot: {}", e);
+                }
+                self.last_snapshot_time = self.clock.now();
+                self.connected = true;
+            }
+            Ok(None) => {} // Arrived after a newer one
+            Err(e) => log::debug!("{}, waiting for the next keyframe", e),
+        }
+    }
+
+    fn handle_server_notice(&mut self, notice: ServerNotice) {
+        match notice {
+            ServerNotice::Broadcast(message) => {
+                log::info!("Server message: {}", message);
+                self.events.push(ClientSessionEvent::Toast(format!("[Server] {}", message), GOLD));
+            }
+            ServerNotice::Kicked(reason) => {
+                log::warn!("Removed from the game by the host: {}", reason);
+                self.events.push(ClientSessionEvent::Toast(format!("Removed by the host: {}", reason), RED));
+                self.connected = false;
+                self.kicked_reason = Some(reason);
+            }
+            ServerNotice::ShotDown { rocket_id, bullet_id, shooter_id, attacker, impact } => {
+                log::info!("Shot down by {}", attacker);
+                let shot = KillShot { bullet_id, shooter_id, shooter_player: None };
+                self.events.push(ClientSessionEvent::ShotDown { rocket_id, shot, impact: Vec2::new(impact.0, impact.1), attacker });
+            }
+            ServerNotice::MapDefinition(json) => match ServerNotice::decode_map(&json) {
+                Ok(map) => {
+                    if self.map.as_ref().map(|current| &current.name) != Some(&map.name) {
+                        log::info!("Host is playing map '{}'", map.name);
+                        self.events.push(ClientSessionEvent::Toast(format!("Map: {}", map.name), SKYBLUE));
+                    }
+                    self.set_map(Some(map));
+                }
+                Err(e) => log::warn!("Ignoring the host's map definition: {}", e),
+            },
+            ServerNotice::SaveRefused(reason) => {
+                log::info!("Host refused to save: {}", reason);
+                self.events.push(ClientSessionEvent::SaveRefused(reason));
+            }
+            ServerNotice::Station(text) => self.events.push(ClientSessionEvent::Toast(text, SKYBLUE)),
+            ServerNotice::HostShutdown => {
+                log::warn!("The host closed the game");
+                self.connected = false;
+                self.connection_lost = true;
+            }
+        }
+    }
+
+    /// Take on a map's names, terrain and physics (None goes back to the classic layout)
+    fn set_map(&mut self, map: Option<MapConfiguration>) {
+        let gravity_model = map.as_ref().map(|map| map.gravity_model).unwrap_or_default();
+        self.world.gravity_simulator_mut().set_model(gravity_model);
+        self.world.set_bullet_speed(map.as_ref().map(|map| map.tunables.bullet_speed()).unwrap_or_else(|| MapTunables::default().bullet_speed()));
+        self.world.set_rings(map.as_ref().map(PlanetRings::from_map).unwrap_or_default());
+        let mut tidal_rules = self.world.tidal_rules();
+        tidal_rules.enabled = map.as_ref().is_some_and(|map| map.tidal_breakup);
+        self.world.set_tidal_rules(tidal_rules);
+        self.map_terrains = map
+            .as_ref()
+            .map(|map| map.celestial_bodies.iter().map(|body| body.terrain.as_ref().map(|t| t.to_terrain())).collect())
+            .unwrap_or_default();
+        self.map = map;
+        self.name_planets_from_map();
+        self.events.push(ClientSessionEvent::MapChanged);
+    }
+
+    /// Ask the host for its map when a snapshot names one we don't have (the copy sent on join can be lost)
+    fn follow_snapshot_map(&mut self, map_name: Option<&str>) {
+        match map_name {
+            None if self.map.is_some() => self.set_map(None),
+            Some(name) if self.map.as_ref().map(|map| map.name.as_str()) != Some(name) && self.map_request_timer <= 0.0 => {
+                self.map_request_timer = MAP_REQUEST_INTERVAL;
+                if let Err(e) = self.socket.send_to(MAP_REQUEST, self.host_addr) {
+                    log::warn!("Failed to request the map: {}", e);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Snapshots don't carry names or terrain; the host adds planets in map order, so match them up by ID
+    fn name_planets_from_map(&mut self) {
+        let Some(map) = &self.map else {
+            return;
+        };
+        let ids: Vec<EntityId> = self.world.planets_with_ids().map(|(id, _)| id).collect();
+        if ids.len() != map.celestial_bodies.len() {
+            return; // A planet broke up or merged; leave them unnamed rather than guess
+        }
+        for ((id, body), terrain) in ids.into_iter().zip(&map.celestial_bodies).zip(&self.map_terrains) {
+            if let Some(planet) = self.world.get_planet_mut(id) {
+                planet.set_name(body.name.clone());
+                planet.set_terrain(terrain.clone());
+            }
+        }
+    }
+
+    /// Compare the host's checksum with our world as of that snapshot; ask for a resync on mismatch
+    fn check_desync(&mut self, packet: ChecksumPacket) {
+        if self.desync.check(packet) != Some(true) {
+            return;
+        }
+        log::warn!(
+            "Desync detected at host time {:.2}s (host checksum {:016x}, {} mismatches this session), requesting resync",
+            packet.snapshot_time,
+            packet.checksum,
+            self.desync.mismatch_count()
+        );
+        self.events.push(ClientSessionEvent::Desync);
+        if self.desync_dumps < MAX_DESYNC_DUMPS {
+            // The host dumps its side when the resync request arrives; compare the two with save_diff
+            self.desync_dumps += 1;
+            if let Err(e) = save_diff::write_desync_dump(&self.world_dump(), "client", packet.snapshot_time) {
+                log::warn!("Failed to write desync dump: {}", e);
+            }
+        }
+        if let Err(e) = self.socket.send_to(RESYNC_REQUEST, self.host_addr) {
+            log::warn!("Failed to request resync: {}", e);
+        }
+    }
+
+    /// Our world as it stands, in save form (for desync dumps)
+    fn world_dump(&self) -> GameSaveData {
+        let mut save_data = GameSaveData::new();
+        save_data.game_time = self.mission_time;
+        save_data.planets = self.world.planets_with_ids().map(|(id, planet)| SavedPlanet::from_planet(id, planet)).collect();
+        save_data.rockets = self.world.rockets_with_ids().map(|(id, rocket)| SavedRocket::from_rocket(id, rocket)).collect();
+        save_data.satellites = self.world.satellites_with_ids().map(|(id, satellite)| SavedSatellite::from_satellite(id, satellite)).collect();
+        save_data.bullets = self.world.bullets_with_ids().map(|(id, bullet)| SavedBullet::from_bullet(id, bullet)).collect();
+        save_data.player_id = Some(self.player_id);
+        save_data.active_rocket_id = self.active_rocket_id;
+        save_data.player_names = self.player_names.clone();
+        save_data.map_name = self.map.as_ref().map(|map| map.name.clone());
+        save_data.origin = self.world.origin().into();
+        save_data.arena = self.world.arena();
+        save_data.cosmetic_seed = self.world.cosmetic_seed();
+        save_data
+    }
+
+    /// Apply received snapshot to local world state
+    fn apply_snapshot(&mut self, snapshot: GameSaveData) {
+        log::debug!("Applying snapshot from host");
+        let snapshot_time = snapshot.game_time;
+        self.mission_time = snapshot_time;
+        self.waypoints = Waypoints::from_list(snapshot.waypoints.iter().map(SavedWaypoint::to_waypoint).collect());
+
+        // Where we had predicted our rocket, to measure against the host's answer
+        let predicted = self.active_rocket_id.and_then(|id| self.world.get_rocket(id)).map(|rocket| rocket.position());
+
+        // Clear existing world, following the host's floating origin (the camera moves with a rebase)
+        let previous_origin = self.world.origin();
+        let origin: FloatingOrigin = snapshot.origin.into();
+        self.world.clear_all_entities();
+        self.world.set_origin(origin);
+        self.world.set_arena(snapshot.arena);
+        self.world.set_cosmetic_seed(snapshot.cosmetic_seed);
+        let shift = origin.shift_from(&previous_origin);
+        if shift != Vec2::ZERO {
+            self.interpolation.shift_positions(shift);
+            self.events.push(ClientSessionEvent::OriginShift(shift));
+        }
+
+        // Load planets with their original IDs
+        self.follow_snapshot_map(snapshot.map_name.as_deref());
+        for saved_planet in snapshot.planets {
+            let (id, planet) = saved_planet.to_planet();
+            self.world.add_planet_with_id(id, planet);
+        }
+        self.name_planets_from_map();
+
+        // Each client's snapshots carry the ID the host gave it (spectators keep theirs matching nothing)
+        if let Some(player_id) = snapshot.player_id.filter(|_| self.crew_role != CrewRole::Spectator) {
+            if player_id != self.player_id {
+                log::info!("Assigned player ID from snapshot: {}", player_id);
+                self.player_id = player_id;
+            }
+        }
+
+        // Load rockets with their original IDs and find ours
+        let mut my_rocket_id: Option<EntityId> = None;
+
+        for saved_rocket in snapshot.rockets {
+            let fuel = saved_rocket.fuel;
+            let (id, mut rocket) = saved_rocket.to_rocket();
+            if let Some(map) = &self.map {
+                // Sized for the map like the host's rockets, before refilling the sent fuel
+                map.tunables.apply_to_rocket(&mut rocket);
+                rocket.set_fuel(fuel);
+            }
+
+            if let Some(pid) = rocket.player_id() {
+                // Check if this rocket belongs to us (or to the pilot we crew for)
+                let owner = match self.crew_role {
+                    CrewRole::Pilot => Some(self.player_id),
+                    CrewRole::Engineer { pilot_id } => Some(pilot_id),
+                    CrewRole::Spectator => None,
+                };
+                if Some(pid) == owner {
+                    my_rocket_id = Some(id);
+                    log::debug!("Found my rocket (player {}): {:?}", self.player_id, id);
+                }
+            }
+
+            self.world.add_rocket_with_id(id, rocket);
+        }
+
+        // The host turns engineers with no pilot to crew for into pilots with their own rocket
+        if let CrewRole::Engineer { pilot_id } = self.crew_role {
+            if my_rocket_id.is_none() && self.active_rocket_id.is_none() {
+                self.crew_role = CrewRole::Pilot;
+                self.events.push(ClientSessionEvent::Toast(format!("No rocket for player {} to crew - flying your own", pilot_id), YELLOW));
+                log::info!("Engineer role rejected (no rocket for player {}), switching to pilot", pilot_id);
+            }
+        }
+
+        // Load satellites with their original IDs
+        for saved_satellite in snapshot.satellites {
+            let (id, satellite) = saved_satellite.to_satellite();
+            self.world.add_satellite_with_id(id, satellite);
+        }
+
+        // Load bullets with their original IDs
+        for saved_bullet in snapshot.bullets {
+            let (id, bullet) = saved_bullet.to_bullet();
+            self.world.add_bullet_with_id(id, bullet);
+        }
+
+        // Player names from the snapshot (the host and everyone still connected; leavers drop out)
+        if !snapshot.player_names.is_empty() {
+            self.player_names = snapshot.player_names;
+        }
+
+        // Update our active rocket to the one that belongs to us
+        if let Some(rocket_id) = my_rocket_id {
+            self.active_rocket_id = Some(rocket_id);
+            self.world.set_active_rocket(Some(rocket_id));
+        } else if self.active_rocket_id.is_none() {
+            // If we haven't found our rocket yet, this might be the first snapshot
+            // before the host has spawned our rocket. Keep waiting.
+            log::debug!("Haven't found my rocket yet (player {}), waiting for host to spawn it", self.player_id);
+        }
+
+        let authoritative = self
+            .active_rocket_id
+            .and_then(|id| self.world.get_rocket(id))
+            .map(|rocket| AuthoritativePose { position: rocket.position(), rotation: rocket.rotation() });
+        self.prediction.record_snapshot(predicted.map(|position| position - shift), authoritative);
+
+        // Note: We keep our local camera instead of using snapshot camera
+        // This gives the client freedom to look around independently
+
+        self.desync.record_applied(snapshot_time, world_checksum(&self.world));
+
+        // A snapshot sent before a removal arrived after it (checksummed above as the host sent it)
+        for bullet_id in self.removed_bullets.stale_in(snapshot_time) {
+            self.world.remove_bullet(bullet_id);
+        }
+        self.interpolation.record(snapshot_time, &self.world, self.active_rocket_id);
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn player_id(&self) -> u32 {
+        self.player_id
+    }
+
+    pub fn crew_role(&self) -> CrewRole {
+        self.crew_role
+    }
+
+    /// The rocket we fly or crew
+    pub fn active_rocket_id(&self) -> Option<EntityId> {
+        self.active_rocket_id
+    }
+
+    pub fn host_addr(&self) -> SocketAddr {
+        self.host_addr
+    }
+
+    /// Host's game time from the latest snapshot
+    pub fn mission_time(&self) -> f32 {
+        self.mission_time
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    /// Why the host removed us, if it did
+    pub fn kicked_reason(&self) -> Option<&str> {
+        self.kicked_reason.as_deref()
+    }
+
+    /// The host shut down or went silent
+    pub fn connection_lost(&self) -> bool {
+        self.connection_lost
+    }
+
+    pub fn seconds_since_snapshot(&self) -> f64 {
+        self.clock.now() - self.last_snapshot_time
+    }
+
+    pub fn player_names(&self) -> &HashMap<u32, String> {
+        &self.player_names
+    }
+
+    pub fn waypoints(&self) -> &Waypoints {
+        &self.waypoints
+    }
+
+    pub fn map(&self) -> Option<&MapConfiguration> {
+        self.map.as_ref()
+    }
+
+    pub fn desync(&self) -> &DesyncMonitor {
+        &self.desync
+    }
+
+    pub fn prediction(&self) -> &PredictionDebug {
+        &self.prediction
+    }
+
+    pub fn prediction_mut(&mut self) -> &mut PredictionDebug {
+        &mut self.prediction
+    }
+}
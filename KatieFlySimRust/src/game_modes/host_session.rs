@@ -0,0 +1,1878 @@
+// Host Session - The authoritative side of an online game, without a window
+// Owns the world, the clients and the wire protocol; MultiplayerHost adds the host's screen and controls on top
+
+use macroquad::prelude::*;
+use std::net::{IpAddr, SocketAddr, UdpSocket};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use serde::Deserialize;
+
+use crate::entities::{Planet, Rocket};
+use crate::game_constants::GameConstants;
+use crate::map_config::{orbit_calculator, ArenaConfig, MapConfiguration, MapTunables};
+use crate::networking::client_input::ClientInputPacket;
+use crate::networking::Clock;
+use crate::physics::CollisionRules;
+use crate::save_system::save_diff::{self, MAX_DESYNC_DUMPS};
+use crate::save_system::{GameSaveData, SavedPlanet, SavedRocket, SavedSatellite, SavedBullet, SavedVector2, SavedWaypoint};
+#[cfg(feature = "sqlite")]
+use crate::save_system::{world_database, PlayerStat, WorldDatabase};
+use crate::systems::{World, EntityId, BoundsConfig, BulletRemovalPacket, ChatMessage, ChecksumPacket, CrewAssignments, CrewRole, DeltaEncoder, DestroyedRocketInfo, FuelTransferEvent, HitEvent, HitEventPacket, KillShot, LagCompensation, LandingEvent, LinkQuality, OwnershipPacket, PlanetRings, RecordedAction, ServerNotice, SessionEventKind, SessionRecorder, StationEvent, StationOps, TransferOffers, WaypointPacket, Waypoints};
+use crate::systems::ownership::{self, TransferOffer};
+use crate::systems::world_bounds;
+use crate::systems::ArenaBounds;
+use crate::systems::desync::{snapshot_checksum, RESYNC_REQUEST};
+use crate::systems::server_notice::{LEAVE, MAP_REQUEST};
+use crate::systems::bullet_sync::MAX_REMOVALS_PER_PACKET;
+use crate::systems::snapshot_pacing::parse_ack;
+use crate::ui::PlayerRow;
+
+/// Host plus connected clients (the most a host can allow)
+pub const MAX_PLAYERS: u32 = 20;
+/// Smallest player cap a host can set (the host and one client)
+pub const MIN_PLAYERS: u32 = 2;
+/// Spectators watching at once; they don't take player slots
+pub const MAX_SPECTATORS: u32 = 8;
+/// Whether a new client fits: spectators against their own limit, everyone else against the player cap
+/// (`playing` and `watching` count connected clients; the host takes one player slot)
+fn lobby_has_room(playing: u32, watching: u32, spectating: bool, max_players: u32) -> bool {
+    if spectating {
+        watching < MAX_SPECTATORS
+    } else {
+        playing + 1 < max_players
+    }
+}
+
+/// Seconds between world checksums sent for desync detection
+const CHECKSUM_INTERVAL: f32 = 2.0;
+/// Seconds after a LEAVE during which late packets from that address are dropped instead of rejoining
+const LEAVE_GRACE: f64 = 5.0;
+
+/// Physics tick rate and snapshot broadcast rate chosen by the host
+/// Each rate is clamped independently to its own allowed range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HostRates {
+    physics_hz: u32,
+    snapshot_hz: u32,
+}
+
+impl HostRates {
+    pub fn new(physics_hz: u32, snapshot_hz: u32) -> Self {
+        HostRates {
+            physics_hz: physics_hz.clamp(GameConstants::MIN_PHYSICS_HZ, GameConstants::MAX_PHYSICS_HZ),
+            snapshot_hz: snapshot_hz.clamp(GameConstants::MIN_SNAPSHOT_HZ, GameConstants::MAX_SNAPSHOT_HZ),
+        }
+    }
+
+    pub fn physics_hz(&self) -> u32 {
+        self.physics_hz
+    }
+
+    pub fn snapshot_hz(&self) -> u32 {
+        self.snapshot_hz
+    }
+
+    /// Seconds per physics step
+    pub fn physics_timestep(&self) -> f32 {
+        1.0 / self.physics_hz as f32
+    }
+
+    /// Seconds between snapshot broadcasts
+    pub fn snapshot_interval(&self) -> f32 {
+        1.0 / self.snapshot_hz as f32
+    }
+}
+
+impl Default for HostRates {
+    fn default() -> Self {
+        HostRates::new(GameConstants::DEFAULT_PHYSICS_HZ, GameConstants::DEFAULT_SNAPSHOT_HZ)
+    }
+}
+
+/// What the host player does this frame, read from the keyboard and controller by MultiplayerHost
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HostControls {
+    pub rotation_delta: f32, // degrees this frame
+    pub thrust_level: f32,   // 0.0 to 1.0
+    pub convert_to_satellite: bool,
+    pub shoot_bullet: bool,
+    pub abort: bool, // Back to spawn (MultiplayerHost only offers it while the rocket is escaping)
+    pub retrieve_satellite: bool,
+}
+
+/// Fuel keys the host player pressed or holds during a physics step
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HostFuelKeys {
+    pub refuel_from_planet: bool, // R: one refuel step
+    pub station_ops: bool,        // 6: step the station ops hold
+    pub transfer_fuel: bool,      // Held: pass fuel to the nearest rocket
+}
+
+/// Something the host player should see or feel, queued for MultiplayerHost (see `take_events`)
+#[derive(Debug, Clone)]
+pub enum HostSessionEvent {
+    Toast(String, Color),
+    Chat(ChatMessage),                   // A line for the chat box (ours, or relayed from a client)
+    Staged(Vec2),                        // The host deployed a satellite here
+    Hit(HitEvent),                       // A rocket was struck (anyone's)
+    Landed(LandingEvent),                // The host's rocket touched down
+    Destroyed(DestroyedRocketInfo),      // A rocket was lost (anyone's; its pilot already has a new one)
+    KillCam { rocket_id: EntityId, shot: KillShot, impact: Vec2, attacker: String }, // The host was shot down
+    IncomingGift(Option<(u32, u32, EntityId)>), // Offer, giver and satellite offered to the host (None: it lapsed)
+    QuickSave(u32),                      // A player's quick save passed the save rules; write it
+    OriginShift(Vec2),                   // The floating origin moved; shift anything kept in local coordinates
+}
+
+struct ConnectedClient {
+    addr: SocketAddr,
+    player_id: u32,
+    last_seen: f64, // Timestamp of last received packet
+    player_name: String, // Player's chosen name
+    link: LinkQuality, // Measured from snapshot acks; picks this client's snapshot rate
+    checksum_due: bool, // Send a checksum with this client's next snapshot
+    delta: DeltaEncoder, // Diffs this client's snapshots against the last one it acked
+}
+
+pub struct HostSession {
+    world: World,
+    active_rocket_id: Option<EntityId>, // The host's own rocket (player 0)
+    clock: Box<dyn Clock>,
+    events: Vec<HostSessionEvent>,
+    fuel_transfers: Vec<FuelTransferEvent>, // Last tick's transfers, for the beam effects
+
+    // Networking
+    socket: UdpSocket,
+    clients: Arc<Mutex<HashMap<SocketAddr, ConnectedClient>>>,
+    snapshot_timer: f32,
+    session_time: f32, // Simulated seconds, stamped into snapshots so checksums can name one
+    last_checksum_time: f32,
+    rates: HostRates, // Physics tick rate and snapshot broadcast rate
+    arena_rule: Option<ArenaConfig>, // Wraparound arena size from --arena (None = open space)
+    hardcore_saves: bool, // Saving needs the saver's rocket in satellite range or landed (--hardcore)
+    map: Option<MapConfiguration>, // Map the world is built from (None = the classic Earth/Moon layout)
+    spawn_planet_id: Option<EntityId>, // Body players spawn around (None = the classic main planet)
+    next_player_id: u32, // Next available player ID for new clients
+    player_names: HashMap<u32, String>, // Map player IDs to player names
+    crew: CrewAssignments, // Engineers aboard other players' rockets
+    spectators: HashSet<u32>, // Clients that joined to watch: never given a rocket
+    recorder: SessionRecorder, // Inputs and keyframes for the post-match replay
+    kicked_addrs: HashSet<SocketAddr>, // Ignored for the rest of the session
+    desync_dumps: u32, // World states written for clients' desync reports this session
+    left_addrs: HashMap<SocketAddr, f64>, // Said goodbye (when); stragglers from them aren't taken as joins
+    banned_ips: HashSet<IpAddr>, // Every port from these is ignored for the rest of the session
+    max_players: u32, // Host plus clients; joins past this are turned away
+    #[cfg(feature = "sqlite")]
+    world_db: Option<WorldDatabase>, // Snapshot/stats archive opened with --world-db
+    #[cfg(feature = "sqlite")]
+    world_db_timer: f32, // Seconds since the last archived snapshot
+    lag_compensation: LagCompensation, // Recent craft positions, to check client shots where the shooter aimed
+
+    // Refueling requests from clients
+    refueling_rockets: HashSet<EntityId>, // Rockets that are currently requesting planet refuel
+    grounded_rockets: HashSet<EntityId>, // Rockets landed last tick (one leaving the ground is logged as a launch)
+    transferring_rockets: HashSet<EntityId>, // Rockets passing fuel to the nearest rocket (host's included)
+    host_transfer: Option<(EntityId, Option<EntityId>, f32)>, // The host's held transfer last tick: donor, recipient, fuel moved
+    station_ops: StationOps, // Landed rockets refuelling themselves, everyone's (6)
+
+    // Satellite gifts between players (offers wait for the recipient to accept)
+    transfer_offers: TransferOffers,
+
+    // Everyone's waypoints (each client is sent its own plus shared ones)
+    waypoints: Waypoints,
+}
+
+impl HostSession {
+    /// Serve clients on `socket` (switched to non-blocking), reading link timing and timeouts from `clock`
+    pub fn new(socket: UdpSocket, player_name: String, clock: Box<dyn Clock>) -> Result<Self, String> {
+        // Set non-blocking mode so we don't freeze waiting for packets
+        socket.set_nonblocking(true)
+            .map_err(|e| format!("Failed to set non-blocking mode: {}", e))?;
+
+        // Initialize player names map with host's name
+        let mut player_names = HashMap::new();
+        player_names.insert(0, player_name);
+
+        let rates = HostRates::default();
+        Ok(Self {
+            world: World::new(),
+            active_rocket_id: None,
+            clock,
+            events: Vec::new(),
+            fuel_transfers: Vec::new(),
+
+            socket,
+            clients: Arc::new(Mutex::new(HashMap::new())),
+            snapshot_timer: 0.0,
+            session_time: 0.0,
+            last_checksum_time: 0.0,
+            rates,
+            arena_rule: None,
+            hardcore_saves: false,
+            map: None,
+            spawn_planet_id: None,
+            next_player_id: 1, // Host is player 0, clients start at 1
+            player_names,
+            crew: CrewAssignments::new(),
+            spectators: HashSet::new(),
+            recorder: SessionRecorder::new(rates.physics_hz()),
+            kicked_addrs: HashSet::new(),
+            desync_dumps: 0,
+            left_addrs: HashMap::new(),
+            banned_ips: HashSet::new(),
+            max_players: MAX_PLAYERS,
+            #[cfg(feature = "sqlite")]
+            world_db: None,
+            #[cfg(feature = "sqlite")]
+            world_db_timer: 0.0,
+            lag_compensation: LagCompensation::new(),
+
+            refueling_rockets: HashSet::new(),
+            grounded_rockets: HashSet::new(),
+            transferring_rockets: HashSet::new(),
+            host_transfer: None,
+            station_ops: StationOps::new(),
+
+            transfer_offers: TransferOffers::new(),
+            waypoints: Waypoints::new(),
+        })
+    }
+
+    /// Calculate spawn position for a player based on their player ID
+    /// Host (player 0) spawns at 0 degrees, each subsequent player at +5 degrees
+    fn calculate_spawn_position(&self, player_id: u32) -> Vec2 {
+        let angle_degrees = player_id as f32 * 5.0;
+        let angle_radians = angle_degrees.to_radians();
+        if let Some(planet) = self.spawn_planet() {
+            let spawn_distance = planet.surface_radius_at(angle_radians) + self.tunables().spawn_altitude();
+            return planet.position() + Vec2::from_angle(angle_radians) * spawn_distance;
+        }
+        let spawn_distance = GameConstants::MAIN_PLANET_RADIUS + 200.0;
+
+        let spawn = DVec2::new(
+            (GameConstants::MAIN_PLANET_X + spawn_distance * angle_radians.cos()) as f64,
+            (GameConstants::MAIN_PLANET_Y + spawn_distance * angle_radians.sin()) as f64,
+        );
+        self.world.origin().to_local(spawn)
+    }
+
+    /// The map's spawn body, if the world was built from a map
+    fn spawn_planet(&self) -> Option<&Planet> {
+        self.spawn_planet_id.and_then(|id| self.world.get_planet(id))
+    }
+
+    /// The map's rocket and bullet overrides (defaults for the classic layout)
+    fn tunables(&self) -> MapTunables {
+        self.map.as_ref().map(|map| map.tunables.clone()).unwrap_or_default()
+    }
+
+    /// A fresh rocket for a player at their spawn point, moving with the spawn body
+    fn new_player_rocket(&self, player_id: u32) -> Rocket {
+        let velocity = self.spawn_planet().map(|planet| planet.velocity()).unwrap_or(Vec2::ZERO);
+        let tunables = self.tunables();
+        let mut rocket = Rocket::new(
+            self.calculate_spawn_position(player_id),
+            velocity,
+            crate::game_constants::colors::player_color(player_id),
+            tunables.rocket_base_mass(),
+        );
+        tunables.apply_to_rocket(&mut rocket);
+        rocket.set_player_id(Some(player_id));
+        rocket
+    }
+
+    /// Average position of every rocket (the floating origin follows it)
+    fn rockets_centroid(&self) -> Option<Vec2> {
+        let count = self.world.rocket_count();
+        if count == 0 {
+            return None;
+        }
+        Some(self.world.rockets().map(|rocket| rocket.position()).sum::<Vec2>() / count as f32)
+    }
+
+    /// Build new games from `map` instead of the classic Earth/Moon layout (None goes back to it)
+    pub fn set_map(&mut self, map: Option<MapConfiguration>) {
+        self.map = map;
+    }
+
+    pub fn map(&self) -> Option<&MapConfiguration> {
+        self.map.as_ref()
+    }
+
+    /// Name of the map new games use
+    pub fn map_name(&self) -> String {
+        self.map.as_ref().map(|map| map.name.clone()).unwrap_or_else(|| "classic Earth/Moon".to_string())
+    }
+
+    /// Initialize a new game with default starting conditions
+    pub fn initialize_new_game(&mut self) {
+        log::info!("Initializing new multiplayer host game on {}", self.map_name());
+
+        self.world.clear_all_entities();
+        self.lag_compensation.clear();
+        self.spawn_planet_id = None;
+        self.apply_map_physics();
+
+        if let Some(map) = self.map.clone() {
+            self.add_map_planets(&map);
+        } else {
+            self.add_classic_planets();
+        }
+        self.world.set_arena(self.arena_from_rule().or_else(|| self.map.as_ref().and_then(MapConfiguration::arena_bounds)));
+        self.world.set_cosmetic_seed(crate::ui::Sky::fresh_seed());
+
+        // Spawn host's rocket (player 0) at 0 degrees
+        let rocket = self.new_player_rocket(0);
+        let rocket_id = self.world.add_rocket(rocket);
+        self.active_rocket_id = Some(rocket_id);
+        self.world.set_active_rocket(Some(rocket_id));
+
+        log::info!("Multiplayer host game initialized - waiting for clients");
+    }
+
+    /// Every body of the map at its starting orbit; players spawn around the map's spawn body
+    fn add_map_planets(&mut self, map: &MapConfiguration) {
+        let initial_states = orbit_calculator::calculate_initial_states(map, GameConstants::G);
+        for (i, (body_config, state)) in map.celestial_bodies.iter().zip(&initial_states).enumerate() {
+            let mut planet = Planet::new(state.position, body_config.radius, body_config.mass, body_config.color);
+            planet.set_velocity(state.velocity);
+            planet.set_name(body_config.name.clone());
+            planet.set_pinned(body_config.is_pinned);
+            planet.set_terrain(body_config.terrain.as_ref().map(|t| t.to_terrain()));
+            let planet_id = self.world.add_planet(planet);
+            if i == map.player_spawn_body_index {
+                self.spawn_planet_id = Some(planet_id);
+            }
+        }
+    }
+
+    /// Gravity model, bullet speed, rings and tidal rules of the map (defaults for the classic layout)
+    fn apply_map_physics(&mut self) {
+        let gravity_model = self.map.as_ref().map(|map| map.gravity_model).unwrap_or_default();
+        self.world.gravity_simulator_mut().set_model(gravity_model);
+        self.world.set_bullet_speed(self.tunables().bullet_speed());
+        self.world.set_rings(self.map.as_ref().map(PlanetRings::from_map).unwrap_or_default());
+        let mut tidal_rules = self.world.tidal_rules();
+        tidal_rules.enabled = self.map.as_ref().is_some_and(|map| map.tidal_breakup);
+        self.world.set_tidal_rules(tidal_rules);
+    }
+
+    /// The original two-body world: Earth with the Moon in orbit
+    fn add_classic_planets(&mut self) {
+        // Create main planet (Earth)
+        let main_planet = Planet::new(
+            Vec2::new(GameConstants::MAIN_PLANET_X, GameConstants::MAIN_PLANET_Y),
+            GameConstants::MAIN_PLANET_RADIUS,
+            GameConstants::MAIN_PLANET_MASS,
+            BLUE,
+        );
+        self.world.add_planet(main_planet);
+
+        // Create secondary planet (Moon) - match single player configuration
+        let moon_x = *crate::game_constants::SECONDARY_PLANET_X;
+        let moon_y = *crate::game_constants::SECONDARY_PLANET_Y;
+        let moon_radius = GameConstants::SECONDARY_PLANET_RADIUS;
+        let moon_velocity = *crate::game_constants::SECONDARY_PLANET_ORBITAL_VELOCITY;
+
+        let mut secondary_planet = Planet::new(
+            Vec2::new(moon_x, moon_y),
+            moon_radius,
+            GameConstants::SECONDARY_PLANET_MASS,
+            Color::from_rgba(150, 150, 150, 255),
+        );
+        secondary_planet.set_velocity(Vec2::new(0.0, -moon_velocity));
+        self.world.add_planet(secondary_planet);
+    }
+
+    /// Take the world, map, session time and waypoints from a save (the host's camera and panels are MultiplayerHost's)
+    pub fn load_from_save(&mut self, save_data: GameSaveData) {
+        // Clear existing world (positions below are relative to the saved floating origin)
+        self.world.clear_all_entities();
+        self.world.set_origin(save_data.origin.into());
+        self.lag_compensation.clear();
+
+        // Load planets with their original IDs
+        for saved_planet in save_data.planets {
+            let (id, planet) = saved_planet.to_planet();
+            self.world.add_planet_with_id(id, planet);
+        }
+
+        // Saves name their map; its physics apply again and players spawn around its spawn body
+        // (planets keep the map's order, so the spawn body is the one at the spawn index)
+        self.map = save_data.map_name.as_ref().and_then(|name| {
+            let map = MapConfiguration::all_maps().into_iter().find(|map| &map.name == name);
+            if map.is_none() {
+                log::warn!("Map '{}' not found, using the classic physics", name);
+            }
+            map
+        });
+        self.apply_map_physics();
+        self.spawn_planet_id = self
+            .map
+            .as_ref()
+            .and_then(|map| self.world.planets_with_ids().nth(map.player_spawn_body_index))
+            .map(|(id, _)| id);
+
+        // A saved arena keeps its place; otherwise --arena applies around the loaded main planet
+        self.world.set_arena(save_data.arena.or_else(|| self.arena_from_rule()));
+        self.world.set_cosmetic_seed(save_data.cosmetic_seed);
+
+        // Load rockets with their original IDs
+        for saved_rocket in save_data.rockets {
+            let (id, rocket) = saved_rocket.to_rocket();
+            self.world.add_rocket_with_id(id, rocket);
+        }
+
+        // Load satellites with their original IDs
+        for saved_satellite in save_data.satellites {
+            let (id, satellite) = saved_satellite.to_satellite();
+            self.world.add_satellite_with_id(id, satellite);
+        }
+
+        // Load bullets with their original IDs
+        for saved_bullet in save_data.bullets {
+            let (id, bullet) = saved_bullet.to_bullet();
+            self.world.add_bullet_with_id(id, bullet);
+        }
+
+        // Restore active rocket
+        self.active_rocket_id = save_data.active_rocket_id;
+        self.world.set_active_rocket(save_data.active_rocket_id);
+
+        self.session_time = save_data.game_time;
+        self.last_checksum_time = save_data.game_time;
+        self.waypoints = Waypoints::from_list(save_data.waypoints.iter().map(SavedWaypoint::to_waypoint).collect());
+    }
+
+    /// Tell clients about a world that just replaced the old one and give everyone a rocket in it
+    pub fn world_changed(&mut self) {
+        self.broadcast_map();
+        self.refueling_rockets.clear();
+        self.transferring_rockets.clear();
+        self.station_ops.clear();
+        self.respawn_missing_players();
+        self.recorder.request_keyframe();
+    }
+
+    /// Everything MultiplayerHost shows or plays since the last call
+    pub fn take_events(&mut self) -> Vec<HostSessionEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Fuel transfers during the last tick (manual and automatic), for the beam effects
+    pub fn take_fuel_transfers(&mut self) -> Vec<FuelTransferEvent> {
+        std::mem::take(&mut self.fuel_transfers)
+    }
+
+    fn toast(&mut self, text: impl Into<String>, color: Color) {
+        self.events.push(HostSessionEvent::Toast(text.into(), color));
+    }
+
+    /// Waypoint edits from a client (they can only remove their own)
+    fn handle_waypoint_packet(&mut self, player_id: u32, packet: WaypointPacket) {
+        match packet {
+            WaypointPacket::Add { x, y, shared } => {
+                let position = self.world.origin().to_local(DVec2::new(x, y));
+                let id = self.waypoints.add(position, Some(player_id), shared);
+                log::info!("Player {} dropped waypoint {}", player_id, id);
+            }
+            WaypointPacket::Remove { id } => {
+                if !self.waypoints.remove(id, Some(player_id)) {
+                    log::warn!("Player {} tried to remove waypoint {} they don't own", player_id, id);
+                }
+            }
+        }
+    }
+
+    /// Waypoints as sent to one player
+    fn saved_waypoints_for(&self, player_id: u32) -> Vec<SavedWaypoint> {
+        self.waypoints.visible_to(Some(player_id)).iter().map(SavedWaypoint::from_waypoint).collect()
+    }
+
+    pub fn player_label(&self, player_id: u32) -> String {
+        self.player_names.get(&player_id).cloned().unwrap_or_else(|| format!("Player {}", player_id))
+    }
+
+    /// Hit markers for a client's shots (a hit event packet); everything else about the hit is MultiplayerHost's
+    fn report_hits(&mut self) {
+        for hit in self.world.take_hit_events() {
+            self.events.push(HostSessionEvent::Hit(hit));
+            let Some(shooter) = hit.shooter_player.filter(|shooter| Some(*shooter) != hit.player_id && *shooter != 0) else {
+                continue;
+            };
+            if let Some(addr) = self.client_addr(shooter) {
+                let packet = HitEventPacket { rocket_id: hit.rocket_id, position: (hit.position.x, hit.position.y) };
+                if let Err(e) = self.socket.send_to(&packet.to_bytes(), addr) {
+                    log::warn!("Failed to send hit event to {}: {}", addr, e);
+                }
+            }
+        }
+    }
+
+    /// Kill cam for a bullet kill: played by MultiplayerHost for the host, sent to the victim otherwise
+    fn report_kill_shot(&mut self, destroyed: &DestroyedRocketInfo, player_id: u32) {
+        let Some(shot) = destroyed.kill_shot else {
+            return;
+        };
+        let attacker = match shot.shooter_player {
+            Some(shooter) if shooter == player_id => "your own bullet".to_string(),
+            Some(shooter) => self.player_label(shooter),
+            None => "a stray bullet".to_string(),
+        };
+        if player_id == 0 {
+            self.events.push(HostSessionEvent::KillCam { rocket_id: destroyed.rocket_id, shot, impact: destroyed.position, attacker });
+            return;
+        }
+        let Some(addr) = self.client_addr(player_id) else {
+            return;
+        };
+        let notice = ServerNotice::ShotDown {
+            rocket_id: destroyed.rocket_id,
+            bullet_id: shot.bullet_id,
+            shooter_id: shot.shooter_id,
+            attacker,
+            impact: (destroyed.position.x, destroyed.position.y),
+        };
+        if let Err(e) = self.socket.send_to(&notice.to_bytes(), addr) {
+            log::warn!("Failed to send kill cam notice to {}: {}", addr, e);
+        }
+    }
+
+    pub fn client_addr(&self, player_id: u32) -> Option<SocketAddr> {
+        let clients = self.clients.lock().unwrap();
+        clients.values().find(|client| client.player_id == player_id).map(|client| client.addr)
+    }
+
+    /// Smoothed round trip to a player's client (0 for the host's own crew)
+    fn client_rtt(&self, player_id: u32) -> f32 {
+        let clients = self.clients.lock().unwrap();
+        clients.values().find(|client| client.player_id == player_id).map_or(0.0, |client| client.link.rtt())
+    }
+
+    fn send_ownership_packet(&self, player_id: u32, packet: &OwnershipPacket) {
+        if let Some(addr) = self.client_addr(player_id) {
+            if let Err(e) = self.socket.send_to(&packet.to_bytes(), addr) {
+                log::warn!("Failed to send ownership packet to {}: {}", addr, e);
+            }
+        }
+    }
+
+    /// Start a gift: check the giver owns the satellite, then ask the recipient
+    pub fn offer_satellite(&mut self, satellite_id: EntityId, from: u32, to: u32) {
+        match self.transfer_offers.offer(&self.world, satellite_id, from, to, self.clock.now()) {
+            Ok(offer_id) => {
+                log::info!("Player {} offered satellite {} to player {}", from, satellite_id, to);
+                if to == 0 {
+                    self.events.push(HostSessionEvent::IncomingGift(Some((offer_id, from, satellite_id))));
+                } else {
+                    self.send_ownership_packet(to, &OwnershipPacket::Offered { offer_id, from_player: from, satellite_id });
+                }
+                if from == 0 {
+                    self.toast(format!("Offered satellite {} to {}", satellite_id, self.player_label(to)), SKYBLUE);
+                }
+            }
+            Err(e) => {
+                if from == 0 {
+                    self.toast(e.message(), ORANGE);
+                } else {
+                    self.send_ownership_packet(from, &OwnershipPacket::Resolved {
+                        satellite_id,
+                        from_player: from,
+                        to_player: to,
+                        accepted: false,
+                    });
+                }
+            }
+        }
+    }
+
+    /// The recipient answered: hand the satellite over if they accepted and the giver still owns it
+    pub fn resolve_offer(&mut self, offer_id: u32, responder: u32, accept: bool) {
+        let Some(offer) = self.transfer_offers.respond(offer_id, responder) else {
+            return;
+        };
+        let accepted = accept
+            && ownership::transfer_satellite(&mut self.world, offer.satellite_id, offer.from_player, offer.to_player).is_ok();
+        if accepted {
+            log::info!("Satellite {} now belongs to player {}", offer.satellite_id, offer.to_player);
+        }
+        self.notify_resolved(offer, accepted);
+    }
+
+    fn notify_resolved(&mut self, offer: TransferOffer, accepted: bool) {
+        for player_id in [offer.from_player, offer.to_player] {
+            if player_id == 0 {
+                let text = ownership::resolution_text(
+                    offer.satellite_id,
+                    offer.from_player,
+                    offer.to_player,
+                    accepted,
+                    0,
+                    |id| self.player_label(id),
+                );
+                self.toast(text, if accepted { GREEN } else { ORANGE });
+            } else {
+                self.send_ownership_packet(player_id, &OwnershipPacket::Resolved {
+                    satellite_id: offer.satellite_id,
+                    from_player: offer.from_player,
+                    to_player: offer.to_player,
+                    accepted,
+                });
+            }
+        }
+    }
+
+    /// Fly the host's rocket for a frame (conversion, weapons and retrieval belong to our engineer if we have one)
+    pub fn fly_host(&mut self, controls: HostControls) {
+        let Some(rocket_id) = self.active_rocket_id else {
+            return;
+        };
+        if controls.rotation_delta != 0.0 {
+            let rotation_radians = controls.rotation_delta * std::f32::consts::PI / 180.0;
+            if let Some(rocket) = self.world.get_rocket_mut(rocket_id) {
+                rocket.rotate(rotation_radians);
+            }
+        }
+        if let Some(rocket) = self.world.get_rocket_mut(rocket_id) {
+            rocket.set_thrust_level(controls.thrust_level);
+        }
+        self.recorder.record(
+            self.session_time,
+            0,
+            RecordedAction::Steer { rotation_delta: controls.rotation_delta, thrust_level: controls.thrust_level },
+        );
+
+        let controls_systems = self.crew.controls_systems(0);
+
+        if controls.abort {
+            self.world.abort_rocket(rocket_id);
+        }
+
+        // Retrieve the nearest retired satellite
+        if controls.retrieve_satellite && controls_systems {
+            match self.world.retrieve_nearest_satellite(rocket_id) {
+                Ok((satellite_id, refund)) => self.toast(format!("Retrieved satellite {} (+{:.0} fuel)", satellite_id, refund), GREEN),
+                Err(e) => self.toast(e, LIGHTGRAY),
+            }
+        }
+
+        if controls.convert_to_satellite && controls_systems {
+            if let Some(satellite_id) = self.world.convert_rocket_to_satellite(rocket_id) {
+                log::info!("Host converted rocket to satellite");
+                if let Some(position) = self.world.get_satellite(satellite_id).map(|satellite| satellite.position()) {
+                    self.events.push(HostSessionEvent::Staged(position));
+                    let description = format!("{} deployed satellite {}", self.player_label(0), satellite_id);
+                    self.record_event(SessionEventKind::Conversion, description, position);
+                }
+                self.recorder.record(self.session_time, 0, RecordedAction::Convert);
+                #[cfg(feature = "sqlite")]
+                self.archive_stat(0, PlayerStat::SatellitesLaunched);
+
+                // Spawn new rocket for host at 0 degrees
+                let new_rocket = self.new_player_rocket(0);
+                let new_rocket_id = self.world.add_rocket(new_rocket);
+                self.active_rocket_id = Some(new_rocket_id);
+                self.world.set_active_rocket(Some(new_rocket_id));
+                self.recorder.request_keyframe();
+                log::info!("Host respawned new rocket");
+                return;
+            }
+        }
+
+        if controls.shoot_bullet && controls_systems {
+            if let Some(bullet_id) = self.world.shoot_bullet_from_rocket(rocket_id) {
+                log::debug!("Bullet {} fired from rocket {}", bullet_id, rocket_id);
+                self.recorder.record(self.session_time, 0, RecordedAction::Shoot);
+                #[cfg(feature = "sqlite")]
+                self.archive_stat(0, PlayerStat::ShotsFired);
+            } else {
+                log::info!("Cannot shoot: not enough fuel (need 1 unit)");
+            }
+        }
+    }
+
+    /// Apply client input to their rocket (engineers act on the rocket they crew)
+    fn apply_client_input(&mut self, input: ClientInputPacket) {
+        // Find the rocket that belongs to this player, or to the pilot an engineer crews for
+        let owner_id = self.crew.rocket_owner(input.player_id);
+        let mut rocket_id: Option<EntityId> = None;
+        for (id, rocket) in self.world.rockets_with_ids() {
+            if rocket.player_id() == Some(owner_id) {
+                rocket_id = Some(id);
+                break;
+            }
+        }
+
+        if let Some(rid) = rocket_id {
+            // Attitude and thrust come from the pilot only
+            if self.crew.controls_flight(input.player_id) {
+                // Apply rotation
+                if input.rotation_delta != 0.0 {
+                    let rotation_radians = input.rotation_delta * std::f32::consts::PI / 180.0;
+                    if let Some(rocket) = self.world.get_rocket_mut(rid) {
+                        rocket.rotate(rotation_radians);
+                    }
+                }
+
+                // Apply thrust
+                if let Some(rocket) = self.world.get_rocket_mut(rid) {
+                    rocket.set_thrust_level(input.thrust_level);
+                }
+                self.recorder.record(
+                    self.session_time,
+                    owner_id,
+                    RecordedAction::Steer { rotation_delta: input.rotation_delta, thrust_level: input.thrust_level },
+                );
+            }
+
+            // Quick save if requested (F5 key) - anyone aboard can save
+            if input.save_requested {
+                self.request_quick_save(input.player_id);
+            }
+
+            // Conversion, weapons and refueling belong to the engineer when the rocket has one
+            if !self.crew.controls_systems(input.player_id) {
+                return;
+            }
+
+            // Convert to satellite if requested
+            if input.convert_to_satellite {
+                if let Some(satellite_id) = self.world.convert_rocket_to_satellite(rid) {
+                    log::info!("Player {} converted player {}'s rocket to satellite", input.player_id, owner_id);
+                    if let Some(position) = self.world.get_satellite(satellite_id).map(|satellite| satellite.position()) {
+                        let description = format!("{} deployed satellite {}", self.player_label(owner_id), satellite_id);
+                        self.record_event(SessionEventKind::Conversion, description, position);
+                    }
+                    self.recorder.record(self.session_time, owner_id, RecordedAction::Convert);
+                    #[cfg(feature = "sqlite")]
+                    self.archive_stat(input.player_id, PlayerStat::SatellitesLaunched);
+                    self.refueling_rockets.remove(&rid);
+                    self.transferring_rockets.remove(&rid);
+
+                    // Spawn new rocket for the pilot (the host's crew converts the host's rocket)
+                    let new_rocket = self.new_player_rocket(owner_id);
+                    let new_rocket_id = self.world.add_rocket(new_rocket);
+                    if owner_id == 0 {
+                        self.active_rocket_id = Some(new_rocket_id);
+                        self.world.set_active_rocket(Some(new_rocket_id));
+                    }
+                    self.recorder.request_keyframe();
+                    log::info!("Respawned new rocket for player {}", owner_id);
+                    return;
+                }
+            }
+
+            // Shoot bullet if requested
+            if input.shoot_bullet {
+                if let Some(bullet_id) = self.world.shoot_bullet_from_rocket(rid) {
+                    log::debug!("Player {} fired bullet {}", input.player_id, bullet_id);
+                    self.recorder.record(self.session_time, owner_id, RecordedAction::Shoot);
+                    // The shooter aimed at where its screen showed things a round trip ago
+                    let lag = LagCompensation::shot_lag(self.client_rtt(input.player_id));
+                    if let Some(target) = self.lag_compensation.resolve_shot(&mut self.world, bullet_id, lag, self.session_time) {
+                        log::debug!("Player {}'s shot hit {:?} {:.0} ms back", input.player_id, target, lag * 1000.0);
+                        self.recorder.request_keyframe(); // Replays fire without the rewind, so pin the outcome
+                    }
+                    #[cfg(feature = "sqlite")]
+                    self.archive_stat(input.player_id, PlayerStat::ShotsFired);
+                } else {
+                    log::debug!("Player {} cannot shoot: not enough fuel", input.player_id);
+                }
+            }
+
+            // Refuel from planet if requested (R key)
+            self.recorder.record(self.session_time, owner_id, RecordedAction::Refueling(input.refuel_from_planet));
+            if input.refuel_from_planet {
+                self.refueling_rockets.insert(rid);
+            } else {
+                self.refueling_rockets.remove(&rid);
+            }
+
+            // Station ops hold on the pad (6)
+            if input.station_ops {
+                self.cycle_station_hold(rid, owner_id);
+            }
+
+            // Pass fuel to the nearest rocket while the transfer key is held (the host picks the recipient)
+            self.recorder.record(self.session_time, owner_id, RecordedAction::TransferringFuel(input.transfer_fuel));
+            if input.transfer_fuel {
+                self.transferring_rockets.insert(rid);
+            } else {
+                self.transferring_rockets.remove(&rid);
+            }
+        }
+    }
+
+    /// Step a rocket's station ops hold and tell its pilot the new level
+    fn cycle_station_hold(&mut self, rocket_id: EntityId, owner_id: u32) {
+        let text = match self.station_ops.cycle(&self.world, rocket_id) {
+            Ok(Some(level)) => format!("Station ops: refueling to {:.0}%", level * 100.0),
+            Ok(None) => "Station ops off".to_string(),
+            Err(e) => e,
+        };
+        self.recorder.request_keyframe(); // Replays don't run the holds, so pin the fuel they move
+        self.tell_pilot(owner_id, text, SKYBLUE);
+    }
+
+    /// Held rockets' news goes to whoever flies them
+    fn report_station_event(&mut self, event: StationEvent) {
+        let (rocket_id, text, color) = match event {
+            StationEvent::Ready { rocket, fuel } => (rocket, format!("Rocket {} refueled to {:.0} and ready", rocket, fuel), GREEN),
+            StationEvent::PlanetDry { rocket, fuel } => {
+                (rocket, format!("Rocket {}'s planet is tapped out ({:.0} fuel aboard)", rocket, fuel), ORANGE)
+            }
+            StationEvent::Released { rocket } => (rocket, format!("Rocket {} left station ops", rocket), LIGHTGRAY),
+        };
+        self.recorder.request_keyframe();
+        let owner = self.world.get_rocket(rocket_id).and_then(|rocket| rocket.player_id());
+        if let Some(owner_id) = owner {
+            self.tell_pilot(owner_id, text, color);
+        }
+    }
+
+    /// A toast for the host (player 0), a station notice for a client
+    fn tell_pilot(&mut self, player_id: u32, text: String, color: Color) {
+        if player_id == 0 {
+            self.toast(text, color);
+            return;
+        }
+        if let Some(addr) = self.client_addr(player_id) {
+            if let Err(e) = self.socket.send_to(&ServerNotice::Station(text).to_bytes(), addr) {
+                log::warn!("Failed to send station notice to {}: {}", addr, e);
+            }
+        }
+    }
+
+    /// Play in a wraparound arena of this size, centered on the main planet
+    pub fn set_arena(&mut self, arena: Option<ArenaConfig>) {
+        self.arena_rule = arena;
+    }
+
+    pub fn arena_rule(&self) -> Option<ArenaConfig> {
+        self.arena_rule
+    }
+
+    /// The arena rule's bounds around the most massive planet now in the world
+    fn arena_from_rule(&self) -> Option<ArenaBounds> {
+        let rule = self.arena_rule?;
+        let main_planet = self
+            .world
+            .planets()
+            .max_by(|a, b| a.mass().partial_cmp(&b.mass()).unwrap_or(std::cmp::Ordering::Equal))?;
+        Some(rule.bounds_around(self.world.origin().to_world(main_planet.position())))
+    }
+
+    /// Set the rocket-rocket / rocket-satellite collision rules for this session
+    pub fn set_collision_rules(&mut self, rules: CollisionRules) {
+        self.world.set_collision_rules(rules);
+        self.recorder.set_collision_rules(rules);
+    }
+
+    /// Hardcore rule: a player may only save from a rocket within reach of the satellite network or landed
+    pub fn set_hardcore_saves(&mut self, hardcore: bool) {
+        self.hardcore_saves = hardcore;
+    }
+
+    /// Take client packets, step the world and send clients what changed
+    pub fn update(&mut self, delta_time: f32, keys: HostFuelKeys) {
+        self.session_time += delta_time;
+
+        // Receive any incoming packets from clients
+        self.receive_client_packets();
+
+        // Manual planet refueling for the host (one step per R press) - BEFORE world update
+        let manual_refuel_active = match self.active_rocket_id {
+            Some(rocket_id) if keys.refuel_from_planet && self.crew.controls_systems(0) => {
+                self.world.handle_manual_planet_refuel(rocket_id, delta_time);
+                self.recorder.record(self.session_time, 0, RecordedAction::RefuelOnce);
+                true
+            }
+            _ => false,
+        };
+
+        // Station ops: 6 steps the host's hold, then every held rocket tops up from its planet
+        if let Some(rocket_id) = self.active_rocket_id {
+            if keys.station_ops && self.crew.controls_systems(0) {
+                self.cycle_station_hold(rocket_id, 0);
+            }
+        }
+        for event in self.station_ops.update(&mut self.world, delta_time) {
+            self.report_station_event(event);
+        }
+
+        // Rocket-to-rocket transfers (the host's held key, held keys in client packets)
+        if let Some(rocket_id) = self.active_rocket_id {
+            let held = keys.transfer_fuel && self.crew.controls_systems(0);
+            self.recorder.record(self.session_time, 0, RecordedAction::TransferringFuel(held));
+            if held {
+                self.transferring_rockets.insert(rocket_id);
+            } else {
+                self.transferring_rockets.remove(&rocket_id);
+            }
+        }
+        self.transferring_rockets.retain(|rocket_id| self.world.get_rocket(*rocket_id).is_some());
+        self.host_transfer = None;
+        for donor_id in self.transferring_rockets.clone() {
+            let recipient_id = self.world.nearest_rocket_in_transfer_range(donor_id);
+            let moved = recipient_id
+                .and_then(|recipient_id| self.world.transfer_fuel_rocket_to_rocket(donor_id, recipient_id, delta_time).ok())
+                .unwrap_or(0.0);
+            if Some(donor_id) == self.active_rocket_id {
+                self.host_transfer = Some((donor_id, recipient_id, moved));
+            }
+        }
+
+        // Update physics
+        self.world.update(delta_time, manual_refuel_active);
+        self.broadcast_bullet_removals();
+        self.report_hits();
+
+        // Keep coordinates small around the players on big maps (snapshots carry the origin to clients)
+        if let Some(focus) = self.rockets_centroid() {
+            if let Some(shift) = self.world.rebase_around(focus) {
+                self.waypoints.shift_positions(shift);
+                self.lag_compensation.shift_positions(shift);
+                self.events.push(HostSessionEvent::OriginShift(shift));
+            }
+        }
+        self.lag_compensation.record(&self.world, self.session_time);
+
+        // Handle manual planet refueling for clients
+        for rocket_id in &self.refueling_rockets {
+            self.world.handle_manual_planet_refuel(*rocket_id, delta_time);
+        }
+
+        // This tick's fuel transfers (manual + automatic) go into the recording and the beam effects
+        let transfer_events = self.world.take_fuel_transfer_events();
+        for event in &transfer_events {
+            if let Some(position) = event.target_position(&self.world) {
+                let world_position = self.world.origin().to_world(position);
+                self.recorder.record_transfer(self.session_time, (event.source_id, event.target_id), event.description(), world_position);
+            }
+        }
+        self.fuel_transfers = transfer_events;
+        self.record_launches();
+
+        // Log touchdowns; the host's own are graded on its screen
+        for event in self.world.take_landing_events() {
+            #[cfg(feature = "sqlite")]
+            if let Some(player_id) = self.world.get_rocket(event.rocket_id).and_then(|rocket| rocket.player_id()) {
+                self.archive_stat(player_id, PlayerStat::Landings);
+            }
+            let planet_name = event.planet_name.as_deref().unwrap_or("Unknown");
+            if let Some(rocket) = self.world.get_rocket(event.rocket_id) {
+                let pilot = self.player_label(rocket.player_id().unwrap_or(0));
+                let description = format!("{} landed on {} ({})", pilot, planet_name, event.result.grade.label());
+                let position = rocket.position();
+                self.record_event(SessionEventKind::Landing, description, position);
+            }
+            if Some(event.rocket_id) == self.active_rocket_id {
+                self.events.push(HostSessionEvent::Landed(event));
+            }
+        }
+
+        // Gift offers nobody answered lapse
+        for offer in self.transfer_offers.expire(self.clock.now()) {
+            if offer.to_player == 0 {
+                self.events.push(HostSessionEvent::IncomingGift(None));
+            }
+            self.notify_resolved(offer, false);
+        }
+
+        // Satellites and bullets that drifted out of the system are gone for good
+        let drifted = world_bounds::remove_lost_entities(&mut self.world, &BoundsConfig::default());
+        if drifted > 0 {
+            self.toast(format!("{} satellite(s) drifted out of the system", drifted), LIGHTGRAY);
+        }
+
+        // Worn-out satellites (only ours, or unowned ones, get a toast)
+        for event in self.world.take_satellite_life_events() {
+            let ours = self.world.get_satellite(event.satellite_id()).is_some_and(|satellite| satellite.owner().unwrap_or(0) == 0);
+            if ours {
+                let (text, color) = event.toast();
+                self.toast(text, color);
+            }
+        }
+
+        // Handle rockets destroyed by bullets (respawn like 'C' key, but without satellite)
+        let destroyed_rockets = self.world.take_destroyed_rockets();
+        for destroyed in destroyed_rockets {
+            let player_id = destroyed.player_id.unwrap_or(0);
+            log::info!("Player {} rocket destroyed by bullet, respawning", player_id);
+            let description = format!("{}'s rocket destroyed by {}", self.player_label(player_id), destroyed.cause);
+            self.record_event(SessionEventKind::Destroyed, description, destroyed.position);
+            #[cfg(feature = "sqlite")]
+            self.archive_stat(player_id, PlayerStat::RocketsLost);
+
+            // Spawn new rocket for this player (same as 'C' key respawn logic)
+            let new_rocket = self.new_player_rocket(player_id);
+            let new_rocket_id = self.world.add_rocket(new_rocket);
+
+            // If this was the host's rocket (player 0), update active_rocket_id
+            if player_id == 0 {
+                self.active_rocket_id = Some(new_rocket_id);
+                self.world.set_active_rocket(Some(new_rocket_id));
+            }
+
+            self.recorder.request_keyframe();
+            log::info!("Respawned new rocket {} for player {}", new_rocket_id, player_id);
+            self.events.push(HostSessionEvent::Destroyed(destroyed.clone()));
+            self.report_kill_shot(&destroyed, player_id);
+        }
+
+        // Update snapshot broadcast timer
+        self.snapshot_timer += delta_time;
+        if self.snapshot_timer >= self.rates.snapshot_interval() {
+            // Periodically follow a client's snapshot with its checksum, so clients can spot a desync
+            let checksum_due = self.session_time - self.last_checksum_time >= CHECKSUM_INTERVAL;
+            if checksum_due {
+                self.last_checksum_time = self.session_time;
+            }
+            self.broadcast_snapshot(checksum_due);
+            self.snapshot_timer = 0.0;
+        }
+
+        // Keyframe the match recording now and then (and right after respawns)
+        if self.recorder.keyframe_due(self.session_time) {
+            self.recorder.add_keyframe(self.session_time, self.create_snapshot());
+        }
+
+        #[cfg(feature = "sqlite")]
+        {
+            self.world_db_timer += delta_time;
+            if self.world_db_timer >= world_database::SNAPSHOT_INTERVAL {
+                self.world_db_timer = 0.0;
+                self.archive_snapshot();
+            }
+        }
+    }
+
+    /// Log a notable moment into the match recording (`position` is in this tick's coordinates)
+    fn record_event(&mut self, kind: SessionEventKind, description: String, position: Vec2) {
+        let world_position = self.world.origin().to_world(position);
+        self.recorder.record_event(self.session_time, kind, description, world_position);
+    }
+
+    /// Log a launch for every rocket that left the ground since last tick
+    fn record_launches(&mut self) {
+        let mut launched = Vec::new();
+        let mut grounded = HashSet::new();
+        for (rocket_id, rocket) in self.world.rockets_with_ids() {
+            if rocket.landed_on_planet_id().is_some() {
+                grounded.insert(rocket_id);
+            } else if self.grounded_rockets.contains(&rocket_id) {
+                launched.push((rocket.player_id().unwrap_or(0), rocket.position()));
+            }
+        }
+        self.grounded_rockets = grounded;
+        for (player_id, position) in launched {
+            let description = format!("{} launched", self.player_label(player_id));
+            self.record_event(SessionEventKind::Launch, description, position);
+        }
+    }
+
+    /// Write this match's recording to the replays folder (call when the host leaves)
+    pub fn finish_recording(&mut self) {
+        match self.recorder.finish() {
+            Ok(Some(path)) => log::info!("Match recording written to {}", path),
+            Ok(None) => log::info!("Match too short to keep a recording"),
+            Err(e) => log::error!("Failed to save match recording: {}", e),
+        }
+    }
+
+    /// Archive snapshots, players and stats to a SQLite database for the rest of this session
+    /// (call after the world is set up; needs the `sqlite` feature)
+    pub fn open_world_database(&mut self, path: &str, save_name: Option<&str>) {
+        #[cfg(feature = "sqlite")]
+        {
+            let opened = WorldDatabase::open(path).and_then(|mut db| {
+                db.start_session(save_name)?;
+                for name in self.player_names.values() {
+                    db.player_joined(name)?;
+                }
+                Ok(db)
+            });
+            match opened {
+                Ok(db) => {
+                    log::info!("Archiving this session to world database {}", path);
+                    self.world_db = Some(db);
+                    self.archive_snapshot();
+                }
+                Err(e) => log::error!("Failed to open world database {}: {}", path, e),
+            }
+        }
+        #[cfg(not(feature = "sqlite"))]
+        {
+            let _ = save_name;
+            log::warn!("Ignoring world database {}: built without the sqlite feature", path);
+        }
+    }
+
+    /// Store a last snapshot before the host leaves
+    pub fn close_world_database(&mut self) {
+        #[cfg(feature = "sqlite")]
+        if self.world_db.is_some() {
+            self.archive_snapshot();
+            self.world_db = None;
+        }
+    }
+
+    #[cfg(feature = "sqlite")]
+    fn archive_snapshot(&mut self) {
+        let save_data = self.save_data();
+        let names: Vec<String> = self.player_names.values().cloned().collect();
+        if let Some(db) = &mut self.world_db {
+            let archived = db.save_snapshot(&save_data).and_then(|id| db.players_seen(&names).map(|_| id));
+            match archived {
+                Ok(id) => log::info!("Archived world snapshot {}", id),
+                Err(e) => log::error!("Failed to archive world snapshot: {}", e),
+            }
+        }
+    }
+
+    #[cfg(feature = "sqlite")]
+    fn archive_stat(&mut self, player_id: u32, stat: PlayerStat) {
+        let name = self.player_label(player_id);
+        if let Some(db) = &mut self.world_db {
+            if let Err(e) = db.add_stat(&name, stat, 1) {
+                log::error!("Failed to archive {} for {}: {}", stat.key(), name, e);
+            }
+        }
+    }
+
+    /// Connected client by player ID or (case-insensitive) name
+    pub fn find_client(&self, player: &str) -> Option<u32> {
+        let clients = self.clients.lock().unwrap();
+        let id = player.parse::<u32>().ok();
+        clients
+            .values()
+            .find(|client| Some(client.player_id) == id || client.player_name.eq_ignore_ascii_case(player))
+            .map(|client| client.player_id)
+    }
+
+    /// Tell a client why, then drop them and their rocket; their address is ignored from now on
+    pub fn kick_player(&mut self, player_id: u32, reason: &str) {
+        let Some(addr) = self.client_addr(player_id) else {
+            return;
+        };
+        if let Err(e) = self.socket.send_to(&ServerNotice::Kicked(reason.to_string()).to_bytes(), addr) {
+            log::warn!("Failed to send kick notice to {}: {}", addr, e);
+        }
+        self.kicked_addrs.insert(addr);
+
+        let name = self.remove_player(player_id, addr);
+        log::info!("Kicked player {} '{}' ({}): {}", player_id, name, addr, reason);
+        self.toast(format!("{} was kicked: {}", name, reason), ORANGE);
+    }
+
+    /// A client sent LEAVE: drop them and their rocket straight away
+    fn player_left(&mut self, addr: SocketAddr) {
+        let Some(player_id) = self.clients.lock().unwrap().get(&addr).map(|client| client.player_id) else {
+            return;
+        };
+        self.left_addrs.insert(addr, self.clock.now());
+        let name = self.remove_player(player_id, addr);
+        log::info!("Player {} '{}' ({}) left", player_id, name, addr);
+        self.toast(format!("{} left the game", name), LIGHTGRAY);
+    }
+
+    /// Forget a client and everything they flew; returns their name
+    fn remove_player(&mut self, player_id: u32, addr: SocketAddr) -> String {
+        self.clients.lock().unwrap().remove(&addr);
+
+        let rockets: Vec<EntityId> = self
+            .world
+            .rockets_with_ids()
+            .filter(|(_, rocket)| rocket.player_id() == Some(player_id))
+            .map(|(id, _)| id)
+            .collect();
+        for rocket_id in rockets {
+            self.world.remove_rocket(rocket_id);
+            self.refueling_rockets.remove(&rocket_id);
+            self.transferring_rockets.remove(&rocket_id);
+        }
+        self.crew.remove_player(player_id);
+        self.spectators.remove(&player_id);
+        self.respawn_missing_players();
+        self.recorder.request_keyframe();
+        self.player_names.remove(&player_id).unwrap_or_else(|| format!("Player {}", player_id))
+    }
+
+    /// Write our side of a client's desync report (compare with the client's dump using save_diff)
+    fn dump_for_desync(&mut self) {
+        if self.desync_dumps >= MAX_DESYNC_DUMPS {
+            return;
+        }
+        self.desync_dumps += 1;
+        if let Err(e) = save_diff::write_desync_dump(&self.create_snapshot(), "host", self.session_time) {
+            log::warn!("Failed to write desync dump: {}", e);
+        }
+    }
+
+    /// Tell every client the game is over, so they leave now instead of timing out (call before dropping the host)
+    pub fn shutdown(&mut self) {
+        let notice = ServerNotice::HostShutdown.to_bytes();
+        let addrs: Vec<SocketAddr> = self.clients.lock().unwrap().keys().copied().collect();
+        for addr in addrs {
+            if let Err(e) = self.socket.send_to(&notice, addr) {
+                log::warn!("Failed to send shutdown notice to {}: {}", addr, e);
+            }
+        }
+        log::info!("Host shut down");
+    }
+
+    /// Kick a client and refuse their address (every port) for the rest of the session
+    pub fn ban_player(&mut self, player_id: u32, reason: &str) {
+        let Some(addr) = self.client_addr(player_id) else {
+            return;
+        };
+        self.banned_ips.insert(addr.ip());
+        log::info!("Banned address {}", addr.ip());
+        self.kick_player(player_id, reason);
+    }
+
+    pub fn banned_count(&self) -> usize {
+        self.banned_ips.len()
+    }
+
+    /// Connected clients for the player list, by player ID
+    pub fn player_rows(&self) -> Vec<PlayerRow> {
+        let now = self.clock.now();
+        let clients = self.clients.lock().unwrap();
+        let mut rows: Vec<PlayerRow> = clients
+            .values()
+            .map(|client| PlayerRow {
+                player_id: client.player_id,
+                name: client.player_name.clone(),
+                addr: client.addr,
+                rtt: client.link.rtt(),
+                last_seen: (now - client.last_seen) as f32,
+            })
+            .collect();
+        rows.sort_by_key(|row| row.player_id);
+        rows
+    }
+
+    /// Each connected client's ID, name and measured link, by player ID
+    pub fn client_links(&self) -> Vec<(u32, String, LinkQuality)> {
+        let clients = self.clients.lock().unwrap();
+        let mut links: Vec<(u32, String, LinkQuality)> =
+            clients.values().map(|client| (client.player_id, client.player_name.clone(), client.link.clone())).collect();
+        links.sort_by_key(|(player_id, _, _)| *player_id);
+        links
+    }
+
+    /// Give a fresh rocket to every player who should fly but has none (after a world change
+    /// or when their pilot left)
+    fn respawn_missing_players(&mut self) {
+        let mut players: Vec<u32> = self.clients.lock().unwrap().values().map(|client| client.player_id).collect();
+        players.push(0);
+        for player_id in players {
+            let has_rocket = self.world.rockets().any(|rocket| rocket.player_id() == Some(player_id));
+            if has_rocket || self.crew.is_engineer(player_id) || self.spectators.contains(&player_id) {
+                continue;
+            }
+            let rocket = self.new_player_rocket(player_id);
+            let rocket_id = self.world.add_rocket(rocket);
+            if player_id == 0 {
+                self.active_rocket_id = Some(rocket_id);
+                self.world.set_active_rocket(Some(rocket_id));
+            }
+        }
+        // The host's active rocket may have come from a save that predates them
+        let host_rocket = self.world.rockets_with_ids().find(|(_, rocket)| rocket.player_id() == Some(0)).map(|(id, _)| id);
+        if let Some(rocket_id) = host_rocket {
+            if self.active_rocket_id != Some(rocket_id) {
+                self.active_rocket_id = Some(rocket_id);
+                self.world.set_active_rocket(Some(rocket_id));
+            }
+        }
+    }
+
+    /// The map definition as sent to clients (None for the classic layout, which they already know)
+    fn map_notice(&self) -> Option<ServerNotice> {
+        let map = self.map.as_ref()?;
+        match ServerNotice::map_definition(map) {
+            Ok(notice) => Some(notice),
+            Err(e) => {
+                log::error!("Failed to encode map '{}' for clients: {}", map.name, e);
+                None
+            }
+        }
+    }
+
+    /// Send the map definition to one client (on join, or when its snapshots name a map it lacks)
+    fn send_map_to(&self, addr: SocketAddr) {
+        if let Some(notice) = self.map_notice() {
+            if let Err(e) = self.socket.send_to(&notice.to_bytes(), addr) {
+                log::warn!("Failed to send map to {}: {}", addr, e);
+            }
+        }
+    }
+
+    /// Send the map definition to every client (after the world changes map)
+    fn broadcast_map(&self) {
+        if let Some(notice) = self.map_notice() {
+            self.broadcast_notice(&notice);
+        }
+    }
+
+    /// Send the host's own chat line to every client (it comes back as a chat event for our chat box)
+    pub fn send_chat(&mut self, text: &str) {
+        let Some(message) = ChatMessage::new(0, self.player_label(0), text) else {
+            return;
+        };
+        self.relay_chat(message, None);
+    }
+
+    /// Log a chat line, archive it and pass it on to every client but the one it came from
+    fn relay_chat(&mut self, message: ChatMessage, from: Option<SocketAddr>) {
+        log::info!("[Chat] {}: {}", message.sender, message.text);
+        #[cfg(feature = "sqlite")]
+        if let Some(db) = &mut self.world_db {
+            if let Err(e) = db.record_chat(self.session_time, &message.sender, &message.text) {
+                log::error!("Failed to archive chat: {}", e);
+            }
+        }
+        let bytes = message.to_bytes();
+        for client in self.clients.lock().unwrap().values().filter(|client| Some(client.addr) != from) {
+            if let Err(e) = self.socket.send_to(&bytes, client.addr) {
+                log::warn!("Failed to send chat to {}: {}", client.addr, e);
+            }
+        }
+        self.events.push(HostSessionEvent::Chat(message));
+    }
+
+    pub fn broadcast_notice(&self, notice: &ServerNotice) {
+        let bytes = notice.to_bytes();
+        for client in self.clients.lock().unwrap().values() {
+            if let Err(e) = self.socket.send_to(&bytes, client.addr) {
+                log::warn!("Failed to send notice to {}: {}", client.addr, e);
+            }
+        }
+    }
+
+    /// Receive and process packets from clients
+    fn receive_client_packets(&mut self) {
+        let mut buf = [0u8; 1024];
+
+        // Process all available packets (non-blocking)
+        loop {
+            match self.socket.recv_from(&mut buf) {
+                Ok((size, src_addr)) => {
+                    if self.kicked_addrs.contains(&src_addr) || self.banned_ips.contains(&src_addr.ip()) {
+                        continue;
+                    }
+
+                    if &buf[..size] == LEAVE {
+                        self.player_left(src_addr);
+                        continue;
+                    }
+                    let now = self.clock.now();
+                    if self.left_addrs.get(&src_addr).is_some_and(|left| now - left < LEAVE_GRACE) {
+                        continue;
+                    }
+
+                    // Snapshot acknowledgements measure each client's link
+                    if let Some(snapshot_time) = parse_ack(&buf[..size]) {
+                        if let Some(client) = self.clients.lock().unwrap().get_mut(&src_addr) {
+                            client.last_seen = now;
+                            client.link.on_ack(snapshot_time, now);
+                            client.delta.on_ack(snapshot_time);
+                        }
+                        continue;
+                    }
+
+                    // Chat lines, named after the sender's join packet whatever the packet says
+                    // (checked before input packets, which a long enough chat packet could pass for)
+                    if let Some(packet) = ChatMessage::from_bytes(&buf[..size]) {
+                        let sender = self.clients.lock().unwrap().get(&src_addr).map(|client| client.player_id);
+                        if let Some(message) = sender.and_then(|player_id| ChatMessage::new(player_id, self.player_label(player_id), &packet.text)) {
+                            self.relay_chat(message, Some(src_addr));
+                        }
+                        continue;
+                    }
+
+                    // Try to parse as input packet first
+                    if let Ok(mut input_packet) = bincode::deserialize::<ClientInputPacket>(&buf[..size]) {
+                        // Update last seen time, and trust the ID we assigned this address over the packet
+                        // (engineers have no rocket to learn their ID from); strangers steer nothing
+                        match self.clients.lock().unwrap().get_mut(&src_addr) {
+                            Some(client) => {
+                                client.last_seen = now;
+                                input_packet.player_id = client.player_id;
+                            }
+                            None => continue,
+                        }
+                        if self.spectators.contains(&input_packet.player_id) {
+                            continue;
+                        }
+
+                        // This is a client input packet - apply it to their rocket
+                        self.apply_client_input(input_packet);
+                        continue;
+                    }
+
+                    // Satellite gift offers and answers
+                    if let Some(packet) = OwnershipPacket::from_bytes(&buf[..size]) {
+                        let sender = self.clients.lock().unwrap().get(&src_addr).map(|client| client.player_id);
+                        match (sender, packet) {
+                            (Some(from), OwnershipPacket::Offer { satellite_id, to_player }) => {
+                                self.offer_satellite(satellite_id, from, to_player);
+                            }
+                            (Some(responder), OwnershipPacket::Respond { offer_id, accept }) => {
+                                self.resolve_offer(offer_id, responder, accept);
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    // Waypoints dropped or removed by a client
+                    if let Some(packet) = WaypointPacket::from_bytes(&buf[..size]) {
+                        let sender = self.clients.lock().unwrap().get(&src_addr).map(|client| client.player_id);
+                        if let Some(player_id) = sender {
+                            self.handle_waypoint_packet(player_id, packet);
+                        }
+                        continue;
+                    }
+
+                    // A client whose checksum didn't match wants a fresh full snapshot right away
+                    if &buf[..size] == RESYNC_REQUEST {
+                        let known = self.clients.lock().unwrap().contains_key(&src_addr);
+                        if known {
+                            log::warn!("Client {} reported a desync, sending a full resync", src_addr);
+                            self.dump_for_desync();
+                            self.send_snapshot_to(src_addr);
+                        }
+                        continue;
+                    }
+
+                    // A client got a snapshot naming a map it hasn't been sent (the first copy was lost)
+                    if &buf[..size] == MAP_REQUEST {
+                        if self.clients.lock().unwrap().contains_key(&src_addr) {
+                            self.send_map_to(src_addr);
+                        }
+                        continue;
+                    }
+
+                    // Not an input packet, check if it's a join/keepalive packet
+                    let mut clients = self.clients.lock().unwrap();
+                    if let Some(client) = clients.get_mut(&src_addr) {
+                        // Existing client - update last seen time
+                        client.last_seen = now;
+                        continue;
+                    }
+
+                    // New client joining - try to deserialize as JoinPacket to get player name and lobby role
+                    #[derive(Deserialize)]
+                    struct JoinPacket {
+                        player_name: String,
+                        crew_role: CrewRole,
+                    }
+                    #[derive(Deserialize)]
+                    struct LegacyJoinPacket {
+                        player_name: String,
+                    }
+
+                    let (player_name, crew_role) = match bincode::deserialize::<JoinPacket>(&buf[..size]) {
+                        Ok(join_packet) => (join_packet.player_name, join_packet.crew_role),
+                        Err(_) => match bincode::deserialize::<LegacyJoinPacket>(&buf[..size]) {
+                            Ok(join_packet) => (join_packet.player_name, CrewRole::Pilot),
+                            // Fallback for old-style JOIN or keepalive packets
+                            Err(_) => (String::new(), CrewRole::Pilot),
+                        },
+                    };
+
+                    // Spectators have their own limit, so a crowd watching never turns away pilots
+                    let spectating = crew_role == CrewRole::Spectator;
+                    let watching = clients.values().filter(|client| self.spectators.contains(&client.player_id)).count() as u32;
+                    let playing = clients.len() as u32 - watching;
+                    if !lobby_has_room(playing, watching, spectating, self.max_players) {
+                        // Full: say so (the client gives up), but let them try again later
+                        drop(clients);
+                        let reason = if spectating {
+                            format!("Spectator slots full ({})", MAX_SPECTATORS)
+                        } else {
+                            format!("Server full ({} players)", self.max_players)
+                        };
+                        log::info!("Turned away {}: {}", src_addr, reason);
+                        let notice = ServerNotice::Kicked(reason);
+                        if let Err(e) = self.socket.send_to(&notice.to_bytes(), src_addr) {
+                            log::warn!("Failed to send server full notice to {}: {}", src_addr, e);
+                        }
+                        continue;
+                    }
+
+                    let player_id = self.next_player_id;
+                    self.next_player_id += 1;
+                    let player_name = if player_name.trim().is_empty() {
+                        format!("Player {}", player_id) // Fallback if name is empty
+                    } else {
+                        player_name
+                    };
+
+                    clients.insert(src_addr, ConnectedClient {
+                        addr: src_addr,
+                        player_id,
+                        last_seen: now,
+                        player_name: player_name.clone(),
+                        link: LinkQuality::new(now),
+                        checksum_due: false,
+                        delta: DeltaEncoder::new(),
+                    });
+
+                    // Add player name to the names map
+                    self.player_names.insert(player_id, player_name.clone());
+                    #[cfg(feature = "sqlite")]
+                    if let Some(db) = &mut self.world_db {
+                        if let Err(e) = db.player_joined(&player_name) {
+                            log::error!("Failed to archive player {}: {}", player_name, e);
+                        }
+                    }
+
+                    log::info!("New client '{}' connected from {} assigned player_id {}", player_name, src_addr, player_id);
+                    drop(clients); // Drop the lock before touching the world
+                    self.send_map_to(src_addr);
+
+                    // Spectators only watch
+                    if crew_role == CrewRole::Spectator {
+                        self.spectators.insert(player_id);
+                        self.toast(format!("{} is spectating", player_name), LIGHTGRAY);
+                        log::info!("Player {} joined as a spectator", player_id);
+                        return;
+                    }
+
+                    // Engineers board the pilot's rocket instead of getting their own
+                    // (no pilot rocket to board means they fly their own after all)
+                    if let CrewRole::Engineer { pilot_id } = crew_role {
+                        let pilot_has_rocket = self.world.rockets().any(|r| r.player_id() == Some(pilot_id));
+                        if pilot_has_rocket && self.crew.assign(player_id, pilot_id) {
+                            let pilot_name = self.player_names.get(&pilot_id).cloned().unwrap_or_default();
+                            self.toast(format!("{} joined as engineer for {}", player_name, pilot_name), SKYBLUE);
+                            log::info!("Player {} is crewing player {}'s rocket as engineer", player_id, pilot_id);
+                            return;
+                        }
+                        log::info!("Player {} can't crew for player {}, spawning their own rocket", player_id, pilot_id);
+                    }
+
+                    // Spawn a rocket for this player at their designated angle
+                    let client_rocket = self.new_player_rocket(player_id);
+                    let client_rocket_id = self.world.add_rocket(client_rocket);
+                    log::info!("Spawned rocket {:?} for player {} at angle {} degrees",
+                        client_rocket_id, player_id, player_id * 5);
+
+                    return; // Exit early since we dropped the lock
+                }
+                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    // No more packets available
+                    break;
+                }
+                Err(e) => {
+                    log::warn!("Error receiving UDP packet: {}", e);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// The world as clients are sent it (without waypoints, which differ per client)
+    pub fn create_snapshot(&self) -> GameSaveData {
+        let mut save_data = GameSaveData::new();
+
+        // Save all planets with their IDs
+        save_data.planets = self.world.planets_with_ids()
+            .map(|(id, planet)| SavedPlanet::from_planet(id, planet))
+            .collect();
+
+        // Save all rockets with their IDs
+        save_data.rockets = self.world.rockets_with_ids()
+            .map(|(id, rocket)| SavedRocket::from_rocket(id, rocket))
+            .collect();
+
+        // Save all satellites with their IDs
+        save_data.satellites = self.world.satellites_with_ids()
+            .map(|(id, satellite)| SavedSatellite::from_satellite(id, satellite))
+            .collect();
+
+        // Save all bullets with their IDs
+        save_data.bullets = self.world.bullets_with_ids()
+            .map(|(id, bullet)| SavedBullet::from_bullet(id, bullet))
+            .collect();
+
+        // Session time names the snapshot for checksum matching
+        save_data.game_time = self.session_time;
+
+        // Save player state (host is player 0)
+        save_data.player_id = Some(0);
+        save_data.active_rocket_id = self.active_rocket_id;
+
+        // Save player names (for network map display on clients)
+        save_data.player_names = self.player_names.clone();
+        save_data.origin = self.world.origin().into();
+        save_data.arena = self.world.arena();
+        save_data.cosmetic_seed = self.world.cosmetic_seed();
+        save_data.map_name = self.map.as_ref().map(|map| map.name.clone());
+
+        save_data
+    }
+
+    /// The snapshot plus everyone's waypoints, as saved and archived
+    pub fn save_data(&self) -> GameSaveData {
+        let mut save_data = self.create_snapshot();
+        save_data.waypoints = self.waypoints.list().iter().map(SavedWaypoint::from_waypoint).collect();
+        save_data
+    }
+
+    /// Send each client due a snapshot its copy, thinned to its interest radius on slow links
+    fn broadcast_snapshot(&self, checksum_due: bool) {
+        let snapshot = self.create_snapshot();
+        let now = self.clock.now();
+        let mut clients = self.clients.lock().unwrap();
+
+        for client in clients.values_mut() {
+            if client.link.update(now) {
+                let tier = client.link.tier();
+                log::info!(
+                    "Snapshot rate for player {} now {} Hz (RTT {:.0} ms, loss {:.0}%)",
+                    client.player_id,
+                    tier.rate_hz(self.rates.snapshot_hz()),
+                    client.link.rtt() * 1000.0,
+                    client.link.loss() * 100.0
+                );
+            }
+            client.checksum_due |= checksum_due;
+            if !client.link.should_send() {
+                continue;
+            }
+
+            let owner = self.crew.rocket_owner(client.player_id);
+            let mut client_snapshot = match client.link.tier().interest_radius() {
+                Some(radius) => Self::filter_for_client(&snapshot, owner, radius),
+                None => snapshot.clone(),
+            };
+            client_snapshot.waypoints = self.saved_waypoints_for(client.player_id);
+            client_snapshot.player_id = Some(client.player_id); // Tells a new client which rocket is theirs
+            let datagrams = match client.delta.encode(&client_snapshot).to_datagrams() {
+                Ok(datagrams) => datagrams,
+                Err(e) => {
+                    log::error!("Failed to serialize snapshot: {}", e);
+                    return;
+                }
+            };
+            if let Err(e) = datagrams.iter().try_for_each(|bytes| self.socket.send_to(bytes, client.addr).map(|_| ())) {
+                log::warn!("Failed to send snapshot to {}: {}", client.addr, e);
+                continue;
+            }
+            client.link.on_sent(client_snapshot.game_time, now);
+
+            // Checksum of exactly what this client was sent
+            if client.checksum_due {
+                client.checksum_due = false;
+                let packet = ChecksumPacket {
+                    snapshot_time: client_snapshot.game_time,
+                    checksum: snapshot_checksum(&client_snapshot),
+                };
+                if let Err(e) = self.socket.send_to(&packet.to_bytes(), client.addr) {
+                    log::warn!("Failed to send checksum to {}: {}", client.addr, e);
+                }
+            }
+        }
+    }
+
+    /// Tell every client right away which bullets were removed this tick, so none fly on as ghosts
+    fn broadcast_bullet_removals(&self) {
+        let removed = self.world.removed_bullets();
+        if removed.is_empty() {
+            return;
+        }
+
+        let clients = self.clients.lock().unwrap();
+        for chunk in removed.chunks(MAX_REMOVALS_PER_PACKET) {
+            let packet = BulletRemovalPacket {
+                host_time: self.session_time,
+                bullet_ids: chunk.to_vec(),
+            };
+            let bytes = packet.to_bytes();
+            for client in clients.values() {
+                if let Err(e) = self.socket.send_to(&bytes, client.addr) {
+                    log::warn!("Failed to send bullet removals to {}: {}", client.addr, e);
+                }
+            }
+        }
+    }
+
+    /// Leave out bullets and other players' rockets beyond `radius` from the owner's rocket
+    fn filter_for_client(snapshot: &GameSaveData, owner: u32, radius: f32) -> GameSaveData {
+        let Some(center) = snapshot.rockets.iter().find(|r| r.player_id == Some(owner)).map(|r| r.position.clone()) else {
+            return snapshot.clone(); // No rocket yet, nothing to center on
+        };
+        let center: Vec2 = center.into();
+        let in_range = |position: &SavedVector2| (Vec2::from(position.clone()) - center).length() <= radius;
+
+        let mut filtered = snapshot.clone();
+        filtered.rockets.retain(|r| r.player_id == Some(owner) || in_range(&r.position));
+        filtered.bullets.retain(|b| in_range(&b.position));
+        filtered
+    }
+
+    /// Send a full snapshot to one client (desync recovery); its deltas start over from this keyframe
+    fn send_snapshot_to(&self, addr: SocketAddr) {
+        let mut snapshot = self.create_snapshot();
+        let mut clients = self.clients.lock().unwrap();
+        let Some(client) = clients.get_mut(&addr) else {
+            return;
+        };
+        snapshot.waypoints = self.saved_waypoints_for(client.player_id);
+        client.delta.reset();
+        match client.delta.encode(&snapshot).to_datagrams() {
+            Ok(datagrams) => {
+                if let Err(e) = datagrams.iter().try_for_each(|bytes| self.socket.send_to(bytes, addr).map(|_| ())) {
+                    log::warn!("Failed to send resync snapshot to {}: {}", addr, e);
+                }
+            }
+            Err(e) => log::error!("Failed to serialize resync snapshot: {}", e),
+        }
+    }
+
+    /// Check the hardcore save rule for a player's rocket, telling them why if they can't save
+    pub fn save_allowed(&mut self, player_id: u32) -> bool {
+        if !self.hardcore_saves {
+            return true;
+        }
+        let owner_id = self.crew.rocket_owner(player_id);
+        let rocket_id = self.world.rockets_with_ids().find(|(_, rocket)| rocket.player_id() == Some(owner_id)).map(|(id, _)| id);
+        let Err(reason) = rocket_id.ok_or_else(|| "No rocket to save from".to_string()).and_then(|id| self.world.check_save_uplink(id)) else {
+            return true;
+        };
+        log::info!("Refused save for player {}: {}", player_id, reason);
+        if player_id == 0 {
+            self.toast(reason, ORANGE);
+        } else if let Some(addr) = self.client_addr(player_id) {
+            if let Err(e) = self.socket.send_to(&ServerNotice::SaveRefused(reason).to_bytes(), addr) {
+                log::warn!("Failed to send save refusal to {}: {}", addr, e);
+            }
+        }
+        false
+    }
+
+    /// A player pressed F5: if the save rules allow it, MultiplayerHost writes the quick save
+    pub fn request_quick_save(&mut self, player_id: u32) {
+        if self.save_allowed(player_id) {
+            self.events.push(HostSessionEvent::QuickSave(player_id));
+        }
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// The host's own rocket
+    pub fn active_rocket_id(&self) -> Option<EntityId> {
+        self.active_rocket_id
+    }
+
+    /// The host's held transfer last tick: donor, recipient in range and fuel moved
+    pub fn host_transfer(&self) -> Option<(EntityId, Option<EntityId>, f32)> {
+        self.host_transfer
+    }
+
+    pub fn session_time(&self) -> f32 {
+        self.session_time
+    }
+
+    pub fn player_names(&self) -> &HashMap<u32, String> {
+        &self.player_names
+    }
+
+    pub fn crew(&self) -> &CrewAssignments {
+        &self.crew
+    }
+
+    pub fn waypoints(&self) -> &Waypoints {
+        &self.waypoints
+    }
+
+    pub fn waypoints_mut(&mut self) -> &mut Waypoints {
+        &mut self.waypoints
+    }
+
+    /// Set the physics and snapshot rates for this session
+    pub fn set_rates(&mut self, rates: HostRates) {
+        log::info!(
+            "Host rates set: physics {} Hz, snapshots {} Hz",
+            rates.physics_hz(),
+            rates.snapshot_hz()
+        );
+        self.rates = rates;
+        self.recorder.set_physics_hz(rates.physics_hz());
+    }
+
+    pub fn rates(&self) -> HostRates {
+        self.rates
+    }
+
+    pub fn client_count(&self) -> usize {
+        self.clients.lock().unwrap().len()
+    }
+
+    /// Cap on the host plus clients, clamped to MIN_PLAYERS..=MAX_PLAYERS (players already in stay)
+    pub fn set_max_players(&mut self, max_players: u32) {
+        self.max_players = max_players.clamp(MIN_PLAYERS, MAX_PLAYERS);
+        log::info!("Host player cap set to {}", self.max_players);
+    }
+
+    pub fn max_players(&self) -> u32 {
+        self.max_players
+    }
+
+    pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spectators_dont_take_player_slots() {
+        // A 4-player lobby: the host plus two clients leaves one pilot slot, however many are watching
+        assert!(lobby_has_room(2, MAX_SPECTATORS, false, 4));
+        assert!(!lobby_has_room(3, 0, false, 4));
+
+        // Spectators only hit their own limit
+        assert!(lobby_has_room(3, MAX_SPECTATORS - 1, true, 4));
+        assert!(!lobby_has_room(0, MAX_SPECTATORS, true, 4));
+    }
+
+    #[test]
+    fn test_default_host_rates() {
+        let rates = HostRates::default();
+        assert_eq!(rates.physics_hz(), GameConstants::DEFAULT_PHYSICS_HZ);
+        assert_eq!(rates.snapshot_hz(), GameConstants::DEFAULT_SNAPSHOT_HZ);
+    }
+
+    #[test]
+    fn test_host_rates_clamp_independently() {
+        // Physics too high, snapshots in range
+        let rates = HostRates::new(10_000, 20);
+        assert_eq!(rates.physics_hz(), GameConstants::MAX_PHYSICS_HZ);
+        assert_eq!(rates.snapshot_hz(), 20);
+
+        // Physics in range, snapshots too low
+        let rates = HostRates::new(60, 0);
+        assert_eq!(rates.physics_hz(), 60);
+        assert_eq!(rates.snapshot_hz(), GameConstants::MIN_SNAPSHOT_HZ);
+    }
+
+    #[test]
+    fn test_host_rates_intervals() {
+        let rates = HostRates::new(120, 30);
+        assert!((rates.physics_timestep() - 1.0 / 120.0).abs() < 1e-6);
+        assert!((rates.snapshot_interval() - 1.0 / 30.0).abs() < 1e-6);
+    }
+}
@@ -2,10 +2,16 @@
 
 pub mod single_player;
 pub mod split_screen;
+pub mod host_session;
 pub mod multiplayer_host;
+pub mod client_session;
 pub mod multiplayer_client;
+pub mod replay_viewer;
 
-pub use single_player::{SinglePlayerGame, SinglePlayerResult};
+pub use single_player::{RetryPlan, SinglePlayerGame, SinglePlayerResult};
 pub use split_screen::{SplitScreenGame, SplitScreenResult};
-pub use multiplayer_host::{MultiplayerHost, MultiplayerHostResult};
+pub use host_session::{HostSession, HostSessionEvent, HostControls, HostFuelKeys};
+pub use multiplayer_host::{MultiplayerHost, MultiplayerHostResult, HostRates};
+pub use client_session::{ClientSession, ClientSessionEvent};
 pub use multiplayer_client::{MultiplayerClient, MultiplayerClientResult};
+pub use replay_viewer::{ReplayViewer, ReplayViewerResult};
@@ -1,31 +1,31 @@
 // Multiplayer Client Mode - Connects to host and receives snapshots via UDP
 // Runs local predicted simulation and syncs with host snapshots
+// The connection itself (world, snapshots, prediction) lives in ClientSession; this is the player's window onto it
 
 use macroquad::prelude::*;
 use std::net::{SocketAddr, UdpSocket};
-use std::sync::Arc;
-use std::collections::{HashSet, HashMap};
-use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
-use crate::entities::{GameObject, Planet, Rocket, Satellite};
+use crate::entities::{GameObject, Planet, Satellite};
 use crate::game_constants::GameConstants;
-use crate::save_system::{GameSaveData, SavedCamera, SavedPlanet, SavedRocket, SavedSatellite, SavedBullet};
-use crate::systems::{World, EntityId, VehicleManager, PlayerInput, PlayerInputState};
-use crate::ui::{Camera, GameInfoDisplay};
-
-const KEEPALIVE_INTERVAL: f32 = 5.0; // Send keepalive every 5 seconds
-
-/// Client input packet - sent from client to host
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct ClientInputPacket {
-    player_id: u32,
-    rotation_delta: f32,  // degrees per frame
-    thrust_level: f32,    // 0.0 to 1.0
-    convert_to_satellite: bool,
-    shoot_bullet: bool,   // true if client wants to shoot
-    save_requested: bool, // true if client pressed F5 (quick save)
-    refuel_from_planet: bool, // true if client wants to refuel from planet (R key)
-}
+use crate::game_modes::multiplayer_host::MAX_PLAYERS;
+use crate::networking::{ClientInputPacket, FrameClock, PresenceInfo};
+use crate::player::Gamepads;
+use crate::save_system::PlayerProfile;
+use crate::systems::{EntityId, VehicleManager, PlayerInput, PlayerInputState, AlertEngine, Controllers, HapticEvent, Haptics, CrewRole, EntityTarget, GiftPrompt, KillCam, KillShot, OwnershipPacket, StateHistory, WaypointPacket, bullet_threats};
+use crate::systems::ownership;
+use crate::ui::{AlertBanner, BulletEffects, Camera, ChatBox, CameraCommand, CameraImpulse, DamageIndicator, EntityInspector, FuelTransferEffects, GameInfoDisplay, RocketTransferReadout, SafeArea, Sky, StrategicView, ThreatIndicator, ToastManager};
+use crate::ui::camera::{self, CRAFT_VIEW_ZOOM, ZOOM_PRESETS};
+use crate::ui::{kill_cam_overlay, mission_clock_display, netcode_overlay, waypoint_markers};
+
+use super::client_session::{ClientSession, ClientSessionEvent};
+
+/// Profile key for this mode's HUD panel layout
+const HUD_LAYOUT_KEY: &str = "online_multiplayer";
+
+const DESYNC_BANNER_DURATION: f32 = 4.0;
+/// Spectator free camera pan speed (screen pixels per second, scaled by zoom)
+const SPECTATOR_PAN_SPEED: f32 = 600.0;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MultiplayerClientResult {
@@ -36,8 +36,10 @@ pub enum MultiplayerClientResult {
 }
 
 pub struct MultiplayerClient {
+    // The world, the host link and prediction
+    session: ClientSession,
+
     // Core game systems
-    world: World,
     camera: Camera,
     vehicle_manager: VehicleManager,
     game_info: GameInfoDisplay,
@@ -45,17 +47,13 @@ pub struct MultiplayerClient {
     // Client player state
     player_input: PlayerInput,
     player_state: PlayerInputState,
-    player_id: u32, // Assigned by host
-    player_name: String, // This client's player name
-    active_rocket_id: Option<EntityId>,
+    gamepads: Gamepads, // The controller in slot 0 flies our rocket
+    haptics: Haptics,   // ...and rumbles
+    spectated_player: Option<u32>, // Spectators: whose rocket the camera follows (None = free camera)
 
     // Networking
-    socket: Arc<UdpSocket>,
-    host_addr: SocketAddr,
-    keepalive_timer: f32,
-    last_snapshot_time: f64,
-    connected: bool,
-    player_names: HashMap<u32, String>, // Map player IDs to player names
+    desync_banner_timer: f32, // Time remaining for the "desync detected" banner
+    gift_prompt: GiftPrompt, // Satellite gift being offered, or offered to us
 
     // Game state
     window_size: Vec2,
@@ -65,38 +63,44 @@ pub struct MultiplayerClient {
 
     // Network map view
     show_network_map: bool,
+    system_view: bool, // End pressed: show the whole system until Home
+    history: StateHistory, // Recent positions (snapshots + prediction), for the kill cam
+    kill_cam: Option<KillCam>, // Replay of the shot that destroyed our rocket, cut when the host reports it
+    kill_cam_return_zoom: f32, // Zoom to go back to once the replay ends
     marked_satellites: HashSet<EntityId>,
 
     // Save celebration (F5 quick save)
     save_celebration_player_id: Option<u32>, // Which player triggered the save
     save_celebration_timer: f32,              // Time remaining for "what a save!!" text
+
+    // Fuel transfer beams, +fuel/s readout and hum
+    transfer_effects: FuelTransferEffects,
+    strategic_view: StrategicView, // Icons, clusters and labels when zoomed far out
+    sky: Sky, // Stars and nebulae from the world's cosmetic seed
+    bullet_effects: BulletEffects, // Tracers, impact flashes and predicted arcs
+    rocket_transfer: RocketTransferReadout, // Our held transfer key
+
+    // Landing grade toasts and persistent best-landing stats
+    toasts: ToastManager,
+    chat: ChatBox, // T to type; the host relays lines from the other players
+    profile: PlayerProfile,
+
+    // Hover tooltips and click-to-target in the world view
+    inspector: EntityInspector,
+
+    // Low fuel / orbit decay / incoming bullet warnings
+    alerts: AlertEngine,
+    alert_banner: AlertBanner,
+
+    // Directional arrows for bullets heading at our rocket(s)
+    threat_indicator: ThreatIndicator,
+    damage_indicator: DamageIndicator, // Edge flashes for hits taken, markers for hits landed
 }
 
 impl MultiplayerClient {
-    /// Calculate spawn position for a player based on their player ID
-    /// Same as host - each player at +5 degrees from previous
-    fn calculate_spawn_position(player_id: u32) -> Vec2 {
-        let angle_degrees = player_id as f32 * 5.0;
-        let angle_radians = angle_degrees.to_radians();
-        let spawn_distance = GameConstants::MAIN_PLANET_RADIUS + 200.0;
-
-        Vec2::new(
-            GameConstants::MAIN_PLANET_X + spawn_distance * angle_radians.cos(),
-            GameConstants::MAIN_PLANET_Y + spawn_distance * angle_radians.sin(),
-        )
-    }
-
     /// Get rocket color for a player based on their player ID
     fn get_player_color(player_id: u32) -> Color {
-        match player_id {
-            0 => Color::from_rgba(255, 100, 100, 255), // Red for host
-            1 => Color::from_rgba(100, 100, 255, 255), // Blue for player 1
-            2 => Color::from_rgba(100, 255, 100, 255), // Green for player 2
-            3 => Color::from_rgba(255, 255, 100, 255), // Yellow for player 3
-            4 => Color::from_rgba(255, 100, 255, 255), // Magenta for player 4
-            5 => Color::from_rgba(100, 255, 255, 255), // Cyan for player 5
-            _ => Color::from_rgba(200, 200, 200, 255), // Gray for others
-        }
+        crate::game_constants::colors::player_color(player_id)
     }
 
     /// Get trajectory color for a player (semi-transparent version)
@@ -112,60 +116,44 @@ impl MultiplayerClient {
         }
     }
 
+
     /// Create a new multiplayer client and connect to host
-    pub fn new(window_size: Vec2, player_name: String, host_ip: &str, host_port: u16) -> Result<Self, String> {
+    pub fn new(window_size: Vec2, player_name: String, host_ip: &str, host_port: u16, crew_role: CrewRole) -> Result<Self, String> {
         // Bind to any available local port
         let socket = UdpSocket::bind("0.0.0.0:0")
             .map_err(|e| format!("Failed to bind UDP socket: {}", e))?;
 
-        // Set non-blocking mode
-        socket.set_nonblocking(true)
-            .map_err(|e| format!("Failed to set non-blocking mode: {}", e))?;
-
         // Parse host address
         let host_addr: SocketAddr = format!("{}:{}", host_ip, host_port)
             .parse()
             .map_err(|e| format!("Invalid host address: {}", e))?;
 
         log::info!("Multiplayer client '{}' connecting to {}:{}", player_name, host_ip, host_port);
+        let session = ClientSession::connect(socket, host_addr, player_name, crew_role, Box::new(FrameClock))?;
 
-        // Send initial join packet with player name
-        #[derive(Serialize)]
-        struct JoinPacket {
-            player_name: String,
+        let profile = PlayerProfile::load_or_default();
+        let mut game_info = GameInfoDisplay::new();
+        if let Some(layout) = profile.hud_layout(HUD_LAYOUT_KEY) {
+            game_info.apply_layout(layout);
         }
 
-        let join_data = JoinPacket {
-            player_name: player_name.clone(),
-        };
-
-        let join_packet = bincode::serialize(&join_data)
-            .map_err(|e| format!("Failed to serialize join packet: {}", e))?;
-        socket.send_to(&join_packet, host_addr)
-            .map_err(|e| format!("Failed to send join packet: {}", e))?;
-
-        // Initialize player names map with this client's name (will be updated with actual player_id later)
-        let mut player_names = HashMap::new();
-        player_names.insert(1, player_name.clone()); // Temporary ID 1, will be updated
+        let mut camera = Camera::new(window_size);
+        camera.set_shake_enabled(profile.screen_shake);
 
         Ok(Self {
-            world: World::new(),
-            camera: Camera::new(window_size),
+            player_state: PlayerInputState::new(session.player_id()), // Temporary, will be updated when assigned
+            session,
+            camera,
             vehicle_manager: VehicleManager::new(),
-            game_info: GameInfoDisplay::new(),
+            game_info,
 
-            player_input: PlayerInput::player1(), // Client uses standard controls
-            player_state: PlayerInputState::new(1), // Temporary, will be updated when assigned
-            player_id: 1, // Temporary, will be assigned by host from snapshot
-            player_name,
-            active_rocket_id: None,
+            player_input: PlayerInput::standard().with_bindings(&profile.key_bindings).with_gamepad(Some(0)),
+            gamepads: Gamepads::disconnected(),
+            haptics: Haptics::disconnected(profile.rumble_intensity),
+            spectated_player: None,
 
-            socket: Arc::new(socket),
-            host_addr,
-            keepalive_timer: 0.0,
-            last_snapshot_time: get_time(),
-            connected: false,
-            player_names,
+            desync_banner_timer: 0.0,
+            gift_prompt: GiftPrompt::default(),
 
             window_size,
             paused: false,
@@ -173,25 +161,76 @@ impl MultiplayerClient {
             show_quit_confirmation: false,
 
             show_network_map: false,
+            system_view: false,
+            history: StateHistory::new(),
+            kill_cam: None,
+            kill_cam_return_zoom: 1.0,
             marked_satellites: HashSet::new(),
 
             save_celebration_player_id: None,
             save_celebration_timer: 0.0,
+            transfer_effects: FuelTransferEffects::new(),
+            strategic_view: StrategicView::new(),
+            sky: Sky::new(),
+            bullet_effects: BulletEffects::new(profile.bullet_view),
+            rocket_transfer: RocketTransferReadout::new(),
+            toasts: ToastManager::new(),
+            chat: ChatBox::new(),
+            profile,
+            inspector: EntityInspector::new(),
+            alerts: AlertEngine::new(),
+            alert_banner: AlertBanner::new(),
+            threat_indicator: ThreatIndicator::new(),
+            damage_indicator: DamageIndicator::new(),
         })
     }
 
     /// Handle input for the client player
     pub fn handle_input(&mut self) -> MultiplayerClientResult {
+        if self.session.connection_lost() {
+            self.haptics.stop();
+            return MultiplayerClientResult::ConnectionLost;
+        }
+
+        // The chat box takes the keyboard while it's open (our rocket coasts)
+        let was_typing = self.chat.is_typing();
+        if let Some(text) = self.chat.handle_input() {
+            self.session.send_chat(&text);
+        }
+        if was_typing || self.chat.is_typing() {
+            self.session.send_idle_input();
+            self.rocket_transfer.release();
+            return MultiplayerClientResult::None;
+        }
+
+        // HUD panels take the mouse first (drag, resize, collapse)
+        let mouse_on_hud = !(self.show_quit_confirmation || self.show_controls || self.show_network_map) && self.game_info.handle_mouse();
+        if self.game_info.take_layout_changed() {
+            self.profile.save_hud_layout(HUD_LAYOUT_KEY, self.game_info.layout());
+        }
+        if is_key_pressed(KeyCode::Key7) {
+            self.game_info.reset_layout();
+        }
+
+        // Hover picking and click-to-target in the world view (suppressed while overlays are open)
+        if self.show_quit_confirmation || self.show_controls || self.show_network_map || mouse_on_hud {
+            self.inspector.clear();
+        } else {
+            self.inspector.update(self.session.world(), &self.camera);
+            if is_mouse_button_pressed(MouseButton::Left) {
+                if let Some(hovered) = self.inspector.hovered() {
+                    self.vehicle_manager.toggle_target(hovered);
+                    log::info!("Target: {:?}", self.vehicle_manager.target());
+                }
+            }
+        }
+
         // Handle quit confirmation popup buttons
         if self.show_quit_confirmation {
             if is_mouse_button_pressed(MouseButton::Left) {
                 let mouse_pos = mouse_position();
-                let screen_w = screen_width();
-                let screen_h = screen_height();
-                let popup_w = 400.0;
-                let popup_h = 200.0;
-                let popup_x = screen_w / 2.0 - popup_w / 2.0;
-                let popup_y = screen_h / 2.0 - popup_h / 2.0;
+                let popup = SafeArea::current().popup(400.0, 200.0);
+                let (popup_x, popup_y, popup_w, popup_h) = (popup.x, popup.y, popup.w, popup.h);
 
                 // Yes button
                 let yes_button_x = popup_x + popup_w / 2.0 - 110.0;
@@ -202,6 +241,7 @@ impl MultiplayerClient {
                 if mouse_pos.0 >= yes_button_x && mouse_pos.0 <= yes_button_x + button_w &&
                    mouse_pos.1 >= yes_button_y && mouse_pos.1 <= yes_button_y + button_h {
                     log::info!("Quit confirmed - disconnecting from host");
+                    self.haptics.stop();
                     return MultiplayerClientResult::ReturnToMenu;
                 }
 
@@ -231,17 +271,17 @@ impl MultiplayerClient {
         }
 
         // Panel visibility toggles (keys 1-5)
-        if is_key_pressed(KeyCode::Key1) {
+        if is_key_pressed(KeyCode::Key1) && !camera::preset_modifier_down() {
             self.game_info.toggle_rocket_panel();
         }
-        if is_key_pressed(KeyCode::Key2) {
+        if is_key_pressed(KeyCode::Key2) && !camera::preset_modifier_down() {
             self.game_info.toggle_planet_panel();
         }
-        if is_key_pressed(KeyCode::Key3) {
+        if is_key_pressed(KeyCode::Key3) && !camera::preset_modifier_down() {
             self.game_info.toggle_orbit_panel();
         }
         // Key 4 removed - controls panel deleted
-        if is_key_pressed(KeyCode::Key5) {
+        if is_key_pressed(KeyCode::Key5) && !camera::preset_modifier_down() {
             self.show_network_map = !self.show_network_map;
             log::info!("Toggled network map: {}", self.show_network_map);
         }
@@ -253,14 +293,19 @@ impl MultiplayerClient {
             self.game_info.hide_all_panels();
         }
 
+        // Quick zoom presets (Home = rocket, End = whole system, Shift+1-6 = fixed zoom levels)
+        if let Some(command) = CameraCommand::from_keys() {
+            self.run_camera_command(command);
+        }
+
         // P - pause/unpause (local only, doesn't affect host, only if controls not showing)
         if is_key_pressed(KeyCode::P) && !self.show_controls {
             self.paused = !self.paused;
             log::info!("Local view {}", if self.paused { "paused" } else { "unpaused" });
         }
 
-        // Visualization toggles
-        if is_key_pressed(KeyCode::T) {
+        // Visualization toggles (plain T opens the chat box)
+        if is_key_pressed(KeyCode::T) && (is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift)) {
             self.vehicle_manager.toggle_trajectory();
             log::info!("Toggled trajectory visualization: {}", self.vehicle_manager.visualization().show_trajectory);
         }
@@ -268,8 +313,21 @@ impl MultiplayerClient {
             self.vehicle_manager.toggle_gravity_forces();
             log::info!("Toggled gravity force visualization: {}", self.vehicle_manager.visualization().show_gravity_forces);
         }
+        if is_key_pressed(KeyCode::L) {
+            self.vehicle_manager.toggle_satellite_orbits();
+            log::info!("Toggled satellite orbit lines: {}", self.vehicle_manager.visualization().show_satellite_orbits);
+        }
+        // Netcode debugging: F8 shows the overlay, F9 stops simulating between snapshots
+        if is_key_pressed(KeyCode::F8) {
+            self.session.prediction_mut().toggle_overlay();
+        }
+        if is_key_pressed(KeyCode::F9) {
+            let enabled = self.session.prediction_mut().toggle_prediction();
+            log::info!("Client-side prediction {}", if enabled { "enabled" } else { "disabled" });
+            self.toasts.push(if enabled { "Prediction on" } else { "Prediction off - showing raw snapshots" }, GOLD);
+        }
         if is_key_pressed(KeyCode::Tab) {
-            let num_bodies = self.world.planets().count();
+            let num_bodies = self.session.world().planets().count();
             self.vehicle_manager.toggle_reference_body(num_bodies);
             log::info!("Cycled to reference body: {}", self.vehicle_manager.visualization().reference_body);
         }
@@ -278,66 +336,167 @@ impl MultiplayerClient {
         // Note: F5 will be sent to host via input packet in handle_player_controls()
         // Celebration will be triggered when host processes it
 
-        // Only process game controls if not paused
-        if !self.paused {
+        // O/Y/N - gift the targeted satellite, or answer a gift offered to us (the host checks ownership)
+        self.handle_gift_keys();
+
+        // B - drop a waypoint at our rocket (Shift+B shares it); right-click on the map places or removes one
+        if is_key_pressed(KeyCode::B) {
+            if let Some(position) = self.session.active_rocket_id().and_then(|id| self.session.world().get_rocket(id)).map(|rocket| rocket.position()) {
+                let shared = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+                let world_pos = self.session.world().origin().to_world(position);
+                self.session.send_waypoint(WaypointPacket::Add { x: world_pos.x, y: world_pos.y, shared });
+                self.toasts.push(if shared { "Shared waypoint" } else { "Dropped waypoint" }, Color::new(1.0, 0.85, 0.2, 1.0));
+            }
+        }
+        if self.show_network_map && is_mouse_button_pressed(MouseButton::Right) {
+            self.toggle_map_waypoint(Vec2::from(mouse_position()));
+        }
+
+        // Only process game controls if not paused (or watching the kill cam)
+        if self.session.crew_role() == CrewRole::Spectator {
+            self.handle_spectator_controls();
+        } else if !self.paused && self.kill_cam.is_none() {
             self.handle_player_controls();
         }
 
         MultiplayerClientResult::None
     }
 
+    fn handle_gift_keys(&mut self) {
+        let targeted_satellite = match self.vehicle_manager.target() {
+            Some(EntityTarget::Satellite(id)) => Some(id),
+            _ => None,
+        };
+        if targeted_satellite.is_none() {
+            self.gift_prompt.recipient = None;
+        }
+
+        if is_key_pressed(KeyCode::O) && targeted_satellite.is_some() {
+            let mut players: Vec<u32> = self.session.player_names().keys().copied().filter(|id| *id != self.session.player_id()).collect();
+            players.sort_unstable();
+            self.gift_prompt.cycle_recipient(&players);
+        }
+        let packet = if is_key_pressed(KeyCode::Y) {
+            if let Some((offer_id, _, _)) = self.gift_prompt.incoming.take() {
+                Some(OwnershipPacket::Respond { offer_id, accept: true })
+            } else if let (Some(to_player), Some(satellite_id)) = (self.gift_prompt.recipient.take(), targeted_satellite) {
+                self.toasts.push(format!("Offered satellite {} to {}", satellite_id, self.session.player_label(to_player)), SKYBLUE);
+                Some(OwnershipPacket::Offer { satellite_id, to_player })
+            } else {
+                None
+            }
+        } else if is_key_pressed(KeyCode::N) {
+            self.gift_prompt.recipient = None;
+            self.gift_prompt.incoming.take().map(|(offer_id, _, _)| OwnershipPacket::Respond { offer_id, accept: false })
+        } else {
+            None
+        };
+
+        if let Some(packet) = packet {
+            self.session.send_ownership(packet);
+        }
+    }
+
+    /// Ask the host to place a waypoint at a network map click, or remove our waypoint near it
+    fn toggle_map_waypoint(&mut self, mouse: Vec2) {
+        let map_size = 700.0;
+        let map_rect = Rect::new(screen_width() / 2.0 - map_size / 2.0, screen_height() / 2.0 - map_size / 2.0, map_size, map_size);
+        if !map_rect.contains(mouse) {
+            return;
+        }
+        let map_scale = (map_size * 0.45) / 50000.0;
+        let world_pos = waypoint_markers::map_to_world(mouse, map_rect.center(), map_scale, self.network_map_origin());
+
+        let packet = match self.session.waypoints().owned_near(world_pos, 10.0 / map_scale, Some(self.session.player_id())) {
+            Some(id) => WaypointPacket::Remove { id },
+            None => {
+                let shared = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+                let world_pos = self.session.world().origin().to_world(world_pos);
+                WaypointPacket::Add { x: world_pos.x, y: world_pos.y, shared }
+            }
+        };
+        self.session.send_waypoint(packet);
+    }
+
+
+    /// World point at the center of the network map (the most massive planet)
+    fn network_map_origin(&self) -> Vec2 {
+        self.session.world()
+            .planets()
+            .max_by(|a, b| a.mass().partial_cmp(&b.mass()).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|planet| planet.position())
+            .unwrap_or(Vec2::new(GameConstants::MAIN_PLANET_X, GameConstants::MAIN_PLANET_Y))
+    }
+
+
+    fn handle_ownership_packet(&mut self, packet: OwnershipPacket) {
+        match packet {
+            OwnershipPacket::Offered { offer_id, from_player, satellite_id } => {
+                self.gift_prompt.incoming = Some((offer_id, from_player, satellite_id));
+                self.toasts.push(format!("{} offers you satellite {}", self.session.player_label(from_player), satellite_id), SKYBLUE);
+            }
+            OwnershipPacket::Resolved { satellite_id, from_player, to_player, accepted } => {
+                if self.gift_prompt.incoming.is_some_and(|(_, _, id)| id == satellite_id) {
+                    self.gift_prompt.incoming = None;
+                }
+                let text = ownership::resolution_text(satellite_id, from_player, to_player, accepted, self.session.player_id(), |id| {
+                    self.session.player_label(id)
+                });
+                self.toasts.push(text, if accepted { GREEN } else { ORANGE });
+            }
+            _ => {}
+        }
+    }
+
+
     fn handle_player_controls(&mut self) {
-        if let Some(rocket_id) = self.active_rocket_id {
+        self.gamepads.poll();
+        self.player_input.sync_gamepad(&self.gamepads);
+
+        if let Some(rocket_id) = self.session.active_rocket_id() {
+            // Engineers leave attitude and thrust to the pilot
+            let is_pilot = self.session.crew_role() == CrewRole::Pilot;
+
             // Build input packet from current controls
             let mut rotation_delta = 0.0;
-            if is_key_down(KeyCode::Left) || is_key_down(KeyCode::A) {
+            if is_pilot && (is_key_down(KeyCode::Left) || is_key_down(self.player_input.rotate_left)) {
                 rotation_delta = 3.0; // degrees per frame
             }
-            if is_key_down(KeyCode::Right) || is_key_down(KeyCode::D) {
+            if is_pilot && (is_key_down(KeyCode::Right) || is_key_down(self.player_input.rotate_right)) {
                 rotation_delta = -3.0;
             }
-
-            // Apply rotation locally for prediction
-            if rotation_delta != 0.0 {
-                let rotation_radians = rotation_delta * std::f32::consts::PI / 180.0;
-                if let Some(rocket) = self.world.get_rocket_mut(rocket_id) {
-                    rocket.rotate(rotation_radians);
-                }
+            if is_pilot && rotation_delta == 0.0 {
+                rotation_delta = -3.0 * self.player_input.gamepad_steer();
             }
 
             // Thrust adjustment (comma to decrease, period to increase)
-            if is_key_pressed(KeyCode::Comma) {
+            if self.player_input.just_decreased_thrust() {
                 self.player_state.adjust_thrust(-0.05);
                 log::info!("Client thrust level: {}%", (self.player_state.thrust_level() * 100.0) as i32);
             }
-            if is_key_pressed(KeyCode::Period) {
+            if self.player_input.just_increased_thrust() {
                 self.player_state.adjust_thrust(0.05);
                 log::info!("Client thrust level: {}%", (self.player_state.thrust_level() * 100.0) as i32);
             }
 
-            // Apply thrust (SPACE key)
-            let thrust_level = if is_key_down(KeyCode::Space) {
-                self.player_state.thrust_level()
+            // Apply thrust (SPACE key or the trigger)
+            let thrust_level = if is_pilot && self.player_input.is_thrusting() {
+                self.player_state.thrust_level() * self.player_input.thrust_amount()
             } else {
                 0.0
             };
 
-            // Apply thrust locally for prediction
-            if let Some(rocket) = self.world.get_rocket_mut(rocket_id) {
-                rocket.set_thrust_level(thrust_level);
-            }
-
             // Convert to satellite (C key)
-            let convert_to_satellite = is_key_pressed(KeyCode::C);
+            let convert_to_satellite = self.player_input.just_converted_to_satellite();
             if convert_to_satellite {
                 log::info!("Client requesting satellite conversion");
             }
 
-            // Shoot bullet (W key for multiplayer, X for singleplayer)
+            // Shoot bullet (W key, or the controller's south button)
             // Don't shoot locally - send to host and let it handle authoritative shooting
-            let shoot_bullet = is_key_pressed(KeyCode::W);
+            let shoot_bullet = self.player_input.just_shot();
             if shoot_bullet {
-                log::info!("Client requesting bullet shot");
+                log::debug!("Client requesting bullet shot");
             }
 
             // Quick save (F5 key) - sends request to host
@@ -345,96 +504,194 @@ impl MultiplayerClient {
             if save_requested {
                 log::info!("Client requesting quick save (F5)");
                 // Trigger celebration locally immediately
-                self.save_celebration_player_id = Some(self.player_id);
+                self.save_celebration_player_id = Some(self.session.player_id());
                 self.save_celebration_timer = 5.0;
             }
 
             // Refuel from planet (R key) - single press
             let refuel_from_planet = is_key_pressed(KeyCode::R);
 
-            // Send input packet to host
-            let input_packet = ClientInputPacket {
-                player_id: self.player_id,
+            // Station ops hold (6) - the host refuels the rocket and says how it's going
+            let station_ops = is_key_pressed(KeyCode::Key6) && !camera::preset_modifier_down();
+
+            // Pass fuel to the nearest rocket (held, I unless rebound) - the host moves the fuel,
+            // the readout follows the recipient's tank in its snapshots
+            let transfer_fuel = self.player_input.is_transferring_fuel();
+            if transfer_fuel {
+                let recipient_id = self.session.world().nearest_rocket_in_transfer_range(rocket_id);
+                self.rocket_transfer.hold(self.session.world(), rocket_id, recipient_id, 0.0);
+            } else {
+                self.rocket_transfer.release();
+            }
+
+            // Send input packet to host (our rocket turns and burns locally too, for prediction)
+            self.session.send_input(ClientInputPacket {
+                player_id: self.session.player_id(),
                 rotation_delta,
                 thrust_level,
                 convert_to_satellite,
                 shoot_bullet,
                 save_requested,
                 refuel_from_planet,
-            };
-
-            if let Ok(bytes) = bincode::serialize(&input_packet) {
-                if let Err(e) = self.socket.send_to(&bytes, self.host_addr) {
-                    log::warn!("Failed to send input packet: {}", e);
-                }
-            }
+                transfer_fuel,
+                station_ops,
+            });
 
             // Zoom controls (local only, doesn't affect game state)
             // Q removed - was causing crashes
-            if is_key_down(KeyCode::E) {
-                self.camera.adjust_zoom(0.02); // Zoom out
+            if self.player_input.is_zooming_out() {
+                self.camera.zoom_held(1.0, get_frame_time()); // Zoom out
             }
 
             // Mouse wheel zoom (local only)
             let mouse_wheel = mouse_wheel().1;
             if mouse_wheel != 0.0 {
-                self.camera.adjust_zoom(-mouse_wheel * 0.02);
+                self.camera.zoom_wheel(mouse_wheel);
             }
+        } else {
+            self.rocket_transfer.release();
         }
     }
 
-    /// Update game simulation and network sync
-    pub fn update(&mut self, delta_time: f32) {
-        // Send keepalive packets to host
-        self.keepalive_timer += delta_time;
-        if self.keepalive_timer >= KEEPALIVE_INTERVAL {
-            self.send_keepalive();
-            self.keepalive_timer = 0.0;
+    /// Spectators: [ and ] follow the previous/next player, WASD pans a free camera, the zoom keys and wheel zoom
+    fn handle_spectator_controls(&mut self) {
+        if is_key_pressed(KeyCode::LeftBracket) {
+            self.cycle_spectated_player(-1);
+        }
+        if is_key_pressed(KeyCode::RightBracket) {
+            self.cycle_spectated_player(1);
         }
 
-        // Receive snapshots from host
-        self.receive_snapshots();
+        let mut pan = Vec2::ZERO;
+        if is_key_down(KeyCode::W) {
+            pan.y -= 1.0;
+        }
+        if is_key_down(KeyCode::S) {
+            pan.y += 1.0;
+        }
+        if is_key_down(KeyCode::A) {
+            pan.x -= 1.0;
+        }
+        if is_key_down(KeyCode::D) {
+            pan.x += 1.0;
+        }
+        if pan != Vec2::ZERO {
+            self.spectated_player = None;
+            self.system_view = false;
+            let target = self.camera.camera().target + pan * SPECTATOR_PAN_SPEED * self.camera.zoom_level() * get_frame_time();
+            self.camera.set_target_center(target);
+        }
 
-        // Check for connection timeout (no snapshot for 30 seconds)
-        let time_since_snapshot = get_time() - self.last_snapshot_time;
-        if time_since_snapshot > 30.0 && self.connected {
-            log::warn!("Connection to host lost (no snapshots for 30 seconds)");
-            self.connected = false;
+        if is_key_down(self.player_input.zoom_in) {
+            self.camera.zoom_held(-1.0, get_frame_time());
+        }
+        if is_key_down(self.player_input.zoom_out) {
+            self.camera.zoom_held(1.0, get_frame_time());
         }
+        let mouse_wheel = mouse_wheel().1;
+        if mouse_wheel != 0.0 {
+            self.camera.zoom_wheel(mouse_wheel);
+        }
+    }
 
-        if self.paused {
+    /// Follow the player `step` places along from the one followed now (by player ID, wrapping)
+    fn cycle_spectated_player(&mut self, step: i32) {
+        let mut players: Vec<u32> = self.session.world().rockets().filter_map(|rocket| rocket.player_id()).collect();
+        players.sort_unstable();
+        players.dedup();
+        if players.is_empty() {
             return;
         }
+        let index = match self.spectated_player.and_then(|current| players.iter().position(|id| *id == current)) {
+            Some(index) => (index as i32 + step).rem_euclid(players.len() as i32) as usize,
+            None if step > 0 => 0,
+            None => players.len() - 1,
+        };
+        self.spectated_player = Some(players[index]);
+        self.system_view = false;
+        self.toasts.push(format!("Following {}", self.session.player_label(players[index])), LIGHTGRAY);
+    }
 
-        // Run local predicted simulation
-        self.world.update(delta_time, false);
+    /// Zoom-to-fit, back-to-rocket and preset zooms, all eased rather than snapped
+    fn run_camera_command(&mut self, command: CameraCommand) {
+        match command {
+            CameraCommand::FitSystem => {
+                if let Some((center, radius)) = self.session.world().system_extent() {
+                    self.system_view = true;
+                    self.camera.zoom_to_fit(center, radius);
+                }
+            }
+            CameraCommand::FocusCraft => {
+                self.system_view = false;
+                self.camera.set_target_zoom(CRAFT_VIEW_ZOOM);
+                self.camera.begin_transition();
+            }
+            CameraCommand::Preset(index) => self.camera.set_target_zoom(ZOOM_PRESETS[index]),
+        }
+    }
 
-        // Handle rockets destroyed by bullets (respawn like 'C' key, but without satellite)
-        let destroyed_rockets = self.world.take_destroyed_rockets();
-        for destroyed in destroyed_rockets {
-            let player_id = destroyed.player_id.unwrap_or(0);
-            log::info!("Player {} rocket destroyed by bullet, respawning", player_id);
+    /// Fly with the controller in slot 0 and rumble it
+    pub fn set_controllers(&mut self, controllers: &Controllers) {
+        self.gamepads = controllers.gamepads();
+        self.haptics = controllers.haptics(0, self.haptics.intensity());
+    }
 
-            // Spawn new rocket for this player (same as 'C' key respawn logic)
-            let spawn_position = Self::calculate_spawn_position(player_id);
-            let mut new_rocket = Rocket::new(
-                spawn_position,
-                Vec2::new(0.0, 0.0),
-                Self::get_player_color(player_id),
-                GameConstants::ROCKET_BASE_MASS,
-            );
-            new_rocket.set_player_id(Some(player_id));
-            let new_rocket_id = self.world.add_rocket(new_rocket);
+    /// Set the looping sound played while fuel transfers are active
+    pub fn set_transfer_sound(&mut self, sound: Option<macroquad::audio::Sound>) {
+        self.transfer_effects.set_hum_sound(sound);
+    }
 
-            // If this was our rocket (this client's player), update active_rocket_id
-            if player_id == self.player_id {
-                self.active_rocket_id = Some(new_rocket_id);
-                self.world.set_active_rocket(Some(new_rocket_id));
-            }
+    /// Set the beep played when a new alert is raised
+    pub fn set_alert_sound(&mut self, sound: Option<macroquad::audio::Sound>) {
+        self.alert_banner.set_warning_sound(sound);
+    }
 
-            log::info!("Respawned new rocket {} for player {}", new_rocket_id, player_id);
+    /// Update game simulation and network sync
+    pub fn update(&mut self, delta_time: f32) {
+        self.session.receive(delta_time);
+        if self.player_state.player_id != self.session.player_id() {
+            self.player_state = PlayerInputState::new(self.session.player_id());
+        }
+
+        if self.paused {
+            self.handle_session_events();
+            self.haptics.stop();
+            return;
         }
 
+        // Run local predicted simulation (with prediction off the world holds the last snapshot)
+        self.session.simulate(delta_time);
+        self.handle_session_events();
+        self.history.record(self.session.world(), get_time() as f32);
+        self.bullet_effects.update(self.session.world(), delta_time, self.camera.camera().target);
+        self.update_kill_cam(delta_time);
+
+        // Engine rumble follows our rocket's throttle while there's fuel to burn
+        let throttle = self
+            .session
+            .active_rocket_id()
+            .and_then(|id| self.session.world().get_rocket(id))
+            .filter(|rocket| rocket.current_fuel() > 0.0)
+            .map_or(0.0, |rocket| rocket.thrust_level());
+        self.haptics.update(delta_time, throttle);
+
+        // Feed predicted fuel transfers into the beam effects
+        let transfer_events = self.session.take_fuel_transfers();
+        self.transfer_effects.update(delta_time, transfer_events);
+        self.vehicle_manager.clear_missing_target(self.session.world());
+
+        self.toasts.update(delta_time);
+        self.chat.update(delta_time);
+
+        // Low fuel / orbit decay / incoming bullet alerts for our rocket(s) and the satellites
+        let watched: Vec<EntityId> = self.session.active_rocket_id().into_iter().collect();
+        self.alerts.update(delta_time, self.session.world(), &watched);
+        self.alert_banner.update(delta_time, self.alerts.alerts());
+        self.threat_indicator.update(delta_time, self.session.world(), &watched);
+        self.damage_indicator.update(delta_time);
+
+        self.desync_banner_timer = (self.desync_banner_timer - delta_time).max(0.0);
+
         // Update save celebration timer
         if self.save_celebration_timer > 0.0 {
             self.save_celebration_timer -= delta_time;
@@ -444,137 +701,114 @@ impl MultiplayerClient {
         }
 
         // Update camera to follow client rocket
-        if let Some(rocket_id) = self.active_rocket_id {
-            if let Some(rocket) = self.world.get_rocket(rocket_id) {
+        if let Some(cam) = &self.kill_cam {
+            self.camera.follow(cam.view_center());
+        } else if let Some((center, _)) = self.session.world().system_extent().filter(|_| self.system_view) {
+            self.camera.follow(center);
+        } else if let Some(rocket_id) = self.session.active_rocket_id() {
+            if let Some(rocket) = self.session.world().get_rocket(rocket_id) {
                 self.camera.set_center(rocket.position());
             }
+        } else if let Some(player_id) = self.spectated_player {
+            if let Some(rocket) = self.session.world().rockets().find(|rocket| rocket.player_id() == Some(player_id)) {
+                self.camera.follow(rocket.position());
+            }
         }
         self.camera.update(delta_time);
     }
 
-    /// Send keepalive packet to host
-    fn send_keepalive(&self) {
-        let keepalive_packet = b"KEEPALIVE";
-        if let Err(e) = self.socket.send_to(keepalive_packet, self.host_addr) {
-            log::warn!("Failed to send keepalive: {}", e);
+    /// Show, play and record what the session reported since the last frame
+    fn handle_session_events(&mut self) {
+        for event in self.session.take_events() {
+            self.handle_session_event(event);
         }
     }
 
-    /// Receive and apply snapshots from host
-    fn receive_snapshots(&mut self) {
-        let mut buf = vec![0u8; 4096]; // Larger buffer for snapshots
-
-        // Process all available packets
-        loop {
-            match self.socket.recv_from(&mut buf) {
-                Ok((size, _src_addr)) => {
-                    // Received snapshot from host
-                    match GameSaveData::from_bytes(&buf[..size]) {
-                        Ok(snapshot) => {
-                            self.apply_snapshot(snapshot);
-                            self.last_snapshot_time = get_time();
-                            self.connected = true;
-                        }
-                        Err(e) => {
-                            log::warn!("Failed to deserialize snapshot: {}", e);
-                        }
-                    }
-                }
-                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                    // No more packets available
-                    break;
+    fn handle_session_event(&mut self, event: ClientSessionEvent) {
+        match event {
+            ClientSessionEvent::Toast(text, color) => self.toasts.push(text, color),
+            ClientSessionEvent::Chat(message) => {
+                self.chat.push(message.sender, message.text, Self::get_player_color(message.player_id));
+            }
+            ClientSessionEvent::HitLanded(position) => self.damage_indicator.on_hit_confirmed(position),
+            ClientSessionEvent::ShotDown { rocket_id, shot, impact, attacker } => {
+                self.haptics.trigger(HapticEvent::Explosion);
+                self.start_kill_cam(rocket_id, shot, impact, attacker);
+            }
+            ClientSessionEvent::SaveRefused(reason) => {
+                self.toasts.push(reason, ORANGE);
+                self.save_celebration_timer = 0.0; // We celebrated when F5 went down
+                self.save_celebration_player_id = None;
+            }
+            ClientSessionEvent::Ownership(packet) => self.handle_ownership_packet(packet),
+            ClientSessionEvent::Desync => self.desync_banner_timer = DESYNC_BANNER_DURATION,
+            ClientSessionEvent::OriginShift(shift) => {
+                self.camera.shift_origin(shift);
+                self.history.shift_positions(shift);
+                self.damage_indicator.shift_positions(shift);
+                self.bullet_effects.shift_positions(shift);
+            }
+            ClientSessionEvent::Hit(hit) => {
+                // Predicted hits on our rocket flash right away
+                if Some(hit.rocket_id) == self.session.active_rocket_id() {
+                    self.damage_indicator.on_hit(hit.position, hit.source);
+                    self.haptics.trigger(HapticEvent::BulletHit);
                 }
-                Err(e) => {
-                    log::warn!("Error receiving UDP packet: {}", e);
-                    break;
+                if hit.shooter_id.is_some() {
+                    self.camera.impulse(CameraImpulse::BulletImpact, hit.position);
                 }
             }
-        }
-    }
-
-    /// Apply received snapshot to local world state
-    fn apply_snapshot(&mut self, snapshot: GameSaveData) {
-        log::debug!("Applying snapshot from host");
-
-        // Clear existing world
-        self.world.clear_all_entities();
-
-        // Load planets with their original IDs
-        for saved_planet in snapshot.planets {
-            let (id, planet) = saved_planet.to_planet();
-            self.world.add_planet_with_id(id, planet);
-        }
-
-        // Load rockets with their original IDs and find ours
-        let mut my_rocket_id: Option<EntityId> = None;
-        let mut highest_player_id: u32 = 0;
-
-        for saved_rocket in snapshot.rockets {
-            let (id, rocket) = saved_rocket.to_rocket();
-
-            // Track highest player_id
-            if let Some(pid) = rocket.player_id() {
-                if pid > highest_player_id {
-                    highest_player_id = pid;
+            ClientSessionEvent::Landed(event) => {
+                // Grade our own touchdowns: toast + best-landing stats in the profile
+                let planet_name = event.planet_name.as_deref().unwrap_or("Unknown");
+                let new_best = self.profile.record_landing(planet_name, &event.result);
+                self.toasts.push(event.result.toast_text(planet_name, new_best), event.result.grade.color());
+                if let Some(rocket) = self.session.world().get_rocket(event.rocket_id) {
+                    self.camera.impulse(CameraImpulse::Landing { vertical_speed: event.result.vertical_speed }, rocket.position());
                 }
-
-                // Check if this rocket belongs to us
-                if pid == self.player_id {
-                    my_rocket_id = Some(id);
-                    log::debug!("Found my rocket (player {}): {:?}", self.player_id, id);
+                self.haptics.trigger(HapticEvent::Landing { vertical_speed: event.result.vertical_speed });
+                if new_best {
+                    if let Err(e) = self.profile.save() {
+                        log::error!("Failed to save profile: {}", e);
+                    }
                 }
             }
-
-            self.world.add_rocket_with_id(id, rocket);
-        }
-
-        // If this is our first snapshot and we haven't found our rocket,
-        // we're probably the newest client, so use the highest player_id
-        if my_rocket_id.is_none() && self.active_rocket_id.is_none() && highest_player_id > 0 {
-            self.player_id = highest_player_id;
-            self.player_state = PlayerInputState::new(highest_player_id);
-            log::info!("Assigned player ID from snapshot: {}", highest_player_id);
-
-            // Find the rocket with this player_id
-            for (id, rocket) in self.world.rockets_with_ids() {
-                if rocket.player_id() == Some(highest_player_id) {
-                    my_rocket_id = Some(id);
-                    log::debug!("Found my rocket: {:?}", id);
-                    break;
-                }
+            ClientSessionEvent::Destroyed(position) => self.camera.impulse(CameraImpulse::Explosion, position),
+            ClientSessionEvent::MapChanged => {
+                let gravity_model = self.session.map().map(|map| map.gravity_model).unwrap_or_default();
+                self.vehicle_manager.set_gravity_model(gravity_model);
             }
         }
+    }
 
-        // Load satellites with their original IDs
-        for saved_satellite in snapshot.satellites {
-            let (id, satellite) = saved_satellite.to_satellite();
-            self.world.add_satellite_with_id(id, satellite);
-        }
+    /// Tell the host we're going, so it drops our rocket now (call before dropping the client)
+    pub fn disconnect(&self) {
+        self.session.disconnect();
+    }
 
-        // Load bullets with their original IDs
-        for saved_bullet in snapshot.bullets {
-            let (id, bullet) = saved_bullet.to_bullet();
-            self.world.add_bullet_with_id(id, bullet);
+    /// Replay the shot that destroyed our rocket, if we saw the bullet fired
+    fn start_kill_cam(&mut self, rocket_id: EntityId, shot: KillShot, impact: Vec2, attacker: String) {
+        self.kill_cam = KillCam::from_history(&self.history, rocket_id, shot, impact, attacker);
+        if let Some(cam) = &self.kill_cam {
+            self.kill_cam_return_zoom = self.camera.zoom_level();
+            self.camera.zoom_to_fit(cam.view_center(), cam.view_radius());
         }
+    }
 
-        // Update player names from snapshot (includes host and all connected clients)
-        for (player_id, player_name) in snapshot.player_names {
-            self.player_names.insert(player_id, player_name);
+    /// Play the kill cam (SPACE skips), then ease back to the new rocket
+    fn update_kill_cam(&mut self, delta_time: f32) {
+        let Some(cam) = self.kill_cam.as_mut() else {
+            return;
+        };
+        if cam.update(delta_time) && !is_key_pressed(KeyCode::Space) {
+            return;
         }
+        self.kill_cam = None;
+        self.camera.set_target_zoom(self.kill_cam_return_zoom);
+        self.camera.begin_transition();
+    }
 
-        // Update our active rocket to the one that belongs to us
-        if let Some(rocket_id) = my_rocket_id {
-            self.active_rocket_id = Some(rocket_id);
-            self.world.set_active_rocket(Some(rocket_id));
-        } else if self.active_rocket_id.is_none() {
-            // If we haven't found our rocket yet, this might be the first snapshot
-            // before the host has spawned our rocket. Keep waiting.
-            log::debug!("Haven't found my rocket yet (player {}), waiting for host to spawn it", self.player_id);
-        }
 
-        // Note: We keep our local camera instead of using snapshot camera
-        // This gives the client freedom to look around independently
-    }
 
     fn draw_network_map(&mut self) {
         use crate::utils::vector_helper;
@@ -609,7 +843,7 @@ impl MultiplayerClient {
         // Find Earth (the most massive planet)
         let mut earth_pos = Vec2::new(GameConstants::MAIN_PLANET_X, GameConstants::MAIN_PLANET_Y);
         let mut earth_mass = 0.0f32;
-        for planet in self.world.planets() {
+        for planet in self.session.world().planets() {
             if planet.mass() > earth_mass {
                 earth_mass = planet.mass();
                 earth_pos = planet.position();
@@ -649,7 +883,7 @@ impl MultiplayerClient {
 
         // Find Moon position for Moon-centered rings
         let mut moon_pos = None;
-        for planet in self.world.planets() {
+        for planet in self.session.world().planets() {
             if planet.mass() != earth_mass {
                 // This is the Moon (not Earth)
                 moon_pos = Some(planet.position());
@@ -678,7 +912,7 @@ impl MultiplayerClient {
         }
 
         // Draw planets
-        for planet in self.world.planets() {
+        for planet in self.session.world().planets() {
             let map_pos = world_to_map(planet.position());
             let planet_radius = planet.radius() * map_scale;
             let planet_radius_clamped = planet_radius.max(8.0);
@@ -692,7 +926,7 @@ impl MultiplayerClient {
         }
 
         // Draw all player rockets (different colors per player)
-        for (_rocket_id, rocket) in self.world.rockets_with_ids() {
+        for (_rocket_id, rocket) in self.session.world().rockets_with_ids() {
             let map_pos = world_to_map(rocket.position());
             let rocket_size = 6.0;
 
@@ -709,16 +943,21 @@ impl MultiplayerClient {
 
             // Label (show player name if available, otherwise P{id})
             if let Some(player_id) = rocket.player_id() {
-                let label = self.player_names.get(&player_id)
+                let label = self.session.player_names().get(&player_id)
                     .map(|name| name.clone())
                     .unwrap_or_else(|| format!("P{}", player_id));
                 draw_text(&label, map_pos.x - 10.0, map_pos.y - 10.0, 12.0, WHITE);
             }
         }
 
+        // Waypoints (ours and shared ones)
+        let rocket_pos = self.session.active_rocket_id().and_then(|id| self.session.world().get_rocket(id)).map(|rocket| rocket.position());
+        let map_bounds = Rect::new(map_x, map_y, map_size, map_size);
+        waypoint_markers::draw_map_waypoints(self.session.waypoints().list(), world_to_map, map_bounds, rocket_pos);
+
         // Draw connection lines between satellites in range
         let satellite_transfer_range = GameConstants::SATELLITE_TRANSFER_RANGE;
-        let satellites: Vec<_> = self.world.satellites_with_ids().collect();
+        let satellites: Vec<_> = self.session.world().satellites_with_ids().collect();
 
         for i in 0..satellites.len() {
             for j in (i + 1)..satellites.len() {
@@ -744,8 +983,21 @@ impl MultiplayerClient {
             }
         }
 
+        // Draw fitted satellite orbits (L toggles)
+        if self.vehicle_manager.visualization().show_satellite_orbits {
+            let planets: Vec<&Planet> = self.session.world().planets().collect();
+            for satellite in self.session.world().satellites() {
+                if let Some(ellipse) = VehicleManager::satellite_orbit_ellipse(satellite, &planets) {
+                    let map_points: Vec<Vec2> = ellipse.points(96).into_iter().map(world_to_map).collect();
+                    for pair in map_points.windows(2) {
+                        draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, 1.0, Color::new(0.0, 1.0, 0.0, 0.25));
+                    }
+                }
+            }
+        }
+
         // Draw satellites
-        let satellites: Vec<_> = self.world.satellites_with_ids().collect();
+        let satellites: Vec<_> = self.session.world().satellites_with_ids().collect();
         for (sat_id, satellite) in &satellites {
             let map_pos = world_to_map(satellite.position());
             let is_marked = self.marked_satellites.contains(sat_id);
@@ -768,111 +1020,14 @@ impl MultiplayerClient {
         }
 
         // Draw bullet trajectories (red lines showing curved path) - same red color for all players
-        let bullets: Vec<_> = self.world.bullets_with_ids().collect();
+        let bullets: Vec<_> = self.session.world().bullets_with_ids().collect();
         for (_bullet_id, bullet) in &bullets {
             let bullet_pos = bullet.position();
             let bullet_vel = bullet.velocity();
 
             // Predict bullet trajectory accounting for moving planets (especially Moon)
             // Use 600 steps (6x normal) to show longer trajectory in map
-            let prediction_steps = 600;
-            let dt = 0.1; // Time step for prediction
-            let mut predicted_positions = Vec::new();
-            let mut current_pos = bullet_pos;
-            let mut current_vel = bullet_vel;
-
-            // Create mutable copies of planet states (position, velocity, mass, radius)
-            // This allows us to simulate their motion during trajectory prediction
-            let mut planet_states: Vec<(Vec2, Vec2, f32, f32)> = self.world.planets()
-                .map(|p| (p.position(), p.velocity(), p.mass(), p.radius()))
-                .collect();
-
-            // Identify Earth (largest planet) for pinning - it doesn't move
-            let earth_index = planet_states
-                .iter()
-                .enumerate()
-                .max_by(|(_, a), (_, b)| a.3.partial_cmp(&b.3).unwrap())
-                .map(|(i, _)| i)
-                .unwrap_or(0);
-
-            for _ in 0..prediction_steps {
-                predicted_positions.push(current_pos);
-
-                // Step 1: Apply planet-to-planet gravity (e.g., Moon orbiting Earth)
-                if planet_states.len() >= 2 {
-                    for i in 0..planet_states.len() {
-                        if i == earth_index {
-                            continue; // Earth is pinned, doesn't move
-                        }
-
-                        let mut planet_acceleration = Vec2::ZERO;
-
-                        // Calculate gravity from all other planets
-                        for j in 0..planet_states.len() {
-                            if i == j {
-                                continue;
-                            }
-
-                            let (pos_i, _, mass_i, radius_i) = planet_states[i];
-                            let (pos_j, _, mass_j, _) = planet_states[j];
-
-                            let direction = pos_j - pos_i;
-                            let distance = direction.length();
-
-                            if distance > radius_i {
-                                // Use same gravity constant as actual physics (GameConstants::G = 100.0)
-                                let force_magnitude = (GameConstants::G * mass_i * mass_j) / (distance * distance);
-                                let force_direction = direction / distance;
-                                planet_acceleration += force_direction * (force_magnitude / mass_i);
-                            }
-                        }
-
-                        // Update planet velocity
-                        planet_states[i].1 += planet_acceleration * dt;
-                    }
-                }
-
-                // Step 2: Update planet positions based on their velocities
-                for i in 0..planet_states.len() {
-                    if i != earth_index {
-                        let vel = planet_states[i].1;
-                        planet_states[i].0 += vel * dt;
-                    }
-                }
-
-                // Step 3: Apply gravity from updated planet positions to bullet
-                // Use same gravity constant as actual physics
-                let mut total_accel = Vec2::ZERO;
-                for &(planet_pos, _, planet_mass, _) in &planet_states {
-                    let diff = planet_pos - current_pos;
-                    let distance = diff.length();
-                    if distance > 0.0 {
-                        let direction = diff / distance;
-                        let bullet_mass = 1.0; // Bullet mass
-                        let force_magnitude = (GameConstants::G * planet_mass * bullet_mass) / (distance * distance);
-                        let acceleration = direction * (force_magnitude / bullet_mass);
-                        total_accel += acceleration;
-                    }
-                }
-
-                // Step 4: Update bullet velocity and position
-                current_vel += total_accel * dt;
-                current_pos += current_vel * dt;
-
-                // Step 5: Check for collision with planets at their PREDICTED positions
-                // Only stop if bullet will actually hit planet when it arrives at this time
-                let mut hit_planet = false;
-                for &(planet_pos, _, _, planet_radius) in &planet_states {
-                    let distance = (planet_pos - current_pos).length();
-                    if distance < planet_radius + 5.0 {
-                        hit_planet = true;
-                        break;
-                    }
-                }
-                if hit_planet {
-                    break;
-                }
-            }
+            let predicted_positions = bullet_threats::predict_bullet_path(self.session.world(), bullet_pos, bullet_vel, 600);
 
             // Draw red trajectory line
             for i in 0..(predicted_positions.len() - 1) {
@@ -898,7 +1053,7 @@ impl MultiplayerClient {
             // Check for predicted collisions with rockets and satellites
             for &predicted_pos in &predicted_positions {
                 // Check rocket collisions
-                for (_rocket_id, rocket) in self.world.rockets_with_ids() {
+                for (_rocket_id, rocket) in self.session.world().rockets_with_ids() {
                     if rocket.is_landed() {
                         continue; // Skip landed rockets
                     }
@@ -975,13 +1130,22 @@ impl MultiplayerClient {
         );
     }
 
+    fn draw_gift_prompt(&self) {
+        let targeted_satellite = match self.vehicle_manager.target() {
+            Some(EntityTarget::Satellite(id)) => Some(id),
+            _ => None,
+        };
+        if let Some(text) = self.gift_prompt.text(targeted_satellite, |id| self.session.player_label(id)) {
+            let width = measure_text(&text, None, 20, 1.0).width;
+            draw_text(&text, screen_width() / 2.0 - width / 2.0, 120.0, 20.0, SKYBLUE);
+        }
+    }
+
     fn draw_controls_popup(&self) {
         let screen_w = screen_width();
         let screen_h = screen_height();
-        let popup_w = 800.0;
-        let popup_h = 600.0;
-        let popup_x = screen_w / 2.0 - popup_w / 2.0;
-        let popup_y = screen_h / 2.0 - popup_h / 2.0;
+        let popup = SafeArea::current().popup(800.0, 600.0);
+        let (popup_x, popup_y, popup_w, popup_h) = (popup.x, popup.y, popup.w, popup.h);
 
         // Semi-transparent overlay
         draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::new(0.0, 0.0, 0.0, 0.5));
@@ -1010,21 +1174,29 @@ impl MultiplayerClient {
             ("SPACE", "Apply thrust"),
             ("A / LEFT", "Rotate left"),
             ("D / RIGHT", "Rotate right"),
-            ("Q", "Zoom in"),
-            ("E", "Zoom out"),
-            ("MOUSE WHEEL", "Zoom"),
+            ("Q / E / WHEEL", "Zoom in / out"),
+            ("HOME / END", "Zoom to rocket / whole system"),
+            ("SHIFT+1-6", "Zoom presets"),
             ("W", "Fire bullet"),
             ("P", "Pause/Unpause (local)"),
+            ("O", "Gift targeted satellite"),
+            ("Y / N", "Accept / decline gift"),
+            ("B", "Drop waypoint (SHIFT shares)"),
+            ("F8 / F9", "Netcode overlay / prediction"),
         ];
 
         let controls_right = [
-            ("T", "Toggle trajectory"),
+            ("T", "Chat (ENTER sends)"),
+            ("SHIFT+T", "Toggle trajectory"),
             ("G", "Toggle gravity forces"),
+            ("L", "Toggle satellite orbits"),
             ("1", "Toggle rocket panel"),
             ("2", "Toggle planet panel"),
             ("3", "Toggle orbit panel"),
             ("4", "Toggle controls panel"),
             ("5", "Toggle network panel"),
+            ("6", "Station ops: auto-refuel while landed"),
+            ("7", "Reset panel layout"),
             ("9", "Hide all panels"),
             ("0", "Show all panels"),
             ("F5", "Request quick save"),
@@ -1088,15 +1260,19 @@ impl MultiplayerClient {
 
     /// Render the game
     pub fn render(&mut self) {
+        self.sky.sync(self.session.world().cosmetic_seed());
+        self.sky.draw(self.session.world().origin().to_world(self.camera.camera().target), self.camera.zoom_level());
+
         // Set camera
         set_camera(self.camera.camera());
 
         // Render world
-        self.world.render();
+        self.strategic_view.render_world(self.session.world(), &self.camera);
+        self.bullet_effects.draw(self.camera.zoom_level());
 
         // Draw trajectory visualizations for all players' rockets with their colors
-        let all_planets: Vec<&Planet> = self.world.planets().collect();
-        for (id, rocket) in self.world.rockets_with_ids() {
+        let all_planets: Vec<&Planet> = self.session.world().planets().collect();
+        for (id, rocket) in self.session.world().rockets_with_ids() {
             if let Some(player_id) = rocket.player_id() {
                 let trajectory_color = Self::get_trajectory_color(player_id);
                 self.vehicle_manager.draw_visualizations_with_color(
@@ -1109,24 +1285,74 @@ impl MultiplayerClient {
             }
         }
 
+        // Draw fitted satellite orbit ellipses
+        let satellites: Vec<&Satellite> = self.session.world().satellites().collect();
+        self.vehicle_manager.draw_satellite_orbits(&satellites, &self.session.world().planets().collect::<Vec<_>>(), self.camera.zoom_level());
+
+        // Draw hover/target highlights
+        self.inspector.draw_highlights(self.session.world(), self.vehicle_manager.target(), self.camera.zoom_level());
+
+        // Draw active fuel transfer beams
+        self.transfer_effects.draw_beams(self.session.world(), self.camera.zoom_level());
+
+        // Own rocket screen position for the +fuel/s readout
+        let fuel_rate_target = self.session.active_rocket_id().and_then(|id| {
+            self.session.world().get_rocket(id)
+                .map(|rocket| (id, self.camera.world_to_screen(rocket.position())))
+        });
+
         // Store celebration rocket position for screen-space rendering
         let celebration_screen_pos = if let Some(player_id) = self.save_celebration_player_id {
             // Find the rocket belonging to this player
-            self.world.rockets_with_ids()
+            self.session.world().rockets_with_ids()
                 .find(|(_id, rocket)| rocket.player_id() == Some(player_id))
                 .map(|(_id, rocket)| self.camera.world_to_screen(rocket.position()))
         } else {
             None
         };
 
+        if let Some(cam) = &self.kill_cam {
+            let top_left = self.camera.screen_to_world(Vec2::ZERO);
+            let bottom_right = self.camera.screen_to_world(Vec2::new(screen_width(), screen_height()));
+            let visible = Rect::new(top_left.x, top_left.y, bottom_right.x - top_left.x, bottom_right.y - top_left.y);
+            kill_cam_overlay::draw_replay(cam, visible, self.camera.zoom_level());
+        }
+        if self.session.prediction().overlay_visible() {
+            netcode_overlay::draw_ghost(self.session.prediction(), self.camera.zoom_level());
+        }
+
         // Reset to default camera for UI
         set_default_camera();
+        self.strategic_view.draw_labels(self.session.world(), &self.camera);
+
+        if let Some((rocket_id, screen_pos)) = fuel_rate_target {
+            self.transfer_effects.draw_rocket_fuel_rate(rocket_id, screen_pos);
+        }
+        let rocket_pos = self.session.active_rocket_id().and_then(|id| self.session.world().get_rocket(id)).map(|rocket| rocket.position());
+        waypoint_markers::draw_waypoints(self.session.waypoints().list(), &self.camera, rocket_pos);
+        self.toasts.draw();
+        self.chat.draw();
+        self.rocket_transfer.draw();
+        mission_clock_display::draw_mission_clock(self.session.mission_time(), 1);
+        if self.session.prediction().overlay_visible() {
+            netcode_overlay::draw_error_graph(self.session.prediction());
+        }
+        if let Some(cam) = &self.kill_cam {
+            kill_cam_overlay::draw_banner(cam);
+        }
+        self.threat_indicator.draw(self.session.world(), &self.camera);
+        self.damage_indicator.draw(&self.camera);
+        self.alert_banner.draw(self.session.world());
+        if self.desync_banner_timer > 0.0 {
+            self.draw_desync_banner();
+        }
+        self.draw_gift_prompt();
 
         // Update and draw game info panels
-        if let Some(rocket_id) = self.active_rocket_id {
-            if let Some(rocket) = self.world.get_rocket(rocket_id) {
-                let all_planets: Vec<&Planet> = self.world.planets().collect();
-                let satellite_stats = self.world.get_satellite_network_stats();
+        if let Some(rocket_id) = self.session.active_rocket_id() {
+            if let Some(rocket) = self.session.world().get_rocket(rocket_id) {
+                let all_planets: Vec<&Planet> = self.session.world().planets().collect();
+                let satellite_stats = self.session.world().get_satellite_network_stats();
 
                 // Get selected planet for panels 2 and 3 based on reference body (same as single player)
                 use crate::systems::ReferenceBody;
@@ -1148,8 +1374,8 @@ impl MultiplayerClient {
                     selected_planet,
                     reference_body_idx,  // Pass reference body index so UI knows which planet
                     self.player_state.thrust_level(),
-                    self.connected,  // network_connected
-                    Some(self.player_id as usize),  // Client player ID
+                    self.session.is_connected(),  // network_connected
+                    Some(self.session.player_id() as usize),  // Client player ID
                     1,  // player_count (just the client for now)
                     Some(&satellite_stats),
                 );
@@ -1158,11 +1384,23 @@ impl MultiplayerClient {
         }
 
         // Show connection status at bottom
-        let status_color = if self.connected { GREEN } else { RED };
-        let status_text = if self.connected {
-            format!("CLIENT | Connected to {} | Player {}", self.host_addr, self.player_id)
+        let status_color = if self.session.is_connected() { GREEN } else { RED };
+        let status_text = if let Some(reason) = self.session.kicked_reason() {
+            format!("CLIENT | Removed by the host: {} | Esc to leave", reason)
+        } else if self.session.is_connected() {
+            match self.session.crew_role() {
+                CrewRole::Pilot => format!("CLIENT | Connected to {} | Player {}", self.session.host_addr(), self.session.player_id()),
+                CrewRole::Engineer { pilot_id } => {
+                    let pilot_name = self.session.player_names().get(&pilot_id).cloned().unwrap_or_else(|| format!("Player {}", pilot_id));
+                    format!("CLIENT | Connected to {} | Engineer aboard {}'s rocket (C/W/R)", self.session.host_addr(), pilot_name)
+                }
+                CrewRole::Spectator => {
+                    let following = self.spectated_player.map_or("free camera".to_string(), |player_id| self.session.player_label(player_id));
+                    format!("CLIENT | Connected to {} | Spectating: {} ([ ] players, WASD pan)", self.session.host_addr(), following)
+                }
+            }
         } else {
-            format!("CLIENT | Disconnected | Last seen: {:.1}s ago", get_time() - self.last_snapshot_time)
+            format!("CLIENT | Disconnected | Last seen: {:.1}s ago", self.session.seconds_since_snapshot())
         };
 
         draw_text(
@@ -1212,6 +1450,9 @@ impl MultiplayerClient {
             draw_text(text, text_x, text_y, text_size, Color::new(1.0, 0.9, 0.0, 1.0));
         }
 
+        // Hover tooltip
+        self.inspector.draw_tooltip(self.session.world(), self.vehicle_manager.target(), self.session.player_names());
+
         // Draw quit confirmation popup if showing
         if self.show_quit_confirmation {
             self.draw_quit_confirmation();
@@ -1231,10 +1472,8 @@ impl MultiplayerClient {
     fn draw_quit_confirmation(&self) {
         let screen_w = screen_width();
         let screen_h = screen_height();
-        let popup_w = 400.0;
-        let popup_h = 200.0;
-        let popup_x = screen_w / 2.0 - popup_w / 2.0;
-        let popup_y = screen_h / 2.0 - popup_h / 2.0;
+        let popup = SafeArea::current().popup(400.0, 200.0);
+        let (popup_x, popup_y, popup_w, popup_h) = (popup.x, popup.y, popup.w, popup.h);
 
         // Semi-transparent overlay
         draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::new(0.0, 0.0, 0.0, 0.6));
@@ -1328,8 +1567,35 @@ impl MultiplayerClient {
         );
     }
 
+    fn draw_desync_banner(&self) {
+        let text = "DESYNC DETECTED - resyncing with host";
+        let dims = measure_text(text, None, 28, 1.0);
+        let x = screen_width() / 2.0 - dims.width / 2.0;
+        let y = 90.0;
+        let alpha = self.desync_banner_timer.min(1.0);
+        draw_rectangle(x - 16.0, y - 30.0, dims.width + 32.0, 42.0, Color::new(0.5, 0.0, 0.0, 0.8 * alpha));
+        draw_text(text, x, y, 28.0, Color::new(1.0, 0.9, 0.9, alpha));
+    }
+
     /// Check if connected to host
     pub fn is_connected(&self) -> bool {
-        self.connected
+        self.session.is_connected()
+    }
+
+    /// Status shown in Discord Rich Presence
+    pub fn presence_info(&self) -> PresenceInfo {
+        let mode = match self.session.crew_role() {
+            CrewRole::Pilot => "Online",
+            CrewRole::Engineer { .. } => "Online (Engineer)",
+            CrewRole::Spectator => "Online (Spectating)",
+        };
+        PresenceInfo {
+            mode: mode.to_string(),
+            map: None,
+            altitude: self.session.active_rocket_id().and_then(|id| self.session.world().rocket_altitude(id)),
+            player_count: self.session.player_names().len().max(1) as u32,
+            max_players: MAX_PLAYERS,
+            join_address: None,
+        }
     }
 }
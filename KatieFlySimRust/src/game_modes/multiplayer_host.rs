@@ -1,32 +1,33 @@
 // Multiplayer Host Mode - Authoritative server broadcasting snapshots via UDP
-// Runs the simulation and broadcasts state at ~60Hz (every frame) for real-time sync
+// Runs the simulation and broadcasts state at a host-configurable rate (default 60Hz)
+// The session itself (world, clients, protocol) lives in HostSession; this is the host's window onto it
 
 use macroquad::prelude::*;
-use std::net::{SocketAddr, UdpSocket};
-use std::collections::{HashMap, HashSet};
-use std::sync::{Arc, Mutex};
-use serde::{Deserialize, Serialize};
+use std::net::UdpSocket;
+use std::collections::HashSet;
 
-use crate::entities::{GameObject, Planet, Rocket, Satellite};
+use crate::entities::{GameObject, Planet, Satellite};
 use crate::game_constants::GameConstants;
-use crate::save_system::{GameSaveData, SavedCamera, SavedPlanet, SavedRocket, SavedSatellite, SavedBullet};
-use crate::systems::{World, EntityId, VehicleManager, PlayerInput, PlayerInputState};
-use crate::ui::{Camera, GameInfoDisplay};
+use crate::map_config::{ArenaConfig, MapConfiguration};
+use crate::networking::FrameClock;
+use crate::networking::lan_discovery::{LanAdvert, LanAdvertiser};
+use crate::networking::rcon::{RconCommand, RconRequest, RconServer};
+use crate::networking::rich_presence::{host_join_address, PresenceInfo};
+use crate::physics::CollisionRules;
+use crate::player::Gamepads;
+use crate::save_system::{GameSaveData, PlayerProfile, SavedCamera, SavedUiState};
+use crate::systems::{EntityId, VehicleManager, PlayerInput, PlayerInputState, AlertEngine, Controllers, HapticEvent, Haptics, AlertKind, EntityTarget, GiftPrompt, KillCam, KillShot, ServerNotice, StateHistory, bullet_threats};
+use crate::systems::world_bounds;
+use crate::ui::{AlertBanner, BulletEffects, Camera, ChatBox, CameraCommand, CameraImpulse, DamageIndicator, EntityInspector, FuelTransferEffects, GameInfoDisplay, PlayerListAction, PlayerListPanel, RocketTransferReadout, SafeArea, Sky, StrategicView, ThreatIndicator, ToastManager};
+use crate::ui::camera::{self, CRAFT_VIEW_ZOOM, ZOOM_PRESETS};
+use crate::ui::{kill_cam_overlay, mission_clock_display, waypoint_markers};
 use crate::utils::vector_helper;
 
-const SNAPSHOT_INTERVAL: f32 = 1.0 / 60.0; // ~16.67ms between broadcasts (~60 Hz) for real-time sync
-
-/// Client input packet - sent from client to host
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct ClientInputPacket {
-    player_id: u32,
-    rotation_delta: f32,  // degrees per frame
-    thrust_level: f32,    // 0.0 to 1.0
-    convert_to_satellite: bool,
-    shoot_bullet: bool,   // true if client wants to shoot
-    save_requested: bool, // true if client pressed F5 (quick save)
-    refuel_from_planet: bool, // true if client wants to refuel from planet (R key)
-}
+use super::host_session::{HostControls, HostFuelKeys, HostSession, HostSessionEvent};
+pub use super::host_session::{HostRates, MAX_PLAYERS, MAX_SPECTATORS, MIN_PLAYERS};
+
+/// Profile key for this mode's HUD panel layout
+const HUD_LAYOUT_KEY: &str = "online_multiplayer";
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MultiplayerHostResult {
@@ -35,16 +36,11 @@ pub enum MultiplayerHostResult {
     Quit,
 }
 
-struct ConnectedClient {
-    addr: SocketAddr,
-    player_id: u32,
-    last_seen: f64, // Timestamp of last received packet
-    player_name: String, // Player's chosen name
-}
-
 pub struct MultiplayerHost {
+    // The world, the clients and the protocol
+    session: HostSession,
+
     // Core game systems
-    world: World,
     camera: Camera,
     vehicle_manager: VehicleManager,
     game_info: GameInfoDisplay,
@@ -52,16 +48,16 @@ pub struct MultiplayerHost {
     // Host player state (player 0)
     player_input: PlayerInput,
     player_state: PlayerInputState,
-    active_rocket_id: Option<EntityId>,
     host_player_name: String, // Host's player name
+    gamepads: Gamepads, // The controller in slot 0 flies the host's rocket
+    haptics: Haptics,   // ...and rumbles
 
     // Networking
-    socket: Arc<UdpSocket>,
-    clients: Arc<Mutex<HashMap<SocketAddr, ConnectedClient>>>,
-    snapshot_timer: f32,
-    next_player_id: u32, // Next available player ID for new clients
     port: u16, // UDP port this host is listening on
-    player_names: HashMap<u32, String>, // Map player IDs to player names
+    join_address: Option<String>, // LAN address shared through Discord join invites
+    rcon: Option<RconServer>, // Remote console, when started with a password
+    lan_advertiser: Option<LanAdvertiser>, // Announces the session to join menus on the LAN
+    player_list: PlayerListPanel, // 4: connected clients, kick and ban
 
     // Game state
     window_size: Vec2,
@@ -72,41 +68,47 @@ pub struct MultiplayerHost {
 
     // Network map view
     show_network_map: bool,
+    system_view: bool, // End pressed: show the whole system until Home
+    history: StateHistory, // Recent positions, for the kill cam
+    kill_cam: Option<KillCam>, // Replay of the shot that destroyed our rocket
+    kill_cam_return_zoom: f32, // Zoom to go back to once the replay ends
     marked_satellites: HashSet<EntityId>,
 
     // Save celebration (F5 quick save)
     save_celebration_player_id: Option<u32>, // Which player triggered the save
     save_celebration_timer: f32,              // Time remaining for "what a save!!" text
 
-    // Refueling requests from clients
-    refueling_rockets: HashSet<EntityId>, // Rockets that are currently requesting planet refuel
+    // Fuel transfer beams, +fuel/s readout and hum
+    transfer_effects: FuelTransferEffects,
+    strategic_view: StrategicView, // Icons, clusters and labels when zoomed far out
+    sky: Sky, // Stars and nebulae from the world's cosmetic seed
+    bullet_effects: BulletEffects, // Tracers, impact flashes and predicted arcs
+    rocket_transfer: RocketTransferReadout, // Our held transfer key
+
+    // Landing grade toasts and persistent best-landing stats
+    toasts: ToastManager,
+    chat: ChatBox, // T to type; lines from every player
+    profile: PlayerProfile,
+
+    // Hover tooltips and click-to-target in the world view
+    inspector: EntityInspector,
+
+    // Low fuel / orbit decay / incoming bullet warnings
+    alerts: AlertEngine,
+    alert_banner: AlertBanner,
+
+    // Directional arrows for bullets heading at our rocket(s)
+    threat_indicator: ThreatIndicator,
+    damage_indicator: DamageIndicator, // Edge flashes for hits taken, markers for hits landed
+
+    // Satellite gifts offered to or by the host
+    gift_prompt: GiftPrompt,
 }
 
 impl MultiplayerHost {
-    /// Calculate spawn position for a player based on their player ID
-    /// Host (player 0) spawns at 0 degrees, each subsequent player at +5 degrees
-    fn calculate_spawn_position(player_id: u32) -> Vec2 {
-        let angle_degrees = player_id as f32 * 5.0;
-        let angle_radians = angle_degrees.to_radians();
-        let spawn_distance = GameConstants::MAIN_PLANET_RADIUS + 200.0;
-
-        Vec2::new(
-            GameConstants::MAIN_PLANET_X + spawn_distance * angle_radians.cos(),
-            GameConstants::MAIN_PLANET_Y + spawn_distance * angle_radians.sin(),
-        )
-    }
-
     /// Get rocket color for a player based on their player ID
     fn get_player_color(player_id: u32) -> Color {
-        match player_id {
-            0 => Color::from_rgba(255, 100, 100, 255), // Red for host
-            1 => Color::from_rgba(100, 100, 255, 255), // Blue for player 1
-            2 => Color::from_rgba(100, 255, 100, 255), // Green for player 2
-            3 => Color::from_rgba(255, 255, 100, 255), // Yellow for player 3
-            4 => Color::from_rgba(255, 100, 255, 255), // Magenta for player 4
-            5 => Color::from_rgba(100, 255, 255, 255), // Cyan for player 5
-            _ => Color::from_rgba(200, 200, 200, 255), // Gray for others
-        }
+        crate::game_constants::colors::player_color(player_id)
     }
 
     /// Get trajectory color for a player (semi-transparent version)
@@ -127,34 +129,40 @@ impl MultiplayerHost {
         // Bind UDP socket
         let socket = UdpSocket::bind(format!("0.0.0.0:{}", port))
             .map_err(|e| format!("Failed to bind UDP socket on port {}: {}", port, e))?;
-
-        // Set non-blocking mode so we don't freeze waiting for packets
-        socket.set_nonblocking(true)
-            .map_err(|e| format!("Failed to set non-blocking mode: {}", e))?;
+        let session = HostSession::new(socket, player_name.clone(), Box::new(FrameClock))?;
 
         log::info!("Multiplayer host '{}' listening on port {}", player_name, port);
 
-        // Initialize player names map with host's name
-        let mut player_names = HashMap::new();
-        player_names.insert(0, player_name.clone());
+        // Network panel shows the rates this host is running at
+        let rates = session.rates();
+        let mut game_info = GameInfoDisplay::new();
+        game_info.set_tick_rates(Some((rates.physics_hz(), rates.snapshot_hz())));
+        let profile = PlayerProfile::load_or_default();
+        if let Some(layout) = profile.hud_layout(HUD_LAYOUT_KEY) {
+            game_info.apply_layout(layout);
+        }
+
+        let mut camera = Camera::new(window_size);
+        camera.set_shake_enabled(profile.screen_shake);
 
         Ok(Self {
-            world: World::new(),
-            camera: Camera::new(window_size),
+            session,
+
+            camera,
             vehicle_manager: VehicleManager::new(),
-            game_info: GameInfoDisplay::new(),
+            game_info,
 
-            player_input: PlayerInput::player1(), // Host uses Player 1 controls
+            player_input: PlayerInput::standard().with_bindings(&profile.key_bindings).with_gamepad(Some(0)),
             player_state: PlayerInputState::new(0), // Host is player 0
-            active_rocket_id: None,
             host_player_name: player_name,
+            gamepads: Gamepads::disconnected(),
+            haptics: Haptics::disconnected(profile.rumble_intensity),
 
-            socket: Arc::new(socket),
-            clients: Arc::new(Mutex::new(HashMap::new())),
-            snapshot_timer: 0.0,
-            next_player_id: 1, // Host is player 0, clients start at 1
             port,
-            player_names,
+            join_address: host_join_address(port),
+            rcon: None,
+            lan_advertiser: LanAdvertiser::start().map_err(|e| log::warn!("LAN discovery unavailable: {}", e)).ok(),
+            player_list: PlayerListPanel::new(),
 
             window_size,
             paused: false,
@@ -163,119 +171,127 @@ impl MultiplayerHost {
             current_save_name: None,
 
             show_network_map: false,
+            system_view: false,
+            history: StateHistory::new(),
+            kill_cam: None,
+            kill_cam_return_zoom: 1.0,
             marked_satellites: HashSet::new(),
 
             save_celebration_player_id: None,
             save_celebration_timer: 0.0,
-
-            refueling_rockets: HashSet::new(),
+            transfer_effects: FuelTransferEffects::new(),
+            strategic_view: StrategicView::new(),
+            sky: Sky::new(),
+            bullet_effects: BulletEffects::new(profile.bullet_view),
+            rocket_transfer: RocketTransferReadout::new(),
+            toasts: ToastManager::new(),
+            chat: ChatBox::new(),
+            profile,
+            inspector: EntityInspector::new(),
+            alerts: AlertEngine::new(),
+            alert_banner: AlertBanner::new(),
+            threat_indicator: ThreatIndicator::new(),
+            damage_indicator: DamageIndicator::new(),
+
+            gift_prompt: GiftPrompt::default(),
         })
     }
 
-    /// Initialize a new game with default starting conditions
-    pub fn initialize_new_game(&mut self) {
-        log::info!("Initializing new multiplayer host game");
-
-        self.world.clear_all_entities();
+    /// Create a host whose new games are built from `map` instead of the classic Earth/Moon layout
+    pub fn new_with_map(window_size: Vec2, player_name: String, port: u16, map: MapConfiguration) -> Result<Self, String> {
+        let mut host = Self::new(window_size, player_name, port)?;
+        host.session.set_map(Some(map));
+        Ok(host)
+    }
 
-        // Create main planet (Earth)
-        let main_planet = Planet::new(
-            Vec2::new(GameConstants::MAIN_PLANET_X, GameConstants::MAIN_PLANET_Y),
-            GameConstants::MAIN_PLANET_RADIUS,
-            GameConstants::MAIN_PLANET_MASS,
-            BLUE,
-        );
-        self.world.add_planet(main_planet);
-
-        // Create secondary planet (Moon) - match single player configuration
-        let moon_x = *crate::game_constants::SECONDARY_PLANET_X;
-        let moon_y = *crate::game_constants::SECONDARY_PLANET_Y;
-        let moon_radius = GameConstants::SECONDARY_PLANET_RADIUS;
-        let moon_velocity = *crate::game_constants::SECONDARY_PLANET_ORBITAL_VELOCITY;
-
-        let mut secondary_planet = Planet::new(
-            Vec2::new(moon_x, moon_y),
-            moon_radius,
-            GameConstants::SECONDARY_PLANET_MASS,
-            Color::from_rgba(150, 150, 150, 255),
-        );
-        secondary_planet.set_velocity(Vec2::new(0.0, -moon_velocity));
-        self.world.add_planet(secondary_planet);
-
-        // Spawn host's rocket (player 0) at 0 degrees
-        let spawn_position = Self::calculate_spawn_position(0);
-        let mut rocket = Rocket::new(
-            spawn_position,
-            Vec2::new(0.0, 0.0),
-            Self::get_player_color(0),
-            GameConstants::ROCKET_BASE_MASS,
-        );
-        rocket.set_player_id(Some(0)); // Host is player 0
+    /// Name of the map new games use
+    pub fn map_name(&self) -> String {
+        self.session.map_name()
+    }
 
-        let rocket_id = self.world.add_rocket(rocket);
-        self.active_rocket_id = Some(rocket_id);
-        self.world.set_active_rocket(Some(rocket_id));
+    /// Initialize a new game with default starting conditions
+    pub fn initialize_new_game(&mut self) {
+        self.session.initialize_new_game();
+        self.history.clear();
+        self.bullet_effects.clear();
+        self.kill_cam = None;
+        self.sync_gravity_model();
 
         // Initialize camera
-        self.camera.set_center(spawn_position);
+        if let Some(rocket) = self.session.active_rocket_id().and_then(|id| self.session.world().get_rocket(id)) {
+            self.camera.set_center(rocket.position());
+        }
+    }
 
-        log::info!("Multiplayer host game initialized - waiting for clients");
+    /// Trajectory previews use the map's gravity model
+    fn sync_gravity_model(&mut self) {
+        let gravity_model = self.session.map().map(|map| map.gravity_model).unwrap_or_default();
+        self.vehicle_manager.set_gravity_model(gravity_model);
     }
 
     /// Load game from a save file
     pub fn load_from_save(&mut self, save_data: GameSaveData, save_name: String) {
         log::info!("Loading multiplayer host game from save: {}", save_name);
 
-        // Clear existing world
-        self.world.clear_all_entities();
+        let camera_center: Vec2 = save_data.camera.center.clone().into();
+        let ui_state = save_data.ui_state.clone();
+        self.session.load_from_save(save_data);
+        self.history.clear();
+        self.bullet_effects.clear();
+        self.kill_cam = None;
+        self.sync_gravity_model();
 
-        // Load planets with their original IDs
-        for saved_planet in save_data.planets {
-            let (id, planet) = saved_planet.to_planet();
-            self.world.add_planet_with_id(id, planet);
-        }
-
-        // Load rockets with their original IDs
-        for saved_rocket in save_data.rockets {
-            let (id, rocket) = saved_rocket.to_rocket();
-            self.world.add_rocket_with_id(id, rocket);
-        }
-
-        // Load satellites with their original IDs
-        for saved_satellite in save_data.satellites {
-            let (id, satellite) = saved_satellite.to_satellite();
-            self.world.add_satellite_with_id(id, satellite);
-        }
+        // Restore camera state
+        self.camera.set_center(camera_center);
 
-        // Load bullets with their original IDs
-        for saved_bullet in save_data.bullets {
-            let (id, bullet) = saved_bullet.to_bullet();
-            self.world.add_bullet_with_id(id, bullet);
+        // Restore the host's marked satellites, panels and visualization toggles
+        if let Some(ui_state) = &ui_state {
+            ui_state.apply(self.session.world(), &mut self.game_info, &mut self.vehicle_manager, &mut self.marked_satellites);
         }
 
-        // Restore active rocket
-        self.active_rocket_id = save_data.active_rocket_id;
-        self.world.set_active_rocket(save_data.active_rocket_id);
-
-        // Restore camera state
-        self.camera.set_center(save_data.camera.center.into());
-
         self.current_save_name = Some(save_name);
         log::info!("Multiplayer host save loaded successfully");
     }
 
     /// Handle input for the host player
     pub fn handle_input(&mut self) -> MultiplayerHostResult {
+        // The chat box takes the keyboard while it's open (our rocket coasts)
+        let was_typing = self.chat.is_typing();
+        if let Some(text) = self.chat.handle_input() {
+            self.session.send_chat(&text);
+        }
+        if was_typing || self.chat.is_typing() {
+            self.session.fly_host(HostControls::default());
+            return MultiplayerHostResult::None;
+        }
+        // HUD panels take the mouse first (drag, resize, collapse)
+        let mouse_on_hud = !(self.show_quit_confirmation || self.show_controls || self.show_network_map) && self.game_info.handle_mouse();
+        if self.game_info.take_layout_changed() {
+            self.profile.save_hud_layout(HUD_LAYOUT_KEY, self.game_info.layout());
+        }
+        if is_key_pressed(KeyCode::Key7) {
+            self.game_info.reset_layout();
+        }
+
+        // Hover picking and click-to-target in the world view (suppressed while overlays are open)
+        if self.show_quit_confirmation || self.show_controls || self.show_network_map || mouse_on_hud {
+            self.inspector.clear();
+        } else {
+            self.inspector.update(self.session.world(), &self.camera);
+            if is_mouse_button_pressed(MouseButton::Left) {
+                if let Some(hovered) = self.inspector.hovered() {
+                    self.vehicle_manager.toggle_target(hovered);
+                    log::info!("Target: {:?}", self.vehicle_manager.target());
+                }
+            }
+        }
+
         // Handle quit confirmation popup buttons
         if self.show_quit_confirmation {
             if is_mouse_button_pressed(MouseButton::Left) {
                 let mouse_pos = mouse_position();
-                let screen_w = screen_width();
-                let screen_h = screen_height();
-                let popup_w = 400.0;
-                let popup_h = 200.0;
-                let popup_x = screen_w / 2.0 - popup_w / 2.0;
-                let popup_y = screen_h / 2.0 - popup_h / 2.0;
+                let popup = SafeArea::current().popup(400.0, 200.0);
+                let (popup_x, popup_y, popup_w, popup_h) = (popup.x, popup.y, popup.w, popup.h);
 
                 // Yes button
                 let yes_button_x = popup_x + popup_w / 2.0 - 110.0;
@@ -286,6 +302,7 @@ impl MultiplayerHost {
                 if mouse_pos.0 >= yes_button_x && mouse_pos.0 <= yes_button_x + button_w &&
                    mouse_pos.1 >= yes_button_y && mouse_pos.1 <= yes_button_y + button_h {
                     log::info!("Quit confirmed - returning to menu");
+                    self.haptics.stop();
                     return MultiplayerHostResult::ReturnToMenu;
                 }
 
@@ -315,17 +332,26 @@ impl MultiplayerHost {
         }
 
         // Panel visibility toggles (keys 1-5)
-        if is_key_pressed(KeyCode::Key1) {
+        if is_key_pressed(KeyCode::Key1) && !camera::preset_modifier_down() {
             self.game_info.toggle_rocket_panel();
         }
-        if is_key_pressed(KeyCode::Key2) {
+        if is_key_pressed(KeyCode::Key2) && !camera::preset_modifier_down() {
             self.game_info.toggle_planet_panel();
         }
-        if is_key_pressed(KeyCode::Key3) {
+        if is_key_pressed(KeyCode::Key3) && !camera::preset_modifier_down() {
             self.game_info.toggle_orbit_panel();
         }
-        // Key 4 removed - controls panel deleted
-        if is_key_pressed(KeyCode::Key5) {
+        // Key 4 - connected players (kick and ban)
+        if is_key_pressed(KeyCode::Key4) && !camera::preset_modifier_down() {
+            self.player_list.toggle();
+        }
+        let rows = self.session.player_rows();
+        match self.player_list.handle_input(&rows) {
+            Some(PlayerListAction::Kick(player_id)) => self.session.kick_player(player_id, "Kicked by the host"),
+            Some(PlayerListAction::Ban(player_id)) => self.session.ban_player(player_id, "Banned by the host"),
+            None => {}
+        }
+        if is_key_pressed(KeyCode::Key5) && !camera::preset_modifier_down() {
             self.show_network_map = !self.show_network_map;
             log::info!("Toggled network map: {}", self.show_network_map);
         }
@@ -337,14 +363,19 @@ impl MultiplayerHost {
             self.game_info.hide_all_panels();
         }
 
+        // Quick zoom presets (Home = rocket, End = whole system, Shift+1-6 = fixed zoom levels)
+        if let Some(command) = CameraCommand::from_keys() {
+            self.run_camera_command(command);
+        }
+
         // P - pause/unpause (only if controls not showing)
         if is_key_pressed(KeyCode::P) && !self.show_controls {
             self.paused = !self.paused;
             log::info!("Game {}", if self.paused { "paused" } else { "unpaused" });
         }
 
-        // Visualization toggles
-        if is_key_pressed(KeyCode::T) {
+        // Visualization toggles (plain T opens the chat box)
+        if is_key_pressed(KeyCode::T) && (is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift)) {
             self.vehicle_manager.toggle_trajectory();
             log::info!("Toggled trajectory visualization: {}", self.vehicle_manager.visualization().show_trajectory);
         }
@@ -352,240 +383,300 @@ impl MultiplayerHost {
             self.vehicle_manager.toggle_gravity_forces();
             log::info!("Toggled gravity force visualization: {}", self.vehicle_manager.visualization().show_gravity_forces);
         }
+        if is_key_pressed(KeyCode::L) {
+            self.vehicle_manager.toggle_satellite_orbits();
+            log::info!("Toggled satellite orbit lines: {}", self.vehicle_manager.visualization().show_satellite_orbits);
+        }
         if is_key_pressed(KeyCode::Tab) {
-            let num_bodies = self.world.planets().count();
+            let num_bodies = self.session.world().planets().count();
             self.vehicle_manager.toggle_reference_body(num_bodies);
             log::info!("Cycled to reference body: {}", self.vehicle_manager.visualization().reference_body);
         }
 
         // F - save game
-        if is_key_pressed(KeyCode::F) {
+        if is_key_pressed(KeyCode::F) && self.session.save_allowed(0) {
             self.save_game();
         }
 
         // F5 - quick save (triggers "what a save!!" celebration)
         if is_key_pressed(KeyCode::F5) {
-            self.quick_save(0); // Host is player 0
+            self.session.request_quick_save(0); // Host is player 0
         }
 
-        // Only process game controls if not paused
-        if !self.paused {
+        // O/Y/N - gift the targeted satellite, or answer a gift offered to us
+        self.handle_gift_keys();
+
+        // B - drop a waypoint at our rocket (Shift+B shares it); right-click on the map places or removes one
+        if is_key_pressed(KeyCode::B) {
+            if let Some(position) = self.session.active_rocket_id().and_then(|id| self.session.world().get_rocket(id)).map(|rocket| rocket.position()) {
+                let shared = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+                let id = self.session.waypoints_mut().add(position, Some(0), shared);
+                let text = if shared { format!("Shared waypoint WP {}", id + 1) } else { format!("Dropped waypoint WP {}", id + 1) };
+                self.toasts.push(text, Color::new(1.0, 0.85, 0.2, 1.0));
+            }
+        }
+        if self.show_network_map && is_mouse_button_pressed(MouseButton::Right) {
+            self.toggle_map_waypoint(Vec2::from(mouse_position()));
+        }
+
+        // Only process game controls if not paused (or watching the kill cam)
+        if !self.paused && self.kill_cam.is_none() {
             self.handle_player_controls();
         }
 
         MultiplayerHostResult::None
     }
 
+    fn handle_gift_keys(&mut self) {
+        let targeted_satellite = match self.vehicle_manager.target() {
+            Some(EntityTarget::Satellite(id)) => Some(id),
+            _ => None,
+        };
+        if targeted_satellite.is_none() {
+            self.gift_prompt.recipient = None;
+        }
+
+        if is_key_pressed(KeyCode::O) && targeted_satellite.is_some() {
+            let mut players: Vec<u32> = self.session.player_names().keys().copied().filter(|id| *id != 0).collect();
+            players.sort_unstable();
+            self.gift_prompt.cycle_recipient(&players);
+        }
+        if is_key_pressed(KeyCode::Y) {
+            if let Some((offer_id, _, _)) = self.gift_prompt.incoming.take() {
+                self.session.resolve_offer(offer_id, 0, true);
+            } else if let (Some(to), Some(satellite_id)) = (self.gift_prompt.recipient.take(), targeted_satellite) {
+                self.session.offer_satellite(satellite_id, 0, to);
+            }
+        }
+        if is_key_pressed(KeyCode::N) {
+            if let Some((offer_id, _, _)) = self.gift_prompt.incoming.take() {
+                self.session.resolve_offer(offer_id, 0, false);
+            } else {
+                self.gift_prompt.recipient = None;
+            }
+        }
+    }
+
+    /// Place a waypoint at a network map click, or remove our waypoint near it
+    fn toggle_map_waypoint(&mut self, mouse: Vec2) {
+        let (map_rect, _) = SafeArea::current().network_map();
+        let map_size = map_rect.w;
+        if !map_rect.contains(mouse) {
+            return;
+        }
+        let map_scale = (map_size * 0.45) / 50000.0;
+        let world_pos = waypoint_markers::map_to_world(mouse, map_rect.center(), map_scale, self.network_map_origin());
+
+        if let Some(id) = self.session.waypoints_mut().owned_near(world_pos, 10.0 / map_scale, Some(0)) {
+            self.session.waypoints_mut().remove(id, Some(0));
+        } else {
+            let shared = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+            self.session.waypoints_mut().add(world_pos, Some(0), shared);
+        }
+    }
+
+
+    /// World point at the center of the network map (the most massive planet)
+    fn network_map_origin(&self) -> Vec2 {
+        self.session.world()
+            .planets()
+            .max_by(|a, b| a.mass().partial_cmp(&b.mass()).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|planet| planet.position())
+            .unwrap_or(Vec2::new(GameConstants::MAIN_PLANET_X, GameConstants::MAIN_PLANET_Y))
+    }
+
+
+    /// Replay the shot that destroyed our rocket, if we saw the bullet fired
+    fn start_kill_cam(&mut self, rocket_id: EntityId, shot: KillShot, impact: Vec2, attacker: String) {
+        self.kill_cam = KillCam::from_history(&self.history, rocket_id, shot, impact, attacker);
+        if let Some(cam) = &self.kill_cam {
+            self.kill_cam_return_zoom = self.camera.zoom_level();
+            self.camera.zoom_to_fit(cam.view_center(), cam.view_radius());
+        }
+    }
+
+    /// Play the kill cam (SPACE skips), then ease back to the new rocket
+    fn update_kill_cam(&mut self, delta_time: f32) {
+        let Some(cam) = self.kill_cam.as_mut() else {
+            return;
+        };
+        if cam.update(delta_time) && !is_key_pressed(KeyCode::Space) {
+            return;
+        }
+        self.kill_cam = None;
+        self.camera.set_target_zoom(self.kill_cam_return_zoom);
+        self.camera.begin_transition();
+    }
+
     fn handle_player_controls(&mut self) {
-        if let Some(rocket_id) = self.active_rocket_id {
-            // Rotation (A/D or Left/Right, same as singleplayer)
+        self.gamepads.poll();
+        self.player_input.sync_gamepad(&self.gamepads);
+
+        if let Some(rocket_id) = self.session.active_rocket_id() {
+            // Rotation (A/D or Left/Right, or the stick, same as singleplayer)
             let mut rotation_delta = 0.0;
-            if is_key_down(KeyCode::Left) || is_key_down(KeyCode::A) {
+            if is_key_down(KeyCode::Left) || is_key_down(self.player_input.rotate_left) {
                 rotation_delta = 3.0; // degrees per frame
             }
-            if is_key_down(KeyCode::Right) || is_key_down(KeyCode::D) {
+            if is_key_down(KeyCode::Right) || is_key_down(self.player_input.rotate_right) {
                 rotation_delta = -3.0;
             }
-
-            if rotation_delta != 0.0 {
-                let rotation_radians = rotation_delta * std::f32::consts::PI / 180.0;
-                if let Some(rocket) = self.world.get_rocket_mut(rocket_id) {
-                    rocket.rotate(rotation_radians);
-                }
+            if rotation_delta == 0.0 {
+                rotation_delta = -3.0 * self.player_input.gamepad_steer();
             }
 
             // Thrust adjustment (comma to decrease, period to increase, same as singleplayer)
-            if is_key_pressed(KeyCode::Comma) {
+            if self.player_input.just_decreased_thrust() {
                 self.player_state.adjust_thrust(-0.05);
                 log::info!("Host thrust level: {}%", (self.player_state.thrust_level() * 100.0) as i32);
             }
-            if is_key_pressed(KeyCode::Period) {
+            if self.player_input.just_increased_thrust() {
                 self.player_state.adjust_thrust(0.05);
                 log::info!("Host thrust level: {}%", (self.player_state.thrust_level() * 100.0) as i32);
             }
 
-            // Apply thrust (SPACE key, same as singleplayer)
-            let thrust_level = if is_key_down(KeyCode::Space) {
-                self.player_state.thrust_level()
+            // Apply thrust (SPACE key or the trigger, same as singleplayer)
+            let thrust_level = if self.player_input.is_thrusting() {
+                self.player_state.thrust_level() * self.player_input.thrust_amount()
             } else {
                 0.0
             };
 
-            if let Some(rocket) = self.world.get_rocket_mut(rocket_id) {
-                rocket.set_thrust_level(thrust_level);
-            }
+            // Abort to spawn (X key, same as singleplayer) - only while the rocket is leaving the system
+            let escaping = self.alerts.alerts().iter().any(|alert| alert.kind == AlertKind::Escaping && alert.subject == EntityTarget::Rocket(rocket_id));
 
-            // Convert to satellite (C key, same as singleplayer)
-            if is_key_pressed(KeyCode::C) {
-                if self.world.convert_rocket_to_satellite(rocket_id).is_some() {
-                    log::info!("Host converted rocket to satellite");
-
-                    // Spawn new rocket for host at 0 degrees
-                    let spawn_position = Self::calculate_spawn_position(0);
-                    let mut new_rocket = Rocket::new(
-                        spawn_position,
-                        Vec2::new(0.0, 0.0),
-                        Self::get_player_color(0),
-                        GameConstants::ROCKET_BASE_MASS,
-                    );
-                    new_rocket.set_player_id(Some(0)); // Host is player 0
-                    let new_rocket_id = self.world.add_rocket(new_rocket);
-                    self.active_rocket_id = Some(new_rocket_id);
-                    self.world.set_active_rocket(Some(new_rocket_id));
-                    log::info!("Host respawned new rocket");
-                }
-            }
-
-            // Shoot bullet (W key for multiplayer, X for singleplayer)
-            if is_key_pressed(KeyCode::W) {
-                if let Some(bullet_id) = self.world.shoot_bullet_from_rocket(rocket_id) {
-                    log::info!("Bullet {} fired from rocket {}", bullet_id, rocket_id);
-                } else {
-                    log::info!("Cannot shoot: not enough fuel (need 1 unit)");
-                }
-            }
+            self.session.fly_host(HostControls {
+                rotation_delta,
+                thrust_level,
+                convert_to_satellite: self.player_input.just_converted_to_satellite(), // C key, same as singleplayer
+                shoot_bullet: self.player_input.just_shot(), // W key, or the controller's south button
+                abort: is_key_pressed(KeyCode::X) && escaping,
+                retrieve_satellite: is_key_pressed(KeyCode::Z), // Z key, same as singleplayer
+            });
 
             // Zoom controls (Q = zoom in, E = zoom out, same as singleplayer)
-            if is_key_down(KeyCode::Q) {
-                self.camera.adjust_zoom(-0.02); // Zoom in
+            if self.player_input.is_zooming_in() {
+                self.camera.zoom_held(-1.0, get_frame_time()); // Zoom in
             }
-            if is_key_down(KeyCode::E) {
-                self.camera.adjust_zoom(0.02); // Zoom out
+            if self.player_input.is_zooming_out() {
+                self.camera.zoom_held(1.0, get_frame_time()); // Zoom out
             }
 
             // Mouse wheel zoom (same as singleplayer)
             let mouse_wheel = mouse_wheel().1;
             if mouse_wheel != 0.0 {
-                self.camera.adjust_zoom(-mouse_wheel * 0.02);
+                self.camera.zoom_wheel(mouse_wheel);
             }
         }
     }
 
-    /// Apply client input to their rocket
-    fn apply_client_input(&mut self, input: ClientInputPacket) {
-        // Find the rocket that belongs to this player
-        let mut rocket_id: Option<EntityId> = None;
-        for (id, rocket) in self.world.rockets_with_ids() {
-            if rocket.player_id() == Some(input.player_id) {
-                rocket_id = Some(id);
-                break;
-            }
-        }
-
-        if let Some(rid) = rocket_id {
-            // Apply rotation
-            if input.rotation_delta != 0.0 {
-                let rotation_radians = input.rotation_delta * std::f32::consts::PI / 180.0;
-                if let Some(rocket) = self.world.get_rocket_mut(rid) {
-                    rocket.rotate(rotation_radians);
+    /// Zoom-to-fit, back-to-rocket and preset zooms, all eased rather than snapped
+    fn run_camera_command(&mut self, command: CameraCommand) {
+        match command {
+            CameraCommand::FitSystem => {
+                if let Some((center, radius)) = self.session.world().system_extent() {
+                    self.system_view = true;
+                    self.camera.zoom_to_fit(center, radius);
                 }
             }
-
-            // Apply thrust
-            if let Some(rocket) = self.world.get_rocket_mut(rid) {
-                rocket.set_thrust_level(input.thrust_level);
+            CameraCommand::FocusCraft => {
+                self.system_view = false;
+                self.camera.set_target_zoom(CRAFT_VIEW_ZOOM);
+                self.camera.begin_transition();
             }
+            CameraCommand::Preset(index) => self.camera.set_target_zoom(ZOOM_PRESETS[index]),
+        }
+    }
 
-            // Convert to satellite if requested
-            if input.convert_to_satellite {
-                if self.world.convert_rocket_to_satellite(rid).is_some() {
-                    log::info!("Player {} converted rocket to satellite", input.player_id);
-
-                    // Spawn new rocket for this player
-                    let spawn_position = Self::calculate_spawn_position(input.player_id);
-                    let mut new_rocket = Rocket::new(
-                        spawn_position,
-                        Vec2::new(0.0, 0.0),
-                        Self::get_player_color(input.player_id),
-                        GameConstants::ROCKET_BASE_MASS,
-                    );
-                    new_rocket.set_player_id(Some(input.player_id));
-                    self.world.add_rocket(new_rocket);
-                    log::info!("Respawned new rocket for player {}", input.player_id);
-                }
-            }
+    /// Fly with the controller in slot 0 and rumble it
+    pub fn set_controllers(&mut self, controllers: &Controllers) {
+        self.gamepads = controllers.gamepads();
+        self.haptics = controllers.haptics(0, self.haptics.intensity());
+    }
 
-            // Shoot bullet if requested
-            if input.shoot_bullet {
-                if let Some(bullet_id) = self.world.shoot_bullet_from_rocket(rid) {
-                    log::info!("Player {} fired bullet {}", input.player_id, bullet_id);
-                } else {
-                    log::debug!("Player {} cannot shoot: not enough fuel", input.player_id);
-                }
-            }
+    /// Set the looping sound played while fuel transfers are active
+    pub fn set_transfer_sound(&mut self, sound: Option<macroquad::audio::Sound>) {
+        self.transfer_effects.set_hum_sound(sound);
+    }
 
-            // Quick save if requested (F5 key)
-            if input.save_requested {
-                self.quick_save(input.player_id);
-            }
+    /// Set the beep played when a new alert is raised
+    pub fn set_alert_sound(&mut self, sound: Option<macroquad::audio::Sound>) {
+        self.alert_banner.set_warning_sound(sound);
+    }
+    /// Play in a wraparound arena of this size, centered on the main planet
+    pub fn set_arena(&mut self, arena: Option<ArenaConfig>) {
+        self.session.set_arena(arena);
+    }
 
-            // Refuel from planet if requested (R key)
-            if input.refuel_from_planet {
-                self.refueling_rockets.insert(rid);
-            } else {
-                self.refueling_rockets.remove(&rid);
-            }
-        }
+    /// Set the rocket-rocket / rocket-satellite collision rules for this session
+    pub fn set_collision_rules(&mut self, rules: CollisionRules) {
+        self.session.set_collision_rules(rules);
+    }
+
+    /// Hardcore rule: a player may only save from a rocket within reach of the satellite network or landed
+    pub fn set_hardcore_saves(&mut self, hardcore: bool) {
+        self.session.set_hardcore_saves(hardcore);
     }
 
     /// Update game simulation and broadcast snapshots
     pub fn update(&mut self, delta_time: f32) {
+        // Operators can still manage a paused game, and LAN players can still find it
+        self.handle_rcon_requests();
+        self.advertise_on_lan(delta_time);
+
         if self.paused {
+            self.handle_session_events();
+            self.haptics.stop();
             return;
         }
 
-        // Receive any incoming packets from clients
-        self.receive_client_packets();
-
-        // Handle manual planet refueling for host (player 0) if R key is pressed - BEFORE world update
-        let manual_refuel_active = if let Some(rocket_id) = self.active_rocket_id {
-            if is_key_pressed(KeyCode::R) {  // Changed to is_key_pressed for single press
-                self.world.handle_manual_planet_refuel(rocket_id, delta_time);
-                true
-            } else {
-                false
-            }
-        } else {
-            false
+        // R refuels once per press, 6 steps the station ops hold, the transfer key (I unless rebound) is held
+        let typing = self.chat.is_typing();
+        let keys = HostFuelKeys {
+            refuel_from_planet: is_key_pressed(KeyCode::R) && !typing,
+            station_ops: is_key_pressed(KeyCode::Key6) && !camera::preset_modifier_down() && !typing,
+            transfer_fuel: self.player_input.is_transferring_fuel() && !typing,
         };
-
-        // Update physics
-        self.world.update(delta_time, manual_refuel_active);
-
-        // Handle manual planet refueling for clients
-        for rocket_id in &self.refueling_rockets {
-            self.world.handle_manual_planet_refuel(*rocket_id, delta_time);
+        self.session.update(delta_time, keys);
+        match self.session.host_transfer() {
+            Some((donor_id, recipient_id, moved)) => self.rocket_transfer.hold(self.session.world(), donor_id, recipient_id, moved),
+            None => self.rocket_transfer.release(),
         }
 
-        // Handle rockets destroyed by bullets (respawn like 'C' key, but without satellite)
-        let destroyed_rockets = self.world.take_destroyed_rockets();
-        for destroyed in destroyed_rockets {
-            let player_id = destroyed.player_id.unwrap_or(0);
-            log::info!("Player {} rocket destroyed by bullet, respawning", player_id);
-
-            // Spawn new rocket for this player (same as 'C' key respawn logic)
-            let spawn_position = Self::calculate_spawn_position(player_id);
-            let mut new_rocket = Rocket::new(
-                spawn_position,
-                Vec2::new(0.0, 0.0),
-                Self::get_player_color(player_id),
-                GameConstants::ROCKET_BASE_MASS,
-            );
-            new_rocket.set_player_id(Some(player_id));
-            let new_rocket_id = self.world.add_rocket(new_rocket);
-
-            // If this was the host's rocket (player 0), update active_rocket_id
-            if player_id == 0 {
-                self.active_rocket_id = Some(new_rocket_id);
-                self.world.set_active_rocket(Some(new_rocket_id));
-            }
-
-            log::info!("Respawned new rocket {} for player {}", new_rocket_id, player_id);
-        }
+        self.handle_session_events();
+        self.history.record(self.session.world(), self.session.session_time());
+        self.bullet_effects.update(self.session.world(), delta_time, self.camera.camera().target);
+        self.update_kill_cam(delta_time);
+
+        // Feed this tick's fuel transfers (manual + automatic) into the beam effects
+        self.transfer_effects.update(delta_time, self.session.take_fuel_transfers());
+        self.vehicle_manager.clear_missing_target(self.session.world());
+        self.toasts.update(delta_time);
+        self.chat.update(delta_time);
+
+        // Low fuel / orbit decay / incoming bullet alerts for our rocket(s) and the satellites
+        let watched: Vec<EntityId> = self.session.active_rocket_id().into_iter().collect();
+        self.alerts.update(delta_time, self.session.world(), &watched);
+        self.alert_banner.update(delta_time, self.alerts.alerts());
+        self.threat_indicator.update(delta_time, self.session.world(), &watched);
+        self.damage_indicator.update(delta_time);
+
+        // Engine rumble follows the host's throttle while there's fuel to burn
+        let throttle = self
+            .session
+            .active_rocket_id()
+            .and_then(|id| self.session.world().get_rocket(id))
+            .filter(|rocket| rocket.current_fuel() > 0.0)
+            .map_or(0.0, |rocket| rocket.thrust_level());
+        self.haptics.update(delta_time, throttle);
 
         // Update camera to follow host rocket
-        if let Some(rocket_id) = self.active_rocket_id {
-            if let Some(rocket) = self.world.get_rocket(rocket_id) {
+        if let Some(cam) = &self.kill_cam {
+            self.camera.follow(cam.view_center());
+        } else if let Some((center, _)) = self.session.world().system_extent().filter(|_| self.system_view) {
+            self.camera.follow(center);
+        } else if let Some(rocket_id) = self.session.active_rocket_id() {
+            if let Some(rocket) = self.session.world().get_rocket(rocket_id) {
                 self.camera.set_center(rocket.position());
             }
         }
@@ -598,174 +689,203 @@ impl MultiplayerHost {
                 self.save_celebration_player_id = None;
             }
         }
+    }
 
-        // Update snapshot broadcast timer
-        self.snapshot_timer += delta_time;
-        if self.snapshot_timer >= SNAPSHOT_INTERVAL {
-            self.broadcast_snapshot();
-            self.snapshot_timer = 0.0;
+    /// Show, play and save whatever the session reported since last frame
+    fn handle_session_events(&mut self) {
+        for event in self.session.take_events() {
+            self.handle_session_event(event);
         }
     }
 
-    /// Receive and process packets from clients
-    fn receive_client_packets(&mut self) {
-        let mut buf = [0u8; 1024];
-
-        // Process all available packets (non-blocking)
-        loop {
-            match self.socket.recv_from(&mut buf) {
-                Ok((size, src_addr)) => {
-                    // Try to parse as input packet first
-                    if let Ok(input_packet) = bincode::deserialize::<ClientInputPacket>(&buf[..size]) {
-                        // This is a client input packet - apply it to their rocket
-                        self.apply_client_input(input_packet);
-
-                        // Update last seen time
-                        let mut clients = self.clients.lock().unwrap();
-                        if let Some(client) = clients.get_mut(&src_addr) {
-                            client.last_seen = get_time();
-                        }
-                        continue;
-                    }
-
-                    // Not an input packet, check if it's a join/keepalive packet
-                    let mut clients = self.clients.lock().unwrap();
-
-                    if !clients.contains_key(&src_addr) && self.next_player_id < 20 {
-                        // New client joining - try to parse join packet with player name
-                        let player_id = self.next_player_id;
-                        self.next_player_id += 1;
-
-                        // Try to deserialize as JoinPacket to get player name
-                        #[derive(Deserialize)]
-                        struct JoinPacket {
-                            player_name: String,
-                        }
-
-                        let player_name = if let Ok(join_packet) = bincode::deserialize::<JoinPacket>(&buf[..size]) {
-                            // Successfully parsed join packet with name
-                            if join_packet.player_name.trim().is_empty() {
-                                format!("Player {}", player_id) // Fallback if name is empty
-                            } else {
-                                join_packet.player_name
-                            }
-                        } else {
-                            // Fallback for old-style JOIN or keepalive packets
-                            format!("Player {}", player_id)
-                        };
-
-                        clients.insert(src_addr, ConnectedClient {
-                            addr: src_addr,
-                            player_id,
-                            last_seen: get_time(),
-                            player_name: player_name.clone(),
-                        });
-
-                        // Add player name to the names map
-                        self.player_names.insert(player_id, player_name.clone());
-
-                        log::info!("New client '{}' connected from {} assigned player_id {}", player_name, src_addr, player_id);
-
-                        // Spawn a rocket for this player at their designated angle
-                        drop(clients); // Drop the lock before spawning
-                        let spawn_position = Self::calculate_spawn_position(player_id);
-                        let mut client_rocket = Rocket::new(
-                            spawn_position,
-                            Vec2::new(0.0, 0.0),
-                            Self::get_player_color(player_id),
-                            GameConstants::ROCKET_BASE_MASS,
-                        );
-                        client_rocket.set_player_id(Some(player_id)); // Tag with player ID
-                        let client_rocket_id = self.world.add_rocket(client_rocket);
-                        log::info!("Spawned rocket {:?} for player {} at angle {} degrees",
-                            client_rocket_id, player_id, player_id * 5);
-
-                        return; // Exit early since we dropped the lock
-                    } else if let Some(client) = clients.get_mut(&src_addr) {
-                        // Existing client - update last seen time
-                        client.last_seen = get_time();
-                    }
+    fn handle_session_event(&mut self, event: HostSessionEvent) {
+        match event {
+            HostSessionEvent::Toast(text, color) => self.toasts.push(text, color),
+            HostSessionEvent::Chat(message) => {
+                self.chat.push(message.sender, message.text, Self::get_player_color(message.player_id));
+            }
+            HostSessionEvent::Staged(position) => self.camera.impulse(CameraImpulse::Staging, position),
+            // Damage flashes for hits on the host's rocket; hit markers for the host's shots
+            HostSessionEvent::Hit(hit) => {
+                if hit.player_id == Some(0) {
+                    self.damage_indicator.on_hit(hit.position, hit.source);
                 }
-                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                    // No more packets available
-                    break;
+                if hit.shooter_id.is_some() {
+                    self.camera.impulse(CameraImpulse::BulletImpact, hit.position);
                 }
-                Err(e) => {
-                    log::warn!("Error receiving UDP packet: {}", e);
-                    break;
+                if hit.shooter_player == Some(0) && hit.player_id != Some(0) {
+                    self.damage_indicator.on_hit_confirmed(hit.position);
                 }
             }
+            // Grade touchdowns: toast + best-landing stats in the profile
+            HostSessionEvent::Landed(event) => {
+                let planet_name = event.planet_name.as_deref().unwrap_or("Unknown");
+                let new_best = self.profile.record_landing(planet_name, &event.result);
+                self.toasts.push(event.result.toast_text(planet_name, new_best), event.result.grade.color());
+                if let Some(rocket) = self.session.world().get_rocket(event.rocket_id) {
+                    self.camera.impulse(CameraImpulse::Landing { vertical_speed: event.result.vertical_speed }, rocket.position());
+                }
+                self.haptics.trigger(HapticEvent::Landing { vertical_speed: event.result.vertical_speed });
+                if new_best {
+                    if let Err(e) = self.profile.save() {
+                        log::error!("Failed to save profile: {}", e);
+                    }
+                }
+            }
+            HostSessionEvent::Destroyed(destroyed) => {
+                self.camera.impulse(CameraImpulse::Explosion, destroyed.position);
+                if destroyed.player_id.unwrap_or(0) == 0 && destroyed.cause != world_bounds::ABORT_CAUSE {
+                    if destroyed.cause == "bullet" {
+                        self.haptics.trigger(HapticEvent::BulletHit);
+                    }
+                    self.haptics.trigger(HapticEvent::Explosion);
+                }
+            }
+            HostSessionEvent::KillCam { rocket_id, shot, impact, attacker } => self.start_kill_cam(rocket_id, shot, impact, attacker),
+            HostSessionEvent::IncomingGift(gift) => self.gift_prompt.incoming = gift,
+            HostSessionEvent::QuickSave(player_id) => self.quick_save(player_id),
+            HostSessionEvent::OriginShift(shift) => {
+                self.camera.shift_origin(shift);
+                self.history.shift_positions(shift);
+                self.damage_indicator.shift_positions(shift);
+                self.bullet_effects.shift_positions(shift);
+            }
         }
     }
 
-    /// Create GameSaveData snapshot from current world state
-    fn create_snapshot(&self) -> GameSaveData {
-        let mut save_data = GameSaveData::new();
-
-        // Save all planets with their IDs
-        save_data.planets = self.world.planets_with_ids()
-            .map(|(id, planet)| SavedPlanet::from_planet(id, planet))
-            .collect();
-
-        // Save all rockets with their IDs
-        save_data.rockets = self.world.rockets_with_ids()
-            .map(|(id, rocket)| SavedRocket::from_rocket(id, rocket))
-            .collect();
+    /// Write this match's recording to the replays folder (call when the host leaves)
+    pub fn finish_recording(&mut self) {
+        self.session.finish_recording();
+    }
 
-        // Save all satellites with their IDs
-        save_data.satellites = self.world.satellites_with_ids()
-            .map(|(id, satellite)| SavedSatellite::from_satellite(id, satellite))
-            .collect();
+    /// Archive snapshots, players and stats to a SQLite database for the rest of this session
+    /// (call after the world is set up; needs the `sqlite` feature)
+    pub fn open_world_database(&mut self, path: &str) {
+        self.session.open_world_database(path, self.current_save_name.as_deref());
+    }
 
-        // Save all bullets with their IDs
-        save_data.bullets = self.world.bullets_with_ids()
-            .map(|(id, bullet)| SavedBullet::from_bullet(id, bullet))
-            .collect();
+    /// Store a last snapshot before the host leaves
+    pub fn close_world_database(&mut self) {
+        self.session.close_world_database();
+    }
 
-        // Save player state (host is player 0)
-        save_data.player_id = Some(0);
-        save_data.active_rocket_id = self.active_rocket_id;
+    /// Announce the session to join menus on the local network
+    fn advertise_on_lan(&mut self, delta_time: f32) {
+        let Some(mut advertiser) = self.lan_advertiser.take() else {
+            return;
+        };
+        advertiser.update(delta_time, || LanAdvert {
+            name: format!("{}'s game", self.host_player_name),
+            map: self.session.map_name(),
+            players: self.session.client_count() as u32 + 1,
+            max_players: self.session.max_players(),
+            port: self.port,
+            pvp: self.session.arena_rule().is_some(),
+        });
+        self.lan_advertiser = Some(advertiser);
+    }
 
-        // Save player names (for network map display on clients)
-        save_data.player_names = self.player_names.clone();
+    /// Listen for remote console connections on `port` (operators log in with `password`)
+    pub fn start_rcon(&mut self, port: u16, password: String) {
+        match RconServer::start(port, password) {
+            Ok(rcon) => self.rcon = Some(rcon),
+            Err(e) => log::error!("Failed to start RCON: {}", e),
+        }
+    }
 
-        // Save camera state
-        save_data.camera = SavedCamera {
-            center: self.camera.camera().target.into(),
-            zoom: self.camera.zoom_level(),
+    fn handle_rcon_requests(&mut self) {
+        let requests: Vec<RconRequest> = match &self.rcon {
+            Some(rcon) => std::iter::from_fn(|| rcon.poll()).collect(),
+            None => return,
         };
-
-        save_data
+        for request in requests {
+            let result = self.run_rcon_command(&request.command);
+            if let Err(e) = &result {
+                log::warn!("RCON command {:?} failed: {}", request.command, e);
+            }
+            request.respond(result);
+        }
     }
 
-    /// Broadcast current game state snapshot to all connected clients
-    fn broadcast_snapshot(&self) {
-        // Create snapshot from current world state
-        let snapshot = self.create_snapshot();
-
-        // Serialize to bytes
-        match snapshot.to_bytes() {
-            Ok(bytes) => {
-                let clients = self.clients.lock().unwrap();
-                let client_count = clients.len();
-
-                if client_count > 0 {
-                    log::debug!("Broadcasting snapshot ({} bytes) to {} clients", bytes.len(), client_count);
-
-                    // Send to all connected clients
-                    for client in clients.values() {
-                        if let Err(e) = self.socket.send_to(&bytes, client.addr) {
-                            log::warn!("Failed to send snapshot to {}: {}", client.addr, e);
-                        }
-                    }
+    fn run_rcon_command(&mut self, command: &RconCommand) -> Result<String, String> {
+        match command {
+            RconCommand::Help => Ok(crate::networking::rcon::HELP_TEXT.to_string()),
+            RconCommand::Status => Ok(format!(
+                "{} players, session time {:.0}s, save {}{}",
+                self.session.player_names().len(),
+                self.session.session_time(),
+                self.current_save_name.as_deref().unwrap_or("(unsaved)"),
+                if self.paused { ", paused" } else { "" },
+            )),
+            RconCommand::ListPlayers => {
+                let mut lines = vec![format!("0  {}  (host)", self.session.player_label(0))];
+                for row in self.session.player_rows() {
+                    let role = match self.session.crew().pilot_for(row.player_id) {
+                        Some(pilot_id) => format!("  engineer for {}", pilot_id),
+                        None => String::new(),
+                    };
+                    lines.push(format!("{}  {}  {}  RTT {:.0} ms{}", row.player_id, row.name, row.addr, row.rtt * 1000.0, role));
                 }
+                Ok(format!("{} players\n{}", lines.len(), lines.join("\n")))
             }
-            Err(e) => {
-                log::error!("Failed to serialize snapshot: {}", e);
+            RconCommand::Kick { player, reason } => {
+                let player_id = self.session.find_client(player).ok_or_else(|| format!("No connected player '{}'", player))?;
+                let name = self.session.player_label(player_id);
+                self.session.kick_player(player_id, reason.as_deref().unwrap_or("Kicked by the server operator"));
+                Ok(format!("Kicked {} ({})", name, player_id))
+            }
+            RconCommand::Ban { player, reason } => {
+                let player_id = self.session.find_client(player).ok_or_else(|| format!("No connected player '{}'", player))?;
+                let name = self.session.player_label(player_id);
+                let addr = self.session.client_addr(player_id).map(|addr| addr.ip().to_string()).unwrap_or_default();
+                self.session.ban_player(player_id, reason.as_deref().unwrap_or("Banned by the server operator"));
+                Ok(format!("Banned {} ({}) at {}", name, player_id, addr))
+            }
+            RconCommand::Save { name } => {
+                let save_name = name
+                    .clone()
+                    .or_else(|| self.current_save_name.clone())
+                    .unwrap_or_else(|| format!("multiplayer_host_{}", get_time() as u64));
+                self.save_as(save_name.clone())?;
+                Ok(format!("Saved as {}", save_name))
+            }
+            RconCommand::ChangeMap { target } => {
+                let map = MapConfiguration::all_maps().into_iter().find(|map| map.name.eq_ignore_ascii_case(target));
+                if target == "new" {
+                    self.initialize_new_game();
+                    self.current_save_name = None;
+                } else if let Some(map) = map.filter(|_| !GameSaveData::multi_save_exists(target)) {
+                    // A map name starts a fresh world on that map (a save of the same name wins)
+                    self.session.set_map(Some(map));
+                    self.initialize_new_game();
+                    self.current_save_name = None;
+                } else {
+                    let save_data = GameSaveData::load_from_multi_file(target).map_err(|e| e.to_string())?;
+                    self.load_from_save(save_data, target.clone());
+                }
+                self.session.world_changed();
+                self.toasts.push(format!("World changed to {}", target), SKYBLUE);
+                Ok(format!("World changed to {}", target))
+            }
+            RconCommand::Broadcast { message } => {
+                self.session.broadcast_notice(&ServerNotice::Broadcast(message.clone()));
+                self.toasts.push(format!("[Server] {}", message), GOLD);
+                Ok(format!("Sent to {} clients", self.session.client_count()))
             }
         }
     }
 
+    /// The session's save data plus the host's camera, marked satellites and panels
+    fn create_save_file_data(&self) -> GameSaveData {
+        let mut save_data = self.session.save_data();
+        save_data.camera = SavedCamera {
+            center: self.camera.camera().target.into(),
+            zoom: self.camera.zoom_level(),
+        };
+        save_data.ui_state = Some(SavedUiState::capture(&self.game_info, &self.vehicle_manager, &self.marked_satellites));
+        save_data
+    }
+
     /// Save current game state to disk
     fn save_game(&mut self) {
         let save_name = if let Some(ref name) = self.current_save_name {
@@ -774,22 +894,23 @@ impl MultiplayerHost {
             format!("multiplayer_host_{}", get_time() as u64)
         };
 
-        let save_data = self.create_snapshot();
-
-        match save_data.save_to_multi_file(&save_name) {
-            Ok(_) => {
-                log::info!("Multiplayer game saved: {}", save_name);
-                self.current_save_name = Some(save_name);
-            }
-            Err(e) => {
-                log::error!("Failed to save multiplayer game: {}", e);
-            }
+        if let Err(e) = self.save_as(save_name) {
+            log::error!("Failed to save multiplayer game: {}", e);
         }
     }
 
-    /// Quick save triggered by F5 key - saves and shows "what a save!!" celebration
+    /// Save to saves/multi/<save_name>.sav and keep saving there
+    fn save_as(&mut self, save_name: String) -> Result<(), String> {
+        let save_data = self.create_save_file_data();
+        save_data.save_to_multi_file(&save_name).map_err(|e| e.to_string())?;
+        log::info!("Multiplayer game saved: {}", save_name);
+        self.current_save_name = Some(save_name);
+        Ok(())
+    }
+
+    /// Quick save for an F5 the session allowed - saves and shows "what a save!!" celebration
     fn quick_save(&mut self, player_id: u32) {
-        let save_data = self.create_snapshot();
+        let save_data = self.create_save_file_data();
 
         match save_data.save_to_multi_file("quicksave") {
             Ok(_) => {
@@ -806,12 +927,16 @@ impl MultiplayerHost {
         }
     }
 
+    /// Tell every client the game is over, so they leave now instead of timing out (call before dropping the host)
+    pub fn shutdown(&mut self) {
+        self.session.shutdown();
+    }
+
     fn draw_network_map(&mut self) {
         let screen_w = screen_width();
         let screen_h = screen_height();
-        let map_size = 700.0;
-        let map_x = screen_w / 2.0 - map_size / 2.0;
-        let map_y = screen_h / 2.0 - map_size / 2.0;
+        let (map_rect, list_rect) = SafeArea::current().network_map();
+        let (map_x, map_y, map_size) = (map_rect.x, map_rect.y, map_rect.w);
 
         // Semi-transparent overlay
         draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::new(0.0, 0.0, 0.0, 0.6));
@@ -837,7 +962,7 @@ impl MultiplayerHost {
         // Find Earth (the most massive planet)
         let mut earth_pos = Vec2::new(GameConstants::MAIN_PLANET_X, GameConstants::MAIN_PLANET_Y);
         let mut earth_mass = 0.0f32;
-        for planet in self.world.planets() {
+        for planet in self.session.world().planets() {
             if planet.mass() > earth_mass {
                 earth_mass = planet.mass();
                 earth_pos = planet.position();
@@ -877,7 +1002,7 @@ impl MultiplayerHost {
 
         // Find Moon position for Moon-centered rings
         let mut moon_pos = None;
-        for planet in self.world.planets() {
+        for planet in self.session.world().planets() {
             if planet.mass() != earth_mass {
                 // This is the Moon (not Earth)
                 moon_pos = Some(planet.position());
@@ -906,7 +1031,7 @@ impl MultiplayerHost {
         }
 
         // Draw planets
-        for planet in self.world.planets() {
+        for planet in self.session.world().planets() {
             let map_pos = world_to_map(planet.position());
             let planet_radius = planet.radius() * map_scale;
             let planet_radius_clamped = planet_radius.max(8.0);
@@ -920,7 +1045,7 @@ impl MultiplayerHost {
         }
 
         // Draw all player rockets (different colors per player)
-        for (rocket_id, rocket) in self.world.rockets_with_ids() {
+        for (rocket_id, rocket) in self.session.world().rockets_with_ids() {
             let map_pos = world_to_map(rocket.position());
             let rocket_size = 6.0;
 
@@ -937,16 +1062,21 @@ impl MultiplayerHost {
 
             // Label (show player name if available, otherwise P{id})
             if let Some(player_id) = rocket.player_id() {
-                let label = self.player_names.get(&player_id)
+                let label = self.session.player_names().get(&player_id)
                     .map(|name| name.clone())
                     .unwrap_or_else(|| format!("P{}", player_id));
                 draw_text(&label, map_pos.x - 10.0, map_pos.y - 10.0, 12.0, WHITE);
             }
         }
 
+        // Waypoints (ours and shared ones)
+        let rocket_pos = self.session.active_rocket_id().and_then(|id| self.session.world().get_rocket(id)).map(|rocket| rocket.position());
+        let map_bounds = Rect::new(map_x, map_y, map_size, map_size);
+        waypoint_markers::draw_map_waypoints(&self.session.waypoints().visible_to(Some(0)), world_to_map, map_bounds, rocket_pos);
+
         // Draw connection lines between satellites in range
         let satellite_transfer_range = GameConstants::SATELLITE_TRANSFER_RANGE;
-        let satellites: Vec<_> = self.world.satellites_with_ids().collect();
+        let satellites: Vec<_> = self.session.world().satellites_with_ids().collect();
 
         for i in 0..satellites.len() {
             for j in (i + 1)..satellites.len() {
@@ -972,8 +1102,21 @@ impl MultiplayerHost {
             }
         }
 
+        // Draw fitted satellite orbits (L toggles)
+        if self.vehicle_manager.visualization().show_satellite_orbits {
+            let planets: Vec<&Planet> = self.session.world().planets().collect();
+            for satellite in self.session.world().satellites() {
+                if let Some(ellipse) = VehicleManager::satellite_orbit_ellipse(satellite, &planets) {
+                    let map_points: Vec<Vec2> = ellipse.points(96).into_iter().map(world_to_map).collect();
+                    for pair in map_points.windows(2) {
+                        draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, 1.0, Color::new(0.0, 1.0, 0.0, 0.25));
+                    }
+                }
+            }
+        }
+
         // Draw satellites
-        let satellites: Vec<_> = self.world.satellites_with_ids().collect();
+        let satellites: Vec<_> = self.session.world().satellites_with_ids().collect();
         for (sat_id, satellite) in &satellites {
             let map_pos = world_to_map(satellite.position());
             let is_marked = self.marked_satellites.contains(sat_id);
@@ -996,112 +1139,15 @@ impl MultiplayerHost {
         }
 
         // Draw bullet trajectories (red lines showing curved path) - same red color for all players
-        let bullets: Vec<_> = self.world.bullets_with_ids().collect();
-        let rockets: Vec<_> = self.world.rockets_with_ids().collect();
+        let bullets: Vec<_> = self.session.world().bullets_with_ids().collect();
+        let rockets: Vec<_> = self.session.world().rockets_with_ids().collect();
         for (_bullet_id, bullet) in &bullets {
             let bullet_pos = bullet.position();
             let bullet_vel = bullet.velocity();
 
             // Predict bullet trajectory accounting for moving planets (especially Moon)
             // Use 600 steps (6x normal) to show longer trajectory in map
-            let prediction_steps = 600;
-            let dt = 0.1; // Time step for prediction
-            let mut predicted_positions = Vec::new();
-            let mut current_pos = bullet_pos;
-            let mut current_vel = bullet_vel;
-
-            // Create mutable copies of planet states (position, velocity, mass, radius)
-            // This allows us to simulate their motion during trajectory prediction
-            let mut planet_states: Vec<(Vec2, Vec2, f32, f32)> = self.world.planets()
-                .map(|p| (p.position(), p.velocity(), p.mass(), p.radius()))
-                .collect();
-
-            // Identify Earth (largest planet) for pinning - it doesn't move
-            let earth_index = planet_states
-                .iter()
-                .enumerate()
-                .max_by(|(_, a), (_, b)| a.3.partial_cmp(&b.3).unwrap())
-                .map(|(i, _)| i)
-                .unwrap_or(0);
-
-            for _ in 0..prediction_steps {
-                predicted_positions.push(current_pos);
-
-                // Step 1: Apply planet-to-planet gravity (e.g., Moon orbiting Earth)
-                if planet_states.len() >= 2 {
-                    for i in 0..planet_states.len() {
-                        if i == earth_index {
-                            continue; // Earth is pinned, doesn't move
-                        }
-
-                        let mut planet_acceleration = Vec2::ZERO;
-
-                        // Calculate gravity from all other planets
-                        for j in 0..planet_states.len() {
-                            if i == j {
-                                continue;
-                            }
-
-                            let (pos_i, _, mass_i, radius_i) = planet_states[i];
-                            let (pos_j, _, mass_j, _) = planet_states[j];
-
-                            let direction = pos_j - pos_i;
-                            let distance = direction.length();
-
-                            if distance > radius_i {
-                                // Use same gravity constant as actual physics (GameConstants::G = 100.0)
-                                let force_magnitude = (GameConstants::G * mass_i * mass_j) / (distance * distance);
-                                let force_direction = direction / distance;
-                                planet_acceleration += force_direction * (force_magnitude / mass_i);
-                            }
-                        }
-
-                        // Update planet velocity
-                        planet_states[i].1 += planet_acceleration * dt;
-                    }
-                }
-
-                // Step 2: Update planet positions based on their velocities
-                for i in 0..planet_states.len() {
-                    if i != earth_index {
-                        let vel = planet_states[i].1;
-                        planet_states[i].0 += vel * dt;
-                    }
-                }
-
-                // Step 3: Apply gravity from updated planet positions to bullet
-                // Use same gravity constant as actual physics
-                let mut total_accel = Vec2::ZERO;
-                for &(planet_pos, _, planet_mass, _) in &planet_states {
-                    let diff = planet_pos - current_pos;
-                    let distance = diff.length();
-                    if distance > 0.0 {
-                        let direction = diff / distance;
-                        let bullet_mass = 1.0; // Bullet mass
-                        let force_magnitude = (GameConstants::G * planet_mass * bullet_mass) / (distance * distance);
-                        let acceleration = direction * (force_magnitude / bullet_mass);
-                        total_accel += acceleration;
-                    }
-                }
-
-                // Step 4: Update bullet velocity and position
-                current_vel += total_accel * dt;
-                current_pos += current_vel * dt;
-
-                // Step 5: Check for collision with planets at their PREDICTED positions
-                // Only stop if bullet will actually hit planet when it arrives at this time
-                let mut hit_planet = false;
-                for &(planet_pos, _, _, planet_radius) in &planet_states {
-                    let distance = (planet_pos - current_pos).length();
-                    if distance < planet_radius + 5.0 {
-                        hit_planet = true;
-                        break;
-                    }
-                }
-                if hit_planet {
-                    break;
-                }
-            }
+            let predicted_positions = bullet_threats::predict_bullet_path(self.session.world(), bullet_pos, bullet_vel, 600);
 
             // Draw red trajectory line
             for i in 0..(predicted_positions.len() - 1) {
@@ -1162,9 +1208,9 @@ impl MultiplayerHost {
         }
 
         // Satellite list on the right side of the map
-        let list_x = map_x + map_size + 20.0;
+        let list_x = list_rect.x;
         let list_y = map_y + 60.0;
-        let list_width = 200.0;
+        let list_width = list_rect.w;
 
         // List background
         draw_rectangle(list_x, list_y - 10.0, list_width, map_size - 50.0, Color::new(0.0, 0.0, 0.0, 0.8));
@@ -1208,15 +1254,19 @@ impl MultiplayerHost {
 
     /// Render the game
     pub fn render(&mut self) {
+        self.sky.sync(self.session.world().cosmetic_seed());
+        self.sky.draw(self.session.world().origin().to_world(self.camera.camera().target), self.camera.zoom_level());
+
         // Set camera
         set_camera(self.camera.camera());
 
         // Render world
-        self.world.render();
+        self.strategic_view.render_world(self.session.world(), &self.camera);
+        self.bullet_effects.draw(self.camera.zoom_level());
 
         // Draw trajectory visualizations for all players' rockets with their colors
-        let all_planets: Vec<&Planet> = self.world.planets().collect();
-        for (id, rocket) in self.world.rockets_with_ids() {
+        let all_planets: Vec<&Planet> = self.session.world().planets().collect();
+        for (id, rocket) in self.session.world().rockets_with_ids() {
             if let Some(player_id) = rocket.player_id() {
                 let trajectory_color = Self::get_trajectory_color(player_id);
                 self.vehicle_manager.draw_visualizations_with_color(
@@ -1229,24 +1279,65 @@ impl MultiplayerHost {
             }
         }
 
+        // Draw fitted satellite orbit ellipses
+        let satellites: Vec<&Satellite> = self.session.world().satellites().collect();
+        self.vehicle_manager.draw_satellite_orbits(&satellites, &self.session.world().planets().collect::<Vec<_>>(), self.camera.zoom_level());
+
+        // Draw hover/target highlights
+        self.inspector.draw_highlights(self.session.world(), self.vehicle_manager.target(), self.camera.zoom_level());
+
+        // Draw active fuel transfer beams
+        self.transfer_effects.draw_beams(self.session.world(), self.camera.zoom_level());
+
+        // Own rocket screen position for the +fuel/s readout
+        let fuel_rate_target = self.session.active_rocket_id().and_then(|id| {
+            self.session.world().get_rocket(id)
+                .map(|rocket| (id, self.camera.world_to_screen(rocket.position())))
+        });
+
         // Store celebration rocket position for screen-space rendering
         let celebration_screen_pos = if let Some(player_id) = self.save_celebration_player_id {
             // Find the rocket belonging to this player
-            self.world.rockets_with_ids()
+            self.session.world().rockets_with_ids()
                 .find(|(_id, rocket)| rocket.player_id() == Some(player_id))
                 .map(|(_id, rocket)| self.camera.world_to_screen(rocket.position()))
         } else {
             None
         };
 
+        if let Some(cam) = &self.kill_cam {
+            let top_left = self.camera.screen_to_world(Vec2::ZERO);
+            let bottom_right = self.camera.screen_to_world(Vec2::new(screen_width(), screen_height()));
+            let visible = Rect::new(top_left.x, top_left.y, bottom_right.x - top_left.x, bottom_right.y - top_left.y);
+            kill_cam_overlay::draw_replay(cam, visible, self.camera.zoom_level());
+        }
+
         // Reset to default camera for UI
         set_default_camera();
+        self.strategic_view.draw_labels(self.session.world(), &self.camera);
+
+        if let Some((rocket_id, screen_pos)) = fuel_rate_target {
+            self.transfer_effects.draw_rocket_fuel_rate(rocket_id, screen_pos);
+        }
+        let rocket_pos = self.session.active_rocket_id().and_then(|id| self.session.world().get_rocket(id)).map(|rocket| rocket.position());
+        waypoint_markers::draw_waypoints(&self.session.waypoints().visible_to(Some(0)), &self.camera, rocket_pos);
+        self.toasts.draw();
+        self.chat.draw();
+        self.rocket_transfer.draw();
+        mission_clock_display::draw_mission_clock(self.session.session_time(), 1);
+        if let Some(cam) = &self.kill_cam {
+            kill_cam_overlay::draw_banner(cam);
+        }
+        self.threat_indicator.draw(self.session.world(), &self.camera);
+        self.damage_indicator.draw(&self.camera);
+        self.alert_banner.draw(self.session.world());
+        self.draw_gift_prompt();
 
         // Update and draw game info panels
-        if let Some(rocket_id) = self.active_rocket_id {
-            if let Some(rocket) = self.world.get_rocket(rocket_id) {
-                let all_planets: Vec<&Planet> = self.world.planets().collect();
-                let satellite_stats = self.world.get_satellite_network_stats();
+        if let Some(rocket_id) = self.session.active_rocket_id() {
+            if let Some(rocket) = self.session.world().get_rocket(rocket_id) {
+                let all_planets: Vec<&Planet> = self.session.world().planets().collect();
+                let satellite_stats = self.session.world().get_satellite_network_stats();
 
                 // Get selected planet for panels 2 and 3 based on reference body
                 let reference_body_idx = self.vehicle_manager.visualization().reference_body;
@@ -1274,18 +1365,39 @@ impl MultiplayerHost {
         }
 
         // Draw visualization HUD (shows T and G key status)
-        self.vehicle_manager.draw_visualization_hud(&self.world.planets().collect::<Vec<_>>());
+        self.vehicle_manager.draw_visualization_hud(&self.session.world().planets().collect::<Vec<_>>());
 
         // Show host status at bottom
         {
-            let clients = self.clients.lock().unwrap();
+            let links = self.session.client_links();
             draw_text(
-                &format!("HOST | Port: {} | Clients: {}", self.port, clients.len()),
+                &format!("HOST | Port: {} | Clients: {}", self.port, links.len()),
                 10.0,
                 screen_height() - 20.0,
                 20.0,
                 GREEN,
             );
+
+            // Connections panel: each client's adaptive snapshot rate and link stats
+            for (i, (player_id, player_name, link)) in links.iter().rev().enumerate() {
+                let tier = link.tier();
+                let radius = tier.interest_radius().map_or("all".to_string(), |r| format!("{:.0}", r));
+                draw_text(
+                    &format!(
+                        "P{} {} | {} Hz | RTT {:.0} ms | loss {:.0}% | radius {}",
+                        player_id,
+                        player_name,
+                        tier.rate_hz(self.session.rates().snapshot_hz()),
+                        link.rtt() * 1000.0,
+                        link.loss() * 100.0,
+                        radius,
+                    ),
+                    10.0,
+                    screen_height() - 44.0 - i as f32 * 18.0,
+                    16.0,
+                    LIGHTGRAY,
+                );
+            }
         }
 
         // Show "Press ENTER for controls" at top-right
@@ -1327,6 +1439,12 @@ impl MultiplayerHost {
             draw_text(text, text_x, text_y, text_size, Color::new(1.0, 0.9, 0.0, 1.0));
         }
 
+        // Hover tooltip
+        self.inspector.draw_tooltip(self.session.world(), self.vehicle_manager.target(), self.session.player_names());
+
+        // Connected players (4)
+        self.player_list.draw(&self.host_player_name, &self.session.player_rows(), self.session.max_players(), self.session.banned_count());
+
         // Draw quit confirmation popup if showing
         if self.show_quit_confirmation {
             self.draw_quit_confirmation();
@@ -1346,10 +1464,8 @@ impl MultiplayerHost {
     fn draw_quit_confirmation(&self) {
         let screen_w = screen_width();
         let screen_h = screen_height();
-        let popup_w = 400.0;
-        let popup_h = 200.0;
-        let popup_x = screen_w / 2.0 - popup_w / 2.0;
-        let popup_y = screen_h / 2.0 - popup_h / 2.0;
+        let popup = SafeArea::current().popup(400.0, 200.0);
+        let (popup_x, popup_y, popup_w, popup_h) = (popup.x, popup.y, popup.w, popup.h);
 
         // Semi-transparent overlay
         draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::new(0.0, 0.0, 0.0, 0.6));
@@ -1443,13 +1559,22 @@ impl MultiplayerHost {
         );
     }
 
+    fn draw_gift_prompt(&self) {
+        let targeted_satellite = match self.vehicle_manager.target() {
+            Some(EntityTarget::Satellite(id)) => Some(id),
+            _ => None,
+        };
+        if let Some(text) = self.gift_prompt.text(targeted_satellite, |id| self.session.player_label(id)) {
+            let width = measure_text(&text, None, 20, 1.0).width;
+            draw_text(&text, screen_width() / 2.0 - width / 2.0, 120.0, 20.0, SKYBLUE);
+        }
+    }
+
     fn draw_controls_popup(&self) {
         let screen_w = screen_width();
         let screen_h = screen_height();
-        let popup_w = 800.0;
-        let popup_h = 600.0;
-        let popup_x = screen_w / 2.0 - popup_w / 2.0;
-        let popup_y = screen_h / 2.0 - popup_h / 2.0;
+        let popup = SafeArea::current().popup(800.0, 600.0);
+        let (popup_x, popup_y, popup_w, popup_h) = (popup.x, popup.y, popup.w, popup.h);
 
         // Semi-transparent overlay
         draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::new(0.0, 0.0, 0.0, 0.5));
@@ -1478,21 +1603,29 @@ impl MultiplayerHost {
             ("SPACE", "Apply thrust"),
             ("A / LEFT", "Rotate left"),
             ("D / RIGHT", "Rotate right"),
-            ("Q", "Zoom in"),
-            ("E", "Zoom out"),
-            ("MOUSE WHEEL", "Zoom"),
+            ("Q / E / WHEEL", "Zoom in / out"),
+            ("HOME / END", "Zoom to rocket / whole system"),
+            ("SHIFT+1-6", "Zoom presets"),
             ("C", "Convert to satellite"),
+            ("Z", "Retrieve retired satellite"),
             ("P", "Pause/Unpause"),
+            ("O", "Gift targeted satellite"),
+            ("Y / N", "Accept / decline gift"),
+            ("B", "Drop waypoint (SHIFT shares)"),
         ];
 
         let controls_right = [
-            ("T", "Toggle trajectory"),
+            ("T", "Chat (ENTER sends)"),
+            ("SHIFT+T", "Toggle trajectory"),
             ("G", "Toggle gravity forces"),
+            ("L", "Toggle satellite orbits"),
             ("1", "Toggle rocket panel"),
             ("2", "Toggle planet panel"),
             ("3", "Toggle orbit panel"),
-            ("4", "Toggle controls panel"),
+            ("4", "Player list (K kick, Shift+K ban)"),
             ("5", "Toggle network panel"),
+            ("6", "Station ops: auto-refuel while landed"),
+            ("7", "Reset panel layout"),
             ("9", "Hide all panels"),
             ("0", "Show all panels"),
             ("F", "Save game"),
@@ -1556,8 +1689,44 @@ impl MultiplayerHost {
         );
     }
 
+    /// Set the physics and snapshot rates for this session
+    pub fn set_rates(&mut self, rates: HostRates) {
+        self.session.set_rates(rates);
+        self.game_info.set_tick_rates(Some((rates.physics_hz(), rates.snapshot_hz())));
+    }
+
+    pub fn rates(&self) -> HostRates {
+        self.session.rates()
+    }
+
+    /// Seconds per physics step at the configured physics rate
+    pub fn physics_timestep(&self) -> f32 {
+        self.session.rates().physics_timestep()
+    }
+
     /// Get connected client count
     pub fn client_count(&self) -> usize {
-        self.clients.lock().unwrap().len()
+        self.session.client_count()
+    }
+
+    /// Cap on the host plus clients, clamped to MIN_PLAYERS..=MAX_PLAYERS (players already in stay)
+    pub fn set_max_players(&mut self, max_players: u32) {
+        self.session.set_max_players(max_players);
+    }
+
+    pub fn max_players(&self) -> u32 {
+        self.session.max_players()
+    }
+
+    /// Status shown in Discord Rich Presence; the join address lets friends join from Discord
+    pub fn presence_info(&self) -> PresenceInfo {
+        PresenceInfo {
+            mode: "Hosting Online".to_string(),
+            map: None,
+            altitude: self.session.active_rocket_id().and_then(|id| self.session.world().rocket_altitude(id)),
+            player_count: self.session.client_count() as u32 + 1,
+            max_players: self.session.max_players(),
+            join_address: self.join_address.clone(),
+        }
     }
 }
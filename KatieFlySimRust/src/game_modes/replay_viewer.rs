@@ -0,0 +1,420 @@
+// Replay Viewer - Re-watch a recorded multiplayer match
+// Follow any player's rocket or fly a free camera; pause, seek and change speed
+// Photo mode (P) hides the HUD and drops camera keys for a cinematic path (C plays it)
+// The event timeline (H) lists the match's launches, landings and kills; picking one jumps the replay there
+
+use macroquad::prelude::*;
+
+use crate::entities::GameObject;
+use crate::systems::mission_clock;
+use crate::systems::{CameraKey, CameraPath, Easing, ReplayPlayback, SessionRecording, SessionTimeline};
+use crate::ui::{Camera, Sky, TimelinePanel};
+
+/// Seconds skipped by one seek key press
+const SEEK_STEP: f32 = 10.0;
+/// Playback speeds cycled with [ and ]
+const SPEEDS: [f32; 6] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0];
+/// Free camera pan speed (screen pixels per second, scaled by zoom)
+const PAN_SPEED: f32 = 600.0;
+/// Height of the seek bar along the bottom of the screen
+const SEEK_BAR_HEIGHT: f32 = 12.0;
+/// Seconds a photo mode message stays up
+const NOTICE_DURATION: f32 = 3.0;
+/// Seconds of lead-in when jumping to a timeline event
+const EVENT_LEAD: f32 = 2.0;
+
+/// Result from replay viewer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayViewerResult {
+    None,
+    ReturnToMenu,
+}
+
+/// Plays a recorded match back through the world renderer
+pub struct ReplayViewer {
+    playback: ReplayPlayback,
+    title: String,
+    camera: Camera,
+    sky: Sky,
+    followed_player: Option<u32>, // None = free camera
+    paused: bool,
+    speed_index: usize,
+    window_size: Vec2,
+    replay_path: String,
+    camera_path: CameraPath,
+    photo_mode: bool,
+    cinematic: bool,    // Camera driven by the path
+    key_easing: Easing, // Easing given to new keys
+    notice: Option<(String, f32)>,
+    timeline: SessionTimeline, // The match's events, timed from the start of the recording
+    timeline_panel: TimelinePanel,
+    jumped_to: Option<f32>, // Event the replay last jumped to (so holding a selection doesn't keep seeking)
+}
+
+impl ReplayViewer {
+    /// Open a recording file; errors name why it can't be played
+    pub fn load(path: &str, window_size: Vec2) -> Result<Self, String> {
+        let recording = SessionRecording::load_from_file(path).map_err(|e| e.to_string())?;
+        let playback = ReplayPlayback::new(recording)?;
+
+        let mut viewer = ReplayViewer {
+            playback,
+            title: SessionRecording::label_for(path),
+            camera: Camera::new(window_size),
+            sky: Sky::new(),
+            followed_player: None,
+            paused: false,
+            speed_index: 2,
+            window_size,
+            replay_path: path.to_string(),
+            camera_path: CameraPath::new(),
+            photo_mode: false,
+            cinematic: false,
+            key_easing: Easing::default(),
+            notice: None,
+            timeline: SessionTimeline::new(),
+            timeline_panel: TimelinePanel::new(),
+            jumped_to: None,
+        };
+        let start = viewer.playback.start_time();
+        let origin = viewer.playback.world().origin();
+        for event in viewer.playback.events() {
+            let position = origin.to_local(event.world_position());
+            viewer.timeline.record(event.time - start, event.kind, event.description.clone(), position);
+        }
+        match CameraPath::load_for(path) {
+            Ok(Some(camera_path)) => viewer.camera_path = camera_path,
+            Ok(None) => {}
+            Err(e) => log::warn!("Couldn't load camera path for {}: {}", path, e),
+        }
+        viewer.followed_player = viewer.players().first().copied();
+        if let Some(position) = viewer.followed_position() {
+            viewer.camera.set_center(position);
+        }
+        Ok(viewer)
+    }
+
+    /// Players with a rocket in the world right now, lowest ID first
+    fn players(&self) -> Vec<u32> {
+        let mut players: Vec<u32> = self.playback.world().rockets().filter_map(|rocket| rocket.player_id()).collect();
+        players.sort_unstable();
+        players.dedup();
+        players
+    }
+
+    fn followed_position(&self) -> Option<Vec2> {
+        let player_id = self.followed_player?;
+        self.playback
+            .world()
+            .rockets()
+            .find(|rocket| rocket.player_id() == Some(player_id))
+            .map(|rocket| rocket.position())
+    }
+
+    fn player_label(&self, player_id: u32) -> String {
+        self.playback
+            .player_names()
+            .get(&player_id)
+            .cloned()
+            .unwrap_or_else(|| format!("Player {}", player_id))
+    }
+
+    /// Follow the next player after the current one (from free camera, the first)
+    fn cycle_player(&mut self) {
+        let players = self.players();
+        let next = match self.followed_player {
+            Some(current) => players.iter().find(|id| **id > current).or(players.first()),
+            None => players.first(),
+        };
+        self.followed_player = next.copied();
+    }
+
+    fn seek(&mut self, time: f32) {
+        let shift = self.playback.seek(time);
+        self.camera.shift_origin(shift);
+        self.timeline.shift_positions(shift);
+    }
+
+    /// Jump a little before the selected timeline event and look at where it happens
+    fn jump_to_selected_event(&mut self) {
+        let Some(event) = self.timeline_panel.selected_event(&self.timeline) else {
+            self.jumped_to = None;
+            return;
+        };
+        if self.jumped_to == Some(event.time) {
+            return;
+        }
+        let time = event.time;
+        self.jumped_to = Some(time);
+        self.seek(self.playback.start_time() + time - EVENT_LEAD);
+        // Looked up again: the seek may have moved the floating origin
+        if let Some(event) = self.timeline_panel.selected_event(&self.timeline) {
+            self.followed_player = None;
+            self.cinematic = false;
+            self.camera.set_center(event.position);
+        }
+    }
+
+    fn show_notice(&mut self, text: String) {
+        self.notice = Some((text, NOTICE_DURATION));
+    }
+
+    /// Key the camera where it is now, at the current replay time
+    fn drop_camera_key(&mut self) {
+        let position = self.playback.world().origin().to_world(self.camera.camera().target);
+        let time = self.playback.time();
+        self.camera_path.insert(CameraKey::new(time, position, self.camera.zoom_level(), self.key_easing));
+        let elapsed = time - self.playback.start_time();
+        self.show_notice(format!("Camera key at {} ({} keys)", mission_clock::format_clock(elapsed), self.camera_path.keys().len()));
+    }
+
+    /// Put the camera where the path says for the current replay time
+    fn apply_camera_path(&mut self) {
+        let Some(pose) = self.camera_path.sample(self.playback.time()) else {
+            return;
+        };
+        let center = self.playback.world().origin().to_local(pose.position);
+        self.camera.set_target_zoom(pose.zoom);
+        self.camera.set_target_center(center);
+        self.camera.snap_to_target();
+    }
+
+    fn handle_photo_input(&mut self) {
+        if is_key_pressed(KeyCode::K) {
+            self.drop_camera_key();
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            if let Some(key) = self.camera_path.remove_nearest(self.playback.time()) {
+                let elapsed = key.time - self.playback.start_time();
+                self.show_notice(format!("Removed key at {}", mission_clock::format_clock(elapsed)));
+            }
+        }
+        if is_key_pressed(KeyCode::G) {
+            self.key_easing = self.key_easing.next();
+            self.show_notice(format!("New keys ease: {}", self.key_easing.label()));
+        }
+        if is_key_pressed(KeyCode::Enter) {
+            let notice = match self.camera_path.save_for(&self.replay_path) {
+                Ok(file_path) => format!("Camera path saved to {}", file_path),
+                Err(e) => format!("Couldn't save camera path: {}", e),
+            };
+            self.show_notice(notice);
+        }
+    }
+
+    fn seek_bar_rect(&self) -> Rect {
+        Rect::new(20.0, self.window_size.y - 30.0, self.window_size.x - 40.0, SEEK_BAR_HEIGHT)
+    }
+
+    pub fn handle_input(&mut self) -> ReplayViewerResult {
+        if is_key_pressed(KeyCode::Escape) {
+            return ReplayViewerResult::ReturnToMenu;
+        }
+
+        if is_key_pressed(KeyCode::Space) {
+            self.paused = !self.paused;
+        }
+        if is_key_pressed(KeyCode::P) {
+            self.photo_mode = !self.photo_mode;
+        }
+        if is_key_pressed(KeyCode::C) {
+            if self.camera_path.is_empty() {
+                self.show_notice("No camera keys yet (P for photo mode, K to key)".to_string());
+            } else {
+                self.cinematic = !self.cinematic;
+                if self.cinematic {
+                    self.followed_player = None;
+                }
+            }
+        }
+        if self.photo_mode {
+            self.handle_photo_input();
+        }
+        if is_key_pressed(KeyCode::H) && !self.photo_mode {
+            self.timeline_panel.toggle();
+        }
+        if self.timeline_panel.is_visible() && !self.photo_mode {
+            let duration = self.playback.end_time() - self.playback.start_time();
+            self.timeline_panel.handle_input(&self.timeline, duration);
+            self.jump_to_selected_event();
+        }
+        if is_key_pressed(KeyCode::Tab) {
+            self.cycle_player();
+            self.cinematic = false;
+        }
+        if is_key_pressed(KeyCode::F) {
+            self.cinematic = false;
+            if self.followed_player.is_some() {
+                self.followed_player = None;
+            } else {
+                self.cycle_player();
+            }
+        }
+        // [ ] step through events while the timeline is open
+        if is_key_pressed(KeyCode::LeftBracket) && !self.timeline_panel.is_visible() {
+            self.speed_index = self.speed_index.saturating_sub(1);
+        }
+        if is_key_pressed(KeyCode::RightBracket) && !self.timeline_panel.is_visible() {
+            self.speed_index = (self.speed_index + 1).min(SPEEDS.len() - 1);
+        }
+
+        // Seeking (arrow keys or a click on the seek bar)
+        if is_key_pressed(KeyCode::Left) {
+            self.seek(self.playback.time() - SEEK_STEP);
+        }
+        if is_key_pressed(KeyCode::Right) {
+            self.seek(self.playback.time() + SEEK_STEP);
+        }
+        if is_key_pressed(KeyCode::Home) {
+            self.seek(self.playback.start_time());
+        }
+        if is_mouse_button_pressed(MouseButton::Left) && !self.timeline_panel.contains_mouse() {
+            let bar = self.seek_bar_rect();
+            let mouse = Vec2::from(mouse_position());
+            if mouse.x >= bar.x && mouse.x <= bar.x + bar.w && (mouse.y - (bar.y + bar.h / 2.0)).abs() <= bar.h {
+                let fraction = (mouse.x - bar.x) / bar.w;
+                let start = self.playback.start_time();
+                self.seek(start + (self.playback.end_time() - start) * fraction);
+            }
+        }
+
+        // Free camera panning (WASD); any pan leaves the followed player
+        let mut pan = Vec2::ZERO;
+        if is_key_down(KeyCode::W) {
+            pan.y -= 1.0;
+        }
+        if is_key_down(KeyCode::S) {
+            pan.y += 1.0;
+        }
+        if is_key_down(KeyCode::A) {
+            pan.x -= 1.0;
+        }
+        if is_key_down(KeyCode::D) {
+            pan.x += 1.0;
+        }
+        if pan != Vec2::ZERO {
+            self.followed_player = None;
+            self.cinematic = false;
+            let target = self.camera.camera().target + pan * PAN_SPEED * self.camera.zoom_level() * get_frame_time();
+            self.camera.set_target_center(target);
+        }
+
+        // Zoom (Q = zoom in, E = zoom out, mouse wheel)
+        if self.cinematic {
+            return ReplayViewerResult::None;
+        }
+        if is_key_down(KeyCode::Q) {
+            self.camera.zoom_held(-1.0, get_frame_time());
+        }
+        if is_key_down(KeyCode::E) {
+            self.camera.zoom_held(1.0, get_frame_time());
+        }
+        let mouse_wheel = mouse_wheel().1;
+        if mouse_wheel != 0.0 {
+            self.camera.zoom_wheel(mouse_wheel);
+        }
+
+        ReplayViewerResult::None
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        if !self.paused {
+            let shift = self.playback.advance(delta_time * SPEEDS[self.speed_index]);
+            self.camera.shift_origin(shift);
+            self.timeline.shift_positions(shift);
+        }
+
+        if let Some((_, remaining)) = &mut self.notice {
+            *remaining -= delta_time;
+            if *remaining <= 0.0 {
+                self.notice = None;
+            }
+        }
+
+        self.sky.sync(self.playback.world().cosmetic_seed());
+        if self.cinematic {
+            self.apply_camera_path();
+        } else if let Some(position) = self.followed_position() {
+            self.camera.set_center(position);
+        }
+        self.camera.update(delta_time);
+    }
+
+    pub fn render(&self) {
+        self.sky.draw(self.playback.world().origin().to_world(self.camera.camera().target), self.camera.zoom_level());
+        set_camera(self.camera.camera());
+        self.playback.world().render();
+        set_default_camera();
+
+        if self.photo_mode {
+            self.render_photo_overlay();
+            return;
+        }
+
+        // Name tags over each player's rocket
+        for rocket in self.playback.world().rockets() {
+            if let Some(player_id) = rocket.player_id() {
+                let screen = self.camera.world_to_screen(rocket.position());
+                let label = self.player_label(player_id);
+                let dims = measure_text(&label, None, 16, 1.0);
+                draw_text(&label, screen.x - dims.width / 2.0, screen.y - 20.0, 16.0, rocket.color());
+            }
+        }
+
+        // Status line
+        let time = self.playback.time() - self.playback.start_time();
+        let duration = self.playback.end_time() - self.playback.start_time();
+        let clock = mission_clock::format_clock;
+        let mut status = format!("REPLAY  {}  {} / {}  x{}", self.title, clock(time), clock(duration), SPEEDS[self.speed_index]);
+        if self.paused {
+            status.push_str("  (paused)");
+        } else if self.playback.is_finished() {
+            status.push_str("  (end)");
+        }
+        draw_text(&status, 20.0, 30.0, 22.0, WHITE);
+
+        let view = match self.followed_player {
+            Some(player_id) => format!("Following: {}", self.player_label(player_id)),
+            None if self.cinematic => format!("Camera path ({} keys)", self.camera_path.keys().len()),
+            None => "Free camera".to_string(),
+        };
+        draw_text(&view, 20.0, 55.0, 20.0, Color::new(0.8, 0.8, 1.0, 1.0));
+
+        // Seek bar
+        let bar = self.seek_bar_rect();
+        let fraction = if duration > 0.0 { (time / duration).clamp(0.0, 1.0) } else { 0.0 };
+        draw_rectangle(bar.x, bar.y, bar.w, bar.h, Color::new(0.2, 0.2, 0.2, 0.8));
+        draw_rectangle(bar.x, bar.y, bar.w * fraction, bar.h, Color::new(0.9, 0.6, 0.2, 0.9));
+
+        // Camera keys along the seek bar
+        for key in self.camera_path.keys() {
+            let key_fraction = if duration > 0.0 { ((key.time - self.playback.start_time()) / duration).clamp(0.0, 1.0) } else { 0.0 };
+            draw_rectangle(bar.x + bar.w * key_fraction - 1.0, bar.y - 3.0, 2.0, bar.h + 6.0, SKYBLUE);
+        }
+
+        let help = "Space: pause | Left/Right: -/+10s | Home: restart | [ ]: speed | Tab: next player | F: free camera | WASD: pan | Q/E: zoom | H: events | P: photo mode | C: camera path | Esc: back";
+        draw_text(help, 20.0, bar.y - 10.0, 16.0, LIGHTGRAY);
+        self.timeline_panel.draw(&self.timeline, duration);
+        self.render_notice();
+    }
+
+    /// Photo mode shows only the world; one hint line while composing, nothing while a path plays
+    fn render_photo_overlay(&self) {
+        if self.cinematic && !self.paused {
+            return;
+        }
+        let hint = format!(
+            "PHOTO  {} keys | K: key camera | Backspace: remove nearest | G: easing ({}) | Enter: save path | C: play path | P: exit",
+            self.camera_path.keys().len(),
+            self.key_easing.label()
+        );
+        draw_text(&hint, 20.0, self.window_size.y - 20.0, 16.0, Color::new(1.0, 1.0, 1.0, 0.7));
+        self.render_notice();
+    }
+
+    fn render_notice(&self) {
+        if let Some((text, _)) = &self.notice {
+            draw_text(text, 20.0, 80.0, 18.0, SKYBLUE);
+        }
+    }
+}
@@ -2,16 +2,39 @@
 // Integrates all systems for playable game
 
 use macroquad::prelude::*;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use crate::entities::{GameObject, Planet, Rocket};
+use crate::entities::{GameObject, Planet, Rocket, Rover, Satellite};
 use crate::game_constants::GameConstants;
 use crate::map_config::{MapConfiguration, orbit_calculator};
-use crate::save_system::{GameSaveData, SavedCamera, SavedPlanet, SavedRocket, SavedSatellite};
-use crate::systems::{World, VehicleManager, EntityId};
-use crate::ui::{Camera, GameInfoDisplay};
+use crate::networking::PresenceInfo;
+use crate::physics::CollisionRules;
+use crate::player::Gamepads;
+use crate::save_system::{local_profiles, BoardKind, GameSaveData, Leaderboard, PlayerProfile, SavedCamera, SavedPlanet, SavedRocket, SavedSatellite, SavedUiState, SavedWaypoint};
+#[cfg(feature = "hot-reload")]
+use crate::systems::hot_reload::{self, HotReloader, MapReload, ReloadEvent};
+use crate::systems::{
+    World, VehicleManager, EntityId, DestroyedRocketInfo, AlertEngine, SampleMission, SurfaceDeposits, PlanetRings, bullet_threats,
+    FuelTransferEvent, SessionEventKind, SessionTimeline, Waypoints,
+    EntityTarget, RoutePlanner, RouteStop, ChallengeDate, ChallengeOutcome, DailyChallenge, LandingEvent, LandingZones,
+    Scenario, ScenarioOutcome, ScenarioProgress, ScenarioRun, AlertKind, AssistGuide, NetworkAnomaly, BoundsConfig, Convoy, ConvoyEvent,
+    ConservationMonitor, ContractEvent, ContractKind, CoverageCache, HapticEvent, Haptics, KillCam, LaunchWindow, Market, MissionClock, OrbitTrails, PlayerInput, StateHistory, TrailWindow,
+    BatchCommand, Controllers, Debrief, FlightLog, RemoteControl, SatelliteSelection, StationEvent, StationOps,
+};
+use crate::systems::{launch_window, mission_clock};
+use crate::systems::world_bounds::{self, ABORT_CAUSE, ABORT_FUEL_FRACTION};
+use crate::ui::{AlertBanner, BatchAction, BulletEffects, Camera, CameraCommand, CameraImpulse, DamageIndicator, EntityInspector, FuelTransferEffects, GameInfoDisplay, MarketAction, MarketPanel, NetworkMapSearch, NotificationCenter, RocketTransferReadout, SafeArea, Anchor, SatelliteBatchBar, SearchJump, Sky, Slider, StrategicView, ThreatIndicator, TimelinePanel, ToastManager};
+use crate::ui::camera::{self, CRAFT_VIEW_ZOOM, ZOOM_PRESETS};
+use crate::ui::{assist_overlay, challenge_panel, conservation_overlay, convoy_panel, coverage_heatmap, kill_cam_overlay, mission_clock_display, relay_overlay, route_overlay, waypoint_markers};
 use crate::utils::vector_helper;
 
+/// Profile key for this mode's HUD panel layout
+const HUD_LAYOUT_KEY: &str = "single_player";
+/// Controls popup size (two columns), shrunk on small windows
+const CONTROLS_POPUP_SIZE: (f32, f32) = (800.0, 720.0);
+/// Auto-warp hands control back this many game seconds before a launch window
+const WINDOW_ARRIVAL_SECONDS: f32 = 1.0;
+
 /// Single player game result
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SinglePlayerResult {
@@ -20,13 +43,33 @@ pub enum SinglePlayerResult {
     Quit,
 }
 
+/// How the debrief's retry starts the session over (a loaded save retries as a new game on its map)
+#[derive(Debug, Clone)]
+pub enum RetryPlan {
+    NewGame(MapConfiguration),
+    Challenge(DailyChallenge),
+    Scenario(Scenario, MapConfiguration),
+}
+
+/// What the camera shows; when it changes the camera snaps instead of sweeping across the map
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CameraSubject {
+    TimelineEvent(f32), // Event time
+    Satellite(EntityId),
+    System, // Zoom-to-fit of every body
+    KillCam,
+    Rover,
+    Rocket(EntityId),
+}
+
 /// Single player game mode
 pub struct SinglePlayerGame {
     world: World,
     camera: Camera,
     info_display: GameInfoDisplay,
     vehicle_manager: VehicleManager,
-    game_time: f32,
+    clock: MissionClock, // Mission elapsed time and time warp
+    window_warp: bool,   // Warp is chasing the next Moon launch window (Y)
     is_paused: bool,
     show_controls: bool,
 
@@ -41,14 +84,91 @@ pub struct SinglePlayerGame {
 
     // Network map view
     show_network_map: bool,
+    coverage: Option<CoverageCache>, // Transfer coverage heatmap (8), recomputed while it's open
+    haptics: Haptics,    // Controller rumble (off when no gamepad is connected)
+    gamepads: Gamepads,  // Controller in slot 0 flies alongside the keyboard
+    rumble_slider: Slider, // Rumble strength, in the controls popup
     marked_satellites: HashSet<EntityId>,
+    map_search: NetworkMapSearch,
+    map_selection: SatelliteSelection, // Shift-click or drag a box on the network map; the batch bar acts on it
+    batch_bar: SatelliteBatchBar,
+    picking_hub: bool,                 // Fuel to hub clicked: the next satellite clicked is the hub
+    map_drag_start: Option<Vec2>,      // Screen point a drag-box started from
+    camera_focus: Option<EntityId>, // Satellite the camera shows instead of the rocket (search jump)
+    camera_subject: Option<CameraSubject>,
+    system_view: bool, // End pressed: show the whole system until Home
+    escape_offered: bool, // The abort-to-spawn hint was shown for the current escape
+    history: StateHistory, // Recent positions, for the kill cam
+    orbit_trails: OrbitTrails, // Session-long positions, for trails in the strategic view
+    trail_window: TrailWindow,
+    kill_cam: Option<KillCam>, // Replay of the shot that destroyed our rocket
+    kill_cam_return_zoom: f32, // Zoom to go back to once the replay ends
+    waypoints: Waypoints,           // B drops one at the rocket, right-click on the map places/removes
+    route: RoutePlanner,            // Shift+right-click on the map or J (targeted planet) adds stops
+    convoy: Convoy,                 // Tanker runs (N hands the rocket over to the targeted satellite)
+    remote: Option<RemoteControl>,  // Shift+N flies the targeted rocket over the satellite relays
+    station_ops: StationOps,        // Landed rockets refuelling themselves (6)
+
+    // Station fuel prices and hauling contracts (M); credits live in the profile
+    market: Market,
+    market_panel: MarketPanel,
+
+    // Session event log and its scrubber panel (H)
+    timeline: SessionTimeline,
+    timeline_panel: TimelinePanel,
+    flight_log: FlightLog, // Telemetry for the debrief shown back at the menu
+    active_rocket_was_landed: bool, // Detects launches
 
     // Save celebration (F5 quick save)
     save_celebration_timer: f32,  // Time remaining for "what a save!!" text
+    hardcore_saves: bool,         // Saving needs a satellite uplink or a landing
+
+    // Fuel transfer beams, +fuel/s readout and hum
+    transfer_effects: FuelTransferEffects,
+    strategic_view: StrategicView, // Icons, clusters and labels when zoomed far out
+    sky: Sky, // Stars and nebulae from the world's cosmetic seed
+    bullet_effects: BulletEffects, // Tracers, impact flashes and predicted arcs
+    conservation: Option<ConservationMonitor>, // Energy/momentum drift overlay (F8), measured only while open
+    rocket_transfer: RocketTransferReadout, // Held transfer key: fuel to the nearest rocket
+
+    // Landing grade toasts and persistent best-landing stats
+    toasts: ToastManager,
+    notifications: NotificationCenter, // Fuel network anomalies, with a jump to the satellite
+    profile: PlayerProfile,
+    controls: PlayerInput, // Standard keys with the profile's bindings applied
+
+    // Hover tooltips and click-to-target in the world view
+    inspector: EntityInspector,
+
+    // Low fuel / orbit decay / incoming bullet warnings
+    alerts: AlertEngine,
+    alert_banner: AlertBanner,
+
+    // Directional arrows for bullets heading at our rocket(s)
+    threat_indicator: ThreatIndicator,
+    damage_indicator: DamageIndicator, // Edge flashes for hits taken, markers for hits landed
+
+    // Sample-return mission from the map's surface sites
+    samples: SampleMission,
+    landing_zones: LandingZones, // The map's precision landing targets and the player's mark
+
+    // Surface rover deployed from the landed rocket, and the fuel deposits it can mine
+    rover: Option<Rover>,
+    deposits: SurfaceDeposits,
+
+    // Daily challenge attempt (None in a normal game)
+    challenge: Option<DailyChallenge>,
+    challenge_outcome: Option<ChallengeOutcome>,
+    scenario: Option<ScenarioRun>, // Community scenario attempt
+    assist_guide: AssistGuide, // Tutorial overlay data while a gravity assist objective is current
 
     // Map configuration
     current_map: MapConfiguration,
     spawn_planet_id: Option<EntityId>, // Which planet to spawn on
+
+    // Dev mode: picks up edits to the running map's file and constants.ron
+    #[cfg(feature = "hot-reload")]
+    hot_reload: HotReloader,
 }
 
 impl SinglePlayerGame {
@@ -57,14 +177,22 @@ impl SinglePlayerGame {
     }
 
     pub fn new_with_map(window_size: Vec2, map: MapConfiguration) -> Self {
-        let info_display = GameInfoDisplay::new();
+        let profile = PlayerProfile::load_or_default();
+        let mut info_display = GameInfoDisplay::new();
+        if let Some(layout) = profile.hud_layout(HUD_LAYOUT_KEY) {
+            info_display.apply_layout(layout);
+        }
+
+        let mut camera = Camera::new(window_size);
+        camera.set_shake_enabled(profile.screen_shake);
 
         SinglePlayerGame {
             world: World::new(),
-            camera: Camera::new(window_size),
+            camera,
             info_display,
             vehicle_manager: VehicleManager::new(),
-            game_time: 0.0,
+            clock: MissionClock::new(),
+            window_warp: false,
             is_paused: false,
             show_controls: false,
             selected_thrust_level: 0.0, // Start at 0% thrust
@@ -73,20 +201,94 @@ impl SinglePlayerGame {
             last_auto_save: 0.0,
             auto_save_interval: 60.0, // Auto-save every 60 seconds
             show_network_map: false,
+            coverage: None,
+            haptics: Haptics::disconnected(profile.rumble_intensity),
+            gamepads: Gamepads::disconnected(),
+            rumble_slider: Slider::new(profile.rumble_intensity),
             marked_satellites: HashSet::new(),
+            map_search: NetworkMapSearch::new(),
+            map_selection: SatelliteSelection::new(),
+            batch_bar: SatelliteBatchBar::new(),
+            picking_hub: false,
+            map_drag_start: None,
+            camera_focus: None,
+            camera_subject: None,
+            system_view: false,
+            escape_offered: false,
+            history: StateHistory::new(),
+            orbit_trails: OrbitTrails::new(),
+            trail_window: TrailWindow::Off,
+            kill_cam: None,
+            kill_cam_return_zoom: 1.0,
+            waypoints: Waypoints::new(),
+            route: RoutePlanner::new(),
+            convoy: Convoy::new(),
+            remote: None,
+            station_ops: StationOps::new(),
+            market: Market::new(),
+            market_panel: MarketPanel::new(),
+            timeline: SessionTimeline::new(),
+            flight_log: FlightLog::new(&map.name),
+            timeline_panel: TimelinePanel::new(),
+            active_rocket_was_landed: false,
             save_celebration_timer: 0.0,
+            hardcore_saves: false,
+            transfer_effects: FuelTransferEffects::new(),
+            strategic_view: StrategicView::new(),
+            sky: Sky::new(),
+            bullet_effects: BulletEffects::new(profile.bullet_view),
+            conservation: None,
+            rocket_transfer: RocketTransferReadout::new(),
+            toasts: ToastManager::new(),
+            notifications: NotificationCenter::new(),
+            controls: PlayerInput::standard().with_bindings(&profile.key_bindings).with_gamepad(Some(0)),
+            profile,
+            inspector: EntityInspector::new(),
+            alerts: AlertEngine::new(),
+            alert_banner: AlertBanner::new(),
+            threat_indicator: ThreatIndicator::new(),
+            damage_indicator: DamageIndicator::new(),
+            samples: SampleMission::default(),
+            landing_zones: LandingZones::default(),
+            rover: None,
+            deposits: SurfaceDeposits::default(),
+            challenge: None,
+            challenge_outcome: None,
+            scenario: None,
+            assist_guide: AssistGuide::new(),
             current_map: map,
             spawn_planet_id: None,
+            #[cfg(feature = "hot-reload")]
+            hot_reload: HotReloader::new(),
         }
     }
 
     /// Initialize a new game with default setup
     pub fn initialize_new_game(&mut self) {
         self.world.clear_all();
-        self.game_time = 0.0;
+        self.clock = MissionClock::new();
+        self.timeline.clear();
+        self.flight_log = FlightLog::new(&self.current_map.name);
+        self.history.clear();
+        self.orbit_trails.clear();
+        self.bullet_effects.clear();
+        if let Some(monitor) = &mut self.conservation {
+            monitor.reset();
+        }
+        self.kill_cam = None;
 
         log::info!("Initializing new game with map: {}", self.current_map.name);
 
+        let mut tidal_rules = self.world.tidal_rules();
+        tidal_rules.enabled = self.current_map.tidal_breakup;
+        self.world.set_tidal_rules(tidal_rules);
+        self.apply_map_physics();
+        self.world.set_cosmetic_seed(Sky::fresh_seed());
+        self.samples = SampleMission::from_map(&self.current_map);
+        self.landing_zones = LandingZones::from_map(&self.current_map);
+        self.deposits = SurfaceDeposits::from_map(&self.current_map);
+        self.rover = None;
+
         // Calculate initial states for all celestial bodies
         let initial_states = orbit_calculator::calculate_initial_states(
             &self.current_map,
@@ -107,6 +309,7 @@ impl SinglePlayerGame {
             planet.set_velocity(state.velocity);
             planet.set_name(body_config.name.clone());
             planet.set_pinned(body_config.is_pinned);
+            planet.set_terrain(body_config.terrain.as_ref().map(|t| t.to_terrain()));
 
             log::info!("Creating {}: pos=({:.1}, {:.1}), radius={:.1}, mass={:.1}, velocity=({:.2}, {:.2}), pinned={}",
                 body_config.name, state.position.x, state.position.y,
@@ -122,12 +325,248 @@ impl SinglePlayerGame {
             }
         }
 
+        self.market = Market::from_world(&self.world, &self.current_map.name);
+
         // Spawn rocket on designated planet
         self.spawn_rocket();
 
         log::info!("New game initialized with {} celestial bodies", self.current_map.celestial_bodies.len());
     }
 
+    /// Start the daily challenge: its seeded map, with the rocket's fuel fixed at the challenge amount
+    pub fn start_daily_challenge(&mut self, challenge: DailyChallenge) {
+        self.current_map = challenge.map.clone();
+        self.initialize_new_game();
+        if let Some(rocket) = self.world.get_active_rocket_mut() {
+            rocket.set_fuel(challenge.starting_fuel);
+        }
+        log::info!("Daily challenge {}: {}", challenge.date.label(), challenge.description());
+        self.toasts.push(challenge.description(), Color::new(1.0, 0.8, 0.3, 1.0));
+        self.challenge = Some(challenge);
+        self.challenge_outcome = None;
+    }
+
+    fn challenge_running(&self) -> bool {
+        self.challenge.is_some() && self.challenge_outcome.is_none()
+    }
+
+    /// Start a scenario on its map (passed to `new_with_map`), with its starting fuel and satellites
+    pub fn start_scenario(&mut self, scenario: Scenario) {
+        self.initialize_new_game();
+        if let Some(fuel) = scenario.start.fuel {
+            if let Some(rocket) = self.world.get_active_rocket_mut() {
+                rocket.set_fuel(fuel);
+            }
+        }
+        scenario.place_satellites(&mut self.world);
+        log::info!("Scenario '{}' started on {}", scenario.name, scenario.map);
+        self.toasts.push(format!("{}: {}", scenario.name, scenario.objectives[0].describe()), Color::new(0.8, 0.6, 1.0, 1.0));
+        self.scenario = Some(ScenarioRun::new(scenario, &self.world));
+    }
+
+    fn refuel_allowed(&self) -> bool {
+        !self.challenge_running() && self.scenario.as_ref().is_none_or(ScenarioRun::allows_refuel)
+    }
+
+    /// Toast scenario progress and record a finished run's time
+    fn report_scenario_progress(&mut self, progress: Vec<ScenarioProgress>) {
+        let Some(run) = &self.scenario else {
+            return;
+        };
+        let objectives = &run.scenario().objectives;
+        for step in progress {
+            match step {
+                ScenarioProgress::ObjectiveDone(i) if i + 1 < objectives.len() => {
+                    let text = format!("Objective {}/{} done - next: {}", i + 1, objectives.len(), objectives[i + 1].describe());
+                    self.toasts.push(text, GREEN);
+                }
+                ScenarioProgress::ObjectiveDone(_) => {}
+                ScenarioProgress::Complete => {
+                    let Some(ScenarioOutcome::Complete { time, under_par }) = run.outcome() else {
+                        continue;
+                    };
+                    let name = run.scenario().name.clone();
+                    let new_best = self.profile.record_scenario_time(&name, *time);
+                    let today = ChallengeDate::today().label();
+                    Leaderboard::record_shared(BoardKind::ScenarioTime, &name, &run.scenario().map, &local_profiles::active(), *time, &today);
+                    let par = match under_par {
+                        Some(true) => " - under par!",
+                        Some(false) => " - over par",
+                        None => "",
+                    };
+                    self.toasts.push(format!("{} complete in {:.0}s{}", name, time, par), GREEN);
+                    log::info!("Scenario '{}' complete in {:.1}s", name, time);
+                    if new_best {
+                        if let Err(e) = self.profile.save() {
+                            log::error!("Failed to save profile: {}", e);
+                        }
+                    }
+                }
+                ScenarioProgress::Failed(reason) => {
+                    self.toasts.push(format!("Scenario failed - {}", reason), RED);
+                }
+            }
+        }
+    }
+
+    /// Score a touchdown inside a landing zone; returns true if it's a new best for one of the map's zones
+    fn score_zone_landing(&mut self, event: &LandingEvent) -> bool {
+        let Some(landing) = self.landing_zones.score_landing(&self.world, event) else {
+            return false;
+        };
+        if let Some(progress) = self.scenario.as_mut().map(|run| run.on_zone_landing(&landing)) {
+            self.report_scenario_progress(progress);
+        }
+        // The player's own mark can go anywhere, so only the map's zones keep bests
+        let new_best = !landing.player_marked && self.profile.record_zone_score(&self.current_map.name, &landing.zone, landing.score);
+        if !landing.player_marked {
+            let board = format!("Zone {}", landing.zone);
+            Leaderboard::record_shared(BoardKind::ZoneScore, &board, &self.current_map.name, &local_profiles::active(), landing.score, &ChallengeDate::today().label());
+        }
+        let text = format!("{}: {:.0} from center, {:.0} pts{}", landing.zone, landing.distance, landing.score, if new_best { " - new best!" } else { "" });
+        self.toasts.push(text, Color::new(1.0, 0.55, 0.1, 1.0));
+        log::info!("Landed {:.0} from the center of {} ({:.1} pts)", landing.distance, landing.zone, landing.score);
+        new_best
+    }
+
+    /// Score a landing on the challenge target (other bodies don't count)
+    fn score_challenge_landing(&mut self, event: &LandingEvent) {
+        let Some(challenge) = self.challenge.as_ref().filter(|_| self.challenge_outcome.is_none()) else {
+            return;
+        };
+        if event.planet_name.as_deref() != Some(challenge.target_body.as_str()) {
+            return;
+        }
+        let fuel_left = self.world.get_rocket(event.rocket_id).map(|rocket| rocket.current_fuel()).unwrap_or(0.0);
+        let outcome = match challenge.score(&event.result, fuel_left, self.clock.elapsed()) {
+            Some(score) => {
+                let share = challenge.score_string(score, self.clock.elapsed());
+                let date = challenge.date.label();
+                macroquad::miniquad::window::clipboard_set(&share);
+                if let Err(e) = Self::append_daily_score(&share) {
+                    log::error!("Failed to record daily score: {}", e);
+                }
+                let new_best = self.profile.record_daily_score(&date, score);
+                let board = format!("Daily {}", date);
+                Leaderboard::record_shared(BoardKind::DailyScore, &board, &challenge.map.name, &local_profiles::active(), score as f32, &date);
+                if new_best {
+                    if let Err(e) = self.profile.save() {
+                        log::error!("Failed to save profile: {}", e);
+                    }
+                }
+                let text = if new_best { format!("Challenge complete: {} pts - new best today!", score) } else { format!("Challenge complete: {} pts", score) };
+                self.toasts.push(text, GREEN);
+                log::info!("{}", share);
+                ChallengeOutcome::Scored { score, share }
+            }
+            None => {
+                self.toasts.push("Challenge failed - crashed on the target".to_string(), RED);
+                ChallengeOutcome::Failed("crashed on the target".to_string())
+            }
+        };
+        self.challenge_outcome = Some(outcome);
+    }
+
+    /// Keep a local history of shared score strings
+    fn append_daily_score(share: &str) -> std::io::Result<()> {
+        use std::io::Write;
+        std::fs::create_dir_all(local_profiles::saves_dir())?;
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(local_profiles::saves_path("daily_scores.txt"))?;
+        writeln!(file, "{}", share)
+    }
+
+    /// Use the current map's gravity model, bullet speed, rings and arena (new games and loaded saves)
+    fn apply_map_physics(&mut self) {
+        let gravity_model = self.current_map.gravity_model;
+        self.world.gravity_simulator_mut().set_model(gravity_model);
+        self.vehicle_manager.set_gravity_model(gravity_model);
+        self.assist_guide.set_gravity_model(gravity_model);
+        self.world.set_bullet_speed(self.current_map.tunables.bullet_speed());
+        self.world.set_rings(PlanetRings::from_map(&self.current_map));
+        self.world.set_arena(self.current_map.arena_bounds());
+        self.world.set_debris_enabled(true);
+        log::info!("Gravity model: {}", gravity_model.name());
+    }
+
+    /// Bring edits to the running map's file and constants.ron into the world
+    #[cfg(feature = "hot-reload")]
+    fn apply_hot_reloads(&mut self, delta_time: f32) {
+        for event in self.hot_reload.poll(delta_time, &self.current_map.name) {
+            match event {
+                ReloadEvent::Map(mut map) => {
+                    map.tunables = hot_reload::overlay_tunables(&map.tunables, self.hot_reload.overrides());
+                    match hot_reload::plan_map_reload(&self.current_map, &map) {
+                        MapReload::Restart => {
+                            log::info!("Hot reload: bodies of '{}' changed, restarting the map", map.name);
+                            self.current_map = *map;
+                            self.initialize_new_game();
+                            self.toasts.push("Map reloaded (bodies changed - restarted)", SKYBLUE);
+                        }
+                        MapReload::Update { rederive } => {
+                            self.current_map = *map;
+                            self.update_bodies_from_map(&rederive);
+                            self.apply_map_physics();
+                            self.apply_tunables_to_rockets();
+                            let text = format!("Map reloaded ({} orbits re-derived)", rederive.len());
+                            log::info!("Hot reload: {}", text);
+                            self.toasts.push(text, SKYBLUE);
+                        }
+                    }
+                }
+                ReloadEvent::Constants => {
+                    self.current_map.tunables = hot_reload::overlay_tunables(&self.current_map.tunables, self.hot_reload.overrides());
+                    self.apply_map_physics();
+                    self.apply_tunables_to_rockets();
+                    log::info!("Hot reload: {} applied {:?}", hot_reload::CONSTANTS_FILE, self.hot_reload.overrides());
+                    self.toasts.push(format!("{} reloaded", hot_reload::CONSTANTS_FILE), SKYBLUE);
+                }
+                ReloadEvent::Failed(e) => {
+                    log::error!("Hot reload failed: {}", e);
+                    self.toasts.push("Hot reload failed (see log)", RED);
+                }
+            }
+        }
+    }
+
+    /// Rebuild each map body in place (matched by name), keeping its motion unless it's in `rederive`
+    #[cfg(feature = "hot-reload")]
+    fn update_bodies_from_map(&mut self, rederive: &[usize]) {
+        let initial_states = orbit_calculator::calculate_initial_states(&self.current_map, GameConstants::G);
+        let origin = self.world.origin();
+        for (i, body_config) in self.current_map.celestial_bodies.iter().enumerate() {
+            let existing = self
+                .world
+                .planets_with_ids()
+                .find(|(_, planet)| planet.name() == Some(body_config.name.as_str()) && !planet.is_fragment())
+                .map(|(id, planet)| (id, planet.position(), planet.velocity()));
+            let Some((planet_id, mut position, mut velocity)) = existing else {
+                continue; // Broken up by tides or otherwise gone
+            };
+            if rederive.contains(&i) {
+                position = origin.to_local(initial_states[i].position.as_dvec2());
+                velocity = initial_states[i].velocity;
+            }
+
+            let mut planet = Planet::new(position, body_config.radius, body_config.mass, body_config.color);
+            planet.set_velocity(velocity);
+            planet.set_name(body_config.name.clone());
+            planet.set_pinned(body_config.is_pinned);
+            planet.set_terrain(body_config.terrain.as_ref().map(|t| t.to_terrain()));
+            self.world.add_planet_with_id(planet_id, planet);
+        }
+    }
+
+    /// Give every rocket the current hull mass and tank size
+    #[cfg(feature = "hot-reload")]
+    fn apply_tunables_to_rockets(&mut self) {
+        let rocket_ids: Vec<EntityId> = self.world.rockets_with_ids().map(|(id, _)| id).collect();
+        for rocket_id in rocket_ids {
+            if let Some(rocket) = self.world.get_rocket_mut(rocket_id) {
+                self.current_map.tunables.apply_to_rocket(rocket);
+            }
+        }
+    }
+
     /// Spawn a new rocket on the spawn planet at its CURRENT position with CURRENT velocity
     /// Returns the rocket ID if successful
     fn spawn_rocket(&mut self) -> Option<EntityId> {
@@ -137,8 +576,9 @@ impl SinglePlayerGame {
                 let planet_position = spawn_planet.position();
                 let planet_velocity = spawn_planet.velocity();
 
-                // Calculate spawn position 200 pixels above planet surface
-                let spawn_distance = spawn_planet.radius() + 200.0;
+                // Calculate spawn position above the planet surface (200 units unless the map says otherwise)
+                let tunables = &self.current_map.tunables;
+                let spawn_distance = spawn_planet.surface_radius_at(0.0) + tunables.spawn_altitude();
                 let rocket_spawn_position = planet_position + Vec2::new(spawn_distance, 0.0);
 
                 // Rocket inherits planet's CURRENT velocity for stable orbit
@@ -152,12 +592,13 @@ impl SinglePlayerGame {
                     rocket_spawn_position,
                     rocket_spawn_velocity,
                     WHITE,
-                    GameConstants::ROCKET_BASE_MASS,
+                    tunables.rocket_base_mass(),
                 );
+                tunables.apply_to_rocket(&mut rocket);
 
                 // Set rocket to 100% fuel for better mass and gravity pull
-                rocket.set_fuel(GameConstants::ROCKET_MAX_FUEL);
-                log::info!("Rocket spawned with 100% fuel ({} kg)", GameConstants::ROCKET_MAX_FUEL);
+                rocket.set_fuel(rocket.max_fuel());
+                log::info!("Rocket spawned with 100% fuel ({} kg)", rocket.max_fuel());
 
                 let rocket_id = self.world.add_rocket(rocket);
 
@@ -179,6 +620,11 @@ impl SinglePlayerGame {
         log::info!("Game loaded from save: {}", save_name);
     }
 
+    /// Show a message to the player (e.g. that a damaged save was swapped for an autosave)
+    pub fn notify(&mut self, text: String, color: Color) {
+        self.toasts.push(text, color);
+    }
+
     /// Save current game state
     pub fn save_game(&self, save_name: &str) -> Result<(), Box<dyn std::error::Error>> {
         let save_data = self.create_save_data();
@@ -198,26 +644,63 @@ impl SinglePlayerGame {
 
     /// Load game state from a snapshot (works for both save files and network packets)
     fn load_from_snapshot(&mut self, snapshot: GameSaveData) {
-        // Clear existing world
+        // Clear existing world (a loaded game is never a challenge attempt)
         self.world.clear_all_entities();
-
-        // Restore game time
-        self.game_time = snapshot.game_time;
+        self.challenge = None;
+        self.challenge_outcome = None;
+        self.scenario = None;
+
+        // Restore game time (the timeline only covers this session)
+        self.clock = MissionClock::starting_at(snapshot.game_time);
+        self.timeline.clear();
+        self.flight_log = FlightLog::new(&self.current_map.name);
+        self.history.clear();
+        self.orbit_trails.clear();
+        self.bullet_effects.clear();
+        if let Some(monitor) = &mut self.conservation {
+            monitor.reset();
+        }
+        self.kill_cam = None;
 
         // Save counts for logging before consuming vectors
         let planet_count = snapshot.planets.len();
         let rocket_count = snapshot.rockets.len();
         let satellite_count = snapshot.satellites.len();
 
+        // Positions below are relative to the saved floating origin
+        self.world.set_origin(snapshot.origin.into());
+
         // Restore all planets with their original IDs
         for saved_planet in snapshot.planets {
             let (id, planet) = saved_planet.to_planet();
             self.world.add_planet_with_id(id, planet);
         }
 
-        // Restore all rockets with their original IDs
+        // Restore map configuration
+        if let Some(map_name) = snapshot.map_name {
+            // Try to find the map in all available maps (built-in + custom)
+            let all_maps = MapConfiguration::all_maps();
+            self.current_map = all_maps
+                .into_iter()
+                .find(|m| m.name == map_name)
+                .unwrap_or_else(|| {
+                    log::warn!("Map '{}' not found, using default", map_name);
+                    MapConfiguration::earth_moon()
+                });
+            log::info!("Restored map: {}", map_name);
+        }
+        self.apply_map_physics();
+        if snapshot.arena.is_some() {
+            self.world.set_arena(snapshot.arena); // Saved bounds follow where the arena was centered
+        }
+        self.world.set_cosmetic_seed(snapshot.cosmetic_seed);
+
+        // Restore all rockets with their original IDs (sized for the map before refilling their saved fuel)
         for saved_rocket in snapshot.rockets {
-            let (id, rocket) = saved_rocket.to_rocket();
+            let fuel = saved_rocket.fuel;
+            let (id, mut rocket) = saved_rocket.to_rocket();
+            self.current_map.tunables.apply_to_rocket(&mut rocket);
+            rocket.set_fuel(fuel);
             self.world.add_rocket_with_id(id, rocket);
         }
 
@@ -231,22 +714,44 @@ impl SinglePlayerGame {
         self.world.set_active_rocket(snapshot.active_rocket_id);
 
         // Restore camera
+        self.system_view = false;
         self.camera.set_center(snapshot.camera.center.into());
         self.camera.set_target_zoom(snapshot.camera.zoom);
+        self.camera.snap_to_target();
 
-        // Restore map configuration
-        if let Some(map_name) = snapshot.map_name {
-            // Try to find the map in all available maps (built-in + custom)
-            let all_maps = MapConfiguration::all_maps();
-            self.current_map = all_maps
-                .into_iter()
-                .find(|m| m.name == map_name)
-                .unwrap_or_else(|| {
-                    log::warn!("Map '{}' not found, using default", map_name);
-                    MapConfiguration::earth_moon()
-                });
-            log::info!("Restored map: {}", map_name);
+        // Terrain isn't in the save format; regenerate it from the map by planet name
+        for body_config in &self.current_map.celestial_bodies {
+            let Some(terrain) = &body_config.terrain else {
+                continue;
+            };
+            let planet_id = self.world.planets_with_ids()
+                .find(|(_, p)| p.name() == Some(body_config.name.as_str()))
+                .map(|(id, _)| id);
+            if let Some(planet) = planet_id.and_then(|id| self.world.get_planet_mut(id)) {
+                planet.set_terrain(Some(terrain.to_terrain()));
+            }
+        }
+
+        // Marked satellites, panels and visualization toggles (older saves keep the current ones)
+        if let Some(ui_state) = &snapshot.ui_state {
+            ui_state.apply(&self.world, &mut self.info_display, &mut self.vehicle_manager, &mut self.marked_satellites);
+        } else {
+            self.marked_satellites.clear();
         }
+        self.map_selection.clear();
+        self.picking_hub = false;
+        self.waypoints = Waypoints::from_list(snapshot.waypoints.iter().map(SavedWaypoint::to_waypoint).collect());
+        self.route.clear();
+        self.convoy = Convoy::new(); // Tanker runs aren't saved; the tankers stay behind as plain rockets
+        self.remote = None;
+        self.station_ops.clear();
+        self.market = Market::from_world(&self.world, &self.current_map.name); // Cargo and contracts neither
+
+        // Sample progress, deposits, the rover and the player's landing mark aren't saved; they restart with the map
+        self.samples = SampleMission::from_map(&self.current_map);
+        self.landing_zones = LandingZones::from_map(&self.current_map);
+        self.deposits = SurfaceDeposits::from_map(&self.current_map);
+        self.rover = None;
 
         log::info!(
             "Loaded snapshot: {} planets, {} rockets, {} satellites at time {:.1}s",
@@ -261,7 +766,7 @@ impl SinglePlayerGame {
     /// This snapshot can be used for both save files and network packets (multiplayer)
     fn create_save_data(&self) -> GameSaveData {
         let mut save_data = GameSaveData::new();
-        save_data.game_time = self.game_time;
+        save_data.game_time = self.clock.elapsed();
 
         // Save all planets with their IDs
         save_data.planets = self.world.planets_with_ids()
@@ -290,6 +795,12 @@ impl SinglePlayerGame {
 
         // Save map configuration
         save_data.map_name = Some(self.current_map.name.clone());
+        save_data.origin = self.world.origin().into();
+        save_data.arena = self.world.arena();
+        save_data.cosmetic_seed = self.world.cosmetic_seed();
+
+        save_data.ui_state = Some(SavedUiState::capture(&self.info_display, &self.vehicle_manager, &self.marked_satellites));
+        save_data.waypoints = self.waypoints.list().iter().map(SavedWaypoint::from_waypoint).collect();
 
         log::info!(
             "Created snapshot: {} planets, {} rockets, {} satellites, map: {}",
@@ -302,8 +813,21 @@ impl SinglePlayerGame {
         save_data
     }
 
+    /// Under hardcore rules, why the active rocket can't save right now
+    fn save_refusal(&self) -> Option<String> {
+        if !self.hardcore_saves {
+            return None;
+        }
+        let rocket_id = self.world.active_rocket_id()?;
+        self.world.check_save_uplink(rocket_id).err()
+    }
+
     /// Quick save triggered by F5 key - saves and shows "what a save!!" celebration
     fn quick_save(&mut self) {
+        if let Some(reason) = self.save_refusal() {
+            self.toasts.push(reason, ORANGE);
+            return;
+        }
         match self.save_game("quicksave") {
             Ok(_) => {
                 log::info!("Quick save successful");
@@ -318,56 +842,137 @@ impl SinglePlayerGame {
         }
     }
 
+    /// Export the current game as JSON for save editors (F6); it shows up in the saves menu as quicksave.json
+    fn export_json(&mut self) {
+        if let Some(reason) = self.save_refusal() {
+            self.toasts.push(reason, ORANGE);
+            return;
+        }
+        let path = local_profiles::saves_path("quicksave.json");
+        match self.create_save_data().export_schema(&path) {
+            Ok(()) => self.toasts.push(format!("Exported {}", path), GREEN),
+            Err(e) => {
+                log::error!("Failed to export game: {}", e);
+                self.toasts.push("Export failed".to_string(), RED);
+            }
+        }
+    }
+
     /// Handle input for game controls
     pub fn handle_input(&mut self) -> SinglePlayerResult {
+        // The network map search box takes the keyboard while it has focus
+        if self.show_network_map {
+            let was_typing = self.map_search.is_focused();
+            self.map_search.refresh(&self.world);
+            self.map_selection.retain_existing(&self.world);
+            if let Some(jump) = self.map_search.handle_input(&self.world) {
+                self.jump_to_satellite(jump);
+            }
+            if was_typing || self.map_search.is_focused() {
+                return SinglePlayerResult::Continue;
+            }
+        }
+
         // Check for escape to return to menu or close popups
         if is_key_pressed(KeyCode::Escape) {
             if self.show_controls {
                 self.show_controls = false;
                 self.is_paused = false;
+            } else if self.show_network_map && (self.picking_hub || !self.map_selection.is_empty()) {
+                // Back out of the hub pick, then the selection, before closing the map
+                if !std::mem::take(&mut self.picking_hub) {
+                    self.map_selection.clear();
+                }
             } else if self.show_network_map {
                 self.show_network_map = false;
             } else {
+                self.transfer_effects.clear();
+                self.toasts.clear();
+                self.notifications.clear();
+                self.alerts.clear();
+                self.alert_banner.clear();
+                self.threat_indicator.clear();
+                self.damage_indicator.clear();
+                self.haptics.stop();
                 return SinglePlayerResult::ReturnToMenu;
             }
         }
 
+        // HUD panels take the mouse first (drag, resize, collapse)
+        let mouse_on_hud = !(self.show_controls || self.show_network_map) && self.info_display.handle_mouse();
+        if self.info_display.take_layout_changed() {
+            self.profile.save_hud_layout(HUD_LAYOUT_KEY, self.info_display.layout());
+        }
+        if is_key_pressed(KeyCode::Key7) {
+            self.info_display.reset_layout();
+        }
+
+        // Notification buttons: Jump shows the satellite (or targets anything else)
+        let on_notification = !(self.show_controls || self.show_network_map) && self.notifications.contains_mouse();
+        if on_notification {
+            match self.notifications.handle_mouse() {
+                Some(EntityTarget::Satellite(satellite_id)) => self.jump_to_satellite(SearchJump { satellite_id, move_camera: true }),
+                Some(target) => self.vehicle_manager.set_target(Some(target)),
+                None => {}
+            }
+        }
+
+        // Hover picking and click-to-target in the world view (suppressed while overlays are open)
+        if self.show_controls || self.show_network_map || mouse_on_hud || on_notification || self.timeline_panel.contains_mouse() {
+            self.inspector.clear();
+        } else {
+            self.inspector.update(&self.world, &self.camera);
+            if is_mouse_button_pressed(MouseButton::Left) {
+                if let Some(hovered) = self.inspector.hovered() {
+                    self.vehicle_manager.toggle_target(hovered);
+                    log::info!("Target: {:?}", self.vehicle_manager.target());
+                }
+            }
+        }
+
         // Toggle controls menu with Enter key
         if is_key_pressed(KeyCode::Enter) {
             self.show_controls = !self.show_controls;
             self.is_paused = self.show_controls; // Pause when showing controls
         }
 
+        // Rumble strength slider in the controls popup (saved when released)
+        if self.show_controls {
+            if let Some(intensity) = self.rumble_slider.handle_input(Self::rumble_slider_rect()) {
+                self.haptics.set_intensity(intensity);
+                self.profile.rumble_intensity = intensity;
+                if let Err(e) = self.profile.save() {
+                    log::error!("Failed to save profile: {}", e);
+                }
+            }
+        }
+
         // Handle mouse click for controls button, popup, and network map
         if is_mouse_button_pressed(MouseButton::Left) {
             let mouse_pos = mouse_position();
-            let screen_w = screen_width();
-            let screen_h = screen_height();
-
-            // Controls button in top-right corner (40x30 button with 10px margin)
-            let button_x = screen_w - 50.0;
-            let button_y = 10.0;
-            let button_w = 40.0;
-            let button_h = 30.0;
 
-            // Check if click is on the button first
-            let clicked_button = mouse_pos.0 >= button_x && mouse_pos.0 <= button_x + button_w &&
-                                 mouse_pos.1 >= button_y && mouse_pos.1 <= button_y + button_h;
+            // Check if click is on the controls button first
+            let clicked_button = Self::controls_button_rect().contains(Vec2::from(mouse_pos));
 
             if clicked_button {
                 self.show_controls = !self.show_controls;
                 self.is_paused = self.show_controls; // Pause when showing controls
                 log::info!("Controls button clicked, show_controls: {}", self.show_controls);
-            } else if self.show_controls {
+            } else if self.show_controls && Self::shake_toggle_rect().contains(Vec2::from(mouse_pos)) {
+                let enabled = !self.camera.shake_enabled();
+                self.camera.set_shake_enabled(enabled);
+                self.profile.screen_shake = enabled;
+                if let Err(e) = self.profile.save() {
+                    log::error!("Failed to save profile: {}", e);
+                }
+            } else if self.show_controls && Self::bullet_toggle_rects().iter().any(|rect| rect.contains(Vec2::from(mouse_pos))) {
+                self.toggle_bullet_option(Vec2::from(mouse_pos));
+            } else if self.show_controls && !self.rumble_slider.is_dragging() {
                 // Only check "click outside to close" if we didn't click the button
-                let popup_x = screen_w / 2.0 - 200.0;
-                let popup_y = screen_h / 2.0 - 250.0;
-                let popup_w = 400.0;
-                let popup_h = 500.0;
+                let popup = SafeArea::current().popup(CONTROLS_POPUP_SIZE.0, CONTROLS_POPUP_SIZE.1);
 
                 // Check if click is inside the popup
-                let clicked_inside = mouse_pos.0 >= popup_x && mouse_pos.0 <= popup_x + popup_w &&
-                                     mouse_pos.1 >= popup_y && mouse_pos.1 <= popup_y + popup_h;
+                let clicked_inside = popup.contains(Vec2::from(mouse_pos));
 
                 // Close if clicking outside the popup
                 if !clicked_inside {
@@ -375,16 +980,27 @@ impl SinglePlayerGame {
                     self.is_paused = false;
                     log::info!("Clicked outside popup, closing controls");
                 }
+            } else if self.show_network_map
+                && !self.map_selection.is_empty()
+                && self.batch_bar.contains(SafeArea::current().network_map().0, Vec2::from(mouse_pos))
+            {
+                let action = self.batch_bar.click(SafeArea::current().network_map().0, Vec2::from(mouse_pos));
+                self.run_batch_action(action);
             } else if self.show_network_map {
                 // Handle clicks on satellites in the network map
-                let map_size = 700.0;
-                let map_x = screen_w / 2.0 - map_size / 2.0;
-                let map_y = screen_h / 2.0 - map_size / 2.0;
+                let (map_rect, list_rect) = SafeArea::current().network_map();
+                let (map_x, map_y, map_size) = (map_rect.x, map_rect.y, map_rect.w);
 
                 // Check if click is in satellite list
-                let list_x = map_x + map_size + 20.0;
+                let list_x = list_rect.x;
                 let list_y = map_y + 60.0;
-                let list_width = 200.0;
+                let list_width = list_rect.w;
+
+                // Search box above the list
+                if self.map_search.contains(Vec2::from(mouse_pos), list_x, map_y, list_width) {
+                    self.map_search.focus();
+                    return SinglePlayerResult::Continue;
+                }
 
                 if mouse_pos.0 >= list_x && mouse_pos.0 <= list_x + list_width {
                     // Calculate which satellite was clicked based on Y position
@@ -392,98 +1008,133 @@ impl SinglePlayerGame {
                     let sat_index = (y_offset / 20.0).floor() as usize;
 
                     // Get satellite at this index
-                    let satellites: Vec<_> = self.world.satellites_with_ids().collect();
-                    if sat_index < satellites.len() {
-                        let (sat_id, _) = satellites[sat_index];
-
-                        // Toggle marked status
-                        if self.marked_satellites.contains(&sat_id) {
-                            self.marked_satellites.remove(&sat_id);
-                            log::info!("Unmarked satellite {}", sat_id);
-                        } else {
-                            self.marked_satellites.insert(sat_id);
-                            log::info!("Marked satellite {}", sat_id);
-                        }
+                    let satellites = self.network_map_list();
+                    if y_offset >= 0.0 && sat_index < satellites.len() {
+                        self.map_satellite_clicked(satellites[sat_index]);
                     }
                 }
 
                 // Check if click is on a satellite in the map itself
-                let mut earth_pos = Vec2::new(GameConstants::MAIN_PLANET_X, GameConstants::MAIN_PLANET_Y);
-                let mut earth_mass = 0.0f32;
-                for planet in self.world.planets() {
-                    if planet.mass() > earth_mass {
-                        earth_mass = planet.mass();
-                        earth_pos = planet.position();
-                    }
-                }
+                let map_origin = self.network_map_origin();
 
                 let map_world_radius = 50000.0;
                 let map_center = Vec2::new(map_x + map_size / 2.0, map_y + map_size / 2.0);
                 let map_scale = (map_size * 0.45) / map_world_radius;
 
                 let world_to_map = |world_pos: Vec2| -> Vec2 {
-                    let relative = world_pos - earth_pos;
+                    let relative = world_pos - map_origin;
                     let scaled = relative * map_scale;
                     // Flip Y coordinate to fix inverted display
                     Vec2::new(map_center.x + scaled.x, map_center.y - scaled.y)
                 };
 
-                // Check each satellite
-                let satellites: Vec<_> = self.world.satellites_with_ids().collect();
-                for (sat_id, satellite) in satellites {
+                // Check each satellite; a press on empty map starts a drag-box
+                let clicked = self.world.satellites_with_ids().find_map(|(sat_id, satellite)| {
                     let map_pos = world_to_map(satellite.position());
                     let click_distance = ((mouse_pos.0 - map_pos.x).powi(2) + (mouse_pos.1 - map_pos.y).powi(2)).sqrt();
-
-                    if click_distance < 10.0 { // Click radius
-                        // Toggle marked status
-                        if self.marked_satellites.contains(&sat_id) {
-                            self.marked_satellites.remove(&sat_id);
-                            log::info!("Unmarked satellite {}", sat_id);
-                        } else {
-                            self.marked_satellites.insert(sat_id);
-                            log::info!("Marked satellite {}", sat_id);
-                        }
-                        break;
-                    }
+                    (click_distance < 10.0).then_some(sat_id) // Click radius
+                });
+                match clicked {
+                    Some(sat_id) => self.map_satellite_clicked(sat_id),
+                    None if map_rect.contains(Vec2::from(mouse_pos)) => self.start_map_drag(Vec2::from(mouse_pos)),
+                    None => {}
                 }
 
                 // Check if click is outside map to close
                 if mouse_pos.0 < map_x || mouse_pos.0 > map_x + map_size + list_width + 20.0 ||
                    mouse_pos.1 < map_y || mouse_pos.1 > map_y + map_size {
                     self.show_network_map = false;
+                    self.map_search.unfocus();
                     log::info!("Clicked outside network map, closing");
                 }
             }
         }
 
+        // Releasing a drag-box selects the satellites inside it (Shift adds to the selection)
+        if is_mouse_button_released(MouseButton::Left) {
+            if let Some(start) = self.map_drag_start.take() {
+                self.finish_map_drag(start, Vec2::from(mouse_position()));
+            }
+        }
+
+        // Right-click on the network map places a waypoint there, or removes one of ours under the cursor
+        // Shift+right-click adds the waypoint or planet under the cursor to the route instead
+        if self.show_network_map && is_mouse_button_pressed(MouseButton::Right) {
+            if is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift) {
+                self.add_map_route_stop(Vec2::from(mouse_position()));
+            } else {
+                self.toggle_map_waypoint(Vec2::from(mouse_position()));
+            }
+        }
+
         // Toggle pause (only if controls not showing)
         if is_key_pressed(KeyCode::P) && !self.show_controls {
             self.is_paused = !self.is_paused;
         }
 
+        // Time warp (- slower, = faster); thrusting drops back to 1x and manual warp overrides window warp
+        if is_key_pressed(KeyCode::Equal) {
+            self.window_warp = false;
+            let warp = self.clock.increase_warp();
+            self.toasts.push(format!("Time warp {}x", warp), SKYBLUE);
+        }
+        if is_key_pressed(KeyCode::Minus) {
+            self.window_warp = false;
+            let warp = self.clock.decrease_warp();
+            self.toasts.push(format!("Time warp {}x", warp), SKYBLUE);
+        }
+        // Y buys while the market board is open
+        if is_key_pressed(KeyCode::Y) && !self.market_panel.is_visible() {
+            self.toggle_window_warp();
+        }
+
         // Quick save (F5 key) - saves and shows "what a save!!" celebration
         if is_key_pressed(KeyCode::F5) {
             self.quick_save();
         }
+        if is_key_pressed(KeyCode::F6) {
+            self.export_json();
+        }
+        // Physics debugging: drift is measured from when the overlay opens
+        if is_key_pressed(KeyCode::F8) {
+            self.conservation = match self.conservation {
+                Some(_) => None,
+                None => Some(ConservationMonitor::new()),
+            };
+        }
 
-        // Panel visibility toggles (keys 1-3, 5)
-        if is_key_pressed(KeyCode::Key1) {
+        // Panel visibility toggles (keys 1-5)
+        if is_key_pressed(KeyCode::Key1) && !camera::preset_modifier_down() {
             self.info_display.toggle_rocket_panel();
             log::info!("Toggled rocket panel");
         }
-        if is_key_pressed(KeyCode::Key2) {
+        if is_key_pressed(KeyCode::Key2) && !camera::preset_modifier_down() {
             self.info_display.toggle_planet_panel();
             log::info!("Toggled planet panel");
         }
-        if is_key_pressed(KeyCode::Key3) {
+        if is_key_pressed(KeyCode::Key3) && !camera::preset_modifier_down() {
             self.info_display.toggle_orbit_panel();
             log::info!("Toggled orbit panel");
         }
-        // Key 4 removed - controls panel deleted
-        if is_key_pressed(KeyCode::Key5) {
+        if is_key_pressed(KeyCode::Key4) && !camera::preset_modifier_down() {
+            self.info_display.toggle_network_panel();
+            log::info!("Toggled satellite network panel");
+        }
+        if is_key_pressed(KeyCode::Key5) && !camera::preset_modifier_down() {
             self.show_network_map = !self.show_network_map;
             log::info!("Toggled network map: {}", self.show_network_map);
         }
+        if is_key_pressed(KeyCode::Key8) {
+            self.coverage = match self.coverage {
+                Some(_) => None,
+                None => Some(CoverageCache::new()),
+            };
+        }
+        if is_key_pressed(KeyCode::H) {
+            self.timeline_panel.toggle();
+            log::info!("Toggled session timeline: {}", self.timeline_panel.is_visible());
+        }
+        self.timeline_panel.handle_input(&self.timeline, self.clock.elapsed());
         // Key 0 to toggle all panels
         if is_key_pressed(KeyCode::Key0) {
             self.info_display.show_all_panels();
@@ -504,6 +1155,118 @@ impl SinglePlayerGame {
             log::info!("Toggled gravity force visualization: {}", self.vehicle_manager.visualization().show_gravity_forces);
         }
 
+        // Shift+L cycles the long-horizon orbit trails (strategic view only)
+        let shift_held = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        if is_key_pressed(KeyCode::L) && shift_held {
+            self.trail_window = self.trail_window.next();
+            self.toasts.push(self.trail_window.label().to_string(), LIGHTGRAY);
+        } else if is_key_pressed(KeyCode::L) {
+            self.vehicle_manager.toggle_satellite_orbits();
+            log::info!("Toggled satellite orbit lines: {}", self.vehicle_manager.visualization().show_satellite_orbits);
+        }
+
+        // Advanced physics: Roche-limit breakup
+        if is_key_pressed(KeyCode::K) {
+            let mut tidal_rules = self.world.tidal_rules();
+            tidal_rules.enabled = !tidal_rules.enabled;
+            self.world.set_tidal_rules(tidal_rules);
+            let state = if tidal_rules.enabled { "ON" } else { "OFF" };
+            self.toasts.push(format!("Tidal breakup {}", state), ORANGE);
+            log::info!("Toggled tidal breakup: {}", tidal_rules.enabled);
+        }
+
+        // Satellite network auto-balancing (idle satellites share fuel with linked neighbours)
+        if is_key_pressed(KeyCode::F) {
+            let enabled = !self.world.auto_balance_enabled();
+            self.world.set_auto_balance(enabled);
+            let state = if enabled { "ON" } else { "OFF" };
+            self.toasts.push(format!("Satellite fuel auto-balancing {}", state), SKYBLUE);
+            log::info!("Toggled satellite auto-balancing: {}", enabled);
+        }
+
+        // Collect a sample at the site we're landed on (or where the rover is parked)
+        if is_key_pressed(KeyCode::S) && !self.samples.is_empty() {
+            let result = match self.rover.as_mut() {
+                Some(rover) => match self.world.get_planet(rover.planet_id()) {
+                    Some(planet) => {
+                        let planet_name = planet.name().unwrap_or_default();
+                        let result = self.samples.collect_at(planet_name, planet.radius(), rover.angle(), rover.rocket_id());
+                        if let Ok(site_name) = &result {
+                            rover.add_sample(site_name.clone());
+                        }
+                        Some(result)
+                    }
+                    None => None,
+                },
+                None => self.world.active_rocket_id().map(|rocket_id| self.samples.try_collect(&self.world, rocket_id)),
+            };
+            match result {
+                Some(Ok(site_name)) => {
+                    let home = self.samples.home_body().unwrap_or("home");
+                    self.toasts.push(format!("Collected sample from {} - return it to {}", site_name, home), GREEN);
+                    log::info!("Collected sample from {}", site_name);
+                }
+                Some(Err(e)) => self.toasts.push(e.message().to_string(), LIGHTGRAY),
+                None => {}
+            }
+        }
+
+        // Deploy / dock the rover
+        if is_key_pressed(KeyCode::V) {
+            self.toggle_rover();
+        }
+
+        // Shift+B marks a landing target on the surface under the cursor
+        let shift_held = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        if is_key_pressed(KeyCode::B) && shift_held {
+            let point = self.camera.screen_to_world(Vec2::from(mouse_position()));
+            if let Some(zone) = self.landing_zones.mark(&self.world, point) {
+                self.toasts.push(format!("Landing target marked on {}", zone.body_name), Color::new(1.0, 0.55, 0.1, 1.0));
+            }
+        }
+
+        // Drop a waypoint at the active rocket
+        if is_key_pressed(KeyCode::B) && !shift_held {
+            if let Some(position) = self.world.get_active_rocket().map(|rocket| rocket.position()) {
+                let id = self.waypoints.add(position, None, false);
+                self.toasts.push(format!("Dropped waypoint WP {}", id + 1), Color::new(1.0, 0.85, 0.2, 1.0));
+                log::info!("Dropped waypoint {} at ({:.0}, {:.0})", id, position.x, position.y);
+            }
+        }
+
+        // Route planning: J adds the targeted planet, U removes the last stop
+        if is_key_pressed(KeyCode::J) {
+            if let Some(EntityTarget::Planet(planet_id)) = self.vehicle_manager.target() {
+                self.route.push(RouteStop::Planet(planet_id));
+            } else {
+                self.toasts.push("Target a planet to add it to the route", LIGHTGRAY);
+            }
+        }
+        if is_key_pressed(KeyCode::U) && self.route.pop().is_some() {
+            log::info!("Removed last route stop ({} left)", self.route.stops().len());
+        }
+
+        // Tanker runs: N on a satellite hands the active rocket over to the convoy, N on a tanker releases it;
+        // Shift+N on a rocket flies it remotely over the relays (and hands control back)
+        if is_key_pressed(KeyCode::N) && shift_held {
+            self.toggle_remote_control();
+        } else if is_key_pressed(KeyCode::N) {
+            self.toggle_tanker();
+        }
+
+        // Station ops: 6 on a landed rocket steps its refuel hold through 50/75/100% and off
+        if is_key_pressed(KeyCode::Key6) && !camera::preset_modifier_down() {
+            self.cycle_station_hold();
+        }
+
+        // Market board: M opens it, its own keys trade and take contracts
+        if is_key_pressed(KeyCode::M) {
+            self.market_panel.toggle();
+        }
+        if let Some(action) = self.market_panel.handle_input(&self.market) {
+            self.handle_market_action(action);
+        }
+
         if is_key_pressed(KeyCode::O) {
             self.vehicle_manager.toggle_planet_trajectories();
             log::info!("Toggled planet trajectory visualization: {}", self.vehicle_manager.visualization().show_planet_trajectories);
@@ -519,111 +1282,834 @@ impl SinglePlayerGame {
         // Mouse wheel zoom (adaptive delta based on current zoom level for smooth zooming)
         let mouse_wheel = mouse_wheel().1;
         if mouse_wheel != 0.0 {
-            let current_zoom = self.camera.zoom_level();
-            let zoom_delta = current_zoom * 0.1; // 10% of current zoom per scroll
-            self.camera.adjust_zoom(-mouse_wheel * zoom_delta);
+            self.camera.zoom_wheel(mouse_wheel); // 10% per notch
+        }
+
+        // Keyboard zoom controls (E = zoom out, Q = zoom in)
+        // Note: zoom_scale = 1/zoom_level, so larger zoom_level = more zoomed out
+        let current_zoom = self.camera.zoom_level();
+        if is_key_down(self.controls.zoom_in) {
+            self.camera.zoom_held(-1.0, get_frame_time());
+        }
+        if is_key_down(self.controls.zoom_out) {
+            self.camera.zoom_held(1.0, get_frame_time());
+        }
+
+        // Quick zoom presets (Home = rocket, End = whole system, Shift+1-6 = fixed zoom levels)
+        if let Some(command) = CameraCommand::from_keys() {
+            self.run_camera_command(command);
+        }
+        if is_key_pressed(KeyCode::PageUp) {
+            // Quick zoom in by 50%
+            self.camera.set_target_zoom(current_zoom * 0.5);
+        }
+        if is_key_pressed(KeyCode::PageDown) {
+            // Quick zoom out by 2x
+            self.camera.set_target_zoom(current_zoom * 2.0);
+        }
+
+        SinglePlayerResult::Continue
+    }
+
+    /// Whether the escape warning is up for the active rocket
+    fn active_rocket_escaping(&self) -> bool {
+        let Some(rocket_id) = self.world.active_rocket_id() else {
+            return false;
+        };
+        self.alerts
+            .alerts()
+            .iter()
+            .any(|alert| alert.kind == AlertKind::Escaping && alert.subject == EntityTarget::Rocket(rocket_id))
+    }
+
+    /// Zoom-to-fit, back-to-rocket and preset zooms, all eased rather than snapped
+    fn run_camera_command(&mut self, command: CameraCommand) {
+        match command {
+            CameraCommand::FitSystem => {
+                if let Some((center, radius)) = self.world.system_extent() {
+                    self.system_view = true;
+                    self.camera.zoom_to_fit(center, radius);
+                }
+            }
+            CameraCommand::FocusCraft => {
+                self.system_view = false;
+                self.camera_focus = None;
+                self.camera.set_target_zoom(CRAFT_VIEW_ZOOM);
+                self.camera.begin_transition();
+            }
+            CameraCommand::Preset(index) => self.camera.set_target_zoom(ZOOM_PRESETS[index]),
+        }
+    }
+
+    /// Fly with the controller in slot 0 and rumble it
+    pub fn set_controllers(&mut self, controllers: &Controllers) {
+        self.gamepads = controllers.gamepads();
+        self.haptics = controllers.haptics(0, self.haptics.intensity());
+    }
+
+    /// Set the looping sound played while fuel transfers are active
+    pub fn set_transfer_sound(&mut self, sound: Option<macroquad::audio::Sound>) {
+        self.transfer_effects.set_hum_sound(sound);
+    }
+
+    /// Set the beep played when a new alert is raised
+    pub fn set_alert_sound(&mut self, sound: Option<macroquad::audio::Sound>) {
+        self.alert_banner.set_warning_sound(sound);
+    }
+
+    /// Set the rocket-rocket / rocket-satellite collision rules for this session
+    pub fn set_collision_rules(&mut self, rules: CollisionRules) {
+        self.world.set_collision_rules(rules);
+    }
+
+    /// Hardcore rule: only save within reach of the satellite network or while landed
+    pub fn set_hardcore_saves(&mut self, hardcore: bool) {
+        self.hardcore_saves = hardcore;
+    }
+
+    /// Next Moon transfer window from the active rocket's orbit
+    fn launch_window(&self) -> Option<LaunchWindow> {
+        self.world.get_active_rocket().and_then(|rocket| LaunchWindow::find(&self.world, rocket))
+    }
+
+    /// Start or cancel warping to the next launch window
+    fn toggle_window_warp(&mut self) {
+        if self.window_warp {
+            self.window_warp = false;
+            self.clock.stop_warp();
+            self.toasts.push("Window warp cancelled".to_string(), SKYBLUE);
+        } else if let Some(window) = self.launch_window() {
+            self.window_warp = true;
+            self.toasts.push(format!("Warping to {} window (T- {})", window.target_name, mission_clock::format_clock(window.time_to_window)), SKYBLUE);
+        } else {
+            self.toasts.push("No launch window from here (get into orbit first)".to_string(), ORANGE);
+        }
+    }
+
+    /// Keep warp as high as the countdown allows and drop to real time right before the window
+    fn update_window_warp(&mut self) {
+        if !self.window_warp {
+            return;
+        }
+        match self.launch_window() {
+            Some(window) if window.time_to_window > WINDOW_ARRIVAL_SECONDS => {
+                self.clock.set_warp(launch_window::warp_for_wait(window.time_to_window));
+            }
+            Some(window) => {
+                self.window_warp = false;
+                self.clock.stop_warp();
+                self.toasts.push(format!("{} window: burn {:.0} m/s prograde now", window.target_name, window.departure_burn), GREEN);
+            }
+            None => {
+                self.window_warp = false;
+                self.clock.stop_warp();
+                self.toasts.push("Launch window lost".to_string(), ORANGE);
+            }
+        }
+    }
+
+    /// Update game state
+    pub fn update(&mut self, delta_time: f32) {
+        #[cfg(feature = "hot-reload")]
+        self.apply_hot_reloads(delta_time);
+
+        if self.is_paused {
+            self.transfer_effects.clear();
+            self.haptics.stop();
+            return;
+        }
+
+        self.update_window_warp();
+
+        // Warp runs extra fixed physics steps per tick rather than longer ones, so orbits stay stable
+        let game_delta = self.clock.tick(delta_time);
+
+        self.gamepads.poll();
+        self.controls.sync_gamepad(&self.gamepads);
+
+        // Handle input for active rocket (not while typing in the network map search or watching the kill cam)
+        let typing = self.show_network_map && self.map_search.is_focused();
+        self.update_kill_cam(delta_time);
+        if !typing && self.kill_cam.is_none() {
+            self.update_rocket_input();
+        }
+        self.update_remote_control();
+
+        // Handle manual planet refueling (R key) - BEFORE world update to prevent satellite interference
+        let manual_refuel_active = if let Some(rocket_id) = self.world.active_rocket_id() {
+            // No planet refueling during a challenge attempt or a no-refuel scenario
+            if is_key_pressed(KeyCode::R) && !typing && self.refuel_allowed() {  // Changed to is_key_pressed for single press
+                self.world.handle_manual_planet_refuel(rocket_id, delta_time);
+                true
+            } else {
+                false
+            }
+        } else {
+            false
+        };
+
+        // Pass fuel to the nearest rocket while the transfer key is held (I unless rebound)
+        let transfer_held = !typing && !self.market_panel.is_visible() && self.controls.is_transferring_fuel();
+        match self.world.active_rocket_id().filter(|_| transfer_held) {
+            Some(donor_id) => {
+                let recipient_id = self.world.nearest_rocket_in_transfer_range(donor_id);
+                let moved = recipient_id
+                    .and_then(|recipient_id| self.world.transfer_fuel_rocket_to_rocket(donor_id, recipient_id, delta_time).ok())
+                    .unwrap_or(0.0);
+                self.rocket_transfer.hold(&self.world, donor_id, recipient_id, moved);
+            }
+            None => self.rocket_transfer.release(),
+        }
+
+        // Tankers steer themselves before the physics tick
+        for event in self.convoy.update(&mut self.world, delta_time) {
+            match event {
+                ConvoyEvent::Delivered { tanker, target, amount } => {
+                    self.toasts.push(format!("Tanker {} delivered {:.0} fuel to satellite {}", tanker, amount, target), SKYBLUE);
+                }
+                ConvoyEvent::Stranded { tanker } => {
+                    self.toasts.push(format!("Tanker {} ran dry and left the convoy", tanker), ORANGE);
+                }
+                ConvoyEvent::RouteLost { tanker } => {
+                    self.toasts.push(format!("Tanker {} lost its route and left the convoy", tanker), LIGHTGRAY);
+                }
+            }
+        }
+
+        // Held rockets top up from their planets (not during a challenge or a no-refuel scenario)
+        if self.refuel_allowed() {
+            for event in self.station_ops.update(&mut self.world, delta_time) {
+                self.report_station_event(event);
+            }
+        }
+
+        // Update world (physics, entities)
+        let flown_rocket = self.world.active_rocket_id();
+        for _ in 0..self.clock.warp() {
+            self.world.update(delta_time, manual_refuel_active);
+            if let Some(monitor) = &mut self.conservation {
+                monitor.record(&self.world, self.clock.elapsed());
+            }
+        }
+        self.flight_log.sample(&self.world, delta_time * self.clock.warp() as f32);
+
+        // Damage flashes for hits on our rocket, hit markers where our bullets land
+        for hit in self.world.take_hit_events() {
+            if Some(hit.rocket_id) == flown_rocket {
+                self.damage_indicator.on_hit(hit.position, hit.source);
+            } else if hit.shooter_id.is_some() && hit.shooter_id == flown_rocket {
+                self.damage_indicator.on_hit_confirmed(hit.position);
+            }
+            if hit.shooter_id.is_some() {
+                self.camera.impulse(CameraImpulse::BulletImpact, hit.position);
+            }
+        }
+
+        // Keep coordinates small around the rocket on big maps
+        if let Some(focus) = self.world.get_active_rocket().map(|rocket| rocket.position()) {
+            if let Some(shift) = self.world.rebase_around(focus) {
+                self.camera.shift_origin(shift);
+                self.waypoints.shift_positions(shift);
+                self.timeline.shift_positions(shift);
+                self.history.shift_positions(shift);
+                self.orbit_trails.shift_positions(shift);
+                self.damage_indicator.shift_positions(shift);
+                self.bullet_effects.shift_positions(shift);
+            }
+        }
+        self.history.record(&self.world, self.clock.elapsed());
+        self.orbit_trails.record(&self.world, self.clock.elapsed());
+        self.bullet_effects.update(&self.world, delta_time, self.camera.camera().target);
+
+        // Feed this tick's fuel transfers (manual + automatic) into the beam effects
+        let transfer_events = self.world.take_fuel_transfer_events();
+        self.record_transfer_events(&transfer_events);
+        self.transfer_effects.update(delta_time, transfer_events);
+        self.record_launch();
+        self.vehicle_manager.clear_missing_target(&self.world);
+
+        // Grade touchdowns: toast + best-landing stats in the profile
+        for event in self.world.take_landing_events() {
+            if Some(event.rocket_id) != self.world.active_rocket_id() {
+                continue;
+            }
+            let planet_name = event.planet_name.as_deref().unwrap_or("Unknown");
+            let new_best = self.profile.record_landing(planet_name, &event.result);
+            self.flight_log.record_landing(event.result.grade);
+            if let Some(rocket) = self.world.get_rocket(event.rocket_id) {
+                let description = format!("Landed on {} ({})", planet_name, event.result.grade.label());
+                self.timeline.record(self.clock.elapsed(), SessionEventKind::Landing, description, rocket.position());
+            }
+            self.toasts.push(event.result.toast_text(planet_name, new_best), event.result.grade.color());
+            self.haptics.trigger(HapticEvent::Landing { vertical_speed: event.result.vertical_speed });
+            if let Some(rocket) = self.world.get_rocket(event.rocket_id) {
+                self.camera.impulse(CameraImpulse::Landing { vertical_speed: event.result.vertical_speed }, rocket.position());
+            }
+            self.score_challenge_landing(&event);
+            if let Some(progress) = self.scenario.as_mut().map(|run| run.on_landing(&event)) {
+                self.report_scenario_progress(progress);
+            }
+            let zone_best = self.score_zone_landing(&event);
+
+            // Deliver carried samples when landing back home
+            let delivered = self.samples.on_landing(event.rocket_id, event.planet_name.as_deref());
+            if delivered > 0 {
+                let total = self.profile.record_sample_return(&self.current_map.name, delivered as u32);
+                let text = if self.samples.is_complete() {
+                    format!("All {} samples returned - mission complete!", self.samples.sites().len())
+                } else {
+                    format!("{} sample(s) returned ({} total on this map)", delivered, total)
+                };
+                self.toasts.push(text, GREEN);
+                log::info!("Returned {} samples to {}", delivered, planet_name);
+            }
+
+            if new_best || zone_best || delivered > 0 {
+                if let Err(e) = self.profile.save() {
+                    log::error!("Failed to save profile: {}", e);
+                }
+            }
+        }
+
+        // Station prices move; contracts pay out when the rocket lands with their cargo
+        let rocket_id = self.world.active_rocket_id();
+        for event in self.market.update(&mut self.world, rocket_id, game_delta) {
+            match event {
+                ContractEvent::Completed(contract) => {
+                    let balance = self.profile.add_credits(contract.payout);
+                    let to = self.market.station_name(contract.to);
+                    let done = match contract.kind {
+                        ContractKind::Haul => format!("Delivered {:.0} fuel to {}", contract.amount, to),
+                        ContractKind::Cleanup { .. } => format!("Cleared {:.0} pieces of debris", contract.amount),
+                    };
+                    self.toasts.push(format!("{}: +{} credits ({})", done, contract.payout, balance), GOLD);
+                    if let Err(e) = self.profile.save() {
+                        log::error!("Failed to save profile: {}", e);
+                    }
+                }
+                ContractEvent::Expired(contract) => {
+                    let to = self.market.station_name(contract.to);
+                    let what = match contract.kind {
+                        ContractKind::Haul => format!("Contract to {}", to),
+                        ContractKind::Cleanup { .. } => "Cleanup contract".to_string(),
+                    };
+                    self.toasts.push(format!("{} expired", what), ORANGE);
+                }
+                ContractEvent::DebrisCleared { planet, progress } => {
+                    let onto = self.market.station_name(planet);
+                    match progress {
+                        Some((cleared, wanted)) => self.toasts.push(format!("Debris down on {} ({}/{})", onto, cleared, wanted), GREEN),
+                        None => self.toasts.push(format!("Debris down on {}", onto), LIGHTGRAY),
+                    }
+                }
+            }
+        }
+
+        self.route.prune(&self.world, &self.waypoints);
+
+        let samples_returned = self.samples.returned_count();
+        if let Some(progress) = self.scenario.as_mut().map(|run| run.update(game_delta, &self.world, rocket_id, samples_returned)) {
+            self.report_scenario_progress(progress);
+        }
+        let assist_target = self.scenario.as_ref().and_then(ScenarioRun::assist_target);
+        self.assist_guide.update(&self.world, rocket_id.and_then(|id| self.world.get_rocket(id)), assist_target);
+
+        let lost = self.samples.update(&self.world);
+        if lost > 0 {
+            self.toasts.push(format!("{} sample(s) lost with the rocket", lost), RED);
+        }
+        self.toasts.update(delta_time);
+        for anomaly in self.world.take_network_anomalies() {
+            self.notify_network_anomaly(anomaly);
+        }
+        self.notifications.update(delta_time);
+
+        // Low fuel / orbit decay / incoming bullet alerts for our rocket(s) and the satellites
+        let watched: Vec<EntityId> = self.world.active_rocket_id().into_iter().collect();
+        self.alerts.update(delta_time, &self.world, &watched);
+        self.alert_banner.update(delta_time, self.alerts.alerts());
+        self.threat_indicator.update(delta_time, &self.world, &watched);
+        self.damage_indicator.update(delta_time);
+        let escaping = self.active_rocket_escaping();
+        if escaping && !self.escape_offered {
+            self.toasts.push("Leaving the system - press X to abort to spawn (new rocket starts low on fuel)".to_string(), ORANGE);
+        }
+        self.escape_offered = escaping;
+
+        // Satellites and bullets that drifted out of the system are gone for good
+        let drifted = world_bounds::remove_lost_entities(&mut self.world, &BoundsConfig::default());
+        if drifted > 0 {
+            self.toasts.push(format!("{} satellite(s) drifted out of the system", drifted), LIGHTGRAY);
+        }
+
+        // Worn-out satellites: retrieve them or they deorbit themselves
+        for event in self.world.take_satellite_life_events() {
+            let (text, color) = event.toast();
+            self.toasts.push(text, color);
+        }
+
+        // Handle rockets destroyed by bullets (respawn at Earth's current position)
+        let destroyed_rockets = self.world.take_destroyed_rockets();
+        for destroyed in destroyed_rockets {
+            log::info!("Rocket destroyed by {}, respawning at Earth's current position", destroyed.cause);
+            let description = format!("Rocket {} destroyed by {}", destroyed.rocket_id, destroyed.cause);
+            self.timeline.record(self.clock.elapsed(), SessionEventKind::Destroyed, description, destroyed.position);
+            self.camera.impulse(CameraImpulse::Explosion, destroyed.position);
+            let shooter = destroyed.kill_shot.and_then(|shot| shot.shooter_id);
+            if shooter.is_some() && shooter == flown_rocket && Some(destroyed.rocket_id) != flown_rocket {
+                self.flight_log.record_kill();
+            }
+            if Some(destroyed.rocket_id) == flown_rocket && destroyed.cause != ABORT_CAUSE {
+                if destroyed.cause == "bullet" {
+                    self.haptics.trigger(HapticEvent::BulletHit);
+                }
+                self.haptics.trigger(HapticEvent::Explosion);
+                self.start_kill_cam(&destroyed);
+            }
+            if self.challenge_running() {
+                self.toasts.push("Challenge failed - rocket destroyed".to_string(), RED);
+                self.challenge_outcome = Some(ChallengeOutcome::Failed(format!("rocket destroyed by {}", destroyed.cause)));
+            }
+            if let Some(progress) = self.scenario.as_mut().map(|run| run.on_rocket_destroyed(destroyed.cause)) {
+                self.report_scenario_progress(progress);
+            }
+
+            // Spawn new rocket at Earth's CURRENT position with CURRENT velocity
+            if let Some(new_rocket_id) = self.spawn_rocket() {
+                self.world.set_active_rocket(Some(new_rocket_id));
+                log::info!("New rocket {} spawned", new_rocket_id);
+                if destroyed.cause == ABORT_CAUSE {
+                    // The abort penalty: the replacement starts nearly dry
+                    if let Some(rocket) = self.world.get_rocket_mut(new_rocket_id) {
+                        rocket.set_fuel(rocket.max_fuel() * ABORT_FUEL_FRACTION);
+                    }
+                    self.toasts.push(format!("Aborted to spawn - new rocket has {:.0}% fuel", ABORT_FUEL_FRACTION * 100.0), ORANGE);
+                }
+            }
+        }
+
+        // Update save celebration timer
+        if self.save_celebration_timer > 0.0 {
+            self.save_celebration_timer -= delta_time;
+        }
+
+        // Engine rumble follows the throttle while there's fuel to burn
+        let throttle = self.world.get_active_rocket().filter(|rocket| rocket.current_fuel() > 0.0).map_or(0.0, |rocket| rocket.thrust_level());
+        self.haptics.update(delta_time, throttle);
+
+        self.update_rover(delta_time);
+
+        // Update camera to ALWAYS follow active rocket (never planets), or the rover while it's out,
+        // or a satellite picked from the network map search
+        let rover_position = self.rover.as_ref().and_then(|rover| {
+            self.world.get_planet(rover.planet_id()).map(|p| rover.position(p.position(), p.surface_radius_at(rover.angle())))
+        });
+        let focus_position = self.camera_focus.and_then(|id| self.world.get_satellite(id)).map(|s| s.position());
+        if focus_position.is_none() {
+            self.camera_focus = None;
+        }
+        let followed = if let Some(cam) = &self.kill_cam {
+            Some((CameraSubject::KillCam, cam.view_center()))
+        } else if let Some(event) = self.timeline_panel.selected_event(&self.timeline) {
+            Some((CameraSubject::TimelineEvent(event.time), event.position))
+        } else if let (Some(id), Some(position)) = (self.camera_focus, focus_position) {
+            Some((CameraSubject::Satellite(id), position))
+        } else if let Some((center, _)) = self.world.system_extent().filter(|_| self.system_view) {
+            Some((CameraSubject::System, center))
+        } else if let Some(position) = rover_position {
+            Some((CameraSubject::Rover, position))
+        } else {
+            self.world.active_rocket_id().zip(self.world.get_active_rocket()).map(|(id, rocket)| (CameraSubject::Rocket(id), rocket.position()))
+        };
+        if let Some((subject, position)) = followed {
+            if self.camera_subject == Some(subject) {
+                self.camera.follow(position);
+            } else {
+                self.camera.snap_to(position);
+                self.camera_subject = Some(subject);
+            }
+        }
+
+        self.camera.update(delta_time);
+
+        // Auto-save
+        if self.clock.elapsed() - self.last_auto_save > self.auto_save_interval {
+            if let Err(e) = self.create_save_data().save_autosave() {
+                log::error!("Auto-save failed: {}", e);
+            }
+            self.last_auto_save = self.clock.elapsed();
+        }
+    }
+
+    /// Replay the shot that destroyed the flown rocket, if a bullet did it and we saw it fired
+    fn start_kill_cam(&mut self, destroyed: &DestroyedRocketInfo) {
+        let Some(shot) = destroyed.kill_shot else {
+            return;
+        };
+        // No NPC gunners yet: anything not fired by us is a stray
+        let attacker = if shot.shooter_id == Some(destroyed.rocket_id) { "your own bullet" } else { "a stray bullet" };
+        self.kill_cam = KillCam::from_history(&self.history, destroyed.rocket_id, shot, destroyed.position, attacker.to_string());
+        if let Some(cam) = &self.kill_cam {
+            self.kill_cam_return_zoom = self.camera.zoom_level();
+            self.camera.zoom_to_fit(cam.view_center(), cam.view_radius());
+            self.camera_subject = Some(CameraSubject::KillCam);
+        }
+    }
+
+    /// Play the kill cam (SPACE skips), then ease back to the new rocket
+    fn update_kill_cam(&mut self, delta_time: f32) {
+        let Some(cam) = self.kill_cam.as_mut() else {
+            return;
+        };
+        if cam.update(delta_time) && !is_key_pressed(KeyCode::Space) {
+            return;
+        }
+        self.kill_cam = None;
+        self.camera.set_target_zoom(self.kill_cam_return_zoom);
+        self.camera.begin_transition();
+    }
+
+    /// Log a launch when the active rocket leaves the surface
+    fn record_launch(&mut self) {
+        let Some(rocket) = self.world.get_active_rocket() else {
+            self.active_rocket_was_landed = false;
+            return;
+        };
+        let landed = rocket.landed_on_planet_id().is_some();
+        if self.active_rocket_was_landed && !landed {
+            let description = format!("Rocket {} launched", self.world.active_rocket_id().unwrap_or_default());
+            self.timeline.record(self.clock.elapsed(), SessionEventKind::Launch, description, rocket.position());
+        }
+        self.active_rocket_was_landed = landed;
+    }
+
+    /// Log fuel transfers as they start (they tick every frame while a link is active)
+    fn record_transfer_events(&mut self, events: &[FuelTransferEvent]) {
+        for event in events {
+            let Some(position) = event.target_position(&self.world) else {
+                continue;
+            };
+            self.timeline.record_transfer(self.clock.elapsed(), (event.source_id, event.target_id), event.description(), position);
+        }
+    }
+
+    /// Put the active rocket on a tanker run to the targeted satellite (and fly a fresh one),
+    /// or release the targeted tanker
+    fn cycle_station_hold(&mut self) {
+        let Some(rocket_id) = self.world.active_rocket_id() else {
+            return;
+        };
+        if !self.refuel_allowed() {
+            self.toasts.push("No planet refueling right now", LIGHTGRAY);
+            return;
         }
-
-        // Keyboard zoom controls (E = zoom out, Q = zoom in)
-        // Note: zoom_scale = 1/zoom_level, so larger zoom_level = more zoomed out
-        let current_zoom = self.camera.zoom_level();
-        if is_key_down(KeyCode::Q) {
-            let zoom_delta = current_zoom * 0.05; // 5% zoom in per frame
-            self.camera.adjust_zoom(-zoom_delta);
+        match self.station_ops.cycle(&self.world, rocket_id) {
+            Ok(Some(level)) => self.toasts.push(format!("Station ops: refueling to {:.0}%", level * 100.0), SKYBLUE),
+            Ok(None) => self.toasts.push("Station ops off", LIGHTGRAY),
+            Err(e) => self.toasts.push(e, LIGHTGRAY),
         }
-        if is_key_down(KeyCode::E) {
-            let zoom_delta = current_zoom * 0.05; // 5% zoom out per frame
-            self.camera.adjust_zoom(zoom_delta);
+    }
+
+    /// Ready and dry planets are notifications (Jump targets the rocket); take-offs just a toast
+    fn report_station_event(&mut self, event: StationEvent) {
+        match event {
+            StationEvent::Ready { rocket, fuel } => {
+                let text = format!("Rocket {} refueled to {:.0} and ready", rocket, fuel);
+                self.notifications.push(text, GREEN, Some(EntityTarget::Rocket(rocket)));
+            }
+            StationEvent::PlanetDry { rocket, fuel } => {
+                let text = format!("Rocket {}'s planet is tapped out ({:.0} fuel aboard)", rocket, fuel);
+                self.notifications.push(text, ORANGE, Some(EntityTarget::Rocket(rocket)));
+            }
+            StationEvent::Released { rocket } => self.toasts.push(format!("Rocket {} left station ops", rocket), LIGHTGRAY),
         }
+    }
 
-        // Quick zoom presets
-        if is_key_pressed(KeyCode::Home) {
-            // Reset to close-up view of rocket
-            self.camera.set_target_zoom(1.0);
+    /// Take over the targeted rocket over the relay network, or hand control back to the craft aboard
+    fn toggle_remote_control(&mut self) {
+        if let Some(remote) = self.remote.take() {
+            self.release_remote(remote, "Remote link closed", LIGHTGRAY);
+            return;
         }
-        if is_key_pressed(KeyCode::End) {
-            // Zoom out to show entire solar system (~237M pixel diameter view)
-            self.camera.set_target_zoom(200000.0);
+        let Some(EntityTarget::Rocket(rocket_id)) = self.vehicle_manager.target() else {
+            self.toasts.push("Target a rocket to take remote control", LIGHTGRAY);
+            return;
+        };
+        let craft = self.world.active_rocket_id();
+        if craft == Some(rocket_id) {
+            return;
         }
-        if is_key_pressed(KeyCode::PageUp) {
-            // Quick zoom in by 50%
-            self.camera.set_target_zoom(current_zoom * 0.5);
+        if self.rover.is_some() {
+            self.toasts.push("Dock the rover before taking remote control", LIGHTGRAY);
+            return;
         }
-        if is_key_pressed(KeyCode::PageDown) {
-            // Quick zoom out by 2x
-            self.camera.set_target_zoom(current_zoom * 2.0);
+        if self.convoy.is_tanker(rocket_id) {
+            self.toasts.push(format!("Rocket {} is flying a tanker run - release it first", rocket_id), LIGHTGRAY);
+            return;
+        }
+        match RemoteControl::connect(&self.world, craft, self.spawn_planet_id, rocket_id) {
+            Ok(remote) => {
+                let chain = remote.chain();
+                self.toasts.push(
+                    format!("Remote control of rocket {} via {} relays ({:.0} ms)", rocket_id, chain.relays.len(), chain.latency * 1000.0),
+                    SKYBLUE,
+                );
+                // The craft aboard coasts while we fly the other one
+                if let Some(rocket) = craft.and_then(|id| self.world.get_rocket_mut(id)) {
+                    rocket.set_thrust_level(0.0);
+                }
+                self.world.set_active_rocket(Some(rocket_id));
+                self.remote = Some(remote);
+            }
+            Err(e) => self.toasts.push(e, LIGHTGRAY),
         }
+    }
 
-        SinglePlayerResult::Continue
+    /// Cut the remote rocket's engine and put the player back in the craft they're aboard
+    fn release_remote(&mut self, remote: RemoteControl, message: &str, color: Color) {
+        if let Some(rocket) = self.world.get_rocket_mut(remote.rocket_id()) {
+            rocket.set_thrust_level(0.0);
+        }
+        if let Some(craft) = remote.craft().filter(|id| self.world.get_rocket(*id).is_some()) {
+            self.world.set_active_rocket(Some(craft));
+        }
+        self.toasts.push(message, color);
     }
 
-    /// Update game state
-    pub fn update(&mut self, delta_time: f32) {
-        if self.is_paused {
+    /// Re-route the relay chain and deliver commands whose latency has run out
+    fn update_remote_control(&mut self) {
+        let Some(remote) = self.remote.as_mut() else {
             return;
+        };
+        // A respawn or conversion moved the controls elsewhere
+        if self.world.active_rocket_id() != Some(remote.rocket_id()) {
+            self.remote = None;
+            return;
+        }
+        if !remote.refresh(&self.world) {
+            if let Some(remote) = self.remote.take() {
+                self.release_remote(remote, "Relay chain broken - control returned", ORANGE);
+            }
+            return;
+        }
+        remote.deliver(&mut self.world, self.clock.elapsed());
+    }
+
+    fn toggle_tanker(&mut self) {
+        match self.vehicle_manager.target() {
+            Some(EntityTarget::Rocket(rocket_id)) if self.convoy.is_tanker(rocket_id) => {
+                self.convoy.release(&mut self.world, rocket_id);
+                self.toasts.push(format!("Tanker {} released", rocket_id), LIGHTGRAY);
+            }
+            Some(EntityTarget::Satellite(satellite_id)) => {
+                let Some(rocket_id) = self.world.active_rocket_id() else {
+                    return;
+                };
+                // Loads where it's landed, otherwise on the spawn planet
+                let landed_on = self.world.get_rocket(rocket_id).and_then(|rocket| rocket.landed_on_planet_id());
+                let Some(source) = landed_on.or(self.spawn_planet_id) else {
+                    return;
+                };
+                match self.convoy.assign(&mut self.world, rocket_id, source, satellite_id) {
+                    Ok(()) => {
+                        self.toasts.push(format!("Rocket {} is now a tanker for satellite {}", rocket_id, satellite_id), SKYBLUE);
+                        if let Some(new_id) = self.spawn_rocket() {
+                            self.world.set_active_rocket(Some(new_id));
+                        }
+                    }
+                    Err(e) => self.toasts.push(e, LIGHTGRAY),
+                }
+            }
+            _ => self.toasts.push("Target a satellite for a tanker run (or a tanker to release it)", LIGHTGRAY),
         }
+    }
 
-        self.game_time += delta_time;
+    /// Controls button (40x30) in the top-right corner of the safe area
+    fn controls_button_rect() -> Rect {
+        let size = Vec2::new(40.0, 30.0);
+        let position = SafeArea::current().anchor(Anchor::TopRight, size, Vec2::ZERO);
+        Rect::new(position.x, position.y, size.x, size.y)
+    }
 
-        // Handle input for active rocket
-        self.update_rocket_input();
+    /// Rumble slider track near the bottom of the controls popup
+    fn rumble_slider_rect() -> Rect {
+        let popup = SafeArea::current().popup(CONTROLS_POPUP_SIZE.0, CONTROLS_POPUP_SIZE.1);
+        Rect::new(popup.x + 160.0, popup.bottom() - 100.0, (popup.w / 2.0 - 160.0).max(80.0), 20.0)
+    }
 
-        // Handle manual planet refueling (R key) - BEFORE world update to prevent satellite interference
-        let manual_refuel_active = if let Some(rocket_id) = self.world.active_rocket_id() {
-            if is_key_pressed(KeyCode::R) {  // Changed to is_key_pressed for single press
-                self.world.handle_manual_planet_refuel(rocket_id, delta_time);
-                true
-            } else {
-                false
-            }
-        } else {
-            false
+    /// ON/OFF switch for screen shake, under the rumble slider
+    fn shake_toggle_rect() -> Rect {
+        let popup = SafeArea::current().popup(CONTROLS_POPUP_SIZE.0, CONTROLS_POPUP_SIZE.1);
+        Rect::new(popup.x + 160.0, popup.bottom() - 68.0, 70.0, 24.0)
+    }
+
+    /// Switches for bullet tracers, impact flashes and predicted arcs, right of screen shake
+    fn bullet_toggle_rects() -> [Rect; 3] {
+        let popup = SafeArea::current().popup(CONTROLS_POPUP_SIZE.0, CONTROLS_POPUP_SIZE.1);
+        let left = popup.center().x + 20.0;
+        [0.0, 1.0, 2.0].map(|i| Rect::new(left + i * 125.0 + 70.0, popup.bottom() - 68.0, 50.0, 24.0))
+    }
+
+    /// Flip the bullet effect under `mouse` and remember it in the profile
+    fn toggle_bullet_option(&mut self, mouse: Vec2) {
+        let Some(index) = Self::bullet_toggle_rects().iter().position(|rect| rect.contains(mouse)) else {
+            return;
         };
+        let mut options = self.bullet_effects.options();
+        match index {
+            0 => options.tracers = !options.tracers,
+            1 => options.impact_flashes = !options.impact_flashes,
+            _ => options.predicted_arcs = !options.predicted_arcs,
+        }
+        self.bullet_effects.set_options(options);
+        self.profile.bullet_view = options;
+        if let Err(e) = self.profile.save() {
+            log::error!("Failed to save profile: {}", e);
+        }
+    }
 
-        // Update world (physics, entities)
-        self.world.update(delta_time, manual_refuel_active);
+    /// An ON/OFF switch in the controls popup
+    fn draw_toggle(rect: Rect, on: bool) {
+        let color = if on { Color::new(0.3, 0.7, 0.4, 1.0) } else { Color::new(0.35, 0.35, 0.35, 1.0) };
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, color);
+        draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 1.0, WHITE);
+        let label = if on { "ON" } else { "OFF" };
+        let label_dims = measure_text(label, None, 16, 1.0);
+        draw_text(label, rect.center().x - label_dims.width / 2.0, rect.y + 17.0, 16.0, WHITE);
+    }
 
-        // Handle rockets destroyed by bullets (respawn at Earth's current position)
-        let destroyed_rockets = self.world.take_destroyed_rockets();
-        for _destroyed in destroyed_rockets {
-            log::info!("Rocket destroyed by bullet, respawning at Earth's current position");
+    /// Planet whose surface is closest to the middle of the view
+    fn coverage_planet(&self) -> Option<EntityId> {
+        let view_center = self.camera.camera().target;
+        self.world
+            .planets_with_ids()
+            .map(|(id, planet)| (id, (planet.position() - view_center).length() - planet.radius()))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(id, _)| id)
+    }
 
-            // Spawn new rocket at Earth's CURRENT position with CURRENT velocity
-            if let Some(new_rocket_id) = self.spawn_rocket() {
-                self.world.set_active_rocket(Some(new_rocket_id));
-                log::info!("New rocket {} spawned", new_rocket_id);
+    /// Trade at the station the active rocket is landed at, or take/drop a contract
+    fn handle_market_action(&mut self, action: MarketAction) {
+        let trade = match action {
+            MarketAction::ToggleContract(id) => {
+                if let Some(accepted) = self.market.toggle_contract(id) {
+                    self.toasts.push(if accepted { "Contract taken" } else { "Contract dropped" }, LIGHTGRAY);
+                }
+                return;
+            }
+            _ if self.challenge.is_some() || self.scenario.is_some() => Err("No trading during challenges".to_string()),
+            MarketAction::Buy => self.world.active_rocket_id().ok_or_else(|| "No rocket".to_string()).and_then(|rocket_id| {
+                let (amount, cost) = self.market.buy(&mut self.world, rocket_id, self.profile.credits)?;
+                self.profile.spend_credits(cost);
+                Ok(format!("Bought {:.0} fuel for {} credits", amount, cost))
+            }),
+            MarketAction::Sell => self.world.active_rocket_id().ok_or_else(|| "No rocket".to_string()).and_then(|rocket_id| {
+                let (amount, earned) = self.market.sell(&mut self.world, rocket_id)?;
+                self.profile.add_credits(earned);
+                Ok(format!("Sold {:.0} fuel for {} credits", amount, earned))
+            }),
+        };
+        match trade {
+            Ok(text) => {
+                self.toasts.push(format!("{} ({} left)", text, self.profile.credits), GOLD);
+                if let Err(e) = self.profile.save() {
+                    log::error!("Failed to save profile: {}", e);
+                }
             }
+            Err(e) => self.toasts.push(e, LIGHTGRAY),
         }
+    }
 
-        // Update save celebration timer
-        if self.save_celebration_timer > 0.0 {
-            self.save_celebration_timer -= delta_time;
+    /// Deploy a rover from the landed active rocket, or dock it if it's back at the rocket
+    fn toggle_rover(&mut self) {
+        let Some(mut rover) = self.rover.take() else {
+            let Some(rocket_id) = self.world.active_rocket_id() else {
+                return;
+            };
+            let landed = self.world.get_rocket(rocket_id).and_then(|rocket| {
+                let planet_id = rocket.landed_on_planet_id()?;
+                let planet = self.world.get_planet(planet_id)?;
+                Some((planet_id, vector_helper::angle(rocket.position() - planet.position())))
+            });
+            match landed {
+                Some((planet_id, angle)) => {
+                    self.rover = Some(Rover::new(rocket_id, planet_id, angle));
+                    self.toasts.push("Rover deployed - A/D to drive, V at the rocket to dock".to_string(), GREEN);
+                    log::info!("Rover deployed on planet {}", planet_id);
+                }
+                None => self.toasts.push("Land first to deploy the rover".to_string(), LIGHTGRAY),
+            }
+            return;
+        };
+
+        let at_rocket = match (self.world.get_rocket(rover.rocket_id()), self.world.get_planet(rover.planet_id())) {
+            (Some(rocket), Some(planet)) => {
+                let rocket_angle = vector_helper::angle(rocket.position() - planet.position());
+                vector_helper::angle_difference(rocket_angle, rover.angle()).abs() * planet.radius()
+                    <= GameConstants::ROVER_DOCK_RANGE
+            }
+            _ => false,
+        };
+        if !at_rocket {
+            self.toasts.push("Drive back to the rocket to dock".to_string(), LIGHTGRAY);
+            self.rover = Some(rover);
+            return;
         }
 
-        // Update camera to ALWAYS follow active rocket (never planets)
-        if let Some(rocket) = self.world.get_active_rocket() {
-            self.camera.follow(rocket.position());
+        // Samples already count as the rocket's cargo; fuel is handed over now
+        let fuel = rover.take_fuel();
+        let samples = rover.take_samples();
+        if let Some(rocket) = self.world.get_rocket_mut(rover.rocket_id()) {
+            rocket.add_fuel(fuel);
         }
+        self.toasts.push(format!("Rover docked: +{:.0} fuel, {} sample(s)", fuel, samples.len()), GREEN);
+        log::info!("Rover docked with {:.1} fuel and {} samples", fuel, samples.len());
+    }
 
-        self.camera.update(delta_time);
+    /// Drive the rover and mine fuel deposits; the rover is lost if its rocket leaves without it
+    fn update_rover(&mut self, delta_time: f32) {
+        let Some(rover) = self.rover.as_ref() else {
+            return;
+        };
+        let rocket_parked = self
+            .world
+            .get_rocket(rover.rocket_id())
+            .is_some_and(|rocket| rocket.landed_on_planet_id() == Some(rover.planet_id()));
+        let planet = self.world.get_planet(rover.planet_id());
+
+        let (Some(planet), true) = (planet, rocket_parked) else {
+            if let Some(mut rover) = self.rover.take() {
+                self.samples.drop_samples(&rover.take_samples());
+            }
+            self.toasts.push("Rover left behind!".to_string(), RED);
+            log::info!("Rover lost: its rocket left the surface");
+            return;
+        };
 
-        // Auto-save
-        if self.game_time - self.last_auto_save > self.auto_save_interval {
-            if let Err(e) = self.save_game("autosave") {
-                log::error!("Auto-save failed: {}", e);
+        let Some(rover) = self.rover.as_mut() else {
+            return;
+        };
+        rover.update(delta_time, planet.radius());
+
+        // Parked rovers mine the deposit they're sitting on
+        if !rover.is_driving() && !rover.is_fuel_full() {
+            if let Some(planet_name) = planet.name() {
+                let capacity_left = GameConstants::ROVER_FUEL_CAPACITY - rover.fuel_cargo();
+                let max_amount = (GameConstants::ROVER_EXTRACT_RATE * delta_time).min(capacity_left);
+                let extracted = self.deposits.extract(planet_name, planet.radius(), rover.angle(), max_amount);
+                rover.load_fuel(extracted);
             }
-            self.last_auto_save = self.game_time;
         }
     }
 
-    /// Update rocket based on keyboard input
+    /// Update rocket based on keyboard (and controller) input
     fn update_rocket_input(&mut self) {
-        // Thrust level adjustment (comma to decrease, period to increase)
-        if is_key_pressed(KeyCode::Comma) {
+        // Thrust level adjustment (comma to decrease, period to increase unless rebound)
+        if self.controls.just_decreased_thrust() {
             self.selected_thrust_level = (self.selected_thrust_level - 0.05).max(0.0);
             log::info!("Thrust level decreased to {}%", (self.selected_thrust_level * 100.0) as i32);
         }
-        if is_key_pressed(KeyCode::Period) {
+        if self.controls.just_increased_thrust() {
             self.selected_thrust_level = (self.selected_thrust_level + 0.05).min(1.0);
             log::info!("Thrust level increased to {}%", (self.selected_thrust_level * 100.0) as i32);
         }
@@ -632,36 +2118,106 @@ impl SinglePlayerGame {
         let mut thrust_level = 0.0;
         let mut rotation_delta = 0.0;
 
-        // Thrust controls - space bar applies the selected thrust level
-        if is_key_down(KeyCode::Space) {
-            thrust_level = self.selected_thrust_level;
+        // Thrust controls - space bar applies the selected thrust level, the trigger part of it
+        if self.controls.is_thrusting() {
+            thrust_level = self.selected_thrust_level * self.controls.thrust_amount();
         }
 
         // Rotation controls
-        if is_key_down(KeyCode::Left) || is_key_down(KeyCode::A) {
+        if is_key_down(KeyCode::Left) || is_key_down(self.controls.rotate_left) {
             rotation_delta = 3.0; // degrees per frame
         }
-        if is_key_down(KeyCode::Right) || is_key_down(KeyCode::D) {
+        if is_key_down(KeyCode::Right) || is_key_down(self.controls.rotate_right) {
             rotation_delta = -3.0;
         }
+        if rotation_delta == 0.0 {
+            rotation_delta = -3.0 * self.controls.gamepad_steer();
+        }
+
+        // While the rover is out, A/D drive it and the rocket stays parked
+        if let Some(rover) = self.rover.as_mut() {
+            let mut drive = 0.0;
+            if is_key_down(KeyCode::Left) || is_key_down(self.controls.rotate_left) {
+                drive += 1.0;
+            }
+            if is_key_down(KeyCode::Right) || is_key_down(self.controls.rotate_right) {
+                drive -= 1.0;
+            }
+            drive -= self.controls.gamepad_steer();
+            rover.set_drive_input(drive.clamp(-1.0, 1.0));
+            thrust_level = 0.0;
+            rotation_delta = 0.0;
+        }
+
+        if thrust_level > 0.0 && self.clock.is_warping() {
+            self.clock.stop_warp();
+            self.window_warp = false;
+            self.toasts.push("Time warp stopped for the burn".to_string(), SKYBLUE);
+        }
+
+        // Steering hands the camera back to the rocket
+        if thrust_level > 0.0 || rotation_delta != 0.0 || self.rover.as_ref().is_some_and(|r| r.is_driving()) {
+            self.camera_focus = None;
+        }
 
         // Convert degrees to radians
         let rotation_radians = rotation_delta * std::f32::consts::PI / 180.0;
 
-        // Apply to active rocket
-        if let Some(rocket) = self.world.get_active_rocket_mut() {
+        // Apply to active rocket; a remote one only gets it after the relay latency
+        if let Some(remote) = self.remote.as_mut() {
+            remote.send(self.clock.elapsed(), thrust_level, rotation_radians);
+        } else if let Some(rocket) = self.world.get_active_rocket_mut() {
             rocket.set_thrust_level(thrust_level);
             if rotation_delta != 0.0 {
                 rocket.rotate(rotation_radians);
             }
         }
 
-        // Convert to satellite (C key)
-        if is_key_pressed(KeyCode::C) {
+        // Abort to spawn (X key) - only while the active rocket is leaving the system
+        if is_key_pressed(KeyCode::X) && self.active_rocket_escaping() {
+            if let Some(rocket_id) = self.world.active_rocket_id() {
+                self.world.abort_rocket(rocket_id);
+            }
+        }
+
+        // Tractor beam (Shift+Z): grab the nearest debris, or let go of the piece in tow
+        let shift_held = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        if is_key_pressed(KeyCode::Z) && shift_held {
+            if let Some(rocket_id) = self.world.active_rocket_id() {
+                if self.world.release_debris(rocket_id).is_some() {
+                    self.toasts.push("Released debris", LIGHTGRAY);
+                } else {
+                    match self.world.capture_debris(rocket_id) {
+                        Ok(_) => self.toasts.push("Debris in tow - let go on a path into a planet, or land with it", SKYBLUE),
+                        Err(e) => self.toasts.push(e, LIGHTGRAY),
+                    }
+                }
+            }
+        }
+
+        // Retrieve the nearest retired satellite (Z key) for part of its fuel
+        if is_key_pressed(KeyCode::Z) && !shift_held {
+            if let Some(rocket_id) = self.world.active_rocket_id() {
+                match self.world.retrieve_nearest_satellite(rocket_id) {
+                    Ok((satellite_id, refund)) => {
+                        self.toasts.push(format!("Retrieved satellite {} (+{:.0} fuel)", satellite_id, refund), GREEN);
+                    }
+                    Err(e) => self.toasts.push(e, LIGHTGRAY),
+                }
+            }
+        }
+
+        // Convert to satellite (C key unless rebound)
+        if self.controls.just_converted_to_satellite() {
             if let Some(rocket_id) = self.world.active_rocket_id() {
                 // Convert rocket to satellite
-                if self.world.convert_rocket_to_satellite(rocket_id).is_some() {
+                if let Some(satellite_id) = self.world.convert_rocket_to_satellite(rocket_id) {
                     log::info!("Rocket converted to satellite");
+                    if let Some(satellite) = self.world.get_satellite(satellite_id) {
+                        let description = format!("Rocket {} became satellite {}", rocket_id, satellite_id);
+                        self.timeline.record(self.clock.elapsed(), SessionEventKind::Conversion, description, satellite.position());
+                        self.camera.impulse(CameraImpulse::Staging, satellite.position());
+                    }
 
                     // Spawn new rocket at Earth's CURRENT position with CURRENT velocity
                     if let Some(new_id) = self.spawn_rocket() {
@@ -672,11 +2228,11 @@ impl SinglePlayerGame {
             }
         }
 
-        // Shoot bullet (W key, same as multiplayer)
-        if is_key_pressed(KeyCode::W) {
+        // Shoot bullet (W key, same as multiplayer, or the controller's south button)
+        if self.controls.just_shot() {
             if let Some(rocket_id) = self.world.active_rocket_id() {
                 if let Some(bullet_id) = self.world.shoot_bullet_from_rocket(rocket_id) {
-                    log::info!("Bullet {} fired from rocket {}", bullet_id, rocket_id);
+                    log::debug!("Bullet {} fired from rocket {}", bullet_id, rocket_id);
                 } else {
                     log::info!("Cannot shoot: not enough fuel (need 1 unit)");
                 }
@@ -684,13 +2240,146 @@ impl SinglePlayerGame {
         }
     }
 
+    /// World point at the center of the network map (a search jump, otherwise the most massive planet)
+    fn network_map_origin(&self) -> Vec2 {
+        if let Some(center) = self.map_search.map_center(&self.world) {
+            return center;
+        }
+        self.world
+            .planets()
+            .max_by(|a, b| a.mass().partial_cmp(&b.mass()).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|planet| planet.position())
+            .unwrap_or(Vec2::new(GameConstants::MAIN_PLANET_X, GameConstants::MAIN_PLANET_Y))
+    }
+
+    /// Place a waypoint at a network map click, or remove our waypoint near it
+    fn toggle_map_waypoint(&mut self, mouse: Vec2) {
+        let (map_rect, _) = SafeArea::current().network_map();
+        let map_size = map_rect.w;
+        if !map_rect.contains(mouse) {
+            return;
+        }
+        let map_scale = (map_size * 0.45) / 50000.0;
+        let world_pos = waypoint_markers::map_to_world(mouse, map_rect.center(), map_scale, self.network_map_origin());
+
+        if let Some(id) = self.waypoints.owned_near(world_pos, 10.0 / map_scale, None) {
+            self.waypoints.remove(id, None);
+            log::info!("Removed waypoint {}", id);
+        } else {
+            let id = self.waypoints.add(world_pos, None, false);
+            log::info!("Placed waypoint {} at ({:.0}, {:.0})", id, world_pos.x, world_pos.y);
+        }
+    }
+
+    /// Add the waypoint or planet under a network map click as the next route stop
+    fn add_map_route_stop(&mut self, mouse: Vec2) {
+        let (map_rect, _) = SafeArea::current().network_map();
+        let map_size = map_rect.w;
+        if !map_rect.contains(mouse) {
+            return;
+        }
+        let map_scale = (map_size * 0.45) / 50000.0;
+        let world_pos = waypoint_markers::map_to_world(mouse, map_rect.center(), map_scale, self.network_map_origin());
+        let pick_radius = 10.0 / map_scale;
+
+        let stop = if let Some(id) = self.waypoints.owned_near(world_pos, pick_radius, None) {
+            RouteStop::Waypoint(id)
+        } else if let Some((id, _)) = self.world.planets_with_ids().find(|(_, p)| p.position().distance(world_pos) <= p.radius() + pick_radius) {
+            RouteStop::Planet(id)
+        } else {
+            return;
+        };
+        self.route.push(stop);
+        log::info!("Route now has {} stop(s)", self.route.stops().len());
+    }
+
+    /// Satellites shown in the network map list (search results while filtering)
+    fn network_map_list(&self) -> Vec<EntityId> {
+        if self.map_search.is_filtering() {
+            self.map_search.results().to_vec()
+        } else {
+            self.world.satellites_with_ids().map(|(id, _)| id).collect()
+        }
+    }
+
+    /// A satellite clicked in the network map or its list: picks the hub, Shift adds it to the selection,
+    /// otherwise toggles its mark
+    fn map_satellite_clicked(&mut self, sat_id: EntityId) {
+        if self.picking_hub {
+            self.picking_hub = false;
+            self.run_batch_action(Some(BatchAction::Command(BatchCommand::FuelToHub(sat_id))));
+        } else if is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift) {
+            self.map_selection.toggle(sat_id);
+        } else if self.marked_satellites.remove(&sat_id) {
+            log::info!("Unmarked satellite {}", sat_id);
+        } else {
+            self.marked_satellites.insert(sat_id);
+            log::info!("Marked satellite {}", sat_id);
+        }
+    }
+
+    fn start_map_drag(&mut self, point: Vec2) {
+        self.map_drag_start = Some(point);
+    }
+
+    /// Select the satellites inside a drag-box (a plain click on empty map does nothing)
+    fn finish_map_drag(&mut self, start: Vec2, end: Vec2) {
+        const MIN_DRAG: f32 = 6.0;
+        if start.distance(end) < MIN_DRAG || !self.show_network_map {
+            return;
+        }
+        let (map_rect, _) = SafeArea::current().network_map();
+        let map_scale = (map_rect.w * 0.45) / 50000.0;
+        let origin = self.network_map_origin();
+        let to_world = |point: Vec2| waypoint_markers::map_to_world(point, map_rect.center(), map_scale, origin);
+        let add = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        self.map_selection.select_box(&self.world, to_world(start), to_world(end), add);
+    }
+
+    /// Run a batch bar action on the selection
+    fn run_batch_action(&mut self, action: Option<BatchAction>) {
+        match action {
+            Some(BatchAction::Command(command)) => {
+                let count = self.map_selection.apply(&mut self.world, &mut self.marked_satellites, command);
+                let color = if count > 0 { SKYBLUE } else { ORANGE };
+                self.toasts.push(command.describe(count), color);
+            }
+            Some(BatchAction::PickHub) => self.picking_hub = true,
+            Some(BatchAction::ClearSelection) => self.map_selection.clear(),
+            None => {}
+        }
+    }
+
+    /// Raise a fuel network anomaly as a notification pointing at the satellite involved
+    fn notify_network_anomaly(&mut self, anomaly: NetworkAnomaly) {
+        let (satellite_id, color) = match anomaly {
+            NetworkAnomaly::Starved { satellite_id } => (satellite_id, RED),
+            NetworkAnomaly::Unreachable { satellite_id } => (satellite_id, ORANGE),
+            // Point at whichever end survived
+            NetworkAnomaly::Interrupted { source_id, destination_id, .. } => {
+                let survivor = if self.world.get_satellite(destination_id).is_some() { destination_id } else { source_id };
+                (survivor, YELLOW)
+            }
+        };
+        let target = self.world.get_satellite(satellite_id).map(|_| EntityTarget::Satellite(satellite_id));
+        self.notifications.push(anomaly.describe(), color, target);
+    }
+
+    /// Search result picked with Enter: the map centers on it; Shift+Enter also points the camera at it
+    fn jump_to_satellite(&mut self, jump: SearchJump) {
+        log::info!("Network map jumped to satellite {}", jump.satellite_id);
+        if jump.move_camera {
+            self.camera_focus = Some(jump.satellite_id);
+            self.toasts.push(format!("Viewing satellite {} - steer to return to your rocket", jump.satellite_id), SKYBLUE);
+        }
+    }
+
     /// Draw network map popup
     fn draw_network_map(&mut self) {
         let screen_w = screen_width();
         let screen_h = screen_height();
-        let map_size = 700.0;
-        let map_x = screen_w / 2.0 - map_size / 2.0;
-        let map_y = screen_h / 2.0 - map_size / 2.0;
+        let (map_rect, list_rect) = SafeArea::current().network_map();
+        let (map_x, map_y, map_size) = (map_rect.x, map_rect.y, map_rect.w);
 
         // Semi-transparent overlay
         draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::new(0.0, 0.0, 0.0, 0.6));
@@ -724,14 +2413,15 @@ impl SinglePlayerGame {
         }
 
         // Map viewport (what area of the game world to show)
-        // Show from 0 to ~50000 units from center
+        // Show from 0 to ~50000 units from center (Earth, or the satellite jumped to by search)
+        let map_origin = self.network_map_origin();
         let map_world_radius = 50000.0;
         let map_center = Vec2::new(map_x + map_size / 2.0, map_y + map_size / 2.0);
         let map_scale = (map_size * 0.45) / map_world_radius;
 
         // Helper function to convert world position to map position
         let world_to_map = |world_pos: Vec2| -> Vec2 {
-            let relative = world_pos - earth_pos;
+            let relative = world_pos - map_origin;
             let scaled = relative * map_scale;
             // Flip Y coordinate to fix inverted display
             Vec2::new(map_center.x + scaled.x, map_center.y - scaled.y)
@@ -739,14 +2429,15 @@ impl SinglePlayerGame {
 
         // Draw range rings at 1500 unit intervals from Earth's surface
         let earth_radius = 10000.0; // From constants
+        let earth_map_pos = world_to_map(earth_pos);
         for i in 1..=40 {
             let ring_distance = earth_radius + (i as f32 * 1500.0);
             let ring_radius_on_map = ring_distance * map_scale;
 
             if ring_radius_on_map < map_size / 2.0 {
                 draw_circle_lines(
-                    map_center.x,
-                    map_center.y,
+                    earth_map_pos.x,
+                    earth_map_pos.y,
                     ring_radius_on_map,
                     1.0,
                     Color::new(0.0, 0.5, 0.0, 0.3),
@@ -794,7 +2485,7 @@ impl SinglePlayerGame {
             draw_circle_lines(map_pos.x, map_pos.y, planet_radius_clamped, 2.0, WHITE);
 
             // Label
-            let label = if planet.mass() == earth_mass { "Earth" } else { "Moon" };
+            let label = planet.name().unwrap_or(if planet.mass() == earth_mass { "Earth" } else { "Moon" });
             draw_text(label, map_pos.x - 15.0, map_pos.y - planet_radius_clamped - 5.0, 14.0, WHITE);
         }
 
@@ -811,6 +2502,17 @@ impl SinglePlayerGame {
             draw_text("YOU", map_pos.x - 12.0, map_pos.y - 10.0, 12.0, WHITE);
         }
 
+        // Waypoints and the planned route
+        let rocket_pos = self.world.get_active_rocket().map(|rocket| rocket.position());
+        let map_bounds = Rect::new(map_x, map_y, map_size, map_size);
+        waypoint_markers::draw_map_waypoints(self.waypoints.list(), world_to_map, map_bounds, rocket_pos);
+        if let Some(plan) = self.world.get_active_rocket().and_then(|rocket| self.route.plan(&self.world, &self.waypoints, rocket)) {
+            route_overlay::draw_map_route(&plan, world_to_map);
+        }
+        if let Some(remote) = &self.remote {
+            relay_overlay::draw_map_chain(remote.chain(), world_to_map);
+        }
+
         // Draw connection lines between satellites in range
         let satellite_transfer_range = GameConstants::SATELLITE_TRANSFER_RANGE;
         let satellites: Vec<_> = self.world.satellites_with_ids().collect();
@@ -839,6 +2541,19 @@ impl SinglePlayerGame {
             }
         }
 
+        // Draw fitted satellite orbits (L toggles)
+        if self.vehicle_manager.visualization().show_satellite_orbits {
+            let planets: Vec<&Planet> = self.world.planets().collect();
+            for satellite in self.world.satellites() {
+                if let Some(ellipse) = VehicleManager::satellite_orbit_ellipse(satellite, &planets) {
+                    let map_points: Vec<Vec2> = ellipse.points(96).into_iter().map(world_to_map).collect();
+                    for pair in map_points.windows(2) {
+                        draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, 1.0, Color::new(0.0, 1.0, 0.0, 0.25));
+                    }
+                }
+            }
+        }
+
         // Draw satellites
         let satellites: Vec<_> = self.world.satellites_with_ids().collect();
         for (sat_id, satellite) in &satellites {
@@ -860,6 +2575,18 @@ impl SinglePlayerGame {
             // Satellite ID label
             let id_text = format!("{}", sat_id);
             draw_text(&id_text, map_pos.x + 7.0, map_pos.y + 4.0, 12.0, WHITE);
+
+            // Group selection gets a square
+            if self.map_selection.contains(*sat_id) {
+                draw_rectangle_lines(map_pos.x - 8.0, map_pos.y - 8.0, 16.0, 16.0, 2.0, Color::new(0.3, 0.9, 1.0, 1.0));
+            }
+
+            // Search results get a ring (brighter for the selected one)
+            if self.map_search.is_match(*sat_id) {
+                let selected = self.map_search.selected() == Some(*sat_id);
+                let (radius, alpha) = if selected { (12.0, 1.0) } else { (9.0, 0.6) };
+                draw_circle_lines(map_pos.x, map_pos.y, radius, 2.0, Color::new(0.3, 0.9, 1.0, alpha));
+            }
         }
 
         // Draw bullet trajectories (red lines showing curved path, 3x longer than default)
@@ -870,104 +2597,7 @@ impl SinglePlayerGame {
 
             // Predict bullet trajectory accounting for moving planets (especially Moon)
             // Use 600 steps (6x normal) to show longer trajectory in map
-            let prediction_steps = 600;
-            let dt = 0.1; // Time step for prediction
-            let mut predicted_positions = Vec::new();
-            let mut current_pos = bullet_pos;
-            let mut current_vel = bullet_vel;
-
-            // Create mutable copies of planet states (position, velocity, mass, radius)
-            // This allows us to simulate their motion during trajectory prediction
-            let mut planet_states: Vec<(Vec2, Vec2, f32, f32)> = self.world.planets()
-                .map(|p| (p.position(), p.velocity(), p.mass(), p.radius()))
-                .collect();
-
-            // Identify Earth (largest planet) for pinning - it doesn't move
-            let earth_index = planet_states
-                .iter()
-                .enumerate()
-                .max_by(|(_, a), (_, b)| a.3.partial_cmp(&b.3).unwrap())
-                .map(|(i, _)| i)
-                .unwrap_or(0);
-
-            for _ in 0..prediction_steps {
-                predicted_positions.push(current_pos);
-
-                // Step 1: Apply planet-to-planet gravity (e.g., Moon orbiting Earth)
-                if planet_states.len() >= 2 {
-                    for i in 0..planet_states.len() {
-                        if i == earth_index {
-                            continue; // Earth is pinned, doesn't move
-                        }
-
-                        let mut planet_acceleration = Vec2::ZERO;
-
-                        // Calculate gravity from all other planets
-                        for j in 0..planet_states.len() {
-                            if i == j {
-                                continue;
-                            }
-
-                            let (pos_i, _, mass_i, radius_i) = planet_states[i];
-                            let (pos_j, _, mass_j, _) = planet_states[j];
-
-                            let direction = pos_j - pos_i;
-                            let distance = direction.length();
-
-                            if distance > radius_i {
-                                // Use same gravity constant as actual physics (GameConstants::G = 100.0)
-                                let force_magnitude = (GameConstants::G * mass_i * mass_j) / (distance * distance);
-                                let force_direction = direction / distance;
-                                planet_acceleration += force_direction * (force_magnitude / mass_i);
-                            }
-                        }
-
-                        // Update planet velocity
-                        planet_states[i].1 += planet_acceleration * dt;
-                    }
-                }
-
-                // Step 2: Update planet positions based on their velocities
-                for i in 0..planet_states.len() {
-                    if i != earth_index {
-                        let vel = planet_states[i].1;
-                        planet_states[i].0 += vel * dt;
-                    }
-                }
-
-                // Step 3: Apply gravity from updated planet positions to bullet
-                // Use same gravity constant as actual physics
-                let mut total_accel = Vec2::ZERO;
-                for &(planet_pos, _, planet_mass, _) in &planet_states {
-                    let diff = planet_pos - current_pos;
-                    let distance = diff.length();
-                    if distance > 0.0 {
-                        let direction = diff / distance;
-                        let bullet_mass = 1.0; // Bullet mass
-                        let force_magnitude = (GameConstants::G * planet_mass * bullet_mass) / (distance * distance);
-                        let acceleration = direction * (force_magnitude / bullet_mass);
-                        total_accel += acceleration;
-                    }
-                }
-
-                // Step 4: Update bullet velocity and position
-                current_vel += total_accel * dt;
-                current_pos += current_vel * dt;
-
-                // Step 5: Check for collision with planets at their PREDICTED positions
-                // Only stop if bullet will actually hit planet when it arrives at this time
-                let mut hit_planet = false;
-                for &(planet_pos, _, _, planet_radius) in &planet_states {
-                    let distance = (planet_pos - current_pos).length();
-                    if distance < planet_radius + 5.0 {
-                        hit_planet = true;
-                        break;
-                    }
-                }
-                if hit_planet {
-                    break;
-                }
-            }
+            let predicted_positions = bullet_threats::predict_bullet_path(&self.world, bullet_pos, bullet_vel, 600);
 
             // Draw red trajectory line
             for i in 0..(predicted_positions.len() - 1) {
@@ -992,9 +2622,9 @@ impl SinglePlayerGame {
         }
 
         // Satellite list on the right side of the map
-        let list_x = map_x + map_size + 20.0;
+        let list_x = list_rect.x;
         let list_y = map_y + 60.0;
-        let list_width = 200.0;
+        let list_width = list_rect.w;
 
         // List background
         draw_rectangle(list_x, list_y - 10.0, list_width, map_size - 50.0, Color::new(0.0, 0.0, 0.0, 0.8));
@@ -1002,16 +2632,29 @@ impl SinglePlayerGame {
 
         draw_text("SATELLITES", list_x + 10.0, list_y + 10.0, 16.0, Color::new(0.0, 1.0, 0.0, 1.0));
 
+        // Search box above the list
+        self.map_search.draw(list_x, map_y, list_width);
+
         let mut y_offset = 40.0;
-        for (sat_id, satellite) in &satellites {
-            let is_marked = self.marked_satellites.contains(sat_id);
+        for sat_id in self.network_map_list() {
+            let Some(satellite) = self.world.get_satellite(sat_id) else {
+                continue;
+            };
+            let is_marked = self.marked_satellites.contains(&sat_id);
             let mark_indicator = if is_marked { "[X]" } else { "[ ]" };
 
             let fuel_pct = satellite.fuel_percentage();
-            let sat_text = format!("{} ID:{} F:{:.0}%", mark_indicator, sat_id, fuel_pct);
+            let mut sat_text = format!("{} ID:{} F:{:.0}%", mark_indicator, sat_id, fuel_pct);
+            if let Some(hub) = self.world.fuel_hub(sat_id) {
+                sat_text.push_str(&format!(" >{}", hub));
+            }
 
             let text_color = if is_marked {
                 Color::new(1.0, 1.0, 0.0, 1.0)
+            } else if self.map_selection.contains(sat_id)
+                || (self.map_search.selected() == Some(sat_id) && self.map_search.is_filtering())
+            {
+                Color::new(0.3, 0.9, 1.0, 1.0)
             } else {
                 WHITE
             };
@@ -1024,8 +2667,17 @@ impl SinglePlayerGame {
             }
         }
 
+        // Drag-box in progress, and the batch commands for the selection
+        if let Some(start) = self.map_drag_start {
+            let end = Vec2::from(mouse_position());
+            let (min, size) = (start.min(end), (end - start).abs());
+            draw_rectangle(min.x, min.y, size.x, size.y, Color::new(0.3, 0.9, 1.0, 0.1));
+            draw_rectangle_lines(min.x, min.y, size.x, size.y, 1.0, Color::new(0.3, 0.9, 1.0, 0.8));
+        }
+        self.batch_bar.draw(map_rect, &self.map_selection, self.picking_hub);
+
         // Instructions
-        let instructions = "Click satellite ID to toggle mark | ESC to close | 5 to toggle";
+        let instructions = "Click satellite to toggle mark | Shift+click or drag to select | / search (id, tanker, fuel>50, near:moon, owner:1) | ESC to close";
         let inst_dims = measure_text(instructions, None, 12, 1.0);
         draw_text(
             instructions,
@@ -1038,11 +2690,19 @@ impl SinglePlayerGame {
 
     /// Render the game
     pub fn render(&mut self) {
+        self.sky.sync(self.world.cosmetic_seed());
+        self.sky.draw(self.world.origin().to_world(self.camera.camera().target), self.camera.zoom_level());
+
         // Set camera view
         set_camera(self.camera.camera());
 
+        // Rings sit behind the planets and vehicles
+        self.world.rings().draw(&self.world, self.camera.zoom_level());
+
         // Render world
-        self.world.render();
+        self.strategic_view.render_world(&self.world, &self.camera);
+        self.strategic_view.draw_trails(&self.orbit_trails, self.trail_window, self.clock.elapsed(), self.camera.zoom_level());
+        self.bullet_effects.draw(self.camera.zoom_level());
 
         // Get all planets for trajectory calculations
         let all_planets: Vec<&Planet> = self.world.planets().collect();
@@ -1058,6 +2718,34 @@ impl SinglePlayerGame {
         // Draw planet trajectory visualizations
         self.vehicle_manager.draw_planet_trajectories(&all_planets, zoom_level);
 
+        // Draw fitted satellite orbit ellipses
+        let satellites: Vec<&Satellite> = self.world.satellites().collect();
+        self.vehicle_manager.draw_satellite_orbits(&satellites, &self.world.planets().collect::<Vec<_>>(), zoom_level);
+
+        // Draw hover/target highlights
+        self.inspector.draw_highlights(&self.world, self.vehicle_manager.target(), zoom_level);
+
+        // Draw active fuel transfer beams
+        self.transfer_effects.draw_beams(&self.world, zoom_level);
+
+        // Coverage heatmap around the planet nearest the view
+        let coverage_planet = self.coverage_planet();
+        if let (Some(cache), Some(planet_id)) = (self.coverage.as_mut(), coverage_planet) {
+            if let Some(map) = cache.get(&self.world, planet_id, get_frame_time()) {
+                coverage_heatmap::draw_heatmap(map, &self.world);
+            }
+        }
+
+        // Draw sample site and fuel deposit markers, and the rover
+        self.samples.draw_sites(&self.world, zoom_level);
+        self.landing_zones.draw_zones(&self.world, zoom_level);
+        self.deposits.draw(&self.world, zoom_level);
+        if let Some(rover) = &self.rover {
+            if let Some(planet) = self.world.get_planet(rover.planet_id()) {
+                rover.draw(planet.position(), planet.surface_radius_at(rover.angle()), Color::new(0.9, 0.9, 0.7, 1.0));
+            }
+        }
+
         // Draw overlay dots for marked satellites
         for sat_id in &self.marked_satellites {
             if let Some(satellite) = self.world.get_satellite(*sat_id) {
@@ -1102,8 +2790,87 @@ impl SinglePlayerGame {
             None
         };
 
+        // Active rocket screen position for the +fuel/s readout
+        let fuel_rate_target = self.world.active_rocket_id().and_then(|id| {
+            self.world.get_rocket(id)
+                .map(|rocket| (id, self.camera.world_to_screen(rocket.position())))
+        });
+
+        if let Some(cam) = &self.kill_cam {
+            let top_left = self.camera.screen_to_world(Vec2::ZERO);
+            let bottom_right = self.camera.screen_to_world(Vec2::new(screen_width(), screen_height()));
+            let visible = Rect::new(top_left.x, top_left.y, bottom_right.x - top_left.x, bottom_right.y - top_left.y);
+            kill_cam_overlay::draw_replay(cam, visible, zoom_level);
+        }
+        if let Some(guidance) = self.assist_guide.guidance() {
+            assist_overlay::draw_world_guides(guidance, zoom_level);
+        }
+
         // Reset to default camera for HUD
         set_default_camera();
+        self.strategic_view.draw_labels(&self.world, &self.camera);
+
+        if let Some((rocket_id, screen_pos)) = fuel_rate_target {
+            self.transfer_effects.draw_rocket_fuel_rate(rocket_id, screen_pos);
+        }
+        let rocket_pos = self.world.get_active_rocket().map(|rocket| rocket.position());
+        waypoint_markers::draw_waypoints(self.waypoints.list(), &self.camera, rocket_pos);
+        if let Some(plan) = self.world.get_active_rocket().and_then(|rocket| self.route.plan(&self.world, &self.waypoints, rocket)) {
+            route_overlay::draw_route(&plan, &self.camera);
+            route_overlay::draw_route_summary(&plan);
+        }
+        convoy_panel::draw_tanker_links(&self.convoy, &self.world, &self.camera);
+        convoy_panel::draw_convoy_panel(&self.convoy, &self.world);
+        if let Some(remote) = &self.remote {
+            relay_overlay::draw_remote_status(remote);
+        }
+        if let Some(cache) = self.coverage.as_mut() {
+            if let Some(planet_id) = coverage_planet {
+                let name = self.world.get_planet(planet_id).and_then(|planet| planet.name()).unwrap_or("planet").to_string();
+                if let Some(map) = cache.get(&self.world, planet_id, 0.0) {
+                    coverage_heatmap::draw_legend(map, &name);
+                }
+            }
+        }
+        let cargo = self.world.active_rocket_id().and_then(|rocket_id| self.market.cargo(rocket_id));
+        self.market_panel.draw(&self.market, self.profile.credits, cargo);
+        self.toasts.draw();
+        self.notifications.draw();
+        self.rocket_transfer.draw();
+        if let Some(cam) = &self.kill_cam {
+            kill_cam_overlay::draw_banner(cam);
+        }
+        self.threat_indicator.draw(&self.world, &self.camera);
+        self.damage_indicator.draw(&self.camera);
+        self.alert_banner.draw(&self.world);
+        self.samples.draw_status();
+        if let Some(rocket_id) = self.world.active_rocket_id() {
+            self.landing_zones.draw_approach(&self.world, rocket_id);
+        }
+        if let Some(rover) = &self.rover {
+            let text = format!(
+                "Rover: {:.0}/{:.0} fuel, {} sample(s) - V at the rocket to dock",
+                rover.fuel_cargo(),
+                GameConstants::ROVER_FUEL_CAPACITY,
+                rover.samples().len()
+            );
+            draw_text(&text, 10.0, screen_height() - 38.0, 18.0, Color::new(0.9, 0.9, 0.7, 0.9));
+        }
+        self.timeline_panel.draw(&self.timeline, self.clock.elapsed());
+        mission_clock_display::draw_mission_clock(self.clock.elapsed(), self.clock.warp());
+        if let Some(monitor) = &self.conservation {
+            conservation_overlay::draw(monitor, self.world.gravity_simulator().model());
+        }
+        if let Some(challenge) = &self.challenge {
+            let fuel = self.world.get_active_rocket().map(|rocket| rocket.current_fuel()).unwrap_or(0.0);
+            let best = self.profile.daily_best.get(&challenge.date.label()).copied();
+            challenge_panel::draw_challenge_panel(challenge, self.clock.elapsed(), fuel, self.challenge_outcome.as_ref(), best);
+        }
+        if let Some(run) = &self.scenario {
+            let best = self.profile.scenario_best.get(&run.scenario().name).copied();
+            challenge_panel::draw_scenario_panel(run, best);
+            assist_overlay::draw_assist_panel(self.assist_guide.guidance(), run.flyby());
+        }
 
         // Update and render GameInfoDisplay
         let all_planets: Vec<&Planet> = self.world.planets().collect();
@@ -1126,6 +2893,7 @@ impl SinglePlayerGame {
             all_planets.first().copied()
         };
 
+        self.info_display.set_launch_window(self.launch_window(), self.window_warp);
         self.info_display.update_all_panels(
             active_rocket,
             &all_planets,
@@ -1183,12 +2951,12 @@ impl SinglePlayerGame {
             );
         }
 
+        // Hover tooltip
+        self.inspector.draw_tooltip(&self.world, self.vehicle_manager.target(), &HashMap::new());
+
         // Draw controls button in top-right corner
         let screen_w = screen_width();
-        let button_x = screen_w - 50.0;
-        let button_y = 10.0;
-        let button_w = 40.0;
-        let button_h = 30.0;
+        let Rect { x: button_x, y: button_y, w: button_w, h: button_h } = Self::controls_button_rect();
 
         // Button background
         draw_rectangle(button_x, button_y, button_w, button_h, Color::new(0.2, 0.2, 0.2, 0.8));
@@ -1208,10 +2976,8 @@ impl SinglePlayerGame {
         // Draw controls popup if showing
         if self.show_controls {
             let screen_h = screen_height();
-            let popup_w = 800.0;  // Wider for two columns
-            let popup_h = 600.0;  // Taller to fit more controls
-            let popup_x = screen_w / 2.0 - popup_w / 2.0;
-            let popup_y = screen_h / 2.0 - popup_h / 2.0;
+            let popup = SafeArea::current().popup(CONTROLS_POPUP_SIZE.0, CONTROLS_POPUP_SIZE.1);
+            let (popup_x, popup_y, popup_w, popup_h) = (popup.x, popup.y, popup.w, popup.h);
 
             // Semi-transparent overlay
             draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::new(0.0, 0.0, 0.0, 0.5));
@@ -1240,26 +3006,40 @@ impl SinglePlayerGame {
                 ("SPACE", "Apply thrust"),
                 ("A / LEFT", "Rotate left"),
                 ("D / RIGHT", "Rotate right"),
-                ("Q", "Zoom in"),
-                ("E", "Zoom out"),
-                ("MOUSE WHEEL", "Zoom"),
-                ("C", "Convert to satellite"),
+                ("Q / E / WHEEL", "Zoom in / out"),
+                ("HOME / END", "Zoom to rocket / whole system"),
+                ("SHIFT+1-6", "Zoom presets"),
+                ("C / Z", "Convert / retrieve satellite"),
+                ("SHIFT+Z", "Tractor beam: grab / release debris"),
                 ("W", "Shoot bullet"),
-                ("R", "Refuel from planet"),
-                ("P", "Pause/Unpause"),
+                ("R / I (hold)", "Refuel from planet / fuel a nearby rocket"),
+                ("S", "Collect sample (when landed)"),
+                ("V", "Deploy / dock rover"),
+                ("B / J / U", "Waypoint / route planet / undo stop"),
+                ("SHIFT+B", "Mark landing target at cursor"),
+                ("H", "Session timeline ([ ] to step)"),
+                ("M", "Market: prices and contracts"),
+                ("P / - / =", "Pause / time warp down / up"),
             ];
 
             let controls_right = [
                 ("T", "Toggle trajectory"),
                 ("G", "Toggle gravity forces"),
+                ("L / SHIFT+L", "Satellite orbits / orbit trails"),
+                ("N", "Tanker run to target satellite"),
+                ("6", "Station ops: auto-refuel while landed"),
+                ("K / F", "Toggle tidal breakup / fuel balancing"),
                 ("TAB", "Switch planet (panels 2/3)"),
                 ("1", "Toggle rocket panel"),
                 ("2", "Toggle planet panel"),
-                ("3", "Toggle orbit panel"),
+                ("3 / Y", "Orbit panel / warp to Moon window"),
+                ("4", "Toggle satellite network panel"),
                 ("5", "Toggle network map"),
+                ("7 / 8", "Reset panel layout / coverage heatmap"),
                 ("9", "Hide all panels"),
                 ("0", "Show all panels"),
-                ("F5", "Quick save"),
+                ("F5 / F6", "Quick save / export JSON"),
+                ("F8", "Energy / momentum drift overlay"),
                 ("ENTER", "Toggle this menu"),
                 ("ESC", "Return to menu"),
             ];
@@ -1308,6 +3088,25 @@ impl SinglePlayerGame {
                 y += line_height;
             }
 
+            // Settings
+            let slider_rect = Self::rumble_slider_rect();
+            let connected = self.haptics.is_connected();
+            let label_color = if connected { Color::new(0.8, 0.8, 1.0, 1.0) } else { GRAY };
+            draw_text("RUMBLE", popup_x + 30.0, slider_rect.y + 15.0, font_size, label_color);
+            self.rumble_slider.draw(slider_rect, connected);
+            if !connected {
+                draw_text("No controller connected", slider_rect.right() + 70.0, slider_rect.y + 15.0, 15.0, GRAY);
+            }
+            let toggle = Self::shake_toggle_rect();
+            draw_text("SCREEN SHAKE", popup_x + 30.0, toggle.y + 17.0, font_size, Color::new(0.8, 0.8, 1.0, 1.0));
+            Self::draw_toggle(toggle, self.camera.shake_enabled());
+            let options = self.bullet_effects.options();
+            let bullet_toggles = [("TRACERS", options.tracers), ("FLASHES", options.impact_flashes), ("ARCS", options.predicted_arcs)];
+            for (rect, (label, on)) in Self::bullet_toggle_rects().into_iter().zip(bullet_toggles) {
+                draw_text(label, rect.x - 70.0, rect.y + 17.0, 16.0, Color::new(0.8, 0.8, 1.0, 1.0));
+                Self::draw_toggle(rect, on);
+            }
+
             // Footer text
             let footer = "Click outside or press ESC to close";
             let footer_dims = measure_text(footer, None, 14, 1.0);
@@ -1331,7 +3130,34 @@ impl SinglePlayerGame {
     }
 
     pub fn game_time(&self) -> f32 {
-        self.game_time
+        self.clock.elapsed()
+    }
+
+    /// The flight so far, for the debrief screen
+    pub fn debrief(&self) -> &Debrief {
+        self.flight_log.debrief()
+    }
+
+    pub fn retry_plan(&self) -> RetryPlan {
+        if let Some(challenge) = &self.challenge {
+            return RetryPlan::Challenge(challenge.clone());
+        }
+        match &self.scenario {
+            Some(run) => RetryPlan::Scenario(run.scenario().clone(), self.current_map.clone()),
+            None => RetryPlan::NewGame(self.current_map.clone()),
+        }
+    }
+
+    /// Status shown in Discord Rich Presence
+    pub fn presence_info(&self) -> PresenceInfo {
+        PresenceInfo {
+            mode: "Single Player".to_string(),
+            map: Some(self.current_map.name.clone()),
+            altitude: self.world.active_rocket_id().and_then(|id| self.world.rocket_altitude(id)),
+            player_count: 1,
+            max_players: 1,
+            join_address: None,
+        }
     }
 }
 
@@ -1,20 +1,58 @@
-// Split-Screen Multiplayer - Two players on same device
-// Shared world simulation with separate controls and dynamic camera
+// Split-Screen Multiplayer - Two to four players on same device
+// Shared world simulation with separate controls, and a viewport and camera per player
 
 use macroquad::prelude::*;
+use std::collections::HashMap;
 
-use crate::entities::{GameObject, Planet, Rocket};
+use crate::entities::{Planet, Rocket, Satellite};
 use crate::game_constants::GameConstants;
-use crate::save_system::GameSaveData;
-use crate::systems::{World, VehicleManager, PlayerInput, PlayerInputState, EntityId};
-use crate::ui::{Camera, GameInfoDisplay};
+use crate::networking::PresenceInfo;
+use crate::physics::CollisionRules;
+use crate::player::{Gamepads, MAX_GAMEPADS};
+use crate::save_system::{GameSaveData, PlayerProfile};
+use crate::systems::{World, VehicleManager, PlayerInput, PlayerInputState, EntityId, AlertEngine, Controllers, HapticEvent, Haptics};
+use crate::ui::camera::CRAFT_VIEW_ZOOM;
+use crate::ui::viewport_layout::MAX_VIEWPORTS;
+use crate::ui::{AlertBanner, BulletEffects, Camera, CameraImpulse, DamageIndicator, EntityInspector, FuelTransferEffects, GameInfoDisplay, RocketTransferReadout, SafeArea, Anchor, Sky, StrategicView, ThreatIndicator, ToastManager, ViewportLayout};
+use crate::ui::mission_clock_display;
+
+/// Local players a split-screen game takes
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = MAX_VIEWPORTS;
+
+/// Rocket colors for Players 1-4
+const PLAYER_COLORS: [Color; MAX_PLAYERS] = [
+    Color::new(1.0, 0.39, 0.39, 1.0),  // Red
+    Color::new(0.39, 0.39, 1.0, 1.0),  // Blue
+    Color::new(0.39, 0.86, 0.39, 1.0), // Green
+    Color::new(0.94, 0.86, 0.31, 1.0), // Yellow
+];
+/// Trajectory line colors for Players 1-4
+const TRAJECTORY_COLORS: [Color; MAX_PLAYERS] = [
+    Color::new(1.0, 0.0, 0.0, 0.6),
+    Color::new(0.0, 0.5, 1.0, 0.6),
+    Color::new(0.2, 0.9, 0.2, 0.6),
+    Color::new(1.0, 0.85, 0.0, 0.6),
+];
+/// Reads one action's key from a player's bindings (a row of the controls popup)
+type KeyOf = fn(&PlayerInput) -> KeyCode;
+
+/// Each player spawns this far around the planet from the one before
+const SPAWN_SPACING_DEGREES: f32 = 5.0;
+
+/// Profile key for a player's HUD panel layout (two-player games keep their original keys)
+fn hud_layout_key(player: usize, player_count: usize) -> String {
+    if player_count == 2 {
+        format!("split_screen_p{}", player + 1)
+    } else {
+        format!("split_screen_{}p_p{}", player_count, player + 1)
+    }
+}
 
-/// Camera mode for split-screen
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum CameraMode {
-    ShowBoth,               // Default: show both players
-    FocusPlayer1(u32),      // Focus on P1, with remaining time in deciseconds
-    FocusPlayer2(u32),      // Focus on P2, with remaining time in deciseconds
+/// `position` and `velocity` turned `angle` radians around `center`
+fn rotate_about(center: Vec2, position: Vec2, velocity: Vec2, angle: f32) -> (Vec2, Vec2) {
+    let turn = Vec2::from_angle(angle);
+    (center + turn.rotate(position - center), turn.rotate(velocity))
 }
 
 /// Split-screen game result
@@ -25,32 +63,32 @@ pub enum SplitScreenResult {
     Quit,
 }
 
+/// One local player: their keys, rocket, and the view and HUD in their part of the screen
+struct SplitPlayer {
+    input: PlayerInput,
+    state: PlayerInputState,
+    rocket_id: Option<EntityId>,
+    camera: Camera,
+    info_display: GameInfoDisplay,
+    rocket_transfer: RocketTransferReadout, // Held transfer key readout
+
+    // Directional arrows for bullets heading at this player's rocket
+    threat_indicator: ThreatIndicator,
+    damage_indicator: DamageIndicator, // Edge flashes for hits taken, markers for hits landed
+}
+
 /// Split-screen multiplayer game mode
 pub struct SplitScreenGame {
     world: World,
-    camera: Camera,
-    player1_info_display: GameInfoDisplay,  // Player 1 UI (left side, red)
-    player2_info_display: GameInfoDisplay,  // Player 2 UI (right side, blue)
+    players: Vec<SplitPlayer>,
+    layout: ViewportLayout,
     vehicle_manager: VehicleManager,
+    gamepads: Gamepads, // START on a controller hands it to the next player
+    rumble: Vec<Haptics>, // One per controller slot; each rumbles for whoever holds that pad
     game_time: f32,
     is_paused: bool,
     show_controls: bool,
 
-    // Player 1 state
-    player1_input: PlayerInput,
-    player1_state: PlayerInputState,
-    player1_rocket_id: Option<EntityId>,
-
-    // Player 2 state
-    player2_input: PlayerInput,
-    player2_state: PlayerInputState,
-    player2_rocket_id: Option<EntityId>,
-
-    // Camera management
-    camera_mode: CameraMode,
-    camera_focus_duration: f32,  // 10 seconds
-    manual_zoom_mode: bool,  // true when user manually zooms, disables auto-zoom
-
     // Save/load
     current_save_name: Option<String>,
     last_auto_save: f32,
@@ -62,50 +100,119 @@ pub struct SplitScreenGame {
 
     // Save celebration (F5 quick save)
     save_celebration_timer: f32,  // Time remaining for "what a save!!" text
+
+    // Fuel transfer beams, +fuel/s readouts and hum
+    transfer_effects: FuelTransferEffects,
+    strategic_view: StrategicView, // Icons, clusters and labels when zoomed far out
+    sky: Sky, // Stars and nebulae from the world's cosmetic seed
+    bullet_effects: BulletEffects, // Tracers, impact flashes and predicted arcs
+
+    // Landing grade toasts and persistent best-landing stats
+    toasts: ToastManager,
+    profile: PlayerProfile,
+
+    // Hover tooltips and click-to-target in the world view
+    inspector: EntityInspector,
+
+    // Low fuel / orbit decay / incoming bullet warnings
+    alerts: AlertEngine,
+    alert_banner: AlertBanner,
 }
 
 impl SplitScreenGame {
-    pub fn new(window_size: Vec2) -> Self {
-        // Create Player 1 info display (left side, red theme)
-        let player1_info_display = GameInfoDisplay::new_for_player(0);
+    /// A game for `player_count` local players (clamped to 2-4)
+    pub fn new(window_size: Vec2, player_count: usize) -> Self {
+        let profile = PlayerProfile::load_or_default();
+        let player_count = player_count.clamp(MIN_PLAYERS, MAX_PLAYERS);
+        let layout = ViewportLayout::new(player_count, window_size);
+
+        // Each player gets the next key set and their own view (controllers are claimed with START)
+        let players = (0..player_count)
+            .map(|index| {
+                let viewport = layout.viewport(index);
+                let mut info_display = GameInfoDisplay::new_for_player(index, viewport);
+                if let Some(layout) = profile.hud_layout(&hud_layout_key(index, player_count)) {
+                    info_display.apply_layout(layout);
+                }
 
-        // Create Player 2 info display (right side, blue theme)
-        let player2_info_display = GameInfoDisplay::new_for_player(1);
+                // Player 1 keeps the profile's rebinds, as in the other modes
+                let mut input = PlayerInput::for_player(index);
+                if index == 0 {
+                    input.apply_bindings(&profile.key_bindings);
+                }
+
+                let mut camera = Camera::new(window_size);
+                camera.set_viewport(Some(viewport), window_size);
+                camera.set_shake_enabled(profile.screen_shake);
+
+                SplitPlayer {
+                    input,
+                    state: PlayerInputState::new(index as u32),
+                    rocket_id: None,
+                    camera,
+                    info_display,
+                    rocket_transfer: RocketTransferReadout::new(),
+                    threat_indicator: ThreatIndicator::new(),
+                    damage_indicator: DamageIndicator::new(),
+                }
+            })
+            .collect();
 
         SplitScreenGame {
             world: World::new(),
-            camera: Camera::new(window_size),
-            player1_info_display,
-            player2_info_display,
+            players,
+            layout,
             vehicle_manager: VehicleManager::new(),
+            gamepads: Gamepads::disconnected(),
+            rumble: (0..MAX_GAMEPADS).map(|_| Haptics::disconnected(profile.rumble_intensity)).collect(),
             game_time: 0.0,
             is_paused: false,
             show_controls: false,
 
-            player1_input: PlayerInput::player1(),
-            player1_state: PlayerInputState::new(0),
-            player1_rocket_id: None,
-
-            player2_input: PlayerInput::player2(),
-            player2_state: PlayerInputState::new(1),
-            player2_rocket_id: None,
-
-            camera_mode: CameraMode::ShowBoth,
-            camera_focus_duration: 10.0,
-            manual_zoom_mode: false,
-
             current_save_name: None,
             last_auto_save: 0.0,
             auto_save_interval: 60.0,
             rocket_spawn_position: Vec2::ZERO,
             rocket_spawn_velocity: Vec2::ZERO,
             save_celebration_timer: 0.0,
+            transfer_effects: FuelTransferEffects::new(),
+            strategic_view: StrategicView::new(),
+            sky: Sky::new(),
+            bullet_effects: BulletEffects::new(profile.bullet_view),
+            toasts: ToastManager::new(),
+            profile,
+            inspector: EntityInspector::new(),
+            alerts: AlertEngine::new(),
+            alert_banner: AlertBanner::new(),
         }
     }
 
-    /// Initialize a new game with two players
+    pub fn player_count(&self) -> usize {
+        self.players.len()
+    }
+
+    /// Where player `index` spawns: Player 1 at the spawn point, each next player a few degrees further round
+    fn spawn_state(&self, index: usize) -> (Vec2, Vec2) {
+        let center = Vec2::new(GameConstants::MAIN_PLANET_X, GameConstants::MAIN_PLANET_Y);
+        let angle = (SPAWN_SPACING_DEGREES * index as f32).to_radians();
+        rotate_about(center, self.rocket_spawn_position, self.rocket_spawn_velocity, angle)
+    }
+
+    /// Put a fresh rocket under player `index`'s control
+    fn spawn_player_rocket(&mut self, index: usize, position: Vec2, velocity: Vec2) -> EntityId {
+        let rocket = Rocket::new(position, velocity, PLAYER_COLORS[index], GameConstants::ROCKET_BASE_MASS);
+        let rocket_id = self.world.add_rocket(rocket);
+        self.players[index].rocket_id = Some(rocket_id);
+        if index == 0 {
+            self.world.set_active_rocket(Some(rocket_id));
+        }
+        rocket_id
+    }
+
+    /// Initialize a new game with a rocket per player
     pub fn initialize_new_game(&mut self) {
         self.world.clear_all();
+        self.world.set_cosmetic_seed(Sky::fresh_seed());
         self.game_time = 0.0;
 
         // Create main planet (Earth)
@@ -132,7 +239,7 @@ impl SplitScreenGame {
         secondary_planet.set_velocity(Vec2::new(0.0, -moon_velocity));
         self.world.add_planet(secondary_planet);
 
-        // Spawn Player 1 rocket at default position
+        // Player 1 spawns at the default position, the others spaced round the planet from there
         let rocket_spawn_distance = GameConstants::MAIN_PLANET_RADIUS + 200.0;
         self.rocket_spawn_position = Vec2::new(
             GameConstants::MAIN_PLANET_X + rocket_spawn_distance,
@@ -140,92 +247,48 @@ impl SplitScreenGame {
         );
         self.rocket_spawn_velocity = Vec2::new(0.0, 0.0);
 
-        let rocket1 = Rocket::new(
-            self.rocket_spawn_position,
-            self.rocket_spawn_velocity,
-            Color::from_rgba(255, 100, 100, 255),  // Red for Player 1
-            GameConstants::ROCKET_BASE_MASS,
-        );
-        let rocket1_id = self.world.add_rocket(rocket1);
-        self.player1_rocket_id = Some(rocket1_id);
-        self.world.set_active_rocket(Some(rocket1_id));
-
-        // Spawn Player 2 rocket with 5 degree offset from Player 1
-        let offset_angle = 5.0_f32.to_radians();
-        let center = Vec2::new(GameConstants::MAIN_PLANET_X, GameConstants::MAIN_PLANET_Y);
-
-        // Rotate spawn position by 5 degrees around planet center
-        let rel_pos = self.rocket_spawn_position - center;
-        let rotated_x = rel_pos.x * offset_angle.cos() - rel_pos.y * offset_angle.sin();
-        let rotated_y = rel_pos.x * offset_angle.sin() + rel_pos.y * offset_angle.cos();
-        let p2_spawn_pos = center + Vec2::new(rotated_x, rotated_y);
-
-        // Rotate velocity vector by 5 degrees
-        let vel_x = self.rocket_spawn_velocity.x * offset_angle.cos() - self.rocket_spawn_velocity.y * offset_angle.sin();
-        let vel_y = self.rocket_spawn_velocity.x * offset_angle.sin() + self.rocket_spawn_velocity.y * offset_angle.cos();
-        let p2_spawn_vel = Vec2::new(vel_x, vel_y);
-
-        let rocket2 = Rocket::new(
-            p2_spawn_pos,
-            p2_spawn_vel,
-            Color::from_rgba(100, 100, 255, 255),  // Blue for Player 2
-            GameConstants::ROCKET_BASE_MASS,
-        );
-        let rocket2_id = self.world.add_rocket(rocket2);
-        self.player2_rocket_id = Some(rocket2_id);
+        for index in 0..self.players.len() {
+            let (position, velocity) = self.spawn_state(index);
+            self.spawn_player_rocket(index, position, velocity);
+        }
 
-        // Set camera to show both players
-        self.update_camera_for_mode();
+        // Start every view on its own rocket
+        self.update_cameras();
+        for player in &mut self.players {
+            player.camera.snap_to_target();
+        }
 
-        log::info!("Split-screen game initialized with two players");
+        log::info!("Split-screen game initialized with {} players", self.players.len());
     }
 
-    /// Load a single player save and add Player 2
-    pub fn load_from_save_with_player2(&mut self, save_data: GameSaveData, save_name: String) {
+    /// Load a single player save and add the other players
+    pub fn load_from_save_with_players(&mut self, save_data: GameSaveData, save_name: String) {
         self.load_from_snapshot(save_data);
 
         // Player 1 gets the active rocket from the save
-        self.player1_rocket_id = self.world.active_rocket_id();
-
-        // Spawn Player 2 rocket with 5 degree offset
-        if let Some(p1_rocket_id) = self.player1_rocket_id {
-            if let Some(p1_rocket) = self.world.get_rocket(p1_rocket_id) {
-                let p1_pos = p1_rocket.position();
-                let p1_vel = p1_rocket.velocity();
-
-                // Find nearest planet to use as center
-                let center = Vec2::new(GameConstants::MAIN_PLANET_X, GameConstants::MAIN_PLANET_Y);
-
-                // 5 degree offset
-                let offset_angle = 5.0_f32.to_radians();
-                let rel_pos = p1_pos - center;
-                let rotated_x = rel_pos.x * offset_angle.cos() - rel_pos.y * offset_angle.sin();
-                let rotated_y = rel_pos.x * offset_angle.sin() + rel_pos.y * offset_angle.cos();
-                let p2_pos = center + Vec2::new(rotated_x, rotated_y);
-
-                let vel_x = p1_vel.x * offset_angle.cos() - p1_vel.y * offset_angle.sin();
-                let vel_y = p1_vel.x * offset_angle.sin() + p1_vel.y * offset_angle.cos();
-                let p2_vel = Vec2::new(vel_x, vel_y);
-
-                let rocket2 = Rocket::new(
-                    p2_pos,
-                    p2_vel,
-                    Color::from_rgba(100, 100, 255, 255),  // Blue for Player 2
-                    GameConstants::ROCKET_BASE_MASS,
-                );
-                let rocket2_id = self.world.add_rocket(rocket2);
-                self.player2_rocket_id = Some(rocket2_id);
-
-                log::info!("Loaded save '{}' with Player 2 added", save_name);
+        self.players[0].rocket_id = self.world.active_rocket_id();
+
+        // The others spawn spaced round the planet from Player 1's rocket
+        let p1_state = self.players[0].rocket_id.and_then(|id| self.world.get_rocket(id)).map(|rocket| (rocket.position(), rocket.velocity()));
+        if let Some((p1_pos, p1_vel)) = p1_state {
+            let center = Vec2::new(GameConstants::MAIN_PLANET_X, GameConstants::MAIN_PLANET_Y);
+            for index in 1..self.players.len() {
+                let angle = (SPAWN_SPACING_DEGREES * index as f32).to_radians();
+                let (position, velocity) = rotate_about(center, p1_pos, p1_vel, angle);
+                self.spawn_player_rocket(index, position, velocity);
             }
+            log::info!("Loaded save '{}' with {} more players added", save_name, self.players.len() - 1);
         }
 
         self.current_save_name = Some(save_name);
-        self.update_camera_for_mode();
+        self.update_cameras();
     }
 
     fn load_from_snapshot(&mut self, snapshot: GameSaveData) {
         self.world.clear_all_entities();
+        self.world.set_origin(snapshot.origin.into());
+        self.world.set_arena(snapshot.arena);
+        self.world.set_cosmetic_seed(snapshot.cosmetic_seed);
         self.game_time = snapshot.game_time;
 
         let planet_count = snapshot.planets.len();
@@ -298,64 +361,169 @@ impl SplitScreenGame {
 
         // Save Player 1's rocket as active (split screen uses Player 1 as primary)
         save_data.player_id = Some(0);
-        save_data.active_rocket_id = self.player1_rocket_id;
+        save_data.active_rocket_id = self.players[0].rocket_id;
+        save_data.origin = self.world.origin().into();
+        save_data.arena = self.world.arena();
+        save_data.cosmetic_seed = self.world.cosmetic_seed();
 
-        // Save camera state
+        // Save Player 1's camera state
         use crate::save_system::SavedCamera;
+        let camera = &self.players[0].camera;
         save_data.camera = SavedCamera {
-            center: self.camera.camera().target.into(),
-            zoom: self.camera.zoom_level(),
+            center: camera.camera().target.into(),
+            zoom: camera.zoom_level(),
         };
 
         save_data
     }
 
-    /// Update camera based on current mode
-    fn update_camera_for_mode(&mut self) {
-        match self.camera_mode {
-            CameraMode::ShowBoth => {
-                // Calculate midpoint between both players
-                if let (Some(r1_id), Some(r2_id)) = (self.player1_rocket_id, self.player2_rocket_id) {
-                    if let (Some(r1), Some(r2)) = (self.world.get_rocket(r1_id), self.world.get_rocket(r2_id)) {
-                        let midpoint = (r1.position() + r2.position()) / 2.0;
-                        self.camera.set_center(midpoint);
-
-                        // Only auto-calculate zoom if not in manual zoom mode
-                        if !self.manual_zoom_mode {
-                            let distance = r1.position().distance(r2.position());
-                            let zoom = (distance / 300.0).max(1.0).min(10.0);
-                            self.camera.set_target_zoom(zoom);
-                        }
-                    }
-                }
+    /// Point each player's camera at their own rocket
+    fn update_cameras(&mut self) {
+        for player in &mut self.players {
+            if let Some(rocket) = player.rocket_id.and_then(|id| self.world.get_rocket(id)) {
+                player.camera.set_center(rocket.position());
             }
-            CameraMode::FocusPlayer1(_) => {
-                if let Some(r1_id) = self.player1_rocket_id {
-                    if let Some(r1) = self.world.get_rocket(r1_id) {
-                        self.camera.set_center(r1.position());
-                        self.camera.set_target_zoom(1.0);
-                    }
-                }
+        }
+    }
+
+    /// Index of the player flying `rocket_id`
+    fn player_for_rocket(&self, rocket_id: EntityId) -> Option<usize> {
+        self.players.iter().position(|player| player.rocket_id == Some(rocket_id))
+    }
+
+    fn player_rocket_ids(&self) -> Vec<EntityId> {
+        self.players.iter().filter_map(|player| player.rocket_id).collect()
+    }
+
+    /// Shake every view for an event at `position` (each by its own distance to it)
+    fn camera_impulse(&mut self, impulse: CameraImpulse, position: Vec2) {
+        for player in &mut self.players {
+            player.camera.impulse(impulse, position);
+        }
+    }
+
+    /// Read every controller slot (players claim pads with START) and rumble each pad
+    pub fn set_controllers(&mut self, controllers: &Controllers) {
+        self.gamepads = controllers.gamepads();
+        let intensity = self.profile.rumble_intensity;
+        self.rumble = (0..MAX_GAMEPADS).map(|slot| controllers.haptics(slot, intensity)).collect();
+    }
+
+    /// Rumble player `index`'s controller (if they have one)
+    fn rumble_player(&mut self, index: usize, event: HapticEvent) {
+        if let Some(haptics) = self.players[index].input.gamepad.and_then(|slot| self.rumble.get_mut(slot)) {
+            haptics.trigger(event);
+        }
+    }
+
+    /// Engine rumble on each claimed pad follows its player's throttle while there's fuel to burn
+    fn update_rumble(&mut self, delta_time: f32) {
+        for (slot, haptics) in self.rumble.iter_mut().enumerate() {
+            let throttle = self
+                .players
+                .iter()
+                .find(|player| player.input.gamepad == Some(slot))
+                .and_then(|player| player.rocket_id)
+                .and_then(|rocket_id| self.world.get_rocket(rocket_id))
+                .filter(|rocket| rocket.current_fuel() > 0.0)
+                .map_or(0.0, |rocket| rocket.thrust_level());
+            haptics.update(delta_time, throttle);
+        }
+    }
+
+    fn stop_rumble(&mut self) {
+        for haptics in &mut self.rumble {
+            haptics.stop();
+        }
+    }
+
+    /// START on a controller gives it to the next player without one (Player 1 first);
+    /// pressing it again passes it on, and after the last player it goes back to unassigned
+    fn handle_controller_claims(&mut self) {
+        let claims: Vec<usize> = self.gamepads.start_pressed().collect();
+        for slot in claims {
+            let owner = self.players.iter().position(|player| player.input.gamepad == Some(slot));
+            if let Some(owner) = owner {
+                self.players[owner].input.set_gamepad(None);
             }
-            CameraMode::FocusPlayer2(_) => {
-                if let Some(r2_id) = self.player2_rocket_id {
-                    if let Some(r2) = self.world.get_rocket(r2_id) {
-                        self.camera.set_center(r2.position());
-                        self.camera.set_target_zoom(1.0);
-                    }
+            let first = owner.map_or(0, |owner| owner + 1);
+            match (first..self.players.len()).find(|&index| self.players[index].input.gamepad.is_none()) {
+                Some(index) => {
+                    self.players[index].input.set_gamepad(Some(slot));
+                    self.toasts.push(format!("Controller {} -> Player {}", slot + 1, index + 1), PLAYER_COLORS[index]);
                 }
+                None => self.toasts.push(format!("Controller {} unassigned", slot + 1), LIGHTGRAY),
             }
         }
+
+        for player in &mut self.players {
+            player.input.sync_gamepad(&self.gamepads);
+        }
     }
 
     /// Handle input for game controls
     pub fn handle_input(&mut self) -> SplitScreenResult {
+        self.gamepads.poll();
+        self.handle_controller_claims();
+
+        // Keep the views in step with the window
+        let screen = Vec2::new(screen_width(), screen_height());
+        if self.layout.resize(screen) {
+            for (index, player) in self.players.iter_mut().enumerate() {
+                player.camera.set_viewport(Some(self.layout.viewport(index)), screen);
+            }
+        }
+
+        // HUD panels take the mouse first (drag, resize, collapse)
+        // Later players' panels draw on top, unless an earlier player is mid-drag
+        let mouse_on_hud = !self.show_controls
+            && match self.players.iter().position(|player| player.info_display.is_dragging()) {
+                Some(dragging) => self.players[dragging].info_display.handle_mouse(),
+                None => self.players.iter_mut().rev().any(|player| player.info_display.handle_mouse()),
+            };
+        let player_count = self.players.len();
+        for (index, player) in self.players.iter_mut().enumerate() {
+            if player.info_display.take_layout_changed() {
+                self.profile.save_hud_layout(&hud_layout_key(index, player_count), player.info_display.layout());
+            }
+        }
+        if is_key_pressed(KeyCode::Key7) {
+            for player in &mut self.players {
+                player.info_display.reset_layout();
+            }
+        }
+
+        // Hover picking and click-to-target in the view under the mouse (suppressed while overlays are open)
+        let (mouse_x, mouse_y) = mouse_position();
+        let hovered_view = self.layout.viewport_at(Vec2::new(mouse_x, mouse_y));
+        match hovered_view.filter(|_| !self.show_controls && !mouse_on_hud) {
+            None => self.inspector.clear(),
+            Some(view) => {
+                self.inspector.update(&self.world, &self.players[view].camera);
+                if is_mouse_button_pressed(MouseButton::Left) {
+                    if let Some(hovered) = self.inspector.hovered() {
+                        self.vehicle_manager.toggle_target(hovered);
+                        log::info!("Target: {:?}", self.vehicle_manager.target());
+                    }
+                }
+            }
+        }
+
         // Check for escape to return to menu or close controls popup
         if is_key_pressed(KeyCode::Escape) {
             if self.show_controls {
                 self.show_controls = false;
                 self.is_paused = false;
             } else {
+                self.transfer_effects.clear();
+                self.toasts.clear();
+                self.alerts.clear();
+                self.alert_banner.clear();
+                for player in &mut self.players {
+                    player.threat_indicator.clear();
+                    player.damage_indicator.clear();
+                }
+                self.stop_rumble();
                 return SplitScreenResult::ReturnToMenu;
             }
         }
@@ -367,32 +535,29 @@ impl SplitScreenGame {
         }
 
         // Toggle UI panels with 0-9 keys (work even when paused)
-        if is_key_pressed(KeyCode::Key0) {
-            self.player1_info_display.toggle_rocket_panel();
-            self.player2_info_display.toggle_rocket_panel();
-        }
-        if is_key_pressed(KeyCode::Key1) {
-            self.player1_info_display.toggle_planet_panel();
-            self.player2_info_display.toggle_planet_panel();
-        }
-        if is_key_pressed(KeyCode::Key2) {
-            self.player1_info_display.toggle_orbit_panel();
-            self.player2_info_display.toggle_orbit_panel();
-        }
-        if is_key_pressed(KeyCode::Key3) {
-            self.player1_info_display.toggle_network_panel();
-            self.player2_info_display.toggle_network_panel();
-        }
-        if is_key_pressed(KeyCode::Key8) {
-            self.player1_info_display.show_all_panels();
-            self.player2_info_display.show_all_panels();
-        }
-        if is_key_pressed(KeyCode::Key9) {
-            self.player1_info_display.hide_all_panels();
-            self.player2_info_display.hide_all_panels();
+        for player in &mut self.players {
+            let display = &mut player.info_display;
+            if is_key_pressed(KeyCode::Key0) {
+                display.toggle_rocket_panel();
+            }
+            if is_key_pressed(KeyCode::Key1) {
+                display.toggle_planet_panel();
+            }
+            if is_key_pressed(KeyCode::Key2) {
+                display.toggle_orbit_panel();
+            }
+            if is_key_pressed(KeyCode::Key3) {
+                display.toggle_network_panel();
+            }
+            if is_key_pressed(KeyCode::Key8) {
+                display.show_all_panels();
+            }
+            if is_key_pressed(KeyCode::Key9) {
+                display.hide_all_panels();
+            }
         }
 
-        // Visualization toggles (shared for both players)
+        // Visualization toggles (shared by all players)
         if is_key_pressed(KeyCode::T) {
             self.vehicle_manager.toggle_trajectory();
             log::info!("Toggled trajectory visualization: {}", self.vehicle_manager.visualization().show_trajectory);
@@ -401,6 +566,10 @@ impl SplitScreenGame {
             self.vehicle_manager.toggle_gravity_forces();
             log::info!("Toggled gravity force visualization: {}", self.vehicle_manager.visualization().show_gravity_forces);
         }
+        if is_key_pressed(KeyCode::L) {
+            self.vehicle_manager.toggle_satellite_orbits();
+            log::info!("Toggled satellite orbit lines: {}", self.vehicle_manager.visualization().show_satellite_orbits);
+        }
         if is_key_pressed(KeyCode::Tab) {
             let num_bodies = self.world.planets().count();
             self.vehicle_manager.toggle_reference_body(num_bodies);
@@ -418,36 +587,17 @@ impl SplitScreenGame {
             log::info!("Game {}", if self.is_paused { "paused" } else { "unpaused" });
         }
 
-        // Mouse wheel zoom - always works, switches to ShowBoth mode with manual zoom
+        // Zoom always works: the mouse wheel zooms the view under the mouse, each player's keys their own
         let mouse_wheel = mouse_wheel().1;
-        if mouse_wheel != 0.0 {
-            self.camera_mode = CameraMode::ShowBoth;
-            self.manual_zoom_mode = true;
-            self.camera.adjust_zoom(-mouse_wheel * 0.02);
-        }
-
-        // Keyboard zoom controls - only work when camera is focused on that player
-        match self.camera_mode {
-            CameraMode::FocusPlayer1(_) => {
-                // Player 1 focused: Q = zoom in, E = zoom out
-                if is_key_down(KeyCode::Q) {
-                    self.camera.adjust_zoom(-0.02);
-                }
-                if is_key_down(KeyCode::E) {
-                    self.camera.adjust_zoom(0.02);
-                }
-            }
-            CameraMode::FocusPlayer2(_) => {
-                // Player 2 focused: / = zoom in, ' = zoom out
-                if is_key_down(KeyCode::Slash) {
-                    self.camera.adjust_zoom(-0.02);
-                }
-                if is_key_down(KeyCode::Apostrophe) {
-                    self.camera.adjust_zoom(0.02);
-                }
+        if let Some(view) = hovered_view.filter(|_| mouse_wheel != 0.0) {
+            self.players[view].camera.zoom_wheel(mouse_wheel);
+        }
+        for player in &mut self.players {
+            if player.input.is_zooming_in() {
+                player.camera.zoom_held(-1.0, get_frame_time());
             }
-            CameraMode::ShowBoth => {
-                // No keyboard zoom in ShowBoth mode
+            if player.input.is_zooming_out() {
+                player.camera.zoom_held(1.0, get_frame_time());
             }
         }
 
@@ -455,36 +605,27 @@ impl SplitScreenGame {
             return SplitScreenResult::Continue;
         }
 
-        // Handle camera focus keys - disable manual zoom mode to re-enable auto-zoom
-        if self.player1_input.just_focused_camera() {
-            self.camera_mode = CameraMode::FocusPlayer1((self.camera_focus_duration * 10.0) as u32);
-            self.manual_zoom_mode = false;
-            log::info!("Camera focused on Player 1");
-        }
-        if self.player2_input.just_focused_camera() {
-            self.camera_mode = CameraMode::FocusPlayer2((self.camera_focus_duration * 10.0) as u32);
-            self.manual_zoom_mode = false;
-            log::info!("Camera focused on Player 2");
+        // Camera focus key brings the player's view back to a close-up of their rocket
+        for (index, player) in self.players.iter_mut().enumerate() {
+            if player.input.just_focused_camera() {
+                player.camera.set_target_zoom(CRAFT_VIEW_ZOOM);
+                log::info!("Player {} camera back on their rocket", index + 1);
+            }
         }
 
-        // Player 1 controls
-        let p1_input = self.player1_input.clone();
-        self.handle_player_input(p1_input, 0, self.player1_rocket_id);
-
-        // Player 2 controls
-        let p2_input = self.player2_input.clone();
-        self.handle_player_input(p2_input, 1, self.player2_rocket_id);
+        // Each player's controls
+        for index in 0..self.players.len() {
+            self.handle_player_input(index);
+        }
 
         SplitScreenResult::Continue
     }
 
-    fn handle_player_input(&mut self, input: PlayerInput, player_index: usize, rocket_id: Option<EntityId>) {
-        // Get the appropriate state
-        let state = if player_index == 0 {
-            &mut self.player1_state
-        } else {
-            &mut self.player2_state
-        };
+    fn handle_player_input(&mut self, index: usize) {
+        let player = &mut self.players[index];
+        let input = &player.input;
+        let state = &mut player.state;
+
         // Thrust level adjustment
         if input.just_decreased_thrust() {
             state.adjust_thrust(-0.05);
@@ -496,219 +637,253 @@ impl SplitScreenGame {
         }
 
         // Apply controls to rocket
-        if let Some(rid) = rocket_id {
+        let Some(rid) = player.rocket_id else {
+            return;
+        };
+        let input = player.input.clone();
+        let thrust_level = player.state.thrust_level() * input.thrust_amount();
+
+        if let Some(rocket) = self.world.get_rocket_mut(rid) {
             // Rotation
             let rotation_input = input.get_rotation_input();
             if rotation_input != 0.0 {
                 let rotation_degrees = rotation_input * 3.0; // degrees per frame
-                let rotation_radians = rotation_degrees.to_radians();
-                if let Some(rocket) = self.world.get_rocket_mut(rid) {
-                    rocket.rotate(rotation_radians);
-                }
+                rocket.rotate(rotation_degrees.to_radians());
             }
 
             // Thrust
-            let thrust_level = if input.is_thrusting() {
-                state.thrust_level()
-            } else {
-                0.0
-            };
+            rocket.set_thrust_level(thrust_level);
+        }
 
-            if let Some(rocket) = self.world.get_rocket_mut(rid) {
-                rocket.set_thrust_level(thrust_level);
-            }
-
-            // Convert to satellite
-            if input.just_converted_to_satellite() {
-                if let Some(new_satellite_id) = self.world.convert_rocket_to_satellite(rid) {
-                    log::info!("Player {} converted rocket to satellite", input.player_id);
-
-                    // Spawn new rocket for this player
-                    let new_rocket = Rocket::new(
-                        self.rocket_spawn_position,
-                        self.rocket_spawn_velocity,
-                        if input.player_id == 0 {
-                            Color::from_rgba(255, 100, 100, 255)
-                        } else {
-                            Color::from_rgba(100, 100, 255, 255)
-                        },
-                        GameConstants::ROCKET_BASE_MASS,
-                    );
-                    let new_rocket_id = self.world.add_rocket(new_rocket);
-
-                    // Update player's rocket ID
-                    if input.player_id == 0 {
-                        self.player1_rocket_id = Some(new_rocket_id);
-                    } else {
-                        self.player2_rocket_id = Some(new_rocket_id);
-                    }
+        // Convert to satellite
+        if input.just_converted_to_satellite() {
+            if let Some(new_satellite_id) = self.world.convert_rocket_to_satellite(rid) {
+                log::info!("Player {} converted rocket to satellite", input.player_id);
+                if let Some(satellite) = self.world.get_satellite(new_satellite_id) {
+                    let position = satellite.position();
+                    self.camera_impulse(CameraImpulse::Staging, position);
                 }
+
+                // Spawn new rocket for this player
+                let (position, velocity) = (self.rocket_spawn_position, self.rocket_spawn_velocity);
+                self.spawn_player_rocket(index, position, velocity);
             }
+        }
 
-            // Shoot bullet (S for Player 1, Down for Player 2)
-            let shoot_pressed = if player_index == 0 {
-                is_key_pressed(KeyCode::S)
+        // Shoot bullet (S / DOWN / numpad 5 / J, or the controller's south button)
+        if input.just_shot() {
+            if let Some(bullet_id) = self.world.shoot_bullet_from_rocket(rid) {
+                log::debug!("Player {} fired bullet {}", input.player_id, bullet_id);
             } else {
-                is_key_pressed(KeyCode::Down)
-            };
-
-            if shoot_pressed {
-                if let Some(bullet_id) = self.world.shoot_bullet_from_rocket(rid) {
-                    log::info!("Player {} fired bullet {}", input.player_id, bullet_id);
-                } else {
-                    log::debug!("Player {} cannot shoot: not enough fuel", input.player_id);
-                }
+                log::debug!("Player {} cannot shoot: not enough fuel", input.player_id);
             }
         }
     }
 
+    /// Set the looping sound played while fuel transfers are active
+    pub fn set_transfer_sound(&mut self, sound: Option<macroquad::audio::Sound>) {
+        self.transfer_effects.set_hum_sound(sound);
+    }
+
+    /// Set the beep played when a new alert is raised
+    pub fn set_alert_sound(&mut self, sound: Option<macroquad::audio::Sound>) {
+        self.alert_banner.set_warning_sound(sound);
+    }
+
+    /// Set the rocket-rocket / rocket-satellite collision rules for this session
+    pub fn set_collision_rules(&mut self, rules: CollisionRules) {
+        self.world.set_collision_rules(rules);
+    }
+
     /// Update game state
     pub fn update(&mut self, delta_time: f32) -> SplitScreenResult {
         if self.is_paused {
+            self.transfer_effects.clear();
+            self.stop_rumble();
             return SplitScreenResult::Continue;
         }
 
-        // Update camera focus timer
-        match self.camera_mode {
-            CameraMode::FocusPlayer1(remaining) | CameraMode::FocusPlayer2(remaining) => {
-                if remaining == 0 {
-                    self.camera_mode = CameraMode::ShowBoth;
-                    log::info!("Camera focus expired, showing both players");
-                } else {
-                    // Decrement timer (in deciseconds)
-                    let new_remaining = remaining.saturating_sub((delta_time * 10.0) as u32);
-                    self.camera_mode = match self.camera_mode {
-                        CameraMode::FocusPlayer1(_) => CameraMode::FocusPlayer1(new_remaining),
-                        CameraMode::FocusPlayer2(_) => CameraMode::FocusPlayer2(new_remaining),
-                        _ => CameraMode::ShowBoth,
-                    };
-                }
-            }
-            _ => {}
-        }
-
         // Update world physics
         self.world.update(delta_time, false);
+        self.bullet_effects.update(&self.world, delta_time, self.players[0].camera.camera().target);
 
         // Handle rockets destroyed by bullets (respawn like 'C' key, but without satellite)
         let destroyed_rockets = self.world.take_destroyed_rockets();
         for destroyed in destroyed_rockets {
-            let player_id = destroyed.player_id.unwrap_or(0);
-            log::info!("Player {} rocket destroyed by bullet, respawning", player_id);
-
-            // Determine spawn position based on player (Player 2 at +5 degrees from Player 1)
-            let (spawn_pos, spawn_vel, color) = if player_id == 0 {
-                (self.rocket_spawn_position, self.rocket_spawn_velocity, Color::from_rgba(255, 100, 100, 255))
-            } else {
-                let offset_angle = 5.0_f32.to_radians();
-                let center = Vec2::new(GameConstants::MAIN_PLANET_X, GameConstants::MAIN_PLANET_Y);
-
-                let rel_pos = self.rocket_spawn_position - center;
-                let rotated_x = rel_pos.x * offset_angle.cos() - rel_pos.y * offset_angle.sin();
-                let rotated_y = rel_pos.x * offset_angle.sin() + rel_pos.y * offset_angle.cos();
-                let p2_pos = center + Vec2::new(rotated_x, rotated_y);
-
-                let vel_x = self.rocket_spawn_velocity.x * offset_angle.cos() - self.rocket_spawn_velocity.y * offset_angle.sin();
-                let vel_y = self.rocket_spawn_velocity.x * offset_angle.sin() + self.rocket_spawn_velocity.y * offset_angle.cos();
-                let p2_vel = Vec2::new(vel_x, vel_y);
-
-                (p2_pos, p2_vel, Color::from_rgba(100, 100, 255, 255))
+            self.camera_impulse(CameraImpulse::Explosion, destroyed.position);
+            let Some(index) = self.player_for_rocket(destroyed.rocket_id) else {
+                continue;
             };
-
-            // Spawn new rocket for this player
-            let new_rocket = Rocket::new(
-                spawn_pos,
-                spawn_vel,
-                color,
-                GameConstants::ROCKET_BASE_MASS,
-            );
-
-            let new_rocket_id = self.world.add_rocket(new_rocket);
-
-            // Update the appropriate player's rocket ID
-            if player_id == 0 {
-                self.player1_rocket_id = Some(new_rocket_id);
-                self.world.set_active_rocket(Some(new_rocket_id));
-            } else {
-                self.player2_rocket_id = Some(new_rocket_id);
+            log::info!("Player {} rocket destroyed by {}, respawning", index + 1, destroyed.cause);
+            if destroyed.cause == "bullet" {
+                self.rumble_player(index, HapticEvent::BulletHit);
             }
+            self.rumble_player(index, HapticEvent::Explosion);
+
+            // Spawn new rocket for this player at their own spot round the planet
+            let (spawn_pos, spawn_vel) = self.spawn_state(index);
+            let new_rocket_id = self.spawn_player_rocket(index, spawn_pos, spawn_vel);
 
-            log::info!("Respawned new rocket {} for player {}", new_rocket_id, player_id);
+            log::info!("Respawned new rocket {} for player {}", new_rocket_id, index + 1);
         }
 
         // Update save celebration timer
         if self.save_celebration_timer > 0.0 {
             self.save_celebration_timer -= delta_time;
         }
+        self.update_rumble(delta_time);
 
-        // Handle manual planet refueling (R key for both players - shared key)
+        // Handle manual planet refueling (R key for every player - shared key)
         if is_key_pressed(KeyCode::R) {  // Changed to is_key_pressed for single press
-            if let Some(rocket_id) = self.player1_rocket_id {
+            for rocket_id in self.player_rocket_ids() {
                 self.world.handle_manual_planet_refuel(rocket_id, delta_time);
             }
-            if let Some(rocket_id) = self.player2_rocket_id {
-                self.world.handle_manual_planet_refuel(rocket_id, delta_time);
+        }
+
+        // Rescue transfers: each player holds their transfer key to fuel the nearest rocket (F / [ / numpad 0 / V)
+        for player in &mut self.players {
+            match player.rocket_id.filter(|_| player.input.is_transferring_fuel()) {
+                Some(donor_id) => {
+                    let recipient_id = self.world.nearest_rocket_in_transfer_range(donor_id);
+                    let moved = recipient_id
+                        .and_then(|recipient_id| self.world.transfer_fuel_rocket_to_rocket(donor_id, recipient_id, delta_time).ok())
+                        .unwrap_or(0.0);
+                    player.rocket_transfer.hold(&self.world, donor_id, recipient_id, moved);
+                }
+                None => player.rocket_transfer.release(),
+            }
+        }
+
+        // Feed this tick's fuel transfers (manual + automatic) into the beam effects
+        let transfer_events = self.world.take_fuel_transfer_events();
+        self.transfer_effects.update(delta_time, transfer_events);
+        self.vehicle_manager.clear_missing_target(&self.world);
+
+        // Damage flashes for hits on a player's rocket, hit markers where their bullets land
+        for hit in self.world.take_hit_events() {
+            if let Some(victim) = self.player_for_rocket(hit.rocket_id) {
+                self.players[victim].damage_indicator.on_hit(hit.position, hit.source);
+            } else if let Some(shooter) = hit.shooter_id.and_then(|id| self.player_for_rocket(id)) {
+                self.players[shooter].damage_indicator.on_hit_confirmed(hit.position);
+            }
+            if hit.shooter_id.is_some() {
+                self.camera_impulse(CameraImpulse::BulletImpact, hit.position);
             }
         }
 
+        // Satellites still retire and deorbit here, but Z is a thrust key so there are no retrieval toasts
+        self.world.take_satellite_life_events();
+
+        // Grade touchdowns: toast for everyone, best-landing stats only for Player 1 (the profile is theirs)
+        for event in self.world.take_landing_events() {
+            let Some(index) = self.player_for_rocket(event.rocket_id) else {
+                continue;
+            };
+            self.rumble_player(index, HapticEvent::Landing { vertical_speed: event.result.vertical_speed });
+            let planet_name = event.planet_name.as_deref().unwrap_or("Unknown");
+            let new_best = index == 0 && self.profile.record_landing(planet_name, &event.result);
+            self.toasts.push(event.result.toast_text(planet_name, new_best), event.result.grade.color());
+            if let Some(position) = self.world.get_rocket(event.rocket_id).map(|rocket| rocket.position()) {
+                self.camera_impulse(CameraImpulse::Landing { vertical_speed: event.result.vertical_speed }, position);
+            }
+            if new_best {
+                if let Err(e) = self.profile.save() {
+                    log::error!("Failed to save profile: {}", e);
+                }
+            }
+        }
+        self.toasts.update(delta_time);
+
+        // Low fuel / orbit decay / incoming bullet alerts for our rockets and the satellites
+        let watched = self.player_rocket_ids();
+        self.alerts.update(delta_time, &self.world, &watched);
+        self.alert_banner.update(delta_time, self.alerts.alerts());
+        for player in &mut self.players {
+            let own: Vec<EntityId> = player.rocket_id.into_iter().collect();
+            player.threat_indicator.update(delta_time, &self.world, &own);
+            player.damage_indicator.update(delta_time);
+        }
+
         // Update game time
         self.game_time += delta_time;
 
-        // Update camera position based on mode
-        self.update_camera_for_mode();
-        self.camera.update(delta_time);
+        // Each view follows its player's rocket
+        self.update_cameras();
+        for player in &mut self.players {
+            player.camera.update(delta_time);
+        }
 
         SplitScreenResult::Continue
     }
 
     /// Render the game
     pub fn render(&mut self) {
-        // Set camera
-        set_camera(self.camera.camera());
+        self.sky.sync(self.world.cosmetic_seed());
+        let all_planets: Vec<&Planet> = self.world.planets().collect();
+        let satellites: Vec<&Satellite> = self.world.satellites().collect();
+
+        for (index, (player, trajectory_color)) in self.players.iter().zip(TRAJECTORY_COLORS).enumerate() {
+            let camera = &player.camera;
 
-        // Draw world entities
-        self.world.render();
+            // Sky behind this view
+            set_camera(&self.layout.screen_camera(index));
+            self.sky.draw(self.world.origin().to_world(camera.camera().target), camera.zoom_level());
 
-        // Draw trajectories for both players with color-coded lines
-        if let Some(r1_id) = self.player1_rocket_id {
-            if let Some(r1) = self.world.get_rocket(r1_id) {
-                let all_planets: Vec<&Planet> = self.world.planets().collect();
-                // Player 1: red trajectory
+            // World entities through this player's camera
+            set_camera(camera.camera());
+            self.strategic_view.render_world(&self.world, camera);
+            self.bullet_effects.draw(camera.zoom_level());
+
+            // The player's own trajectory in their color
+            if let Some(rocket) = player.rocket_id.and_then(|id| self.world.get_rocket(id)) {
                 self.vehicle_manager.draw_visualizations_with_color(
-                    r1,
+                    rocket,
                     &all_planets,
-                    self.camera.zoom_level(),
-                    self.camera.camera(),
-                    Some(Color::new(1.0, 0.0, 0.0, 0.6)),
+                    camera.zoom_level(),
+                    camera.camera(),
+                    Some(trajectory_color),
                 );
             }
-        }
 
-        if let Some(r2_id) = self.player2_rocket_id {
-            if let Some(r2) = self.world.get_rocket(r2_id) {
-                let all_planets: Vec<&Planet> = self.world.planets().collect();
-                // Player 2: blue trajectory
-                self.vehicle_manager.draw_visualizations_with_color(
-                    r2,
-                    &all_planets,
-                    self.camera.zoom_level(),
-                    self.camera.camera(),
-                    Some(Color::new(0.0, 0.5, 1.0, 0.6)),
-                );
+            // Draw fitted satellite orbit ellipses
+            self.vehicle_manager.draw_satellite_orbits(&satellites, &all_planets, camera.zoom_level());
+
+            // Draw hover/target highlights
+            self.inspector.draw_highlights(&self.world, self.vehicle_manager.target(), camera.zoom_level());
+
+            // Draw active fuel transfer beams
+            self.transfer_effects.draw_beams(&self.world, camera.zoom_level());
+
+            // Screen-space overlays clipped to this view
+            set_camera(&self.layout.screen_camera(index));
+            self.strategic_view.draw_labels(&self.world, camera);
+            if let Some(rocket) = player.rocket_id.and_then(|id| self.world.get_rocket(id).map(|rocket| (id, rocket))) {
+                self.transfer_effects.draw_rocket_fuel_rate(rocket.0, camera.world_to_screen(rocket.1.position()));
             }
+            player.threat_indicator.draw(&self.world, camera);
+            player.damage_indicator.draw(camera);
         }
 
         // Store celebration rocket position for screen-space rendering (Player 1's rocket)
         let celebration_screen_pos = if self.save_celebration_timer > 0.0 {
-            self.player1_rocket_id
+            let player = &self.players[0];
+            player.rocket_id
                 .and_then(|rid| self.world.get_rocket(rid))
-                .map(|rocket| self.camera.world_to_screen(rocket.position()))
+                .map(|rocket| player.camera.world_to_screen(rocket.position()))
         } else {
             None
         };
 
         // Reset to default camera for UI
         set_default_camera();
+        self.layout.draw_dividers();
+
+        self.toasts.draw();
+        for (row, player) in self.players.iter().enumerate() {
+            player.rocket_transfer.draw_row(row);
+        }
+        mission_clock_display::draw_mission_clock(self.game_time, 1);
+        self.alert_banner.draw(&self.world);
 
         // Draw "what a save!!" celebration text in screen space
         if let Some(screen_pos) = celebration_screen_pos {
@@ -737,6 +912,9 @@ impl SplitScreenGame {
         // Draw UI
         self.draw_ui();
 
+        // Hover tooltip
+        self.inspector.draw_tooltip(&self.world, self.vehicle_manager.target(), &HashMap::new());
+
         // Draw controls popup if showing
         if self.show_controls {
             self.draw_controls_popup();
@@ -744,68 +922,46 @@ impl SplitScreenGame {
     }
 
     fn draw_ui(&mut self) {
-        let screen_w = screen_width();
-        let screen_h = screen_height();
         let all_planets: Vec<&Planet> = self.world.planets().collect();
+        let player_count = self.players.len();
 
         // Get satellite stats for network panel
         let satellite_stats = self.world.get_satellite_network_stats();
 
-        // Update and draw Player 1 info display (left side, red theme)
-        if let Some(r1_id) = self.player1_rocket_id {
-            if let Some(r1) = self.world.get_rocket(r1_id) {
-                // Default to Earth (first planet) for split-screen
-                use crate::systems::ReferenceBody;
-                let selected_planet = all_planets.get(0).copied();
-                self.player1_info_display.update_all_panels(
-                    Some(r1),
-                    &all_planets,
-                    selected_planet,
-                    0,  // Default to first planet (index 0)
-                    self.player1_state.thrust_level(),
-                    false,  // network_connected (not applicable for local split-screen)
-                    Some(0),  // Player 1 ID
-                    2,  // player_count (always 2 in split-screen)
-                    Some(&satellite_stats),
-                );
-                self.player1_info_display.draw_all_panels();
-            }
+        // Update and draw each player's info display in their view
+        for (index, player) in self.players.iter_mut().enumerate() {
+            let Some(rocket) = player.rocket_id.and_then(|id| self.world.get_rocket(id)) else {
+                continue;
+            };
+            // Default to Earth (first planet) for split-screen
+            let selected_planet = all_planets.first().copied();
+            player.info_display.update_all_panels(
+                Some(rocket),
+                &all_planets,
+                selected_planet,
+                0,  // Default to first planet (index 0)
+                player.state.thrust_level(),
+                false,  // network_connected (not applicable for local split-screen)
+                Some(index),
+                player_count,
+                Some(&satellite_stats),
+            );
+            player.info_display.draw_all_panels();
         }
 
-        // Update and draw Player 2 info display (right side, blue theme)
-        if let Some(r2_id) = self.player2_rocket_id {
-            if let Some(r2) = self.world.get_rocket(r2_id) {
-                // Default to Earth (first planet) for split-screen
-                use crate::systems::ReferenceBody;
-                let selected_planet = all_planets.get(0).copied();
-                self.player2_info_display.update_all_panels(
-                    Some(r2),
-                    &all_planets,
-                    selected_planet,
-                    0,  // Default to first planet (index 0)
-                    self.player2_state.thrust_level(),
-                    false,  // network_connected
-                    Some(1),  // Player 2 ID
-                    2,  // player_count
-                    Some(&satellite_stats),
-                );
-                self.player2_info_display.draw_all_panels();
-            }
+        // Player name at the top of each view
+        for (index, rect) in self.layout.viewports().iter().enumerate() {
+            let name = format!("Player {}", index + 1);
+            let name_w = measure_text(&name, None, 20, 1.0).width;
+            draw_text(&name, rect.center().x - name_w / 2.0, rect.y + 24.0, 20.0, PLAYER_COLORS[index]);
         }
 
-        // Camera mode indicator (center-top)
-        let mode_text = match self.camera_mode {
-            CameraMode::ShowBoth => "Camera: Both Players",
-            CameraMode::FocusPlayer1(t) => &format!("Camera: Player 1 ({}s)", t / 10),
-            CameraMode::FocusPlayer2(t) => &format!("Camera: Player 2 ({}s)", t / 10),
-        };
-        let text_w = measure_text(mode_text, None, 20, 1.0).width;
-        draw_text(mode_text, screen_w / 2.0 - text_w / 2.0, 30.0, 20.0, YELLOW);
-
         // "Press ENTER for controls" text at top-right
+        let safe_area = SafeArea::current();
         let help_text = "Press ENTER for controls";
         let help_w = measure_text(help_text, None, 18, 1.0).width;
-        draw_text(help_text, screen_w - help_w - 20.0, 30.0, 18.0, LIGHTGRAY);
+        let help_pos = safe_area.anchor(Anchor::TopRight, Vec2::new(help_w, 18.0), Vec2::new(10.0, 0.0));
+        draw_text(help_text, help_pos.x, help_pos.y + 20.0, 18.0, LIGHTGRAY);
     }
 
     fn draw_controls_popup(&self) {
@@ -816,10 +972,8 @@ impl SplitScreenGame {
         draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::from_rgba(0, 0, 0, 180));
 
         // Draw popup window
-        let popup_w = 900.0;
-        let popup_h = 700.0;
-        let popup_x = screen_w / 2.0 - popup_w / 2.0;
-        let popup_y = screen_h / 2.0 - popup_h / 2.0;
+        let popup = SafeArea::current().popup(1000.0, 800.0);
+        let (popup_x, popup_y, popup_w, popup_h) = (popup.x, popup.y, popup.w, popup.h);
 
         draw_rectangle(popup_x, popup_y, popup_w, popup_h, Color::from_rgba(40, 40, 60, 255));
         draw_rectangle_lines(popup_x, popup_y, popup_w, popup_h, 2.0, WHITE);
@@ -829,43 +983,76 @@ impl SplitScreenGame {
         let title_w = measure_text(title, None, 32, 1.0).width;
         draw_text(title, popup_x + popup_w / 2.0 - title_w / 2.0, popup_y + 40.0, 32.0, WHITE);
 
-        // Three columns: Player 1, Action, Player 2
-        let col1_x = popup_x + 50.0;
-        let col2_x = popup_x + 350.0;
-        let col3_x = popup_x + 650.0;
+        // Action column, then a key column per player
+        let action_x = popup_x + 40.0;
+        let key_x = |player: usize| popup_x + 340.0 + player as f32 * 160.0;
         let start_y = popup_y + 100.0;
         let line_height = 30.0;
 
         // Column headers
-        draw_text("Player 1", col1_x, start_y, 24.0, RED);
-        draw_text("Action", col2_x, start_y, 24.0, YELLOW);
-        draw_text("Player 2", col3_x, start_y, 24.0, BLUE);
+        draw_text("Action", action_x, start_y, 24.0, YELLOW);
+        for (index, player) in self.players.iter().enumerate() {
+            draw_text(&format!("Player {}", player.input.player_id + 1), key_x(index), start_y, 24.0, PLAYER_COLORS[index]);
+        }
 
         let mut y = start_y + 40.0;
 
-        let controls = [
-            ("D", "Rotate Left", "RIGHT"),
-            ("A", "Rotate Right", "LEFT"),
-            ("W", "Thrust", "UP"),
-            ("Z", "Decrease Thrust", "COMMA"),
-            ("X", "Increase Thrust", "PERIOD"),
-            ("C", "Convert to Satellite", "]"),
-            ("S", "Shoot Bullet", "DOWN"),
-            ("Q", "Zoom In", "/"),
-            ("E", "Zoom Out", "'"),
-            ("R", "Focus Camera (10s)", ";"),
+        let rows: [(&str, KeyOf); 11] = [
+            ("Rotate Left", |input| input.rotate_right),
+            ("Rotate Right", |input| input.rotate_left),
+            ("Thrust", |input| input.thrust),
+            ("Decrease Thrust", |input| input.decrease_thrust),
+            ("Increase Thrust", |input| input.increase_thrust),
+            ("Convert to Satellite", |input| input.convert_to_satellite),
+            ("Shoot Bullet", |input| input.shoot),
+            ("Zoom In", |input| input.zoom_in),
+            ("Zoom Out", |input| input.zoom_out),
+            ("Camera Back on Rocket", |input| input.camera_focus),
+            ("Fuel Nearby Rocket (hold)", |input| input.transfer_fuel),
         ];
 
-        for (p1_key, action, p2_key) in controls.iter() {
-            draw_text(p1_key, col1_x, y, 20.0, WHITE);
-            draw_text(action, col2_x, y, 20.0, WHITE);
-            draw_text(p2_key, col3_x, y, 20.0, WHITE);
+        for (action, key) in rows {
+            draw_text(action, action_x, y, 20.0, WHITE);
+            for (index, player) in self.players.iter().enumerate() {
+                let name = format!("{:?}", key(&player.input)).to_uppercase();
+                draw_text(&name, key_x(index), y, 20.0, WHITE);
+            }
             y += line_height;
         }
 
+        // Controller column: which pad each player has claimed
+        draw_text("Controller", action_x, y, 20.0, WHITE);
+        for (index, player) in self.players.iter().enumerate() {
+            let pad = player.input.gamepad.map_or("-".to_string(), |slot| format!("PAD {}", slot + 1));
+            draw_text(&pad, key_x(index), y, 20.0, WHITE);
+        }
+        y += line_height;
+
         y += 20.0;
-        draw_text("ENTER - Toggle Controls Menu", col2_x, y, 20.0, GRAY);
+        draw_text("START on a controller - Claim it / pass it to the next player", action_x, y, 20.0, GRAY);
+        y += line_height;
+        draw_text("Controller: trigger thrust, stick steer, A shoot, B satellite, X fuel, Y camera", action_x, y, 20.0, GRAY);
+        y += line_height;
+        draw_text("Mouse wheel - Zoom the view under the mouse", action_x, y, 20.0, GRAY);
         y += line_height;
-        draw_text("ESC - Return to Menu", col2_x, y, 20.0, GRAY);
+        draw_text("L - Toggle Satellite Orbits", action_x, y, 20.0, GRAY);
+        y += line_height;
+        draw_text("7 - Reset Panel Layout (drag title bars to move)", action_x, y, 20.0, GRAY);
+        y += line_height;
+        draw_text("ENTER - Toggle Controls Menu", action_x, y, 20.0, GRAY);
+        y += line_height;
+        draw_text("ESC - Return to Menu", action_x, y, 20.0, GRAY);
+    }
+
+    /// Status shown in Discord Rich Presence (altitude is player 1's)
+    pub fn presence_info(&self) -> PresenceInfo {
+        PresenceInfo {
+            mode: "Split Screen".to_string(),
+            map: None,
+            altitude: self.players[0].rocket_id.and_then(|id| self.world.rocket_altitude(id)),
+            player_count: self.players.len() as u32,
+            max_players: MAX_PLAYERS as u32,
+            join_address: None,
+        }
     }
 }
@@ -6,16 +6,23 @@ pub enum GameState {
     MainMenu,
     SavesMenu,
     MapSelection,
+    ScenariosMenu,
     MultiplayerMenu,
     OnlineMultiplayerMenu,
     OnlineHostMenu,
     MultiplayerSavesMenu,
     OnlineJoinMenu,
+    ReplaysMenu,
+    ProfileMenu,
+    LeaderboardMenu,
+    Loading,
     Playing,
     Paused,
+    Debrief, // Post-flight summary between a single player session and the main menu
     MultiplayerHost,
     MultiplayerClient,
     SplitScreen,
+    Replay,
     Quit,
 }
 
@@ -31,5 +38,8 @@ pub enum GameMode {
     None,
     SinglePlayer,
     Multiplayer,
+    BugReport,
+    Profiles,
+    Leaderboard,
     Quit,
 }
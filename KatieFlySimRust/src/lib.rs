@@ -37,9 +37,22 @@ pub mod networking;
 // Save/load system
 pub mod save_system;
 
+// Stable map and save schemas for modding tools
+pub mod schemas;
+
 // Player control
 pub mod player;
 
+// Embedding API for driving the simulation from other crates
+pub mod embed;
+
+// C ABI over the embedding API (Python and other languages)
+pub mod ffi;
+
+// Reinforcement-learning environments over the embedding API
+pub mod rl_env;
+
 // Re-export commonly used types
 pub use game_constants::GameConstants;
-pub use player::{Player, PlayerType, PlayerState};
+pub use player::{Gamepad, GamepadButton, GamepadSource, GamepadState, Gamepads, Player, PlayerType, PlayerState};
+pub use embed::{RocketControls, Simulation};
@@ -6,13 +6,15 @@ use macroquad::prelude::*;
 
 use katie_fly_sim_rust::game_constants::GameConstants;
 use katie_fly_sim_rust::game_modes::{
-    SinglePlayerGame, SinglePlayerResult,
+    RetryPlan, SinglePlayerGame, SinglePlayerResult,
     SplitScreenGame, SplitScreenResult,
-    MultiplayerHost, MultiplayerHostResult,
+    MultiplayerHost, MultiplayerHostResult, HostRates,
     MultiplayerClient, MultiplayerClientResult,
+    ReplayViewer, ReplayViewerResult,
 };
+use katie_fly_sim_rust::game_modes::multiplayer_host::MAX_PLAYERS;
 use katie_fly_sim_rust::game_state::{GameMode, GameState};
-use katie_fly_sim_rust::map_config::MapConfiguration;
+use katie_fly_sim_rust::map_config::{ArenaConfig, MapConfiguration};
 use katie_fly_sim_rust::menus::{
     MainMenu, SavesMenu, SavesMenuResult,
     MapSelectionMenu, MapSelectionResult,
@@ -21,8 +23,20 @@ use katie_fly_sim_rust::menus::{
     OnlineHostMenu, OnlineHostMenuResult,
     MultiplayerSavesMenu, MultiplayerSavesMenuResult,
     OnlineJoinMenu, OnlineJoinMenuResult,
+    ScenariosMenu, ScenariosMenuResult,
+    ReplaysMenu, ReplaysMenuResult,
+    ProfileMenu, ProfileMenuResult,
+    LeaderboardMenu, LeaderboardMenuResult,
+    DebriefMenu, DebriefMenuResult,
 };
-use katie_fly_sim_rust::save_system::GameSaveData;
+use katie_fly_sim_rust::networking::rcon::{DEFAULT_RCON_PORT, RCON_PASSWORD_ENV};
+use katie_fly_sim_rust::networking::{LanDiscovery, PresenceInfo, RichPresence};
+use katie_fly_sim_rust::physics::CollisionRules;
+use katie_fly_sim_rust::save_system::{local_profiles, BugReport, GameSaveData};
+use katie_fly_sim_rust::systems::asset_manager;
+use katie_fly_sim_rust::systems::{AdaptiveTimestep, AssetManager, Controllers, CrewRole, DailyChallenge, Scenario};
+use katie_fly_sim_rust::ui::{timestep_warning, LoadingScreen, LogConsole};
+use katie_fly_sim_rust::utils::log_buffer;
 
 // Window configuration
 fn window_conf() -> Conf {
@@ -36,10 +50,103 @@ fn window_conf() -> Conf {
     }
 }
 
+/// Read host tick rates from `--physics-hz N` and `--snapshot-hz N` command-line flags
+fn host_rates_from_args() -> HostRates {
+    let args: Vec<String> = std::env::args().collect();
+    let mut physics_hz = GameConstants::DEFAULT_PHYSICS_HZ;
+    let mut snapshot_hz = GameConstants::DEFAULT_SNAPSHOT_HZ;
+
+    for pair in args.windows(2) {
+        match (pair[0].as_str(), pair[1].parse::<u32>()) {
+            ("--physics-hz", Ok(hz)) => physics_hz = hz,
+            ("--snapshot-hz", Ok(hz)) => snapshot_hz = hz,
+            ("--physics-hz", Err(_)) | ("--snapshot-hz", Err(_)) => {
+                log::warn!("Ignoring invalid value '{}' for {}", pair[1], pair[0]);
+            }
+            _ => {}
+        }
+    }
+
+    HostRates::new(physics_hz, snapshot_hz)
+}
+
+/// Read vehicle collision rules from `--no-collisions`, `--collision-restitution X`
+/// and `--collision-damage X` command-line flags
+fn collision_rules_from_args() -> CollisionRules {
+    let args: Vec<String> = std::env::args().collect();
+    let mut rules = CollisionRules::default();
+
+    if args.iter().any(|arg| arg == "--no-collisions") {
+        rules.enabled = false;
+    }
+    for pair in args.windows(2) {
+        match (pair[0].as_str(), pair[1].parse::<f32>()) {
+            ("--collision-restitution", Ok(value)) => rules.restitution = value.clamp(0.0, 1.0),
+            ("--collision-damage", Ok(value)) => rules.damage_per_speed = value.max(0.0),
+            ("--collision-restitution", Err(_)) | ("--collision-damage", Err(_)) => {
+                log::warn!("Ignoring invalid value '{}' for {}", pair[1], pair[0]);
+            }
+            _ => {}
+        }
+    }
+
+    rules
+}
+
+/// Hardcore saving from `--hardcore`: saves need a satellite uplink or a landed rocket
+fn hardcore_from_args() -> bool {
+    std::env::args().any(|arg| arg == "--hardcore")
+}
+
+/// Wraparound arena for hosted games, from `--arena 8000` or `--arena 12000x8000`
+fn arena_from_args() -> Option<ArenaConfig> {
+    let args: Vec<String> = std::env::args().collect();
+    let pair = args.windows(2).find(|pair| pair[0] == "--arena")?;
+    let arena = ArenaConfig::parse(&pair[1]);
+    if arena.is_none() {
+        log::warn!("Ignoring invalid value '{}' for --arena", pair[1]);
+    }
+    arena
+}
+
+/// SQLite file hosts archive snapshots, players and stats to, from `--world-db PATH`
+/// (only with the `sqlite` feature)
+fn world_database_from_args() -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+    args.windows(2).find(|pair| pair[0] == "--world-db").map(|pair| pair[1].clone())
+}
+
+/// Local profile to start on, from `--profile NAME` (otherwise the one used last)
+fn profile_from_args() -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+    args.windows(2).find(|pair| pair[0] == "--profile").map(|pair| pair[1].clone())
+}
+
+/// Remote console port and password for hosts, from `--rcon-port N` and `--rcon-password X`
+/// (or the KATIE_RCON_PASSWORD environment variable); off without a password
+fn rcon_from_args() -> Option<(u16, String)> {
+    let args: Vec<String> = std::env::args().collect();
+    let mut port = DEFAULT_RCON_PORT;
+    let mut password = std::env::var(RCON_PASSWORD_ENV).ok();
+
+    for pair in args.windows(2) {
+        match pair[0].as_str() {
+            "--rcon-port" => match pair[1].parse() {
+                Ok(value) => port = value,
+                Err(_) => log::warn!("Ignoring invalid value '{}' for --rcon-port", pair[1]),
+            },
+            "--rcon-password" => password = Some(pair[1].clone()),
+            _ => {}
+        }
+    }
+
+    password.filter(|password| !password.is_empty()).map(|password| (port, password))
+}
+
 #[macroquad::main(window_conf)]
 async fn main() {
-    // Initialize logger
-    env_logger::init();
+    // Initialize logger (recent lines are also kept for bug reports)
+    log_buffer::init();
     log::info!("Starting KatieFlySimRust v0.1.0");
     log::info!("Rust port of FlySimNewA - Space Flight Simulator");
     log::info!("Using macroquad for pure Rust graphics (no SFML dependency!)");
@@ -52,43 +159,95 @@ async fn main() {
 
     let window_size = Vec2::new(window_width, window_height);
 
+    // Sounds and custom maps load behind a progress bar instead of stalling the first frame
+    let mut assets = AssetManager::new();
+    let mut loading_screen = LoadingScreen::new();
+    loading_screen.start("Loading");
+    while !assets.is_loaded() {
+        assets.step().await;
+        loading_screen.update(assets.progress(), get_frame_time());
+        loading_screen.draw(assets.current());
+        next_frame().await;
+    }
+
+    // Soft hum played while fuel transfers are active (silent if audio is unavailable)
+    let transfer_hum = assets.sound(asset_manager::TRANSFER_HUM);
+    // Warning beep for new alerts (low fuel, orbit decay, incoming bullets)
+    let alert_beep = assets.sound(asset_manager::ALERT_BEEP);
+    // Controllers for every mode (keyboard-only unless built with the `gamepad` feature)
+    let controllers = Controllers::start();
+
+    // Local profile first - it decides which saves folder the menus list
+    match profile_from_args() {
+        Some(name) => {
+            if let Err(e) = local_profiles::select(&name) {
+                log::error!("Failed to select profile '{}': {}", name, e);
+            }
+        }
+        None => local_profiles::restore_last(),
+    }
+
     // Game state
     let mut game_state = GameState::MainMenu;
+    let mut after_loading = GameState::MainMenu; // Where the loading screen goes once assets are in
     let mut main_menu = MainMenu::new(window_size);
     let mut saves_menu = SavesMenu::new(window_size);
     let mut map_selection_menu = MapSelectionMenu::new(window_size);
+    map_selection_menu.set_maps(assets.maps());
+    map_selection_menu.set_map_errors(assets.map_errors());
+    let mut scenarios_menu = ScenariosMenu::new(window_size);
     let mut multiplayer_menu = MultiplayerMenu::new(window_size);
     let mut online_multiplayer_menu = OnlineMultiplayerMenu::new(window_size);
     let mut online_host_menu = OnlineHostMenu::new(window_size);
+    online_host_menu.set_default_rates(host_rates_from_args());
     let mut multiplayer_saves_menu = MultiplayerSavesMenu::new(window_size);
     let mut online_join_menu = OnlineJoinMenu::new(window_size);
+    let mut lan_discovery: Option<LanDiscovery> = None; // Listening only while the join menu is open
+    let mut replays_menu = ReplaysMenu::new(window_size);
+    let mut profile_menu = ProfileMenu::new(window_size);
+    let mut leaderboard_menu = LeaderboardMenu::new(window_size);
+    let mut debrief_menu: Option<DebriefMenu> = None; // Shown after a single player session with something to report
     let mut single_player_game: Option<SinglePlayerGame> = None;
     let mut split_screen_game: Option<SplitScreenGame> = None;
     let mut multiplayer_host: Option<MultiplayerHost> = None;
     let mut multiplayer_client: Option<MultiplayerClient> = None;
+    let mut replay_viewer: Option<ReplayViewer> = None;
 
     // Store player name and port from menus
     let mut host_player_name: Option<String> = None;
     let mut host_port: Option<u16> = None;
+    let mut host_rates = HostRates::default();
+    let mut host_max_players = MAX_PLAYERS;
+    let mut host_new_game_port: Option<u16> = None; // Set while the host picks a map for a new game
+    let collision_rules = collision_rules_from_args();
+    let world_database = world_database_from_args();
+    let arena = arena_from_args();
+    let hardcore = hardcore_from_args();
+    let rcon = rcon_from_args();
+
+    // Discord Rich Presence (only with the `discord` feature and KATIE_DISCORD_APP_ID set)
+    let mut rich_presence = RichPresence::new();
+
+    // In-game log viewer (F1)
+    let mut log_console = LogConsole::new();
 
     // Frame tracking
     let mut frame_count = 0u64;
     let mut fps_timer = 0.0f32;
 
-    // Fixed timestep for smooth physics
-    const PHYSICS_TIMESTEP: f32 = 1.0 / 120.0; // 120 Hz physics for ultra-smooth movement
-    let mut physics_accumulator = 0.0f32;
+    // Fixed timestep physics (120 Hz for ultra-smooth movement), stepped coarser when the machine can't keep up
+    let mut physics_clock = AdaptiveTimestep::default();
 
-    log::info!("Entering main game loop with fixed timestep physics (120 Hz)");
+    log::info!("Entering main game loop with fixed timestep physics ({} Hz)", physics_clock.hz());
 
     // Main game loop
     loop {
-        let delta_time = get_frame_time().min(0.1); // Cap max frame time to prevent spiral of death
+        let frame_time = get_frame_time(); // The physics clock caps this itself
+        let delta_time = frame_time.min(0.1); // Cap max frame time for menus and effects
         frame_count += 1;
         fps_timer += delta_time;
 
-        // Accumulate frame time for fixed timestep physics
-        physics_accumulator += delta_time;
+        log_console.handle_input();
 
         // Handle input based on game state
         match game_state {
@@ -104,6 +263,37 @@ async fn main() {
                         log::info!("Multiplayer mode selected");
                         game_state = GameState::MultiplayerMenu;
                     }
+                    GameMode::BugReport => {
+                        let default_rates = host_rates_from_args();
+                        let settings = format!(
+                            "Collision rules: {:?}\nHardcore saves: {}\nDefault host rates: {} Hz physics, {} Hz snapshots\n",
+                            collision_rules,
+                            hardcore,
+                            default_rates.physics_hz(),
+                            default_rates.snapshot_hz(),
+                        );
+                        match BugReport::collect(&settings).save() {
+                            Ok(path) => main_menu.set_status(
+                                format!("Bug report saved to {} - attach it to your GitHub issue", path.display()),
+                                GREEN,
+                            ),
+                            Err(e) => {
+                                log::error!("Failed to write bug report: {}", e);
+                                main_menu.set_status(format!("Failed to write bug report: {}", e), RED);
+                            }
+                        }
+                        main_menu.reset();
+                    }
+                    GameMode::Profiles => {
+                        profile_menu.refresh_profiles();
+                        game_state = GameState::ProfileMenu;
+                        main_menu.reset();
+                    }
+                    GameMode::Leaderboard => {
+                        leaderboard_menu.refresh();
+                        game_state = GameState::LeaderboardMenu;
+                        main_menu.reset();
+                    }
                     GameMode::Quit => {
                         log::info!("Quit selected");
                         break;
@@ -116,20 +306,35 @@ async fn main() {
                 let result = saves_menu.update();
                 match result {
                     SavesMenuResult::NewGame => {
-                        log::info!("New game selected, showing map selection");
-                        game_state = GameState::MapSelection;
+                        log::info!("New game selected, rescanning maps before map selection");
+                        assets.reload_maps();
+                        assets.reset_progress();
+                        loading_screen.start("Loading Maps");
+                        after_loading = GameState::MapSelection;
+                        game_state = GameState::Loading;
+                        host_new_game_port = None;
+                        map_selection_menu.set_host_mode(false);
                     }
                     SavesMenuResult::LoadGame(save_name) => {
                         log::info!("Loading game: {}", save_name);
-                        match GameSaveData::load_from_file(&save_name) {
-                            Ok(save_data) => {
+                        match GameSaveData::load_or_recover(&save_name) {
+                            Ok(recovered) => {
                                 let mut loaded_game = SinglePlayerGame::new(window_size);
-                                loaded_game.load_from_save(save_data, save_name);
+                                loaded_game.set_transfer_sound(transfer_hum.clone());
+                                loaded_game.set_controllers(&controllers);
+                                loaded_game.set_alert_sound(alert_beep.clone());
+                                loaded_game.set_collision_rules(collision_rules);
+                                loaded_game.set_hardcore_saves(hardcore);
+                                loaded_game.load_from_save(recovered.data, recovered.save_name);
+                                if let Some(notice) = recovered.notice {
+                                    loaded_game.notify(notice, ORANGE);
+                                }
                                 single_player_game = Some(loaded_game);
                                 game_state = GameState::Playing;
                             }
                             Err(e) => {
                                 log::error!("Failed to load save: {}", e);
+                                saves_menu.set_status(format!("Couldn't load {}: {}", save_name, e));
                             }
                         }
                     }
@@ -146,21 +351,77 @@ async fn main() {
                 match result {
                     MapSelectionResult::MapSelected(map_name) => {
                         log::info!("Map selected: {}", map_name);
-                        // Find the map configuration by name from all available maps
-                        let all_maps = MapConfiguration::all_maps();
-                        let selected_map = all_maps
-                            .into_iter()
-                            .find(|m| m.name == map_name)
+                        // Find the map configuration by name from the loaded maps
+                        let selected_map = assets.map(&map_name)
                             .unwrap_or_else(|| {
                                 log::warn!("Map '{}' not found, using default", map_name);
                                 MapConfiguration::earth_moon()
                             });
 
-                        let mut new_game = SinglePlayerGame::new_with_map(window_size, selected_map);
-                        new_game.initialize_new_game();
+                        if let Some(port) = host_new_game_port {
+                            let player_name = host_player_name.as_deref().unwrap_or("Host");
+                            log::info!("Starting new multiplayer game '{}' on {} (port {})", player_name, map_name, port);
+                            match MultiplayerHost::new_with_map(window_size, player_name.to_string(), port, selected_map) {
+                                Ok(mut host) => {
+                                    host.set_rates(host_rates);
+                                    host.set_max_players(host_max_players);
+                                    host.set_transfer_sound(transfer_hum.clone());
+                                    host.set_controllers(&controllers);
+                                    host.set_alert_sound(alert_beep.clone());
+                                    host.set_collision_rules(collision_rules);
+                                    host.set_arena(arena);
+                                    host.set_hardcore_saves(hardcore);
+                                    host.initialize_new_game();
+                                    if let Some(path) = &world_database {
+                                        host.open_world_database(path);
+                                    }
+                                    if let Some((port, password)) = &rcon {
+                                        host.start_rcon(*port, password.clone());
+                                    }
+                                    multiplayer_host = Some(host);
+                                    host_new_game_port = None;
+                                    game_state = GameState::MultiplayerHost;
+                                }
+                                Err(e) => {
+                                    log::error!("Failed to start host: {}", e);
+                                    // Stay in menu
+                                }
+                            }
+                        } else {
+                            let mut new_game = SinglePlayerGame::new_with_map(window_size, selected_map);
+                            new_game.set_transfer_sound(transfer_hum.clone());
+                            new_game.set_controllers(&controllers);
+                            new_game.set_alert_sound(alert_beep.clone());
+                            new_game.set_collision_rules(collision_rules);
+                            new_game.set_hardcore_saves(hardcore);
+                            new_game.initialize_new_game();
+                            single_player_game = Some(new_game);
+                            game_state = GameState::Playing;
+                        }
+                    }
+                    MapSelectionResult::DailyChallenge => {
+                        let challenge = DailyChallenge::today();
+                        log::info!("Daily challenge selected: {}", challenge.date.label());
+                        let mut new_game = SinglePlayerGame::new_with_map(window_size, challenge.map.clone());
+                        new_game.set_transfer_sound(transfer_hum.clone());
+                        new_game.set_controllers(&controllers);
+                        new_game.set_alert_sound(alert_beep.clone());
+                        new_game.set_collision_rules(collision_rules);
+                        new_game.set_hardcore_saves(hardcore);
+                        new_game.start_daily_challenge(challenge);
                         single_player_game = Some(new_game);
                         game_state = GameState::Playing;
                     }
+                    MapSelectionResult::Scenarios => {
+                        log::info!("Scenarios selected");
+                        scenarios_menu.refresh_scenarios();
+                        game_state = GameState::ScenariosMenu;
+                    }
+                    MapSelectionResult::Back if host_new_game_port.is_some() => {
+                        log::info!("Returning to multiplayer saves menu from map selection");
+                        host_new_game_port = None;
+                        game_state = GameState::MultiplayerSavesMenu;
+                    }
                     MapSelectionResult::Back => {
                         log::info!("Returning to saves menu from map selection");
                         game_state = GameState::SavesMenu;
@@ -169,10 +430,47 @@ async fn main() {
                 }
             }
 
+            GameState::ScenariosMenu => {
+                match scenarios_menu.update() {
+                    ScenariosMenuResult::Start(path) => {
+                        log::info!("Starting scenario: {}", path);
+                        let scenario = Scenario::load_from_file(&path)
+                            .and_then(|scenario| scenario.resolve_map(assets.maps().to_vec()).map(|map| (scenario, map)));
+                        match scenario {
+                            Ok((scenario, map)) => {
+                                let mut new_game = SinglePlayerGame::new_with_map(window_size, map);
+                                new_game.set_transfer_sound(transfer_hum.clone());
+                                new_game.set_controllers(&controllers);
+                                new_game.set_alert_sound(alert_beep.clone());
+                                new_game.set_collision_rules(collision_rules);
+                                new_game.set_hardcore_saves(hardcore);
+                                new_game.start_scenario(scenario);
+                                single_player_game = Some(new_game);
+                                game_state = GameState::Playing;
+                            }
+                            Err(e) => {
+                                log::error!("Failed to start scenario: {}", e);
+                                scenarios_menu.set_status(e, RED);
+                            }
+                        }
+                    }
+                    ScenariosMenuResult::Back => {
+                        log::info!("Returning to map selection from scenarios");
+                        game_state = GameState::MapSelection;
+                    }
+                    ScenariosMenuResult::None => {}
+                }
+            }
+
             GameState::Playing => {
                 if let Some(ref mut game) = single_player_game {
                     // Handle input
                     match game.handle_input() {
+                        SinglePlayerResult::ReturnToMenu if !game.debrief().is_empty() => {
+                            log::info!("Returning to main menu via the flight debrief");
+                            debrief_menu = Some(DebriefMenu::new(window_size, game.debrief().clone()));
+                            game_state = GameState::Debrief;
+                        }
                         SinglePlayerResult::ReturnToMenu => {
                             log::info!("Returning to main menu");
                             game_state = GameState::MainMenu;
@@ -186,9 +484,9 @@ async fn main() {
                     }
 
                     // Fixed timestep physics update
-                    while physics_accumulator >= PHYSICS_TIMESTEP {
-                        game.update(PHYSICS_TIMESTEP);
-                        physics_accumulator -= PHYSICS_TIMESTEP;
+                    physics_clock.set_base_hz(GameConstants::DEFAULT_PHYSICS_HZ);
+                    for _ in 0..physics_clock.advance(frame_time) {
+                        game.update(physics_clock.timestep());
                     }
                 }
             }
@@ -197,6 +495,48 @@ async fn main() {
                 // Paused state - don't update game, but still render
             }
 
+            GameState::Debrief => {
+                let result = debrief_menu.as_mut().map_or(DebriefMenuResult::Continue, |menu| menu.update());
+                match result {
+                    DebriefMenuResult::Retry => {
+                        debrief_menu = None;
+                        match single_player_game.as_ref().map(|game| game.retry_plan()) {
+                            Some(plan) => {
+                                log::info!("Retrying the flight");
+                                let map = match &plan {
+                                    RetryPlan::NewGame(map) | RetryPlan::Scenario(_, map) => map.clone(),
+                                    RetryPlan::Challenge(challenge) => challenge.map.clone(),
+                                };
+                                let mut new_game = SinglePlayerGame::new_with_map(window_size, map);
+                                new_game.set_transfer_sound(transfer_hum.clone());
+                                new_game.set_controllers(&controllers);
+                                new_game.set_alert_sound(alert_beep.clone());
+                                new_game.set_collision_rules(collision_rules);
+                                new_game.set_hardcore_saves(hardcore);
+                                match plan {
+                                    RetryPlan::NewGame(_) => new_game.initialize_new_game(),
+                                    RetryPlan::Challenge(challenge) => new_game.start_daily_challenge(challenge),
+                                    RetryPlan::Scenario(scenario, _) => new_game.start_scenario(scenario),
+                                }
+                                single_player_game = Some(new_game);
+                                game_state = GameState::Playing;
+                            }
+                            None => {
+                                game_state = GameState::MainMenu;
+                                main_menu.reset();
+                            }
+                        }
+                    }
+                    DebriefMenuResult::Continue => {
+                        log::info!("Returning to main menu");
+                        debrief_menu = None;
+                        game_state = GameState::MainMenu;
+                        main_menu.reset();
+                    }
+                    DebriefMenuResult::None => {}
+                }
+            }
+
             GameState::Quit => {
                 break;
             }
@@ -204,9 +544,13 @@ async fn main() {
             GameState::MultiplayerMenu => {
                 let result = multiplayer_menu.update();
                 match result {
-                    MultiplayerMenuResult::SplitScreen => {
-                        log::info!("Split-Screen selected");
-                        let mut new_game = SplitScreenGame::new(window_size);
+                    MultiplayerMenuResult::SplitScreen(players) => {
+                        log::info!("Split-Screen selected ({} players)", players);
+                        let mut new_game = SplitScreenGame::new(window_size, players);
+                        new_game.set_transfer_sound(transfer_hum.clone());
+                        new_game.set_controllers(&controllers);
+                        new_game.set_alert_sound(alert_beep.clone());
+                        new_game.set_collision_rules(collision_rules);
                         new_game.initialize_new_game();
                         split_screen_game = Some(new_game);
                         game_state = GameState::SplitScreen;
@@ -215,6 +559,11 @@ async fn main() {
                         log::info!("Online Multiplayer selected");
                         game_state = GameState::OnlineMultiplayerMenu;
                     }
+                    MultiplayerMenuResult::Replays => {
+                        log::info!("Match Replays selected");
+                        replays_menu.refresh_replays();
+                        game_state = GameState::ReplaysMenu;
+                    }
                     MultiplayerMenuResult::Back => {
                         log::info!("Returning to main menu from multiplayer");
                         game_state = GameState::MainMenu;
@@ -239,9 +588,9 @@ async fn main() {
                     }
 
                     // Fixed timestep physics update
-                    while physics_accumulator >= PHYSICS_TIMESTEP {
-                        game.update(PHYSICS_TIMESTEP);
-                        physics_accumulator -= PHYSICS_TIMESTEP;
+                    physics_clock.set_base_hz(GameConstants::DEFAULT_PHYSICS_HZ);
+                    for _ in 0..physics_clock.advance(frame_time) {
+                        game.update(physics_clock.timestep());
                     }
                 }
             }
@@ -268,10 +617,12 @@ async fn main() {
             GameState::OnlineHostMenu => {
                 let result = online_host_menu.update();
                 match result {
-                    OnlineHostMenuResult::StartHost(player_name, port) => {
+                    OnlineHostMenuResult::StartHost(player_name, port, rates, max_players) => {
                         log::info!("Host '{}' proceeding to multiplayer saves menu on port {}", player_name, port);
                         host_player_name = Some(player_name);
                         host_port = Some(port);
+                        host_rates = rates;
+                        host_max_players = max_players;
                         game_state = GameState::MultiplayerSavesMenu;
                         multiplayer_saves_menu.refresh_saves();
                     }
@@ -287,19 +638,14 @@ async fn main() {
                 let result = multiplayer_saves_menu.update();
                 match result {
                     MultiplayerSavesMenuResult::NewGame(port) => {
-                        let player_name = host_player_name.as_deref().unwrap_or("Host");
-                        log::info!("Starting new multiplayer game '{}' on port {}", player_name, port);
-                        match MultiplayerHost::new(window_size, player_name.to_string(), port) {
-                            Ok(mut host) => {
-                                host.initialize_new_game();
-                                multiplayer_host = Some(host);
-                                game_state = GameState::MultiplayerHost;
-                            }
-                            Err(e) => {
-                                log::error!("Failed to start host: {}", e);
-                                // Stay in menu
-                            }
-                        }
+                        log::info!("New multiplayer game on port {}, rescanning maps before map selection", port);
+                        assets.reload_maps();
+                        assets.reset_progress();
+                        loading_screen.start("Loading Maps");
+                        host_new_game_port = Some(port);
+                        map_selection_menu.set_host_mode(true);
+                        after_loading = GameState::MapSelection;
+                        game_state = GameState::Loading;
                     }
                     MultiplayerSavesMenuResult::LoadGame(save_name, port) => {
                         let player_name = host_player_name.as_deref().unwrap_or("Host");
@@ -308,7 +654,21 @@ async fn main() {
                             Ok(save_data) => {
                                 match MultiplayerHost::new(window_size, player_name.to_string(), port) {
                                     Ok(mut host) => {
+                                        host.set_rates(host_rates);
+                                        host.set_max_players(host_max_players);
+                                        host.set_transfer_sound(transfer_hum.clone());
+                                        host.set_controllers(&controllers);
+                                        host.set_alert_sound(alert_beep.clone());
+                                        host.set_collision_rules(collision_rules);
+                                        host.set_arena(arena);
+                                        host.set_hardcore_saves(hardcore);
                                         host.load_from_save(save_data, save_name);
+                                        if let Some(path) = &world_database {
+                                            host.open_world_database(path);
+                                        }
+                                        if let Some((port, password)) = &rcon {
+                                            host.start_rcon(*port, password.clone());
+                                        }
                                         multiplayer_host = Some(host);
                                         game_state = GameState::MultiplayerHost;
                                     }
@@ -320,7 +680,7 @@ async fn main() {
                             }
                             Err(e) => {
                                 log::error!("Failed to load multiplayer save: {}", e);
-                                // Stay in menu
+                                multiplayer_saves_menu.set_error(format!("Couldn't load {}: {}", save_name, e));
                             }
                         }
                     }
@@ -333,12 +693,30 @@ async fn main() {
             }
 
             GameState::OnlineJoinMenu => {
+                // Listen for hosts on the LAN while the menu is open (retrying each refresh if the port was busy)
+                if online_join_menu.take_refresh_request() && lan_discovery.is_none() {
+                    lan_discovery = LanDiscovery::start().map_err(|e| log::warn!("LAN discovery unavailable: {}", e)).ok();
+                }
+                if let Some(ref discovery) = lan_discovery {
+                    for listing in discovery.poll() {
+                        online_join_menu.browser_mut().upsert(listing);
+                    }
+                }
+
                 let result = online_join_menu.update();
+                if !matches!(result, OnlineJoinMenuResult::None) {
+                    // Free the discovery port; listen again as soon as the menu reopens
+                    lan_discovery = None;
+                    online_join_menu.browser_mut().refresh_now();
+                }
                 match result {
-                    OnlineJoinMenuResult::Connect(player_name, ip, port) => {
-                        log::info!("'{}' connecting to {}:{}", player_name, ip, port);
-                        match MultiplayerClient::new(window_size, player_name, &ip, port) {
-                            Ok(client) => {
+                    OnlineJoinMenuResult::Connect(player_name, ip, port, role) => {
+                        log::info!("'{}' connecting to {}:{} as {}", player_name, ip, port, role.label());
+                        match MultiplayerClient::new(window_size, player_name, &ip, port, role) {
+                            Ok(mut client) => {
+                                client.set_transfer_sound(transfer_hum.clone());
+                                client.set_controllers(&controllers);
+                                client.set_alert_sound(alert_beep.clone());
                                 multiplayer_client = Some(client);
                                 game_state = GameState::MultiplayerClient;
                             }
@@ -362,21 +740,27 @@ async fn main() {
                     match host.handle_input() {
                         MultiplayerHostResult::ReturnToMenu => {
                             log::info!("Returning to multiplayer menu from host");
+                            host.shutdown();
+                            host.finish_recording();
+                            host.close_world_database();
                             should_drop_host = true;
                             game_state = GameState::MultiplayerMenu;
                         }
                         MultiplayerHostResult::Quit => {
                             log::info!("Quit requested from host");
+                            host.shutdown();
+                            host.finish_recording();
+                            host.close_world_database();
                             break;
                         }
                         _ => {}
                     }
 
                     if !should_drop_host {
-                        // Fixed timestep physics update
-                        while physics_accumulator >= PHYSICS_TIMESTEP {
-                            host.update(PHYSICS_TIMESTEP);
-                            physics_accumulator -= PHYSICS_TIMESTEP;
+                        // Fixed timestep physics update at the host's configured tick rate
+                        physics_clock.set_base_hz(host.rates().physics_hz());
+                        for _ in 0..physics_clock.advance(frame_time) {
+                            host.update(physics_clock.timestep());
                         }
                     }
                 }
@@ -392,11 +776,13 @@ async fn main() {
                     match client.handle_input() {
                         MultiplayerClientResult::ReturnToMenu => {
                             log::info!("Returning to multiplayer menu from client");
+                            client.disconnect();
                             should_drop_client = true;
                             game_state = GameState::MultiplayerMenu;
                         }
                         MultiplayerClientResult::Quit => {
                             log::info!("Quit requested from client");
+                            client.disconnect();
                             break;
                         }
                         MultiplayerClientResult::ConnectionLost => {
@@ -409,9 +795,9 @@ async fn main() {
 
                     if !should_drop_client {
                         // Fixed timestep physics update
-                        while physics_accumulator >= PHYSICS_TIMESTEP {
-                            client.update(PHYSICS_TIMESTEP);
-                            physics_accumulator -= PHYSICS_TIMESTEP;
+                        physics_clock.set_base_hz(GameConstants::DEFAULT_PHYSICS_HZ);
+                        for _ in 0..physics_clock.advance(frame_time) {
+                            client.update(physics_clock.timestep());
                         }
                     }
                 }
@@ -420,6 +806,118 @@ async fn main() {
                     multiplayer_client = None;
                 }
             }
+
+            GameState::ReplaysMenu => {
+                match replays_menu.update() {
+                    ReplaysMenuResult::Watch(path) => {
+                        log::info!("Watching replay: {}", path);
+                        match ReplayViewer::load(&path, window_size) {
+                            Ok(viewer) => {
+                                replay_viewer = Some(viewer);
+                                game_state = GameState::Replay;
+                            }
+                            Err(e) => {
+                                log::error!("Failed to open replay: {}", e);
+                                replays_menu.set_status(e, RED);
+                            }
+                        }
+                    }
+                    ReplaysMenuResult::Back => {
+                        log::info!("Returning to multiplayer menu from replays");
+                        game_state = GameState::MultiplayerMenu;
+                    }
+                    ReplaysMenuResult::None => {}
+                }
+            }
+
+            GameState::Loading => {
+                assets.step().await;
+                loading_screen.update(assets.progress(), delta_time);
+                if assets.is_loaded() {
+                    map_selection_menu.set_maps(assets.maps());
+                    map_selection_menu.set_map_errors(assets.map_errors());
+                    game_state = after_loading;
+                }
+            }
+
+            GameState::ProfileMenu => {
+                match profile_menu.update() {
+                    ProfileMenuResult::Select(name) => match local_profiles::select(&name) {
+                        Ok(()) => {
+                            main_menu.refresh_profile();
+                            saves_menu.refresh_saves();
+                            multiplayer_saves_menu.refresh_saves();
+                            game_state = GameState::MainMenu;
+                        }
+                        Err(e) => {
+                            log::error!("Failed to select profile '{}': {}", name, e);
+                            profile_menu.set_status(e, RED);
+                        }
+                    },
+                    ProfileMenuResult::Back => {
+                        game_state = GameState::MainMenu;
+                    }
+                    ProfileMenuResult::None => {}
+                }
+            }
+
+            GameState::LeaderboardMenu => {
+                match leaderboard_menu.update() {
+                    LeaderboardMenuResult::Back => {
+                        game_state = GameState::MainMenu;
+                    }
+                    LeaderboardMenuResult::None => {}
+                }
+            }
+
+            GameState::Replay => {
+                if let Some(ref mut viewer) = replay_viewer {
+                    if viewer.handle_input() == ReplayViewerResult::ReturnToMenu {
+                        log::info!("Returning to replays menu");
+                        game_state = GameState::ReplaysMenu;
+                    } else {
+                        viewer.update(delta_time);
+                    }
+                }
+                if game_state != GameState::Replay {
+                    replay_viewer = None;
+                }
+            }
+        }
+
+        // Share what we're doing with Discord, and follow join invites accepted there
+        let presence = match game_state {
+            GameState::Playing => single_player_game.as_ref().map(|game| game.presence_info()),
+            GameState::SplitScreen => split_screen_game.as_ref().map(|game| game.presence_info()),
+            GameState::MultiplayerHost => multiplayer_host.as_ref().map(|host| host.presence_info()),
+            GameState::MultiplayerClient => multiplayer_client.as_ref().map(|client| client.presence_info()),
+            _ => None,
+        };
+        let in_game = presence.is_some();
+        rich_presence.update(delta_time, presence.unwrap_or_else(PresenceInfo::menus));
+
+        if let Some((ip, port)) = rich_presence.take_join_request() {
+            if in_game {
+                log::info!("Ignoring Discord join request for {}:{} while in a game", ip, port);
+            } else {
+                log::info!("Joining {}:{} from a Discord invite", ip, port);
+                online_join_menu.set_host(&ip, port);
+                let player_name = online_join_menu.player_name().to_string();
+                match MultiplayerClient::new(window_size, player_name, &ip, port, CrewRole::Pilot) {
+                    Ok(mut client) => {
+                        client.set_transfer_sound(transfer_hum.clone());
+                        client.set_controllers(&controllers);
+                        client.set_alert_sound(alert_beep.clone());
+                        multiplayer_client = Some(client);
+                        game_state = GameState::MultiplayerClient;
+                    }
+                    Err(e) => {
+                        // Leave the address filled in so the player can retry from the join menu
+                        log::error!("Failed to join from Discord: {}", e);
+                        game_state = GameState::OnlineJoinMenu;
+                    }
+                }
+            }
         }
 
         // Render based on game state
@@ -438,6 +936,10 @@ async fn main() {
                 map_selection_menu.render();
             }
 
+            GameState::ScenariosMenu => {
+                scenarios_menu.draw();
+            }
+
             GameState::MultiplayerMenu => {
                 multiplayer_menu.draw();
             }
@@ -448,6 +950,12 @@ async fn main() {
                 }
             }
 
+            GameState::Debrief => {
+                if let Some(ref menu) = debrief_menu {
+                    menu.draw();
+                }
+            }
+
             GameState::SplitScreen => {
                 if let Some(ref mut game) = split_screen_game {
                     game.render();
@@ -483,8 +991,38 @@ async fn main() {
                     client.render();
                 }
             }
+
+            GameState::ReplaysMenu => {
+                replays_menu.draw();
+            }
+
+            GameState::ProfileMenu => {
+                profile_menu.draw();
+            }
+
+            GameState::LeaderboardMenu => {
+                leaderboard_menu.draw();
+            }
+
+            GameState::Loading => {
+                loading_screen.draw(assets.current());
+            }
+
+            GameState::Replay => {
+                if let Some(ref viewer) = replay_viewer {
+                    viewer.render();
+                }
+            }
+        }
+
+        // Heavy-load warning over the game screens
+        if matches!(game_state, GameState::Playing | GameState::SplitScreen | GameState::MultiplayerHost | GameState::MultiplayerClient) {
+            timestep_warning::draw(&physics_clock);
         }
 
+        // Log console draws over every screen
+        log_console.draw();
+
         // Log FPS every second
         if fps_timer >= 1.0 {
             let fps = get_fps();
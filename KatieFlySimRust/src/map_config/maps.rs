@@ -19,6 +19,7 @@ impl MapConfiguration {
                     orbital_period: None,
                     initial_angle: 0.0,
                     is_pinned: true, // Stays at origin
+                    terrain: None,
                 },
                 // --- MOON (index 1) ---
                 CelestialBodyConfig {
@@ -31,10 +32,43 @@ impl MapConfiguration {
                     orbital_period: Some(GameConstants::ORBIT_PERIOD),
                     initial_angle: 0.0, // Starts to the right of Earth
                     is_pinned: false,
+                    terrain: Some(TerrainConfig { seed: 1969, amplitude: 0.03, heights: Vec::new() }), // Cratered surface
                 },
             ],
             player_spawn_body_index: 0, // Spawn on Earth
             central_body_index: Some(0), // Earth is center
+            tidal_breakup: false,
+            gravity_model: GravityModel::Realistic,
+            tunables: MapTunables::default(),
+            sample_sites: vec![
+                SampleSiteConfig {
+                    name: "Tranquility Base".to_string(),
+                    body_index: 1, // Moon
+                    angle: 0.0,
+                },
+                SampleSiteConfig {
+                    name: "Far Side Crater".to_string(),
+                    body_index: 1,
+                    angle: std::f32::consts::PI,
+                },
+            ],
+            fuel_deposits: vec![
+                FuelDepositConfig {
+                    body_index: 1, // Moon
+                    angle: std::f32::consts::PI / 2.0,
+                    amount: 60.0,
+                },
+            ],
+            landing_zones: vec![
+                LandingZoneConfig {
+                    name: "Apollo Pad".to_string(),
+                    body_index: 1, // Moon, facing away from Earth at the start
+                    angle: -std::f32::consts::PI / 2.0,
+                    radius: 250.0,
+                },
+            ],
+            rings: Vec::new(),
+            arena: None,
         }
     }
 
@@ -63,6 +97,7 @@ impl MapConfiguration {
                     orbital_period: None,
                     initial_angle: 0.0,
                     is_pinned: true, // Center of solar system
+                    terrain: None,
                 },
 
                 // --- MERCURY (index 1) ---
@@ -77,6 +112,7 @@ impl MapConfiguration {
                     orbital_period: None, // Calculated from physics
                     initial_angle: 0.0,
                     is_pinned: false,
+                    terrain: None,
                 },
 
                 // --- VENUS (index 2) ---
@@ -91,6 +127,7 @@ impl MapConfiguration {
                     orbital_period: None,
                     initial_angle: std::f32::consts::PI / 4.0, // 45 degrees
                     is_pinned: false,
+                    terrain: None,
                 },
 
                 // --- EARTH (index 3) ---
@@ -105,6 +142,7 @@ impl MapConfiguration {
                     orbital_period: None, // Physics-calculated
                     initial_angle: std::f32::consts::PI / 2.0, // 90 degrees
                     is_pinned: false, // Orbits Sun
+                    terrain: None,
                 },
 
                 // --- MOON (index 4) ---
@@ -119,6 +157,7 @@ impl MapConfiguration {
                     orbital_period: None,
                     initial_angle: 0.0,
                     is_pinned: false,
+                    terrain: Some(TerrainConfig { seed: 1969, amplitude: 0.03, heights: Vec::new() }), // Cratered surface
                 },
 
                 // --- MARS (index 5) ---
@@ -133,6 +172,7 @@ impl MapConfiguration {
                     orbital_period: None,
                     initial_angle: std::f32::consts::PI, // 180 degrees
                     is_pinned: false,
+                    terrain: Some(TerrainConfig { seed: 1976, amplitude: 0.02, heights: Vec::new() }), // Rocky highlands
                 },
 
                 // --- JUPITER (index 6) ---
@@ -147,6 +187,7 @@ impl MapConfiguration {
                     orbital_period: None,
                     initial_angle: 3.0 * std::f32::consts::PI / 2.0, // 270 degrees
                     is_pinned: false,
+                    terrain: None,
                 },
 
                 // --- SATURN (index 7) ---
@@ -161,6 +202,7 @@ impl MapConfiguration {
                     orbital_period: None,
                     initial_angle: 2.0 * std::f32::consts::PI / 3.0, // 120 degrees
                     is_pinned: false,
+                    terrain: None,
                 },
 
                 // --- URANUS (index 8) ---
@@ -175,6 +217,7 @@ impl MapConfiguration {
                     orbital_period: None,
                     initial_angle: std::f32::consts::PI / 6.0, // 30 degrees
                     is_pinned: false,
+                    terrain: None,
                 },
 
                 // --- NEPTUNE (index 9) ---
@@ -189,6 +232,7 @@ impl MapConfiguration {
                     orbital_period: None,
                     initial_angle: 5.0 * std::f32::consts::PI / 6.0, // 150 degrees
                     is_pinned: false,
+                    terrain: None,
                 },
 
                 // --- PLUTO (index 10) ---
@@ -203,10 +247,47 @@ impl MapConfiguration {
                     orbital_period: None,
                     initial_angle: 4.0 * std::f32::consts::PI / 3.0, // 240 degrees
                     is_pinned: false,
+                    terrain: None,
                 },
             ],
             player_spawn_body_index: 3, // Spawn on Earth (index 3)
             central_body_index: Some(0), // Sun is center
+            tidal_breakup: false,
+            gravity_model: GravityModel::Realistic,
+            tunables: MapTunables::default(),
+            sample_sites: vec![
+                SampleSiteConfig {
+                    name: "Tranquility Base".to_string(),
+                    body_index: 4, // Moon
+                    angle: 0.0,
+                },
+                SampleSiteConfig {
+                    name: "Olympus Mons".to_string(),
+                    body_index: 5, // Mars
+                    angle: std::f32::consts::PI / 2.0,
+                },
+            ],
+            fuel_deposits: Vec::new(),
+            landing_zones: vec![
+                LandingZoneConfig {
+                    name: "Jezero Crater".to_string(),
+                    body_index: 5, // Mars
+                    angle: -std::f32::consts::PI / 2.0,
+                    radius: 300.0,
+                },
+            ],
+            rings: vec![
+                RingConfig {
+                    body_index: 7, // Saturn
+                    inner_radius: earth_radius * 11.5,
+                    outer_radius: earth_radius * 22.0,
+                    color: Color::from_rgba(214, 196, 150, 255),
+                    density: 0.6,
+                    harvestable: true,
+                    hazardous: true,
+                },
+            ],
+            arena: None,
         }
     }
 
@@ -226,6 +307,7 @@ impl MapConfiguration {
                     orbital_period: None,
                     initial_angle: 0.0,
                     is_pinned: true,            // Stays at origin (center of map)
+                    terrain: None,
                 },
 
                 // --- MOON (index 1) - Orbits Earth ---
@@ -239,6 +321,7 @@ impl MapConfiguration {
                     orbital_period: Some(120.0), // Time for one orbit in seconds
                     initial_angle: 0.0,         // Starting position (0 = right, PI/2 = top, PI = left)
                     is_pinned: false,
+                    terrain: Some(TerrainConfig { seed: 1969, amplitude: 0.03, heights: Vec::new() }), // Cratered surface
                 },
 
                 // --- KATIE (index 2) - Orbits Moon ---
@@ -252,50 +335,99 @@ impl MapConfiguration {
                     orbital_period: Some(60.0), // Time for one orbit in seconds (1/2x Moon's period)
                     initial_angle: 0.0, // Starting position
                     is_pinned: false,
+                    terrain: Some(TerrainConfig { seed: 7, amplitude: 0.05, heights: Vec::new() }), // Rolling hills
                 },
             ],
             player_spawn_body_index: 0, // Spawn on Earth (index 0)
             central_body_index: Some(0), // Earth is center of view
+            tidal_breakup: false,
+            gravity_model: GravityModel::Realistic,
+            tunables: MapTunables::default(),
+            sample_sites: vec![
+                SampleSiteConfig {
+                    name: "Moon Highlands".to_string(),
+                    body_index: 1, // Moon
+                    angle: std::f32::consts::PI / 2.0,
+                },
+                SampleSiteConfig {
+                    name: "Katie's Garden".to_string(),
+                    body_index: 2, // Katie
+                    angle: 0.0,
+                },
+            ],
+            fuel_deposits: vec![
+                FuelDepositConfig {
+                    body_index: 2, // Katie
+                    angle: std::f32::consts::PI,
+                    amount: 40.0,
+                },
+            ],
+            landing_zones: Vec::new(),
+            rings: Vec::new(),
+            arena: None,
         }
     }
 
-    /// Load custom maps from the maps/ folder
-    pub fn load_custom_maps() -> Vec<MapConfiguration> {
-        let mut custom_maps = Vec::new();
-        let maps_folder = "maps";
-
-        // Create maps folder if it doesn't exist
+    /// Map files (.ron or .json) in a folder, sorted by name; creates the folder if it's missing
+    pub fn custom_map_paths(maps_folder: &str) -> Vec<String> {
         let _ = std::fs::create_dir_all(maps_folder);
 
-        // Try to read the maps directory
-        if let Ok(entries) = std::fs::read_dir(maps_folder) {
-            for entry in entries.flatten() {
-                if let Ok(path) = entry.path().canonicalize() {
-                    if path.extension().and_then(|s| s.to_str()) == Some("ron") {
-                        match MapConfiguration::load_from_file(path.to_str().unwrap()) {
-                            Ok(map) => {
-                                println!("Loaded custom map: {}", map.name);
-                                custom_maps.push(map);
-                            }
-                            Err(e) => {
-                                println!("Failed to load map from {:?}: {}", path, e);
-                            }
-                        }
-                    }
+        let mut paths: Vec<String> = std::fs::read_dir(maps_folder)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| entry.path().canonicalize().ok())
+                    .filter_map(|path| path.to_str().map(str::to_string))
+                    .filter(|path| crate::schemas::SchemaFormat::from_path(path).is_some())
+                    .collect()
+            })
+            .unwrap_or_default();
+        paths.sort();
+        paths
+    }
+
+    /// Load and check one custom map file; its name must not be taken by a map in `loaded`
+    /// (maps are looked up by name, so a duplicate could never be picked)
+    pub fn load_custom(path: &str, loaded: &[MapConfiguration]) -> Result<MapConfiguration, String> {
+        let map = MapConfiguration::load_from_file(path)?;
+        if loaded.iter().any(|other| other.name == map.name) {
+            return Err(format!("Map name '{}' is already used by another map", map.name));
+        }
+        Ok(map)
+    }
+
+    /// Load custom maps (.ron or .json) from the maps/ folder
+    pub fn load_custom_maps() -> Vec<MapConfiguration> {
+        let mut maps = MapConfiguration::built_in_maps();
+        let built_in = maps.len();
+
+        for path in MapConfiguration::custom_map_paths("maps") {
+            match MapConfiguration::load_custom(&path, &maps) {
+                Ok(map) => {
+                    log::info!("Loaded custom map: {}", map.name);
+                    maps.push(map);
+                }
+                Err(e) => {
+                    log::warn!("Failed to load map from {}: {}", path, e);
                 }
             }
         }
 
-        custom_maps
+        maps.split_off(built_in)
     }
 
-    /// Get all available maps (built-in + custom)
-    pub fn all_maps() -> Vec<MapConfiguration> {
-        let mut maps = vec![
+    /// The maps shipped with the game
+    pub fn built_in_maps() -> Vec<MapConfiguration> {
+        vec![
             MapConfiguration::earth_moon(),
             MapConfiguration::solar_1(),
             MapConfiguration::katie_1(),
-        ];
+        ]
+    }
+
+    /// Get all available maps (built-in + custom)
+    pub fn all_maps() -> Vec<MapConfiguration> {
+        let mut maps = MapConfiguration::built_in_maps();
 
         // Add custom maps
         maps.extend(MapConfiguration::load_custom_maps());
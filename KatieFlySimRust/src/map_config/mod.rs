@@ -4,6 +4,12 @@ pub mod orbit_calculator;
 use macroquad::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::entities::{Rocket, Terrain};
+use crate::game_constants::GameConstants;
+use crate::physics::GravityModel;
+use crate::schemas::MapSchema;
+use crate::systems::ArenaBounds;
+
 /// Serializable wrapper for macroquad Color
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SerializableColor {
@@ -37,6 +43,125 @@ pub struct MapConfiguration {
     pub celestial_bodies: Vec<CelestialBodyConfig>,
     pub player_spawn_body_index: usize, // Which body to spawn on
     pub central_body_index: Option<usize>, // Which body is the center (if any)
+    #[serde(default)]
+    pub tidal_breakup: bool, // Advanced physics: Roche-limit breakup of moons, satellites and rockets
+    #[serde(default)]
+    pub gravity_model: GravityModel, // How planet gravity falls off for rockets, satellites and bullets
+    #[serde(default)]
+    pub tunables: MapTunables, // Per-map overrides of rocket and bullet physics
+    #[serde(default)]
+    pub sample_sites: Vec<SampleSiteConfig>, // Surface sites to collect samples from (returned to the spawn body)
+    #[serde(default)]
+    pub fuel_deposits: Vec<FuelDepositConfig>, // Surface fuel a rover can extract
+    #[serde(default)]
+    pub landing_zones: Vec<LandingZoneConfig>, // Precision landing targets, scored by distance from center
+    #[serde(default)]
+    pub rings: Vec<RingConfig>, // Ring systems around bodies
+    #[serde(default)]
+    pub arena: Option<ArenaConfig>, // Wrapping PvP arena around the central body (None = open space)
+}
+
+/// Per-map overrides of entity physics; anything left as None uses the GameConstants default
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MapTunables {
+    #[serde(default)]
+    pub rocket_base_mass: Option<f32>,
+    #[serde(default)]
+    pub rocket_max_fuel: Option<f32>,
+    #[serde(default)]
+    pub bullet_speed: Option<f32>,
+    #[serde(default)]
+    pub spawn_altitude: Option<f32>, // Height above the spawn body's surface
+}
+
+impl MapTunables {
+    pub fn rocket_base_mass(&self) -> f32 {
+        self.rocket_base_mass.unwrap_or(GameConstants::ROCKET_BASE_MASS)
+    }
+
+    pub fn rocket_max_fuel(&self) -> f32 {
+        self.rocket_max_fuel.unwrap_or(GameConstants::ROCKET_MAX_FUEL)
+    }
+
+    pub fn bullet_speed(&self) -> f32 {
+        self.bullet_speed.unwrap_or(GameConstants::BULLET_SPEED)
+    }
+
+    pub fn spawn_altitude(&self) -> f32 {
+        self.spawn_altitude.unwrap_or(GameConstants::ROCKET_SPAWN_ALTITUDE)
+    }
+
+    /// Give a rocket this map's hull mass and tank size (keeping as much of its fuel as fits)
+    pub fn apply_to_rocket(&self, rocket: &mut Rocket) {
+        rocket.set_base_mass(self.rocket_base_mass());
+        rocket.set_max_fuel(self.rocket_max_fuel());
+    }
+}
+
+/// A surface site where a landed rocket can collect a sample
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SampleSiteConfig {
+    pub name: String,
+    pub body_index: usize, // Which body the site is on
+    pub angle: f32,        // Position on the surface in radians (same convention as initial_angle)
+}
+
+/// A surface fuel deposit that a rover can extract from
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FuelDepositConfig {
+    pub body_index: usize,
+    pub angle: f32,
+    pub amount: f32, // Total fuel in the deposit
+}
+
+/// A precision landing target on a body's surface
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LandingZoneConfig {
+    pub name: String,
+    pub body_index: usize,
+    pub angle: f32,  // Center of the zone in radians (same convention as initial_angle)
+    pub radius: f32, // Distance along the surface from the center that still counts
+}
+
+/// Arena size as written in a map file or the host's --arena flag
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ArenaConfig {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl ArenaConfig {
+    /// Parse "8000" (square) or "12000x8000"
+    pub fn parse(text: &str) -> Option<ArenaConfig> {
+        let (width, height) = match text.split_once(['x', 'X']) {
+            Some((width, height)) => (width.trim().parse().ok()?, height.trim().parse().ok()?),
+            None => {
+                let side: f32 = text.trim().parse().ok()?;
+                (side, side)
+            }
+        };
+        (width > 0.0 && height > 0.0).then_some(ArenaConfig { width, height })
+    }
+
+    /// Bounds of this size centered on `center`
+    pub fn bounds_around(&self, center: DVec2) -> ArenaBounds {
+        ArenaBounds::new(center, Vec2::new(self.width, self.height))
+    }
+}
+
+/// A ring system around a body, drawn as a translucent annulus
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RingConfig {
+    pub body_index: usize,
+    pub inner_radius: f32, // Distance from the body's center
+    pub outer_radius: f32,
+    #[serde(with = "color_serde")]
+    pub color: Color,
+    pub density: f32, // 0.0-1.0: opacity, harvest yield and hazard damage scale with it
+    #[serde(default)]
+    pub harvestable: bool, // Rockets drifting with the particles scoop fuel
+    #[serde(default)]
+    pub hazardous: bool, // Crossing the particles at high relative speed damages the hull
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -51,6 +176,24 @@ pub struct CelestialBodyConfig {
     pub orbital_period: Option<f32>, // Seconds to complete orbit
     pub initial_angle: f32, // Starting angle in radians (0 = right, π/2 = up)
     pub is_pinned: bool, // If true, doesn't move (for central bodies)
+    #[serde(default)]
+    pub terrain: Option<TerrainConfig>, // Surface heightmap (None = perfectly round)
+}
+
+/// Surface terrain for a body: generated from a seed, or authored heights if any are given
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TerrainConfig {
+    pub seed: u64,
+    pub amplitude: f32, // Tallest peak as a fraction of the radius (e.g. 0.03)
+    #[serde(default)]
+    pub heights: Vec<f32>, // Authored heights (fractions of the radius), evenly spaced from angle 0
+}
+
+impl TerrainConfig {
+    pub fn to_terrain(&self) -> Terrain {
+        Terrain::from_heights(self.heights.clone())
+            .unwrap_or_else(|| Terrain::generate(self.seed, self.amplitude))
+    }
 }
 
 // Custom serde module for Color
@@ -80,25 +223,22 @@ impl MapConfiguration {
         &self.celestial_bodies[self.player_spawn_body_index]
     }
 
-    /// Load a map from a RON file
-    pub fn load_from_file(filename: &str) -> Result<Self, String> {
-        let contents = std::fs::read_to_string(filename)
-            .map_err(|e| format!("Failed to read file {}: {}", filename, e))?;
-
-        let map: MapConfiguration = ron::from_str(&contents)
-            .map_err(|e| format!("Failed to parse RON from {}: {}", filename, e))?;
+    /// The arena around where the central (or spawn) body starts, if this map is an arena
+    pub fn arena_bounds(&self) -> Option<ArenaBounds> {
+        let arena = self.arena?;
+        let index = self.central_body_index.unwrap_or(self.player_spawn_body_index);
+        let states = orbit_calculator::calculate_initial_states(self, GameConstants::G);
+        let center = states.get(index)?.position;
+        Some(arena.bounds_around(center.as_dvec2()))
+    }
 
-        Ok(map)
+    /// Load a map from a RON or JSON file (see `schemas::MapSchema` for the layout)
+    pub fn load_from_file(filename: &str) -> Result<Self, String> {
+        crate::schemas::read_file::<MapSchema>(filename)?.into_map()
     }
 
-    /// Save a map to a RON file
+    /// Save a map as JSON if the file name ends in .json, RON otherwise
     pub fn save_to_file(&self, filename: &str) -> Result<(), String> {
-        let ron_string = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
-            .map_err(|e| format!("Failed to serialize map: {}", e))?;
-
-        std::fs::write(filename, ron_string)
-            .map_err(|e| format!("Failed to write file {}: {}", filename, e))?;
-
-        Ok(())
+        crate::schemas::write_file(&MapSchema::from(self), filename)
     }
 }
@@ -49,15 +49,25 @@ pub fn calculate_initial_states(
         } else if let Some(parent_index) = body.orbital_parent_index {
             // Calculate orbital velocity around parent
             if let Some(distance) = body.orbital_distance {
+                let parent = &map.celestial_bodies[parent_index];
                 let parent_pos = states[parent_index].position;
-                let parent_mass = map.celestial_bodies[parent_index].mass;
                 let body_pos = states[i].position;
 
+                // A pinned parent can't be pulled back, so only its mass counts;
+                // otherwise both bodies orbit their shared barycenter (two-body problem)
+                let mut central_mass = if parent.is_pinned {
+                    parent.mass
+                } else {
+                    parent.mass + body.mass
+                };
+                // Siblings on tighter orbits (e.g. the partner star under a circumbinary planet) pull inward too
+                central_mass += enclosed_sibling_mass(map, parent_index, i, distance);
+
                 // Calculate orbital velocity perpendicular to radius
                 let orbital_velocity = calculate_orbital_velocity(
                     parent_pos,
                     body_pos,
-                    parent_mass,
+                    central_mass,
                     gravity_constant,
                 );
 
@@ -68,9 +78,97 @@ pub fn calculate_initial_states(
         }
     }
 
+    // Third pass: systems with no pinned body orbit their barycenter instead of drifting
+    // (e.g. a binary star, where the secondary's orbital velocity would drag the whole system)
+    for root in 0..map.celestial_bodies.len() {
+        if map.celestial_bodies[root].orbital_parent_index.is_some() {
+            continue;
+        }
+
+        let members: Vec<usize> = (0..map.celestial_bodies.len())
+            .filter(|&i| root_of(map, i) == root)
+            .collect();
+        if members.iter().any(|&i| map.celestial_bodies[i].is_pinned) {
+            continue;
+        }
+
+        let bodies: Vec<(Vec2, Vec2, f32)> = members
+            .iter()
+            .map(|&i| (states[i].position, states[i].velocity, map.celestial_bodies[i].mass))
+            .collect();
+        let Some((center, center_velocity)) = barycenter(&bodies) else {
+            continue;
+        };
+
+        // Keep the barycenter where the root was placed and cancel the net momentum
+        let offset = states[root].position - center;
+        for &i in &members {
+            states[i].position += offset;
+            states[i].velocity -= center_velocity;
+        }
+    }
+
     states
 }
 
+/// Barycenter (center of mass) position and velocity of (position, velocity, mass) bodies
+/// Returns None if the total mass is zero
+pub fn barycenter(bodies: &[(Vec2, Vec2, f32)]) -> Option<(Vec2, Vec2)> {
+    let total_mass: f32 = bodies.iter().map(|(_, _, mass)| mass).sum();
+    if total_mass <= 0.0 {
+        return None;
+    }
+
+    let position: Vec2 = bodies.iter().map(|(pos, _, mass)| *pos * *mass).sum();
+    let momentum: Vec2 = bodies.iter().map(|(_, vel, mass)| *vel * *mass).sum();
+    Some((position / total_mass, momentum / total_mass))
+}
+
+/// Mass of bodies (with everything orbiting them) that share a parent and orbit closer in than `distance`
+fn enclosed_sibling_mass(map: &MapConfiguration, parent_index: usize, body_index: usize, distance: f32) -> f32 {
+    let bodies = &map.celestial_bodies;
+    let inner_siblings: Vec<usize> = (0..bodies.len())
+        .filter(|&j| {
+            j != body_index
+                && bodies[j].orbital_parent_index == Some(parent_index)
+                && bodies[j].orbital_distance.is_some_and(|d| d < distance)
+        })
+        .collect();
+
+    (0..bodies.len())
+        .filter(|&k| inner_siblings.iter().any(|&sibling| is_in_subtree(map, k, sibling)))
+        .map(|k| bodies[k].mass)
+        .sum()
+}
+
+/// True if `index` is `ancestor` or orbits it (directly or through other bodies)
+fn is_in_subtree(map: &MapConfiguration, index: usize, ancestor: usize) -> bool {
+    let mut current = index;
+    for _ in 0..=map.celestial_bodies.len() {
+        if current == ancestor {
+            return true;
+        }
+        match map.celestial_bodies[current].orbital_parent_index {
+            Some(parent) if parent < map.celestial_bodies.len() => current = parent,
+            _ => return false,
+        }
+    }
+    false
+}
+
+/// Follow orbital parents up to the body at the top of the hierarchy
+fn root_of(map: &MapConfiguration, index: usize) -> usize {
+    let mut current = index;
+    // Bounded walk so a malformed map with a parent cycle can't hang loading
+    for _ in 0..map.celestial_bodies.len() {
+        match map.celestial_bodies[current].orbital_parent_index {
+            Some(parent) if parent < map.celestial_bodies.len() => current = parent,
+            _ => break,
+        }
+    }
+    current
+}
+
 /// Convert polar coordinates (distance, angle) to Cartesian (x, y)
 fn polar_to_cartesian(parent_pos: Vec2, distance: f32, angle: f32) -> Vec2 {
     Vec2::new(
@@ -101,3 +199,92 @@ fn calculate_orbital_velocity(
 
     direction * speed
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map_config::{CelestialBodyConfig, MapTunables};
+    use crate::physics::GravityModel;
+
+    fn body(name: &str, mass: f32, parent: Option<usize>, distance: Option<f32>, pinned: bool) -> CelestialBodyConfig {
+        CelestialBodyConfig {
+            name: name.to_string(),
+            mass,
+            radius: 100.0,
+            color: WHITE,
+            orbital_parent_index: parent,
+            orbital_distance: distance,
+            orbital_period: None,
+            initial_angle: 0.0,
+            is_pinned: pinned,
+            terrain: None,
+        }
+    }
+
+    fn map(bodies: Vec<CelestialBodyConfig>) -> MapConfiguration {
+        MapConfiguration {
+            name: "test".to_string(),
+            description: String::new(),
+            celestial_bodies: bodies,
+            player_spawn_body_index: 0,
+            central_body_index: Some(0),
+            tidal_breakup: false,
+            gravity_model: GravityModel::Realistic,
+            tunables: MapTunables::default(),
+            sample_sites: Vec::new(),
+            fuel_deposits: Vec::new(),
+            landing_zones: Vec::new(),
+            rings: Vec::new(),
+            arena: None,
+        }
+    }
+
+    #[test]
+    fn test_binary_pair_orbits_barycenter() {
+        let binary = map(vec![
+            body("A", 3000.0, None, None, false),
+            body("B", 1000.0, Some(0), Some(400.0), false),
+        ]);
+        let states = calculate_initial_states(&binary, 100.0);
+
+        let bodies: Vec<(Vec2, Vec2, f32)> = states
+            .iter()
+            .zip(&binary.celestial_bodies)
+            .map(|(state, config)| (state.position, state.velocity, config.mass))
+            .collect();
+        let (center, center_velocity) = barycenter(&bodies).unwrap();
+        assert!(center.length() < 1e-3);
+        assert!(center_velocity.length() < 1e-3);
+
+        // Separation unchanged, relative speed is the two-body circular speed
+        assert!(((states[1].position - states[0].position).length() - 400.0).abs() < 1e-3);
+        let relative_speed = (states[1].velocity - states[0].velocity).length();
+        assert!((relative_speed - (100.0f32 * 4000.0 / 400.0).sqrt()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_pinned_parent_keeps_simple_orbit() {
+        let system = map(vec![
+            body("Earth", 4000.0, None, None, true),
+            body("Moon", 100.0, Some(0), Some(400.0), false),
+        ]);
+        let states = calculate_initial_states(&system, 100.0);
+
+        assert_eq!(states[0].position, Vec2::ZERO);
+        assert_eq!(states[0].velocity, Vec2::ZERO);
+        assert!((states[1].velocity.length() - (100.0f32 * 4000.0 / 400.0).sqrt()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_circumbinary_orbit_includes_inner_star() {
+        let system = map(vec![
+            body("A", 3000.0, None, None, true),
+            body("B", 1000.0, Some(0), Some(400.0), false),
+            body("Planet", 10.0, Some(0), Some(2000.0), false),
+        ]);
+        let states = calculate_initial_states(&system, 100.0);
+
+        let expected = (100.0f32 * (3000.0 + 1000.0) / 2000.0).sqrt();
+        assert!((states[2].velocity.length() - expected).abs() < 1e-3);
+    }
+}
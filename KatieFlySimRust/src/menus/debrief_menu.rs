@@ -0,0 +1,124 @@
+// Debrief Menu - Post-flight summary shown when a single player session returns to the menu
+// Delta-v spent against the theoretical minimum for each orbit reached, fuel efficiency, peak g,
+// landings and kills from the flight log, with R to fly the same map (or challenge/scenario) again
+
+use macroquad::prelude::*;
+
+use crate::systems::Debrief;
+use crate::ui::Button;
+
+/// Landing grades listed before the rest are summed up as "+N more"
+const MAX_LANDINGS_SHOWN: usize = 8;
+
+/// Result from debrief interaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebriefMenuResult {
+    None,
+    Retry,
+    Continue,
+}
+
+pub struct DebriefMenu {
+    debrief: Debrief,
+    retry_button: Button,
+    continue_button: Button,
+    window_size: Vec2,
+}
+
+impl DebriefMenu {
+    pub fn new(window_size: Vec2, debrief: Debrief) -> Self {
+        let retry_button = Button::new(
+            Vec2::new(window_size.x / 2.0 - 220.0, window_size.y - 100.0),
+            Vec2::new(200.0, 50.0),
+            "Retry (R)",
+            Color::from_rgba(50, 120, 100, 255),
+        );
+        let continue_button = Button::new(
+            Vec2::new(window_size.x / 2.0 + 20.0, window_size.y - 100.0),
+            Vec2::new(200.0, 50.0),
+            "Continue (Enter)",
+            Color::from_rgba(100, 100, 100, 255),
+        );
+
+        DebriefMenu { debrief, retry_button, continue_button, window_size }
+    }
+
+    pub fn update(&mut self) -> DebriefMenuResult {
+        let mouse_pressed = is_mouse_button_down(MouseButton::Left);
+
+        if self.retry_button.update(mouse_pressed) || is_key_pressed(KeyCode::R) {
+            return DebriefMenuResult::Retry;
+        }
+        if self.continue_button.update(mouse_pressed) || is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Escape) {
+            return DebriefMenuResult::Continue;
+        }
+        DebriefMenuResult::None
+    }
+
+    pub fn draw(&self) {
+        let debrief = &self.debrief;
+        let center_x = self.window_size.x / 2.0;
+        let title = "Flight Debrief";
+        let dims = measure_text(title, None, 48, 1.0);
+        draw_text(title, center_x - dims.width / 2.0, 110.0, 48.0, WHITE);
+        let subtitle = format!("{}  -  {:.0}s flown  -  score {}", debrief.map_name, debrief.flight_time, debrief.score());
+        let dims = measure_text(&subtitle, None, 22, 1.0);
+        draw_text(&subtitle, center_x - dims.width / 2.0, 145.0, 22.0, GOLD);
+
+        let left = center_x - 300.0;
+        let mut y = 200.0;
+        let mut row = |label: &str, value: String, color: Color| {
+            draw_text(label, left, y, 24.0, LIGHTGRAY);
+            draw_text(&value, left + 300.0, y, 24.0, color);
+            y += 34.0;
+        };
+
+        row("Delta-v spent", format!("{:.0} m/s", debrief.delta_v_spent), WHITE);
+        row("Fuel burned", format!("{:.1}", debrief.fuel_burned), WHITE);
+        match debrief.delta_v_per_fuel() {
+            Some(per_fuel) => row("Fuel efficiency", format!("{:.1} m/s per unit", per_fuel), WHITE),
+            None => row("Fuel efficiency", "-".to_string(), GRAY),
+        }
+        row("Max acceleration", format!("{:.1} g", debrief.max_g), WHITE);
+        row("Kills", debrief.kills.to_string(), if debrief.kills > 0 { ORANGE } else { WHITE });
+
+        let landings = if debrief.landings.is_empty() {
+            "none".to_string()
+        } else {
+            let mut grades: Vec<&str> = debrief.landings.iter().take(MAX_LANDINGS_SHOWN).map(|grade| grade.label()).collect();
+            let more = debrief.landings.len().saturating_sub(MAX_LANDINGS_SHOWN);
+            let extra = format!("+{} more", more);
+            if more > 0 {
+                grades.push(&extra);
+            }
+            format!("{} ({})", debrief.landings.len(), grades.join(", "))
+        };
+        row("Landings", landings, WHITE);
+
+        // Orbits: what the climb cost against the ideal single burn from the surface
+        y += 16.0;
+        draw_text("Orbits reached", left, y, 26.0, SKYBLUE);
+        y += 34.0;
+        if debrief.orbits.is_empty() {
+            draw_text("None - lap a body after launch to score an orbit", left, y, 20.0, GRAY);
+            y += 30.0;
+        }
+        for orbit in &debrief.orbits {
+            let line = format!(
+                "{} at {:.0}: {:.0} m/s spent, {:.0} m/s minimum",
+                orbit.body_name, orbit.altitude, orbit.delta_v_spent, orbit.delta_v_minimum
+            );
+            draw_text(&line, left, y, 20.0, WHITE);
+            let efficiency = orbit.efficiency();
+            let color = if efficiency > 0.8 { GREEN } else if efficiency > 0.5 { YELLOW } else { ORANGE };
+            draw_text(&format!("{:.0}%", efficiency * 100.0), left + 540.0, y, 20.0, color);
+            y += 28.0;
+        }
+        if let Some(efficiency) = debrief.orbit_efficiency().filter(|_| debrief.orbits.len() > 1) {
+            draw_text(&format!("Overall orbit efficiency {:.0}%", efficiency * 100.0), left, y + 6.0, 20.0, LIGHTGRAY);
+        }
+
+        self.retry_button.draw();
+        self.continue_button.draw();
+    }
+}
@@ -0,0 +1,187 @@
+// Leaderboard Menu - Rankings of every local profile, one board per scenario or daily challenge
+// Reached from the main menu; Export writes the shown board to a JSON file to share
+
+use macroquad::prelude::*;
+
+use crate::save_system::leaderboard::EXPORT_PATH;
+use crate::save_system::{local_profiles, BoardInfo, Leaderboard};
+use crate::ui::Button;
+
+/// Only this many places are shown per board
+const MAX_ROWS: usize = 10;
+
+/// Result from leaderboard menu interaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LeaderboardMenuResult {
+    None,
+    Back,
+}
+
+/// Pages through the boards of the shared leaderboard
+pub struct LeaderboardMenu {
+    title_text: String,
+    title_position: Vec2,
+    title_font_size: f32,
+    back_button: Button,
+    previous_button: Button,
+    next_button: Button,
+    export_button: Button,
+    leaderboard: Leaderboard,
+    boards: Vec<BoardInfo>,
+    selected: usize,
+    status_message: Option<(String, Color)>, // Where the last export went
+    window_size: Vec2,
+}
+
+impl LeaderboardMenu {
+    pub fn new(window_size: Vec2) -> Self {
+        // Title
+        let title_text = "Leaderboard".to_string();
+        let title_font_size = 48.0;
+
+        // Calculate title position (centered)
+        let text_dims = measure_text(&title_text, None, title_font_size as u16, 1.0);
+        let title_position = Vec2::new(
+            window_size.x / 2.0 - text_dims.width / 2.0,
+            80.0 + text_dims.height,
+        );
+
+        // Back button
+        let back_button = Button::new(
+            Vec2::new(50.0, window_size.y - 80.0),
+            Vec2::new(150.0, 50.0),
+            "Back",
+            Color::from_rgba(100, 100, 100, 255),
+        );
+
+        // Board paging, either side of the board name
+        let previous_button = Button::new(
+            Vec2::new(window_size.x / 2.0 - 350.0, 160.0),
+            Vec2::new(60.0, 50.0),
+            "<",
+            Color::from_rgba(70, 70, 90, 255),
+        );
+        let next_button = Button::new(
+            Vec2::new(window_size.x / 2.0 + 290.0, 160.0),
+            Vec2::new(60.0, 50.0),
+            ">",
+            Color::from_rgba(70, 70, 90, 255),
+        );
+
+        // Export button (bottom right)
+        let export_button = Button::new(
+            Vec2::new(window_size.x - 250.0, window_size.y - 80.0),
+            Vec2::new(200.0, 50.0),
+            "Export Board",
+            Color::from_rgba(50, 120, 100, 255),
+        );
+
+        LeaderboardMenu {
+            title_text,
+            title_position,
+            title_font_size,
+            back_button,
+            previous_button,
+            next_button,
+            export_button,
+            leaderboard: Leaderboard::default(),
+            boards: Vec::new(),
+            selected: 0,
+            status_message: None,
+            window_size,
+        }
+    }
+
+    /// Re-read the leaderboard file (other profiles may have set records since)
+    pub fn refresh(&mut self) {
+        self.leaderboard = Leaderboard::load();
+        self.boards = self.leaderboard.boards();
+        self.selected = self.selected.min(self.boards.len().saturating_sub(1));
+        self.status_message = None;
+    }
+
+    /// Update menu and handle input
+    pub fn update(&mut self) -> LeaderboardMenuResult {
+        let mouse_pressed = is_mouse_button_down(MouseButton::Left);
+
+        if self.back_button.update(mouse_pressed) {
+            return LeaderboardMenuResult::Back;
+        }
+
+        if !self.boards.is_empty() {
+            let count = self.boards.len();
+            if self.previous_button.update(mouse_pressed) || is_key_pressed(KeyCode::Left) {
+                self.selected = (self.selected + count - 1) % count;
+                self.status_message = None;
+            }
+            if self.next_button.update(mouse_pressed) || is_key_pressed(KeyCode::Right) {
+                self.selected = (self.selected + 1) % count;
+                self.status_message = None;
+            }
+            if self.export_button.update(mouse_pressed) {
+                let board = &self.boards[self.selected];
+                self.status_message = Some(match self.leaderboard.export_board(board.kind, &board.board, EXPORT_PATH) {
+                    Ok(()) => (format!("Exported to {}", EXPORT_PATH), GREEN),
+                    Err(e) => {
+                        log::error!("Failed to export leaderboard: {}", e);
+                        (format!("Export failed: {}", e), RED)
+                    }
+                });
+            }
+        }
+
+        LeaderboardMenuResult::None
+    }
+
+    /// Draw the menu
+    pub fn draw(&self) {
+        draw_text(
+            &self.title_text,
+            self.title_position.x,
+            self.title_position.y,
+            self.title_font_size,
+            WHITE,
+        );
+
+        let center_x = self.window_size.x / 2.0;
+        let Some(board) = self.boards.get(self.selected) else {
+            let text = "No records yet - finish a scenario or the daily challenge to get on the board";
+            let dims = measure_text(text, None, 22, 1.0);
+            draw_text(text, center_x - dims.width / 2.0, 220.0, 22.0, LIGHTGRAY);
+            self.back_button.draw();
+            return;
+        };
+
+        self.previous_button.draw();
+        self.next_button.draw();
+        let heading = format!("{}  ({}/{})", board.board, self.selected + 1, self.boards.len());
+        let dims = measure_text(&heading, None, 28, 1.0);
+        draw_text(&heading, center_x - dims.width / 2.0, 190.0, 28.0, WHITE);
+        let map = format!("Map: {}", board.map);
+        let dims = measure_text(&map, None, 18, 1.0);
+        draw_text(&map, center_x - dims.width / 2.0, 212.0, 18.0, GRAY);
+
+        let active = local_profiles::active();
+        let left = center_x - 280.0;
+        for (i, entry) in self.leaderboard.rankings(board.kind, &board.board).iter().take(MAX_ROWS).enumerate() {
+            let y = 260.0 + i as f32 * 34.0;
+            let color = match i {
+                0 => GOLD,
+                _ if entry.profile == active => SKYBLUE,
+                _ => WHITE,
+            };
+            draw_text(&format!("{:>2}.", i + 1), left, y, 24.0, color);
+            draw_text(&entry.profile, left + 50.0, y, 24.0, color);
+            draw_text(&board.kind.format(entry.value), left + 300.0, y, 24.0, color);
+            draw_text(&entry.date, left + 440.0, y, 20.0, GRAY);
+        }
+
+        if let Some((message, color)) = &self.status_message {
+            let dims = measure_text(message, None, 20, 1.0);
+            draw_text(message, center_x - dims.width / 2.0, self.window_size.y - 110.0, 20.0, *color);
+        }
+
+        self.export_button.draw();
+        self.back_button.draw();
+    }
+}
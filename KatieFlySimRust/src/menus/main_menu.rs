@@ -4,6 +4,7 @@
 use macroquad::prelude::*;
 
 use crate::game_state::GameMode;
+use crate::save_system::local_profiles;
 use crate::ui::Button;
 
 /// Main menu with game mode selection
@@ -13,8 +14,12 @@ pub struct MainMenu {
     title_font_size: f32,
     single_player_button: Button,
     multiplayer_button: Button,
+    bug_report_button: Button,
     quit_button: Button,
+    profile_button: Button, // Shows the active local profile
+    leaderboard_button: Button,
     selected_mode: GameMode,
+    status_message: Option<(String, Color)>, // Result of the last bug report
 }
 
 impl MainMenu {
@@ -58,25 +63,56 @@ impl MainMenu {
             Color::from_rgba(50, 120, 100, 255),
         );
 
+        // Bug report button
+        let bug_report_button = Button::new(
+            Vec2::new(
+                window_size.x / 2.0 - button_width / 2.0,
+                start_y + button_spacing * 2.0,
+            ),
+            Vec2::new(button_width, button_height),
+            "Create Bug Report",
+            Color::from_rgba(90, 90, 90, 255),
+        );
+
         // Quit button
         let quit_button = Button::new(
             Vec2::new(
                 window_size.x / 2.0 - button_width / 2.0,
-                start_y + button_spacing * 2.0,
+                start_y + button_spacing * 3.0,
             ),
             Vec2::new(button_width, button_height),
             "Fine, Leave then...",
             Color::from_rgba(120, 50, 50, 255),
         );
 
+        // Profile button (top right)
+        let profile_button = Button::new(
+            Vec2::new(window_size.x - 330.0, 30.0),
+            Vec2::new(300.0, 50.0),
+            &format!("Profile: {}", local_profiles::active()),
+            Color::from_rgba(80, 60, 120, 255),
+        );
+
+        // Leaderboard button (under the profile button)
+        let leaderboard_button = Button::new(
+            Vec2::new(window_size.x - 330.0, 90.0),
+            Vec2::new(300.0, 50.0),
+            "Leaderboard",
+            Color::from_rgba(140, 110, 40, 255),
+        );
+
         MainMenu {
             title_text,
             title_position,
             title_font_size,
             single_player_button,
             multiplayer_button,
+            bug_report_button,
             quit_button,
+            profile_button,
+            leaderboard_button,
             selected_mode: GameMode::None,
+            status_message: None,
         }
     }
 
@@ -95,6 +131,21 @@ impl MainMenu {
             return GameMode::Multiplayer;
         }
 
+        if self.bug_report_button.update(mouse_pressed) {
+            self.selected_mode = GameMode::BugReport;
+            return GameMode::BugReport;
+        }
+
+        if self.profile_button.update(mouse_pressed) {
+            self.selected_mode = GameMode::Profiles;
+            return GameMode::Profiles;
+        }
+
+        if self.leaderboard_button.update(mouse_pressed) {
+            self.selected_mode = GameMode::Leaderboard;
+            return GameMode::Leaderboard;
+        }
+
         if self.quit_button.update(mouse_pressed) {
             self.selected_mode = GameMode::Quit;
             return GameMode::Quit;
@@ -117,7 +168,26 @@ impl MainMenu {
         // Draw buttons
         self.single_player_button.draw();
         self.multiplayer_button.draw();
+        self.bug_report_button.draw();
         self.quit_button.draw();
+        self.profile_button.draw();
+        self.leaderboard_button.draw();
+
+        if let Some((message, color)) = &self.status_message {
+            let dims = measure_text(message, None, 20, 1.0);
+            let y = self.quit_button.position().y + self.quit_button.size().y + 40.0;
+            draw_text(message, screen_width() / 2.0 - dims.width / 2.0, y, 20.0, *color);
+        }
+    }
+
+    /// Show a line under the buttons (e.g. where a bug report was written)
+    pub fn set_status(&mut self, message: String, color: Color) {
+        self.status_message = Some((message, color));
+    }
+
+    /// Relabel the profile button after switching profiles
+    pub fn refresh_profile(&mut self) {
+        self.profile_button.set_text(&format!("Profile: {}", local_profiles::active()));
     }
 
     /// Get the selected mode
@@ -1,16 +1,26 @@
 // Map Selection Menu - Choose which map to play
 // Allows player to select between different planetary configurations
+// Custom maps from the maps/ folder (e.g. saved by KatieMapMaker) follow the built-in ones; the list scrolls when they don't fit
 
 use macroquad::prelude::*;
 
 use crate::map_config::MapConfiguration;
 use crate::ui::Button;
 
+const BUTTON_WIDTH: f32 = 500.0;
+const BUTTON_HEIGHT: f32 = 70.0;
+const BUTTON_SPACING: f32 = 105.0; // Leaves room for the description under each button
+const LIST_TOP: f32 = 180.0;
+/// Rejected map files listed under the maps, at most (the rest are only in the log)
+const MAX_ERRORS_SHOWN: usize = 2;
+
 /// Result from map selection menu interaction
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MapSelectionResult {
     None,
     MapSelected(String), // Returns the map name
+    DailyChallenge,
+    Scenarios,
     Back,
 }
 
@@ -20,9 +30,15 @@ pub struct MapSelectionMenu {
     title_position: Vec2,
     title_font_size: f32,
     back_button: Button,
+    daily_button: Button,
+    scenarios_button: Button,
     map_buttons: Vec<Button>,
     map_names: Vec<String>,
     map_descriptions: Vec<String>,
+    custom_maps: Vec<bool>,
+    host_mode: bool,           // Picking the map for a hosted game (no daily challenge or scenarios)
+    scroll: usize,             // Index of the first map shown
+    map_errors: Vec<String>,   // Custom map files that failed to load, and why
     window_size: Vec2,
 }
 
@@ -47,52 +63,103 @@ impl MapSelectionMenu {
             Color::from_rgba(100, 100, 100, 255),
         );
 
+        // Today's seeded challenge (bottom right, opposite Back)
+        let daily_button = Button::new(
+            Vec2::new(window_size.x - 300.0, window_size.y - 80.0),
+            Vec2::new(250.0, 50.0),
+            "Daily Challenge",
+            Color::from_rgba(150, 110, 30, 255),
+        );
+
+        // Community challenge files, next to the daily challenge
+        let scenarios_button = Button::new(
+            Vec2::new(window_size.x - 560.0, window_size.y - 80.0),
+            Vec2::new(230.0, 50.0),
+            "Scenarios",
+            Color::from_rgba(120, 80, 150, 255),
+        );
+
         let mut menu = MapSelectionMenu {
             title_text,
             title_position,
             title_font_size,
             back_button,
+            daily_button,
+            scenarios_button,
             map_buttons: Vec::new(),
             map_names: Vec::new(),
             map_descriptions: Vec::new(),
+            custom_maps: Vec::new(),
+            host_mode: false,
+            scroll: 0,
+            map_errors: Vec::new(),
             window_size,
         };
 
-        // Initialize map buttons
-        menu.load_maps();
+        // Built-in maps until the asset manager has scanned the maps folder
+        menu.set_maps(&MapConfiguration::built_in_maps());
 
         menu
     }
 
-    /// Load available maps and create buttons
-    fn load_maps(&mut self) {
-        // Clear existing buttons
-        self.map_buttons.clear();
-        self.map_names.clear();
-        self.map_descriptions.clear();
+    /// Rebuild the map list from the loaded maps (built-in maps first, then custom ones)
+    pub fn set_maps(&mut self, maps: &[MapConfiguration]) {
+        let built_in = MapConfiguration::built_in_maps().len();
+        self.map_names = maps.iter().map(|map| map.name.clone()).collect();
+        self.map_descriptions = maps.iter().map(|map| map.description.clone()).collect();
+        self.custom_maps = (0..maps.len()).map(|i| i >= built_in).collect();
+        self.scroll = 0;
+        self.layout_buttons();
+    }
 
-        // Get all available maps
-        let maps = MapConfiguration::all_maps();
+    /// Pick a map for a hosted multiplayer game rather than single player
+    pub fn set_host_mode(&mut self, host_mode: bool) {
+        self.host_mode = host_mode;
+        self.title_text = if host_mode { "Select Map to Host".to_string() } else { "Select Map".to_string() };
+        let text_dims = measure_text(&self.title_text, None, self.title_font_size as u16, 1.0);
+        self.title_position.x = self.window_size.x / 2.0 - text_dims.width / 2.0;
+    }
 
-        let button_width = 500.0;
-        let button_height = 80.0;
-        let button_spacing = 120.0;
-        let start_y = 180.0;
+    /// Show which custom map files were skipped and why
+    pub fn set_map_errors(&mut self, errors: &[String]) {
+        self.map_errors = errors.to_vec();
+    }
+
+    /// How many maps fit between the title and the bottom buttons
+    fn visible_rows(&self) -> usize {
+        let bottom = self.window_size.y - 200.0;
+        (((bottom - LIST_TOP) / BUTTON_SPACING).floor() as usize).max(1)
+    }
 
-        for (i, map) in maps.iter().enumerate() {
-            let button = Button::new(
+    /// Buttons for the maps currently scrolled into view
+    fn layout_buttons(&mut self) {
+        self.map_buttons.clear();
+        let end = (self.scroll + self.visible_rows()).min(self.map_names.len());
+        for (row, i) in (self.scroll..end).enumerate() {
+            let (label, color) = if self.custom_maps[i] {
+                (format!("{} (custom)", self.map_names[i]), Color::from_rgba(60, 120, 90, 255))
+            } else {
+                (self.map_names[i].clone(), Color::from_rgba(50, 100, 150, 255))
+            };
+            self.map_buttons.push(Button::new(
                 Vec2::new(
-                    self.window_size.x / 2.0 - button_width / 2.0,
-                    start_y + (i as f32 * button_spacing),
+                    self.window_size.x / 2.0 - BUTTON_WIDTH / 2.0,
+                    LIST_TOP + row as f32 * BUTTON_SPACING,
                 ),
-                Vec2::new(button_width, button_height),
-                &map.name,
-                Color::from_rgba(50, 100, 150, 255),
-            );
+                Vec2::new(BUTTON_WIDTH, BUTTON_HEIGHT),
+                &label,
+                color,
+            ));
+        }
+    }
 
-            self.map_buttons.push(button);
-            self.map_names.push(map.name.clone());
-            self.map_descriptions.push(map.description.clone());
+    /// Scroll the list by `rows`, clamped so the last map stays at the bottom
+    fn scroll_by(&mut self, rows: i32) {
+        let max_scroll = self.map_names.len().saturating_sub(self.visible_rows());
+        let scroll = (self.scroll as i32 + rows).clamp(0, max_scroll as i32) as usize;
+        if scroll != self.scroll {
+            self.scroll = scroll;
+            self.layout_buttons();
         }
     }
 
@@ -105,10 +172,29 @@ impl MapSelectionMenu {
             return MapSelectionResult::Back;
         }
 
+        if !self.host_mode && self.daily_button.update(mouse_pressed) {
+            return MapSelectionResult::DailyChallenge;
+        }
+
+        if !self.host_mode && self.scenarios_button.update(mouse_pressed) {
+            return MapSelectionResult::Scenarios;
+        }
+
+        let (_, wheel) = mouse_wheel();
+        if wheel != 0.0 {
+            self.scroll_by(if wheel > 0.0 { -1 } else { 1 });
+        }
+        if is_key_pressed(KeyCode::Up) || is_key_pressed(KeyCode::PageUp) {
+            self.scroll_by(-1);
+        }
+        if is_key_pressed(KeyCode::Down) || is_key_pressed(KeyCode::PageDown) {
+            self.scroll_by(1);
+        }
+
         // Check map buttons
-        for (i, button) in self.map_buttons.iter_mut().enumerate() {
+        for (row, button) in self.map_buttons.iter_mut().enumerate() {
             if button.update(mouse_pressed) {
-                return MapSelectionResult::MapSelected(self.map_names[i].clone());
+                return MapSelectionResult::MapSelected(self.map_names[self.scroll + row].clone());
             }
         }
 
@@ -129,14 +215,14 @@ impl MapSelectionMenu {
         );
 
         // Draw map buttons
-        for (i, button) in self.map_buttons.iter().enumerate() {
+        for (row, button) in self.map_buttons.iter().enumerate() {
             button.draw();
 
             // Draw description below button
             let desc_x = button.position().x + 10.0;
             let desc_y = button.position().y + button.size().y + 20.0;
             draw_text(
-                &self.map_descriptions[i],
+                &self.map_descriptions[self.scroll + row],
                 desc_x,
                 desc_y,
                 20.0,
@@ -144,11 +230,37 @@ impl MapSelectionMenu {
             );
         }
 
+        // Scroll hints when maps are off the list
+        let hint_x = self.window_size.x / 2.0 + BUTTON_WIDTH / 2.0 + 20.0;
+        if self.scroll > 0 {
+            draw_text(&format!("^ {} more", self.scroll), hint_x, LIST_TOP + 20.0, 20.0, LIGHTGRAY);
+        }
+        let below = self.map_names.len().saturating_sub(self.scroll + self.map_buttons.len());
+        if below > 0 {
+            let y = LIST_TOP + (self.map_buttons.len() as f32 - 1.0) * BUTTON_SPACING + BUTTON_HEIGHT;
+            draw_text(&format!("v {} more (scroll)", below), hint_x, y, 20.0, LIGHTGRAY);
+        }
+
+        // Custom map files that didn't load
+        let hidden = self.map_errors.len().saturating_sub(MAX_ERRORS_SHOWN);
+        for (i, error) in self.map_errors.iter().take(MAX_ERRORS_SHOWN).enumerate() {
+            let mut text = format!("Skipped {}", error);
+            if hidden > 0 && i + 1 == MAX_ERRORS_SHOWN {
+                text.push_str(&format!(" (+{} more in the log)", hidden));
+            }
+            let y = self.window_size.y - 185.0 + i as f32 * 18.0;
+            draw_text(&text, 50.0, y, 18.0, ORANGE);
+        }
+
         // Draw back button
         self.back_button.draw();
+        if !self.host_mode {
+            self.daily_button.draw();
+            self.scenarios_button.draw();
+        }
 
         // Draw instructions
-        let instructions = "Click a map to start a new game";
+        let instructions = if self.host_mode { "Click a map to start hosting - clients are sent the map when they join" } else { "Click a map to start a new game" };
         let inst_size = 24.0;
         let inst_dims = measure_text(instructions, None, inst_size as u16, 1.0);
         draw_text(
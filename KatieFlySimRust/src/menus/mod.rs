@@ -8,6 +8,11 @@ pub mod online_multiplayer_menu;
 pub mod online_host_menu;
 pub mod multiplayer_saves_menu;
 pub mod online_join_menu;
+pub mod scenarios_menu;
+pub mod replays_menu;
+pub mod profile_menu;
+pub mod leaderboard_menu;
+pub mod debrief_menu;
 
 pub use main_menu::MainMenu;
 pub use saves_menu::{SavesMenu, SavesMenuResult};
@@ -17,3 +22,8 @@ pub use online_multiplayer_menu::{OnlineMultiplayerMenu, OnlineMultiplayerMenuRe
 pub use online_host_menu::{OnlineHostMenu, OnlineHostMenuResult};
 pub use multiplayer_saves_menu::{MultiplayerSavesMenu, MultiplayerSavesMenuResult};
 pub use online_join_menu::{OnlineJoinMenu, OnlineJoinMenuResult};
+pub use scenarios_menu::{ScenariosMenu, ScenariosMenuResult};
+pub use replays_menu::{ReplaysMenu, ReplaysMenuResult};
+pub use profile_menu::{ProfileMenu, ProfileMenuResult};
+pub use leaderboard_menu::{LeaderboardMenu, LeaderboardMenuResult};
+pub use debrief_menu::{DebriefMenu, DebriefMenuResult};
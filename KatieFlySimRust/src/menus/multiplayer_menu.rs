@@ -3,13 +3,15 @@
 
 use macroquad::prelude::*;
 use crate::ui::Button;
+use crate::game_modes::split_screen::{MIN_PLAYERS, MAX_PLAYERS};
 
 /// Result from multiplayer menu interaction
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MultiplayerMenuResult {
     None,
-    SplitScreen,
+    SplitScreen(usize), // Local player count
     OnlineMultiplayer,
+    Replays,
     Back,
 }
 
@@ -19,7 +21,10 @@ pub struct MultiplayerMenu {
     title_position: Vec2,
     title_font_size: f32,
     split_screen_button: Button,
+    player_count_button: Button, // Cycles the split-screen player count
+    split_screen_players: usize,
     online_button: Button,
+    replays_button: Button,
     back_button: Button,
     window_size: Vec2,
 }
@@ -54,6 +59,17 @@ impl MultiplayerMenu {
             Color::from_rgba(50, 150, 50, 255),
         );
 
+        // Player count, beside the split-screen button
+        let player_count_button = Button::new(
+            Vec2::new(
+                window_size.x / 2.0 + button_width / 2.0 + 10.0,
+                start_y,
+            ),
+            Vec2::new(140.0, button_height),
+            &Self::player_count_label(MIN_PLAYERS),
+            Color::from_rgba(40, 110, 40, 255),
+        );
+
         // Online multiplayer button
         let online_button = Button::new(
             Vec2::new(
@@ -65,6 +81,17 @@ impl MultiplayerMenu {
             Color::from_rgba(50, 100, 200, 255),
         );
 
+        // Match replays button
+        let replays_button = Button::new(
+            Vec2::new(
+                window_size.x / 2.0 - button_width / 2.0,
+                start_y + button_spacing * 2.0,
+            ),
+            Vec2::new(button_width, button_height),
+            "Match Replays",
+            Color::from_rgba(150, 100, 50, 255),
+        );
+
         // Back button
         let back_button = Button::new(
             Vec2::new(50.0, window_size.y - 80.0),
@@ -78,19 +105,36 @@ impl MultiplayerMenu {
             title_position,
             title_font_size,
             split_screen_button,
+            player_count_button,
+            split_screen_players: MIN_PLAYERS,
             online_button,
+            replays_button,
             back_button,
             window_size,
         }
     }
 
+    fn player_count_label(players: usize) -> String {
+        format!("{} Players", players)
+    }
+
     /// Update menu and handle input
     pub fn update(&mut self) -> MultiplayerMenuResult {
         let mouse_pressed = is_mouse_button_down(MouseButton::Left);
 
         // Check split-screen button
         if self.split_screen_button.update(mouse_pressed) {
-            return MultiplayerMenuResult::SplitScreen;
+            return MultiplayerMenuResult::SplitScreen(self.split_screen_players);
+        }
+
+        // Cycle 2 -> 3 -> 4 -> 2 players
+        if self.player_count_button.update(mouse_pressed) {
+            self.split_screen_players = if self.split_screen_players >= MAX_PLAYERS {
+                MIN_PLAYERS
+            } else {
+                self.split_screen_players + 1
+            };
+            self.player_count_button.set_text(&Self::player_count_label(self.split_screen_players));
         }
 
         // Check online button
@@ -98,6 +142,11 @@ impl MultiplayerMenu {
             return MultiplayerMenuResult::OnlineMultiplayer;
         }
 
+        // Check replays button
+        if self.replays_button.update(mouse_pressed) {
+            return MultiplayerMenuResult::Replays;
+        }
+
         // Check back button
         if self.back_button.update(mouse_pressed) {
             return MultiplayerMenuResult::Back;
@@ -130,15 +179,19 @@ impl MultiplayerMenu {
 
         // Draw buttons
         self.split_screen_button.draw();
+        self.player_count_button.draw();
         self.online_button.draw();
+        self.replays_button.draw();
         self.back_button.draw();
 
         // Draw mode descriptions
-        let split_desc = "Play with a friend on the same device";
+        let split_desc = "Play with up to three friends on the same device";
         let online_desc = "Host or join a game over the network";
+        let replays_desc = "Re-watch online matches you hosted";
 
         let split_desc_dims = measure_text(split_desc, None, 16, 1.0);
         let online_desc_dims = measure_text(online_desc, None, 16, 1.0);
+        let replays_desc_dims = measure_text(replays_desc, None, 16, 1.0);
 
         draw_text(
             split_desc,
@@ -155,5 +208,13 @@ impl MultiplayerMenu {
             16.0,
             LIGHTGRAY,
         );
+
+        draw_text(
+            replays_desc,
+            self.window_size.x / 2.0 - replays_desc_dims.width / 2.0,
+            430.0,
+            16.0,
+            LIGHTGRAY,
+        );
     }
 }
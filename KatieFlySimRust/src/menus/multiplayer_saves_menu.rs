@@ -3,6 +3,7 @@
 
 use macroquad::prelude::*;
 
+use crate::save_system::local_profiles;
 use crate::ui::Button;
 
 /// Result from multiplayer saves menu interaction
@@ -113,15 +114,15 @@ impl MultiplayerSavesMenu {
     fn load_save_list(&self) -> Result<Vec<String>, std::io::Error> {
         use std::fs;
 
-        let saves_dir = "saves/multi";
+        let saves_dir = local_profiles::saves_path("multi");
 
         // Create saves/multi directory if it doesn't exist
-        fs::create_dir_all(saves_dir)?;
+        fs::create_dir_all(&saves_dir)?;
 
         let mut saves = Vec::new();
 
         // Read all .sav files in saves/multi directory
-        for entry in fs::read_dir(saves_dir)? {
+        for entry in fs::read_dir(&saves_dir)? {
             let entry = entry?;
             let path = entry.path();
 
@@ -205,6 +206,11 @@ impl MultiplayerSavesMenu {
         MultiplayerSavesMenuResult::None
     }
 
+    /// Show an error under the buttons (e.g. why a save couldn't be loaded)
+    pub fn set_error(&mut self, message: String) {
+        self.error_message = Some(message);
+    }
+
     /// Draw the menu
     pub fn draw(&self) {
         // Draw title
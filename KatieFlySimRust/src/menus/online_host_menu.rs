@@ -1,28 +1,45 @@
 // Online Host Menu - Configure and start hosting a multiplayer game
 
 use macroquad::prelude::*;
+use crate::game_constants::GameConstants;
+use crate::game_modes::HostRates;
+use crate::game_modes::multiplayer_host::{MAX_PLAYERS, MIN_PLAYERS};
+use crate::networking::{PortCheck, PortCheckReport, StepOutcome};
 use crate::ui::Button;
 
+/// Connection report panel width and wrap length
+const REPORT_WIDTH: f32 = 600.0;
+const REPORT_WRAP_CHARS: usize = 72;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum InputField {
     Name,
     Port,
+    PhysicsHz,
+    SnapshotHz,
+    MaxPlayers,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum OnlineHostMenuResult {
     None,
-    StartHost(String, u16), // Start hosting with player name and port
+    StartHost(String, u16, HostRates, u32), // Start hosting with player name, port, tick rates and player cap
     Back,
 }
 
 pub struct OnlineHostMenu {
     host_button: Button,
     back_button: Button,
+    test_button: Button,
     name_input: String,
     port_input: String,
+    physics_hz_input: String,
+    snapshot_hz_input: String,
+    max_players_input: String,
     active_field: InputField,
     error_message: Option<String>,
+    port_check: Option<PortCheck>,     // "Test my connection" in progress
+    port_report: Option<PortCheckReport>,
 }
 
 impl OnlineHostMenu {
@@ -30,7 +47,7 @@ impl OnlineHostMenu {
         let button_width = 300.0;
         let button_height = 50.0;
         let center_x = window_size.x / 2.0 - button_width / 2.0;
-        let start_y = window_size.y / 2.0 + 100.0;
+        let start_y = window_size.y / 2.0 + 260.0;
         let spacing = 70.0;
 
         Self {
@@ -46,13 +63,31 @@ impl OnlineHostMenu {
                 "Back",
                 Color::from_rgba(120, 50, 50, 255),
             ),
+            // Beside the port field
+            test_button: Button::new(
+                Vec2::new(window_size.x / 2.0 + 195.0, window_size.y / 2.0 - 40.0),
+                Vec2::new(260.0, button_height),
+                "Test my connection",
+                Color::from_rgba(50, 100, 150, 255),
+            ),
             name_input: "Player".to_string(), // Default name
             port_input: "7777".to_string(), // Default port
+            physics_hz_input: GameConstants::DEFAULT_PHYSICS_HZ.to_string(),
+            snapshot_hz_input: GameConstants::DEFAULT_SNAPSHOT_HZ.to_string(),
+            max_players_input: MAX_PLAYERS.to_string(),
             active_field: InputField::Name,
             error_message: None,
+            port_check: None,
+            port_report: None,
         }
     }
 
+    /// Pre-fill the rate fields (e.g. from command-line flags)
+    pub fn set_default_rates(&mut self, rates: HostRates) {
+        self.physics_hz_input = rates.physics_hz().to_string();
+        self.snapshot_hz_input = rates.snapshot_hz().to_string();
+    }
+
     pub fn update(&mut self) -> OnlineHostMenuResult {
         let mouse_pressed = is_mouse_button_pressed(MouseButton::Left);
 
@@ -60,7 +95,10 @@ impl OnlineHostMenu {
         if is_key_pressed(KeyCode::Tab) {
             self.active_field = match self.active_field {
                 InputField::Name => InputField::Port,
-                InputField::Port => InputField::Name,
+                InputField::Port => InputField::PhysicsHz,
+                InputField::PhysicsHz => InputField::SnapshotHz,
+                InputField::SnapshotHz => InputField::MaxPlayers,
+                InputField::MaxPlayers => InputField::Name,
             };
         }
 
@@ -72,6 +110,9 @@ impl OnlineHostMenu {
             let input = match self.active_field {
                 InputField::Name => &mut self.name_input,
                 InputField::Port => &mut self.port_input,
+                InputField::PhysicsHz => &mut self.physics_hz_input,
+                InputField::SnapshotHz => &mut self.snapshot_hz_input,
+                InputField::MaxPlayers => &mut self.max_players_input,
             };
 
             match key {
@@ -114,23 +155,61 @@ impl OnlineHostMenu {
                         self.port_input.truncate(5);
                     }
                 }
+                InputField::PhysicsHz => {
+                    if self.physics_hz_input.len() > 3 {
+                        self.physics_hz_input.truncate(3);
+                    }
+                }
+                InputField::SnapshotHz => {
+                    if self.snapshot_hz_input.len() > 3 {
+                        self.snapshot_hz_input.truncate(3);
+                    }
+                }
+                InputField::MaxPlayers => {
+                    if self.max_players_input.len() > 2 {
+                        self.max_players_input.truncate(2);
+                    }
+                }
             }
         }
 
         // Check for button clicks
         if self.host_button.update(mouse_pressed) {
-            // Validate name and port
+            // Validate name, port and rates
+            let physics_hz = self.physics_hz_input.parse::<u32>();
+            let snapshot_hz = self.snapshot_hz_input.parse::<u32>();
+            let port = self.port_input.parse::<u16>();
+            let max_players = self.max_players_input.parse::<u32>();
+
             if self.name_input.trim().is_empty() {
                 self.error_message = Some("Please enter a player name".to_string());
-            } else if let Ok(port) = self.port_input.parse::<u16>() {
-                if port > 0 {
-                    log::info!("Starting host '{}' on port {}", self.name_input, port);
-                    return OnlineHostMenuResult::StartHost(self.name_input.clone(), port);
-                } else {
-                    self.error_message = Some("Invalid port number".to_string());
-                }
             } else {
-                self.error_message = Some("Invalid port number".to_string());
+                match (port, physics_hz, snapshot_hz, max_players) {
+                    (Ok(port), Ok(physics_hz), Ok(snapshot_hz), Ok(max_players)) if port > 0 => {
+                        // Out-of-range rates and caps are clamped rather than rejected
+                        let rates = HostRates::new(physics_hz, snapshot_hz);
+                        let max_players = max_players.clamp(MIN_PLAYERS, MAX_PLAYERS);
+                        self.set_default_rates(rates);
+                        self.max_players_input = max_players.to_string();
+                        log::info!(
+                            "Starting host '{}' on port {} ({} Hz physics, {} Hz snapshots, up to {} players)",
+                            self.name_input, port, rates.physics_hz(), rates.snapshot_hz(), max_players
+                        );
+                        return OnlineHostMenuResult::StartHost(self.name_input.clone(), port, rates, max_players);
+                    }
+                    (_, Err(_), _, _) => {
+                        self.error_message = Some("Invalid physics rate".to_string());
+                    }
+                    (_, _, Err(_), _) => {
+                        self.error_message = Some("Invalid snapshot rate".to_string());
+                    }
+                    (_, _, _, Err(_)) => {
+                        self.error_message = Some("Invalid player cap".to_string());
+                    }
+                    _ => {
+                        self.error_message = Some("Invalid port number".to_string());
+                    }
+                }
             }
         }
 
@@ -138,6 +217,23 @@ impl OnlineHostMenu {
             return OnlineHostMenuResult::Back;
         }
 
+        // Connection test: bind the port on a background thread and report what blocks players
+        if self.test_button.update(mouse_pressed) && self.port_check.is_none() {
+            match self.port_input.parse::<u16>() {
+                Ok(port) if port > 0 => {
+                    log::info!("Testing connection on port {}", port);
+                    self.error_message = None;
+                    self.port_report = None;
+                    self.port_check = Some(PortCheck::start(port));
+                }
+                _ => self.error_message = Some("Invalid port number".to_string()),
+            }
+        }
+        if let Some(report) = self.port_check.as_ref().and_then(|check| check.poll()) {
+            self.port_check = None;
+            self.port_report = Some(report);
+        }
+
         OnlineHostMenuResult::None
     }
 
@@ -175,112 +271,159 @@ impl OnlineHostMenu {
         }
     }
 
-    pub fn draw(&self) {
-        clear_background(Color::new(0.05, 0.05, 0.1, 1.0));
-
-        // Title
-        let title = "Host Multiplayer Game";
-        let title_size = 48.0;
-        let title_dims = measure_text(title, None, title_size as u16, 1.0);
-        let title_x = screen_width() / 2.0 - title_dims.width / 2.0;
-        let title_y = screen_height() / 2.0 - 220.0;
-        draw_text(title, title_x, title_y, title_size, WHITE);
-
-        // Instructions
-        let instructions = "Enter your name and port number (press TAB to switch fields)";
-        let inst_size = 20.0;
-        let inst_dims = measure_text(instructions, None, inst_size as u16, 1.0);
-        let inst_x = screen_width() / 2.0 - inst_dims.width / 2.0;
-        let inst_y = title_y + 60.0;
-        draw_text(instructions, inst_x, inst_y, inst_size, LIGHTGRAY);
-
+    /// Draw a labelled text input box, highlighted when it is the active field
+    fn draw_input_field(&self, label: &str, value: &str, field: InputField, y: f32) {
         let input_width = 350.0;
         let input_height = 50.0;
         let input_x = screen_width() / 2.0 - input_width / 2.0;
         let label_size = 20.0;
+        let is_active = self.active_field == field;
 
-        // Name input
-        let name_y = screen_height() / 2.0 - 80.0;
-
-        let name_color = if matches!(self.active_field, InputField::Name) {
+        let box_color = if is_active {
             Color::new(0.3, 0.3, 0.5, 1.0)
         } else {
             Color::new(0.2, 0.2, 0.3, 1.0)
         };
 
-        draw_rectangle(input_x, name_y, input_width, input_height, name_color);
+        draw_rectangle(input_x, y, input_width, input_height, box_color);
         draw_rectangle_lines(
             input_x,
-            name_y,
+            y,
             input_width,
             input_height,
             2.0,
-            if matches!(self.active_field, InputField::Name) { YELLOW } else { WHITE }
+            if is_active { YELLOW } else { WHITE }
         );
 
-        // Name label
-        let name_label = "Name:";
-        let name_label_dims = measure_text(name_label, None, label_size as u16, 1.0);
-        draw_text(name_label, input_x - name_label_dims.width - 20.0, name_y + 32.0, label_size, WHITE);
-
-        // Name text
-        let name_text_size = 28.0;
-        let name_text_dims = measure_text(&self.name_input, None, name_text_size as u16, 1.0);
-        let name_text_x = input_x + input_width / 2.0 - name_text_dims.width / 2.0;
-        draw_text(&self.name_input, name_text_x, name_y + 33.0, name_text_size, WHITE);
-
-        // Name cursor
-        if matches!(self.active_field, InputField::Name) && (get_time() * 2.0) as i32 % 2 == 0 {
-            let cursor_x = name_text_x + name_text_dims.width + 5.0;
-            draw_rectangle(cursor_x, name_y + 10.0, 2.0, 30.0, YELLOW);
+        // Label to the left of the box
+        let label_dims = measure_text(label, None, label_size as u16, 1.0);
+        draw_text(label, input_x - label_dims.width - 20.0, y + 32.0, label_size, WHITE);
+
+        // Value text
+        let text_size = 28.0;
+        let text_dims = measure_text(value, None, text_size as u16, 1.0);
+        let text_x = input_x + input_width / 2.0 - text_dims.width / 2.0;
+        draw_text(value, text_x, y + 33.0, text_size, WHITE);
+
+        // Cursor
+        if is_active && (get_time() * 2.0) as i32 % 2 == 0 {
+            let cursor_x = text_x + text_dims.width + 5.0;
+            draw_rectangle(cursor_x, y + 10.0, 2.0, 30.0, YELLOW);
         }
+    }
 
-        // Port input
-        let port_y = name_y + 80.0;
+    /// Connection test progress or results, below the test button
+    fn draw_port_report(&self, x: f32, y: f32) {
+        if self.port_check.is_some() {
+            let dots = ".".repeat((get_time() * 3.0) as usize % 4);
+            draw_text(&format!("Testing port {}{}", self.port_input, dots), x, y + 20.0, 20.0, LIGHTGRAY);
+            return;
+        }
+        let Some(report) = &self.port_report else {
+            return;
+        };
 
-        let port_color = if matches!(self.active_field, InputField::Port) {
-            Color::new(0.3, 0.3, 0.5, 1.0)
+        let mut lines: Vec<(String, Color)> = Vec::new();
+        let (title, title_color) = if report.all_passed() {
+            (format!("Port {}: no problems found", report.port), GREEN)
         } else {
-            Color::new(0.2, 0.2, 0.3, 1.0)
+            (format!("Port {}: players may not be able to join", report.port), ORANGE)
         };
+        lines.push((title, title_color));
+        for step in &report.steps {
+            let color = match step.outcome {
+                StepOutcome::Passed => GREEN,
+                StepOutcome::Failed => RED,
+                StepOutcome::Skipped => GRAY,
+            };
+            lines.push((format!("{} {} ({})", step.outcome.label(), step.name, step.detail), color));
+        }
+        for tip in &report.advice {
+            for (i, line) in Self::wrap(tip, REPORT_WRAP_CHARS).into_iter().enumerate() {
+                let bullet = if i == 0 { "- " } else { "  " };
+                lines.push((format!("{}{}", bullet, line), WHITE));
+            }
+        }
 
-        draw_rectangle(input_x, port_y, input_width, input_height, port_color);
-        draw_rectangle_lines(
-            input_x,
-            port_y,
-            input_width,
-            input_height,
-            2.0,
-            if matches!(self.active_field, InputField::Port) { YELLOW } else { WHITE }
-        );
+        let line_height = 22.0;
+        let height = lines.len() as f32 * line_height + 16.0;
+        draw_rectangle(x, y, REPORT_WIDTH, height, Color::new(0.1, 0.1, 0.15, 0.9));
+        draw_rectangle_lines(x, y, REPORT_WIDTH, height, 2.0, title_color);
+        for (i, (text, color)) in lines.iter().enumerate() {
+            draw_text(text, x + 10.0, y + 24.0 + i as f32 * line_height, 17.0, *color);
+        }
+    }
 
-        // Port label
-        let port_label = "Port:";
-        let port_label_dims = measure_text(port_label, None, label_size as u16, 1.0);
-        draw_text(port_label, input_x - port_label_dims.width - 20.0, port_y + 32.0, label_size, WHITE);
-
-        // Port text
-        let port_text_size = 28.0;
-        let port_text_dims = measure_text(&self.port_input, None, port_text_size as u16, 1.0);
-        let port_text_x = input_x + input_width / 2.0 - port_text_dims.width / 2.0;
-        draw_text(&self.port_input, port_text_x, port_y + 33.0, port_text_size, WHITE);
-
-        // Port cursor
-        if matches!(self.active_field, InputField::Port) && (get_time() * 2.0) as i32 % 2 == 0 {
-            let cursor_x = port_text_x + port_text_dims.width + 5.0;
-            draw_rectangle(cursor_x, port_y + 10.0, 2.0, 30.0, YELLOW);
+    /// Split `text` into lines of at most `width` characters at spaces
+    fn wrap(text: &str, width: usize) -> Vec<String> {
+        let mut lines = vec![String::new()];
+        for word in text.split_whitespace() {
+            let line = lines.last_mut().unwrap();
+            if !line.is_empty() && line.len() + 1 + word.len() > width {
+                lines.push(word.to_string());
+            } else {
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(word);
+            }
         }
+        lines
+    }
+
+    pub fn draw(&self) {
+        clear_background(Color::new(0.05, 0.05, 0.1, 1.0));
+
+        // Title
+        let title = "Host Multiplayer Game";
+        let title_size = 48.0;
+        let title_dims = measure_text(title, None, title_size as u16, 1.0);
+        let title_x = screen_width() / 2.0 - title_dims.width / 2.0;
+        let title_y = screen_height() / 2.0 - 220.0;
+        draw_text(title, title_x, title_y, title_size, WHITE);
+
+        // Instructions
+        let instructions = "Enter your name, port, tick rates and player cap (press TAB to switch fields)";
+        let inst_size = 20.0;
+        let inst_dims = measure_text(instructions, None, inst_size as u16, 1.0);
+        let inst_x = screen_width() / 2.0 - inst_dims.width / 2.0;
+        let inst_y = title_y + 60.0;
+        draw_text(instructions, inst_x, inst_y, inst_size, LIGHTGRAY);
+
+        let name_y = screen_height() / 2.0 - 120.0;
+        self.draw_input_field("Name:", &self.name_input, InputField::Name, name_y);
+        self.draw_input_field("Port:", &self.port_input, InputField::Port, name_y + 80.0);
+        self.draw_input_field(
+            &format!("Physics Hz ({}-{}):", GameConstants::MIN_PHYSICS_HZ, GameConstants::MAX_PHYSICS_HZ),
+            &self.physics_hz_input,
+            InputField::PhysicsHz,
+            name_y + 160.0,
+        );
+        self.draw_input_field(
+            &format!("Snapshot Hz ({}-{}):", GameConstants::MIN_SNAPSHOT_HZ, GameConstants::MAX_SNAPSHOT_HZ),
+            &self.snapshot_hz_input,
+            InputField::SnapshotHz,
+            name_y + 240.0,
+        );
+        self.draw_input_field(
+            &format!("Max players ({}-{}):", MIN_PLAYERS, MAX_PLAYERS),
+            &self.max_players_input,
+            InputField::MaxPlayers,
+            name_y + 320.0,
+        );
 
         // Draw buttons
         self.host_button.draw();
         self.back_button.draw();
+        self.test_button.draw();
+        self.draw_port_report(screen_width() / 2.0 + 195.0, name_y + 160.0);
 
         // Draw error message if any
         if let Some(ref error) = self.error_message {
             let error_size = 24.0;
             let error_dims = measure_text(error, None, error_size as u16, 1.0);
             let error_x = screen_width() / 2.0 - error_dims.width / 2.0;
-            let error_y = screen_height() / 2.0 + 240.0;
+            let error_y = screen_height() / 2.0 + 400.0;
             draw_text(error, error_x, error_y, error_size, RED);
         }
 
@@ -1,7 +1,12 @@
 // Online Join Menu - Connect to a multiplayer host
 
 use macroquad::prelude::*;
-use crate::ui::Button;
+use crate::networking::session_browser::{SessionBrowser, SessionListing, SessionSort, PING_LIMITS};
+use crate::systems::CrewRole;
+use crate::ui::{Button, Table, TableColumn, TableEvent};
+
+/// Highest pilot an engineer can pick in the role button's cycle (host = 0)
+const MAX_CREW_PILOT_ID: u32 = 5;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum InputField {
@@ -13,18 +18,30 @@ enum InputField {
 #[derive(Debug, Clone, PartialEq)]
 pub enum OnlineJoinMenuResult {
     None,
-    Connect(String, String, u16), // Connect with player name, IP, and port
+    Connect(String, String, u16, CrewRole), // Connect with player name, IP, port and crew role
     Back,
 }
 
 pub struct OnlineJoinMenu {
     join_button: Button,
     back_button: Button,
+    role_button: Button,
+    role: CrewRole,
     name_input: String,
     ip_input: String,
     port_input: String,
     active_field: InputField,
     error_message: Option<String>,
+    // Session browser on the left: filter buttons above a sortable table
+    browser: SessionBrowser,
+    session_table: Table,
+    map_filter_button: Button,
+    pvp_filter_button: Button,
+    password_filter_button: Button,
+    ping_filter_button: Button,
+    full_filter_button: Button,
+    refresh_button: Button,
+    refresh_requested: bool,
 }
 
 impl OnlineJoinMenu {
@@ -35,6 +52,22 @@ impl OnlineJoinMenu {
         let start_y = window_size.y / 2.0 + 150.0;
         let spacing = 70.0;
 
+        let browser_x = 40.0;
+        let browser_width = window_size.x / 2.0 - 420.0;
+        let filter_width = browser_width / 3.0 - 4.0;
+        let filter_size = Vec2::new(filter_width, 34.0);
+        let filter_color = Color::from_rgba(60, 60, 90, 255);
+        let filter_button = |column: usize, row: usize, text: &str| {
+            let position = Vec2::new(browser_x + column as f32 * (filter_width + 6.0), window_size.y / 2.0 - 175.0 + row as f32 * 40.0);
+            Button::new(position, filter_size, text, filter_color)
+        };
+        let columns = vec![
+            TableColumn { title: SessionSort::Name.title(), width: 0.4 },
+            TableColumn { title: SessionSort::Map.title(), width: 0.28 },
+            TableColumn { title: SessionSort::Players.title(), width: 0.16 },
+            TableColumn { title: SessionSort::Ping.title(), width: 0.16 },
+        ];
+
         Self {
             join_button: Button::new(
                 Vec2::new(center_x, start_y),
@@ -48,14 +81,51 @@ impl OnlineJoinMenu {
                 "Back",
                 Color::from_rgba(120, 50, 50, 255),
             ),
+            role_button: Button::new(
+                Vec2::new(center_x, start_y - button_height - 8.0),
+                Vec2::new(button_width, button_height),
+                &format!("Role: {}", CrewRole::Pilot.label()),
+                Color::from_rgba(60, 60, 120, 255),
+            ),
+            role: CrewRole::Pilot,
             name_input: "Player".to_string(), // Default name
             ip_input: "127.0.0.1".to_string(), // Default localhost
             port_input: "7777".to_string(), // Default port
             active_field: InputField::Name,
             error_message: None,
+            browser: SessionBrowser::new(),
+            session_table: Table::new(Rect::new(browser_x, window_size.y / 2.0 - 90.0, browser_width, 330.0), columns),
+            map_filter_button: filter_button(0, 0, "Map: all"),
+            pvp_filter_button: filter_button(1, 0, "PvP: any"),
+            password_filter_button: filter_button(2, 0, "Password: any"),
+            ping_filter_button: filter_button(0, 1, "Ping: any"),
+            full_filter_button: filter_button(1, 1, "Full: show"),
+            refresh_button: filter_button(2, 1, "Refresh"),
+            refresh_requested: false,
         }
     }
 
+    /// Sessions found by LAN discovery (upsert listings as they're announced)
+    pub fn browser_mut(&mut self) -> &mut SessionBrowser {
+        &mut self.browser
+    }
+
+    /// True once per auto-refresh interval (or after Refresh is clicked): time to re-run discovery
+    pub fn take_refresh_request(&mut self) -> bool {
+        std::mem::take(&mut self.refresh_requested)
+    }
+
+    /// Fill in a host address (from a Discord join invite)
+    pub fn set_host(&mut self, ip: &str, port: u16) {
+        self.ip_input = ip.to_string();
+        self.port_input = port.to_string();
+        self.error_message = None;
+    }
+
+    pub fn player_name(&self) -> &str {
+        self.name_input.trim()
+    }
+
     pub fn update(&mut self) -> OnlineJoinMenuResult {
         let mouse_pressed = is_mouse_button_pressed(MouseButton::Left);
 
@@ -135,30 +205,130 @@ impl OnlineJoinMenu {
 
         // Check for button clicks
         if self.join_button.update(mouse_pressed) {
-            // Validate name, IP and port
-            if self.name_input.trim().is_empty() {
-                self.error_message = Some("Please enter a player name".to_string());
-            } else if !self.validate_ip(&self.ip_input) {
-                self.error_message = Some("Invalid IP address".to_string());
-            } else if let Ok(port) = self.port_input.parse::<u16>() {
-                if port > 0 {
-                    log::info!("Connecting as '{}' to {}:{}", self.name_input, self.ip_input, port);
-                    return OnlineJoinMenuResult::Connect(self.name_input.clone(), self.ip_input.clone(), port);
-                } else {
-                    self.error_message = Some("Invalid port number".to_string());
-                }
-            } else {
-                self.error_message = Some("Invalid port number".to_string());
+            if let Some(result) = self.try_connect() {
+                return result;
             }
         }
 
+        if let Some(result) = self.update_browser(mouse_pressed) {
+            return result;
+        }
+
         if self.back_button.update(mouse_pressed) {
             return OnlineJoinMenuResult::Back;
         }
 
+        // Cycle Pilot -> Engineer for host -> Engineer for player 1.. -> Spectator -> Pilot
+        if self.role_button.update(mouse_pressed) {
+            self.role = match self.role {
+                CrewRole::Pilot => CrewRole::Engineer { pilot_id: 0 },
+                CrewRole::Engineer { pilot_id } if pilot_id < MAX_CREW_PILOT_ID => {
+                    CrewRole::Engineer { pilot_id: pilot_id + 1 }
+                }
+                CrewRole::Engineer { .. } => CrewRole::Spectator,
+                CrewRole::Spectator => CrewRole::Pilot,
+            };
+            self.role_button.set_text(&format!("Role: {}", self.role.label()));
+        }
+
         OnlineJoinMenuResult::None
     }
 
+    /// Validate name, IP and port; the Connect result if they're good
+    fn try_connect(&mut self) -> Option<OnlineJoinMenuResult> {
+        if self.name_input.trim().is_empty() {
+            self.error_message = Some("Please enter a player name".to_string());
+        } else if !self.validate_ip(&self.ip_input) {
+            self.error_message = Some("Invalid IP address".to_string());
+        } else if let Ok(port) = self.port_input.parse::<u16>() {
+            if port > 0 {
+                log::info!("Connecting as '{}' to {}:{}", self.name_input, self.ip_input, port);
+                return Some(OnlineJoinMenuResult::Connect(self.name_input.clone(), self.ip_input.clone(), port, self.role));
+            } else {
+                self.error_message = Some("Invalid port number".to_string());
+            }
+        } else {
+            self.error_message = Some("Invalid port number".to_string());
+        }
+        None
+    }
+
+    /// Filter buttons, auto-refresh and the session table (picking a session fills in its address)
+    fn update_browser(&mut self, mouse_pressed: bool) -> Option<OnlineJoinMenuResult> {
+        if self.browser.update(get_frame_time()) {
+            self.refresh_requested = true;
+        }
+        if self.refresh_button.update(mouse_pressed) {
+            self.browser.refresh_now();
+        }
+
+        if self.map_filter_button.update(mouse_pressed) {
+            // All maps -> each map seen -> all maps
+            let maps = self.browser.maps();
+            let next = match &self.browser.filter.map {
+                None => maps.first().cloned(),
+                Some(current) => maps.iter().position(|map| map.eq_ignore_ascii_case(current)).and_then(|i| maps.get(i + 1).cloned()),
+            };
+            self.map_filter_button.set_text(&format!("Map: {}", next.as_deref().unwrap_or("all")));
+            self.browser.filter.map = next;
+        }
+        if self.pvp_filter_button.update(mouse_pressed) {
+            self.browser.filter.pvp = self.browser.filter.pvp.next();
+            self.pvp_filter_button.set_text(&format!("PvP: {}", self.browser.filter.pvp.label()));
+        }
+        if self.password_filter_button.update(mouse_pressed) {
+            self.browser.filter.password = self.browser.filter.password.next();
+            self.password_filter_button.set_text(&format!("Password: {}", self.browser.filter.password.label()));
+        }
+        if self.ping_filter_button.update(mouse_pressed) {
+            let current = PING_LIMITS.iter().position(|limit| *limit == self.browser.filter.max_ping).unwrap_or(0);
+            let next = PING_LIMITS[(current + 1) % PING_LIMITS.len()];
+            self.browser.filter.max_ping = next;
+            self.ping_filter_button.set_text(&match next {
+                Some(limit) => format!("Ping: <{} ms", limit),
+                None => "Ping: any".to_string(),
+            });
+        }
+        if self.full_filter_button.update(mouse_pressed) {
+            self.browser.filter.hide_full = !self.browser.filter.hide_full;
+            self.full_filter_button.set_text(if self.browser.filter.hide_full { "Full: hide" } else { "Full: show" });
+        }
+
+        let row_count = self.browser.visible().len();
+        let picked = match self.session_table.handle_input(row_count)? {
+            TableEvent::HeaderClicked(column) => {
+                self.browser.sort_by(SessionSort::COLUMNS[column]);
+                self.session_table.clear_selection();
+                return None;
+            }
+            TableEvent::Selected(row) => (row, false),
+            TableEvent::Activated(row) => (row, true),
+        };
+        let (address, port) = self.browser.visible().get(picked.0).map(|l| (l.address.clone(), l.port))?;
+        self.set_host(&address, port);
+        if picked.1 {
+            return self.try_connect();
+        }
+        None
+    }
+
+    /// Table cells for one session
+    fn session_row(listing: &SessionListing) -> Vec<String> {
+        let mut name = listing.name.clone();
+        if listing.pvp {
+            name.push_str(" [PvP]");
+        }
+        if listing.password {
+            name.push_str(" [pw]");
+        }
+        vec![
+            name,
+            listing.map.clone(),
+            format!("{}/{}", listing.players, listing.max_players),
+            listing.ping_ms.map_or("?".to_string(), |ping| format!("{} ms", ping)),
+        ]
+    }
+
     fn key_to_char_static(key: KeyCode) -> Option<char> {
         match key {
             KeyCode::A => Some('A'),
@@ -344,9 +514,34 @@ impl OnlineJoinMenu {
         }
 
         // Draw buttons
+        self.role_button.draw();
         self.join_button.draw();
         self.back_button.draw();
 
+        // Session browser
+        let table_rect = self.session_table.rect;
+        draw_text("BROWSE LAN GAMES (click to fill in, double-click to join)", table_rect.x, screen_height() / 2.0 - 190.0, 20.0, WHITE);
+        for button in [
+            &self.map_filter_button,
+            &self.pvp_filter_button,
+            &self.password_filter_button,
+            &self.ping_filter_button,
+            &self.full_filter_button,
+            &self.refresh_button,
+        ] {
+            button.draw();
+        }
+        let visible = self.browser.visible();
+        let rows: Vec<Vec<String>> = visible.iter().map(|listing| Self::session_row(listing)).collect();
+        let (sort, descending) = self.browser.sort();
+        let sorted_column = SessionSort::COLUMNS.iter().position(|column| *column == sort).map(|i| (i, descending));
+        let empty_text = if self.browser.is_empty() {
+            "No LAN games found - enter an address instead"
+        } else {
+            "No sessions match the filters"
+        };
+        self.session_table.draw(&rows, sorted_column, empty_text);
+
         // Draw error message if any
         if let Some(ref error) = self.error_message {
             let error_size = 24.0;
@@ -368,5 +563,10 @@ impl OnlineJoinMenu {
         let info2_dims = measure_text(info2, None, info_size as u16, 1.0);
         let info2_x = screen_width() / 2.0 - info2_dims.width / 2.0;
         draw_text(info2, info2_x, info_y + 25.0, info_size, YELLOW);
+
+        let info3 = "Engineers crew another player's rocket: they fly, you run fuel, satellites and weapons";
+        let info3_dims = measure_text(info3, None, info_size as u16, 1.0);
+        let info3_x = screen_width() / 2.0 - info3_dims.width / 2.0;
+        draw_text(info3, info3_x, info_y + 50.0, info_size, YELLOW);
     }
 }
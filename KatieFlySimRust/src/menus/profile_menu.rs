@@ -0,0 +1,179 @@
+// Profile Menu - Pick or create the local profile that owns saves, settings and key bindings
+// Reached from the "Profile:" button on the main menu
+
+use macroquad::prelude::*;
+
+use crate::save_system::local_profiles;
+use crate::ui::Button;
+
+/// Only this many profiles get a button
+const MAX_LISTED: usize = 8;
+
+/// Result from profile menu interaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileMenuResult {
+    None,
+    Select(String), // Name of the chosen (or newly created) profile
+    Back,
+}
+
+/// Lists the local profiles and creates new ones
+pub struct ProfileMenu {
+    title_text: String,
+    title_position: Vec2,
+    title_font_size: f32,
+    back_button: Button,
+    create_button: Button,
+    profile_buttons: Vec<Button>,
+    profile_names: Vec<String>,
+    name_input: String,
+    status_message: Option<(String, Color)>, // Why the last profile couldn't be selected
+    window_size: Vec2,
+}
+
+impl ProfileMenu {
+    pub fn new(window_size: Vec2) -> Self {
+        // Title
+        let title_text = "Who's Playing?".to_string();
+        let title_font_size = 48.0;
+
+        // Calculate title position (centered)
+        let text_dims = measure_text(&title_text, None, title_font_size as u16, 1.0);
+        let title_position = Vec2::new(
+            window_size.x / 2.0 - text_dims.width / 2.0,
+            80.0 + text_dims.height,
+        );
+
+        // Back button
+        let back_button = Button::new(
+            Vec2::new(50.0, window_size.y - 80.0),
+            Vec2::new(150.0, 50.0),
+            "Back",
+            Color::from_rgba(100, 100, 100, 255),
+        );
+
+        // Create button, right of the name field
+        let create_button = Button::new(
+            Vec2::new(window_size.x / 2.0 + 110.0, window_size.y - 200.0),
+            Vec2::new(140.0, 50.0),
+            "Create",
+            Color::from_rgba(50, 120, 100, 255),
+        );
+
+        let mut menu = ProfileMenu {
+            title_text,
+            title_position,
+            title_font_size,
+            back_button,
+            create_button,
+            profile_buttons: Vec::new(),
+            profile_names: Vec::new(),
+            name_input: String::new(),
+            status_message: None,
+            window_size,
+        };
+        menu.refresh_profiles();
+        menu
+    }
+
+    /// Re-read the profiles folder and rebuild the buttons
+    pub fn refresh_profiles(&mut self) {
+        self.profile_buttons.clear();
+        self.profile_names.clear();
+        self.status_message = None;
+
+        let active = local_profiles::active();
+        let button_width = 400.0;
+        let button_height = 50.0;
+        let button_spacing = 65.0;
+        let start_y = 180.0;
+
+        for (i, name) in local_profiles::list().into_iter().take(MAX_LISTED).enumerate() {
+            let color = if name == active {
+                Color::from_rgba(50, 100, 150, 255)
+            } else {
+                Color::from_rgba(70, 70, 90, 255)
+            };
+            let button = Button::new(
+                Vec2::new(
+                    self.window_size.x / 2.0 - button_width / 2.0,
+                    start_y + (i as f32 * button_spacing),
+                ),
+                Vec2::new(button_width, button_height),
+                &name,
+                color,
+            );
+            self.profile_buttons.push(button);
+            self.profile_names.push(name);
+        }
+    }
+
+    /// Show a line under the title (e.g. why a name was rejected)
+    pub fn set_status(&mut self, message: String, color: Color) {
+        self.status_message = Some((message, color));
+    }
+
+    /// Update menu and handle input
+    pub fn update(&mut self) -> ProfileMenuResult {
+        let mouse_pressed = is_mouse_button_down(MouseButton::Left);
+
+        // Typing goes into the new profile name
+        while let Some(ch) = get_char_pressed() {
+            if !ch.is_control() && self.name_input.chars().count() < local_profiles::MAX_NAME_LENGTH {
+                self.name_input.push(ch);
+            }
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            self.name_input.pop();
+        }
+
+        if self.back_button.update(mouse_pressed) {
+            return ProfileMenuResult::Back;
+        }
+
+        if (self.create_button.update(mouse_pressed) || is_key_pressed(KeyCode::Enter)) && !self.name_input.trim().is_empty() {
+            match local_profiles::validate_name(&self.name_input) {
+                Ok(_) => return ProfileMenuResult::Select(std::mem::take(&mut self.name_input)),
+                Err(e) => self.set_status(e, RED),
+            }
+        }
+
+        for (i, button) in self.profile_buttons.iter_mut().enumerate() {
+            if button.update(mouse_pressed) {
+                return ProfileMenuResult::Select(self.profile_names[i].clone());
+            }
+        }
+
+        ProfileMenuResult::None
+    }
+
+    /// Draw the menu
+    pub fn draw(&self) {
+        draw_text(
+            &self.title_text,
+            self.title_position.x,
+            self.title_position.y,
+            self.title_font_size,
+            WHITE,
+        );
+
+        for button in &self.profile_buttons {
+            button.draw();
+        }
+
+        // New profile name field
+        let field = Rect::new(self.window_size.x / 2.0 - 250.0, self.window_size.y - 200.0, 350.0, 50.0);
+        draw_text("New profile:", field.x, field.y - 10.0, 20.0, LIGHTGRAY);
+        draw_rectangle(field.x, field.y, field.w, field.h, Color::from_rgba(40, 40, 50, 255));
+        draw_rectangle_lines(field.x, field.y, field.w, field.h, 2.0, Color::from_rgba(100, 150, 255, 255));
+        draw_text(&format!("{}_", self.name_input), field.x + 10.0, field.y + 33.0, 28.0, WHITE);
+        self.create_button.draw();
+
+        if let Some((message, color)) = &self.status_message {
+            let dims = measure_text(message, None, 20, 1.0);
+            draw_text(message, self.window_size.x / 2.0 - dims.width / 2.0, 160.0, 20.0, *color);
+        }
+
+        self.back_button.draw();
+    }
+}
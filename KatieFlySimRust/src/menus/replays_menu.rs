@@ -0,0 +1,140 @@
+// Replays Menu - Pick a recorded multiplayer match to re-watch
+// Lists the recordings the host wrote to saves/replays, newest first
+
+use macroquad::prelude::*;
+
+use crate::systems::SessionRecording;
+use crate::ui::Button;
+
+/// Only this many of the newest recordings get a button
+const MAX_LISTED: usize = 8;
+
+/// Result from replays menu interaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplaysMenuResult {
+    None,
+    Watch(String), // Path of the chosen recording
+    Back,
+}
+
+/// Lists the recorded matches
+pub struct ReplaysMenu {
+    title_text: String,
+    title_position: Vec2,
+    title_font_size: f32,
+    back_button: Button,
+    replay_buttons: Vec<Button>,
+    replay_paths: Vec<String>,
+    status_message: Option<(String, Color)>, // Why the last recording couldn't play
+    window_size: Vec2,
+}
+
+impl ReplaysMenu {
+    pub fn new(window_size: Vec2) -> Self {
+        // Title
+        let title_text = "Match Replays".to_string();
+        let title_font_size = 48.0;
+
+        // Calculate title position (centered)
+        let text_dims = measure_text(&title_text, None, title_font_size as u16, 1.0);
+        let title_position = Vec2::new(
+            window_size.x / 2.0 - text_dims.width / 2.0,
+            80.0 + text_dims.height,
+        );
+
+        // Back button
+        let back_button = Button::new(
+            Vec2::new(50.0, window_size.y - 80.0),
+            Vec2::new(150.0, 50.0),
+            "Back",
+            Color::from_rgba(100, 100, 100, 255),
+        );
+
+        ReplaysMenu {
+            title_text,
+            title_position,
+            title_font_size,
+            back_button,
+            replay_buttons: Vec::new(),
+            replay_paths: Vec::new(),
+            status_message: None,
+            window_size,
+        }
+    }
+
+    /// Re-read the replays folder and rebuild the buttons
+    pub fn refresh_replays(&mut self) {
+        self.replay_buttons.clear();
+        self.replay_paths.clear();
+        self.status_message = None;
+
+        let button_width = 500.0;
+        let button_height = 50.0;
+        let button_spacing = 65.0;
+        let start_y = 180.0;
+
+        for (i, path) in SessionRecording::list().into_iter().take(MAX_LISTED).enumerate() {
+            let button = Button::new(
+                Vec2::new(
+                    self.window_size.x / 2.0 - button_width / 2.0,
+                    start_y + (i as f32 * button_spacing),
+                ),
+                Vec2::new(button_width, button_height),
+                &SessionRecording::label_for(&path),
+                Color::from_rgba(150, 100, 50, 255),
+            );
+            self.replay_buttons.push(button);
+            self.replay_paths.push(path);
+        }
+    }
+
+    /// Show a line under the title (e.g. why a recording couldn't play)
+    pub fn set_status(&mut self, message: String, color: Color) {
+        self.status_message = Some((message, color));
+    }
+
+    /// Update menu and handle input
+    pub fn update(&mut self) -> ReplaysMenuResult {
+        let mouse_pressed = is_mouse_button_down(MouseButton::Left);
+
+        if self.back_button.update(mouse_pressed) {
+            return ReplaysMenuResult::Back;
+        }
+
+        for (i, button) in self.replay_buttons.iter_mut().enumerate() {
+            if button.update(mouse_pressed) {
+                return ReplaysMenuResult::Watch(self.replay_paths[i].clone());
+            }
+        }
+
+        ReplaysMenuResult::None
+    }
+
+    /// Draw the menu
+    pub fn draw(&self) {
+        draw_text(
+            &self.title_text,
+            self.title_position.x,
+            self.title_position.y,
+            self.title_font_size,
+            WHITE,
+        );
+
+        for button in &self.replay_buttons {
+            button.draw();
+        }
+
+        if self.replay_buttons.is_empty() {
+            let text = "No recordings yet - matches you host are recorded when you leave them";
+            let dims = measure_text(text, None, 22, 1.0);
+            draw_text(text, self.window_size.x / 2.0 - dims.width / 2.0, 220.0, 22.0, LIGHTGRAY);
+        }
+
+        if let Some((message, color)) = &self.status_message {
+            let dims = measure_text(message, None, 20, 1.0);
+            draw_text(message, self.window_size.x / 2.0 - dims.width / 2.0, 150.0, 20.0, *color);
+        }
+
+        self.back_button.draw();
+    }
+}
@@ -3,6 +3,7 @@
 
 use macroquad::prelude::*;
 
+use crate::save_system::local_profiles;
 use crate::ui::Button;
 
 /// Result from saves menu interaction
@@ -23,6 +24,7 @@ pub struct SavesMenu {
     back_button: Button,
     save_buttons: Vec<Button>,
     save_names: Vec<String>,
+    status_message: Option<String>, // Why the last save couldn't be loaded
     window_size: Vec2,
 }
 
@@ -71,6 +73,7 @@ impl SavesMenu {
             back_button,
             save_buttons: Vec::new(),
             save_names: Vec::new(),
+            status_message: None,
             window_size,
         }
     }
@@ -80,6 +83,7 @@ impl SavesMenu {
         // Clear existing save buttons
         self.save_buttons.clear();
         self.save_names.clear();
+        self.status_message = None;
 
         // Get save files from disk
         if let Ok(saves) = self.load_save_list() {
@@ -105,26 +109,39 @@ impl SavesMenu {
         }
     }
 
+    /// Show a line under the title (e.g. why a save couldn't be loaded)
+    pub fn set_status(&mut self, message: String) {
+        self.status_message = Some(message);
+    }
+
     /// Load list of save files from disk
     fn load_save_list(&self) -> Result<Vec<String>, std::io::Error> {
         use std::fs;
 
-        let saves_dir = "saves";
+        let saves_dir = local_profiles::saves_dir();
 
         // Create saves directory if it doesn't exist
-        fs::create_dir_all(saves_dir)?;
+        fs::create_dir_all(&saves_dir)?;
 
         let mut saves = Vec::new();
 
-        // Read all .sav files in saves directory
+        // Read all .sav files in saves directory, plus JSON exports (listed with their extension)
         for entry in fs::read_dir(saves_dir)? {
             let entry = entry?;
             let path = entry.path();
 
-            if path.extension().and_then(|s| s.to_str()) == Some("sav") {
-                if let Some(file_name) = path.file_stem().and_then(|s| s.to_str()) {
-                    saves.push(file_name.to_string());
+            match path.extension().and_then(|s| s.to_str()) {
+                Some("sav") => {
+                    if let Some(file_name) = path.file_stem().and_then(|s| s.to_str()) {
+                        saves.push(file_name.to_string());
+                    }
+                }
+                Some("json") => {
+                    if let Some(file_name) = path.file_name().and_then(|s| s.to_str()) {
+                        saves.push(file_name.to_string());
+                    }
                 }
+                _ => {}
             }
         }
 
@@ -169,6 +186,11 @@ impl SavesMenu {
             WHITE,
         );
 
+        if let Some(message) = &self.status_message {
+            let dims = measure_text(message, None, 20, 1.0);
+            draw_text(message, self.window_size.x / 2.0 - dims.width / 2.0, 160.0, 20.0, RED);
+        }
+
         // Draw buttons
         self.new_game_button.draw();
 
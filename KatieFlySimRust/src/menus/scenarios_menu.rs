@@ -0,0 +1,157 @@
+// Scenarios Menu - Pick a community challenge from the scenarios/ folder
+// Each entry shows the scenario's description, author and par time
+
+use macroquad::prelude::*;
+
+use crate::systems::Scenario;
+use crate::ui::Button;
+
+/// Result from scenarios menu interaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScenariosMenuResult {
+    None,
+    Start(String), // Path of the chosen scenario file
+    Back,
+}
+
+/// Lists the installed scenarios
+pub struct ScenariosMenu {
+    title_text: String,
+    title_position: Vec2,
+    title_font_size: f32,
+    back_button: Button,
+    scenario_buttons: Vec<Button>,
+    scenario_paths: Vec<String>,
+    scenario_details: Vec<String>,
+    status_message: Option<(String, Color)>, // Why the last scenario couldn't start
+    window_size: Vec2,
+}
+
+impl ScenariosMenu {
+    pub fn new(window_size: Vec2) -> Self {
+        // Title
+        let title_text = "Scenarios".to_string();
+        let title_font_size = 48.0;
+
+        // Calculate title position (centered)
+        let text_dims = measure_text(&title_text, None, title_font_size as u16, 1.0);
+        let title_position = Vec2::new(
+            window_size.x / 2.0 - text_dims.width / 2.0,
+            80.0 + text_dims.height,
+        );
+
+        // Back button
+        let back_button = Button::new(
+            Vec2::new(50.0, window_size.y - 80.0),
+            Vec2::new(150.0, 50.0),
+            "Back",
+            Color::from_rgba(100, 100, 100, 255),
+        );
+
+        ScenariosMenu {
+            title_text,
+            title_position,
+            title_font_size,
+            back_button,
+            scenario_buttons: Vec::new(),
+            scenario_paths: Vec::new(),
+            scenario_details: Vec::new(),
+            status_message: None,
+            window_size,
+        }
+    }
+
+    /// Re-read the scenarios folder and rebuild the buttons
+    pub fn refresh_scenarios(&mut self) {
+        self.scenario_buttons.clear();
+        self.scenario_paths.clear();
+        self.scenario_details.clear();
+        self.status_message = None;
+
+        let button_width = 500.0;
+        let button_height = 60.0;
+        let button_spacing = 100.0;
+        let start_y = 180.0;
+
+        for (i, (path, scenario)) in Scenario::load_all().into_iter().enumerate() {
+            let button = Button::new(
+                Vec2::new(
+                    self.window_size.x / 2.0 - button_width / 2.0,
+                    start_y + (i as f32 * button_spacing),
+                ),
+                Vec2::new(button_width, button_height),
+                &scenario.name,
+                Color::from_rgba(120, 80, 150, 255),
+            );
+
+            let mut details = scenario.description.clone();
+            if !scenario.author.is_empty() {
+                details.push_str(&format!(" - by {}", scenario.author));
+            }
+            if let Some(par) = scenario.par_time {
+                details.push_str(&format!(" (par {:02}:{:02})", par as u32 / 60, par as u32 % 60));
+            }
+
+            self.scenario_buttons.push(button);
+            self.scenario_paths.push(path);
+            self.scenario_details.push(details);
+        }
+    }
+
+    /// Show a line under the title (e.g. why a scenario couldn't start)
+    pub fn set_status(&mut self, message: String, color: Color) {
+        self.status_message = Some((message, color));
+    }
+
+    /// Update menu and handle input
+    pub fn update(&mut self) -> ScenariosMenuResult {
+        let mouse_pressed = is_mouse_button_down(MouseButton::Left);
+
+        if self.back_button.update(mouse_pressed) {
+            return ScenariosMenuResult::Back;
+        }
+
+        for (i, button) in self.scenario_buttons.iter_mut().enumerate() {
+            if button.update(mouse_pressed) {
+                return ScenariosMenuResult::Start(self.scenario_paths[i].clone());
+            }
+        }
+
+        ScenariosMenuResult::None
+    }
+
+    /// Draw the menu
+    pub fn draw(&self) {
+        draw_text(
+            &self.title_text,
+            self.title_position.x,
+            self.title_position.y,
+            self.title_font_size,
+            WHITE,
+        );
+
+        for (i, button) in self.scenario_buttons.iter().enumerate() {
+            button.draw();
+            draw_text(
+                &self.scenario_details[i],
+                button.position().x + 10.0,
+                button.position().y + button.size().y + 20.0,
+                18.0,
+                Color::from_rgba(180, 180, 180, 255),
+            );
+        }
+
+        if self.scenario_buttons.is_empty() {
+            let text = "No scenarios yet - put .ron or .json scenario files in the scenarios folder";
+            let dims = measure_text(text, None, 22, 1.0);
+            draw_text(text, self.window_size.x / 2.0 - dims.width / 2.0, 220.0, 22.0, LIGHTGRAY);
+        }
+
+        if let Some((message, color)) = &self.status_message {
+            let dims = measure_text(message, None, 20, 1.0);
+            draw_text(message, self.window_size.x / 2.0 - dims.width / 2.0, 150.0, 20.0, *color);
+        }
+
+        self.back_button.draw();
+    }
+}
@@ -0,0 +1,49 @@
+// Client Input - The per-frame controls packet a client sends to the host
+// One definition for both ends, so a field added on the client can't go missing on the host
+
+use serde::{Deserialize, Serialize};
+
+/// Client input packet - sent from client to host every frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClientInputPacket {
+    pub player_id: u32,         // The host overwrites this with the ID it assigned the sender's address
+    pub rotation_delta: f32,    // degrees per frame
+    pub thrust_level: f32,      // 0.0 to 1.0
+    pub convert_to_satellite: bool,
+    pub shoot_bullet: bool,       // true if client wants to shoot
+    pub save_requested: bool,     // true if client pressed F5 (quick save)
+    pub refuel_from_planet: bool, // true if client wants to refuel from planet (R key)
+    pub transfer_fuel: bool,      // true while client holds the rocket-to-rocket transfer key
+    pub station_ops: bool,        // true if client pressed the station ops key (6)
+}
+
+impl ClientInputPacket {
+    /// Hands off the controls: no thrust, no turning, no requests
+    pub fn idle(player_id: u32) -> Self {
+        ClientInputPacket {
+            player_id,
+            rotation_delta: 0.0,
+            thrust_level: 0.0,
+            convert_to_satellite: false,
+            shoot_bullet: false,
+            save_requested: false,
+            refuel_from_planet: false,
+            transfer_fuel: false,
+            station_ops: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refuel_request_survives_the_wire() {
+        let packet = ClientInputPacket { refuel_from_planet: true, thrust_level: 0.5, ..ClientInputPacket::idle(3) };
+        let bytes = bincode::serialize(&packet).unwrap();
+        let decoded: ClientInputPacket = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, packet);
+        assert!(decoded.refuel_from_planet);
+    }
+}
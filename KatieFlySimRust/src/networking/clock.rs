@@ -0,0 +1,17 @@
+// Clock - Where the multiplayer sessions read the time (link timing, timeouts, gift expiry)
+// macroquad's clock needs a window, so headless sessions are handed another one
+
+/// Seconds from some fixed start; only differences between readings matter
+pub trait Clock {
+    fn now(&self) -> f64;
+}
+
+/// macroquad's `get_time`, for sessions running inside the game window
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FrameClock;
+
+impl Clock for FrameClock {
+    fn now(&self) -> f64 {
+        macroquad::prelude::get_time()
+    }
+}
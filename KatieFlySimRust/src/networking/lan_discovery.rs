@@ -0,0 +1,189 @@
+// LAN Discovery - Hosts broadcast a small advert on the local network; the join menu listens and lists them
+// Adverts carry the session name, map, player count and game port; the sender's address fills in the rest
+
+use std::io::ErrorKind;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+
+use serde::{Deserialize, Serialize};
+
+use crate::networking::session_browser::SessionListing;
+
+/// UDP port adverts are broadcast to (game traffic is on 7777, RCON on 7778)
+pub const DISCOVERY_PORT: u16 = 7779;
+/// Seconds between adverts (the browser drops sessions after a few silent refreshes)
+pub const ADVERT_INTERVAL: f32 = 1.0;
+/// Prefix marking an advert packet, so stray traffic on the port is ignored
+const ADVERT_PREFIX: &[u8] = b"KFLYLAN1";
+/// Adverts are a few dozen bytes; anything longer isn't one
+const MAX_ADVERT_BYTES: usize = 512;
+
+/// What a host announces about its session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LanAdvert {
+    pub name: String,
+    pub map: String,
+    pub players: u32,
+    pub max_players: u32,
+    pub port: u16, // Game port to connect to
+    pub pvp: bool, // Arena rules
+}
+
+impl LanAdvert {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = ADVERT_PREFIX.to_vec();
+        bytes.extend(bincode::serialize(self).unwrap_or_default());
+        bytes
+    }
+
+    /// None if the bytes aren't an advert
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let payload = bytes.strip_prefix(ADVERT_PREFIX)?;
+        bincode::deserialize(payload).ok()
+    }
+
+    /// Browser listing for an advert heard from `address` (ping isn't measured on the LAN)
+    pub fn listing(&self, address: IpAddr) -> SessionListing {
+        SessionListing {
+            name: self.name.clone(),
+            address: address.to_string(),
+            port: self.port,
+            map: self.map.clone(),
+            players: self.players,
+            max_players: self.max_players,
+            ping_ms: None,
+            pvp: self.pvp,
+            password: false,
+        }
+    }
+}
+
+/// Host side: broadcasts an advert every `ADVERT_INTERVAL`
+pub struct LanAdvertiser {
+    socket: UdpSocket,
+    target: SocketAddr,
+    timer: f32,
+}
+
+impl LanAdvertiser {
+    /// Broadcast to the discovery port on the local network
+    pub fn start() -> Result<Self, String> {
+        Self::start_to(SocketAddr::from((Ipv4Addr::BROADCAST, DISCOVERY_PORT)))
+    }
+
+    /// Send adverts to `target` instead (tests, or a directed broadcast address)
+    pub fn start_to(target: SocketAddr) -> Result<Self, String> {
+        let socket = UdpSocket::bind("0.0.0.0:0").map_err(|e| format!("Failed to bind LAN advert socket: {}", e))?;
+        socket.set_broadcast(true).map_err(|e| format!("Failed to enable broadcast: {}", e))?;
+        socket.set_nonblocking(true).map_err(|e| format!("Failed to set non-blocking mode: {}", e))?;
+        log::info!("Advertising this game on the LAN ({})", target);
+        Ok(LanAdvertiser { socket, target, timer: 0.0 })
+    }
+
+    /// Count down and send an advert when one is due (`advert` is only built then)
+    pub fn update(&mut self, delta_time: f32, advert: impl FnOnce() -> LanAdvert) {
+        self.timer -= delta_time;
+        if self.timer > 0.0 {
+            return;
+        }
+        self.timer = ADVERT_INTERVAL;
+        if let Err(e) = self.socket.send_to(&advert().to_bytes(), self.target) {
+            log::debug!("LAN advert not sent: {}", e);
+        }
+    }
+}
+
+/// Join menu side: listens for adverts on the discovery port
+pub struct LanDiscovery {
+    socket: UdpSocket,
+}
+
+impl LanDiscovery {
+    pub fn start() -> Result<Self, String> {
+        Self::start_on(DISCOVERY_PORT)
+    }
+
+    /// Listen on `port` (0 picks a free one)
+    pub fn start_on(port: u16) -> Result<Self, String> {
+        let socket = UdpSocket::bind(("0.0.0.0", port)).map_err(|e| format!("Failed to bind LAN discovery port {}: {}", port, e))?;
+        socket.set_nonblocking(true).map_err(|e| format!("Failed to set non-blocking mode: {}", e))?;
+        log::info!("Listening for LAN games on UDP port {}", port);
+        Ok(LanDiscovery { socket })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.socket.local_addr().map(|addr| addr.port()).unwrap_or(0)
+    }
+
+    /// Sessions announced since the last poll (call every frame the browser is open)
+    pub fn poll(&self) -> Vec<SessionListing> {
+        let mut listings = Vec::new();
+        let mut buffer = [0u8; MAX_ADVERT_BYTES];
+        loop {
+            match self.socket.recv_from(&mut buffer) {
+                Ok((len, from)) => {
+                    if let Some(advert) = LanAdvert::from_bytes(&buffer[..len]) {
+                        listings.push(advert.listing(from.ip()));
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => {
+                    log::debug!("LAN discovery receive failed: {}", e);
+                    break;
+                }
+            }
+        }
+        listings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn advert() -> LanAdvert {
+        LanAdvert {
+            name: "Katie's game".to_string(),
+            map: "Solar System".to_string(),
+            players: 3,
+            max_players: 20,
+            port: 7777,
+            pvp: true,
+        }
+    }
+
+    #[test]
+    fn test_advert_reaches_browser() {
+        assert_eq!(LanAdvert::from_bytes(&advert().to_bytes()), Some(advert()));
+        assert_eq!(LanAdvert::from_bytes(b"KEEPALIVE"), None);
+
+        let discovery = LanDiscovery::start_on(0).unwrap();
+        let mut advertiser = LanAdvertiser::start_to(SocketAddr::from((Ipv4Addr::LOCALHOST, discovery.port()))).unwrap();
+
+        // The first update sends straight away; the next waits out the interval
+        let mut built = 0;
+        advertiser.update(0.0, || {
+            built += 1;
+            advert()
+        });
+        advertiser.update(ADVERT_INTERVAL / 2.0, || {
+            built += 1;
+            advert()
+        });
+        assert_eq!(built, 1);
+
+        let deadline = Instant::now() + Duration::from_secs(2);
+        let listings = loop {
+            let listings = discovery.poll();
+            if !listings.is_empty() || Instant::now() > deadline {
+                break listings;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        };
+        assert_eq!(listings.len(), 1);
+        assert_eq!(listings[0].address, "127.0.0.1");
+        assert_eq!(listings[0].port, 7777);
+        assert_eq!(listings[0].players, 3);
+        assert!(listings[0].pvp);
+    }
+}
@@ -2,12 +2,26 @@
 // Phases 10-12: Networking infrastructure with tokio async networking
 
 pub mod network_manager;
+pub mod client_input;
 pub mod multiplayer_host;
 pub mod multiplayer_client;
+pub mod rich_presence;
+pub mod rcon;
+pub mod port_check;
+pub mod session_browser;
+pub mod lan_discovery;
+pub mod clock;
 
 pub use network_manager::{
     NetworkManager, NetworkRole, NetworkMessage, NetworkError,
     NetworkStats, PlayerStateData, GameStateSnapshot,
 };
+pub use client_input::ClientInputPacket;
 pub use multiplayer_host::{MultiplayerHost, HostEvent};
 pub use multiplayer_client::{MultiplayerClient, ClientEvent};
+pub use rich_presence::{RichPresence, PresenceInfo};
+pub use rcon::{RconCommand, RconServer};
+pub use port_check::{PortCheck, PortCheckReport, StepOutcome};
+pub use session_browser::{SessionBrowser, SessionFilter, SessionListing, SessionSort};
+pub use lan_discovery::{LanAdvert, LanAdvertiser, LanDiscovery};
+pub use clock::{Clock, FrameClock};
@@ -0,0 +1,398 @@
+// Port Check - "Test my connection" for hosts, run before anyone tries to join
+// Binds the port, connects back over loopback and the LAN address, optionally asks an external echo
+// service to connect in, then turns the results into plain-language firewall/NAT advice
+
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+use crate::networking::rich_presence::lan_address;
+
+/// Environment variable naming an echo service ("host:port") that tests the port from the internet
+/// Protocol: we send "CHECK <port>\n", it tries to connect back and answers "OPEN" or "CLOSED"
+pub const PORT_CHECK_SERVICE_ENV: &str = "KATIE_PORT_CHECK_SERVICE";
+/// How long each local connection attempt may take
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+/// How long the echo service may take to answer
+const SERVICE_TIMEOUT: Duration = Duration::from_secs(8);
+
+/// Why the port couldn't be opened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BindError {
+    InUse,
+    NeedsAdmin,
+    Other(String),
+}
+
+/// Result of asking the echo service to connect in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalCheck {
+    NotConfigured,
+    ServiceUnavailable(String),
+    Reachable,
+    Unreachable,
+}
+
+/// Where an address sits on the network
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressKind {
+    Loopback,
+    Private,      // Home/office network behind a router
+    SharedCgnat,  // Carrier-grade NAT (100.64.0.0/10): the ISP shares one public address
+    Public,
+}
+
+impl AddressKind {
+    pub fn of(ip: IpAddr) -> Self {
+        match ip {
+            IpAddr::V4(v4) if v4.is_loopback() => AddressKind::Loopback,
+            IpAddr::V4(v4) if v4.octets()[0] == 100 && (v4.octets()[1] & 0xC0) == 64 => AddressKind::SharedCgnat,
+            IpAddr::V4(v4) if v4.is_private() || v4.is_link_local() => AddressKind::Private,
+            IpAddr::V6(v6) if v6.is_loopback() => AddressKind::Loopback,
+            IpAddr::V6(v6) if (v6.segments()[0] & 0xFE00) == 0xFC00 || (v6.segments()[0] & 0xFFC0) == 0xFE80 => AddressKind::Private,
+            _ => AddressKind::Public,
+        }
+    }
+}
+
+/// Raw results of the checks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortCheckFindings {
+    pub port: u16,
+    pub bind: Result<(), BindError>,
+    pub loopback: bool,
+    pub lan_address: Option<IpAddr>,
+    pub lan_reachable: bool,
+    pub external: ExternalCheck,
+}
+
+/// Outcome of one line in the report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepOutcome {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+impl StepOutcome {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StepOutcome::Passed => "[ OK ]",
+            StepOutcome::Failed => "[FAIL]",
+            StepOutcome::Skipped => "[ -- ]",
+        }
+    }
+}
+
+/// One check, as shown in the host menu
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckStep {
+    pub name: &'static str,
+    pub outcome: StepOutcome,
+    pub detail: String,
+}
+
+/// Steps and advice for the host menu
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortCheckReport {
+    pub port: u16,
+    pub steps: Vec<CheckStep>,
+    pub advice: Vec<String>,
+}
+
+impl PortCheckReport {
+    /// True when nothing failed (friends on the internet may still need port forwarding)
+    pub fn all_passed(&self) -> bool {
+        self.steps.iter().all(|step| step.outcome != StepOutcome::Failed)
+    }
+
+    pub fn from_findings(findings: &PortCheckFindings) -> Self {
+        let port = findings.port;
+        let mut steps = Vec::new();
+        let mut advice = Vec::new();
+        let step = |name, passed: bool, detail: String| CheckStep {
+            name,
+            outcome: if passed { StepOutcome::Passed } else { StepOutcome::Failed },
+            detail,
+        };
+
+        if let Err(error) = &findings.bind {
+            let (detail, tip) = match error {
+                BindError::InUse => (
+                    "already in use".to_string(),
+                    format!("Port {} is taken by another program, often another copy of the game. Close it or pick a different port.", port),
+                ),
+                BindError::NeedsAdmin => (
+                    "permission denied".to_string(),
+                    "Ports below 1024 need administrator rights. Use a higher port such as 7777.".to_string(),
+                ),
+                BindError::Other(e) => (e.clone(), format!("The game couldn't open port {}: {}", port, e)),
+            };
+            steps.push(step("Open port", false, detail));
+            advice.push(tip);
+            return PortCheckReport { port, steps, advice };
+        }
+        steps.push(step("Open port", true, format!("listening on TCP {}", port)));
+
+        steps.push(step("Connect from this computer", findings.loopback, "127.0.0.1".to_string()));
+        if !findings.loopback {
+            advice.push("This computer couldn't connect to itself. Security software may be blocking the game - allow it through your firewall.".to_string());
+        }
+
+        let Some(lan) = findings.lan_address else {
+            steps.push(CheckStep { name: "Connect over your network", outcome: StepOutcome::Skipped, detail: "no network found".to_string() });
+            advice.push("No network connection was found. Connect to Wi-Fi or a cable before hosting.".to_string());
+            return PortCheckReport { port, steps, advice };
+        };
+        steps.push(step("Connect over your network", findings.lan_reachable, lan.to_string()));
+        if !findings.lan_reachable {
+            advice.push(format!(
+                "Your firewall is blocking other devices. Allow the game (TCP port {}) through it, e.g. Windows Security > Firewall > Allow an app.",
+                port
+            ));
+        }
+
+        match &findings.external {
+            ExternalCheck::NotConfigured => {
+                steps.push(CheckStep { name: "Connect from the internet", outcome: StepOutcome::Skipped, detail: "no echo service".to_string() });
+            }
+            ExternalCheck::ServiceUnavailable(e) => {
+                steps.push(CheckStep { name: "Connect from the internet", outcome: StepOutcome::Skipped, detail: format!("echo service unavailable ({})", e) });
+            }
+            ExternalCheck::Reachable => steps.push(step("Connect from the internet", true, "echo service got through".to_string())),
+            ExternalCheck::Unreachable => steps.push(step("Connect from the internet", false, "echo service couldn't connect".to_string())),
+        }
+
+        match AddressKind::of(lan) {
+            AddressKind::Private => {
+                advice.push(format!("Players on your network can join at {}:{}.", lan, port));
+                if findings.external != ExternalCheck::Reachable {
+                    advice.push(format!(
+                        "You're behind a router (NAT). For friends over the internet, forward TCP port {} on your router to {} and give them your public IP.",
+                        port, lan
+                    ));
+                }
+            }
+            AddressKind::SharedCgnat => advice.push(
+                "Your internet provider shares one public address between customers (carrier-grade NAT), so port forwarding won't help. \
+                 Use a virtual LAN/VPN tool with your friends, or let someone else host."
+                    .to_string(),
+            ),
+            AddressKind::Public | AddressKind::Loopback => {
+                advice.push(format!("This computer has a public address; players can join at {}:{} if no firewall blocks it.", lan, port));
+            }
+        }
+
+        match findings.external {
+            ExternalCheck::Reachable => advice.push("Players on the internet can reach you.".to_string()),
+            ExternalCheck::Unreachable => advice.push(format!(
+                "Port {} is closed from the internet. Check the router's port forwarding and any firewall in between.",
+                port
+            )),
+            _ => advice.push(format!(
+                "Reachability from the internet wasn't tested (set {} to an echo service). Ask a friend to try joining.",
+                PORT_CHECK_SERVICE_ENV
+            )),
+        }
+
+        PortCheckReport { port, steps, advice }
+    }
+}
+
+/// Run every check (blocking - a few seconds at most); `port` 0 tests any free port
+pub fn run_checks(port: u16, echo_service: Option<&str>) -> PortCheckFindings {
+    let mut findings = PortCheckFindings {
+        port,
+        bind: Ok(()),
+        loopback: false,
+        lan_address: None,
+        lan_reachable: false,
+        external: ExternalCheck::NotConfigured,
+    };
+
+    let listener = match TcpListener::bind(("0.0.0.0", port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            findings.bind = Err(match e.kind() {
+                ErrorKind::AddrInUse => BindError::InUse,
+                ErrorKind::PermissionDenied => BindError::NeedsAdmin,
+                _ => BindError::Other(e.to_string()),
+            });
+            return findings;
+        }
+    };
+    if let Ok(address) = listener.local_addr() {
+        findings.port = address.port();
+    }
+    if let Err(e) = listener.set_nonblocking(true) {
+        findings.bind = Err(BindError::Other(e.to_string()));
+        return findings;
+    }
+    let port = findings.port;
+
+    findings.loopback = connects(&listener, SocketAddr::new(Ipv4Addr::LOCALHOST.into(), port));
+    findings.lan_address = lan_address();
+    findings.lan_reachable = findings.lan_address.is_some_and(|ip| connects(&listener, SocketAddr::new(ip, port)));
+    if let Some(service) = echo_service {
+        findings.external = ask_echo_service(&listener, service, port);
+    }
+    findings
+    // The listener closes here so the real host can bind the port
+}
+
+/// Connect to our own listener at `address` and check it accepts
+fn connects(listener: &TcpListener, address: SocketAddr) -> bool {
+    match TcpStream::connect_timeout(&address, CONNECT_TIMEOUT) {
+        Ok(_stream) => accept_within(listener, CONNECT_TIMEOUT),
+        Err(_) => false,
+    }
+}
+
+/// Wait up to `timeout` for an incoming connection on the (non-blocking) listener
+fn accept_within(listener: &TcpListener, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        match listener.accept() {
+            Ok(_) => return true,
+            Err(e) if e.kind() == ErrorKind::WouldBlock && Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            Err(_) => return false,
+        }
+    }
+}
+
+fn ask_echo_service(listener: &TcpListener, service: &str, port: u16) -> ExternalCheck {
+    let unavailable = |e: std::io::Error| ExternalCheck::ServiceUnavailable(e.to_string());
+    let Some(address) = service.to_socket_addrs().ok().and_then(|mut addresses| addresses.next()) else {
+        return ExternalCheck::ServiceUnavailable(format!("can't resolve {}", service));
+    };
+    let mut stream = match TcpStream::connect_timeout(&address, CONNECT_TIMEOUT) {
+        Ok(stream) => stream,
+        Err(e) => return unavailable(e),
+    };
+    if let Err(e) = stream.set_read_timeout(Some(SERVICE_TIMEOUT)).and_then(|_| writeln!(stream, "CHECK {}", port)) {
+        return unavailable(e);
+    }
+
+    let mut reply = String::new();
+    if let Err(e) = BufReader::new(&stream).read_line(&mut reply) {
+        return unavailable(e);
+    }
+    // The service's own connection attempt is waiting in the listener if it got through
+    let got_through = accept_within(listener, Duration::ZERO);
+    match reply.trim() {
+        "OPEN" => ExternalCheck::Reachable,
+        "CLOSED" if got_through => ExternalCheck::Reachable,
+        "CLOSED" => ExternalCheck::Unreachable,
+        other => ExternalCheck::ServiceUnavailable(format!("unexpected reply {:?}", other)),
+    }
+}
+
+/// A connection test running on a background thread
+pub struct PortCheck {
+    receiver: Receiver<PortCheckReport>,
+}
+
+impl PortCheck {
+    /// Start testing `port`, using the echo service from the environment if one is set
+    pub fn start(port: u16) -> Self {
+        let service = std::env::var(PORT_CHECK_SERVICE_ENV).ok().filter(|s| !s.trim().is_empty());
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let findings = run_checks(port, service.as_deref());
+            log::info!("Port check for {}: {:?}", port, findings);
+            let _ = sender.send(PortCheckReport::from_findings(&findings));
+        });
+        PortCheck { receiver }
+    }
+
+    /// The report, once the checks have finished
+    pub fn poll(&self) -> Option<PortCheckReport> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn findings(lan: &str, lan_reachable: bool, external: ExternalCheck) -> PortCheckFindings {
+        PortCheckFindings {
+            port: 7777,
+            bind: Ok(()),
+            loopback: true,
+            lan_address: Some(lan.parse().unwrap()),
+            lan_reachable,
+            external,
+        }
+    }
+
+    #[test]
+    fn test_address_kinds() {
+        assert_eq!(AddressKind::of("192.168.1.20".parse().unwrap()), AddressKind::Private);
+        assert_eq!(AddressKind::of("10.0.0.5".parse().unwrap()), AddressKind::Private);
+        assert_eq!(AddressKind::of("100.72.3.4".parse().unwrap()), AddressKind::SharedCgnat);
+        assert_eq!(AddressKind::of("100.128.0.1".parse().unwrap()), AddressKind::Public);
+        assert_eq!(AddressKind::of("203.0.113.9".parse().unwrap()), AddressKind::Public);
+        assert_eq!(AddressKind::of("fd00::1".parse().unwrap()), AddressKind::Private);
+        assert_eq!(AddressKind::of("127.0.0.1".parse().unwrap()), AddressKind::Loopback);
+    }
+
+    #[test]
+    fn test_report_advice() {
+        // Behind a router with no outside test: LAN address plus port-forwarding advice
+        let report = PortCheckReport::from_findings(&findings("192.168.1.20", true, ExternalCheck::NotConfigured));
+        assert!(report.all_passed());
+        assert!(report.advice.iter().any(|tip| tip.contains("192.168.1.20:7777")));
+        assert!(report.advice.iter().any(|tip| tip.contains("forward TCP port 7777")));
+
+        // Firewall blocking the LAN and the echo service failing
+        let report = PortCheckReport::from_findings(&findings("192.168.1.20", false, ExternalCheck::Unreachable));
+        assert!(!report.all_passed());
+        assert!(report.advice.iter().any(|tip| tip.contains("firewall")));
+
+        // CGNAT: forwarding won't help
+        let report = PortCheckReport::from_findings(&findings("100.72.3.4", true, ExternalCheck::NotConfigured));
+        assert!(report.advice.iter().any(|tip| tip.contains("carrier-grade NAT")));
+
+        // A port that's taken stops the checks early
+        let mut taken = findings("192.168.1.20", true, ExternalCheck::NotConfigured);
+        taken.bind = Err(BindError::InUse);
+        let report = PortCheckReport::from_findings(&taken);
+        assert_eq!(report.steps.len(), 1);
+        assert!(report.advice[0].contains("different port"));
+    }
+
+    #[test]
+    fn test_checks_bind_and_loop_back() {
+        let findings = run_checks(0, None);
+        assert_eq!(findings.bind, Ok(()));
+        assert!(findings.port > 0);
+        assert!(findings.loopback);
+
+        // The listener is released afterwards, and a port in use is reported as such
+        let holder = TcpListener::bind(("0.0.0.0", findings.port)).unwrap();
+        assert_eq!(run_checks(findings.port, None).bind, Err(BindError::InUse));
+        drop(holder);
+    }
+
+    #[test]
+    fn test_echo_service_connects_back() {
+        let service = TcpListener::bind("127.0.0.1:0").unwrap();
+        let service_address = service.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (stream, _) = service.accept().unwrap();
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line).unwrap();
+            let port: u16 = line.trim().strip_prefix("CHECK ").unwrap().parse().unwrap();
+            let open = TcpStream::connect(("127.0.0.1", port)).is_ok();
+            writeln!(&stream, "{}", if open { "OPEN" } else { "CLOSED" }).unwrap();
+        });
+
+        let findings = run_checks(0, Some(&service_address.to_string()));
+        assert_eq!(findings.external, ExternalCheck::Reachable);
+        assert!(matches!(run_checks(0, Some("127.0.0.1:1")).external, ExternalCheck::ServiceUnavailable(_)));
+    }
+}
@@ -0,0 +1,431 @@
+// RCON - Password-protected remote console for managing a running host over TCP
+// Line-based so netcat works: send the password, then one command per line; every reply ends with a blank line
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, Shutdown, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Port the console listens on unless --rcon-port says otherwise
+pub const DEFAULT_RCON_PORT: u16 = 7778;
+/// Environment variable holding the console password (--rcon-password overrides it)
+pub const RCON_PASSWORD_ENV: &str = "KATIE_RCON_PASSWORD";
+/// Wrong passwords allowed before the connection is dropped
+const MAX_AUTH_ATTEMPTS: u32 = 3;
+/// How long a connection waits for the game loop to run its command
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Connections idle this long are closed
+const IDLE_TIMEOUT: Duration = Duration::from_secs(600);
+/// Consoles connected at once; later ones are turned away
+const MAX_CONNECTIONS: usize = 4;
+/// Longest line read from a console (commands and passwords are far shorter)
+const MAX_LINE_BYTES: u64 = 1024;
+/// Wrong passwords from one address, across connections, before it is locked out
+const MAX_FAILED_LOGINS: u32 = 10;
+/// How long a locked-out address is refused
+const LOCKOUT: Duration = Duration::from_secs(300);
+/// How often the accept thread checks whether the server was dropped
+const ACCEPT_POLL: Duration = Duration::from_millis(50);
+
+pub const HELP_TEXT: &str = "Commands:
+  status                    Session time, players and save name
+  list-players              Connected players with IDs and addresses
+  kick <id|name> [reason]   Remove a player for the rest of this session
+  ban <id|name> [reason]    Remove a player and refuse their address for the rest of this session
+  save [name]               Save the game (to the current save unless named)
+  change-map <save|map|new> Switch to a multiplayer save, a fresh world on a map, or a fresh world on the current map
+  broadcast <message>       Show a message to every player
+  help                      This list";
+
+/// A console command, parsed from one line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RconCommand {
+    Help,
+    Status,
+    ListPlayers,
+    Kick { player: String, reason: Option<String> },
+    Ban { player: String, reason: Option<String> },
+    Save { name: Option<String> },
+    ChangeMap { target: String },
+    Broadcast { message: String },
+}
+
+impl RconCommand {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim();
+        let (name, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+        let argument = |usage: &str| if rest.is_empty() { Err(format!("Usage: {}", usage)) } else { Ok(rest.to_string()) };
+
+        match name.to_ascii_lowercase().as_str() {
+            "help" | "?" => Ok(RconCommand::Help),
+            "status" => Ok(RconCommand::Status),
+            "list-players" | "players" => Ok(RconCommand::ListPlayers),
+            "kick" | "ban" => {
+                let args = argument(&format!("{} <id|name> [reason]", name.to_ascii_lowercase()))?;
+                let (player, reason) = args.split_once(char::is_whitespace).unwrap_or((&args, ""));
+                let (player, reason) = (player.to_string(), Some(reason.trim().to_string()).filter(|reason| !reason.is_empty()));
+                if name.eq_ignore_ascii_case("ban") {
+                    Ok(RconCommand::Ban { player, reason })
+                } else {
+                    Ok(RconCommand::Kick { player, reason })
+                }
+            }
+            "save" => Ok(RconCommand::Save { name: Some(rest.to_string()).filter(|name| !name.is_empty()) }),
+            "change-map" => Ok(RconCommand::ChangeMap { target: argument("change-map <save|map|new>")? }),
+            "broadcast" | "say" => Ok(RconCommand::Broadcast { message: argument("broadcast <message>")? }),
+            "" => Err("Empty command".to_string()),
+            other => Err(format!("Unknown command '{}' (try help)", other)),
+        }
+    }
+}
+
+/// A command waiting for the game loop; answer it with `respond`
+pub struct RconRequest {
+    pub command: RconCommand,
+    reply: Sender<Result<String, String>>,
+}
+
+impl RconRequest {
+    pub fn respond(self, result: Result<String, String>) {
+        let _ = self.reply.send(result);
+    }
+}
+
+/// Wrong passwords per address, so reconnecting doesn't reset the count
+#[derive(Debug, Default)]
+struct LoginGuard {
+    failures: HashMap<IpAddr, (u32, Instant)>, // Count and when the last one happened
+}
+
+impl LoginGuard {
+    fn is_locked(&mut self, ip: IpAddr, now: Instant) -> bool {
+        match self.failures.get(&ip) {
+            Some((_, last)) if now.duration_since(*last) >= LOCKOUT => {
+                self.failures.remove(&ip);
+                false
+            }
+            Some((count, _)) => *count >= MAX_FAILED_LOGINS,
+            None => false,
+        }
+    }
+
+    fn record_failure(&mut self, ip: IpAddr, now: Instant) {
+        let entry = self.failures.entry(ip).or_insert((0, now));
+        entry.0 += 1;
+        entry.1 = now;
+    }
+
+    fn record_success(&mut self, ip: IpAddr) {
+        self.failures.remove(&ip);
+    }
+}
+
+/// What the accept thread and every connection share
+struct Shared {
+    password: String,
+    requests: Sender<RconRequest>,
+    logins: Mutex<LoginGuard>,
+    connections: Mutex<HashMap<u64, TcpStream>>, // Open consoles, closed when the server is dropped
+    shutdown: AtomicBool,
+}
+
+/// Listens for console connections on a background thread and queues their commands;
+/// dropping it stops listening and closes every console
+pub struct RconServer {
+    requests: Receiver<RconRequest>,
+    port: u16,
+    shared: Arc<Shared>,
+    accept_thread: Option<JoinHandle<()>>,
+}
+
+impl RconServer {
+    /// Listen on `port` (0 picks a free one); an empty password is refused
+    pub fn start(port: u16, password: String) -> Result<Self, String> {
+        if password.is_empty() {
+            return Err("RCON needs a password".to_string());
+        }
+        let listener = TcpListener::bind(("0.0.0.0", port)).map_err(|e| format!("Failed to bind RCON port {}: {}", port, e))?;
+        let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+        // Polled, so the thread notices when the server is dropped
+        listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+
+        let (request_tx, requests) = mpsc::channel();
+        let shared = Arc::new(Shared {
+            password,
+            requests: request_tx,
+            logins: Mutex::new(LoginGuard::default()),
+            connections: Mutex::new(HashMap::new()),
+            shutdown: AtomicBool::new(false),
+        });
+        let accept_shared = Arc::clone(&shared);
+        let accept_thread = std::thread::spawn(move || accept_connections(listener, accept_shared));
+
+        log::info!("RCON listening on TCP port {}", port);
+        Ok(RconServer { requests, port, shared, accept_thread: Some(accept_thread) })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Next queued command, if any (call from the game loop)
+    pub fn poll(&self) -> Option<RconRequest> {
+        self.requests.try_recv().ok()
+    }
+}
+
+impl Drop for RconServer {
+    fn drop(&mut self) {
+        self.shared.shutdown.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.accept_thread.take() {
+            let _ = thread.join(); // The listener closes with it, freeing the port
+        }
+        for stream in self.shared.connections.lock().unwrap().values() {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+fn accept_connections(listener: TcpListener, shared: Arc<Shared>) {
+    let mut next_id = 0u64;
+    while !shared.shutdown.load(Ordering::SeqCst) {
+        let mut stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(ACCEPT_POLL);
+                continue;
+            }
+            Err(e) => {
+                log::debug!("RCON accept failed: {}", e);
+                continue;
+            }
+        };
+        let peer = stream.peer_addr().map(|addr| addr.to_string()).unwrap_or_default();
+        let clone = match stream.set_nonblocking(false).and_then(|_| stream.try_clone()) {
+            Ok(clone) => clone,
+            Err(e) => {
+                log::debug!("RCON connection from {} failed: {}", peer, e);
+                continue;
+            }
+        };
+        {
+            let mut connections = shared.connections.lock().unwrap();
+            if connections.len() >= MAX_CONNECTIONS {
+                drop(connections);
+                log::warn!("RCON turned away {}: too many consoles connected", peer);
+                let _ = write_reply(&mut stream, &Err("Too many consoles connected".to_string()));
+                continue;
+            }
+            connections.insert(next_id, clone);
+        }
+
+        let id = next_id;
+        next_id += 1;
+        let shared = Arc::clone(&shared);
+        std::thread::spawn(move || {
+            if let Err(e) = serve_connection(stream, &shared) {
+                log::debug!("RCON connection from {} ended: {}", peer, e);
+            }
+            shared.connections.lock().unwrap().remove(&id);
+        });
+    }
+}
+
+/// Compare without stopping at the first differing byte
+fn password_matches(given: &str, expected: &str) -> bool {
+    let (given, expected) = (given.as_bytes(), expected.as_bytes());
+    given.len() == expected.len() && given.iter().zip(expected).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+fn write_reply(stream: &mut TcpStream, result: &Result<String, String>) -> std::io::Result<()> {
+    let text = match result {
+        Ok(text) if text.is_empty() => "OK".to_string(),
+        Ok(text) => format!("OK {}", text),
+        Err(e) => format!("ERR {}", e),
+    };
+    write!(stream, "{}\n\n", text.trim_end())
+}
+
+/// Next line without its ending, or None at end of stream; over-long lines end the connection
+fn read_line(reader: &mut BufReader<TcpStream>) -> std::io::Result<Option<String>> {
+    let mut line = String::new();
+    let read = reader.by_ref().take(MAX_LINE_BYTES + 1).read_line(&mut line)?;
+    if read == 0 {
+        return Ok(None);
+    }
+    if !line.ends_with('\n') && read as u64 > MAX_LINE_BYTES {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "line too long"));
+    }
+    Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+}
+
+fn serve_connection(stream: TcpStream, shared: &Shared) -> std::io::Result<()> {
+    let peer = stream.peer_addr()?;
+    stream.set_read_timeout(Some(IDLE_TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+
+    // Authenticate first (an address with too many wrong passwords waits out its lockout)
+    let mut attempts = 0;
+    loop {
+        if shared.logins.lock().unwrap().is_locked(peer.ip(), Instant::now()) {
+            log::warn!("RCON client {} is locked out after too many wrong passwords", peer);
+            write_reply(&mut writer, &Err("Too many wrong passwords, try again later".to_string()))?;
+            return Ok(());
+        }
+        let Some(line) = read_line(&mut reader)? else {
+            return Ok(());
+        };
+        if password_matches(line.trim(), &shared.password) {
+            shared.logins.lock().unwrap().record_success(peer.ip());
+            log::info!("RCON client {} authenticated", peer);
+            write_reply(&mut writer, &Ok("Authenticated (type help for commands)".to_string()))?;
+            break;
+        }
+        shared.logins.lock().unwrap().record_failure(peer.ip(), Instant::now());
+        attempts += 1;
+        log::warn!("RCON client {} sent a wrong password ({}/{})", peer, attempts, MAX_AUTH_ATTEMPTS);
+        write_reply(&mut writer, &Err("Wrong password".to_string()))?;
+        if attempts >= MAX_AUTH_ATTEMPTS {
+            return Ok(());
+        }
+    }
+
+    while let Some(line) = read_line(&mut reader)? {
+        let result = match RconCommand::parse(&line) {
+            Ok(RconCommand::Help) => Ok(HELP_TEXT.to_string()),
+            Ok(command) => {
+                log::info!("RCON {}: {}", peer, line.trim());
+                let (reply, answer) = mpsc::channel();
+                if shared.requests.send(RconRequest { command, reply }).is_err() {
+                    return Ok(()); // Host is gone
+                }
+                answer
+                    .recv_timeout(REPLY_TIMEOUT)
+                    .unwrap_or_else(|_| Err("Host didn't answer (paused or shutting down?)".to_string()))
+            }
+            Err(e) => Err(e),
+        };
+        write_reply(&mut writer, &result)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(RconCommand::parse("list-players"), Ok(RconCommand::ListPlayers));
+        assert_eq!(
+            RconCommand::parse("kick 3  too fast"),
+            Ok(RconCommand::Kick { player: "3".to_string(), reason: Some("too fast".to_string()) })
+        );
+        assert_eq!(RconCommand::parse("KICK Sam"), Ok(RconCommand::Kick { player: "Sam".to_string(), reason: None }));
+        assert_eq!(
+            RconCommand::parse("ban 4 griefing"),
+            Ok(RconCommand::Ban { player: "4".to_string(), reason: Some("griefing".to_string()) })
+        );
+        assert_eq!(RconCommand::parse("save"), Ok(RconCommand::Save { name: None }));
+        assert_eq!(RconCommand::parse("save nightly"), Ok(RconCommand::Save { name: Some("nightly".to_string()) }));
+        assert_eq!(RconCommand::parse("broadcast  restart in 5 min "), Ok(RconCommand::Broadcast { message: "restart in 5 min".to_string() }));
+        assert!(RconCommand::parse("kick").is_err());
+        assert_eq!(RconCommand::parse("ban"), Err("Usage: ban <id|name> [reason]".to_string()));
+        assert!(RconCommand::parse("change-map").is_err());
+        assert!(RconCommand::parse("teleport 3").is_err());
+    }
+
+    #[test]
+    fn test_password_check() {
+        assert!(password_matches("hunter2", "hunter2"));
+        assert!(!password_matches("hunter3", "hunter2"));
+        assert!(!password_matches("hunter", "hunter2"));
+        assert!(RconServer::start(0, String::new()).is_err());
+    }
+
+    #[test]
+    fn test_wrong_passwords_lock_out_the_address_across_connections() {
+        let ip: IpAddr = "10.0.0.7".parse().unwrap();
+        let other: IpAddr = "10.0.0.8".parse().unwrap();
+        let start = Instant::now();
+        let mut guard = LoginGuard::default();
+        for _ in 0..MAX_FAILED_LOGINS {
+            assert!(!guard.is_locked(ip, start));
+            guard.record_failure(ip, start);
+        }
+        assert!(guard.is_locked(ip, start));
+        assert!(!guard.is_locked(other, start));
+        assert!(!guard.is_locked(ip, start + LOCKOUT));
+
+        guard.record_failure(ip, start);
+        guard.record_success(ip);
+        assert!(!guard.is_locked(ip, start));
+    }
+
+    #[test]
+    fn test_dropping_the_server_frees_the_port() {
+        let server = RconServer::start(0, "secret".to_string()).unwrap();
+        let port = server.port();
+        let console = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        drop(server);
+
+        // The console was closed, and a new host can listen on the same port
+        let mut reply = String::new();
+        console.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        assert_eq!(BufReader::new(console).read_line(&mut reply).unwrap_or(0), 0);
+        assert!(RconServer::start(port, "secret".to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_overlong_lines_end_the_connection() {
+        let server = RconServer::start(0, "secret".to_string()).unwrap();
+        let stream = TcpStream::connect(("127.0.0.1", server.port())).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        let _ = writer.write_all(&vec![b'a'; MAX_LINE_BYTES as usize * 4]);
+
+        let mut reply = String::new();
+        assert_eq!(BufReader::new(stream).read_line(&mut reply).unwrap_or(0), 0);
+    }
+
+    #[test]
+    fn test_commands_reach_the_game_loop() {
+        let server = RconServer::start(0, "secret".to_string()).unwrap();
+        let stream = TcpStream::connect(("127.0.0.1", server.port())).unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut read_reply = || {
+            let mut reply = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    return reply;
+                }
+                reply.push_str(&line);
+            }
+        };
+
+        writeln!(writer, "wrong").unwrap();
+        assert!(read_reply().starts_with("ERR"));
+        writeln!(writer, "secret").unwrap();
+        assert!(read_reply().starts_with("OK"));
+
+        writeln!(writer, "broadcast hello").unwrap();
+        let request = loop {
+            if let Some(request) = server.poll() {
+                break request;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        };
+        assert_eq!(request.command, RconCommand::Broadcast { message: "hello".to_string() });
+        request.respond(Ok("Sent".to_string()));
+        assert_eq!(read_reply(), "OK Sent\n");
+    }
+}
@@ -0,0 +1,311 @@
+// Rich Presence - Optional Discord status (mode, map, altitude, player count) and join-from-Discord
+// The Discord connection only exists with the `discord` feature; without it this is a no-op
+
+use std::net::{IpAddr, UdpSocket};
+
+/// Environment variable holding the Discord application ID
+pub const DISCORD_APP_ID_ENV: &str = "KATIE_DISCORD_APP_ID";
+/// Discord rate-limits presence updates; altitude is only refreshed this often (seconds)
+const PRESENCE_REFRESH_INTERVAL: f32 = 15.0;
+/// Join secrets carry the host address after this prefix
+const JOIN_SECRET_PREFIX: &str = "katiefly-join:";
+
+/// What the player is doing right now
+#[derive(Debug, Clone, PartialEq)]
+pub struct PresenceInfo {
+    pub mode: String,
+    pub map: Option<String>,
+    pub altitude: Option<f32>,
+    pub player_count: u32,
+    pub max_players: u32,
+    pub join_address: Option<String>, // "ip:port" friends can join (hosts only)
+}
+
+impl PresenceInfo {
+    /// Browsing menus (no game running)
+    pub fn menus() -> Self {
+        PresenceInfo {
+            mode: "In menus".to_string(),
+            map: None,
+            altitude: None,
+            player_count: 1,
+            max_players: 1,
+            join_address: None,
+        }
+    }
+
+    /// Top line, e.g. "Single Player - Earth & Moon"
+    pub fn details(&self) -> String {
+        match &self.map {
+            Some(map) => format!("{} - {}", self.mode, map),
+            None => self.mode.clone(),
+        }
+    }
+
+    /// Second line, e.g. "Altitude 1,250 - 3 players"
+    pub fn state(&self) -> String {
+        let players = if self.player_count == 1 {
+            "Solo".to_string()
+        } else {
+            format!("{} players", self.player_count)
+        };
+        match self.altitude {
+            Some(altitude) => format!("Altitude {} - {}", format_altitude(altitude), players),
+            None => players,
+        }
+    }
+
+    /// Same mode, map, players and join address (only altitude differs)
+    fn same_session(&self, other: &PresenceInfo) -> bool {
+        self.mode == other.mode
+            && self.map == other.map
+            && self.player_count == other.player_count
+            && self.join_address == other.join_address
+    }
+}
+
+/// Altitude with thousands separators, rounded to whole units
+fn format_altitude(altitude: f32) -> String {
+    let digits = (altitude.max(0.0).round() as u64).to_string();
+    let mut text = String::new();
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            text.push(',');
+        }
+        text.push(ch);
+    }
+    text
+}
+
+/// Join secret for a host address
+pub fn join_secret(address: &str) -> String {
+    format!("{}{}", JOIN_SECRET_PREFIX, address)
+}
+
+/// Host IP and port from a join secret (None for secrets we didn't create)
+pub fn parse_join_secret(secret: &str) -> Option<(String, u16)> {
+    let address = secret.strip_prefix(JOIN_SECRET_PREFIX)?;
+    let (ip, port) = address.rsplit_once(':')?;
+    let ip: IpAddr = ip.parse().ok()?;
+    Some((ip.to_string(), port.parse().ok()?))
+}
+
+/// Address friends can use to reach a host on `port`
+/// Uses the LAN address of the default route (no packets are sent); friends outside the LAN
+/// still need the host's public address and a forwarded port
+pub fn host_join_address(port: u16) -> Option<String> {
+    Some(format!("{}:{}", lan_address()?, port))
+}
+
+/// This computer's address on the default route's network
+pub fn lan_address() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    Some(socket.local_addr().ok()?.ip())
+}
+
+/// Discord Rich Presence connection (inactive without the feature or an application ID)
+pub struct RichPresence {
+    #[cfg(feature = "discord")]
+    worker: Option<discord::Worker>,
+    last_sent: Option<PresenceInfo>,
+    refresh_timer: f32,
+}
+
+impl RichPresence {
+    pub fn new() -> Self {
+        RichPresence {
+            #[cfg(feature = "discord")]
+            worker: discord::Worker::start(),
+            last_sent: None,
+            refresh_timer: 0.0,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        #[cfg(feature = "discord")]
+        return self.worker.is_some();
+        #[cfg(not(feature = "discord"))]
+        return false;
+    }
+
+    /// Publish the current activity; session changes go out immediately, altitude every few seconds
+    pub fn update(&mut self, delta_time: f32, info: PresenceInfo) {
+        self.refresh_timer += delta_time;
+        let changed = self.last_sent.as_ref().is_none_or(|last| !last.same_session(&info));
+        if !changed && self.refresh_timer < PRESENCE_REFRESH_INTERVAL {
+            return;
+        }
+        self.refresh_timer = 0.0;
+
+        #[cfg(feature = "discord")]
+        if let Some(worker) = &self.worker {
+            worker.publish(info.clone());
+        }
+        self.last_sent = Some(info);
+    }
+
+    /// Host address from a friend accepting a Discord join invite
+    pub fn take_join_request(&mut self) -> Option<(String, u16)> {
+        #[cfg(feature = "discord")]
+        if let Some(worker) = &self.worker {
+            return worker.take_join_secret().and_then(|secret| parse_join_secret(&secret));
+        }
+        None
+    }
+}
+
+impl Default for RichPresence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "discord")]
+mod discord {
+    use super::{join_secret, PresenceInfo, DISCORD_APP_ID_ENV};
+    use discord_rich_presence::activity::{Activity, Party, Secrets, Timestamps};
+    use discord_rich_presence::{DiscordIpc, DiscordIpcClient};
+    use serde_json::json;
+    use std::sync::mpsc::{self, Receiver, Sender};
+
+    /// Background thread that owns the Discord IPC connection
+    /// Join events are read while waiting for each update's reply, so they arrive with the next refresh
+    pub struct Worker {
+        updates: Sender<PresenceInfo>,
+        joins: Receiver<String>,
+    }
+
+    impl Worker {
+        pub fn start() -> Option<Self> {
+            let Ok(app_id) = std::env::var(DISCORD_APP_ID_ENV) else {
+                log::info!("Discord Rich Presence disabled: set {} to enable it", DISCORD_APP_ID_ENV);
+                return None;
+            };
+
+            let (updates, update_rx) = mpsc::channel::<PresenceInfo>();
+            let (join_tx, joins) = mpsc::channel();
+            std::thread::spawn(move || run(app_id, update_rx, join_tx));
+            Some(Worker { updates, joins })
+        }
+
+        pub fn publish(&self, info: PresenceInfo) {
+            let _ = self.updates.send(info);
+        }
+
+        pub fn take_join_secret(&self) -> Option<String> {
+            self.joins.try_recv().ok()
+        }
+    }
+
+    fn run(app_id: String, updates: Receiver<PresenceInfo>, joins: Sender<String>) {
+        let started = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+        let mut client: Option<DiscordIpcClient> = None;
+
+        // Blocks until the next update; ends when the game drops the worker
+        while let Ok(info) = updates.recv() {
+            if client.is_none() {
+                client = connect(&app_id, &joins);
+            }
+            let Some(ipc) = client.as_mut() else {
+                continue; // Discord isn't running; try again with the next update
+            };
+
+            let details = info.details();
+            let state = info.state();
+            let secret = info.join_address.as_deref().map(join_secret);
+            let mut activity = Activity::new()
+                .details(details.as_str())
+                .state(state.as_str())
+                .timestamps(Timestamps::new().start(started));
+            if let Some(secret) = &secret {
+                activity = activity
+                    .party(
+                        Party::new()
+                            .id(secret.as_str())
+                            .size([info.player_count as i32, info.max_players as i32]),
+                    )
+                    .secrets(Secrets::new().join(secret.as_str()));
+            }
+
+            let sent = ipc.set_activity(activity).is_ok() && read_until_reply(ipc, "SET_ACTIVITY", &joins);
+            if !sent {
+                log::warn!("Lost connection to Discord, will reconnect on the next update");
+                let _ = ipc.close();
+                client = None;
+            }
+        }
+    }
+
+    /// Connect and subscribe to join events
+    fn connect(app_id: &str, joins: &Sender<String>) -> Option<DiscordIpcClient> {
+        let mut ipc = DiscordIpcClient::new(app_id);
+        if let Err(e) = ipc.connect() {
+            log::debug!("Discord not available: {}", e);
+            return None;
+        }
+        // The handshake reply (READY) comes first
+        if ipc.recv().is_err() {
+            return None;
+        }
+
+        let subscribe = json!({ "cmd": "SUBSCRIBE", "evt": "ACTIVITY_JOIN", "nonce": "katiefly-subscribe" });
+        if ipc.send(subscribe, 1).is_err() || !read_until_reply(&mut ipc, "SUBSCRIBE", joins) {
+            return None;
+        }
+        log::info!("Connected to Discord Rich Presence");
+        Some(ipc)
+    }
+
+    /// Read frames until the reply to `cmd`, forwarding any join events seen on the way
+    fn read_until_reply(ipc: &mut DiscordIpcClient, cmd: &str, joins: &Sender<String>) -> bool {
+        loop {
+            let Ok((_, frame)) = ipc.recv() else {
+                return false;
+            };
+            if frame["evt"] == "ACTIVITY_JOIN" {
+                if let Some(secret) = frame["data"]["secret"].as_str() {
+                    log::info!("Discord join request received");
+                    let _ = joins.send(secret.to_string());
+                }
+                continue;
+            }
+            if frame["cmd"] == cmd {
+                return true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_secret_round_trip() {
+        let secret = join_secret("192.168.1.20:7777");
+        assert_eq!(parse_join_secret(&secret), Some(("192.168.1.20".to_string(), 7777)));
+
+        assert_eq!(parse_join_secret("192.168.1.20:7777"), None);
+        assert_eq!(parse_join_secret("katiefly-join:not-an-ip:7777"), None);
+        assert_eq!(parse_join_secret("katiefly-join:10.0.0.1:99999"), None);
+    }
+
+    #[test]
+    fn test_presence_text() {
+        let info = PresenceInfo {
+            mode: "Hosting Online".to_string(),
+            map: Some("Earth & Moon".to_string()),
+            altitude: Some(1234567.4),
+            player_count: 3,
+            max_players: 20,
+            join_address: None,
+        };
+        assert_eq!(info.details(), "Hosting Online - Earth & Moon");
+        assert_eq!(info.state(), "Altitude 1,234,567 - 3 players");
+        assert_eq!(PresenceInfo::menus().state(), "Solo");
+    }
+}
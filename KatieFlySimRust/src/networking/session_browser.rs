@@ -0,0 +1,302 @@
+// Session Browser - Hosted sessions found by discovery, with the join menu's filters and sort order
+// Discovery feeds listings in with `upsert`; listings not re-announced for a while drop out on their own
+
+use std::cmp::Ordering;
+
+/// Seconds between refresh requests to discovery
+pub const REFRESH_INTERVAL: f32 = 5.0;
+/// Listings not heard from for this many refreshes are dropped
+const STALE_REFRESHES: f32 = 3.0;
+
+/// One hosted session
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionListing {
+    pub name: String,
+    pub address: String,
+    pub port: u16,
+    pub map: String,
+    pub players: u32,
+    pub max_players: u32,
+    pub ping_ms: Option<u32>, // None until measured
+    pub pvp: bool,
+    pub password: bool,
+}
+
+impl SessionListing {
+    pub fn is_full(&self) -> bool {
+        self.max_players > 0 && self.players >= self.max_players
+    }
+}
+
+/// Yes/no filter that can also be switched off
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlagFilter {
+    #[default]
+    Any,
+    Only,
+    Hide,
+}
+
+impl FlagFilter {
+    pub fn accepts(self, flag: bool) -> bool {
+        match self {
+            FlagFilter::Any => true,
+            FlagFilter::Only => flag,
+            FlagFilter::Hide => !flag,
+        }
+    }
+
+    /// Any -> Only -> Hide -> Any
+    pub fn next(self) -> Self {
+        match self {
+            FlagFilter::Any => FlagFilter::Only,
+            FlagFilter::Only => FlagFilter::Hide,
+            FlagFilter::Hide => FlagFilter::Any,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FlagFilter::Any => "any",
+            FlagFilter::Only => "only",
+            FlagFilter::Hide => "hide",
+        }
+    }
+}
+
+/// Ping limits the ping filter cycles through (None is no limit)
+pub const PING_LIMITS: [Option<u32>; 4] = [None, Some(50), Some(100), Some(200)];
+
+/// What the browser shows
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SessionFilter {
+    pub map: Option<String>,
+    pub hide_full: bool,
+    pub hide_empty: bool,
+    pub max_ping: Option<u32>, // Unmeasured pings pass
+    pub pvp: FlagFilter,
+    pub password: FlagFilter,
+}
+
+impl SessionFilter {
+    pub fn matches(&self, listing: &SessionListing) -> bool {
+        self.map.as_ref().is_none_or(|map| listing.map.eq_ignore_ascii_case(map))
+            && !(self.hide_full && listing.is_full())
+            && !(self.hide_empty && listing.players == 0)
+            && self.max_ping.is_none_or(|limit| listing.ping_ms.is_none_or(|ping| ping <= limit))
+            && self.pvp.accepts(listing.pvp)
+            && self.password.accepts(listing.password)
+    }
+}
+
+/// Column the browser is sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SessionSort {
+    Name,
+    Map,
+    Players,
+    #[default]
+    Ping,
+}
+
+impl SessionSort {
+    /// Table columns, in order
+    pub const COLUMNS: [SessionSort; 4] = [SessionSort::Name, SessionSort::Map, SessionSort::Players, SessionSort::Ping];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            SessionSort::Name => "Session",
+            SessionSort::Map => "Map",
+            SessionSort::Players => "Players",
+            SessionSort::Ping => "Ping",
+        }
+    }
+
+    fn compare(self, a: &SessionListing, b: &SessionListing) -> Ordering {
+        match self {
+            SessionSort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SessionSort::Map => a.map.to_lowercase().cmp(&b.map.to_lowercase()),
+            SessionSort::Players => a.players.cmp(&b.players),
+            // Unmeasured pings sort last
+            SessionSort::Ping => a.ping_ms.unwrap_or(u32::MAX).cmp(&b.ping_ms.unwrap_or(u32::MAX)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    listing: SessionListing,
+    age: f32, // Seconds since discovery last reported it
+}
+
+/// Known sessions plus the current filter and sort
+#[derive(Debug, Clone, Default)]
+pub struct SessionBrowser {
+    entries: Vec<Entry>,
+    pub filter: SessionFilter,
+    sort: SessionSort,
+    descending: bool,
+    refresh_timer: f32,
+}
+
+impl SessionBrowser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or update a session (matched by address and port)
+    pub fn upsert(&mut self, listing: SessionListing) {
+        match self.entries.iter_mut().find(|e| e.listing.address == listing.address && e.listing.port == listing.port) {
+            Some(entry) => *entry = Entry { listing, age: 0.0 },
+            None => self.entries.push(Entry { listing, age: 0.0 }),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Age listings and drop stale ones; true when it's time to ask discovery for a refresh
+    pub fn update(&mut self, delta_time: f32) -> bool {
+        for entry in &mut self.entries {
+            entry.age += delta_time;
+        }
+        self.entries.retain(|entry| entry.age < REFRESH_INTERVAL * STALE_REFRESHES);
+
+        self.refresh_timer -= delta_time;
+        if self.refresh_timer <= 0.0 {
+            self.refresh_timer = REFRESH_INTERVAL;
+            return true;
+        }
+        false
+    }
+
+    /// Refresh on the next update
+    pub fn refresh_now(&mut self) {
+        self.refresh_timer = 0.0;
+    }
+
+    pub fn sort(&self) -> (SessionSort, bool) {
+        (self.sort, self.descending)
+    }
+
+    /// Sort by `column`; picking the current column again flips the direction
+    pub fn sort_by(&mut self, column: SessionSort) {
+        if self.sort == column {
+            self.descending = !self.descending;
+        } else {
+            self.sort = column;
+            self.descending = column == SessionSort::Players; // Busiest sessions first
+        }
+    }
+
+    /// Maps of the known sessions, for the map filter
+    pub fn maps(&self) -> Vec<String> {
+        let mut maps: Vec<String> = self.entries.iter().map(|e| e.listing.map.clone()).collect();
+        maps.sort_by_key(|map| map.to_lowercase());
+        maps.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+        maps
+    }
+
+    /// Sessions passing the filter, in sort order (ties by name)
+    pub fn visible(&self) -> Vec<&SessionListing> {
+        let mut listings: Vec<&SessionListing> =
+            self.entries.iter().map(|e| &e.listing).filter(|listing| self.filter.matches(listing)).collect();
+        listings.sort_by(|a, b| {
+            let order = self.sort.compare(a, b);
+            let order = if self.descending { order.reverse() } else { order };
+            order.then_with(|| SessionSort::Name.compare(a, b))
+        });
+        listings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listing(name: &str, map: &str, players: u32, ping_ms: Option<u32>, pvp: bool, password: bool) -> SessionListing {
+        SessionListing {
+            name: name.to_string(),
+            address: format!("192.168.1.{}", name.len()),
+            port: 7777,
+            map: map.to_string(),
+            players,
+            max_players: 4,
+            ping_ms,
+            pvp,
+            password,
+        }
+    }
+
+    fn browser() -> SessionBrowser {
+        let mut browser = SessionBrowser::new();
+        browser.upsert(listing("Alpha", "Solar System", 4, Some(30), true, false));
+        browser.upsert(listing("Bravo!", "Binary", 1, Some(150), false, true));
+        browser.upsert(listing("Charlie", "solar system", 0, None, false, false));
+        browser
+    }
+
+    fn names(browser: &SessionBrowser) -> Vec<&str> {
+        browser.visible().iter().map(|l| l.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_filters() {
+        let mut browser = browser();
+        assert_eq!(browser.maps(), vec!["Binary".to_string(), "Solar System".to_string()]);
+
+        browser.filter.map = Some("Solar System".to_string());
+        assert_eq!(names(&browser), vec!["Alpha", "Charlie"]);
+
+        browser.filter = SessionFilter { hide_full: true, hide_empty: true, ..Default::default() };
+        assert_eq!(names(&browser), vec!["Bravo!"]);
+
+        // Unmeasured pings pass the ping limit
+        browser.filter = SessionFilter { max_ping: Some(100), ..Default::default() };
+        assert_eq!(names(&browser), vec!["Alpha", "Charlie"]);
+
+        browser.filter = SessionFilter { pvp: FlagFilter::Only, ..Default::default() };
+        assert_eq!(names(&browser), vec!["Alpha"]);
+        browser.filter = SessionFilter { password: FlagFilter::Hide, ..Default::default() };
+        assert_eq!(names(&browser), vec!["Alpha", "Charlie"]);
+    }
+
+    #[test]
+    fn test_sorting() {
+        let mut browser = browser();
+        // Default: lowest ping first, unmeasured last
+        assert_eq!(names(&browser), vec!["Alpha", "Bravo!", "Charlie"]);
+
+        browser.sort_by(SessionSort::Players);
+        assert_eq!(names(&browser), vec!["Alpha", "Bravo!", "Charlie"]);
+        browser.sort_by(SessionSort::Players);
+        assert_eq!(names(&browser), vec!["Charlie", "Bravo!", "Alpha"]);
+
+        browser.sort_by(SessionSort::Map);
+        assert_eq!(browser.sort(), (SessionSort::Map, false));
+        assert_eq!(names(&browser), vec!["Bravo!", "Alpha", "Charlie"]);
+    }
+
+    #[test]
+    fn test_auto_refresh_and_stale_listings() {
+        let mut browser = browser();
+        assert!(browser.update(0.0)); // First update asks straight away
+        assert!(!browser.update(1.0));
+        assert!(browser.update(REFRESH_INTERVAL));
+
+        // Re-announced sessions stay; silent ones drop out
+        browser.upsert(listing("Alpha", "Solar System", 3, Some(30), true, false));
+        browser.update(REFRESH_INTERVAL * STALE_REFRESHES - REFRESH_INTERVAL - 1.0);
+        assert_eq!(names(&browser), vec!["Alpha"]);
+        assert_eq!(browser.visible()[0].players, 3);
+    }
+}
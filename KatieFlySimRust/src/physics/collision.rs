@@ -0,0 +1,209 @@
+// Collision - Impulse-based collision response between vehicles
+// Restitution 1.0 conserves kinetic energy; lower values absorb some of it as damage
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Session rules for rocket-rocket and rocket-satellite collisions
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CollisionRules {
+    pub enabled: bool,
+    pub restitution: f32,            // 1.0 = elastic, 0.0 = perfectly inelastic
+    pub damage_threshold_speed: f32, // Closing speeds below this do no damage (m/s)
+    pub damage_per_speed: f32,       // Hull damage per m/s of closing speed above the threshold
+}
+
+impl CollisionRules {
+    /// Vehicles pass through each other (used by clients; the host resolves collisions)
+    pub fn disabled() -> Self {
+        CollisionRules {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+
+    /// Hull damage dealt to each vehicle for an impact at the given closing speed
+    pub fn damage_for_impact(&self, closing_speed: f32) -> f32 {
+        (closing_speed - self.damage_threshold_speed).max(0.0) * self.damage_per_speed
+    }
+}
+
+impl Default for CollisionRules {
+    fn default() -> Self {
+        CollisionRules {
+            enabled: true,
+            restitution: 0.8,
+            damage_threshold_speed: 20.0,
+            damage_per_speed: 1.0,
+        }
+    }
+}
+
+/// Result of resolving one contact between two bodies
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollisionResponse {
+    pub velocity_a: Vec2,
+    pub velocity_b: Vec2,
+    pub correction_a: Vec2, // Position offsets that separate the overlapping bodies
+    pub correction_b: Vec2,
+    pub closing_speed: f32, // Normal approach speed before the impact
+}
+
+/// Resolve a collision between two circles
+/// Returns None if they don't overlap or are already separating
+#[allow(clippy::too_many_arguments)]
+pub fn resolve_collision(
+    pos_a: Vec2,
+    vel_a: Vec2,
+    mass_a: f32,
+    radius_a: f32,
+    pos_b: Vec2,
+    vel_b: Vec2,
+    mass_b: f32,
+    radius_b: f32,
+    restitution: f32,
+) -> Option<CollisionResponse> {
+    let offset = pos_b - pos_a;
+    let distance = offset.length();
+    let overlap = radius_a + radius_b - distance;
+    if overlap <= 0.0 {
+        return None;
+    }
+
+    // Contact normal from A to B (arbitrary if perfectly stacked)
+    let normal = if distance > 0.0 { offset / distance } else { Vec2::X };
+    let closing_speed = (vel_a - vel_b).dot(normal);
+    if closing_speed <= 0.0 {
+        return None;
+    }
+
+    let inv_mass_a = 1.0 / mass_a;
+    let inv_mass_b = 1.0 / mass_b;
+    let inv_mass_sum = inv_mass_a + inv_mass_b;
+
+    // Impulse along the normal: j = (1 + e) * v_closing / (1/ma + 1/mb)
+    let impulse = (1.0 + restitution.clamp(0.0, 1.0)) * closing_speed / inv_mass_sum;
+
+    // Split the overlap by inverse mass so lighter bodies get pushed further
+    let correction = normal * (overlap / inv_mass_sum);
+
+    Some(CollisionResponse {
+        velocity_a: vel_a - normal * (impulse * inv_mass_a),
+        velocity_b: vel_b + normal * (impulse * inv_mass_b),
+        correction_a: -correction * inv_mass_a,
+        correction_b: correction * inv_mass_b,
+        closing_speed,
+    })
+}
+
+/// Earliest fraction of a tick (0..=1) at which two circles moving in straight lines touch
+/// Catches fast bodies that would skip through each other between ticks; None if they never touch
+pub fn swept_circle_hit(
+    start_a: Vec2,
+    end_a: Vec2,
+    start_b: Vec2,
+    end_b: Vec2,
+    combined_radius: f32,
+) -> Option<f32> {
+    // Solve |offset + motion * t| = r for the smaller root, with motion relative to B
+    let offset = start_a - start_b;
+    let motion = (end_a - start_a) - (end_b - start_b);
+    let c = offset.length_squared() - combined_radius * combined_radius;
+    if c <= 0.0 {
+        return Some(0.0); // Already touching at the start of the tick
+    }
+    let a = motion.length_squared();
+    let b = 2.0 * offset.dot(motion);
+    if a <= f32::EPSILON || b >= 0.0 {
+        return None; // Not moving relative to each other, or moving apart
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let t = (-b - discriminant.sqrt()) / (2.0 * a);
+    (t <= 1.0).then_some(t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinetic_energy(mass: f32, velocity: Vec2) -> f32 {
+        0.5 * mass * velocity.length_squared()
+    }
+
+    #[test]
+    fn test_elastic_collision_conserves_momentum_and_energy() {
+        let (mass_a, mass_b) = (2.0, 5.0);
+        let (vel_a, vel_b) = (Vec2::new(30.0, 5.0), Vec2::new(-10.0, 0.0));
+        let response = resolve_collision(
+            Vec2::ZERO, vel_a, mass_a, 12.0,
+            Vec2::new(20.0, 0.0), vel_b, mass_b, 12.0,
+            1.0,
+        )
+        .unwrap();
+
+        let momentum_before = vel_a * mass_a + vel_b * mass_b;
+        let momentum_after = response.velocity_a * mass_a + response.velocity_b * mass_b;
+        assert!((momentum_before - momentum_after).length() < 1e-3);
+
+        let energy_before = kinetic_energy(mass_a, vel_a) + kinetic_energy(mass_b, vel_b);
+        let energy_after = kinetic_energy(mass_a, response.velocity_a) + kinetic_energy(mass_b, response.velocity_b);
+        assert!((energy_before - energy_after).abs() < 1e-2);
+        assert!((response.closing_speed - 40.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_inelastic_collision_matches_velocities() {
+        let response = resolve_collision(
+            Vec2::ZERO, Vec2::new(10.0, 0.0), 1.0, 12.0,
+            Vec2::new(20.0, 0.0), Vec2::ZERO, 1.0, 12.0,
+            0.0,
+        )
+        .unwrap();
+        assert!((response.velocity_a - Vec2::new(5.0, 0.0)).length() < 1e-4);
+        assert!((response.velocity_b - Vec2::new(5.0, 0.0)).length() < 1e-4);
+    }
+
+    #[test]
+    fn test_separating_or_distant_bodies_ignored() {
+        // Overlapping but moving apart
+        assert!(resolve_collision(
+            Vec2::ZERO, Vec2::new(-10.0, 0.0), 1.0, 12.0,
+            Vec2::new(20.0, 0.0), Vec2::ZERO, 1.0, 12.0,
+            1.0,
+        )
+        .is_none());
+        // Approaching but not touching
+        assert!(resolve_collision(
+            Vec2::ZERO, Vec2::new(10.0, 0.0), 1.0, 12.0,
+            Vec2::new(100.0, 0.0), Vec2::ZERO, 1.0, 12.0,
+            1.0,
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_damage_threshold() {
+        let rules = CollisionRules::default();
+        assert_eq!(rules.damage_for_impact(10.0), 0.0);
+        assert!((rules.damage_for_impact(rules.damage_threshold_speed + 30.0) - 30.0 * rules.damage_per_speed).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_swept_hit_catches_tunneling() {
+        // A bullet jumping from one side of a small target to the other in one tick
+        let t = swept_circle_hit(Vec2::new(-100.0, 0.0), Vec2::new(100.0, 0.0), Vec2::ZERO, Vec2::ZERO, 10.0).unwrap();
+        assert!((t - 0.45).abs() < 1e-4);
+
+        // Passing beside it, stopping short of it, or moving away never touches
+        assert_eq!(swept_circle_hit(Vec2::new(-100.0, 20.0), Vec2::new(100.0, 20.0), Vec2::ZERO, Vec2::ZERO, 10.0), None);
+        assert_eq!(swept_circle_hit(Vec2::new(-100.0, 0.0), Vec2::new(-50.0, 0.0), Vec2::ZERO, Vec2::ZERO, 10.0), None);
+        assert_eq!(swept_circle_hit(Vec2::new(-20.0, 0.0), Vec2::new(-200.0, 0.0), Vec2::ZERO, Vec2::ZERO, 10.0), None);
+
+        // Motion is relative: a target flying into a resting bullet still hits
+        assert!(swept_circle_hit(Vec2::ZERO, Vec2::ZERO, Vec2::new(100.0, 0.0), Vec2::new(-100.0, 0.0), 5.0).is_some());
+        assert_eq!(swept_circle_hit(Vec2::ZERO, Vec2::ZERO, Vec2::new(3.0, 0.0), Vec2::new(3.0, 0.0), 5.0), Some(0.0));
+    }
+}
@@ -2,15 +2,86 @@
 // Ported from C++ GravitySimulator class
 
 use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::entities::{GameObject, Planet, Rocket, Satellite};
 use crate::game_constants::GameConstants;
 use crate::utils::vector_helper;
 
+/// Minimum distance to prevent extreme forces (increased for large scaled planets)
+/// This prevents jittering when very close to massive bodies
+pub const MIN_DISTANCE: f32 = 20.0;
+
+/// How a planet's pull on rockets, satellites and bullets falls off with distance (chosen per map)
+/// Planet-to-planet gravity stays inverse-square so calculated moon orbits remain valid
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum GravityModel {
+    /// Inverse-square everywhere
+    #[default]
+    Realistic,
+    /// Constant gravity just above the surface, then a steeper falloff
+    Arcade,
+    /// Falls off as 1/r^exponent, matching inverse-square at the surface
+    Custom { exponent: f32 },
+}
+
+impl GravityModel {
+    pub fn name(&self) -> String {
+        match self {
+            GravityModel::Realistic => "Realistic".to_string(),
+            GravityModel::Arcade => "Arcade".to_string(),
+            GravityModel::Custom { exponent } => format!("Custom (1/r^{:.1})", exponent),
+        }
+    }
+
+    /// Acceleration towards a body with gravitational parameter `mu` and surface `radius`, at `distance`
+    pub fn acceleration(&self, mu: f32, distance: f32, radius: f32) -> f32 {
+        let surface = radius.max(1.0);
+        match self {
+            GravityModel::Realistic => mu / (distance * distance),
+            GravityModel::Arcade => {
+                let distance = distance.max(surface * GameConstants::ARCADE_GRAVITY_FLAT_BAND);
+                mu / (surface * surface) * (surface / distance).powf(GameConstants::ARCADE_GRAVITY_EXPONENT)
+            }
+            GravityModel::Custom { exponent } => {
+                mu / (surface * surface) * (surface / distance).powf(*exponent)
+            }
+        }
+    }
+
+    /// Potential energy per unit mass at `distance`, measured from the surface (the integral of `acceleration`)
+    pub fn potential(&self, mu: f32, distance: f32, radius: f32) -> f64 {
+        let (mu, distance, surface) = (mu as f64, distance as f64, radius.max(1.0) as f64);
+        let surface_gravity = mu / (surface * surface);
+        // Integral of surface_gravity * (surface / s)^exponent for s from `from` to `to`
+        let falloff = |exponent: f64, from: f64, to: f64| {
+            if (exponent - 1.0).abs() < 1.0e-6 {
+                surface_gravity * surface * (to / from).ln()
+            } else {
+                surface_gravity * surface.powf(exponent) * (to.powf(1.0 - exponent) - from.powf(1.0 - exponent)) / (1.0 - exponent)
+            }
+        };
+        match self {
+            GravityModel::Realistic => mu / surface - mu / distance,
+            GravityModel::Arcade => {
+                let band = surface * GameConstants::ARCADE_GRAVITY_FLAT_BAND as f64;
+                let flat_gravity = surface_gravity * (surface / band).powf(GameConstants::ARCADE_GRAVITY_EXPONENT as f64);
+                if distance <= band {
+                    flat_gravity * (distance - surface)
+                } else {
+                    flat_gravity * (band - surface) + falloff(GameConstants::ARCADE_GRAVITY_EXPONENT as f64, band, distance)
+                }
+            }
+            GravityModel::Custom { exponent } => falloff(*exponent as f64, surface, distance),
+        }
+    }
+}
+
 /// Gravity simulator that applies gravitational forces between all objects
 pub struct GravitySimulator {
     g: f32,
     simulate_planet_gravity: bool,
+    model: GravityModel,
 }
 
 impl GravitySimulator {
@@ -18,6 +89,7 @@ impl GravitySimulator {
         GravitySimulator {
             g: GameConstants::G,
             simulate_planet_gravity: true,
+            model: GravityModel::Realistic,
         }
     }
 
@@ -25,6 +97,14 @@ impl GravitySimulator {
         self.simulate_planet_gravity = enable;
     }
 
+    pub fn model(&self) -> GravityModel {
+        self.model
+    }
+
+    pub fn set_model(&mut self, model: GravityModel) {
+        self.model = model;
+    }
+
     /// Apply gravity from all planets to a rocket
     pub fn apply_planet_gravity_to_rocket(
         &self,
@@ -37,11 +117,12 @@ impl GravitySimulator {
         }
 
         for planet in planets {
-            let force = self.calculate_gravitational_force(
+            let force = self.calculate_body_gravity(
                 rocket.position(),
                 rocket.mass(),
                 planet.position(),
                 planet.mass(),
+                planet.radius(),
             );
 
             let acceleration = force / rocket.mass();
@@ -61,11 +142,12 @@ impl GravitySimulator {
         }
 
         for planet in planets {
-            let force = self.calculate_gravitational_force(
+            let force = self.calculate_body_gravity(
                 satellite.position(),
                 satellite.mass(),
                 planet.position(),
                 planet.mass(),
+                planet.radius(),
             );
 
             let acceleration = force / satellite.mass();
@@ -131,9 +213,6 @@ impl GravitySimulator {
         let direction = pos2 - pos1;
         let mut distance = vector_helper::magnitude(direction);
 
-        // Minimum distance to prevent extreme forces (increased for large scaled planets)
-        // This prevents jittering when very close to massive bodies
-        const MIN_DISTANCE: f32 = 20.0;
         if distance < MIN_DISTANCE {
             distance = MIN_DISTANCE;
         }
@@ -145,6 +224,27 @@ impl GravitySimulator {
         vector_helper::normalize(direction) * force_magnitude
     }
 
+    /// Force a planet of `body_mass` and `body_radius` exerts on a small object, following the gravity model
+    /// Returns the force vector applied to the object
+    pub fn calculate_body_gravity(
+        &self,
+        pos: Vec2,
+        mass: f32,
+        body_pos: Vec2,
+        body_mass: f32,
+        body_radius: f32,
+    ) -> Vec2 {
+        if self.model == GravityModel::Realistic {
+            return self.calculate_gravitational_force(pos, mass, body_pos, body_mass);
+        }
+
+        let direction = body_pos - pos;
+        let distance = vector_helper::magnitude(direction).max(MIN_DISTANCE);
+        let acceleration = self.model.acceleration(self.g * body_mass, distance, body_radius);
+
+        vector_helper::normalize(direction) * acceleration * mass
+    }
+
     /// Calculate orbital velocity for a circular orbit
     pub fn calculate_circular_orbit_velocity(
         &self,
@@ -273,6 +373,88 @@ pub mod orbital {
             f32::INFINITY
         }
     }
+
+    /// Bound (elliptical) orbit fitted from a state vector, in world coordinates
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct OrbitEllipse {
+        pub center: Vec2,
+        pub semi_major_axis: f32,
+        pub semi_minor_axis: f32,
+        pub eccentricity: f32,
+        pub periapsis_direction: Vec2, // Unit vector from focus towards periapsis
+    }
+
+    impl OrbitEllipse {
+        /// Sample points around the ellipse (closed loop: last point equals first)
+        pub fn points(&self, segments: usize) -> Vec<Vec2> {
+            let segments = segments.max(3);
+            let major = self.periapsis_direction;
+            let minor = Vec2::new(-major.y, major.x);
+
+            (0..=segments)
+                .map(|i| {
+                    let t = i as f32 / segments as f32 * std::f32::consts::TAU;
+                    self.center
+                        + major * (self.semi_major_axis * t.cos())
+                        + minor * (self.semi_minor_axis * t.sin())
+                })
+                .collect()
+        }
+    }
+
+    /// Fit the osculating ellipse of a body orbiting `body_pos`
+    /// Returns None for escape (parabolic/hyperbolic) trajectories
+    pub fn fit_orbit_ellipse(
+        position: Vec2,
+        velocity: Vec2,
+        body_pos: Vec2,
+        body_velocity: Vec2,
+        body_mass: f32,
+        g: f32,
+    ) -> Option<OrbitEllipse> {
+        let r = position - body_pos;
+        let v = velocity - body_velocity;
+        let r_len = r.length();
+        let mu = g * body_mass;
+
+        if r_len < 0.01 || mu <= 0.0 {
+            return None;
+        }
+
+        // Specific orbital energy: E = v^2/2 - GM/r
+        let energy = v.length_squared() / 2.0 - mu / r_len;
+        if energy >= 0.0 {
+            return None;
+        }
+
+        // Eccentricity vector: e = ((v^2 - GM/r) r - (r.v) v) / GM
+        let e_vec = (r * (v.length_squared() - mu / r_len) - v * r.dot(v)) / mu;
+        let eccentricity = e_vec.length();
+        if eccentricity >= 1.0 {
+            return None;
+        }
+
+        let semi_major_axis = -mu / (2.0 * energy);
+        let semi_minor_axis = semi_major_axis * (1.0 - eccentricity * eccentricity).sqrt();
+
+        // Near-circular orbits have no well-defined periapsis - any axis will do
+        let periapsis_direction = if eccentricity > 1e-4 {
+            e_vec / eccentricity
+        } else {
+            r / r_len
+        };
+
+        // Focus sits at the body; ellipse center is offset away from periapsis by a*e
+        let center = body_pos - periapsis_direction * (semi_major_axis * eccentricity);
+
+        Some(OrbitEllipse {
+            center,
+            semi_major_axis,
+            semi_minor_axis,
+            eccentricity,
+            periapsis_direction,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -280,6 +462,34 @@ mod tests {
     use super::*;
     use approx::assert_relative_eq;
 
+    #[test]
+    fn test_gravity_models_agree_at_the_surface() {
+        let mu = GameConstants::G * 1_000_000.0;
+        let radius = 500.0;
+        let realistic = GravityModel::Realistic.acceleration(mu, radius, radius);
+        let custom = GravityModel::Custom { exponent: 3.0 }.acceleration(mu, radius, radius);
+        assert_relative_eq!(realistic, custom, epsilon = 1e-3);
+
+        // Steeper falloff further out, and exponent 2 is inverse-square
+        let far = radius * 4.0;
+        assert!(GravityModel::Custom { exponent: 3.0 }.acceleration(mu, far, radius) < GravityModel::Realistic.acceleration(mu, far, radius));
+        assert_relative_eq!(
+            GravityModel::Custom { exponent: 2.0 }.acceleration(mu, far, radius),
+            GravityModel::Realistic.acceleration(mu, far, radius),
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn test_arcade_gravity_is_flat_near_the_surface() {
+        let mu = GameConstants::G * 1_000_000.0;
+        let radius = 500.0;
+        let at_surface = GravityModel::Arcade.acceleration(mu, radius, radius);
+        let just_above = GravityModel::Arcade.acceleration(mu, radius * 1.1, radius);
+        assert_relative_eq!(at_surface, just_above, epsilon = 1e-3);
+        assert!(GravityModel::Arcade.acceleration(mu, radius * 3.0, radius) < at_surface);
+    }
+
     #[test]
     fn test_gravitational_force_calculation() {
         let sim = GravitySimulator::new();
@@ -326,4 +536,51 @@ mod tests {
         let expected = (2.0 * GameConstants::G * mass / distance).sqrt();
         assert_relative_eq!(v_escape, expected, epsilon = 0.01);
     }
+
+    #[test]
+    fn test_circular_orbit_ellipse() {
+        let sim = GravitySimulator::new();
+        let center = Vec2::new(100.0, 200.0);
+        let mass = 5000.0;
+        let position = center + Vec2::new(300.0, 0.0);
+        let velocity = sim.calculate_circular_orbit_velocity(center, mass, position);
+
+        let ellipse = orbital::fit_orbit_ellipse(position, velocity, center, Vec2::ZERO, mass, sim.g)
+            .expect("circular orbit should be bound");
+
+        assert!(ellipse.eccentricity < 1e-3);
+        assert_relative_eq!(ellipse.semi_major_axis, 300.0, epsilon = 0.5);
+        assert_relative_eq!(ellipse.semi_minor_axis, 300.0, epsilon = 0.5);
+        assert_relative_eq!(ellipse.center.x, center.x, epsilon = 0.5);
+        assert_relative_eq!(ellipse.center.y, center.y, epsilon = 0.5);
+    }
+
+    #[test]
+    fn test_elliptical_orbit_passes_through_position() {
+        let g: f32 = 100.0;
+        let mass = 5000.0;
+        let position = Vec2::new(200.0, 0.0);
+        // Faster than circular -> periapsis at current position
+        let circular_speed = (g * mass / 200.0).sqrt();
+        let velocity = Vec2::new(0.0, circular_speed * 1.2);
+
+        let ellipse = orbital::fit_orbit_ellipse(position, velocity, Vec2::ZERO, Vec2::ZERO, mass, g).unwrap();
+        assert!(ellipse.eccentricity > 0.1 && ellipse.eccentricity < 1.0);
+
+        // First sampled point is the periapsis, which is where we are now
+        let periapsis = ellipse.points(64)[0];
+        assert_relative_eq!(periapsis.x, position.x, epsilon = 0.5);
+        assert_relative_eq!(periapsis.y, position.y, epsilon = 0.5);
+    }
+
+    #[test]
+    fn test_escape_trajectory_has_no_ellipse() {
+        let g: f32 = 100.0;
+        let mass = 5000.0;
+        let position = Vec2::new(200.0, 0.0);
+        let escape_speed = (2.0 * g * mass / 200.0).sqrt();
+        let velocity = Vec2::new(0.0, escape_speed * 1.1);
+
+        assert!(orbital::fit_orbit_ellipse(position, velocity, Vec2::ZERO, Vec2::ZERO, mass, g).is_none());
+    }
 }
@@ -2,6 +2,10 @@
 
 pub mod gravity_simulator;
 pub mod trajectory;
+pub mod collision;
+pub mod tidal;
 
-pub use gravity_simulator::{GravitySimulator, orbital};
+pub use gravity_simulator::{GravityModel, GravitySimulator, orbital};
 pub use trajectory::{TrajectoryPredictor, TrajectoryPoint};
+pub use collision::{CollisionRules, CollisionResponse};
+pub use tidal::TidalRules;
@@ -0,0 +1,104 @@
+// Tidal - Roche limits and tidal breakup of bodies that stray too close
+// Advanced physics option (off by default), mainly for custom-map scenarios
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{GameObject, Planet};
+
+/// Roche coefficient for fluid (self-gravitating) bodies like moons
+pub const ROCHE_FLUID_COEFFICIENT: f32 = 2.44;
+/// Roche coefficient for rigid bodies like rockets and satellites
+pub const ROCHE_RIGID_COEFFICIENT: f32 = 1.26;
+
+/// Tidal breakup settings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TidalRules {
+    pub enabled: bool,
+    pub rocket_damage_per_second: f32, // Hull damage while inside a Roche limit
+    pub fragment_count: usize,         // Pieces a moon breaks into
+    pub min_fragment_radius: f32,      // Bodies smaller than this never fragment
+}
+
+impl Default for TidalRules {
+    fn default() -> Self {
+        TidalRules {
+            enabled: false,
+            rocket_damage_per_second: 25.0,
+            fragment_count: 5,
+            min_fragment_radius: 200.0,
+        }
+    }
+}
+
+/// Distance from the primary at which the secondary is torn apart
+/// d = k * r_secondary * (M_primary / m_secondary)^(1/3), which is k * R * (rho_M / rho_m)^(1/3)
+pub fn roche_limit(primary_mass: f32, secondary_mass: f32, secondary_radius: f32, coefficient: f32) -> f32 {
+    if secondary_mass <= 0.0 {
+        return 0.0;
+    }
+    coefficient * secondary_radius * (primary_mass / secondary_mass).cbrt()
+}
+
+/// Break a body into smaller pieces of equal density strung along its orbit
+/// Fragments get a small velocity spread so the debris stream stretches out over time
+pub fn fragment_planet(planet: &Planet, primary_position: Vec2, count: usize) -> Vec<Planet> {
+    let count = count.max(2);
+    let fragment_mass = planet.mass() / count as f32;
+    let fragment_radius = planet.radius() / (count as f32).cbrt(); // Same density
+
+    let radial = (planet.position() - primary_position).try_normalize().unwrap_or(Vec2::X);
+    let tangent = radial.perp();
+    let spread_speed = planet.velocity().length() * 0.02;
+    let color = Color::new(planet.color().r * 0.8, planet.color().g * 0.8, planet.color().b * 0.8, 1.0);
+
+    (0..count)
+        .map(|i| {
+            // -1.0 ..= 1.0 across the fragments
+            let t = i as f32 / (count - 1) as f32 * 2.0 - 1.0;
+            let position = planet.position() + tangent * (t * planet.radius()) - radial * (t * fragment_radius);
+            let velocity = planet.velocity() + tangent * (t * spread_speed);
+
+            let mut fragment = Planet::new(position, fragment_radius, fragment_mass, color);
+            fragment.set_velocity(velocity);
+            fragment.set_fragment(true);
+            if let Some(name) = planet.name() {
+                fragment.set_name(format!("{} Fragment {}", name, i + 1));
+            }
+            fragment
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roche_limit_scales_with_density_ratio() {
+        // Equal density bodies: limit is just the coefficient times the primary radius
+        let limit = roche_limit(8000.0, 1000.0, 50.0, ROCHE_FLUID_COEFFICIENT);
+        assert!((limit - ROCHE_FLUID_COEFFICIENT * 100.0).abs() < 1e-2);
+
+        assert_eq!(roche_limit(1000.0, 0.0, 10.0, ROCHE_RIGID_COEFFICIENT), 0.0);
+    }
+
+    #[test]
+    fn test_fragments_conserve_mass_and_momentum() {
+        let mut moon = Planet::new(Vec2::new(1000.0, 0.0), 400.0, 5000.0, WHITE);
+        moon.set_velocity(Vec2::new(0.0, 30.0));
+        moon.set_name("Moon".to_string());
+
+        let fragments = fragment_planet(&moon, Vec2::ZERO, 4);
+        assert_eq!(fragments.len(), 4);
+
+        let total_mass: f32 = fragments.iter().map(|f| f.mass()).sum();
+        assert!((total_mass - 5000.0).abs() < 1e-2);
+
+        let momentum: Vec2 = fragments.iter().map(|f| f.velocity() * f.mass()).sum();
+        assert!((momentum - Vec2::new(0.0, 30.0) * 5000.0).length() < 1e-1);
+
+        assert!(fragments.iter().all(|f| f.radius() < moon.radius() && f.is_fragment()));
+        assert_eq!(fragments[0].name(), Some("Moon Fragment 1"));
+    }
+}
@@ -2,7 +2,7 @@
 
 use macroquad::prelude::*;
 use crate::entities::{Planet, Rocket};
-use crate::physics::GravitySimulator;
+use crate::physics::{GravityModel, GravitySimulator};
 use crate::systems::vehicle_manager::ReferenceBody;
 use crate::utils::vector_helper;
 
@@ -26,6 +26,11 @@ impl TrajectoryPredictor {
         }
     }
 
+    /// Match the world's gravity model so predictions follow the map's falloff
+    pub fn set_gravity_model(&mut self, model: GravityModel) {
+        self.gravity_simulator.set_model(model);
+    }
+
     /// Predict trajectory for a rocket, accounting for planet motion
     ///
     /// # Arguments
@@ -40,7 +45,7 @@ impl TrajectoryPredictor {
     ///
     /// Note: Uses unified physics simulation where all bodies (rocket, Earth, Moon)
     /// are simulated together with identical time steps and force calculations.
-    /// Earth remains fixed as the inertial reference frame.
+    /// Pinned planets stay fixed; everything else moves, so binary stars work too.
     pub fn predict_trajectory(
         &mut self,
         rocket: &Rocket,
@@ -63,13 +68,8 @@ impl TrajectoryPredictor {
             .map(|p| (p.position(), p.velocity(), p.mass(), p.radius()))
             .collect();
 
-        // Identify Earth (largest planet) - it stays fixed as our inertial reference
-        let earth_index = planet_states
-            .iter()
-            .enumerate()
-            .max_by(|(_, a), (_, b)| a.3.partial_cmp(&b.3).unwrap())
-            .map(|(i, _)| i)
-            .unwrap_or(0);
+        // Pinned planets stay fixed, exactly like in the world simulation
+        let pinned: Vec<bool> = planets.iter().map(|p| p.is_pinned()).collect();
 
         // Simulate forward in time using unified physics
         for _ in 0..steps {
@@ -81,14 +81,14 @@ impl TrajectoryPredictor {
 
             // === UNIFIED PHYSICS STEP ===
 
-            // Step 1: Calculate accelerations for all non-Earth planets (e.g., Moon from Earth)
+            // Step 1: Calculate accelerations for all unpinned planets (e.g., Moon from Earth)
             // We do this first to match the Moon-reference approach
             let mut planet_accels = vec![Vec2::ZERO; planet_states.len()];
 
             if planet_states.len() >= 2 {
                 for i in 0..planet_states.len() {
-                    if i == earth_index {
-                        continue; // Earth is fixed
+                    if pinned[i] {
+                        continue; // Pinned planets don't move
                     }
 
                     let (planet_pos, _, planet_mass, planet_radius) = planet_states[i];
@@ -123,11 +123,12 @@ impl TrajectoryPredictor {
                 let distance = vector_helper::magnitude(direction);
 
                 if distance > planet_radius {
-                    let force = self.gravity_simulator.calculate_gravitational_force(
+                    let force = self.gravity_simulator.calculate_body_gravity(
                         rocket_pos,
                         rocket.current_mass(),
                         planet_pos,
                         planet_mass,
+                        planet_radius,
                     );
                     rocket_accel += force / rocket.current_mass();
                 }
@@ -137,9 +138,9 @@ impl TrajectoryPredictor {
             rocket_vel += rocket_accel * time_step;
             rocket_pos += rocket_vel * time_step;
 
-            // Step 4: Update planet velocities and positions (except Earth)
+            // Step 4: Update planet velocities and positions (except pinned ones)
             for i in 0..planet_states.len() {
-                if i != earth_index {
+                if !pinned[i] {
                     planet_states[i].1 += planet_accels[i] * time_step;
                     let updated_vel = planet_states[i].1;
                     planet_states[i].0 += updated_vel * time_step;
@@ -282,11 +283,12 @@ impl TrajectoryPredictor {
                 let distance = vector_helper::magnitude(direction);
 
                 if distance > planet_radius {
-                    let force = self.gravity_simulator.calculate_gravitational_force(
+                    let force = self.gravity_simulator.calculate_body_gravity(
                         rocket_pos_abs,
                         rocket.current_mass(),
                         planet_pos,
                         planet_mass,
+                        planet_radius,
                     );
                     rocket_accel_abs += force / rocket.current_mass();
                 }
@@ -397,13 +399,8 @@ impl TrajectoryPredictor {
             .map(|p| (p.position(), p.velocity(), p.mass(), p.radius()))
             .collect();
 
-        // Identify Earth (largest planet) for pinning - it doesn't move
-        let earth_index = planet_states
-            .iter()
-            .enumerate()
-            .max_by(|(_, a), (_, b)| a.3.partial_cmp(&b.3).unwrap())
-            .map(|(i, _)| i)
-            .unwrap_or(0);
+        // Pinned planets stay fixed, exactly like in the world simulation
+        let pinned: Vec<bool> = planets.iter().map(|p| p.is_pinned()).collect();
 
         // Simulate forward in time
         for _ in 0..steps {
@@ -417,8 +414,8 @@ impl TrajectoryPredictor {
             // This updates planet velocities based on gravitational interactions
             if planet_states.len() >= 2 {
                 for i in 0..planet_states.len() {
-                    if i == earth_index {
-                        continue; // Earth is pinned, doesn't move
+                    if pinned[i] {
+                        continue; // Pinned planets don't move
                     }
 
                     let mut planet_acceleration = Vec2::ZERO;
@@ -450,7 +447,7 @@ impl TrajectoryPredictor {
 
             // Step 2: Update planet positions based on their velocities
             for i in 0..planet_states.len() {
-                if i != earth_index {
+                if !pinned[i] {
                     let vel = planet_states[i].1;
                     planet_states[i].0 += vel * time_step;
                 }
@@ -463,11 +460,12 @@ impl TrajectoryPredictor {
                 let distance = vector_helper::magnitude(direction);
 
                 if distance > planet_radius {
-                    let force_vec = self.gravity_simulator.calculate_gravitational_force(
+                    let force_vec = self.gravity_simulator.calculate_body_gravity(
                         position,
                         mass,
                         planet_pos,
                         planet_mass,
+                        planet_radius,
                     );
                     acceleration += force_vec / mass;
                 }
@@ -668,11 +666,12 @@ impl TrajectoryPredictor {
             let distance = vector_helper::magnitude(direction);
 
             if distance > planet.radius() {
-                let force_vec = self.gravity_simulator.calculate_gravitational_force(
+                let force_vec = self.gravity_simulator.calculate_body_gravity(
                     rocket_pos,
                     rocket.current_mass(),
                     planet.position(),
                     planet.mass(),
+                    planet.radius(),
                 );
 
                 let force_magnitude = vector_helper::magnitude(force_vec);
@@ -47,6 +47,143 @@ pub struct PlayerState {
     pub timestamp: f32,
 }
 
+/// Most controllers read at once (one per split-screen player)
+pub const MAX_GAMEPADS: usize = 4;
+/// Stick travel ignored around center (worn sticks drift)
+const STICK_DEADZONE: f32 = 0.2;
+/// Trigger travel ignored before thrust starts
+const TRIGGER_DEADZONE: f32 = 0.1;
+
+/// Controller buttons the game reads (named by position, so any layout works)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamepadButton {
+    South, // A / Cross: shoot
+    East,  // B / Circle: convert to satellite
+    West,  // X / Square: fuel a nearby rocket (hold)
+    North, // Y / Triangle: camera back on rocket
+    LeftShoulder,  // Thrust level down
+    RightShoulder, // Thrust level up
+    DPadUp,        // Zoom in (hold)
+    DPadDown,      // Zoom out (hold)
+    Start,         // Claim / pass on the controller in split-screen
+}
+
+impl GamepadButton {
+    fn bit(self) -> u16 {
+        1 << self as u16
+    }
+}
+
+/// One controller's state for a frame, as reported by the backend
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GamepadState {
+    pub left_stick: Vec2,   // -1.0 to 1.0 each axis, +x right
+    pub right_trigger: f32, // 0.0 to 1.0
+    buttons: u16,
+}
+
+impl GamepadState {
+    pub fn with_button(mut self, button: GamepadButton) -> Self {
+        self.buttons |= button.bit();
+        self
+    }
+
+    pub fn is_down(&self, button: GamepadButton) -> bool {
+        self.buttons & button.bit() != 0
+    }
+}
+
+/// Where controller state comes from
+/// macroquad has no gamepad API, so controller backends implement this (as with haptics' `RumbleDevice`)
+pub trait GamepadSource {
+    /// Catch up with the backend (connections, button and axis changes); called once a frame before the reads
+    fn update(&mut self) {}
+
+    /// State of the controller in `slot` (0-3), None if nothing is plugged in there
+    fn read(&mut self, slot: usize) -> Option<GamepadState>;
+}
+
+/// Used when no controller backend is available: every slot empty
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoGamepads;
+
+impl GamepadSource for NoGamepads {
+    fn read(&mut self, _slot: usize) -> Option<GamepadState> {
+        None
+    }
+}
+
+/// A connected controller: this frame's state and last frame's, for presses
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Gamepad {
+    current: GamepadState,
+    previous: GamepadState,
+}
+
+impl Gamepad {
+    pub fn is_down(&self, button: GamepadButton) -> bool {
+        self.current.is_down(button)
+    }
+
+    pub fn just_pressed(&self, button: GamepadButton) -> bool {
+        self.current.is_down(button) && !self.previous.is_down(button)
+    }
+
+    /// Right trigger, 0.0 to 1.0 after the deadzone
+    pub fn thrust(&self) -> f32 {
+        rescale_past_deadzone(self.current.right_trigger, TRIGGER_DEADZONE)
+    }
+
+    /// Left stick across, -1.0 (left) to 1.0 (right) after the deadzone
+    pub fn steer(&self) -> f32 {
+        let x = self.current.left_stick.x.clamp(-1.0, 1.0);
+        x.signum() * rescale_past_deadzone(x.abs(), STICK_DEADZONE)
+    }
+}
+
+/// `value` (0.0 to 1.0) with everything below `deadzone` dropped and the rest stretched back to 0.0-1.0
+fn rescale_past_deadzone(value: f32, deadzone: f32) -> f32 {
+    ((value - deadzone) / (1.0 - deadzone)).clamp(0.0, 1.0)
+}
+
+/// Every controller slot, polled once per frame
+pub struct Gamepads {
+    source: Box<dyn GamepadSource>,
+    slots: [Option<Gamepad>; MAX_GAMEPADS],
+}
+
+impl Gamepads {
+    pub fn new(source: Box<dyn GamepadSource>) -> Self {
+        Gamepads { source, slots: [None; MAX_GAMEPADS] }
+    }
+
+    /// Gamepads without a controller backend (keyboard only)
+    pub fn disconnected() -> Self {
+        Self::new(Box::new(NoGamepads))
+    }
+
+    /// Read every slot; call once at the start of a frame
+    pub fn poll(&mut self) {
+        self.source.update();
+        for (slot, pad) in self.slots.iter_mut().enumerate() {
+            *pad = self.source.read(slot).map(|current| Gamepad {
+                current,
+                previous: pad.map(|pad| pad.current).unwrap_or_default(),
+            });
+        }
+    }
+
+    /// The controller in `slot`, if one is connected
+    pub fn get(&self, slot: usize) -> Option<Gamepad> {
+        self.slots.get(slot).copied().flatten()
+    }
+
+    /// Slots where Start was just pressed
+    pub fn start_pressed(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..MAX_GAMEPADS).filter(|&slot| self.get(slot).is_some_and(|pad| pad.just_pressed(GamepadButton::Start)))
+    }
+}
+
 /// Input state tracking
 #[derive(Debug, Clone, Default)]
 struct InputState {
@@ -513,6 +650,41 @@ mod tests {
         assert!(player.should_send_state(0.0));
     }
 
+    /// Plays back a fixed script of controller states in slot 1, one per poll
+    struct ScriptedPad {
+        frames: Vec<Option<GamepadState>>,
+    }
+
+    impl GamepadSource for ScriptedPad {
+        fn read(&mut self, slot: usize) -> Option<GamepadState> {
+            if slot != 1 || self.frames.is_empty() {
+                return None;
+            }
+            self.frames.remove(0)
+        }
+    }
+
+    #[test]
+    fn test_gamepad_edges_and_deadzones() {
+        let held = GamepadState { left_stick: Vec2::new(0.6, 0.0), right_trigger: 0.05, ..Default::default() }
+            .with_button(GamepadButton::South);
+        let mut pads = Gamepads::new(Box::new(ScriptedPad { frames: vec![Some(held), Some(held), None] }));
+
+        pads.poll();
+        assert!(pads.get(0).is_none());
+        let pad = pads.get(1).unwrap();
+        assert!(pad.just_pressed(GamepadButton::South));
+        assert!(!pad.is_down(GamepadButton::East));
+        assert_eq!(pad.thrust(), 0.0); // Resting trigger inside the deadzone
+        assert!((pad.steer() - 0.5).abs() < 1e-5);
+
+        pads.poll();
+        assert!(!pads.get(1).unwrap().just_pressed(GamepadButton::South)); // Held, not pressed again
+
+        pads.poll();
+        assert!(pads.get(1).is_none()); // Unplugged
+    }
+
     #[test]
     fn test_spawn_position_management() {
         let mut player = Player::new_local(1, "Test".to_string(), Vec2::new(100.0, 100.0));
@@ -0,0 +1,444 @@
+// RL Environment - Gym-style reset/step adapter over the embedding API
+// One agent-controlled rocket per environment; tasks score orbit insertion or landing on the spawn body
+
+use macroquad::prelude::Vec2;
+
+use crate::embed::{RocketControls, Simulation};
+use crate::game_constants::GameConstants;
+use crate::map_config::MapConfiguration;
+use crate::physics::gravity_simulator::orbital;
+use crate::systems::daily_challenge::SplitMix64;
+use crate::systems::{EntityId, LandingGrade};
+
+/// Values in an observation (see `RlEnv::observation` for the layout)
+pub const OBSERVATION_SIZE: usize = 10;
+pub type Observation = [f32; OBSERVATION_SIZE];
+
+/// Reward for finishing the task, and penalty for failing it outright
+const TERMINAL_REWARD: f32 = 10.0;
+/// Orbit insertion succeeds within this fraction of the target altitude and circular speed
+const ORBIT_TOLERANCE: f32 = 0.05;
+
+/// What the agent is trained to do around the map's spawn body
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RlTask {
+    /// Start on the pad and settle into a circular orbit at `altitude`
+    OrbitInsertion { altitude: f32 },
+    /// Start falling from around `start_altitude` on a decaying orbit and touch down gently
+    Landing { start_altitude: f32 },
+}
+
+/// Environment settings
+#[derive(Debug, Clone)]
+pub struct RlConfig {
+    pub task: RlTask,
+    pub map: MapConfiguration,
+    pub timestep: f32,      // Seconds per physics step
+    pub action_repeat: u32, // Physics steps each action is held for
+    pub max_steps: u32,     // Actions before the episode is truncated
+}
+
+impl Default for RlConfig {
+    fn default() -> Self {
+        RlConfig {
+            task: RlTask::OrbitInsertion { altitude: 1500.0 },
+            map: MapConfiguration::earth_moon(),
+            timestep: 1.0 / GameConstants::DEFAULT_PHYSICS_HZ as f32,
+            action_repeat: 8,
+            max_steps: 2000,
+        }
+    }
+}
+
+/// Outcome of one action
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StepResult {
+    pub observation: Observation,
+    pub reward: f32,
+    pub terminated: bool, // Task finished or failed
+    pub truncated: bool,  // Ran out of steps
+    pub success: bool,
+}
+
+impl StepResult {
+    pub fn done(&self) -> bool {
+        self.terminated || self.truncated
+    }
+}
+
+/// Why an environment couldn't be built
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RlEnvError {
+    NoSpawnBody,    // The map has nowhere to put the agent's rocket
+    NoEnvironments, // A VecEnv was asked for zero environments
+}
+
+impl std::fmt::Display for RlEnvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RlEnvError::NoSpawnBody => write!(f, "Map has no spawn body"),
+            RlEnvError::NoEnvironments => write!(f, "VecEnv needs at least one environment"),
+        }
+    }
+}
+
+impl std::error::Error for RlEnvError {}
+
+/// A single environment; the same seed and actions always give the same episode
+pub struct RlEnv {
+    config: RlConfig,
+    simulation: Simulation,
+    rocket_id: EntityId,
+    body_id: EntityId,
+    steps: u32,
+    previous_score: f32,
+}
+
+impl RlEnv {
+    pub fn new(config: RlConfig) -> Result<Self, RlEnvError> {
+        let (simulation, rocket_id, body_id) = Self::build(&config, 0)?;
+        let mut env = RlEnv { config, simulation, rocket_id, body_id, steps: 0, previous_score: 0.0 };
+        env.previous_score = env.score();
+        Ok(env)
+    }
+
+    fn build(config: &RlConfig, seed: u64) -> Result<(Simulation, EntityId, EntityId), RlEnvError> {
+        let mut simulation = Simulation::from_map(config.map.clone());
+        simulation.set_timestep(config.timestep);
+        let body_id = simulation.spawn_body_id().ok_or(RlEnvError::NoSpawnBody)?;
+        let rocket_id = simulation.spawn_rocket(Some(0)).ok_or(RlEnvError::NoSpawnBody)?;
+
+        let mut rng = SplitMix64(seed);
+        let mut jitter = |spread: f32| (rng.unit() * 2.0 - 1.0) * spread;
+        let body = simulation.world().get_planet(body_id).map(|planet| (planet.position(), planet.velocity(), planet.radius(), planet.mass()));
+        let (body_position, body_velocity, body_radius, body_mass) = body.ok_or(RlEnvError::NoSpawnBody)?;
+        let gravity_model = simulation.world().gravity_simulator().model();
+
+        let rocket_rotation_jitter = jitter(0.3);
+        let rocket = simulation.world_mut().get_rocket_mut(rocket_id).expect("rocket just spawned");
+        rocket.rotate(rocket_rotation_jitter);
+        if let RlTask::Landing { start_altitude } = config.task {
+            // Somewhere around the body, moving sideways a little too slowly to stay up
+            let angle = jitter(std::f32::consts::PI);
+            let distance = body_radius + start_altitude * (1.0 + jitter(0.1));
+            let outward = Vec2::new(angle.cos(), angle.sin());
+            let mu = GameConstants::G * body_mass;
+            let circular_speed = (gravity_model.acceleration(mu, distance, body_radius) * distance).sqrt();
+            let sideways = Vec2::new(-outward.y, outward.x) * circular_speed * (0.8 + jitter(0.1));
+            rocket.set_position(body_position + outward * distance);
+            rocket.set_velocity(body_velocity + sideways);
+        }
+        Ok((simulation, rocket_id, body_id))
+    }
+
+    /// Start a new episode
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        // The map never changes, and `new` already built it once
+        let (simulation, rocket_id, body_id) = Self::build(&self.config, seed).expect("map has a spawn body");
+        self.simulation = simulation;
+        self.rocket_id = rocket_id;
+        self.body_id = body_id;
+        self.steps = 0;
+        self.previous_score = self.score();
+        self.observation()
+    }
+
+    /// Hold `action` for `action_repeat` physics steps
+    pub fn step(&mut self, action: RocketControls) -> StepResult {
+        self.simulation.set_controls(self.rocket_id, action);
+        for _ in 0..self.config.action_repeat {
+            self.simulation.step();
+        }
+        self.steps += 1;
+
+        let landing = self
+            .simulation
+            .take_landings()
+            .into_iter()
+            .find(|landing| landing.rocket_id == self.rocket_id);
+        let score = self.score();
+        let mut reward = score - self.previous_score;
+        self.previous_score = score;
+
+        // (finished, bonus on success); a destroyed rocket or a touchdown on the way to orbit fails
+        let outcome = if self.simulation.rocket(self.rocket_id).is_none() {
+            Some(None)
+        } else {
+            match (self.config.task, landing) {
+                (RlTask::Landing { .. }, Some(landing)) if landing.result.grade != LandingGrade::Crash => {
+                    Some(Some(TERMINAL_REWARD * landing.result.score / 100.0))
+                }
+                (_, Some(_)) => Some(None),
+                (RlTask::OrbitInsertion { altitude }, None) if self.in_orbit(altitude) => Some(Some(TERMINAL_REWARD)),
+                _ => None,
+            }
+        };
+        reward += match outcome {
+            Some(Some(bonus)) => bonus,
+            Some(None) => -TERMINAL_REWARD,
+            None => 0.0,
+        };
+        let terminated = outcome.is_some();
+        let success = matches!(outcome, Some(Some(_)));
+
+        StepResult {
+            observation: self.observation(),
+            reward,
+            terminated,
+            truncated: !terminated && self.steps >= self.config.max_steps,
+            success,
+        }
+    }
+
+    pub fn simulation(&self) -> &Simulation {
+        &self.simulation
+    }
+
+    pub fn rocket_id(&self) -> EntityId {
+        self.rocket_id
+    }
+
+    /// Rocket relative to the spawn body: (offset, velocity, body radius, surface circular speed)
+    fn relative_state(&self) -> Option<(Vec2, Vec2, f32, f32)> {
+        let rocket = self.simulation.rocket(self.rocket_id)?;
+        let body = self.simulation.world().get_planet(self.body_id)?;
+        let mu = GameConstants::G * body.mass();
+        let model = self.simulation.world().gravity_simulator().model();
+        let surface_speed = (model.acceleration(mu, body.radius(), body.radius()) * body.radius()).sqrt().max(1.0);
+        Some((rocket.position - body.position(), rocket.velocity - body.velocity(), body.radius(), surface_speed))
+    }
+
+    /// Speed of a circular orbit `distance` from the body's center
+    fn circular_speed(&self, distance: f32) -> f32 {
+        let Some(body) = self.simulation.world().get_planet(self.body_id) else {
+            return 0.0;
+        };
+        let mu = GameConstants::G * body.mass();
+        let model = self.simulation.world().gravity_simulator().model();
+        (model.acceleration(mu, distance, body.radius()) * distance).sqrt()
+    }
+
+    fn in_orbit(&self, altitude: f32) -> bool {
+        let Some((offset, velocity, radius, _)) = self.relative_state() else {
+            return false;
+        };
+        let distance = offset.length();
+        let outward = offset / distance.max(1.0);
+        let target_speed = self.circular_speed(radius + altitude);
+        let radial_speed = velocity.dot(outward);
+        let tangential_speed = velocity.perp_dot(outward).abs();
+        ((distance - radius) - altitude).abs() < altitude * ORBIT_TOLERANCE
+            && radial_speed.abs() < target_speed * ORBIT_TOLERANCE
+            && (tangential_speed - target_speed).abs() < target_speed * ORBIT_TOLERANCE
+    }
+
+    /// Shaping potential: rewards are its change per step, so it only has to rise towards the goal
+    /// (orbit insertion scores how far apoapsis and periapsis are from the target, from two-body orbit elements)
+    fn score(&self) -> f32 {
+        let Some((offset, velocity, radius, surface_speed)) = self.relative_state() else {
+            return 0.0;
+        };
+        match self.config.task {
+            RlTask::OrbitInsertion { altitude } => {
+                let Some(body) = self.simulation.world().get_planet(self.body_id) else {
+                    return 0.0;
+                };
+                let target = radius + altitude;
+                let apoapsis = orbital::calculate_apoapsis(offset, velocity, Vec2::ZERO, body.mass(), GameConstants::G);
+                let periapsis = orbital::calculate_periapsis(offset, velocity, Vec2::ZERO, body.mass(), GameConstants::G);
+                -((apoapsis.min(target * 3.0) - target).abs() + (periapsis - target).abs()) / target
+            }
+            RlTask::Landing { start_altitude } => {
+                let altitude = offset.length() - radius;
+                -(altitude.max(0.0) / start_altitude + velocity.length() / surface_speed)
+            }
+        }
+    }
+
+    /// [x, y, vx, vy] relative to the spawn body (in body radii and surface orbital speeds),
+    /// then [sin, cos] of the heading, fuel fraction, altitude (radii), radial speed, landed flag
+    pub fn observation(&self) -> Observation {
+        let (Some((offset, velocity, radius, surface_speed)), Some(rocket)) =
+            (self.relative_state(), self.simulation.rocket(self.rocket_id))
+        else {
+            return [0.0; OBSERVATION_SIZE];
+        };
+        let distance = offset.length();
+        let outward = offset / distance.max(1.0);
+        [
+            offset.x / radius,
+            offset.y / radius,
+            velocity.x / surface_speed,
+            velocity.y / surface_speed,
+            rocket.rotation.sin(),
+            rocket.rotation.cos(),
+            rocket.fuel / rocket.max_fuel.max(1.0),
+            (distance - radius) / radius,
+            velocity.dot(outward) / surface_speed,
+            if rocket.landed { 1.0 } else { 0.0 },
+        ]
+    }
+}
+
+/// A batch of environments stepped together across threads; finished episodes restart automatically
+pub struct VecEnv {
+    envs: Vec<RlEnv>,
+    seed: u64,
+    episodes: Vec<u64>, // Episodes started per environment (feeds its next seed)
+}
+
+impl VecEnv {
+    pub fn new(config: RlConfig, count: usize) -> Result<Self, RlEnvError> {
+        if count == 0 {
+            return Err(RlEnvError::NoEnvironments);
+        }
+        Ok(VecEnv {
+            envs: (0..count).map(|_| RlEnv::new(config.clone())).collect::<Result<_, _>>()?,
+            seed: 0,
+            episodes: vec![0; count],
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.envs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.envs.is_empty()
+    }
+
+    pub fn envs(&self) -> &[RlEnv] {
+        &self.envs
+    }
+
+    /// Every environment's episode seed is derived from `seed`, its index and how many episodes it has run
+    fn episode_seed(&self, index: usize) -> u64 {
+        SplitMix64(self.seed ^ ((index as u64) << 32) ^ self.episodes[index]).next()
+    }
+
+    /// Restart every environment from `seed`
+    pub fn reset(&mut self, seed: u64) -> Vec<Observation> {
+        self.seed = seed;
+        self.episodes.iter_mut().for_each(|episodes| *episodes = 0);
+        (0..self.envs.len())
+            .map(|index| {
+                let episode_seed = self.episode_seed(index);
+                self.envs[index].reset(episode_seed)
+            })
+            .collect()
+    }
+
+    /// One action per environment; a finished environment restarts and reports its new episode's first observation
+    pub fn step(&mut self, actions: &[RocketControls]) -> Vec<StepResult> {
+        assert_eq!(actions.len(), self.envs.len(), "one action per environment");
+
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get()).min(self.envs.len()).max(1);
+        let chunk = self.envs.len().div_ceil(threads);
+        let mut results: Vec<StepResult> = Vec::with_capacity(self.envs.len());
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .envs
+                .chunks_mut(chunk)
+                .zip(actions.chunks(chunk))
+                .map(|(envs, actions)| {
+                    scope.spawn(move || envs.iter_mut().zip(actions).map(|(env, action)| env.step(*action)).collect::<Vec<_>>())
+                })
+                .collect();
+            for handle in handles {
+                results.extend(handle.join().expect("environment thread panicked"));
+            }
+        });
+
+        for (index, result) in results.iter_mut().enumerate() {
+            if result.done() {
+                self.episodes[index] += 1;
+                let episode_seed = self.episode_seed(index);
+                result.observation = self.envs[index].reset(episode_seed);
+            }
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn landing_config() -> RlConfig {
+        RlConfig { task: RlTask::Landing { start_altitude: 300.0 }, max_steps: 400, ..RlConfig::default() }
+    }
+
+    #[test]
+    fn test_same_seed_same_episode() {
+        let mut a = RlEnv::new(landing_config()).unwrap();
+        let mut b = RlEnv::new(landing_config()).unwrap();
+        assert_eq!(a.reset(42), b.reset(42));
+        let action = RocketControls { thrust: 0.5, turn_rate: 0.2 };
+        for _ in 0..20 {
+            assert_eq!(a.step(action), b.step(action));
+        }
+        assert_ne!(a.reset(43), b.reset(42));
+    }
+
+    #[test]
+    fn test_map_without_spawn_body_is_an_error() {
+        let mut map = MapConfiguration::earth_moon();
+        map.player_spawn_body_index = map.celestial_bodies.len();
+        let config = RlConfig { map, ..RlConfig::default() };
+        assert_eq!(RlEnv::new(config.clone()).err(), Some(RlEnvError::NoSpawnBody));
+        assert!(VecEnv::new(config, 2).is_err());
+    }
+
+    #[test]
+    fn test_empty_vec_env_is_an_error() {
+        assert_eq!(VecEnv::new(landing_config(), 0).err(), Some(RlEnvError::NoEnvironments));
+    }
+
+    #[test]
+    fn test_landing_episode_ends_on_touchdown() {
+        let mut env = RlEnv::new(landing_config()).unwrap();
+        let first = env.reset(7);
+        assert_eq!(first[9], 0.0);
+        assert!(first[7] > 0.0);
+
+        let result = (0..400)
+            .map(|_| env.step(RocketControls::default()))
+            .find(StepResult::done)
+            .expect("an unpowered fall ends the episode");
+        assert!(result.terminated);
+    }
+
+    /// Total reward for turning to face straight up (heading r thrusts along (sin r, -cos r)) then holding `thrust`
+    fn climb_reward(thrust: f32) -> f32 {
+        let mut env = RlEnv::new(RlConfig::default()).unwrap();
+        let observation = env.reset(1);
+        let heading = observation[0].atan2(-observation[1]);
+        let turn = heading - env.simulation().rocket(env.rocket_id()).unwrap().rotation;
+        let turn_time = env.config.action_repeat as f32 * env.config.timestep;
+        let mut total = env.step(RocketControls { thrust: 0.0, turn_rate: turn / turn_time }).reward;
+        for _ in 0..10 {
+            total += env.step(RocketControls { thrust, turn_rate: 0.0 }).reward;
+        }
+        total
+    }
+
+    #[test]
+    fn test_orbit_insertion_rewards_climbing() {
+        assert!(climb_reward(1.0) > climb_reward(0.0));
+    }
+
+    #[test]
+    fn test_vec_env_restarts_finished_episodes() {
+        let mut envs = VecEnv::new(RlConfig { max_steps: 3, ..landing_config() }, 3).unwrap();
+        let first = envs.reset(5);
+        assert_eq!(first.len(), 3);
+        assert_ne!(first[0], first[1]);
+
+        let actions = vec![RocketControls::default(); 3];
+        envs.step(&actions);
+        envs.step(&actions);
+        let results = envs.step(&actions);
+        assert!(results.iter().all(StepResult::done));
+        assert!(envs.envs().iter().all(|env| env.steps == 0));
+    }
+}
@@ -0,0 +1,163 @@
+// Bug Report - Packages logs, the latest save, its map, settings and version info into one zip
+// Written to bug_reports/ so players can attach a single file to a GitHub issue
+
+use std::fs;
+use std::io::{Seek, Write};
+use std::path::{Path, PathBuf};
+
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::map_config::MapConfiguration;
+use crate::save_system::{local_profiles, GameSaveData, PlayerProfile};
+use crate::utils::log_buffer;
+
+/// Folder the bundles are written to
+const BUG_REPORT_FOLDER: &str = "bug_reports";
+
+/// Files collected for a bug report
+#[derive(Debug, Clone, Default)]
+pub struct BugReport {
+    files: Vec<(String, Vec<u8>)>, // Name inside the zip, contents
+}
+
+impl BugReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gather version info, recent logs, the newest save (with its map) and settings
+    /// `settings` describes runtime options that aren't stored on disk (command-line flags etc.)
+    pub fn collect(settings: &str) -> Self {
+        let mut report = BugReport::new();
+        report.add("version.txt", version_info());
+
+        let logs: Vec<String> = log_buffer::recent_entries().iter().map(|entry| entry.format()).collect();
+        report.add("logs.txt", logs.join("\n"));
+
+        let mut settings = settings.to_string();
+        settings.push_str(&format!("\nCommand line: {}\n", std::env::args().collect::<Vec<_>>().join(" ")));
+        report.add("settings.txt", settings);
+        if let Ok(profile) = fs::read(PlayerProfile::path()) {
+            report.add("profile.ron", profile);
+        }
+
+        if let Some(save_path) = newest_save() {
+            report.add_save(&save_path);
+        }
+        report
+    }
+
+    pub fn add(&mut self, name: &str, contents: impl Into<Vec<u8>>) {
+        self.files.push((name.to_string(), contents.into()));
+    }
+
+    /// Names of the collected files
+    pub fn file_names(&self) -> impl Iterator<Item = &str> {
+        self.files.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Add a save file and the map it was played on
+    fn add_save(&mut self, path: &Path) {
+        let Ok(bytes) = fs::read(path) else {
+            return;
+        };
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("save.sav");
+        self.add(&format!("save/{}", file_name), bytes.clone());
+
+        let map_name = GameSaveData::decode_save_file(&bytes).ok().and_then(|save| save.map_name);
+        let map = map_name.and_then(|name| MapConfiguration::all_maps().into_iter().find(|map| map.name == name));
+        if let Some(map) = map {
+            match ron::ser::to_string_pretty(&map, ron::ser::PrettyConfig::default()) {
+                Ok(ron) => self.add("map.ron", ron),
+                Err(e) => log::warn!("Failed to include map in bug report: {}", e),
+            }
+        }
+    }
+
+    /// Write every file into a zip archive
+    pub fn write_zip<W: Write + Seek>(&self, writer: W) -> Result<(), Box<dyn std::error::Error>> {
+        let mut zip = ZipWriter::new(writer);
+        let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        for (name, contents) in &self.files {
+            zip.start_file(name.as_str(), options)?;
+            zip.write_all(contents)?;
+        }
+        zip.finish()?;
+        Ok(())
+    }
+
+    /// Write the bundle to bug_reports/ and return its path
+    pub fn save(&self) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        fs::create_dir_all(BUG_REPORT_FOLDER)?;
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let path = Path::new(BUG_REPORT_FOLDER).join(format!("bug_report_{}.zip", timestamp));
+        self.write_zip(fs::File::create(&path)?)?;
+
+        log::info!("Bug report written to: {}", path.display());
+        Ok(path)
+    }
+}
+
+/// Game version, build and platform
+fn version_info() -> String {
+    let features: Vec<&str> = [("discord", cfg!(feature = "discord")), ("gamepad", cfg!(feature = "gamepad"))]
+        .iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| *name)
+        .collect();
+    format!(
+        "{} {}\nBuild: {}\nFeatures: {}\nPlatform: {} {}\n",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        if cfg!(debug_assertions) { "debug" } else { "release" },
+        if features.is_empty() { "none".to_string() } else { features.join(", ") },
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+    )
+}
+
+/// Most recently written .sav file across the active profile's single and multiplayer save folders
+fn newest_save() -> Option<PathBuf> {
+    [local_profiles::saves_dir(), local_profiles::saves_path("multi")]
+        .iter()
+        .filter_map(|folder| fs::read_dir(folder).ok())
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("sav"))
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((modified, path))
+        })
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Read};
+
+    #[test]
+    fn test_zip_contains_every_file() {
+        let mut report = BugReport::new();
+        report.add("version.txt", version_info());
+        report.add("logs.txt", "[    1.000 INFO  katie] Hello");
+        report.add("save/quicksave.sav", vec![1u8, 2, 3]);
+
+        let mut buffer = Cursor::new(Vec::new());
+        report.write_zip(&mut buffer).unwrap();
+
+        let mut archive = zip::ZipArchive::new(buffer).unwrap();
+        assert_eq!(archive.len(), 3);
+        let mut logs = String::new();
+        archive.by_name("logs.txt").unwrap().read_to_string(&mut logs).unwrap();
+        assert_eq!(logs, "[    1.000 INFO  katie] Hello");
+        let mut save = Vec::new();
+        archive.by_name("save/quicksave.sav").unwrap().read_to_end(&mut save).unwrap();
+        assert_eq!(save, vec![1, 2, 3]);
+    }
+}
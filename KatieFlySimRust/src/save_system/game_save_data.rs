@@ -6,13 +6,78 @@ use serde::{Deserialize, Serialize};
 use macroquad::prelude::*;
 use std::fs;
 use std::path::Path;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::entities::{Planet, Rocket, Satellite, Bullet};
-use crate::systems::EntityId;
+use crate::systems::{ArenaBounds, EntityId, FloatingOrigin, VehicleManager, Waypoint, World};
+use crate::systems::daily_challenge::fnv1a;
+use crate::save_system::local_profiles;
+use crate::ui::{GameInfoDisplay, HudPanel};
+
+/// Current save format version (2 added the per-save UI state, 3 the bullet time-to-live, 4 satellite owners, 5 waypoints,
+/// 6 the floating origin, 7 satellite wear, 8 the arena bounds, 9 the cosmetic seed)
+pub const SAVE_VERSION: u32 = 9;
+
+/// Save files end with this marker and an FNV-1a hash of everything before it (older files have neither)
+const CHECKSUM_MAGIC: &[u8; 4] = b"KSUM";
+const CHECKSUM_TRAILER_LEN: usize = 12;
+/// Autosave slots kept (autosave, autosave_1, ...) so a damaged save has older ones to fall back on
+pub const AUTOSAVE_SLOTS: usize = 3;
+pub const AUTOSAVE_NAME: &str = "autosave";
+
+/// Append the checksum trailer
+fn seal(mut bytes: Vec<u8>) -> Vec<u8> {
+    let hash = fnv1a(&bytes);
+    bytes.extend_from_slice(CHECKSUM_MAGIC);
+    bytes.extend_from_slice(&hash.to_le_bytes());
+    bytes
+}
+
+/// Check and strip the checksum trailer; files without one (written before checksums) pass through
+fn unseal(bytes: &[u8]) -> Result<&[u8], String> {
+    if bytes.len() < CHECKSUM_TRAILER_LEN {
+        return Ok(bytes);
+    }
+    let (payload, trailer) = bytes.split_at(bytes.len() - CHECKSUM_TRAILER_LEN);
+    if &trailer[..4] != CHECKSUM_MAGIC {
+        return Ok(bytes);
+    }
+    let mut stored = [0u8; 8];
+    stored.copy_from_slice(&trailer[4..]);
+    if u64::from_le_bytes(stored) != fnv1a(payload) {
+        return Err("save file is corrupted (checksum mismatch)".to_string());
+    }
+    Ok(payload)
+}
+
+/// Write through a temporary file so a crash mid-write can't leave a truncated save behind
+fn write_sealed(file_path: &str, bytes: Vec<u8>) -> std::io::Result<usize> {
+    let sealed = seal(bytes);
+    let temp_path = format!("{}.tmp", file_path);
+    fs::write(&temp_path, &sealed)?;
+    fs::rename(&temp_path, file_path)?;
+    Ok(sealed.len())
+}
+
+/// Name of an autosave slot (slot 0 is the newest)
+fn autosave_slot_name(slot: usize) -> String {
+    if slot == 0 {
+        AUTOSAVE_NAME.to_string()
+    } else {
+        format!("{}_{}", AUTOSAVE_NAME, slot)
+    }
+}
+
+/// A save loaded by `load_or_recover`
+#[derive(Debug)]
+pub struct RecoveredSave {
+    pub data: GameSaveData,
+    pub save_name: String,      // The save actually loaded
+    pub notice: Option<String>, // Set when the requested save was damaged and an autosave was loaded instead
+}
 
 /// Serializable Vec2 wrapper
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SavedVector2 {
     pub x: f32,
     pub y: f32,
@@ -31,7 +96,7 @@ impl From<SavedVector2> for Vec2 {
 }
 
 /// Saved planet data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SavedPlanet {
     pub id: EntityId,
     pub position: SavedVector2,
@@ -85,7 +150,7 @@ impl SavedPlanet {
 }
 
 /// Saved rocket data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SavedRocket {
     pub id: EntityId,
     pub position: SavedVector2,
@@ -136,7 +201,7 @@ impl SavedRocket {
 }
 
 /// Saved satellite data (extended for comprehensive state)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SavedSatellite {
     pub id: EntityId,
     pub position: SavedVector2,
@@ -158,6 +223,97 @@ pub struct SavedSatellite {
 
     // Network configuration
     pub transfer_range: f32,
+
+    // Player who owns it (multiplayer)
+    pub owner: Option<u32>,
+
+    // Service life used up (1.0 = end of life)
+    pub wear: f32,
+}
+
+/// Version 4-6 satellite layout (before wear)
+#[derive(Deserialize)]
+struct SavedSatelliteV6 {
+    id: EntityId,
+    position: SavedVector2,
+    velocity: SavedVector2,
+    rotation: f32,
+    fuel: f32,
+    target_orbit_radius: f32,
+    is_maintaining_orbit: bool,
+    last_maintenance_time: f32,
+    maintenance_interval: f32,
+    maintenance_fuel_reserve: f32,
+    is_collecting_fuel: bool,
+    fuel_source_planet_id: Option<usize>,
+    collection_rate: f32,
+    transfer_range: f32,
+    owner: Option<u32>,
+}
+
+impl From<SavedSatelliteV6> for SavedSatellite {
+    fn from(v6: SavedSatelliteV6) -> Self {
+        SavedSatellite {
+            id: v6.id,
+            position: v6.position,
+            velocity: v6.velocity,
+            rotation: v6.rotation,
+            fuel: v6.fuel,
+            target_orbit_radius: v6.target_orbit_radius,
+            is_maintaining_orbit: v6.is_maintaining_orbit,
+            last_maintenance_time: v6.last_maintenance_time,
+            maintenance_interval: v6.maintenance_interval,
+            maintenance_fuel_reserve: v6.maintenance_fuel_reserve,
+            is_collecting_fuel: v6.is_collecting_fuel,
+            fuel_source_planet_id: v6.fuel_source_planet_id,
+            collection_rate: v6.collection_rate,
+            transfer_range: v6.transfer_range,
+            owner: v6.owner,
+            wear: 0.0,
+        }
+    }
+}
+
+/// Version 1-3 satellite layout (before owner)
+#[derive(Deserialize)]
+struct SavedSatelliteV3 {
+    id: EntityId,
+    position: SavedVector2,
+    velocity: SavedVector2,
+    rotation: f32,
+    fuel: f32,
+    target_orbit_radius: f32,
+    is_maintaining_orbit: bool,
+    last_maintenance_time: f32,
+    maintenance_interval: f32,
+    maintenance_fuel_reserve: f32,
+    is_collecting_fuel: bool,
+    fuel_source_planet_id: Option<usize>,
+    collection_rate: f32,
+    transfer_range: f32,
+}
+
+impl From<SavedSatelliteV3> for SavedSatellite {
+    fn from(v3: SavedSatelliteV3) -> Self {
+        SavedSatellite {
+            id: v3.id,
+            position: v3.position,
+            velocity: v3.velocity,
+            rotation: v3.rotation,
+            fuel: v3.fuel,
+            target_orbit_radius: v3.target_orbit_radius,
+            is_maintaining_orbit: v3.is_maintaining_orbit,
+            last_maintenance_time: v3.last_maintenance_time,
+            maintenance_interval: v3.maintenance_interval,
+            maintenance_fuel_reserve: v3.maintenance_fuel_reserve,
+            is_collecting_fuel: v3.is_collecting_fuel,
+            fuel_source_planet_id: v3.fuel_source_planet_id,
+            collection_rate: v3.collection_rate,
+            transfer_range: v3.transfer_range,
+            owner: None,
+            wear: 0.0,
+        }
+    }
 }
 
 impl SavedSatellite {
@@ -179,6 +335,8 @@ impl SavedSatellite {
             fuel_source_planet_id: satellite.fuel_source_planet_id(),
             collection_rate: satellite.collection_rate(),
             transfer_range: satellite.transfer_range(),
+            owner: satellite.owner(),
+            wear: satellite.wear(),
         }
     }
 
@@ -208,20 +366,48 @@ impl SavedSatellite {
 
         // Restore network configuration
         satellite.set_transfer_range(self.transfer_range);
+        satellite.set_owner(self.owner);
+        satellite.set_wear(self.wear);
 
         (self.id, satellite)
     }
 }
 
 /// Saved bullet data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SavedBullet {
     pub id: EntityId,
     pub position: SavedVector2,
     pub velocity: SavedVector2,
     pub mass: f32,
-    pub lifetime: f32,
+    pub lifetime: f32,     // Age in seconds (spawned at snapshot game_time - lifetime)
     pub color: (u8, u8, u8),
+    pub max_lifetime: f32, // Time-to-live; clients despawn on the host's value
+}
+
+/// Version 1-2 bullet layout (before max_lifetime)
+#[derive(Deserialize)]
+struct SavedBulletV2 {
+    id: EntityId,
+    position: SavedVector2,
+    velocity: SavedVector2,
+    mass: f32,
+    lifetime: f32,
+    color: (u8, u8, u8),
+}
+
+impl From<SavedBulletV2> for SavedBullet {
+    fn from(v2: SavedBulletV2) -> Self {
+        SavedBullet {
+            id: v2.id,
+            position: v2.position,
+            velocity: v2.velocity,
+            mass: v2.mass,
+            lifetime: v2.lifetime,
+            color: v2.color,
+            max_lifetime: Bullet::new(Vec2::ZERO, Vec2::ZERO).max_lifetime(),
+        }
+    }
 }
 
 impl SavedBullet {
@@ -239,6 +425,7 @@ impl SavedBullet {
                 (bullet.color().g * 255.0) as u8,
                 (bullet.color().b * 255.0) as u8,
             ),
+            max_lifetime: bullet.max_lifetime(),
         }
     }
 
@@ -250,18 +437,145 @@ impl SavedBullet {
 
         // Restore lifetime (critical for bullets to maintain their age across network)
         bullet.set_lifetime(self.lifetime);
+        bullet.set_max_lifetime(self.max_lifetime);
+        let (r, g, b) = self.color;
+        bullet.set_color(Color::from_rgba(r, g, b, 255));
 
         (self.id, bullet)
     }
 }
 
+/// Saved waypoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedWaypoint {
+    pub id: u32,
+    pub name: String,
+    pub position: SavedVector2,
+    pub owner: Option<u32>,
+    pub shared: bool,
+}
+
+impl SavedWaypoint {
+    pub fn from_waypoint(waypoint: &Waypoint) -> Self {
+        SavedWaypoint {
+            id: waypoint.id,
+            name: waypoint.name.clone(),
+            position: waypoint.position.into(),
+            owner: waypoint.owner,
+            shared: waypoint.shared,
+        }
+    }
+
+    pub fn to_waypoint(&self) -> Waypoint {
+        Waypoint {
+            id: self.id,
+            name: self.name.clone(),
+            position: self.position.clone().into(),
+            owner: self.owner,
+            shared: self.shared,
+        }
+    }
+}
+
+/// World position of the floating origin (saved positions are relative to it)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavedOrigin {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl From<FloatingOrigin> for SavedOrigin {
+    fn from(origin: FloatingOrigin) -> Self {
+        SavedOrigin { x: origin.offset().x, y: origin.offset().y }
+    }
+}
+
+impl From<SavedOrigin> for FloatingOrigin {
+    fn from(origin: SavedOrigin) -> Self {
+        FloatingOrigin::from_offset(DVec2::new(origin.x, origin.y))
+    }
+}
+
 /// Camera save data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SavedCamera {
     pub center: SavedVector2,
     pub zoom: f32,
 }
 
+/// The player's working context, restored with the save (not sent in network snapshots)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedUiState {
+    pub marked_satellites: Vec<EntityId>,
+
+    // HUD panel visibility
+    pub show_rocket_panel: bool,
+    pub show_planet_panel: bool,
+    pub show_orbit_panel: bool,
+    pub show_network_panel: bool,
+
+    // Visualization toggles
+    pub show_trajectory: bool,
+    pub show_gravity_forces: bool,
+    pub show_planet_trajectories: bool,
+    pub show_satellite_orbits: bool,
+    pub reference_body: usize,
+}
+
+impl SavedUiState {
+    pub fn capture(display: &GameInfoDisplay, vehicle_manager: &VehicleManager, marked_satellites: &HashSet<EntityId>) -> Self {
+        let visualization = vehicle_manager.visualization();
+        let mut marked: Vec<EntityId> = marked_satellites.iter().copied().collect();
+        marked.sort_unstable();
+
+        SavedUiState {
+            marked_satellites: marked,
+            show_rocket_panel: display.is_panel_shown(HudPanel::Rocket),
+            show_planet_panel: display.is_panel_shown(HudPanel::Planet),
+            show_orbit_panel: display.is_panel_shown(HudPanel::Orbit),
+            show_network_panel: display.is_panel_shown(HudPanel::Network),
+            show_trajectory: visualization.show_trajectory,
+            show_gravity_forces: visualization.show_gravity_forces,
+            show_planet_trajectories: visualization.show_planet_trajectories,
+            show_satellite_orbits: visualization.show_satellite_orbits,
+            reference_body: visualization.reference_body,
+        }
+    }
+
+    /// Apply to a freshly loaded world (marks on satellites that no longer exist are dropped)
+    pub fn apply(
+        &self,
+        world: &World,
+        display: &mut GameInfoDisplay,
+        vehicle_manager: &mut VehicleManager,
+        marked_satellites: &mut HashSet<EntityId>,
+    ) {
+        *marked_satellites = self
+            .marked_satellites
+            .iter()
+            .copied()
+            .filter(|&id| world.get_satellite(id).is_some())
+            .collect();
+
+        display.set_panel_shown(HudPanel::Rocket, self.show_rocket_panel);
+        display.set_panel_shown(HudPanel::Planet, self.show_planet_panel);
+        display.set_panel_shown(HudPanel::Orbit, self.show_orbit_panel);
+        display.set_panel_shown(HudPanel::Network, self.show_network_panel);
+
+        let mut visualization = vehicle_manager.visualization().clone();
+        visualization.show_trajectory = self.show_trajectory;
+        visualization.show_gravity_forces = self.show_gravity_forces;
+        visualization.show_planet_trajectories = self.show_planet_trajectories;
+        visualization.show_satellite_orbits = self.show_satellite_orbits;
+        visualization.reference_body = if self.reference_body < world.planet_count() {
+            self.reference_body
+        } else {
+            0
+        };
+        vehicle_manager.set_visualization(visualization);
+    }
+}
+
 /// Complete game save data / state snapshot
 /// Used for both save files (disk) and network packets (multiplayer)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -286,12 +600,286 @@ pub struct GameSaveData {
 
     // Map configuration
     pub map_name: Option<String>,    // Which map is being played (e.g., "earth moon", "solar 1")
+
+    // Per-save UI state (only in save files)
+    pub ui_state: Option<SavedUiState>,
+
+    // Player waypoints (snapshots carry only the ones the receiving player can see)
+    pub waypoints: Vec<SavedWaypoint>,
+
+    // Floating origin all positions above are relative to
+    pub origin: SavedOrigin,
+
+    // Wrapping arena (None = open space); snapshots carry it so clients wrap like the host
+    pub arena: Option<ArenaBounds>,
+
+    // Look of the sky and planet surfaces (0 = classic); snapshots carry it so clients see the host's sky
+    pub cosmetic_seed: u64,
+}
+
+/// Version 8 layout (before the cosmetic seed)
+#[derive(Deserialize)]
+struct GameSaveDataV8 {
+    version: u32,
+    timestamp_secs: u64,
+    game_time: f32,
+    planets: Vec<SavedPlanet>,
+    rockets: Vec<SavedRocket>,
+    satellites: Vec<SavedSatellite>,
+    bullets: Vec<SavedBullet>,
+    player_id: Option<u32>,
+    active_rocket_id: Option<EntityId>,
+    player_names: HashMap<u32, String>,
+    camera: SavedCamera,
+    map_name: Option<String>,
+    ui_state: Option<SavedUiState>,
+    waypoints: Vec<SavedWaypoint>,
+    origin: SavedOrigin,
+    arena: Option<ArenaBounds>,
+}
+
+impl From<GameSaveDataV8> for GameSaveData {
+    fn from(v8: GameSaveDataV8) -> Self {
+        GameSaveData {
+            version: v8.version,
+            timestamp_secs: v8.timestamp_secs,
+            game_time: v8.game_time,
+            planets: v8.planets,
+            rockets: v8.rockets,
+            satellites: v8.satellites,
+            bullets: v8.bullets,
+            player_id: v8.player_id,
+            active_rocket_id: v8.active_rocket_id,
+            player_names: v8.player_names,
+            camera: v8.camera,
+            map_name: v8.map_name,
+            ui_state: v8.ui_state,
+            waypoints: v8.waypoints,
+            origin: v8.origin,
+            arena: v8.arena,
+            cosmetic_seed: 0,
+        }
+    }
+}
+
+/// Version 7 layout (before the arena bounds)
+#[derive(Deserialize)]
+struct GameSaveDataV7 {
+    version: u32,
+    timestamp_secs: u64,
+    game_time: f32,
+    planets: Vec<SavedPlanet>,
+    rockets: Vec<SavedRocket>,
+    satellites: Vec<SavedSatellite>,
+    bullets: Vec<SavedBullet>,
+    player_id: Option<u32>,
+    active_rocket_id: Option<EntityId>,
+    player_names: HashMap<u32, String>,
+    camera: SavedCamera,
+    map_name: Option<String>,
+    ui_state: Option<SavedUiState>,
+    waypoints: Vec<SavedWaypoint>,
+    origin: SavedOrigin,
+}
+
+impl From<GameSaveDataV7> for GameSaveData {
+    fn from(v7: GameSaveDataV7) -> Self {
+        GameSaveData {
+            version: v7.version,
+            timestamp_secs: v7.timestamp_secs,
+            game_time: v7.game_time,
+            planets: v7.planets,
+            rockets: v7.rockets,
+            satellites: v7.satellites,
+            bullets: v7.bullets,
+            player_id: v7.player_id,
+            active_rocket_id: v7.active_rocket_id,
+            player_names: v7.player_names,
+            camera: v7.camera,
+            map_name: v7.map_name,
+            ui_state: v7.ui_state,
+            waypoints: v7.waypoints,
+            origin: v7.origin,
+            arena: None,
+            cosmetic_seed: 0,
+        }
+    }
+}
+
+/// Version 6 layout (before satellite wear)
+#[derive(Deserialize)]
+struct GameSaveDataV6 {
+    version: u32,
+    timestamp_secs: u64,
+    game_time: f32,
+    planets: Vec<SavedPlanet>,
+    rockets: Vec<SavedRocket>,
+    satellites: Vec<SavedSatelliteV6>,
+    bullets: Vec<SavedBullet>,
+    player_id: Option<u32>,
+    active_rocket_id: Option<EntityId>,
+    player_names: HashMap<u32, String>,
+    camera: SavedCamera,
+    map_name: Option<String>,
+    ui_state: Option<SavedUiState>,
+    waypoints: Vec<SavedWaypoint>,
+    origin: SavedOrigin,
+}
+
+impl From<GameSaveDataV6> for GameSaveData {
+    fn from(v6: GameSaveDataV6) -> Self {
+        GameSaveData {
+            version: v6.version,
+            timestamp_secs: v6.timestamp_secs,
+            game_time: v6.game_time,
+            planets: v6.planets,
+            rockets: v6.rockets,
+            satellites: v6.satellites.into_iter().map(SavedSatellite::from).collect(),
+            bullets: v6.bullets,
+            player_id: v6.player_id,
+            active_rocket_id: v6.active_rocket_id,
+            player_names: v6.player_names,
+            camera: v6.camera,
+            map_name: v6.map_name,
+            ui_state: v6.ui_state,
+            waypoints: v6.waypoints,
+            origin: v6.origin,
+            arena: None,
+            cosmetic_seed: 0,
+        }
+    }
+}
+
+/// Version 5 layout (before the floating origin, so positions are world positions)
+#[derive(Deserialize)]
+struct GameSaveDataV5 {
+    version: u32,
+    timestamp_secs: u64,
+    game_time: f32,
+    planets: Vec<SavedPlanet>,
+    rockets: Vec<SavedRocket>,
+    satellites: Vec<SavedSatelliteV6>,
+    bullets: Vec<SavedBullet>,
+    player_id: Option<u32>,
+    active_rocket_id: Option<EntityId>,
+    player_names: HashMap<u32, String>,
+    camera: SavedCamera,
+    map_name: Option<String>,
+    ui_state: Option<SavedUiState>,
+    waypoints: Vec<SavedWaypoint>,
+}
+
+impl From<GameSaveDataV5> for GameSaveData {
+    fn from(v5: GameSaveDataV5) -> Self {
+        GameSaveData {
+            version: v5.version,
+            timestamp_secs: v5.timestamp_secs,
+            game_time: v5.game_time,
+            planets: v5.planets,
+            rockets: v5.rockets,
+            satellites: v5.satellites.into_iter().map(SavedSatellite::from).collect(),
+            bullets: v5.bullets,
+            player_id: v5.player_id,
+            active_rocket_id: v5.active_rocket_id,
+            player_names: v5.player_names,
+            camera: v5.camera,
+            map_name: v5.map_name,
+            ui_state: v5.ui_state,
+            waypoints: v5.waypoints,
+            origin: SavedOrigin::default(),
+            arena: None,
+            cosmetic_seed: 0,
+        }
+    }
+}
+
+/// Version 2-4 layout (before waypoints; versions 2-3 satellites lack owners, version 2 bullets time-to-live)
+#[derive(Deserialize)]
+struct GameSaveDataV2<B, S> {
+    version: u32,
+    timestamp_secs: u64,
+    game_time: f32,
+    planets: Vec<SavedPlanet>,
+    rockets: Vec<SavedRocket>,
+    satellites: Vec<S>,
+    bullets: Vec<B>,
+    player_id: Option<u32>,
+    active_rocket_id: Option<EntityId>,
+    player_names: HashMap<u32, String>,
+    camera: SavedCamera,
+    map_name: Option<String>,
+    ui_state: Option<SavedUiState>,
+}
+
+impl<B: Into<SavedBullet>, S: Into<SavedSatellite>> From<GameSaveDataV2<B, S>> for GameSaveData {
+    fn from(v2: GameSaveDataV2<B, S>) -> Self {
+        GameSaveData {
+            version: v2.version,
+            timestamp_secs: v2.timestamp_secs,
+            game_time: v2.game_time,
+            planets: v2.planets,
+            rockets: v2.rockets,
+            satellites: v2.satellites.into_iter().map(Into::into).collect(),
+            bullets: v2.bullets.into_iter().map(Into::into).collect(),
+            player_id: v2.player_id,
+            active_rocket_id: v2.active_rocket_id,
+            player_names: v2.player_names,
+            camera: v2.camera,
+            map_name: v2.map_name,
+            ui_state: v2.ui_state,
+            waypoints: Vec::new(),
+            origin: SavedOrigin::default(),
+            arena: None,
+            cosmetic_seed: 0,
+        }
+    }
+}
+
+/// Version 1 layout (before ui_state), so older save files still load
+#[derive(Deserialize)]
+struct GameSaveDataV1 {
+    version: u32,
+    timestamp_secs: u64,
+    game_time: f32,
+    planets: Vec<SavedPlanet>,
+    rockets: Vec<SavedRocket>,
+    satellites: Vec<SavedSatelliteV3>,
+    bullets: Vec<SavedBulletV2>,
+    player_id: Option<u32>,
+    active_rocket_id: Option<EntityId>,
+    player_names: HashMap<u32, String>,
+    camera: SavedCamera,
+    map_name: Option<String>,
+}
+
+impl From<GameSaveDataV1> for GameSaveData {
+    fn from(v1: GameSaveDataV1) -> Self {
+        GameSaveData {
+            version: v1.version,
+            timestamp_secs: v1.timestamp_secs,
+            game_time: v1.game_time,
+            planets: v1.planets,
+            rockets: v1.rockets,
+            satellites: v1.satellites.into_iter().map(SavedSatellite::from).collect(),
+            bullets: v1.bullets.into_iter().map(SavedBullet::from).collect(),
+            player_id: v1.player_id,
+            active_rocket_id: v1.active_rocket_id,
+            player_names: v1.player_names,
+            camera: v1.camera,
+            map_name: v1.map_name,
+            ui_state: None,
+            waypoints: Vec::new(),
+            origin: SavedOrigin::default(),
+            arena: None,
+            cosmetic_seed: 0,
+        }
+    }
 }
 
 impl GameSaveData {
     pub fn new() -> Self {
         GameSaveData {
-            version: 1,
+            version: SAVE_VERSION,
             timestamp_secs: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
@@ -309,19 +897,39 @@ impl GameSaveData {
                 zoom: 1.0,
             },
             map_name: None,   // No map specified by default
+            ui_state: None,
+            waypoints: Vec::new(),
+            origin: SavedOrigin::default(),
+            arena: None,
+            cosmetic_seed: 0,
         }
     }
 
+    /// Verify a save file's checksum and decode it in whichever layout its leading version number says
+    pub(crate) fn decode_save_file(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        let bytes = unseal(bytes)?;
+        let version: u32 = bincode::deserialize(bytes)?;
+        Ok(match version {
+            1 => bincode::deserialize::<GameSaveDataV1>(bytes)?.into(),
+            2 => bincode::deserialize::<GameSaveDataV2<SavedBulletV2, SavedSatelliteV3>>(bytes)?.into(),
+            3 => bincode::deserialize::<GameSaveDataV2<SavedBullet, SavedSatelliteV3>>(bytes)?.into(),
+            4 => bincode::deserialize::<GameSaveDataV2<SavedBullet, SavedSatelliteV6>>(bytes)?.into(),
+            5 => bincode::deserialize::<GameSaveDataV5>(bytes)?.into(),
+            6 => bincode::deserialize::<GameSaveDataV6>(bytes)?.into(),
+            7 => bincode::deserialize::<GameSaveDataV7>(bytes)?.into(),
+            8 => bincode::deserialize::<GameSaveDataV8>(bytes)?.into(),
+            _ => bincode::deserialize::<GameSaveData>(bytes)?,
+        })
+    }
+
     /// Save to binary file using bincode
     /// Binary format is compact and fast - perfect for both saves and network packets
     pub fn save_to_file(&self, save_name: &str) -> Result<(), Box<dyn std::error::Error>> {
         // Create saves directory if it doesn't exist
-        fs::create_dir_all("saves")?;
+        fs::create_dir_all(local_profiles::saves_dir())?;
 
-        let file_path = format!("saves/{}.sav", save_name);
-        let bytes = bincode::serialize(self)?;
-        let byte_count = bytes.len();
-        fs::write(&file_path, bytes)?;
+        let file_path = local_profiles::saves_path(&format!("{}.sav", save_name));
+        let byte_count = write_sealed(&file_path, bincode::serialize(self)?)?;
 
         log::info!("Game saved to: {} ({} bytes)", file_path, byte_count);
         Ok(())
@@ -330,50 +938,111 @@ impl GameSaveData {
     /// Save to multiplayer saves folder (saves/multi/)
     pub fn save_to_multi_file(&self, save_name: &str) -> Result<(), Box<dyn std::error::Error>> {
         // Create saves/multi directory if it doesn't exist
-        fs::create_dir_all("saves/multi")?;
+        fs::create_dir_all(local_profiles::saves_path("multi"))?;
 
-        let file_path = format!("saves/multi/{}.sav", save_name);
-        let bytes = bincode::serialize(self)?;
-        let byte_count = bytes.len();
-        fs::write(&file_path, bytes)?;
+        let file_path = local_profiles::saves_path(&format!("multi/{}.sav", save_name));
+        let byte_count = write_sealed(&file_path, bincode::serialize(self)?)?;
 
         log::info!("Multiplayer game saved to: {} ({} bytes)", file_path, byte_count);
         Ok(())
     }
 
-    /// Load from binary file using bincode
+    /// Load from binary file using bincode (names ending in .json are imported from the modding schema)
     pub fn load_from_file(save_name: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let file_path = format!("saves/{}.sav", save_name);
+        if save_name.ends_with(".json") {
+            return Self::import_schema(&local_profiles::saves_path(save_name));
+        }
+        let file_path = local_profiles::saves_path(&format!("{}.sav", save_name));
 
         if !Path::new(&file_path).exists() {
             return Err(format!("Save file not found: {}", file_path).into());
         }
 
         let bytes = fs::read(&file_path)?;
-        let save_data: GameSaveData = bincode::deserialize(&bytes)?;
+        let save_data = Self::decode_save_file(&bytes)?;
 
         log::info!("Game loaded from: {} ({} bytes)", file_path, bytes.len());
         Ok(save_data)
     }
 
+    /// Write a sealed save file to an exact path (for tools and debug dumps); returns the byte count
+    pub fn save_to_path(&self, path: &str) -> Result<usize, Box<dyn std::error::Error>> {
+        Ok(write_sealed(path, bincode::serialize(self)?)?)
+    }
+
+    /// Load a save file by its path rather than its name (.json files are imported from the modding schema)
+    pub fn load_from_path(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        if path.ends_with(".json") {
+            return Self::import_schema(path);
+        }
+        if !Path::new(path).exists() {
+            return Err(format!("Save file not found: {}", path).into());
+        }
+        Self::decode_save_file(&fs::read(path)?)
+    }
+
     /// Load from multiplayer saves folder (saves/multi/)
     pub fn load_from_multi_file(save_name: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let file_path = format!("saves/multi/{}.sav", save_name);
+        let file_path = local_profiles::saves_path(&format!("multi/{}.sav", save_name));
 
         if !Path::new(&file_path).exists() {
             return Err(format!("Multiplayer save file not found: {}", file_path).into());
         }
 
         let bytes = fs::read(&file_path)?;
-        let save_data: GameSaveData = bincode::deserialize(&bytes)?;
+        let save_data = Self::decode_save_file(&bytes)?;
 
         log::info!("Multiplayer game loaded from: {} ({} bytes)", file_path, bytes.len());
         Ok(save_data)
     }
 
+    /// Save to the newest autosave slot, shifting the older ones down (the oldest is dropped)
+    pub fn save_autosave(&self) -> Result<(), Box<dyn std::error::Error>> {
+        fs::create_dir_all(local_profiles::saves_dir())?;
+        for slot in (1..AUTOSAVE_SLOTS).rev() {
+            let from = local_profiles::saves_path(&format!("{}.sav", autosave_slot_name(slot - 1)));
+            if Path::new(&from).exists() {
+                fs::rename(&from, local_profiles::saves_path(&format!("{}.sav", autosave_slot_name(slot))))?;
+            }
+        }
+        self.save_to_file(AUTOSAVE_NAME)
+    }
+
+    /// Load a save; if it's damaged or truncated, fall back to the newest autosave that still loads
+    pub fn load_or_recover(save_name: &str) -> Result<RecoveredSave, Box<dyn std::error::Error>> {
+        let error = match Self::load_from_file(save_name) {
+            Ok(data) => return Ok(RecoveredSave { data, save_name: save_name.to_string(), notice: None }),
+            Err(e) => e,
+        };
+        log::warn!("Failed to load save '{}': {}", save_name, error);
+
+        // Newest first by modification time (a crash can leave the slots out of order)
+        let mut autosaves: Vec<(String, std::time::SystemTime)> = (0..AUTOSAVE_SLOTS)
+            .map(autosave_slot_name)
+            .filter(|name| name != save_name)
+            .filter_map(|name| {
+                let modified = fs::metadata(local_profiles::saves_path(&format!("{}.sav", name))).and_then(|meta| meta.modified()).ok()?;
+                Some((name, modified))
+            })
+            .collect();
+        autosaves.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+
+        for (name, _) in autosaves {
+            match Self::load_from_file(&name) {
+                Ok(data) => {
+                    let notice = format!("'{}' is damaged ({}) - loaded {} instead", save_name, error, name);
+                    log::warn!("{}", notice);
+                    return Ok(RecoveredSave { data, save_name: name, notice: Some(notice) });
+                }
+                Err(e) => log::warn!("Autosave '{}' is unusable too: {}", name, e),
+            }
+        }
+        Err(format!("'{}' could not be loaded ({}) and no autosave is usable", save_name, error).into())
+    }
+
     /// Delete a save file
     pub fn delete_save(save_name: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let file_path = format!("saves/{}.sav", save_name);
+        let file_path = local_profiles::saves_path(&format!("{}.sav", save_name));
         fs::remove_file(&file_path)?;
         log::info!("Deleted save: {}", file_path);
         Ok(())
@@ -381,10 +1050,28 @@ impl GameSaveData {
 
     /// Check if a save exists
     pub fn save_exists(save_name: &str) -> bool {
-        let file_path = format!("saves/{}.sav", save_name);
+        let file_path = local_profiles::saves_path(&format!("{}.sav", save_name));
         Path::new(&file_path).exists()
     }
 
+    /// Check if a multiplayer save exists
+    pub fn multi_save_exists(save_name: &str) -> bool {
+        let file_path = local_profiles::saves_path(&format!("multi/{}.sav", save_name));
+        Path::new(&file_path).exists()
+    }
+
+    /// Export in the modding schema (JSON if the path ends in .json, RON otherwise)
+    pub fn export_schema(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        crate::schemas::write_file(&crate::schemas::SaveSchema::from(self), path)?;
+        log::info!("Game exported to: {}", path);
+        Ok(())
+    }
+
+    /// Import a save written in the modding schema
+    pub fn import_schema(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(crate::schemas::read_file::<crate::schemas::SaveSchema>(path)?.into_save_data()?)
+    }
+
     /// Serialize to bytes (for network packets)
     pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
         Ok(bincode::serialize(self)?)
@@ -421,7 +1108,7 @@ mod tests {
     #[test]
     fn test_save_data_creation() {
         let save_data = GameSaveData::new();
-        assert_eq!(save_data.version, 1);
+        assert_eq!(save_data.version, SAVE_VERSION);
         assert_eq!(save_data.game_time, 0.0);
         assert!(save_data.planets.is_empty());
     }
@@ -453,4 +1140,137 @@ mod tests {
         assert_eq!(restored_data.version, save_data.version);
         assert_eq!(restored_data.player_id, None);
     }
+
+    #[test]
+    fn test_ui_state_round_trip() {
+        let mut save_data = GameSaveData::new();
+        save_data.ui_state = Some(SavedUiState {
+            marked_satellites: vec![3, 7],
+            show_rocket_panel: true,
+            show_planet_panel: false,
+            show_orbit_panel: true,
+            show_network_panel: true,
+            show_trajectory: false,
+            show_gravity_forces: true,
+            show_planet_trajectories: false,
+            show_satellite_orbits: true,
+            reference_body: 2,
+        });
+
+        let bytes = bincode::serialize(&save_data).unwrap();
+        let restored = GameSaveData::decode_save_file(&bytes).unwrap();
+        assert_eq!(restored.ui_state, save_data.ui_state);
+    }
+
+    #[test]
+    fn test_version_1_save_still_loads() {
+        // A version 1 file is the current layout without the trailing ui_state
+        let mut save_data = GameSaveData::new();
+        save_data.version = 1;
+        save_data.map_name = Some("earth moon".to_string());
+        let bytes = bincode::serialize(&save_data).unwrap();
+        let v1_bytes = &bytes[..bytes.len() - 1];
+
+        let restored = GameSaveData::decode_save_file(v1_bytes).unwrap();
+        assert_eq!(restored.version, 1);
+        assert_eq!(restored.map_name, save_data.map_name);
+        assert!(restored.ui_state.is_none());
+    }
+
+    #[test]
+    fn test_version_2_bullets_get_default_lifetime() {
+        // A version 2 bullet is the current one without the trailing max_lifetime
+        let bullet = (7usize, SavedVector2 { x: 1.0, y: 2.0 }, SavedVector2 { x: 3.0, y: 4.0 }, 1.0f32, 5.0f32, (255u8, 255u8, 255u8));
+        let save = GameSaveData::new();
+        let v2 = (
+            2u32, save.timestamp_secs, save.game_time, save.planets, save.rockets, save.satellites, vec![bullet],
+            save.player_id, save.active_rocket_id, save.player_names, save.camera, save.map_name, save.ui_state,
+        );
+        let bytes = bincode::serialize(&v2).unwrap();
+
+        let restored = GameSaveData::decode_save_file(&bytes).unwrap();
+        assert_eq!(restored.version, 2);
+        assert_eq!(restored.bullets.len(), 1);
+        let (id, bullet) = restored.bullets[0].to_bullet();
+        assert_eq!(id, 7);
+        assert_eq!(bullet.lifetime(), 5.0);
+        assert_eq!(bullet.max_lifetime(), Bullet::new(Vec2::ZERO, Vec2::ZERO).max_lifetime());
+    }
+
+    #[test]
+    fn test_origin_round_trip_and_version_5() {
+        let mut save_data = GameSaveData::new();
+        save_data.origin = SavedOrigin { x: 3_000_000.0, y: -125_000.5 };
+        let restored = GameSaveData::from_bytes(&save_data.to_bytes().unwrap()).unwrap();
+        assert_eq!(FloatingOrigin::from(restored.origin).offset(), DVec2::new(3_000_000.0, -125_000.5));
+
+        // A version 5 file is the current layout without the trailing origin
+        save_data.version = 5;
+        let bytes = bincode::serialize(&save_data).unwrap();
+        let restored = GameSaveData::decode_save_file(&bytes[..bytes.len() - 16]).unwrap();
+        assert_eq!(restored.version, 5);
+        assert_eq!(restored.origin, SavedOrigin::default());
+    }
+
+    #[test]
+    fn test_satellite_wear_round_trip_and_version_6() {
+        let mut satellite = Satellite::new(Vec2::new(10.0, 20.0), Vec2::ZERO, WHITE);
+        satellite.set_wear(0.75);
+        let saved = SavedSatellite::from_satellite(4, &satellite);
+        assert_eq!(saved.to_satellite().1.wear(), 0.75);
+
+        // A version 6 satellite is the current one without the trailing wear
+        let mut save_data = GameSaveData::new();
+        save_data.version = 6;
+        save_data.satellites.push(saved.clone());
+        let bytes = bincode::serialize(&save_data).unwrap();
+        let satellite_bytes = bincode::serialize(&saved).unwrap();
+        let start = bytes.windows(satellite_bytes.len()).position(|window| window == satellite_bytes).unwrap();
+        let wear_start = start + satellite_bytes.len() - 4;
+        let v6_bytes = [&bytes[..wear_start], &bytes[wear_start + 4..]].concat();
+
+        let restored = GameSaveData::decode_save_file(&v6_bytes).unwrap();
+        assert_eq!(restored.version, 6);
+        let (id, satellite) = restored.satellites[0].to_satellite();
+        assert_eq!(id, 4);
+        assert_eq!(satellite.wear(), 0.0);
+        assert_eq!(satellite.position(), Vec2::new(10.0, 20.0));
+    }
+
+    #[test]
+    fn test_cosmetic_seed_round_trip_and_version_8() {
+        let mut save_data = GameSaveData::new();
+        save_data.cosmetic_seed = 0xdead_beef;
+        let restored = GameSaveData::from_bytes(&save_data.to_bytes().unwrap()).unwrap();
+        assert_eq!(restored.cosmetic_seed, 0xdead_beef);
+
+        // A version 8 file is the current layout without the trailing seed, and keeps the classic look
+        save_data.version = 8;
+        let bytes = bincode::serialize(&save_data).unwrap();
+        let restored = GameSaveData::decode_save_file(&bytes[..bytes.len() - 8]).unwrap();
+        assert_eq!(restored.version, 8);
+        assert_eq!(restored.cosmetic_seed, 0);
+    }
+
+    #[test]
+    fn test_checksum_catches_corruption_and_truncation() {
+        let mut save_data = GameSaveData::new();
+        save_data.map_name = Some("earth moon".to_string());
+        let sealed = seal(bincode::serialize(&save_data).unwrap());
+        assert_eq!(GameSaveData::decode_save_file(&sealed).unwrap().map_name, save_data.map_name);
+
+        let mut flipped = sealed.clone();
+        flipped[10] ^= 0x40;
+        let error = GameSaveData::decode_save_file(&flipped).unwrap_err().to_string();
+        assert!(error.contains("checksum"));
+
+        // Cut off mid-payload: the trailer is gone and what's left doesn't decode
+        assert!(GameSaveData::decode_save_file(&sealed[..sealed.len() / 2]).is_err());
+
+        // Files from before checksums still load
+        let legacy = bincode::serialize(&save_data).unwrap();
+        assert_eq!(unseal(&legacy).unwrap(), &legacy[..]);
+        assert_eq!(autosave_slot_name(0), AUTOSAVE_NAME);
+        assert_eq!(autosave_slot_name(2), "autosave_2");
+    }
 }
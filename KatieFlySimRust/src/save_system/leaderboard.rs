@@ -0,0 +1,206 @@
+// Leaderboard - Best scenario times, landing zone scores and daily challenge scores of every local profile on this machine
+// Shared by all profiles at saves/leaderboard.ron; a board is only written out for sharing when the player exports it
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Shared by every profile, so it lives in saves/ rather than a profile's folder
+pub const LEADERBOARD_PATH: &str = "saves/leaderboard.ron";
+/// Where "Export" writes the shown board
+pub const EXPORT_PATH: &str = "saves/leaderboard_export.json";
+
+/// What a board ranks by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoardKind {
+    ScenarioTime, // Seconds, fastest first
+    DailyScore,   // Points, highest first
+    ZoneScore,    // Precision landing points (0-100), highest first
+}
+
+impl BoardKind {
+    fn better(&self, a: f32, b: f32) -> bool {
+        match self {
+            BoardKind::ScenarioTime => a < b,
+            BoardKind::DailyScore | BoardKind::ZoneScore => a > b,
+        }
+    }
+
+    /// A value as shown on the board
+    pub fn format(&self, value: f32) -> String {
+        match self {
+            BoardKind::ScenarioTime => format!("{:.1}s", value),
+            BoardKind::DailyScore => format!("{:.0} pts", value),
+            BoardKind::ZoneScore => format!("{:.1} pts", value),
+        }
+    }
+
+    /// Where boards of this kind are listed (scenarios, then landing zones, then dailies)
+    fn list_order(&self) -> u8 {
+        match self {
+            BoardKind::ScenarioTime => 0,
+            BoardKind::ZoneScore => 1,
+            BoardKind::DailyScore => 2,
+        }
+    }
+}
+
+/// One profile's best on one board
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub board: String, // Scenario name or "Daily 2026-10-17"
+    pub kind: BoardKind,
+    pub map: String,
+    pub profile: String,
+    pub value: f32,
+    pub date: String, // Day the best was set
+}
+
+/// A board's header, for paging between boards
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardInfo {
+    pub board: String,
+    pub kind: BoardKind,
+    pub map: String,
+}
+
+/// Every profile's bests, across sessions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Leaderboard {
+    entries: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    /// Load the shared leaderboard, starting empty if it's missing or unreadable
+    pub fn load() -> Self {
+        match Self::load_from_path(LEADERBOARD_PATH) {
+            Ok(leaderboard) => leaderboard,
+            Err(e) => {
+                if Path::new(LEADERBOARD_PATH).exists() {
+                    log::warn!("Failed to load leaderboard, starting a new one: {}", e);
+                }
+                Self::default()
+            }
+        }
+    }
+
+    pub fn load_from_path(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(path)?;
+        Ok(ron::from_str(&contents)?)
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        fs::create_dir_all("saves")?;
+        self.save_to_path(LEADERBOARD_PATH)
+    }
+
+    pub fn save_to_path(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let ron_string = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, ron_string)?;
+        Ok(())
+    }
+
+    /// Record a result; returns true if it is the profile's new best on that board
+    pub fn record(&mut self, kind: BoardKind, board: &str, map: &str, profile: &str, value: f32, date: &str) -> bool {
+        let entry = LeaderboardEntry {
+            board: board.to_string(),
+            kind,
+            map: map.to_string(),
+            profile: profile.to_string(),
+            value,
+            date: date.to_string(),
+        };
+        match self.entries.iter_mut().find(|e| e.kind == kind && e.board == board && e.profile == profile) {
+            Some(best) if !kind.better(value, best.value) => false,
+            Some(best) => {
+                *best = entry;
+                true
+            }
+            None => {
+                self.entries.push(entry);
+                true
+            }
+        }
+    }
+
+    /// Load, record and save in one go (for the game modes, which don't keep the board around)
+    pub fn record_shared(kind: BoardKind, board: &str, map: &str, profile: &str, value: f32, date: &str) {
+        let mut leaderboard = Self::load();
+        if leaderboard.record(kind, board, map, profile, value, date) {
+            if let Err(e) = leaderboard.save() {
+                log::error!("Failed to save leaderboard: {}", e);
+            }
+        }
+    }
+
+    /// Every board with at least one entry: scenarios and landing zones by name, then daily challenges newest first
+    pub fn boards(&self) -> Vec<BoardInfo> {
+        let mut boards: Vec<BoardInfo> = Vec::new();
+        for entry in &self.entries {
+            if !boards.iter().any(|b| b.kind == entry.kind && b.board == entry.board) {
+                boards.push(BoardInfo { board: entry.board.clone(), kind: entry.kind, map: entry.map.clone() });
+            }
+        }
+        boards.sort_by(|a, b| {
+            a.kind.list_order().cmp(&b.kind.list_order()).then_with(|| match a.kind {
+                BoardKind::DailyScore => b.board.cmp(&a.board),
+                BoardKind::ScenarioTime | BoardKind::ZoneScore => a.board.cmp(&b.board),
+            })
+        });
+        boards
+    }
+
+    /// One board's entries, best first
+    pub fn rankings(&self, kind: BoardKind, board: &str) -> Vec<&LeaderboardEntry> {
+        let mut ranked: Vec<&LeaderboardEntry> = self.entries.iter().filter(|e| e.kind == kind && e.board == board).collect();
+        ranked.sort_by(|a, b| match kind {
+            BoardKind::ScenarioTime => a.value.total_cmp(&b.value),
+            BoardKind::DailyScore | BoardKind::ZoneScore => b.value.total_cmp(&a.value),
+        });
+        ranked
+    }
+
+    /// Write one board's rankings as JSON for sharing
+    pub fn export_board(&self, kind: BoardKind, board: &str, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let ranked = self.rankings(kind, board);
+        fs::write(path, serde_json::to_string_pretty(&ranked)?)?;
+        log::info!("Leaderboard '{}' exported to: {}", board, path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_rank() {
+        let mut leaderboard = Leaderboard::default();
+        assert!(leaderboard.record(BoardKind::ScenarioTime, "First Orbit", "Earth-Moon", "Katie", 120.0, "2026-10-16"));
+        assert!(leaderboard.record(BoardKind::ScenarioTime, "First Orbit", "Earth-Moon", "Sam", 95.0, "2026-10-16"));
+        // A slower run doesn't replace Katie's best, a faster one does
+        assert!(!leaderboard.record(BoardKind::ScenarioTime, "First Orbit", "Earth-Moon", "Katie", 130.0, "2026-10-17"));
+        assert!(leaderboard.record(BoardKind::ScenarioTime, "First Orbit", "Earth-Moon", "Katie", 90.0, "2026-10-17"));
+        assert!(leaderboard.record(BoardKind::DailyScore, "Daily 2026-10-17", "Solar 1", "Sam", 800.0, "2026-10-17"));
+        assert!(leaderboard.record(BoardKind::DailyScore, "Daily 2026-10-17", "Solar 1", "Katie", 650.0, "2026-10-17"));
+        assert!(leaderboard.record(BoardKind::ZoneScore, "Zone Apollo Pad", "earth moon", "Katie", 87.5, "2026-10-17"));
+
+        let times: Vec<(&str, f32)> = leaderboard
+            .rankings(BoardKind::ScenarioTime, "First Orbit")
+            .iter()
+            .map(|e| (e.profile.as_str(), e.value))
+            .collect();
+        assert_eq!(times, vec![("Katie", 90.0), ("Sam", 95.0)]);
+        let scores: Vec<&str> = leaderboard.rankings(BoardKind::DailyScore, "Daily 2026-10-17").iter().map(|e| e.profile.as_str()).collect();
+        assert_eq!(scores, vec!["Sam", "Katie"]);
+
+        let boards: Vec<String> = leaderboard.boards().into_iter().map(|b| b.board).collect();
+        assert_eq!(boards, vec!["First Orbit".to_string(), "Zone Apollo Pad".to_string(), "Daily 2026-10-17".to_string()]);
+
+        let text = ron::to_string(&leaderboard).unwrap();
+        let restored: Leaderboard = ron::from_str(&text).unwrap();
+        assert_eq!(restored.rankings(BoardKind::ScenarioTime, "First Orbit").len(), 2);
+    }
+}
@@ -0,0 +1,121 @@
+// Local Profiles - Named players sharing one install, each with their own saves folder
+// The default profile keeps using saves/ directly; named ones live in saves/profiles/<name>/
+
+use std::fs;
+use std::path::Path;
+use std::sync::RwLock;
+
+/// Name shown for the profile that owns saves/ itself
+pub const DEFAULT_PROFILE: &str = "Default";
+/// Longest profile name accepted (matches the other name fields)
+pub const MAX_NAME_LENGTH: usize = 16;
+/// Folder holding one subfolder per named profile
+const PROFILES_FOLDER: &str = "saves/profiles";
+/// Remembers the profile picked last so the next launch starts on it
+const LAST_PROFILE_PATH: &str = "saves/last_profile.txt";
+
+lazy_static::lazy_static! {
+    static ref ACTIVE: RwLock<Option<String>> = RwLock::new(None); // None is the default profile
+}
+
+/// Name of the active profile
+pub fn active() -> String {
+    ACTIVE.read().unwrap().clone().unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// Saves folder of the active profile
+pub fn saves_dir() -> String {
+    dir_for(ACTIVE.read().unwrap().as_deref())
+}
+
+/// Path of `file_name` inside the active profile's saves folder
+pub fn saves_path(file_name: &str) -> String {
+    format!("{}/{}", saves_dir(), file_name)
+}
+
+/// Saves folder for a profile (None is the default profile)
+pub fn dir_for(profile: Option<&str>) -> String {
+    match profile {
+        Some(name) => format!("{}/{}", PROFILES_FOLDER, name),
+        None => "saves".to_string(),
+    }
+}
+
+/// Check a profile name; returns the trimmed name, or None for the default profile
+pub fn validate_name(name: &str) -> Result<Option<String>, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name is empty".to_string());
+    }
+    if name.eq_ignore_ascii_case(DEFAULT_PROFILE) {
+        return Ok(None);
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(format!("Profile names are at most {} characters", MAX_NAME_LENGTH));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == ' ' || c == '-' || c == '_') {
+        return Err("Profile names may only use letters, digits, spaces, '-' and '_'".to_string());
+    }
+    Ok(Some(name.to_string()))
+}
+
+/// Switch to a profile, creating its folder if it's new, and remember it for the next launch
+pub fn select(name: &str) -> Result<(), String> {
+    let profile = validate_name(name)?;
+    let dir = dir_for(profile.as_deref());
+    fs::create_dir_all(&dir).map_err(|e| format!("Couldn't create {}: {}", dir, e))?;
+    if let Err(e) = fs::write(LAST_PROFILE_PATH, profile.as_deref().unwrap_or(DEFAULT_PROFILE)) {
+        log::warn!("Failed to remember the selected profile: {}", e);
+    }
+    log::info!("Profile '{}' selected (saves in {})", profile.as_deref().unwrap_or(DEFAULT_PROFILE), dir);
+    *ACTIVE.write().unwrap() = profile;
+    Ok(())
+}
+
+/// Switch back to the profile used last, if it still exists
+pub fn restore_last() {
+    let Ok(name) = fs::read_to_string(LAST_PROFILE_PATH) else {
+        return;
+    };
+    match validate_name(&name) {
+        Ok(Some(profile)) if Path::new(&dir_for(Some(&profile))).is_dir() => {
+            *ACTIVE.write().unwrap() = Some(profile);
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("Ignoring last profile '{}': {}", name.trim(), e),
+    }
+}
+
+/// Every profile on disk, the default first and the rest alphabetically
+pub fn list() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(PROFILES_FOLDER)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter(|name| matches!(validate_name(name), Ok(Some(_))))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort_by_key(|name| name.to_lowercase());
+    names.insert(0, DEFAULT_PROFILE.to_string());
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_names_and_folders() {
+        assert_eq!(validate_name("  Katie "), Ok(Some("Katie".to_string())));
+        assert_eq!(validate_name("default"), Ok(None));
+        assert!(validate_name("").is_err());
+        assert!(validate_name("../escape").is_err());
+        assert!(validate_name("a name far too long to fit").is_err());
+
+        assert_eq!(dir_for(None), "saves");
+        assert_eq!(dir_for(Some("Katie")), "saves/profiles/Katie");
+    }
+}
@@ -1,5 +1,18 @@
 // Save/load system module
 
+pub mod bug_report;
 pub mod game_save_data;
+pub mod leaderboard;
+pub mod local_profiles;
+pub mod player_profile;
+pub mod save_diff;
+#[cfg(feature = "sqlite")]
+pub mod world_database;
 
-pub use game_save_data::{GameSaveData, SavedCamera, SavedPlanet, SavedRocket, SavedSatellite, SavedBullet, SavedVector2};
+pub use game_save_data::{GameSaveData, RecoveredSave, SavedCamera, SavedPlanet, SavedRocket, SavedSatellite, SavedBullet, SavedVector2, SavedUiState, SavedWaypoint, SavedOrigin};
+pub use player_profile::{PlayerProfile, LandingRecord};
+pub use bug_report::BugReport;
+pub use save_diff::{SaveChange, SaveDiff};
+pub use leaderboard::{BoardInfo, BoardKind, Leaderboard, LeaderboardEntry};
+#[cfg(feature = "sqlite")]
+pub use world_database::{PlayerStat, WorldDatabase};
@@ -0,0 +1,297 @@
+// Player Profile - Persistent per-player stats and preferences
+// Stored as human-readable RON at profile.ron in the active local profile's saves folder
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::save_system::local_profiles;
+use crate::systems::{LandingGrade, LandingResult};
+use crate::ui::{BulletViewOptions, HudLayout};
+
+/// Credits a new profile starts with, enough for a first cargo
+const STARTING_CREDITS: u32 = 500;
+
+/// Best landing achieved on one planet
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LandingRecord {
+    pub grade: LandingGrade,
+    pub score: f32,
+    pub vertical_speed: f32,
+    pub horizontal_speed: f32,
+    pub tilt_degrees: f32,
+    pub landings: u32, // Total landings on this planet
+}
+
+/// Persistent player profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerProfile {
+    pub name: String,
+    /// Best landing per planet name
+    pub best_landings: HashMap<String, LandingRecord>,
+    /// HUD panel arrangement per layout key (e.g. "single_player", "split_screen_p1")
+    pub hud_layouts: HashMap<String, HudLayout>,
+    /// Samples returned home per map name (sample-return mission credit)
+    pub returned_samples: HashMap<String, u32>,
+    /// Best daily challenge score per date ("2026-10-17")
+    pub daily_best: HashMap<String, u32>,
+    /// Fastest completion per scenario name (seconds)
+    pub scenario_best: HashMap<String, f32>,
+    /// Best precision landing score per map landing zone ("earth moon: Apollo Pad")
+    pub zone_best: HashMap<String, f32>,
+    /// Earned hauling fuel between stations, spent buying it
+    pub credits: u32,
+    /// Gamepad rumble strength from the settings slider (0.0 is off)
+    pub rumble_intensity: f32,
+    /// Camera shake on landings, explosions and hits (off for players sensitive to motion)
+    pub screen_shake: bool,
+    /// Bullet tracers, impact flashes and predicted arcs
+    pub bullet_view: BulletViewOptions,
+    /// Rebound keys, action name -> key name (see player_input::BINDABLE_ACTIONS)
+    pub key_bindings: BTreeMap<String, String>,
+}
+
+impl Default for PlayerProfile {
+    fn default() -> Self {
+        PlayerProfile {
+            name: "Player".to_string(),
+            best_landings: HashMap::new(),
+            hud_layouts: HashMap::new(),
+            returned_samples: HashMap::new(),
+            daily_best: HashMap::new(),
+            scenario_best: HashMap::new(),
+            zone_best: HashMap::new(),
+            credits: STARTING_CREDITS,
+            rumble_intensity: 1.0,
+            screen_shake: true,
+            bullet_view: BulletViewOptions::default(),
+            key_bindings: BTreeMap::new(),
+        }
+    }
+}
+
+impl PlayerProfile {
+    /// Load the profile, falling back to a fresh one if missing or unreadable
+    pub fn load_or_default() -> Self {
+        let path = Self::path();
+        match Self::load_from_path(&path) {
+            Ok(profile) => profile,
+            Err(e) => {
+                if Path::new(&path).exists() {
+                    log::warn!("Failed to load profile, using defaults: {}", e);
+                }
+                Self::default()
+            }
+        }
+    }
+
+    pub fn load_from_path(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(path)?;
+        let profile: PlayerProfile = ron::from_str(&contents)?;
+        Ok(profile)
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        fs::create_dir_all(local_profiles::saves_dir())?;
+        self.save_to_path(&Self::path())
+    }
+
+    /// Where the active local profile keeps its settings and stats
+    pub fn path() -> String {
+        local_profiles::saves_path("profile.ron")
+    }
+
+    pub fn save_to_path(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let ron_string = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, ron_string)?;
+        Ok(())
+    }
+
+    /// Record a landing; returns true if it is a new best for that planet
+    pub fn record_landing(&mut self, planet_name: &str, result: &LandingResult) -> bool {
+        let new_record = LandingRecord {
+            grade: result.grade,
+            score: result.score,
+            vertical_speed: result.vertical_speed,
+            horizontal_speed: result.horizontal_speed,
+            tilt_degrees: result.tilt_degrees,
+            landings: 1,
+        };
+
+        match self.best_landings.get_mut(planet_name) {
+            Some(record) => {
+                let landings = record.landings + 1;
+                let is_best = result.score > record.score;
+                if is_best {
+                    *record = new_record;
+                }
+                record.landings = landings;
+                is_best
+            }
+            None => {
+                self.best_landings.insert(planet_name.to_string(), new_record);
+                true
+            }
+        }
+    }
+
+    pub fn best_landing(&self, planet_name: &str) -> Option<&LandingRecord> {
+        self.best_landings.get(planet_name)
+    }
+
+    /// Credit samples delivered home on a map; returns the map's new total
+    pub fn record_sample_return(&mut self, map_name: &str, count: u32) -> u32 {
+        let total = self.returned_samples.entry(map_name.to_string()).or_insert(0);
+        *total += count;
+        *total
+    }
+
+    /// Record a daily challenge score; returns true if it beats the earlier attempts that day
+    pub fn record_daily_score(&mut self, date: &str, score: u32) -> bool {
+        match self.daily_best.get(date) {
+            Some(&best) if best >= score => false,
+            _ => {
+                self.daily_best.insert(date.to_string(), score);
+                true
+            }
+        }
+    }
+
+    /// Record a scenario completion time; returns true if it's the fastest so far
+    pub fn record_scenario_time(&mut self, scenario: &str, time: f32) -> bool {
+        match self.scenario_best.get(scenario) {
+            Some(&best) if best <= time => false,
+            _ => {
+                self.scenario_best.insert(scenario.to_string(), time);
+                true
+            }
+        }
+    }
+
+    /// Record a precision landing score in a map's landing zone; returns true if it's the best so far
+    pub fn record_zone_score(&mut self, map_name: &str, zone: &str, score: f32) -> bool {
+        let key = format!("{}: {}", map_name, zone);
+        match self.zone_best.get(&key) {
+            Some(&best) if best >= score => false,
+            _ => {
+                self.zone_best.insert(key, score);
+                true
+            }
+        }
+    }
+
+    /// Add credits; returns the new balance
+    pub fn add_credits(&mut self, amount: u32) -> u32 {
+        self.credits = self.credits.saturating_add(amount);
+        self.credits
+    }
+
+    /// Take credits if there are enough; false leaves the balance alone
+    pub fn spend_credits(&mut self, amount: u32) -> bool {
+        match self.credits.checked_sub(amount) {
+            Some(left) => {
+                self.credits = left;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn hud_layout(&self, key: &str) -> Option<&HudLayout> {
+        self.hud_layouts.get(key)
+    }
+
+    /// Store a HUD layout and write the profile to disk
+    pub fn save_hud_layout(&mut self, key: &str, layout: HudLayout) {
+        self.hud_layouts.insert(key.to_string(), layout);
+        if let Err(e) = self.save() {
+            log::error!("Failed to save profile: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::{HudPanel, PanelLayout};
+    use macroquad::prelude::Vec2;
+
+    fn landing(vertical_speed: f32) -> LandingResult {
+        LandingResult::evaluate(
+            Vec2::new(0.0, -100.0),
+            Vec2::new(0.0, vertical_speed),
+            0.0,
+            Vec2::ZERO,
+            Vec2::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_record_landing_keeps_best() {
+        let mut profile = PlayerProfile::default();
+
+        assert!(profile.record_landing("Earth", &landing(40.0)));
+        assert!(profile.record_landing("Earth", &landing(5.0)));
+        assert!(!profile.record_landing("Earth", &landing(100.0)));
+
+        let record = profile.best_landing("Earth").unwrap();
+        assert_eq!(record.grade, LandingGrade::Perfect);
+        assert_eq!(record.landings, 3);
+    }
+
+    #[test]
+    fn test_daily_best_per_date() {
+        let mut profile = PlayerProfile::default();
+        assert!(profile.record_daily_score("2026-10-17", 500));
+        assert!(!profile.record_daily_score("2026-10-17", 450));
+        assert!(profile.record_daily_score("2026-10-17", 720));
+        assert!(profile.record_daily_score("2026-10-18", 100));
+        assert_eq!(profile.daily_best.get("2026-10-17"), Some(&720));
+
+        assert!(profile.record_scenario_time("Moon Hop", 300.0));
+        assert!(!profile.record_scenario_time("Moon Hop", 310.0));
+        assert!(profile.record_scenario_time("Moon Hop", 290.0));
+        assert!(profile.record_zone_score("earth moon", "Apollo Pad", 72.0));
+        assert!(!profile.record_zone_score("earth moon", "Apollo Pad", 60.0));
+    }
+
+    #[test]
+    fn test_credits_never_go_negative() {
+        let mut profile = PlayerProfile::default();
+        assert_eq!(profile.credits, STARTING_CREDITS);
+        assert!(!profile.spend_credits(STARTING_CREDITS + 1));
+        assert!(profile.spend_credits(200));
+        assert_eq!(profile.add_credits(50), STARTING_CREDITS - 150);
+    }
+
+    #[test]
+    fn test_profile_round_trip() {
+        let mut profile = PlayerProfile::default();
+        profile.record_landing("Moon", &landing(10.0));
+        let mut layout = HudLayout::default();
+        layout.set(HudPanel::Rocket, PanelLayout { x: 1.0, y: 2.0, width: 300.0, height: 150.0, collapsed: true });
+        profile.hud_layouts.insert("single_player".to_string(), layout);
+
+        let path = std::env::temp_dir().join("katie_profile_test.ron");
+        let path = path.to_str().unwrap();
+        profile.save_to_path(path).unwrap();
+        let loaded = PlayerProfile::load_from_path(path).unwrap();
+        let _ = fs::remove_file(path);
+
+        assert_eq!(loaded.name, profile.name);
+        assert_eq!(loaded.best_landing("Moon"), profile.best_landing("Moon"));
+        assert_eq!(loaded.hud_layouts, profile.hud_layouts);
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let profile: PlayerProfile = ron::from_str("(name: \"Katie\")").unwrap();
+        assert_eq!(profile.name, "Katie");
+        assert!(profile.best_landings.is_empty());
+        assert_eq!(profile.credits, STARTING_CREDITS);
+        assert_eq!(profile.rumble_intensity, 1.0);
+    }
+}
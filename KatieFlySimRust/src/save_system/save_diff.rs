@@ -0,0 +1,220 @@
+// Save Diff - Field-by-field comparison of two saved world states
+// Used by the save_diff tool to chase "my save loads wrong" reports and host/client divergence: entities are
+// matched by ID, every field is compared (numbers within a tolerance), and only what differs is listed
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::save_system::{local_profiles, GameSaveData};
+use crate::systems::EntityId;
+
+/// Numbers closer than this count as equal unless the caller picks another tolerance
+pub const DEFAULT_TOLERANCE: f64 = 1e-3;
+/// Desync dumps written per session by each side (the first few mismatches are the interesting ones)
+pub const MAX_DESYNC_DUMPS: u32 = 5;
+
+/// Write a world state for a desync report to saves/desync/<side>_<time>.sav and return the path
+pub fn write_desync_dump(save: &GameSaveData, side: &str, game_time: f32) -> Result<String, Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(local_profiles::saves_path("desync"))?;
+    let path = local_profiles::saves_path(&format!("desync/{}_{:.2}.sav", side, game_time));
+    save.save_to_path(&path)?;
+    log::info!("Desync dump written to {}", path);
+    Ok(path)
+}
+
+/// One difference between the left and right saves
+#[derive(Debug, Clone, PartialEq)]
+pub enum SaveChange {
+    /// Only the left save has this (e.g. "rocket 3")
+    OnlyLeft(String),
+    /// Only the right save has this
+    OnlyRight(String),
+    /// Both have the field with different values
+    Changed { path: String, left: String, right: String, delta: Option<f64> },
+}
+
+impl fmt::Display for SaveChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveChange::OnlyLeft(path) => write!(f, "- {} (left only)", path),
+            SaveChange::OnlyRight(path) => write!(f, "+ {} (right only)", path),
+            SaveChange::Changed { path, left, right, delta: Some(delta) } => {
+                write!(f, "~ {}: {} -> {} (delta {:+.4})", path, left, right, delta)
+            }
+            SaveChange::Changed { path, left, right, delta: None } => write!(f, "~ {}: {} -> {}", path, left, right),
+        }
+    }
+}
+
+/// Everything that differs between two saves, in a stable order (header, then planets, rockets, satellites, bullets, waypoints)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SaveDiff {
+    pub changes: Vec<SaveChange>,
+    pub origins_differ: bool, // Entity positions are relative to different origins, so position deltas include the offset
+}
+
+impl SaveDiff {
+    pub fn compare(left: &GameSaveData, right: &GameSaveData, tolerance: f64) -> Self {
+        let mut diff = SaveDiff { changes: Vec::new(), origins_differ: left.origin != right.origin };
+
+        // Header fields (entities and per-save UI state are compared separately or not at all)
+        let header = |save: &GameSaveData| {
+            let mut value = to_value(save);
+            if let Value::Object(map) = &mut value {
+                for key in ["planets", "rockets", "satellites", "bullets", "waypoints", "ui_state", "camera", "timestamp_secs"] {
+                    map.remove(key);
+                }
+            }
+            value
+        };
+        diff_values("save", &header(left), &header(right), tolerance, &mut diff.changes);
+
+        diff.compare_entities("planet", by_id(&left.planets, |p| p.id), by_id(&right.planets, |p| p.id), tolerance);
+        diff.compare_entities("rocket", by_id(&left.rockets, |r| r.id), by_id(&right.rockets, |r| r.id), tolerance);
+        diff.compare_entities("satellite", by_id(&left.satellites, |s| s.id), by_id(&right.satellites, |s| s.id), tolerance);
+        diff.compare_entities("bullet", by_id(&left.bullets, |b| b.id), by_id(&right.bullets, |b| b.id), tolerance);
+        diff.compare_entities(
+            "waypoint",
+            by_id(&left.waypoints, |w| w.id as EntityId),
+            by_id(&right.waypoints, |w| w.id as EntityId),
+            tolerance,
+        );
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    fn compare_entities(&mut self, kind: &str, left: BTreeMap<EntityId, Value>, right: BTreeMap<EntityId, Value>, tolerance: f64) {
+        for (id, left_value) in &left {
+            let path = format!("{} {}", kind, id);
+            match right.get(id) {
+                Some(right_value) => diff_values(&path, left_value, right_value, tolerance, &mut self.changes),
+                None => self.changes.push(SaveChange::OnlyLeft(path)),
+            }
+        }
+        for id in right.keys().filter(|id| !left.contains_key(id)) {
+            self.changes.push(SaveChange::OnlyRight(format!("{} {}", kind, id)));
+        }
+    }
+
+    /// Counts per change kind, for the last line of a report
+    pub fn summary(&self) -> String {
+        let count = |matches: fn(&SaveChange) -> bool| self.changes.iter().filter(|change| matches(change)).count();
+        format!(
+            "{} changed, {} left only, {} right only",
+            count(|change| matches!(change, SaveChange::Changed { .. })),
+            count(|change| matches!(change, SaveChange::OnlyLeft(_))),
+            count(|change| matches!(change, SaveChange::OnlyRight(_))),
+        )
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+fn by_id<T: Serialize>(entities: &[T], id: impl Fn(&T) -> EntityId) -> BTreeMap<EntityId, Value> {
+    entities.iter().map(|entity| (id(entity), to_value(entity))).collect()
+}
+
+/// Walk two values side by side, recording leaves that differ under `path` (e.g. "rocket 3.position.x")
+fn diff_values(path: &str, left: &Value, right: &Value, tolerance: f64, changes: &mut Vec<SaveChange>) {
+    match (left, right) {
+        (Value::Object(left_map), Value::Object(right_map)) => {
+            let mut keys: Vec<&String> = left_map.keys().chain(right_map.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let child = format!("{}.{}", path, key);
+                match (left_map.get(key), right_map.get(key)) {
+                    (Some(l), Some(r)) => diff_values(&child, l, r, tolerance, changes),
+                    (Some(_), None) => changes.push(SaveChange::OnlyLeft(child)),
+                    (None, Some(_)) => changes.push(SaveChange::OnlyRight(child)),
+                    (None, None) => {}
+                }
+            }
+        }
+        (Value::Array(left_items), Value::Array(right_items)) if left_items.len() == right_items.len() => {
+            for (i, (l, r)) in left_items.iter().zip(right_items).enumerate() {
+                diff_values(&format!("{}[{}]", path, i), l, r, tolerance, changes);
+            }
+        }
+        (Value::Number(l), Value::Number(r)) => {
+            let (Some(l), Some(r)) = (l.as_f64(), r.as_f64()) else {
+                return;
+            };
+            if (l - r).abs() > tolerance {
+                changes.push(SaveChange::Changed { path: path.to_string(), left: short(left), right: short(right), delta: Some(r - l) });
+            }
+        }
+        _ if left != right => {
+            changes.push(SaveChange::Changed { path: path.to_string(), left: short(left), right: short(right), delta: None });
+        }
+        _ => {}
+    }
+}
+
+/// A value on one line, cut down if it's long
+fn short(value: &Value) -> String {
+    const MAX_LEN: usize = 60;
+    let text = value.to_string();
+    if text.chars().count() <= MAX_LEN {
+        return text;
+    }
+    let cut: String = text.chars().take(MAX_LEN).collect();
+    format!("{}...", cut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Planet, Rocket};
+    use crate::save_system::{SavedPlanet, SavedRocket};
+    use macroquad::prelude::*;
+
+    fn save_with_rocket(x: f32) -> GameSaveData {
+        let mut save = GameSaveData::new();
+        save.map_name = Some("earth moon".to_string());
+        save.planets.push(SavedPlanet::from_planet(0, &Planet::new(Vec2::ZERO, 100.0, 1000.0, BLUE)));
+        save.rockets.push(SavedRocket::from_rocket(1, &Rocket::new(Vec2::new(x, 0.0), Vec2::ZERO, WHITE, 1.0)));
+        save
+    }
+
+    #[test]
+    fn test_identical_saves_have_no_diff() {
+        let save = save_with_rocket(200.0);
+        let mut later = save.clone();
+        later.timestamp_secs += 60; // When it was written doesn't matter
+        assert!(SaveDiff::compare(&save, &later, DEFAULT_TOLERANCE).is_empty());
+    }
+
+    #[test]
+    fn test_diff_lists_moved_missing_and_renamed() {
+        let left = save_with_rocket(200.0);
+        let mut right = save_with_rocket(200.5);
+        right.map_name = Some("solar 1".to_string());
+        right.rockets.push(SavedRocket::from_rocket(7, &Rocket::new(Vec2::ZERO, Vec2::ZERO, RED, 1.0)));
+        right.planets.clear();
+
+        let diff = SaveDiff::compare(&left, &right, DEFAULT_TOLERANCE);
+        assert!(diff.changes.contains(&SaveChange::Changed {
+            path: "save.map_name".to_string(),
+            left: "\"earth moon\"".to_string(),
+            right: "\"solar 1\"".to_string(),
+            delta: None,
+        }));
+        assert!(diff.changes.iter().any(|change| matches!(change, SaveChange::Changed { path, delta: Some(d), .. } if path == "rocket 1.position.x" && (d - 0.5).abs() < 1e-6)));
+        assert!(diff.changes.contains(&SaveChange::OnlyLeft("planet 0".to_string())));
+        assert!(diff.changes.contains(&SaveChange::OnlyRight("rocket 7".to_string())));
+        assert_eq!(diff.summary(), "2 changed, 1 left only, 1 right only");
+
+        // A loose enough tolerance ignores the drift
+        let diff = SaveDiff::compare(&left, &right, 1.0);
+        assert!(!diff.changes.iter().any(|change| matches!(change, SaveChange::Changed { path, .. } if path.starts_with("rocket 1"))));
+    }
+}
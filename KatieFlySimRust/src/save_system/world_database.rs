@@ -0,0 +1,400 @@
+// World Database - Optional SQLite archive for long-running hosts (`sqlite` feature)
+// Periodic world snapshots, player identities, lifetime stats and chat history in one database file
+
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::save_system::GameSaveData;
+use crate::systems::daily_challenge::ChallengeDate;
+
+/// Seconds of game time between automatic snapshots
+pub const SNAPSHOT_INTERVAL: f32 = 60.0;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS sessions (
+        id INTEGER PRIMARY KEY,
+        started_at INTEGER NOT NULL,
+        save_name TEXT
+    );
+    CREATE TABLE IF NOT EXISTS snapshots (
+        id INTEGER PRIMARY KEY,
+        session_id INTEGER NOT NULL REFERENCES sessions(id),
+        created_at INTEGER NOT NULL,
+        game_time REAL NOT NULL,
+        map_name TEXT,
+        player_count INTEGER NOT NULL,
+        data BLOB NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS players (
+        name TEXT PRIMARY KEY,
+        first_seen INTEGER NOT NULL,
+        last_seen INTEGER NOT NULL,
+        sessions INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS stats (
+        player TEXT NOT NULL,
+        stat TEXT NOT NULL,
+        value INTEGER NOT NULL,
+        PRIMARY KEY (player, stat)
+    );
+    CREATE TABLE IF NOT EXISTS chat (
+        id INTEGER PRIMARY KEY,
+        session_id INTEGER NOT NULL REFERENCES sessions(id),
+        sent_at INTEGER NOT NULL,
+        game_time REAL NOT NULL,
+        player TEXT NOT NULL,
+        message TEXT NOT NULL
+    );
+";
+
+/// Lifetime counters kept per player name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerStat {
+    ShotsFired,
+    SatellitesLaunched,
+    RocketsLost,
+    Landings,
+}
+
+impl PlayerStat {
+    pub const ALL: [PlayerStat; 4] = [
+        PlayerStat::ShotsFired,
+        PlayerStat::SatellitesLaunched,
+        PlayerStat::RocketsLost,
+        PlayerStat::Landings,
+    ];
+
+    /// Column value in the stats table
+    pub fn key(&self) -> &'static str {
+        match self {
+            PlayerStat::ShotsFired => "shots_fired",
+            PlayerStat::SatellitesLaunched => "satellites_launched",
+            PlayerStat::RocketsLost => "rockets_lost",
+            PlayerStat::Landings => "landings",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|stat| stat.key() == key)
+    }
+}
+
+/// One stored snapshot (without its world data)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotInfo {
+    pub id: i64,
+    pub session_id: i64,
+    pub created_at: i64, // Unix seconds
+    pub game_time: f32,
+    pub map_name: Option<String>,
+    pub player_count: u32,
+    pub size: usize, // Bytes of world data
+}
+
+/// A player identity (players are known by name across sessions)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerRecord {
+    pub name: String,
+    pub first_seen: i64, // Unix seconds
+    pub last_seen: i64,
+    pub sessions: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatRecord {
+    pub session_id: i64,
+    pub sent_at: i64, // Unix seconds
+    pub game_time: f32,
+    pub player: String,
+    pub message: String,
+}
+
+/// Open archive database; the host writes through it, the world_db tool reads it
+pub struct WorldDatabase {
+    connection: Connection,
+    session_id: Option<i64>, // Session this process is writing
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// "2026-10-17 14:05 UTC" for a unix timestamp
+pub fn format_timestamp(secs: i64) -> String {
+    let date = ChallengeDate::from_days_since_epoch(secs.div_euclid(86_400));
+    let minutes = secs.rem_euclid(86_400) / 60;
+    format!("{} {:02}:{:02} UTC", date.label(), minutes / 60, minutes % 60)
+}
+
+impl WorldDatabase {
+    /// Open (or create) the database file
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
+        if let Some(parent) = path.as_ref().parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// Throwaway database (for tests and tools)
+    pub fn open_in_memory() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, Box<dyn std::error::Error>> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(WorldDatabase { connection, session_id: None })
+    }
+
+    /// Begin a new hosting session; snapshots and chat are filed under it
+    pub fn start_session(&mut self, save_name: Option<&str>) -> Result<i64, Box<dyn std::error::Error>> {
+        self.connection.execute(
+            "INSERT INTO sessions (started_at, save_name) VALUES (?1, ?2)",
+            params![now(), save_name],
+        )?;
+        let id = self.connection.last_insert_rowid();
+        self.session_id = Some(id);
+        Ok(id)
+    }
+
+    fn current_session(&self) -> Result<i64, Box<dyn std::error::Error>> {
+        self.session_id.ok_or_else(|| "No session started".into())
+    }
+
+    /// Store the world as it is now; returns the snapshot ID
+    pub fn save_snapshot(&mut self, save_data: &GameSaveData) -> Result<i64, Box<dyn std::error::Error>> {
+        let session_id = self.current_session()?;
+        let data = bincode::serialize(save_data)?;
+        self.connection.execute(
+            "INSERT INTO snapshots (session_id, created_at, game_time, map_name, player_count, data)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                session_id,
+                now(),
+                save_data.game_time,
+                save_data.map_name,
+                save_data.player_names.len() as u32,
+                data,
+            ],
+        )?;
+        Ok(self.connection.last_insert_rowid())
+    }
+
+    /// Newest snapshots first
+    pub fn snapshots(&self, limit: usize) -> Result<Vec<SnapshotInfo>, Box<dyn std::error::Error>> {
+        let mut statement = self.connection.prepare(
+            "SELECT id, session_id, created_at, game_time, map_name, player_count, length(data)
+             FROM snapshots ORDER BY id DESC LIMIT ?1",
+        )?;
+        let rows = statement.query_map(params![limit as i64], |row| {
+            Ok(SnapshotInfo {
+                id: row.get(0)?,
+                session_id: row.get(1)?,
+                created_at: row.get(2)?,
+                game_time: row.get(3)?,
+                map_name: row.get(4)?,
+                player_count: row.get(5)?,
+                size: row.get::<_, i64>(6)? as usize,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// World stored in a snapshot
+    pub fn load_snapshot(&self, id: i64) -> Result<GameSaveData, Box<dyn std::error::Error>> {
+        let data: Option<Vec<u8>> = self
+            .connection
+            .query_row("SELECT data FROM snapshots WHERE id = ?1", params![id], |row| row.get(0))
+            .optional()?;
+        let data = data.ok_or_else(|| format!("No snapshot {}", id))?;
+        GameSaveData::decode_save_file(&data)
+    }
+
+    /// Forget every snapshot newer than `id` so history continues from it; returns how many were dropped
+    pub fn roll_back_to(&mut self, id: i64) -> Result<usize, Box<dyn std::error::Error>> {
+        let exists: Option<i64> = self
+            .connection
+            .query_row("SELECT id FROM snapshots WHERE id = ?1", params![id], |row| row.get(0))
+            .optional()?;
+        if exists.is_none() {
+            return Err(format!("No snapshot {}", id).into());
+        }
+        Ok(self.connection.execute("DELETE FROM snapshots WHERE id > ?1", params![id])?)
+    }
+
+    /// A player joined this session (first sighting creates their identity)
+    pub fn player_joined(&mut self, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let now = now();
+        self.connection.execute(
+            "INSERT INTO players (name, first_seen, last_seen, sessions) VALUES (?1, ?2, ?2, 1)
+             ON CONFLICT(name) DO UPDATE SET last_seen = ?2, sessions = sessions + 1",
+            params![name, now],
+        )?;
+        Ok(())
+    }
+
+    /// Players still around; refreshes their last-seen time
+    pub fn players_seen(&mut self, names: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+        let transaction = self.connection.transaction()?;
+        {
+            let mut statement = transaction.prepare("UPDATE players SET last_seen = ?1 WHERE name = ?2")?;
+            let now = now();
+            for name in names {
+                statement.execute(params![now, name])?;
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Most recently seen first
+    pub fn players(&self) -> Result<Vec<PlayerRecord>, Box<dyn std::error::Error>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT name, first_seen, last_seen, sessions FROM players ORDER BY last_seen DESC, name")?;
+        let rows = statement.query_map([], |row| {
+            Ok(PlayerRecord {
+                name: row.get(0)?,
+                first_seen: row.get(1)?,
+                last_seen: row.get(2)?,
+                sessions: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    pub fn add_stat(&mut self, player: &str, stat: PlayerStat, amount: u32) -> Result<(), Box<dyn std::error::Error>> {
+        self.connection.execute(
+            "INSERT INTO stats (player, stat, value) VALUES (?1, ?2, ?3)
+             ON CONFLICT(player, stat) DO UPDATE SET value = value + ?3",
+            params![player, stat.key(), amount],
+        )?;
+        Ok(())
+    }
+
+    /// One player's counters (missing ones are zero)
+    pub fn stats_for(&self, player: &str) -> Result<Vec<(PlayerStat, u64)>, Box<dyn std::error::Error>> {
+        let mut statement = self.connection.prepare("SELECT value FROM stats WHERE player = ?1 AND stat = ?2")?;
+        let mut stats = Vec::new();
+        for stat in PlayerStat::ALL {
+            let value: Option<i64> = statement.query_row(params![player, stat.key()], |row| row.get(0)).optional()?;
+            stats.push((stat, value.unwrap_or(0) as u64));
+        }
+        Ok(stats)
+    }
+
+    /// Highest counts of one stat, best first
+    pub fn leaderboard(&self, stat: PlayerStat, limit: usize) -> Result<Vec<(String, u64)>, Box<dyn std::error::Error>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT player, value FROM stats WHERE stat = ?1 ORDER BY value DESC, player LIMIT ?2")?;
+        let rows = statement.query_map(params![stat.key(), limit as i64], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    pub fn record_chat(&mut self, game_time: f32, player: &str, message: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let session_id = self.current_session()?;
+        self.connection.execute(
+            "INSERT INTO chat (session_id, sent_at, game_time, player, message) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![session_id, now(), game_time, player, message],
+        )?;
+        Ok(())
+    }
+
+    /// Latest chat lines, oldest first
+    pub fn chat_history(&self, limit: usize) -> Result<Vec<ChatRecord>, Box<dyn std::error::Error>> {
+        let mut statement = self.connection.prepare(
+            "SELECT session_id, sent_at, game_time, player, message FROM
+             (SELECT * FROM chat ORDER BY id DESC LIMIT ?1) ORDER BY id",
+        )?;
+        let rows = statement.query_map(params![limit as i64], |row| {
+            Ok(ChatRecord {
+                session_id: row.get(0)?,
+                sent_at: row.get(1)?,
+                game_time: row.get(2)?,
+                player: row.get(3)?,
+                message: row.get(4)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot_at(game_time: f32) -> GameSaveData {
+        let mut save_data = GameSaveData::new();
+        save_data.game_time = game_time;
+        save_data.map_name = Some("earth moon".to_string());
+        save_data.player_names.insert(0, "Host".to_string());
+        save_data
+    }
+
+    #[test]
+    fn test_snapshots_round_trip_and_roll_back() {
+        let mut db = WorldDatabase::open_in_memory().unwrap();
+        assert!(db.save_snapshot(&snapshot_at(1.0)).is_err()); // No session yet
+
+        db.start_session(Some("server")).unwrap();
+        let first = db.save_snapshot(&snapshot_at(60.0)).unwrap();
+        db.save_snapshot(&snapshot_at(120.0)).unwrap();
+        db.save_snapshot(&snapshot_at(180.0)).unwrap();
+
+        let listed = db.snapshots(10).unwrap();
+        assert_eq!(listed.len(), 3);
+        assert_eq!(listed[0].game_time, 180.0);
+        assert_eq!(listed[2].map_name.as_deref(), Some("earth moon"));
+
+        let loaded = db.load_snapshot(first).unwrap();
+        assert_eq!(loaded.game_time, 60.0);
+        assert_eq!(loaded.player_names.get(&0).map(String::as_str), Some("Host"));
+
+        assert_eq!(db.roll_back_to(first).unwrap(), 2);
+        assert_eq!(db.snapshots(10).unwrap().len(), 1);
+        assert!(db.roll_back_to(first + 1).is_err());
+        assert!(db.load_snapshot(first + 1).is_err());
+    }
+
+    #[test]
+    fn test_players_stats_and_chat() {
+        let mut db = WorldDatabase::open_in_memory().unwrap();
+        db.start_session(None).unwrap();
+        db.player_joined("Katie").unwrap();
+        db.player_joined("Sam").unwrap();
+        db.player_joined("Katie").unwrap();
+
+        let players = db.players().unwrap();
+        assert_eq!(players.len(), 2);
+        assert_eq!(players.iter().find(|p| p.name == "Katie").unwrap().sessions, 2);
+
+        db.add_stat("Katie", PlayerStat::ShotsFired, 3).unwrap();
+        db.add_stat("Katie", PlayerStat::ShotsFired, 2).unwrap();
+        db.add_stat("Sam", PlayerStat::ShotsFired, 7).unwrap();
+        let katie = db.stats_for("Katie").unwrap();
+        assert!(katie.contains(&(PlayerStat::ShotsFired, 5)));
+        assert!(katie.contains(&(PlayerStat::Landings, 0)));
+        assert_eq!(db.leaderboard(PlayerStat::ShotsFired, 1).unwrap(), vec![("Sam".to_string(), 7)]);
+
+        db.record_chat(1.0, "Katie", "hello").unwrap();
+        db.record_chat(2.0, "Sam", "hi").unwrap();
+        let chat = db.chat_history(1).unwrap();
+        assert_eq!(chat.len(), 1);
+        assert_eq!(chat[0].message, "hi");
+    }
+
+    #[test]
+    fn test_stat_keys_round_trip() {
+        for stat in PlayerStat::ALL {
+            assert_eq!(PlayerStat::from_key(stat.key()), Some(stat));
+        }
+        assert_eq!(format_timestamp(86_400 + 3_660), "1970-01-02 01:01 UTC");
+    }
+}
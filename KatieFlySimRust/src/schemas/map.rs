@@ -0,0 +1,496 @@
+// Map Schema - The interchange layout for map files
+// Field names match the game's RON maps, so every existing maps/*.ron file is already a valid schema document
+
+use macroquad::prelude::Color;
+use serde::{Deserialize, Serialize};
+
+use super::schema_version;
+use crate::map_config::{ArenaConfig, CelestialBodyConfig, FuelDepositConfig, LandingZoneConfig, MapConfiguration, MapTunables, RingConfig, SampleSiteConfig, TerrainConfig};
+use crate::physics::GravityModel;
+use crate::systems::arena::MIN_ARENA_SIZE;
+
+/// A whole map: bodies, where players spawn and the optional surface features
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapSchema {
+    #[serde(default = "schema_version")]
+    pub schema_version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub celestial_bodies: Vec<BodySchema>,
+    pub player_spawn_body_index: usize, // Index into celestial_bodies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub central_body_index: Option<usize>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub tidal_breakup: bool,
+    #[serde(default, skip_serializing_if = "GravityModelSchema::is_realistic")]
+    pub gravity_model: GravityModelSchema,
+    #[serde(default, skip_serializing_if = "TunablesSchema::is_empty")]
+    pub tunables: TunablesSchema,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sample_sites: Vec<SampleSiteSchema>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fuel_deposits: Vec<FuelDepositSchema>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub landing_zones: Vec<LandingZoneSchema>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rings: Vec<RingSchema>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arena: Option<ArenaSizeSchema>,
+}
+
+/// How planet gravity falls off with distance
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum GravityModelSchema {
+    #[default]
+    Realistic, // Inverse-square
+    Arcade,    // Flat near the surface, steeper falloff
+    Custom { exponent: f32 }, // 1/r^exponent, equal to inverse-square at the surface
+}
+
+impl GravityModelSchema {
+    fn is_realistic(&self) -> bool {
+        *self == GravityModelSchema::Realistic
+    }
+}
+
+impl From<GravityModel> for GravityModelSchema {
+    fn from(model: GravityModel) -> Self {
+        match model {
+            GravityModel::Realistic => GravityModelSchema::Realistic,
+            GravityModel::Arcade => GravityModelSchema::Arcade,
+            GravityModel::Custom { exponent } => GravityModelSchema::Custom { exponent },
+        }
+    }
+}
+
+impl From<GravityModelSchema> for GravityModel {
+    fn from(model: GravityModelSchema) -> Self {
+        match model {
+            GravityModelSchema::Realistic => GravityModel::Realistic,
+            GravityModelSchema::Arcade => GravityModel::Arcade,
+            GravityModelSchema::Custom { exponent } => GravityModel::Custom { exponent },
+        }
+    }
+}
+
+/// Per-map overrides of rocket and bullet physics (omitted values use the game defaults)
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct TunablesSchema {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rocket_base_mass: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rocket_max_fuel: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bullet_speed: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spawn_altitude: Option<f32>, // Above the spawn body's surface
+}
+
+impl TunablesSchema {
+    fn is_empty(&self) -> bool {
+        *self == TunablesSchema::default()
+    }
+}
+
+/// RGBA, each 0.0-1.0
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ColorSchema {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    #[serde(default = "opaque")]
+    pub a: f32,
+}
+
+fn opaque() -> f32 {
+    1.0
+}
+
+/// A planet, moon or star
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BodySchema {
+    pub name: String,
+    pub mass: f32,
+    pub radius: f32,
+    pub color: ColorSchema,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orbital_parent_index: Option<usize>, // None = stationary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orbital_distance: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orbital_period: Option<f32>, // Seconds per orbit
+    #[serde(default)]
+    pub initial_angle: f32, // Radians (0 = right, π/2 = up)
+    #[serde(default)]
+    pub is_pinned: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terrain: Option<TerrainSchema>, // None = perfectly round
+}
+
+/// Generated terrain, or authored heights if any are given
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TerrainSchema {
+    pub seed: u64,
+    pub amplitude: f32, // Tallest peak as a fraction of the radius
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub heights: Vec<f32>, // Fractions of the radius, evenly spaced from angle 0
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SampleSiteSchema {
+    pub name: String,
+    pub body_index: usize,
+    pub angle: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FuelDepositSchema {
+    pub body_index: usize,
+    pub angle: f32,
+    pub amount: f32,
+}
+
+/// A precision landing target (radius measured along the surface)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LandingZoneSchema {
+    pub name: String,
+    pub body_index: usize,
+    pub angle: f32,
+    pub radius: f32,
+}
+
+/// A ring system around a body (radii from the body's center)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RingSchema {
+    pub body_index: usize,
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    pub color: ColorSchema,
+    pub density: f32, // 0.0-1.0
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub harvestable: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hazardous: bool,
+}
+
+/// Wrapping arena around the central body (or the spawn body when there is none)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ArenaSizeSchema {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl From<&MapConfiguration> for MapSchema {
+    fn from(map: &MapConfiguration) -> Self {
+        MapSchema {
+            schema_version: schema_version(),
+            name: map.name.clone(),
+            description: map.description.clone(),
+            celestial_bodies: map
+                .celestial_bodies
+                .iter()
+                .map(|body| BodySchema {
+                    name: body.name.clone(),
+                    mass: body.mass,
+                    radius: body.radius,
+                    color: ColorSchema { r: body.color.r, g: body.color.g, b: body.color.b, a: body.color.a },
+                    orbital_parent_index: body.orbital_parent_index,
+                    orbital_distance: body.orbital_distance,
+                    orbital_period: body.orbital_period,
+                    initial_angle: body.initial_angle,
+                    is_pinned: body.is_pinned,
+                    terrain: body.terrain.as_ref().map(|terrain| TerrainSchema {
+                        seed: terrain.seed,
+                        amplitude: terrain.amplitude,
+                        heights: terrain.heights.clone(),
+                    }),
+                })
+                .collect(),
+            player_spawn_body_index: map.player_spawn_body_index,
+            central_body_index: map.central_body_index,
+            tidal_breakup: map.tidal_breakup,
+            gravity_model: map.gravity_model.into(),
+            tunables: TunablesSchema {
+                rocket_base_mass: map.tunables.rocket_base_mass,
+                rocket_max_fuel: map.tunables.rocket_max_fuel,
+                bullet_speed: map.tunables.bullet_speed,
+                spawn_altitude: map.tunables.spawn_altitude,
+            },
+            sample_sites: map
+                .sample_sites
+                .iter()
+                .map(|site| SampleSiteSchema { name: site.name.clone(), body_index: site.body_index, angle: site.angle })
+                .collect(),
+            fuel_deposits: map
+                .fuel_deposits
+                .iter()
+                .map(|deposit| FuelDepositSchema { body_index: deposit.body_index, angle: deposit.angle, amount: deposit.amount })
+                .collect(),
+            landing_zones: map
+                .landing_zones
+                .iter()
+                .map(|zone| LandingZoneSchema { name: zone.name.clone(), body_index: zone.body_index, angle: zone.angle, radius: zone.radius })
+                .collect(),
+            rings: map
+                .rings
+                .iter()
+                .map(|ring| RingSchema {
+                    body_index: ring.body_index,
+                    inner_radius: ring.inner_radius,
+                    outer_radius: ring.outer_radius,
+                    color: ColorSchema { r: ring.color.r, g: ring.color.g, b: ring.color.b, a: ring.color.a },
+                    density: ring.density,
+                    harvestable: ring.harvestable,
+                    hazardous: ring.hazardous,
+                })
+                .collect(),
+            arena: map.arena.map(|arena| ArenaSizeSchema { width: arena.width, height: arena.height }),
+        }
+    }
+}
+
+impl MapSchema {
+    /// Check the document and convert it to a playable map
+    pub fn into_map(self) -> Result<MapConfiguration, String> {
+        if self.schema_version > super::SCHEMA_VERSION {
+            return Err(format!("Map '{}' uses schema version {} (newest supported is {})", self.name, self.schema_version, super::SCHEMA_VERSION));
+        }
+        if self.name.trim().is_empty() {
+            return Err("Map has no name".to_string());
+        }
+        let body_count = self.celestial_bodies.len();
+        let check_index = |index: usize, what: &str| {
+            if index < body_count {
+                Ok(())
+            } else {
+                Err(format!("Map '{}': {} refers to body {} but there are only {}", self.name, what, index, body_count))
+            }
+        };
+        check_index(self.player_spawn_body_index, "player_spawn_body_index")?;
+        if let Some(index) = self.central_body_index {
+            check_index(index, "central_body_index")?;
+        }
+        for (i, body) in self.celestial_bodies.iter().enumerate() {
+            if !(body.mass > 0.0 && body.mass.is_finite() && body.radius > 0.0 && body.radius.is_finite()) {
+                return Err(format!("Map '{}': body '{}' needs a positive mass and radius (got {} and {})", self.name, body.name, body.mass, body.radius));
+            }
+            if let Some(parent) = body.orbital_parent_index {
+                check_index(parent, &format!("body '{}' orbital_parent_index", body.name))?;
+                if parent == i {
+                    return Err(format!("Map '{}': body '{}' orbits itself", self.name, body.name));
+                }
+            }
+        }
+        for site in &self.sample_sites {
+            check_index(site.body_index, &format!("sample site '{}'", site.name))?;
+        }
+        for deposit in &self.fuel_deposits {
+            check_index(deposit.body_index, "fuel deposit")?;
+        }
+        for zone in &self.landing_zones {
+            check_index(zone.body_index, &format!("landing zone '{}'", zone.name))?;
+            if !(zone.radius > 0.0 && zone.radius.is_finite()) {
+                return Err(format!("Map '{}': landing zone '{}' needs a positive radius (got {})", self.name, zone.name, zone.radius));
+            }
+        }
+        for ring in &self.rings {
+            check_index(ring.body_index, "ring")?;
+            if !(ring.inner_radius > 0.0 && ring.outer_radius > ring.inner_radius && ring.outer_radius.is_finite()) {
+                return Err(format!("Map '{}': ring radii must satisfy 0 < inner < outer (got {} - {})", self.name, ring.inner_radius, ring.outer_radius));
+            }
+            if !(0.0..=1.0).contains(&ring.density) {
+                return Err(format!("Map '{}': ring density must be between 0 and 1 (got {})", self.name, ring.density));
+            }
+        }
+        if let Some(arena) = self.arena {
+            if !(arena.width >= MIN_ARENA_SIZE && arena.height >= MIN_ARENA_SIZE && arena.width.is_finite() && arena.height.is_finite()) {
+                return Err(format!("Map '{}': arena sides must be at least {} (got {} x {})", self.name, MIN_ARENA_SIZE, arena.width, arena.height));
+            }
+        }
+        if let GravityModelSchema::Custom { exponent } = self.gravity_model {
+            if !(exponent > 0.0 && exponent.is_finite()) {
+                return Err(format!("Map '{}': gravity exponent must be positive (got {})", self.name, exponent));
+            }
+        }
+        let tunables = [
+            ("rocket_base_mass", self.tunables.rocket_base_mass),
+            ("rocket_max_fuel", self.tunables.rocket_max_fuel),
+            ("bullet_speed", self.tunables.bullet_speed),
+            ("spawn_altitude", self.tunables.spawn_altitude),
+        ];
+        for (name, value) in tunables {
+            if let Some(value) = value.filter(|v| !(*v > 0.0 && v.is_finite())) {
+                return Err(format!("Map '{}': {} must be positive (got {})", self.name, name, value));
+            }
+        }
+
+        Ok(MapConfiguration {
+            name: self.name,
+            description: self.description,
+            celestial_bodies: self
+                .celestial_bodies
+                .into_iter()
+                .map(|body| CelestialBodyConfig {
+                    name: body.name,
+                    mass: body.mass,
+                    radius: body.radius,
+                    color: Color::new(body.color.r, body.color.g, body.color.b, body.color.a),
+                    orbital_parent_index: body.orbital_parent_index,
+                    orbital_distance: body.orbital_distance,
+                    orbital_period: body.orbital_period,
+                    initial_angle: body.initial_angle,
+                    is_pinned: body.is_pinned,
+                    terrain: body.terrain.map(|terrain| TerrainConfig {
+                        seed: terrain.seed,
+                        amplitude: terrain.amplitude,
+                        heights: terrain.heights,
+                    }),
+                })
+                .collect(),
+            player_spawn_body_index: self.player_spawn_body_index,
+            central_body_index: self.central_body_index,
+            tidal_breakup: self.tidal_breakup,
+            gravity_model: self.gravity_model.into(),
+            tunables: MapTunables {
+                rocket_base_mass: self.tunables.rocket_base_mass,
+                rocket_max_fuel: self.tunables.rocket_max_fuel,
+                bullet_speed: self.tunables.bullet_speed,
+                spawn_altitude: self.tunables.spawn_altitude,
+            },
+            sample_sites: self
+                .sample_sites
+                .into_iter()
+                .map(|site| SampleSiteConfig { name: site.name, body_index: site.body_index, angle: site.angle })
+                .collect(),
+            fuel_deposits: self
+                .fuel_deposits
+                .into_iter()
+                .map(|deposit| FuelDepositConfig { body_index: deposit.body_index, angle: deposit.angle, amount: deposit.amount })
+                .collect(),
+            landing_zones: self
+                .landing_zones
+                .into_iter()
+                .map(|zone| LandingZoneConfig { name: zone.name, body_index: zone.body_index, angle: zone.angle, radius: zone.radius })
+                .collect(),
+            rings: self
+                .rings
+                .into_iter()
+                .map(|ring| RingConfig {
+                    body_index: ring.body_index,
+                    inner_radius: ring.inner_radius,
+                    outer_radius: ring.outer_radius,
+                    color: Color::new(ring.color.r, ring.color.g, ring.color.b, ring.color.a),
+                    density: ring.density,
+                    harvestable: ring.harvestable,
+                    hazardous: ring.hazardous,
+                })
+                .collect(),
+            arena: self.arena.map(|arena| ArenaConfig { width: arena.width, height: arena.height }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemas::SchemaFormat;
+
+    #[test]
+    fn test_map_round_trips_through_json_and_ron() {
+        let map = MapConfiguration::earth_moon();
+        let schema = MapSchema::from(&map);
+        for format in [SchemaFormat::Json, SchemaFormat::Ron] {
+            let text = format.encode(&schema).unwrap();
+            let decoded: MapSchema = format.decode(&text).unwrap();
+            assert_eq!(decoded, schema);
+            let restored = decoded.into_map().unwrap();
+            assert_eq!(restored.celestial_bodies.len(), map.celestial_bodies.len());
+            assert_eq!(restored.celestial_bodies[1].terrain.as_ref().unwrap().seed, 1969);
+            assert_eq!(restored.landing_zones[0].name, "Apollo Pad");
+        }
+    }
+
+    #[test]
+    fn test_existing_ron_maps_are_schema_documents() {
+        for ron in [include_str!("../../maps/New Map.ron"), include_str!("../../maps/example_binary_system.ron")] {
+            let schema: MapSchema = SchemaFormat::Ron.decode(ron).unwrap();
+            assert_eq!(schema.schema_version, crate::schemas::SCHEMA_VERSION);
+            assert!(schema.into_map().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_minimal_json_map_and_bad_indices() {
+        let json = r#"{
+            "name": "Lonely",
+            "celestial_bodies": [{ "name": "Rock", "mass": 1000000.0, "radius": 500.0, "color": { "r": 0.5, "g": 0.5, "b": 0.5 } }],
+            "player_spawn_body_index": 0
+        }"#;
+        let schema: MapSchema = SchemaFormat::Json.decode(json).unwrap();
+        let map = schema.clone().into_map().unwrap();
+        assert_eq!(map.celestial_bodies[0].color.a, 1.0);
+        assert!(map.sample_sites.is_empty());
+
+        let mut broken = schema.clone();
+        broken.celestial_bodies[0].orbital_parent_index = Some(3);
+        assert!(broken.into_map().unwrap_err().contains("orbital_parent_index"));
+
+        let mut flat = schema;
+        flat.celestial_bodies[0].radius = 0.0;
+        assert!(flat.into_map().unwrap_err().contains("positive mass and radius"));
+    }
+
+    #[test]
+    fn test_tunables_default_and_override() {
+        let json = r#"{
+            "name": "Heavy",
+            "celestial_bodies": [{ "name": "Rock", "mass": 1000000.0, "radius": 500.0, "color": { "r": 0.5, "g": 0.5, "b": 0.5 } }],
+            "player_spawn_body_index": 0,
+            "tunables": { "rocket_max_fuel": 512.0, "spawn_altitude": 50.0 }
+        }"#;
+        let schema: MapSchema = SchemaFormat::Json.decode(json).unwrap();
+        let map = schema.clone().into_map().unwrap();
+        assert_eq!(map.tunables.rocket_max_fuel(), 512.0);
+        assert_eq!(map.tunables.spawn_altitude(), 50.0);
+        assert_eq!(map.tunables.bullet_speed(), crate::game_constants::GameConstants::BULLET_SPEED);
+        assert_eq!(MapSchema::from(&map).tunables, schema.tunables);
+
+        let mut bad = schema;
+        bad.tunables.bullet_speed = Some(-5.0);
+        assert!(bad.into_map().unwrap_err().contains("bullet_speed"));
+    }
+
+    #[test]
+    fn test_gravity_model_round_trips() {
+        let mut map = MapConfiguration::earth_moon();
+        map.gravity_model = GravityModel::Custom { exponent: 2.5 };
+        let schema = MapSchema::from(&map);
+        let text = SchemaFormat::Ron.encode(&schema).unwrap();
+        let restored = SchemaFormat::Ron.decode::<MapSchema>(&text).unwrap().into_map().unwrap();
+        assert_eq!(restored.gravity_model, GravityModel::Custom { exponent: 2.5 });
+
+        let mut bad = schema;
+        bad.gravity_model = GravityModelSchema::Custom { exponent: 0.0 };
+        assert!(bad.into_map().unwrap_err().contains("gravity exponent"));
+    }
+
+    #[test]
+    fn test_rings_round_trip_and_validate() {
+        let map = MapConfiguration::solar_1();
+        let schema = MapSchema::from(&map);
+        assert_eq!(schema.rings.len(), 1);
+        let text = SchemaFormat::Ron.encode(&schema).unwrap();
+        let restored = SchemaFormat::Ron.decode::<MapSchema>(&text).unwrap().into_map().unwrap();
+        let ring = &restored.rings[0];
+        assert_eq!(ring.body_index, 7);
+        assert!(ring.harvestable && ring.hazardous);
+
+        let mut inverted = schema.clone();
+        inverted.rings[0].outer_radius = inverted.rings[0].inner_radius * 0.5;
+        assert!(inverted.into_map().unwrap_err().contains("ring radii"));
+        let mut dense = schema;
+        dense.rings[0].density = 1.5;
+        assert!(dense.into_map().unwrap_err().contains("ring density"));
+    }
+}
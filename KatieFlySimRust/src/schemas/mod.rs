@@ -0,0 +1,95 @@
+// Schemas - Stable, documented interchange formats for maps and saves
+// External tools (web map builders, save editors) read and write these as JSON or RON; the game converts them to its own types
+
+pub mod map;
+pub mod save;
+
+pub use map::{ArenaSizeSchema, BodySchema, ColorSchema, FuelDepositSchema, LandingZoneSchema, MapSchema, SampleSiteSchema, TerrainSchema};
+pub use save::{ArenaSchema, BulletSchema, CameraSchema, PlanetSchema, RocketSchema, SatelliteSchema, SaveSchema, WaypointSchema};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Bumped only for breaking changes (new optional fields keep the version)
+pub const SCHEMA_VERSION: u32 = 1;
+
+pub(crate) fn schema_version() -> u32 {
+    SCHEMA_VERSION
+}
+
+/// Text format of a schema file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaFormat {
+    Json,
+    Ron,
+}
+
+impl SchemaFormat {
+    /// Format for a file by its extension (None for anything but .json and .ron)
+    pub fn from_path(path: &str) -> Option<Self> {
+        match std::path::Path::new(path).extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(SchemaFormat::Json),
+            "ron" => Some(SchemaFormat::Ron),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SchemaFormat::Json => "JSON",
+            SchemaFormat::Ron => "RON",
+        }
+    }
+
+    /// Pretty-printed, one field per line so files diff cleanly
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<String, String> {
+        match self {
+            SchemaFormat::Json => serde_json::to_string_pretty(value).map_err(|e| e.to_string()),
+            SchemaFormat::Ron => ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default()).map_err(|e| e.to_string()),
+        }
+        .map_err(|e| format!("Failed to serialize {}: {}", self.name(), e))
+    }
+
+    pub fn decode<T: DeserializeOwned>(&self, text: &str) -> Result<T, String> {
+        match self {
+            SchemaFormat::Json => serde_json::from_str(text).map_err(|e| e.to_string()),
+            SchemaFormat::Ron => ron::from_str(text).map_err(|e| e.to_string()),
+        }
+        .map_err(|e| format!("Failed to parse {}: {}", self.name(), e))
+    }
+}
+
+/// Read a schema file, picking the format from its extension (RON unless it ends in .json)
+pub fn read_file<T: DeserializeOwned>(path: &str) -> Result<T, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| format!("Failed to read file {}: {}", path, e))?;
+    SchemaFormat::from_path(path)
+        .unwrap_or(SchemaFormat::Ron)
+        .decode(&contents)
+        .map_err(|e| format!("{} ({})", e, path))
+}
+
+/// Write a schema file, picking the format from its extension (RON unless it ends in .json)
+pub fn write_file<T: Serialize>(value: &T, path: &str) -> Result<(), String> {
+    let text = SchemaFormat::from_path(path).unwrap_or(SchemaFormat::Ron).encode(value)?;
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+    }
+    std::fs::write(path, text).map_err(|e| format!("Failed to write file {}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(SchemaFormat::from_path("maps/binary.json"), Some(SchemaFormat::Json));
+        assert_eq!(SchemaFormat::from_path("maps/New Map.RON"), Some(SchemaFormat::Ron));
+        assert_eq!(SchemaFormat::from_path("saves/quicksave.sav"), None);
+
+        let err = SchemaFormat::Json.decode::<MapSchema>("{ not json").unwrap_err();
+        assert!(err.starts_with("Failed to parse JSON"));
+    }
+}
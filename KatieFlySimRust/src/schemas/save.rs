@@ -0,0 +1,410 @@
+// Save Schema - The interchange layout for saved games
+// Entities are sorted by ID and players by number so exports of the same game diff cleanly
+
+use std::collections::BTreeMap;
+
+use macroquad::prelude::{DVec2, Vec2};
+use serde::{Deserialize, Serialize};
+
+use super::schema_version;
+use crate::save_system::{
+    GameSaveData, SavedBullet, SavedCamera, SavedOrigin, SavedPlanet, SavedRocket, SavedSatellite, SavedVector2, SavedWaypoint,
+};
+use crate::systems::{ArenaBounds, EntityId};
+
+/// A saved game. Positions are `[x, y]` relative to `origin`, the world position of the floating origin
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveSchema {
+    #[serde(default = "schema_version")]
+    pub schema_version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map_name: Option<String>,
+    #[serde(default)]
+    pub game_time: f32,
+    #[serde(default)]
+    pub origin: [f64; 2],
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_rocket_id: Option<EntityId>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub players: BTreeMap<u32, String>, // Player number -> name (multiplayer)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub camera: Option<CameraSchema>,
+    #[serde(default)]
+    pub planets: Vec<PlanetSchema>,
+    #[serde(default)]
+    pub rockets: Vec<RocketSchema>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub satellites: Vec<SatelliteSchema>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bullets: Vec<BulletSchema>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub waypoints: Vec<WaypointSchema>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arena: Option<ArenaSchema>,
+    #[serde(default)]
+    pub cosmetic_seed: u64, // Sky and planet surface look (0 = classic)
+}
+
+/// Wrapping arena, centered in world coordinates (like `origin`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArenaSchema {
+    pub center: [f64; 2],
+    pub size: [f32; 2],
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraSchema {
+    pub center: [f32; 2],
+    pub zoom: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanetSchema {
+    pub id: EntityId,
+    pub position: [f32; 2],
+    #[serde(default)]
+    pub velocity: [f32; 2],
+    pub mass: f32,
+    pub radius: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_mass: Option<f32>, // Mass the map started with (defaults to mass)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_radius: Option<f32>,
+    pub color: [u8; 3], // RGB
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RocketSchema {
+    pub id: EntityId,
+    pub position: [f32; 2],
+    #[serde(default)]
+    pub velocity: [f32; 2],
+    #[serde(default)]
+    pub rotation: f32, // Radians
+    pub fuel: f32,
+    pub color: [u8; 3],
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player_id: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SatelliteSchema {
+    pub id: EntityId,
+    pub position: [f32; 2],
+    #[serde(default)]
+    pub velocity: [f32; 2],
+    #[serde(default)]
+    pub rotation: f32,
+    pub fuel: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<u32>,
+    pub transfer_range: f32,
+    pub target_orbit_radius: f32,
+    pub is_maintaining_orbit: bool,
+    pub last_maintenance_time: f32,
+    pub maintenance_interval: f32,
+    pub maintenance_fuel_reserve: f32,
+    pub is_collecting_fuel: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fuel_source_planet_id: Option<usize>,
+    pub collection_rate: f32,
+    #[serde(default)]
+    pub wear: f32, // Service life used up (1.0 = end of life)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BulletSchema {
+    pub id: EntityId,
+    pub position: [f32; 2],
+    pub velocity: [f32; 2],
+    pub mass: f32,
+    pub lifetime: f32,     // Age in seconds
+    pub max_lifetime: f32, // Despawns at this age
+    pub color: [u8; 3],
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WaypointSchema {
+    pub id: u32,
+    pub name: String,
+    pub position: [f32; 2],
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<u32>,
+    #[serde(default)]
+    pub shared: bool,
+}
+
+fn pair(v: &SavedVector2) -> [f32; 2] {
+    [v.x, v.y]
+}
+
+fn vector([x, y]: [f32; 2]) -> SavedVector2 {
+    SavedVector2 { x, y }
+}
+
+fn rgb((r, g, b): (u8, u8, u8)) -> [u8; 3] {
+    [r, g, b]
+}
+
+fn sorted_by_id<T>(mut items: Vec<T>, id: impl Fn(&T) -> usize) -> Vec<T> {
+    items.sort_by_key(|item| id(item));
+    items
+}
+
+impl From<&GameSaveData> for SaveSchema {
+    fn from(save: &GameSaveData) -> Self {
+        SaveSchema {
+            schema_version: schema_version(),
+            map_name: save.map_name.clone(),
+            game_time: save.game_time,
+            origin: [save.origin.x, save.origin.y],
+            active_rocket_id: save.active_rocket_id,
+            players: save.player_names.iter().map(|(id, name)| (*id, name.clone())).collect(),
+            camera: Some(CameraSchema { center: pair(&save.camera.center), zoom: save.camera.zoom }),
+            planets: sorted_by_id(
+                save.planets
+                    .iter()
+                    .map(|p| PlanetSchema {
+                        id: p.id,
+                        position: pair(&p.position),
+                        velocity: pair(&p.velocity),
+                        mass: p.mass,
+                        radius: p.radius,
+                        initial_mass: p.initial_mass,
+                        initial_radius: p.initial_radius,
+                        color: rgb(p.color),
+                    })
+                    .collect(),
+                |p| p.id,
+            ),
+            rockets: sorted_by_id(
+                save.rockets
+                    .iter()
+                    .map(|r| RocketSchema {
+                        id: r.id,
+                        position: pair(&r.position),
+                        velocity: pair(&r.velocity),
+                        rotation: r.rotation,
+                        fuel: r.fuel,
+                        color: rgb(r.color),
+                        player_id: r.player_id,
+                    })
+                    .collect(),
+                |r| r.id,
+            ),
+            satellites: sorted_by_id(
+                save.satellites
+                    .iter()
+                    .map(|s| SatelliteSchema {
+                        id: s.id,
+                        position: pair(&s.position),
+                        velocity: pair(&s.velocity),
+                        rotation: s.rotation,
+                        fuel: s.fuel,
+                        owner: s.owner,
+                        transfer_range: s.transfer_range,
+                        target_orbit_radius: s.target_orbit_radius,
+                        is_maintaining_orbit: s.is_maintaining_orbit,
+                        last_maintenance_time: s.last_maintenance_time,
+                        maintenance_interval: s.maintenance_interval,
+                        maintenance_fuel_reserve: s.maintenance_fuel_reserve,
+                        is_collecting_fuel: s.is_collecting_fuel,
+                        fuel_source_planet_id: s.fuel_source_planet_id,
+                        collection_rate: s.collection_rate,
+                        wear: s.wear,
+                    })
+                    .collect(),
+                |s| s.id,
+            ),
+            bullets: sorted_by_id(
+                save.bullets
+                    .iter()
+                    .map(|b| BulletSchema {
+                        id: b.id,
+                        position: pair(&b.position),
+                        velocity: pair(&b.velocity),
+                        mass: b.mass,
+                        lifetime: b.lifetime,
+                        max_lifetime: b.max_lifetime,
+                        color: rgb(b.color),
+                    })
+                    .collect(),
+                |b| b.id,
+            ),
+            waypoints: sorted_by_id(
+                save.waypoints
+                    .iter()
+                    .map(|w| WaypointSchema {
+                        id: w.id,
+                        name: w.name.clone(),
+                        position: pair(&w.position),
+                        owner: w.owner,
+                        shared: w.shared,
+                    })
+                    .collect(),
+                |w| w.id as usize,
+            ),
+            arena: save.arena.map(|arena| ArenaSchema { center: [arena.center_x, arena.center_y], size: [arena.width, arena.height] }),
+            cosmetic_seed: save.cosmetic_seed,
+        }
+    }
+}
+
+impl SaveSchema {
+    /// Check the document and convert it to save data the game can load
+    pub fn into_save_data(self) -> Result<GameSaveData, String> {
+        if self.schema_version > super::SCHEMA_VERSION {
+            return Err(format!("Save uses schema version {} (newest supported is {})", self.schema_version, super::SCHEMA_VERSION));
+        }
+        let mut ids: Vec<EntityId> = self
+            .planets
+            .iter()
+            .map(|p| p.id)
+            .chain(self.rockets.iter().map(|r| r.id))
+            .chain(self.satellites.iter().map(|s| s.id))
+            .chain(self.bullets.iter().map(|b| b.id))
+            .collect();
+        ids.sort_unstable();
+        if let Some(pair) = ids.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(format!("Entity ID {} is used more than once", pair[0]));
+        }
+        if let Some(id) = self.active_rocket_id {
+            if !self.rockets.iter().any(|r| r.id == id) {
+                return Err(format!("active_rocket_id {} is not one of the rockets", id));
+            }
+        }
+
+        let mut save = GameSaveData::new();
+        save.map_name = self.map_name;
+        save.game_time = self.game_time;
+        save.origin = SavedOrigin { x: self.origin[0], y: self.origin[1] };
+        save.active_rocket_id = self.active_rocket_id;
+        save.player_names = self.players.into_iter().collect();
+        if let Some(camera) = self.camera {
+            save.camera = SavedCamera { center: vector(camera.center), zoom: camera.zoom };
+        }
+        save.planets = self
+            .planets
+            .into_iter()
+            .map(|p| SavedPlanet {
+                id: p.id,
+                position: vector(p.position),
+                velocity: vector(p.velocity),
+                mass: p.mass,
+                radius: p.radius,
+                initial_mass: p.initial_mass,
+                initial_radius: p.initial_radius,
+                color: (p.color[0], p.color[1], p.color[2]),
+            })
+            .collect();
+        save.rockets = self
+            .rockets
+            .into_iter()
+            .map(|r| SavedRocket {
+                id: r.id,
+                position: vector(r.position),
+                velocity: vector(r.velocity),
+                rotation: r.rotation,
+                fuel: r.fuel,
+                color: (r.color[0], r.color[1], r.color[2]),
+                player_id: r.player_id,
+            })
+            .collect();
+        save.satellites = self
+            .satellites
+            .into_iter()
+            .map(|s| SavedSatellite {
+                id: s.id,
+                position: vector(s.position),
+                velocity: vector(s.velocity),
+                rotation: s.rotation,
+                fuel: s.fuel,
+                target_orbit_radius: s.target_orbit_radius,
+                is_maintaining_orbit: s.is_maintaining_orbit,
+                last_maintenance_time: s.last_maintenance_time,
+                maintenance_interval: s.maintenance_interval,
+                maintenance_fuel_reserve: s.maintenance_fuel_reserve,
+                is_collecting_fuel: s.is_collecting_fuel,
+                fuel_source_planet_id: s.fuel_source_planet_id,
+                collection_rate: s.collection_rate,
+                transfer_range: s.transfer_range,
+                owner: s.owner,
+                wear: s.wear,
+            })
+            .collect();
+        save.bullets = self
+            .bullets
+            .into_iter()
+            .map(|b| SavedBullet {
+                id: b.id,
+                position: vector(b.position),
+                velocity: vector(b.velocity),
+                mass: b.mass,
+                lifetime: b.lifetime,
+                color: (b.color[0], b.color[1], b.color[2]),
+                max_lifetime: b.max_lifetime,
+            })
+            .collect();
+        save.waypoints = self
+            .waypoints
+            .into_iter()
+            .map(|w| SavedWaypoint { id: w.id, name: w.name, position: vector(w.position), owner: w.owner, shared: w.shared })
+            .collect();
+        save.arena = self.arena.map(|arena| {
+            ArenaBounds::new(DVec2::new(arena.center[0], arena.center[1]), Vec2::new(arena.size[0], arena.size[1]))
+        });
+        save.cosmetic_seed = self.cosmetic_seed;
+        Ok(save)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Planet, Rocket};
+    use crate::schemas::SchemaFormat;
+    use macroquad::prelude::*;
+
+    fn sample_save() -> GameSaveData {
+        let mut save = GameSaveData::new();
+        save.map_name = Some("earth moon".to_string());
+        save.game_time = 42.5;
+        save.origin = SavedOrigin { x: 1_000_000.0, y: -2_000.0 };
+        save.planets.push(SavedPlanet::from_planet(3, &Planet::new(Vec2::ZERO, 500.0, 1.0e6, BLUE)));
+        save.rockets.push(SavedRocket::from_rocket(7, &Rocket::new(Vec2::new(600.0, 0.0), Vec2::new(0.0, 40.0), WHITE, 1.0)));
+        save.rockets.push(SavedRocket::from_rocket(5, &Rocket::new(Vec2::new(-600.0, 0.0), Vec2::ZERO, RED, 1.0)));
+        save.active_rocket_id = Some(7);
+        save.player_names.insert(1, "Katie".to_string());
+        save
+    }
+
+    #[test]
+    fn test_save_round_trips_through_json_and_ron() {
+        let schema = SaveSchema::from(&sample_save());
+        // Sorted by ID regardless of save order
+        assert_eq!(schema.rockets.iter().map(|r| r.id).collect::<Vec<_>>(), vec![5, 7]);
+
+        for format in [SchemaFormat::Json, SchemaFormat::Ron] {
+            let text = format.encode(&schema).unwrap();
+            assert_eq!(format.decode::<SaveSchema>(&text).unwrap(), schema);
+        }
+
+        let restored = schema.into_save_data().unwrap();
+        assert_eq!(restored.origin, SavedOrigin { x: 1_000_000.0, y: -2_000.0 });
+        assert_eq!(restored.active_rocket_id, Some(7));
+        assert_eq!(restored.player_names.get(&1).map(String::as_str), Some("Katie"));
+        let (_, rocket) = restored.rockets.iter().find(|r| r.id == 7).unwrap().to_rocket();
+        assert_eq!(rocket.velocity(), Vec2::new(0.0, 40.0));
+    }
+
+    #[test]
+    fn test_hand_written_save_is_checked() {
+        let mut schema = SaveSchema::from(&sample_save());
+        schema.planets[0].id = 5;
+        assert!(schema.clone().into_save_data().unwrap_err().contains("Entity ID 5"));
+
+        schema.planets[0].id = 3;
+        schema.active_rocket_id = Some(3);
+        assert!(schema.into_save_data().unwrap_err().contains("active_rocket_id"));
+    }
+}
@@ -0,0 +1,152 @@
+// Adaptive Timestep - Fixed-step physics clock that coarsens its rate under load instead of slowing the game
+// When a frame needs more steps than the budget the rate halves (down to the minimum), so the same game time
+// is covered in fewer, longer steps; it climbs back once frames have fitted comfortably for a while
+
+use crate::game_constants::GameConstants;
+
+/// Most physics steps run in one frame before the rate is lowered
+pub const MAX_STEPS_PER_FRAME: u32 = 6;
+/// Frame time beyond which the rest is dropped (window drags, loading hitches)
+const MAX_FRAME_TIME: f32 = 0.25;
+/// Seconds of comfortable frames before stepping the rate back up
+const RECOVERY_TIME: f32 = 2.0;
+/// Steps per frame that count as comfortable (doubled, they stay within two thirds of the budget)
+const CALM_STEPS: u32 = MAX_STEPS_PER_FRAME / 3;
+/// How long the "running slow" warning stays up after game time was dropped
+const SLOW_WARNING_TIME: f32 = 1.0;
+
+/// Physics clock for the main loop: feed it frame time, run the steps it returns at its current timestep
+#[derive(Debug, Clone)]
+pub struct AdaptiveTimestep {
+    base_hz: u32, // Configured rate, returned to when load allows
+    hz: u32,      // Current rate
+    accumulator: f32,
+    calm_time: f32,    // How long frames would have fitted at twice the current rate
+    slow_warning: f32, // Seconds left on the "running slow" warning
+}
+
+impl AdaptiveTimestep {
+    pub fn new(base_hz: u32) -> Self {
+        let base_hz = base_hz.clamp(GameConstants::MIN_PHYSICS_HZ, GameConstants::MAX_PHYSICS_HZ);
+        AdaptiveTimestep { base_hz, hz: base_hz, accumulator: 0.0, calm_time: 0.0, slow_warning: 0.0 }
+    }
+
+    /// Follow a changed configured rate (the host's tick rate), starting over at full rate
+    pub fn set_base_hz(&mut self, base_hz: u32) {
+        let base_hz = base_hz.clamp(GameConstants::MIN_PHYSICS_HZ, GameConstants::MAX_PHYSICS_HZ);
+        if base_hz != self.base_hz {
+            self.base_hz = base_hz;
+            self.hz = base_hz;
+            self.calm_time = 0.0;
+        }
+    }
+
+    pub fn base_hz(&self) -> u32 {
+        self.base_hz
+    }
+
+    pub fn hz(&self) -> u32 {
+        self.hz
+    }
+
+    /// Seconds per step at the current rate
+    pub fn timestep(&self) -> f32 {
+        1.0 / self.hz as f32
+    }
+
+    /// Running below the configured rate to keep up
+    pub fn is_degraded(&self) -> bool {
+        self.hz < self.base_hz
+    }
+
+    /// Game time was dropped recently even at the lowest rate (the game ran in slow motion)
+    pub fn is_running_slow(&self) -> bool {
+        self.slow_warning > 0.0
+    }
+
+    /// Forget banked time (entering a game from a menu)
+    pub fn reset(&mut self) {
+        self.accumulator = 0.0;
+    }
+
+    /// Bank a frame's time and return how many steps of `timestep()` to run now
+    pub fn advance(&mut self, frame_time: f32) -> u32 {
+        let frame_time = frame_time.max(0.0);
+        self.slow_warning = (self.slow_warning - frame_time).max(0.0);
+        if frame_time > MAX_FRAME_TIME {
+            self.slow_warning = SLOW_WARNING_TIME;
+        }
+        self.accumulator += frame_time.min(MAX_FRAME_TIME);
+
+        // Step back up once twice the rate would have fitted comfortably for a while
+        // (before counting steps, so the steps returned match `timestep()`)
+        if self.is_degraded() && self.calm_time >= RECOVERY_TIME {
+            self.hz = (self.hz * 2).min(self.base_hz);
+            self.calm_time = 0.0;
+            log::info!("Physics load eased; stepping at {} Hz", self.hz);
+        }
+
+        // Too many steps owed: cover the same time in fewer, longer steps
+        let steps_owed = |hz: u32, accumulator: f32| (accumulator * hz as f32) as u32;
+        while steps_owed(self.hz, self.accumulator) > MAX_STEPS_PER_FRAME && self.hz > GameConstants::MIN_PHYSICS_HZ {
+            self.hz = (self.hz / 2).max(GameConstants::MIN_PHYSICS_HZ);
+            self.calm_time = 0.0;
+            log::warn!("Physics can't keep up; stepping at {} Hz (configured {} Hz)", self.hz, self.base_hz);
+        }
+
+        let mut steps = steps_owed(self.hz, self.accumulator);
+        if steps > MAX_STEPS_PER_FRAME {
+            // Already at the lowest rate: drop the backlog rather than spiral
+            steps = MAX_STEPS_PER_FRAME;
+            self.accumulator = self.timestep() * steps as f32;
+            self.slow_warning = SLOW_WARNING_TIME;
+        }
+        self.accumulator -= self.timestep() * steps as f32;
+
+        if self.is_degraded() && steps <= CALM_STEPS {
+            self.calm_time += frame_time;
+        } else {
+            self.calm_time = 0.0;
+        }
+        steps
+    }
+}
+
+impl Default for AdaptiveTimestep {
+    fn default() -> Self {
+        Self::new(GameConstants::DEFAULT_PHYSICS_HZ)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Game time covered by the steps for a run of frames
+    fn simulate(clock: &mut AdaptiveTimestep, frame_time: f32, frames: usize) -> f32 {
+        (0..frames).map(|_| clock.advance(frame_time) as f32 * clock.timestep()).sum()
+    }
+
+    #[test]
+    fn test_slow_frames_lower_the_rate_without_losing_time() {
+        let mut clock = AdaptiveTimestep::new(120);
+        let covered = simulate(&mut clock, 1.0 / 60.0, 120);
+        assert_eq!(clock.hz(), 120);
+        assert!((covered - 2.0).abs() < 0.02);
+
+        // 150 ms frames need 18 steps each at 120 Hz; 30 Hz covers them in 4-5 and keeps game time at wall time
+        let covered = simulate(&mut clock, 0.15, 50);
+        assert_eq!(clock.hz(), 30);
+        assert!((covered - 7.5).abs() < 0.05);
+        assert!(clock.is_degraded());
+        assert!(!clock.is_running_slow());
+
+        // Once load eases the rate climbs back a step at a time
+        simulate(&mut clock, 1.0 / 60.0, 300);
+        assert_eq!(clock.hz(), 120);
+
+        // A frame too slow even for 30 Hz drops the rest and says so
+        clock.advance(0.5);
+        assert!(clock.is_running_slow());
+    }
+}
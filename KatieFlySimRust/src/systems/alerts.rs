@@ -0,0 +1,331 @@
+// Alerts - Watches the player's rocket and satellites for trouble
+// Low fuel, decaying orbits and incoming bullets, each with an estimated time-to-event
+
+use std::collections::HashMap;
+
+use macroquad::prelude::Vec2;
+
+use crate::entities::GameObject;
+use crate::game_constants::GameConstants;
+use crate::physics::GravityModel;
+use crate::systems::entity_picker::EntityTarget;
+use crate::systems::world_bounds::{self, BoundsConfig, BoundsStatus};
+use crate::systems::{EntityId, World};
+
+/// How often alerts are re-evaluated (seconds) - orbit prediction isn't free
+const EVALUATION_INTERVAL: f32 = 0.25;
+
+/// Kind of alert, ordered from least to most urgent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AlertKind {
+    LowFuel,
+    Escaping,
+    OrbitDecay,
+    CollisionRisk,
+}
+
+impl AlertKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AlertKind::LowFuel => "LOW FUEL",
+            AlertKind::Escaping => "ESCAPE TRAJECTORY",
+            AlertKind::OrbitDecay => "ORBIT DECAY",
+            AlertKind::CollisionRisk => "COLLISION RISK",
+        }
+    }
+}
+
+/// One active warning
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    pub kind: AlertKind,
+    pub subject: EntityTarget,
+    pub time_to_event: Option<f32>, // Seconds until empty/impact/hit, if known
+}
+
+/// Alert thresholds and prediction settings
+#[derive(Debug, Clone)]
+pub struct AlertsConfig {
+    pub low_fuel_fraction: f32,  // Warn below this fraction of max fuel
+    pub decay_horizon: f32,      // How far ahead to look for surface impact (seconds)
+    pub decay_step: f32,         // Integration step for orbit prediction (seconds)
+    pub collision_horizon: f32,  // How far ahead to look for bullet hits (seconds)
+    pub collision_radius: f32,   // Closest approach that counts as a hit (world units)
+    pub bounds: BoundsConfig,    // Edge of the system for escape warnings
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        AlertsConfig {
+            low_fuel_fraction: 0.15,
+            decay_horizon: 300.0,
+            decay_step: 0.5,
+            collision_horizon: 10.0,
+            collision_radius: 40.0,
+            bounds: BoundsConfig::default(),
+        }
+    }
+}
+
+/// Evaluates alerts for a set of watched rockets and satellites
+pub struct AlertEngine {
+    config: AlertsConfig,
+    alerts: Vec<Alert>,
+    time_since_evaluation: f32,
+    last_fuel: HashMap<EntityTarget, f32>, // For burn-rate (time-to-empty) estimates
+}
+
+impl AlertEngine {
+    pub fn new() -> Self {
+        Self::with_config(AlertsConfig::default())
+    }
+
+    pub fn with_config(config: AlertsConfig) -> Self {
+        AlertEngine {
+            config,
+            alerts: Vec::new(),
+            time_since_evaluation: EVALUATION_INTERVAL, // Evaluate on the first update
+            last_fuel: HashMap::new(),
+        }
+    }
+
+    /// Active alerts, most urgent first
+    pub fn alerts(&self) -> &[Alert] {
+        &self.alerts
+    }
+
+    pub fn clear(&mut self) {
+        self.alerts.clear();
+        self.last_fuel.clear();
+    }
+
+    /// Re-evaluate alerts for the watched rockets and all satellites (throttled)
+    pub fn update(&mut self, delta_time: f32, world: &World, rocket_ids: &[EntityId]) {
+        self.time_since_evaluation += delta_time;
+        if self.time_since_evaluation < EVALUATION_INTERVAL {
+            return;
+        }
+        let elapsed = self.time_since_evaluation;
+        self.time_since_evaluation = 0.0;
+
+        let mut subjects: Vec<EntityTarget> = rocket_ids.iter().map(|id| EntityTarget::Rocket(*id)).collect();
+        subjects.extend(world.satellites_with_ids().map(|(id, _)| EntityTarget::Satellite(id)));
+
+        let mut alerts = Vec::new();
+        let mut fuel_levels = HashMap::new();
+        for subject in subjects {
+            let Some(state) = SubjectState::read(world, subject) else {
+                continue;
+            };
+
+            // Low fuel, with time-to-empty from the burn rate since the last evaluation
+            // Empty tanks aren't flagged - everything spawns empty and waits for a refuel
+            let previous_fuel = self.last_fuel.get(&subject).copied();
+            fuel_levels.insert(subject, state.fuel);
+            if state.fuel > 0.0 && state.fuel < state.max_fuel * self.config.low_fuel_fraction {
+                let burn_rate = previous_fuel.map(|previous| (previous - state.fuel) / elapsed).unwrap_or(0.0);
+                let time_to_empty = (burn_rate > 0.01).then(|| state.fuel / burn_rate);
+                alerts.push(Alert { kind: AlertKind::LowFuel, subject, time_to_event: time_to_empty });
+            }
+
+            // Decaying orbit (landed rockets are safe on the ground)
+            if !state.landed {
+                if let Some(time_to_impact) = self.predict_surface_impact(world, state.position, state.velocity) {
+                    alerts.push(Alert { kind: AlertKind::OrbitDecay, subject, time_to_event: Some(time_to_impact) });
+                }
+            }
+
+            // Leaving the system for good, with time until it's out of reach
+            if let BoundsStatus::Escaping { time_to_lost } = world_bounds::status(world, state.position, state.velocity, &self.config.bounds) {
+                alerts.push(Alert { kind: AlertKind::Escaping, subject, time_to_event: time_to_lost });
+            }
+
+            // Incoming bullets
+            if let Some(time_to_hit) = self.predict_bullet_hit(world, state.position, state.velocity) {
+                alerts.push(Alert { kind: AlertKind::CollisionRisk, subject, time_to_event: Some(time_to_hit) });
+            }
+        }
+
+        // Most urgent kind first, then soonest
+        alerts.sort_by(|a, b| {
+            b.kind.cmp(&a.kind).then_with(|| {
+                let ta = a.time_to_event.unwrap_or(f32::INFINITY);
+                let tb = b.time_to_event.unwrap_or(f32::INFINITY);
+                ta.partial_cmp(&tb).unwrap_or(std::cmp::Ordering::Equal)
+            })
+        });
+
+        self.alerts = alerts;
+        self.last_fuel = fuel_levels;
+    }
+
+    /// Time until the body hits the surface of its dominant planet, if within the horizon
+    /// Planets are extrapolated at constant velocity (good enough over a few minutes)
+    fn predict_surface_impact(&self, world: &World, position: Vec2, velocity: Vec2) -> Option<f32> {
+        let dominant = world.planets().max_by(|a, b| {
+            let pull_a = a.mass() / (a.position() - position).length_squared().max(1.0);
+            let pull_b = b.mass() / (b.position() - position).length_squared().max(1.0);
+            pull_a.partial_cmp(&pull_b).unwrap_or(std::cmp::Ordering::Equal)
+        })?;
+
+        let mu = GameConstants::G * dominant.mass();
+        let planet_velocity = dominant.velocity();
+        let radius = dominant.radius();
+        let gravity_model = world.gravity_simulator().model();
+
+        // Work in the planet's frame
+        let mut r = position - dominant.position();
+        let mut v = velocity - planet_velocity;
+
+        // Quick reject: escaping or periapsis above the surface (Kepler orbits need inverse-square gravity)
+        if gravity_model == GravityModel::Realistic {
+            let r_len = r.length();
+            let energy = v.length_squared() / 2.0 - mu / r_len;
+            if energy >= 0.0 && r.dot(v) >= 0.0 {
+                return None;
+            }
+            if energy < 0.0 {
+                let h = r.perp_dot(v);
+                let eccentricity = (1.0 + 2.0 * energy * h * h / (mu * mu)).max(0.0).sqrt();
+                let periapsis = -mu / (2.0 * energy) * (1.0 - eccentricity);
+                if periapsis > radius {
+                    return None;
+                }
+            }
+        }
+
+        // Step forward (semi-implicit Euler) until the surface is reached
+        let dt = self.config.decay_step;
+        let mut t = 0.0;
+        while t < self.config.decay_horizon {
+            let distance = r.length();
+            if distance <= radius {
+                return Some(t);
+            }
+            v += -r / distance * gravity_model.acceleration(mu, distance, radius) * dt;
+            r += v * dt;
+            t += dt;
+        }
+        None
+    }
+
+    /// Time until a bullet passes within the collision radius (straight-line relative motion)
+    /// Bullet and target feel nearly the same gravity at close range, so relative motion is ~linear
+    fn predict_bullet_hit(&self, world: &World, position: Vec2, velocity: Vec2) -> Option<f32> {
+        world
+            .bullets()
+            .filter_map(|bullet| {
+                let relative_pos = bullet.position() - position;
+                let relative_vel = bullet.velocity() - velocity;
+                let speed_sq = relative_vel.length_squared();
+                if speed_sq < 1e-6 {
+                    return None;
+                }
+                let t_closest = (-relative_pos.dot(relative_vel) / speed_sq).clamp(0.0, self.config.collision_horizon);
+                let miss_distance = (relative_pos + relative_vel * t_closest).length();
+                (miss_distance <= self.config.collision_radius).then_some(t_closest)
+            })
+            .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+    }
+}
+
+impl Default for AlertEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Snapshot of the fields alerts need from a rocket or satellite
+struct SubjectState {
+    position: Vec2,
+    velocity: Vec2,
+    fuel: f32,
+    max_fuel: f32,
+    landed: bool,
+}
+
+impl SubjectState {
+    fn read(world: &World, subject: EntityTarget) -> Option<Self> {
+        match subject {
+            EntityTarget::Rocket(id) => world.get_rocket(id).map(|r| SubjectState {
+                position: r.position(),
+                velocity: r.velocity(),
+                fuel: r.current_fuel(),
+                max_fuel: r.max_fuel(),
+                landed: r.is_landed(),
+            }),
+            EntityTarget::Satellite(id) => world.get_satellite(id).map(|s| SubjectState {
+                position: s.position(),
+                velocity: s.velocity(),
+                fuel: s.current_fuel(),
+                max_fuel: s.max_fuel(),
+                landed: false,
+            }),
+            EntityTarget::Planet(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Bullet, Planet, Rocket};
+    use macroquad::prelude::{BLUE, WHITE};
+
+    fn world_with_planet() -> World {
+        let mut world = World::new();
+        world.add_planet(Planet::new(Vec2::new(0.0, 0.0), 100.0, 10000.0, BLUE));
+        world
+    }
+
+    #[test]
+    fn test_low_fuel_alert() {
+        let mut world = world_with_planet();
+        // Circular orbit so only fuel is flagged
+        let speed = (GameConstants::G * 10000.0 / 500.0).sqrt();
+        let rocket_id = world.add_rocket(Rocket::new(Vec2::new(500.0, 0.0), Vec2::new(0.0, speed), WHITE, 1.0));
+
+        let mut engine = AlertEngine::new();
+        engine.update(0.016, &world, &[rocket_id]);
+        assert!(engine.alerts().is_empty(), "empty tanks are not flagged");
+
+        world.get_rocket_mut(rocket_id).unwrap().set_fuel(10.0);
+        engine.update(0.25, &world, &[rocket_id]);
+        world.get_rocket_mut(rocket_id).unwrap().set_fuel(9.0);
+        engine.update(0.25, &world, &[rocket_id]);
+
+        assert_eq!(engine.alerts().len(), 1);
+        let alert = &engine.alerts()[0];
+        assert_eq!(alert.kind, AlertKind::LowFuel);
+        assert_eq!(alert.subject, EntityTarget::Rocket(rocket_id));
+        // Burning 4 fuel/s with 9 left
+        assert!((alert.time_to_event.unwrap() - 2.25).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_falling_rocket_predicts_impact() {
+        let mut world = world_with_planet();
+        let rocket_id = world.add_rocket(Rocket::new(Vec2::new(300.0, 0.0), Vec2::ZERO, WHITE, 1.0));
+
+        let mut engine = AlertEngine::new();
+        engine.update(0.016, &world, &[rocket_id]);
+
+        let decay = engine.alerts().iter().find(|a| a.kind == AlertKind::OrbitDecay).expect("decay alert");
+        let time_to_impact = decay.time_to_event.unwrap();
+        assert!(time_to_impact > 0.0 && time_to_impact < 300.0);
+    }
+
+    #[test]
+    fn test_incoming_bullet_alert_is_most_urgent() {
+        let mut world = world_with_planet();
+        let rocket_id = world.add_rocket(Rocket::new(Vec2::new(1000.0, 0.0), Vec2::ZERO, WHITE, 1.0));
+        world.add_bullet(Bullet::new(Vec2::new(1000.0, -200.0), Vec2::new(0.0, 100.0)));
+
+        let mut engine = AlertEngine::new();
+        engine.update(0.016, &world, &[rocket_id]);
+
+        let first = &engine.alerts()[0];
+        assert_eq!(first.kind, AlertKind::CollisionRisk);
+        assert!((first.time_to_event.unwrap() - 2.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,94 @@
+// Arena - Optional toroidal playfield: craft and bullets leaving one edge re-enter at the opposite one
+// Set from a map's arena config or the host's --arena flag; saves and snapshots carry it so clients wrap the same way
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::systems::FloatingOrigin;
+
+/// Smallest arena side allowed (keeps a planet and some room to fly)
+pub const MIN_ARENA_SIZE: f32 = 2_000.0;
+
+/// A rectangle in world coordinates that wraps at its edges
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ArenaBounds {
+    pub center_x: f64, // World coordinates (not relative to the floating origin)
+    pub center_y: f64,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl ArenaBounds {
+    pub fn new(center: DVec2, size: Vec2) -> Self {
+        ArenaBounds {
+            center_x: center.x,
+            center_y: center.y,
+            width: size.x.max(MIN_ARENA_SIZE),
+            height: size.y.max(MIN_ARENA_SIZE),
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        Vec2::new(self.width, self.height)
+    }
+
+    /// The arena in local coordinates under `origin`
+    pub fn local_rect(&self, origin: &FloatingOrigin) -> Rect {
+        let center = origin.to_local(DVec2::new(self.center_x, self.center_y));
+        Rect::new(center.x - self.width / 2.0, center.y - self.height / 2.0, self.width, self.height)
+    }
+
+    /// `position` brought back inside the arena, or None if it is already inside
+    pub fn wrap(&self, origin: &FloatingOrigin, position: Vec2) -> Option<Vec2> {
+        let rect = self.local_rect(origin);
+        if rect.contains(position) {
+            return None;
+        }
+        Some(Vec2::new(
+            rect.x + (position.x - rect.x).rem_euclid(rect.w),
+            rect.y + (position.y - rect.y).rem_euclid(rect.h),
+        ))
+    }
+
+    /// Offsets of the neighbouring copies of the arena that overlap `visible` (for drawing across an edge)
+    pub fn visible_copies(&self, origin: &FloatingOrigin, visible: Rect) -> Vec<Vec2> {
+        let rect = self.local_rect(origin);
+        let mut offsets = Vec::new();
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+                let offset = Vec2::new(dx as f32 * rect.w, dy as f32 * rect.h);
+                if rect.offset(offset).overlaps(&visible) {
+                    offsets.push(offset);
+                }
+            }
+        }
+        offsets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map_config::ArenaConfig;
+
+    #[test]
+    fn test_wrap_and_parse() {
+        let origin = FloatingOrigin::new();
+        let arena = ArenaBounds::new(DVec2::new(1000.0, 0.0), Vec2::new(4000.0, 2000.0));
+        assert_eq!(arena.wrap(&origin, Vec2::new(500.0, 200.0)), None);
+        assert_eq!(arena.wrap(&origin, Vec2::new(3100.0, 0.0)), Some(Vec2::new(-900.0, 0.0)));
+        assert_eq!(arena.wrap(&origin, Vec2::new(0.0, -1100.0)), Some(Vec2::new(0.0, 900.0)));
+
+        // Looking over the right edge shows the copy to the right (and nothing else)
+        let visible = Rect::new(2500.0, -200.0, 1000.0, 400.0);
+        assert_eq!(arena.visible_copies(&origin, visible), vec![Vec2::new(4000.0, 0.0)]);
+
+        assert_eq!(ArenaConfig::parse("8000"), Some(ArenaConfig { width: 8000.0, height: 8000.0 }));
+        assert_eq!(ArenaConfig::parse("12000x6000"), Some(ArenaConfig { width: 12000.0, height: 6000.0 }));
+        assert_eq!(ArenaConfig::parse("wide"), None);
+        assert_eq!(ArenaBounds::new(DVec2::ZERO, Vec2::splat(10.0)).width, MIN_ARENA_SIZE);
+    }
+}
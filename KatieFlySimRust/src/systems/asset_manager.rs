@@ -0,0 +1,242 @@
+// Asset Manager - Loads sounds and maps across frames so a loading screen can show progress
+// Map files are parsed on a background thread; sounds are decoded one per frame on the main thread (audio needs it)
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use macroquad::audio::{load_sound_from_bytes, Sound};
+
+use crate::map_config::MapConfiguration;
+
+/// Key of the fuel transfer hum
+pub const TRANSFER_HUM: &str = "transfer_hum";
+/// Key of the new-alert beep
+pub const ALERT_BEEP: &str = "alert_beep";
+/// Folder custom maps are read from
+const MAPS_FOLDER: &str = "maps";
+
+/// Message from the map loading thread
+enum MapLoadMessage {
+    Found(usize),                // Number of map files about to be parsed
+    Parsed(String),              // File just finished (ok or not)
+    Done(Vec<MapConfiguration>, Vec<String>), // Built-in maps followed by every custom map that parsed, and why the others didn't
+}
+
+/// Background loading of game assets with progress reporting
+pub struct AssetManager {
+    sounds: HashMap<String, Sound>,
+    maps: Vec<MapConfiguration>,
+    map_errors: Vec<String>, // "file: reason" for each custom map that was rejected
+    pending_sounds: VecDeque<(String, Vec<u8>)>, // Queued for decoding, oldest first
+    map_job: Option<Receiver<MapLoadMessage>>,
+    completed: usize,
+    total: usize,
+    current: String, // What is being loaded right now
+}
+
+impl AssetManager {
+    /// Queue the built-in sounds and start scanning the maps folder
+    pub fn new() -> Self {
+        let mut manager = AssetManager::empty();
+        manager.queue_sound(TRANSFER_HUM, crate::ui::FuelTransferEffects::hum_wav_bytes());
+        manager.queue_sound(ALERT_BEEP, crate::ui::AlertBanner::warning_wav_bytes());
+        manager.reload_maps();
+        manager
+    }
+
+    /// Manager with nothing loaded or queued (only the built-in maps)
+    pub fn empty() -> Self {
+        AssetManager {
+            sounds: HashMap::new(),
+            maps: MapConfiguration::built_in_maps(),
+            map_errors: Vec::new(),
+            pending_sounds: VecDeque::new(),
+            map_job: None,
+            completed: 0,
+            total: 0,
+            current: String::new(),
+        }
+    }
+
+    /// Queue encoded audio to be decoded under `name`
+    pub fn queue_sound(&mut self, name: &str, bytes: Vec<u8>) {
+        self.pending_sounds.push_back((name.to_string(), bytes));
+        self.total += 1;
+    }
+
+    /// Re-scan the maps folder in the background (picks up maps added since startup)
+    pub fn reload_maps(&mut self) {
+        self.reload_maps_from(MAPS_FOLDER);
+    }
+
+    /// Scan a specific folder in the background
+    pub fn reload_maps_from(&mut self, folder: &str) {
+        let (sender, receiver) = mpsc::channel();
+        let folder = folder.to_string();
+        thread::spawn(move || {
+            let paths = MapConfiguration::custom_map_paths(&folder);
+            let _ = sender.send(MapLoadMessage::Found(paths.len()));
+
+            let mut maps = MapConfiguration::built_in_maps();
+            let mut errors = Vec::new();
+            for path in paths {
+                match MapConfiguration::load_custom(&path, &maps) {
+                    Ok(map) => {
+                        log::info!("Loaded custom map: {}", map.name);
+                        maps.push(map);
+                    }
+                    Err(e) => {
+                        log::warn!("Failed to load map from {}: {}", path, e);
+                        errors.push(format!("{}: {}", file_name(&path), e));
+                    }
+                }
+                let _ = sender.send(MapLoadMessage::Parsed(path));
+            }
+            let _ = sender.send(MapLoadMessage::Done(maps, errors));
+        });
+
+        // The scan itself counts as one step so the bar never reads full before it reports
+        self.map_job = Some(receiver);
+        self.total += 1;
+        self.current = "Scanning maps".to_string();
+    }
+
+    /// Collect finished map files without blocking
+    pub fn poll_maps(&mut self) {
+        let Some(receiver) = &self.map_job else {
+            return;
+        };
+        loop {
+            match receiver.try_recv() {
+                Ok(MapLoadMessage::Found(count)) => self.total += count,
+                Ok(MapLoadMessage::Parsed(path)) => {
+                    self.completed += 1;
+                    self.current = format!("Map {}", file_name(&path));
+                }
+                Ok(MapLoadMessage::Done(maps, errors)) => {
+                    self.maps = maps;
+                    self.map_errors = errors;
+                    self.completed += 1;
+                    self.map_job = None;
+                    break;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    log::error!("Map loading thread stopped before finishing");
+                    self.completed += 1;
+                    self.map_job = None;
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Advance loading by one frame's worth: decode the next sound and collect parsed maps
+    pub async fn step(&mut self) {
+        if let Some((name, bytes)) = self.pending_sounds.pop_front() {
+            self.current = format!("Sound {}", name);
+            match load_sound_from_bytes(&bytes).await {
+                Ok(sound) => {
+                    self.sounds.insert(name, sound);
+                }
+                Err(e) => log::warn!("Failed to load sound {}: {:?}", name, e),
+            }
+            self.completed += 1;
+        }
+        self.poll_maps();
+    }
+
+    /// Whether everything queued has finished (successfully or not)
+    pub fn is_loaded(&self) -> bool {
+        self.pending_sounds.is_empty() && self.map_job.is_none()
+    }
+
+    /// Fraction loaded so far (0.0 - 1.0)
+    pub fn progress(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        (self.completed as f32 / self.total as f32).min(1.0)
+    }
+
+    /// What is being loaded right now
+    pub fn current(&self) -> &str {
+        &self.current
+    }
+
+    /// Start counting progress from zero for the next batch
+    pub fn reset_progress(&mut self) {
+        self.completed = 0;
+        self.total = self.pending_sounds.len() + usize::from(self.map_job.is_some());
+    }
+
+    /// A decoded sound (None if it failed or audio is unavailable)
+    pub fn sound(&self, name: &str) -> Option<Sound> {
+        self.sounds.get(name).cloned()
+    }
+
+    /// Built-in maps followed by the custom maps that loaded
+    pub fn maps(&self) -> &[MapConfiguration] {
+        &self.maps
+    }
+
+    /// Custom map files that were skipped on the last scan, with the reason
+    pub fn map_errors(&self) -> &[String] {
+        &self.map_errors
+    }
+
+    /// A loaded map by name
+    pub fn map(&self, name: &str) -> Option<MapConfiguration> {
+        self.maps.iter().find(|map| map.name == name).cloned()
+    }
+}
+
+impl Default for AssetManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Last component of a path, for progress and error lines
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maps_load_in_background_with_progress() {
+        let folder = std::env::temp_dir().join(format!("katie_asset_maps_{}", std::process::id()));
+        std::fs::create_dir_all(&folder).unwrap();
+        let mut custom = MapConfiguration::earth_moon();
+        custom.name = "Background Map".to_string();
+        custom.save_to_file(folder.join("background.ron").to_str().unwrap()).unwrap();
+        std::fs::write(folder.join("broken.ron"), "not a map").unwrap();
+        // Same name as a built-in map, so it could never be picked
+        MapConfiguration::earth_moon().save_to_file(folder.join("copy.ron").to_str().unwrap()).unwrap();
+
+        let mut assets = AssetManager::empty();
+        assets.reload_maps_from(folder.to_str().unwrap());
+        assert!(!assets.is_loaded());
+        assert!(assets.progress() < 1.0);
+
+        for _ in 0..500 {
+            assets.poll_maps();
+            if assets.is_loaded() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(assets.is_loaded());
+        assert_eq!(assets.progress(), 1.0);
+        assert_eq!(assets.maps().len(), MapConfiguration::built_in_maps().len() + 1); // Broken file and duplicate skipped
+        assert!(assets.map("Background Map").is_some());
+        assert_eq!(assets.map_errors().len(), 2);
+        assert!(assets.map_errors().iter().any(|e| e.starts_with("copy.ron") && e.contains("already used")));
+
+        let _ = std::fs::remove_dir_all(&folder);
+    }
+}
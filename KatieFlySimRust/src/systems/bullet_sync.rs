@@ -0,0 +1,79 @@
+// Bullet Sync - Host-authoritative bullet removal for multiplayer clients
+// The host sends the IDs of bullets it removed right away; clients drop them instead of flying ghosts until the next snapshot
+
+use serde::{Deserialize, Serialize};
+
+use crate::systems::EntityId;
+
+/// Prefix marking a bullet removal packet (snapshots never start with these bytes)
+const REMOVAL_PACKET_PREFIX: &[u8] = b"BULLETSGONE";
+/// Most IDs per packet, keeping packets well under the client's receive buffer
+pub const MAX_REMOVALS_PER_PACKET: usize = 256;
+
+/// Bullets the host removed (lifetime up or hit something) at `host_time`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BulletRemovalPacket {
+    pub host_time: f32, // Host session time, comparable with snapshot game_time
+    pub bullet_ids: Vec<EntityId>,
+}
+
+impl BulletRemovalPacket {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = REMOVAL_PACKET_PREFIX.to_vec();
+        bytes.extend(bincode::serialize(self).unwrap_or_default());
+        bytes
+    }
+
+    /// None if the bytes aren't a removal packet
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let payload = bytes.strip_prefix(REMOVAL_PACKET_PREFIX)?;
+        bincode::deserialize(payload).ok()
+    }
+}
+
+/// Client-side record of host removals, so snapshots sent before a removal don't bring the bullet back
+#[derive(Debug, Clone, Default)]
+pub struct RemovedBullets {
+    removed: Vec<(EntityId, f32)>, // Bullet ID, host time it was removed
+}
+
+impl RemovedBullets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, packet: &BulletRemovalPacket) {
+        self.removed.extend(packet.bullet_ids.iter().map(|id| (*id, packet.host_time)));
+    }
+
+    /// Bullets in a snapshot taken at `snapshot_time` that the host has since removed
+    /// Removals older than the snapshot are forgotten (the snapshot already left them out)
+    pub fn stale_in(&mut self, snapshot_time: f32) -> Vec<EntityId> {
+        self.removed.retain(|(_, removed_at)| *removed_at >= snapshot_time);
+        self.removed.iter().map(|(id, _)| *id).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packet_round_trip() {
+        let packet = BulletRemovalPacket { host_time: 4.5, bullet_ids: vec![3, 9] };
+        assert_eq!(BulletRemovalPacket::from_bytes(&packet.to_bytes()), Some(packet));
+        assert_eq!(BulletRemovalPacket::from_bytes(b"KEEPALIVE"), None);
+    }
+
+    #[test]
+    fn test_removals_outlive_older_snapshots_only() {
+        let mut removed = RemovedBullets::new();
+        removed.record(&BulletRemovalPacket { host_time: 2.0, bullet_ids: vec![5] });
+
+        // A snapshot from before the removal still has the bullet
+        assert_eq!(removed.stale_in(1.9), vec![5]);
+        // Newer snapshots don't, so the removal is dropped
+        assert!(removed.stale_in(2.1).is_empty());
+        assert!(removed.stale_in(1.9).is_empty());
+    }
+}
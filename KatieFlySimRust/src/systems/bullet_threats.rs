@@ -0,0 +1,232 @@
+// Bullet Threats - Bullet path prediction and incoming-bullet detection
+// Shared by the network map (trajectory lines) and the HUD threat arrows
+
+use macroquad::prelude::Vec2;
+
+use crate::entities::GameObject;
+use crate::game_constants::GameConstants;
+use crate::systems::{EntityId, World};
+
+/// Time step for bullet path prediction (seconds)
+pub const BULLET_PREDICTION_DT: f32 = 0.1;
+
+/// A bullet whose predicted path passes close to a rocket
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BulletThreat {
+    pub bullet_id: EntityId,
+    pub direction: Vec2,      // Unit vector from the rocket to the bullet's current position
+    pub distance: f32,        // Current distance to the bullet
+    pub time_to_closest: f32, // Seconds until closest approach
+    pub miss_distance: f32,   // Predicted closest approach
+}
+
+/// Predict the paths of several free-falling bodies, simulating planet motion alongside
+/// Pinned planets stay fixed like in the real physics; each path stops where it hits a planet
+pub fn predict_paths(world: &World, bodies: &[(Vec2, Vec2)], steps: usize, dt: f32) -> Vec<Vec<Vec2>> {
+    // Mutable copies of planet states (position, velocity, mass, radius)
+    let mut planet_states: Vec<(Vec2, Vec2, f32, f32)> = world.planets()
+        .map(|p| (p.position(), p.velocity(), p.mass(), p.radius()))
+        .collect();
+
+    let pinned: Vec<bool> = world.planets().map(|p| p.is_pinned()).collect();
+    let gravity_model = world.gravity_simulator().model();
+
+    let mut states: Vec<(Vec2, Vec2)> = bodies.to_vec();
+    let mut paths: Vec<Vec<Vec2>> = bodies.iter().map(|_| Vec::with_capacity(steps)).collect();
+    let mut active: Vec<bool> = vec![true; bodies.len()];
+
+    for _ in 0..steps {
+        for (i, (pos, _)) in states.iter().enumerate() {
+            if active[i] {
+                paths[i].push(*pos);
+            }
+        }
+
+        // Step 1: Apply planet-to-planet gravity (e.g., Moon orbiting Earth)
+        if planet_states.len() >= 2 {
+            for i in 0..planet_states.len() {
+                if pinned[i] {
+                    continue; // Pinned planets don't move
+                }
+
+                let mut planet_acceleration = Vec2::ZERO;
+                for j in 0..planet_states.len() {
+                    if i == j {
+                        continue;
+                    }
+
+                    let (pos_i, _, mass_i, radius_i) = planet_states[i];
+                    let (pos_j, _, mass_j, _) = planet_states[j];
+
+                    let direction = pos_j - pos_i;
+                    let distance = direction.length();
+
+                    if distance > radius_i {
+                        let force_magnitude = (GameConstants::G * mass_i * mass_j) / (distance * distance);
+                        planet_acceleration += direction / distance * (force_magnitude / mass_i);
+                    }
+                }
+
+                planet_states[i].1 += planet_acceleration * dt;
+            }
+        }
+
+        // Step 2: Update planet positions based on their velocities
+        for (i, planet) in planet_states.iter_mut().enumerate() {
+            if !pinned[i] {
+                planet.0 += planet.1 * dt;
+            }
+        }
+
+        // Step 3: Apply gravity from the updated planet positions to each body
+        for (i, (pos, vel)) in states.iter_mut().enumerate() {
+            if !active[i] {
+                continue;
+            }
+
+            let mut total_accel = Vec2::ZERO;
+            for &(planet_pos, _, planet_mass, planet_radius) in &planet_states {
+                let diff = planet_pos - *pos;
+                let distance = diff.length();
+                if distance > 0.0 {
+                    total_accel += diff / distance * gravity_model.acceleration(GameConstants::G * planet_mass, distance, planet_radius);
+                }
+            }
+
+            *vel += total_accel * dt;
+            *pos += *vel * dt;
+
+            // Step 4: Stop at planets' PREDICTED positions (only if it will actually hit)
+            if planet_states
+                .iter()
+                .any(|&(planet_pos, _, _, planet_radius)| (planet_pos - *pos).length() < planet_radius + 5.0)
+            {
+                active[i] = false;
+            }
+        }
+
+        if !active.iter().any(|a| *a) {
+            break;
+        }
+    }
+
+    paths
+}
+
+/// Predict one bullet's path (for the network map)
+pub fn predict_bullet_path(world: &World, position: Vec2, velocity: Vec2, steps: usize) -> Vec<Vec2> {
+    predict_paths(world, &[(position, velocity)], steps, BULLET_PREDICTION_DT)
+        .pop()
+        .unwrap_or_default()
+}
+
+/// Find bullets predicted to pass within `warning_radius` of a rocket in the next `horizon` seconds
+/// The rocket is assumed to coast; bullets already moving away (e.g. just fired by this rocket) are ignored
+pub fn find_bullet_threats(world: &World, rocket_id: EntityId, warning_radius: f32, horizon: f32) -> Vec<BulletThreat> {
+    let Some(rocket) = world.get_rocket(rocket_id) else {
+        return Vec::new();
+    };
+    if rocket.is_landed() {
+        return Vec::new(); // Landed rockets can't be hit
+    }
+    let rocket_pos = rocket.position();
+    let rocket_vel = rocket.velocity();
+
+    let incoming: Vec<(EntityId, Vec2, Vec2)> = world
+        .bullets_with_ids()
+        .map(|(id, b)| (id, b.position(), b.velocity()))
+        .filter(|(_, pos, vel)| (*pos - rocket_pos).dot(*vel - rocket_vel) < 0.0)
+        .collect();
+    if incoming.is_empty() {
+        return Vec::new();
+    }
+
+    // Rocket first, then every incoming bullet
+    let mut bodies = vec![(rocket_pos, rocket_vel)];
+    bodies.extend(incoming.iter().map(|(_, pos, vel)| (*pos, *vel)));
+    let steps = (horizon / BULLET_PREDICTION_DT).ceil() as usize;
+    let paths = predict_paths(world, &bodies, steps, BULLET_PREDICTION_DT);
+    let rocket_path = &paths[0];
+
+    let mut threats: Vec<BulletThreat> = incoming
+        .iter()
+        .zip(&paths[1..])
+        .filter_map(|((bullet_id, bullet_pos, _), bullet_path)| {
+            // Closest approach, treating relative motion as linear within each step
+            let mut best: Option<(f32, f32)> = None; // (miss distance, time)
+            for step in 0..bullet_path.len().min(rocket_path.len()).saturating_sub(1) {
+                let start = bullet_path[step] - rocket_path[step];
+                let end = bullet_path[step + 1] - rocket_path[step + 1];
+                let segment = end - start;
+                let fraction = if segment.length_squared() > 0.0 {
+                    (-start.dot(segment) / segment.length_squared()).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                let miss = (start + segment * fraction).length();
+                if best.is_none_or(|(best_miss, _)| miss < best_miss) {
+                    best = Some((miss, (step as f32 + fraction) * BULLET_PREDICTION_DT));
+                }
+            }
+
+            let (miss_distance, time_to_closest) = best?;
+            if miss_distance > warning_radius {
+                return None;
+            }
+            let offset = *bullet_pos - rocket_pos;
+            Some(BulletThreat {
+                bullet_id: *bullet_id,
+                direction: offset.normalize_or_zero(),
+                distance: offset.length(),
+                time_to_closest,
+                miss_distance,
+            })
+        })
+        .collect();
+
+    threats.sort_by(|a, b| a.time_to_closest.partial_cmp(&b.time_to_closest).unwrap_or(std::cmp::Ordering::Equal));
+    threats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Bullet, Planet, Rocket};
+    use macroquad::prelude::{BLUE, WHITE};
+
+    #[test]
+    fn test_bullet_path_stops_at_planet() {
+        let mut world = World::new();
+        world.add_planet(Planet::new(Vec2::new(0.0, 0.0), 100.0, 10000.0, BLUE));
+
+        let path = predict_bullet_path(&world, Vec2::new(1000.0, 0.0), Vec2::new(-500.0, 0.0), 600);
+        assert!(path.len() < 600);
+        assert!(path.last().unwrap().x > 100.0);
+    }
+
+    #[test]
+    fn test_incoming_bullet_is_a_threat() {
+        let mut world = World::new();
+        let rocket_id = world.add_rocket(Rocket::new(Vec2::new(5000.0, 0.0), Vec2::ZERO, WHITE, 1.0));
+        world.add_bullet(Bullet::new(Vec2::new(5000.0, -1000.0), Vec2::new(0.0, 500.0)));
+        // Moving away - not a threat
+        world.add_bullet(Bullet::new(Vec2::new(5000.0, 50.0), Vec2::new(0.0, 500.0)));
+
+        let threats = find_bullet_threats(&world, rocket_id, 50.0, 10.0);
+        assert_eq!(threats.len(), 1);
+        let threat = threats[0];
+        assert!((threat.time_to_closest - 2.0).abs() < 0.05);
+        assert!(threat.miss_distance < 1.0);
+        assert!((threat.direction - Vec2::new(0.0, -1.0)).length() < 1e-4);
+    }
+
+    #[test]
+    fn test_near_miss_outside_radius_ignored() {
+        let mut world = World::new();
+        let rocket_id = world.add_rocket(Rocket::new(Vec2::new(5000.0, 0.0), Vec2::ZERO, WHITE, 1.0));
+        world.add_bullet(Bullet::new(Vec2::new(5200.0, -1000.0), Vec2::new(0.0, 500.0)));
+
+        assert!(find_bullet_threats(&world, rocket_id, 100.0, 10.0).is_empty());
+        assert_eq!(find_bullet_threats(&world, rocket_id, 300.0, 10.0).len(), 1);
+    }
+}
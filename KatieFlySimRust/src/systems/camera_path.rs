@@ -0,0 +1,202 @@
+// Camera Path - Keyframed camera moves for replays (position, zoom and replay time)
+// Keys are dropped in the replay viewer's photo mode and eased between on playback; positions are stored
+// in f64 world coordinates so they survive floating origin rebases. Saved next to the replay as <name>.campath
+
+use std::fs;
+use std::path::Path;
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const CAMERA_PATH_EXTENSION: &str = "campath";
+/// Keys closer together than this (seconds) replace each other instead of stacking
+const KEY_MERGE_WINDOW: f32 = 0.05;
+
+/// How a segment eases from one key into the next
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Easing {
+    Linear,
+    #[default]
+    EaseInOut, // Slow out of the key, slow into the next
+    Hold,      // Stay on the key until the next one, then cut
+}
+
+impl Easing {
+    /// Map segment progress 0.0-1.0 through the curve
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+            Easing::Hold => 0.0,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Easing::Linear => Easing::EaseInOut,
+            Easing::EaseInOut => Easing::Hold,
+            Easing::Hold => Easing::Linear,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Easing::Linear => "linear",
+            Easing::EaseInOut => "ease in/out",
+            Easing::Hold => "hold",
+        }
+    }
+}
+
+/// One camera placement at a replay time
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CameraKey {
+    pub time: f32,
+    pub position: (f64, f64), // World position (local position + floating origin offset)
+    pub zoom: f32,
+    pub easing: Easing, // Into the next key
+}
+
+impl CameraKey {
+    pub fn new(time: f32, position: DVec2, zoom: f32, easing: Easing) -> Self {
+        CameraKey { time, position: (position.x, position.y), zoom, easing }
+    }
+
+    pub fn world_position(&self) -> DVec2 {
+        DVec2::new(self.position.0, self.position.1)
+    }
+}
+
+/// Where the path puts the camera at one moment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraPose {
+    pub position: DVec2,
+    pub zoom: f32,
+}
+
+/// Keys in time order
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CameraPath {
+    keys: Vec<CameraKey>,
+}
+
+impl CameraPath {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn keys(&self) -> &[CameraKey] {
+        &self.keys
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.keys.clear();
+    }
+
+    /// Add a key, replacing one already at (nearly) the same time
+    pub fn insert(&mut self, key: CameraKey) {
+        self.keys.retain(|existing| (existing.time - key.time).abs() > KEY_MERGE_WINDOW);
+        let index = self.keys.partition_point(|existing| existing.time < key.time);
+        self.keys.insert(index, key);
+    }
+
+    /// Drop the key nearest `time`; returns it
+    pub fn remove_nearest(&mut self, time: f32) -> Option<CameraKey> {
+        let index = (0..self.keys.len()).min_by(|a, b| {
+            let da = (self.keys[*a].time - time).abs();
+            let db = (self.keys[*b].time - time).abs();
+            da.total_cmp(&db)
+        })?;
+        Some(self.keys.remove(index))
+    }
+
+    /// Camera at `time`: the first/last key outside the path, eased between keys inside it
+    pub fn sample(&self, time: f32) -> Option<CameraPose> {
+        let first = self.keys.first()?;
+        let last = self.keys.last()?;
+        if time <= first.time {
+            return Some(CameraPose { position: first.world_position(), zoom: first.zoom });
+        }
+        if time >= last.time {
+            return Some(CameraPose { position: last.world_position(), zoom: last.zoom });
+        }
+
+        let next = self.keys.partition_point(|key| key.time <= time);
+        let (from, to) = (&self.keys[next - 1], &self.keys[next]);
+        let span = to.time - from.time;
+        let t = if span > 0.0 { from.easing.apply((time - from.time) / span) } else { 1.0 };
+        // Zoom eases in log space so zooming in and out feel equally fast
+        let zoom = (from.zoom.ln() + (to.zoom.ln() - from.zoom.ln()) * t).exp();
+        Some(CameraPose { position: from.world_position().lerp(to.world_position(), t as f64), zoom })
+    }
+
+    /// File the path for `replay_path` lives in (same folder and name, .campath)
+    pub fn path_for(replay_path: &str) -> String {
+        Path::new(replay_path).with_extension(CAMERA_PATH_EXTENSION).to_string_lossy().to_string()
+    }
+
+    /// Write next to the replay and return the path
+    pub fn save_for(&self, replay_path: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let file_path = Self::path_for(replay_path);
+        fs::write(&file_path, serde_json::to_string_pretty(self)?)?;
+        log::info!("Camera path saved to: {} ({} keys)", file_path, self.keys.len());
+        Ok(file_path)
+    }
+
+    /// The replay's saved path, if it has one
+    pub fn load_for(replay_path: &str) -> Result<Option<Self>, Box<dyn std::error::Error>> {
+        let file_path = Self::path_for(replay_path);
+        if !Path::new(&file_path).exists() {
+            return Ok(None);
+        }
+        let mut path: CameraPath = serde_json::from_str(&fs::read_to_string(&file_path)?)?;
+        path.keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Ok(Some(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(time: f32, x: f64, zoom: f32, easing: Easing) -> CameraKey {
+        CameraKey::new(time, DVec2::new(x, 0.0), zoom, easing)
+    }
+
+    #[test]
+    fn test_sample_eases_between_keys() {
+        let mut path = CameraPath::new();
+        path.insert(key(10.0, 100.0, 4.0, Easing::Linear));
+        path.insert(key(0.0, 0.0, 1.0, Easing::EaseInOut));
+        assert_eq!(path.keys()[0].time, 0.0);
+
+        // Clamped outside the path
+        assert_eq!(path.sample(-5.0).unwrap().position.x, 0.0);
+        assert_eq!(path.sample(20.0).unwrap().zoom, 4.0);
+
+        // Ease in/out is symmetric about the midpoint but slow near the keys
+        let middle = path.sample(5.0).unwrap();
+        assert!((middle.position.x - 50.0).abs() < 1e-6);
+        assert!((middle.zoom - 2.0).abs() < 1e-4);
+        assert!(path.sample(1.0).unwrap().position.x < 10.0);
+
+        path.insert(key(0.01, 0.0, 1.0, Easing::Hold));
+        assert_eq!(path.keys().len(), 2, "a key at the same time replaces the old one");
+        assert_eq!(path.sample(9.0).unwrap().position.x, 0.0);
+    }
+
+    #[test]
+    fn test_remove_nearest_and_file_name() {
+        let mut path = CameraPath::new();
+        path.insert(key(0.0, 0.0, 1.0, Easing::Linear));
+        path.insert(key(8.0, 10.0, 1.0, Easing::Linear));
+        assert_eq!(path.remove_nearest(6.0).map(|key| key.time), Some(8.0));
+        assert_eq!(path.keys().len(), 1);
+        assert_eq!(CameraPath::path_for("saves/replays/match_1.replay"), "saves/replays/match_1.campath");
+    }
+}
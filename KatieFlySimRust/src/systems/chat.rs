@@ -0,0 +1,61 @@
+// Chat - Text messages between players in a multiplayer game
+// Clients send their line to the host, which names the sender from their join packet and relays it to everyone else
+
+use serde::{Deserialize, Serialize};
+
+/// Prefix marking a chat packet (snapshots never start with these bytes)
+const CHAT_PACKET_PREFIX: &[u8] = b"CHATMSG";
+/// Longest message in characters; longer ones are cut
+pub const MAX_CHAT_LENGTH: usize = 160;
+
+/// One line of chat (clients leave `sender` empty; the host fills it in)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub player_id: u32,
+    pub sender: String,
+    pub text: String,
+}
+
+impl ChatMessage {
+    /// A message with its text cleaned up; None if nothing printable is left
+    pub fn new(player_id: u32, sender: impl Into<String>, text: &str) -> Option<Self> {
+        let text = Self::clean(text);
+        if text.is_empty() {
+            return None;
+        }
+        Some(ChatMessage { player_id, sender: sender.into(), text })
+    }
+
+    /// Drop control characters, trim and cut to `MAX_CHAT_LENGTH`
+    pub fn clean(text: &str) -> String {
+        let printable: String = text.chars().filter(|ch| !ch.is_control()).collect();
+        printable.trim().chars().take(MAX_CHAT_LENGTH).collect()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = CHAT_PACKET_PREFIX.to_vec();
+        bytes.extend(bincode::serialize(self).unwrap_or_default());
+        bytes
+    }
+
+    /// None if the bytes aren't a chat packet
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let payload = bytes.strip_prefix(CHAT_PACKET_PREFIX)?;
+        bincode::deserialize(payload).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_cleaning_and_round_trip() {
+        let message = ChatMessage::new(2, "Katie", "  hi\u{7}there  ").unwrap();
+        assert_eq!(message.text, "hithere");
+        assert_eq!(ChatMessage::from_bytes(&message.to_bytes()), Some(message));
+        assert_eq!(ChatMessage::new(2, "Katie", " \n "), None);
+        assert_eq!(ChatMessage::clean(&"x".repeat(500)).len(), MAX_CHAT_LENGTH);
+        assert_eq!(ChatMessage::from_bytes(b"KEEPALIVE"), None);
+    }
+}
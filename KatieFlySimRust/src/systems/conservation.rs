@@ -0,0 +1,172 @@
+// Conservation - Total energy and momentum of the world, tracked for drift each tick
+// Without thrust, shots or collisions both totals should hold steady; drift points at integrator
+// or collision-response bugs (small craft don't pull on planets, so expect a little momentum drift near them)
+
+use std::collections::VecDeque;
+
+use macroquad::prelude::*;
+
+use crate::entities::GameObject;
+use crate::game_constants::GameConstants;
+use crate::physics::gravity_simulator::MIN_DISTANCE;
+use crate::systems::World;
+
+/// Samples kept for the drift graph (ten seconds at 60 Hz)
+pub const CONSERVATION_HISTORY_LEN: usize = 600;
+
+/// Totals over every body in the world at one moment (f64, as the sums span huge and tiny bodies)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConservationSample {
+    pub time: f32,
+    pub kinetic: f64,
+    pub potential: f64, // Planet pairs and rocket pairs inverse-square; craft in planet fields by the map's model
+    pub momentum: DVec2,
+}
+
+impl ConservationSample {
+    /// Measure the world (`time` only labels the sample)
+    pub fn measure(world: &World, time: f32) -> Self {
+        let g = GameConstants::G as f64;
+        let mut kinetic = 0.0;
+        let mut momentum = DVec2::ZERO;
+        let mut add_body = |mass: f32, velocity: Vec2| {
+            let velocity = velocity.as_dvec2();
+            kinetic += 0.5 * mass as f64 * velocity.length_squared();
+            momentum += velocity * mass as f64;
+        };
+        let planets: Vec<_> = world.planets().collect();
+        for planet in &planets {
+            add_body(planet.mass(), planet.velocity());
+        }
+        for rocket in world.rockets() {
+            add_body(rocket.mass(), rocket.velocity());
+        }
+        for satellite in world.satellites() {
+            add_body(satellite.mass(), satellite.velocity());
+        }
+        for bullet in world.bullets() {
+            add_body(bullet.mass(), bullet.velocity());
+        }
+
+        let pair_potential = |mass_a: f32, position_a: Vec2, mass_b: f32, position_b: Vec2| {
+            let distance = position_a.distance(position_b).max(MIN_DISTANCE) as f64;
+            -g * mass_a as f64 * mass_b as f64 / distance
+        };
+        let mut potential = 0.0;
+        for (i, a) in planets.iter().enumerate() {
+            for b in &planets[i + 1..] {
+                potential += pair_potential(a.mass(), a.position(), b.mass(), b.position());
+            }
+        }
+        let rockets: Vec<_> = world.rockets().collect();
+        for (i, a) in rockets.iter().enumerate() {
+            for b in &rockets[i + 1..] {
+                potential += pair_potential(a.mass(), a.position(), b.mass(), b.position());
+            }
+        }
+
+        // Craft feel the planets through the map's gravity model (landed rockets are held still)
+        let model = world.gravity_simulator().model();
+        let craft = rockets
+            .iter()
+            .filter(|rocket| !rocket.is_landed())
+            .map(|rocket| (rocket.mass(), rocket.position()))
+            .chain(world.satellites().map(|satellite| (satellite.mass(), satellite.position())))
+            .chain(world.bullets().map(|bullet| (bullet.mass(), bullet.position())));
+        for (mass, position) in craft {
+            for planet in &planets {
+                let distance = position.distance(planet.position()).max(MIN_DISTANCE);
+                potential += mass as f64 * model.potential(GameConstants::G * planet.mass(), distance, planet.radius());
+            }
+        }
+
+        ConservationSample { time, kinetic, potential, momentum }
+    }
+
+    pub fn energy(&self) -> f64 {
+        self.kinetic + self.potential
+    }
+}
+
+/// Energy and momentum history since a baseline, for the physics debug overlay
+#[derive(Debug, Clone, Default)]
+pub struct ConservationMonitor {
+    baseline: Option<ConservationSample>,
+    samples: VecDeque<ConservationSample>, // Oldest first
+}
+
+impl ConservationMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Measure the world after a tick; the first sample after a reset becomes the baseline
+    pub fn record(&mut self, world: &World, time: f32) {
+        let sample = ConservationSample::measure(world, time);
+        self.baseline.get_or_insert(sample);
+        if self.samples.len() == CONSERVATION_HISTORY_LEN {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Start measuring drift from the next sample (after a load, or when the overlay opens)
+    pub fn reset(&mut self) {
+        self.baseline = None;
+        self.samples.clear();
+    }
+
+    pub fn latest(&self) -> Option<&ConservationSample> {
+        self.samples.back()
+    }
+
+    /// Energy change since the baseline as a fraction of the baseline's size, for each sample oldest first
+    pub fn energy_drift(&self) -> impl Iterator<Item = f64> + '_ {
+        let baseline = self.baseline.map_or(0.0, |sample| sample.energy());
+        let scale = baseline.abs().max(f64::EPSILON);
+        self.samples.iter().map(move |sample| (sample.energy() - baseline) / scale)
+    }
+
+    /// How far total momentum has moved from the baseline's, for each sample oldest first
+    pub fn momentum_drift(&self) -> impl Iterator<Item = f64> + '_ {
+        let baseline = self.baseline.map_or(DVec2::ZERO, |sample| sample.momentum);
+        self.samples.iter().map(move |sample| (sample.momentum - baseline).length())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Planet, Rocket};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_totals_and_drift() {
+        let mut world = World::new();
+        world.add_planet(Planet::new(Vec2::ZERO, 100.0, 1000.0, BLUE));
+        world.add_planet(Planet::new(Vec2::new(1000.0, 0.0), 50.0, 10.0, GRAY));
+        let rocket_id = world.add_rocket(Rocket::new(Vec2::new(0.0, -500.0), Vec2::new(4.0, 0.0), WHITE, 1.0));
+        let rocket_mass = world.get_rocket(rocket_id).unwrap().mass() as f64;
+
+        let sample = ConservationSample::measure(&world, 0.0);
+        assert_relative_eq!(sample.kinetic, 8.0 * rocket_mass, epsilon = 1e-6);
+        assert_relative_eq!(sample.momentum.x, 4.0 * rocket_mass, epsilon = 1e-6);
+        // Planet pair from infinity; the rocket's height in each well from that planet's surface
+        let g = GameConstants::G as f64;
+        let planet_pair = -g * 1000.0 * 10.0 / 1000.0;
+        let heights = g * 1000.0 * (1.0 / 100.0 - 1.0 / 500.0) + g * 10.0 * (1.0 / 50.0 - 1.0 / 500.0_f64.hypot(1000.0));
+        assert_relative_eq!(sample.potential, planet_pair + rocket_mass * heights, max_relative = 1e-5);
+
+        let mut monitor = ConservationMonitor::new();
+        monitor.record(&world, 0.0);
+        world.get_rocket_mut(rocket_id).unwrap().set_velocity(Vec2::new(0.0, 4.0));
+        monitor.record(&world, 0.1);
+        let energy: Vec<f64> = monitor.energy_drift().collect();
+        assert_eq!(energy[0], 0.0);
+        assert!(energy[1].abs() < 1e-9); // Same speed, same energy
+        assert_relative_eq!(monitor.momentum_drift().last().unwrap(), 32.0_f64.sqrt() * rocket_mass, epsilon = 1e-6);
+
+        monitor.reset();
+        assert!(monitor.latest().is_none());
+    }
+}
@@ -0,0 +1,235 @@
+// Controllers - Gamepad input and rumble from gilrs (build with --features gamepad)
+// One gilrs context serves every mode: pads take slots 0-3 in the order they connect and keep them until unplugged
+// Without the feature there are no pads, so `Gamepads` stay empty and `Haptics` stay silent
+
+use crate::player::Gamepads;
+use crate::systems::{Haptics, NoGamepad, RumbleDevice};
+
+/// The controller backend, started once and handed to each game mode
+#[derive(Clone, Default)]
+pub struct Controllers {
+    #[cfg(feature = "gamepad")]
+    backend: Option<gilrs_backend::Shared>,
+}
+
+impl Controllers {
+    /// Open the platform's controller API (logs and carries on keyboard-only if it can't)
+    pub fn start() -> Self {
+        #[cfg(feature = "gamepad")]
+        return Controllers { backend: gilrs_backend::start() };
+        #[cfg(not(feature = "gamepad"))]
+        return Controllers::default();
+    }
+
+    /// Every controller slot, for a mode's input
+    pub fn gamepads(&self) -> Gamepads {
+        #[cfg(feature = "gamepad")]
+        if let Some(backend) = &self.backend {
+            return Gamepads::new(Box::new(gilrs_backend::PadInput(backend.clone())));
+        }
+        Gamepads::disconnected()
+    }
+
+    /// The motors of whichever controller is in `slot`
+    pub fn rumble(&self, slot: usize) -> Box<dyn RumbleDevice> {
+        #[cfg(feature = "gamepad")]
+        if let Some(backend) = &self.backend {
+            return Box::new(gilrs_backend::PadRumble::new(backend.clone(), slot));
+        }
+        let _ = slot;
+        Box::new(NoGamepad)
+    }
+
+    /// Rumble mixer for the controller in `slot`
+    pub fn haptics(&self, slot: usize, intensity: f32) -> Haptics {
+        Haptics::new(self.rumble(slot), intensity)
+    }
+}
+
+#[cfg(feature = "gamepad")]
+mod gilrs_backend {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use gilrs::ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Replay, Ticks};
+    use gilrs::{Axis, Button, EventType, GamepadId, Gilrs};
+    use macroquad::prelude::Vec2;
+
+    use crate::player::{GamepadButton, GamepadSource, GamepadState, MAX_GAMEPADS};
+    use crate::systems::RumbleDevice;
+
+    /// gilrs buttons for the game's buttons
+    const BUTTONS: [(Button, GamepadButton); 9] = [
+        (Button::South, GamepadButton::South),
+        (Button::East, GamepadButton::East),
+        (Button::West, GamepadButton::West),
+        (Button::North, GamepadButton::North),
+        (Button::LeftTrigger, GamepadButton::LeftShoulder),
+        (Button::RightTrigger, GamepadButton::RightShoulder),
+        (Button::DPadUp, GamepadButton::DPadUp),
+        (Button::DPadDown, GamepadButton::DPadDown),
+        (Button::Start, GamepadButton::Start),
+    ];
+    /// Length of one loop of a rumble effect (it repeats until stopped, so this only sets the restart granularity)
+    const RUMBLE_LOOP_MS: u32 = 1000;
+
+    pub type Shared = Rc<RefCell<Backend>>;
+
+    pub fn start() -> Option<Shared> {
+        match Gilrs::new() {
+            Ok(gilrs) => {
+                let mut backend = Backend { gilrs, slots: [None; MAX_GAMEPADS] };
+                let connected: Vec<GamepadId> = backend.gilrs.gamepads().map(|(id, _)| id).collect();
+                for id in connected {
+                    backend.connect(id);
+                }
+                Some(Rc::new(RefCell::new(backend)))
+            }
+            Err(e) => {
+                log::warn!("Controller support unavailable: {}", e);
+                None
+            }
+        }
+    }
+
+    pub struct Backend {
+        gilrs: Gilrs,
+        slots: [Option<GamepadId>; MAX_GAMEPADS],
+    }
+
+    impl Backend {
+        fn connect(&mut self, id: GamepadId) {
+            if self.slots.contains(&Some(id)) {
+                return;
+            }
+            match self.slots.iter().position(Option::is_none) {
+                Some(slot) => {
+                    log::info!("Controller '{}' connected as pad {}", self.gilrs.gamepad(id).name(), slot + 1);
+                    self.slots[slot] = Some(id);
+                }
+                None => log::info!("Ignoring controller '{}': all {} pads in use", self.gilrs.gamepad(id).name(), MAX_GAMEPADS),
+            }
+        }
+
+        /// Drain gilrs' events: that updates its cached pad state and tells us about plugs and unplugs
+        fn pump(&mut self) {
+            while let Some(event) = self.gilrs.next_event() {
+                match event.event {
+                    EventType::Connected => self.connect(event.id),
+                    EventType::Disconnected => {
+                        if let Some(slot) = self.slots.iter().position(|id| *id == Some(event.id)) {
+                            log::info!("Pad {} disconnected", slot + 1);
+                            self.slots[slot] = None;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        fn id(&self, slot: usize) -> Option<GamepadId> {
+            self.slots.get(slot).copied().flatten()
+        }
+
+        fn state(&self, slot: usize) -> Option<GamepadState> {
+            let pad = self.gilrs.connected_gamepad(self.id(slot)?)?;
+            let mut state = GamepadState::default();
+            // gilrs has +y up; the game's screen has +y down
+            state.left_stick = Vec2::new(pad.value(Axis::LeftStickX), -pad.value(Axis::LeftStickY));
+            state.right_trigger = pad.button_data(Button::RightTrigger2).map_or(0.0, |data| data.value());
+            for (button, game_button) in BUTTONS {
+                if pad.is_pressed(button) {
+                    state = state.with_button(game_button);
+                }
+            }
+            Some(state)
+        }
+    }
+
+    /// Controller input through the shared context
+    pub struct PadInput(pub Shared);
+
+    impl GamepadSource for PadInput {
+        fn update(&mut self) {
+            self.0.borrow_mut().pump();
+        }
+
+        fn read(&mut self, slot: usize) -> Option<GamepadState> {
+            self.0.borrow().state(slot)
+        }
+    }
+
+    /// Both motors of one pad as looping effects; strength is set through their gain
+    struct Motors {
+        pad: GamepadId,
+        heavy: Effect,
+        light: Effect,
+        playing: bool,
+    }
+
+    /// Rumble for whichever pad is in a slot (rebuilt when a different pad takes the slot)
+    pub struct PadRumble {
+        backend: Shared,
+        slot: usize,
+        motors: Option<Motors>,
+    }
+
+    impl PadRumble {
+        pub fn new(backend: Shared, slot: usize) -> Self {
+            PadRumble { backend, slot, motors: None }
+        }
+
+        fn looping_effect(gilrs: &mut Gilrs, pad: GamepadId, kind: BaseEffectType) -> Option<Effect> {
+            let scheduling = Replay { play_for: Ticks::from_ms(RUMBLE_LOOP_MS), ..Default::default() };
+            EffectBuilder::new()
+                .add_effect(BaseEffect { kind, scheduling, ..Default::default() })
+                .gamepads(&[pad])
+                .gain(0.0)
+                .finish(gilrs)
+                .map_err(|e| log::warn!("Couldn't set up rumble on pad {:?}: {}", pad, e))
+                .ok()
+        }
+
+        /// The motors of the pad in the slot now, if it can rumble
+        fn motors(&mut self) -> Option<&mut Motors> {
+            let mut backend = self.backend.borrow_mut();
+            let pad = backend.id(self.slot)?;
+            if self.motors.as_ref().is_none_or(|motors| motors.pad != pad) {
+                let heavy = Self::looping_effect(&mut backend.gilrs, pad, BaseEffectType::Strong { magnitude: u16::MAX });
+                let light = Self::looping_effect(&mut backend.gilrs, pad, BaseEffectType::Weak { magnitude: u16::MAX });
+                self.motors = heavy.zip(light).map(|(heavy, light)| Motors { pad, heavy, light, playing: false });
+            }
+            self.motors.as_mut()
+        }
+    }
+
+    impl RumbleDevice for PadRumble {
+        fn is_connected(&self) -> bool {
+            let backend = self.backend.borrow();
+            backend
+                .id(self.slot)
+                .and_then(|id| backend.gilrs.connected_gamepad(id))
+                .is_some_and(|pad| pad.is_ff_supported())
+        }
+
+        fn set_rumble(&mut self, low: f32, high: f32) {
+            let Some(motors) = self.motors() else {
+                return;
+            };
+            let result = if low <= 0.0 && high <= 0.0 {
+                motors.playing = false;
+                motors.heavy.stop().and(motors.light.stop())
+            } else {
+                let mut result = motors.heavy.set_gain(low).and(motors.light.set_gain(high));
+                if !motors.playing {
+                    motors.playing = true;
+                    result = result.and(motors.heavy.play()).and(motors.light.play());
+                }
+                result
+            };
+            if let Err(e) = result {
+                log::warn!("Rumble failed on pad {}: {}", self.slot + 1, e);
+            }
+        }
+    }
+}
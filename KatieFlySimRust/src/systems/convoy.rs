@@ -0,0 +1,410 @@
+// Convoy - Uncrewed tanker rockets flying repeating refuel runs
+// Load up on a planet's surface, climb to orbit, hand the fuel to a satellite, land again and repeat
+
+use macroquad::prelude::*;
+
+use crate::entities::{Planet, Rocket};
+use crate::game_constants::GameConstants;
+use crate::systems::{EntityId, World};
+
+/// Fuel a tanker keeps for the trip home instead of delivering it
+pub const TANKER_RETURN_RESERVE: f32 = 40.0;
+/// Seconds between refuel pulls while loading (a tanker leaves once planet refueling stops topping it up)
+const LOAD_INTERVAL: f32 = 1.0;
+/// Altitude the climb reaches before heading for the satellite, and the lowest phasing orbit
+const ASCENT_CLEARANCE: f32 = 300.0;
+/// Closing speed cap relative to whatever the tanker is flying to
+const CRUISE_SPEED: f32 = 400.0;
+/// Deceleration the approach profile plans for (well under a tanker's thrust)
+const BRAKING_ACCEL: f32 = 100.0;
+/// Closer than this the tanker flies straight at the satellite instead of following orbits
+const RENDEZVOUS_RANGE: f32 = 1500.0;
+/// Within this range and speed of the satellite the fuel starts flowing
+const DOCKING_RANGE: f32 = 60.0;
+/// Where the tanker holds station, clear of the satellite's hull
+const DOCKING_STANDOFF: f32 = 35.0;
+const DOCKING_SPEED: f32 = 15.0;
+/// How hard velocity errors are corrected (1/seconds)
+const STEERING_GAIN: f32 = 2.0;
+/// Climb or sink rate toward the wanted orbit radius, per unit of radius error, and its cap
+const RADIAL_GAIN: f32 = 0.5;
+const MAX_RADIAL_SPEED: f32 = 150.0;
+/// Phase angle (radians) over which the phasing orbit rises back to the satellite's
+const PHASING_RAMP: f32 = 0.5;
+
+/// Where a tanker is in its run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TankerLeg {
+    Loading,   // Landed on the source planet, filling up
+    Ascending, // Climbing clear of the surface
+    Transit,   // Phasing around to the satellite, then closing in
+    Unloading, // Alongside the satellite, pumping fuel across
+    Returning, // Descending to the source planet
+}
+
+impl TankerLeg {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TankerLeg::Loading => "Loading",
+            TankerLeg::Ascending => "Ascending",
+            TankerLeg::Transit => "In transit",
+            TankerLeg::Unloading => "Unloading",
+            TankerLeg::Returning => "Returning",
+        }
+    }
+}
+
+/// One tanker and the run it repeats
+#[derive(Debug, Clone, PartialEq)]
+pub struct TankerRoute {
+    pub tanker: EntityId,
+    pub source: EntityId, // Planet it loads on
+    pub target: EntityId, // Satellite it delivers to
+    pub leg: TankerLeg,
+    pub trips: u32,
+    pub delivered: f32,
+    load_timer: f32,
+}
+
+/// Something the player should hear about
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConvoyEvent {
+    Delivered { tanker: EntityId, target: EntityId, amount: f32 },
+    /// Out of fuel away from the pad; the route is dropped
+    Stranded { tanker: EntityId },
+    /// The tanker, its planet or its satellite is gone; the route is dropped
+    RouteLost { tanker: EntityId },
+}
+
+/// Rotation and throttle that bring `rocket` to `desired_velocity`
+/// `unbalanced_accel` is whatever acts on the rocket that the desired path doesn't already account for
+pub fn steer_velocity(rocket: &Rocket, desired_velocity: Vec2, unbalanced_accel: Vec2) -> (f32, f32) {
+    let wanted_accel = (desired_velocity - rocket.velocity()) * STEERING_GAIN - unbalanced_accel;
+    let thrust_accel = GameConstants::ENGINE_THRUST_POWER / rocket.mass().max(0.001);
+    let throttle = (wanted_accel.length() / thrust_accel).min(1.0);
+    // No free thrust under the fuel threshold: either burn properly or coast
+    let throttle = if throttle < GameConstants::FUEL_CONSUMPTION_MIN_THRESHOLD / 2.0 {
+        0.0
+    } else {
+        throttle.max(GameConstants::FUEL_CONSUMPTION_MIN_THRESHOLD)
+    };
+    let rotation = if wanted_accel == Vec2::ZERO { rocket.rotation() } else { wanted_accel.x.atan2(-wanted_accel.y) };
+    (rotation, throttle)
+}
+
+/// Fly straight at `target_pos`, arriving at `target_velocity`; closing speed tapers so the tanker can still brake
+/// `relative_gravity` is the pull the rocket feels that the target doesn't
+pub fn steer(rocket: &Rocket, target_pos: Vec2, target_velocity: Vec2, max_speed: f32, relative_gravity: Vec2) -> (f32, f32) {
+    let offset = target_pos - rocket.position();
+    let closing_speed = (2.0 * BRAKING_ACCEL * offset.length()).sqrt().min(max_speed);
+    steer_velocity(rocket, target_velocity + offset.normalize_or_zero() * closing_speed, relative_gravity)
+}
+
+/// Gravitational acceleration from every planet at `position`
+fn gravity_at(world: &World, position: Vec2) -> Vec2 {
+    world
+        .planets()
+        .map(|planet| {
+            let offset = planet.position() - position;
+            let distance = offset.length().max(planet.radius());
+            offset * (GameConstants::G * planet.mass() / distance.powi(3))
+        })
+        .sum()
+}
+
+/// Follow a circular orbit at the current radius while climbing or sinking toward `radius`
+/// `prograde` is +1 for counter-clockwise (in world coordinates), -1 for clockwise
+fn steer_orbit(rocket: &Rocket, planet: &Planet, radius: f32, prograde: f32, gravity: Vec2) -> (f32, f32) {
+    let offset = rocket.position() - planet.position();
+    let r = offset.length().max(1.0);
+    let up = offset / r;
+    let along = up.perp() * prograde;
+    let circular_speed = (GameConstants::G * planet.mass() / r).sqrt();
+    let climb = ((radius - r) * RADIAL_GAIN).clamp(-MAX_RADIAL_SPEED, MAX_RADIAL_SPEED);
+    let desired = planet.velocity() + along * circular_speed + up * climb;
+
+    // The curve of the rocket's actual path takes care of that much of gravity on its own
+    let tangential_speed = (rocket.velocity() - planet.velocity()).dot(along);
+    let path_accel = -up * (tangential_speed * tangential_speed / r);
+    steer_velocity(rocket, desired, gravity - path_accel)
+}
+
+/// Every tanker route in the session
+#[derive(Debug, Clone, Default)]
+pub struct Convoy {
+    routes: Vec<TankerRoute>,
+}
+
+impl Convoy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn routes(&self) -> &[TankerRoute] {
+        &self.routes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    pub fn is_tanker(&self, rocket_id: EntityId) -> bool {
+        self.routes.iter().any(|route| route.tanker == rocket_id)
+    }
+
+    /// Put a rocket on a run between `source` and `target` (replacing any run it already had)
+    pub fn assign(&mut self, world: &mut World, tanker: EntityId, source: EntityId, target: EntityId) -> Result<(), String> {
+        let rocket = world.get_rocket(tanker).ok_or("No rocket to make a tanker")?;
+        world.get_planet(source).ok_or("No planet to load from")?;
+        world.get_satellite(target).ok_or("Target a satellite to deliver to")?;
+
+        let leg = if rocket.landed_on_planet_id() == Some(source) { TankerLeg::Loading } else { TankerLeg::Returning };
+        self.release(world, tanker);
+        if let Some(rocket) = world.get_rocket_mut(tanker) {
+            rocket.start_fuel_transfer_out(GameConstants::MANUAL_FUEL_TRANSFER_RATE);
+        }
+        self.routes.push(TankerRoute { tanker, source, target, leg, trips: 0, delivered: 0.0, load_timer: 0.0 });
+        Ok(())
+    }
+
+    /// Take a tanker off its run (it coasts from here); returns its route
+    pub fn release(&mut self, world: &mut World, tanker: EntityId) -> Option<TankerRoute> {
+        let index = self.routes.iter().position(|route| route.tanker == tanker)?;
+        Self::stand_down(world, tanker);
+        Some(self.routes.remove(index))
+    }
+
+    /// Engine off and back to an ordinary rocket satellites will refuel
+    fn stand_down(world: &mut World, tanker: EntityId) {
+        if let Some(rocket) = world.get_rocket_mut(tanker) {
+            rocket.set_thrust_level(0.0);
+            rocket.stop_fuel_transfer();
+        }
+    }
+
+    /// Fly every tanker one tick (before `World::update`)
+    pub fn update(&mut self, world: &mut World, delta_time: f32) -> Vec<ConvoyEvent> {
+        let mut events = Vec::new();
+        self.routes.retain_mut(|route| {
+            let alive = world.get_rocket(route.tanker).is_some()
+                && world.get_planet(route.source).is_some()
+                && world.get_satellite(route.target).is_some();
+            if !alive {
+                Self::stand_down(world, route.tanker);
+                events.push(ConvoyEvent::RouteLost { tanker: route.tanker });
+                return false;
+            }
+            match Self::fly(route, world, delta_time) {
+                Some(event @ ConvoyEvent::Delivered { .. }) => {
+                    events.push(event);
+                    true
+                }
+                Some(event) => {
+                    Self::stand_down(world, route.tanker);
+                    events.push(event);
+                    false
+                }
+                None => true,
+            }
+        });
+        events
+    }
+
+    fn fly(route: &mut TankerRoute, world: &mut World, delta_time: f32) -> Option<ConvoyEvent> {
+        let rocket = world.get_rocket(route.tanker)?;
+        let planet = world.get_planet(route.source)?;
+        let satellite = world.get_satellite(route.target)?;
+        let position = rocket.position();
+        let altitude = position.distance(planet.position()) - planet.surface_radius_toward(position);
+        let landed_home = rocket.landed_on_planet_id() == Some(route.source);
+        if !rocket.is_landed() && !rocket.can_thrust() {
+            return Some(ConvoyEvent::Stranded { tanker: route.tanker });
+        }
+
+        // Orbit the way the satellite does
+        let satellite_offset = satellite.position() - planet.position();
+        let prograde = if satellite_offset.perp_dot(satellite.velocity() - planet.velocity()) >= 0.0 { 1.0 } else { -1.0 };
+        let parking_radius = planet.radius() + ASCENT_CLEARANCE;
+        let gravity = gravity_at(world, position);
+
+        let mut event = None;
+        let command = match route.leg {
+            TankerLeg::Loading => {
+                if !landed_home {
+                    route.leg = TankerLeg::Returning;
+                    return None;
+                }
+                route.load_timer -= delta_time;
+                if route.load_timer <= 0.0 {
+                    route.load_timer = LOAD_INTERVAL;
+                    let before = rocket.current_fuel();
+                    world.handle_manual_planet_refuel(route.tanker, delta_time);
+                    let loaded = world.get_rocket(route.tanker).map_or(before, |rocket| rocket.current_fuel());
+                    // Full, and the satellite has room for at least half the load
+                    let room = world.get_satellite(route.target).map_or(0.0, |s| s.max_fuel() - s.current_fuel());
+                    if loaded <= before && loaded > TANKER_RETURN_RESERVE && room >= (loaded - TANKER_RETURN_RESERVE) / 2.0 {
+                        route.leg = TankerLeg::Ascending;
+                    }
+                }
+                None
+            }
+            TankerLeg::Ascending => {
+                if altitude >= ASCENT_CLEARANCE {
+                    route.leg = TankerLeg::Transit;
+                }
+                if altitude < ASCENT_CLEARANCE / 2.0 {
+                    // Straight up first so the burn for orbital speed doesn't scrape the surface
+                    let up = (position - planet.position()).normalize_or_zero();
+                    Some(steer(rocket, position + up * ASCENT_CLEARANCE, planet.velocity(), CRUISE_SPEED, gravity))
+                } else {
+                    Some(steer_orbit(rocket, planet, parking_radius + ASCENT_CLEARANCE, prograde, gravity))
+                }
+            }
+            TankerLeg::Transit | TankerLeg::Unloading => {
+                if rocket.is_landed() {
+                    route.leg = TankerLeg::Ascending; // Clipped the surface
+                    return None;
+                }
+                let distance = position.distance(satellite.position());
+                if distance > RENDEZVOUS_RANGE {
+                    // Phasing: a lower orbit is faster, so sink while the satellite is far ahead and rise as it's caught
+                    let offset = position - planet.position();
+                    let phase = (prograde * offset.angle_between(satellite_offset)).rem_euclid(std::f32::consts::TAU);
+                    let target_radius = satellite_offset.length();
+                    let depth = (target_radius - parking_radius).max(0.0) * (phase / PHASING_RAMP).min(1.0);
+                    Some(steer_orbit(rocket, planet, target_radius - depth, prograde, gravity))
+                } else {
+                    let (satellite_pos, satellite_vel) = (satellite.position(), satellite.velocity());
+                    let relative_gravity = gravity - gravity_at(world, satellite_pos);
+                    if distance <= DOCKING_RANGE && rocket.velocity().distance(satellite_vel) <= DOCKING_SPEED {
+                        route.leg = TankerLeg::Unloading;
+                        let spare = rocket.current_fuel() - TANKER_RETURN_RESERVE;
+                        let amount = (GameConstants::MANUAL_FUEL_TRANSFER_RATE * delta_time).min(spare);
+                        let moved = if amount > 0.0 {
+                            world.transfer_fuel_rocket_to_satellite(route.tanker, route.target, amount)
+                        } else {
+                            0.0
+                        };
+                        route.delivered += moved;
+                        if moved < amount || amount <= 0.0 {
+                            // Tank at reserve or satellite full: head home
+                            route.trips += 1;
+                            route.leg = TankerLeg::Returning;
+                            event = Some(ConvoyEvent::Delivered { tanker: route.tanker, target: route.target, amount: route.delivered });
+                            route.delivered = 0.0;
+                        }
+                    } else if route.leg == TankerLeg::Unloading {
+                        route.leg = TankerLeg::Transit; // Drifted off the satellite
+                    }
+                    let rocket = world.get_rocket(route.tanker)?;
+                    let station = satellite_pos + (position - satellite_pos).normalize_or_zero() * DOCKING_STANDOFF;
+                    Some(steer(rocket, station, satellite_vel, CRUISE_SPEED, relative_gravity))
+                }
+            }
+            TankerLeg::Returning => {
+                if landed_home {
+                    route.leg = TankerLeg::Loading;
+                    route.load_timer = 0.0;
+                    None
+                } else if rocket.is_landed() {
+                    // Came down somewhere else: hop back up and try again
+                    route.leg = TankerLeg::Ascending;
+                    None
+                } else {
+                    // Straight down to the surface below; the approach profile does the braking
+                    let up = (position - planet.position()).normalize_or_zero();
+                    let below = planet.position() + up * planet.surface_radius_toward(position);
+                    Some(steer(rocket, below, planet.velocity(), CRUISE_SPEED, gravity))
+                }
+            }
+        };
+
+        if let Some(rocket) = world.get_rocket_mut(route.tanker) {
+            match command {
+                Some((rotation, throttle)) => {
+                    rocket.set_rotation(rotation);
+                    rocket.set_thrust_level(throttle);
+                }
+                None => rocket.set_thrust_level(0.0),
+            }
+        }
+        event
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Planet, Satellite};
+
+    #[test]
+    fn test_steer_points_along_the_correction() {
+        let mut rocket = Rocket::new(Vec2::ZERO, Vec2::ZERO, WHITE, 1.0);
+        rocket.set_fuel(rocket.max_fuel());
+        // Target straight up the screen (negative y), no gravity: nose up, some throttle
+        let (rotation, throttle) = steer(&rocket, Vec2::new(0.0, -1000.0), Vec2::ZERO, CRUISE_SPEED, Vec2::ZERO);
+        assert!(rotation.abs() < 1e-4 || (rotation - std::f32::consts::TAU).abs() < 1e-4);
+        assert!(throttle >= GameConstants::FUEL_CONSUMPTION_MIN_THRESHOLD);
+
+        // Already there and matched: coast
+        let (_, throttle) = steer(&rocket, Vec2::ZERO, Vec2::ZERO, CRUISE_SPEED, Vec2::ZERO);
+        assert_eq!(throttle, 0.0);
+    }
+
+    #[test]
+    fn test_circular_orbit_needs_no_thrust() {
+        let planet = Planet::new(Vec2::ZERO, 1000.0, 10000.0, BLUE);
+        let speed = (GameConstants::G * 10000.0 / 2000.0_f32).sqrt();
+        let mut rocket = Rocket::new(Vec2::new(2000.0, 0.0), Vec2::new(0.0, speed), WHITE, 1.0);
+        rocket.set_fuel(rocket.max_fuel());
+        let gravity = Vec2::new(-GameConstants::G * 10000.0 / (2000.0 * 2000.0), 0.0);
+        let (_, throttle) = steer_orbit(&rocket, &planet, 2000.0, 1.0, gravity);
+        assert_eq!(throttle, 0.0);
+
+        // Wanting a higher orbit means burning, with the nose pointing partly outward
+        let (rotation, throttle) = steer_orbit(&rocket, &planet, 3000.0, 1.0, gravity);
+        assert!(throttle > 0.0);
+        assert!(Vec2::new(rotation.sin(), -rotation.cos()).x > 0.0);
+    }
+
+    #[test]
+    fn test_tanker_unloads_down_to_its_reserve_then_heads_home() {
+        let mut world = World::new();
+        let planet = world.add_planet(Planet::new(Vec2::ZERO, 1000.0, 10000.0, BLUE));
+        let satellite = world.add_satellite(Satellite::new(Vec2::new(3000.0, 0.0), Vec2::ZERO, GREEN));
+        let mut rocket = Rocket::new(Vec2::new(3020.0, 0.0), Vec2::ZERO, WHITE, 1.0);
+        rocket.set_fuel(TANKER_RETURN_RESERVE + 5.0);
+        let tanker = world.add_rocket(rocket);
+
+        let mut convoy = Convoy::new();
+        convoy.assign(&mut world, tanker, planet, satellite).unwrap();
+        assert_eq!(convoy.routes()[0].leg, TankerLeg::Returning);
+        convoy.routes[0].leg = TankerLeg::Transit;
+
+        let mut delivered = None;
+        for _ in 0..200 {
+            if let Some(event) = convoy.update(&mut world, 0.1).pop() {
+                delivered = Some(event);
+                break;
+            }
+        }
+        assert!(matches!(delivered, Some(ConvoyEvent::Delivered { amount, .. }) if (amount - 5.0).abs() < 0.01));
+        assert_eq!(convoy.routes()[0].leg, TankerLeg::Returning);
+        assert_eq!(convoy.routes()[0].trips, 1);
+        assert!((world.get_rocket(tanker).unwrap().current_fuel() - TANKER_RETURN_RESERVE).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_route_dropped_when_satellite_goes() {
+        let mut world = World::new();
+        let planet = world.add_planet(Planet::new(Vec2::ZERO, 1000.0, 10000.0, BLUE));
+        let satellite = world.add_satellite(Satellite::new(Vec2::new(3000.0, 0.0), Vec2::ZERO, GREEN));
+        let tanker = world.add_rocket(Rocket::new(Vec2::new(1500.0, 0.0), Vec2::ZERO, WHITE, 1.0));
+        let mut convoy = Convoy::new();
+        convoy.assign(&mut world, tanker, planet, satellite).unwrap();
+
+        world.remove_satellite(satellite);
+        assert_eq!(convoy.update(&mut world, 0.1), vec![ConvoyEvent::RouteLost { tanker }]);
+        assert!(convoy.is_empty());
+    }
+}
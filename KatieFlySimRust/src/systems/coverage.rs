@@ -0,0 +1,178 @@
+// Coverage - Fuel-transfer coverage of the orbital shells around a planet
+// A polar grid of altitude shells and sectors; each cell counts the satellites with spare fuel in range of its center
+
+use std::f32::consts::TAU;
+
+use macroquad::prelude::Vec2;
+
+use crate::systems::{EntityId, World};
+
+/// Cells are about this fraction of a satellite's transfer range across, so one in range is rarely missed
+const CELL_FRACTION: f32 = 0.75;
+/// Grid limits, so huge planets don't make a huge grid
+const MAX_SHELLS: usize = 24;
+const MIN_SECTORS: usize = 24;
+const MAX_SECTORS: usize = 720;
+/// Seconds a computed map stays fresh while satellites move
+const REFRESH_INTERVAL: f32 = 1.0;
+
+/// Coverage of the space around one planet, relative to its center
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageMap {
+    pub planet: EntityId,
+    pub inner_radius: f32, // Bottom of the first shell (the surface)
+    pub shell_width: f32,
+    pub shells: usize,
+    pub sectors: usize,
+    counts: Vec<u8>, // shells * sectors, saturating
+}
+
+impl CoverageMap {
+    /// Shells from the surface up past the highest satellite orbiting `planet_id`
+    pub fn compute(world: &World, planet_id: EntityId) -> Option<Self> {
+        let planet = world.get_planet(planet_id)?;
+        let center = planet.position();
+        let inner_radius = planet.radius();
+
+        // Satellites that can hand out fuel, and the ones whose nearest planet this is
+        let suppliers: Vec<(Vec2, f32)> = world
+            .satellites()
+            .filter(|s| s.current_fuel() > s.maintenance_fuel_reserve() && !s.is_end_of_life())
+            .map(|s| (s.position() - center, s.transfer_range()))
+            .collect();
+        let altitude_of = |position: Vec2| -> Option<f32> {
+            let (nearest, altitude) = world
+                .planets_with_ids()
+                .map(|(id, p)| (id, (position - p.position()).length() - p.radius()))
+                .min_by(|a, b| a.1.total_cmp(&b.1))?;
+            (nearest == planet_id).then_some(altitude)
+        };
+        let highest = world.satellites().filter_map(|s| altitude_of(s.position())).fold(0.0_f32, f32::max);
+
+        let typical_range = world.satellites().next().map_or(500.0, |s| s.transfer_range());
+        let cell = typical_range * CELL_FRACTION;
+        let depth = (highest + typical_range).max(inner_radius);
+        let shells = ((depth / cell).ceil() as usize).clamp(1, MAX_SHELLS);
+        let shell_width = depth / shells as f32;
+        let sectors = ((TAU * (inner_radius + depth) / cell).ceil() as usize).clamp(MIN_SECTORS, MAX_SECTORS);
+
+        let mut counts = vec![0u8; shells * sectors];
+        for shell in 0..shells {
+            let radius = inner_radius + (shell as f32 + 0.5) * shell_width;
+            for sector in 0..sectors {
+                let point = Vec2::from_angle((sector as f32 + 0.5) / sectors as f32 * TAU) * radius;
+                let in_range = suppliers.iter().filter(|(position, range)| position.distance(point) <= *range).count();
+                counts[shell * sectors + sector] = in_range.min(u8::MAX as usize) as u8;
+            }
+        }
+
+        Some(CoverageMap { planet: planet_id, inner_radius, shell_width, shells, sectors, counts })
+    }
+
+    /// Satellites covering one cell
+    pub fn count(&self, shell: usize, sector: usize) -> u8 {
+        self.counts[shell * self.sectors + sector]
+    }
+
+    /// Inner and outer radius, start and end angle of a cell
+    pub fn cell_bounds(&self, shell: usize, sector: usize) -> (f32, f32, f32, f32) {
+        let r0 = self.inner_radius + shell as f32 * self.shell_width;
+        let step = TAU / self.sectors as f32;
+        (r0, r0 + self.shell_width, sector as f32 * step, (sector + 1) as f32 * step)
+    }
+
+    /// Fraction of one shell's cells with at least one satellite in range
+    pub fn shell_coverage(&self, shell: usize) -> f32 {
+        let row = &self.counts[shell * self.sectors..(shell + 1) * self.sectors];
+        row.iter().filter(|&&count| count > 0).count() as f32 / self.sectors as f32
+    }
+
+    /// Fraction of all cells with at least one satellite in range
+    pub fn coverage(&self) -> f32 {
+        self.counts.iter().filter(|&&count| count > 0).count() as f32 / self.counts.len() as f32
+    }
+
+    /// The least covered shell (lowest first on ties) and its coverage
+    pub fn worst_shell(&self) -> (usize, f32) {
+        (0..self.shells)
+            .map(|shell| (shell, self.shell_coverage(shell)))
+            .fold((0, f32::MAX), |worst, shell| if shell.1 < worst.1 { shell } else { worst })
+    }
+}
+
+/// The last coverage map, recomputed when it goes stale or the planet changes
+#[derive(Debug, Clone, Default)]
+pub struct CoverageCache {
+    map: Option<CoverageMap>,
+    age: f32,
+}
+
+impl CoverageCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop the cached map (the overlay was closed or the world was replaced)
+    pub fn clear(&mut self) {
+        self.map = None;
+    }
+
+    /// Coverage around `planet_id`, computed only when needed
+    pub fn get(&mut self, world: &World, planet_id: EntityId, delta_time: f32) -> Option<&CoverageMap> {
+        self.age += delta_time;
+        let stale = self.age >= REFRESH_INTERVAL || self.map.as_ref().is_none_or(|map| map.planet != planet_id);
+        if stale {
+            self.map = CoverageMap::compute(world, planet_id);
+            self.age = 0.0;
+        }
+        self.map.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Planet, Satellite};
+    use macroquad::prelude::BLUE;
+
+    fn world_with_satellite(fuel: f32) -> (World, EntityId) {
+        let mut world = World::new();
+        let planet = world.add_planet(Planet::new(Vec2::ZERO, 1000.0, 10000.0, BLUE));
+        world.add_satellite(Satellite::from_rocket(Vec2::new(1500.0, 0.0), Vec2::ZERO, fuel));
+        (world, planet)
+    }
+
+    #[test]
+    fn test_cells_near_a_fuelled_satellite_are_covered() {
+        let (world, planet) = world_with_satellite(100.0);
+        let map = CoverageMap::compute(&world, planet).unwrap();
+        assert!(map.shells > 1 && map.sectors >= MIN_SECTORS);
+
+        // The satellite sits at angle 0, 500 up; the far side of the planet is a gap
+        let shell = ((500.0 / map.shell_width) as usize).min(map.shells - 1);
+        assert_eq!(map.count(shell, 0), 1);
+        assert_eq!(map.count(shell, map.sectors / 2), 0);
+        assert!(map.coverage() > 0.0 && map.coverage() < 0.5);
+        assert!(map.worst_shell().1 <= map.shell_coverage(shell));
+    }
+
+    #[test]
+    fn test_empty_satellites_cover_nothing() {
+        let (world, planet) = world_with_satellite(0.0);
+        assert_eq!(CoverageMap::compute(&world, planet).unwrap().coverage(), 0.0);
+    }
+
+    #[test]
+    fn test_cache_recomputes_only_when_stale() {
+        let (mut world, planet) = world_with_satellite(100.0);
+        let mut cache = CoverageCache::new();
+        let before = cache.get(&world, planet, 0.0).unwrap().coverage();
+
+        let ids: Vec<EntityId> = world.satellites_with_ids().map(|(id, _)| id).collect();
+        for id in ids {
+            world.remove_satellite(id);
+        }
+        assert_eq!(cache.get(&world, planet, 0.1).unwrap().coverage(), before); // Still cached
+        assert_eq!(cache.get(&world, planet, REFRESH_INTERVAL).unwrap().coverage(), 0.0);
+    }
+}
@@ -0,0 +1,145 @@
+// Crew - Co-op rocket crews for online multiplayer
+// A pilot flies the rocket (attitude/thrust); an engineer aboard runs fuel, satellites and weapons;
+// a spectator only watches (snapshots, but no rocket and no say in anything)
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Role a client asks for in the join lobby
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CrewRole {
+    /// Own rocket, full control
+    #[default]
+    Pilot,
+    /// No rocket of their own; crews the rocket flown by `pilot_id`
+    Engineer { pilot_id: u32 },
+    /// No rocket and no controls; a free camera that can follow any player
+    Spectator,
+}
+
+impl CrewRole {
+    pub fn label(&self) -> String {
+        match self {
+            CrewRole::Pilot => "Pilot (own rocket)".to_string(),
+            CrewRole::Engineer { pilot_id: 0 } => "Engineer for the host".to_string(),
+            CrewRole::Engineer { pilot_id } => format!("Engineer for player {}", pilot_id),
+            CrewRole::Spectator => "Spectator (watch only)".to_string(),
+        }
+    }
+}
+
+/// Host-side record of which engineers crew which pilot's rocket
+#[derive(Debug, Clone, Default)]
+pub struct CrewAssignments {
+    pilots: HashMap<u32, u32>, // Engineer player ID -> pilot player ID
+}
+
+impl CrewAssignments {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Put an engineer aboard a pilot's rocket; engineers can't crew themselves or other engineers
+    pub fn assign(&mut self, engineer_id: u32, pilot_id: u32) -> bool {
+        if engineer_id == pilot_id || self.is_engineer(pilot_id) || self.has_engineer(engineer_id) {
+            return false;
+        }
+        self.pilots.insert(engineer_id, pilot_id);
+        true
+    }
+
+    /// Pilot whose rocket this engineer crews
+    pub fn pilot_for(&self, engineer_id: u32) -> Option<u32> {
+        self.pilots.get(&engineer_id).copied()
+    }
+
+    /// True if anyone crews this pilot's rocket
+    pub fn has_engineer(&self, pilot_id: u32) -> bool {
+        self.pilots.values().any(|pilot| *pilot == pilot_id)
+    }
+
+    pub fn is_engineer(&self, player_id: u32) -> bool {
+        self.pilot_for(player_id).is_some()
+    }
+
+    /// Player whose rocket this player's inputs act on
+    pub fn rocket_owner(&self, player_id: u32) -> u32 {
+        self.pilot_for(player_id).unwrap_or(player_id)
+    }
+
+    /// Forget a player who left, along with any engineers crewing for them; returns those engineers
+    pub fn remove_player(&mut self, player_id: u32) -> Vec<u32> {
+        self.pilots.remove(&player_id);
+        let mut stranded: Vec<u32> = self.pilots.iter().filter(|(_, pilot)| **pilot == player_id).map(|(engineer, _)| *engineer).collect();
+        stranded.sort_unstable();
+        for engineer in &stranded {
+            self.pilots.remove(engineer);
+        }
+        stranded
+    }
+
+    /// Attitude and thrust belong to the pilot only
+    pub fn controls_flight(&self, player_id: u32) -> bool {
+        !self.is_engineer(player_id)
+    }
+
+    /// Conversion, weapons and refueling belong to the engineer when the rocket has one
+    pub fn controls_systems(&self, player_id: u32) -> bool {
+        self.is_engineer(player_id) || !self.has_engineer(player_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engineer_takes_over_systems() {
+        let mut crew = CrewAssignments::new();
+        assert!(crew.controls_flight(0) && crew.controls_systems(0));
+
+        assert!(crew.assign(2, 0));
+        assert_eq!(crew.rocket_owner(2), 0);
+        assert!(crew.controls_flight(0) && !crew.controls_systems(0));
+        assert!(!crew.controls_flight(2) && crew.controls_systems(2));
+
+        // Other pilots are unaffected
+        assert!(crew.controls_flight(1) && crew.controls_systems(1));
+    }
+
+    #[test]
+    fn test_invalid_assignments() {
+        let mut crew = CrewAssignments::new();
+        assert!(crew.assign(2, 0));
+        assert!(crew.assign(3, 0)); // A second engineer on the same rocket is fine
+        assert!(!crew.assign(4, 2)); // Engineers have no rocket to crew
+        assert!(!crew.assign(0, 1)); // Pilots with crew can't leave to crew elsewhere
+        assert!(!crew.assign(5, 5));
+        assert_eq!(crew.pilot_for(3), Some(0));
+    }
+
+    #[test]
+    fn test_removing_a_pilot_strands_their_engineers() {
+        let mut crew = CrewAssignments::new();
+        assert!(crew.assign(3, 0));
+        assert!(crew.assign(2, 0));
+        assert!(crew.assign(4, 1));
+
+        assert_eq!(crew.remove_player(0), vec![2, 3]);
+        assert!(!crew.is_engineer(2) && !crew.is_engineer(3));
+        assert_eq!(crew.remove_player(4), Vec::<u32>::new());
+        assert!(!crew.has_engineer(1));
+    }
+
+    #[test]
+    fn test_spectator_role_keeps_older_roles_on_the_wire() {
+        // Join packets carry the role as bincode; adding Spectator must not renumber the others
+        let pilot = bincode::serialize(&CrewRole::Pilot).unwrap();
+        let engineer = bincode::serialize(&CrewRole::Engineer { pilot_id: 3 }).unwrap();
+        assert_eq!(pilot, 0u32.to_le_bytes());
+        assert_eq!(&engineer[..4], 1u32.to_le_bytes());
+
+        let spectator = bincode::serialize(&CrewRole::Spectator).unwrap();
+        assert_eq!(bincode::deserialize::<CrewRole>(&spectator).unwrap(), CrewRole::Spectator);
+    }
+}
@@ -0,0 +1,231 @@
+// Daily Challenge - A deterministic scenario generated from the date
+// Everyone playing on the same UTC day gets the same map layout, fuel and objective, so scores compare directly
+
+use crate::game_constants::GameConstants;
+use crate::map_config::MapConfiguration;
+use crate::systems::{LandingGrade, LandingResult};
+
+/// A calendar day (UTC)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChallengeDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl ChallengeDate {
+    /// Today in UTC
+    pub fn today() -> Self {
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Self::from_days_since_epoch((secs / 86_400) as i64)
+    }
+
+    /// Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+        ChallengeDate { year, month, day }
+    }
+
+    /// "2026-10-17"
+    pub fn label(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
+    fn seed(&self) -> u64 {
+        fnv1a(self.label().as_bytes())
+    }
+}
+
+/// What the day's attempt is scored on (every objective ends with a landing on the target body)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeObjective {
+    MostFuelLeft,
+    Fastest,
+    Softest,
+}
+
+impl ChallengeObjective {
+    const ALL: [ChallengeObjective; 3] = [ChallengeObjective::MostFuelLeft, ChallengeObjective::Fastest, ChallengeObjective::Softest];
+
+    pub fn describe(&self, target: &str) -> String {
+        match self {
+            ChallengeObjective::MostFuelLeft => format!("Land on {} with the most fuel remaining", target),
+            ChallengeObjective::Fastest => format!("Land on {} as fast as you can", target),
+            ChallengeObjective::Softest => format!("Make the softest landing on {}", target),
+        }
+    }
+}
+
+/// How a finished attempt went
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChallengeOutcome {
+    Scored { score: u32, share: String },
+    Failed(String),
+}
+
+/// The day's scenario
+#[derive(Debug, Clone)]
+pub struct DailyChallenge {
+    pub date: ChallengeDate,
+    pub objective: ChallengeObjective,
+    pub map: MapConfiguration,
+    pub target_body: String,
+    pub starting_fuel: f32,
+}
+
+impl DailyChallenge {
+    pub fn today() -> Self {
+        Self::for_date(ChallengeDate::today())
+    }
+
+    /// Build the scenario for `date`; the same date always gives the same scenario
+    pub fn for_date(date: ChallengeDate) -> Self {
+        let mut rng = SplitMix64(date.seed());
+        let maps = [MapConfiguration::earth_moon(), MapConfiguration::solar_1(), MapConfiguration::katie_1()];
+        let mut map = maps[rng.below(maps.len())].clone();
+
+        // Scatter the orbiting bodies around their parents
+        for body in &mut map.celestial_bodies {
+            if body.orbital_parent_index.is_some() && !body.is_pinned {
+                body.initial_angle = rng.unit() * GameConstants::PI * 2.0;
+            }
+        }
+        map.description = format!("Daily challenge {}", date.label());
+
+        let candidates: Vec<usize> = (0..map.celestial_bodies.len()).filter(|&i| i != map.player_spawn_body_index).collect();
+        let target_index = if candidates.is_empty() { map.player_spawn_body_index } else { candidates[rng.below(candidates.len())] };
+        let target_body = map.celestial_bodies[target_index].name.clone();
+
+        let objective = ChallengeObjective::ALL[rng.below(ChallengeObjective::ALL.len())];
+        // 40-100% of a full tank, in 5% steps
+        let starting_fuel = GameConstants::ROCKET_MAX_FUEL * (40 + 5 * rng.below(13)) as f32 / 100.0;
+
+        DailyChallenge { date, objective, map, target_body, starting_fuel }
+    }
+
+    pub fn description(&self) -> String {
+        self.objective.describe(&self.target_body)
+    }
+
+    /// Score a landing on the target after `elapsed` game seconds (None for a crash)
+    pub fn score(&self, landing: &LandingResult, fuel_left: f32, elapsed: f32) -> Option<u32> {
+        if landing.grade == LandingGrade::Crash {
+            return None;
+        }
+        let fuel_fraction = (fuel_left / self.starting_fuel.max(f32::EPSILON)).clamp(0.0, 1.0);
+        let landing_points = landing.score.clamp(0.0, 100.0);
+        let score = match self.objective {
+            ChallengeObjective::MostFuelLeft => fuel_fraction * 1000.0 + landing_points * 2.0,
+            ChallengeObjective::Fastest => 1000.0 * 600.0 / (elapsed.max(0.0) + 600.0) + landing_points * 2.0,
+            ChallengeObjective::Softest => landing_points * 10.0 + fuel_fraction * 100.0,
+        };
+        Some(score.round() as u32)
+    }
+
+    /// Shareable one-liner ending in a check code, e.g. "KatieFly Daily 2026-10-17 | Land on Moon ... | 842 pts | 05:12 | 3f9a"
+    pub fn score_string(&self, score: u32, elapsed: f32) -> String {
+        let secs = elapsed.max(0.0) as u32;
+        let body = format!(
+            "KatieFly Daily {} | {} | {} pts | {:02}:{:02}",
+            self.date.label(),
+            self.description(),
+            score,
+            secs / 60,
+            secs % 60
+        );
+        format!("{} | {:04x}", body, fnv1a(body.as_bytes()) & 0xffff)
+    }
+
+    /// Whether a shared score string's check code matches the rest of it
+    pub fn verify_score_string(text: &str) -> bool {
+        let Some((body, code)) = text.rsplit_once(" | ") else {
+            return false;
+        };
+        u64::from_str_radix(code, 16).ok() == Some(fnv1a(body.as_bytes()) & 0xffff)
+    }
+}
+
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// Small deterministic generator (same sequence on every platform)
+#[derive(Debug, Clone)]
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl SplitMix64 {
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    pub(crate) fn unit(&mut self) -> f32 {
+        (self.next() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macroquad::prelude::Vec2;
+
+    #[test]
+    fn test_dates_from_epoch_days() {
+        assert_eq!(ChallengeDate::from_days_since_epoch(0).label(), "1970-01-01");
+        assert_eq!(ChallengeDate::from_days_since_epoch(11_016).label(), "2000-02-29");
+        assert_eq!(ChallengeDate::from_days_since_epoch(20_743).label(), "2026-10-17");
+    }
+
+    #[test]
+    fn test_same_date_same_scenario() {
+        let date = ChallengeDate { year: 2026, month: 10, day: 17 };
+        let a = DailyChallenge::for_date(date);
+        let b = DailyChallenge::for_date(date);
+        assert_eq!(a.map.name, b.map.name);
+        assert_eq!(a.target_body, b.target_body);
+        assert_eq!(a.objective, b.objective);
+        assert_eq!(a.starting_fuel, b.starting_fuel);
+        let angles = |c: &DailyChallenge| c.map.celestial_bodies.iter().map(|body| body.initial_angle).collect::<Vec<_>>();
+        assert_eq!(angles(&a), angles(&b));
+
+        assert_ne!(a.target_body, a.map.get_spawn_body().name);
+        assert!(a.starting_fuel >= GameConstants::ROCKET_MAX_FUEL * 0.4 && a.starting_fuel <= GameConstants::ROCKET_MAX_FUEL);
+    }
+
+    #[test]
+    fn test_scoring_and_share_string() {
+        let mut challenge = DailyChallenge::for_date(ChallengeDate { year: 2026, month: 1, day: 2 });
+        challenge.objective = ChallengeObjective::MostFuelLeft;
+        let soft = LandingResult::evaluate(Vec2::new(0.0, -100.0), Vec2::new(0.0, 5.0), 0.0, Vec2::ZERO, Vec2::ZERO);
+        let crash = LandingResult::evaluate(Vec2::new(0.0, -100.0), Vec2::new(0.0, 500.0), 0.0, Vec2::ZERO, Vec2::ZERO);
+
+        let full = challenge.score(&soft, challenge.starting_fuel, 100.0).unwrap();
+        let half = challenge.score(&soft, challenge.starting_fuel / 2.0, 100.0).unwrap();
+        assert!(full > half);
+        assert_eq!(challenge.score(&crash, challenge.starting_fuel, 100.0), None);
+
+        let share = challenge.score_string(full, 312.0);
+        assert!(share.starts_with("KatieFly Daily 2026-01-02 | Land on"));
+        assert!(share.contains("05:12"));
+        assert!(DailyChallenge::verify_score_string(&share));
+        assert!(!DailyChallenge::verify_score_string(&share.replace(&format!("| {} pts", full), "| 9999 pts")));
+    }
+}
@@ -0,0 +1,301 @@
+// Debris - Wreckage left by destroyed rockets and satellites, and the tractor beam that clears it
+// Pieces coast under planet gravity until they hit a surface. A rocket's tractor beam (Shift+Z) grabs the
+// nearest piece and tows it on a short tether; letting go leaves it on the rocket's path, so a piece
+// released on a trajectory into a planet (or towed all the way down to a landing) counts as cleaned up
+
+use std::collections::BTreeMap;
+use std::f32::consts::TAU;
+
+use macroquad::prelude::*;
+
+use crate::entities::Planet;
+use crate::physics::GravitySimulator;
+use crate::systems::EntityId;
+
+/// How far the tractor beam reaches to grab a piece
+pub const TRACTOR_RANGE: f32 = 400.0;
+/// Tether length a towed piece trails at
+const TOW_LENGTH: f32 = 40.0;
+/// Pieces left by a destroyed rocket
+pub const ROCKET_DEBRIS_PIECES: usize = 4;
+/// Pieces left by a destroyed satellite
+pub const SATELLITE_DEBRIS_PIECES: usize = 3;
+/// Speed pieces fly apart at, on top of the wreck's velocity
+const SCATTER_SPEED: f32 = 15.0;
+/// Pieces are tracked as point masses (only their own motion matters)
+const PIECE_MASS: f32 = 1.0;
+const PIECE_SIZE: f32 = 4.0;
+/// Pieces tracked at once; the oldest go first
+const MAX_PIECES: usize = 200;
+
+/// One piece of wreckage
+#[derive(Debug, Clone, PartialEq)]
+pub struct Debris {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub towed_by: Option<EntityId>,    // Rocket holding it on the tractor beam
+    pub released_by: Option<EntityId>, // Last rocket to let go of it (credited if it comes down)
+}
+
+/// A piece brought down by a rocket's tractor beam
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DebrisCleared {
+    pub rocket_id: EntityId,
+    pub planet_id: EntityId,
+}
+
+/// Every piece of wreckage in the world
+/// Off unless the game mode enables it: pieces aren't saved or sent in snapshots
+#[derive(Debug, Clone, Default)]
+pub struct DebrisField {
+    enabled: bool,
+    pieces: BTreeMap<u32, Debris>,
+    next_id: u32,
+    cleared: Vec<DebrisCleared>,
+}
+
+impl DebrisField {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.clear();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.pieces.clear();
+        self.cleared.clear();
+    }
+
+    pub fn pieces(&self) -> impl Iterator<Item = (u32, &Debris)> {
+        self.pieces.iter().map(|(id, piece)| (*id, piece))
+    }
+
+    pub fn len(&self) -> usize {
+        self.pieces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pieces.is_empty()
+    }
+
+    /// Piece on `rocket_id`'s tractor beam
+    pub fn towed_by(&self, rocket_id: EntityId) -> Option<u32> {
+        self.pieces.iter().find(|(_, piece)| piece.towed_by == Some(rocket_id)).map(|(id, _)| *id)
+    }
+
+    /// Blow a wreck into `count` pieces flying apart evenly around its velocity
+    pub fn scatter(&mut self, position: Vec2, velocity: Vec2, count: usize) {
+        if !self.enabled {
+            return;
+        }
+        // Turn each wreck's pattern a little so consecutive wrecks don't stack
+        let offset = self.next_id as f32 * 0.7;
+        for i in 0..count {
+            let direction = Vec2::from_angle(offset + TAU * i as f32 / count as f32);
+            self.add(Debris { position: position + direction * PIECE_SIZE, velocity: velocity + direction * SCATTER_SPEED, towed_by: None, released_by: None });
+        }
+    }
+
+    pub fn add(&mut self, piece: Debris) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.pieces.insert(id, piece);
+        while self.pieces.len() > MAX_PIECES {
+            self.pieces.pop_first();
+        }
+        id
+    }
+
+    /// Grab the nearest free piece within tractor range of a rocket that isn't already towing
+    pub fn capture(&mut self, rocket_id: EntityId, rocket_position: Vec2) -> Result<u32, String> {
+        if self.towed_by(rocket_id).is_some() {
+            return Err("Already towing debris - release it first".to_string());
+        }
+        let (id, piece) = self
+            .pieces
+            .iter_mut()
+            .filter(|(_, piece)| piece.towed_by.is_none() && piece.position.distance(rocket_position) <= TRACTOR_RANGE)
+            .min_by(|(_, a), (_, b)| a.position.distance(rocket_position).total_cmp(&b.position.distance(rocket_position)))
+            .ok_or_else(|| format!("No debris within {:.0} of the rocket", TRACTOR_RANGE))?;
+        piece.towed_by = Some(rocket_id);
+        Ok(*id)
+    }
+
+    /// Let go of the towed piece; it keeps the rocket's velocity
+    pub fn release(&mut self, rocket_id: EntityId) -> Option<u32> {
+        let id = self.towed_by(rocket_id)?;
+        let piece = self.pieces.get_mut(&id)?;
+        piece.towed_by = None;
+        piece.released_by = Some(rocket_id);
+        Some(id)
+    }
+
+    /// Move everything by `-shift` (floating origin rebase)
+    pub fn shift(&mut self, shift: Vec2) {
+        for piece in self.pieces.values_mut() {
+            piece.position -= shift;
+        }
+    }
+
+    /// Step free pieces under gravity and drag towed ones along behind their rockets
+    /// `rockets` yields each rocket's id, position, velocity and the planet it's landed on
+    pub fn update(
+        &mut self,
+        delta_time: f32,
+        planets: &BTreeMap<EntityId, Planet>,
+        rockets: &[(EntityId, Vec2, Vec2, Option<EntityId>)],
+        gravity: &GravitySimulator,
+    ) {
+        let mut landed = Vec::new();
+        let mut lost_tow = Vec::new();
+        for (id, piece) in self.pieces.iter_mut() {
+            if let Some(rocket_id) = piece.towed_by {
+                let Some(&(_, position, velocity, landed_on)) = rockets.iter().find(|(rocket, ..)| *rocket == rocket_id) else {
+                    lost_tow.push(*id);
+                    continue;
+                };
+                // Towed down to the ground: job done
+                if let Some(planet_id) = landed_on {
+                    landed.push((*id, Some(DebrisCleared { rocket_id, planet_id })));
+                    continue;
+                }
+                let tether = (piece.position - position).try_normalize().unwrap_or(Vec2::X);
+                piece.position = position + tether * TOW_LENGTH;
+                piece.velocity = velocity;
+                continue;
+            }
+
+            for planet in planets.values() {
+                let force = gravity.calculate_body_gravity(piece.position, PIECE_MASS, planet.position(), planet.mass(), planet.radius());
+                piece.velocity += force / PIECE_MASS * delta_time;
+            }
+            piece.position += piece.velocity * delta_time;
+
+            let hit = planets.iter().find(|(_, planet)| piece.position.distance(planet.position()) < planet.surface_radius_toward(piece.position));
+            if let Some((planet_id, _)) = hit {
+                let cleared = piece.released_by.map(|rocket_id| DebrisCleared { rocket_id, planet_id: *planet_id });
+                landed.push((*id, cleared));
+            }
+        }
+
+        // A destroyed tower drops its piece where it was
+        for id in lost_tow {
+            if let Some(piece) = self.pieces.get_mut(&id) {
+                piece.towed_by = None;
+            }
+        }
+        for (id, cleared) in landed {
+            self.pieces.remove(&id);
+            self.cleared.extend(cleared);
+        }
+    }
+
+    /// Get and clear the pieces rockets brought down since the last call
+    pub fn take_cleared(&mut self) -> Vec<DebrisCleared> {
+        std::mem::take(&mut self.cleared)
+    }
+
+    /// Pieces in each altitude band around `planet`, and how crowded each band is
+    /// Bands run from the surface up to each altitude in `shells` (pieces per million square units)
+    pub fn shell_density(&self, planet: &Planet, shells: &[f32]) -> Vec<ShellDebris> {
+        let mut floor = 0.0;
+        shells
+            .iter()
+            .map(|&altitude| {
+                let (inner, outer) = (planet.radius() + floor, planet.radius() + altitude);
+                let pieces = self
+                    .pieces
+                    .values()
+                    .filter(|piece| {
+                        let distance = piece.position.distance(planet.position());
+                        distance >= inner && distance < outer
+                    })
+                    .count();
+                let area = std::f32::consts::PI * (outer * outer - inner * inner);
+                floor = altitude;
+                ShellDebris { altitude, pieces, density: pieces as f32 / area * 1.0e6 }
+            })
+            .collect()
+    }
+
+    pub fn draw(&self, towing_rockets: impl Fn(EntityId) -> Option<Vec2>) {
+        for piece in self.pieces.values() {
+            let Vec2 { x, y } = piece.position;
+            draw_rectangle(x - PIECE_SIZE / 2.0, y - PIECE_SIZE / 2.0, PIECE_SIZE, PIECE_SIZE, Color::new(0.6, 0.55, 0.5, 1.0));
+            if let Some(rocket) = piece.towed_by.and_then(&towing_rockets) {
+                draw_line(rocket.x, rocket.y, x, y, 1.0, Color::new(0.5, 1.0, 0.8, 0.8));
+            }
+        }
+    }
+}
+
+/// Debris in one orbital shell around the primary body
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShellDebris {
+    pub altitude: f32, // Top of the band
+    pub pieces: usize,
+    pub density: f32, // Pieces per million square units
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planets() -> BTreeMap<EntityId, Planet> {
+        BTreeMap::from([(0, Planet::new(Vec2::ZERO, 1000.0, 100000.0, BLUE))])
+    }
+
+    #[test]
+    fn test_scatter_only_when_enabled() {
+        let mut field = DebrisField::new();
+        field.scatter(Vec2::new(2000.0, 0.0), Vec2::ZERO, ROCKET_DEBRIS_PIECES);
+        assert!(field.is_empty());
+
+        field.set_enabled(true);
+        field.scatter(Vec2::new(2000.0, 0.0), Vec2::ZERO, ROCKET_DEBRIS_PIECES);
+        assert_eq!(field.len(), ROCKET_DEBRIS_PIECES);
+        let shells = field.shell_density(&planets()[&0], &[500.0, 1500.0]);
+        assert_eq!(shells.iter().map(|shell| shell.pieces).collect::<Vec<_>>(), vec![0, ROCKET_DEBRIS_PIECES]);
+        assert!(shells[1].density > 0.0);
+    }
+
+    #[test]
+    fn test_tow_and_release_into_the_ground_counts_as_cleaned() {
+        let mut field = DebrisField::new();
+        field.set_enabled(true);
+        let piece = field.add(Debris { position: Vec2::new(0.0, -1300.0), velocity: Vec2::ZERO, towed_by: None, released_by: None });
+        let gravity = GravitySimulator::new();
+
+        assert!(field.capture(7, Vec2::new(0.0, -2000.0)).is_err()); // Out of reach
+        assert_eq!(field.capture(7, Vec2::new(0.0, -1500.0)), Ok(piece));
+        field.update(0.1, &planets(), &[(7, Vec2::new(0.0, -1500.0), Vec2::new(0.0, 50.0), None)], &gravity);
+        let towed = field.pieces().next().unwrap().1.clone();
+        assert!((towed.position - Vec2::new(0.0, -1460.0)).length() < 0.01);
+        assert_eq!(towed.velocity, Vec2::new(0.0, 50.0));
+
+        // Let go heading down; it falls in and the rocket gets the credit
+        assert_eq!(field.release(7), Some(piece));
+        for _ in 0..200 {
+            field.update(0.1, &planets(), &[], &gravity);
+        }
+        assert!(field.is_empty());
+        assert_eq!(field.take_cleared(), vec![DebrisCleared { rocket_id: 7, planet_id: 0 }]);
+    }
+
+    #[test]
+    fn test_wreckage_falling_by_itself_earns_nothing() {
+        let mut field = DebrisField::new();
+        field.set_enabled(true);
+        field.add(Debris { position: Vec2::new(0.0, -1100.0), velocity: Vec2::ZERO, towed_by: None, released_by: None });
+        for _ in 0..100 {
+            field.update(0.1, &planets(), &[], &GravitySimulator::new());
+        }
+        assert!(field.is_empty());
+        assert!(field.take_cleared().is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+// Desync - World-state checksums exchanged between host and clients
+// The host sends the checksum of the snapshot a client was sent; the client compares it with its world after applying it
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::entities::GameObject;
+use crate::save_system::GameSaveData;
+use crate::systems::World;
+
+/// Prefix marking a checksum packet (snapshots never start with these bytes)
+const CHECKSUM_PACKET_PREFIX: &[u8] = b"CHECKSUM";
+/// Client -> host request for a fresh full snapshot after a mismatch
+pub const RESYNC_REQUEST: &[u8] = b"RESYNC";
+/// Values are rounded to this fraction before hashing so save/load float noise doesn't count
+const CHECKSUM_RESOLUTION: f32 = 16.0;
+/// Applied snapshots remembered while waiting for their checksums
+const MAX_PENDING: usize = 32;
+
+/// FNV-1a, stable across builds and platforms (std's hasher isn't guaranteed to be)
+struct Fnv64(u64);
+
+impl Fnv64 {
+    fn new() -> Self {
+        Fnv64(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_f32(&mut self, value: f32) {
+        self.write(&((value * CHECKSUM_RESOLUTION).round() as i64).to_le_bytes());
+    }
+
+    /// One entity: kind tag, ID, then its quantized values
+    fn write_entity(&mut self, tag: u8, id: usize, values: &[f32]) {
+        self.write(&[tag]);
+        self.write(&(id as u64).to_le_bytes());
+        for value in values {
+            self.write_f32(*value);
+        }
+    }
+}
+
+/// Hash of every body's position and velocity plus rocket and satellite fuel, in ID order
+pub fn world_checksum(world: &World) -> u64 {
+    let mut entities: Vec<(u8, usize, Vec<f32>)> = Vec::new();
+    for (id, planet) in world.planets_with_ids() {
+        let (p, v) = (planet.position(), planet.velocity());
+        entities.push((b'P', id, vec![p.x, p.y, v.x, v.y]));
+    }
+    for (id, rocket) in world.rockets_with_ids() {
+        let (p, v) = (rocket.position(), rocket.velocity());
+        entities.push((b'R', id, vec![p.x, p.y, v.x, v.y, rocket.current_fuel()]));
+    }
+    for (id, satellite) in world.satellites_with_ids() {
+        let (p, v) = (satellite.position(), satellite.velocity());
+        entities.push((b'S', id, vec![p.x, p.y, v.x, v.y, satellite.current_fuel()]));
+    }
+    for (id, bullet) in world.bullets_with_ids() {
+        let (p, v) = (bullet.position(), bullet.velocity());
+        entities.push((b'B', id, vec![p.x, p.y, v.x, v.y]));
+    }
+    checksum_entities(entities)
+}
+
+/// Same hash computed from a snapshot (equals `world_checksum` of a world rebuilt from it)
+pub fn snapshot_checksum(snapshot: &GameSaveData) -> u64 {
+    let mut entities: Vec<(u8, usize, Vec<f32>)> = Vec::new();
+    for planet in &snapshot.planets {
+        entities.push((b'P', planet.id, vec![planet.position.x, planet.position.y, planet.velocity.x, planet.velocity.y]));
+    }
+    for rocket in &snapshot.rockets {
+        let (p, v) = (&rocket.position, &rocket.velocity);
+        entities.push((b'R', rocket.id, vec![p.x, p.y, v.x, v.y, rocket.fuel]));
+    }
+    for satellite in &snapshot.satellites {
+        let (p, v) = (&satellite.position, &satellite.velocity);
+        entities.push((b'S', satellite.id, vec![p.x, p.y, v.x, v.y, satellite.fuel]));
+    }
+    for bullet in &snapshot.bullets {
+        let (p, v) = (&bullet.position, &bullet.velocity);
+        entities.push((b'B', bullet.id, vec![p.x, p.y, v.x, v.y]));
+    }
+    checksum_entities(entities)
+}
+
+fn checksum_entities(mut entities: Vec<(u8, usize, Vec<f32>)>) -> u64 {
+    // Storage is hash-map ordered; sort so both sides hash in the same order
+    entities.sort_by_key(|(tag, id, _)| (*tag, *id));
+
+    let mut hasher = Fnv64::new();
+    for (tag, id, values) in &entities {
+        hasher.write_entity(*tag, *id, values);
+    }
+    hasher.0
+}
+
+/// Checksum of the snapshot the host sent with the same `snapshot_time`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChecksumPacket {
+    pub snapshot_time: f32, // Host session time stamped into the snapshot (GameSaveData::game_time)
+    pub checksum: u64,
+}
+
+impl ChecksumPacket {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = CHECKSUM_PACKET_PREFIX.to_vec();
+        bytes.extend(bincode::serialize(self).unwrap_or_default());
+        bytes
+    }
+
+    /// None if the bytes aren't a checksum packet
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let payload = bytes.strip_prefix(CHECKSUM_PACKET_PREFIX)?;
+        bincode::deserialize(payload).ok()
+    }
+}
+
+/// Client-side record of applied snapshots, checked against the host's checksums
+#[derive(Debug, Clone, Default)]
+pub struct DesyncMonitor {
+    applied: VecDeque<(f32, u64)>, // Snapshot time, checksum of our world right after applying it
+    mismatches: u32,
+}
+
+impl DesyncMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember the checksum of our world right after applying a snapshot
+    pub fn record_applied(&mut self, snapshot_time: f32, checksum: u64) {
+        if self.applied.len() >= MAX_PENDING {
+            self.applied.pop_front();
+        }
+        self.applied.push_back((snapshot_time, checksum));
+    }
+
+    /// Compare a host checksum with the matching applied snapshot
+    /// Some(true) on a mismatch, None if that snapshot never arrived (lost or undecodable)
+    pub fn check(&mut self, packet: ChecksumPacket) -> Option<bool> {
+        let (_, ours) = self.applied.iter().find(|(time, _)| *time == packet.snapshot_time)?;
+        let mismatch = *ours != packet.checksum;
+        if mismatch {
+            self.mismatches += 1;
+        }
+        Some(mismatch)
+    }
+
+    /// Mismatches seen this session
+    pub fn mismatch_count(&self) -> u32 {
+        self.mismatches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Planet, Rocket};
+    use macroquad::prelude::{Vec2, WHITE};
+
+    fn world_with_rocket(fuel: f32) -> World {
+        let mut world = World::new();
+        world.add_planet(Planet::new(Vec2::ZERO, 100.0, 1000.0, WHITE));
+        let mut rocket = Rocket::new(Vec2::new(200.0, 0.0), Vec2::new(0.0, 5.0), WHITE, 1.0);
+        rocket.set_fuel(fuel);
+        world.add_rocket(rocket);
+        world
+    }
+
+    #[test]
+    fn test_checksum_tracks_state() {
+        assert_eq!(world_checksum(&world_with_rocket(50.0)), world_checksum(&world_with_rocket(50.0)));
+        assert_eq!(world_checksum(&world_with_rocket(50.0)), world_checksum(&world_with_rocket(50.001)));
+        assert_ne!(world_checksum(&world_with_rocket(50.0)), world_checksum(&world_with_rocket(40.0)));
+    }
+
+    #[test]
+    fn test_snapshot_checksum_matches_rebuilt_world() {
+        use crate::save_system::{SavedPlanet, SavedRocket};
+
+        let world = world_with_rocket(37.3);
+        let mut snapshot = GameSaveData::new();
+        snapshot.planets = world.planets_with_ids().map(|(id, p)| SavedPlanet::from_planet(id, p)).collect();
+        snapshot.rockets = world.rockets_with_ids().map(|(id, r)| SavedRocket::from_rocket(id, r)).collect();
+
+        let mut rebuilt = World::new();
+        for planet in &snapshot.planets {
+            let (id, planet) = planet.to_planet();
+            rebuilt.add_planet_with_id(id, planet);
+        }
+        for rocket in &snapshot.rockets {
+            let (id, rocket) = rocket.to_rocket();
+            rebuilt.add_rocket_with_id(id, rocket);
+        }
+        assert_eq!(snapshot_checksum(&snapshot), world_checksum(&rebuilt));
+    }
+
+    #[test]
+    fn test_monitor_matches_by_snapshot_time() {
+        let packet = ChecksumPacket { snapshot_time: 2.5, checksum: 42 };
+        assert_eq!(ChecksumPacket::from_bytes(&packet.to_bytes()), Some(packet));
+        assert_eq!(ChecksumPacket::from_bytes(b"KEEPALIVE"), None);
+
+        let mut monitor = DesyncMonitor::new();
+        assert_eq!(monitor.check(packet), None);
+        monitor.record_applied(2.5, 42);
+        monitor.record_applied(3.0, 7);
+        assert_eq!(monitor.check(packet), Some(false));
+        assert_eq!(monitor.check(ChecksumPacket { snapshot_time: 3.0, checksum: 8 }), Some(true));
+        assert_eq!(monitor.mismatch_count(), 1);
+    }
+}
@@ -0,0 +1,502 @@
+// Economy - Fuel prices at planet stations and a board of hauling and debris cleanup contracts
+// Prices drift around each station's base; fuel bought here rides in the tank as cargo until sold or delivered
+
+use std::collections::HashMap;
+
+use crate::systems::daily_challenge::{fnv1a, SplitMix64};
+use crate::systems::{EntityId, World};
+
+/// Average station fuel price (credits per unit)
+pub const BASE_FUEL_PRICE: f32 = 4.0;
+/// Stations sell this much above their price and buy this much below it
+const PRICE_SPREAD: f32 = 0.1;
+/// Seconds between price moves
+const PRICE_TICK: f32 = 10.0;
+/// Fraction of the gap back to the base price closed each tick
+const PRICE_REVERSION: f32 = 0.15;
+/// Largest random move per tick, as a fraction of the base price
+const PRICE_VOLATILITY: f32 = 0.12;
+/// Open contracts kept on the board
+const BOARD_SIZE: usize = 4;
+/// Seconds between new contracts while the board has room
+const CONTRACT_INTERVAL: f32 = 30.0;
+/// Seconds to deliver once a contract is posted
+const CONTRACT_TIME: f32 = 600.0;
+/// Payout over what the cargo costs at the pickup station
+const CONTRACT_MARKUP: f32 = 1.5;
+/// Extra credits per unit of distance between the two stations
+const HAUL_RATE: f32 = 0.01;
+/// Credits per piece of debris a cleanup contract asks for
+const CLEANUP_RATE: f32 = 60.0;
+/// Debris pieces in the world before cleanup contracts get posted
+const CLEANUP_MIN_DEBRIS: usize = 3;
+
+/// A planet that trades fuel
+#[derive(Debug, Clone, PartialEq)]
+pub struct Station {
+    pub planet: EntityId,
+    pub name: String,
+    base_price: f32,
+    price: f32,
+}
+
+impl Station {
+    pub fn price(&self) -> f32 {
+        self.price
+    }
+
+    /// What the station charges per unit
+    pub fn ask(&self) -> f32 {
+        self.price * (1.0 + PRICE_SPREAD)
+    }
+
+    /// What the station pays per unit
+    pub fn bid(&self) -> f32 {
+        self.price * (1.0 - PRICE_SPREAD)
+    }
+}
+
+/// Haul `amount` fuel bought at `from` to `to` before the time runs out,
+/// or for cleanup, bring `amount` pieces of debris down anywhere
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contract {
+    pub id: u32,
+    pub kind: ContractKind,
+    pub from: EntityId,
+    pub to: EntityId,
+    pub amount: f32,
+    pub payout: u32,
+    pub time_left: f32,
+    pub accepted: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContractKind {
+    Haul,
+    Cleanup { cleared: u32 }, // Pieces brought down since it was taken
+}
+
+/// Market fuel carried in a rocket's tank
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cargo {
+    pub origin: EntityId, // Station it was bought at
+    pub amount: f32,
+}
+
+/// Something on the contracts board the player should hear about
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContractEvent {
+    Completed(Contract),
+    Expired(Contract), // Only reported for accepted contracts
+    DebrisCleared { planet: EntityId, progress: Option<(u32, u32)> }, // Cleared / wanted on the contract it counted towards
+}
+
+/// Station prices, cargo and contracts for one session
+#[derive(Debug, Clone)]
+pub struct Market {
+    stations: Vec<Station>,
+    contracts: Vec<Contract>,
+    cargo: HashMap<EntityId, Cargo>,
+    rng: SplitMix64,
+    price_timer: f32,
+    contract_timer: f32,
+    next_contract_id: u32,
+}
+
+impl Default for Market {
+    fn default() -> Self {
+        Market {
+            stations: Vec::new(),
+            contracts: Vec::new(),
+            cargo: HashMap::new(),
+            rng: SplitMix64(0),
+            price_timer: 0.0,
+            contract_timer: 0.0,
+            next_contract_id: 1,
+        }
+    }
+}
+
+impl Market {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A station on every planet, with base prices seeded by the map name, and a full board
+    pub fn from_world(world: &World, map_name: &str) -> Self {
+        let mut rng = SplitMix64(fnv1a(map_name.as_bytes()));
+        let stations = world
+            .planets_with_ids()
+            .map(|(planet, body)| {
+                let base_price = BASE_FUEL_PRICE * (0.6 + 0.8 * rng.unit());
+                let name = body.name().map_or_else(|| format!("Planet {}", planet), str::to_string);
+                Station { planet, name, base_price, price: base_price }
+            })
+            .collect();
+        let mut market = Market { stations, rng, ..Self::default() };
+        while market.contracts.len() < BOARD_SIZE && market.post_contract(world) {}
+        market
+    }
+
+    pub fn stations(&self) -> &[Station] {
+        &self.stations
+    }
+
+    pub fn station(&self, planet: EntityId) -> Option<&Station> {
+        self.stations.iter().find(|station| station.planet == planet)
+    }
+
+    pub fn station_name(&self, planet: EntityId) -> &str {
+        self.station(planet).map_or("?", |station| station.name.as_str())
+    }
+
+    pub fn contracts(&self) -> &[Contract] {
+        &self.contracts
+    }
+
+    pub fn cargo(&self, rocket_id: EntityId) -> Option<Cargo> {
+        self.cargo.get(&rocket_id).copied()
+    }
+
+    /// Post a random contract between two stations; false if there aren't two
+    fn post_contract(&mut self, world: &World) -> bool {
+        let count = self.stations.len();
+        if count < 2 {
+            return false;
+        }
+        let from = (self.rng.next() % count as u64) as usize;
+        let to = (from + 1 + (self.rng.next() % (count as u64 - 1)) as usize) % count;
+        let (from, to) = (&self.stations[from], &self.stations[to]);
+        let distance = match (world.get_planet(from.planet), world.get_planet(to.planet)) {
+            (Some(a), Some(b)) => (a.position() - b.position()).length(),
+            _ => 0.0,
+        };
+
+        let amount = 20.0 + (self.rng.unit() * 8.0).floor() * 5.0; // 20 to 55 in steps of 5
+        let payout = (amount * from.ask() * CONTRACT_MARKUP + distance * HAUL_RATE).round() as u32;
+        let contract = Contract {
+            id: self.next_contract_id,
+            kind: ContractKind::Haul,
+            from: from.planet,
+            to: to.planet,
+            amount,
+            payout,
+            time_left: CONTRACT_TIME,
+            accepted: false,
+        };
+        self.next_contract_id += 1;
+        self.contracts.push(contract);
+        true
+    }
+
+    /// Post a contract to clear some of the world's debris; false if there's too little to bother
+    fn post_cleanup_contract(&mut self, world: &World) -> bool {
+        let debris = world.debris().len();
+        let Some(station) = self.stations.first().map(|station| station.planet) else {
+            return false;
+        };
+        if debris < CLEANUP_MIN_DEBRIS {
+            return false;
+        }
+        let pieces = 1 + self.rng.next() % debris.min(5) as u64; // 1 to 5, never more than there is
+        let contract = Contract {
+            id: self.next_contract_id,
+            kind: ContractKind::Cleanup { cleared: 0 },
+            from: station,
+            to: station,
+            amount: pieces as f32,
+            payout: (pieces as f32 * CLEANUP_RATE).round() as u32,
+            time_left: CONTRACT_TIME,
+            accepted: false,
+        };
+        self.next_contract_id += 1;
+        self.contracts.push(contract);
+        true
+    }
+
+    /// Move prices, run contract clocks, and pay out when `player_rocket` lands with an accepted contract's cargo
+    /// or brings down the debris a cleanup contract asked for
+    pub fn update(&mut self, world: &mut World, player_rocket: Option<EntityId>, delta_time: f32) -> Vec<ContractEvent> {
+        let mut events = Vec::new();
+
+        self.price_timer += delta_time;
+        while self.price_timer >= PRICE_TICK {
+            self.price_timer -= PRICE_TICK;
+            for station in &mut self.stations {
+                let shock = (self.rng.unit() * 2.0 - 1.0) * PRICE_VOLATILITY * station.base_price;
+                let drift = (station.base_price - station.price) * PRICE_REVERSION + shock;
+                station.price = (station.price + drift).clamp(station.base_price * 0.3, station.base_price * 3.0);
+            }
+        }
+
+        // Cargo is the last fuel burned, but it can't outlast the tank
+        self.cargo.retain(|rocket_id, cargo| {
+            let Some(rocket) = world.get_rocket(*rocket_id) else {
+                return false;
+            };
+            cargo.amount = cargo.amount.min(rocket.current_fuel());
+            cargo.amount > 0.0
+        });
+
+        if let Some(rocket_id) = player_rocket {
+            self.deliver(world, rocket_id, &mut events);
+        }
+        for cleared in world.take_cleared_debris() {
+            if player_rocket == Some(cleared.rocket_id) {
+                self.count_cleanup(cleared.planet_id, &mut events);
+            }
+        }
+
+        self.contracts.retain_mut(|contract| {
+            contract.time_left -= delta_time;
+            if contract.time_left > 0.0 {
+                return true;
+            }
+            if contract.accepted {
+                events.push(ContractEvent::Expired(contract.clone()));
+            }
+            false
+        });
+
+        self.contract_timer += delta_time;
+        if self.contract_timer >= CONTRACT_INTERVAL {
+            self.contract_timer = 0.0;
+            if self.contracts.iter().filter(|contract| !contract.accepted).count() < BOARD_SIZE {
+                // Every third posting is a cleanup job while there's debris about
+                let cleanup = self.rng.next().is_multiple_of(3) && self.post_cleanup_contract(world);
+                if !cleanup {
+                    self.post_contract(world);
+                }
+            }
+        }
+
+        events
+    }
+
+    /// Count a piece the player brought down towards the oldest accepted cleanup contract
+    fn count_cleanup(&mut self, planet: EntityId, events: &mut Vec<ContractEvent>) {
+        let contract = self.contracts.iter_mut().find(|contract| contract.accepted && matches!(contract.kind, ContractKind::Cleanup { .. }));
+        let Some(contract) = contract else {
+            events.push(ContractEvent::DebrisCleared { planet, progress: None });
+            return;
+        };
+        let ContractKind::Cleanup { cleared } = &mut contract.kind else {
+            return;
+        };
+        *cleared += 1;
+        let progress = (*cleared, contract.amount as u32);
+        events.push(ContractEvent::DebrisCleared { planet, progress: Some(progress) });
+        if progress.0 >= progress.1 {
+            let id = contract.id;
+            events.push(ContractEvent::Completed(contract.clone()));
+            self.contracts.retain(|contract| contract.id != id);
+        }
+    }
+
+    /// Complete accepted contracts the rocket has just carried to their destination
+    fn deliver(&mut self, world: &mut World, rocket_id: EntityId, events: &mut Vec<ContractEvent>) {
+        let Some(landed_on) = world.get_rocket(rocket_id).and_then(|rocket| rocket.landed_on_planet_id()) else {
+            return;
+        };
+        let cargo = &mut self.cargo;
+        self.contracts.retain(|contract| {
+            let Some(held) = cargo.get_mut(&rocket_id) else {
+                return true;
+            };
+            let due = contract.accepted
+                && contract.kind == ContractKind::Haul
+                && contract.to == landed_on
+                && contract.from == held.origin
+                && held.amount >= contract.amount;
+            if !due {
+                return true;
+            }
+            held.amount -= contract.amount;
+            if let Some(rocket) = world.get_rocket_mut(rocket_id) {
+                rocket.set_fuel(rocket.current_fuel() - contract.amount);
+            }
+            events.push(ContractEvent::Completed(contract.clone()));
+            false
+        });
+        cargo.retain(|_, held| held.amount > 0.0);
+    }
+
+    /// The station a rocket is landed at, for trading
+    fn docked_station(&self, world: &World, rocket_id: EntityId) -> Result<&Station, String> {
+        let planet = world
+            .get_rocket(rocket_id)
+            .and_then(|rocket| rocket.landed_on_planet_id())
+            .ok_or("Land at a station to trade")?;
+        self.station(planet).ok_or_else(|| "No station here".to_string())
+    }
+
+    /// Fill the tank with cargo at the station's ask, spending at most `credits`
+    /// Returns the fuel bought and what it cost
+    pub fn buy(&mut self, world: &mut World, rocket_id: EntityId, credits: u32) -> Result<(f32, u32), String> {
+        let station = self.docked_station(world, rocket_id)?;
+        let (planet, ask) = (station.planet, station.ask());
+        if let Some(held) = self.cargo(rocket_id).filter(|held| held.origin != planet) {
+            return Err(format!("Sell the cargo from {} first", self.station_name(held.origin)));
+        }
+        let rocket = world.get_rocket_mut(rocket_id).ok_or("No rocket")?;
+        let room = rocket.max_fuel() - rocket.current_fuel();
+        let amount = room.min(credits as f32 / ask).floor();
+        if amount < 1.0 {
+            return Err(if room < 1.0 { "Tank is full".to_string() } else { "Not enough credits".to_string() });
+        }
+
+        rocket.add_fuel(amount);
+        let cost = (amount * ask).ceil() as u32;
+        self.cargo.entry(rocket_id).or_insert(Cargo { origin: planet, amount: 0.0 }).amount += amount;
+        Ok((amount, cost))
+    }
+
+    /// Sell all the rocket's cargo at the station's bid; returns the fuel sold and the credits earned
+    pub fn sell(&mut self, world: &mut World, rocket_id: EntityId) -> Result<(f32, u32), String> {
+        let bid = self.docked_station(world, rocket_id)?.bid();
+        let held = self.cargo.remove(&rocket_id).ok_or("No cargo to sell")?;
+        if let Some(rocket) = world.get_rocket_mut(rocket_id) {
+            rocket.set_fuel(rocket.current_fuel() - held.amount);
+        }
+        Ok((held.amount, (held.amount * bid).floor() as u32))
+    }
+
+    /// Accept an open contract or drop an accepted one; returns whether it's now accepted
+    pub fn toggle_contract(&mut self, id: u32) -> Option<bool> {
+        let contract = self.contracts.iter_mut().find(|contract| contract.id == id)?;
+        contract.accepted = !contract.accepted;
+        Some(contract.accepted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Planet, Rocket};
+    use macroquad::prelude::{Vec2, BLUE, GRAY, WHITE};
+
+    fn two_station_world() -> (World, EntityId, EntityId, EntityId) {
+        let mut world = World::new();
+        let mut earth = Planet::new(Vec2::ZERO, 100.0, 10000.0, BLUE);
+        earth.set_name("Earth".to_string());
+        let mut moon = Planet::new(Vec2::new(5000.0, 0.0), 30.0, 500.0, GRAY);
+        moon.set_name("Moon".to_string());
+        let earth = world.add_planet(earth);
+        let moon = world.add_planet(moon);
+        let rocket = world.add_rocket(Rocket::new(Vec2::new(0.0, -100.0), Vec2::ZERO, WHITE, 1.0));
+        world.get_rocket_mut(rocket).unwrap().set_fuel(0.0);
+        (world, earth, moon, rocket)
+    }
+
+    fn land(world: &mut World, rocket: EntityId, planet: EntityId) {
+        let position = world.get_rocket(rocket).unwrap().position();
+        world.get_rocket_mut(rocket).unwrap().land_on_planet(planet, position, Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_prices_drift_but_stay_near_base() {
+        let (mut world, ..) = two_station_world();
+        let mut market = Market::from_world(&world, "Test");
+        assert_eq!(market.stations().len(), 2);
+        let start: Vec<f32> = market.stations().iter().map(Station::price).collect();
+
+        for _ in 0..100 {
+            market.update(&mut world, None, PRICE_TICK);
+        }
+        for (station, start) in market.stations().iter().zip(start) {
+            assert_ne!(station.price(), start);
+            assert!(station.price() >= station.base_price * 0.3 && station.price() <= station.base_price * 3.0);
+            assert!(station.ask() > station.bid());
+        }
+        // Same map, same opening prices
+        assert_eq!(Market::from_world(&world, "Test").stations(), Market::from_world(&world, "Test").stations());
+    }
+
+    #[test]
+    fn test_trading_needs_a_station_and_credits() {
+        let (mut world, earth, moon, rocket) = two_station_world();
+        let mut market = Market::from_world(&world, "Test");
+        assert!(market.buy(&mut world, rocket, 1000).is_err()); // Not landed
+
+        land(&mut world, rocket, earth);
+        assert_eq!(market.buy(&mut world, rocket, 0), Err("Not enough credits".to_string()));
+        let (amount, cost) = market.buy(&mut world, rocket, 40).unwrap();
+        assert!(amount >= 1.0 && cost <= 40);
+        assert_eq!(market.cargo(rocket), Some(Cargo { origin: earth, amount }));
+
+        land(&mut world, rocket, moon);
+        assert!(market.buy(&mut world, rocket, 40).unwrap_err().contains("Earth"));
+        let (sold, earned) = market.sell(&mut world, rocket).unwrap();
+        assert_eq!(sold, amount);
+        assert!(earned > 0);
+        assert_eq!(world.get_rocket(rocket).unwrap().current_fuel(), 0.0);
+        assert!(market.sell(&mut world, rocket).is_err());
+    }
+
+    #[test]
+    fn test_accepted_contract_pays_on_landing_at_destination() {
+        let (mut world, earth, moon, rocket) = two_station_world();
+        let mut market = Market::from_world(&world, "Test");
+        assert_eq!(market.contracts().len(), BOARD_SIZE);
+        let contract = market.contracts().iter().find(|c| c.from == earth).cloned().unwrap();
+        assert_eq!(contract.to, moon);
+        assert_eq!(market.toggle_contract(contract.id), Some(true));
+
+        land(&mut world, rocket, earth);
+        market.buy(&mut world, rocket, 10_000).unwrap();
+        assert!(market.update(&mut world, Some(rocket), 0.1).is_empty()); // Still at the pickup
+
+        land(&mut world, rocket, moon);
+        let events = market.update(&mut world, Some(rocket), 0.1);
+        assert!(matches!(events.as_slice(), [ContractEvent::Completed(done)] if done.id == contract.id));
+        assert!(market.contracts().iter().all(|c| c.id != contract.id));
+        let left = market.cargo(rocket).map_or(0.0, |cargo| cargo.amount);
+        assert_eq!(world.get_rocket(rocket).unwrap().current_fuel(), left);
+    }
+
+    #[test]
+    fn test_only_accepted_contracts_report_expiry() {
+        let (mut world, ..) = two_station_world();
+        let mut market = Market::from_world(&world, "Test");
+        let id = market.contracts()[0].id;
+        market.toggle_contract(id);
+
+        let events = market.update(&mut world, None, CONTRACT_TIME);
+        assert!(matches!(events.as_slice(), [ContractEvent::Expired(expired)] if expired.id == id));
+        assert_eq!(market.contracts().len(), 1); // A fresh one was posted
+    }
+
+    #[test]
+    fn test_cleanup_contract_pays_once_enough_debris_is_brought_down() {
+        let (world, earth, ..) = two_station_world();
+        let mut market = Market::from_world(&world, "Test");
+        assert!(!market.post_cleanup_contract(&world)); // Nothing to clean up yet
+
+        let contract = Contract {
+            id: 99,
+            kind: ContractKind::Cleanup { cleared: 0 },
+            from: earth,
+            to: earth,
+            amount: 2.0,
+            payout: 120,
+            time_left: CONTRACT_TIME,
+            accepted: true,
+        };
+        market.contracts.push(contract);
+
+        let mut events = Vec::new();
+        market.count_cleanup(earth, &mut events);
+        assert_eq!(events, vec![ContractEvent::DebrisCleared { planet: earth, progress: Some((1, 2)) }]);
+        assert!(market.contracts().iter().any(|c| c.id == 99));
+
+        events.clear();
+        market.count_cleanup(earth, &mut events);
+        assert!(matches!(events.as_slice(), [ContractEvent::DebrisCleared { progress: Some((2, 2)), .. }, ContractEvent::Completed(done)] if done.id == 99));
+        assert!(market.contracts().iter().all(|c| c.id != 99));
+
+        // Nothing left to count towards
+        events.clear();
+        market.count_cleanup(earth, &mut events);
+        assert_eq!(events, vec![ContractEvent::DebrisCleared { planet: earth, progress: None }]);
+    }
+}
@@ -0,0 +1,130 @@
+// Entity Picker - Finds the entity under the cursor and tracks the selected target
+// The selected target is what targeting/autopilot features act on
+
+use macroquad::prelude::Vec2;
+
+use crate::systems::{EntityId, World};
+
+/// Minimum pick radius in screen pixels (small entities are hard to hit otherwise)
+pub const PICK_RADIUS_PIXELS: f32 = 14.0;
+
+/// A reference to any pickable entity in the world
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntityTarget {
+    Planet(EntityId),
+    Rocket(EntityId),
+    Satellite(EntityId),
+}
+
+impl EntityTarget {
+    pub fn id(&self) -> EntityId {
+        match self {
+            EntityTarget::Planet(id) | EntityTarget::Rocket(id) | EntityTarget::Satellite(id) => *id,
+        }
+    }
+
+    /// Current world position (None if the entity no longer exists)
+    pub fn position(&self, world: &World) -> Option<Vec2> {
+        match self {
+            EntityTarget::Planet(id) => world.get_planet(*id).map(|p| p.position()),
+            EntityTarget::Rocket(id) => world.get_rocket(*id).map(|r| r.position()),
+            EntityTarget::Satellite(id) => world.get_satellite(*id).map(|s| s.position()),
+        }
+    }
+
+    /// Current world velocity (None if the entity no longer exists)
+    pub fn velocity(&self, world: &World) -> Option<Vec2> {
+        match self {
+            EntityTarget::Planet(id) => world.get_planet(*id).map(|p| p.velocity()),
+            EntityTarget::Rocket(id) => world.get_rocket(*id).map(|r| r.velocity()),
+            EntityTarget::Satellite(id) => world.get_satellite(*id).map(|s| s.velocity()),
+        }
+    }
+
+    pub fn exists(&self, world: &World) -> bool {
+        self.position(world).is_some()
+    }
+
+    /// Display name, e.g. "Moon", "Rocket #12", "Satellite #7"
+    pub fn display_name(&self, world: &World) -> String {
+        match self {
+            EntityTarget::Planet(id) => world
+                .get_planet(*id)
+                .and_then(|p| p.name().map(str::to_string))
+                .unwrap_or_else(|| format!("Planet #{}", id)),
+            EntityTarget::Rocket(id) => format!("Rocket #{}", id),
+            EntityTarget::Satellite(id) => format!("Satellite #{}", id),
+        }
+    }
+}
+
+/// Find the entity under a world position
+/// Rockets and satellites take priority over planets since they sit on top of them
+pub fn pick_entity(world: &World, world_pos: Vec2, pick_radius: f32) -> Option<EntityTarget> {
+    let closest = |candidates: Vec<(EntityTarget, f32)>| {
+        candidates
+            .into_iter()
+            .filter(|(_, distance)| *distance <= pick_radius)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(target, _)| target)
+    };
+
+    let small_entities: Vec<(EntityTarget, f32)> = world
+        .rockets_with_ids()
+        .map(|(id, r)| (EntityTarget::Rocket(id), (r.position() - world_pos).length()))
+        .chain(
+            world
+                .satellites_with_ids()
+                .map(|(id, s)| (EntityTarget::Satellite(id), (s.position() - world_pos).length())),
+        )
+        .collect();
+
+    if let Some(target) = closest(small_entities) {
+        return Some(target);
+    }
+
+    // Planets are hit anywhere on their disc (plus the pick radius)
+    world
+        .planets_with_ids()
+        .map(|(id, p)| (id, (p.position() - world_pos).length() - p.radius()))
+        .filter(|(_, surface_distance)| *surface_distance <= pick_radius)
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(id, _)| EntityTarget::Planet(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Planet, Rocket, Satellite};
+    use macroquad::prelude::{BLUE, WHITE};
+
+    #[test]
+    fn test_pick_prefers_rocket_over_planet() {
+        let mut world = World::new();
+        let planet_id = world.add_planet(Planet::new(Vec2::new(0.0, 0.0), 50.0, 10000.0, BLUE));
+        let rocket_id = world.add_rocket(Rocket::new(Vec2::new(55.0, 0.0), Vec2::ZERO, WHITE, 1.0));
+
+        // Click right on the rocket (also within planet pick range)
+        assert_eq!(pick_entity(&world, Vec2::new(54.0, 0.0), 10.0), Some(EntityTarget::Rocket(rocket_id)));
+
+        // Click in the planet's interior, far from the rocket
+        assert_eq!(pick_entity(&world, Vec2::new(-20.0, 0.0), 10.0), Some(EntityTarget::Planet(planet_id)));
+
+        // Empty space
+        assert_eq!(pick_entity(&world, Vec2::new(500.0, 500.0), 10.0), None);
+    }
+
+    #[test]
+    fn test_target_tracks_entity_lifetime() {
+        let mut world = World::new();
+        let satellite_id = world.add_satellite(Satellite::new(Vec2::new(100.0, 0.0), Vec2::ZERO, WHITE));
+        let target = EntityTarget::Satellite(satellite_id);
+
+        assert!(target.exists(&world));
+        assert_eq!(target.position(&world), Some(Vec2::new(100.0, 0.0)));
+        assert_eq!(target.display_name(&world), format!("Satellite #{}", satellite_id));
+
+        world.clear_all();
+        assert!(!target.exists(&world));
+    }
+}
@@ -0,0 +1,240 @@
+// Flight Log - Telemetry for the post-flight debrief
+// Sampled once per tick for the flown rocket: delta-v from engine thrust, fuel burned, peak acceleration,
+// landings, kills and the orbits it reached. An orbit counts once a launch laps its body without landing;
+// its theoretical minimum delta-v is sqrt(2 * its specific energy above rest on the surface), so the
+// debrief can say how much of the burn went into the orbit and how much was wasted
+
+use std::f32::consts::{PI, TAU};
+
+use crate::game_constants::GameConstants;
+use crate::systems::{EntityId, LandingGrade, World};
+
+/// One g: surface gravity of the main planet, for the peak acceleration readout
+pub const STANDARD_GRAVITY: f32 =
+    GameConstants::G * GameConstants::MAIN_PLANET_MASS / (GameConstants::MAIN_PLANET_RADIUS * GameConstants::MAIN_PLANET_RADIUS);
+/// Points for an orbit reached with no wasted delta-v (scaled down by efficiency)
+const ORBIT_POINTS: f32 = 1000.0;
+const LANDING_POINTS: u32 = 100;
+const KILL_POINTS: u32 = 250;
+
+/// An orbit the flight reached
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrbitRecord {
+    pub body_name: String,
+    pub altitude: f32,        // Above the surface when the lap closed
+    pub delta_v_spent: f32,   // From lift-off to the closed lap
+    pub delta_v_minimum: f32, // Impulsive ideal from rest on the surface
+}
+
+impl OrbitRecord {
+    /// Share of the spent delta-v the orbit actually needed (1.0 = perfect)
+    pub fn efficiency(&self) -> f32 {
+        if self.delta_v_spent <= 0.0 {
+            return 0.0;
+        }
+        (self.delta_v_minimum / self.delta_v_spent).min(1.0)
+    }
+}
+
+/// Everything the debrief screen shows
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Debrief {
+    pub map_name: String,
+    pub flight_time: f32,
+    pub delta_v_spent: f32,
+    pub fuel_burned: f32,
+    pub max_g: f32,
+    pub landings: Vec<LandingGrade>,
+    pub kills: u32,
+    pub orbits: Vec<OrbitRecord>,
+}
+
+impl Debrief {
+    /// Nothing happened worth a debrief (menu straight after loading, say)
+    pub fn is_empty(&self) -> bool {
+        self.delta_v_spent <= 0.0 && self.landings.is_empty() && self.kills == 0
+    }
+
+    /// Delta-v per unit of fuel burned
+    pub fn delta_v_per_fuel(&self) -> Option<f32> {
+        (self.fuel_burned > 0.0).then(|| self.delta_v_spent / self.fuel_burned)
+    }
+
+    /// Minimum over spent, across every orbit reached
+    pub fn orbit_efficiency(&self) -> Option<f32> {
+        let spent: f32 = self.orbits.iter().map(|orbit| orbit.delta_v_spent).sum();
+        let minimum: f32 = self.orbits.iter().map(|orbit| orbit.delta_v_minimum).sum();
+        (spent > 0.0).then(|| (minimum / spent).min(1.0))
+    }
+
+    pub fn score(&self) -> u32 {
+        let orbits: f32 = self.orbits.iter().map(|orbit| ORBIT_POINTS * orbit.efficiency()).sum();
+        orbits.round() as u32 + self.landings.len() as u32 * LANDING_POINTS + self.kills * KILL_POINTS
+    }
+}
+
+/// A launch being watched for a closed lap around its body
+#[derive(Debug, Clone)]
+struct Ascent {
+    body_id: EntityId,
+    delta_v_at_launch: f32,
+    last_angle: f32,
+    swept: f32,   // Signed radians around the body since lift-off
+    orbited: bool, // Already recorded; nothing more to count until the next landing
+}
+
+/// Records the flown rocket through a single player session
+#[derive(Debug, Clone, Default)]
+pub struct FlightLog {
+    rocket_id: Option<EntityId>,
+    last_fuel: f32,
+    launch_body: Option<EntityId>, // Last body landed on (or the one the flight started near)
+    ascent: Option<Ascent>,
+    debrief: Debrief,
+}
+
+impl FlightLog {
+    pub fn new(map_name: &str) -> Self {
+        FlightLog { debrief: Debrief { map_name: map_name.to_string(), ..Debrief::default() }, ..Self::default() }
+    }
+
+    pub fn debrief(&self) -> &Debrief {
+        &self.debrief
+    }
+
+    pub fn record_landing(&mut self, grade: LandingGrade) {
+        self.debrief.landings.push(grade);
+    }
+
+    pub fn record_kill(&mut self) {
+        self.debrief.kills += 1;
+    }
+
+    /// Sample the active rocket after a tick of `delta_time` game seconds
+    pub fn sample(&mut self, world: &World, delta_time: f32) {
+        self.debrief.flight_time += delta_time;
+        let Some((rocket_id, rocket)) = world.active_rocket_id().and_then(|id| Some((id, world.get_rocket(id)?))) else {
+            return;
+        };
+
+        // Switched vehicles or respawned: start the bookkeeping over for this rocket
+        if self.rocket_id != Some(rocket_id) {
+            self.rocket_id = Some(rocket_id);
+            self.last_fuel = rocket.current_fuel();
+            self.launch_body = rocket.landed_on_planet_id().or_else(|| nearest_body(world, rocket.position()));
+            self.ascent = None;
+        }
+
+        // Refuels aren't burns
+        self.debrief.fuel_burned += (self.last_fuel - rocket.current_fuel()).max(0.0);
+        self.last_fuel = rocket.current_fuel();
+
+        let delta_v_before = self.debrief.delta_v_spent;
+        if rocket.is_thrusting() {
+            let acceleration = GameConstants::ENGINE_THRUST_POWER * rocket.thrust_level() / rocket.mass().max(f32::EPSILON);
+            self.debrief.delta_v_spent += acceleration * delta_time;
+            self.debrief.max_g = self.debrief.max_g.max(acceleration / STANDARD_GRAVITY);
+        }
+
+        if let Some(body_id) = rocket.landed_on_planet_id() {
+            self.launch_body = Some(body_id);
+            self.ascent = None;
+            return;
+        }
+        let Some((body_id, body)) = self.launch_body.and_then(|id| Some((id, world.get_planet(id)?))) else {
+            return;
+        };
+        let offset = rocket.position() - body.position();
+        let angle = offset.y.atan2(offset.x);
+        let Some(ascent) = self.ascent.as_mut().filter(|ascent| ascent.body_id == body_id) else {
+            self.ascent = Some(Ascent { body_id, delta_v_at_launch: delta_v_before, last_angle: angle, swept: 0.0, orbited: false });
+            return;
+        };
+
+        // Wrap the step into (-pi, pi] so crossing the +/-pi seam doesn't look like a full turn
+        let step = (angle - ascent.last_angle + PI).rem_euclid(TAU) - PI;
+        ascent.last_angle = angle;
+        ascent.swept += step;
+        if ascent.orbited || ascent.swept.abs() < TAU {
+            return;
+        }
+        ascent.orbited = true;
+
+        let distance = offset.length();
+        let speed = (rocket.velocity() - body.velocity()).length();
+        let mu = GameConstants::G * body.mass();
+        let potential = world.gravity_simulator().model().potential(mu, distance, body.radius()) as f32;
+        let energy = speed * speed / 2.0 + potential;
+        self.debrief.orbits.push(OrbitRecord {
+            body_name: body.name().unwrap_or("Unknown").to_string(),
+            altitude: distance - body.radius(),
+            delta_v_spent: self.debrief.delta_v_spent - ascent.delta_v_at_launch,
+            delta_v_minimum: (2.0 * energy.max(0.0)).sqrt(),
+        });
+    }
+}
+
+/// Body whose surface is closest (a fresh rocket that spawned above the ground)
+fn nearest_body(world: &World, position: macroquad::prelude::Vec2) -> Option<EntityId> {
+    world
+        .planets_with_ids()
+        .min_by(|(_, a), (_, b)| {
+            let da = (a.position() - position).length() - a.radius();
+            let db = (b.position() - position).length() - b.radius();
+            da.total_cmp(&db)
+        })
+        .map(|(id, _)| id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Planet, Rocket};
+    use macroquad::prelude::*;
+
+    #[test]
+    fn test_orbit_counts_after_a_lap_with_its_minimum_delta_v() {
+        let mut world = World::new();
+        let planet = world.add_planet(Planet::new(Vec2::ZERO, 1000.0, 100000.0, BLUE));
+        let mut rocket = Rocket::new(Vec2::new(0.0, -1000.0), Vec2::ZERO, WHITE, 1.0);
+        rocket.land_on_planet(planet, Vec2::new(0.0, -1000.0), Vec2::ZERO);
+        let rocket = world.add_rocket(rocket);
+        world.set_active_rocket(Some(rocket));
+
+        let mut log = FlightLog::new("test");
+        log.sample(&world, 0.1);
+        assert!(log.debrief().is_empty());
+
+        // Circular orbit at 1000 above the surface, flown in 16 steps a lap (one extra to close it)
+        let radius = 2000.0;
+        let speed = (GameConstants::G * 100000.0 / radius).sqrt();
+        world.get_rocket_mut(rocket).unwrap().take_off();
+        for step in 0..=17 {
+            let angle = -PI / 2.0 + step as f32 * TAU / 16.0;
+            let direction = Vec2::new(angle.cos(), angle.sin());
+            let rocket = world.get_rocket_mut(rocket).unwrap();
+            rocket.set_position(direction * radius);
+            rocket.set_velocity(direction.perp() * speed);
+            log.sample(&world, 0.1);
+        }
+
+        let orbit = &log.debrief().orbits[..];
+        assert_eq!(orbit.len(), 1, "one lap, one orbit: {:?}", orbit);
+        // Kinetic mu/(2r) plus the climb mu/R - mu/r from the surface
+        let mu = GameConstants::G * 100000.0;
+        let expected = (2.0 * (mu / 1000.0 - mu / (2.0 * radius))).sqrt();
+        assert!((orbit[0].delta_v_minimum - expected).abs() < expected * 1e-3, "{} vs {}", orbit[0].delta_v_minimum, expected);
+        assert!((orbit[0].altitude - 1000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_score_and_efficiency() {
+        let mut debrief = Debrief { delta_v_spent: 400.0, fuel_burned: 20.0, kills: 1, ..Debrief::default() };
+        debrief.landings.push(LandingGrade::Perfect);
+        debrief.orbits.push(OrbitRecord { body_name: "Earth".to_string(), altitude: 500.0, delta_v_spent: 400.0, delta_v_minimum: 300.0 });
+        assert_eq!(debrief.delta_v_per_fuel(), Some(20.0));
+        assert_eq!(debrief.orbit_efficiency(), Some(0.75));
+        assert_eq!(debrief.score(), 750 + LANDING_POINTS + KILL_POINTS);
+        assert!(!debrief.is_empty());
+    }
+}
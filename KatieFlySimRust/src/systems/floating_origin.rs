@@ -0,0 +1,88 @@
+// Floating Origin - Keeps f32 positions small on big maps by rebasing coordinates around the action
+// Entity positions are local to `offset`, the f64 world position of the local origin
+
+use macroquad::prelude::*;
+
+/// Rebase once the focus point is this far from the local origin
+pub const REBASE_DISTANCE: f32 = 50_000.0;
+/// Shifts are whole multiples of this, so offsets stay exact and repeated rebases don't drift
+const REBASE_GRID: f32 = 1_000.0;
+
+/// Where the local origin sits in world coordinates
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FloatingOrigin {
+    offset: DVec2,
+}
+
+impl FloatingOrigin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_offset(offset: DVec2) -> Self {
+        FloatingOrigin { offset }
+    }
+
+    pub fn offset(&self) -> DVec2 {
+        self.offset
+    }
+
+    /// Shift to subtract from every local position when `focus` has strayed too far (None while it's close enough)
+    pub fn rebase_shift(&self, focus: Vec2) -> Option<Vec2> {
+        if focus.length() < REBASE_DISTANCE {
+            return None;
+        }
+        Some((focus / REBASE_GRID).round() * REBASE_GRID)
+    }
+
+    /// Record that local positions moved by `-shift`
+    pub fn apply(&mut self, shift: Vec2) {
+        self.offset += shift.as_dvec2();
+    }
+
+    /// Shift that rebases positions from `previous` into this origin (subtract it, as with `rebase_shift`)
+    pub fn shift_from(&self, previous: &FloatingOrigin) -> Vec2 {
+        (self.offset - previous.offset).as_vec2()
+    }
+
+    pub fn to_world(&self, local: Vec2) -> DVec2 {
+        self.offset + local.as_dvec2()
+    }
+
+    pub fn to_local(&self, world: DVec2) -> Vec2 {
+        (world - self.offset).as_vec2()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebase_keeps_world_positions() {
+        let mut origin = FloatingOrigin::new();
+        assert_eq!(origin.rebase_shift(Vec2::new(REBASE_DISTANCE - 1.0, 0.0)), None);
+
+        let local = Vec2::new(2_400_123.5, -60_250.0);
+        let shift = origin.rebase_shift(local).unwrap();
+        assert_eq!(shift, Vec2::new(2_400_000.0, -60_000.0));
+
+        let world_before = origin.to_world(local);
+        origin.apply(shift);
+        let rebased = local - shift;
+        assert_eq!(rebased, Vec2::new(123.5, -250.0));
+        assert_eq!(origin.to_world(rebased), world_before);
+        assert_eq!(origin.to_local(world_before), rebased);
+    }
+
+    #[test]
+    fn test_shift_between_origins() {
+        let old = FloatingOrigin::from_offset(DVec2::new(1_000_000.0, 0.0));
+        let new = FloatingOrigin::from_offset(DVec2::new(1_050_000.0, 2_000.0));
+        // The new frame's origin sits at local (50_000, 2_000) in the old frame
+        let point = Vec2::new(50_000.0, 2_000.0);
+        assert_eq!(new.shift_from(&old), point);
+        assert_eq!(point - new.shift_from(&old), Vec2::ZERO);
+        assert_eq!(new.to_local(old.to_world(point)), Vec2::ZERO);
+    }
+}
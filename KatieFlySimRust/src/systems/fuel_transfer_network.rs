@@ -6,6 +6,9 @@ use crate::systems::EntityId;
 use crate::utils::vector_helper;
 use macroquad::prelude::*;
 
+/// Cap on pending anomalies so a network nobody drains can't grow forever
+const MAX_PENDING_ANOMALIES: usize = 1024;
+
 /// Fuel transfer request priority
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TransferPriority {
@@ -25,6 +28,49 @@ pub enum TransferStatus {
     Failed,
 }
 
+/// Why a live transfer stopped short
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterruptCause {
+    Occluded,  // A body moved between the two ends
+    Destroyed, // One end no longer exists
+}
+
+/// A network problem worth telling the player about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkAnomaly {
+    /// Below its maintenance reserve with no linked donor that can reach it
+    Unreachable { satellite_id: EntityId },
+    /// Out of fuel entirely: no station keeping until it is refuelled
+    Starved { satellite_id: EntityId },
+    /// A live transfer cut off before the two ends evened out
+    Interrupted { source_id: EntityId, destination_id: EntityId, cause: InterruptCause },
+}
+
+impl NetworkAnomaly {
+    pub fn describe(&self) -> String {
+        match self {
+            NetworkAnomaly::Unreachable { satellite_id } => format!("Satellite {} needs fuel - no linked donor can reach it", satellite_id),
+            NetworkAnomaly::Starved { satellite_id } => format!("Satellite {} is out of fuel - station keeping stopped", satellite_id),
+            NetworkAnomaly::Interrupted { source_id, destination_id, cause: InterruptCause::Occluded } => {
+                format!("Transfer {} -> {} blocked by a planet", source_id, destination_id)
+            }
+            NetworkAnomaly::Interrupted { source_id, destination_id, cause: InterruptCause::Destroyed } => {
+                format!("Transfer {} -> {} lost - a satellite was destroyed", source_id, destination_id)
+            }
+        }
+    }
+}
+
+/// A satellite as the supply check sees it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SupplyNode {
+    pub id: EntityId,
+    pub position: Vec2,
+    pub fuel: f32,
+    pub reserve: f32,       // Below this it needs fuel; above it, it can give
+    pub owner: Option<u32>, // Only satellites of the same owner link up
+}
+
 /// Fuel transfer request
 #[derive(Debug, Clone)]
 pub struct FuelTransferRequest {
@@ -86,6 +132,10 @@ pub struct FuelTransferNetwork {
     // Statistics
     stats: NetworkFlowStats,
     game_time: f32,
+
+    // Anomalies waiting for the HUD, and the supply problems already reported per satellite
+    anomalies: Vec<NetworkAnomaly>,
+    open_anomalies: HashMap<EntityId, NetworkAnomaly>,
 }
 
 impl FuelTransferNetwork {
@@ -102,6 +152,8 @@ impl FuelTransferNetwork {
             critical_fuel_threshold: 0.05,
             stats: NetworkFlowStats::default(),
             game_time: 0.0,
+            anomalies: Vec::new(),
+            open_anomalies: HashMap::new(),
         }
     }
 
@@ -400,6 +452,98 @@ impl FuelTransferNetwork {
         }
     }
 
+    // === Anomalies ===
+
+    /// Mirror this tick's live transfers (source, destination, amount): new ones start in progress,
+    /// ones that stopped complete, or fail with the cause `interrupted` finds for them
+    pub fn sync_transfers(
+        &mut self,
+        live: &[(EntityId, EntityId, f32)],
+        mut interrupted: impl FnMut(EntityId, EntityId) -> Option<InterruptCause>,
+    ) {
+        for &(source_id, destination_id, amount) in live {
+            if let Some(request) = self.active_transfers.get_mut(&destination_id) {
+                request.amount += amount;
+                continue;
+            }
+            let request = FuelTransferRequest {
+                id: self.next_request_id,
+                source_id,
+                destination_id,
+                amount,
+                priority: TransferPriority::Normal,
+                status: TransferStatus::InProgress,
+                created_time: self.game_time,
+            };
+            self.next_request_id += 1;
+            self.active_transfers.insert(destination_id, request);
+            self.stats.active_transfers += 1;
+        }
+
+        let stopped: Vec<(EntityId, EntityId)> = self
+            .active_transfers
+            .values()
+            .filter(|request| !live.iter().any(|&(_, destination_id, _)| destination_id == request.destination_id))
+            .map(|request| (request.source_id, request.destination_id))
+            .collect();
+        for (source_id, destination_id) in stopped {
+            let cause = interrupted(source_id, destination_id);
+            self.complete_transfer(destination_id, cause.is_none());
+            if let Some(cause) = cause {
+                self.record_anomaly(NetworkAnomaly::Interrupted { source_id, destination_id, cause });
+            }
+        }
+    }
+
+    /// Flag satellites that are out of fuel, or below their reserve with no linked donor;
+    /// each is reported once until it recovers (or its problem changes)
+    pub fn check_supply(&mut self, nodes: &[SupplyNode]) {
+        let mut owners: Vec<Option<u32>> = nodes.iter().map(|node| node.owner).collect();
+        owners.sort();
+        owners.dedup();
+
+        let mut found = HashMap::new();
+        for owner in owners {
+            let fleet: Vec<&SupplyNode> = nodes.iter().filter(|node| node.owner == owner).collect();
+            self.update_connections(&fleet.iter().map(|node| (node.id, node.position)).collect());
+            for node in &fleet {
+                let anomaly = if node.fuel <= 0.0 {
+                    Some(NetworkAnomaly::Starved { satellite_id: node.id })
+                } else if node.fuel < node.reserve
+                    && !fleet
+                        .iter()
+                        .any(|donor| donor.id != node.id && donor.fuel > donor.reserve && self.dijkstra_fuel_path(donor.id, node.id).is_some())
+                {
+                    Some(NetworkAnomaly::Unreachable { satellite_id: node.id })
+                } else {
+                    None
+                };
+                if let Some(anomaly) = anomaly {
+                    found.insert(node.id, anomaly);
+                }
+            }
+        }
+
+        for (id, anomaly) in &found {
+            if self.open_anomalies.get(id) != Some(anomaly) {
+                self.record_anomaly(*anomaly);
+            }
+        }
+        self.open_anomalies = found;
+    }
+
+    fn record_anomaly(&mut self, anomaly: NetworkAnomaly) {
+        if self.anomalies.len() >= MAX_PENDING_ANOMALIES {
+            self.anomalies.remove(0);
+        }
+        self.anomalies.push(anomaly);
+    }
+
+    /// Get and clear anomalies raised since the last call
+    pub fn take_anomalies(&mut self) -> Vec<NetworkAnomaly> {
+        std::mem::take(&mut self.anomalies)
+    }
+
     // === Getters ===
 
     pub fn stats(&self) -> &NetworkFlowStats {
@@ -478,6 +622,47 @@ mod tests {
         assert_eq!(network.active_transfer_count(), 2);
     }
 
+    #[test]
+    fn test_anomalies() {
+        let mut network = FuelTransferNetwork::new();
+
+        // A transfer that stops cleanly completes; one that stops blocked is reported
+        network.sync_transfers(&[(1, 2, 1.0), (3, 4, 1.0)], |_, _| None);
+        assert_eq!(network.active_transfer_count(), 2);
+        network.sync_transfers(&[], |source, _| (source == 3).then_some(InterruptCause::Occluded));
+        assert_eq!(network.stats().completed_transfers, 1);
+        assert_eq!(network.stats().failed_transfers, 1);
+        assert_eq!(
+            network.take_anomalies(),
+            vec![NetworkAnomaly::Interrupted { source_id: 3, destination_id: 4, cause: InterruptCause::Occluded }]
+        );
+
+        // Satellite 11 is low with its only donor out of range; 12 is empty
+        let node = |id, x, fuel| SupplyNode { id, position: Vec2::new(x, 0.0), fuel, reserve: 20.0, owner: None };
+        let nodes = [node(10, 0.0, 80.0), node(11, 5000.0, 10.0), node(12, 200.0, 0.0)];
+        network.check_supply(&nodes);
+        let mut found = network.take_anomalies();
+        found.sort_by_key(|anomaly| format!("{:?}", anomaly));
+        assert_eq!(found, vec![NetworkAnomaly::Starved { satellite_id: 12 }, NetworkAnomaly::Unreachable { satellite_id: 11 }]);
+
+        // Still the same problems: nothing new. Once 11 drifts into range it recovers and can be flagged again later
+        network.check_supply(&nodes);
+        assert!(network.take_anomalies().is_empty());
+        network.check_supply(&[node(10, 0.0, 80.0), node(11, 300.0, 10.0), node(12, 200.0, 0.0)]);
+        assert!(network.take_anomalies().is_empty());
+        network.check_supply(&nodes);
+        assert_eq!(network.take_anomalies(), vec![NetworkAnomaly::Unreachable { satellite_id: 11 }]);
+
+        // Nobody draining them: only the latest are kept
+        for destination_id in 0..MAX_PENDING_ANOMALIES + 10 {
+            network.sync_transfers(&[(1, destination_id, 1.0)], |_, _| None);
+            network.sync_transfers(&[], |_, _| Some(InterruptCause::Occluded));
+        }
+        let kept = network.take_anomalies();
+        assert_eq!(kept.len(), MAX_PENDING_ANOMALIES);
+        assert_eq!(kept[0], NetworkAnomaly::Interrupted { source_id: 1, destination_id: 10, cause: InterruptCause::Occluded });
+    }
+
     #[test]
     fn test_dijkstra_routing() {
         let mut network = FuelTransferNetwork::new();
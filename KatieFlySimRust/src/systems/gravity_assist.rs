@@ -0,0 +1,339 @@
+// Gravity Assist - Flyby maths for the slingshot tutorial: turn angle, speed gained per approach corridor and flyby scoring
+// Speeds are measured against the body the target orbits (the most massive other planet), the frame an assist pays off in
+
+use macroquad::prelude::*;
+
+use crate::entities::{Planet, Rocket};
+use crate::game_constants::GameConstants;
+use crate::physics::{GravityModel, TrajectoryPredictor};
+use crate::systems::World;
+
+/// Corridor periapsis altitudes shown in the guidance, as fractions of the target's radius
+pub const CORRIDOR_ALTITUDES: [f32; 3] = [0.1, 0.5, 1.5];
+/// Look-ahead used to find where the current path passes the target
+const PREDICTION_STEP: f32 = 0.5;
+const PREDICTION_STEPS: usize = 600;
+
+/// Angle a hyperbolic flyby turns the approach velocity through
+pub fn turn_angle(mu: f32, excess_speed: f32, periapsis: f32) -> f32 {
+    2.0 * (1.0 / (1.0 + periapsis * excess_speed * excess_speed / mu)).asin()
+}
+
+/// Speed change in the central frame when the target turns `v_inf` through `turn` radians (positive = counter-clockwise)
+pub fn speed_gain(target_velocity: Vec2, v_inf: Vec2, turn: f32) -> f32 {
+    (target_velocity + Vec2::from_angle(turn).rotate(v_inf)).length() - (target_velocity + v_inf).length()
+}
+
+/// Hyperbolic excess speed of a craft at `offset` / `relative_velocity` from a body (None if the body has captured it)
+pub fn excess_speed(mu: f32, offset: Vec2, relative_velocity: Vec2) -> Option<f32> {
+    let energy = relative_velocity.length_squared() - 2.0 * mu / offset.length();
+    (energy > 0.0).then(|| energy.sqrt())
+}
+
+/// Most speed a flyby could add to this approach: the lowest pass on the better side
+pub fn best_gain(mu: f32, radius: f32, target_velocity: Vec2, v_inf: Vec2) -> f32 {
+    let turn = turn_angle(mu, v_inf.length(), radius);
+    speed_gain(target_velocity, v_inf, turn).max(speed_gain(target_velocity, v_inf, -turn))
+}
+
+/// One way past the target and what it does to the craft's speed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AssistCorridor {
+    pub altitude: f32, // Periapsis above the surface
+    pub behind: bool,  // Passing behind the target (relative to its motion) speeds the craft up
+    pub speed_gain: f32,
+}
+
+/// Speed gained passing behind and ahead of the target at each corridor altitude
+pub fn corridors(mu: f32, radius: f32, target_velocity: Vec2, v_inf: Vec2) -> Vec<AssistCorridor> {
+    CORRIDOR_ALTITUDES
+        .iter()
+        .flat_map(|fraction| {
+            let altitude = fraction * radius;
+            let turn = turn_angle(mu, v_inf.length(), radius + altitude);
+            let (left, right) = (speed_gain(target_velocity, v_inf, turn), speed_gain(target_velocity, v_inf, -turn));
+            [
+                AssistCorridor { altitude, behind: true, speed_gain: left.max(right) },
+                AssistCorridor { altitude, behind: false, speed_gain: left.min(right) },
+            ]
+        })
+        .collect()
+}
+
+/// The assist target and the body it orbits
+#[derive(Clone, Copy)]
+pub struct AssistBodies<'a> {
+    pub target: &'a Planet,
+    pub central: &'a Planet,
+}
+
+impl<'a> AssistBodies<'a> {
+    /// `target_name` and the most massive other planet (None if either is missing)
+    pub fn find(world: &'a World, target_name: &str) -> Option<Self> {
+        let target = world.planets().find(|planet| planet.name() == Some(target_name))?;
+        let central = world
+            .planets()
+            .filter(|planet| !std::ptr::eq(*planet, target))
+            .max_by(|a, b| a.mass().partial_cmp(&b.mass()).unwrap_or(std::cmp::Ordering::Equal))?;
+        Some(AssistBodies { target, central })
+    }
+
+    pub fn mu(&self) -> f32 {
+        GameConstants::G * self.target.mass()
+    }
+
+    /// The target's velocity in the central frame
+    pub fn target_velocity(&self) -> Vec2 {
+        self.target.velocity() - self.central.velocity()
+    }
+
+    /// Radius of the target's sphere of influence; flybys are scored across it
+    pub fn sphere_of_influence(&self) -> f32 {
+        let distance = self.target.position().distance(self.central.position());
+        (distance * (self.target.mass() / self.central.mass()).powf(0.4)).max(self.target.radius() * 2.0)
+    }
+
+    /// A craft's speed in the central frame
+    pub fn central_speed(&self, velocity: Vec2) -> f32 {
+        (velocity - self.central.velocity()).length()
+    }
+
+    /// Approach velocity relative to the target, stretched to its excess speed (None once captured)
+    pub fn approach(&self, position: Vec2, velocity: Vec2) -> Option<Vec2> {
+        let relative_velocity = velocity - self.target.velocity();
+        let excess = excess_speed(self.mu(), position - self.target.position(), relative_velocity)?;
+        Some(relative_velocity.normalize_or_zero() * excess)
+    }
+}
+
+/// How a finished flyby went
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlybyResult {
+    pub speed_gain: f32,
+    pub best_gain: f32, // What the lowest pass on the better side would have given the same approach
+    pub periapsis_altitude: f32,
+}
+
+impl FlybyResult {
+    /// Share of the best possible gain achieved, 0..=1
+    pub fn efficiency(&self) -> f32 {
+        if self.best_gain <= 0.0 {
+            return 0.0;
+        }
+        (self.speed_gain / self.best_gain).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ActiveFlyby {
+    entry_speed: f32,
+    best_gain: f32,
+    periapsis: f32,
+    speed_gain: f32, // So far
+}
+
+/// Follows a craft through the target's sphere of influence and scores the flyby as it leaves
+#[derive(Debug, Clone, Default)]
+pub struct FlybyTracker {
+    active: Option<ActiveFlyby>,
+    last: Option<FlybyResult>,
+}
+
+impl FlybyTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Step with the craft's current state; returns the result on the frame it leaves the sphere of influence
+    pub fn update(&mut self, bodies: &AssistBodies, rocket: &Rocket) -> Option<FlybyResult> {
+        if rocket.is_landed() {
+            self.active = None; // Touching down ends the pass without scoring it
+            return None;
+        }
+        let distance = rocket.position().distance(bodies.target.position());
+        let speed = bodies.central_speed(rocket.velocity());
+        if distance > bodies.sphere_of_influence() {
+            let flyby = self.active.take()?;
+            let result = FlybyResult {
+                speed_gain: speed - flyby.entry_speed,
+                best_gain: flyby.best_gain,
+                periapsis_altitude: flyby.periapsis - bodies.target.radius(),
+            };
+            self.last = Some(result);
+            return Some(result);
+        }
+
+        match &mut self.active {
+            Some(flyby) => {
+                flyby.periapsis = flyby.periapsis.min(distance);
+                flyby.speed_gain = speed - flyby.entry_speed;
+            }
+            None => {
+                // Captured craft aren't flying by; they're arriving
+                let v_inf = bodies.approach(rocket.position(), rocket.velocity())?;
+                self.active = Some(ActiveFlyby {
+                    entry_speed: speed,
+                    best_gain: best_gain(bodies.mu(), bodies.target.radius(), bodies.target_velocity(), v_inf),
+                    periapsis: distance,
+                    speed_gain: 0.0,
+                });
+            }
+        }
+        None
+    }
+
+    /// Speed gained so far and the best possible, while inside the sphere of influence
+    pub fn in_progress(&self) -> Option<(f32, f32)> {
+        self.active.map(|flyby| (flyby.speed_gain, flyby.best_gain))
+    }
+
+    pub fn last(&self) -> Option<FlybyResult> {
+        self.last
+    }
+}
+
+/// Where the current path passes the target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PredictedFlyby {
+    pub position: Vec2, // The craft at closest approach
+    pub altitude: f32,  // Closest approach above the surface (negative = impact)
+    pub time: f32,      // Seconds until then
+    pub speed_gain: f32,
+}
+
+/// Everything the tutorial overlay shows for the current frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssistGuidance {
+    pub target_name: String,
+    pub target_position: Vec2,
+    pub target_velocity: Vec2,         // In the central frame
+    pub corridors: Vec<AssistCorridor>, // Empty once the target has captured the craft
+    pub predicted: Option<PredictedFlyby>,
+}
+
+/// Keeps the overlay's guidance up to date with its own trajectory predictor
+pub struct AssistGuide {
+    predictor: TrajectoryPredictor,
+    guidance: Option<AssistGuidance>,
+}
+
+impl AssistGuide {
+    pub fn new() -> Self {
+        AssistGuide { predictor: TrajectoryPredictor::new(), guidance: None }
+    }
+
+    pub fn set_gravity_model(&mut self, model: GravityModel) {
+        self.predictor.set_gravity_model(model);
+    }
+
+    /// Recompute for `rocket` heading past `target_name` (clears the guidance when either is None)
+    pub fn update(&mut self, world: &World, rocket: Option<&Rocket>, target_name: Option<&str>) {
+        self.guidance = rocket.zip(target_name).and_then(|(rocket, name)| {
+            let bodies = AssistBodies::find(world, name)?;
+            let corridors = bodies
+                .approach(rocket.position(), rocket.velocity())
+                .map(|v_inf| corridors(bodies.mu(), bodies.target.radius(), bodies.target_velocity(), v_inf))
+                .unwrap_or_default();
+            let predicted = self.predict(world, &bodies, rocket);
+            Some(AssistGuidance {
+                target_name: name.to_string(),
+                target_position: bodies.target.position(),
+                target_velocity: bodies.target_velocity(),
+                corridors,
+                predicted,
+            })
+        });
+    }
+
+    pub fn guidance(&self) -> Option<&AssistGuidance> {
+        self.guidance.as_ref()
+    }
+
+    /// Closest approach along the predicted path, with the gain from the turn the target gives there
+    fn predict(&mut self, world: &World, bodies: &AssistBodies, rocket: &Rocket) -> Option<PredictedFlyby> {
+        if rocket.is_landed() {
+            return None;
+        }
+        let planets: Vec<&Planet> = world.planets().collect();
+        let others: Vec<&Planet> = planets.iter().copied().filter(|planet| !std::ptr::eq(*planet, bodies.target)).collect();
+        let (path, _) = self.predictor.predict_trajectory_from_state(
+            rocket.position(),
+            rocket.velocity(),
+            rocket.mass(),
+            &planets,
+            PREDICTION_STEP,
+            PREDICTION_STEPS,
+            false,
+        );
+        let (target_path, _) = self.predictor.predict_planet_trajectory(bodies.target, &others, PREDICTION_STEP, PREDICTION_STEPS, false);
+
+        let (closest, (point, target_point)) = path
+            .iter()
+            .zip(&target_path)
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                let da = a.0.position.distance(a.1.position);
+                let db = b.0.position.distance(b.1.position);
+                da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
+            })?;
+        // Still closing at the end of the look-ahead, or already moving away
+        if closest == 0 || closest + 1 >= path.len().min(target_path.len()) {
+            return None;
+        }
+
+        let offset = point.position - target_point.position;
+        let relative_velocity = point.velocity - target_point.velocity;
+        let distance = offset.length();
+        let speed_gain = match excess_speed(bodies.mu(), offset, relative_velocity) {
+            Some(excess) => {
+                // The pass turns the approach through `turn`, half of it before periapsis and half after
+                let turn = turn_angle(bodies.mu(), excess, distance) * offset.perp_dot(relative_velocity).signum();
+                let v_inf_in = Vec2::from_angle(-turn / 2.0).rotate(relative_velocity.normalize_or_zero() * excess);
+                speed_gain(target_point.velocity - bodies.central.velocity(), v_inf_in, turn)
+            }
+            None => 0.0,
+        };
+        Some(PredictedFlyby {
+            position: point.position,
+            altitude: distance - bodies.target.radius(),
+            time: point.time,
+            speed_gain,
+        })
+    }
+}
+
+impl Default for AssistGuide {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flyby_turn_and_gain() {
+        let mu = 1.0e6;
+        // Slower approaches and lower passes turn further
+        assert!(turn_angle(mu, 10.0, 1000.0) > turn_angle(mu, 40.0, 1000.0));
+        assert!(turn_angle(mu, 10.0, 1000.0) > turn_angle(mu, 10.0, 4000.0));
+
+        // Crossing the path of a target moving +x: turning the approach toward its motion is a gain
+        let target_velocity = Vec2::new(50.0, 0.0);
+        let v_inf = Vec2::new(0.0, 30.0);
+        let found = corridors(mu, 1000.0, target_velocity, v_inf);
+        assert_eq!(found.len(), CORRIDOR_ALTITUDES.len() * 2);
+        assert!(found.iter().all(|corridor| corridor.behind == (corridor.speed_gain > 0.0)));
+        let best = best_gain(mu, 1000.0, target_velocity, v_inf);
+        assert!(found.iter().all(|corridor| corridor.speed_gain <= best + 1e-4));
+
+        let result = FlybyResult { speed_gain: best / 2.0, best_gain: best, periapsis_altitude: 100.0 };
+        assert!((result.efficiency() - 0.5).abs() < 1e-5);
+        assert_eq!(FlybyResult { speed_gain: -5.0, ..result }.efficiency(), 0.0);
+
+        // A craft slower than escape speed has no excess speed
+        assert!(excess_speed(mu, Vec2::new(1000.0, 0.0), Vec2::new(10.0, 0.0)).is_none());
+        assert!(excess_speed(mu, Vec2::new(1000.0, 0.0), Vec2::new(100.0, 0.0)).is_some());
+    }
+}
@@ -0,0 +1,228 @@
+// Haptics - Gamepad rumble for thrust, landings, bullet hits and explosions
+// Mixes a steady engine rumble with decaying pulses and sends the result to the controller's motors
+
+/// Engine rumble at full throttle (low motor, high motor)
+const THRUST_RUMBLE: (f32, f32) = (0.35, 0.15);
+/// Throttle below this doesn't rumble (trim burns stay quiet)
+const THRUST_DEADZONE: f32 = 0.05;
+/// Touchdowns this hard (m/s into the surface) give the strongest landing thump
+const HARD_LANDING_SPEED: f32 = 120.0;
+
+/// Something the player should feel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HapticEvent {
+    Landing { vertical_speed: f32 },
+    BulletHit,
+    Explosion,
+}
+
+impl HapticEvent {
+    /// (low motor, high motor, seconds) of the pulse this event starts
+    fn pulse(&self) -> (f32, f32, f32) {
+        match *self {
+            HapticEvent::Landing { vertical_speed } => {
+                let strength = 0.25 + 0.75 * (vertical_speed / HARD_LANDING_SPEED).clamp(0.0, 1.0);
+                (strength, strength * 0.4, 0.25)
+            }
+            HapticEvent::BulletHit => (0.3, 1.0, 0.15),
+            HapticEvent::Explosion => (1.0, 0.7, 0.8),
+        }
+    }
+}
+
+/// A controller that can rumble
+/// macroquad has no gamepad API, so controller backends implement this and hand it to `Haptics`
+pub trait RumbleDevice {
+    fn is_connected(&self) -> bool;
+    /// Motor strengths, 0.0 to 1.0 (low is the heavy motor, high the light buzzy one)
+    fn set_rumble(&mut self, low: f32, high: f32);
+}
+
+/// Used when no controller backend is available: never connected, so rumble stays off
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoGamepad;
+
+impl RumbleDevice for NoGamepad {
+    fn is_connected(&self) -> bool {
+        false
+    }
+
+    fn set_rumble(&mut self, _low: f32, _high: f32) {}
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Pulse {
+    low: f32,
+    high: f32,
+    duration: f32,
+    remaining: f32,
+}
+
+/// Rumble mixer for the local player's controller
+pub struct Haptics {
+    device: Box<dyn RumbleDevice>,
+    intensity: f32, // Settings slider, 0.0 (off) to 1.0
+    pulses: Vec<Pulse>,
+    output: (f32, f32),
+}
+
+impl Haptics {
+    pub fn new(device: Box<dyn RumbleDevice>, intensity: f32) -> Self {
+        Haptics { device, intensity: intensity.clamp(0.0, 1.0), pulses: Vec::new(), output: (0.0, 0.0) }
+    }
+
+    /// Haptics without a controller backend (rumble disabled)
+    pub fn disconnected(intensity: f32) -> Self {
+        Self::new(Box::new(NoGamepad), intensity)
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.device.is_connected()
+    }
+
+    pub fn intensity(&self) -> f32 {
+        self.intensity
+    }
+
+    pub fn set_intensity(&mut self, intensity: f32) {
+        self.intensity = intensity.clamp(0.0, 1.0);
+    }
+
+    /// Motor strengths sent last update
+    pub fn output(&self) -> (f32, f32) {
+        self.output
+    }
+
+    /// Start a pulse (ignored while no controller is connected)
+    pub fn trigger(&mut self, event: HapticEvent) {
+        if !self.is_connected() {
+            return;
+        }
+        let (low, high, duration) = event.pulse();
+        self.pulses.push(Pulse { low, high, duration, remaining: duration });
+    }
+
+    /// Age the pulses, mix them with the engine rumble for `throttle` (0.0 to 1.0) and drive the motors
+    pub fn update(&mut self, delta_time: f32, throttle: f32) {
+        if !self.is_connected() {
+            // Unplugged: drop anything queued so it doesn't fire on reconnect
+            self.pulses.clear();
+            self.output = (0.0, 0.0);
+            return;
+        }
+
+        for pulse in &mut self.pulses {
+            pulse.remaining -= delta_time;
+        }
+        self.pulses.retain(|pulse| pulse.remaining > 0.0);
+
+        let throttle = if throttle > THRUST_DEADZONE { throttle.min(1.0) } else { 0.0 };
+        let (mut low, mut high) = (THRUST_RUMBLE.0 * throttle, THRUST_RUMBLE.1 * throttle);
+        for pulse in &self.pulses {
+            // Pulses fade out linearly; overlapping ones take the strongest
+            let fade = pulse.remaining / pulse.duration;
+            low = low.max(pulse.low * fade);
+            high = high.max(pulse.high * fade);
+        }
+
+        let output = (low * self.intensity, high * self.intensity);
+        if output != self.output {
+            self.device.set_rumble(output.0, output.1);
+            self.output = output;
+        }
+    }
+
+    /// Stop the motors (pause, menus, leaving the game)
+    pub fn stop(&mut self) {
+        self.pulses.clear();
+        if self.output != (0.0, 0.0) {
+            self.device.set_rumble(0.0, 0.0);
+            self.output = (0.0, 0.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    type Shared<T> = Rc<Cell<T>>;
+
+    /// Records the last motor command
+    struct TestPad {
+        connected: Shared<bool>,
+        last: Shared<(f32, f32)>,
+    }
+
+    impl RumbleDevice for TestPad {
+        fn is_connected(&self) -> bool {
+            self.connected.get()
+        }
+
+        fn set_rumble(&mut self, low: f32, high: f32) {
+            self.last.set((low, high));
+        }
+    }
+
+    fn haptics(intensity: f32) -> (Haptics, Shared<bool>, Shared<(f32, f32)>) {
+        let connected = Rc::new(Cell::new(true));
+        let last = Rc::new(Cell::new((0.0, 0.0)));
+        let pad = TestPad { connected: connected.clone(), last: last.clone() };
+        (Haptics::new(Box::new(pad), intensity), connected, last)
+    }
+
+    #[test]
+    fn test_thrust_rumble_scales_with_throttle_and_intensity() {
+        let (mut haptics, _, last) = haptics(1.0);
+        haptics.update(0.016, 0.5);
+        let half = last.get().0;
+        haptics.update(0.016, 1.0);
+        assert!((last.get().0 - 2.0 * half).abs() < 1e-5);
+
+        haptics.set_intensity(0.5);
+        haptics.update(0.016, 1.0);
+        assert!((last.get().0 - half).abs() < 1e-5);
+
+        haptics.update(0.016, 0.0);
+        assert_eq!(last.get(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_pulses_fade_out() {
+        let (mut haptics, _, last) = haptics(1.0);
+        haptics.trigger(HapticEvent::Explosion);
+        haptics.update(0.1, 0.0);
+        let strong = last.get().0;
+        haptics.update(0.4, 0.0);
+        assert!(last.get().0 < strong && last.get().0 > 0.0);
+        haptics.update(0.5, 0.0);
+        assert_eq!(last.get(), (0.0, 0.0));
+
+        // Harder landings thump harder
+        let soft = HapticEvent::Landing { vertical_speed: 10.0 }.pulse().0;
+        let hard = HapticEvent::Landing { vertical_speed: 200.0 }.pulse().0;
+        assert!(hard > soft && hard <= 1.0);
+    }
+
+    #[test]
+    fn test_disconnected_pad_is_silent() {
+        let (mut haptics, connected, _) = haptics(1.0);
+        connected.set(false);
+        haptics.trigger(HapticEvent::BulletHit);
+        haptics.update(0.016, 1.0);
+        assert_eq!(haptics.output(), (0.0, 0.0));
+
+        // Nothing queued while unplugged fires on reconnect
+        connected.set(true);
+        haptics.update(0.016, 0.0);
+        assert_eq!(haptics.output(), (0.0, 0.0));
+
+        let mut none = Haptics::disconnected(1.0);
+        none.trigger(HapticEvent::Explosion);
+        none.update(0.016, 1.0);
+        assert!(!none.is_connected());
+        assert_eq!(none.output(), (0.0, 0.0));
+    }
+}
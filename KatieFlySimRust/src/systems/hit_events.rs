@@ -0,0 +1,42 @@
+// Hit Events - Host tells a shooter that their bullet connected
+// The shooter's client flashes a hit marker where the target was; the host shows its own directly
+
+use serde::{Deserialize, Serialize};
+
+use crate::systems::EntityId;
+
+/// Prefix marking a hit event packet (snapshots never start with these bytes)
+const HIT_PACKET_PREFIX: &[u8] = b"HITEVENT";
+
+/// Your bullet hit `rocket_id` at `position` (world coordinates)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HitEventPacket {
+    pub rocket_id: EntityId,
+    pub position: (f32, f32),
+}
+
+impl HitEventPacket {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = HIT_PACKET_PREFIX.to_vec();
+        bytes.extend(bincode::serialize(self).unwrap_or_default());
+        bytes
+    }
+
+    /// None if the bytes aren't a hit event packet
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let payload = bytes.strip_prefix(HIT_PACKET_PREFIX)?;
+        bincode::deserialize(payload).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packet_round_trip() {
+        let packet = HitEventPacket { rocket_id: 12, position: (-40.0, 7.5) };
+        assert_eq!(HitEventPacket::from_bytes(&packet.to_bytes()), Some(packet));
+        assert_eq!(HitEventPacket::from_bytes(b"KEEPALIVE"), None);
+    }
+}
@@ -0,0 +1,253 @@
+// Hot Reload - Dev-mode watcher for map files and constants.ron (`hot-reload` feature)
+// Polls modification times, so edits saved from any editor or KatieMapMaker show up in the running game
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::map_config::{CelestialBodyConfig, MapConfiguration, MapTunables};
+
+/// Dev overrides of the map tunables, applied on top of whatever map is running
+pub const CONSTANTS_FILE: &str = "constants.ron";
+/// Folder holding custom maps
+const MAPS_FOLDER: &str = "maps";
+/// Seconds between checks of the watched files
+const POLL_INTERVAL: f32 = 0.5;
+
+/// Something changed on disk
+#[derive(Debug, Clone)]
+pub enum ReloadEvent {
+    /// The running map's file was saved (freshly loaded, without constants.ron overrides)
+    Map(Box<MapConfiguration>),
+    /// constants.ron was saved; see `HotReloader::overrides`
+    Constants,
+    /// A watched file changed but couldn't be loaded
+    Failed(String),
+}
+
+/// How a reloaded map can be brought into a running world
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapReload {
+    /// Bodies were added, removed, renamed or reordered; only a fresh start matches the file
+    Restart,
+    /// Same bodies: update them in place, re-deriving position and velocity for these (by index)
+    Update { rederive: Vec<usize> },
+}
+
+/// Decide how to apply `new` over a world built from `old`
+pub fn plan_map_reload(old: &MapConfiguration, new: &MapConfiguration) -> MapReload {
+    let same_bodies = old.celestial_bodies.len() == new.celestial_bodies.len()
+        && old.celestial_bodies.iter().zip(&new.celestial_bodies).all(|(a, b)| a.name == b.name)
+        && old.player_spawn_body_index == new.player_spawn_body_index;
+    if !same_bodies {
+        return MapReload::Restart;
+    }
+
+    let orbit_changed = |a: &CelestialBodyConfig, b: &CelestialBodyConfig| {
+        a.orbital_parent_index != b.orbital_parent_index
+            || a.orbital_distance != b.orbital_distance
+            || a.orbital_period != b.orbital_period
+            || a.initial_angle != b.initial_angle
+            || a.is_pinned != b.is_pinned
+    };
+    let mut rederive: Vec<usize> = Vec::new();
+    for (i, (a, b)) in old.celestial_bodies.iter().zip(&new.celestial_bodies).enumerate() {
+        // A body moves with its parent, so a re-derived parent drags its moons along
+        let parent_moved = b.orbital_parent_index.is_some_and(|parent| rederive.contains(&parent));
+        let parent_mass_changed = b
+            .orbital_parent_index
+            .is_some_and(|parent| old.celestial_bodies.get(parent).map(|p| p.mass) != new.celestial_bodies.get(parent).map(|p| p.mass));
+        if orbit_changed(a, b) || parent_moved || parent_mass_changed {
+            rederive.push(i);
+        }
+    }
+    MapReload::Update { rederive }
+}
+
+/// `base` with every field `overrides` sets replaced
+pub fn overlay_tunables(base: &MapTunables, overrides: &MapTunables) -> MapTunables {
+    MapTunables {
+        rocket_base_mass: overrides.rocket_base_mass.or(base.rocket_base_mass),
+        rocket_max_fuel: overrides.rocket_max_fuel.or(base.rocket_max_fuel),
+        bullet_speed: overrides.bullet_speed.or(base.bullet_speed),
+        spawn_altitude: overrides.spawn_altitude.or(base.spawn_altitude),
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// Watches the maps folder and constants.ron
+pub struct HotReloader {
+    maps_folder: PathBuf,
+    constants_path: PathBuf,
+    map_times: HashMap<PathBuf, SystemTime>,
+    constants_time: Option<SystemTime>,
+    overrides: MapTunables,
+    poll_timer: f32,
+}
+
+impl HotReloader {
+    pub fn new() -> Self {
+        Self::watching(MAPS_FOLDER, CONSTANTS_FILE)
+    }
+
+    /// Watch other locations (tests and tools)
+    pub fn watching(maps_folder: impl Into<PathBuf>, constants_path: impl Into<PathBuf>) -> Self {
+        let mut reloader = HotReloader {
+            maps_folder: maps_folder.into(),
+            constants_path: constants_path.into(),
+            map_times: HashMap::new(),
+            constants_time: None,
+            overrides: MapTunables::default(),
+            poll_timer: 0.0,
+        };
+        // Maps already on disk are the ones the game started with; constants.ron applies from the first poll
+        reloader.map_times = reloader.map_files().into_iter().filter_map(|path| Some((path.clone(), modified(&path)?))).collect();
+        reloader
+    }
+
+    /// Current constants.ron overrides (empty when the file is missing)
+    pub fn overrides(&self) -> &MapTunables {
+        &self.overrides
+    }
+
+    fn map_files(&self) -> Vec<PathBuf> {
+        fs::read_dir(&self.maps_folder)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| crate::schemas::SchemaFormat::from_path(&path.to_string_lossy()).is_some())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Check the watched files every half second; `map_name` is the running map
+    pub fn poll(&mut self, delta_time: f32, map_name: &str) -> Vec<ReloadEvent> {
+        self.poll_timer += delta_time;
+        if self.poll_timer < POLL_INTERVAL {
+            return Vec::new();
+        }
+        self.poll_timer = 0.0;
+        self.check_now(map_name)
+    }
+
+    /// Check the watched files right away
+    pub fn check_now(&mut self, map_name: &str) -> Vec<ReloadEvent> {
+        let mut events = Vec::new();
+
+        let constants_time = modified(&self.constants_path);
+        if constants_time != self.constants_time {
+            self.constants_time = constants_time;
+            let loaded = match constants_time {
+                Some(_) => fs::read_to_string(&self.constants_path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|text| ron::from_str::<MapTunables>(&text).map_err(|e| e.to_string())),
+                None => Ok(MapTunables::default()), // Deleted: back to the map's own values
+            };
+            match loaded {
+                Ok(overrides) => {
+                    self.overrides = overrides;
+                    events.push(ReloadEvent::Constants);
+                }
+                Err(e) => events.push(ReloadEvent::Failed(format!("{}: {}", self.constants_path.display(), e))),
+            }
+        }
+
+        for path in self.map_files() {
+            let Some(time) = modified(&path) else {
+                continue;
+            };
+            if self.map_times.insert(path.clone(), time) == Some(time) {
+                continue;
+            }
+            match MapConfiguration::load_from_file(&path.to_string_lossy()) {
+                Ok(map) if map.name == map_name => events.push(ReloadEvent::Map(Box::new(map))),
+                Ok(map) => log::info!("Map '{}' changed on disk (not the running map)", map.name),
+                Err(e) => events.push(ReloadEvent::Failed(format!("{}: {}", path.display(), e))),
+            }
+        }
+
+        events
+    }
+}
+
+impl Default for HotReloader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_keeps_bodies_and_rederives_changed_orbits() {
+        let old = MapConfiguration::earth_moon();
+        let mut new = old.clone();
+        assert_eq!(plan_map_reload(&old, &new), MapReload::Update { rederive: vec![] });
+
+        // Heavier Earth: the Moon's orbit needs a new velocity
+        new.celestial_bodies[0].mass *= 2.0;
+        assert_eq!(plan_map_reload(&old, &new), MapReload::Update { rederive: vec![1] });
+
+        new = old.clone();
+        new.celestial_bodies[1].initial_angle += 1.0;
+        assert_eq!(plan_map_reload(&old, &new), MapReload::Update { rederive: vec![1] });
+
+        new.celestial_bodies.pop();
+        assert_eq!(plan_map_reload(&old, &new), MapReload::Restart);
+    }
+
+    #[test]
+    fn test_overlay_replaces_only_set_fields() {
+        let base = MapTunables { bullet_speed: Some(300.0), spawn_altitude: Some(50.0), ..Default::default() };
+        let overrides = MapTunables { bullet_speed: Some(900.0), rocket_max_fuel: Some(10.0), ..Default::default() };
+        let merged = overlay_tunables(&base, &overrides);
+        assert_eq!(merged.bullet_speed, Some(900.0));
+        assert_eq!(merged.spawn_altitude, Some(50.0));
+        assert_eq!(merged.rocket_max_fuel, Some(10.0));
+        assert_eq!(merged.rocket_base_mass, None);
+    }
+
+    #[test]
+    fn test_shipped_constants_file_overrides_nothing() {
+        let overrides: MapTunables = ron::from_str(include_str!("../../constants.ron")).unwrap();
+        assert_eq!(overrides, MapTunables::default());
+    }
+
+    #[test]
+    fn test_detects_constant_and_map_edits() {
+        let dir = std::env::temp_dir().join(format!("katie_hot_reload_{}", std::process::id()));
+        let maps = dir.join("maps");
+        fs::create_dir_all(&maps).unwrap();
+        let constants = dir.join("constants.ron");
+        let map_path = maps.join("test.ron");
+        let mut map = MapConfiguration::earth_moon();
+        map.name = "Reload Test".to_string();
+        map.save_to_file(&map_path.to_string_lossy()).unwrap();
+
+        let mut reloader = HotReloader::watching(&maps, &constants);
+        assert!(reloader.check_now("Reload Test").is_empty());
+
+        fs::write(&constants, "(bullet_speed: Some(750.0))").unwrap();
+        let events = reloader.check_now("Reload Test");
+        assert!(matches!(events.as_slice(), [ReloadEvent::Constants]));
+        assert_eq!(reloader.overrides().bullet_speed, Some(750.0));
+
+        // Make sure the rewrite gets a different timestamp even on coarse filesystems
+        map.description = "edited".to_string();
+        map.save_to_file(&map_path.to_string_lossy()).unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(2);
+        fs::File::options().write(true).open(&map_path).unwrap().set_modified(later).unwrap();
+        let events = reloader.check_now("Reload Test");
+        assert!(matches!(events.as_slice(), [ReloadEvent::Map(reloaded)] if reloaded.description == "edited"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
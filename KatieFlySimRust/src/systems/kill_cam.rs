@@ -0,0 +1,288 @@
+// Kill Cam - Replays the last seconds before a rocket was shot down
+// A rolling history of rocket and bullet positions; when a bullet kills us, the clip around its
+// flight is cut from it and played back from the shooter's side
+
+use std::collections::VecDeque;
+
+use macroquad::prelude::{Color, Vec2};
+
+use crate::entities::GameObject;
+use crate::systems::{EntityId, KillShot, World};
+
+/// Length of the replay (seconds)
+pub const KILL_CAM_DURATION: f32 = 5.0;
+/// History samples per second
+const SAMPLE_INTERVAL: f32 = 0.05;
+/// History kept, a little longer than a replay
+const HISTORY_SECONDS: f32 = KILL_CAM_DURATION + 1.0;
+/// Smallest view radius around the shooter, so point-blank kills aren't a blur
+const MIN_VIEW_RADIUS: f32 = 150.0;
+
+/// A rocket in one history sample
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RocketState {
+    pub id: EntityId,
+    pub position: Vec2,
+    pub rotation: f32,
+    pub color: Color,
+}
+
+/// Rockets and bullets at one moment
+#[derive(Debug, Clone, PartialEq)]
+struct Frame {
+    time: f32,
+    rockets: Vec<RocketState>,
+    bullets: Vec<(EntityId, Vec2)>,
+}
+
+impl Frame {
+    fn rocket(&self, id: EntityId) -> Option<&RocketState> {
+        self.rockets.iter().find(|rocket| rocket.id == id)
+    }
+
+    fn bullet(&self, id: EntityId) -> Option<Vec2> {
+        self.bullets.iter().find(|(bullet_id, _)| *bullet_id == id).map(|(_, position)| *position)
+    }
+}
+
+/// Rolling buffer of recent world states
+#[derive(Debug, Clone, Default)]
+pub struct StateHistory {
+    frames: VecDeque<Frame>,
+    last_sample: Option<f32>,
+}
+
+impl StateHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sample the world at `time` (game seconds) if a sample is due
+    pub fn record(&mut self, world: &World, time: f32) {
+        // (Small slack so a fixed step of exactly the interval isn't skipped by rounding; going back in time resamples)
+        let due = self.last_sample.is_none_or(|last| time < last || time - last >= SAMPLE_INTERVAL - 1e-4);
+        if !due {
+            return;
+        }
+        self.last_sample = Some(time);
+        self.frames.push_back(Frame {
+            time,
+            rockets: world
+                .rockets_with_ids()
+                .map(|(id, rocket)| RocketState { id, position: rocket.position(), rotation: rocket.rotation(), color: rocket.color() })
+                .collect(),
+            bullets: world.bullets_with_ids().map(|(id, bullet)| (id, bullet.position())).collect(),
+        });
+        while self.frames.front().is_some_and(|frame| time - frame.time > HISTORY_SECONDS || frame.time > time) {
+            self.frames.pop_front();
+        }
+    }
+
+    /// Follow a floating origin rebase (positions moved by `-shift`)
+    pub fn shift_positions(&mut self, shift: Vec2) {
+        for frame in &mut self.frames {
+            for rocket in &mut frame.rockets {
+                rocket.position -= shift;
+            }
+            for (_, position) in &mut frame.bullets {
+                *position -= shift;
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.last_sample = None;
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// What the replay shows at one moment
+#[derive(Debug, Clone, PartialEq)]
+pub struct KillCamView {
+    pub bullet: Option<Vec2>,
+    pub trail: Vec<Vec2>, // Bullet path so far
+    pub victim: Option<RocketState>,
+    pub shooter: Option<RocketState>,
+    pub impact: bool, // The last moment: show the explosion
+}
+
+/// A replay being played back
+#[derive(Debug, Clone)]
+pub struct KillCam {
+    frames: Vec<Frame>,
+    victim_id: EntityId,
+    shot: KillShot,
+    impact_position: Vec2,
+    pub attacker: String,
+    elapsed: f32,
+}
+
+impl KillCam {
+    /// Cut the replay of `shot` killing `victim_id` at `impact_position`; None without history of that bullet
+    pub fn from_history(history: &StateHistory, victim_id: EntityId, shot: KillShot, impact_position: Vec2, attacker: String) -> Option<Self> {
+        let end = history.frames.back()?.time;
+        let frames: Vec<Frame> = history.frames.iter().filter(|frame| end - frame.time <= KILL_CAM_DURATION).cloned().collect();
+        frames.iter().any(|frame| frame.bullet(shot.bullet_id).is_some()).then_some(KillCam {
+            frames,
+            victim_id,
+            shot,
+            impact_position,
+            attacker,
+            elapsed: 0.0,
+        })
+    }
+
+    /// Advance playback; false once it has finished
+    pub fn update(&mut self, delta_time: f32) -> bool {
+        self.elapsed += delta_time;
+        !self.is_finished()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration()
+    }
+
+    /// Clip length (shorter if the history was)
+    pub fn duration(&self) -> f32 {
+        match (self.frames.first(), self.frames.last()) {
+            (Some(first), Some(last)) => (last.time - first.time).max(SAMPLE_INTERVAL),
+            _ => 0.0,
+        }
+    }
+
+    pub fn progress(&self) -> f32 {
+        (self.elapsed / self.duration().max(f32::EPSILON)).clamp(0.0, 1.0)
+    }
+
+    /// Where the shooter was when the clip starts (or the bullet's first position): the camera's anchor
+    pub fn view_center(&self) -> Vec2 {
+        let shooter = self.shot.shooter_id.and_then(|id| self.frames.iter().find_map(|frame| frame.rocket(id)));
+        shooter
+            .map(|rocket| rocket.position)
+            .or_else(|| self.frames.iter().find_map(|frame| frame.bullet(self.shot.bullet_id)))
+            .unwrap_or(self.impact_position)
+    }
+
+    /// Radius around `view_center` that takes in the whole bullet path and the victim
+    pub fn view_radius(&self) -> f32 {
+        let center = self.view_center();
+        let farthest = self
+            .frames
+            .iter()
+            .flat_map(|frame| frame.bullet(self.shot.bullet_id).into_iter().chain(frame.rocket(self.victim_id).map(|rocket| rocket.position)))
+            .chain(std::iter::once(self.impact_position))
+            .map(|position| position.distance(center))
+            .fold(0.0_f32, f32::max);
+        farthest.max(MIN_VIEW_RADIUS)
+    }
+
+    /// The scene at the current playback time
+    pub fn view(&self) -> KillCamView {
+        let Some(first) = self.frames.first() else {
+            return KillCamView { bullet: None, trail: Vec::new(), victim: None, shooter: None, impact: true };
+        };
+        let time = first.time + self.elapsed.min(self.duration());
+        // Last sample at or before the playback time, and how far toward the next one we are
+        let index = self.frames.iter().rposition(|frame| frame.time <= time).unwrap_or(0);
+        let frame = &self.frames[index];
+        let next = self.frames.get(index + 1);
+        let t = next.map_or(0.0, |next| ((time - frame.time) / (next.time - frame.time).max(f32::EPSILON)).clamp(0.0, 1.0));
+
+        let lerp_rocket = |id: EntityId| -> Option<RocketState> {
+            let now = *frame.rocket(id)?;
+            let position = next.and_then(|next| next.rocket(id)).map_or(now.position, |later| now.position.lerp(later.position, t));
+            Some(RocketState { position, ..now })
+        };
+        let bullet_id = self.shot.bullet_id;
+        let bullet = frame.bullet(bullet_id).map(|now| next.and_then(|next| next.bullet(bullet_id)).map_or(now, |later| now.lerp(later, t)));
+        let mut trail: Vec<Vec2> = self.frames[..=index].iter().filter_map(|frame| frame.bullet(bullet_id)).collect();
+        trail.extend(bullet);
+
+        KillCamView {
+            bullet,
+            trail,
+            victim: lerp_rocket(self.victim_id),
+            shooter: self.shot.shooter_id.and_then(lerp_rocket),
+            impact: self.is_finished() || index + 1 >= self.frames.len(),
+        }
+    }
+
+    /// Where the rocket blew up
+    pub fn impact_position(&self) -> Vec2 {
+        self.impact_position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Bullet, Planet, Rocket};
+    use macroquad::prelude::{BLUE, RED};
+
+    fn shot_world() -> (World, EntityId, EntityId, EntityId) {
+        let mut world = World::new();
+        world.add_planet(Planet::new(Vec2::new(0.0, 100000.0), 10.0, 1.0, BLUE));
+        let shooter = world.add_rocket(Rocket::new(Vec2::ZERO, Vec2::ZERO, RED, 1.0));
+        let victim = world.add_rocket(Rocket::new(Vec2::new(500.0, 0.0), Vec2::ZERO, BLUE, 1.0));
+        let mut bullet = Bullet::new(Vec2::new(20.0, 0.0), Vec2::new(100.0, 0.0));
+        bullet.set_shooter(Some(shooter));
+        let bullet = world.add_bullet(bullet);
+        (world, shooter, victim, bullet)
+    }
+
+    #[test]
+    fn test_history_keeps_a_rolling_window() {
+        let (world, ..) = shot_world();
+        let mut history = StateHistory::new();
+        for step in 0..=1000 {
+            history.record(&world, step as f32 * 0.01);
+        }
+        // Most frames are skipped by the sample interval; old samples fall off the front
+        let oldest = history.frames.front().unwrap().time;
+        assert!((10.0 - HISTORY_SECONDS..10.0 - HISTORY_SECONDS + 0.1).contains(&oldest));
+        assert!(history.len() <= (HISTORY_SECONDS / SAMPLE_INTERVAL) as usize + 1);
+
+        // Loading an earlier save starts the history over
+        history.record(&world, 1.0);
+        assert_eq!(history.len(), 1);
+    }
+
+    #[test]
+    fn test_replay_follows_the_bullet_from_the_shooter() {
+        let (mut world, shooter, victim, bullet) = shot_world();
+        let mut history = StateHistory::new();
+        for step in 0..=100 {
+            let time = step as f32 * SAMPLE_INTERVAL;
+            let position = Vec2::new(20.0 + 100.0 * time, 0.0);
+            world.get_bullet_mut(bullet).unwrap().set_position(position);
+            history.record(&world, time);
+        }
+
+        let shot = KillShot { bullet_id: bullet, shooter_id: Some(shooter), shooter_player: None };
+        let mut cam = KillCam::from_history(&history, victim, shot, Vec2::new(500.0, 0.0), "you".to_string()).unwrap();
+        assert!((cam.duration() - KILL_CAM_DURATION).abs() < 0.01);
+        assert_eq!(cam.view_center(), Vec2::ZERO);
+        assert!(cam.view_radius() >= 500.0);
+
+        let start = cam.view().bullet.unwrap().x;
+        assert!(cam.update(2.0));
+        let view = cam.view();
+        assert!((view.bullet.unwrap().x - (start + 200.0)).abs() < 1.0);
+        assert!(view.trail.len() > 10 && view.shooter.is_some() && view.victim.is_some() && !view.impact);
+
+        assert!(!cam.update(KILL_CAM_DURATION));
+        assert!(cam.view().impact);
+
+        // A bullet the history never saw has nothing to replay
+        let unseen = KillShot { bullet_id: 999, ..shot };
+        assert!(KillCam::from_history(&history, victim, unseen, Vec2::ZERO, String::new()).is_none());
+    }
+}
@@ -0,0 +1,173 @@
+// Lag Compensation - Host-side rewind so client shots land where the shooter saw their target
+// A client's shot reaches the host about a round trip (plus the client's interpolation delay) after its
+// screen showed the target, so the bullet's first moments are checked against where craft were back then
+
+use std::collections::{HashMap, VecDeque};
+
+use macroquad::prelude::*;
+
+use crate::entities::GameObject;
+use crate::physics::collision;
+use crate::systems::snapshot_interpolation::INTERPOLATION_DELAY;
+use crate::systems::{BulletTarget, EntityId, World};
+
+/// Longest rewind granted, however bad the shooter's link (seconds)
+pub const MAX_REWIND: f32 = 0.5;
+/// History kept, with some slack past the longest rewind (seconds)
+const HISTORY_SECONDS: f32 = MAX_REWIND + 0.25;
+/// Same hitboxes as the tick's bullet checks
+const ROCKET_RADIUS: f32 = 12.0;
+const SATELLITE_RADIUS: f32 = 7.0;
+
+#[derive(Debug, Clone, Default)]
+struct Frame {
+    time: f32, // Host session time
+    rockets: HashMap<EntityId, Vec2>,
+    satellites: HashMap<EntityId, Vec2>,
+}
+
+/// Recent rocket and satellite positions on the host, one frame per tick
+#[derive(Debug, Clone, Default)]
+pub struct LagCompensation {
+    frames: VecDeque<Frame>, // Oldest first
+}
+
+impl LagCompensation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How far back a client's shot is checked: its round trip plus the delay it draws other craft at
+    pub fn shot_lag(rtt: f32) -> f32 {
+        (rtt + INTERPOLATION_DELAY).clamp(0.0, MAX_REWIND)
+    }
+
+    /// Store the world's craft positions after a tick (going back in time starts over)
+    pub fn record(&mut self, world: &World, time: f32) {
+        if self.frames.back().is_some_and(|latest| time <= latest.time) {
+            self.frames.clear();
+        }
+        self.frames.push_back(Frame {
+            time,
+            rockets: world.rockets_with_ids().map(|(id, rocket)| (id, rocket.position())).collect(),
+            satellites: world.satellites_with_ids().map(|(id, satellite)| (id, satellite.position())).collect(),
+        });
+        while self.frames.front().is_some_and(|frame| time - frame.time > HISTORY_SECONDS) {
+            self.frames.pop_front();
+        }
+    }
+
+    /// Follow a floating origin rebase (positions moved by `-shift`)
+    pub fn shift_positions(&mut self, shift: Vec2) {
+        for frame in &mut self.frames {
+            for position in frame.rockets.values_mut().chain(frame.satellites.values_mut()) {
+                *position -= shift;
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Fly a freshly fired bullet through the last `lag` seconds of history, against craft where they were
+    /// A hit is applied to the world and returned; a miss leaves the bullet `lag` seconds along its path, where
+    /// the shooter expects it (gravity is ignored over the window)
+    pub fn resolve_shot(&self, world: &mut World, bullet_id: EntityId, lag: f32, now: f32) -> Option<BulletTarget> {
+        let lag = lag.clamp(0.0, MAX_REWIND);
+        let bullet = world.get_bullet(bullet_id)?;
+        let (spawn, velocity, size, shooter) = (bullet.position(), bullet.velocity(), bullet.size(), bullet.shooter());
+        let start = now - lag;
+        let bullet_at = |time: f32| spawn + velocity * (time - start);
+
+        // Frames covering the window, starting from the last one at or before it begins
+        let first = self.frames.iter().rposition(|frame| frame.time <= start).unwrap_or(0);
+        let frames: Vec<&Frame> = self.frames.iter().skip(first).filter(|frame| frame.time <= now).collect();
+
+        for pair in frames.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            let from_time = from.time.max(start);
+            let span = (to.time - from.time).max(f32::EPSILON);
+            let lerp = |a: Vec2, b: Vec2, time: f32| a.lerp(b, ((time - from.time) / span).clamp(0.0, 1.0));
+            let (bullet_from, bullet_to) = (bullet_at(from_time), bullet_at(to.time));
+
+            // Earliest contact within this stretch of history
+            let mut hit: Option<(f32, BulletTarget)> = None;
+            let mut check = |target: BulletTarget, a: Vec2, b: Vec2, radius: f32| {
+                let (target_from, target_to) = (lerp(a, b, from_time), b);
+                if let Some(t) = collision::swept_circle_hit(bullet_from, bullet_to, target_from, target_to, radius + size) {
+                    if hit.is_none_or(|(earliest, _)| t < earliest) {
+                        hit = Some((t, target));
+                    }
+                }
+            };
+            for (id, a) in &from.rockets {
+                // The shooter's own rocket is never rewound, and landed rockets are safe as in the tick
+                let live = world.get_rocket(*id).filter(|rocket| !rocket.is_landed());
+                if let (Some(b), Some(_)) = (to.rockets.get(id), live.filter(|_| Some(*id) != shooter)) {
+                    check(BulletTarget::Rocket(*id), *a, *b, ROCKET_RADIUS);
+                }
+            }
+            for (id, a) in &from.satellites {
+                if let (Some(b), Some(_)) = (to.satellites.get(id), world.get_satellite(*id)) {
+                    check(BulletTarget::Satellite(*id), *a, *b, SATELLITE_RADIUS);
+                }
+            }
+
+            if let Some((t, target)) = hit {
+                if let Some(bullet) = world.get_bullet_mut(bullet_id) {
+                    bullet.set_position(bullet_from.lerp(bullet_to, t));
+                }
+                world.apply_bullet_hit(bullet_id, target);
+                return Some(target);
+            }
+        }
+
+        if let Some(bullet) = world.get_bullet_mut(bullet_id) {
+            bullet.set_position(bullet_at(now));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Bullet, Rocket};
+
+    #[test]
+    fn test_rewound_target_is_hit() {
+        let mut world = World::new();
+        let shooter = world.add_rocket(Rocket::new(Vec2::ZERO, Vec2::ZERO, WHITE, 1.0));
+        let target = world.add_rocket(Rocket::new(Vec2::new(100.0, 0.0), Vec2::ZERO, WHITE, 1.0));
+        world.get_rocket_mut(target).unwrap().take_off();
+
+        // The target crossed the line of fire 0.1 s ago and has since moved clear of it
+        let mut history = LagCompensation::new();
+        for step in 0..=10 {
+            let y = step as f32 * 20.0 - 160.0;
+            world.get_rocket_mut(target).unwrap().set_position(Vec2::new(100.0, y));
+            history.record(&world, step as f32 * 0.05);
+        }
+        let fire = |world: &mut World| {
+            let mut bullet = Bullet::new(Vec2::new(20.0, 0.0), Vec2::new(400.0, 0.0));
+            bullet.set_shooter(Some(shooter));
+            world.add_bullet(bullet)
+        };
+
+        // Without rewinding, the bullet flies on past where the target now is
+        let bullet = fire(&mut world);
+        assert_eq!(history.resolve_shot(&mut world, bullet, 0.0, 0.5), None);
+        assert_eq!(world.get_bullet(bullet).unwrap().position(), Vec2::new(20.0, 0.0));
+        world.remove_bullet(bullet);
+
+        // Rewound by the shooter's lag, it meets the target where the shooter saw it
+        let bullet = fire(&mut world);
+        assert_eq!(history.resolve_shot(&mut world, bullet, 0.3, 0.5), Some(BulletTarget::Rocket(target)));
+        assert!(world.get_bullet(bullet).is_none());
+        assert!(world.get_rocket(target).is_none());
+        let hit = world.take_hit_events();
+        assert_eq!(hit.len(), 1);
+        assert_eq!(hit[0].shooter_id, Some(shooter));
+    }
+}
@@ -0,0 +1,193 @@
+// Landing Evaluator - Grades touchdowns from approach speed and tilt
+// Vertical/horizontal speeds are measured relative to the planet's surface frame
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Landing quality grade, best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum LandingGrade {
+    Perfect,
+    Good,
+    Hard,
+    Crash,
+}
+
+impl LandingGrade {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LandingGrade::Perfect => "PERFECT",
+            LandingGrade::Good => "GOOD",
+            LandingGrade::Hard => "HARD",
+            LandingGrade::Crash => "CRASH",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            LandingGrade::Perfect => Color::new(0.3, 1.0, 0.5, 1.0), // Green
+            LandingGrade::Good => Color::new(0.5, 0.8, 1.0, 1.0),    // Light blue
+            LandingGrade::Hard => Color::new(1.0, 0.7, 0.2, 1.0),    // Orange
+            LandingGrade::Crash => Color::new(1.0, 0.25, 0.25, 1.0), // Red
+        }
+    }
+}
+
+/// Grade thresholds: (max vertical speed, max horizontal speed, max tilt degrees)
+const PERFECT_LIMITS: (f32, f32, f32) = (20.0, 10.0, 10.0);
+const GOOD_LIMITS: (f32, f32, f32) = (50.0, 30.0, 25.0);
+const HARD_LIMITS: (f32, f32, f32) = (120.0, 80.0, 45.0);
+
+/// Measured touchdown state and its grade
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LandingResult {
+    pub vertical_speed: f32,   // Speed into the surface (m/s)
+    pub horizontal_speed: f32, // Speed along the surface (m/s)
+    pub tilt_degrees: f32,     // Angle between rocket nose and local "up"
+    pub grade: LandingGrade,
+    pub score: f32,            // 0-100, higher is better
+}
+
+impl LandingResult {
+    /// Evaluate a touchdown from rocket and planet state just before contact
+    pub fn evaluate(
+        rocket_position: Vec2,
+        rocket_velocity: Vec2,
+        rocket_rotation: f32,
+        planet_position: Vec2,
+        planet_velocity: Vec2,
+    ) -> Self {
+        let up = (rocket_position - planet_position).normalize_or_zero();
+        Self::evaluate_on_surface(rocket_velocity, rocket_rotation, up, planet_velocity)
+    }
+
+    /// Evaluate a touchdown against the local surface normal (slopes count as tilt)
+    pub fn evaluate_on_surface(
+        rocket_velocity: Vec2,
+        rocket_rotation: f32,
+        surface_normal: Vec2,
+        planet_velocity: Vec2,
+    ) -> Self {
+        let up = surface_normal.normalize_or_zero();
+        let tangent = Vec2::new(-up.y, up.x);
+        let relative_velocity = rocket_velocity - planet_velocity;
+
+        // Positive when moving into the surface
+        let vertical_speed = (-relative_velocity.dot(up)).max(0.0);
+        let horizontal_speed = relative_velocity.dot(tangent).abs();
+
+        // Rocket nose points in direction (sin(rotation), -cos(rotation))
+        let nose = Vec2::new(rocket_rotation.sin(), -rocket_rotation.cos());
+        let tilt_degrees = nose.dot(up).clamp(-1.0, 1.0).acos().to_degrees();
+
+        let grade = Self::grade_for(vertical_speed, horizontal_speed, tilt_degrees);
+
+        // Score: each axis contributes up to a third, scaled against the "hard" limits
+        let axis_score = |value: f32, limit: f32| (1.0 - value / limit).clamp(0.0, 1.0);
+        let score = 100.0 / 3.0
+            * (axis_score(vertical_speed, HARD_LIMITS.0)
+                + axis_score(horizontal_speed, HARD_LIMITS.1)
+                + axis_score(tilt_degrees, HARD_LIMITS.2));
+
+        LandingResult {
+            vertical_speed,
+            horizontal_speed,
+            tilt_degrees,
+            grade,
+            score,
+        }
+    }
+
+    fn grade_for(vertical_speed: f32, horizontal_speed: f32, tilt_degrees: f32) -> LandingGrade {
+        let within = |limits: (f32, f32, f32)| {
+            vertical_speed <= limits.0 && horizontal_speed <= limits.1 && tilt_degrees <= limits.2
+        };
+
+        if within(PERFECT_LIMITS) {
+            LandingGrade::Perfect
+        } else if within(GOOD_LIMITS) {
+            LandingGrade::Good
+        } else if within(HARD_LIMITS) {
+            LandingGrade::Hard
+        } else {
+            LandingGrade::Crash
+        }
+    }
+
+    /// One-line toast text, e.g. "GOOD LANDING on Moon  V 32 | H 12 | Tilt 8°"
+    pub fn toast_text(&self, planet_name: &str, new_best: bool) -> String {
+        format!(
+            "{} LANDING on {}  V {:.0} | H {:.0} | Tilt {:.0}°{}",
+            self.grade.label(),
+            planet_name,
+            self.vertical_speed,
+            self.horizontal_speed,
+            self.tilt_degrees,
+            if new_best { "  - NEW BEST!" } else { "" }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gentle_upright_landing_is_perfect() {
+        // Rocket directly above planet (negative Y is up), nose up, slow descent
+        let result = LandingResult::evaluate(
+            Vec2::new(0.0, -100.0),
+            Vec2::new(0.0, 5.0),
+            0.0,
+            Vec2::ZERO,
+            Vec2::ZERO,
+        );
+        assert_eq!(result.grade, LandingGrade::Perfect);
+        assert!((result.vertical_speed - 5.0).abs() < 0.01);
+        assert!(result.horizontal_speed < 0.01);
+        assert!(result.tilt_degrees < 0.1);
+        assert!(result.score > 90.0);
+    }
+
+    #[test]
+    fn test_fast_sideways_landing_is_crash() {
+        let result = LandingResult::evaluate(
+            Vec2::new(0.0, -100.0),
+            Vec2::new(200.0, 150.0),
+            std::f32::consts::FRAC_PI_2,
+            Vec2::ZERO,
+            Vec2::ZERO,
+        );
+        assert_eq!(result.grade, LandingGrade::Crash);
+        assert!((result.tilt_degrees - 90.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_speeds_relative_to_moving_planet() {
+        // Planet and rocket moving together - should be a perfect landing
+        let result = LandingResult::evaluate(
+            Vec2::new(100.0, 0.0),
+            Vec2::new(500.0, 300.0),
+            std::f32::consts::FRAC_PI_2,
+            Vec2::ZERO,
+            Vec2::new(500.0, 300.0),
+        );
+        assert_eq!(result.grade, LandingGrade::Perfect);
+    }
+
+    #[test]
+    fn test_slope_counts_as_tilt() {
+        // Nose straight "up" (negative Y) onto a surface tilted 30 degrees
+        let slope = 30.0f32.to_radians();
+        let normal = Vec2::new(slope.sin(), -slope.cos());
+        let result = LandingResult::evaluate_on_surface(Vec2::new(0.0, 5.0), 0.0, normal, Vec2::ZERO);
+        assert!((result.tilt_degrees - 30.0).abs() < 0.1);
+        assert_eq!(result.grade, LandingGrade::Hard);
+    }
+
+    #[test]
+    fn test_grade_ordering() {
+        assert!(LandingGrade::Perfect < LandingGrade::Good);
+        assert!(LandingGrade::Hard < LandingGrade::Crash);
+    }
+}
@@ -0,0 +1,239 @@
+// Landing Zones - Precision landing targets on planet surfaces, from the map or marked by the player
+// A touchdown inside a zone scores 0-100 by its distance along the surface from the zone's center
+
+use macroquad::prelude::*;
+
+use crate::entities::Planet;
+use crate::map_config::MapConfiguration;
+use crate::systems::{EntityId, LandingEvent, LandingGrade, World};
+use crate::utils::vector_helper;
+
+/// Radius of the zone the player marks (Shift+B)
+pub const PLAYER_ZONE_RADIUS: f32 = 250.0;
+/// Name of the player's zone; there is one at a time and marking again moves it
+pub const PLAYER_ZONE_NAME: &str = "Marked target";
+const ZONE_COLOR: Color = Color::new(1.0, 0.55, 0.1, 0.9);
+const INSIDE_COLOR: Color = Color::new(0.3, 1.0, 0.5, 0.9);
+
+/// A target area on a planet surface
+#[derive(Debug, Clone, PartialEq)]
+pub struct LandingZone {
+    pub name: String,
+    pub body_name: String,
+    pub angle: f32,  // Center, radians from the planet's center
+    pub radius: f32, // Along the surface
+    pub player_marked: bool,
+}
+
+/// A touchdown inside a zone
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneLanding {
+    pub zone: String,
+    pub body_name: String,
+    pub distance: f32, // Along the surface from the center
+    pub score: f32,    // 0-100, 100 dead center
+    pub player_marked: bool,
+}
+
+/// A rocket coming down toward a zone
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneApproach {
+    pub zone: String,
+    pub distance: f32, // Downrange along the surface to the center
+    pub altitude: f32,
+    pub inside: bool, // Directly above the zone
+}
+
+/// The session's landing zones: the map's, plus the one the player marked
+#[derive(Debug, Clone, Default)]
+pub struct LandingZones {
+    zones: Vec<LandingZone>,
+}
+
+impl LandingZones {
+    /// The map's zones (empty if it has none)
+    pub fn from_map(map: &MapConfiguration) -> Self {
+        let zones = map
+            .landing_zones
+            .iter()
+            .filter_map(|zone| {
+                let body = map.celestial_bodies.get(zone.body_index)?;
+                Some(LandingZone {
+                    name: zone.name.clone(),
+                    body_name: body.name.clone(),
+                    angle: zone.angle,
+                    radius: zone.radius,
+                    player_marked: false,
+                })
+            })
+            .collect();
+        LandingZones { zones }
+    }
+
+    pub fn zones(&self) -> &[LandingZone] {
+        &self.zones
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.zones.is_empty()
+    }
+
+    /// World position of a zone's center, following its planet
+    pub fn zone_position(&self, world: &World, zone: &LandingZone) -> Option<Vec2> {
+        let planet = world.planets().find(|p| p.name() == Some(zone.body_name.as_str()))?;
+        Some(planet.position() + Vec2::from_angle(zone.angle) * planet.surface_radius_at(zone.angle))
+    }
+
+    /// Mark the player's zone on the named planet whose surface is nearest `point`, replacing the last mark
+    pub fn mark(&mut self, world: &World, point: Vec2) -> Option<&LandingZone> {
+        let planet = world
+            .planets()
+            .filter(|planet| planet.name().is_some())
+            .min_by(|a, b| Self::height_above(a, point).total_cmp(&Self::height_above(b, point)))?;
+        let zone = LandingZone {
+            name: PLAYER_ZONE_NAME.to_string(),
+            body_name: planet.name()?.to_string(),
+            angle: vector_helper::angle(point - planet.position()),
+            radius: PLAYER_ZONE_RADIUS,
+            player_marked: true,
+        };
+        self.clear_mark();
+        self.zones.push(zone);
+        self.zones.last()
+    }
+
+    /// Remove the player's zone; returns false if there wasn't one
+    pub fn clear_mark(&mut self) -> bool {
+        let count = self.zones.len();
+        self.zones.retain(|zone| !zone.player_marked);
+        self.zones.len() != count
+    }
+
+    fn height_above(planet: &Planet, point: Vec2) -> f32 {
+        point.distance(planet.position()) - planet.surface_radius_toward(point)
+    }
+
+    /// The nearest zone on the planet below a rocket in flight, while it is within a planet radius of the surface
+    pub fn approach(&self, world: &World, rocket_id: EntityId) -> Option<ZoneApproach> {
+        let rocket = world.get_rocket(rocket_id).filter(|rocket| !rocket.is_landed())?;
+        let position = rocket.position();
+        let planet = world.planets().min_by(|a, b| Self::height_above(a, position).total_cmp(&Self::height_above(b, position)))?;
+        let altitude = Self::height_above(planet, position);
+        if altitude > planet.radius() {
+            return None;
+        }
+        let (zone, distance) = self.nearest_on(planet.name()?, planet.radius(), vector_helper::angle(position - planet.position()))?;
+        Some(ZoneApproach { zone: zone.name.clone(), distance, altitude, inside: distance <= zone.radius })
+    }
+
+    /// Nearest zone on a body to a surface angle, and the distance along the surface to its center
+    fn nearest_on(&self, body_name: &str, planet_radius: f32, surface_angle: f32) -> Option<(&LandingZone, f32)> {
+        self.zones
+            .iter()
+            .filter(|zone| zone.body_name == body_name)
+            .map(|zone| (zone, vector_helper::angle_difference(surface_angle, zone.angle).abs() * planet_radius))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Score a touchdown at `surface_angle` on a body; None outside every zone
+    pub fn score_touchdown(&self, body_name: &str, planet_radius: f32, surface_angle: f32) -> Option<ZoneLanding> {
+        let (zone, distance) = self.nearest_on(body_name, planet_radius, surface_angle)?;
+        if distance > zone.radius {
+            return None;
+        }
+        Some(ZoneLanding {
+            zone: zone.name.clone(),
+            body_name: zone.body_name.clone(),
+            distance,
+            score: (100.0 * (1.0 - distance / zone.radius)).clamp(0.0, 100.0),
+            player_marked: zone.player_marked,
+        })
+    }
+
+    /// Score a landing event (crashes don't count); call while the rocket still sits where it touched down
+    pub fn score_landing(&self, world: &World, event: &LandingEvent) -> Option<ZoneLanding> {
+        if event.result.grade == LandingGrade::Crash {
+            return None;
+        }
+        let rocket = world.get_rocket(event.rocket_id)?;
+        let planet = world.get_planet(event.planet_id)?;
+        let surface_angle = vector_helper::angle(rocket.position() - planet.position());
+        self.score_touchdown(planet.name()?, planet.radius(), surface_angle)
+    }
+
+    /// Draw each zone as an arc along its planet's surface with a center tick (call in world space)
+    pub fn draw_zones(&self, world: &World, zoom_level: f32) {
+        const SEGMENTS: usize = 24;
+        let thickness = 3.0 * zoom_level.max(1.0);
+        for zone in &self.zones {
+            let Some(planet) = world.planets().find(|p| p.name() == Some(zone.body_name.as_str())) else {
+                continue;
+            };
+            let half_angle = zone.radius / planet.radius();
+            let surface_point = |angle: f32| planet.position() + Vec2::from_angle(angle) * planet.surface_radius_at(angle);
+            let points: Vec<Vec2> = (0..=SEGMENTS)
+                .map(|i| surface_point(zone.angle - half_angle + 2.0 * half_angle * i as f32 / SEGMENTS as f32))
+                .collect();
+            for pair in points.windows(2) {
+                draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, thickness, ZONE_COLOR);
+            }
+
+            // Keep the center tick visible when zoomed out
+            let center = surface_point(zone.angle);
+            let tick = center + Vec2::from_angle(zone.angle) * (20.0 * zoom_level).max(20.0);
+            draw_line(center.x, center.y, tick.x, tick.y, thickness, ZONE_COLOR);
+        }
+    }
+
+    /// Distance-to-target readout above the sample and rover status lines (call in screen space)
+    pub fn draw_approach(&self, world: &World, rocket_id: EntityId) {
+        let Some(approach) = self.approach(world, rocket_id) else {
+            return;
+        };
+        let color = if approach.inside { INSIDE_COLOR } else { ZONE_COLOR };
+        let text = format!("Target {}: {:.0} downrange, {:.0} up", approach.zone, approach.distance, approach.altitude);
+        draw_text(&text, 10.0, screen_height() - 61.0, 18.0, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::Rocket;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_touchdowns_scored_by_distance_from_center() {
+        let mut world = World::new();
+        let mut moon = Planet::new(Vec2::ZERO, 1000.0, 1000.0, GRAY);
+        moon.set_name("Moon".to_string());
+        world.add_planet(moon);
+        let mut map = MapConfiguration::earth_moon();
+        map.landing_zones[0].angle = 0.0;
+        let mut zones = LandingZones::from_map(&map);
+        assert_eq!(zones.zones()[0].body_name, "Moon");
+
+        // Dead center, halfway out (250 radius on a 1000 radius planet), and just outside
+        assert_relative_eq!(zones.score_touchdown("Moon", 1000.0, 0.0).unwrap().score, 100.0);
+        assert_relative_eq!(zones.score_touchdown("Moon", 1000.0, -0.125).unwrap().score, 50.0, epsilon = 1e-3);
+        assert!(zones.score_touchdown("Moon", 1000.0, 0.3).is_none());
+        assert!(zones.score_touchdown("Earth", 1000.0, 0.0).is_none());
+
+        // A rocket coming down 100 above the far side sees the player's mark rather than the map zone
+        let rocket_id = world.add_rocket(Rocket::new(Vec2::new(-1100.0, 0.0), Vec2::ZERO, WHITE, 1.0));
+        world.get_rocket_mut(rocket_id).unwrap().take_off();
+        assert!(zones.approach(&world, rocket_id).unwrap().distance > 3000.0);
+        zones.mark(&world, Vec2::new(-800.0, 600.0));
+        let approach = zones.approach(&world, rocket_id).unwrap();
+        assert_eq!(approach.zone, PLAYER_ZONE_NAME);
+        assert_relative_eq!(approach.altitude, 100.0, epsilon = 1e-3);
+        assert!(!approach.inside);
+
+        // Marking again moves the mark
+        zones.mark(&world, Vec2::new(-1200.0, 10.0));
+        assert_eq!(zones.zones().len(), 2);
+        assert!(zones.approach(&world, rocket_id).unwrap().inside);
+        assert!(zones.clear_mark());
+        assert!(!zones.clear_mark());
+    }
+}
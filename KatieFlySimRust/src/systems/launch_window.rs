@@ -0,0 +1,139 @@
+// Launch Window - Next phase-angle window for a Hohmann transfer from the rocket's orbit to a moon
+// The moon is the planet closest to the most massive one; both orbits are treated as circular around that body
+
+use macroquad::prelude::*;
+
+use crate::entities::{Planet, Rocket};
+use crate::game_constants::GameConstants;
+use crate::systems::mission_clock::WARP_LEVELS;
+use crate::systems::route_planner::hohmann_transfer;
+use crate::systems::World;
+
+/// Warp is only used while at least this many real seconds would pass before the window
+const WARP_LEAD_SECONDS: f32 = 5.0;
+
+/// When and how to leave for the target
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchWindow {
+    pub target_name: String,
+    pub current_phase: f32,  // Radians the target leads the rocket (in the rocket's direction of travel)
+    pub required_phase: f32, // Lead needed at the burn so the target meets the rocket at apoapsis
+    pub time_to_window: f32, // Game seconds until the phases line up
+    pub transfer_time: f32,  // Seconds along the transfer ellipse
+    pub departure_burn: f32, // Prograde delta-v at the window
+}
+
+/// Wrap an angle into [0, 2π)
+fn wrap_angle(angle: f32) -> f32 {
+    angle.rem_euclid(2.0 * GameConstants::PI)
+}
+
+/// Required lead angle and seconds until it is reached.
+/// Rates are angular velocities (rad/s) in the rocket's direction of travel; None if the phase never changes
+pub fn phase_window(mu: f32, r1: f32, r2: f32, phase: f32, rocket_rate: f32, target_rate: f32) -> Option<(f32, f32)> {
+    let (_, _, transfer_time) = hohmann_transfer(mu, r1, r2);
+    let required = wrap_angle(GameConstants::PI - target_rate * transfer_time);
+
+    // The lead shrinks when the rocket circles faster than the target (lower orbit) and grows otherwise
+    let closing_rate = rocket_rate - target_rate;
+    if closing_rate.abs() < 1e-6 {
+        return None;
+    }
+    let to_close = if closing_rate > 0.0 {
+        wrap_angle(phase - required)
+    } else {
+        wrap_angle(required - phase)
+    };
+    Some((required, to_close / closing_rate.abs()))
+}
+
+/// Highest warp that still leaves `WARP_LEAD_SECONDS` of real time before a window `time_to_window` away
+pub fn warp_for_wait(time_to_window: f32) -> u32 {
+    WARP_LEVELS
+        .iter()
+        .copied()
+        .filter(|level| time_to_window / *level as f32 >= WARP_LEAD_SECONDS)
+        .max()
+        .unwrap_or(1)
+}
+
+impl LaunchWindow {
+    /// The next window from `rocket`'s orbit to the moon of the most massive planet (None when landed or no moon)
+    pub fn find(world: &World, rocket: &Rocket) -> Option<LaunchWindow> {
+        if rocket.is_landed() {
+            return None;
+        }
+        let central = world
+            .planets()
+            .max_by(|a, b| a.mass().partial_cmp(&b.mass()).unwrap_or(std::cmp::Ordering::Equal))?;
+        let target = world
+            .planets()
+            .filter(|planet| !std::ptr::eq(*planet, central))
+            .min_by(|a, b| {
+                let da = a.position().distance(central.position());
+                let db = b.position().distance(central.position());
+                da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
+            })?;
+        Self::between(central, target, rocket.position(), rocket.velocity())
+    }
+
+    /// Window from an orbit at `position`/`velocity` around `central` to `target`
+    pub fn between(central: &Planet, target: &Planet, position: Vec2, velocity: Vec2) -> Option<LaunchWindow> {
+        let mu = GameConstants::G * central.mass();
+        let rocket_offset = position - central.position();
+        let target_offset = target.position() - central.position();
+        let (r1, r2) = (rocket_offset.length(), target_offset.length());
+        if r1 < central.radius() || r2 < f32::EPSILON {
+            return None;
+        }
+
+        // Angular velocities from the current states, flipped so the rocket's travel is positive
+        let angular_rate = |offset: Vec2, relative_velocity: Vec2| offset.perp_dot(relative_velocity) / offset.length_squared();
+        let rocket_rate = angular_rate(rocket_offset, velocity - central.velocity());
+        let direction = if rocket_rate >= 0.0 { 1.0 } else { -1.0 };
+        let target_rate = direction * angular_rate(target_offset, target.velocity() - central.velocity());
+        let phase = wrap_angle(direction * (target_offset.y.atan2(target_offset.x) - rocket_offset.y.atan2(rocket_offset.x)));
+
+        let (required_phase, time_to_window) = phase_window(mu, r1, r2, phase, rocket_rate.abs(), target_rate)?;
+        let (departure_burn, _, transfer_time) = hohmann_transfer(mu, r1, r2);
+        Some(LaunchWindow {
+            target_name: target.name().unwrap_or("Moon").to_string(),
+            current_phase: phase,
+            required_phase,
+            time_to_window,
+            transfer_time,
+            departure_burn,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_window_timing() {
+        let mu = 1.0e9;
+        let (r1, r2) = (1000.0f32, 4000.0f32);
+        let rocket_rate = (mu / r1.powi(3)).sqrt();
+        let target_rate = (mu / r2.powi(3)).sqrt();
+        let (_, _, transfer_time) = hohmann_transfer(mu, r1, r2);
+
+        // 0.1 rad short of the window closes at the difference of the orbital rates
+        let required = wrap_angle(GameConstants::PI - target_rate * transfer_time);
+        let (found, wait) = phase_window(mu, r1, r2, required + 0.1, rocket_rate, target_rate).unwrap();
+        assert!((found - required).abs() < 1e-5);
+        assert!((wait - 0.1 / (rocket_rate - target_rate)).abs() < 1e-3);
+
+        // From a higher orbit the lead grows, so the wait runs the other way round
+        let (_, wait_outside) = phase_window(mu, r2, r1, 0.0, target_rate, rocket_rate).unwrap();
+        assert!(wait_outside > 0.0);
+
+        // Same orbit: the phase never changes
+        assert!(phase_window(mu, r1, r1, 1.0, rocket_rate, rocket_rate).is_none());
+
+        assert_eq!(warp_for_wait(1000.0), *WARP_LEVELS.last().unwrap());
+        assert_eq!(warp_for_wait(12.0), 2);
+        assert_eq!(warp_for_wait(1.0), 1);
+    }
+}
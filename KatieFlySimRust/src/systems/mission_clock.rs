@@ -0,0 +1,112 @@
+// Mission Clock - Universal time of a session and the time warp factor
+// Saved with the game so a loaded session continues the same calendar; timelines, contracts and replays format times with it
+
+/// Selectable warp factors (- and = step through them)
+pub const WARP_LEVELS: [u32; 5] = [1, 2, 4, 10, 25];
+
+/// Elapsed mission time and current warp
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MissionClock {
+    elapsed: f64, // Seconds of game time since the session began (f64 so long campaigns keep sub-frame precision)
+    warp_index: usize,
+}
+
+impl MissionClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resume a saved clock at `elapsed` seconds (warp always restarts at 1x)
+    pub fn starting_at(elapsed: f32) -> Self {
+        MissionClock { elapsed: elapsed.max(0.0) as f64, warp_index: 0 }
+    }
+
+    /// Advance by one real tick; returns the game time that passed (the tick scaled by warp)
+    pub fn tick(&mut self, delta_time: f32) -> f32 {
+        let scaled = delta_time * self.warp() as f32;
+        self.elapsed += scaled as f64;
+        scaled
+    }
+
+    /// Seconds of game time since the session began
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed as f32
+    }
+
+    pub fn warp(&self) -> u32 {
+        WARP_LEVELS[self.warp_index]
+    }
+
+    pub fn is_warping(&self) -> bool {
+        self.warp_index > 0
+    }
+
+    /// Step up to the next warp level; returns the new factor
+    pub fn increase_warp(&mut self) -> u32 {
+        self.warp_index = (self.warp_index + 1).min(WARP_LEVELS.len() - 1);
+        self.warp()
+    }
+
+    /// Step down to the previous warp level; returns the new factor
+    pub fn decrease_warp(&mut self) -> u32 {
+        self.warp_index = self.warp_index.saturating_sub(1);
+        self.warp()
+    }
+
+    /// Jump to the highest warp level not above `factor`
+    pub fn set_warp(&mut self, factor: u32) {
+        self.warp_index = WARP_LEVELS.iter().rposition(|level| *level <= factor).unwrap_or(0);
+    }
+
+    /// Drop back to real time (thrusting, alerts)
+    pub fn stop_warp(&mut self) {
+        self.warp_index = 0;
+    }
+}
+
+/// "mm:ss" under an hour, "h:mm:ss" under a day, "Dd hh:mm:ss" after
+pub fn format_clock(seconds: f32) -> String {
+    let total = seconds.max(0.0) as u64;
+    let (days, hours, minutes, secs) = (total / 86400, (total / 3600) % 24, (total / 60) % 60, total % 60);
+    if days > 0 {
+        format!("{}d {:02}:{:02}:{:02}", days, hours, minutes, secs)
+    } else if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, secs)
+    } else {
+        format!("{:02}:{:02}", minutes, secs)
+    }
+}
+
+/// Mission elapsed time as shown in the HUD ("T+ 01:05")
+pub fn format_met(seconds: f32) -> String {
+    format!("T+ {}", format_clock(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_warp_and_format() {
+        let mut clock = MissionClock::starting_at(100.0);
+        assert_eq!(clock.tick(0.5), 0.5);
+        assert_eq!(clock.increase_warp(), 2);
+        assert_eq!(clock.tick(0.5), 1.0);
+        assert!((clock.elapsed() - 101.5).abs() < 1e-4);
+
+        for _ in 0..10 {
+            clock.increase_warp();
+        }
+        assert_eq!(clock.warp(), *WARP_LEVELS.last().unwrap());
+        clock.stop_warp();
+        assert!(!clock.is_warping());
+        assert_eq!(clock.decrease_warp(), 1);
+        clock.set_warp(12);
+        assert_eq!(clock.warp(), 10);
+
+        assert_eq!(format_clock(65.0), "01:05");
+        assert_eq!(format_clock(3725.0), "1:02:05");
+        assert_eq!(format_clock(90061.0), "1d 01:01:01");
+        assert_eq!(format_met(-3.0), "T+ 00:00");
+    }
+}
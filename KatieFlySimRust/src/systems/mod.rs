@@ -6,11 +6,63 @@ pub mod orbit_maintenance;
 pub mod vehicle_manager;
 pub mod satellite_manager;
 pub mod player_input;
+pub mod landing_evaluator;
+pub mod entity_picker;
+pub mod alerts;
+pub mod bullet_threats;
+pub mod sample_return;
+pub mod surface_deposits;
+pub mod planet_rings;
+pub mod satellite_search;
+pub mod session_timeline;
+pub mod session_recording;
+pub mod crew;
+pub mod desync;
+pub mod snapshot_pacing;
+pub mod snapshot_delta;
+pub mod bullet_sync;
+pub mod ownership;
+pub mod server_notice;
+pub mod waypoints;
+pub mod route_planner;
+pub mod floating_origin;
+pub mod daily_challenge;
+pub mod economy;
+pub mod scenario;
+pub mod world_bounds;
+pub mod convoy;
+pub mod coverage;
+pub mod haptics;
+pub mod controllers;
+pub mod kill_cam;
+pub mod orbit_trails;
+pub mod hit_events;
+pub mod chat;
+pub mod asset_manager;
+pub mod mission_clock;
+pub mod launch_window;
+pub mod gravity_assist;
+pub mod prediction_debug;
+pub mod snapshot_interpolation;
+pub mod conservation;
+pub mod lag_compensation;
+pub mod landing_zones;
+pub mod adaptive_timestep;
+pub mod satellite_groups;
+pub mod station_ops;
+pub mod camera_path;
+pub mod flight_log;
+pub mod relay_control;
+pub mod debris;
+pub mod arena;
+#[cfg(feature = "hot-reload")]
+pub mod hot_reload;
 
-pub use world::{World, EntityId, DestroyedRocketInfo};
+pub use world::{World, EntityId, BulletTarget, DestroyedRocketInfo, FuelTransferEvent, FuelTransferKind, HitEvent, KillShot, LandingEvent, SatelliteLifeEvent};
 pub use fuel_transfer_network::{
     FuelTransferNetwork, FuelTransferRequest, TransferPriority,
     TransferStatus, NetworkOptimizationMode, NetworkFlowStats,
+    InterruptCause, NetworkAnomaly, SupplyNode,
 };
 pub use orbit_maintenance::{
     OrbitMaintenance, OrbitDriftAnalysis, DriftSeverity,
@@ -19,6 +71,55 @@ pub use orbit_maintenance::{
 pub use vehicle_manager::{VehicleManager, VisualizationOptions, ReferenceBody};
 pub use satellite_manager::{
     SatelliteManager, SatelliteStatus, SatelliteNetworkStats,
-    SatelliteManagerConfig,
+    SatelliteManagerConfig, ShellCoverage,
 };
 pub use player_input::{PlayerInput, PlayerInputState};
+pub use landing_evaluator::{LandingGrade, LandingResult};
+pub use entity_picker::EntityTarget;
+pub use alerts::{Alert, AlertEngine, AlertKind, AlertsConfig};
+pub use bullet_threats::BulletThreat;
+pub use sample_return::{SampleMission, SampleSite, SampleState, SampleCollectError};
+pub use satellite_search::{SatelliteQuery, SatelliteRole};
+pub use surface_deposits::{FuelDeposit, SurfaceDeposits};
+pub use planet_rings::{PlanetRing, PlanetRings};
+pub use crew::{CrewAssignments, CrewRole};
+pub use desync::{ChecksumPacket, DesyncMonitor};
+pub use snapshot_pacing::{LinkQuality, SnapshotTier};
+pub use snapshot_delta::{DeltaDecoder, DeltaEncoder, SnapshotFragment, SnapshotPacket};
+pub use bullet_sync::{BulletRemovalPacket, RemovedBullets};
+pub use ownership::{GiftPrompt, OwnershipError, OwnershipPacket, TransferOffers};
+pub use server_notice::ServerNotice;
+pub use waypoints::{Waypoint, WaypointPacket, Waypoints};
+pub use route_planner::{RouteLeg, RoutePlan, RoutePlanner, RouteStop};
+pub use floating_origin::FloatingOrigin;
+pub use daily_challenge::{ChallengeDate, ChallengeObjective, ChallengeOutcome, DailyChallenge};
+pub use world_bounds::{BoundsConfig, BoundsStatus};
+pub use convoy::{Convoy, ConvoyEvent, TankerLeg, TankerRoute};
+pub use coverage::{CoverageCache, CoverageMap};
+pub use haptics::{HapticEvent, Haptics, NoGamepad, RumbleDevice};
+pub use controllers::Controllers;
+pub use kill_cam::{KillCam, KillCamView, StateHistory, KILL_CAM_DURATION};
+pub use orbit_trails::{OrbitTrails, TrailWindow};
+pub use hit_events::HitEventPacket;
+pub use chat::ChatMessage;
+pub use asset_manager::AssetManager;
+pub use mission_clock::MissionClock;
+pub use launch_window::LaunchWindow;
+pub use gravity_assist::{AssistGuide, AssistGuidance, FlybyResult, FlybyTracker};
+pub use prediction_debug::{AuthoritativePose, PredictionDebug};
+pub use snapshot_interpolation::SnapshotInterpolator;
+pub use conservation::{ConservationMonitor, ConservationSample};
+pub use lag_compensation::LagCompensation;
+pub use landing_zones::{LandingZone, LandingZones, ZoneApproach, ZoneLanding};
+pub use adaptive_timestep::AdaptiveTimestep;
+pub use satellite_groups::{BatchCommand, MaintenancePreset, SatelliteSelection, MAINTENANCE_PRESETS};
+pub use station_ops::{StationEvent, StationHold, StationOps};
+pub use camera_path::{CameraKey, CameraPath, CameraPose, Easing};
+pub use flight_log::{Debrief, FlightLog, OrbitRecord};
+pub use relay_control::{ControlOrigin, RelayChain, RemoteControl, RELAY_RANGE};
+pub use debris::{Debris, DebrisCleared, DebrisField, ShellDebris};
+pub use arena::ArenaBounds;
+pub use economy::{Cargo, Contract, ContractEvent, ContractKind, Market, Station};
+pub use scenario::{Scenario, ScenarioObjective, ScenarioOutcome, ScenarioProgress, ScenarioRun};
+pub use session_timeline::{SessionEvent, SessionEventKind, SessionTimeline, TransferDebounce};
+pub use session_recording::{RecordedAction, RecordedEvent, RecordedInput, ReplayPlayback, SessionRecorder, SessionRecording};
@@ -32,6 +32,7 @@ pub enum ManeuverType {
     Retrograde,        // Slow down (lower periapsis)
     Circularization,   // Reduce eccentricity
     InclinationCorrection, // Adjust orbital plane
+    Deorbit,           // End of life: drop periapsis into the planet
 }
 
 /// Scheduled orbital maneuver
@@ -313,6 +314,34 @@ impl OrbitMaintenance {
         maneuvers
     }
 
+    /// Plan the retrograde burn that drops an end-of-life satellite's periapsis into the planet
+    /// None when it's already on its way down
+    pub fn plan_deorbit(&self, satellite: &Satellite, planet: &Planet) -> Option<ScheduledManeuver> {
+        let r = vector_helper::magnitude(satellite.position() - planet.position());
+        let relative_velocity = satellite.velocity() - planet.velocity();
+        let speed = vector_helper::magnitude(relative_velocity);
+        let periapsis = planet.radius() * GameConstants::SATELLITE_DEORBIT_PERIAPSIS;
+        if r <= periapsis {
+            return None;
+        }
+
+        // Vis-viva: speed at apoapsis r of an ellipse reaching down to the target periapsis
+        let mu = GameConstants::G * planet.mass();
+        let target_speed = (2.0 * mu * periapsis / (r * (r + periapsis))).sqrt();
+        if speed <= target_speed {
+            return None;
+        }
+
+        let correction_magnitude = speed - target_speed;
+        Some(ScheduledManeuver {
+            maneuver_type: ManeuverType::Deorbit,
+            delta_v: vector_helper::normalize(relative_velocity) * -correction_magnitude,
+            fuel_cost: correction_magnitude * GameConstants::SATELLITE_DEORBIT_FUEL_PER_DV,
+            priority: 10,
+            execute_time: 0.0,
+        })
+    }
+
     /// Execute scheduled maneuvers
     pub fn execute_scheduled_maneuvers(
         &mut self,
@@ -476,6 +505,22 @@ mod tests {
         assert_eq!(severity, DriftSeverity::Critical);
     }
 
+    #[test]
+    fn test_deorbit_burn_is_retrograde_until_coming_down() {
+        let maintenance = OrbitMaintenance::new(MaintenanceConfig::default());
+        let planet = Planet::new(Vec2::ZERO, 100.0, 10000.0, BLUE);
+        let circular_speed = maintenance.calculate_orbital_velocity(10000.0, 300.0);
+        let mut satellite = Satellite::new(Vec2::new(300.0, 0.0), Vec2::new(0.0, circular_speed), GREEN);
+
+        let burn = maintenance.plan_deorbit(&satellite, &planet).unwrap();
+        assert_eq!(burn.maneuver_type, ManeuverType::Deorbit);
+        assert!(burn.delta_v.y < 0.0 && burn.fuel_cost > 0.0);
+
+        // After the burn it's on an ellipse into the planet, so there's nothing left to plan
+        satellite.set_velocity(satellite.velocity() + burn.delta_v * 1.01);
+        assert!(maintenance.plan_deorbit(&satellite, &planet).is_none());
+    }
+
     #[test]
     fn test_orbital_velocity_calculation() {
         let maintenance = OrbitMaintenance::with_target_radius(300.0);
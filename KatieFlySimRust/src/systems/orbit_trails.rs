@@ -0,0 +1,208 @@
+// Orbit Trails - Long-horizon position history for multi-orbit trails in the strategic view
+// Planets, satellites and rockets are sampled every few game seconds for the whole session; a body that
+// sits still extends one run instead of adding points, and a trail that outgrows its budget thins its
+// older half, so old orbits stay visible at coarser resolution while memory stays bounded
+
+use std::collections::HashMap;
+
+use macroquad::prelude::{Color, Vec2};
+
+use crate::entities::GameObject;
+use crate::systems::{EntityId, World};
+
+/// Game seconds between samples (an orbit of the Moon is still hundreds of points)
+const SAMPLE_INTERVAL: f32 = 2.0;
+/// A body that moved less than this since its last point (world units) extends that point's run
+const RUN_TOLERANCE: f32 = 1.0;
+/// Points a trail may hold before its older half is thinned
+pub const MAX_TRAIL_POINTS: usize = 1024;
+
+/// How much history the trails show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrailWindow {
+    #[default]
+    Off,
+    LastHour,
+    Session,
+}
+
+impl TrailWindow {
+    /// Off -> last hour -> whole session -> off
+    pub fn next(self) -> Self {
+        match self {
+            TrailWindow::Off => TrailWindow::LastHour,
+            TrailWindow::LastHour => TrailWindow::Session,
+            TrailWindow::Session => TrailWindow::Off,
+        }
+    }
+
+    /// Game seconds of history shown, None for all of it
+    pub fn seconds(self) -> Option<f32> {
+        match self {
+            TrailWindow::LastHour => Some(3600.0),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TrailWindow::Off => "Orbit trails off",
+            TrailWindow::LastHour => "Orbit trails: last hour",
+            TrailWindow::Session => "Orbit trails: whole session",
+        }
+    }
+}
+
+/// One run of the trail: where a body was from `start` to `end` (the same time for a moving body)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrailPoint {
+    pub position: Vec2,
+    pub start: f32,
+    pub end: f32,
+}
+
+/// A body's path, oldest first
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trail {
+    pub color: Color,
+    points: Vec<TrailPoint>,
+}
+
+impl Trail {
+    pub fn points(&self) -> &[TrailPoint] {
+        &self.points
+    }
+
+    /// Points that were current at or after `since` (game seconds)
+    pub fn since(&self, since: f32) -> &[TrailPoint] {
+        let first = self.points.partition_point(|point| point.end < since);
+        &self.points[first..]
+    }
+
+    fn push(&mut self, position: Vec2, time: f32) {
+        match self.points.last_mut() {
+            Some(last) if last.position.distance(position) < RUN_TOLERANCE => last.end = time,
+            _ => self.points.push(TrailPoint { position, start: time, end: time }),
+        }
+        if self.points.len() > MAX_TRAIL_POINTS {
+            self.thin_older_half();
+        }
+    }
+
+    /// Drop every other point in the older half; repeated, each older stretch ends up half as dense as the next
+    fn thin_older_half(&mut self) {
+        let older = self.points.len() / 2;
+        let mut index = 0;
+        self.points.retain(|_| {
+            let keep = index >= older || index % 2 == 0;
+            index += 1;
+            keep
+        });
+    }
+}
+
+/// Session-long trails of every body in the world
+#[derive(Debug, Clone, Default)]
+pub struct OrbitTrails {
+    trails: HashMap<EntityId, Trail>,
+    last_sample: Option<f32>,
+}
+
+impl OrbitTrails {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.trails.clear();
+        self.last_sample = None;
+    }
+
+    pub fn trail(&self, id: EntityId) -> Option<&Trail> {
+        self.trails.get(&id)
+    }
+
+    pub fn trails(&self) -> impl Iterator<Item = (EntityId, &Trail)> {
+        self.trails.iter().map(|(id, trail)| (*id, trail))
+    }
+
+    /// Total points held across all trails
+    pub fn point_count(&self) -> usize {
+        self.trails.values().map(|trail| trail.points.len()).sum()
+    }
+
+    /// Sample the world at `time` (game seconds) if a sample is due; bodies that are gone lose their trails
+    pub fn record(&mut self, world: &World, time: f32) {
+        // Going back in time (a load) starts over
+        if self.last_sample.is_some_and(|last| time < last) {
+            self.clear();
+        }
+        if self.last_sample.is_some_and(|last| time - last < SAMPLE_INTERVAL - 1e-4) {
+            return;
+        }
+        self.last_sample = Some(time);
+
+        let bodies: Vec<(EntityId, Vec2, Color)> = world
+            .planets_with_ids()
+            .map(|(id, planet)| (id, planet.position(), planet.color()))
+            .chain(world.satellites_with_ids().map(|(id, satellite)| (id, satellite.position(), satellite.status_color())))
+            .chain(world.rockets_with_ids().map(|(id, rocket)| (id, rocket.position(), rocket.color())))
+            .collect();
+        self.trails.retain(|id, _| bodies.iter().any(|(body_id, _, _)| body_id == id));
+        for (id, position, color) in bodies {
+            let trail = self.trails.entry(id).or_insert_with(|| Trail { color, points: Vec::new() });
+            trail.color = color;
+            trail.push(position, time);
+        }
+    }
+
+    /// Move every point by a floating-origin rebase shift
+    pub fn shift_positions(&mut self, shift: Vec2) {
+        for trail in self.trails.values_mut() {
+            for point in &mut trail.points {
+                point.position -= shift;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trail() -> Trail {
+        Trail { color: Color::new(1.0, 1.0, 1.0, 1.0), points: Vec::new() }
+    }
+
+    #[test]
+    fn test_still_body_extends_one_run() {
+        let mut trail = trail();
+        for step in 0..10 {
+            trail.push(Vec2::new(100.0, 0.0), step as f32 * SAMPLE_INTERVAL);
+        }
+        trail.push(Vec2::new(150.0, 0.0), 20.0);
+
+        assert_eq!(trail.points().len(), 2);
+        assert_eq!((trail.points()[0].start, trail.points()[0].end), (0.0, 18.0));
+        assert_eq!(trail.since(19.0).len(), 1);
+    }
+
+    #[test]
+    fn test_long_trail_stays_bounded_and_keeps_its_start() {
+        let mut trail = trail();
+        let samples = MAX_TRAIL_POINTS * 20;
+        for step in 0..samples {
+            trail.push(Vec2::new(step as f32 * 10.0, 0.0), step as f32);
+        }
+
+        let points = trail.points();
+        assert!(points.len() <= MAX_TRAIL_POINTS);
+        assert_eq!(points[0].start, 0.0); // The session's first point survives thinning
+        assert_eq!(points.last().unwrap().start, (samples - 1) as f32);
+        assert!(points.windows(2).all(|pair| pair[0].start < pair[1].start));
+
+        // Recent history is denser than old history
+        let gap = |pair: &[TrailPoint]| pair[1].start - pair[0].start;
+        assert!(gap(&points[..2]) > gap(&points[points.len() - 2..]));
+    }
+}
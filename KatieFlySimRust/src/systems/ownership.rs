@@ -0,0 +1,228 @@
+// Ownership - Gifting satellites between players in online multiplayer
+// The giver offers through the host; the satellite changes hands only once the recipient accepts
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::systems::{EntityId, World};
+
+/// Prefix marking an ownership packet (snapshots never start with these bytes)
+const OWNERSHIP_PACKET_PREFIX: &[u8] = b"OWNERSHIP";
+/// Unanswered offers lapse after this long (seconds)
+pub const OFFER_TIMEOUT: f64 = 30.0;
+
+/// Why a satellite can't change hands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OwnershipError {
+    NoSuchSatellite,
+    NotOwner,
+    SamePlayer,
+}
+
+impl OwnershipError {
+    pub fn message(&self) -> &'static str {
+        match self {
+            OwnershipError::NoSuchSatellite => "That satellite no longer exists",
+            OwnershipError::NotOwner => "You can only gift your own satellites",
+            OwnershipError::SamePlayer => "You already own that satellite",
+        }
+    }
+}
+
+/// Hand a satellite from `from` to `to` (fails unless `from` owns it)
+pub fn transfer_satellite(world: &mut World, satellite_id: EntityId, from: u32, to: u32) -> Result<(), OwnershipError> {
+    if from == to {
+        return Err(OwnershipError::SamePlayer);
+    }
+    let satellite = world.get_satellite_mut(satellite_id).ok_or(OwnershipError::NoSuchSatellite)?;
+    if satellite.owner() != Some(from) {
+        return Err(OwnershipError::NotOwner);
+    }
+    satellite.set_owner(Some(to));
+    Ok(())
+}
+
+/// Ownership handshake messages between host and clients
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum OwnershipPacket {
+    /// Client -> host: offer my satellite to another player
+    Offer { satellite_id: EntityId, to_player: u32 },
+    /// Host -> recipient: someone is offering you a satellite
+    Offered { offer_id: u32, from_player: u32, satellite_id: EntityId },
+    /// Recipient -> host: answer to an offer
+    Respond { offer_id: u32, accept: bool },
+    /// Host -> giver and recipient: how the offer ended
+    Resolved { satellite_id: EntityId, from_player: u32, to_player: u32, accepted: bool },
+}
+
+impl OwnershipPacket {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = OWNERSHIP_PACKET_PREFIX.to_vec();
+        bytes.extend(bincode::serialize(self).unwrap_or_default());
+        bytes
+    }
+
+    /// None if the bytes aren't an ownership packet
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let payload = bytes.strip_prefix(OWNERSHIP_PACKET_PREFIX)?;
+        bincode::deserialize(payload).ok()
+    }
+}
+
+/// An offer waiting for the recipient's answer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransferOffer {
+    pub satellite_id: EntityId,
+    pub from_player: u32,
+    pub to_player: u32,
+    pub expires_at: f64,
+}
+
+/// Host-side pending offers
+#[derive(Debug, Clone, Default)]
+pub struct TransferOffers {
+    offers: HashMap<u32, TransferOffer>,
+    next_id: u32,
+}
+
+impl TransferOffers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an offer after checking the giver owns the satellite; a newer offer of the same satellite replaces the old one
+    pub fn offer(&mut self, world: &World, satellite_id: EntityId, from: u32, to: u32, now: f64) -> Result<u32, OwnershipError> {
+        let satellite = world.get_satellite(satellite_id).ok_or(OwnershipError::NoSuchSatellite)?;
+        if satellite.owner() != Some(from) {
+            return Err(OwnershipError::NotOwner);
+        }
+        if from == to {
+            return Err(OwnershipError::SamePlayer);
+        }
+
+        self.offers.retain(|_, offer| offer.satellite_id != satellite_id);
+        let offer_id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.offers.insert(offer_id, TransferOffer {
+            satellite_id,
+            from_player: from,
+            to_player: to,
+            expires_at: now + OFFER_TIMEOUT,
+        });
+        Ok(offer_id)
+    }
+
+    /// Take an offer answered by `responder` (None if it isn't theirs to answer or has lapsed)
+    pub fn respond(&mut self, offer_id: u32, responder: u32) -> Option<TransferOffer> {
+        if self.offers.get(&offer_id)?.to_player != responder {
+            return None;
+        }
+        self.offers.remove(&offer_id)
+    }
+
+    /// Remove and return offers nobody answered in time
+    pub fn expire(&mut self, now: f64) -> Vec<TransferOffer> {
+        let expired: Vec<u32> = self
+            .offers
+            .iter()
+            .filter(|(_, offer)| now >= offer.expires_at)
+            .map(|(id, _)| *id)
+            .collect();
+        expired.into_iter().filter_map(|id| self.offers.remove(&id)).collect()
+    }
+}
+
+/// Per-player gifting UI state: the recipient being picked and any offer awaiting an answer
+#[derive(Debug, Clone, Default)]
+pub struct GiftPrompt {
+    pub recipient: Option<u32>,                   // Player the targeted satellite would go to (O cycles)
+    pub incoming: Option<(u32, u32, EntityId)>,   // Offer ID, giver, satellite
+}
+
+impl GiftPrompt {
+    /// Next recipient after the current one among `players` (sorted, excluding ourselves)
+    pub fn cycle_recipient(&mut self, players: &[u32]) {
+        self.recipient = match self.recipient {
+            Some(current) => players.iter().find(|p| **p > current).or(players.first()).copied(),
+            None => players.first().copied(),
+        };
+    }
+
+    /// Prompt line while an offer awaits our answer or a recipient is picked for the targeted satellite
+    pub fn text(&self, targeted_satellite: Option<EntityId>, name: impl Fn(u32) -> String) -> Option<String> {
+        if let Some((_, from, satellite_id)) = self.incoming {
+            return Some(format!("{} offers you satellite {} - Y accept / N decline", name(from), satellite_id));
+        }
+        let (recipient, satellite_id) = (self.recipient?, targeted_satellite?);
+        Some(format!("Gift satellite {} to {}? Y offer / O next player / N cancel", satellite_id, name(recipient)))
+    }
+}
+
+/// Toast text for how an offer ended, from player `me`'s point of view
+pub fn resolution_text(satellite_id: EntityId, from: u32, to: u32, accepted: bool, me: u32, name: impl Fn(u32) -> String) -> String {
+    match (accepted, me == from) {
+        (true, true) => format!("{} accepted satellite {}", name(to), satellite_id),
+        (true, false) => format!("Satellite {} from {} is now yours", satellite_id, name(from)),
+        (false, true) => format!("Gift of satellite {} to {} didn't go through", satellite_id, name(to)),
+        (false, false) => format!("Offer of satellite {} from {} closed", satellite_id, name(from)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::Satellite;
+    use macroquad::prelude::{Vec2, WHITE};
+
+    fn world_with_satellite(owner: u32) -> (World, EntityId) {
+        let mut world = World::new();
+        let mut satellite = Satellite::new(Vec2::ZERO, Vec2::ZERO, WHITE);
+        satellite.set_owner(Some(owner));
+        let id = world.add_satellite(satellite);
+        (world, id)
+    }
+
+    #[test]
+    fn test_offer_accept_handshake() {
+        let (mut world, sat_id) = world_with_satellite(1);
+        let mut offers = TransferOffers::new();
+
+        assert_eq!(offers.offer(&world, sat_id, 2, 3, 0.0), Err(OwnershipError::NotOwner));
+        let offer_id = offers.offer(&world, sat_id, 1, 2, 0.0).unwrap();
+
+        // Only the recipient can answer
+        assert!(offers.respond(offer_id, 3).is_none());
+        let offer = offers.respond(offer_id, 2).unwrap();
+        assert!(offers.respond(offer_id, 2).is_none());
+
+        transfer_satellite(&mut world, offer.satellite_id, offer.from_player, offer.to_player).unwrap();
+        assert_eq!(world.get_satellite(sat_id).unwrap().owner(), Some(2));
+        assert_eq!(transfer_satellite(&mut world, sat_id, 1, 3), Err(OwnershipError::NotOwner));
+    }
+
+    #[test]
+    fn test_offers_expire() {
+        let (world, sat_id) = world_with_satellite(0);
+        let mut offers = TransferOffers::new();
+        offers.offer(&world, sat_id, 0, 1, 0.0).unwrap();
+        assert!(offers.expire(OFFER_TIMEOUT - 1.0).is_empty());
+        assert_eq!(offers.expire(OFFER_TIMEOUT).len(), 1);
+    }
+
+    #[test]
+    fn test_packet_round_trip_and_recipient_cycle() {
+        let packet = OwnershipPacket::Respond { offer_id: 4, accept: true };
+        assert_eq!(OwnershipPacket::from_bytes(&packet.to_bytes()), Some(packet));
+        assert_eq!(OwnershipPacket::from_bytes(b"KEEPALIVE"), None);
+
+        let mut prompt = GiftPrompt::default();
+        let players = [0, 2, 5];
+        prompt.cycle_recipient(&players);
+        assert_eq!(prompt.recipient, Some(0));
+        prompt.cycle_recipient(&players);
+        prompt.cycle_recipient(&players);
+        assert_eq!(prompt.recipient, Some(5));
+        prompt.cycle_recipient(&players);
+        assert_eq!(prompt.recipient, Some(0));
+    }
+}
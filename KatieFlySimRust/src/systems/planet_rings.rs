@@ -0,0 +1,149 @@
+// Planet Rings - Map-defined ring systems drawn as translucent annuli
+// Ring particles orbit their body; rockets drifting with them can scoop fuel, rockets crossing them fast take damage
+
+use macroquad::prelude::*;
+
+use crate::game_constants::GameConstants;
+use crate::map_config::MapConfiguration;
+use crate::systems::World;
+
+/// A ring system around one planet
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanetRing {
+    pub body_name: String,
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    pub color: Color,
+    pub density: f32,
+    pub harvestable: bool,
+    pub hazardous: bool,
+}
+
+impl PlanetRing {
+    /// Whether a point at `offset` from the planet's center lies in the ring
+    pub fn contains(&self, offset: Vec2) -> bool {
+        (self.inner_radius..=self.outer_radius).contains(&offset.length())
+    }
+
+    /// Velocity of the ring particles at `offset`: a prograde circular orbit around a planet moving at `planet_velocity`
+    pub fn particle_velocity(&self, offset: Vec2, planet_mass: f32, planet_velocity: Vec2) -> Vec2 {
+        let distance = offset.length();
+        if distance < 0.001 {
+            return planet_velocity;
+        }
+        let speed = (GameConstants::G * planet_mass / distance).sqrt();
+        planet_velocity + offset.perp() / distance * speed
+    }
+
+    /// Fuel scooped over `delta_time` at `relative_speed` to the particles (0 unless harvestable and slow enough)
+    pub fn harvest(&self, relative_speed: f32, delta_time: f32) -> f32 {
+        if !self.harvestable || relative_speed > GameConstants::RING_HARVEST_MAX_SPEED {
+            return 0.0;
+        }
+        GameConstants::RING_HARVEST_RATE * self.density * delta_time
+    }
+
+    /// Hull damage over `delta_time` at `relative_speed` to the particles (0 unless hazardous and fast enough)
+    pub fn damage(&self, relative_speed: f32, delta_time: f32) -> f32 {
+        let excess = relative_speed - GameConstants::RING_HAZARD_SPEED;
+        if !self.hazardous || excess <= 0.0 {
+            return 0.0;
+        }
+        excess * GameConstants::RING_DAMAGE_RATE * self.density * delta_time
+    }
+}
+
+/// All ring systems in the session
+#[derive(Debug, Clone, Default)]
+pub struct PlanetRings {
+    rings: Vec<PlanetRing>,
+}
+
+impl PlanetRings {
+    pub fn from_map(map: &MapConfiguration) -> Self {
+        let rings = map
+            .rings
+            .iter()
+            .filter_map(|ring| {
+                let body = map.celestial_bodies.get(ring.body_index)?;
+                Some(PlanetRing {
+                    body_name: body.name.clone(),
+                    inner_radius: ring.inner_radius,
+                    outer_radius: ring.outer_radius,
+                    color: ring.color,
+                    density: ring.density.clamp(0.0, 1.0),
+                    harvestable: ring.harvestable,
+                    hazardous: ring.hazardous,
+                })
+            })
+            .collect();
+        PlanetRings { rings }
+    }
+
+    pub fn rings(&self) -> &[PlanetRing] {
+        &self.rings
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rings.is_empty()
+    }
+
+    /// Draw every ring around its planet (call in world space, before the planets and vehicles)
+    pub fn draw(&self, world: &World, zoom_level: f32) {
+        for ring in &self.rings {
+            let Some(planet) = world.planets().find(|p| p.name() == Some(ring.body_name.as_str())) else {
+                continue;
+            };
+            let center = planet.position();
+            let width = ring.outer_radius - ring.inner_radius;
+            let fill = Color::new(ring.color.r, ring.color.g, ring.color.b, ring.color.a * (0.1 + 0.4 * ring.density));
+            draw_arc(center.x, center.y, 255, ring.inner_radius, 0.0, width, 360.0, fill);
+
+            // A few denser bands so the ring doesn't read as a flat disc
+            let band = Color::new(ring.color.r, ring.color.g, ring.color.b, ring.color.a * 0.25 * ring.density);
+            let thickness = (2.0 * zoom_level).max(width * 0.04);
+            for fraction in [0.2, 0.45, 0.8] {
+                draw_arc(center.x, center.y, 255, ring.inner_radius + width * fraction, 0.0, thickness, 360.0, band);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring(harvestable: bool, hazardous: bool) -> PlanetRing {
+        PlanetRing {
+            body_name: "Saturn".to_string(),
+            inner_radius: 1000.0,
+            outer_radius: 2000.0,
+            color: WHITE,
+            density: 0.5,
+            harvestable,
+            hazardous,
+        }
+    }
+
+    #[test]
+    fn test_harvest_slow_and_damage_fast() {
+        let ring = ring(true, true);
+        assert!(ring.contains(Vec2::new(0.0, 1500.0)));
+        assert!(!ring.contains(Vec2::new(500.0, 0.0)));
+        assert!(!ring.contains(Vec2::new(2500.0, 0.0)));
+
+        // Particles orbit prograde (counter-clockwise) at circular speed, carried along with the planet
+        let velocity = ring.particle_velocity(Vec2::new(1600.0, 0.0), 4000.0, Vec2::new(10.0, 0.0));
+        assert!((velocity - Vec2::new(10.0, (GameConstants::G * 4000.0 / 1600.0).sqrt())).length() < 1e-3);
+
+        assert_eq!(ring.harvest(5.0, 2.0), GameConstants::RING_HARVEST_RATE * 0.5 * 2.0);
+        assert_eq!(ring.harvest(GameConstants::RING_HARVEST_MAX_SPEED + 1.0, 2.0), 0.0);
+        assert_eq!(ring.damage(GameConstants::RING_HAZARD_SPEED, 1.0), 0.0);
+        assert!(ring.damage(GameConstants::RING_HAZARD_SPEED + 100.0, 1.0) > 0.0);
+
+        // Decorative rings do neither
+        let plain = self::ring(false, false);
+        assert_eq!(plain.harvest(0.0, 1.0), 0.0);
+        assert_eq!(plain.damage(1000.0, 1.0), 0.0);
+    }
+}
@@ -1,8 +1,51 @@
 // Player Input System - Input abstraction for multiplayer support
 // Allows multiple players with different key bindings
 
+use std::collections::BTreeMap;
+
 use macroquad::prelude::*;
 
+use crate::player::{Gamepad, GamepadButton, Gamepads};
+
+/// Actions a local profile can rebind, by the name stored in the profile
+pub const BINDABLE_ACTIONS: [&str; 10] = [
+    "rotate_left",
+    "rotate_right",
+    "thrust",
+    "decrease_thrust",
+    "increase_thrust",
+    "zoom_out",
+    "zoom_in",
+    "convert_to_satellite",
+    "camera_focus",
+    "transfer_fuel",
+];
+
+/// Keys that can be bound (named as in KeyCode, e.g. "W", "Space", "Comma")
+const BINDABLE_KEYS: [KeyCode; 56] = [
+    KeyCode::A, KeyCode::B, KeyCode::C, KeyCode::D, KeyCode::E, KeyCode::F, KeyCode::G,
+    KeyCode::H, KeyCode::I, KeyCode::J, KeyCode::K, KeyCode::L, KeyCode::M, KeyCode::N,
+    KeyCode::O, KeyCode::P, KeyCode::Q, KeyCode::R, KeyCode::S, KeyCode::T, KeyCode::U,
+    KeyCode::V, KeyCode::W, KeyCode::X, KeyCode::Y, KeyCode::Z,
+    KeyCode::Key0, KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4,
+    KeyCode::Key5, KeyCode::Key6, KeyCode::Key7, KeyCode::Key8, KeyCode::Key9,
+    KeyCode::Space, KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right,
+    KeyCode::Comma, KeyCode::Period, KeyCode::Slash, KeyCode::Semicolon, KeyCode::Apostrophe,
+    KeyCode::LeftBracket, KeyCode::RightBracket, KeyCode::Minus, KeyCode::Equal,
+    KeyCode::LeftShift, KeyCode::RightShift, KeyCode::LeftControl, KeyCode::RightControl,
+    KeyCode::Home, KeyCode::End,
+];
+
+/// Look up a bindable key by name (case-insensitive)
+pub fn key_from_name(name: &str) -> Option<KeyCode> {
+    BINDABLE_KEYS.iter().copied().find(|key| key_name(*key).eq_ignore_ascii_case(name.trim()))
+}
+
+/// Name a key is stored under in a profile
+pub fn key_name(key: KeyCode) -> String {
+    format!("{:?}", key)
+}
+
 /// Input configuration for a single player
 #[derive(Debug, Clone)]
 pub struct PlayerInput {
@@ -24,6 +67,12 @@ pub struct PlayerInput {
     // Actions
     pub convert_to_satellite: KeyCode,
     pub camera_focus: KeyCode,  // Focus camera on this player
+    pub transfer_fuel: KeyCode, // Hold to pass fuel to a nearby rocket
+    pub shoot: KeyCode,         // Split-screen only; the other modes keep W
+
+    // Controller slot read alongside the keys (None = keyboard only)
+    pub gamepad: Option<usize>,
+    pad: Option<Gamepad>, // That controller's state this frame
 }
 
 impl PlayerInput {
@@ -36,10 +85,34 @@ impl PlayerInput {
             thrust: KeyCode::W,
             decrease_thrust: KeyCode::Z,
             increase_thrust: KeyCode::X,
-            zoom_out: KeyCode::Q,
-            zoom_in: KeyCode::E,
+            zoom_out: KeyCode::E,
+            zoom_in: KeyCode::Q,
             convert_to_satellite: KeyCode::C,
             camera_focus: KeyCode::R,
+            transfer_fuel: KeyCode::F,
+            shoot: KeyCode::S,
+            gamepad: None,
+            pad: None,
+        }
+    }
+
+    /// Keyboard controls of single player and online play (the arrow keys also steer there)
+    pub fn standard() -> Self {
+        PlayerInput {
+            player_id: 0,
+            rotate_left: KeyCode::A,
+            rotate_right: KeyCode::D,
+            thrust: KeyCode::Space,
+            decrease_thrust: KeyCode::Comma,
+            increase_thrust: KeyCode::Period,
+            zoom_out: KeyCode::E,
+            zoom_in: KeyCode::Q,
+            convert_to_satellite: KeyCode::C,
+            camera_focus: KeyCode::Home,
+            transfer_fuel: KeyCode::I,
+            shoot: KeyCode::W,
+            gamepad: None,
+            pad: None,
         }
     }
 
@@ -52,13 +125,134 @@ impl PlayerInput {
             thrust: KeyCode::Up,
             decrease_thrust: KeyCode::Comma,
             increase_thrust: KeyCode::Period,
-            zoom_out: KeyCode::Slash,
-            zoom_in: KeyCode::Apostrophe,
+            zoom_out: KeyCode::Apostrophe,
+            zoom_in: KeyCode::Slash,
             convert_to_satellite: KeyCode::RightBracket,
             camera_focus: KeyCode::Semicolon,
+            transfer_fuel: KeyCode::LeftBracket,
+            shoot: KeyCode::Down,
+            gamepad: None,
+            pad: None,
+        }
+    }
+
+    /// Create Player 3 input configuration (split-screen, on the numpad)
+    pub fn player3() -> Self {
+        PlayerInput {
+            player_id: 2,
+            rotate_left: KeyCode::Kp6,
+            rotate_right: KeyCode::Kp4,
+            thrust: KeyCode::Kp8,
+            decrease_thrust: KeyCode::Kp1,
+            increase_thrust: KeyCode::Kp3,
+            zoom_out: KeyCode::KpSubtract,
+            zoom_in: KeyCode::KpAdd,
+            convert_to_satellite: KeyCode::Kp9,
+            camera_focus: KeyCode::Kp7,
+            transfer_fuel: KeyCode::Kp0,
+            shoot: KeyCode::Kp5,
+            gamepad: None,
+            pad: None,
         }
     }
 
+    /// Create Player 4 input configuration (split-screen, around H/J/K/U)
+    pub fn player4() -> Self {
+        PlayerInput {
+            player_id: 3,
+            rotate_left: KeyCode::K,
+            rotate_right: KeyCode::H,
+            thrust: KeyCode::U,
+            decrease_thrust: KeyCode::N,
+            increase_thrust: KeyCode::M,
+            zoom_out: KeyCode::Y,
+            zoom_in: KeyCode::I,
+            convert_to_satellite: KeyCode::O,
+            camera_focus: KeyCode::B,
+            transfer_fuel: KeyCode::V,
+            shoot: KeyCode::J,
+            gamepad: None,
+            pad: None,
+        }
+    }
+
+    /// Split-screen keys for the player at `index` (0-3)
+    pub fn for_player(index: usize) -> Self {
+        match index {
+            0 => Self::player1(),
+            1 => Self::player2(),
+            2 => Self::player3(),
+            _ => Self::player4(),
+        }
+    }
+
+    /// Also read the controller in `slot`
+    pub fn with_gamepad(mut self, slot: Option<usize>) -> Self {
+        self.set_gamepad(slot);
+        self
+    }
+
+    /// Hand this player a controller slot (None = keyboard only)
+    pub fn set_gamepad(&mut self, slot: Option<usize>) {
+        self.gamepad = slot;
+        self.pad = None;
+    }
+
+    /// Pick up this frame's state of the assigned controller (call after `Gamepads::poll`)
+    pub fn sync_gamepad(&mut self, gamepads: &Gamepads) {
+        self.pad = self.gamepad.and_then(|slot| gamepads.get(slot));
+    }
+
+    /// Left stick across from the assigned controller, -1.0 (left) to 1.0 (right)
+    pub fn gamepad_steer(&self) -> f32 {
+        self.pad.map_or(0.0, |pad| pad.steer())
+    }
+
+    fn pad_down(&self, button: GamepadButton) -> bool {
+        self.pad.is_some_and(|pad| pad.is_down(button))
+    }
+
+    fn pad_pressed(&self, button: GamepadButton) -> bool {
+        self.pad.is_some_and(|pad| pad.just_pressed(button))
+    }
+
+    /// The key bound to an action from BINDABLE_ACTIONS
+    fn key_mut(&mut self, action: &str) -> Option<&mut KeyCode> {
+        Some(match action {
+            "rotate_left" => &mut self.rotate_left,
+            "rotate_right" => &mut self.rotate_right,
+            "thrust" => &mut self.thrust,
+            "decrease_thrust" => &mut self.decrease_thrust,
+            "increase_thrust" => &mut self.increase_thrust,
+            "zoom_out" => &mut self.zoom_out,
+            "zoom_in" => &mut self.zoom_in,
+            "convert_to_satellite" => &mut self.convert_to_satellite,
+            "camera_focus" => &mut self.camera_focus,
+            "transfer_fuel" => &mut self.transfer_fuel,
+            _ => return None,
+        })
+    }
+
+    /// Override keys with a profile's bindings (action name -> key name); unknown entries are skipped with a warning
+    pub fn apply_bindings(&mut self, bindings: &BTreeMap<String, String>) {
+        for (action, key_name) in bindings {
+            let Some(key) = key_from_name(key_name) else {
+                log::warn!("Ignoring binding {} = '{}': unknown key", action, key_name);
+                continue;
+            };
+            match self.key_mut(action) {
+                Some(slot) => *slot = key,
+                None => log::warn!("Ignoring binding for unknown action '{}'", action),
+            }
+        }
+    }
+
+    /// Builder form of apply_bindings
+    pub fn with_bindings(mut self, bindings: &BTreeMap<String, String>) -> Self {
+        self.apply_bindings(bindings);
+        self
+    }
+
     /// Get the rotation input for this frame (-1.0 = left, 1.0 = right, 0.0 = none)
     pub fn get_rotation_input(&self) -> f32 {
         let mut rotation = 0.0;
@@ -70,42 +264,64 @@ impl PlayerInput {
             rotation += 1.0;
         }
 
-        rotation
+        // Stick right turns the same way as the right-hand rotate key
+        rotation -= self.gamepad_steer();
+
+        rotation.clamp(-1.0, 1.0)
     }
 
-    /// Check if thrust key is pressed
+    /// Check if thrust key (or the trigger) is pressed
     pub fn is_thrusting(&self) -> bool {
-        is_key_down(self.thrust)
+        self.thrust_amount() > 0.0
+    }
+
+    /// How hard to burn, 0.0 to 1.0: full for the key, partial for a half-pulled trigger
+    pub fn thrust_amount(&self) -> f32 {
+        if is_key_down(self.thrust) {
+            1.0
+        } else {
+            self.pad.map_or(0.0, |pad| pad.thrust())
+        }
     }
 
     /// Check if decrease thrust was just pressed
     pub fn just_decreased_thrust(&self) -> bool {
-        is_key_pressed(self.decrease_thrust)
+        is_key_pressed(self.decrease_thrust) || self.pad_pressed(GamepadButton::LeftShoulder)
     }
 
     /// Check if increase thrust was just pressed
     pub fn just_increased_thrust(&self) -> bool {
-        is_key_pressed(self.increase_thrust)
+        is_key_pressed(self.increase_thrust) || self.pad_pressed(GamepadButton::RightShoulder)
     }
 
     /// Check if zoom out key is down
     pub fn is_zooming_out(&self) -> bool {
-        is_key_down(self.zoom_out)
+        is_key_down(self.zoom_out) || self.pad_down(GamepadButton::DPadDown)
     }
 
     /// Check if zoom in key is down
     pub fn is_zooming_in(&self) -> bool {
-        is_key_down(self.zoom_in)
+        is_key_down(self.zoom_in) || self.pad_down(GamepadButton::DPadUp)
     }
 
     /// Check if convert to satellite was just pressed
     pub fn just_converted_to_satellite(&self) -> bool {
-        is_key_pressed(self.convert_to_satellite)
+        is_key_pressed(self.convert_to_satellite) || self.pad_pressed(GamepadButton::East)
     }
 
     /// Check if camera focus was just pressed
     pub fn just_focused_camera(&self) -> bool {
-        is_key_pressed(self.camera_focus)
+        is_key_pressed(self.camera_focus) || self.pad_pressed(GamepadButton::North)
+    }
+
+    /// Check if the fuel transfer key is held
+    pub fn is_transferring_fuel(&self) -> bool {
+        is_key_down(self.transfer_fuel) || self.pad_down(GamepadButton::West)
+    }
+
+    /// Check if shoot was just pressed
+    pub fn just_shot(&self) -> bool {
+        is_key_pressed(self.shoot) || self.pad_pressed(GamepadButton::South)
     }
 }
 
@@ -143,11 +359,40 @@ mod tests {
     fn test_player_input_creation() {
         let p1 = PlayerInput::player1();
         assert_eq!(p1.player_id, 0);
-        assert_eq!(p1.rotate_left, KeyCode::A);
+        assert_eq!(p1.rotate_left, KeyCode::D);
 
         let p2 = PlayerInput::player2();
         assert_eq!(p2.player_id, 1);
-        assert_eq!(p2.rotate_left, KeyCode::Left);
+        assert_eq!(p2.rotate_left, KeyCode::Right);
+
+        // No two split-screen players share a key
+        let keys = |input: &PlayerInput| {
+            [
+                input.rotate_left, input.rotate_right, input.thrust, input.decrease_thrust, input.increase_thrust,
+                input.zoom_out, input.zoom_in, input.convert_to_satellite, input.camera_focus, input.transfer_fuel, input.shoot,
+            ]
+        };
+        let all: Vec<KeyCode> = (0..4).flat_map(|i| keys(&PlayerInput::for_player(i))).collect();
+        for (i, key) in all.iter().enumerate() {
+            assert!(!all[i + 1..].contains(key), "{:?} bound twice", key);
+        }
+        assert_eq!(PlayerInput::for_player(3).player_id, 3);
+    }
+
+    #[test]
+    fn test_apply_bindings() {
+        let mut bindings = BTreeMap::new();
+        bindings.insert("thrust".to_string(), "w".to_string());
+        bindings.insert("convert_to_satellite".to_string(), "LeftShift".to_string());
+        bindings.insert("thrust_harder".to_string(), "T".to_string());
+        bindings.insert("zoom_in".to_string(), "NotAKey".to_string());
+
+        let input = PlayerInput::standard().with_bindings(&bindings);
+        assert_eq!(input.thrust, KeyCode::W);
+        assert_eq!(input.convert_to_satellite, KeyCode::LeftShift);
+        assert_eq!(input.zoom_in, KeyCode::Q); // Unknown key left alone
+        assert_eq!(key_from_name(&key_name(KeyCode::Apostrophe)), Some(KeyCode::Apostrophe));
+        assert!(BINDABLE_ACTIONS.iter().all(|action| PlayerInput::standard().key_mut(action).is_some()));
     }
 
     #[test]
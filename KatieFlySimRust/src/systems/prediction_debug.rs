@@ -0,0 +1,126 @@
+// Prediction Debug - How far the client's predicted rocket drifted from each authoritative snapshot
+// Clients can also switch prediction off, freezing the world between snapshots, to see raw host state
+
+use std::collections::VecDeque;
+
+use macroquad::prelude::*;
+
+/// Snapshots of error history kept for the graph
+pub const ERROR_HISTORY_LEN: usize = 120;
+
+/// Last host state of our rocket, drawn as a ghost outline next to the predicted one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AuthoritativePose {
+    pub position: Vec2,
+    pub rotation: f32,
+}
+
+/// Client-side prediction switch and error statistics
+#[derive(Debug, Clone)]
+pub struct PredictionDebug {
+    prediction_enabled: bool,
+    overlay_visible: bool,
+    errors: VecDeque<f32>, // Distance predicted -> authoritative at each snapshot, oldest first
+    ghost: Option<AuthoritativePose>,
+}
+
+impl PredictionDebug {
+    pub fn new() -> Self {
+        PredictionDebug {
+            prediction_enabled: true,
+            overlay_visible: false,
+            errors: VecDeque::with_capacity(ERROR_HISTORY_LEN),
+            ghost: None,
+        }
+    }
+
+    pub fn prediction_enabled(&self) -> bool {
+        self.prediction_enabled
+    }
+
+    /// Switch local simulation between snapshots on or off; returns the new state
+    pub fn toggle_prediction(&mut self) -> bool {
+        self.prediction_enabled = !self.prediction_enabled;
+        self.errors.clear(); // Errors with and without prediction aren't comparable
+        self.prediction_enabled
+    }
+
+    pub fn overlay_visible(&self) -> bool {
+        self.overlay_visible
+    }
+
+    pub fn toggle_overlay(&mut self) {
+        self.overlay_visible = !self.overlay_visible;
+    }
+
+    /// Record a snapshot: where we had predicted our rocket (already in the snapshot's origin) and where the host put it
+    pub fn record_snapshot(&mut self, predicted: Option<Vec2>, authoritative: Option<AuthoritativePose>) {
+        if let (Some(predicted), Some(pose)) = (predicted, authoritative) {
+            if self.errors.len() == ERROR_HISTORY_LEN {
+                self.errors.pop_front();
+            }
+            self.errors.push_back(predicted.distance(pose.position));
+        }
+        self.ghost = authoritative;
+    }
+
+    /// Last authoritative pose of our rocket
+    pub fn ghost(&self) -> Option<AuthoritativePose> {
+        self.ghost
+    }
+
+    /// Error at each recent snapshot, oldest first
+    pub fn errors(&self) -> impl Iterator<Item = f32> + '_ {
+        self.errors.iter().copied()
+    }
+
+    pub fn latest_error(&self) -> Option<f32> {
+        self.errors.back().copied()
+    }
+
+    pub fn average_error(&self) -> Option<f32> {
+        if self.errors.is_empty() {
+            return None;
+        }
+        Some(self.errors.iter().sum::<f32>() / self.errors.len() as f32)
+    }
+
+    pub fn max_error(&self) -> Option<f32> {
+        self.errors.iter().copied().reduce(f32::max)
+    }
+}
+
+impl Default for PredictionDebug {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_history() {
+        let mut debug = PredictionDebug::new();
+        assert!(debug.average_error().is_none());
+
+        let pose = |x: f32| Some(AuthoritativePose { position: Vec2::new(x, 0.0), rotation: 0.0 });
+        debug.record_snapshot(Some(Vec2::ZERO), pose(3.0));
+        debug.record_snapshot(Some(Vec2::ZERO), pose(5.0));
+        debug.record_snapshot(None, pose(100.0)); // No predicted rocket yet (just spawned): ghost only
+        assert_eq!(debug.latest_error(), Some(5.0));
+        assert_eq!(debug.average_error(), Some(4.0));
+        assert_eq!(debug.max_error(), Some(5.0));
+        assert_eq!(debug.ghost().unwrap().position.x, 100.0);
+
+        for _ in 0..ERROR_HISTORY_LEN + 5 {
+            debug.record_snapshot(Some(Vec2::ZERO), pose(1.0));
+        }
+        assert_eq!(debug.errors().count(), ERROR_HISTORY_LEN);
+        assert_eq!(debug.max_error(), Some(1.0));
+
+        assert!(!debug.toggle_prediction());
+        assert!(debug.latest_error().is_none());
+    }
+}
@@ -0,0 +1,298 @@
+// Relay Control - Flying a distant rocket over the satellite relay network
+// Commands reach a rocket directly when it's close to the pilot's craft (or home base); farther out they
+// hop along satellites linked within transfer range. Each hop adds delay, and a link stretched toward the
+// edge of range adds more, so a sparse constellation makes for a sluggish (or missing) remote connection
+
+use std::collections::VecDeque;
+
+use macroquad::prelude::*;
+
+use crate::game_constants::GameConstants;
+use crate::systems::{EntityId, World};
+
+/// Longest single link between two nodes (craft, satellite or home base surface)
+pub const RELAY_RANGE: f32 = GameConstants::SATELLITE_TRANSFER_RANGE;
+/// Signal travel speed (units per second)
+const SIGNAL_SPEED: f32 = 20000.0;
+/// Processing delay at every node a command passes through (seconds)
+const HOP_DELAY: f32 = 0.05;
+/// Extra delay for a link stretched to the edge of range (scales with the square of the stretch)
+const STRETCH_DELAY: f32 = 0.25;
+/// Quality lost by a link at the edge of range
+const STRETCH_LOSS: f32 = 0.75;
+
+/// Where the player's commands leave from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlOrigin {
+    Craft(EntityId),    // The rocket the player is aboard
+    HomeBase(EntityId), // Mission control on the spawn planet
+}
+
+/// Route commands take from the origin to the controlled rocket
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelayChain {
+    pub origin: ControlOrigin,
+    pub relays: Vec<EntityId>,
+    pub points: Vec<Vec2>, // Origin, each relay, then the rocket (for drawing)
+    pub latency: f32,      // Seconds from key press to the rocket
+    pub quality: f32,      // Weakest link, 0-1
+}
+
+/// Delay and quality of one link `distance` long
+fn link(distance: f32) -> (f32, f32) {
+    let stretch = (distance / RELAY_RANGE).clamp(0.0, 1.0);
+    let delay = distance / SIGNAL_SPEED + HOP_DELAY + STRETCH_DELAY * stretch * stretch;
+    (delay, 1.0 - STRETCH_LOSS * stretch * stretch)
+}
+
+/// A place commands can start from: where it is, and how far its link reaches past that point
+struct Source {
+    origin: ControlOrigin,
+    center: Vec2,
+    radius: f32, // Planet surface for home base, zero for a craft
+}
+
+impl Source {
+    /// Point on the source closest to `to`, and the gap between them
+    fn reach(&self, to: Vec2) -> (Vec2, f32) {
+        let offset = to - self.center;
+        let length = offset.length();
+        if length <= self.radius {
+            return (to, 0.0);
+        }
+        (self.center + offset / length * self.radius, length - self.radius)
+    }
+}
+
+/// Cheapest (lowest latency) chain from the pilot's craft or home base to `target`
+pub fn find_chain(world: &World, craft: Option<EntityId>, home_base: Option<EntityId>, target: EntityId) -> Option<RelayChain> {
+    let target_position = world.get_rocket(target)?.position();
+    let mut sources = Vec::new();
+    if let Some(rocket) = craft.filter(|id| *id != target).and_then(|id| Some((id, world.get_rocket(id)?))) {
+        sources.push(Source { origin: ControlOrigin::Craft(rocket.0), center: rocket.1.position(), radius: 0.0 });
+    }
+    if let Some(planet) = home_base.and_then(|id| Some((id, world.get_planet(id)?))) {
+        sources.push(Source { origin: ControlOrigin::HomeBase(planet.0), center: planet.1.position(), radius: planet.1.radius() });
+    }
+    let relays: Vec<(EntityId, Vec2)> =
+        world.satellites_with_ids().filter(|(_, sat)| !sat.is_deorbiting()).map(|(id, sat)| (id, sat.position())).collect();
+
+    // Dijkstra over the relays, seeded by every relay a source reaches directly
+    let mut latency = vec![f32::INFINITY; relays.len()];
+    let mut quality = vec![0.0f32; relays.len()];
+    let mut previous: Vec<Option<usize>> = vec![None; relays.len()];
+    let mut seeded_by: Vec<Option<(usize, Vec2)>> = vec![None; relays.len()];
+    let mut done = vec![false; relays.len()];
+    for (s, source) in sources.iter().enumerate() {
+        for (r, (_, position)) in relays.iter().enumerate() {
+            let (from, distance) = source.reach(*position);
+            let (delay, link_quality) = link(distance);
+            if distance <= RELAY_RANGE && delay < latency[r] {
+                latency[r] = delay;
+                quality[r] = link_quality;
+                seeded_by[r] = Some((s, from));
+            }
+        }
+    }
+    while let Some(current) = (0..relays.len()).filter(|r| !done[*r] && latency[*r].is_finite()).min_by(|a, b| latency[*a].total_cmp(&latency[*b])) {
+        done[current] = true;
+        for next in 0..relays.len() {
+            if done[next] {
+                continue;
+            }
+            let distance = relays[current].1.distance(relays[next].1);
+            let (delay, link_quality) = link(distance);
+            if distance <= RELAY_RANGE && latency[current] + delay < latency[next] {
+                latency[next] = latency[current] + delay;
+                quality[next] = quality[current].min(link_quality);
+                previous[next] = Some(current);
+                seeded_by[next] = None;
+            }
+        }
+    }
+
+    // Best way in: straight from a source, or off the last relay
+    let mut best: Option<RelayChain> = None;
+    for source in &sources {
+        let (from, distance) = source.reach(target_position);
+        let (delay, link_quality) = link(distance);
+        if distance <= RELAY_RANGE && best.as_ref().is_none_or(|chain| delay < chain.latency) {
+            best = Some(RelayChain {
+                origin: source.origin,
+                relays: Vec::new(),
+                points: vec![from, target_position],
+                latency: delay,
+                quality: link_quality,
+            });
+        }
+    }
+    for (last, (_, position)) in relays.iter().enumerate() {
+        let distance = position.distance(target_position);
+        let (delay, link_quality) = link(distance);
+        if !latency[last].is_finite() || distance > RELAY_RANGE || best.as_ref().is_some_and(|chain| latency[last] + delay >= chain.latency) {
+            continue;
+        }
+        let mut path = vec![last];
+        while let Some(before) = previous[*path.last().unwrap()] {
+            path.push(before);
+        }
+        path.reverse();
+        let (source, from) = seeded_by[path[0]]?;
+        let mut points = vec![from];
+        points.extend(path.iter().map(|r| relays[*r].1));
+        points.push(target_position);
+        best = Some(RelayChain {
+            origin: sources[source].origin,
+            relays: path.iter().map(|r| relays[*r].0).collect(),
+            points,
+            latency: latency[last] + delay,
+            quality: quality[last].min(link_quality),
+        });
+    }
+    best
+}
+
+/// A command waiting out the link latency
+#[derive(Debug, Clone, Copy)]
+struct QueuedCommand {
+    due: f32,
+    thrust: f32,
+    rotation: f32, // Radians
+}
+
+/// The player flying a rocket other than the one they're aboard
+#[derive(Debug, Clone)]
+pub struct RemoteControl {
+    rocket_id: EntityId,
+    craft: Option<EntityId>,
+    home_base: Option<EntityId>,
+    chain: RelayChain,
+    queue: VecDeque<QueuedCommand>,
+}
+
+impl RemoteControl {
+    /// Connect to `rocket_id` from the craft the player is aboard (or home base)
+    pub fn connect(world: &World, craft: Option<EntityId>, home_base: Option<EntityId>, rocket_id: EntityId) -> Result<Self, String> {
+        if world.get_rocket(rocket_id).is_none() {
+            return Err(format!("Rocket {} not found", rocket_id));
+        }
+        let chain = find_chain(world, craft, home_base, rocket_id)
+            .ok_or_else(|| format!("No relay chain reaches rocket {} - link satellites within {:.0} of each other", rocket_id, RELAY_RANGE))?;
+        Ok(RemoteControl { rocket_id, craft, home_base, chain, queue: VecDeque::new() })
+    }
+
+    pub fn rocket_id(&self) -> EntityId {
+        self.rocket_id
+    }
+
+    /// The rocket the player is physically aboard
+    pub fn craft(&self) -> Option<EntityId> {
+        self.craft
+    }
+
+    pub fn chain(&self) -> &RelayChain {
+        &self.chain
+    }
+
+    /// Re-route after everything moved; false once no chain reaches the rocket
+    pub fn refresh(&mut self, world: &World) -> bool {
+        match find_chain(world, self.craft, self.home_base, self.rocket_id) {
+            Some(chain) => {
+                self.chain = chain;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Send this tick's input; it reaches the rocket one chain latency after `now`
+    pub fn send(&mut self, now: f32, thrust: f32, rotation: f32) {
+        self.queue.push_back(QueuedCommand { due: now + self.chain.latency, thrust, rotation });
+    }
+
+    /// Apply every command whose delay has run out
+    pub fn deliver(&mut self, world: &mut World, now: f32) {
+        while self.queue.front().is_some_and(|command| command.due <= now) {
+            let command = self.queue.pop_front().unwrap();
+            if let Some(rocket) = world.get_rocket_mut(self.rocket_id) {
+                rocket.set_thrust_level(command.thrust);
+                if command.rotation != 0.0 {
+                    rocket.rotate(command.rotation);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Planet, Rocket, Satellite};
+
+    fn world_with_rockets(far: f32) -> (World, EntityId, EntityId) {
+        let mut world = World::new();
+        let craft = world.add_rocket(Rocket::new(Vec2::ZERO, Vec2::ZERO, WHITE, 1.0));
+        let target = world.add_rocket(Rocket::new(Vec2::new(far, 0.0), Vec2::ZERO, WHITE, 1.0));
+        (world, craft, target)
+    }
+
+    #[test]
+    fn test_close_rocket_links_directly() {
+        let (world, craft, target) = world_with_rockets(RELAY_RANGE * 0.5);
+        let chain = find_chain(&world, Some(craft), None, target).unwrap();
+        assert!(chain.relays.is_empty());
+        assert_eq!(chain.origin, ControlOrigin::Craft(craft));
+        assert!(chain.quality > 0.75 && chain.quality < 1.0);
+    }
+
+    #[test]
+    fn test_relays_bridge_the_gap_and_add_latency() {
+        let (mut world, craft, target) = world_with_rockets(RELAY_RANGE * 2.5);
+        assert!(find_chain(&world, Some(craft), None, target).is_none());
+
+        let near = world.add_satellite(Satellite::new(Vec2::new(RELAY_RANGE * 0.8, 0.0), Vec2::ZERO, WHITE));
+        let far = world.add_satellite(Satellite::new(Vec2::new(RELAY_RANGE * 1.6, 0.0), Vec2::ZERO, WHITE));
+        let chain = find_chain(&world, Some(craft), None, target).unwrap();
+        assert_eq!(chain.relays, vec![near, far]);
+        assert_eq!(chain.points.len(), 4);
+
+        // A third relay evens the links out: more hops, but no link stretched near the edge
+        let (mut dense, craft, target) = world_with_rockets(RELAY_RANGE * 2.5);
+        for i in 1..=3 {
+            dense.add_satellite(Satellite::new(Vec2::new(RELAY_RANGE * 0.625 * i as f32, 0.0), Vec2::ZERO, WHITE));
+        }
+        let dense_chain = find_chain(&dense, Some(craft), None, target).unwrap();
+        assert!(dense_chain.quality > chain.quality);
+
+        let (close, craft, target) = world_with_rockets(RELAY_RANGE * 0.5);
+        let direct = find_chain(&close, Some(craft), None, target).unwrap();
+        assert!(chain.latency > direct.latency);
+    }
+
+    #[test]
+    fn test_home_base_reaches_from_the_surface() {
+        let (mut world, craft, target) = world_with_rockets(RELAY_RANGE * 10.0);
+        let home = world.add_planet(Planet::new(Vec2::new(RELAY_RANGE * 10.0, 5000.0), 4000.0, 100000.0, BLUE));
+        // The rocket sits 1000 above the surface; far from the craft, but mission control can see it
+        let chain = find_chain(&world, Some(craft), Some(home), target).unwrap();
+        assert_eq!(chain.origin, ControlOrigin::HomeBase(home));
+        assert!((chain.points[0] - Vec2::new(RELAY_RANGE * 10.0, 1000.0)).length() < 1.0);
+    }
+
+    #[test]
+    fn test_commands_arrive_after_the_latency() {
+        let (mut world, craft, target) = world_with_rockets(RELAY_RANGE * 0.5);
+        let mut remote = RemoteControl::connect(&world, Some(craft), None, target).unwrap();
+        let latency = remote.chain().latency;
+        remote.send(0.0, 1.0, 0.0);
+
+        remote.deliver(&mut world, latency * 0.5);
+        assert_eq!(world.get_rocket(target).unwrap().thrust_level(), 0.0);
+        remote.deliver(&mut world, latency);
+        assert_eq!(world.get_rocket(target).unwrap().thrust_level(), 1.0);
+
+        // Drifting apart breaks the link
+        world.get_rocket_mut(target).unwrap().set_position(Vec2::new(RELAY_RANGE * 3.0, 0.0));
+        assert!(!remote.refresh(&world));
+    }
+}
@@ -0,0 +1,216 @@
+// Route Planner - Multi-leg routes between waypoints and planets with delta-v and fuel estimates
+// Each leg is treated as a Hohmann transfer around the dominant body; fuel follows the rocket equation at full thrust
+
+use macroquad::prelude::*;
+
+use crate::entities::Rocket;
+use crate::game_constants::GameConstants;
+use crate::systems::{EntityId, Waypoints, World};
+
+/// A place the route passes through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteStop {
+    Waypoint(u32),
+    Planet(EntityId),
+}
+
+/// One planned transfer between consecutive stops
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteLeg {
+    pub from: Vec2,
+    pub to: Vec2,
+    pub departure_burn: f32, // Delta-v leaving the start orbit (first leg includes circularizing)
+    pub arrival_burn: f32,   // Delta-v circularizing at the destination
+    pub transfer_time: f32,  // Seconds along the transfer ellipse
+    pub fuel: f32,           // Fuel burned on this leg at the mass left after earlier legs
+}
+
+impl RouteLeg {
+    pub fn delta_v(&self) -> f32 {
+        self.departure_burn + self.arrival_burn
+    }
+}
+
+/// Estimated route from the rocket through every stop
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutePlan {
+    pub legs: Vec<RouteLeg>,
+    pub total_delta_v: f32,
+    pub total_time: f32,
+    pub fuel_required: f32,
+    pub fuel_available: f32,
+}
+
+impl RoutePlan {
+    pub fn exceeds_fuel(&self) -> bool {
+        self.fuel_required > self.fuel_available
+    }
+}
+
+/// Effective exhaust velocity at full thrust (thrust power over fuel burned per second)
+pub fn exhaust_velocity() -> f32 {
+    GameConstants::ENGINE_SPECIFIC_IMPULSE
+}
+
+/// Fuel a rocket of `mass` burns to change velocity by `delta_v` (rocket equation)
+pub fn fuel_for_delta_v(mass: f32, delta_v: f32) -> f32 {
+    mass * (1.0 - (-delta_v / exhaust_velocity()).exp())
+}
+
+/// Departure burn, arrival burn and transfer time between circular orbits of radius `r1` and `r2`
+pub fn hohmann_transfer(mu: f32, r1: f32, r2: f32) -> (f32, f32, f32) {
+    let semi_major = (r1 + r2) / 2.0;
+    let departure = (mu / r1).sqrt() * ((2.0 * r2 / (r1 + r2)).sqrt() - 1.0);
+    let arrival = (mu / r2).sqrt() * (1.0 - (2.0 * r1 / (r1 + r2)).sqrt());
+    let time = GameConstants::PI * (semi_major.powi(3) / mu).sqrt();
+    (departure.abs(), arrival.abs(), time)
+}
+
+/// Ordered stops the player has picked
+#[derive(Debug, Clone, Default)]
+pub struct RoutePlanner {
+    stops: Vec<RouteStop>,
+}
+
+impl RoutePlanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn stops(&self) -> &[RouteStop] {
+        &self.stops
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stops.is_empty()
+    }
+
+    /// Append a stop (ignored if it's already the last one)
+    pub fn push(&mut self, stop: RouteStop) {
+        if self.stops.last() != Some(&stop) {
+            self.stops.push(stop);
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<RouteStop> {
+        self.stops.pop()
+    }
+
+    pub fn clear(&mut self) {
+        self.stops.clear();
+    }
+
+    /// Where a stop is now (None once its waypoint or planet is gone)
+    pub fn stop_position(stop: RouteStop, world: &World, waypoints: &Waypoints) -> Option<Vec2> {
+        match stop {
+            RouteStop::Waypoint(id) => waypoints.list().iter().find(|w| w.id == id).map(|w| w.position),
+            RouteStop::Planet(id) => world.get_planet(id).map(|planet| planet.position()),
+        }
+    }
+
+    /// Drop stops whose waypoint or planet no longer exists
+    pub fn prune(&mut self, world: &World, waypoints: &Waypoints) {
+        self.stops.retain(|stop| Self::stop_position(*stop, world, waypoints).is_some());
+    }
+
+    /// Estimate the route from `rocket` through every stop, around the most massive planet
+    pub fn plan(&self, world: &World, waypoints: &Waypoints, rocket: &Rocket) -> Option<RoutePlan> {
+        if self.stops.is_empty() {
+            return None;
+        }
+        let central = world
+            .planets()
+            .max_by(|a, b| a.mass().partial_cmp(&b.mass()).unwrap_or(std::cmp::Ordering::Equal))?;
+        let mu = GameConstants::G * central.mass();
+        let center = central.position();
+        let radius_of = |point: Vec2| point.distance(center).max(central.radius());
+
+        // Circularizing where the rocket is now comes first
+        let relative_pos = rocket.position() - center;
+        let relative_vel = rocket.velocity() - central.velocity();
+        let r0 = radius_of(rocket.position());
+        let prograde = if relative_pos.perp_dot(relative_vel) >= 0.0 { relative_pos.perp() } else { -relative_pos.perp() };
+        let circular_vel = prograde.normalize_or_zero() * (mu / r0).sqrt();
+        let mut correction = if rocket.is_landed() { 0.0 } else { (relative_vel - circular_vel).length() };
+
+        let mut mass = rocket.mass();
+        let mut legs = Vec::new();
+        let mut from = rocket.position();
+        for stop in &self.stops {
+            let to = Self::stop_position(*stop, world, waypoints)?;
+            let (departure, arrival, time) = hohmann_transfer(mu, radius_of(from), radius_of(to));
+            let departure_burn = departure + correction;
+            correction = 0.0;
+
+            let fuel = fuel_for_delta_v(mass, departure_burn + arrival);
+            mass -= fuel;
+            legs.push(RouteLeg { from, to, departure_burn, arrival_burn: arrival, transfer_time: time, fuel });
+            from = to;
+        }
+
+        Some(RoutePlan {
+            total_delta_v: legs.iter().map(RouteLeg::delta_v).sum(),
+            total_time: legs.iter().map(|leg| leg.transfer_time).sum(),
+            fuel_required: legs.iter().map(|leg| leg.fuel).sum(),
+            fuel_available: rocket.current_fuel(),
+            legs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hohmann_matches_circular_speeds() {
+        let mu = 1.0e6;
+        let (departure, arrival, time) = hohmann_transfer(mu, 1000.0, 1000.0);
+        assert!(departure.abs() < 1e-3 && arrival.abs() < 1e-3);
+        // Half an orbit of the same circle
+        assert!((time - GameConstants::PI * (1000.0f32.powi(3) / mu).sqrt()).abs() < 1e-2);
+
+        // Raising the orbit costs more going further out
+        let (low, _, _) = hohmann_transfer(mu, 1000.0, 2000.0);
+        let (high, _, _) = hohmann_transfer(mu, 1000.0, 4000.0);
+        assert!(high > low && low > 0.0);
+    }
+
+    #[test]
+    fn test_fuel_follows_rocket_equation() {
+        assert_eq!(fuel_for_delta_v(50.0, 0.0), 0.0);
+        // Burning everything but the dry mass gives ve * ln(m0 / m1)
+        let delta_v = exhaust_velocity() * (129.0f32 / 1.0).ln();
+        assert!((fuel_for_delta_v(129.0, delta_v) - 128.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_plan_warns_when_fuel_runs_short() {
+        let mut world = World::new();
+        let planet = crate::entities::Planet::new(Vec2::ZERO, GameConstants::MAIN_PLANET_RADIUS, GameConstants::MAIN_PLANET_MASS, BLUE);
+        world.add_planet(planet);
+        let mut waypoints = Waypoints::new();
+        let near = waypoints.add(Vec2::new(0.0, 20_000.0), None, false);
+        let far = waypoints.add(Vec2::new(0.0, -45_000.0), None, false);
+
+        let mut rocket = Rocket::new(Vec2::new(15_000.0, 0.0), Vec2::ZERO, WHITE, GameConstants::ROCKET_BASE_MASS);
+        rocket.set_fuel(0.1);
+        let mut planner = RoutePlanner::new();
+        planner.push(RouteStop::Waypoint(near));
+        planner.push(RouteStop::Waypoint(near));
+        planner.push(RouteStop::Waypoint(far));
+
+        let plan = planner.plan(&world, &waypoints, &rocket).unwrap();
+        assert_eq!(plan.legs.len(), 2);
+        assert!(plan.legs.iter().all(|leg| leg.fuel > 0.0));
+        assert!(plan.exceeds_fuel());
+
+        rocket.set_fuel(GameConstants::ROCKET_MAX_FUEL);
+        assert!(!planner.plan(&world, &waypoints, &rocket).unwrap().exceeds_fuel());
+
+        // Removing a waypoint drops it from the route
+        waypoints.remove(far, None);
+        planner.prune(&world, &waypoints);
+        assert_eq!(planner.stops(), &[RouteStop::Waypoint(near)]);
+    }
+}
@@ -0,0 +1,349 @@
+// Sample Return - Collect surface samples and bring them home for mission credit
+// Sites come from the map; the home body is the map's spawn body
+
+use macroquad::prelude::*;
+
+use crate::game_constants::GameConstants;
+use crate::map_config::MapConfiguration;
+use crate::systems::{EntityId, World};
+use crate::utils::vector_helper;
+
+/// Where a site's sample currently is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleState {
+    Available,
+    Carried,
+    Returned,
+}
+
+/// A sample site on a planet surface
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleSite {
+    pub name: String,
+    pub body_name: String,
+    pub angle: f32,
+    pub state: SampleState,
+}
+
+/// Why a sample couldn't be collected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleCollectError {
+    NotLanded,
+    NoSiteNearby,
+    AlreadyCollected,
+}
+
+impl SampleCollectError {
+    pub fn message(&self) -> &'static str {
+        match self {
+            SampleCollectError::NotLanded => "Land at a sample site first",
+            SampleCollectError::NoSiteNearby => "No sample site nearby",
+            SampleCollectError::AlreadyCollected => "Sample already collected here",
+        }
+    }
+}
+
+/// Sample-return mission state for one session
+#[derive(Debug, Clone, Default)]
+pub struct SampleMission {
+    sites: Vec<SampleSite>,
+    home_body: Option<String>,
+    carrier: Option<EntityId>, // Rocket holding the carried samples
+}
+
+impl SampleMission {
+    /// Build the mission from a map's sample sites (empty if the map has none)
+    pub fn from_map(map: &MapConfiguration) -> Self {
+        let sites = map
+            .sample_sites
+            .iter()
+            .filter_map(|site| {
+                let body = map.celestial_bodies.get(site.body_index)?;
+                Some(SampleSite {
+                    name: site.name.clone(),
+                    body_name: body.name.clone(),
+                    angle: site.angle,
+                    state: SampleState::Available,
+                })
+            })
+            .collect();
+
+        SampleMission {
+            sites,
+            home_body: map.celestial_bodies.get(map.player_spawn_body_index).map(|b| b.name.clone()),
+            carrier: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sites.is_empty()
+    }
+
+    pub fn sites(&self) -> &[SampleSite] {
+        &self.sites
+    }
+
+    pub fn home_body(&self) -> Option<&str> {
+        self.home_body.as_deref()
+    }
+
+    pub fn carried_count(&self) -> usize {
+        self.count(SampleState::Carried)
+    }
+
+    pub fn returned_count(&self) -> usize {
+        self.count(SampleState::Returned)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        !self.sites.is_empty() && self.returned_count() == self.sites.len()
+    }
+
+    fn count(&self, state: SampleState) -> usize {
+        self.sites.iter().filter(|s| s.state == state).count()
+    }
+
+    /// World position of a site, following its planet
+    pub fn site_position(&self, world: &World, site: &SampleSite) -> Option<Vec2> {
+        let planet = world.planets().find(|p| p.name() == Some(site.body_name.as_str()))?;
+        Some(planet.position() + Vec2::from_angle(site.angle) * planet.surface_radius_at(site.angle))
+    }
+
+    /// Collect the sample at the site the rocket is landed next to
+    /// Returns the site name on success
+    pub fn try_collect(&mut self, world: &World, rocket_id: EntityId) -> Result<String, SampleCollectError> {
+        let rocket = world.get_rocket(rocket_id).ok_or(SampleCollectError::NotLanded)?;
+        let planet = rocket
+            .landed_on_planet_id()
+            .and_then(|id| world.get_planet(id))
+            .ok_or(SampleCollectError::NotLanded)?;
+        let planet_name = planet.name().ok_or(SampleCollectError::NoSiteNearby)?;
+
+        let surface_angle = vector_helper::angle(rocket.position() - planet.position());
+        self.collect_at(planet_name, planet.radius(), surface_angle, rocket_id)
+    }
+
+    /// Collect the sample at the site nearest a surface position (rocket or rover)
+    /// The sample is carried by `carrier_rocket_id`
+    pub fn collect_at(
+        &mut self,
+        planet_name: &str,
+        planet_radius: f32,
+        surface_angle: f32,
+        carrier_rocket_id: EntityId,
+    ) -> Result<String, SampleCollectError> {
+        // Distance along the surface to each site on this planet
+        let nearest = self
+            .sites
+            .iter()
+            .enumerate()
+            .filter(|(_, site)| site.body_name == planet_name)
+            .map(|(index, site)| {
+                (vector_helper::angle_difference(surface_angle, site.angle).abs() * planet_radius, index)
+            })
+            .filter(|(arc_distance, _)| *arc_distance <= GameConstants::SAMPLE_SITE_RANGE)
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let (_, index) = nearest.ok_or(SampleCollectError::NoSiteNearby)?;
+        if self.sites[index].state != SampleState::Available {
+            return Err(SampleCollectError::AlreadyCollected);
+        }
+
+        // Samples stay with one rocket; switching carriers drops what the old one held
+        if self.carrier.is_some_and(|carrier| carrier != carrier_rocket_id) {
+            for site in self.sites.iter_mut().filter(|s| s.state == SampleState::Carried) {
+                site.state = SampleState::Available;
+            }
+        }
+
+        self.sites[index].state = SampleState::Carried;
+        self.carrier = Some(carrier_rocket_id);
+        Ok(self.sites[index].name.clone())
+    }
+
+    /// Put carried samples back at their sites (e.g. a rover was left behind with them)
+    pub fn drop_samples(&mut self, site_names: &[String]) {
+        for site in self.sites.iter_mut().filter(|s| s.state == SampleState::Carried) {
+            if site_names.contains(&site.name) {
+                site.state = SampleState::Available;
+            }
+        }
+        if self.carried_count() == 0 {
+            self.carrier = None;
+        }
+    }
+
+    /// Handle a landing; returns how many samples were delivered home
+    pub fn on_landing(&mut self, rocket_id: EntityId, planet_name: Option<&str>) -> usize {
+        if self.carrier != Some(rocket_id) || planet_name.is_none() || planet_name != self.home_body.as_deref() {
+            return 0;
+        }
+
+        let mut delivered = 0;
+        for site in self.sites.iter_mut().filter(|s| s.state == SampleState::Carried) {
+            site.state = SampleState::Returned;
+            delivered += 1;
+        }
+        self.carrier = None;
+        delivered
+    }
+
+    /// Drop carried samples if their rocket no longer exists (destroyed or converted)
+    /// Returns how many samples were lost
+    pub fn update(&mut self, world: &World) -> usize {
+        let Some(carrier) = self.carrier else {
+            return 0;
+        };
+        if world.get_rocket(carrier).is_some() {
+            return 0;
+        }
+
+        let mut lost = 0;
+        for site in self.sites.iter_mut().filter(|s| s.state == SampleState::Carried) {
+            site.state = SampleState::Available;
+            lost += 1;
+        }
+        self.carrier = None;
+        lost
+    }
+
+    /// Draw site markers on planet surfaces (call in world space)
+    pub fn draw_sites(&self, world: &World, zoom_level: f32) {
+        for site in &self.sites {
+            let Some(position) = self.site_position(world, site) else {
+                continue;
+            };
+            let color = match site.state {
+                SampleState::Available => Color::new(0.2, 1.0, 0.6, 0.9),
+                SampleState::Carried => Color::new(1.0, 0.8, 0.2, 0.6),
+                SampleState::Returned => Color::new(0.5, 0.5, 0.5, 0.6),
+            };
+
+            // Keep the marker visible when zoomed out
+            let size = (12.0 * zoom_level).max(12.0);
+            draw_circle_lines(position.x, position.y, size, 2.0 * zoom_level.max(1.0), color);
+            draw_circle(position.x, position.y, size * 0.35, color);
+        }
+    }
+
+    /// Draw the mission status line at the bottom left (call in screen space)
+    pub fn draw_status(&self) {
+        if self.sites.is_empty() {
+            return;
+        }
+
+        let home = self.home_body.as_deref().unwrap_or("home");
+        let text = if self.is_complete() {
+            format!("Samples: all {} returned - mission complete!", self.sites.len())
+        } else {
+            format!(
+                "Samples: {} carried, {}/{} returned to {} (S to collect)",
+                self.carried_count(),
+                self.returned_count(),
+                self.sites.len(),
+                home
+            )
+        };
+        draw_text(&text, 10.0, screen_height() - 15.0, 18.0, Color::new(0.2, 1.0, 0.6, 0.9));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Planet, Rocket};
+    use crate::map_config::{CelestialBodyConfig, MapTunables, SampleSiteConfig};
+    use crate::physics::GravityModel;
+
+    fn body(name: &str) -> CelestialBodyConfig {
+        CelestialBodyConfig {
+            name: name.to_string(),
+            mass: 1000.0,
+            radius: 1000.0,
+            color: WHITE,
+            orbital_parent_index: None,
+            orbital_distance: None,
+            orbital_period: None,
+            initial_angle: 0.0,
+            is_pinned: true,
+            terrain: None,
+        }
+    }
+
+    fn test_map() -> MapConfiguration {
+        MapConfiguration {
+            name: "test".to_string(),
+            description: String::new(),
+            celestial_bodies: vec![body("Earth"), body("Moon")],
+            player_spawn_body_index: 0,
+            central_body_index: Some(0),
+            tidal_breakup: false,
+            gravity_model: GravityModel::Realistic,
+            tunables: MapTunables::default(),
+            sample_sites: vec![SampleSiteConfig {
+                name: "Crater".to_string(),
+                body_index: 1,
+                angle: 0.0,
+            }],
+            fuel_deposits: Vec::new(),
+            landing_zones: Vec::new(),
+            rings: Vec::new(),
+            arena: None,
+        }
+    }
+
+    /// World with Earth and Moon far apart and a rocket landed on the Moon at `angle`
+    fn world_with_landed_rocket(angle: f32) -> (World, EntityId) {
+        let mut world = World::new();
+        let mut earth = Planet::new(Vec2::new(-100_000.0, 0.0), 1000.0, 1000.0, BLUE);
+        earth.set_name("Earth".to_string());
+        world.add_planet(earth);
+        let mut moon = Planet::new(Vec2::ZERO, 1000.0, 1000.0, GRAY);
+        moon.set_name("Moon".to_string());
+        let moon_id = world.add_planet(moon);
+
+        let surface = Vec2::from_angle(angle) * 1000.0;
+        let mut rocket = Rocket::new(surface, Vec2::ZERO, WHITE, 1.0);
+        rocket.land_on_planet(moon_id, surface, Vec2::ZERO);
+        let rocket_id = world.add_rocket(rocket);
+        (world, rocket_id)
+    }
+
+    #[test]
+    fn test_collect_and_return_sample() {
+        let mut mission = SampleMission::from_map(&test_map());
+        let (world, rocket_id) = world_with_landed_rocket(0.1); // 100 px along the surface
+
+        assert_eq!(mission.try_collect(&world, rocket_id), Ok("Crater".to_string()));
+        assert_eq!(mission.carried_count(), 1);
+        assert_eq!(mission.try_collect(&world, rocket_id), Err(SampleCollectError::AlreadyCollected));
+
+        // Landing anywhere but home doesn't count
+        assert_eq!(mission.on_landing(rocket_id, Some("Moon")), 0);
+        assert_eq!(mission.on_landing(rocket_id, Some("Earth")), 1);
+        assert!(mission.is_complete());
+    }
+
+    #[test]
+    fn test_collect_requires_nearby_site() {
+        let mut mission = SampleMission::from_map(&test_map());
+        let (world, rocket_id) = world_with_landed_rocket(std::f32::consts::PI);
+        assert_eq!(mission.try_collect(&world, rocket_id), Err(SampleCollectError::NoSiteNearby));
+
+        let mut world = World::new();
+        let rocket_id = world.add_rocket(Rocket::new(Vec2::new(1200.0, 0.0), Vec2::ZERO, WHITE, 1.0));
+        assert_eq!(mission.try_collect(&world, rocket_id), Err(SampleCollectError::NotLanded));
+    }
+
+    #[test]
+    fn test_samples_lost_with_rocket() {
+        let mut mission = SampleMission::from_map(&test_map());
+        let (mut world, rocket_id) = world_with_landed_rocket(0.0);
+        mission.try_collect(&world, rocket_id).unwrap();
+
+        assert_eq!(mission.update(&world), 0);
+        world.convert_rocket_to_satellite(rocket_id);
+        assert_eq!(mission.update(&world), 1);
+        assert_eq!(mission.sites()[0].state, SampleState::Available);
+    }
+}
@@ -0,0 +1,300 @@
+// Satellite Groups - Several satellites picked in the network map, and the commands issued to all of them at once
+// Groups come from shift-clicks and drag-boxes; fuel sent to a hub travels along linked satellites each tick
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use macroquad::prelude::*;
+
+use crate::game_constants::GameConstants;
+use crate::systems::satellite_manager::{link_clear, BalanceNode};
+use crate::systems::{EntityId, SatelliteRole, World};
+
+/// Fuel per second a satellite sends toward its hub
+pub const HUB_TRANSFER_RATE: f32 = 4.0;
+
+/// Station-keeping settings applied to a group: how often to check the orbit, and the fuel kept back for it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaintenancePreset {
+    pub name: &'static str,
+    pub interval: f32, // Seconds between orbit checks
+    pub reserve: f32,  // Fuel never given away
+}
+
+/// Presets the batch command cycles through (Standard matches a new satellite)
+pub const MAINTENANCE_PRESETS: [MaintenancePreset; 3] = [
+    MaintenancePreset { name: "Tight", interval: 2.0, reserve: 40.0 },
+    MaintenancePreset { name: "Standard", interval: 5.0, reserve: 20.0 },
+    MaintenancePreset { name: "Loose", interval: 15.0, reserve: 5.0 },
+];
+
+/// Share of the tank a tanker and a relay keep back when set by role
+const TANKER_RESERVE: f32 = 0.1;
+const RELAY_RESERVE: f32 = 0.5;
+
+/// A command for every satellite in a group
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BatchCommand {
+    SetRole(SatelliteRole),
+    SetMaintenance(MaintenancePreset),
+    Mark,
+    Unmark,
+    Deorbit,
+    FuelToHub(EntityId),
+}
+
+impl BatchCommand {
+    /// Past tense for the result toast, e.g. "Deorbited 4 satellites"
+    pub fn describe(&self, count: usize) -> String {
+        let satellites = if count == 1 { "satellite".to_string() } else { "satellites".to_string() };
+        match self {
+            BatchCommand::SetRole(role) => format!("Set {} {} to {}", count, satellites, role.name()),
+            BatchCommand::SetMaintenance(preset) => format!("{} maintenance on {} {}", preset.name, count, satellites),
+            BatchCommand::Mark => format!("Marked {} {}", count, satellites),
+            BatchCommand::Unmark => format!("Unmarked {} {}", count, satellites),
+            BatchCommand::Deorbit => format!("Deorbit burn on {} {}", count, satellites),
+            BatchCommand::FuelToHub(hub) => format!("{} {} sending fuel to satellite {}", count, satellites, hub),
+        }
+    }
+}
+
+/// The satellites picked in the network map
+#[derive(Debug, Clone, Default)]
+pub struct SatelliteSelection {
+    ids: BTreeSet<EntityId>,
+}
+
+impl SatelliteSelection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = EntityId> + '_ {
+        self.ids.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn contains(&self, id: EntityId) -> bool {
+        self.ids.contains(&id)
+    }
+
+    /// Shift-click: add or remove one satellite
+    pub fn toggle(&mut self, id: EntityId) {
+        if !self.ids.remove(&id) {
+            self.ids.insert(id);
+        }
+    }
+
+    /// Drag-box: select the satellites inside the box between two world points (added to the group when `add`)
+    pub fn select_box(&mut self, world: &World, corner_a: Vec2, corner_b: Vec2, add: bool) {
+        let (min, max) = (corner_a.min(corner_b), corner_a.max(corner_b));
+        if !add {
+            self.ids.clear();
+        }
+        for (id, satellite) in world.satellites_with_ids() {
+            let position = satellite.position();
+            if position.cmpge(min).all() && position.cmple(max).all() {
+                self.ids.insert(id);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.ids.clear();
+    }
+
+    /// Forget satellites that no longer exist
+    pub fn retain_existing(&mut self, world: &World) {
+        self.ids.retain(|id| world.get_satellite(*id).is_some());
+    }
+
+    /// Run a command on every selected satellite; returns how many it applied to
+    pub fn apply(&self, world: &mut World, marked: &mut HashSet<EntityId>, command: BatchCommand) -> usize {
+        let mut applied = 0;
+        for id in self.ids() {
+            if world.get_satellite(id).is_none() {
+                continue;
+            }
+            let done = match command {
+                BatchCommand::SetRole(role) => set_role(world, id, role),
+                BatchCommand::SetMaintenance(preset) => {
+                    if let Some(satellite) = world.get_satellite_mut(id) {
+                        satellite.set_maintenance_interval(preset.interval);
+                        satellite.set_maintenance_fuel_reserve(preset.reserve);
+                    }
+                    true
+                }
+                BatchCommand::Mark => marked.insert(id),
+                BatchCommand::Unmark => marked.remove(&id),
+                BatchCommand::Deorbit => world.command_deorbit(id),
+                BatchCommand::FuelToHub(hub) => world.send_fuel_to_hub(id, hub),
+            };
+            if done {
+                applied += 1;
+            }
+        }
+        log::info!("{}", command.describe(applied));
+        applied
+    }
+}
+
+/// Role presets: collectors are flagged as such, tankers and relays differ in how much fuel they keep back
+fn set_role(world: &mut World, id: EntityId, role: SatelliteRole) -> bool {
+    let Some(satellite) = world.get_satellite_mut(id) else {
+        return false;
+    };
+    let reserve = match role {
+        SatelliteRole::Collector => {
+            satellite.set_is_collecting_fuel(true);
+            return true;
+        }
+        SatelliteRole::Tanker => TANKER_RESERVE,
+        SatelliteRole::Relay => RELAY_RESERVE,
+        SatelliteRole::Depleted => return false, // A state, not something to assign
+    };
+    satellite.set_is_collecting_fuel(false);
+    satellite.set_maintenance_fuel_reserve(satellite.max_fuel() * reserve);
+    true
+}
+
+/// Fuel moved this tick from a satellite to its hub, and the links it crossed (source first, hub last)
+#[derive(Debug, Clone, PartialEq)]
+pub struct HubTransfer {
+    pub source: EntityId,
+    pub hub: EntityId,
+    pub amount: f32,
+    pub path: Vec<EntityId>,
+}
+
+/// This tick's hub transfers for `orders` (source -> hub), and the orders that are finished
+/// (source down to its reserve, hub full, or no chain of links between them)
+pub fn plan_hub_transfers(
+    nodes: &[BalanceNode],
+    bodies: &[(Vec2, f32)],
+    orders: &HashMap<EntityId, EntityId>,
+    delta_time: f32,
+) -> (Vec<HubTransfer>, Vec<EntityId>) {
+    let mut fuel: HashMap<EntityId, f32> = nodes.iter().map(|node| (node.id, node.fuel)).collect();
+    let mut transfers = Vec::new();
+    let mut finished = Vec::new();
+
+    let mut sources: Vec<(EntityId, EntityId)> = orders.iter().map(|(source, hub)| (*source, *hub)).collect();
+    sources.sort_unstable();
+    for (source, hub) in sources {
+        let (Some(from), Some(to)) = (nodes.iter().find(|n| n.id == source), nodes.iter().find(|n| n.id == hub)) else {
+            finished.push(source);
+            continue;
+        };
+        let spare = (fuel[&source] - from.reserve).max(0.0);
+        let room = (to.max_fuel - fuel[&hub]).max(0.0);
+        let Some(path) = link_path(nodes, bodies, source, hub) else {
+            finished.push(source);
+            continue;
+        };
+        let amount = spare.min(room).min(HUB_TRANSFER_RATE * delta_time);
+        if amount <= 0.0 {
+            finished.push(source);
+            continue;
+        }
+        *fuel.get_mut(&source).unwrap() -= amount;
+        *fuel.get_mut(&hub).unwrap() += amount;
+        transfers.push(HubTransfer { source, hub, amount, path });
+    }
+    (transfers, finished)
+}
+
+/// Fewest-hops chain of links from one satellite to another (same owner, in range, clear of bodies)
+fn link_path(nodes: &[BalanceNode], bodies: &[(Vec2, f32)], from: EntityId, to: EntityId) -> Option<Vec<EntityId>> {
+    let linked = |a: &BalanceNode, b: &BalanceNode| {
+        a.owner == b.owner
+            && a.position.distance(b.position) <= GameConstants::SATELLITE_TRANSFER_RANGE
+            && link_clear(a.position, b.position, bodies)
+    };
+    let mut previous: HashMap<EntityId, EntityId> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    while let Some(current) = queue.pop_front() {
+        if current == to {
+            let mut path = vec![to];
+            while let Some(step) = previous.get(path.last()?) {
+                path.push(*step);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        let node = nodes.iter().find(|n| n.id == current)?;
+        for next in nodes {
+            if next.id != from && !previous.contains_key(&next.id) && linked(node, next) {
+                previous.insert(next.id, current);
+                queue.push_back(next.id);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::Satellite;
+
+    fn node(id: EntityId, x: f32, fuel: f32) -> BalanceNode {
+        BalanceNode { id, position: Vec2::new(x, 0.0), fuel, max_fuel: 128.0, reserve: 20.0, owner: None }
+    }
+
+    #[test]
+    fn test_fuel_to_hub_follows_links() {
+        // 1 reaches 3 only through 2; 4 is out of range of everyone
+        let range = GameConstants::SATELLITE_TRANSFER_RANGE;
+        let nodes = [node(1, 0.0, 100.0), node(2, range, 50.0), node(3, 2.0 * range, 10.0), node(4, 10.0 * range, 100.0), node(5, range * 0.5, 21.0)];
+        let orders = HashMap::from([(1, 3), (4, 3), (5, 3)]);
+        let (transfers, finished) = plan_hub_transfers(&nodes, &[], &orders, 0.5);
+        assert_eq!(transfers, vec![
+            HubTransfer { source: 1, hub: 3, amount: HUB_TRANSFER_RATE * 0.5, path: vec![1, 2, 3] },
+            HubTransfer { source: 5, hub: 3, amount: 1.0, path: vec![5, 2, 3] },
+        ]);
+        assert_eq!(finished, vec![4]);
+
+        // A planet in the way breaks the chain
+        let (transfers, finished) = plan_hub_transfers(&nodes[..3], &[(Vec2::new(range * 1.5, 0.0), 100.0)], &HashMap::from([(1, 3)]), 0.5);
+        assert!(transfers.is_empty());
+        assert_eq!(finished, vec![1]);
+    }
+
+    #[test]
+    fn test_batch_commands() {
+        let mut world = World::new();
+        let ids: Vec<EntityId> = (0..4)
+            .map(|i| world.add_satellite(Satellite::from_rocket(Vec2::new(i as f32 * 100.0, 0.0), Vec2::ZERO, 128.0)))
+            .collect();
+
+        // Box round the first three, then shift-click the last one out and back in
+        let mut selection = SatelliteSelection::new();
+        selection.select_box(&world, Vec2::new(250.0, 10.0), Vec2::new(-10.0, -10.0), false);
+        assert_eq!(selection.ids().collect::<Vec<_>>(), ids[..3]);
+        selection.toggle(ids[3]);
+        selection.toggle(ids[1]);
+        assert_eq!(selection.len(), 3);
+
+        let mut marked = HashSet::new();
+        assert_eq!(selection.apply(&mut world, &mut marked, BatchCommand::Mark), 3);
+        assert!(!marked.contains(&ids[1]));
+        assert_eq!(selection.apply(&mut world, &mut marked, BatchCommand::SetRole(SatelliteRole::Relay)), 3);
+        assert_eq!(world.get_satellite(ids[3]).unwrap().maintenance_fuel_reserve(), 64.0);
+        assert_eq!(selection.apply(&mut world, &mut marked, BatchCommand::SetRole(SatelliteRole::Depleted)), 0);
+        assert_eq!(selection.apply(&mut world, &mut marked, BatchCommand::SetMaintenance(MAINTENANCE_PRESETS[2])), 3);
+        assert_eq!(world.get_satellite(ids[0]).unwrap().maintenance_interval(), 15.0);
+
+        // A satellite can't be its own hub
+        assert_eq!(selection.apply(&mut world, &mut marked, BatchCommand::FuelToHub(ids[0])), 2);
+
+        world.remove_satellite(ids[0]);
+        selection.retain_existing(&world);
+        assert_eq!(selection.len(), 2);
+    }
+}
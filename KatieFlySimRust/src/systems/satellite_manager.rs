@@ -5,8 +5,8 @@ use std::collections::HashMap;
 use macroquad::prelude::*;
 
 use crate::entities::{Satellite, Rocket, Planet, GameObject};
-use crate::systems::{EntityId, FuelTransferNetwork, OrbitMaintenance};
-use crate::systems::fuel_transfer_network::{TransferPriority, NetworkOptimizationMode};
+use crate::systems::{EntityId, FuelTransferNetwork, OrbitMaintenance, ShellDebris};
+use crate::systems::fuel_transfer_network::{InterruptCause, NetworkAnomaly, NetworkOptimizationMode, SupplyNode, TransferPriority};
 use crate::physics::GravitySimulator;
 use crate::game_constants::GameConstants;
 use crate::utils::vector_helper;
@@ -33,6 +33,136 @@ pub struct SatelliteNetworkStats {
     pub active_fuel_transfers: usize,
     pub total_delta_v_expended: f32,
     pub satellites_by_status: HashMap<String, usize>,
+    pub connected_components: usize,            // Groups of satellites linked within transfer range
+    pub satellites_below_reserve: usize,        // Fuel at or below their maintenance reserve
+    pub transfers_last_minute: usize,           // Distinct source -> target links that moved fuel
+    pub fuel_transferred_last_minute: f32,
+    pub shell_coverage: Vec<ShellCoverage>,
+    pub shell_debris: Vec<ShellDebris>,         // Wreckage in the same shells
+    pub auto_balance: bool,                     // Auto-balancing mode on
+    pub balance_throughput: f32,                // Fuel/sec auto-balancing is moving (smoothed)
+}
+
+/// How much of an orbital shell around the primary body is within reach of a satellite
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShellCoverage {
+    pub altitude: f32,
+    pub coverage_percent: f32,
+}
+
+/// Number of groups of satellites that can reach each other by hopping within `link_range`
+pub fn count_network_components(positions: &[Vec2], link_range: f32) -> usize {
+    // Union-find over satellite indices
+    let mut parent: Vec<usize> = (0..positions.len()).collect();
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    let range_squared = link_range * link_range;
+    let mut components = positions.len();
+    for i in 0..positions.len() {
+        for j in (i + 1)..positions.len() {
+            if positions[i].distance_squared(positions[j]) > range_squared {
+                continue;
+            }
+            let (root_i, root_j) = (find(&mut parent, i), find(&mut parent, j));
+            if root_i != root_j {
+                parent[root_j] = root_i;
+                components -= 1;
+            }
+        }
+    }
+    components
+}
+
+/// Percentage of a circular shell (sampled every 2 degrees) within `reach` of any satellite
+pub fn shell_coverage_percent(center: Vec2, shell_radius: f32, positions: &[Vec2], reach: f32) -> f32 {
+    const SAMPLES: usize = 180;
+    if positions.is_empty() {
+        return 0.0;
+    }
+
+    let reach_squared = reach * reach;
+    let covered = (0..SAMPLES)
+        .filter(|&i| {
+            let angle = i as f32 / SAMPLES as f32 * std::f32::consts::TAU;
+            let point = center + Vec2::from_angle(angle) * shell_radius;
+            positions.iter().any(|p| p.distance_squared(point) <= reach_squared)
+        })
+        .count();
+    covered as f32 / SAMPLES as f32 * 100.0
+}
+
+/// Fuel/sec one auto-balancing link can move
+const BALANCE_LINK_RATE: f32 = 2.0;
+/// Fuel-fraction gap below which a link is left alone (no back-and-forth trickle)
+const BALANCE_THRESHOLD: f32 = 0.05;
+/// Seconds the auto-balancing throughput readout averages over
+const THROUGHPUT_WINDOW: f32 = 5.0;
+/// Seconds between fuel network supply checks (each one routes every needy satellite)
+const SUPPLY_CHECK_INTERVAL: f32 = 1.0;
+
+/// A satellite as auto-balancing sees it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BalanceNode {
+    pub id: EntityId,
+    pub position: Vec2,
+    pub fuel: f32,
+    pub max_fuel: f32,
+    pub reserve: f32,       // Maintenance reserve it never gives away
+    pub owner: Option<u32>, // Only satellites of the same owner share fuel
+}
+
+/// Whether the straight link from `a` to `b` clears every body (center, radius)
+pub fn link_clear(a: Vec2, b: Vec2, bodies: &[(Vec2, f32)]) -> bool {
+    let ab = b - a;
+    bodies.iter().all(|&(center, radius)| {
+        let t = if ab.length_squared() > 0.0 { ((center - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0) } else { 0.0 };
+        (a + ab * t).distance(center) > radius
+    })
+}
+
+/// Transfers (source, target, amount) this tick that even out fuel fractions across linked satellites
+/// Links need `link_range` and a clear line past `bodies`; donors keep their maintenance reserve
+pub fn plan_balancing(nodes: &[BalanceNode], bodies: &[(Vec2, f32)], link_range: f32, delta_time: f32) -> Vec<(EntityId, EntityId, f32)> {
+    let mut fuel: Vec<f32> = nodes.iter().map(|node| node.fuel).collect();
+    let budget = BALANCE_LINK_RATE * delta_time;
+    let mut transfers = Vec::new();
+
+    for i in 0..nodes.len() {
+        for j in (i + 1)..nodes.len() {
+            let (a, b) = (&nodes[i], &nodes[j]);
+            if a.owner != b.owner
+                || a.max_fuel <= 0.0
+                || b.max_fuel <= 0.0
+                || a.position.distance(b.position) > link_range
+                || !link_clear(a.position, b.position, bodies)
+            {
+                continue;
+            }
+            let (fraction_a, fraction_b) = (fuel[i] / a.max_fuel, fuel[j] / b.max_fuel);
+            if (fraction_a - fraction_b).abs() < BALANCE_THRESHOLD {
+                continue;
+            }
+            let (from, to) = if fraction_a > fraction_b { (i, j) } else { (j, i) };
+            // Amount that leaves both at the same fraction
+            let even = (fraction_a - fraction_b).abs() * a.max_fuel * b.max_fuel / (a.max_fuel + b.max_fuel);
+            let spare = (fuel[from] - nodes[from].reserve).max(0.0);
+            let room = (nodes[to].max_fuel - fuel[to]).max(0.0);
+            let amount = even.min(spare).min(room).min(budget);
+            if amount <= 0.0 {
+                continue;
+            }
+            fuel[from] -= amount;
+            fuel[to] += amount;
+            transfers.push((nodes[from].id, nodes[to].id, amount));
+        }
+    }
+    transfers
 }
 
 /// Configuration for satellite system
@@ -46,6 +176,7 @@ pub struct SatelliteManagerConfig {
     pub max_transfer_range: f32,
     pub emergency_fuel_threshold: f32,
     pub critical_fuel_threshold: f32,
+    pub enable_auto_balance: bool, // Idle satellites even out fuel with linked neighbours
 }
 
 impl Default for SatelliteManagerConfig {
@@ -59,6 +190,7 @@ impl Default for SatelliteManagerConfig {
             max_transfer_range: 500.0,
             emergency_fuel_threshold: 0.30,
             critical_fuel_threshold: 0.10,
+            enable_auto_balance: false,
         }
     }
 }
@@ -87,6 +219,8 @@ pub struct SatelliteManager {
     network_stats: SatelliteNetworkStats,
     stats_update_interval: f32,
     time_since_stats_update: f32,
+    balance_throughput: f32, // Smoothed fuel/sec moved by auto-balancing
+    supply_check_timer: f32, // Seconds since the last fuel network supply check
 
     // Visualization flags
     pub show_orbit_paths: bool,
@@ -110,6 +244,8 @@ impl SatelliteManager {
             network_stats: SatelliteNetworkStats::default(),
             stats_update_interval: 1.0,
             time_since_stats_update: 0.0,
+            balance_throughput: 0.0,
+            supply_check_timer: 0.0,
             show_orbit_paths: true,
             show_target_orbit_paths: false,
             show_fuel_transfer_lines: true,
@@ -296,6 +432,61 @@ impl SatelliteManager {
         self.config.enable_automatic_collection = enabled;
     }
 
+    pub fn set_auto_balance(&mut self, enabled: bool) {
+        self.config.enable_auto_balance = enabled;
+        if !enabled {
+            self.balance_throughput = 0.0;
+        }
+    }
+
+    pub fn auto_balance_enabled(&self) -> bool {
+        self.config.enable_auto_balance
+    }
+
+    /// Feed the throughput readout with the fuel auto-balancing moved this tick
+    pub fn record_balanced(&mut self, amount: f32, delta_time: f32) {
+        if delta_time <= 0.0 {
+            return;
+        }
+        let rate = amount / delta_time;
+        self.balance_throughput += (rate - self.balance_throughput) * (delta_time / THROUGHPUT_WINDOW).min(1.0);
+    }
+
+    /// Smoothed fuel/sec auto-balancing is moving
+    pub fn balance_throughput(&self) -> f32 {
+        self.balance_throughput
+    }
+
+    /// Track this tick's auto-balancing transfers in the fuel network (see `FuelTransferNetwork::sync_transfers`)
+    pub fn sync_network_transfers(
+        &mut self,
+        live: &[(EntityId, EntityId, f32)],
+        interrupted: impl FnMut(EntityId, EntityId) -> Option<InterruptCause>,
+    ) {
+        self.fuel_transfer_network.sync_transfers(live, interrupted);
+    }
+
+    /// Whether a supply check is due this tick (once every `SUPPLY_CHECK_INTERVAL`)
+    pub fn supply_check_due(&mut self, delta_time: f32) -> bool {
+        self.supply_check_timer += delta_time;
+        if self.supply_check_timer < SUPPLY_CHECK_INTERVAL {
+            return false;
+        }
+        self.supply_check_timer = 0.0;
+        true
+    }
+
+    /// Flag satellites the network can't supply, linking satellites within `link_range`
+    pub fn check_network_supply(&mut self, nodes: &[SupplyNode], link_range: f32) {
+        self.fuel_transfer_network.set_max_transfer_range(link_range);
+        self.fuel_transfer_network.check_supply(nodes);
+    }
+
+    /// Get and clear fuel network anomalies raised since the last call
+    pub fn take_network_anomalies(&mut self) -> Vec<NetworkAnomaly> {
+        self.fuel_transfer_network.take_anomalies()
+    }
+
     pub fn set_network_optimization_mode(&mut self, mode: NetworkOptimizationMode) {
         self.fuel_transfer_network.set_optimization_mode(mode);
     }
@@ -671,6 +862,49 @@ impl Default for SatelliteManager {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_count_network_components() {
+        // Two chained satellites, one loner
+        let positions = [Vec2::new(0.0, 0.0), Vec2::new(900.0, 0.0), Vec2::new(1800.0, 0.0), Vec2::new(9000.0, 0.0)];
+        assert_eq!(count_network_components(&positions, 1000.0), 2);
+        assert_eq!(count_network_components(&positions, 100.0), 4);
+        assert_eq!(count_network_components(&[], 1000.0), 0);
+    }
+
+    #[test]
+    fn test_shell_coverage_percent() {
+        // One satellite on the shell covering a quarter of it either way
+        let radius = 1000.0;
+        let reach = 2.0 * radius * (std::f32::consts::PI / 8.0).sin();
+        let coverage = shell_coverage_percent(Vec2::ZERO, radius, &[Vec2::new(radius, 0.0)], reach);
+        assert!((coverage - 25.0).abs() < 2.0, "coverage was {}", coverage);
+
+        assert_eq!(shell_coverage_percent(Vec2::ZERO, radius, &[], reach), 0.0);
+        assert_eq!(shell_coverage_percent(Vec2::ZERO, radius, &[Vec2::ZERO], 2.0 * radius), 100.0);
+    }
+
+    #[test]
+    fn test_balancing_evens_out_linked_satellites() {
+        let node = |id, x: f32, fuel, owner| BalanceNode { id, position: Vec2::new(x, 0.0), fuel, max_fuel: 100.0, reserve: 20.0, owner };
+        let nodes = [node(1, 0.0, 90.0, None), node(2, 100.0, 10.0, None), node(3, 200.0, 10.0, Some(1))];
+
+        // A long tick moves the full even share; the other owner's satellite is left out
+        let transfers = plan_balancing(&nodes, &[], 500.0, 100.0);
+        assert_eq!(transfers.len(), 1);
+        assert_eq!((transfers[0].0, transfers[0].1), (1, 2));
+        assert!((transfers[0].2 - 40.0).abs() < 1e-3);
+
+        // Rate-limited per link
+        let transfers = plan_balancing(&nodes, &[], 500.0, 1.0);
+        assert_eq!(transfers, vec![(1, 2, BALANCE_LINK_RATE)]);
+
+        // Out of range, behind a planet, or nothing spare above the reserve: no transfer
+        assert!(plan_balancing(&nodes, &[], 50.0, 1.0).is_empty());
+        assert!(plan_balancing(&nodes, &[(Vec2::new(50.0, 5.0), 10.0)], 500.0, 1.0).is_empty());
+        let dry = [node(1, 0.0, 20.0, None), node(2, 100.0, 0.0, None)];
+        assert!(plan_balancing(&dry, &[], 500.0, 1.0).is_empty());
+    }
+
     #[test]
     fn test_satellite_manager_creation() {
         let manager = SatelliteManager::new();
@@ -0,0 +1,191 @@
+// Satellite Search - Query language for finding satellites in the network map
+// Terms are space-separated and all must match, e.g. "tanker near:moon fuel>50"
+
+use crate::entities::Satellite;
+use crate::systems::{EntityId, World};
+use crate::utils::vector_helper;
+
+/// What a satellite is currently doing for the network
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SatelliteRole {
+    Collector, // Gathering fuel from a planet
+    Tanker,    // Plenty of fuel to hand out
+    Relay,     // Some fuel, mostly passing it along (or set to keep half its tank)
+    Depleted,  // At or below its maintenance reserve
+}
+
+impl SatelliteRole {
+    pub fn of(satellite: &Satellite) -> Self {
+        if satellite.is_collecting_fuel() {
+            SatelliteRole::Collector
+        } else if satellite.current_fuel() <= satellite.maintenance_fuel_reserve() {
+            SatelliteRole::Depleted
+        } else if satellite.fuel_percentage() >= 50.0 && satellite.maintenance_fuel_reserve() < satellite.max_fuel() * 0.5 {
+            SatelliteRole::Tanker
+        } else {
+            SatelliteRole::Relay
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SatelliteRole::Collector => "collector",
+            SatelliteRole::Tanker => "tanker",
+            SatelliteRole::Relay => "relay",
+            SatelliteRole::Depleted => "depleted",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [SatelliteRole::Collector, SatelliteRole::Tanker, SatelliteRole::Relay, SatelliteRole::Depleted]
+            .into_iter()
+            .find(|role| role.name() == name)
+    }
+}
+
+/// One search term
+#[derive(Debug, Clone, PartialEq)]
+enum SearchTerm {
+    Id(EntityId),
+    Role(SatelliteRole),
+    FuelRange(f32, f32), // Percent, inclusive
+    Owner(Option<u32>),
+    Near(String),        // Nearest planet name starts with this
+}
+
+/// A parsed search query (an empty query matches everything)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SatelliteQuery {
+    terms: Vec<SearchTerm>,
+}
+
+impl SatelliteQuery {
+    /// Parse a query; unrecognised words are treated as planet names ("moon" = "near:moon")
+    ///
+    /// Supported terms: `12` / `#12` / `id:12`, `tanker` / `role:tanker`, `fuel>50`, `fuel<20`,
+    /// `fuel:20-60`, `owner:2` / `owner:none`, `near:moon`
+    pub fn parse(text: &str) -> Self {
+        let terms = text
+            .split_whitespace()
+            .map(|word| word.to_lowercase())
+            .filter_map(|word| Self::parse_term(&word))
+            .collect();
+        SatelliteQuery { terms }
+    }
+
+    fn parse_term(word: &str) -> Option<SearchTerm> {
+        if let Some(rest) = word.strip_prefix("fuel") {
+            return Self::parse_fuel(rest);
+        }
+        if let Some((key, value)) = word.split_once(':') {
+            return match key {
+                "id" => value.trim_start_matches('#').parse().ok().map(SearchTerm::Id),
+                "role" => SatelliteRole::from_name(value).map(SearchTerm::Role),
+                "owner" if value == "none" => Some(SearchTerm::Owner(None)),
+                "owner" => value.trim_start_matches('p').parse().ok().map(|id| SearchTerm::Owner(Some(id))),
+                "near" if !value.is_empty() => Some(SearchTerm::Near(value.to_string())),
+                _ => None,
+            };
+        }
+        if let Ok(id) = word.trim_start_matches('#').parse() {
+            return Some(SearchTerm::Id(id));
+        }
+        Some(match SatelliteRole::from_name(word) {
+            Some(role) => SearchTerm::Role(role),
+            None => SearchTerm::Near(word.to_string()),
+        })
+    }
+
+    /// "fuel>50", "fuel<20", "fuel:20-60" (the "fuel" prefix already stripped)
+    fn parse_fuel(rest: &str) -> Option<SearchTerm> {
+        let number = |s: &str| s.trim_end_matches('%').parse::<f32>().ok();
+        if let Some(value) = rest.strip_prefix(">=").or_else(|| rest.strip_prefix('>')) {
+            return number(value).map(|min| SearchTerm::FuelRange(min, f32::INFINITY));
+        }
+        if let Some(value) = rest.strip_prefix("<=").or_else(|| rest.strip_prefix('<')) {
+            return number(value).map(|max| SearchTerm::FuelRange(f32::NEG_INFINITY, max));
+        }
+        let (min, max) = rest.strip_prefix(':')?.split_once('-')?;
+        Some(SearchTerm::FuelRange(number(min)?, number(max)?))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn matches(&self, world: &World, id: EntityId, satellite: &Satellite) -> bool {
+        self.terms.iter().all(|term| match term {
+            SearchTerm::Id(wanted) => id == *wanted,
+            SearchTerm::Role(role) => SatelliteRole::of(satellite) == *role,
+            SearchTerm::FuelRange(min, max) => (*min..=*max).contains(&satellite.fuel_percentage()),
+            SearchTerm::Owner(owner) => satellite.owner() == *owner,
+            SearchTerm::Near(prefix) => nearest_planet_name(world, satellite)
+                .is_some_and(|name| name.to_lowercase().starts_with(prefix.as_str())),
+        })
+    }
+
+    /// Matching satellite IDs in ascending order
+    pub fn search(&self, world: &World) -> Vec<EntityId> {
+        let mut ids: Vec<EntityId> = world
+            .satellites_with_ids()
+            .filter(|(id, satellite)| self.matches(world, *id, satellite))
+            .map(|(id, _)| id)
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+}
+
+/// Name of the planet whose surface is closest to the satellite
+fn nearest_planet_name<'a>(world: &'a World, satellite: &Satellite) -> Option<&'a str> {
+    world
+        .planets()
+        .map(|planet| (vector_helper::distance(planet.position(), satellite.position()) - planet.radius(), planet))
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+        .and_then(|(_, planet)| planet.name())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::Planet;
+    use macroquad::prelude::*;
+
+    #[test]
+    fn test_parse_terms() {
+        let query = SatelliteQuery::parse("Tanker near:Moon fuel>50 #7 owner:none");
+        assert_eq!(
+            query.terms,
+            vec![
+                SearchTerm::Role(SatelliteRole::Tanker),
+                SearchTerm::Near("moon".to_string()),
+                SearchTerm::FuelRange(50.0, f32::INFINITY),
+                SearchTerm::Id(7),
+                SearchTerm::Owner(None),
+            ]
+        );
+        assert_eq!(SatelliteQuery::parse("fuel:20-60").terms, vec![SearchTerm::FuelRange(20.0, 60.0)]);
+        assert_eq!(SatelliteQuery::parse("owner:p2").terms, vec![SearchTerm::Owner(Some(2))]);
+        assert!(SatelliteQuery::parse("   fuel>abc").is_empty());
+    }
+
+    #[test]
+    fn test_search_by_role_and_nearest_planet() {
+        let mut world = World::new();
+        let mut earth = Planet::new(Vec2::ZERO, 1000.0, 10000.0, BLUE);
+        earth.set_name("Earth".to_string());
+        world.add_planet(earth);
+        let mut moon = Planet::new(Vec2::new(20000.0, 0.0), 300.0, 1000.0, GRAY);
+        moon.set_name("Moon".to_string());
+        world.add_planet(moon);
+
+        let full = world.add_satellite(Satellite::from_rocket(Vec2::new(20500.0, 0.0), Vec2::ZERO, 128.0));
+        let empty = world.add_satellite(Satellite::from_rocket(Vec2::new(19500.0, 0.0), Vec2::ZERO, 0.0));
+        let near_earth = world.add_satellite(Satellite::from_rocket(Vec2::new(1500.0, 0.0), Vec2::ZERO, 128.0));
+
+        assert_eq!(SatelliteQuery::parse("tanker moon").search(&world), vec![full]);
+        assert_eq!(SatelliteQuery::parse("depleted").search(&world), vec![empty]);
+        assert_eq!(SatelliteQuery::parse("near:ear").search(&world), vec![near_earth]);
+        assert_eq!(SatelliteQuery::parse("").search(&world).len(), 3);
+    }
+}
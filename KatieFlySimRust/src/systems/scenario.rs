@@ -0,0 +1,485 @@
+// Scenario - Community challenge files: a map, starting setup, ordered objectives, constraints and a par time
+// Loaded from scenarios/*.ron or *.json; a ScenarioRun tracks an attempt as the game plays
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::Satellite;
+use crate::game_constants::{colors, GameConstants};
+use crate::map_config::MapConfiguration;
+use crate::schemas::{self, SCHEMA_VERSION};
+use crate::systems::gravity_assist::AssistBodies;
+use crate::systems::{EntityId, FlybyTracker, LandingEvent, LandingGrade, World, ZoneLanding};
+
+const SCENARIOS_FOLDER: &str = "scenarios";
+
+fn default_hold() -> f32 {
+    10.0
+}
+
+/// One step of a scenario, completed in file order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ScenarioObjective {
+    /// Touch down on a body, optionally at least as well as `min_grade`
+    Land {
+        body: String,
+        #[serde(default)]
+        min_grade: Option<LandingGrade>,
+    },
+    /// Stay between two altitudes above a body, in flight, for `hold` seconds
+    Orbit {
+        body: String,
+        min_altitude: f32,
+        max_altitude: f32,
+        #[serde(default = "default_hold")]
+        hold: f32,
+    },
+    /// Launch this many satellites (ones the scenario starts with don't count)
+    DeploySatellites { count: usize },
+    /// Bring this many of the map's surface samples home
+    ReturnSamples { count: usize },
+    /// Fly past a body and leave its sphere of influence at least `min_gain` faster (relative to what it orbits)
+    GravityAssist { body: String, min_gain: f32 },
+    /// Touch down inside one of the map's landing zones, scoring at least `min_score` (0-100)
+    LandInZone {
+        zone: String,
+        #[serde(default)]
+        min_score: f32,
+    },
+}
+
+impl ScenarioObjective {
+    pub fn describe(&self) -> String {
+        match self {
+            ScenarioObjective::Land { body, min_grade: Some(grade) } => format!("Land on {} ({} or better)", body, grade.label()),
+            ScenarioObjective::Land { body, min_grade: None } => format!("Land on {}", body),
+            ScenarioObjective::Orbit { body, min_altitude, max_altitude, hold } => {
+                format!("Hold {:.0}-{:.0} altitude above {} for {:.0}s", min_altitude, max_altitude, body, hold)
+            }
+            ScenarioObjective::DeploySatellites { count } => format!("Deploy {} satellite(s)", count),
+            ScenarioObjective::ReturnSamples { count } => format!("Return {} sample(s) home", count),
+            ScenarioObjective::GravityAssist { body, min_gain } => format!("Slingshot past {} for +{:.0} speed", body, min_gain),
+            ScenarioObjective::LandInZone { zone, min_score } if *min_score > 0.0 => format!("Land in {} (score {:.0}+)", zone, min_score),
+            ScenarioObjective::LandInZone { zone, .. } => format!("Land in {}", zone),
+        }
+    }
+}
+
+/// A satellite the scenario starts with, in a circular orbit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioSatellite {
+    pub body: String,
+    pub altitude: f32, // Above the surface
+    #[serde(default)]
+    pub angle: f32, // Radians (same convention as map initial_angle)
+    #[serde(default)]
+    pub fuel: f32,
+}
+
+/// How the attempt starts (anything left out keeps the map's normal setup)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScenarioStart {
+    pub spawn_body: Option<String>,
+    pub fuel: Option<f32>, // Rocket fuel (full tank if unset)
+    pub satellites: Vec<ScenarioSatellite>,
+}
+
+/// Rules that fail or restrict the attempt
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScenarioConstraints {
+    pub no_refuel: bool,         // Planet refueling (R) is disabled
+    pub time_limit: Option<f32>, // Seconds before the attempt fails
+    pub allow_respawn: bool,     // Otherwise losing the rocket fails the attempt
+}
+
+/// A scenario file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Scenario {
+    #[serde(default = "schemas::schema_version")]
+    pub schema_version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub author: String,
+    pub map: String, // Built-in or custom map name
+    #[serde(default)]
+    pub start: ScenarioStart,
+    pub objectives: Vec<ScenarioObjective>,
+    #[serde(default)]
+    pub constraints: ScenarioConstraints,
+    #[serde(default)]
+    pub par_time: Option<f32>, // Seconds to beat for an under-par finish
+}
+
+impl Scenario {
+    pub fn load_from_file(path: &str) -> Result<Self, String> {
+        let scenario: Scenario = schemas::read_file(path)?;
+        if scenario.schema_version > SCHEMA_VERSION {
+            return Err(format!("Scenario '{}' uses schema version {} (newest supported is {})", scenario.name, scenario.schema_version, SCHEMA_VERSION));
+        }
+        if scenario.objectives.is_empty() {
+            return Err(format!("Scenario '{}' has no objectives", scenario.name));
+        }
+        Ok(scenario)
+    }
+
+    /// Every scenario in the scenarios/ folder with its path, sorted by name (broken files are logged and skipped)
+    pub fn load_all() -> Vec<(String, Scenario)> {
+        let _ = std::fs::create_dir_all(SCENARIOS_FOLDER);
+        let mut scenarios = Vec::new();
+        if let Ok(entries) = std::fs::read_dir(SCENARIOS_FOLDER) {
+            for entry in entries.flatten() {
+                let path = entry.path().to_string_lossy().to_string();
+                if schemas::SchemaFormat::from_path(&path).is_none() {
+                    continue;
+                }
+                match Self::load_from_file(&path) {
+                    Ok(scenario) => scenarios.push((path, scenario)),
+                    Err(e) => log::warn!("Failed to load scenario {}: {}", path, e),
+                }
+            }
+        }
+        scenarios.sort_by(|a, b| a.1.name.cmp(&b.1.name));
+        scenarios
+    }
+
+    /// The scenario's map from `maps`, with the spawn body swapped if the scenario asks; errors name the missing map or body
+    pub fn resolve_map(&self, maps: Vec<MapConfiguration>) -> Result<MapConfiguration, String> {
+        let mut map = maps
+            .into_iter()
+            .find(|m| m.name == self.map)
+            .ok_or_else(|| format!("Scenario '{}' needs map '{}', which isn't installed", self.name, self.map))?;
+
+        let body_index = |name: &str| {
+            map.celestial_bodies
+                .iter()
+                .position(|body| body.name == name)
+                .ok_or_else(|| format!("Scenario '{}' refers to '{}', which isn't on map '{}'", self.name, name, map.name))
+        };
+        let mut bodies: Vec<&str> = self.start.satellites.iter().map(|s| s.body.as_str()).collect();
+        for objective in &self.objectives {
+            if let ScenarioObjective::Land { body, .. } | ScenarioObjective::Orbit { body, .. } | ScenarioObjective::GravityAssist { body, .. } = objective {
+                bodies.push(body);
+            }
+        }
+        for body in bodies {
+            body_index(body)?;
+        }
+        for objective in &self.objectives {
+            if let ScenarioObjective::LandInZone { zone, .. } = objective {
+                if !map.landing_zones.iter().any(|z| &z.name == zone) {
+                    return Err(format!("Scenario '{}' refers to landing zone '{}', which isn't on map '{}'", self.name, zone, map.name));
+                }
+            }
+        }
+        if let Some(spawn) = &self.start.spawn_body {
+            map.player_spawn_body_index = body_index(spawn)?;
+        }
+        Ok(map)
+    }
+
+    /// Put the starting satellites into a freshly built world
+    pub fn place_satellites(&self, world: &mut World) {
+        for placed in &self.start.satellites {
+            let Some(planet) = world.planets().find(|p| p.name() == Some(placed.body.as_str())) else {
+                continue;
+            };
+            let radius = planet.radius() + placed.altitude;
+            let offset = Vec2::new(placed.angle.cos(), placed.angle.sin()) * radius;
+            let speed = (GameConstants::G * planet.mass() / radius).sqrt();
+            let position = planet.position() + offset;
+            let velocity = planet.velocity() + offset.perp().normalize() * speed;
+
+            let mut satellite = Satellite::new(position, velocity, colors::SATELLITE_BODY_COLOR);
+            satellite.add_fuel(placed.fuel - satellite.current_fuel());
+            satellite.set_target_orbit_radius(radius);
+            world.add_satellite(satellite);
+        }
+    }
+}
+
+/// How an attempt ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScenarioOutcome {
+    Complete { time: f32, under_par: Option<bool> },
+    Failed(String),
+}
+
+/// Something worth telling the player about
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScenarioProgress {
+    ObjectiveDone(usize),
+    Complete,
+    Failed(String),
+}
+
+/// One attempt at a scenario
+#[derive(Debug, Clone)]
+pub struct ScenarioRun {
+    scenario: Scenario,
+    current: usize,
+    elapsed: f32,
+    hold_time: f32,
+    starting_satellites: usize,
+    flyby: FlybyTracker, // Passes scored for gravity assist objectives
+    outcome: Option<ScenarioOutcome>,
+}
+
+impl ScenarioRun {
+    /// Start an attempt in a world already set up for it
+    pub fn new(scenario: Scenario, world: &World) -> Self {
+        ScenarioRun {
+            scenario,
+            current: 0,
+            elapsed: 0.0,
+            hold_time: 0.0,
+            starting_satellites: world.satellite_count(),
+            flyby: FlybyTracker::new(),
+            outcome: None,
+        }
+    }
+
+    pub fn scenario(&self) -> &Scenario {
+        &self.scenario
+    }
+
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Objectives completed so far
+    pub fn completed(&self) -> usize {
+        self.current
+    }
+
+    pub fn current_objective(&self) -> Option<&ScenarioObjective> {
+        self.scenario.objectives.get(self.current).filter(|_| self.outcome.is_none())
+    }
+
+    /// Seconds held so far on an orbit objective
+    pub fn hold_time(&self) -> f32 {
+        self.hold_time
+    }
+
+    /// The body the current objective wants a gravity assist from
+    pub fn assist_target(&self) -> Option<&str> {
+        match self.current_objective() {
+            Some(ScenarioObjective::GravityAssist { body, .. }) => Some(body),
+            _ => None,
+        }
+    }
+
+    /// Flybys so far (the last one stays scored after the objective is done)
+    pub fn flyby(&self) -> &FlybyTracker {
+        &self.flyby
+    }
+
+    pub fn outcome(&self) -> Option<&ScenarioOutcome> {
+        self.outcome.as_ref()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.outcome.is_none()
+    }
+
+    pub fn allows_refuel(&self) -> bool {
+        !(self.is_running() && self.scenario.constraints.no_refuel)
+    }
+
+    /// A landing by the player's rocket
+    pub fn on_landing(&mut self, event: &LandingEvent) -> Vec<ScenarioProgress> {
+        if let Some(ScenarioObjective::Land { body, min_grade }) = self.current_objective() {
+            let on_target = event.planet_name.as_deref() == Some(body.as_str());
+            if on_target && min_grade.is_none_or(|grade| event.result.grade <= grade) {
+                return self.advance();
+            }
+        }
+        Vec::new()
+    }
+
+    /// A touchdown by the player's rocket inside a landing zone
+    pub fn on_zone_landing(&mut self, landing: &ZoneLanding) -> Vec<ScenarioProgress> {
+        if let Some(ScenarioObjective::LandInZone { zone, min_score }) = self.current_objective() {
+            if landing.zone == *zone && !landing.player_marked && landing.score >= *min_score {
+                return self.advance();
+            }
+        }
+        Vec::new()
+    }
+
+    /// The player's rocket was destroyed
+    pub fn on_rocket_destroyed(&mut self, cause: &str) -> Vec<ScenarioProgress> {
+        if !self.is_running() || self.scenario.constraints.allow_respawn {
+            return Vec::new();
+        }
+        self.fail(format!("rocket destroyed by {}", cause))
+    }
+
+    /// Advance the clock and check objectives that depend on the ongoing state
+    pub fn update(&mut self, delta_time: f32, world: &World, rocket_id: Option<EntityId>, samples_returned: usize) -> Vec<ScenarioProgress> {
+        if !self.is_running() {
+            return Vec::new();
+        }
+        self.elapsed += delta_time;
+        if let Some(limit) = self.scenario.constraints.time_limit {
+            if self.elapsed > limit {
+                return self.fail(format!("over the {:.0}s time limit", limit));
+            }
+        }
+
+        let mut progress = Vec::new();
+        while let Some(objective) = self.current_objective().cloned() {
+            let done = match objective {
+                ScenarioObjective::Land { .. } | ScenarioObjective::LandInZone { .. } => false,
+                ScenarioObjective::Orbit { body, min_altitude, max_altitude, hold } => {
+                    let in_band = rocket_id.and_then(|id| world.get_rocket(id)).zip(world.planets().find(|p| p.name() == Some(body.as_str()))).is_some_and(
+                        |(rocket, planet)| {
+                            let altitude = rocket.position().distance(planet.position()) - planet.radius();
+                            !rocket.is_landed() && altitude >= min_altitude && altitude <= max_altitude
+                        },
+                    );
+                    self.hold_time = if in_band { self.hold_time + delta_time } else { 0.0 };
+                    self.hold_time >= hold
+                }
+                ScenarioObjective::DeploySatellites { count } => world.satellite_count().saturating_sub(self.starting_satellites) >= count,
+                ScenarioObjective::ReturnSamples { count } => samples_returned >= count,
+                ScenarioObjective::GravityAssist { body, min_gain } => {
+                    let rocket = rocket_id.and_then(|id| world.get_rocket(id));
+                    let result = rocket.zip(AssistBodies::find(world, &body)).and_then(|(rocket, bodies)| self.flyby.update(&bodies, rocket));
+                    result.is_some_and(|result| result.speed_gain >= min_gain)
+                }
+            };
+            if !done {
+                break;
+            }
+            progress.extend(self.advance());
+        }
+        progress
+    }
+
+    fn advance(&mut self) -> Vec<ScenarioProgress> {
+        let mut progress = vec![ScenarioProgress::ObjectiveDone(self.current)];
+        self.current += 1;
+        self.hold_time = 0.0;
+        if self.current >= self.scenario.objectives.len() {
+            self.outcome = Some(ScenarioOutcome::Complete {
+                time: self.elapsed,
+                under_par: self.scenario.par_time.map(|par| self.elapsed <= par),
+            });
+            progress.push(ScenarioProgress::Complete);
+        }
+        progress
+    }
+
+    fn fail(&mut self, reason: String) -> Vec<ScenarioProgress> {
+        self.outcome = Some(ScenarioOutcome::Failed(reason.clone()));
+        vec![ScenarioProgress::Failed(reason)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Planet, Rocket};
+    use crate::schemas::SchemaFormat;
+    use crate::systems::LandingResult;
+
+    fn landing_on(name: &str, vertical_speed: f32) -> LandingEvent {
+        LandingEvent {
+            rocket_id: 0,
+            planet_id: 0,
+            player_id: None,
+            planet_name: Some(name.to_string()),
+            result: LandingResult::evaluate(Vec2::new(0.0, -100.0), Vec2::new(0.0, vertical_speed), 0.0, Vec2::ZERO, Vec2::ZERO),
+        }
+    }
+
+    #[test]
+    fn test_example_scenario_loads_and_resolves() {
+        let scenario: Scenario = SchemaFormat::Ron.decode(include_str!("../../scenarios/moon_hop.ron")).unwrap();
+        let map = scenario.resolve_map(MapConfiguration::all_maps()).unwrap();
+        assert_eq!(map.name, scenario.map);
+
+        let mut broken = scenario.clone();
+        broken.objectives.push(ScenarioObjective::Land { body: "Pluto".to_string(), min_grade: None });
+        assert!(broken.resolve_map(MapConfiguration::all_maps()).unwrap_err().contains("Pluto"));
+        let mut off_map = scenario.clone();
+        off_map.objectives.push(ScenarioObjective::LandInZone { zone: "Nowhere".to_string(), min_score: 0.0 });
+        assert!(off_map.resolve_map(MapConfiguration::all_maps()).unwrap_err().contains("Nowhere"));
+
+        let tutorial: Scenario = SchemaFormat::Ron.decode(include_str!("../../scenarios/gravity_assist.ron")).unwrap();
+        assert!(tutorial.resolve_map(MapConfiguration::all_maps()).is_ok());
+        let pinpoint: Scenario = SchemaFormat::Ron.decode(include_str!("../../scenarios/pinpoint_landing.ron")).unwrap();
+        assert!(pinpoint.resolve_map(MapConfiguration::all_maps()).is_ok());
+    }
+
+    #[test]
+    fn test_objectives_complete_in_order() {
+        let json = r#"{
+            "name": "Hop",
+            "map": "earth moon",
+            "objectives": [
+                { "Land": { "body": "Moon", "min_grade": "Good" } },
+                { "LandInZone": { "zone": "Apollo Pad", "min_score": 50.0 } },
+                { "DeploySatellites": { "count": 1 } }
+            ],
+            "par_time": 100.0
+        }"#;
+        let scenario: Scenario = SchemaFormat::Json.decode(json).unwrap();
+        let mut world = World::new();
+        let mut run = ScenarioRun::new(scenario, &world);
+
+        // Wrong body, then too hard a landing, don't count
+        assert!(run.on_landing(&landing_on("Earth", 5.0)).is_empty());
+        assert!(run.on_landing(&landing_on("Moon", 100.0)).is_empty());
+        assert_eq!(run.on_landing(&landing_on("Moon", 5.0)), vec![ScenarioProgress::ObjectiveDone(0)]);
+
+        // Too far from the pad's center, or in the player's own mark, doesn't count either
+        let mut in_zone = ZoneLanding { zone: "Apollo Pad".to_string(), body_name: "Moon".to_string(), distance: 200.0, score: 20.0, player_marked: false };
+        assert!(run.on_zone_landing(&in_zone).is_empty());
+        in_zone.score = 80.0;
+        assert!(run.on_zone_landing(&ZoneLanding { player_marked: true, ..in_zone.clone() }).is_empty());
+        assert_eq!(run.on_zone_landing(&in_zone), vec![ScenarioProgress::ObjectiveDone(1)]);
+
+        assert!(run.update(1.0, &world, None, 0).is_empty());
+        world.add_satellite(Satellite::new(Vec2::new(5000.0, 0.0), Vec2::ZERO, WHITE));
+        let progress = run.update(1.0, &world, None, 0);
+        assert_eq!(progress, vec![ScenarioProgress::ObjectiveDone(2), ScenarioProgress::Complete]);
+        assert_eq!(run.outcome(), Some(&ScenarioOutcome::Complete { time: 2.0, under_par: Some(true) }));
+    }
+
+    #[test]
+    fn test_orbit_hold_and_constraints() {
+        let mut world = World::new();
+        let mut planet = Planet::new(Vec2::ZERO, 1000.0, 1.0e6, BLUE);
+        planet.set_name("Rock".to_string());
+        world.add_planet(planet);
+        let rocket_id = world.add_rocket(Rocket::new(Vec2::new(1500.0, 0.0), Vec2::ZERO, WHITE, 1.0));
+
+        let scenario = Scenario {
+            schema_version: SCHEMA_VERSION,
+            name: "Loiter".to_string(),
+            description: String::new(),
+            author: String::new(),
+            map: "earth moon".to_string(),
+            start: ScenarioStart::default(),
+            objectives: vec![ScenarioObjective::Orbit { body: "Rock".to_string(), min_altitude: 400.0, max_altitude: 600.0, hold: 3.0 }],
+            constraints: ScenarioConstraints { no_refuel: true, time_limit: Some(10.0), allow_respawn: false },
+            par_time: None,
+        };
+        let mut run = ScenarioRun::new(scenario.clone(), &world);
+        assert!(!run.allows_refuel());
+        assert!(run.update(2.0, &world, Some(rocket_id), 0).is_empty());
+        assert_eq!(run.update(1.5, &world, Some(rocket_id), 0), vec![ScenarioProgress::ObjectiveDone(0), ScenarioProgress::Complete]);
+        assert!(run.allows_refuel());
+
+        // Losing the rocket or running out of time fails the attempt
+        let mut run = ScenarioRun::new(scenario.clone(), &world);
+        assert!(matches!(run.on_rocket_destroyed("bullet")[..], [ScenarioProgress::Failed(_)]));
+        let mut run = ScenarioRun::new(scenario, &world);
+        assert!(matches!(run.update(11.0, &world, None, 0)[..], [ScenarioProgress::Failed(_)]));
+    }
+}
@@ -0,0 +1,97 @@
+// Server Notice - Host-to-client announcements from the host or its remote console
+// Broadcast messages show as toasts; a kick notice tells the client why it was removed, a
+// shot-down notice lets it replay the bullet that destroyed its rocket, a map definition
+// gives it the names, terrain and physics of a custom map that snapshots don't carry, and a
+// save refusal says why a hardcore game wouldn't save, a station notice reports a landed rocket's refuel hold,
+// and a shutdown notice tells every client the host has closed the game (clients send LEAVE when they quit)
+
+use serde::{Deserialize, Serialize};
+
+use crate::map_config::MapConfiguration;
+use crate::schemas::{MapSchema, SchemaFormat};
+use crate::systems::EntityId;
+
+/// Prefix marking a notice packet (snapshots never start with these bytes)
+const NOTICE_PACKET_PREFIX: &[u8] = b"NOTICE";
+/// Client -> host request for the map definition (a snapshot named a map the client wasn't sent)
+pub const MAP_REQUEST: &[u8] = b"MAPREQ";
+/// Client -> host goodbye: drop me and my rocket now rather than at a timeout
+pub const LEAVE: &[u8] = b"LEAVE";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ServerNotice {
+    /// Message for every player
+    Broadcast(String),
+    /// You were removed from the game or turned away at the door (reason, e.g. kicked, banned or server full)
+    Kicked(String),
+    /// Your rocket was shot down (ids as in the snapshots; `impact` in world coordinates)
+    ShotDown {
+        rocket_id: EntityId,
+        bullet_id: EntityId,
+        shooter_id: Option<EntityId>,
+        attacker: String,
+        impact: (f32, f32),
+    },
+    /// The map the world was built from, as schema JSON (see `schemas::MapSchema`)
+    MapDefinition(String),
+    /// Your save request was turned down by the hardcore rule (reason)
+    SaveRefused(String),
+    /// News about your rocket's station ops hold (level changed, ready, planet tapped out)
+    Station(String),
+    /// The host closed the game; stop waiting for snapshots
+    HostShutdown,
+}
+
+impl ServerNotice {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = NOTICE_PACKET_PREFIX.to_vec();
+        bytes.extend(bincode::serialize(self).unwrap_or_default());
+        bytes
+    }
+
+    /// Map definition notice for `map`
+    pub fn map_definition(map: &MapConfiguration) -> Result<Self, String> {
+        let json = serde_json::to_string(&MapSchema::from(map)).map_err(|e| e.to_string())?;
+        Ok(ServerNotice::MapDefinition(json))
+    }
+
+    /// The map in a map definition notice, checked like a map file (a host's map is untrusted input)
+    pub fn decode_map(json: &str) -> Result<MapConfiguration, String> {
+        SchemaFormat::Json.decode::<MapSchema>(json)?.into_map()
+    }
+
+    /// None if the bytes aren't a notice packet
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let payload = bytes.strip_prefix(NOTICE_PACKET_PREFIX)?;
+        bincode::deserialize(payload).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notice_round_trip() {
+        let notice = ServerNotice::Kicked("spamming".to_string());
+        assert_eq!(ServerNotice::from_bytes(&notice.to_bytes()), Some(notice));
+        assert_eq!(ServerNotice::from_bytes(b"KEEPALIVE"), None);
+        assert_eq!(ServerNotice::from_bytes(&ServerNotice::HostShutdown.to_bytes()), Some(ServerNotice::HostShutdown));
+        assert_eq!(ServerNotice::from_bytes(LEAVE), None);
+
+        let notice = ServerNotice::ShotDown { rocket_id: 4, bullet_id: 17, shooter_id: Some(2), attacker: "Katie".to_string(), impact: (1.5, -3.0) };
+        assert_eq!(ServerNotice::from_bytes(&notice.to_bytes()), Some(notice));
+
+        // The biggest built-in map still fits one client receive buffer
+        let map = MapConfiguration::solar_1();
+        let notice = ServerNotice::map_definition(&map).unwrap();
+        let bytes = notice.to_bytes();
+        assert!(bytes.len() < 4096, "{} bytes", bytes.len());
+        let Some(ServerNotice::MapDefinition(json)) = ServerNotice::from_bytes(&bytes) else {
+            panic!("not a map definition");
+        };
+        let decoded = ServerNotice::decode_map(&json).unwrap();
+        assert_eq!(decoded.name, map.name);
+        assert_eq!(decoded.celestial_bodies.len(), map.celestial_bodies.len());
+    }
+}
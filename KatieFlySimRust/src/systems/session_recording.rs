@@ -0,0 +1,642 @@
+// Session Recording - Host-side log of a multiplayer match for post-match replay
+// Periodic world keyframes plus every player input in between, replayed through the normal physics
+
+use macroquad::prelude::{DVec2, Vec2};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::physics::CollisionRules;
+use crate::save_system::GameSaveData;
+use crate::systems::daily_challenge::ChallengeDate;
+use crate::systems::floating_origin::FloatingOrigin;
+use crate::systems::{EntityId, SessionEventKind, TransferDebounce, World};
+
+/// Folder the host writes recordings into
+pub const REPLAYS_FOLDER: &str = "saves/replays";
+/// File extension of a recording
+const REPLAY_EXTENSION: &str = "replay";
+/// Seconds between world keyframes (seeking lands on one and simulates forward from there)
+pub const KEYFRAME_INTERVAL: f32 = 5.0;
+/// Bumped whenever the recording layout changes; older files are refused
+const RECORDING_VERSION: u32 = 3;
+
+/// What a player did, as the host applied it
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RecordedAction {
+    Steer { rotation_delta: f32, thrust_level: f32 }, // Degrees this frame, throttle 0-1
+    Shoot,
+    Convert,
+    RefuelOnce,      // Host's single-press planet refuel
+    Refueling(bool), // Client's held planet refuel starting or stopping
+    TransferringFuel(bool), // Held rocket-to-rocket transfer starting or stopping
+}
+
+/// One input, stamped with session time and the player whose rocket it acted on
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RecordedInput {
+    pub time: f32,
+    pub player_id: u32, // Rocket owner (an engineer's input is filed under their pilot)
+    pub action: RecordedAction,
+}
+
+/// A notable moment of the match (launch, landing, kill...), listed in the replay's event timeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    pub time: f32,
+    pub kind: SessionEventKind,
+    pub description: String,
+    pub world_position: (f64, f64), // Where it happened, independent of the floating origin
+}
+
+impl RecordedEvent {
+    pub fn world_position(&self) -> DVec2 {
+        DVec2::new(self.world_position.0, self.world_position.1)
+    }
+}
+
+/// A whole recorded match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRecording {
+    pub version: u32,
+    pub physics_hz: u32,
+    pub collision_rules: CollisionRules,
+    pub keyframes: Vec<(f32, GameSaveData)>, // Oldest first
+    pub inputs: Vec<RecordedInput>,          // Oldest first
+    pub events: Vec<RecordedEvent>,          // Oldest first
+}
+
+impl SessionRecording {
+    pub fn new(physics_hz: u32) -> Self {
+        SessionRecording {
+            version: RECORDING_VERSION,
+            physics_hz,
+            collision_rules: CollisionRules::default(),
+            keyframes: Vec::new(),
+            inputs: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// First recorded moment
+    pub fn start_time(&self) -> f32 {
+        self.keyframes.first().map(|(time, _)| *time).unwrap_or(0.0)
+    }
+
+    /// Last recorded moment (the last keyframe or input, whichever is later)
+    pub fn end_time(&self) -> f32 {
+        let last_keyframe = self.keyframes.last().map(|(time, _)| *time).unwrap_or(0.0);
+        let last_input = self.inputs.last().map(|input| input.time).unwrap_or(0.0);
+        last_keyframe.max(last_input)
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.end_time() - self.start_time()
+    }
+
+    /// Write to saves/replays/<name>.replay and return the path
+    pub fn save_to_file(&self, name: &str) -> Result<String, Box<dyn std::error::Error>> {
+        fs::create_dir_all(REPLAYS_FOLDER)?;
+
+        let file_path = format!("{}/{}.{}", REPLAYS_FOLDER, name, REPLAY_EXTENSION);
+        let bytes = self.to_bytes()?;
+        let byte_count = bytes.len();
+        fs::write(&file_path, bytes)?;
+
+        log::info!("Match recording saved to: {} ({} bytes)", file_path, byte_count);
+        Ok(file_path)
+    }
+
+    pub fn load_from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        if !Path::new(path).exists() {
+            return Err(format!("Recording not found: {}", path).into());
+        }
+        let recording = Self::from_bytes(&fs::read(path)?)?;
+        log::info!("Match recording loaded from: {}", path);
+        Ok(recording)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        Ok(bincode::serialize(self)?)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        let version: u32 = bincode::deserialize(bytes)?;
+        if version != RECORDING_VERSION {
+            return Err(format!("Recording version {} is not supported (expected {})", version, RECORDING_VERSION).into());
+        }
+        Ok(bincode::deserialize(bytes)?)
+    }
+
+    /// Paths of every recording in the replays folder, newest first
+    pub fn list() -> Vec<String> {
+        let mut paths: Vec<String> = fs::read_dir(REPLAYS_FOLDER)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|ext| ext == REPLAY_EXTENSION))
+                    .map(|path| path.to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        paths.sort();
+        paths.reverse();
+        paths
+    }
+
+    /// Menu label for a recording path ("Match of 2026-10-17 14:05 UTC" for files the host named)
+    pub fn label_for(path: &str) -> String {
+        let stem = Path::new(path).file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+        match stem.strip_prefix("match_").and_then(|secs| secs.parse::<u64>().ok()) {
+            Some(secs) => {
+                let date = ChallengeDate::from_days_since_epoch((secs / 86_400) as i64);
+                let minutes = secs % 86_400 / 60;
+                format!("Match of {} {:02}:{:02} UTC", date.label(), minutes / 60, minutes % 60)
+            }
+            None => stem,
+        }
+    }
+}
+
+/// Builds a recording while the host runs the match
+#[derive(Debug, Clone)]
+pub struct SessionRecorder {
+    recording: SessionRecording,
+    keyframe_requested: bool,
+    last_thrust: HashMap<u32, f32>,     // Last recorded throttle per player
+    last_refueling: HashMap<u32, bool>, // Last recorded held-refuel state per player
+    last_transferring: HashMap<u32, bool>, // Last recorded held-transfer state per player
+    transfers: TransferDebounce,
+}
+
+impl SessionRecorder {
+    pub fn new(physics_hz: u32) -> Self {
+        SessionRecorder {
+            recording: SessionRecording::new(physics_hz),
+            keyframe_requested: false,
+            last_thrust: HashMap::new(),
+            last_refueling: HashMap::new(),
+            last_transferring: HashMap::new(),
+            transfers: TransferDebounce::default(),
+        }
+    }
+
+    pub fn set_physics_hz(&mut self, physics_hz: u32) {
+        self.recording.physics_hz = physics_hz;
+    }
+
+    pub fn set_collision_rules(&mut self, rules: CollisionRules) {
+        self.recording.collision_rules = rules;
+    }
+
+    pub fn recording(&self) -> &SessionRecording {
+        &self.recording
+    }
+
+    /// Log an input, skipping steering and refuel states that change nothing
+    pub fn record(&mut self, time: f32, player_id: u32, action: RecordedAction) {
+        match action {
+            RecordedAction::Steer { rotation_delta, thrust_level } => {
+                let thrust_changed = self.last_thrust.get(&player_id) != Some(&thrust_level);
+                if rotation_delta == 0.0 && !thrust_changed {
+                    return;
+                }
+                self.last_thrust.insert(player_id, thrust_level);
+            }
+            RecordedAction::Refueling(active) => {
+                if self.last_refueling.get(&player_id).copied().unwrap_or(false) == active {
+                    return;
+                }
+                self.last_refueling.insert(player_id, active);
+            }
+            RecordedAction::TransferringFuel(active) => {
+                if self.last_transferring.get(&player_id).copied().unwrap_or(false) == active {
+                    return;
+                }
+                self.last_transferring.insert(player_id, active);
+            }
+            _ => {}
+        }
+        self.recording.inputs.push(RecordedInput { time, player_id, action });
+    }
+
+    /// Log a notable moment for the replay's event timeline
+    pub fn record_event(&mut self, time: f32, kind: SessionEventKind, description: impl Into<String>, world_position: DVec2) {
+        self.recording.events.push(RecordedEvent {
+            time,
+            kind,
+            description: description.into(),
+            world_position: (world_position.x, world_position.y),
+        });
+    }
+
+    /// Log a fuel transfer only when its link starts flowing (transfers tick every frame)
+    pub fn record_transfer(&mut self, time: f32, link: (EntityId, EntityId), description: impl Into<String>, world_position: DVec2) {
+        if self.transfers.starts(time, link) {
+            self.record_event(time, SessionEventKind::Transfer, description, world_position);
+        }
+    }
+
+    /// Take a keyframe at the next chance (after a respawn the inputs alone can't rebuild the world)
+    pub fn request_keyframe(&mut self) {
+        self.keyframe_requested = true;
+    }
+
+    pub fn keyframe_due(&self, time: f32) -> bool {
+        self.keyframe_requested
+            || self.recording.keyframes.last().is_none_or(|(last, _)| time - last >= KEYFRAME_INTERVAL)
+    }
+
+    pub fn add_keyframe(&mut self, time: f32, snapshot: GameSaveData) {
+        self.recording.keyframes.push((time, snapshot));
+        self.keyframe_requested = false;
+    }
+
+    /// Write the recording to the replays folder (nothing is written for an empty match); the recorder starts over
+    pub fn finish(&mut self) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        let physics_hz = self.recording.physics_hz;
+        let collision_rules = self.recording.collision_rules;
+        let recording = std::mem::replace(&mut self.recording, SessionRecording::new(physics_hz));
+        self.recording.collision_rules = collision_rules;
+        self.keyframe_requested = false;
+        self.last_thrust.clear();
+        self.last_refueling.clear();
+        self.last_transferring.clear();
+        self.transfers.clear();
+
+        if recording.keyframes.len() < 2 {
+            return Ok(None);
+        }
+        let name = format!("match_{}", recording.keyframes[0].1.timestamp_secs);
+        recording.save_to_file(&name).map(Some)
+    }
+}
+
+/// Re-simulates a recording: jumps to keyframes and replays the inputs between them
+pub struct ReplayPlayback {
+    recording: SessionRecording,
+    world: World,
+    time: f32,
+    accumulator: f32,
+    next_keyframe: usize,
+    next_input: usize,
+    thrust: HashMap<u32, f32>,  // Throttle each player currently holds
+    refueling: HashSet<u32>,    // Players holding planet refuel
+    transferring: HashSet<u32>, // Players holding the rocket-to-rocket transfer
+}
+
+impl ReplayPlayback {
+    pub fn new(recording: SessionRecording) -> Result<Self, String> {
+        if recording.keyframes.is_empty() {
+            return Err("Recording has no keyframes".to_string());
+        }
+        let mut world = World::new();
+        world.set_collision_rules(recording.collision_rules);
+        let mut playback = ReplayPlayback {
+            time: recording.start_time(),
+            recording,
+            world,
+            accumulator: 0.0,
+            next_keyframe: 0,
+            next_input: 0,
+            thrust: HashMap::new(),
+            refueling: HashSet::new(),
+            transferring: HashSet::new(),
+        };
+        playback.seek(playback.time);
+        Ok(playback)
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    pub fn start_time(&self) -> f32 {
+        self.recording.start_time()
+    }
+
+    pub fn end_time(&self) -> f32 {
+        self.recording.end_time()
+    }
+
+    /// The match's notable moments, oldest first
+    pub fn events(&self) -> &[RecordedEvent] {
+        &self.recording.events
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.time >= self.end_time()
+    }
+
+    /// Player names as of the last keyframe played
+    pub fn player_names(&self) -> &HashMap<u32, String> {
+        let index = self.next_keyframe.saturating_sub(1);
+        &self.recording.keyframes[index].1.player_names
+    }
+
+    fn timestep(&self) -> f32 {
+        1.0 / self.recording.physics_hz.max(1) as f32
+    }
+
+    fn rocket_of(&self, player_id: u32) -> Option<EntityId> {
+        self.world
+            .rockets_with_ids()
+            .find(|(_, rocket)| rocket.player_id() == Some(player_id))
+            .map(|(id, _)| id)
+    }
+
+    /// Replace the world with keyframe `index`; returns how far the floating origin moved
+    fn load_keyframe(&mut self, index: usize) -> Vec2 {
+        let (time, snapshot) = self.recording.keyframes[index].clone();
+
+        let previous_origin = self.world.origin();
+        let origin: FloatingOrigin = snapshot.origin.into();
+        self.world.clear_all_entities();
+        self.world.set_origin(origin);
+        self.world.set_cosmetic_seed(snapshot.cosmetic_seed);
+
+        for saved_planet in snapshot.planets {
+            let (id, planet) = saved_planet.to_planet();
+            self.world.add_planet_with_id(id, planet);
+        }
+        for saved_rocket in snapshot.rockets {
+            let (id, rocket) = saved_rocket.to_rocket();
+            self.world.add_rocket_with_id(id, rocket);
+        }
+        for saved_satellite in snapshot.satellites {
+            let (id, satellite) = saved_satellite.to_satellite();
+            self.world.add_satellite_with_id(id, satellite);
+        }
+        for saved_bullet in snapshot.bullets {
+            let (id, bullet) = saved_bullet.to_bullet();
+            self.world.add_bullet_with_id(id, bullet);
+        }
+
+        // Keyframes don't carry throttle, so re-apply what each player was holding
+        for (player_id, thrust) in self.thrust.clone() {
+            if let Some(rocket_id) = self.rocket_of(player_id) {
+                if let Some(rocket) = self.world.get_rocket_mut(rocket_id) {
+                    rocket.set_thrust_level(thrust);
+                }
+            }
+        }
+
+        self.time = time;
+        self.next_keyframe = index + 1;
+        origin.shift_from(&previous_origin)
+    }
+
+    /// Jump to `time` (clamped to the recording); returns how far the floating origin moved
+    pub fn seek(&mut self, time: f32) -> Vec2 {
+        let time = time.clamp(self.start_time(), self.end_time());
+        let index = self
+            .recording
+            .keyframes
+            .partition_point(|(keyframe_time, _)| *keyframe_time <= time)
+            .saturating_sub(1);
+        let keyframe_time = self.recording.keyframes[index].0;
+
+        // Rebuild held throttle/refuel/transfer from every input before the keyframe
+        self.next_input = self.recording.inputs.partition_point(|input| input.time < keyframe_time);
+        self.thrust.clear();
+        self.refueling.clear();
+        self.transferring.clear();
+        for input in &self.recording.inputs[..self.next_input] {
+            match input.action {
+                RecordedAction::Steer { thrust_level, .. } => {
+                    self.thrust.insert(input.player_id, thrust_level);
+                }
+                RecordedAction::Refueling(true) => {
+                    self.refueling.insert(input.player_id);
+                }
+                RecordedAction::Refueling(false) => {
+                    self.refueling.remove(&input.player_id);
+                }
+                RecordedAction::TransferringFuel(true) => {
+                    self.transferring.insert(input.player_id);
+                }
+                RecordedAction::TransferringFuel(false) => {
+                    self.transferring.remove(&input.player_id);
+                }
+                _ => {}
+            }
+        }
+
+        let mut shift = self.load_keyframe(index);
+        self.accumulator = 0.0;
+        shift += self.advance(time - keyframe_time);
+        shift
+    }
+
+    /// Play forward by `delta_time`; returns how far the floating origin moved
+    pub fn advance(&mut self, delta_time: f32) -> Vec2 {
+        let step = self.timestep();
+        let mut shift = Vec2::ZERO;
+        self.accumulator += delta_time.max(0.0);
+
+        while self.accumulator >= step {
+            // Snap to the host's world whenever we reach one of its keyframes
+            if self
+                .recording
+                .keyframes
+                .get(self.next_keyframe)
+                .is_some_and(|(keyframe_time, _)| *keyframe_time <= self.time + step * 0.5)
+            {
+                shift += self.load_keyframe(self.next_keyframe);
+            }
+            if self.is_finished() {
+                break;
+            }
+            self.accumulator -= step;
+
+            while let Some(input) = self.recording.inputs.get(self.next_input).copied() {
+                if input.time >= self.time + step * 0.5 {
+                    break;
+                }
+                self.apply_input(input, step);
+                self.next_input += 1;
+            }
+
+            for player_id in self.refueling.clone() {
+                if let Some(rocket_id) = self.rocket_of(player_id) {
+                    self.world.handle_manual_planet_refuel(rocket_id, step);
+                }
+            }
+            for player_id in self.transferring.clone() {
+                if let Some(donor_id) = self.rocket_of(player_id) {
+                    if let Some(recipient_id) = self.world.nearest_rocket_in_transfer_range(donor_id) {
+                        let _ = self.world.transfer_fuel_rocket_to_rocket(donor_id, recipient_id, step);
+                    }
+                }
+            }
+
+            self.world.update(step, false);
+            self.world.take_destroyed_rockets();
+            self.world.take_fuel_transfer_events();
+            self.world.take_landing_events();
+            self.world.take_hit_events();
+            self.time += step;
+        }
+        if self.is_finished() {
+            self.accumulator = 0.0;
+        }
+        shift
+    }
+
+    fn apply_input(&mut self, input: RecordedInput, step: f32) {
+        if let RecordedAction::Refueling(active) = input.action {
+            if active {
+                self.refueling.insert(input.player_id);
+            } else {
+                self.refueling.remove(&input.player_id);
+            }
+            return;
+        }
+        if let RecordedAction::TransferringFuel(active) = input.action {
+            if active {
+                self.transferring.insert(input.player_id);
+            } else {
+                self.transferring.remove(&input.player_id);
+            }
+            return;
+        }
+        if let RecordedAction::Steer { thrust_level, .. } = input.action {
+            self.thrust.insert(input.player_id, thrust_level);
+        }
+
+        let Some(rocket_id) = self.rocket_of(input.player_id) else {
+            return;
+        };
+        match input.action {
+            RecordedAction::Steer { rotation_delta, thrust_level } => {
+                if let Some(rocket) = self.world.get_rocket_mut(rocket_id) {
+                    rocket.rotate(rotation_delta.to_radians());
+                    rocket.set_thrust_level(thrust_level);
+                }
+            }
+            RecordedAction::Shoot => {
+                self.world.shoot_bullet_from_rocket(rocket_id);
+            }
+            RecordedAction::Convert => {
+                self.world.convert_rocket_to_satellite(rocket_id);
+            }
+            RecordedAction::RefuelOnce => {
+                self.world.handle_manual_planet_refuel(rocket_id, step);
+            }
+            RecordedAction::Refueling(_) | RecordedAction::TransferringFuel(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::Rocket;
+    use crate::save_system::SavedRocket;
+    use macroquad::prelude::WHITE;
+
+    fn keyframe(time: f32, rotation: f32) -> (f32, GameSaveData) {
+        let mut rocket = Rocket::new(Vec2::new(0.0, 0.0), Vec2::new(0.0, 0.0), WHITE, 1.0);
+        rocket.set_player_id(Some(1));
+        rocket.rotate(rotation);
+        let mut snapshot = GameSaveData::new();
+        snapshot.game_time = time;
+        snapshot.rockets.push(SavedRocket::from_rocket(7, &rocket));
+        (time, snapshot)
+    }
+
+    fn rotation_of(playback: &ReplayPlayback) -> f32 {
+        playback.world().rockets().next().unwrap().rotation()
+    }
+
+    #[test]
+    fn test_recorder_skips_idle_inputs() {
+        let mut recorder = SessionRecorder::new(60);
+        let idle = RecordedAction::Steer { rotation_delta: 0.0, thrust_level: 0.5 };
+        recorder.record(0.0, 1, idle);
+        recorder.record(0.1, 1, idle);
+        recorder.record(0.2, 1, RecordedAction::Refueling(false));
+        recorder.record(0.3, 1, RecordedAction::Refueling(true));
+        recorder.record(0.4, 1, RecordedAction::Refueling(true));
+        recorder.record(0.5, 2, idle);
+        recorder.record(0.6, 1, RecordedAction::Shoot);
+
+        let times: Vec<f32> = recorder.recording().inputs.iter().map(|input| input.time).collect();
+        assert_eq!(times, vec![0.0, 0.3, 0.5, 0.6]);
+    }
+
+    #[test]
+    fn test_events_are_saved_with_the_recording() {
+        let mut recorder = SessionRecorder::new(60);
+        let far_away = DVec2::new(1.0e9, -2.5);
+        recorder.record_event(1.0, SessionEventKind::Launch, "Katie launched", far_away);
+        recorder.record_transfer(2.0, (1, 2), "Rocket 1 passing fuel to rocket 2", DVec2::ZERO);
+        recorder.record_transfer(2.5, (1, 2), "Rocket 1 passing fuel to rocket 2", DVec2::ZERO);
+
+        let bytes = recorder.recording().to_bytes().unwrap();
+        let loaded = SessionRecording::from_bytes(&bytes).unwrap();
+        let kinds: Vec<SessionEventKind> = loaded.events.iter().map(|event| event.kind).collect();
+        assert_eq!(kinds, vec![SessionEventKind::Launch, SessionEventKind::Transfer]);
+        assert_eq!(loaded.events[0].world_position(), far_away);
+    }
+
+    #[test]
+    fn test_keyframes_fall_due_on_interval_or_request() {
+        let mut recorder = SessionRecorder::new(60);
+        assert!(recorder.keyframe_due(0.0));
+        recorder.add_keyframe(0.0, GameSaveData::new());
+        assert!(!recorder.keyframe_due(1.0));
+        assert!(recorder.keyframe_due(KEYFRAME_INTERVAL));
+        recorder.request_keyframe();
+        assert!(recorder.keyframe_due(1.0));
+    }
+
+    #[test]
+    fn test_recording_round_trips_through_bytes() {
+        let mut recording = SessionRecording::new(30);
+        recording.keyframes.push(keyframe(0.0, 0.0));
+        recording.inputs.push(RecordedInput { time: 0.5, player_id: 1, action: RecordedAction::Shoot });
+
+        let restored = SessionRecording::from_bytes(&recording.to_bytes().unwrap()).unwrap();
+        assert_eq!(restored.physics_hz, 30);
+        assert_eq!(restored.keyframes.len(), 1);
+        assert_eq!(restored.inputs, recording.inputs);
+    }
+
+    #[test]
+    fn test_label_for_named_and_renamed_files() {
+        assert_eq!(SessionRecording::label_for("saves/replays/match_86460.replay"), "Match of 1970-01-02 00:01 UTC");
+        assert_eq!(SessionRecording::label_for("saves/replays/final.replay"), "final");
+    }
+
+    #[test]
+    fn test_playback_replays_inputs_and_snaps_to_keyframes() {
+        let mut recording = SessionRecording::new(10);
+        recording.keyframes.push(keyframe(0.0, 0.0));
+        recording.keyframes.push(keyframe(2.0, 1.0));
+        recording.inputs.push(RecordedInput {
+            time: 0.5,
+            player_id: 1,
+            action: RecordedAction::Steer { rotation_delta: 90.0, thrust_level: 0.0 },
+        });
+        let mut playback = ReplayPlayback::new(recording).unwrap();
+
+        playback.advance(1.0);
+        assert!((rotation_of(&playback) - 90f32.to_radians()).abs() < 1e-4);
+
+        // Reaching the second keyframe takes its rocket rotation over
+        playback.advance(1.5);
+        assert!((rotation_of(&playback) - 1.0).abs() < 1e-4);
+
+        // Seeking back lands before the input again
+        playback.seek(0.2);
+        assert!(rotation_of(&playback).abs() < 1e-4);
+        assert!((playback.time() - 0.2).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,167 @@
+// Session Timeline - Chronological log of major events in the current session
+// Each event keeps where it happened so the timeline can point the view at it
+
+use macroquad::prelude::{Color, Vec2};
+use serde::{Deserialize, Serialize};
+
+use crate::systems::EntityId;
+
+/// Oldest events are dropped past this many
+const MAX_EVENTS: usize = 500;
+/// A fuel link that goes quiet for this long is logged again when it restarts (seconds)
+const TRANSFER_EVENT_GAP: f32 = 5.0;
+
+/// Category of a timeline event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionEventKind {
+    Launch,
+    Landing,
+    Conversion,
+    Destroyed,
+    Transfer,
+}
+
+impl SessionEventKind {
+    pub fn color(&self) -> Color {
+        match self {
+            SessionEventKind::Launch => Color::new(1.0, 0.6, 0.2, 1.0),
+            SessionEventKind::Landing => Color::new(0.3, 1.0, 0.4, 1.0),
+            SessionEventKind::Conversion => Color::new(0.4, 0.8, 1.0, 1.0),
+            SessionEventKind::Destroyed => Color::new(1.0, 0.25, 0.25, 1.0),
+            SessionEventKind::Transfer => Color::new(1.0, 1.0, 0.4, 1.0),
+        }
+    }
+}
+
+/// One logged event
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionEvent {
+    pub time: f32, // Game time in seconds
+    pub kind: SessionEventKind,
+    pub description: String,
+    pub position: Vec2,
+}
+
+/// Remembers when each fuel link last moved fuel, so a flow is logged once rather than every tick
+#[derive(Debug, Clone, Default)]
+pub struct TransferDebounce {
+    last_transfer: Vec<((EntityId, EntityId), f32)>, // Last time each fuel link moved fuel
+}
+
+impl TransferDebounce {
+    pub fn clear(&mut self) {
+        self.last_transfer.clear();
+    }
+
+    /// True if `link` is starting to flow at `time` (it was idle, or quiet for a while)
+    pub fn starts(&mut self, time: f32, link: (EntityId, EntityId)) -> bool {
+        self.last_transfer.retain(|(_, last)| time - *last <= TRANSFER_EVENT_GAP);
+        if let Some(entry) = self.last_transfer.iter_mut().find(|(l, _)| *l == link) {
+            entry.1 = time;
+            return false;
+        }
+        self.last_transfer.push((link, time));
+        true
+    }
+}
+
+/// Events for the current session, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct SessionTimeline {
+    events: Vec<SessionEvent>,
+    transfers: TransferDebounce,
+}
+
+impl SessionTimeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn events(&self) -> &[SessionEvent] {
+        &self.events
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+        self.transfers.clear();
+    }
+
+    pub fn record(&mut self, time: f32, kind: SessionEventKind, description: impl Into<String>, position: Vec2) {
+        if self.events.len() >= MAX_EVENTS {
+            self.events.remove(0);
+        }
+        self.events.push(SessionEvent {
+            time,
+            kind,
+            description: description.into(),
+            position,
+        });
+    }
+
+    /// Follow a floating origin rebase (positions moved by `-shift`)
+    pub fn shift_positions(&mut self, shift: Vec2) {
+        for event in &mut self.events {
+            event.position -= shift;
+        }
+    }
+
+    /// Log a fuel transfer only when its link starts flowing (transfers tick every frame)
+    /// Returns true if an event was recorded
+    pub fn record_transfer(
+        &mut self,
+        time: f32,
+        link: (EntityId, EntityId),
+        description: impl Into<String>,
+        position: Vec2,
+    ) -> bool {
+        if !self.transfers.starts(time, link) {
+            return false;
+        }
+        self.record(time, SessionEventKind::Transfer, description, position);
+        true
+    }
+
+    /// Index of the event closest in time (None if there are no events)
+    pub fn nearest_index(&self, time: f32) -> Option<usize> {
+        self.events
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                (a.time - time).abs().partial_cmp(&(b.time - time).abs()).unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|(index, _)| index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_logged_once_per_flow() {
+        let mut timeline = SessionTimeline::new();
+
+        assert!(timeline.record_transfer(0.0, (1, 2), "Satellite 1 refueled rocket 2", Vec2::ZERO));
+        assert!(!timeline.record_transfer(0.5, (1, 2), "Satellite 1 refueled rocket 2", Vec2::ZERO));
+        assert!(timeline.record_transfer(0.5, (3, 2), "Satellite 3 refueled rocket 2", Vec2::ZERO));
+
+        // The same link restarting after a pause is a new event
+        assert!(timeline.record_transfer(10.0, (1, 2), "Satellite 1 refueled rocket 2", Vec2::ZERO));
+        assert_eq!(timeline.events().len(), 3);
+    }
+
+    #[test]
+    fn test_nearest_index() {
+        let mut timeline = SessionTimeline::new();
+        assert_eq!(timeline.nearest_index(5.0), None);
+
+        timeline.record(1.0, SessionEventKind::Launch, "Launched", Vec2::ZERO);
+        timeline.record(20.0, SessionEventKind::Landing, "Landed", Vec2::ZERO);
+        assert_eq!(timeline.nearest_index(8.0), Some(0));
+        assert_eq!(timeline.nearest_index(15.0), Some(1));
+    }
+}
@@ -0,0 +1,607 @@
+// Snapshot Delta - Sends each client only the entity fields that changed since a snapshot it acked
+// Every snapshot gets a sequence number; deltas name the acked baseline they patch, and periodic keyframes resend everything
+// Packets too big for one datagram (keyframes of a busy world) go out in numbered pieces the client puts back together
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::save_system::{
+    GameSaveData, SavedBullet, SavedCamera, SavedOrigin, SavedPlanet, SavedRocket, SavedSatellite, SavedVector2,
+    SavedWaypoint,
+};
+use crate::systems::{ArenaBounds, EntityId};
+
+/// Prefix marking a snapshot packet (keyframe or delta)
+const SNAPSHOT_PACKET_PREFIX: &[u8] = b"SNAP";
+/// Prefix marking one piece of a snapshot packet too big for a single datagram
+const FRAGMENT_PACKET_PREFIX: &[u8] = b"KEYPART";
+/// Largest datagram a snapshot is sent as (bytes), so each piece fits one Ethernet frame and a lost one costs little
+const MAX_DATAGRAM: usize = 1200;
+/// Most pieces one snapshot may be split into (about 1.2 MB); anything bigger isn't sent
+const MAX_FRAGMENTS: usize = 1024;
+/// A client is sent a full keyframe at least this often (host session seconds), so a lost baseline can't stall it for long
+pub const KEYFRAME_INTERVAL: f32 = 2.0;
+/// Snapshots the client remembers, to find the baseline a delta names
+const HISTORY: usize = 128;
+/// Unacked snapshots the host remembers per client (a quarter second at 60 Hz); acks for older ones are ignored
+const SENT_WINDOW: usize = 16;
+
+/// Entity fields that change nearly every tick, sent one by one
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FieldPatch {
+    pub id: EntityId,
+    pub position: Option<SavedVector2>,
+    pub velocity: Option<SavedVector2>,
+    pub rotation: Option<f32>,
+    pub fuel: Option<f32>,
+    pub wear: Option<f32>,     // Satellites
+    pub lifetime: Option<f32>, // Bullets
+}
+
+impl FieldPatch {
+    fn is_empty(&self) -> bool {
+        self.position.is_none()
+            && self.velocity.is_none()
+            && self.rotation.is_none()
+            && self.fuel.is_none()
+            && self.wear.is_none()
+            && self.lifetime.is_none()
+    }
+}
+
+/// The value if it differs from the baseline's
+fn changed<T: PartialEq + Clone>(current: &T, baseline: &T) -> Option<T> {
+    (current != baseline).then(|| current.clone())
+}
+
+/// A saved entity that can be patched field by field
+pub trait DeltaEntity: Clone + PartialEq {
+    fn id(&self) -> EntityId;
+    /// The frequently changing fields that differ from `baseline`
+    fn patch_against(&self, baseline: &Self) -> FieldPatch;
+    fn apply(&mut self, patch: &FieldPatch);
+}
+
+impl DeltaEntity for SavedPlanet {
+    fn id(&self) -> EntityId {
+        self.id
+    }
+
+    fn patch_against(&self, baseline: &Self) -> FieldPatch {
+        FieldPatch {
+            id: self.id,
+            position: changed(&self.position, &baseline.position),
+            velocity: changed(&self.velocity, &baseline.velocity),
+            ..FieldPatch::default()
+        }
+    }
+
+    fn apply(&mut self, patch: &FieldPatch) {
+        if let Some(position) = &patch.position {
+            self.position = position.clone();
+        }
+        if let Some(velocity) = &patch.velocity {
+            self.velocity = velocity.clone();
+        }
+    }
+}
+
+impl DeltaEntity for SavedRocket {
+    fn id(&self) -> EntityId {
+        self.id
+    }
+
+    fn patch_against(&self, baseline: &Self) -> FieldPatch {
+        FieldPatch {
+            id: self.id,
+            position: changed(&self.position, &baseline.position),
+            velocity: changed(&self.velocity, &baseline.velocity),
+            rotation: changed(&self.rotation, &baseline.rotation),
+            fuel: changed(&self.fuel, &baseline.fuel),
+            ..FieldPatch::default()
+        }
+    }
+
+    fn apply(&mut self, patch: &FieldPatch) {
+        if let Some(position) = &patch.position {
+            self.position = position.clone();
+        }
+        if let Some(velocity) = &patch.velocity {
+            self.velocity = velocity.clone();
+        }
+        self.rotation = patch.rotation.unwrap_or(self.rotation);
+        self.fuel = patch.fuel.unwrap_or(self.fuel);
+    }
+}
+
+impl DeltaEntity for SavedSatellite {
+    fn id(&self) -> EntityId {
+        self.id
+    }
+
+    fn patch_against(&self, baseline: &Self) -> FieldPatch {
+        FieldPatch {
+            id: self.id,
+            position: changed(&self.position, &baseline.position),
+            velocity: changed(&self.velocity, &baseline.velocity),
+            rotation: changed(&self.rotation, &baseline.rotation),
+            fuel: changed(&self.fuel, &baseline.fuel),
+            wear: changed(&self.wear, &baseline.wear),
+            ..FieldPatch::default()
+        }
+    }
+
+    fn apply(&mut self, patch: &FieldPatch) {
+        if let Some(position) = &patch.position {
+            self.position = position.clone();
+        }
+        if let Some(velocity) = &patch.velocity {
+            self.velocity = velocity.clone();
+        }
+        self.rotation = patch.rotation.unwrap_or(self.rotation);
+        self.fuel = patch.fuel.unwrap_or(self.fuel);
+        self.wear = patch.wear.unwrap_or(self.wear);
+    }
+}
+
+impl DeltaEntity for SavedBullet {
+    fn id(&self) -> EntityId {
+        self.id
+    }
+
+    fn patch_against(&self, baseline: &Self) -> FieldPatch {
+        FieldPatch {
+            id: self.id,
+            position: changed(&self.position, &baseline.position),
+            velocity: changed(&self.velocity, &baseline.velocity),
+            lifetime: changed(&self.lifetime, &baseline.lifetime),
+            ..FieldPatch::default()
+        }
+    }
+
+    fn apply(&mut self, patch: &FieldPatch) {
+        if let Some(position) = &patch.position {
+            self.position = position.clone();
+        }
+        if let Some(velocity) = &patch.velocity {
+            self.velocity = velocity.clone();
+        }
+        self.lifetime = patch.lifetime.unwrap_or(self.lifetime);
+    }
+}
+
+/// One entity list's changes against the baseline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityChanges<T> {
+    pub records: Vec<T>,          // Whole entities: new ones, or ones whose other fields changed
+    pub patches: Vec<FieldPatch>, // Only the changed fast-moving fields
+    pub removed: Vec<EntityId>,
+}
+
+impl<T: DeltaEntity> EntityChanges<T> {
+    fn diff(baseline: &[T], current: &[T]) -> Self {
+        let base: BTreeMap<EntityId, &T> = baseline.iter().map(|entity| (entity.id(), entity)).collect();
+        let mut changes = EntityChanges { records: Vec::new(), patches: Vec::new(), removed: Vec::new() };
+        for entity in current {
+            let Some(old) = base.get(&entity.id()) else {
+                changes.records.push(entity.clone());
+                continue;
+            };
+            let patch = entity.patch_against(old);
+            let mut patched = (*old).clone();
+            patched.apply(&patch);
+            if patched != *entity {
+                changes.records.push(entity.clone());
+            } else if !patch.is_empty() {
+                changes.patches.push(patch);
+            }
+        }
+        let current_ids: BTreeSet<EntityId> = current.iter().map(|entity| entity.id()).collect();
+        changes.removed = base.keys().copied().filter(|id| !current_ids.contains(id)).collect();
+        changes
+    }
+
+    /// The baseline's list with these changes applied (in ID order, as the host's world stores them)
+    fn apply(&self, baseline: &[T]) -> Vec<T> {
+        let mut entities: BTreeMap<EntityId, T> = baseline.iter().map(|entity| (entity.id(), entity.clone())).collect();
+        for id in &self.removed {
+            entities.remove(id);
+        }
+        for patch in &self.patches {
+            if let Some(entity) = entities.get_mut(&patch.id) {
+                entity.apply(patch);
+            }
+        }
+        for record in &self.records {
+            entities.insert(record.id(), record.clone());
+        }
+        entities.into_values().collect()
+    }
+}
+
+/// Snapshot fields that rarely change, sent only when they do
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotHeader {
+    pub player_id: Option<u32>,
+    pub active_rocket_id: Option<EntityId>,
+    pub player_names: Vec<(u32, String)>,
+    pub map_name: Option<String>,
+    pub waypoints: Vec<SavedWaypoint>,
+    pub arena: Option<ArenaBounds>,
+    pub cosmetic_seed: u64,
+}
+
+impl SnapshotHeader {
+    fn of(snapshot: &GameSaveData) -> Self {
+        let mut player_names: Vec<(u32, String)> = snapshot.player_names.iter().map(|(id, name)| (*id, name.clone())).collect();
+        player_names.sort();
+        SnapshotHeader {
+            player_id: snapshot.player_id,
+            active_rocket_id: snapshot.active_rocket_id,
+            player_names,
+            map_name: snapshot.map_name.clone(),
+            waypoints: snapshot.waypoints.clone(),
+            arena: snapshot.arena,
+            cosmetic_seed: snapshot.cosmetic_seed,
+        }
+    }
+
+    fn apply(&self, snapshot: &mut GameSaveData) {
+        snapshot.player_id = self.player_id;
+        snapshot.active_rocket_id = self.active_rocket_id;
+        snapshot.player_names = self.player_names.iter().cloned().collect();
+        snapshot.map_name = self.map_name.clone();
+        snapshot.waypoints = self.waypoints.clone();
+        snapshot.arena = self.arena;
+        snapshot.cosmetic_seed = self.cosmetic_seed;
+    }
+}
+
+/// A snapshot as the changes from an earlier one the client acked
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotDelta {
+    pub baseline: u32, // Sequence of the snapshot this patches
+    pub game_time: f32,
+    pub timestamp_secs: u64,
+    pub origin: SavedOrigin,
+    pub camera: SavedCamera,
+    pub header: Option<SnapshotHeader>, // Players, map, waypoints and arena, when they changed
+    pub planets: EntityChanges<SavedPlanet>,
+    pub rockets: EntityChanges<SavedRocket>,
+    pub satellites: EntityChanges<SavedSatellite>,
+    pub bullets: EntityChanges<SavedBullet>,
+}
+
+impl SnapshotDelta {
+    pub fn diff(baseline_sequence: u32, baseline: &GameSaveData, current: &GameSaveData) -> Self {
+        let header = SnapshotHeader::of(current);
+        SnapshotDelta {
+            baseline: baseline_sequence,
+            game_time: current.game_time,
+            timestamp_secs: current.timestamp_secs,
+            origin: current.origin,
+            camera: current.camera.clone(),
+            header: (header != SnapshotHeader::of(baseline)).then_some(header),
+            planets: EntityChanges::diff(&baseline.planets, &current.planets),
+            rockets: EntityChanges::diff(&baseline.rockets, &current.rockets),
+            satellites: EntityChanges::diff(&baseline.satellites, &current.satellites),
+            bullets: EntityChanges::diff(&baseline.bullets, &current.bullets),
+        }
+    }
+
+    /// The full snapshot this delta describes
+    pub fn apply(&self, baseline: &GameSaveData) -> GameSaveData {
+        let mut snapshot = baseline.clone();
+        snapshot.game_time = self.game_time;
+        snapshot.timestamp_secs = self.timestamp_secs;
+        snapshot.origin = self.origin;
+        snapshot.camera = self.camera.clone();
+        if let Some(header) = &self.header {
+            header.apply(&mut snapshot);
+        }
+        snapshot.planets = self.planets.apply(&baseline.planets);
+        snapshot.rockets = self.rockets.apply(&baseline.rockets);
+        snapshot.satellites = self.satellites.apply(&baseline.satellites);
+        snapshot.bullets = self.bullets.apply(&baseline.bullets);
+        snapshot
+    }
+}
+
+/// What the host sends a client each snapshot tick
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SnapshotPacket {
+    Keyframe { sequence: u32, snapshot: GameSaveData },
+    Delta { sequence: u32, delta: SnapshotDelta },
+}
+
+impl SnapshotPacket {
+    pub fn sequence(&self) -> u32 {
+        match self {
+            SnapshotPacket::Keyframe { sequence, .. } | SnapshotPacket::Delta { sequence, .. } => *sequence,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let mut bytes = SNAPSHOT_PACKET_PREFIX.to_vec();
+        bytes.extend(bincode::serialize(self)?);
+        Ok(bytes)
+    }
+
+    /// None if the bytes aren't a snapshot packet
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let payload = bytes.strip_prefix(SNAPSHOT_PACKET_PREFIX)?;
+        bincode::deserialize(payload).ok()
+    }
+
+    /// The datagrams to send: the packet itself, or its pieces when it's bigger than `MAX_DATAGRAM`
+    pub fn to_datagrams(&self) -> Result<Vec<Vec<u8>>, Box<dyn std::error::Error>> {
+        let bytes = self.to_bytes()?;
+        if bytes.len() <= MAX_DATAGRAM {
+            return Ok(vec![bytes]);
+        }
+        // Room for the piece's prefix, then its sequence, index, count and length as bincode writes them
+        let chunks: Vec<&[u8]> = bytes.chunks(MAX_DATAGRAM - FRAGMENT_PACKET_PREFIX.len() - 16).collect();
+        if chunks.len() > MAX_FRAGMENTS {
+            return Err(format!("Snapshot of {} bytes needs more than {} pieces", bytes.len(), MAX_FRAGMENTS).into());
+        }
+        let count = chunks.len() as u16;
+        chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, chunk)| {
+                let fragment = SnapshotFragment { sequence: self.sequence(), index: index as u16, count, bytes: chunk.to_vec() };
+                fragment.to_bytes()
+            })
+            .collect()
+    }
+}
+
+/// One piece of a snapshot packet that didn't fit in a datagram
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotFragment {
+    pub sequence: u32,
+    pub index: u16,
+    pub count: u16,
+    pub bytes: Vec<u8>,
+}
+
+impl SnapshotFragment {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let mut bytes = FRAGMENT_PACKET_PREFIX.to_vec();
+        bytes.extend(bincode::serialize(self)?);
+        Ok(bytes)
+    }
+
+    /// None if the bytes aren't a snapshot piece
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let payload = bytes.strip_prefix(FRAGMENT_PACKET_PREFIX)?;
+        bincode::deserialize(payload).ok()
+    }
+}
+
+/// Host side, one per client: numbers snapshots and diffs them against the newest one the client acked
+#[derive(Debug, Clone, Default)]
+pub struct DeltaEncoder {
+    next_sequence: u32,
+    sent: VecDeque<(u32, GameSaveData)>, // Latest `SENT_WINDOW` sent but not yet acked, oldest first
+    baseline: Option<(u32, GameSaveData)>, // Newest acked
+    last_keyframe_time: Option<f32>,
+}
+
+impl DeltaEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Packet for `snapshot`: a delta against the acked baseline, or a keyframe when none is usable or one is due
+    pub fn encode(&mut self, snapshot: &GameSaveData) -> SnapshotPacket {
+        let sequence = self.next_sequence;
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+        if self.sent.len() >= SENT_WINDOW {
+            self.sent.pop_front();
+        }
+        self.sent.push_back((sequence, snapshot.clone()));
+
+        let keyframe_due = self.last_keyframe_time.is_none_or(|time| snapshot.game_time - time >= KEYFRAME_INTERVAL);
+        match &self.baseline {
+            Some((baseline_sequence, baseline)) if !keyframe_due => SnapshotPacket::Delta {
+                sequence,
+                delta: SnapshotDelta::diff(*baseline_sequence, baseline, snapshot),
+            },
+            _ => {
+                self.last_keyframe_time = Some(snapshot.game_time);
+                SnapshotPacket::Keyframe { sequence, snapshot: snapshot.clone() }
+            }
+        }
+    }
+
+    /// The client acked the snapshot stamped `snapshot_time`; it becomes the baseline if it's newer
+    pub fn on_ack(&mut self, snapshot_time: f32) {
+        let Some(index) = self.sent.iter().position(|(_, snapshot)| snapshot.game_time == snapshot_time) else {
+            return;
+        };
+        self.baseline = self.sent.remove(index);
+        self.sent.drain(..index); // Anything older will never beat this baseline
+    }
+
+    /// Forget the baseline so the next packet is a keyframe (after a desync, or a world change)
+    pub fn reset(&mut self) {
+        self.baseline = None;
+        self.last_keyframe_time = None;
+        self.sent.clear();
+    }
+}
+
+/// Client side: rebuilds full snapshots from keyframes and deltas
+#[derive(Debug, Clone, Default)]
+pub struct DeltaDecoder {
+    received: VecDeque<(u32, GameSaveData)>, // Rebuilt snapshots, oldest first
+    latest: Option<u32>,
+    pieces: Option<(u32, Vec<Option<Vec<u8>>>)>, // The newest split packet still being put together
+}
+
+impl DeltaDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The full snapshot a packet carries; None for packets older than one already applied,
+    /// Err when a delta's baseline is no longer remembered (the next keyframe recovers)
+    pub fn decode(&mut self, packet: SnapshotPacket) -> Result<Option<GameSaveData>, String> {
+        let sequence = packet.sequence();
+        // Far older than anything recent means the host started numbering over (we rejoined), not a late packet
+        if self.latest.is_some_and(|latest| (latest.wrapping_sub(sequence) as usize) < HISTORY) {
+            return Ok(None);
+        }
+        let snapshot = match packet {
+            SnapshotPacket::Keyframe { snapshot, .. } => snapshot,
+            SnapshotPacket::Delta { delta, .. } => {
+                let baseline = self
+                    .received
+                    .iter()
+                    .find(|(received, _)| *received == delta.baseline)
+                    .map(|(_, snapshot)| snapshot)
+                    .ok_or_else(|| format!("Snapshot {} patches {}, which we no longer have", sequence, delta.baseline))?;
+                delta.apply(baseline)
+            }
+        };
+        if self.received.len() >= HISTORY {
+            self.received.pop_front();
+        }
+        self.received.push_back((sequence, snapshot.clone()));
+        self.latest = Some(sequence);
+        Ok(Some(snapshot))
+    }
+
+    /// Hold a piece of a split packet; the whole packet once its last missing piece arrives.
+    /// Pieces of an older packet than the one being gathered are dropped, and a newer one starts over
+    pub fn add_fragment(&mut self, fragment: SnapshotFragment) -> Option<SnapshotPacket> {
+        let count = fragment.count as usize;
+        let index = fragment.index as usize;
+        if count == 0 || count > MAX_FRAGMENTS || index >= count {
+            return None;
+        }
+        let gathering = self.pieces.as_ref().map(|(sequence, pieces)| (*sequence, pieces.len()));
+        match gathering {
+            Some((sequence, len)) if sequence == fragment.sequence && len == count => {}
+            Some((sequence, _)) if (fragment.sequence.wrapping_sub(sequence) as i32) < 0 => return None,
+            _ => self.pieces = Some((fragment.sequence, vec![None; count])),
+        }
+
+        let (_, pieces) = self.pieces.as_mut()?;
+        pieces[index] = Some(fragment.bytes);
+        if pieces.iter().any(Option::is_none) {
+            return None;
+        }
+        let (_, pieces) = self.pieces.take()?;
+        SnapshotPacket::from_bytes(&pieces.into_iter().flatten().flatten().collect::<Vec<u8>>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::Rocket;
+    use macroquad::prelude::{Vec2, WHITE};
+
+    fn snapshot(time: f32, rockets: &[(EntityId, f32)]) -> GameSaveData {
+        let mut snapshot = GameSaveData::new();
+        snapshot.game_time = time;
+        for (id, x) in rockets {
+            snapshot.rockets.push(SavedRocket::from_rocket(*id, &Rocket::new(Vec2::new(*x, 0.0), Vec2::ZERO, WHITE, 1.0)));
+        }
+        snapshot
+    }
+
+    fn send(encoder: &mut DeltaEncoder, decoder: &mut DeltaDecoder, snapshot: &GameSaveData) -> SnapshotPacket {
+        let packet = encoder.encode(snapshot);
+        let bytes = packet.to_bytes().unwrap();
+        let rebuilt = decoder.decode(SnapshotPacket::from_bytes(&bytes).unwrap()).unwrap().unwrap();
+        assert_eq!(rebuilt.rockets, snapshot.rockets);
+        assert_eq!(rebuilt.game_time, snapshot.game_time);
+        encoder.on_ack(rebuilt.game_time);
+        packet
+    }
+
+    #[test]
+    fn test_deltas_rebuild_snapshots_and_keyframes_recur() {
+        let mut encoder = DeltaEncoder::new();
+        let mut decoder = DeltaDecoder::new();
+
+        // Nothing acked yet: a keyframe
+        let first = snapshot(0.0, &[(1, 100.0), (2, 200.0)]);
+        assert!(matches!(send(&mut encoder, &mut decoder, &first), SnapshotPacket::Keyframe { .. }));
+
+        // Rocket 1 moves, rocket 2 is gone, rocket 3 appears, rocket 1 changes color (a whole record)
+        let mut second = snapshot(0.1, &[(1, 110.0), (3, 300.0)]);
+        let packet = send(&mut encoder, &mut decoder, &second);
+        let SnapshotPacket::Delta { delta, .. } = &packet else {
+            panic!("expected a delta");
+        };
+        assert_eq!(delta.rockets.patches.len(), 1);
+        assert_eq!(delta.rockets.removed, vec![2]);
+        assert_eq!(delta.rockets.records.len(), 1);
+        assert!(delta.header.is_none());
+        second.game_time = 0.2;
+        second.rockets[0].color = (1, 2, 3);
+        send(&mut encoder, &mut decoder, &second);
+
+        // An unchanged world costs far less than a full snapshot
+        second.game_time = 0.3;
+        let unchanged = encoder.encode(&second);
+        assert!(unchanged.to_bytes().unwrap().len() < second.to_bytes().unwrap().len());
+
+        // Replayed or late packets are ignored; a keyframe is forced once the interval passes
+        assert!(decoder.decode(packet).unwrap().is_none());
+        let late = snapshot(KEYFRAME_INTERVAL + 0.5, &[(1, 120.0)]);
+        assert!(matches!(encoder.encode(&late), SnapshotPacket::Keyframe { .. }));
+    }
+
+    #[test]
+    fn test_host_remembers_only_a_small_window() {
+        let mut encoder = DeltaEncoder::new();
+        for step in 0..100 {
+            encoder.encode(&snapshot(step as f32 * 0.01, &[(1, step as f32)]));
+        }
+        assert_eq!(encoder.sent.len(), SENT_WINDOW);
+
+        // An ack for a snapshot already forgotten changes nothing; a recent one becomes the baseline
+        encoder.on_ack(0.0);
+        assert!(encoder.baseline.is_none());
+        encoder.on_ack(0.95);
+        assert_eq!(encoder.baseline.as_ref().map(|(sequence, _)| *sequence), Some(95));
+        assert_eq!(encoder.sent.len(), 4);
+    }
+
+    #[test]
+    fn test_big_keyframes_go_out_in_pieces() {
+        let rockets: Vec<(EntityId, f32)> = (0..200).map(|id| (id, id as f32)).collect();
+        let world = snapshot(0.0, &rockets);
+        let mut encoder = DeltaEncoder::new();
+        let packet = encoder.encode(&world);
+        let datagrams = packet.to_datagrams().unwrap();
+        assert!(datagrams.len() > 1);
+        assert!(datagrams.iter().all(|datagram| datagram.len() <= MAX_DATAGRAM));
+
+        // Pieces come back in any order; the packet is whole once the last one lands
+        let mut decoder = DeltaDecoder::new();
+        let mut fragments: Vec<SnapshotFragment> =
+            datagrams.iter().map(|datagram| SnapshotFragment::from_bytes(datagram).unwrap()).collect();
+        fragments.reverse();
+        let last = fragments.pop().unwrap();
+        for fragment in fragments {
+            assert!(decoder.add_fragment(fragment).is_none());
+        }
+        let rebuilt = decoder.add_fragment(last).unwrap();
+        assert_eq!(decoder.decode(rebuilt).unwrap().unwrap().rockets, world.rockets);
+
+        // A newer packet's pieces replace an unfinished older one, whose stragglers are then dropped
+        let newer = encoder.encode(&world).to_datagrams().unwrap();
+        let first = SnapshotFragment::from_bytes(&newer[0]).unwrap();
+        assert!(decoder.add_fragment(first).is_none());
+        let older = SnapshotFragment::from_bytes(&datagrams[0]).unwrap();
+        assert!(decoder.add_fragment(older).is_none());
+        assert_eq!(decoder.pieces.as_ref().map(|(sequence, _)| *sequence), Some(1));
+    }
+}
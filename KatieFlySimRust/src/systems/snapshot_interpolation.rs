@@ -0,0 +1,166 @@
+// Snapshot Interpolation - Smooths remote craft on clients by drawing them slightly in the past
+// Poses from the last few snapshots are buffered and the world is set between the two bracketing ones,
+// so uneven packet arrival no longer makes other rockets, satellites and bullets jump
+
+use std::collections::{HashMap, VecDeque};
+
+use macroquad::prelude::*;
+
+use crate::entities::GameObject;
+use crate::systems::{EntityId, World};
+use crate::utils::vector_helper::angle_difference;
+
+/// How far behind the newest snapshot remote craft are drawn (seconds)
+pub const INTERPOLATION_DELAY: f32 = 0.1;
+/// Snapshots kept to interpolate between
+const BUFFER_LENGTH: usize = 8;
+/// Render clock error past which it jumps instead of easing back (seconds)
+const MAX_CLOCK_DRIFT: f32 = 0.25;
+/// Fraction of the render clock error corrected each frame
+const CLOCK_CORRECTION: f32 = 0.1;
+
+#[derive(Debug, Clone, Default)]
+struct Sample {
+    time: f32, // Host game time of the snapshot
+    rockets: HashMap<EntityId, (Vec2, f32)>, // Position and rotation
+    satellites: HashMap<EntityId, Vec2>,
+    bullets: HashMap<EntityId, Vec2>,
+}
+
+/// Buffer of recent snapshot poses and the clock remote craft are drawn at
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotInterpolator {
+    samples: VecDeque<Sample>, // Oldest first
+    render_time: Option<f32>,
+}
+
+impl SnapshotInterpolator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Host time remote craft are currently drawn at
+    pub fn render_time(&self) -> Option<f32> {
+        self.render_time
+    }
+
+    /// Buffer the poses of a freshly applied snapshot, leaving out `skip` (our predicted rocket)
+    pub fn record(&mut self, time: f32, world: &World, skip: Option<EntityId>) {
+        if self.samples.back().is_some_and(|latest| time <= latest.time) {
+            // Time went backwards (a loaded save or a new session), so start over
+            self.samples.clear();
+            self.render_time = None;
+        }
+        if self.samples.len() == BUFFER_LENGTH {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(Sample {
+            time,
+            rockets: world
+                .rockets_with_ids()
+                .filter(|(id, _)| Some(*id) != skip)
+                .map(|(id, rocket)| (id, (rocket.position(), rocket.rotation())))
+                .collect(),
+            satellites: world.satellites_with_ids().map(|(id, satellite)| (id, satellite.position())).collect(),
+            bullets: world.bullets_with_ids().map(|(id, bullet)| (id, bullet.position())).collect(),
+        });
+        self.render_time.get_or_insert(time - INTERPOLATION_DELAY);
+    }
+
+    /// Run the render clock, easing it toward `INTERPOLATION_DELAY` behind the newest snapshot
+    pub fn advance(&mut self, delta_time: f32) {
+        let (Some(render_time), Some(latest)) = (self.render_time.as_mut(), self.samples.back()) else {
+            return;
+        };
+        *render_time += delta_time;
+        let error = latest.time - INTERPOLATION_DELAY - *render_time;
+        if error.abs() > MAX_CLOCK_DRIFT {
+            *render_time += error;
+        } else {
+            *render_time += error * CLOCK_CORRECTION;
+        }
+    }
+
+    /// Follow a floating origin rebase (positions moved by `-shift`)
+    pub fn shift_positions(&mut self, shift: Vec2) {
+        for sample in &mut self.samples {
+            for (position, _) in sample.rockets.values_mut() {
+                *position -= shift;
+            }
+            for position in sample.satellites.values_mut().chain(sample.bullets.values_mut()) {
+                *position -= shift;
+            }
+        }
+    }
+
+    /// Put buffered craft at their interpolated poses; craft missing from either snapshot are left alone
+    pub fn apply(&self, world: &mut World) {
+        let Some(time) = self.render_time else {
+            return;
+        };
+        // Last snapshot at or before the render time, and how far toward the next one we are
+        let Some(index) = self.samples.iter().rposition(|sample| sample.time <= time) else {
+            return;
+        };
+        let (Some(from), Some(to)) = (self.samples.get(index), self.samples.get(index + 1)) else {
+            return; // Past the newest snapshot: hold where it put everything
+        };
+        let t = ((time - from.time) / (to.time - from.time).max(f32::EPSILON)).clamp(0.0, 1.0);
+
+        for (id, (from_position, from_rotation)) in &from.rockets {
+            let (Some((to_position, to_rotation)), Some(rocket)) = (to.rockets.get(id), world.get_rocket_mut(*id)) else {
+                continue;
+            };
+            rocket.set_position(from_position.lerp(*to_position, t));
+            rocket.set_rotation(from_rotation + angle_difference(*to_rotation, *from_rotation) * t);
+        }
+        for (id, from_position) in &from.satellites {
+            if let (Some(to_position), Some(satellite)) = (to.satellites.get(id), world.get_satellite_mut(*id)) {
+                satellite.set_position(from_position.lerp(*to_position, t));
+            }
+        }
+        for (id, from_position) in &from.bullets {
+            if let (Some(to_position), Some(bullet)) = (to.bullets.get(id), world.get_bullet_mut(*id)) {
+                bullet.set_position(from_position.lerp(*to_position, t));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::Rocket;
+
+    #[test]
+    fn test_remote_rockets_drawn_between_snapshots() {
+        let mut world = World::new();
+        let remote = world.add_rocket(Rocket::new(Vec2::ZERO, Vec2::ZERO, WHITE, 1.0));
+        let ours = world.add_rocket(Rocket::new(Vec2::ZERO, Vec2::ZERO, WHITE, 1.0));
+        world.get_rocket_mut(remote).unwrap().set_rotation(3.1);
+        let mut interpolator = SnapshotInterpolator::new();
+        interpolator.record(1.0, &world, Some(ours));
+        assert_eq!(interpolator.render_time(), Some(1.0 - INTERPOLATION_DELAY));
+
+        // Next snapshot moves both rockets and turns the remote one across the +-PI wrap
+        world.get_rocket_mut(remote).unwrap().set_position(Vec2::new(100.0, 0.0));
+        world.get_rocket_mut(remote).unwrap().set_rotation(-3.1);
+        world.get_rocket_mut(ours).unwrap().set_position(Vec2::new(100.0, 0.0));
+        interpolator.record(1.1, &world, Some(ours));
+        interpolator.advance(0.15);
+        let time = interpolator.render_time().unwrap();
+        assert!(time > 1.0 && time < 1.1);
+
+        interpolator.apply(&mut world);
+        let t = (time - 1.0) / 0.1;
+        let rocket = world.get_rocket(remote).unwrap();
+        assert!((rocket.position().x - 100.0 * t).abs() < 1e-3);
+        let turn = std::f32::consts::TAU - 6.2;
+        assert!((rocket.rotation() - (3.1 + turn * t)).abs() < 1e-4);
+        assert_eq!(world.get_rocket(ours).unwrap().position(), Vec2::new(100.0, 0.0));
+
+        // A save loaded mid-session restarts the buffer
+        interpolator.record(0.5, &world, None);
+        assert_eq!(interpolator.render_time(), Some(0.5 - INTERPOLATION_DELAY));
+    }
+}
@@ -0,0 +1,232 @@
+// Snapshot Pacing - Per-client snapshot rate and interest radius chosen from measured link quality
+// Clients ack each snapshot; round-trip time and loss pick a tier (full, half or quarter rate)
+
+use std::collections::VecDeque;
+
+/// Prefix of a client's snapshot acknowledgement (followed by the snapshot's time as f32 LE)
+const ACK_PREFIX: &[u8] = b"ACK";
+/// A snapshot not acked within this long counts as lost (seconds)
+const ACK_TIMEOUT: f64 = 1.0;
+/// Weight of each new sample in the smoothed RTT and loss
+const SMOOTHING: f32 = 0.1;
+/// Minimum time between tier changes so one bad second doesn't flap the rate (seconds)
+const TIER_HOLD: f64 = 3.0;
+/// Unacked snapshots tracked per client
+const MAX_PENDING: usize = 256;
+
+/// Step down when the link is this bad...
+const DEGRADE_LOSS: f32 = 0.10;
+const DEGRADE_RTT: f32 = 0.25;
+/// ...and back up only once it's this good
+const RECOVER_LOSS: f32 = 0.02;
+const RECOVER_RTT: f32 = 0.12;
+
+/// How often (and how much) a client is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SnapshotTier {
+    Full,
+    Half,
+    Quarter,
+}
+
+impl SnapshotTier {
+    /// Broadcast ticks per snapshot sent
+    pub fn divisor(&self) -> u32 {
+        match self {
+            SnapshotTier::Full => 1,
+            SnapshotTier::Half => 2,
+            SnapshotTier::Quarter => 4,
+        }
+    }
+
+    /// Bullets and other rockets farther than this from the client's rocket are left out
+    pub fn interest_radius(&self) -> Option<f32> {
+        match self {
+            SnapshotTier::Full => None,
+            SnapshotTier::Half => Some(40000.0),
+            SnapshotTier::Quarter => Some(15000.0),
+        }
+    }
+
+    /// Snapshots per second at the host's broadcast rate
+    pub fn rate_hz(&self, base_hz: u32) -> u32 {
+        (base_hz / self.divisor()).max(1)
+    }
+
+    fn worse(&self) -> Self {
+        match self {
+            SnapshotTier::Full => SnapshotTier::Half,
+            _ => SnapshotTier::Quarter,
+        }
+    }
+
+    fn better(&self) -> Self {
+        match self {
+            SnapshotTier::Quarter => SnapshotTier::Half,
+            _ => SnapshotTier::Full,
+        }
+    }
+}
+
+/// Acknowledgement packet bytes for a snapshot
+pub fn ack_packet(snapshot_time: f32) -> Vec<u8> {
+    let mut bytes = ACK_PREFIX.to_vec();
+    bytes.extend_from_slice(&snapshot_time.to_le_bytes());
+    bytes
+}
+
+/// Snapshot time from an acknowledgement packet (None for other packets)
+pub fn parse_ack(bytes: &[u8]) -> Option<f32> {
+    let payload: [u8; 4] = bytes.strip_prefix(ACK_PREFIX)?.try_into().ok()?;
+    Some(f32::from_le_bytes(payload))
+}
+
+/// Host-side link measurements and the tier they led to for one client
+#[derive(Debug, Clone)]
+pub struct LinkQuality {
+    pending: VecDeque<(f32, f64)>, // Snapshot time, wall time it was sent
+    rtt: f32,  // Smoothed round-trip time (seconds)
+    loss: f32, // Smoothed fraction of snapshots never acked
+    tier: SnapshotTier,
+    ticks: u32, // Broadcast ticks since the last snapshot sent
+    last_tier_change: f64,
+    measured: bool, // Any ack yet (clients that never ack are left at full rate)
+}
+
+impl LinkQuality {
+    pub fn new(now: f64) -> Self {
+        LinkQuality {
+            pending: VecDeque::new(),
+            rtt: 0.0,
+            loss: 0.0,
+            tier: SnapshotTier::Full,
+            ticks: 0,
+            last_tier_change: now,
+            measured: false,
+        }
+    }
+
+    pub fn tier(&self) -> SnapshotTier {
+        self.tier
+    }
+
+    /// Smoothed round-trip time in seconds
+    pub fn rtt(&self) -> f32 {
+        self.rtt
+    }
+
+    /// Smoothed loss fraction (0-1)
+    pub fn loss(&self) -> f32 {
+        self.loss
+    }
+
+    /// Called on every broadcast tick; true if this client gets a snapshot this tick
+    pub fn should_send(&mut self) -> bool {
+        self.ticks += 1;
+        if self.ticks >= self.tier.divisor() {
+            self.ticks = 0;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn on_sent(&mut self, snapshot_time: f32, now: f64) {
+        if self.pending.len() >= MAX_PENDING {
+            self.pending.pop_front();
+        }
+        self.pending.push_back((snapshot_time, now));
+    }
+
+    pub fn on_ack(&mut self, snapshot_time: f32, now: f64) {
+        let Some(index) = self.pending.iter().position(|(time, _)| *time == snapshot_time) else {
+            return; // Already timed out or a duplicate
+        };
+        let (_, sent_at) = self.pending.remove(index).unwrap_or_default();
+        let sample = (now - sent_at) as f32;
+        self.rtt = if self.measured { self.rtt + (sample - self.rtt) * SMOOTHING } else { sample };
+        self.loss -= self.loss * SMOOTHING;
+        self.measured = true;
+    }
+
+    /// Count timed-out snapshots as lost and re-pick the tier; returns true if the tier changed
+    pub fn update(&mut self, now: f64) -> bool {
+        while let Some((_, sent_at)) = self.pending.front() {
+            if now - sent_at < ACK_TIMEOUT {
+                break;
+            }
+            self.pending.pop_front();
+            if self.measured {
+                self.loss += (1.0 - self.loss) * SMOOTHING;
+            }
+        }
+
+        if !self.measured || now - self.last_tier_change < TIER_HOLD {
+            return false;
+        }
+        let next = if self.loss > DEGRADE_LOSS || self.rtt > DEGRADE_RTT {
+            self.tier.worse()
+        } else if self.loss < RECOVER_LOSS && self.rtt < RECOVER_RTT {
+            self.tier.better()
+        } else {
+            self.tier
+        };
+        if next == self.tier {
+            return false;
+        }
+        self.tier = next;
+        self.last_tier_change = now;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ack_round_trip() {
+        assert_eq!(parse_ack(&ack_packet(12.5)), Some(12.5));
+        assert_eq!(parse_ack(b"KEEPALIVE"), None);
+        assert_eq!(parse_ack(b"ACK12"), None);
+    }
+
+    #[test]
+    fn test_tier_follows_link_quality() {
+        let mut link = LinkQuality::new(0.0);
+        let mut now = 0.0;
+        let mut time = 0.0f32;
+
+        // Half the snapshots are never acked: the rate steps down, one tier per hold period
+        for i in 0..600 {
+            now += 1.0 / 60.0;
+            time += 1.0 / 60.0;
+            link.on_sent(time, now);
+            if i % 2 == 0 {
+                link.on_ack(time, now + 0.03);
+            }
+            link.update(now);
+        }
+        assert_eq!(link.tier(), SnapshotTier::Quarter);
+        assert!(link.loss() > DEGRADE_LOSS);
+
+        // A clean link recovers
+        for _ in 0..900 {
+            now += 1.0 / 60.0;
+            time += 1.0 / 60.0;
+            link.on_sent(time, now);
+            link.on_ack(time, now + 0.03);
+            link.update(now);
+        }
+        assert_eq!(link.tier(), SnapshotTier::Full);
+    }
+
+    #[test]
+    fn test_should_send_follows_divisor() {
+        let mut link = LinkQuality::new(0.0);
+        link.tier = SnapshotTier::Quarter;
+        let sent = (0..8).filter(|_| link.should_send()).count();
+        assert_eq!(sent, 2);
+        assert_eq!(SnapshotTier::Quarter.rate_hz(60), 15);
+    }
+}
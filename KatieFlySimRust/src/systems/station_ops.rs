@@ -0,0 +1,165 @@
+// Station Ops - Landed rockets left on the pad to refuel themselves
+// A held rocket pulls fuel from the planet it sits on up to a set level, then reports it's ready;
+// taking off ends the hold. The host runs every player's holds in multiplayer
+
+use crate::systems::{EntityId, World};
+
+/// Tank levels a hold cycles through (6 on a landed rocket), then off
+pub const HOLD_LEVELS: [f32; 3] = [0.5, 0.75, 1.0];
+/// Fuel per second a held rocket pulls from its planet
+pub const STATION_REFUEL_RATE: f32 = 8.0;
+
+/// One rocket holding on the pad
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StationHold {
+    pub rocket: EntityId,
+    pub planet: EntityId,
+    pub level: f32,       // Share of the tank to fill to
+    pub ready: bool,      // Reached its level (and already said so)
+    pub planet_dry: bool, // Planet can't give more (and already said so)
+}
+
+/// Something the rocket's pilot should hear about
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StationEvent {
+    Ready { rocket: EntityId, fuel: f32 },
+    /// The planet is down to its minimum mass before the level was reached (the hold stays)
+    PlanetDry { rocket: EntityId, fuel: f32 },
+    /// Took off, or the rocket is gone; the hold is dropped
+    Released { rocket: EntityId },
+}
+
+/// Every rocket on station hold
+#[derive(Debug, Clone, Default)]
+pub struct StationOps {
+    holds: Vec<StationHold>,
+}
+
+impl StationOps {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn holds(&self) -> &[StationHold] {
+        &self.holds
+    }
+
+    pub fn hold_for(&self, rocket: EntityId) -> Option<&StationHold> {
+        self.holds.iter().find(|hold| hold.rocket == rocket)
+    }
+
+    pub fn clear(&mut self) {
+        self.holds.clear();
+    }
+
+    /// Step a landed rocket to the next hold level, or off after the last; returns the new level
+    pub fn cycle(&mut self, world: &World, rocket: EntityId) -> Result<Option<f32>, String> {
+        let next = match self.holds.iter().position(|hold| hold.rocket == rocket) {
+            Some(index) => {
+                let hold = self.holds.remove(index);
+                HOLD_LEVELS.iter().copied().find(|level| *level > hold.level)
+            }
+            None => Some(HOLD_LEVELS[0]),
+        };
+        let Some(level) = next else {
+            return Ok(None);
+        };
+        let planet = world
+            .get_rocket(rocket)
+            .filter(|rocket| rocket.is_landed())
+            .and_then(|rocket| rocket.landed_on_planet_id())
+            .ok_or("Station ops needs a landed rocket")?;
+        self.holds.push(StationHold { rocket, planet, level, ready: false, planet_dry: false });
+        Ok(Some(level))
+    }
+
+    /// Refuel every held rocket one tick (before `World::update`)
+    pub fn update(&mut self, world: &mut World, delta_time: f32) -> Vec<StationEvent> {
+        let mut events = Vec::new();
+        self.holds.retain_mut(|hold| {
+            let Some(rocket) = world.get_rocket(hold.rocket).filter(|rocket| rocket.landed_on_planet_id() == Some(hold.planet)) else {
+                events.push(StationEvent::Released { rocket: hold.rocket });
+                return false;
+            };
+            let wanted = rocket.max_fuel() * hold.level - rocket.current_fuel();
+            if wanted <= 0.0 {
+                hold.planet_dry = false;
+                if !hold.ready {
+                    hold.ready = true;
+                    events.push(StationEvent::Ready { rocket: hold.rocket, fuel: rocket.current_fuel() });
+                }
+                return true;
+            }
+            hold.ready = false;
+            let moved = world.refuel_landed_rocket(hold.rocket, wanted.min(STATION_REFUEL_RATE * delta_time));
+            if moved > 0.0 {
+                hold.planet_dry = false;
+            } else if !hold.planet_dry {
+                // Nothing left to give; say so once, then sit quietly
+                hold.planet_dry = true;
+                let fuel = world.get_rocket(hold.rocket).map_or(0.0, |rocket| rocket.current_fuel());
+                events.push(StationEvent::PlanetDry { rocket: hold.rocket, fuel });
+            }
+            true
+        });
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Planet, Rocket};
+    use crate::game_constants::GameConstants;
+    use macroquad::prelude::*;
+
+    fn landed_world(planet_mass: f32) -> (World, EntityId, EntityId) {
+        let mut world = World::new();
+        let planet = world.add_planet(Planet::new(Vec2::ZERO, 1000.0, planet_mass, BLUE));
+        let mut rocket = Rocket::new(Vec2::new(0.0, -1000.0), Vec2::ZERO, WHITE, 1.0);
+        rocket.set_fuel(0.0);
+        rocket.land_on_planet(planet, Vec2::new(0.0, -1000.0), Vec2::ZERO);
+        let rocket = world.add_rocket(rocket);
+        (world, planet, rocket)
+    }
+
+    #[test]
+    fn test_hold_fills_to_its_level_then_reports_ready() {
+        let (mut world, _, rocket) = landed_world(100000.0);
+        let mut ops = StationOps::new();
+        assert_eq!(ops.cycle(&world, rocket), Ok(Some(0.5)));
+        assert_eq!(ops.cycle(&world, rocket), Ok(Some(0.75)));
+
+        let target = world.get_rocket(rocket).unwrap().max_fuel() * 0.75;
+        let mut events = Vec::new();
+        for _ in 0..200 {
+            events.extend(ops.update(&mut world, 0.1));
+        }
+        assert!(matches!(events[..], [StationEvent::Ready { fuel, .. }] if (fuel - target).abs() < 0.01), "{:?}", events);
+        assert!(ops.hold_for(rocket).unwrap().ready);
+
+        // Past the last level the hold comes off
+        assert_eq!(ops.cycle(&world, rocket), Ok(Some(1.0)));
+        assert_eq!(ops.cycle(&world, rocket), Ok(None));
+        assert!(ops.holds().is_empty());
+    }
+
+    #[test]
+    fn test_hold_stops_at_the_planets_minimum_mass() {
+        let (mut world, planet, rocket) = landed_world(GameConstants::MIN_VIABLE_PLANET_MASS + 10.0);
+        let mut ops = StationOps::new();
+        ops.cycle(&world, rocket).unwrap();
+
+        let mut events = Vec::new();
+        for _ in 0..100 {
+            events.extend(ops.update(&mut world, 0.1));
+        }
+        assert!(matches!(events[..], [StationEvent::PlanetDry { fuel, .. }] if (fuel - 10.0).abs() < 0.01), "{:?}", events);
+        assert_eq!(world.get_planet(planet).unwrap().mass(), GameConstants::MIN_VIABLE_PLANET_MASS);
+
+        // Taking off drops the hold
+        world.get_rocket_mut(rocket).unwrap().take_off();
+        assert_eq!(ops.update(&mut world, 0.1), vec![StationEvent::Released { rocket }]);
+        assert!(ops.cycle(&world, rocket).is_err());
+    }
+}
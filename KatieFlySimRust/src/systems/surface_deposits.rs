@@ -0,0 +1,108 @@
+// Surface Deposits - Map-defined fuel deposits that rovers can extract
+// Deposits are finite; once drained they stay empty for the session
+
+use macroquad::prelude::*;
+
+use crate::game_constants::GameConstants;
+use crate::map_config::MapConfiguration;
+use crate::systems::World;
+use crate::utils::vector_helper;
+
+/// A fuel deposit on a planet surface
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuelDeposit {
+    pub body_name: String,
+    pub angle: f32,
+    pub remaining: f32,
+}
+
+/// All fuel deposits in the session
+#[derive(Debug, Clone, Default)]
+pub struct SurfaceDeposits {
+    deposits: Vec<FuelDeposit>,
+}
+
+impl SurfaceDeposits {
+    pub fn from_map(map: &MapConfiguration) -> Self {
+        let deposits = map
+            .fuel_deposits
+            .iter()
+            .filter_map(|deposit| {
+                let body = map.celestial_bodies.get(deposit.body_index)?;
+                Some(FuelDeposit {
+                    body_name: body.name.clone(),
+                    angle: deposit.angle,
+                    remaining: deposit.amount,
+                })
+            })
+            .collect();
+        SurfaceDeposits { deposits }
+    }
+
+    pub fn deposits(&self) -> &[FuelDeposit] {
+        &self.deposits
+    }
+
+    /// Take up to `max_amount` fuel from the nearest non-empty deposit in range of a surface position
+    /// Returns the amount extracted (0 if there's no deposit in range)
+    pub fn extract(&mut self, planet_name: &str, planet_radius: f32, surface_angle: f32, max_amount: f32) -> f32 {
+        let nearest = self
+            .deposits
+            .iter_mut()
+            .filter(|d| d.body_name == planet_name && d.remaining > 0.0)
+            .map(|d| (vector_helper::angle_difference(surface_angle, d.angle).abs() * planet_radius, d))
+            .filter(|(arc_distance, _)| *arc_distance <= GameConstants::FUEL_DEPOSIT_RANGE)
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        match nearest {
+            Some((_, deposit)) => {
+                let amount = max_amount.min(deposit.remaining).max(0.0);
+                deposit.remaining -= amount;
+                amount
+            }
+            None => 0.0,
+        }
+    }
+
+    /// Draw deposit markers on planet surfaces (call in world space)
+    pub fn draw(&self, world: &World, zoom_level: f32) {
+        for deposit in &self.deposits {
+            let Some(planet) = world.planets().find(|p| p.name() == Some(deposit.body_name.as_str())) else {
+                continue;
+            };
+            let position = planet.position() + Vec2::from_angle(deposit.angle) * planet.surface_radius_at(deposit.angle);
+            let color = if deposit.remaining > 0.0 {
+                Color::new(1.0, 0.55, 0.1, 0.9)
+            } else {
+                Color::new(0.4, 0.4, 0.4, 0.5)
+            };
+
+            let size = (10.0 * zoom_level).max(10.0);
+            draw_poly(position.x, position.y, 4, size, 45.0, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_drains_deposit_in_range() {
+        let mut deposits = SurfaceDeposits {
+            deposits: vec![FuelDeposit {
+                body_name: "Moon".to_string(),
+                angle: 0.0,
+                remaining: 8.0,
+            }],
+        };
+
+        // Out of range (1000 px along the surface) or wrong planet
+        assert_eq!(deposits.extract("Moon", 1000.0, 1.0, 5.0), 0.0);
+        assert_eq!(deposits.extract("Earth", 1000.0, 0.0, 5.0), 0.0);
+
+        assert_eq!(deposits.extract("Moon", 1000.0, 0.05, 5.0), 5.0);
+        assert_eq!(deposits.extract("Moon", 1000.0, 0.05, 5.0), 3.0);
+        assert_eq!(deposits.extract("Moon", 1000.0, 0.05, 5.0), 0.0);
+    }
+}
@@ -2,9 +2,12 @@
 // Extended from original C++ VehicleManager with trajectory and force display
 
 use macroquad::prelude::*;
-use crate::entities::{Rocket, Planet, GameObject};
-use crate::physics::TrajectoryPredictor;
-use crate::systems::EntityId;
+use crate::entities::{Rocket, Planet, Satellite, GameObject};
+use crate::game_constants::GameConstants;
+use crate::physics::{GravityModel, TrajectoryPredictor};
+use crate::physics::orbital::{self, OrbitEllipse};
+use crate::systems::{EntityId, World};
+use crate::systems::entity_picker::EntityTarget;
 
 /// Reference body index for trajectory calculations
 /// This is an index into the planets array
@@ -16,6 +19,7 @@ pub struct VisualizationOptions {
     pub show_trajectory: bool,
     pub show_gravity_forces: bool,
     pub show_planet_trajectories: bool,
+    pub show_satellite_orbits: bool,
     pub trajectory_steps: usize,
     pub trajectory_time_step: f32,
     pub force_vector_scale: f32,
@@ -28,6 +32,7 @@ impl Default for VisualizationOptions {
             show_trajectory: true,
             show_gravity_forces: false,
             show_planet_trajectories: false,
+            show_satellite_orbits: false,
             trajectory_steps: 200,
             trajectory_time_step: 0.5,
             force_vector_scale: 15.0,
@@ -41,6 +46,7 @@ pub struct VehicleManager {
     active_vehicle_id: Option<EntityId>,
     trajectory_predictor: TrajectoryPredictor,
     visualization: VisualizationOptions,
+    target: Option<EntityTarget>, // Selected target for targeting/autopilot
 }
 
 impl VehicleManager {
@@ -49,6 +55,7 @@ impl VehicleManager {
             active_vehicle_id: None,
             trajectory_predictor: TrajectoryPredictor::new(),
             visualization: VisualizationOptions::default(),
+            target: None,
         }
     }
 
@@ -62,6 +69,33 @@ impl VehicleManager {
         self.active_vehicle_id
     }
 
+    /// Get the selected target
+    pub fn target(&self) -> Option<EntityTarget> {
+        self.target
+    }
+
+    /// Select a target (None clears it)
+    pub fn set_target(&mut self, target: Option<EntityTarget>) {
+        self.target = target;
+    }
+
+    /// Select a target, or clear it if it is already selected
+    pub fn toggle_target(&mut self, target: EntityTarget) {
+        self.target = if self.target == Some(target) { None } else { Some(target) };
+    }
+
+    /// Drop the target if its entity no longer exists (destroyed, converted, etc.)
+    pub fn clear_missing_target(&mut self, world: &World) {
+        if self.target.is_some_and(|target| !target.exists(world)) {
+            self.target = None;
+        }
+    }
+
+    /// Predict trajectories with the map's gravity model
+    pub fn set_gravity_model(&mut self, model: GravityModel) {
+        self.trajectory_predictor.set_gravity_model(model);
+    }
+
     /// Toggle trajectory visualization
     pub fn toggle_trajectory(&mut self) {
         self.visualization.show_trajectory = !self.visualization.show_trajectory;
@@ -77,6 +111,11 @@ impl VehicleManager {
         self.visualization.show_planet_trajectories = !self.visualization.show_planet_trajectories;
     }
 
+    /// Toggle fitted satellite orbit ellipses
+    pub fn toggle_satellite_orbits(&mut self) {
+        self.visualization.show_satellite_orbits = !self.visualization.show_satellite_orbits;
+    }
+
     /// Cycle to next reference body
     pub fn toggle_reference_body(&mut self, num_bodies: usize) {
         if num_bodies == 0 {
@@ -245,10 +284,49 @@ impl VehicleManager {
         }
     }
 
+    /// Fit a satellite's orbit ellipse around its dominant body (strongest gravitational pull)
+    pub fn satellite_orbit_ellipse(satellite: &Satellite, planets: &[&Planet]) -> Option<OrbitEllipse> {
+        let position = satellite.position();
+        let dominant = planets.iter().max_by(|a, b| {
+            let pull_a = a.mass() / (a.position() - position).length_squared().max(1.0);
+            let pull_b = b.mass() / (b.position() - position).length_squared().max(1.0);
+            pull_a.partial_cmp(&pull_b).unwrap_or(std::cmp::Ordering::Equal)
+        })?;
+
+        orbital::fit_orbit_ellipse(
+            position,
+            satellite.velocity(),
+            dominant.position(),
+            dominant.velocity(),
+            dominant.mass(),
+            GameConstants::G,
+        )
+    }
+
+    /// Draw each satellite's fitted orbit as a faint ellipse (world space)
+    pub fn draw_satellite_orbits(&self, satellites: &[&Satellite], planets: &[&Planet], zoom_level: f32) {
+        if !self.visualization.show_satellite_orbits {
+            return;
+        }
+
+        for satellite in satellites {
+            let Some(ellipse) = Self::satellite_orbit_ellipse(satellite, planets) else {
+                continue;
+            };
+
+            let base_color = satellite.status_color();
+            let color = Color::new(base_color.r, base_color.g, base_color.b, 0.25);
+            let points = ellipse.points(128);
+            for pair in points.windows(2) {
+                draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, 1.5 * zoom_level, color);
+            }
+        }
+    }
+
     /// Draw HUD overlay for visualization status
     pub fn draw_visualization_hud(&self, planets: &[&Planet]) {
         let x = 10.0;
-        let mut y = screen_height() - 170.0;
+        let mut y = screen_height() - 195.0;
         let line_height = 25.0;
         let font_size = 18.0;
 
@@ -298,6 +376,20 @@ impl VehicleManager {
         draw_text(planet_traj_status, x, y, font_size, planet_traj_color);
         y += line_height;
 
+        // Satellite orbit ellipses status
+        let sat_orbits_status = if self.visualization.show_satellite_orbits {
+            "✓ Satellite Orbits (L)"
+        } else {
+            "  Satellite Orbits (L)"
+        };
+        let sat_orbits_color = if self.visualization.show_satellite_orbits {
+            Color::new(0.0, 1.0, 0.0, 1.0)
+        } else {
+            Color::new(0.5, 0.5, 0.5, 1.0)
+        };
+        draw_text(sat_orbits_status, x, y, font_size, sat_orbits_color);
+        y += line_height;
+
         // Reference body status - show actual planet name
         let ref_body_name = if planets.is_empty() {
             "Unknown"
@@ -0,0 +1,164 @@
+// Waypoints - Named markers players drop in the world
+// Saved with the game; in multiplayer the host keeps everyone's and each player sees their own plus shared ones
+
+use macroquad::prelude::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// Prefix marking a waypoint packet (snapshots never start with these bytes)
+const WAYPOINT_PACKET_PREFIX: &[u8] = b"WAYPOINT";
+/// Most waypoints one player can have; the oldest is dropped past this
+pub const MAX_WAYPOINTS_PER_PLAYER: usize = 32;
+
+/// One named marker
+#[derive(Debug, Clone, PartialEq)]
+pub struct Waypoint {
+    pub id: u32,
+    pub name: String,
+    pub position: Vec2,
+    pub owner: Option<u32>, // Player who dropped it (None in single player)
+    pub shared: bool,       // Visible to every player, not just the owner
+}
+
+/// All waypoints in a session
+#[derive(Debug, Clone, Default)]
+pub struct Waypoints {
+    list: Vec<Waypoint>,
+    next_id: u32,
+}
+
+impl Waypoints {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restore saved waypoints
+    pub fn from_list(list: Vec<Waypoint>) -> Self {
+        let next_id = list.iter().map(|w| w.id + 1).max().unwrap_or(0);
+        Waypoints { list, next_id }
+    }
+
+    pub fn list(&self) -> &[Waypoint] {
+        &self.list
+    }
+
+    /// Drop a waypoint named "WP n"; returns its ID
+    pub fn add(&mut self, position: Vec2, owner: Option<u32>, shared: bool) -> u32 {
+        let owned: Vec<u32> = self.list.iter().filter(|w| w.owner == owner).map(|w| w.id).collect();
+        if owned.len() >= MAX_WAYPOINTS_PER_PLAYER {
+            self.remove(owned[0], owner);
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.list.push(Waypoint {
+            id,
+            name: format!("WP {}", id + 1),
+            position,
+            owner,
+            shared,
+        });
+        id
+    }
+
+    /// Remove a waypoint if `owner` dropped it
+    pub fn remove(&mut self, id: u32, owner: Option<u32>) -> bool {
+        let before = self.list.len();
+        self.list.retain(|w| !(w.id == id && w.owner == owner));
+        self.list.len() != before
+    }
+
+    /// Follow a floating origin rebase (positions moved by `-shift`)
+    pub fn shift_positions(&mut self, shift: Vec2) {
+        for waypoint in &mut self.list {
+            waypoint.position -= shift;
+        }
+    }
+
+    /// Waypoints a player sees: their own and shared ones
+    pub fn visible_to(&self, player: Option<u32>) -> Vec<Waypoint> {
+        self.list.iter().filter(|w| w.shared || w.owner == player).cloned().collect()
+    }
+
+    /// The player's own waypoint within `radius` of `position`, nearest first
+    pub fn owned_near(&self, position: Vec2, radius: f32, owner: Option<u32>) -> Option<u32> {
+        self.list
+            .iter()
+            .filter(|w| w.owner == owner)
+            .map(|w| (w.id, w.position.distance(position)))
+            .filter(|(_, distance)| *distance <= radius)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(id, _)| id)
+    }
+}
+
+/// Client -> host waypoint edits (the host owns the list and sends it back in snapshots)
+/// Positions are world coordinates, so a floating origin rebase in flight doesn't move them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WaypointPacket {
+    Add { x: f64, y: f64, shared: bool },
+    Remove { id: u32 },
+}
+
+impl WaypointPacket {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = WAYPOINT_PACKET_PREFIX.to_vec();
+        bytes.extend(bincode::serialize(self).unwrap_or_default());
+        bytes
+    }
+
+    /// None if the bytes aren't a waypoint packet
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let payload = bytes.strip_prefix(WAYPOINT_PACKET_PREFIX)?;
+        bincode::deserialize(payload).ok()
+    }
+}
+
+/// Distance readout ("850 m", "12.4 km")
+pub fn format_distance(distance: f32) -> String {
+    if distance < 1000.0 {
+        format!("{:.0} m", distance)
+    } else {
+        format!("{:.1} km", distance / 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visibility_and_removal() {
+        let mut waypoints = Waypoints::new();
+        let mine = waypoints.add(Vec2::new(10.0, 0.0), Some(1), false);
+        let shared = waypoints.add(Vec2::new(500.0, 0.0), Some(2), true);
+        waypoints.add(Vec2::ZERO, Some(2), false);
+
+        let ids = |list: Vec<Waypoint>| list.iter().map(|w| w.id).collect::<Vec<_>>();
+        assert_eq!(ids(waypoints.visible_to(Some(1))), vec![mine, shared]);
+        assert_eq!(waypoints.visible_to(Some(2)).len(), 2);
+
+        // Only the owner can remove a waypoint
+        assert!(!waypoints.remove(shared, Some(1)));
+        assert_eq!(waypoints.owned_near(Vec2::ZERO, 50.0, Some(1)), Some(mine));
+        assert!(waypoints.remove(mine, Some(1)));
+        assert_eq!(waypoints.owned_near(Vec2::ZERO, 50.0, Some(1)), None);
+
+        // Restored lists keep numbering after the highest ID
+        let mut restored = Waypoints::from_list(waypoints.list().to_vec());
+        assert_eq!(restored.add(Vec2::ZERO, None, false), 3);
+    }
+
+    #[test]
+    fn test_per_player_limit_drops_oldest() {
+        let mut waypoints = Waypoints::new();
+        for i in 0..=MAX_WAYPOINTS_PER_PLAYER {
+            waypoints.add(Vec2::new(i as f32, 0.0), None, false);
+        }
+        assert_eq!(waypoints.list().len(), MAX_WAYPOINTS_PER_PLAYER);
+        assert_eq!(waypoints.list()[0].id, 1);
+
+        let packet = WaypointPacket::Remove { id: 3 };
+        assert_eq!(WaypointPacket::from_bytes(&packet.to_bytes()), Some(packet));
+        assert_eq!(format_distance(12_400.0), "12.4 km");
+    }
+}
@@ -0,0 +1,130 @@
+// World Bounds - Spots craft that have left the system for good
+// Escaping = unbound from every body and past the edge of the system; lost = so far past it that nothing comes back
+
+use macroquad::prelude::Vec2;
+
+use crate::entities::GameObject;
+use crate::game_constants::GameConstants;
+use crate::systems::{EntityId, World};
+
+/// Destruction cause reported when a player aborts an escaping rocket back to spawn
+pub const ABORT_CAUSE: &str = "abort to spawn";
+/// Fuel the replacement rocket gets after an abort, as a fraction of a full tank (the penalty)
+pub const ABORT_FUEL_FRACTION: f32 = 0.25;
+
+/// Where the edge of the system sits, relative to the circle holding every body
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundsConfig {
+    pub edge_factor: f32, // Escaping craft past this many system radii get a warning
+    pub lost_factor: f32, // ...and past this many they're gone (NPC satellites and bullets get removed)
+}
+
+impl Default for BoundsConfig {
+    fn default() -> Self {
+        BoundsConfig { edge_factor: 1.5, lost_factor: 4.0 }
+    }
+}
+
+/// Where a craft stands relative to the system
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundsStatus {
+    Inside,
+    /// Unbound and past the edge; `time_to_lost` while it's still heading outward
+    Escaping { time_to_lost: Option<f32> },
+    Lost,
+}
+
+/// Whether something at `position` moving at `velocity` has escaped every body for good
+pub fn status(world: &World, position: Vec2, velocity: Vec2, config: &BoundsConfig) -> BoundsStatus {
+    let Some((center, radius)) = world.system_extent() else {
+        return BoundsStatus::Inside;
+    };
+    let offset = position - center;
+    let distance = offset.length();
+    if distance < radius * config.edge_factor {
+        return BoundsStatus::Inside;
+    }
+
+    // Bound to any body (positive specific energy means it never comes back on its own)
+    let bound = world.planets().any(|planet| {
+        let r = (position - planet.position()).length().max(1.0);
+        let v = velocity - planet.velocity();
+        v.length_squared() / 2.0 - GameConstants::G * planet.mass() / r < 0.0
+    });
+    if bound {
+        return BoundsStatus::Inside;
+    }
+
+    let lost_distance = radius * config.lost_factor;
+    if distance >= lost_distance {
+        return BoundsStatus::Lost;
+    }
+    let outward_speed = offset.dot(velocity) / distance.max(1.0);
+    let time_to_lost = (outward_speed > 0.01).then(|| (lost_distance - distance) / outward_speed);
+    BoundsStatus::Escaping { time_to_lost }
+}
+
+/// Satellites and bullets that are lost for good (player rockets are left to the game mode)
+pub fn lost_entities(world: &World, config: &BoundsConfig) -> (Vec<EntityId>, Vec<EntityId>) {
+    let is_lost = |position: Vec2, velocity: Vec2| status(world, position, velocity, config) == BoundsStatus::Lost;
+    let satellites = world.satellites_with_ids().filter(|(_, s)| is_lost(s.position(), s.velocity())).map(|(id, _)| id).collect();
+    let bullets = world.bullets_with_ids().filter(|(_, b)| is_lost(b.position(), b.velocity())).map(|(id, _)| id).collect();
+    (satellites, bullets)
+}
+
+/// Remove lost satellites and bullets; returns how many satellites went
+pub fn remove_lost_entities(world: &mut World, config: &BoundsConfig) -> usize {
+    let (satellites, bullets) = lost_entities(world, config);
+    for id in &satellites {
+        log::info!("Satellite {} drifted out of the system and was removed", id);
+        world.remove_satellite(*id);
+    }
+    for id in bullets {
+        world.remove_bullet(id);
+    }
+    satellites.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Planet, Satellite};
+    use macroquad::prelude::{BLUE, GREEN};
+
+    fn one_planet_world() -> World {
+        let mut world = World::new();
+        world.add_planet(Planet::new(Vec2::ZERO, 100.0, 10000.0, BLUE));
+        world
+    }
+
+    #[test]
+    fn test_bound_or_nearby_craft_are_inside() {
+        let world = one_planet_world();
+        let config = BoundsConfig::default();
+        // Close in, even if fast
+        assert_eq!(status(&world, Vec2::new(120.0, 0.0), Vec2::new(5000.0, 0.0), &config), BoundsStatus::Inside);
+        // Far out but too slow to escape (v^2/2 < G*M/r)
+        assert_eq!(status(&world, Vec2::new(300.0, 0.0), Vec2::new(10.0, 0.0), &config), BoundsStatus::Inside);
+    }
+
+    #[test]
+    fn test_escaping_then_lost() {
+        let world = one_planet_world();
+        let config = BoundsConfig::default();
+        match status(&world, Vec2::new(200.0, 0.0), Vec2::new(500.0, 0.0), &config) {
+            BoundsStatus::Escaping { time_to_lost: Some(t) } => assert!((t - 0.4).abs() < 0.01),
+            other => panic!("expected escaping, got {:?}", other),
+        }
+        assert_eq!(status(&world, Vec2::new(500.0, 0.0), Vec2::new(500.0, 0.0), &config), BoundsStatus::Lost);
+    }
+
+    #[test]
+    fn test_remove_lost_satellites() {
+        let mut world = one_planet_world();
+        let kept = world.add_satellite(Satellite::new(Vec2::new(150.0, 0.0), Vec2::new(0.0, 80.0), GREEN));
+        let lost = world.add_satellite(Satellite::new(Vec2::new(5000.0, 0.0), Vec2::new(300.0, 0.0), GREEN));
+        assert_eq!(remove_lost_entities(&mut world, &BoundsConfig::default()), 1);
+        assert!(world.get_satellite(kept).is_some());
+        assert!(world.get_satellite(lost).is_none());
+    }
+}
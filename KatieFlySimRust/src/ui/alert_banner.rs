@@ -0,0 +1,206 @@
+// Alert Banner - Flashing warning banner for active alerts
+// Shows the most urgent alerts with time-to-event and beeps when a new one appears
+
+use std::collections::HashSet;
+
+use macroquad::audio::{play_sound, PlaySoundParams, Sound};
+use macroquad::prelude::*;
+
+use crate::systems::entity_picker::EntityTarget;
+use crate::systems::{Alert, AlertKind, World};
+use crate::ui::{Anchor, SafeArea};
+
+/// Maximum number of alert lines shown in the banner
+const MAX_BANNER_LINES: usize = 2;
+/// Banner flash rate (flashes per second)
+const FLASH_RATE: f32 = 2.0;
+/// Volume of the warning beep
+const WARNING_VOLUME: f32 = 0.4;
+
+/// Draws active alerts and plays the warning sound
+pub struct AlertBanner {
+    alerts: Vec<Alert>,
+    seen: HashSet<(AlertKind, EntityTarget)>,
+    flash_time: f32,
+    warning_sound: Option<Sound>,
+}
+
+impl AlertBanner {
+    pub fn new() -> Self {
+        AlertBanner {
+            alerts: Vec::new(),
+            seen: HashSet::new(),
+            flash_time: 0.0,
+            warning_sound: None,
+        }
+    }
+
+    /// Set the sound played when a new alert is raised
+    pub fn set_warning_sound(&mut self, sound: Option<Sound>) {
+        self.warning_sound = sound;
+    }
+
+    /// Take the latest alerts; beeps if any alert wasn't active last time
+    pub fn update(&mut self, delta_time: f32, alerts: &[Alert]) {
+        self.flash_time += delta_time;
+
+        let current: HashSet<(AlertKind, EntityTarget)> = alerts.iter().map(|a| (a.kind, a.subject)).collect();
+        let has_new = current.iter().any(|key| !self.seen.contains(key));
+        if has_new {
+            self.flash_time = 0.0;
+            if let Some(sound) = &self.warning_sound {
+                play_sound(sound, PlaySoundParams { looped: false, volume: WARNING_VOLUME });
+            }
+        }
+
+        self.seen = current;
+        self.alerts = alerts.to_vec();
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.alerts.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.alerts.clear();
+        self.seen.clear();
+    }
+
+    /// Banner text for one alert, e.g. "ORBIT DECAY - Rocket #3 - impact in 42s"
+    fn alert_text(world: &World, alert: &Alert) -> String {
+        let event = match alert.kind {
+            AlertKind::LowFuel => "empty",
+            AlertKind::Escaping => "lost",
+            AlertKind::OrbitDecay => "impact",
+            AlertKind::CollisionRisk => "hit",
+        };
+        let name = alert.subject.display_name(world);
+        match alert.time_to_event {
+            Some(seconds) => format!("{} - {} - {} in {:.0}s", alert.kind.label(), name, event, seconds.ceil()),
+            None => format!("{} - {}", alert.kind.label(), name),
+        }
+    }
+
+    fn alert_color(kind: AlertKind) -> Color {
+        match kind {
+            AlertKind::LowFuel => Color::new(1.0, 0.8, 0.2, 1.0),
+            AlertKind::Escaping => Color::new(0.75, 0.5, 1.0, 1.0),
+            AlertKind::OrbitDecay => Color::new(1.0, 0.5, 0.1, 1.0),
+            AlertKind::CollisionRisk => Color::new(1.0, 0.2, 0.2, 1.0),
+        }
+    }
+
+    /// Draw the banner at the top center of the screen (call in screen space)
+    pub fn draw(&self, world: &World) {
+        if self.alerts.is_empty() {
+            return;
+        }
+
+        let text_size = 22.0;
+        let line_height = 26.0;
+        let padding = 8.0;
+
+        let mut lines: Vec<(String, Color)> = self
+            .alerts
+            .iter()
+            .take(MAX_BANNER_LINES)
+            .map(|alert| (Self::alert_text(world, alert), Self::alert_color(alert.kind)))
+            .collect();
+        if self.alerts.len() > MAX_BANNER_LINES {
+            lines.push((format!("+{} more", self.alerts.len() - MAX_BANNER_LINES), LIGHTGRAY));
+        }
+
+        let width = lines
+            .iter()
+            .map(|(text, _)| measure_text(text, None, text_size as u16, 1.0).width)
+            .fold(0.0, f32::max)
+            + padding * 2.0;
+        let height = lines.len() as f32 * line_height + padding;
+        let Vec2 { x, y } = SafeArea::current().anchor(Anchor::TopCenter, Vec2::new(width, height), Vec2::ZERO);
+
+        // Border flashes in the most urgent alert's color
+        let flash_on = (self.flash_time * FLASH_RATE).fract() < 0.5;
+        let border = Self::alert_color(self.alerts[0].kind);
+        let background_alpha = if flash_on { 0.85 } else { 0.6 };
+
+        draw_rectangle(x, y, width, height, Color::new(0.25, 0.0, 0.0, background_alpha));
+        if flash_on {
+            draw_rectangle_lines(x, y, width, height, 3.0, border);
+        }
+        for (i, (text, color)) in lines.iter().enumerate() {
+            draw_text(
+                text,
+                x + padding,
+                y + padding / 2.0 + text_size * 0.8 + i as f32 * line_height,
+                text_size,
+                *color,
+            );
+        }
+    }
+
+    /// Build a short two-tone warning beep as a 16-bit mono WAV
+    pub fn warning_wav_bytes() -> Vec<u8> {
+        let sample_rate: u32 = 22050;
+        let sample_count = sample_rate * 2 / 5; // 0.4 seconds
+        let mut samples: Vec<u8> = Vec::with_capacity(sample_count as usize * 2);
+
+        for n in 0..sample_count {
+            let t = n as f32 / sample_rate as f32;
+            let tau = std::f32::consts::TAU;
+            // 880 Hz then 660 Hz, each with a short fade in/out to avoid clicks
+            let (frequency, local_t) = if t < 0.2 { (880.0, t) } else { (660.0, t - 0.2) };
+            let envelope = (local_t / 0.01).min(1.0) * ((0.2 - local_t) / 0.02).clamp(0.0, 1.0);
+            let value = envelope * (tau * frequency * t).sin();
+            let sample = (value * 0.5 * i16::MAX as f32) as i16;
+            samples.extend_from_slice(&sample.to_le_bytes());
+        }
+
+        let data_len = samples.len() as u32;
+        let mut wav = Vec::with_capacity(44 + samples.len());
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVE");
+        wav.extend_from_slice(b"fmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes()); // PCM chunk size
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM format
+        wav.extend_from_slice(&1u16.to_le_bytes()); // Mono
+        wav.extend_from_slice(&sample_rate.to_le_bytes());
+        wav.extend_from_slice(&(sample_rate * 2).to_le_bytes()); // Byte rate
+        wav.extend_from_slice(&2u16.to_le_bytes()); // Block align
+        wav.extend_from_slice(&16u16.to_le_bytes()); // Bits per sample
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        wav.extend_from_slice(&samples);
+        wav
+    }
+}
+
+impl Default for AlertBanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alert_text_includes_time_to_event() {
+        let world = World::new();
+        let alert = Alert {
+            kind: AlertKind::OrbitDecay,
+            subject: EntityTarget::Rocket(3),
+            time_to_event: Some(41.2),
+        };
+        assert_eq!(AlertBanner::alert_text(&world, &alert), "ORBIT DECAY - Rocket #3 - impact in 42s");
+    }
+
+    #[test]
+    fn test_warning_wav_header() {
+        let wav = AlertBanner::warning_wav_bytes();
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[8..12], b"WAVE");
+        assert_eq!(wav.len(), 44 + 8820 * 2);
+    }
+}
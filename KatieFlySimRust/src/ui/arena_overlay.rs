@@ -0,0 +1,49 @@
+// Arena Overlay - Draws the world across a wrapping arena's edges and outlines the arena
+// Near an edge the neighbouring copy is drawn too, so craft coming round the other side are visible before they wrap
+
+use macroquad::prelude::*;
+
+use crate::systems::World;
+use crate::ui::Camera;
+
+const EDGE_COLOR: Color = Color::new(1.0, 0.3, 0.3, 0.5);
+
+/// The world's entities plus, in arena mode, the copies seen past each edge (call with `camera` set)
+pub fn render_world(world: &World, camera: &Camera) {
+    world.render_at_zoom(camera.zoom_level());
+    let Some(arena) = world.arena() else {
+        return;
+    };
+
+    let top_left = camera.screen_to_world(Vec2::ZERO);
+    let bottom_right = camera.screen_to_world(Vec2::new(screen_width(), screen_height()));
+    let visible = Rect::new(top_left.x, top_left.y, bottom_right.x - top_left.x, bottom_right.y - top_left.y);
+    let origin = world.origin();
+    let copies = arena.visible_copies(&origin, visible);
+    for offset in &copies {
+        // Moving the camera back by the offset draws every entity shifted forward by it
+        let view = camera.camera();
+        set_camera(&Camera2D {
+            target: view.target - *offset,
+            zoom: view.zoom,
+            rotation: view.rotation,
+            offset: view.offset,
+            render_target: view.render_target.clone(),
+            viewport: view.viewport,
+        });
+        world.render_at_zoom(camera.zoom_level());
+    }
+    if !copies.is_empty() {
+        set_camera(camera.camera());
+    }
+    draw_outline(world, camera);
+}
+
+/// The arena's edges, if the world has one (call with `camera` set)
+pub fn draw_outline(world: &World, camera: &Camera) {
+    let Some(arena) = world.arena() else {
+        return;
+    };
+    let rect = arena.local_rect(&world.origin());
+    draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, (2.0 * camera.zoom_level()).max(2.0), EDGE_COLOR);
+}
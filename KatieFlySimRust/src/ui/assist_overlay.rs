@@ -0,0 +1,79 @@
+// Assist Overlay - Guidance for the gravity assist tutorial: the target's velocity, corridor gains and the flyby score
+// Shown while a scenario's current objective is a gravity assist; the last flyby's efficiency stays up afterwards
+
+use macroquad::prelude::*;
+
+use crate::systems::{AssistGuidance, FlybyTracker};
+use crate::ui::mission_clock_display::CLOCK_HEIGHT;
+use crate::ui::{Anchor, SafeArea};
+
+const ASSIST_COLOR: Color = Color::new(0.4, 1.0, 0.8, 1.0);
+/// The velocity arrow shows where the target will be this many seconds from now
+const ARROW_SECONDS: f32 = 30.0;
+const PANEL_WIDTH: f32 = 320.0;
+
+/// The target's velocity arrow and the predicted closest approach (call in world space)
+pub fn draw_world_guides(guidance: &AssistGuidance, zoom_level: f32) {
+    let thickness = (2.0 * zoom_level).max(1.5);
+    let start = guidance.target_position;
+    let end = start + guidance.target_velocity * ARROW_SECONDS;
+    draw_line(start.x, start.y, end.x, end.y, thickness, ASSIST_COLOR);
+    let back = -guidance.target_velocity.normalize_or_zero() * 12.0 * zoom_level.max(1.0);
+    for side in [0.5f32, -0.5] {
+        let barb = end + Vec2::from_angle(side).rotate(back);
+        draw_line(end.x, end.y, barb.x, barb.y, thickness, ASSIST_COLOR);
+    }
+
+    if let Some(predicted) = guidance.predicted {
+        let color = if predicted.altitude < 0.0 { RED } else { ASSIST_COLOR };
+        draw_circle_lines(predicted.position.x, predicted.position.y, 8.0 * zoom_level.max(1.0), thickness, color);
+    }
+}
+
+/// Corridor table, the current path's prediction and the live / last flyby efficiency (call in screen space)
+pub fn draw_assist_panel(guidance: Option<&AssistGuidance>, flyby: &FlybyTracker) {
+    let mut lines: Vec<(String, Color)> = Vec::new();
+    if let Some(guidance) = guidance {
+        lines.push((format!("GRAVITY ASSIST: {}", guidance.target_name), ASSIST_COLOR));
+        lines.push((format!("{} speed {:.0} (arrow)", guidance.target_name, guidance.target_velocity.length()), LIGHTGRAY));
+        if guidance.corridors.is_empty() {
+            lines.push(("Captured - no flyby from here".to_string(), ORANGE));
+        }
+        for corridor in &guidance.corridors {
+            let side = if corridor.behind { "behind" } else { "ahead " };
+            let color = if corridor.speed_gain >= 0.0 { GREEN } else { GRAY };
+            lines.push((format!("  alt {:>5.0} {}  {:+.0}", corridor.altitude, side, corridor.speed_gain), color));
+        }
+        match guidance.predicted {
+            Some(predicted) if predicted.altitude < 0.0 => {
+                lines.push((format!("Path: impact in {:.0}s", predicted.time), RED));
+            }
+            Some(predicted) => lines.push((
+                format!("Path: alt {:.0} in {:.0}s, {:+.0} speed", predicted.altitude, predicted.time, predicted.speed_gain),
+                WHITE,
+            )),
+            None => lines.push(("Path: no close pass predicted".to_string(), GRAY)),
+        }
+    }
+    if let Some((gain, best)) = flyby.in_progress() {
+        lines.push((format!("Flyby: {:+.0} so far (best {:+.0})", gain, best), YELLOW));
+    }
+    if let Some(result) = flyby.last() {
+        lines.push((
+            format!("Last flyby: {:+.0} at alt {:.0}, efficiency {:.0}%", result.speed_gain, result.periapsis_altitude, result.efficiency() * 100.0),
+            if result.efficiency() >= 0.5 { GREEN } else { ORANGE },
+        ));
+    }
+    if lines.is_empty() {
+        return;
+    }
+
+    let line_height = 20.0;
+    let size = Vec2::new(PANEL_WIDTH, lines.len() as f32 * line_height + 10.0);
+    let Vec2 { x, y } = SafeArea::current().anchor(Anchor::BottomRight, size, Vec2::new(0.0, CLOCK_HEIGHT + 6.0));
+    draw_rectangle(x, y, size.x, size.y, Color::new(0.0, 0.0, 0.0, 0.7));
+    draw_rectangle_lines(x, y, size.x, size.y, 1.0, ASSIST_COLOR);
+    for (i, (text, color)) in lines.iter().enumerate() {
+        draw_text(text, x + 8.0, y + line_height * (i as f32 + 1.0), 16.0, *color);
+    }
+}
@@ -0,0 +1,221 @@
+// Bullet Effects - Tracers, impact flashes and predicted arcs for live bullets in the main view
+// Everything is colored by the shooter's rocket; arcs are capped to the bullets nearest the view
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::GameObject;
+use crate::systems::bullet_threats::{predict_paths, BULLET_PREDICTION_DT};
+use crate::systems::{EntityId, World};
+
+/// Seconds between tracer samples
+const TRAIL_SAMPLE_INTERVAL: f32 = 0.04;
+/// Samples kept per tracer (about half a second of trail)
+const TRAIL_POINTS: usize = 12;
+/// A bullet vanishing with more life than this left hit something (less means it expired)
+const EXPIRY_GRACE: f32 = 0.5;
+/// How long an impact flash lasts (seconds)
+const FLASH_DURATION: f32 = 0.5;
+/// Flash radius at its largest (screen pixels)
+const FLASH_PIXELS: f32 = 18.0;
+/// How far ahead each predicted arc looks (seconds)
+const ARC_HORIZON: f32 = 3.0;
+
+/// Which bullet effects to draw (saved in the player profile)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BulletViewOptions {
+    pub tracers: bool,
+    pub impact_flashes: bool,
+    pub predicted_arcs: bool,
+    pub max_predicted_arcs: usize, // Only the bullets nearest the view center get an arc
+}
+
+impl Default for BulletViewOptions {
+    fn default() -> Self {
+        BulletViewOptions { tracers: true, impact_flashes: true, predicted_arcs: false, max_predicted_arcs: 16 }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Trail {
+    points: VecDeque<Vec2>, // Oldest first
+    color: Color,
+    life_left: f32,
+    sample_timer: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Flash {
+    position: Vec2,
+    color: Color,
+    age: f32,
+}
+
+/// Per-mode state for the bullet effects (update each frame, draw in world space)
+#[derive(Debug, Default)]
+pub struct BulletEffects {
+    options: BulletViewOptions,
+    trails: HashMap<EntityId, Trail>,
+    flashes: Vec<Flash>,
+    arcs: Vec<(Color, Vec<Vec2>)>,
+}
+
+impl BulletEffects {
+    pub fn new(options: BulletViewOptions) -> Self {
+        BulletEffects { options, ..Self::default() }
+    }
+
+    pub fn options(&self) -> BulletViewOptions {
+        self.options
+    }
+
+    pub fn set_options(&mut self, options: BulletViewOptions) {
+        self.options = options;
+        if !options.predicted_arcs {
+            self.arcs.clear();
+        }
+    }
+
+    /// Follow the bullets: extend tracers, flash where bullets vanished early, re-predict arcs
+    pub fn update(&mut self, world: &World, delta_time: f32, view_center: Vec2) {
+        let live: HashSet<EntityId> = world.bullets_with_ids().map(|(id, _)| id).collect();
+        let options = self.options;
+        let flashes = &mut self.flashes;
+        self.trails.retain(|id, trail| {
+            let vanished_early = !live.contains(id) && trail.life_left > EXPIRY_GRACE;
+            if vanished_early && options.impact_flashes {
+                if let Some(position) = trail.points.back() {
+                    flashes.push(Flash { position: *position, color: trail.color, age: 0.0 });
+                }
+            }
+            live.contains(id)
+        });
+
+        for (id, bullet) in world.bullets_with_ids() {
+            let trail = self.trails.entry(id).or_insert_with(|| Trail {
+                points: VecDeque::with_capacity(TRAIL_POINTS),
+                color: bullet.color(),
+                life_left: 0.0,
+                sample_timer: TRAIL_SAMPLE_INTERVAL,
+            });
+            trail.life_left = bullet.max_lifetime() - bullet.lifetime();
+            trail.sample_timer += delta_time;
+            if trail.sample_timer >= TRAIL_SAMPLE_INTERVAL {
+                trail.sample_timer = 0.0;
+                if trail.points.len() == TRAIL_POINTS {
+                    trail.points.pop_front();
+                }
+                trail.points.push_back(bullet.position());
+            }
+        }
+
+        for flash in &mut self.flashes {
+            flash.age += delta_time;
+        }
+        self.flashes.retain(|flash| flash.age < FLASH_DURATION);
+
+        self.arcs.clear();
+        if options.predicted_arcs && options.max_predicted_arcs > 0 {
+            let mut nearest: Vec<_> = world.bullets().collect();
+            nearest.sort_by(|a, b| a.position().distance(view_center).total_cmp(&b.position().distance(view_center)));
+            nearest.truncate(options.max_predicted_arcs);
+            let bodies: Vec<(Vec2, Vec2)> = nearest.iter().map(|bullet| (bullet.position(), bullet.velocity())).collect();
+            let steps = (ARC_HORIZON / BULLET_PREDICTION_DT).ceil() as usize;
+            let paths = predict_paths(world, &bodies, steps, BULLET_PREDICTION_DT);
+            self.arcs = nearest.iter().map(|bullet| bullet.color()).zip(paths).collect();
+        }
+    }
+
+    /// Follow a floating origin rebase (positions moved by `-shift`)
+    pub fn shift_positions(&mut self, shift: Vec2) {
+        for trail in self.trails.values_mut() {
+            for point in &mut trail.points {
+                *point -= shift;
+            }
+        }
+        for flash in &mut self.flashes {
+            flash.position -= shift;
+        }
+        for (_, path) in &mut self.arcs {
+            for point in path {
+                *point -= shift;
+            }
+        }
+    }
+
+    /// Forget every bullet (a new world was loaded; its bullets didn't hit anything)
+    pub fn clear(&mut self) {
+        self.trails.clear();
+        self.flashes.clear();
+        self.arcs.clear();
+    }
+
+    /// Arcs, tracers and flashes (call with the world camera set)
+    pub fn draw(&self, zoom_level: f32) {
+        for (color, path) in &self.arcs {
+            let segments = path.len().saturating_sub(1).max(1) as f32;
+            for (i, pair) in path.windows(2).enumerate() {
+                // Fade out toward the end of the horizon
+                let alpha = 0.5 * (1.0 - i as f32 / segments);
+                draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, 1.5 * zoom_level, Color::new(color.r, color.g, color.b, alpha));
+            }
+        }
+
+        if self.options.tracers {
+            for trail in self.trails.values() {
+                let segments = trail.points.len().saturating_sub(1).max(1) as f32;
+                for (i, (a, b)) in trail.points.iter().zip(trail.points.iter().skip(1)).enumerate() {
+                    let alpha = 0.8 * (i + 1) as f32 / segments;
+                    let color = Color::new(trail.color.r, trail.color.g, trail.color.b, alpha);
+                    draw_line(a.x, a.y, b.x, b.y, 2.0 * zoom_level.max(1.0), color);
+                }
+            }
+        }
+
+        for flash in &self.flashes {
+            let t = flash.age / FLASH_DURATION;
+            let radius = FLASH_PIXELS * zoom_level.max(1.0) * (0.3 + 0.7 * t);
+            let fade = 1.0 - t;
+            draw_circle(flash.position.x, flash.position.y, radius * 0.5, Color::new(1.0, 0.95, 0.8, 0.8 * fade));
+            draw_circle_lines(
+                flash.position.x,
+                flash.position.y,
+                radius,
+                2.0 * zoom_level.max(1.0),
+                Color::new(flash.color.r, flash.color.g, flash.color.b, fade),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::Bullet;
+
+    #[test]
+    fn test_early_vanish_flashes_and_arcs_are_capped() {
+        let mut world = World::new();
+        let hit_id = world.add_bullet(Bullet::new(Vec2::new(5000.0, 0.0), Vec2::new(0.0, 100.0)));
+        let mut expiring = Bullet::new(Vec2::new(6000.0, 0.0), Vec2::new(0.0, 100.0));
+        expiring.set_lifetime(expiring.max_lifetime() - 0.1);
+        let expiring_id = world.add_bullet(expiring);
+
+        let options = BulletViewOptions { predicted_arcs: true, max_predicted_arcs: 1, ..BulletViewOptions::default() };
+        let mut effects = BulletEffects::new(options);
+        effects.update(&world, 0.016, Vec2::new(5000.0, 0.0));
+        assert_eq!(effects.trails.len(), 2);
+        assert_eq!(effects.arcs.len(), 1);
+
+        // Only the bullet with life left counts as an impact
+        world.remove_bullet(hit_id);
+        world.remove_bullet(expiring_id);
+        effects.update(&world, 0.016, Vec2::ZERO);
+        assert!(effects.trails.is_empty());
+        assert_eq!(effects.flashes.len(), 1);
+        assert_eq!(effects.flashes[0].position, Vec2::new(5000.0, 0.0));
+    }
+}
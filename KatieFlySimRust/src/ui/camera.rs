@@ -1,17 +1,114 @@
 // Camera - View management with zoom and pan
 // Camera system for following entities and zooming
 
+use macroquad::camera::Camera as _; // Camera2D::matrix
 use macroquad::prelude::*;
 
+/// Zoom speed while a zoom key is held (natural log of the factor per second, ~12x)
+const ZOOM_HOLD_RATE: f32 = 2.5;
+/// Zoom factor per mouse wheel notch
+const ZOOM_WHEEL_STEP: f32 = 1.1;
+/// Empty space left around a zoom-to-fit view
+const FIT_MARGIN: f32 = 1.15;
+/// During a transition, how close (as a fraction of the view width) counts as arrived
+const TRANSITION_SETTLE: f32 = 0.05;
+/// Largest shake displacement (screen pixels, at full trauma)
+const SHAKE_MAX_OFFSET: f32 = 16.0;
+/// Trauma lost per second (a full-strength shake dies out in under a second)
+const SHAKE_DECAY: f32 = 1.4;
+/// Events this far from the view center (screen pixels) shake at half strength
+const SHAKE_FALLOFF: f32 = 500.0;
+/// Touchdowns this hard (m/s into the surface) give the strongest landing jolt
+const HARD_LANDING_SPEED: f32 = 120.0;
+
+/// Zoom levels on Shift+1..Shift+6, each 4x further out than the last
+pub const ZOOM_PRESETS: [f32; 6] = [1.0, 4.0, 16.0, 64.0, 256.0, 1024.0];
+/// Close-up zoom used when jumping back to the player's craft
+pub const CRAFT_VIEW_ZOOM: f32 = 1.0;
+
+/// Camera commands shared by the game modes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraCommand {
+    FitSystem,     // End: every body of the map
+    FocusCraft,    // Home: back to the player's craft at close-up zoom
+    Preset(usize), // Shift+1..Shift+6: index into ZOOM_PRESETS
+}
+
+impl CameraCommand {
+    /// The command pressed this frame, if any
+    pub fn from_keys() -> Option<Self> {
+        if is_key_pressed(KeyCode::End) {
+            return Some(CameraCommand::FitSystem);
+        }
+        if is_key_pressed(KeyCode::Home) {
+            return Some(CameraCommand::FocusCraft);
+        }
+        if !preset_modifier_down() {
+            return None;
+        }
+        const PRESET_KEYS: [KeyCode; 6] = [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4, KeyCode::Key5, KeyCode::Key6];
+        PRESET_KEYS.iter().position(|key| is_key_pressed(*key)).map(CameraCommand::Preset)
+    }
+}
+
+/// Shift turns the number row into zoom presets (modes skip their own number keys while it's held)
+pub fn preset_modifier_down() -> bool {
+    is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift)
+}
+
+/// Something that jolts the view, scaled by how much energy it releases
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CameraImpulse {
+    Landing { vertical_speed: f32 },
+    Explosion,
+    BulletImpact,
+    Staging, // A rocket separating into a satellite
+}
+
+impl CameraImpulse {
+    /// Trauma (0.0-1.0) added at the view center
+    fn strength(&self) -> f32 {
+        match *self {
+            CameraImpulse::Landing { vertical_speed } => 0.6 * (vertical_speed / HARD_LANDING_SPEED).clamp(0.0, 1.0),
+            CameraImpulse::Explosion => 0.9,
+            CameraImpulse::BulletImpact => 0.3,
+            CameraImpulse::Staging => 0.2,
+        }
+    }
+}
+
+/// How quickly the camera eases toward its targets, as exponential rates per second
+/// (higher settles faster; the motion is the same at any frame rate)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraSmoothing {
+    pub zoom_rate: f32,   // Zoom eases in log space, so 1x->2x feels like 1000x->2000x
+    pub follow_rate: f32, // Center catching up with the followed position
+}
+
+impl Default for CameraSmoothing {
+    fn default() -> Self {
+        CameraSmoothing { zoom_rate: 6.0, follow_rate: 40.0 }
+    }
+}
+
+/// Fraction of the remaining distance to cover this frame
+fn smoothing_factor(rate: f32, delta_time: f32) -> f32 {
+    1.0 - (-rate * delta_time.max(0.0)).exp()
+}
+
 /// Camera for managing the game view
 pub struct Camera {
     camera: Camera2D,
     zoom_level: f32,
     target_zoom: f32,
     target_center: Vec2,
-    zoom_speed: f32,
-    follow_smoothing: f32,
-    window_size: Vec2,
+    smoothing: CameraSmoothing,
+    transitioning: bool, // Instant moves ease too until the view arrives (camera commands)
+    window_size: Vec2,    // Size of the area drawn into (the viewport's, when split)
+    viewport: Option<Rect>, // Screen rect drawn into instead of the whole window (split-screen)
+    shake_enabled: bool, // Accessibility setting; impulses are ignored while off
+    trauma: f32,         // Current shake strength, 0.0-1.0 (displacement grows with its square)
+    shake_clock: f32,    // Drives the shake pattern
 }
 
 impl Camera {
@@ -32,9 +129,13 @@ impl Camera {
             zoom_level: 1.0,
             target_zoom: 1.0,
             target_center: center,
-            zoom_speed: 2.0,          // Reduced from 5.0 for slower zoom
-            follow_smoothing: 0.5,    // Increased from 0.1 for tighter following
+            smoothing: CameraSmoothing::default(),
+            transitioning: false,
             window_size,
+            viewport: None,
+            shake_enabled: true,
+            trauma: 0.0,
+            shake_clock: 0.0,
         }
     }
 
@@ -43,27 +144,131 @@ impl Camera {
         // Smooth zoom - use relative threshold since zoom can vary widely
         let threshold = self.target_zoom * 0.001; // 0.1% of target zoom
         if (self.zoom_level - self.target_zoom).abs() > threshold {
-            let zoom_delta = (self.target_zoom - self.zoom_level) * self.zoom_speed * delta_time;
-            self.zoom_level += zoom_delta;
-
-            // Update camera zoom
-            let zoom_scale = 1.0 / self.zoom_level;
-            self.camera.zoom = vec2(
-                zoom_scale / self.window_size.x * 2.0,
-                -zoom_scale / self.window_size.y * 2.0,
-            );
+            let t = smoothing_factor(self.smoothing.zoom_rate, delta_time);
+            let log_zoom = self.zoom_level.ln();
+            self.zoom_level = (log_zoom + (self.target_zoom.ln() - log_zoom) * t).exp();
+        } else {
+            self.zoom_level = self.target_zoom;
         }
+        self.apply_zoom();
 
         // Smooth follow
-        let current_center = self.camera.target;
-        let center_delta = self.target_center - current_center;
-
+        let center_delta = self.target_center - self.camera.target;
         if center_delta.x.abs() > 0.1 || center_delta.y.abs() > 0.1 {
-            let smooth_delta = center_delta * self.follow_smoothing;
-            self.camera.target = current_center + smooth_delta;
+            self.camera.target += center_delta * smoothing_factor(self.smoothing.follow_rate, delta_time);
+        } else {
+            self.camera.target = self.target_center;
+        }
+
+        self.update_shake(delta_time);
+
+        if self.transitioning {
+            let settle_distance = self.window_size.x * self.zoom_level * TRANSITION_SETTLE;
+            if self.zoom_level == self.target_zoom && self.camera.target.distance(self.target_center) < settle_distance {
+                self.transitioning = false;
+            }
+        }
+    }
+
+    /// Push the zoom level into the macroquad camera
+    fn apply_zoom(&mut self) {
+        let zoom_scale = 1.0 / self.zoom_level;
+        self.camera.zoom = vec2(
+            zoom_scale / self.window_size.x * 2.0,
+            -zoom_scale / self.window_size.y * 2.0,
+        );
+    }
+
+    /// Decay the shake and offset the view by it (in clip space, so it's the same size at any zoom)
+    fn update_shake(&mut self, delta_time: f32) {
+        self.trauma = (self.trauma - SHAKE_DECAY * delta_time.max(0.0)).max(0.0);
+        self.shake_clock += delta_time.max(0.0);
+        let offset = self.shake_offset();
+        self.camera.offset = vec2(offset.x / self.window_size.x * 2.0, offset.y / self.window_size.y * 2.0);
+    }
+
+    /// Current shake displacement in screen pixels
+    fn shake_offset(&self) -> Vec2 {
+        if self.trauma <= 0.0 {
+            return Vec2::ZERO;
+        }
+        // Layered sines at unrelated frequencies read as noise without needing a random source
+        let t = self.shake_clock;
+        let wobble = Vec2::new((t * 47.0).sin() + 0.5 * (t * 83.0).sin(), (t * 53.0).cos() + 0.5 * (t * 71.0).sin()) / 1.5;
+        wobble * SHAKE_MAX_OFFSET * self.trauma * self.trauma
+    }
+
+    /// Shake the view for an event at `position` (weaker the further it is from the view center)
+    pub fn impulse(&mut self, impulse: CameraImpulse, position: Vec2) {
+        let screen_distance = (position - self.camera.target).length() / self.zoom_level;
+        let falloff = 1.0 / (1.0 + (screen_distance / SHAKE_FALLOFF).powi(2));
+        self.add_trauma(impulse.strength() * falloff);
+    }
+
+    /// Add shake directly (0.0-1.0; stacks up to full strength)
+    pub fn add_trauma(&mut self, amount: f32) {
+        if self.shake_enabled {
+            self.trauma = (self.trauma + amount.max(0.0)).min(1.0);
+        }
+    }
+
+    pub fn shake_enabled(&self) -> bool {
+        self.shake_enabled
+    }
+
+    /// Turn screen shake on or off (turning it off stops any shake in progress)
+    pub fn set_shake_enabled(&mut self, enabled: bool) {
+        self.shake_enabled = enabled;
+        if !enabled {
+            self.trauma = 0.0;
+            self.camera.offset = Vec2::ZERO;
+        }
+    }
+
+    /// Current smoothing rates
+    pub fn smoothing(&self) -> CameraSmoothing {
+        self.smoothing
+    }
+
+    /// Change how quickly zoom and follow ease toward their targets
+    pub fn set_smoothing(&mut self, smoothing: CameraSmoothing) {
+        self.smoothing = smoothing;
+    }
+
+    /// Jump straight to the target zoom and center (use when switching what the camera shows)
+    pub fn snap_to_target(&mut self) {
+        self.zoom_level = self.target_zoom;
+        self.camera.target = self.target_center;
+        self.transitioning = false;
+        self.apply_zoom();
+    }
+
+    /// Follow a new position without sweeping across the map to reach it (eases instead during a transition)
+    pub fn snap_to(&mut self, center: Vec2) {
+        self.target_center = center;
+        if !self.transitioning {
+            self.camera.target = center;
         }
     }
 
+    /// Ease the next target switch instead of jumping, until the view arrives
+    pub fn begin_transition(&mut self) {
+        self.transitioning = true;
+    }
+
+    /// Whether a transition is still easing toward its target
+    pub fn is_transitioning(&self) -> bool {
+        self.transitioning
+    }
+
+    /// Ease to show a circle (e.g. a whole planet system) with a margin around it
+    pub fn zoom_to_fit(&mut self, center: Vec2, radius: f32) {
+        let diameter = radius * 2.0 * FIT_MARGIN;
+        self.set_target_zoom(diameter / self.window_size.x.min(self.window_size.y));
+        self.target_center = center;
+        self.begin_transition();
+    }
+
     /// Set target zoom level
     pub fn set_target_zoom(&mut self, zoom: f32) {
         self.target_zoom = zoom.max(0.1).min(2000000.0); // Clamp zoom (0.1 = very close, 2000000.0 = entire solar system)
@@ -74,10 +279,19 @@ impl Camera {
         self.set_target_zoom(self.target_zoom + delta);
     }
 
-    /// Set center position (instant)
+    /// Zoom while a key is held: `direction` > 0 zooms out, < 0 zooms in, at the same speed at any frame rate
+    pub fn zoom_held(&mut self, direction: f32, delta_time: f32) {
+        self.set_target_zoom(self.target_zoom * (ZOOM_HOLD_RATE * direction * delta_time).exp());
+    }
+
+    /// Zoom by mouse wheel notches (positive = wheel up = zoom in), 10% per notch
+    pub fn zoom_wheel(&mut self, notches: f32) {
+        self.set_target_zoom(self.target_zoom * ZOOM_WHEEL_STEP.powf(-notches));
+    }
+
+    /// Set center position (instant, unless a transition is easing)
     pub fn set_center(&mut self, center: Vec2) {
-        self.target_center = center;
-        self.camera.target = center;
+        self.snap_to(center);
     }
 
     /// Set target center (smooth follow)
@@ -90,6 +304,12 @@ impl Camera {
         self.set_target_center(position);
     }
 
+    /// Follow a floating origin rebase (positions moved by `-shift`)
+    pub fn shift_origin(&mut self, shift: Vec2) {
+        self.camera.target -= shift;
+        self.target_center -= shift;
+    }
+
     /// Get the current camera (for use with set_camera())
     pub fn camera(&self) -> &Camera2D {
         &self.camera
@@ -108,24 +328,30 @@ impl Camera {
         self.target_center = center;
         self.camera.target = center;
         self.window_size = window_size;
-
-        let zoom_scale = 1.0 / self.zoom_level;
-        self.camera.zoom = vec2(
-            zoom_scale / window_size.x * 2.0,
-            -zoom_scale / window_size.y * 2.0,
-        );
+        self.apply_zoom();
         self.camera.render_target = None;
         self.camera.viewport = None;
+        self.viewport = None;
+        self.trauma = 0.0;
+        self.camera.offset = Vec2::ZERO;
     }
 
     /// Handle window resize
     pub fn handle_resize(&mut self, new_size: Vec2) {
         self.window_size = new_size;
-        let zoom_scale = 1.0 / self.zoom_level;
-        self.camera.zoom = vec2(
-            zoom_scale / new_size.x * 2.0,
-            -zoom_scale / new_size.y * 2.0,
-        );
+        self.apply_zoom();
+    }
+
+    /// Draw into `viewport` (screen pixels) of a `screen_size` window, or the whole window for None
+    pub fn set_viewport(&mut self, viewport: Option<Rect>, screen_size: Vec2) {
+        self.viewport = viewport;
+        // GL viewports count up from the bottom of the window
+        self.camera.viewport = viewport.map(|rect| (rect.x as i32, (screen_size.y - rect.bottom()) as i32, rect.w as i32, rect.h as i32));
+        self.handle_resize(viewport.map_or(screen_size, |rect| rect.size()));
+    }
+
+    pub fn viewport(&self) -> Option<Rect> {
+        self.viewport
     }
 
     /// Convert screen coordinates to world coordinates
@@ -136,8 +362,13 @@ impl Camera {
 
     /// Convert world coordinates to screen coordinates
     pub fn world_to_screen(&self, world_pos: Vec2) -> Vec2 {
-        // Use macroquad's camera to convert world to screen coordinates
-        self.camera.world_to_screen(world_pos)
+        let Some(rect) = self.viewport else {
+            // Use macroquad's camera to convert world to screen coordinates
+            return self.camera.world_to_screen(world_pos);
+        };
+        // macroquad maps onto the whole window whatever the viewport, so place it in ours
+        let clip = self.camera.matrix().transform_point3(vec3(world_pos.x, world_pos.y, 0.0));
+        Vec2::new(rect.x + (clip.x / 2.0 + 0.5) * rect.w, rect.y + (0.5 - clip.y / 2.0) * rect.h)
     }
 
     /// Set camera position (instant, no smoothing)
@@ -180,4 +411,107 @@ mod tests {
         camera.follow(target_pos);
         assert_eq!(camera.target_center, target_pos);
     }
+
+    #[test]
+    fn test_smoothing_is_frame_rate_independent() {
+        let run = |fps: u32| {
+            let mut camera = Camera::new(Vec2::new(1920.0, 1080.0));
+            camera.set_target_zoom(1000.0);
+            camera.follow(Vec2::new(5000.0, 0.0));
+            for _ in 0..fps / 4 {
+                camera.update(1.0 / fps as f32);
+            }
+            (camera.zoom_level(), camera.camera().target.x)
+        };
+        let (zoom_32, x_32) = run(32);
+        let (zoom_240, x_240) = run(240);
+        assert!(zoom_32 > 1.0 && zoom_32 < 1000.0);
+        assert!((zoom_32 / zoom_240 - 1.0).abs() < 0.01);
+        assert!((x_32 - x_240).abs() < 1.0);
+
+        // Held zoom keys cover the same ground per second too
+        let mut slow = Camera::new(Vec2::new(1920.0, 1080.0));
+        let mut fast = Camera::new(Vec2::new(1920.0, 1080.0));
+        for _ in 0..30 {
+            slow.zoom_held(1.0, 1.0 / 30.0);
+        }
+        for _ in 0..240 {
+            fast.zoom_held(1.0, 1.0 / 240.0);
+        }
+        assert!((slow.target_zoom / fast.target_zoom - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_viewport_maps_the_view_center_to_its_middle() {
+        let mut camera = Camera::new(Vec2::new(1600.0, 900.0));
+        camera.set_viewport(Some(Rect::new(800.0, 450.0, 800.0, 450.0)), Vec2::new(1600.0, 900.0));
+        assert_eq!(camera.camera().viewport, Some((800, 0, 800, 450)));
+        camera.snap_to(Vec2::new(5000.0, -200.0));
+        assert!(camera.world_to_screen(Vec2::new(5000.0, -200.0)).distance(Vec2::new(1200.0, 675.0)) < 0.01);
+        // At zoom 1 a world unit is a pixel across the viewport
+        assert!(camera.world_to_screen(Vec2::new(5100.0, -200.0)).distance(Vec2::new(1300.0, 675.0)) < 0.01);
+    }
+
+    #[test]
+    fn test_snap_to_target() {
+        let mut camera = Camera::new(Vec2::new(1920.0, 1080.0));
+        camera.set_target_zoom(50.0);
+        camera.snap_to(Vec2::new(-300.0, 42.0));
+        assert_eq!(camera.camera().target, Vec2::new(-300.0, 42.0));
+        assert_eq!(camera.zoom_level(), 1.0); // Zoom still easing
+
+        camera.snap_to_target();
+        assert_eq!(camera.zoom_level(), 50.0);
+        camera.update(1.0 / 60.0);
+        assert_eq!(camera.camera().target, Vec2::new(-300.0, 42.0));
+    }
+
+    #[test]
+    fn test_zoom_to_fit_eases_then_settles() {
+        let mut camera = Camera::new(Vec2::new(1000.0, 800.0));
+        camera.zoom_to_fit(Vec2::new(40000.0, 0.0), 20000.0);
+        assert!((camera.target_zoom - 40000.0 * FIT_MARGIN / 800.0).abs() < 0.01);
+
+        // Following the rocket doesn't cut the transition short
+        camera.set_center(Vec2::ZERO);
+        assert!(camera.is_transitioning());
+        camera.update(1.0 / 60.0);
+        assert!(camera.camera().target.x > 0.0 && camera.zoom_level() > 1.0);
+
+        for _ in 0..600 {
+            camera.update(1.0 / 60.0);
+        }
+        assert!(!camera.is_transitioning());
+        camera.set_center(Vec2::new(10.0, 10.0));
+        assert_eq!(camera.camera().target, Vec2::new(10.0, 10.0));
+    }
+
+    #[test]
+    fn test_shake_scales_decays_and_can_be_disabled() {
+        let mut camera = Camera::new(Vec2::new(1000.0, 800.0));
+        camera.snap_to(Vec2::ZERO);
+
+        // A nearby explosion shakes harder than a distant one or a soft landing
+        camera.impulse(CameraImpulse::Explosion, Vec2::ZERO);
+        let near = camera.trauma;
+        camera.trauma = 0.0;
+        camera.impulse(CameraImpulse::Explosion, Vec2::new(5000.0, 0.0));
+        assert!(camera.trauma < near * 0.05);
+        camera.trauma = 0.0;
+        camera.impulse(CameraImpulse::Landing { vertical_speed: 10.0 }, Vec2::ZERO);
+        assert!(camera.trauma < CameraImpulse::Landing { vertical_speed: 200.0 }.strength());
+
+        camera.add_trauma(1.0);
+        camera.update(1.0 / 60.0);
+        assert!(camera.camera().offset != Vec2::ZERO);
+        for _ in 0..60 {
+            camera.update(1.0 / 60.0);
+        }
+        assert_eq!(camera.trauma, 0.0);
+        assert_eq!(camera.camera().offset, Vec2::ZERO);
+
+        camera.set_shake_enabled(false);
+        camera.impulse(CameraImpulse::Explosion, Vec2::ZERO);
+        assert_eq!(camera.trauma, 0.0);
+    }
 }
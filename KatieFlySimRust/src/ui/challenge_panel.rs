@@ -0,0 +1,89 @@
+// Challenge Panel - Daily challenge and scenario objectives, clock and result
+// Sits at the bottom center; the daily share string is shown once the attempt is scored
+
+use macroquad::prelude::*;
+
+use crate::systems::mission_clock;
+use crate::systems::{ChallengeOutcome, DailyChallenge, ScenarioOutcome, ScenarioRun};
+use crate::ui::{Anchor, SafeArea};
+
+const CHALLENGE_COLOR: Color = Color::new(1.0, 0.8, 0.3, 1.0);
+const SCENARIO_COLOR: Color = Color::new(0.8, 0.6, 1.0, 1.0);
+
+/// Objective, time and fuel while the attempt runs; score (or failure) once it ends
+pub fn draw_challenge_panel(challenge: &DailyChallenge, elapsed: f32, fuel: f32, outcome: Option<&ChallengeOutcome>, best: Option<u32>) {
+    let mut lines: Vec<(String, Color)> = vec![
+        (format!("DAILY CHALLENGE {}", challenge.date.label()), CHALLENGE_COLOR),
+        (challenge.description(), WHITE),
+    ];
+    match outcome {
+        None => lines.push((format!("T+{}   Fuel {:.1} / {:.1}", mission_clock::format_clock(elapsed), fuel, challenge.starting_fuel), LIGHTGRAY)),
+        Some(ChallengeOutcome::Scored { score, share }) => {
+            lines.push((format!("Score: {} pts (copied to clipboard)", score), GREEN));
+            lines.push((share.clone(), LIGHTGRAY));
+        }
+        Some(ChallengeOutcome::Failed(reason)) => {
+            lines.push((format!("Attempt failed: {}", reason), RED));
+        }
+    }
+    if let Some(best) = best {
+        lines.push((format!("Today's best: {} pts", best), LIGHTGRAY));
+    }
+    draw_panel(&lines, CHALLENGE_COLOR);
+}
+
+/// Scenario objectives with progress, the clock against par, and the result once it ends
+pub fn draw_scenario_panel(run: &ScenarioRun, best: Option<f32>) {
+    let scenario = run.scenario();
+    let mut lines: Vec<(String, Color)> = vec![(format!("SCENARIO: {}", scenario.name), SCENARIO_COLOR)];
+    for (i, objective) in scenario.objectives.iter().enumerate() {
+        let line = if i < run.completed() {
+            (format!("[x] {}", objective.describe()), GREEN)
+        } else if i == run.completed() && run.is_running() {
+            let hold = if run.hold_time() > 0.0 { format!(" ({:.0}s held)", run.hold_time()) } else { String::new() };
+            (format!("> {}{}", objective.describe(), hold), WHITE)
+        } else {
+            (format!("[ ] {}", objective.describe()), GRAY)
+        };
+        lines.push(line);
+    }
+
+    let par = scenario.par_time.map(|par| format!("   Par {}", mission_clock::format_clock(par))).unwrap_or_default();
+    let limit = scenario.constraints.time_limit.map(|limit| format!("   Limit {}", mission_clock::format_clock(limit))).unwrap_or_default();
+    lines.push((format!("T+{}{}{}", mission_clock::format_clock(run.elapsed()), par, limit), LIGHTGRAY));
+    match run.outcome() {
+        Some(ScenarioOutcome::Complete { time, under_par }) => {
+            let verdict = match under_par {
+                Some(true) => " - under par!",
+                Some(false) => " - over par",
+                None => "",
+            };
+            lines.push((format!("Complete in {}{}", mission_clock::format_clock(*time), verdict), GREEN));
+        }
+        Some(ScenarioOutcome::Failed(reason)) => lines.push((format!("Failed: {}", reason), RED)),
+        None => {}
+    }
+    if let Some(best) = best {
+        lines.push((format!("Best: {}", mission_clock::format_clock(best)), LIGHTGRAY));
+    }
+    draw_panel(&lines, SCENARIO_COLOR);
+}
+
+fn draw_panel(lines: &[(String, Color)], border: Color) {
+    let text_size = 18.0;
+    let line_height = 22.0;
+    let padding = 10.0;
+    let width = lines
+        .iter()
+        .map(|(text, _)| measure_text(text, None, text_size as u16, 1.0).width)
+        .fold(0.0, f32::max)
+        + padding * 2.0;
+    let height = lines.len() as f32 * line_height + padding;
+    let Vec2 { x, y } = SafeArea::current().anchor(Anchor::BottomCenter, Vec2::new(width, height), Vec2::ZERO);
+
+    draw_rectangle(x, y, width, height, Color::new(0.0, 0.0, 0.0, 0.7));
+    draw_rectangle_lines(x, y, width, height, 1.0, border);
+    for (i, (text, color)) in lines.iter().enumerate() {
+        draw_text(text, x + padding, y + line_height * (i as f32 + 1.0), text_size, *color);
+    }
+}
@@ -0,0 +1,128 @@
+// Chat Box - Multiplayer chat: T opens a text box, Enter sends, and recent lines fade out above it
+// The game mode sends what's typed and pushes every line it receives into the log
+
+use std::collections::VecDeque;
+
+use macroquad::prelude::*;
+
+use crate::systems::chat::{ChatMessage, MAX_CHAT_LENGTH};
+use crate::ui::{Anchor, SafeArea};
+
+/// Lines kept in the log
+const LOG_LENGTH: usize = 8;
+/// Seconds a line stays up while the box is closed
+const LINE_DURATION: f32 = 10.0;
+/// Fade-out time at the end of a line's life (seconds)
+const LINE_FADE_TIME: f32 = 1.5;
+const LINE_HEIGHT: f32 = 20.0;
+const BOX_SIZE: Vec2 = Vec2::new(460.0, 26.0);
+
+struct ChatLine {
+    sender: String,
+    text: String,
+    color: Color, // Sender's player color
+    age: f32,
+}
+
+/// Chat input box and message log
+#[derive(Default)]
+pub struct ChatBox {
+    typing: bool,
+    text: String,
+    log: VecDeque<ChatLine>,
+}
+
+impl ChatBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The box has the keyboard (the game mode should skip its own keys)
+    pub fn is_typing(&self) -> bool {
+        self.typing
+    }
+
+    /// T opens the box; while open, type, Enter returns the message to send and ESC cancels
+    pub fn handle_input(&mut self) -> Option<String> {
+        if !self.typing {
+            if is_key_pressed(KeyCode::T) && !is_key_down(KeyCode::LeftShift) && !is_key_down(KeyCode::RightShift) {
+                while get_char_pressed().is_some() {} // Drop the 't' that opened us
+                self.typing = true;
+            }
+            return None;
+        }
+
+        if is_key_pressed(KeyCode::Escape) {
+            self.close();
+            return None;
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            self.text.pop();
+        }
+
+        // The char queue pops the most recent character first
+        let mut typed = Vec::new();
+        while let Some(ch) = get_char_pressed() {
+            typed.push(ch);
+        }
+        for ch in typed.into_iter().rev() {
+            if !ch.is_control() && self.text.chars().count() < MAX_CHAT_LENGTH {
+                self.text.push(ch);
+            }
+        }
+
+        if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::KpEnter) {
+            let text = ChatMessage::clean(&self.text);
+            self.close();
+            return (!text.is_empty()).then_some(text);
+        }
+        None
+    }
+
+    fn close(&mut self) {
+        self.typing = false;
+        self.text.clear();
+    }
+
+    /// Add a line to the log
+    pub fn push(&mut self, sender: impl Into<String>, text: impl Into<String>, color: Color) {
+        if self.log.len() == LOG_LENGTH {
+            self.log.pop_front();
+        }
+        self.log.push_back(ChatLine { sender: sender.into(), text: text.into(), color, age: 0.0 });
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        for line in &mut self.log {
+            line.age += delta_time;
+        }
+    }
+
+    /// Recent lines above the input box, bottom left (call in screen space)
+    pub fn draw(&self) {
+        let Vec2 { x, y } = SafeArea::current().anchor(Anchor::BottomLeft, BOX_SIZE, Vec2::new(10.0, 230.0));
+
+        if self.typing {
+            draw_rectangle(x, y, BOX_SIZE.x, BOX_SIZE.y, Color::new(0.0, 0.0, 0.0, 0.75));
+            draw_rectangle_lines(x, y, BOX_SIZE.x, BOX_SIZE.y, 1.5, SKYBLUE);
+            let cursor = if (get_time() * 2.0) as i64 % 2 == 0 { "_" } else { "" };
+            draw_text(&format!("Say: {}{}", self.text, cursor), x + 8.0, y + 18.0, 18.0, WHITE);
+        }
+
+        let mut line_y = y - 8.0;
+        for line in self.log.iter().rev() {
+            // Everything stays readable while typing; otherwise old lines fade out
+            let alpha = if self.typing { 1.0 } else { ((LINE_DURATION - line.age) / LINE_FADE_TIME).clamp(0.0, 1.0) };
+            if alpha <= 0.0 {
+                continue;
+            }
+            let label = format!("{}: ", line.sender);
+            let label_width = measure_text(&label, None, 18, 1.0).width;
+            let text_width = measure_text(&line.text, None, 18, 1.0).width;
+            draw_rectangle(x, line_y - 15.0, label_width + text_width + 12.0, LINE_HEIGHT, Color::new(0.0, 0.0, 0.0, 0.5 * alpha));
+            draw_text(&label, x + 6.0, line_y, 18.0, Color::new(line.color.r, line.color.g, line.color.b, alpha));
+            draw_text(&line.text, x + 6.0 + label_width, line_y, 18.0, Color::new(1.0, 1.0, 1.0, alpha));
+            line_y -= LINE_HEIGHT;
+        }
+    }
+}
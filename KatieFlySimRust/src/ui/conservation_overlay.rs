@@ -0,0 +1,66 @@
+// Conservation Overlay - Physics debug graph of energy and momentum drift since the overlay opened
+// Toggled with F8 in single player; thrust, shots, landings and collisions all show up as steps
+
+use macroquad::prelude::*;
+
+use crate::physics::gravity_simulator::GravityModel;
+use crate::systems::conservation::CONSERVATION_HISTORY_LEN;
+use crate::systems::ConservationMonitor;
+use crate::ui::mission_clock_display::CLOCK_HEIGHT;
+use crate::ui::{Anchor, SafeArea};
+
+const ENERGY_COLOR: Color = Color::new(1.0, 0.75, 0.2, 0.9);
+const MOMENTUM_COLOR: Color = Color::new(0.4, 0.8, 1.0, 0.9);
+const GRAPH_SIZE: Vec2 = Vec2::new(320.0, 170.0);
+/// The energy plot never zooms in past this relative drift, so rounding noise reads as flat
+const MIN_ENERGY_SCALE: f64 = 1.0e-6;
+
+/// Energy drift (signed, around the middle line) and momentum drift (from the bottom), above the mission clock
+/// (call in screen space)
+pub fn draw(monitor: &ConservationMonitor, model: GravityModel) {
+    let Vec2 { x, y } = SafeArea::current().anchor(Anchor::BottomRight, GRAPH_SIZE, Vec2::new(0.0, CLOCK_HEIGHT + 6.0));
+    draw_rectangle(x, y, GRAPH_SIZE.x, GRAPH_SIZE.y, Color::new(0.0, 0.0, 0.0, 0.75));
+    draw_rectangle_lines(x, y, GRAPH_SIZE.x, GRAPH_SIZE.y, 1.0, ENERGY_COLOR);
+
+    let title = match model {
+        GravityModel::Realistic => "CONSERVATION".to_string(),
+        other => format!("CONSERVATION ({} gravity)", other.name()),
+    };
+    draw_text(&title, x + 8.0, y + 18.0, 16.0, WHITE);
+
+    let energy: Vec<f64> = monitor.energy_drift().collect();
+    let momentum: Vec<f64> = monitor.momentum_drift().collect();
+    let (Some(latest_energy), Some(latest_momentum), Some(sample)) = (energy.last(), momentum.last(), monitor.latest()) else {
+        draw_text("Waiting for a tick", x + 8.0, y + 36.0, 15.0, LIGHTGRAY);
+        return;
+    };
+    draw_text(&format!("E drift {:+.5}%  (E {:.4e})", latest_energy * 100.0, sample.energy()), x + 8.0, y + 36.0, 15.0, ENERGY_COLOR);
+    draw_text(&format!("|dp| {:.3e}  (|p| {:.4e})", latest_momentum, sample.momentum.length()), x + 8.0, y + 52.0, 15.0, MOMENTUM_COLOR);
+
+    // Plot area below the text; newest sample on the right
+    let plot = Rect::new(x + 8.0, y + 60.0, GRAPH_SIZE.x - 16.0, GRAPH_SIZE.y - 68.0);
+    let middle = plot.y + plot.h / 2.0;
+    draw_line(plot.x, middle, plot.right(), middle, 1.0, DARKGRAY);
+    draw_line(plot.x, plot.bottom(), plot.right(), plot.bottom(), 1.0, DARKGRAY);
+    let step = plot.w / (CONSERVATION_HISTORY_LEN - 1) as f32;
+    let slot_x = |i: usize, count: usize| plot.x + (CONSERVATION_HISTORY_LEN - count + i) as f32 * step;
+
+    let energy_scale = energy.iter().fold(MIN_ENERGY_SCALE, |max, drift| max.max(drift.abs()));
+    let energy_points: Vec<Vec2> = energy
+        .iter()
+        .enumerate()
+        .map(|(i, drift)| Vec2::new(slot_x(i, energy.len()), middle - (drift / energy_scale) as f32 * plot.h / 2.0))
+        .collect();
+    let momentum_scale = momentum.iter().fold(f64::MIN_POSITIVE, |max, drift| max.max(*drift));
+    let momentum_points: Vec<Vec2> = momentum
+        .iter()
+        .enumerate()
+        .map(|(i, drift)| Vec2::new(slot_x(i, momentum.len()), plot.bottom() - (drift / momentum_scale) as f32 * plot.h))
+        .collect();
+    for (points, color) in [(&momentum_points, MOMENTUM_COLOR), (&energy_points, ENERGY_COLOR)] {
+        for pair in points.windows(2) {
+            draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, 1.5, color);
+        }
+    }
+    draw_text(&format!("+/-{:.1e}%", energy_scale * 100.0), plot.x + 2.0, plot.y + 10.0, 12.0, GRAY);
+}
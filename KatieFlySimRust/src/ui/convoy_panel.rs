@@ -0,0 +1,56 @@
+// Convoy Panel - Tanker runs: where each tanker is, trips flown and the link to its satellite
+// Links are drawn in the main view; the list sits bottom-left while any tanker is assigned
+
+use macroquad::prelude::*;
+
+use crate::systems::{Convoy, TankerLeg, World};
+use crate::ui::{Anchor, Camera, SafeArea};
+
+const LINK_COLOR: Color = Color::new(0.4, 0.8, 1.0, 0.5);
+const UNLOADING_COLOR: Color = Color::new(0.4, 0.8, 1.0, 1.0);
+
+/// Faint line from each tanker to its satellite (call in screen space)
+pub fn draw_tanker_links(convoy: &Convoy, world: &World, camera: &Camera) {
+    for route in convoy.routes() {
+        let (Some(tanker), Some(satellite)) = (world.get_rocket(route.tanker), world.get_satellite(route.target)) else {
+            continue;
+        };
+        let (from, to) = (camera.world_to_screen(tanker.position()), camera.world_to_screen(satellite.position()));
+        let color = if route.leg == TankerLeg::Unloading { UNLOADING_COLOR } else { LINK_COLOR };
+        draw_line(from.x, from.y, to.x, to.y, 1.0, color);
+        draw_text("TANKER", from.x + 10.0, from.y + 4.0, 14.0, color);
+    }
+}
+
+/// One line per tanker in the bottom-left corner
+pub fn draw_convoy_panel(convoy: &Convoy, world: &World) {
+    if convoy.is_empty() {
+        return;
+    }
+    let mut lines = vec![("CONVOY (N on a tanker releases it)".to_string(), WHITE)];
+    for route in convoy.routes() {
+        let fuel = world.get_rocket(route.tanker).map_or(0.0, |rocket| rocket.current_fuel());
+        lines.push((
+            format!(
+                "Tanker {} -> Sat {}: {:<10} fuel {:>3.0}  {} trips",
+                route.tanker,
+                route.target,
+                route.leg.label(),
+                fuel,
+                route.trips
+            ),
+            if route.leg == TankerLeg::Unloading { UNLOADING_COLOR } else { LIGHTGRAY },
+        ));
+    }
+
+    let width = 360.0;
+    let line_height = 18.0;
+    let height = lines.len() as f32 * line_height + 12.0;
+    // Clear of the sample and rover status lines
+    let Vec2 { x, y } = SafeArea::current().anchor(Anchor::BottomLeft, Vec2::new(width, height), Vec2::new(0.0, 50.0));
+    draw_rectangle(x, y, width, height, Color::new(0.0, 0.0, 0.0, 0.7));
+    draw_rectangle_lines(x, y, width, height, 1.0, LINK_COLOR);
+    for (i, (text, color)) in lines.iter().enumerate() {
+        draw_text(text, x + 8.0, y + 20.0 + i as f32 * line_height, 15.0, *color);
+    }
+}
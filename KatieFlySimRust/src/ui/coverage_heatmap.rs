@@ -0,0 +1,54 @@
+// Coverage Heatmap - Colors the orbital shells around a planet by fuel-transfer coverage (8)
+// Red cells are gaps no satellite with spare fuel can reach; yellow has one supplier, green two or more
+
+use macroquad::prelude::*;
+
+use crate::systems::{CoverageMap, World};
+use crate::ui::{Anchor, SafeArea};
+
+const GAP_COLOR: Color = Color::new(1.0, 0.2, 0.2, 0.18);
+const SINGLE_COLOR: Color = Color::new(1.0, 0.85, 0.2, 0.22);
+const MULTI_COLOR: Color = Color::new(0.2, 1.0, 0.4, 0.25);
+
+fn cell_color(count: u8) -> Color {
+    match count {
+        0 => GAP_COLOR,
+        1 => SINGLE_COLOR,
+        _ => MULTI_COLOR,
+    }
+}
+
+/// Fill every cell around the planet's current position (call in world space)
+pub fn draw_heatmap(map: &CoverageMap, world: &World) {
+    let Some(planet) = world.get_planet(map.planet) else {
+        return;
+    };
+    let center = planet.position();
+    for shell in 0..map.shells {
+        for sector in 0..map.sectors {
+            let (r0, r1, a0, a1) = map.cell_bounds(shell, sector);
+            let (d0, d1) = (Vec2::from_angle(a0), Vec2::from_angle(a1));
+            let color = cell_color(map.count(shell, sector));
+            draw_triangle(center + d0 * r0, center + d0 * r1, center + d1 * r1, color);
+            draw_triangle(center + d0 * r0, center + d1 * r1, center + d1 * r0, color);
+        }
+    }
+}
+
+/// Overall coverage and the worst shell, under the top HUD (call in screen space)
+pub fn draw_legend(map: &CoverageMap, planet_name: &str) {
+    let (worst, worst_coverage) = map.worst_shell();
+    let altitude = (worst as f32 + 0.5) * map.shell_width;
+    let lines = [
+        (format!("COVERAGE around {}: {:.0}% of shells in transfer range", planet_name, map.coverage() * 100.0), WHITE),
+        (format!("Biggest gap: {:.0}% covered at altitude {:.0}", worst_coverage * 100.0, altitude), LIGHTGRAY),
+        ("red = gap   yellow = 1 satellite   green = 2+   (8 to close)".to_string(), GRAY),
+    ];
+
+    let width = 430.0;
+    let Vec2 { x, y } = SafeArea::current().anchor(Anchor::TopCenter, Vec2::new(width, 64.0), Vec2::new(0.0, 40.0));
+    draw_rectangle(x, y, width, 64.0, Color::new(0.0, 0.0, 0.0, 0.7));
+    for (i, (text, color)) in lines.iter().enumerate() {
+        draw_text(text, x + 8.0, y + 18.0 + i as f32 * 18.0, 15.0, *color);
+    }
+}
@@ -0,0 +1,143 @@
+// Damage Indicator - Screen-edge flashes pointing at whatever hit our rocket, and hit markers
+// for bullets we landed. Flashes from the same side merge so a grinding collision doesn't stack up
+
+use macroquad::prelude::*;
+
+use crate::ui::{Camera, SafeArea};
+
+/// Seconds a damage flash stays up
+const FLASH_DURATION: f32 = 1.2;
+/// Seconds a hit marker stays up
+const MARKER_DURATION: f32 = 0.6;
+/// Hits closer than this (dot product of directions) refresh one flash
+const MERGE_DOT: f32 = 0.95;
+/// Flash wedge size (pixels)
+const FLASH_LENGTH: f32 = 46.0;
+const FLASH_WIDTH: f32 = 70.0;
+
+#[derive(Debug, Clone, Copy)]
+struct Flash {
+    direction: Vec2, // Unit vector from our rocket toward the hit's source
+    age: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Marker {
+    position: Vec2, // World position of the rocket we hit
+    age: f32,
+}
+
+/// Recent hits taken and landed
+#[derive(Debug, Clone, Default)]
+pub struct DamageIndicator {
+    flashes: Vec<Flash>,
+    markers: Vec<Marker>,
+}
+
+impl DamageIndicator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Our rocket at `position` was hit from `source`
+    pub fn on_hit(&mut self, position: Vec2, source: Vec2) {
+        let direction = (source - position).normalize_or_zero();
+        if direction == Vec2::ZERO {
+            return;
+        }
+        match self.flashes.iter_mut().find(|flash| flash.direction.dot(direction) > MERGE_DOT) {
+            Some(flash) => *flash = Flash { direction, age: 0.0 },
+            None => self.flashes.push(Flash { direction, age: 0.0 }),
+        }
+    }
+
+    /// One of our bullets hit a rocket at `position`
+    pub fn on_hit_confirmed(&mut self, position: Vec2) {
+        self.markers.push(Marker { position, age: 0.0 });
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        for flash in &mut self.flashes {
+            flash.age += delta_time;
+        }
+        self.flashes.retain(|flash| flash.age < FLASH_DURATION);
+        for marker in &mut self.markers {
+            marker.age += delta_time;
+        }
+        self.markers.retain(|marker| marker.age < MARKER_DURATION);
+    }
+
+    pub fn clear(&mut self) {
+        self.flashes.clear();
+        self.markers.clear();
+    }
+
+    /// Follow a floating origin rebase (positions moved by `-shift`)
+    pub fn shift_positions(&mut self, shift: Vec2) {
+        for marker in &mut self.markers {
+            marker.position -= shift;
+        }
+    }
+
+    /// Draw flashes on the edge of the camera's view (or the safe area) and markers over the rockets we hit (call in screen space)
+    pub fn draw(&self, camera: &Camera) {
+        let area = camera.viewport().unwrap_or(SafeArea::current().rect);
+        for flash in &self.flashes {
+            let alpha = 1.0 - flash.age / FLASH_DURATION;
+            let tip = edge_point(area, flash.direction);
+            let side = flash.direction.perp() * FLASH_WIDTH * 0.5;
+            let base = tip - flash.direction * FLASH_LENGTH;
+            draw_triangle(tip + side, tip - side, base, Color::new(1.0, 0.1, 0.1, 0.6 * alpha));
+            draw_triangle_lines(tip + side, tip - side, base, 2.0, Color::new(1.0, 0.4, 0.3, alpha));
+        }
+
+        for marker in &self.markers {
+            let t = marker.age / MARKER_DURATION;
+            let center = camera.world_to_screen(marker.position);
+            // Pops out a little as it fades
+            let (inner, outer) = (6.0 + 4.0 * t, 14.0 + 4.0 * t);
+            let color = Color::new(1.0, 1.0, 1.0, 1.0 - t);
+            for corner in [Vec2::new(1.0, 1.0), Vec2::new(1.0, -1.0), Vec2::new(-1.0, 1.0), Vec2::new(-1.0, -1.0)] {
+                let corner = corner.normalize();
+                let (from, to) = (center + corner * inner, center + corner * outer);
+                draw_line(from.x, from.y, to.x, to.y, 3.0, color);
+            }
+        }
+    }
+}
+
+/// Where a ray from the middle of `area` along `direction` leaves it
+fn edge_point(area: Rect, direction: Vec2) -> Vec2 {
+    let center = area.center();
+    let half = area.size() * 0.5;
+    let scale_x = if direction.x != 0.0 { half.x / direction.x.abs() } else { f32::INFINITY };
+    let scale_y = if direction.y != 0.0 { half.y / direction.y.abs() } else { f32::INFINITY };
+    center + direction * scale_x.min(scale_y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flashes_point_at_the_source_and_fade() {
+        let area = Rect::new(0.0, 0.0, 200.0, 100.0);
+        assert_eq!(edge_point(area, Vec2::X), Vec2::new(200.0, 50.0));
+        assert_eq!(edge_point(area, -Vec2::Y), Vec2::new(100.0, 0.0));
+        // Diagonals hit the nearer (top/bottom) edge of a wide area
+        assert_eq!(edge_point(area, Vec2::new(1.0, 1.0).normalize()).y, 100.0);
+
+        let mut indicator = DamageIndicator::new();
+        indicator.on_hit(Vec2::ZERO, Vec2::new(50.0, 0.0));
+        indicator.on_hit(Vec2::ZERO, Vec2::new(50.0, 1.0)); // Same side: merges
+        indicator.on_hit(Vec2::ZERO, Vec2::new(-50.0, 0.0));
+        indicator.on_hit(Vec2::ZERO, Vec2::ZERO); // No direction
+        indicator.on_hit_confirmed(Vec2::new(10.0, 10.0));
+        assert_eq!((indicator.flashes.len(), indicator.markers.len()), (2, 1));
+
+        indicator.update(MARKER_DURATION);
+        assert_eq!((indicator.flashes.len(), indicator.markers.len()), (2, 0));
+        indicator.update(FLASH_DURATION);
+        assert!(indicator.flashes.is_empty());
+    }
+}
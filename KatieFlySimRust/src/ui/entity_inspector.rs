@@ -0,0 +1,180 @@
+// Entity Inspector - Hover tooltips and target highlighting in the world view
+// Hover shows name/owner/fuel/velocity; clicking selects the entity as the current target
+
+use std::collections::HashMap;
+
+use macroquad::prelude::*;
+
+use crate::systems::entity_picker::{self, EntityTarget, PICK_RADIUS_PIXELS};
+use crate::systems::World;
+use crate::ui::Camera;
+
+/// Tracks the entity under the mouse cursor
+pub struct EntityInspector {
+    hovered: Option<EntityTarget>,
+}
+
+impl EntityInspector {
+    pub fn new() -> Self {
+        EntityInspector { hovered: None }
+    }
+
+    /// Re-pick the hovered entity from the current mouse position
+    pub fn update(&mut self, world: &World, camera: &Camera) {
+        let (mouse_x, mouse_y) = mouse_position();
+        let world_pos = camera.screen_to_world(Vec2::new(mouse_x, mouse_y));
+        let pick_radius = PICK_RADIUS_PIXELS * camera.zoom_level();
+        self.hovered = entity_picker::pick_entity(world, world_pos, pick_radius);
+    }
+
+    pub fn hovered(&self) -> Option<EntityTarget> {
+        self.hovered
+    }
+
+    pub fn clear(&mut self) {
+        self.hovered = None;
+    }
+
+    // === Drawing ===
+
+    /// Draw hover and target highlights (call while the world camera is active)
+    pub fn draw_highlights(&self, world: &World, target: Option<EntityTarget>, zoom_level: f32) {
+        if let Some(hovered) = self.hovered {
+            if Some(hovered) != target {
+                if let Some(pos) = hovered.position(world) {
+                    let radius = Self::highlight_radius(world, hovered, zoom_level);
+                    draw_circle_lines(pos.x, pos.y, radius, 1.5 * zoom_level, Color::new(1.0, 1.0, 1.0, 0.5));
+                }
+            }
+        }
+
+        // Target: four corner brackets around the entity
+        let Some(target) = target else {
+            return;
+        };
+        let Some(pos) = target.position(world) else {
+            return;
+        };
+        let radius = Self::highlight_radius(world, target, zoom_level);
+        let arm = radius * 0.4;
+        let thickness = 2.0 * zoom_level;
+        let color = Color::new(1.0, 0.3, 0.3, 0.9);
+        for (sx, sy) in [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)] {
+            let corner = Vec2::new(pos.x + sx * radius, pos.y + sy * radius);
+            draw_line(corner.x, corner.y, corner.x - sx * arm, corner.y, thickness, color);
+            draw_line(corner.x, corner.y, corner.x, corner.y - sy * arm, thickness, color);
+        }
+    }
+
+    fn highlight_radius(world: &World, entity: EntityTarget, zoom_level: f32) -> f32 {
+        let min_radius = PICK_RADIUS_PIXELS * zoom_level;
+        match entity {
+            EntityTarget::Planet(id) => world
+                .get_planet(id)
+                .map(|p| p.radius() + 10.0 * zoom_level)
+                .unwrap_or(min_radius)
+                .max(min_radius),
+            EntityTarget::Rocket(_) | EntityTarget::Satellite(_) => min_radius,
+        }
+    }
+
+    /// Draw the hover tooltip next to the mouse cursor (call in screen space)
+    pub fn draw_tooltip(&self, world: &World, target: Option<EntityTarget>, player_names: &HashMap<u32, String>) {
+        let Some(hovered) = self.hovered else {
+            return;
+        };
+        let lines = Self::tooltip_lines(world, hovered, target, player_names);
+        if lines.is_empty() {
+            return;
+        }
+
+        let font_size = 16.0;
+        let line_height = 18.0;
+        let padding = 8.0;
+        let width = lines
+            .iter()
+            .map(|line| measure_text(line, None, font_size as u16, 1.0).width)
+            .fold(0.0, f32::max)
+            + padding * 2.0;
+        let height = lines.len() as f32 * line_height + padding * 2.0;
+
+        // Keep the tooltip on screen
+        let (mouse_x, mouse_y) = mouse_position();
+        let x = (mouse_x + 16.0).min(screen_width() - width - 4.0);
+        let y = (mouse_y + 16.0).min(screen_height() - height - 4.0);
+
+        draw_rectangle(x, y, width, height, Color::new(0.0, 0.0, 0.0, 0.8));
+        draw_rectangle_lines(x, y, width, height, 1.0, Color::new(0.6, 0.6, 0.6, 0.9));
+        for (i, line) in lines.iter().enumerate() {
+            let color = if i == 0 { YELLOW } else { WHITE };
+            draw_text(line, x + padding, y + padding + font_size * 0.8 + i as f32 * line_height, font_size, color);
+        }
+    }
+
+    fn tooltip_lines(
+        world: &World,
+        entity: EntityTarget,
+        target: Option<EntityTarget>,
+        player_names: &HashMap<u32, String>,
+    ) -> Vec<String> {
+        let Some(velocity) = entity.velocity(world) else {
+            return Vec::new();
+        };
+
+        let mut lines = vec![entity.display_name(world)];
+        match entity {
+            EntityTarget::Planet(id) => {
+                if let Some(planet) = world.get_planet(id) {
+                    lines.push(format!("Mass: {:.0}", planet.mass()));
+                    lines.push(format!("Radius: {:.0} m", planet.radius()));
+                }
+            }
+            EntityTarget::Rocket(id) => {
+                if let Some(rocket) = world.get_rocket(id) {
+                    let owner = match rocket.player_id() {
+                        Some(player_id) => player_names
+                            .get(&player_id)
+                            .cloned()
+                            .unwrap_or_else(|| format!("Player {}", player_id)),
+                        None => "Local".to_string(),
+                    };
+                    lines.push(format!("Owner: {}", owner));
+                    lines.push(format!("Fuel: {:.0} / {:.0}", rocket.current_fuel(), rocket.max_fuel()));
+                }
+            }
+            EntityTarget::Satellite(id) => {
+                if let Some(satellite) = world.get_satellite(id) {
+                    let owner = match satellite.owner() {
+                        Some(player_id) => player_names
+                            .get(&player_id)
+                            .cloned()
+                            .unwrap_or_else(|| format!("Player {}", player_id)),
+                        None => "Network".to_string(),
+                    };
+                    lines.push(format!("Owner: {}", owner));
+                    lines.push(format!("Fuel: {:.0} / {:.0}", satellite.current_fuel(), satellite.max_fuel()));
+                    lines.push(if satellite.is_deorbiting() {
+                        "Deorbiting".to_string()
+                    } else if satellite.is_end_of_life() {
+                        "End of life (retrieve with Z)".to_string()
+                    } else {
+                        format!("Wear: {:.0}%", satellite.wear() * 100.0)
+                    });
+                }
+            }
+        }
+        lines.push(format!("Velocity: {:.1} m/s", velocity.length()));
+        lines.push(if Some(entity) == target {
+            "[TARGET] Click to clear".to_string()
+        } else {
+            "Click to target".to_string()
+        });
+        lines
+    }
+}
+
+impl Default for EntityInspector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
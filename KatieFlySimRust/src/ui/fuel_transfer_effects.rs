@@ -0,0 +1,317 @@
+// Fuel Transfer Effects - Animated beams, +fuel/sec readouts and a soft hum
+// Driven by FuelTransferEvents drained from World each frame
+
+use std::collections::HashMap;
+
+use macroquad::audio::{play_sound, set_sound_volume, stop_sound, PlaySoundParams, Sound};
+use macroquad::prelude::*;
+
+use crate::systems::{EntityId, FuelTransferEvent, FuelTransferKind, World};
+
+/// How long a beam stays visible after its last transfer event (seconds)
+const BEAM_LINGER: f32 = 0.5;
+/// Smoothing time constant for the fuel/sec estimate (seconds)
+const RATE_SMOOTHING: f32 = 0.5;
+/// Number of particles travelling along each beam
+const BEAM_PARTICLES: usize = 8;
+/// Particle travel speed along the beam (beam lengths per second)
+const BEAM_PARTICLE_SPEED: f32 = 1.5;
+/// Volume of the transfer hum while any beam is active
+const HUM_VOLUME: f32 = 0.15;
+
+/// One source -> target transfer that is currently (or was very recently) active
+struct ActiveBeam {
+    time_since_event: f32,
+    fuel_rate: f32, // Smoothed fuel units per second
+}
+
+/// Tracks active fuel transfers and draws/plays feedback for them
+pub struct FuelTransferEffects {
+    beams: HashMap<(FuelTransferKind, EntityId, EntityId), ActiveBeam>,
+    animation_time: f32,
+    hum: Option<Sound>,
+    hum_playing: bool,
+}
+
+impl FuelTransferEffects {
+    pub fn new() -> Self {
+        FuelTransferEffects {
+            beams: HashMap::new(),
+            animation_time: 0.0,
+            hum: None,
+            hum_playing: false,
+        }
+    }
+
+    /// Set the looping sound played while transfers are active
+    pub fn set_hum_sound(&mut self, sound: Option<Sound>) {
+        self.stop_hum();
+        self.hum = sound;
+    }
+
+    /// Feed new transfer events and age existing beams
+    pub fn update(&mut self, delta_time: f32, events: Vec<FuelTransferEvent>) {
+        self.animation_time += delta_time;
+
+        // Decay existing rate estimates and age beams
+        let decay = (-delta_time / RATE_SMOOTHING).exp();
+        for beam in self.beams.values_mut() {
+            beam.time_since_event += delta_time;
+            beam.fuel_rate *= decay;
+        }
+
+        // Each event adds its amount spread over the smoothing window
+        for event in events {
+            let beam = self
+                .beams
+                .entry((event.kind, event.source_id, event.target_id))
+                .or_insert(ActiveBeam {
+                    time_since_event: 0.0,
+                    fuel_rate: 0.0,
+                });
+            beam.time_since_event = 0.0;
+            beam.fuel_rate += event.amount / RATE_SMOOTHING;
+        }
+
+        self.beams.retain(|_, beam| beam.time_since_event <= BEAM_LINGER);
+
+        // Hum follows beam activity
+        if self.has_active_transfers() {
+            self.start_hum();
+        } else {
+            self.stop_hum();
+        }
+    }
+
+    pub fn has_active_transfers(&self) -> bool {
+        !self.beams.is_empty()
+    }
+
+    /// Smoothed fuel/sec currently flowing into a rocket (0 if none)
+    pub fn fuel_rate_into_rocket(&self, rocket_id: EntityId) -> f32 {
+        self.beams
+            .iter()
+            .filter(|((kind, _, target), _)| {
+                *target == rocket_id
+                    && matches!(kind, FuelTransferKind::PlanetToRocket | FuelTransferKind::SatelliteToRocket | FuelTransferKind::RocketToRocket)
+            })
+            .map(|(_, beam)| beam.fuel_rate)
+            .sum()
+    }
+
+    /// Clear all beams and silence the hum (e.g. when leaving a game)
+    pub fn clear(&mut self) {
+        self.beams.clear();
+        self.stop_hum();
+    }
+
+    fn start_hum(&mut self) {
+        if self.hum_playing {
+            return;
+        }
+        if let Some(ref sound) = self.hum {
+            play_sound(sound, PlaySoundParams { looped: true, volume: HUM_VOLUME });
+            set_sound_volume(sound, HUM_VOLUME);
+            self.hum_playing = true;
+        }
+    }
+
+    fn stop_hum(&mut self) {
+        if !self.hum_playing {
+            return;
+        }
+        if let Some(ref sound) = self.hum {
+            stop_sound(sound);
+        }
+        self.hum_playing = false;
+    }
+
+    // === Drawing ===
+
+    /// Draw beams in world space (call while the world camera is active)
+    pub fn draw_beams(&self, world: &World, zoom_level: f32) {
+        for ((kind, source_id, target_id), beam) in &self.beams {
+            let source_pos = match kind {
+                FuelTransferKind::PlanetToRocket | FuelTransferKind::PlanetToSatellite => {
+                    world.get_planet(*source_id).map(|p| p.position())
+                }
+                FuelTransferKind::SatelliteToRocket | FuelTransferKind::SatelliteToSatellite => {
+                    world.get_satellite(*source_id).map(|s| s.position())
+                }
+                FuelTransferKind::RocketToSatellite | FuelTransferKind::RocketToRocket => {
+                    world.get_rocket(*source_id).map(|r| r.position())
+                }
+            };
+            let target_pos = match kind {
+                FuelTransferKind::PlanetToSatellite | FuelTransferKind::RocketToSatellite | FuelTransferKind::SatelliteToSatellite => {
+                    world.get_satellite(*target_id).map(|s| s.position())
+                }
+                FuelTransferKind::PlanetToRocket | FuelTransferKind::SatelliteToRocket | FuelTransferKind::RocketToRocket => {
+                    world.get_rocket(*target_id).map(|r| r.position())
+                }
+            };
+
+            let (Some(source_pos), Some(target_pos)) = (source_pos, target_pos) else {
+                continue;
+            };
+
+            // For planets, start the beam at the surface nearest the target
+            let source_pos = match kind {
+                FuelTransferKind::PlanetToRocket | FuelTransferKind::PlanetToSatellite => {
+                    match world.get_planet(*source_id) {
+                        Some(planet) => {
+                            let dir = (target_pos - source_pos).normalize_or_zero();
+                            source_pos + dir * planet.radius()
+                        }
+                        None => source_pos,
+                    }
+                }
+                FuelTransferKind::SatelliteToRocket
+                | FuelTransferKind::RocketToSatellite
+                | FuelTransferKind::SatelliteToSatellite
+                | FuelTransferKind::RocketToRocket => source_pos,
+            };
+
+            // Fade out during the linger period
+            let alpha = (1.0 - beam.time_since_event / BEAM_LINGER).clamp(0.0, 1.0);
+            let base_color = Self::beam_color(*kind);
+
+            // Faint guide line
+            draw_line(
+                source_pos.x,
+                source_pos.y,
+                target_pos.x,
+                target_pos.y,
+                1.5 * zoom_level,
+                Color::new(base_color.r, base_color.g, base_color.b, 0.25 * alpha),
+            );
+
+            // Particles flowing from source to target
+            let particle_radius = 3.0 * zoom_level;
+            for i in 0..BEAM_PARTICLES {
+                let t = (self.animation_time * BEAM_PARTICLE_SPEED + i as f32 / BEAM_PARTICLES as f32).fract();
+                let pos = source_pos.lerp(target_pos, t);
+                draw_circle(
+                    pos.x,
+                    pos.y,
+                    particle_radius,
+                    Color::new(base_color.r, base_color.g, base_color.b, 0.9 * alpha),
+                );
+            }
+        }
+    }
+
+    /// Draw the +fuel/sec readout for a rocket (call in screen space)
+    pub fn draw_rocket_fuel_rate(&self, rocket_id: EntityId, screen_pos: Vec2) {
+        let rate = self.fuel_rate_into_rocket(rocket_id);
+        if rate < 0.1 {
+            return;
+        }
+
+        let text = format!("+{:.1} fuel/s", rate);
+        let text_size = 20.0;
+        let dims = measure_text(&text, None, text_size as u16, 1.0);
+        let x = screen_pos.x - dims.width / 2.0;
+        let y = screen_pos.y + 40.0; // Below the rocket
+
+        draw_text(&text, x + 1.0, y + 1.0, text_size, BLACK);
+        draw_text(&text, x, y, text_size, Color::new(0.3, 1.0, 0.5, 1.0));
+    }
+
+    fn beam_color(kind: FuelTransferKind) -> Color {
+        match kind {
+            FuelTransferKind::PlanetToRocket => Color::new(1.0, 0.8, 0.2, 1.0),    // Gold
+            FuelTransferKind::PlanetToSatellite => Color::new(1.0, 0.6, 0.1, 1.0), // Orange
+            FuelTransferKind::SatelliteToRocket => Color::new(0.2, 1.0, 0.6, 1.0), // Green
+            FuelTransferKind::RocketToSatellite => Color::new(0.4, 0.8, 1.0, 1.0), // Blue
+            FuelTransferKind::SatelliteToSatellite => Color::new(0.8, 0.5, 1.0, 1.0), // Violet
+            FuelTransferKind::RocketToRocket => Color::new(1.0, 0.5, 0.7, 1.0),      // Pink
+        }
+    }
+
+    /// Build a short, seamlessly looping low hum as a 16-bit mono WAV
+    pub fn hum_wav_bytes() -> Vec<u8> {
+        let sample_rate: u32 = 22050;
+        let sample_count = sample_rate; // One second loop (whole cycles of every tone)
+        let mut samples: Vec<u8> = Vec::with_capacity(sample_count as usize * 2);
+
+        for n in 0..sample_count {
+            let t = n as f32 / sample_rate as f32;
+            let tau = std::f32::consts::TAU;
+            // 110 Hz fundamental with a quieter octave and a slow 2 Hz swell
+            let swell = 0.75 + 0.25 * (tau * 2.0 * t).sin();
+            let value = swell * (0.6 * (tau * 110.0 * t).sin() + 0.25 * (tau * 220.0 * t).sin());
+            let sample = (value * 0.5 * i16::MAX as f32) as i16;
+            samples.extend_from_slice(&sample.to_le_bytes());
+        }
+
+        let data_len = samples.len() as u32;
+        let mut wav = Vec::with_capacity(44 + samples.len());
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVE");
+        wav.extend_from_slice(b"fmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes()); // PCM chunk size
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM format
+        wav.extend_from_slice(&1u16.to_le_bytes()); // Mono
+        wav.extend_from_slice(&sample_rate.to_le_bytes());
+        wav.extend_from_slice(&(sample_rate * 2).to_le_bytes()); // Byte rate
+        wav.extend_from_slice(&2u16.to_le_bytes()); // Block align
+        wav.extend_from_slice(&16u16.to_le_bytes()); // Bits per sample
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        wav.extend_from_slice(&samples);
+        wav
+    }
+}
+
+impl Drop for FuelTransferEffects {
+    fn drop(&mut self) {
+        // Don't leave the hum looping after the game mode goes away
+        self.stop_hum();
+    }
+}
+
+impl Default for FuelTransferEffects {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: FuelTransferKind, source_id: EntityId, target_id: EntityId, amount: f32) -> FuelTransferEvent {
+        FuelTransferEvent { kind, source_id, target_id, amount }
+    }
+
+    #[test]
+    fn test_beam_appears_and_expires() {
+        let mut effects = FuelTransferEffects::new();
+        effects.update(0.016, vec![event(FuelTransferKind::SatelliteToRocket, 1, 2, 0.2)]);
+        assert!(effects.has_active_transfers());
+        assert!(effects.fuel_rate_into_rocket(2) > 0.0);
+
+        // No further events - beam should linger then disappear
+        effects.update(BEAM_LINGER + 0.1, Vec::new());
+        assert!(!effects.has_active_transfers());
+        assert_eq!(effects.fuel_rate_into_rocket(2), 0.0);
+    }
+
+    #[test]
+    fn test_satellite_collection_not_counted_for_rockets() {
+        let mut effects = FuelTransferEffects::new();
+        effects.update(0.016, vec![event(FuelTransferKind::PlanetToSatellite, 0, 5, 32.0)]);
+        assert!(effects.has_active_transfers());
+        assert_eq!(effects.fuel_rate_into_rocket(5), 0.0);
+    }
+
+    #[test]
+    fn test_hum_wav_header() {
+        let wav = FuelTransferEffects::hum_wav_bytes();
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[8..12], b"WAVE");
+        assert_eq!(wav.len(), 44 + 22050 * 2);
+    }
+}
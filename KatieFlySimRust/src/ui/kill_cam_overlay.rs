@@ -0,0 +1,62 @@
+// Kill Cam Overlay - Draws the replay of the shot that destroyed the player's rocket
+// The live world is dimmed; the recorded shooter, victim and bullet trail are drawn on top
+
+use macroquad::prelude::*;
+
+use crate::systems::kill_cam::{KillCam, RocketState};
+use crate::ui::{Anchor, SafeArea};
+
+const TRAIL_COLOR: Color = Color::new(1.0, 0.9, 0.3, 0.8);
+const SHOOTER_RING: Color = Color::new(1.0, 0.3, 0.3, 0.9);
+
+/// Rocket silhouette `pixels` tall at any zoom
+fn draw_rocket_marker(rocket: &RocketState, pixels: f32, zoom_level: f32) {
+    let forward = Vec2::new(rocket.rotation.sin(), -rocket.rotation.cos());
+    let side = forward.perp();
+    let size = pixels * zoom_level;
+    let nose = rocket.position + forward * size * 0.6;
+    let left = rocket.position - forward * size * 0.4 + side * size * 0.35;
+    let right = rocket.position - forward * size * 0.4 - side * size * 0.35;
+    draw_triangle(nose, left, right, rocket.color);
+}
+
+/// Replay scene (call in world space); `visible` is the camera's view, which gets dimmed
+pub fn draw_replay(cam: &KillCam, visible: Rect, zoom_level: f32) {
+    draw_rectangle(visible.x, visible.y, visible.w, visible.h, Color::new(0.0, 0.0, 0.05, 0.6));
+
+    let view = cam.view();
+    let line = 2.0 * zoom_level;
+    for pair in view.trail.windows(2) {
+        draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, line, TRAIL_COLOR);
+    }
+    if let Some(shooter) = &view.shooter {
+        draw_rocket_marker(shooter, 18.0, zoom_level);
+        draw_circle_lines(shooter.position.x, shooter.position.y, 16.0 * zoom_level, line, SHOOTER_RING);
+    }
+    if let Some(victim) = &view.victim {
+        draw_rocket_marker(victim, 18.0, zoom_level);
+    }
+    if let Some(bullet) = view.bullet {
+        draw_circle(bullet.x, bullet.y, 4.0 * zoom_level, WHITE);
+    }
+    if view.impact {
+        let impact = cam.impact_position();
+        draw_circle(impact.x, impact.y, 24.0 * zoom_level, Color::new(1.0, 0.5, 0.1, 0.7));
+        draw_circle(impact.x, impact.y, 12.0 * zoom_level, Color::new(1.0, 0.95, 0.6, 0.9));
+    }
+}
+
+/// "KILL CAM" banner with who fired and a progress bar (call in screen space)
+pub fn draw_banner(cam: &KillCam) {
+    let size = Vec2::new(420.0, 70.0);
+    let Vec2 { x, y } = SafeArea::current().anchor(Anchor::TopCenter, size, Vec2::new(0.0, 40.0));
+    draw_rectangle(x, y, size.x, size.y, Color::new(0.0, 0.0, 0.0, 0.75));
+    draw_rectangle_lines(x, y, size.x, size.y, 2.0, SHOOTER_RING);
+    draw_text("KILL CAM", x + 12.0, y + 26.0, 26.0, SHOOTER_RING);
+    draw_text(&format!("Shot down by {}", cam.attacker), x + 140.0, y + 25.0, 18.0, WHITE);
+    draw_text("SPACE to skip", x + size.x - 110.0, y + size.y - 12.0, 14.0, GRAY);
+
+    let bar = Rect::new(x + 12.0, y + 40.0, size.x - 140.0, 8.0);
+    draw_rectangle(bar.x, bar.y, bar.w, bar.h, Color::new(0.3, 0.3, 0.3, 1.0));
+    draw_rectangle(bar.x, bar.y, bar.w * cam.progress(), bar.h, SHOOTER_RING);
+}
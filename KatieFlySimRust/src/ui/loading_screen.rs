@@ -0,0 +1,61 @@
+// Loading Screen - Progress bar shown while the asset manager works between menus and games
+// The bar eases toward the reported progress so short loads don't flicker
+
+use macroquad::prelude::*;
+
+const BAR_WIDTH: f32 = 600.0;
+const BAR_HEIGHT: f32 = 24.0;
+/// How quickly the drawn bar catches up with the real progress (per second)
+const EASE_RATE: f32 = 8.0;
+
+/// Full-screen progress display
+#[derive(Debug, Clone)]
+pub struct LoadingScreen {
+    title: String,
+    shown: f32, // Drawn progress, trailing the reported one
+}
+
+impl LoadingScreen {
+    pub fn new() -> Self {
+        LoadingScreen { title: "Loading".to_string(), shown: 0.0 }
+    }
+
+    /// Begin a new load with an empty bar
+    pub fn start(&mut self, title: &str) {
+        self.title = title.to_string();
+        self.shown = 0.0;
+    }
+
+    /// Ease the bar toward `progress` (0.0 - 1.0)
+    pub fn update(&mut self, progress: f32, delta_time: f32) {
+        let target = progress.clamp(0.0, 1.0);
+        self.shown += (target - self.shown) * (EASE_RATE * delta_time).min(1.0);
+    }
+
+    /// Draw the title, the bar and what is being loaded
+    pub fn draw(&self, detail: &str) {
+        clear_background(Color::from_rgba(10, 10, 25, 255));
+        let center = Vec2::new(screen_width() / 2.0, screen_height() / 2.0);
+
+        let title_dims = measure_text(&self.title, None, 48, 1.0);
+        draw_text(&self.title, center.x - title_dims.width / 2.0, center.y - 50.0, 48.0, WHITE);
+
+        let bar = Rect::new(center.x - BAR_WIDTH / 2.0, center.y, BAR_WIDTH, BAR_HEIGHT);
+        draw_rectangle(bar.x, bar.y, bar.w, bar.h, Color::from_rgba(40, 40, 60, 255));
+        draw_rectangle(bar.x, bar.y, bar.w * self.shown, bar.h, Color::from_rgba(80, 160, 255, 255));
+        draw_rectangle_lines(bar.x, bar.y, bar.w, bar.h, 2.0, Color::from_rgba(120, 120, 160, 255));
+
+        let percent = format!("{:.0}%", self.shown * 100.0);
+        let percent_dims = measure_text(&percent, None, 20, 1.0);
+        draw_text(&percent, center.x - percent_dims.width / 2.0, bar.y + 18.0, 20.0, WHITE);
+
+        let detail_dims = measure_text(detail, None, 20, 1.0);
+        draw_text(detail, center.x - detail_dims.width / 2.0, bar.y + BAR_HEIGHT + 35.0, 20.0, LIGHTGRAY);
+    }
+}
+
+impl Default for LoadingScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,214 @@
+// Log Console - In-game overlay for the captured log with level/module filters
+// F1 toggles it in any screen; capture levels can be changed here while the game runs
+
+use log::{Level, LevelFilter};
+use macroquad::prelude::*;
+
+use crate::utils::log_buffer::{self, LogEntry};
+
+/// Fraction of the screen height the console covers
+const HEIGHT_FRACTION: f32 = 0.45;
+const LINE_HEIGHT: f32 = 16.0;
+/// Module paths are shown without the crate prefix
+const CRATE_PREFIX: &str = "katie_fly_sim_rust::";
+
+/// Log console state
+#[derive(Debug, Clone)]
+pub struct LogConsole {
+    visible: bool,
+    view_level: Level,          // Most verbose level shown
+    module: Option<String>,     // Only show this module (and its children)
+    scroll: usize,              // Lines scrolled up from the newest
+}
+
+impl Default for LogConsole {
+    fn default() -> Self {
+        LogConsole {
+            visible: false,
+            view_level: Level::Trace,
+            module: None,
+            scroll: 0,
+        }
+    }
+}
+
+impl LogConsole {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// F1 toggle, F2 view level, F3 module filter, F4 capture level, F6/F7 scroll
+    pub fn handle_input(&mut self) {
+        if is_key_pressed(KeyCode::F1) {
+            self.visible = !self.visible;
+            self.scroll = 0;
+        }
+        if !self.visible {
+            return;
+        }
+
+        if is_key_pressed(KeyCode::F2) {
+            self.view_level = next_level(self.view_level);
+            self.scroll = 0;
+        }
+        if is_key_pressed(KeyCode::F3) {
+            self.module = next_module(&log_buffer::recent_entries(), self.module.as_deref());
+            self.scroll = 0;
+        }
+        if is_key_pressed(KeyCode::F4) {
+            let module = self.module.clone().unwrap_or_default();
+            let current = log_buffer::levels().level_for(&module);
+            log_buffer::set_module_level(&module, next_capture_level(current));
+        }
+
+        let page = self.visible_rows().saturating_sub(1).max(1);
+        if is_key_pressed(KeyCode::F6) {
+            self.scroll = (self.scroll + page).min(log_buffer::recent_entries().len());
+        }
+        if is_key_pressed(KeyCode::F7) {
+            self.scroll = self.scroll.saturating_sub(page);
+        }
+    }
+
+    fn visible_rows(&self) -> usize {
+        ((screen_height() * HEIGHT_FRACTION - 50.0) / LINE_HEIGHT).max(1.0) as usize
+    }
+
+    /// Draw the console over everything (call last, in screen space)
+    pub fn draw(&self) {
+        if !self.visible {
+            return;
+        }
+
+        let height = screen_height() * HEIGHT_FRACTION;
+        draw_rectangle(0.0, 0.0, screen_width(), height, Color::new(0.0, 0.0, 0.0, 0.85));
+        draw_line(0.0, height, screen_width(), height, 1.0, GRAY);
+
+        let entries = log_buffer::recent_entries();
+        let shown = filter_entries(&entries, self.view_level, self.module.as_deref());
+        let module_label = self.module.as_deref().map_or("all".to_string(), short_module);
+        let capture = log_buffer::levels().level_for(self.module.as_deref().unwrap_or_default());
+        let header = format!(
+            "Log ({} of {} lines) | F2 show: {} and up | F3 module: {} | F4 capture: {} | F6/F7 scroll | F1 close",
+            shown.len(),
+            entries.len(),
+            self.view_level,
+            module_label,
+            capture,
+        );
+        draw_text(&header, 10.0, 20.0, 18.0, WHITE);
+
+        let rows = self.visible_rows();
+        let end = shown.len().saturating_sub(self.scroll.min(shown.len().saturating_sub(rows)));
+        let start = end.saturating_sub(rows);
+        for (row, entry) in shown[start..end].iter().enumerate() {
+            let text = format!(
+                "{:8.2} {:<5} {}: {}",
+                entry.seconds,
+                entry.level,
+                short_module(&entry.target),
+                entry.message
+            );
+            draw_text(&text, 10.0, 44.0 + row as f32 * LINE_HEIGHT, 16.0, level_color(entry.level));
+        }
+        if shown.is_empty() {
+            draw_text("No log lines match the current filters", 10.0, 44.0, 16.0, GRAY);
+        }
+    }
+}
+
+/// Entries at or above `view_level` from `module` (None = every module)
+pub fn filter_entries<'a>(entries: &'a [LogEntry], view_level: Level, module: Option<&str>) -> Vec<&'a LogEntry> {
+    entries
+        .iter()
+        .filter(|entry| entry.level <= view_level)
+        .filter(|entry| {
+            module.is_none_or(|m| entry.target == m || entry.target.starts_with(&format!("{}::", m)))
+        })
+        .collect()
+}
+
+/// Cycle all -> each module seen in the log (sorted) -> all
+fn next_module(entries: &[LogEntry], current: Option<&str>) -> Option<String> {
+    let mut modules: Vec<&str> = entries.iter().map(|entry| entry.target.as_str()).collect();
+    modules.sort_unstable();
+    modules.dedup();
+    match current {
+        None => modules.first().map(|m| m.to_string()),
+        Some(current) => modules.iter().find(|m| **m > current).map(|m| m.to_string()),
+    }
+}
+
+/// Error -> Warn -> Info -> Debug -> Trace -> Error
+fn next_level(level: Level) -> Level {
+    match level {
+        Level::Error => Level::Warn,
+        Level::Warn => Level::Info,
+        Level::Info => Level::Debug,
+        Level::Debug => Level::Trace,
+        Level::Trace => Level::Error,
+    }
+}
+
+/// Off -> Error -> ... -> Trace -> Off
+fn next_capture_level(level: LevelFilter) -> LevelFilter {
+    match level {
+        LevelFilter::Off => LevelFilter::Error,
+        LevelFilter::Error => LevelFilter::Warn,
+        LevelFilter::Warn => LevelFilter::Info,
+        LevelFilter::Info => LevelFilter::Debug,
+        LevelFilter::Debug => LevelFilter::Trace,
+        LevelFilter::Trace => LevelFilter::Off,
+    }
+}
+
+fn short_module(target: &str) -> String {
+    target.strip_prefix(CRATE_PREFIX).unwrap_or(target).to_string()
+}
+
+fn level_color(level: Level) -> Color {
+    match level {
+        Level::Error => Color::new(1.0, 0.35, 0.35, 1.0),
+        Level::Warn => Color::new(1.0, 0.8, 0.3, 1.0),
+        Level::Info => WHITE,
+        Level::Debug => LIGHTGRAY,
+        Level::Trace => GRAY,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: Level, target: &str) -> LogEntry {
+        LogEntry {
+            seconds: 0.0,
+            level,
+            target: target.to_string(),
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_filter_by_level_and_module() {
+        let entries = vec![
+            entry(Level::Info, "katie_fly_sim_rust::systems::world"),
+            entry(Level::Debug, "katie_fly_sim_rust::systems::world"),
+            entry(Level::Warn, "katie_fly_sim_rust::game_modes::multiplayer_host"),
+        ];
+        assert_eq!(filter_entries(&entries, Level::Trace, None).len(), 3);
+        assert_eq!(filter_entries(&entries, Level::Info, None).len(), 2);
+        assert_eq!(filter_entries(&entries, Level::Trace, Some("katie_fly_sim_rust::systems")).len(), 2);
+        assert_eq!(filter_entries(&entries, Level::Warn, Some("katie_fly_sim_rust::systems")).len(), 0);
+
+        assert_eq!(
+            next_module(&entries, None).as_deref(),
+            Some("katie_fly_sim_rust::game_modes::multiplayer_host")
+        );
+        assert_eq!(next_module(&entries, Some("katie_fly_sim_rust::systems::world")), None);
+    }
+}
@@ -0,0 +1,112 @@
+// Market Panel - Station fuel prices, the contracts board and the player's credits (M)
+// Up/Down pick a contract, I takes or drops it; Y buys a tank of cargo and Shift+Y sells it while landed
+
+use macroquad::prelude::*;
+
+use crate::systems::mission_clock;
+use crate::systems::{Cargo, ContractKind, Market};
+use crate::ui::{Anchor, SafeArea};
+
+const PANEL_WIDTH: f32 = 380.0;
+const LINE_HEIGHT: f32 = 18.0;
+const HEADER_COLOR: Color = Color::new(1.0, 0.85, 0.4, 1.0);
+
+/// What the player asked the market to do this frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarketAction {
+    Buy,
+    Sell,
+    ToggleContract(u32),
+}
+
+/// Contracts board state
+#[derive(Debug, Clone, Default)]
+pub struct MarketPanel {
+    visible: bool,
+    selected: usize,
+}
+
+impl MarketPanel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    /// Board keys while the panel is open
+    pub fn handle_input(&mut self, market: &Market) -> Option<MarketAction> {
+        if !self.visible {
+            return None;
+        }
+        let count = market.contracts().len();
+        self.selected = self.selected.min(count.saturating_sub(1));
+        if is_key_pressed(KeyCode::Down) && count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+        if is_key_pressed(KeyCode::Up) && count > 0 {
+            self.selected = (self.selected + count - 1) % count;
+        }
+        if is_key_pressed(KeyCode::I) {
+            return market.contracts().get(self.selected).map(|contract| MarketAction::ToggleContract(contract.id));
+        }
+        if is_key_pressed(KeyCode::Y) {
+            let shift = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+            return Some(if shift { MarketAction::Sell } else { MarketAction::Buy });
+        }
+        None
+    }
+
+    /// Prices, contracts and balance on the right side of the screen (call in screen space)
+    pub fn draw(&self, market: &Market, credits: u32, cargo: Option<Cargo>) {
+        if !self.visible {
+            return;
+        }
+
+        let mut lines: Vec<(String, Color)> = vec![(format!("MARKET - {} credits", credits), HEADER_COLOR)];
+        match cargo {
+            Some(cargo) => lines.push((format!("Cargo: {:.0} fuel from {}", cargo.amount, market.station_name(cargo.origin)), SKYBLUE)),
+            None => lines.push(("Cargo: none".to_string(), GRAY)),
+        }
+        lines.push(("Station        buy    sell".to_string(), LIGHTGRAY));
+        for station in market.stations() {
+            lines.push((format!("{:<12} {:>5.2}  {:>5.2}", station.name, station.ask(), station.bid()), WHITE));
+        }
+
+        lines.push(("Contracts (Up/Down, I to take or drop)".to_string(), LIGHTGRAY));
+        if market.contracts().is_empty() {
+            lines.push(("  Nothing posted".to_string(), GRAY));
+        }
+        for (i, contract) in market.contracts().iter().enumerate() {
+            let marker = if i == self.selected { ">" } else { " " };
+            let taken = if contract.accepted { "[x]" } else { "[ ]" };
+            let color = if contract.accepted { GREEN } else if i == self.selected { WHITE } else { LIGHTGRAY };
+            let job = match contract.kind {
+                ContractKind::Haul => {
+                    format!("{:.0} {} -> {}", contract.amount, market.station_name(contract.from), market.station_name(contract.to))
+                }
+                ContractKind::Cleanup { cleared } => format!("Deorbit debris {}/{:.0}", cleared, contract.amount),
+            };
+            lines.push((
+                format!("{}{} {}  {}cr  {}", marker, taken, job, contract.payout, mission_clock::format_clock(contract.time_left)),
+                color,
+            ));
+        }
+        lines.push(("Landed: Y buy a tank, Shift+Y sell cargo".to_string(), GRAY));
+        lines.push(("Shift+Z: tractor beam grabs / lets go of debris".to_string(), GRAY));
+
+        let height = lines.len() as f32 * LINE_HEIGHT + 12.0;
+        // Below the controls button
+        let Vec2 { x, y } = SafeArea::current().anchor(Anchor::TopRight, Vec2::new(PANEL_WIDTH, height), Vec2::new(0.0, 50.0));
+        draw_rectangle(x, y, PANEL_WIDTH, height, Color::new(0.0, 0.0, 0.0, 0.75));
+        draw_rectangle_lines(x, y, PANEL_WIDTH, height, 1.0, HEADER_COLOR);
+        for (i, (text, color)) in lines.iter().enumerate() {
+            draw_text(text, x + 8.0, y + 20.0 + i as f32 * LINE_HEIGHT, 15.0, *color);
+        }
+    }
+}
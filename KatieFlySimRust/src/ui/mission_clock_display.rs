@@ -0,0 +1,25 @@
+// Mission Clock Display - Mission elapsed time and warp factor in the bottom-right corner
+// Shared by every mode; the route overlay stacks above it
+
+use macroquad::prelude::*;
+
+use crate::systems::mission_clock;
+use crate::ui::{Anchor, SafeArea};
+
+/// Height of the clock box (overlays in the same corner sit above it)
+pub const CLOCK_HEIGHT: f32 = 30.0;
+const CLOCK_WIDTH: f32 = 220.0;
+const WARP_COLOR: Color = Color::new(1.0, 0.75, 0.2, 1.0);
+
+/// Draw "T+ 1:02:05" and, while warping, the factor next to it
+pub fn draw_mission_clock(elapsed: f32, warp: u32) {
+    let Vec2 { x, y } = SafeArea::current().anchor(Anchor::BottomRight, Vec2::new(CLOCK_WIDTH, CLOCK_HEIGHT), Vec2::ZERO);
+    let border = if warp > 1 { WARP_COLOR } else { Color::new(0.5, 0.5, 0.6, 0.8) };
+    draw_rectangle(x, y, CLOCK_WIDTH, CLOCK_HEIGHT, Color::new(0.0, 0.0, 0.0, 0.7));
+    draw_rectangle_lines(x, y, CLOCK_WIDTH, CLOCK_HEIGHT, 1.0, border);
+    draw_text(&mission_clock::format_met(elapsed), x + 8.0, y + 21.0, 20.0, WHITE);
+
+    let warp_text = format!("{}x", warp);
+    let width = measure_text(&warp_text, None, 20, 1.0).width;
+    draw_text(&warp_text, x + CLOCK_WIDTH - width - 8.0, y + 21.0, 20.0, if warp > 1 { WARP_COLOR } else { GRAY });
+}
@@ -6,10 +6,71 @@ pub mod hud;
 pub mod text_panel;
 pub mod ui_manager;
 pub mod game_info_display;
+pub mod fuel_transfer_effects;
+pub mod toast;
+pub mod entity_inspector;
+pub mod alert_banner;
+pub mod threat_indicator;
+pub mod damage_indicator;
+pub mod network_map_search;
+pub mod satellite_batch_bar;
+pub mod timeline_panel;
+pub mod log_console;
+pub mod waypoint_markers;
+pub mod route_overlay;
+pub mod challenge_panel;
+pub mod convoy_panel;
+pub mod coverage_heatmap;
+pub mod kill_cam_overlay;
+pub mod market_panel;
+pub mod safe_area;
+pub mod slider;
+pub mod table;
+pub mod loading_screen;
+pub mod sprite_batch;
+pub mod mission_clock_display;
+pub mod netcode_overlay;
+pub mod conservation_overlay;
+pub mod timestep_warning;
+pub mod arena_overlay;
+pub mod sky;
+pub mod assist_overlay;
+pub mod notifications;
+pub mod rocket_transfer_readout;
+pub mod strategic_view;
+pub mod bullet_effects;
+pub mod chat_box;
+pub mod player_list;
+pub mod relay_overlay;
+pub mod viewport_layout;
 
 pub use button::Button;
-pub use camera::Camera;
+pub use camera::{Camera, CameraCommand, CameraImpulse, CameraSmoothing};
 pub use hud::Hud;
 pub use text_panel::{TextPanel, TextPanelConfig, TextAlignment};
 pub use ui_manager::UIManager;
-pub use game_info_display::{GameInfoDisplay, GameMode, NetworkRole};
+pub use game_info_display::{GameInfoDisplay, GameMode, NetworkRole, HudLayout, HudPanel, PanelLayout};
+pub use fuel_transfer_effects::FuelTransferEffects;
+pub use toast::ToastManager;
+pub use notifications::NotificationCenter;
+pub use rocket_transfer_readout::RocketTransferReadout;
+pub use strategic_view::StrategicView;
+pub use bullet_effects::{BulletEffects, BulletViewOptions};
+pub use chat_box::ChatBox;
+pub use player_list::{PlayerListAction, PlayerListPanel, PlayerRow};
+pub use entity_inspector::EntityInspector;
+pub use alert_banner::AlertBanner;
+pub use threat_indicator::ThreatIndicator;
+pub use damage_indicator::DamageIndicator;
+pub use network_map_search::{NetworkMapSearch, SearchJump};
+pub use satellite_batch_bar::{BatchAction, SatelliteBatchBar};
+pub use timeline_panel::TimelinePanel;
+pub use log_console::LogConsole;
+pub use market_panel::{MarketAction, MarketPanel};
+pub use safe_area::{Anchor, SafeArea};
+pub use slider::Slider;
+pub use table::{Table, TableColumn, TableEvent};
+pub use loading_screen::LoadingScreen;
+pub use sprite_batch::{Sprite, SpriteBatch};
+pub use sky::Sky;
+pub use viewport_layout::ViewportLayout;
@@ -0,0 +1,67 @@
+// Netcode Overlay - Client debug view of prediction: host ghost of our rocket and the error graph
+// Toggled with F8 on clients; F9 switches prediction itself off
+
+use macroquad::prelude::*;
+
+use crate::game_constants::GameConstants;
+use crate::systems::prediction_debug::ERROR_HISTORY_LEN;
+use crate::systems::PredictionDebug;
+use crate::ui::mission_clock_display::CLOCK_HEIGHT;
+use crate::ui::{Anchor, SafeArea};
+
+const GHOST_COLOR: Color = Color::new(1.0, 0.4, 1.0, 0.9);
+const GRAPH_SIZE: Vec2 = Vec2::new(300.0, 150.0);
+/// The graph's vertical scale never drops below this (world units), so a quiet link reads as flat
+const MIN_GRAPH_SCALE: f32 = 10.0;
+
+/// Outline of our rocket where the last snapshot put it (call in world space)
+pub fn draw_ghost(debug: &PredictionDebug, zoom_level: f32) {
+    let Some(pose) = debug.ghost() else {
+        return;
+    };
+    let size = GameConstants::ROCKET_SIZE;
+    let (sin_r, cos_r) = pose.rotation.sin_cos();
+    let points: Vec<Vec2> = [Vec2::new(0.0, -size), Vec2::new(-size / 2.0, size), Vec2::new(size / 2.0, size)]
+        .iter()
+        .map(|p| pose.position + Vec2::new(p.x * cos_r - p.y * sin_r, p.x * sin_r + p.y * cos_r))
+        .collect();
+    let thickness = (1.5 * zoom_level).max(1.0);
+    for i in 0..points.len() {
+        let (a, b) = (points[i], points[(i + 1) % points.len()]);
+        draw_line(a.x, a.y, b.x, b.y, thickness, GHOST_COLOR);
+    }
+}
+
+/// Prediction error per snapshot with latest / average / max, above the mission clock (call in screen space)
+pub fn draw_error_graph(debug: &PredictionDebug) {
+    let Vec2 { x, y } = SafeArea::current().anchor(Anchor::BottomRight, GRAPH_SIZE, Vec2::new(0.0, CLOCK_HEIGHT + 6.0));
+    draw_rectangle(x, y, GRAPH_SIZE.x, GRAPH_SIZE.y, Color::new(0.0, 0.0, 0.0, 0.75));
+    draw_rectangle_lines(x, y, GRAPH_SIZE.x, GRAPH_SIZE.y, 1.0, GHOST_COLOR);
+
+    let title = if debug.prediction_enabled() { "NETCODE (prediction on)" } else { "NETCODE (prediction OFF)" };
+    draw_text(title, x + 8.0, y + 18.0, 16.0, if debug.prediction_enabled() { WHITE } else { ORANGE });
+    let stats = match (debug.latest_error(), debug.average_error(), debug.max_error()) {
+        (Some(latest), Some(average), Some(max)) => format!("err {:.1}  avg {:.1}  max {:.1}", latest, average, max),
+        _ => "Waiting for snapshots".to_string(),
+    };
+    draw_text(&stats, x + 8.0, y + 36.0, 15.0, LIGHTGRAY);
+
+    // Plot area below the text; newest sample on the right
+    let plot = Rect::new(x + 8.0, y + 44.0, GRAPH_SIZE.x - 16.0, GRAPH_SIZE.y - 52.0);
+    draw_line(plot.x, plot.bottom(), plot.right(), plot.bottom(), 1.0, DARKGRAY);
+    let scale = debug.max_error().unwrap_or(0.0).max(MIN_GRAPH_SCALE);
+    let step = plot.w / (ERROR_HISTORY_LEN - 1) as f32;
+    let count = debug.errors().count();
+    let points: Vec<Vec2> = debug
+        .errors()
+        .enumerate()
+        .map(|(i, error)| {
+            let slot = ERROR_HISTORY_LEN - count + i;
+            Vec2::new(plot.x + slot as f32 * step, plot.bottom() - (error / scale).min(1.0) * plot.h)
+        })
+        .collect();
+    for pair in points.windows(2) {
+        draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, 1.5, GHOST_COLOR);
+    }
+    draw_text(&format!("{:.0}", scale), plot.x + 2.0, plot.y + 10.0, 12.0, GRAY);
+}
@@ -0,0 +1,179 @@
+// Network Map Search - Search box for the satellite network map
+// Filters satellites with a SatelliteQuery and jumps the map (or camera) to a result
+
+use macroquad::prelude::*;
+
+use crate::systems::{EntityId, SatelliteQuery, World};
+
+/// Longest query the box accepts
+const MAX_QUERY_LENGTH: usize = 40;
+
+/// Jump requested with Enter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchJump {
+    pub satellite_id: EntityId,
+    pub move_camera: bool, // Shift+Enter also pans the main camera
+}
+
+/// Search box state for the network map
+#[derive(Debug, Clone, Default)]
+pub struct NetworkMapSearch {
+    text: String,
+    query: SatelliteQuery,
+    focused: bool,
+    results: Vec<EntityId>,
+    selected: usize,
+    map_focus: Option<EntityId>, // Satellite the map is centered on
+}
+
+impl NetworkMapSearch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Start typing (drops characters typed before the box had focus)
+    pub fn focus(&mut self) {
+        while get_char_pressed().is_some() {}
+        self.focused = true;
+    }
+
+    pub fn unfocus(&mut self) {
+        self.focused = false;
+    }
+
+    /// An active filter (the satellite list shows only results)
+    pub fn is_filtering(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    pub fn results(&self) -> &[EntityId] {
+        &self.results
+    }
+
+    pub fn selected(&self) -> Option<EntityId> {
+        self.results.get(self.selected).copied()
+    }
+
+    pub fn is_match(&self, id: EntityId) -> bool {
+        self.is_filtering() && self.results.contains(&id)
+    }
+
+    /// World point the map should be centered on (None = the default view)
+    pub fn map_center(&self, world: &World) -> Option<Vec2> {
+        self.map_focus.and_then(|id| world.get_satellite(id)).map(|s| s.position())
+    }
+
+    pub fn clear_map_focus(&mut self) {
+        self.map_focus = None;
+    }
+
+    /// Re-run the query against the current world (satellites come and go)
+    pub fn refresh(&mut self, world: &World) {
+        self.results = self.query.search(world);
+        if self.selected >= self.results.len() {
+            self.selected = 0;
+        }
+        if self.map_focus.is_some_and(|id| world.get_satellite(id).is_none()) {
+            self.map_focus = None;
+        }
+    }
+
+    fn set_text(&mut self, text: String, world: &World) {
+        self.text = text;
+        self.query = SatelliteQuery::parse(&self.text);
+        self.selected = 0;
+        self.refresh(world);
+    }
+
+    /// Handle keyboard input while focused; "/" focuses the box
+    /// Returns a jump when Enter is pressed on a result
+    pub fn handle_input(&mut self, world: &World) -> Option<SearchJump> {
+        if !self.focused {
+            if is_key_pressed(KeyCode::Slash) {
+                self.focus();
+            }
+            return None;
+        }
+
+        if is_key_pressed(KeyCode::Escape) {
+            self.focused = false;
+            return None;
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            let mut text = self.text.clone();
+            text.pop();
+            self.set_text(text, world);
+        }
+        if is_key_pressed(KeyCode::Down) && !self.results.is_empty() {
+            self.selected = (self.selected + 1) % self.results.len();
+        }
+        if is_key_pressed(KeyCode::Up) && !self.results.is_empty() {
+            self.selected = (self.selected + self.results.len() - 1) % self.results.len();
+        }
+
+        // The char queue pops the most recent character first
+        let mut typed = Vec::new();
+        while let Some(ch) = get_char_pressed() {
+            typed.push(ch);
+        }
+        let mut text = self.text.clone();
+        for ch in typed.into_iter().rev() {
+            if !ch.is_control() && text.len() < MAX_QUERY_LENGTH {
+                text.push(ch);
+            }
+        }
+        if text != self.text {
+            self.set_text(text, world);
+        }
+
+        if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::KpEnter) {
+            // Enter with nothing selected recenters the map on its default view
+            self.map_focus = self.selected();
+            let satellite_id = self.map_focus?;
+            let move_camera = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+            return Some(SearchJump { satellite_id, move_camera });
+        }
+        None
+    }
+
+    /// True if a screen point is inside the box drawn at (x, y, width)
+    pub fn contains(&self, point: Vec2, x: f32, y: f32, width: f32) -> bool {
+        Rect::new(x, y, width, 24.0).contains(point)
+    }
+
+    /// Draw the search box and result count
+    pub fn draw(&self, x: f32, y: f32, width: f32) {
+        let border = if self.focused {
+            Color::new(1.0, 1.0, 0.0, 0.9)
+        } else {
+            Color::new(0.0, 1.0, 0.0, 0.6)
+        };
+        draw_rectangle(x, y, width, 24.0, Color::new(0.0, 0.0, 0.0, 0.85));
+        draw_rectangle_lines(x, y, width, 24.0, 2.0, border);
+
+        let (text, color) = if self.text.is_empty() && !self.focused {
+            ("/ to search satellites".to_string(), GRAY)
+        } else if self.focused && (get_time() * 2.0) as i64 % 2 == 0 {
+            (format!("{}_", self.text), WHITE)
+        } else {
+            (self.text.clone(), WHITE)
+        };
+        draw_text(&text, x + 6.0, y + 17.0, 16.0, color);
+
+        if self.is_filtering() {
+            let count = match self.results.len() {
+                0 => "No matches".to_string(),
+                n => format!("{} of {} - Enter to jump", self.selected + 1, n),
+            };
+            draw_text(&count, x, y + 40.0, 14.0, Color::new(0.7, 0.7, 0.7, 1.0));
+        }
+    }
+}
@@ -0,0 +1,164 @@
+// Notifications - Actionable HUD notices stacked at the bottom left, each with Jump and dismiss buttons
+// Any system can post one with an entity to jump to; fuel network anomalies are the first source
+
+use macroquad::prelude::*;
+
+use crate::systems::entity_picker::EntityTarget;
+use crate::ui::{Anchor, SafeArea};
+
+/// Time a notification stays up unless dismissed (seconds)
+const NOTIFICATION_DURATION: f32 = 12.0;
+/// Most notifications shown at once (oldest are dropped)
+const MAX_NOTIFICATIONS: usize = 4;
+const CARD_SIZE: Vec2 = Vec2::new(440.0, 30.0);
+const CARD_SPACING: f32 = 4.0;
+const BUTTON_WIDTH: f32 = 48.0;
+
+struct Notification {
+    text: String,
+    color: Color,
+    target: Option<EntityTarget>, // What the Jump button shows
+    remaining: f32,
+}
+
+/// Where one notification and its buttons sit on screen
+struct CardLayout {
+    card: Rect,
+    jump: Option<Rect>,
+    close: Rect,
+}
+
+/// Stack of notifications the player can act on
+pub struct NotificationCenter {
+    notifications: Vec<Notification>,
+}
+
+impl NotificationCenter {
+    pub fn new() -> Self {
+        NotificationCenter { notifications: Vec::new() }
+    }
+
+    /// Post a notification; the same text again just restarts its timer
+    pub fn push(&mut self, text: impl Into<String>, color: Color, target: Option<EntityTarget>) {
+        let text = text.into();
+        self.notifications.retain(|notification| notification.text != text);
+        if self.notifications.len() >= MAX_NOTIFICATIONS {
+            self.notifications.remove(0);
+        }
+        self.notifications.push(Notification { text, color, target, remaining: NOTIFICATION_DURATION });
+    }
+
+    /// Age notifications and drop expired ones
+    pub fn update(&mut self, delta_time: f32) {
+        for notification in &mut self.notifications {
+            notification.remaining -= delta_time;
+        }
+        self.notifications.retain(|notification| notification.remaining > 0.0);
+    }
+
+    pub fn len(&self) -> usize {
+        self.notifications.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.notifications.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.notifications.clear();
+    }
+
+    /// Newest at the bottom, stacking upward
+    fn layout(&self) -> Vec<CardLayout> {
+        let stack = Vec2::new(CARD_SIZE.x, self.notifications.len() as f32 * (CARD_SIZE.y + CARD_SPACING));
+        let Vec2 { x, y } = SafeArea::current().anchor(Anchor::BottomLeft, stack, Vec2::new(0.0, 60.0));
+        self.notifications
+            .iter()
+            .enumerate()
+            .map(|(i, notification)| {
+                let card = Rect::new(x, y + i as f32 * (CARD_SIZE.y + CARD_SPACING), CARD_SIZE.x, CARD_SIZE.y);
+                let close = Rect::new(card.right() - CARD_SIZE.y, card.y, CARD_SIZE.y, CARD_SIZE.y);
+                let jump = notification.target.map(|_| Rect::new(close.x - BUTTON_WIDTH - 4.0, card.y + 4.0, BUTTON_WIDTH, CARD_SIZE.y - 8.0));
+                CardLayout { card, jump, close }
+            })
+            .collect()
+    }
+
+    /// Whether the mouse is over a notification (so clicks don't also pick in the world)
+    pub fn contains_mouse(&self) -> bool {
+        let mouse = Vec2::from(mouse_position());
+        self.layout().iter().any(|layout| layout.card.contains(mouse))
+    }
+
+    /// Handle a click: dismiss with ×, or return the entity a Jump button points at (call once per frame)
+    pub fn handle_mouse(&mut self) -> Option<EntityTarget> {
+        if !is_mouse_button_pressed(MouseButton::Left) {
+            return None;
+        }
+        let mouse = Vec2::from(mouse_position());
+        let layouts = self.layout();
+        let index = layouts.iter().position(|layout| layout.card.contains(mouse))?;
+        if layouts[index].close.contains(mouse) {
+            self.notifications.remove(index);
+            return None;
+        }
+        if layouts[index].jump.is_some_and(|jump| jump.contains(mouse)) {
+            return self.notifications.remove(index).target;
+        }
+        None
+    }
+
+    /// Draw the stack (call in screen space)
+    pub fn draw(&self) {
+        let mouse = Vec2::from(mouse_position());
+        for (notification, layout) in self.notifications.iter().zip(self.layout()) {
+            let alpha = notification.remaining.clamp(0.0, 1.0);
+            let color = Color::new(notification.color.r, notification.color.g, notification.color.b, alpha);
+            let card = layout.card;
+            draw_rectangle(card.x, card.y, card.w, card.h, Color::new(0.0, 0.0, 0.0, 0.75 * alpha));
+            draw_rectangle_lines(card.x, card.y, card.w, card.h, 1.5, color);
+            draw_text(&notification.text, card.x + 8.0, card.y + 20.0, 16.0, color);
+
+            if let Some(jump) = layout.jump {
+                let fill = if jump.contains(mouse) { 0.45 } else { 0.25 };
+                draw_rectangle(jump.x, jump.y, jump.w, jump.h, Color::new(fill, fill, fill, alpha));
+                draw_text("Jump", jump.x + 8.0, jump.y + 16.0, 16.0, Color::new(1.0, 1.0, 1.0, alpha));
+            }
+            let close = layout.close;
+            let close_color = if close.contains(mouse) { Color::new(1.0, 1.0, 1.0, alpha) } else { Color::new(0.6, 0.6, 0.6, alpha) };
+            draw_text("x", close.x + 10.0, close.y + 20.0, 20.0, close_color);
+        }
+    }
+}
+
+impl Default for NotificationCenter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notifications_dedupe_and_expire() {
+        let mut center = NotificationCenter::new();
+        center.push("Satellite 1001 is out of fuel", RED, Some(EntityTarget::Satellite(1001)));
+        center.update(NOTIFICATION_DURATION - 1.0);
+        // Posting it again restarts its timer rather than stacking a copy
+        center.push("Satellite 1001 is out of fuel", RED, Some(EntityTarget::Satellite(1001)));
+        assert_eq!(center.len(), 1);
+        center.update(2.0);
+        assert_eq!(center.len(), 1);
+
+        for i in 0..MAX_NOTIFICATIONS + 1 {
+            center.push(format!("notice {}", i), WHITE, None);
+        }
+        assert_eq!(center.len(), MAX_NOTIFICATIONS);
+        assert_eq!(center.notifications[0].text, "notice 1");
+
+        center.update(NOTIFICATION_DURATION);
+        assert!(center.is_empty());
+    }
+}
@@ -0,0 +1,123 @@
+// Player List - Connected clients with ping and last-seen time, for the host (4)
+// Up/Down pick a player, K kicks them and Shift+K bans their address for the rest of the session
+
+use std::net::SocketAddr;
+
+use macroquad::prelude::*;
+
+use crate::ui::{Anchor, SafeArea};
+
+const PANEL_WIDTH: f32 = 460.0;
+const LINE_HEIGHT: f32 = 18.0;
+const HEADER_COLOR: Color = Color::new(0.3, 0.9, 1.0, 1.0);
+/// Seconds without a packet before a row is shown as stalling
+const STALE_AFTER: f32 = 3.0;
+
+/// One connected client, as the host sees it this frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerRow {
+    pub player_id: u32,
+    pub name: String,
+    pub addr: SocketAddr,
+    pub rtt: f32,       // Seconds, from snapshot acks
+    pub last_seen: f32, // Seconds since their last packet
+}
+
+/// What the host asked to do to the selected player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerListAction {
+    Kick(u32),
+    Ban(u32),
+}
+
+/// Player list state
+#[derive(Debug, Clone, Default)]
+pub struct PlayerListPanel {
+    visible: bool,
+    selected: usize,
+}
+
+impl PlayerListPanel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    /// List keys while the panel is open (`rows` sorted as drawn)
+    pub fn handle_input(&mut self, rows: &[PlayerRow]) -> Option<PlayerListAction> {
+        if !self.visible {
+            return None;
+        }
+        let count = rows.len();
+        self.selected = self.selected.min(count.saturating_sub(1));
+        if is_key_pressed(KeyCode::Down) && count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+        if is_key_pressed(KeyCode::Up) && count > 0 {
+            self.selected = (self.selected + count - 1) % count;
+        }
+        if is_key_pressed(KeyCode::K) {
+            let player_id = rows.get(self.selected)?.player_id;
+            let shift = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+            return Some(if shift { PlayerListAction::Ban(player_id) } else { PlayerListAction::Kick(player_id) });
+        }
+        None
+    }
+
+    /// Host line, one line per client and the key hints, centred over the game (call in screen space)
+    pub fn draw(&self, host_name: &str, rows: &[PlayerRow], max_players: u32, banned: usize) {
+        if !self.visible {
+            return;
+        }
+
+        let mut lines: Vec<(String, Color)> = vec![
+            (format!("PLAYERS {}/{}", rows.len() + 1, max_players), HEADER_COLOR),
+            ("  ID  Name              Address                Ping   Seen".to_string(), LIGHTGRAY),
+            (format!("   0  {:<16}  (host)", host_name), WHITE),
+        ];
+        for (i, row) in rows.iter().enumerate() {
+            let marker = if i == self.selected { ">" } else { " " };
+            let color = if row.last_seen > STALE_AFTER {
+                ORANGE
+            } else if i == self.selected {
+                WHITE
+            } else {
+                LIGHTGRAY
+            };
+            lines.push((
+                format!(
+                    "{}{:>3}  {:<16}  {:<21}  {:>4.0}ms  {:>4.1}s",
+                    marker,
+                    row.player_id,
+                    row.name,
+                    row.addr.to_string(),
+                    row.rtt * 1000.0,
+                    row.last_seen
+                ),
+                color,
+            ));
+        }
+        if rows.is_empty() {
+            lines.push(("  No one else connected".to_string(), GRAY));
+        }
+        if banned > 0 {
+            lines.push((format!("{} banned address{}", banned, if banned == 1 { "" } else { "es" }), GRAY));
+        }
+        lines.push(("Up/Down pick, K kick, Shift+K ban address, 4 close".to_string(), GRAY));
+
+        let height = lines.len() as f32 * LINE_HEIGHT + 12.0;
+        let Vec2 { x, y } = SafeArea::current().anchor(Anchor::Center, Vec2::new(PANEL_WIDTH, height), Vec2::ZERO);
+        draw_rectangle(x, y, PANEL_WIDTH, height, Color::new(0.0, 0.0, 0.0, 0.75));
+        draw_rectangle_lines(x, y, PANEL_WIDTH, height, 1.0, HEADER_COLOR);
+        for (i, (text, color)) in lines.iter().enumerate() {
+            draw_text(text, x + 8.0, y + 20.0 + i as f32 * LINE_HEIGHT, 15.0, *color);
+        }
+    }
+}
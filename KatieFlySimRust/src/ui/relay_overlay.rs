@@ -0,0 +1,51 @@
+// Relay Overlay - The relay chain carrying remote control, on the network map and as a status line
+// Links fade from green to red as the weakest hop stretches toward the edge of range
+
+use macroquad::prelude::*;
+
+use crate::systems::{ControlOrigin, RelayChain, RemoteControl};
+use crate::ui::{Anchor, SafeArea};
+
+/// Green for a solid chain, red for one barely holding
+fn quality_color(quality: f32) -> Color {
+    let quality = quality.clamp(0.0, 1.0);
+    Color::new(1.0 - quality, 0.4 + 0.6 * quality, 0.3, 0.95)
+}
+
+/// Chain links over the network map, with the latency by the controlled rocket
+pub fn draw_map_chain(chain: &RelayChain, world_to_map: impl Fn(Vec2) -> Vec2) {
+    let color = quality_color(chain.quality);
+    let points: Vec<Vec2> = chain.points.iter().map(|point| world_to_map(*point)).collect();
+    for pair in points.windows(2) {
+        draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, 3.0, color);
+    }
+    for relay in &points[1..points.len().saturating_sub(1)] {
+        draw_circle_lines(relay.x, relay.y, 6.0, 1.5, color);
+    }
+    if let Some(end) = points.last() {
+        draw_text(&format!("{:.0} ms", chain.latency * 1000.0), end.x + 10.0, end.y + 14.0, 14.0, color);
+    }
+}
+
+/// "REMOTE" line at the top of the screen while flying a rocket over the relays
+pub fn draw_remote_status(remote: &RemoteControl) {
+    let chain = remote.chain();
+    let origin = match chain.origin {
+        ControlOrigin::Craft(id) => format!("rocket {}", id),
+        ControlOrigin::HomeBase(_) => "home base".to_string(),
+    };
+    let text = format!(
+        "REMOTE: rocket {} from {} via {} relays  |  {:.0} ms  |  link {:.0}%  (Shift+N returns)",
+        remote.rocket_id(),
+        origin,
+        chain.relays.len(),
+        chain.latency * 1000.0,
+        chain.quality * 100.0
+    );
+    let dims = measure_text(&text, None, 18, 1.0);
+    let size = Vec2::new(dims.width + 20.0, 28.0);
+    let Vec2 { x, y } = SafeArea::current().anchor(Anchor::TopCenter, size, Vec2::new(0.0, 40.0));
+    draw_rectangle(x, y, size.x, size.y, Color::new(0.0, 0.0, 0.0, 0.7));
+    draw_rectangle_lines(x, y, size.x, size.y, 1.0, quality_color(chain.quality));
+    draw_text(&text, x + 10.0, y + 19.0, 18.0, WHITE);
+}
@@ -0,0 +1,120 @@
+// Rocket Transfer Readout - Progress of a rocket-to-rocket fuel transfer while the transfer key is held
+// Shows the recipient's tank filling, or why fuel can't flow yet (too far, too fast, nothing to give)
+
+use macroquad::prelude::*;
+
+use crate::game_constants::GameConstants;
+use crate::systems::{EntityId, World};
+use crate::ui::{Anchor, SafeArea};
+
+const READOUT_SIZE: Vec2 = Vec2::new(360.0, 52.0);
+const TRANSFER_COLOR: Color = Color::new(1.0, 0.5, 0.7, 1.0); // Matches the rocket-to-rocket beam
+
+#[derive(Debug, Clone, PartialEq)]
+enum TransferStatus {
+    Flowing { recipient_id: EntityId, fuel: f32, max_fuel: f32 },
+    Blocked(String),
+}
+
+/// What the held transfer key is doing, for the readout under the rocket
+#[derive(Debug, Default)]
+pub struct RocketTransferReadout {
+    status: Option<TransferStatus>,
+    given: f32, // Fuel passed since the key went down (only known where the transfer runs)
+}
+
+impl RocketTransferReadout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call every frame the key is held, with the fuel the donor passed this frame
+    /// (clients pass 0 and follow the recipient's tank in the host's snapshots)
+    pub fn hold(&mut self, world: &World, donor_id: EntityId, recipient_id: Option<EntityId>, moved: f32) {
+        self.given += moved;
+        self.status = Some(match recipient_id {
+            None => TransferStatus::Blocked(format!("No rocket within {:.0}", GameConstants::ROCKET_FUEL_TRANSFER_RANGE)),
+            Some(recipient_id) => match (world.check_rocket_fuel_transfer(donor_id, recipient_id), world.get_rocket(recipient_id)) {
+                (Ok(()), Some(recipient)) => {
+                    TransferStatus::Flowing { recipient_id, fuel: recipient.current_fuel(), max_fuel: recipient.max_fuel() }
+                }
+                (Err(e), _) => TransferStatus::Blocked(e),
+                (Ok(()), None) => TransferStatus::Blocked("Rocket is gone".to_string()),
+            },
+        });
+    }
+
+    /// The key was let go
+    pub fn release(&mut self) {
+        self.status = None;
+        self.given = 0.0;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status.is_some()
+    }
+
+    /// Recipient's tank as a bar, or the blocking reason (call in screen space)
+    pub fn draw(&self) {
+        self.draw_row(0);
+    }
+
+    /// Draw stacked above `row` other readouts (split screen shows one per player)
+    pub fn draw_row(&self, row: usize) {
+        let Some(status) = &self.status else {
+            return;
+        };
+        let offset = 110.0 + row as f32 * (READOUT_SIZE.y + 6.0);
+        let Vec2 { x, y } = SafeArea::current().anchor(Anchor::BottomCenter, READOUT_SIZE, Vec2::new(0.0, offset));
+        draw_rectangle(x, y, READOUT_SIZE.x, READOUT_SIZE.y, Color::new(0.0, 0.0, 0.0, 0.7));
+        draw_rectangle_lines(x, y, READOUT_SIZE.x, READOUT_SIZE.y, 1.5, TRANSFER_COLOR);
+
+        match status {
+            TransferStatus::Flowing { recipient_id, fuel, max_fuel } => {
+                let heading = if self.given > 0.0 {
+                    format!("Fuel to rocket {}: {:.1} given", recipient_id, self.given)
+                } else {
+                    format!("Fuel to rocket {}", recipient_id)
+                };
+                draw_text(&heading, x + 10.0, y + 20.0, 18.0, TRANSFER_COLOR);
+                let bar = Rect::new(x + 10.0, y + 30.0, READOUT_SIZE.x - 110.0, 12.0);
+                let fill = if *max_fuel > 0.0 { (fuel / max_fuel).clamp(0.0, 1.0) } else { 0.0 };
+                draw_rectangle(bar.x, bar.y, bar.w, bar.h, Color::new(0.2, 0.2, 0.2, 1.0));
+                draw_rectangle(bar.x, bar.y, bar.w * fill, bar.h, TRANSFER_COLOR);
+                draw_text(&format!("{:.0}/{:.0}", fuel, max_fuel), bar.right() + 10.0, bar.bottom(), 16.0, WHITE);
+            }
+            TransferStatus::Blocked(reason) => {
+                draw_text("Fuel transfer", x + 10.0, y + 20.0, 18.0, TRANSFER_COLOR);
+                draw_text(reason, x + 10.0, y + 42.0, 16.0, ORANGE);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::Rocket;
+
+    #[test]
+    fn test_readout_follows_recipient_and_blockers() {
+        let mut world = World::new();
+        let mut donor = Rocket::new(Vec2::new(5000.0, 0.0), Vec2::ZERO, WHITE, 1.0);
+        donor.set_fuel(20.0);
+        let donor_id = world.add_rocket(donor);
+        let recipient_id = world.add_rocket(Rocket::new(Vec2::new(5050.0, 0.0), Vec2::ZERO, WHITE, 1.0));
+
+        let mut readout = RocketTransferReadout::new();
+        let moved = world.transfer_fuel_rocket_to_rocket(donor_id, recipient_id, 0.5).unwrap();
+        readout.hold(&world, donor_id, Some(recipient_id), moved);
+        assert_eq!(readout.given, moved);
+        assert!(matches!(readout.status, Some(TransferStatus::Flowing { fuel, .. }) if fuel == moved));
+
+        readout.hold(&world, donor_id, None, 0.0);
+        assert!(matches!(readout.status, Some(TransferStatus::Blocked(_))));
+
+        readout.release();
+        assert!(!readout.is_active());
+        assert_eq!(readout.given, 0.0);
+    }
+}
@@ -0,0 +1,82 @@
+// Route Overlay - Planned route legs, per-leg delta-v and the fuel summary
+// Legs turn red when the plan needs more fuel than the rocket carries
+
+use macroquad::prelude::*;
+
+use crate::systems::RoutePlan;
+use crate::ui::mission_clock_display::CLOCK_HEIGHT;
+use crate::ui::{Anchor, Camera, SafeArea};
+
+/// Dash length along a leg (pixels)
+const DASH: f32 = 10.0;
+
+const ROUTE_COLOR: Color = Color::new(0.4, 0.9, 1.0, 0.85);
+const SHORT_COLOR: Color = Color::new(1.0, 0.3, 0.25, 0.9);
+
+fn route_color(plan: &RoutePlan) -> Color {
+    if plan.exceeds_fuel() {
+        SHORT_COLOR
+    } else {
+        ROUTE_COLOR
+    }
+}
+
+fn draw_dashed_line(a: Vec2, b: Vec2, thickness: f32, color: Color) {
+    let length = a.distance(b);
+    if length <= 0.0 {
+        return;
+    }
+    let direction = (b - a) / length;
+    let mut t = 0.0;
+    while t < length {
+        let start = a + direction * t;
+        let end = a + direction * (t + DASH).min(length);
+        draw_line(start.x, start.y, end.x, end.y, thickness, color);
+        t += DASH * 2.0;
+    }
+}
+
+/// Legs in the main view with their delta-v (call in screen space)
+pub fn draw_route(plan: &RoutePlan, camera: &Camera) {
+    let color = route_color(plan);
+    for (i, leg) in plan.legs.iter().enumerate() {
+        let (from, to) = (camera.world_to_screen(leg.from), camera.world_to_screen(leg.to));
+        draw_dashed_line(from, to, 2.0, color);
+        let mid = (from + to) / 2.0;
+        draw_text(&format!("Leg {} dv {:.0}", i + 1, leg.delta_v()), mid.x + 8.0, mid.y - 8.0, 15.0, color);
+    }
+}
+
+/// Legs on the network map
+pub fn draw_map_route(plan: &RoutePlan, world_to_map: impl Fn(Vec2) -> Vec2) {
+    let color = route_color(plan);
+    for leg in &plan.legs {
+        draw_dashed_line(world_to_map(leg.from), world_to_map(leg.to), 1.5, color);
+    }
+}
+
+/// Per-leg burns and totals in the bottom-right corner, with a warning when fuel runs short
+pub fn draw_route_summary(plan: &RoutePlan) {
+    let mut lines: Vec<(String, Color)> = vec![("ROUTE PLAN".to_string(), WHITE)];
+    for (i, leg) in plan.legs.iter().enumerate() {
+        lines.push((
+            format!("Leg {}: {:.0} + {:.0} dv, {:.0}s, {:.1} fuel", i + 1, leg.departure_burn, leg.arrival_burn, leg.transfer_time, leg.fuel),
+            LIGHTGRAY,
+        ));
+    }
+    lines.push((format!("Total: {:.0} dv, {:.0}s", plan.total_delta_v, plan.total_time), WHITE));
+    lines.push((format!("Fuel: {:.1} needed / {:.1} aboard", plan.fuel_required, plan.fuel_available), route_color(plan)));
+    if plan.exceeds_fuel() {
+        lines.push((format!("Short by {:.1} fuel - refuel before departing", plan.fuel_required - plan.fuel_available), SHORT_COLOR));
+    }
+
+    let width = 330.0;
+    let line_height = 18.0;
+    let height = lines.len() as f32 * line_height + 12.0;
+    let Vec2 { x, y } = SafeArea::current().anchor(Anchor::BottomRight, Vec2::new(width, height), Vec2::new(0.0, CLOCK_HEIGHT + 6.0));
+    draw_rectangle(x, y, width, height, Color::new(0.0, 0.0, 0.0, 0.7));
+    draw_rectangle_lines(x, y, width, height, 1.0, route_color(plan));
+    for (i, (text, color)) in lines.iter().enumerate() {
+        draw_text(text, x + 8.0, y + 20.0 + i as f32 * line_height, 15.0, *color);
+    }
+}
@@ -0,0 +1,143 @@
+// Safe Area - Where screen-space UI may go, whatever the window's aspect ratio
+// HUD panels, popups and the network map anchor to it instead of assuming a 16:9 screen
+
+use macroquad::prelude::*;
+
+/// Gap kept between the UI and the window edge
+pub const SAFE_MARGIN: f32 = 10.0;
+/// Wider windows (32:9 and up) keep the UI in a centered 21:9 band so it stays in view
+pub const MAX_UI_ASPECT: f32 = 21.0 / 9.0;
+
+/// Network map size on a large enough screen, and the satellite list beside it
+const NETWORK_MAP_SIZE: f32 = 700.0;
+const NETWORK_LIST_WIDTH: f32 = 200.0;
+const NETWORK_LIST_GAP: f32 = 20.0;
+
+/// A point on the safe area's edge that UI hangs from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    TopLeft,
+    TopCenter,
+    TopRight,
+    Center,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+/// The part of the window UI is laid out in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SafeArea {
+    pub rect: Rect,
+}
+
+impl SafeArea {
+    /// Safe area of the current window
+    pub fn current() -> Self {
+        Self::for_screen(screen_width(), screen_height())
+    }
+
+    /// Safe area of a `width` x `height` window
+    pub fn for_screen(width: f32, height: f32) -> Self {
+        let usable_width = width.min(height * MAX_UI_ASPECT);
+        let x = (width - usable_width) / 2.0 + SAFE_MARGIN;
+        let rect = Rect::new(x, SAFE_MARGIN, (usable_width - 2.0 * SAFE_MARGIN).max(0.0), (height - 2.0 * SAFE_MARGIN).max(0.0));
+        SafeArea { rect }
+    }
+
+    pub fn center(&self) -> Vec2 {
+        self.rect.center()
+    }
+
+    /// `size` shrunk to fit inside the safe area
+    pub fn fit(&self, size: Vec2) -> Vec2 {
+        size.min(self.rect.size())
+    }
+
+    /// Top-left corner for something of `size` hung from `anchor`, moved by `offset` (positive is inward)
+    pub fn anchor(&self, anchor: Anchor, size: Vec2, offset: Vec2) -> Vec2 {
+        let r = self.rect;
+        let (left, right) = (r.x + offset.x, r.right() - size.x - offset.x);
+        let (top, bottom) = (r.y + offset.y, r.bottom() - size.y - offset.y);
+        let middle = Vec2::new(r.center().x - size.x / 2.0 + offset.x, r.center().y - size.y / 2.0 + offset.y);
+        match anchor {
+            Anchor::TopLeft => Vec2::new(left, top),
+            Anchor::TopCenter => Vec2::new(middle.x, top),
+            Anchor::TopRight => Vec2::new(right, top),
+            Anchor::Center => middle,
+            Anchor::BottomLeft => Vec2::new(left, bottom),
+            Anchor::BottomCenter => Vec2::new(middle.x, bottom),
+            Anchor::BottomRight => Vec2::new(right, bottom),
+        }
+    }
+
+    /// A centered popup of at most `width` x `height`
+    pub fn popup(&self, width: f32, height: f32) -> Rect {
+        let size = self.fit(Vec2::new(width, height));
+        let position = self.anchor(Anchor::Center, size, Vec2::ZERO);
+        Rect::new(position.x, position.y, size.x, size.y)
+    }
+
+    /// The square network map and the satellite list to its right, centered together
+    pub fn network_map(&self) -> (Rect, Rect) {
+        let beside = NETWORK_LIST_WIDTH + NETWORK_LIST_GAP;
+        let size = NETWORK_MAP_SIZE.min(self.rect.h).min(self.rect.w - beside).max(0.0);
+        let position = self.anchor(Anchor::Center, Vec2::new(size + beside, size), Vec2::ZERO);
+        let map = Rect::new(position.x, position.y, size, size);
+        let list = Rect::new(map.right() + NETWORK_LIST_GAP, map.y, NETWORK_LIST_WIDTH, size);
+        (map, list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 16:9, 21:9, 32:9, 16:10 and 4:3 windows
+    const SCREENS: [(f32, f32); 5] = [(1920.0, 1080.0), (2560.0, 1080.0), (5120.0, 1440.0), (1680.0, 1050.0), (1024.0, 768.0)];
+
+    fn inside(outer: Rect, inner: Rect) -> bool {
+        inner.x >= outer.x - 0.01 && inner.y >= outer.y - 0.01 && inner.right() <= outer.right() + 0.01 && inner.bottom() <= outer.bottom() + 0.01
+    }
+
+    #[test]
+    fn test_ultrawide_keeps_ui_in_a_centered_band() {
+        // 21:9 uses the whole width
+        assert_eq!(SafeArea::for_screen(2520.0, 1080.0).rect, Rect::new(10.0, 10.0, 2500.0, 1060.0));
+        // 32:9 gets a centered 21:9 band
+        let area = SafeArea::for_screen(5120.0, 1440.0);
+        assert!((area.rect.w + 2.0 * SAFE_MARGIN - 1440.0 * MAX_UI_ASPECT).abs() < 0.5);
+        assert!((area.center().x - 2560.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_anchors_stay_inside() {
+        for (width, height) in SCREENS {
+            let area = SafeArea::for_screen(width, height);
+            let size = Vec2::new(280.0, 200.0);
+            for anchor in [Anchor::TopLeft, Anchor::TopCenter, Anchor::TopRight, Anchor::Center, Anchor::BottomLeft, Anchor::BottomCenter, Anchor::BottomRight] {
+                let position = area.anchor(anchor, size, Vec2::ZERO);
+                assert!(inside(area.rect, Rect::new(position.x, position.y, size.x, size.y)), "{:?} at {}x{}", anchor, width, height);
+            }
+        }
+        let area = SafeArea::for_screen(1920.0, 1080.0);
+        assert_eq!(area.anchor(Anchor::TopRight, Vec2::new(100.0, 50.0), Vec2::new(5.0, 5.0)), Vec2::new(1805.0, 15.0));
+    }
+
+    #[test]
+    fn test_popups_and_network_map_fit_every_aspect() {
+        for (width, height) in SCREENS {
+            let area = SafeArea::for_screen(width, height);
+            let popup = area.popup(900.0, 700.0);
+            assert!(inside(area.rect, popup), "popup at {}x{}", width, height);
+
+            let (map, list) = area.network_map();
+            assert!(inside(area.rect, map) && inside(area.rect, list), "network map at {}x{}", width, height);
+            assert_eq!(map.w, map.h);
+            assert!(map.w >= 500.0);
+        }
+        // Small windows shrink the map instead of clipping it
+        let (map, _) = SafeArea::for_screen(800.0, 600.0).network_map();
+        assert!(map.w < NETWORK_MAP_SIZE);
+    }
+}
@@ -0,0 +1,130 @@
+// Satellite Batch Bar - Command buttons along the bottom of the network map while satellites are selected
+// Each button applies to the whole selection; Maintenance steps through the presets, Fuel to hub waits for a hub click
+
+use macroquad::prelude::*;
+
+use crate::systems::{BatchCommand, SatelliteRole, SatelliteSelection, MAINTENANCE_PRESETS};
+
+const BAR_HEIGHT: f32 = 26.0;
+const BAR_COLOR: Color = Color::new(0.3, 0.9, 1.0, 1.0);
+/// Clear of the instructions line at the bottom of the map
+const BOTTOM_MARGIN: f32 = 32.0;
+
+/// What a bar click asks the network map to do
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BatchAction {
+    Command(BatchCommand),
+    PickHub, // Next satellite clicked becomes the hub
+    ClearSelection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Slot {
+    Role(SatelliteRole),
+    Maintenance,
+    Mark,
+    Unmark,
+    Deorbit,
+    Hub,
+    Clear,
+}
+
+const SLOTS: [Slot; 9] = [
+    Slot::Role(SatelliteRole::Tanker),
+    Slot::Role(SatelliteRole::Relay),
+    Slot::Role(SatelliteRole::Collector),
+    Slot::Maintenance,
+    Slot::Mark,
+    Slot::Unmark,
+    Slot::Deorbit,
+    Slot::Hub,
+    Slot::Clear,
+];
+
+/// Batch command buttons for the network map
+#[derive(Debug, Clone)]
+pub struct SatelliteBatchBar {
+    next_preset: usize, // Index into MAINTENANCE_PRESETS the next Maintenance click applies
+}
+
+impl SatelliteBatchBar {
+    pub fn new() -> Self {
+        // Start on the preset after Standard, which new satellites already use
+        SatelliteBatchBar { next_preset: 2 }
+    }
+
+    fn slot_rects(map_rect: Rect) -> impl Iterator<Item = (Slot, Rect)> {
+        let width = (map_rect.w - 20.0) / SLOTS.len() as f32;
+        let y = map_rect.bottom() - BOTTOM_MARGIN - BAR_HEIGHT;
+        SLOTS
+            .into_iter()
+            .enumerate()
+            .map(move |(i, slot)| (slot, Rect::new(map_rect.x + 10.0 + i as f32 * width, y, width - 4.0, BAR_HEIGHT)))
+    }
+
+    /// Whether `point` is on the bar (clicks there don't reach the map)
+    pub fn contains(&self, map_rect: Rect, point: Vec2) -> bool {
+        Self::slot_rects(map_rect).any(|(_, rect)| rect.contains(point))
+    }
+
+    /// Action for a left click at `point`, if it hit a button
+    pub fn click(&mut self, map_rect: Rect, point: Vec2) -> Option<BatchAction> {
+        let (slot, _) = Self::slot_rects(map_rect).find(|(_, rect)| rect.contains(point))?;
+        Some(match slot {
+            Slot::Role(role) => BatchAction::Command(BatchCommand::SetRole(role)),
+            Slot::Maintenance => {
+                let preset = MAINTENANCE_PRESETS[self.next_preset];
+                self.next_preset = (self.next_preset + 1) % MAINTENANCE_PRESETS.len();
+                BatchAction::Command(BatchCommand::SetMaintenance(preset))
+            }
+            Slot::Mark => BatchAction::Command(BatchCommand::Mark),
+            Slot::Unmark => BatchAction::Command(BatchCommand::Unmark),
+            Slot::Deorbit => BatchAction::Command(BatchCommand::Deorbit),
+            Slot::Hub => BatchAction::PickHub,
+            Slot::Clear => BatchAction::ClearSelection,
+        })
+    }
+
+    fn label(&self, slot: Slot) -> String {
+        match slot {
+            Slot::Role(role) => role.name().to_string(),
+            Slot::Maintenance => format!("maint: {}", MAINTENANCE_PRESETS[self.next_preset].name.to_lowercase()),
+            Slot::Mark => "mark".to_string(),
+            Slot::Unmark => "unmark".to_string(),
+            Slot::Deorbit => "deorbit".to_string(),
+            Slot::Hub => "fuel to hub".to_string(),
+            Slot::Clear => "clear".to_string(),
+        }
+    }
+
+    /// Selection count and the buttons, or the hub prompt while one is being picked
+    pub fn draw(&self, map_rect: Rect, selection: &SatelliteSelection, picking_hub: bool) {
+        if selection.is_empty() {
+            return;
+        }
+        let y = map_rect.bottom() - BOTTOM_MARGIN - BAR_HEIGHT;
+        let header = if picking_hub {
+            format!("{} SELECTED - click the hub satellite (ESC cancels)", selection.len())
+        } else {
+            format!("{} SELECTED", selection.len())
+        };
+        draw_text(&header, map_rect.x + 10.0, y - 6.0, 16.0, BAR_COLOR);
+
+        let mouse = Vec2::from(mouse_position());
+        for (slot, rect) in Self::slot_rects(map_rect) {
+            let active = picking_hub && slot == Slot::Hub;
+            let fill = if active || rect.contains(mouse) { 0.35 } else { 0.15 };
+            draw_rectangle(rect.x, rect.y, rect.w, rect.h, Color::new(0.0, fill, fill, 0.9));
+            draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 1.0, BAR_COLOR);
+            let label = self.label(slot);
+            let width = measure_text(&label, None, 13, 1.0).width;
+            draw_text(&label, rect.x + (rect.w - width) / 2.0, rect.y + 17.0, 13.0, WHITE);
+        }
+    }
+}
+
+impl Default for SatelliteBatchBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
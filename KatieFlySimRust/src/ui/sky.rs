@@ -0,0 +1,142 @@
+// Sky - Star field and nebula glow behind the world, laid out from the save's cosmetic seed
+// Purely visual: drawn in screen space with a little parallax, so each playthrough of a map gets its own sky
+// without touching anything the physics or the network sees. Seed 0 is the classic black sky
+
+use macroquad::prelude::*;
+
+use crate::systems::daily_challenge::SplitMix64;
+
+const STAR_COUNT: usize = 220;
+/// Screen-space tile the stars repeat over (wider than any window, so the repeat isn't noticeable)
+const STAR_TILE: f64 = 2048.0;
+/// How far the nearest star layer moves relative to the camera (the furthest barely moves)
+const MAX_PARALLAX: f64 = 0.08;
+/// Nebula colour palettes (pairs of glow colours); a seed picks one
+const NEBULA_PALETTES: [[Color; 2]; 4] = [
+    [Color::new(0.45, 0.15, 0.6, 1.0), Color::new(0.15, 0.3, 0.7, 1.0)],
+    [Color::new(0.7, 0.25, 0.2, 1.0), Color::new(0.8, 0.55, 0.2, 1.0)],
+    [Color::new(0.1, 0.5, 0.5, 1.0), Color::new(0.2, 0.6, 0.3, 1.0)],
+    [Color::new(0.6, 0.2, 0.45, 1.0), Color::new(0.25, 0.2, 0.65, 1.0)],
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Star {
+    tile_position: DVec2, // Within the tile
+    depth: f64,           // Parallax share, 0.0-1.0
+    size: f32,
+    color: Color,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Nebula {
+    screen_fraction: Vec2, // Center as a share of the window
+    radius: f32,           // Share of the window's larger side
+    color: Color,
+}
+
+/// The sky for one cosmetic seed (rebuilt when the seed changes)
+#[derive(Debug, Clone, Default)]
+pub struct Sky {
+    seed: u64,
+    stars: Vec<Star>,
+    nebulae: Vec<Nebula>,
+}
+
+impl Sky {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A seed for a new game (never 0, which means the classic look)
+    pub fn fresh_seed() -> u64 {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+        SplitMix64(nanos).next().max(1)
+    }
+
+    /// Match the world's seed, laying the sky out again if it changed
+    pub fn sync(&mut self, seed: u64) {
+        if seed == self.seed {
+            return;
+        }
+        self.seed = seed;
+        self.stars.clear();
+        self.nebulae.clear();
+        if seed == 0 {
+            return;
+        }
+
+        let mut rng = SplitMix64(seed);
+        for _ in 0..STAR_COUNT {
+            let tile_position = DVec2::new(rng.unit() as f64, rng.unit() as f64) * STAR_TILE;
+            let depth = (rng.unit() as f64).powi(2); // Mostly distant
+            let brightness = 0.35 + 0.65 * rng.unit();
+            let warmth = rng.unit() * 0.25 - 0.125;
+            let color = Color::new(brightness + warmth, brightness, brightness - warmth, 1.0);
+            self.stars.push(Star { tile_position, depth, size: 0.6 + 1.2 * depth as f32, color });
+        }
+
+        let palette = NEBULA_PALETTES[(rng.next() % NEBULA_PALETTES.len() as u64) as usize];
+        for i in 0..2 + rng.next() % 3 {
+            self.nebulae.push(Nebula {
+                screen_fraction: Vec2::new(rng.unit(), rng.unit()),
+                radius: 0.2 + 0.3 * rng.unit(),
+                color: palette[i as usize % palette.len()],
+            });
+        }
+    }
+
+    /// Draw behind the world (screen space, before setting the world camera)
+    /// `view_center` is the camera center in world coordinates (floating origin included), so rebases don't jump the stars
+    pub fn draw(&self, view_center: DVec2, zoom_level: f32) {
+        if self.seed == 0 {
+            return;
+        }
+        let (width, height) = (screen_width(), screen_height());
+
+        // Soft glow from stacked translucent discs
+        for nebula in &self.nebulae {
+            let center = nebula.screen_fraction * Vec2::new(width, height);
+            let radius = nebula.radius * width.max(height);
+            for ring in 0..6 {
+                let scale = 1.0 - ring as f32 * 0.15;
+                let color = Color::new(nebula.color.r, nebula.color.g, nebula.color.b, 0.025);
+                draw_circle(center.x, center.y, radius * scale, color);
+            }
+        }
+
+        let scroll = view_center / zoom_level.max(f32::EPSILON) as f64;
+        for star in &self.stars {
+            let parallax = star.depth * MAX_PARALLAX;
+            let x = (star.tile_position.x - scroll.x * parallax).rem_euclid(STAR_TILE) as f32;
+            let y = (star.tile_position.y - scroll.y * parallax).rem_euclid(STAR_TILE) as f32;
+            if x <= width && y <= height {
+                draw_circle(x, y, star.size, star.color);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sky() {
+        let (mut a, mut b, mut c) = (Sky::new(), Sky::new(), Sky::new());
+        a.sync(42);
+        b.sync(42);
+        c.sync(43);
+        assert_eq!(a.stars, b.stars);
+        assert_eq!(a.nebulae, b.nebulae);
+        assert_ne!(a.stars, c.stars);
+        assert_eq!(a.stars.len(), STAR_COUNT);
+
+        // Seed 0 is the plain black sky
+        a.sync(0);
+        assert!(a.stars.is_empty() && a.nebulae.is_empty());
+        assert_ne!(Sky::fresh_seed(), 0);
+    }
+}
@@ -0,0 +1,82 @@
+// Slider - Horizontal 0-100% slider for settings
+// Click or drag along the track; the new value is reported when the mouse is released
+
+use macroquad::prelude::*;
+
+const TRACK_HEIGHT: f32 = 6.0;
+const KNOB_RADIUS: f32 = 9.0;
+
+/// A draggable value from 0.0 to 1.0
+#[derive(Debug, Clone)]
+pub struct Slider {
+    value: f32,
+    dragging: bool,
+}
+
+impl Slider {
+    pub fn new(value: f32) -> Self {
+        Slider { value: value.clamp(0.0, 1.0), dragging: false }
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    /// Track the mouse over `rect`; returns the final value when a drag ends
+    pub fn handle_input(&mut self, rect: Rect) -> Option<f32> {
+        let mouse = Vec2::from(mouse_position());
+        let grab_area = Rect::new(rect.x - KNOB_RADIUS, rect.y, rect.w + 2.0 * KNOB_RADIUS, rect.h);
+        if is_mouse_button_pressed(MouseButton::Left) && grab_area.contains(mouse) {
+            self.dragging = true;
+        }
+        if !self.dragging {
+            return None;
+        }
+
+        self.value = Self::value_at(rect, mouse.x);
+        if is_mouse_button_down(MouseButton::Left) {
+            None
+        } else {
+            self.dragging = false;
+            Some(self.value)
+        }
+    }
+
+    /// Slider value for a mouse x over `rect`
+    fn value_at(rect: Rect, x: f32) -> f32 {
+        if rect.w <= 0.0 {
+            return 0.0;
+        }
+        ((x - rect.x) / rect.w).clamp(0.0, 1.0)
+    }
+
+    /// Track, knob and percentage (grayed out when `enabled` is false)
+    pub fn draw(&self, rect: Rect, enabled: bool) {
+        let color = if enabled { Color::new(0.8, 0.8, 1.0, 1.0) } else { GRAY };
+        let center_y = rect.y + rect.h / 2.0;
+        let knob_x = rect.x + rect.w * self.value;
+        draw_rectangle(rect.x, center_y - TRACK_HEIGHT / 2.0, rect.w, TRACK_HEIGHT, Color::new(0.3, 0.3, 0.3, 1.0));
+        draw_rectangle(rect.x, center_y - TRACK_HEIGHT / 2.0, knob_x - rect.x, TRACK_HEIGHT, color);
+        draw_circle(knob_x, center_y, KNOB_RADIUS, if self.dragging { WHITE } else { color });
+        draw_text(&format!("{:.0}%", self.value * 100.0), rect.right() + 16.0, center_y + 5.0, 17.0, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_follows_track_position() {
+        let track = Rect::new(100.0, 0.0, 200.0, 20.0);
+        assert_eq!(Slider::value_at(track, 100.0), 0.0);
+        assert_eq!(Slider::value_at(track, 150.0), 0.25);
+        assert_eq!(Slider::value_at(track, 500.0), 1.0);
+        assert_eq!(Slider::value_at(track, -50.0), 0.0);
+        assert_eq!(Slider::new(3.0).value(), 1.0);
+    }
+}
@@ -0,0 +1,174 @@
+// Sprite Batch - Draws many small repeated entities (satellites, bullets) as textured quads in a few meshes
+// One generated atlas holds every sprite, so a whole swarm costs a handful of draw calls instead of one per shape
+
+use std::cell::OnceCell;
+
+use macroquad::models::{Mesh, Vertex};
+use macroquad::prelude::*;
+
+/// Side of one atlas cell in pixels
+const CELL_SIZE: u16 = 32;
+/// Quads per mesh; macroquad clamps a draw call at 5000 indices (6 per quad)
+const MAX_QUADS_PER_MESH: usize = 800;
+/// Entities smaller than this on screen are batched; bigger ones keep their detailed vector drawing
+pub const BATCH_BELOW_PIXELS: f32 = 6.0;
+/// Batched sprites never shrink below this on screen so distant swarms stay visible
+const MIN_SPRITE_PIXELS: f32 = 2.0;
+
+/// Cells of the atlas, left to right
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sprite {
+    Disc,
+    Square,
+    Ring,
+}
+
+impl Sprite {
+    const ALL: [Sprite; 3] = [Sprite::Disc, Sprite::Square, Sprite::Ring];
+
+    /// Horizontal UV range of this cell
+    fn uv_range(self) -> (f32, f32) {
+        let index = Sprite::ALL.iter().position(|sprite| *sprite == self).unwrap() as f32;
+        let width = 1.0 / Sprite::ALL.len() as f32;
+        (index * width, (index + 1.0) * width)
+    }
+
+    /// Coverage (0-1) of the pixel at (x, y) in a cell, white so vertex colors tint it
+    fn coverage(self, x: u16, y: u16) -> f32 {
+        let center = CELL_SIZE as f32 / 2.0;
+        let distance = Vec2::new(x as f32 + 0.5 - center, y as f32 + 0.5 - center).length();
+        match self {
+            Sprite::Disc => (center - distance).clamp(0.0, 1.0),
+            Sprite::Square => 1.0,
+            Sprite::Ring => (1.5 - (distance - (center - 2.0)).abs()).clamp(0.0, 1.0),
+        }
+    }
+}
+
+thread_local! {
+    static ATLAS: OnceCell<Texture2D> = const { OnceCell::new() };
+}
+
+/// The sprite atlas, generated on first use (needs the graphics context)
+fn atlas() -> Texture2D {
+    ATLAS.with(|atlas| {
+        atlas
+            .get_or_init(|| {
+                let mut image = Image::gen_image_color(CELL_SIZE * Sprite::ALL.len() as u16, CELL_SIZE, BLANK);
+                for (cell, sprite) in Sprite::ALL.iter().enumerate() {
+                    for y in 0..CELL_SIZE {
+                        for x in 0..CELL_SIZE {
+                            let alpha = sprite.coverage(x, y);
+                            image.set_pixel(cell as u32 * CELL_SIZE as u32 + x as u32, y as u32, Color::new(1.0, 1.0, 1.0, alpha));
+                        }
+                    }
+                }
+                let texture = Texture2D::from_image(&image);
+                texture.set_filter(FilterMode::Linear);
+                texture
+            })
+            .clone()
+    })
+}
+
+/// Quads collected over a frame, drawn together by `flush`
+#[derive(Debug, Clone)]
+pub struct SpriteBatch {
+    zoom_level: f32, // World units per screen pixel
+    vertices: Vec<Vertex>,
+}
+
+impl SpriteBatch {
+    pub fn new(zoom_level: f32) -> Self {
+        SpriteBatch { zoom_level: zoom_level.max(f32::EPSILON), vertices: Vec::new() }
+    }
+
+    /// Whether something `world_size` across should be batched at this zoom rather than drawn in detail
+    pub fn should_batch(&self, world_size: f32) -> bool {
+        world_size / self.zoom_level < BATCH_BELOW_PIXELS
+    }
+
+    /// Queue a sprite `world_size` across centered at `center`
+    pub fn push(&mut self, sprite: Sprite, center: Vec2, world_size: f32, color: Color) {
+        let half = world_size.max(MIN_SPRITE_PIXELS * self.zoom_level) / 2.0;
+        let (u0, u1) = sprite.uv_range();
+        self.vertices.extend_from_slice(&[
+            Vertex::new(center.x - half, center.y - half, 0.0, u0, 0.0, color),
+            Vertex::new(center.x + half, center.y - half, 0.0, u1, 0.0, color),
+            Vertex::new(center.x + half, center.y + half, 0.0, u1, 1.0, color),
+            Vertex::new(center.x - half, center.y + half, 0.0, u0, 1.0, color),
+        ]);
+    }
+
+    pub fn len(&self) -> usize {
+        self.vertices.len() / 4
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Split the queued quads into meshes small enough for one draw call each
+    fn meshes(&self, texture: Option<Texture2D>) -> Vec<Mesh> {
+        self.vertices
+            .chunks(MAX_QUADS_PER_MESH * 4)
+            .map(|chunk| {
+                let indices = (0..chunk.len() as u16 / 4)
+                    .flat_map(|quad| {
+                        let base = quad * 4;
+                        [base, base + 1, base + 2, base, base + 2, base + 3]
+                    })
+                    .collect();
+                Mesh { vertices: chunk.to_vec(), indices, texture: texture.clone() }
+            })
+            .collect()
+    }
+
+    /// Draw everything queued (call in world space) and empty the batch
+    pub fn flush(&mut self) {
+        if self.vertices.is_empty() {
+            return;
+        }
+        for mesh in self.meshes(Some(atlas())) {
+            draw_mesh(&mesh);
+        }
+        self.vertices.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_splits_into_draw_call_sized_meshes() {
+        let mut batch = SpriteBatch::new(100.0);
+        assert!(batch.should_batch(12.0)); // A satellite is a fraction of a pixel this far out
+        assert!(!SpriteBatch::new(1.0).should_batch(12.0));
+
+        for i in 0..(MAX_QUADS_PER_MESH + 10) {
+            batch.push(Sprite::Disc, Vec2::new(i as f32, 0.0), 12.0, WHITE);
+        }
+        assert_eq!(batch.len(), MAX_QUADS_PER_MESH + 10);
+
+        let meshes = batch.meshes(None);
+        assert_eq!(meshes.len(), 2);
+        assert_eq!(meshes[0].indices.len(), MAX_QUADS_PER_MESH * 6);
+        assert!(meshes[0].indices.len() < 5000);
+        assert_eq!(meshes[1].vertices.len(), 40);
+        assert_eq!(*meshes[1].indices.iter().max().unwrap(), 39);
+
+        // Tiny sprites are grown to stay visible: 12 units at 100 units/pixel would be 0.12 px
+        let first = &meshes[0].vertices[..4];
+        assert_eq!(first[1].position.x - first[0].position.x, MIN_SPRITE_PIXELS * 100.0);
+    }
+
+    #[test]
+    fn test_atlas_cells() {
+        assert_eq!(Sprite::Disc.uv_range().0, 0.0);
+        assert_eq!(Sprite::Ring.uv_range().1, 1.0);
+        assert_eq!(Sprite::Disc.coverage(CELL_SIZE / 2, CELL_SIZE / 2), 1.0);
+        assert_eq!(Sprite::Disc.coverage(0, 0), 0.0);
+        assert_eq!(Sprite::Ring.coverage(CELL_SIZE / 2, CELL_SIZE / 2), 0.0); // Hollow middle
+    }
+}
@@ -0,0 +1,250 @@
+// Strategic View - Icon rendering of the main view once the camera is zoomed far out
+// Craft become screen-sized icons, nearby satellites merge into counted clusters with their orbits and
+// network links drawn, and bodies get labels, so the far-out view does the job of the network map
+
+use std::collections::HashMap;
+
+use macroquad::prelude::*;
+
+use crate::entities::{GameObject, Planet};
+use crate::game_constants::GameConstants;
+use crate::systems::{EntityId, OrbitTrails, TrailWindow, VehicleManager, World};
+use crate::ui::{arena_overlay, Camera};
+
+/// Zoom where icons start fading in over the sprites
+pub const STRATEGIC_ZOOM_START: f32 = 16.0;
+/// Zoom where the sprites are gone and only icons remain
+pub const STRATEGIC_ZOOM_FULL: f32 = 64.0;
+/// Satellites within one cell of this many screen pixels draw as a single cluster
+const CLUSTER_CELL_PIXELS: f32 = 24.0;
+/// Points per cluster orbit ellipse
+const ORBIT_POINTS: usize = 64;
+/// Trail points closer than this on screen are skipped (old orbits overlap many times over)
+const TRAIL_MIN_STEP_PIXELS: f32 = 2.0;
+
+const LINK_COLOR: Color = Color::new(0.0, 1.0, 0.0, 0.35);
+const LABEL_COLOR: Color = Color::new(0.85, 0.95, 0.85, 1.0);
+
+/// How far into the strategic view `zoom_level` is: 0 = normal sprites, 1 = icons only
+/// (eased over the zoom range on a log scale, as each zoom step multiplies the level)
+pub fn blend(zoom_level: f32) -> f32 {
+    let span = (STRATEGIC_ZOOM_FULL / STRATEGIC_ZOOM_START).ln();
+    let t = ((zoom_level / STRATEGIC_ZOOM_START).max(f32::MIN_POSITIVE).ln() / span).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Satellites close enough on screen to draw as one icon
+#[derive(Debug, Clone, PartialEq)]
+pub struct SatelliteCluster {
+    pub satellite_ids: Vec<EntityId>, // Ascending; the first one stands in for the cluster's orbit
+    pub center: Vec2,                 // Mean position (world space)
+    pub color: Color,                 // Status color of the member lowest on fuel
+}
+
+/// Group satellites into clusters by grid cell of `cell_size` world units, ordered by lowest id
+pub fn cluster_satellites(world: &World, cell_size: f32) -> Vec<SatelliteCluster> {
+    let mut cells: HashMap<(i64, i64), Vec<EntityId>> = HashMap::new();
+    for (id, satellite) in world.satellites_with_ids() {
+        let cell = (satellite.position() / cell_size).floor();
+        cells.entry((cell.x as i64, cell.y as i64)).or_default().push(id);
+    }
+
+    let mut clusters: Vec<SatelliteCluster> = cells
+        .into_values()
+        .filter_map(|mut satellite_ids| {
+            satellite_ids.sort_unstable();
+            let members: Vec<_> = satellite_ids.iter().filter_map(|id| world.get_satellite(*id)).collect();
+            let neediest = members.iter().min_by(|a, b| a.fuel_percentage().total_cmp(&b.fuel_percentage()))?;
+            let center = members.iter().map(|satellite| satellite.position()).sum::<Vec2>() / members.len() as f32;
+            Some(SatelliteCluster { color: neediest.status_color(), center, satellite_ids })
+        })
+        .collect();
+    clusters.sort_by_key(|cluster| cluster.satellite_ids[0]);
+    clusters
+}
+
+/// Switches the main view to icons past the strategic zoom (one per game mode)
+#[derive(Debug, Default)]
+pub struct StrategicView {
+    blend: f32,
+    clusters: Vec<SatelliteCluster>,
+}
+
+impl StrategicView {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.blend > 0.0
+    }
+
+    /// Draw the world: sprites when close, icons fading in past the strategic zoom
+    /// (replaces `arena_overlay::render_world`; call with `camera` set)
+    pub fn render_world(&mut self, world: &World, camera: &Camera) {
+        let zoom_level = camera.zoom_level();
+        self.blend = blend(zoom_level);
+        if self.blend < 1.0 {
+            arena_overlay::render_world(world, camera);
+        } else {
+            for planet in world.planets() {
+                planet.draw();
+            }
+            arena_overlay::draw_outline(world, camera);
+        }
+        if !self.is_active() {
+            self.clusters.clear();
+            return;
+        }
+
+        self.clusters = cluster_satellites(world, CLUSTER_CELL_PIXELS * zoom_level);
+        let alpha = self.blend;
+        let planets: Vec<&Planet> = world.planets().collect();
+
+        // One orbit per cluster; its members were launched onto much the same path
+        for cluster in &self.clusters {
+            let Some(ellipse) = world
+                .get_satellite(cluster.satellite_ids[0])
+                .and_then(|satellite| VehicleManager::satellite_orbit_ellipse(satellite, &planets))
+            else {
+                continue;
+            };
+            let color = Color::new(cluster.color.r, cluster.color.g, cluster.color.b, 0.3 * alpha);
+            let points = ellipse.points(ORBIT_POINTS);
+            for pair in points.windows(2) {
+                draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, zoom_level, color);
+            }
+        }
+
+        // Network links between clusters in transfer range
+        let link_color = Color::new(LINK_COLOR.r, LINK_COLOR.g, LINK_COLOR.b, LINK_COLOR.a * alpha);
+        for (i, a) in self.clusters.iter().enumerate() {
+            for b in &self.clusters[i + 1..] {
+                if a.center.distance(b.center) <= GameConstants::SATELLITE_TRANSFER_RANGE {
+                    draw_line(a.center.x, a.center.y, b.center.x, b.center.y, 1.5 * zoom_level, link_color);
+                }
+            }
+        }
+
+        for cluster in &self.clusters {
+            let color = Color::new(cluster.color.r, cluster.color.g, cluster.color.b, alpha);
+            let (x, y) = (cluster.center.x, cluster.center.y);
+            if cluster.satellite_ids.len() == 1 {
+                draw_poly(x, y, 4, 4.0 * zoom_level, 45.0, color);
+            } else {
+                let radius = (5.0 + (cluster.satellite_ids.len() as f32).sqrt() * 1.5) * zoom_level;
+                draw_circle(x, y, radius, Color::new(color.r, color.g, color.b, 0.35 * alpha));
+                draw_circle_lines(x, y, radius, 1.5 * zoom_level, color);
+            }
+        }
+
+        // Rockets as arrowheads along their heading, ours ringed
+        let active_rocket_id = world.active_rocket_id();
+        for (id, rocket) in world.rockets_with_ids() {
+            let position = rocket.position();
+            let heading = Vec2::from_angle(rocket.rotation() - std::f32::consts::FRAC_PI_2);
+            let side = heading.perp();
+            let size = 9.0 * zoom_level;
+            let base = rocket.color();
+            let color = Color::new(base.r, base.g, base.b, alpha);
+            draw_triangle(
+                position + heading * size,
+                position - heading * size * 0.6 + side * size * 0.6,
+                position - heading * size * 0.6 - side * size * 0.6,
+                color,
+            );
+            if Some(id) == active_rocket_id {
+                draw_circle_lines(position.x, position.y, size * 1.6, 1.5 * zoom_level, Color::new(1.0, 1.0, 1.0, alpha));
+            }
+        }
+
+        let bullet_color = Color::new(1.0, 0.3, 0.3, alpha);
+        for bullet in world.bullets() {
+            draw_circle(bullet.position().x, bullet.position().y, 1.5 * zoom_level, bullet_color);
+        }
+    }
+
+    /// Long-horizon trails within `window`, older parts fainter (call with `camera` set, after `render_world`)
+    pub fn draw_trails(&self, trails: &OrbitTrails, window: TrailWindow, now: f32, zoom_level: f32) {
+        if !self.is_active() || window == TrailWindow::Off {
+            return;
+        }
+        let since = window.seconds().map_or(f32::NEG_INFINITY, |seconds| now - seconds);
+        let min_step = TRAIL_MIN_STEP_PIXELS * zoom_level;
+
+        for (_, trail) in trails.trails() {
+            let points = trail.since(since);
+            let Some(first) = points.first() else {
+                continue;
+            };
+            let span = (now - first.start).max(1.0);
+            let mut last = first;
+            for point in &points[1..] {
+                if point.position.distance(last.position) < min_step {
+                    continue;
+                }
+                let age = ((now - point.end) / span).clamp(0.0, 1.0);
+                let alpha = (0.1 + 0.5 * (1.0 - age)) * self.blend;
+                let color = Color::new(trail.color.r, trail.color.g, trail.color.b, alpha);
+                draw_line(last.position.x, last.position.y, point.position.x, point.position.y, zoom_level, color);
+                last = point;
+            }
+        }
+    }
+
+    /// Body names, cluster counts and the view's tag (call in screen space, after `render_world`)
+    pub fn draw_labels(&self, world: &World, camera: &Camera) {
+        if !self.is_active() {
+            return;
+        }
+        let fade = |color: Color| Color::new(color.r, color.g, color.b, color.a * self.blend);
+        let zoom_level = camera.zoom_level();
+
+        for planet in world.planets() {
+            let Some(name) = planet.name() else {
+                continue;
+            };
+            let screen = camera.world_to_screen(planet.position());
+            let radius = planet.radius() / zoom_level;
+            let width = measure_text(name, None, 16, 1.0).width;
+            draw_text(name, screen.x - width / 2.0, screen.y - radius - 8.0, 16.0, fade(LABEL_COLOR));
+        }
+
+        for cluster in self.clusters.iter().filter(|cluster| cluster.satellite_ids.len() > 1) {
+            let screen = camera.world_to_screen(cluster.center);
+            draw_text(&cluster.satellite_ids.len().to_string(), screen.x + 12.0, screen.y + 4.0, 14.0, fade(WHITE));
+        }
+
+        let tag = "STRATEGIC VIEW";
+        let width = measure_text(tag, None, 14, 1.0).width;
+        let view = camera.viewport().unwrap_or(Rect::new(0.0, 0.0, screen_width(), screen_height()));
+        draw_text(tag, view.center().x - width / 2.0, view.y + 20.0, 14.0, fade(Color::new(0.0, 1.0, 0.0, 0.8)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::Satellite;
+
+    #[test]
+    fn test_blend_and_satellite_clusters() {
+        assert_eq!(blend(1.0), 0.0);
+        assert_eq!(blend(STRATEGIC_ZOOM_START), 0.0);
+        assert!(blend(32.0) > 0.0 && blend(32.0) < 1.0);
+        assert_eq!(blend(STRATEGIC_ZOOM_FULL), 1.0);
+
+        let mut world = World::new();
+        let a = world.add_satellite(Satellite::new(Vec2::new(10.0, 10.0), Vec2::ZERO, WHITE));
+        let b = world.add_satellite(Satellite::new(Vec2::new(50.0, 30.0), Vec2::ZERO, WHITE));
+        let c = world.add_satellite(Satellite::new(Vec2::new(5000.0, 0.0), Vec2::ZERO, WHITE));
+        world.get_satellite_mut(b).unwrap().consume_fuel(10.0);
+
+        let clusters = cluster_satellites(&world, 100.0);
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].satellite_ids, vec![a, b]);
+        assert_eq!(clusters[0].center, Vec2::new(30.0, 20.0));
+        assert_eq!(clusters[0].color, world.get_satellite(b).unwrap().status_color());
+        assert_eq!(clusters[1].satellite_ids, vec![c]);
+    }
+}
@@ -0,0 +1,201 @@
+// Table - Scrollable table with clickable column headers and row selection
+// Callers own the data: pass rows of cell text each frame and react to the returned event
+
+use macroquad::prelude::*;
+
+const HEADER_HEIGHT: f32 = 30.0;
+const ROW_HEIGHT: f32 = 26.0;
+const FONT_SIZE: f32 = 18.0;
+/// Seconds between clicks that count as a double click
+const DOUBLE_CLICK_TIME: f64 = 0.4;
+
+/// A column heading and its share of the table width
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableColumn {
+    pub title: &'static str,
+    pub width: f32, // Fraction of the table width
+}
+
+/// What the user did to the table this frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableEvent {
+    HeaderClicked(usize),
+    Selected(usize),
+    Activated(usize), // Double click or Enter on the selected row
+}
+
+/// Table layout and interaction state
+#[derive(Debug, Clone)]
+pub struct Table {
+    pub rect: Rect,
+    columns: Vec<TableColumn>,
+    selected: Option<usize>,
+    scroll: usize, // First visible row
+    last_click: Option<(usize, f64)>,
+}
+
+impl Table {
+    pub fn new(rect: Rect, columns: Vec<TableColumn>) -> Self {
+        Table { rect, columns, selected: None, scroll: 0, last_click: None }
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selected = None;
+    }
+
+    /// Rows that fit below the header
+    fn visible_rows(&self) -> usize {
+        ((self.rect.h - HEADER_HEIGHT) / ROW_HEIGHT).floor().max(0.0) as usize
+    }
+
+    /// Column under an x position
+    fn column_at(&self, x: f32) -> Option<usize> {
+        let mut left = self.rect.x;
+        for (i, column) in self.columns.iter().enumerate() {
+            let right = left + column.width * self.rect.w;
+            if x >= left && x < right {
+                return Some(i);
+            }
+            left = right;
+        }
+        None
+    }
+
+    /// Row index (into all rows) under a y position
+    fn row_at(&self, y: f32, row_count: usize) -> Option<usize> {
+        let top = self.rect.y + HEADER_HEIGHT;
+        if y < top {
+            return None;
+        }
+        let row = self.scroll + ((y - top) / ROW_HEIGHT) as usize;
+        (row < row_count && row < self.scroll + self.visible_rows()).then_some(row)
+    }
+
+    /// Mouse, wheel and arrow keys; `row_count` is the number of rows this frame
+    pub fn handle_input(&mut self, row_count: usize) -> Option<TableEvent> {
+        // Rows can disappear between frames (filters, refreshes)
+        self.selected = self.selected.filter(|&row| row < row_count);
+        self.scroll = self.scroll.min(row_count.saturating_sub(self.visible_rows()));
+
+        let mouse = Vec2::from(mouse_position());
+        if self.rect.contains(mouse) {
+            let wheel = mouse_wheel().1;
+            if wheel > 0.0 {
+                self.scroll = self.scroll.saturating_sub(1);
+            } else if wheel < 0.0 && self.scroll + self.visible_rows() < row_count {
+                self.scroll += 1;
+            }
+
+            if is_mouse_button_pressed(MouseButton::Left) {
+                if mouse.y < self.rect.y + HEADER_HEIGHT {
+                    return self.column_at(mouse.x).map(TableEvent::HeaderClicked);
+                }
+                if let Some(row) = self.row_at(mouse.y, row_count) {
+                    let now = get_time();
+                    let double = self.last_click.is_some_and(|(last, time)| last == row && now - time < DOUBLE_CLICK_TIME);
+                    self.last_click = Some((row, now));
+                    self.selected = Some(row);
+                    return Some(if double { TableEvent::Activated(row) } else { TableEvent::Selected(row) });
+                }
+            }
+        }
+
+        if row_count > 0 {
+            let moved = if is_key_pressed(KeyCode::Down) {
+                Some(self.selected.map_or(0, |row| (row + 1).min(row_count - 1)))
+            } else if is_key_pressed(KeyCode::Up) {
+                Some(self.selected.map_or(0, |row| row.saturating_sub(1)))
+            } else {
+                None
+            };
+            if let Some(row) = moved {
+                self.selected = Some(row);
+                // Keep the selection in view
+                if row < self.scroll {
+                    self.scroll = row;
+                } else if row >= self.scroll + self.visible_rows() {
+                    self.scroll = row + 1 - self.visible_rows();
+                }
+                return Some(TableEvent::Selected(row));
+            }
+        }
+        if is_key_pressed(KeyCode::Enter) {
+            return self.selected.map(TableEvent::Activated);
+        }
+        None
+    }
+
+    /// Draw the header (marking `sorted` = (column, descending)), the rows and `empty_text` when there are none
+    pub fn draw(&self, rows: &[Vec<String>], sorted: Option<(usize, bool)>, empty_text: &str) {
+        let r = self.rect;
+        draw_rectangle(r.x, r.y, r.w, r.h, Color::new(0.1, 0.1, 0.15, 0.9));
+        draw_rectangle(r.x, r.y, r.w, HEADER_HEIGHT, Color::new(0.2, 0.2, 0.3, 1.0));
+
+        let mut left = r.x;
+        for (i, column) in self.columns.iter().enumerate() {
+            let title = match sorted {
+                Some((sorted_column, descending)) if sorted_column == i => {
+                    format!("{} {}", column.title, if descending { "v" } else { "^" })
+                }
+                _ => column.title.to_string(),
+            };
+            let color = if sorted.is_some_and(|(sorted_column, _)| sorted_column == i) { YELLOW } else { WHITE };
+            draw_text(&title, left + 8.0, r.y + 21.0, FONT_SIZE, color);
+            left += column.width * r.w;
+        }
+
+        if rows.is_empty() {
+            draw_text(empty_text, r.x + 8.0, r.y + HEADER_HEIGHT + 22.0, FONT_SIZE, GRAY);
+        }
+        for (row, cells) in rows.iter().enumerate().skip(self.scroll).take(self.visible_rows()) {
+            let y = r.y + HEADER_HEIGHT + (row - self.scroll) as f32 * ROW_HEIGHT;
+            if self.selected == Some(row) {
+                draw_rectangle(r.x, y, r.w, ROW_HEIGHT, Color::new(0.3, 0.3, 0.5, 1.0));
+            }
+            let mut left = r.x;
+            for (cell, column) in cells.iter().zip(&self.columns) {
+                draw_text(cell, left + 8.0, y + 19.0, FONT_SIZE, WHITE);
+                left += column.width * r.w;
+            }
+        }
+
+        // Scroll hint
+        if rows.len() > self.visible_rows() {
+            let hint = format!("{}-{} of {}", self.scroll + 1, (self.scroll + self.visible_rows()).min(rows.len()), rows.len());
+            let dims = measure_text(&hint, None, 14, 1.0);
+            draw_text(&hint, r.right() - dims.width - 8.0, r.bottom() - 6.0, 14.0, GRAY);
+        }
+        draw_rectangle_lines(r.x, r.y, r.w, r.h, 2.0, WHITE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> Table {
+        let columns = vec![TableColumn { title: "A", width: 0.5 }, TableColumn { title: "B", width: 0.5 }];
+        Table::new(Rect::new(0.0, 0.0, 200.0, HEADER_HEIGHT + 3.0 * ROW_HEIGHT), columns)
+    }
+
+    #[test]
+    fn test_hit_testing() {
+        let mut table = table();
+        assert_eq!(table.visible_rows(), 3);
+        assert_eq!(table.column_at(50.0), Some(0));
+        assert_eq!(table.column_at(150.0), Some(1));
+        assert_eq!(table.column_at(250.0), None);
+
+        let first_row = HEADER_HEIGHT + 1.0;
+        assert_eq!(table.row_at(10.0, 5), None); // Header
+        assert_eq!(table.row_at(first_row, 5), Some(0));
+        assert_eq!(table.row_at(first_row + ROW_HEIGHT, 1), None); // Past the last row
+
+        table.scroll = 2;
+        assert_eq!(table.row_at(first_row, 5), Some(2));
+    }
+}
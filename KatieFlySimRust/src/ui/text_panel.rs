@@ -46,12 +46,17 @@ impl Default for TextPanelConfig {
     }
 }
 
+/// Size of the collapse button and resize grip hit areas (pixels)
+const HANDLE_SIZE: f32 = 14.0;
+
 /// TextPanel - Display multi-line text with background
 pub struct TextPanel {
     config: TextPanelConfig,
     lines: Vec<String>,
     title: Option<String>,
     visible: bool,
+    collapsed: bool,  // Only the title bar is drawn
+    show_handles: bool, // Draw collapse button and resize grip
 }
 
 impl TextPanel {
@@ -68,6 +73,8 @@ impl TextPanel {
             lines: Vec::new(),
             title: None,
             visible: true,
+            collapsed: false,
+            show_handles: false,
         }
     }
 
@@ -77,6 +84,8 @@ impl TextPanel {
             lines: Vec::new(),
             title: None,
             visible: true,
+            collapsed: false,
+            show_handles: false,
         }
     }
 
@@ -115,6 +124,37 @@ impl TextPanel {
         self.config.height = height;
     }
 
+    pub fn size(&self) -> Vec2 {
+        Vec2::new(self.config.width, self.config.height)
+    }
+
+    pub fn set_collapsed(&mut self, collapsed: bool) {
+        self.collapsed = collapsed;
+    }
+
+    pub fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    /// Show the collapse button and resize grip (for movable panels)
+    pub fn set_show_handles(&mut self, show: bool) {
+        self.show_handles = show;
+    }
+
+    /// Height of the title bar (whole panel height when collapsed)
+    pub fn title_bar_height(&self) -> f32 {
+        self.config.font_size + 4.0 + self.config.padding * 2.0
+    }
+
+    /// Height currently drawn on screen
+    pub fn drawn_height(&self) -> f32 {
+        if self.collapsed {
+            self.title_bar_height()
+        } else {
+            self.config.height
+        }
+    }
+
     pub fn set_visible(&mut self, visible: bool) {
         self.visible = visible;
     }
@@ -170,7 +210,7 @@ impl TextPanel {
 
         let pos = self.config.position;
         let width = self.config.width;
-        let height = self.config.height;
+        let height = self.drawn_height();
 
         // Draw background
         draw_rectangle(
@@ -211,6 +251,13 @@ impl TextPanel {
             );
             text_y += title_size + self.config.line_spacing * 2.0;
 
+            if self.show_handles {
+                self.draw_collapse_button();
+            }
+            if self.collapsed {
+                return;
+            }
+
             // Draw separator line
             draw_line(
                 pos.x + self.config.padding,
@@ -249,6 +296,46 @@ impl TextPanel {
 
             text_y += self.config.font_size + self.config.line_spacing;
         }
+
+        if self.show_handles {
+            self.draw_resize_grip();
+        }
+    }
+
+    /// Draw the "-"/"+" collapse button in the title bar
+    fn draw_collapse_button(&self) {
+        let pos = self.collapse_button_position();
+        draw_rectangle_lines(pos.x, pos.y, HANDLE_SIZE, HANDLE_SIZE, 1.0, self.config.border_color);
+        let mid_y = pos.y + HANDLE_SIZE / 2.0;
+        draw_line(pos.x + 3.0, mid_y, pos.x + HANDLE_SIZE - 3.0, mid_y, 1.5, self.config.text_color);
+        if self.collapsed {
+            let mid_x = pos.x + HANDLE_SIZE / 2.0;
+            draw_line(mid_x, pos.y + 3.0, mid_x, pos.y + HANDLE_SIZE - 3.0, 1.5, self.config.text_color);
+        }
+    }
+
+    /// Draw diagonal grip lines in the bottom-right corner
+    fn draw_resize_grip(&self) {
+        let corner = self.config.position + Vec2::new(self.config.width, self.config.height);
+        for i in 1..=3 {
+            let offset = i as f32 * 4.0;
+            draw_line(
+                corner.x - offset,
+                corner.y - 2.0,
+                corner.x - 2.0,
+                corner.y - offset,
+                1.0,
+                self.config.border_color,
+            );
+        }
+    }
+
+    fn collapse_button_position(&self) -> Vec2 {
+        let title_bar = self.title_bar_height();
+        Vec2::new(
+            self.config.position.x + self.config.width - HANDLE_SIZE - self.config.padding / 2.0,
+            self.config.position.y + (title_bar - HANDLE_SIZE) / 2.0,
+        )
     }
 
     /// Draw a single line with alignment
@@ -397,7 +484,31 @@ impl TextPanel {
         point.x >= pos.x
             && point.x <= pos.x + self.config.width
             && point.y >= pos.y
-            && point.y <= pos.y + self.config.height
+            && point.y <= pos.y + self.drawn_height()
+    }
+
+    /// Point is on the title bar (drag handle)
+    pub fn title_bar_contains(&self, point: Vec2) -> bool {
+        let pos = self.config.position;
+        point.x >= pos.x
+            && point.x <= pos.x + self.config.width
+            && point.y >= pos.y
+            && point.y <= pos.y + self.title_bar_height()
+    }
+
+    /// Point is on the collapse button
+    pub fn collapse_button_contains(&self, point: Vec2) -> bool {
+        let pos = self.collapse_button_position();
+        point.x >= pos.x && point.x <= pos.x + HANDLE_SIZE && point.y >= pos.y && point.y <= pos.y + HANDLE_SIZE
+    }
+
+    /// Point is on the bottom-right resize grip (never while collapsed)
+    pub fn resize_grip_contains(&self, point: Vec2) -> bool {
+        if self.collapsed {
+            return false;
+        }
+        let corner = self.config.position + Vec2::new(self.config.width, self.config.height);
+        point.x >= corner.x - HANDLE_SIZE && point.x <= corner.x && point.y >= corner.y - HANDLE_SIZE && point.y <= corner.y
     }
 }
 
@@ -442,6 +553,22 @@ mod tests {
         assert!(!panel.is_visible());
     }
 
+    #[test]
+    fn test_collapsed_panel_hit_areas() {
+        let mut panel = TextPanel::new(Vec2::new(100.0, 100.0), Vec2::new(200.0, 150.0));
+        let title_bar = panel.title_bar_height();
+
+        assert!(panel.title_bar_contains(Vec2::new(150.0, 105.0)));
+        assert!(panel.resize_grip_contains(Vec2::new(295.0, 245.0)));
+        assert!(panel.contains_point(Vec2::new(150.0, 200.0)));
+
+        panel.set_collapsed(true);
+        assert_eq!(panel.drawn_height(), title_bar);
+        assert!(!panel.resize_grip_contains(Vec2::new(295.0, 245.0)));
+        assert!(!panel.contains_point(Vec2::new(150.0, 200.0)));
+        assert!(panel.collapse_button_contains(panel.collapse_button_position() + Vec2::splat(2.0)));
+    }
+
     #[test]
     fn test_contains_point() {
         let panel = TextPanel::new(
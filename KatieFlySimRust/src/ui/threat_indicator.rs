@@ -0,0 +1,97 @@
+// Threat Indicator - Directional arrows around the rocket for incoming bullets
+// Lets players dodge without opening the network map
+
+use macroquad::prelude::*;
+
+use crate::game_constants::GameConstants;
+use crate::systems::bullet_threats::{self, BulletThreat};
+use crate::systems::{EntityId, World};
+use crate::ui::Camera;
+
+/// Distance of the arrows from the rocket's screen position (pixels)
+const ARROW_RING_RADIUS: f32 = 70.0;
+/// Arrow length (pixels)
+const ARROW_LENGTH: f32 = 22.0;
+/// Below this time to closest approach, arrows pulse red
+const URGENT_TIME: f32 = 3.0;
+
+/// Tracks bullets heading for the player's rocket(s)
+pub struct ThreatIndicator {
+    threats: Vec<(EntityId, BulletThreat)>,
+    pulse_time: f32,
+}
+
+impl ThreatIndicator {
+    pub fn new() -> Self {
+        ThreatIndicator {
+            threats: Vec::new(),
+            pulse_time: 0.0,
+        }
+    }
+
+    /// Re-check bullet paths against the watched rockets
+    pub fn update(&mut self, delta_time: f32, world: &World, rocket_ids: &[EntityId]) {
+        self.pulse_time += delta_time;
+        self.threats = rocket_ids
+            .iter()
+            .flat_map(|&rocket_id| {
+                bullet_threats::find_bullet_threats(
+                    world,
+                    rocket_id,
+                    GameConstants::BULLET_WARNING_RADIUS,
+                    GameConstants::BULLET_WARNING_HORIZON,
+                )
+                .into_iter()
+                .map(move |threat| (rocket_id, threat))
+            })
+            .collect();
+    }
+
+    pub fn threats(&self) -> &[(EntityId, BulletThreat)] {
+        &self.threats
+    }
+
+    pub fn clear(&mut self) {
+        self.threats.clear();
+    }
+
+    /// Draw one arrow per threat around its rocket (call in screen space)
+    pub fn draw(&self, world: &World, camera: &Camera) {
+        for (rocket_id, threat) in &self.threats {
+            let Some(rocket) = world.get_rocket(*rocket_id) else {
+                continue;
+            };
+            let center = camera.world_to_screen(rocket.position());
+            let direction = threat.direction;
+            if direction == Vec2::ZERO {
+                continue;
+            }
+
+            let urgent = threat.time_to_closest < URGENT_TIME;
+            let pulse = if urgent { 0.6 + 0.4 * (self.pulse_time * 12.0).sin().abs() } else { 1.0 };
+            let color = if urgent {
+                Color::new(1.0, 0.15, 0.15, pulse)
+            } else {
+                Color::new(1.0, 0.6, 0.1, 0.9)
+            };
+
+            // Arrow sits on a ring around the rocket and points at the incoming bullet
+            let tip = center + direction * (ARROW_RING_RADIUS + ARROW_LENGTH);
+            let base = center + direction * ARROW_RING_RADIUS;
+            let side = direction.perp() * (ARROW_LENGTH * 0.45);
+            draw_triangle(tip, base + side, base - side, color);
+            draw_triangle_lines(tip, base + side, base - side, 1.5, WHITE);
+
+            let label = format!("{:.1}s", threat.time_to_closest);
+            let label_pos = center + direction * (ARROW_RING_RADIUS + ARROW_LENGTH + 14.0);
+            let dims = measure_text(&label, None, 16, 1.0);
+            draw_text(&label, label_pos.x - dims.width / 2.0, label_pos.y + 5.0, 16.0, color);
+        }
+    }
+}
+
+impl Default for ThreatIndicator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
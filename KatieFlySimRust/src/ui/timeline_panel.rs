@@ -0,0 +1,146 @@
+// Timeline Panel - Scrubber bar and event list for the session timeline
+// Selecting an event points the camera at where it happened
+
+use macroquad::prelude::*;
+
+use crate::systems::mission_clock;
+use crate::systems::{SessionEvent, SessionTimeline};
+
+/// Panel height (pixels)
+const PANEL_HEIGHT: f32 = 150.0;
+/// Events listed around the selection
+const LIST_ROWS: usize = 5;
+
+/// Session timeline panel state
+#[derive(Debug, Clone, Default)]
+pub struct TimelinePanel {
+    visible: bool,
+    selected_time: Option<f32>, // Time of the selected event (indices shift when old events drop off)
+    dragging: bool,
+}
+
+impl TimelinePanel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Show or hide the panel; hiding releases the camera
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        self.selected_time = None;
+        self.dragging = false;
+    }
+
+    fn selected_index(&self, timeline: &SessionTimeline) -> Option<usize> {
+        self.selected_time.and_then(|time| timeline.nearest_index(time))
+    }
+
+    /// The selected event while the panel is open
+    pub fn selected_event<'a>(&self, timeline: &'a SessionTimeline) -> Option<&'a SessionEvent> {
+        if !self.visible {
+            return None;
+        }
+        self.selected_index(timeline).map(|index| &timeline.events()[index])
+    }
+
+    /// Bar rectangle along the bottom of the screen
+    fn bar_rect() -> Rect {
+        Rect::new(20.0, screen_height() - PANEL_HEIGHT + 30.0, screen_width() - 40.0, 16.0)
+    }
+
+    /// True if the mouse is over the panel (so clicks don't reach the world)
+    pub fn contains_mouse(&self) -> bool {
+        self.visible && mouse_position().1 >= screen_height() - PANEL_HEIGHT
+    }
+
+    /// `[` / `]` step through events, clicking or dragging the bar scrubs to the nearest one
+    pub fn handle_input(&mut self, timeline: &SessionTimeline, now: f32) {
+        if !self.visible || timeline.is_empty() {
+            return;
+        }
+
+        let last = timeline.events().len() - 1;
+        if is_key_pressed(KeyCode::LeftBracket) {
+            let index = self.selected_index(timeline).map_or(last, |i| i.saturating_sub(1));
+            self.selected_time = Some(timeline.events()[index].time);
+        }
+        if is_key_pressed(KeyCode::RightBracket) {
+            let index = self.selected_index(timeline).map_or(last, |i| (i + 1).min(last));
+            self.selected_time = Some(timeline.events()[index].time);
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            self.selected_time = None;
+        }
+
+        let bar = Self::bar_rect();
+        let mouse = Vec2::from(mouse_position());
+        if is_mouse_button_pressed(MouseButton::Left) && bar.contains(mouse) {
+            self.dragging = true;
+        }
+        if !is_mouse_button_down(MouseButton::Left) {
+            self.dragging = false;
+        }
+        if self.dragging && now > 0.0 {
+            let fraction = ((mouse.x - bar.x) / bar.w).clamp(0.0, 1.0);
+            self.selected_time = timeline.nearest_index(fraction * now).map(|i| timeline.events()[i].time);
+        }
+    }
+
+    /// Draw the scrubber and nearby events (call in screen space)
+    pub fn draw(&self, timeline: &SessionTimeline, now: f32) {
+        if !self.visible {
+            return;
+        }
+
+        let top = screen_height() - PANEL_HEIGHT;
+        draw_rectangle(0.0, top, screen_width(), PANEL_HEIGHT, Color::new(0.0, 0.0, 0.0, 0.8));
+        draw_text(
+            &format!("Session Timeline - {} events ([ ] to step, click bar to scrub, H to close)", timeline.events().len()),
+            20.0,
+            top + 20.0,
+            16.0,
+            WHITE,
+        );
+
+        let bar = Self::bar_rect();
+        draw_rectangle(bar.x, bar.y, bar.w, bar.h, Color::new(0.2, 0.2, 0.2, 1.0));
+        let x_at = |time: f32| bar.x + bar.w * if now > 0.0 { (time / now).clamp(0.0, 1.0) } else { 0.0 };
+        for event in timeline.events() {
+            let x = x_at(event.time);
+            draw_line(x, bar.y, x, bar.y + bar.h, 2.0, event.kind.color());
+        }
+
+        let selected = self.selected_index(timeline);
+        if let Some(index) = selected {
+            let x = x_at(timeline.events()[index].time);
+            draw_triangle(
+                vec2(x, bar.y - 2.0),
+                vec2(x - 6.0, bar.y - 10.0),
+                vec2(x + 6.0, bar.y - 10.0),
+                WHITE,
+            );
+        }
+
+        if timeline.is_empty() {
+            draw_text("Nothing has happened yet", 20.0, bar.y + 40.0, 16.0, GRAY);
+            return;
+        }
+
+        // List a few events around the selection (the latest ones if nothing is selected)
+        let events = timeline.events();
+        let center = selected.unwrap_or(events.len() - 1);
+        let start = center.saturating_sub(LIST_ROWS / 2).min(events.len().saturating_sub(LIST_ROWS));
+        let end = (start + LIST_ROWS).min(events.len());
+        for (row, index) in (start..end).enumerate() {
+            let event = &events[index];
+            let y = bar.y + 40.0 + row as f32 * 18.0;
+            let prefix = if Some(index) == selected { ">" } else { " " };
+            let text = format!("{} {} {}", prefix, mission_clock::format_clock(event.time), event.description);
+            draw_text(&text, 20.0, y, 16.0, event.kind.color());
+        }
+    }
+}
@@ -0,0 +1,26 @@
+// Timestep Warning - Banner shown while physics steps below its configured rate to keep up with load
+// Drawn by the main loop over every game screen, so players know why motion looks coarser (or slow)
+
+use macroquad::prelude::*;
+
+use crate::systems::AdaptiveTimestep;
+use crate::ui::{Anchor, SafeArea};
+
+const WARNING_COLOR: Color = Color::new(1.0, 0.6, 0.2, 1.0);
+const BANNER_SIZE: Vec2 = Vec2::new(360.0, 28.0);
+
+/// Heavy-load banner below the alert banner, if the clock is degraded or dropped time (call in screen space)
+pub fn draw(clock: &AdaptiveTimestep) {
+    let text = if clock.is_running_slow() {
+        "HEAVY LOAD - simulation running slow".to_string()
+    } else if clock.is_degraded() {
+        format!("HEAVY LOAD - physics at {} Hz (of {} Hz)", clock.hz(), clock.base_hz())
+    } else {
+        return;
+    };
+    let Vec2 { x, y } = SafeArea::current().anchor(Anchor::TopCenter, BANNER_SIZE, Vec2::new(0.0, 115.0));
+    draw_rectangle(x, y, BANNER_SIZE.x, BANNER_SIZE.y, Color::new(0.0, 0.0, 0.0, 0.7));
+    draw_rectangle_lines(x, y, BANNER_SIZE.x, BANNER_SIZE.y, 1.0, WARNING_COLOR);
+    let width = measure_text(&text, None, 18, 1.0).width;
+    draw_text(&text, x + (BANNER_SIZE.x - width) / 2.0, y + 19.0, 18.0, WARNING_COLOR);
+}
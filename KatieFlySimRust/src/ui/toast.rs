@@ -0,0 +1,136 @@
+// Toast - Short-lived notification messages stacked at the top of the screen
+// Game modes push messages; toasts fade out on their own
+
+use macroquad::prelude::*;
+
+use crate::ui::{Anchor, SafeArea};
+
+/// Default time a toast stays on screen (seconds)
+const DEFAULT_TOAST_DURATION: f32 = 3.0;
+/// Fade-out time at the end of a toast's life (seconds)
+const TOAST_FADE_TIME: f32 = 0.5;
+/// Maximum number of toasts shown at once (oldest are dropped)
+const MAX_TOASTS: usize = 5;
+
+struct Toast {
+    text: String,
+    color: Color,
+    remaining: f32,
+}
+
+/// Queue of on-screen notifications
+pub struct ToastManager {
+    toasts: Vec<Toast>,
+}
+
+impl ToastManager {
+    pub fn new() -> Self {
+        ToastManager { toasts: Vec::new() }
+    }
+
+    /// Show a toast for the default duration
+    pub fn push(&mut self, text: impl Into<String>, color: Color) {
+        self.push_for(text, color, DEFAULT_TOAST_DURATION);
+    }
+
+    /// Show a toast for a specific duration (seconds)
+    pub fn push_for(&mut self, text: impl Into<String>, color: Color, duration: f32) {
+        if self.toasts.len() >= MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+        self.toasts.push(Toast {
+            text: text.into(),
+            color,
+            remaining: duration,
+        });
+    }
+
+    /// Age toasts and drop expired ones
+    pub fn update(&mut self, delta_time: f32) {
+        for toast in &mut self.toasts {
+            toast.remaining -= delta_time;
+        }
+        self.toasts.retain(|toast| toast.remaining > 0.0);
+    }
+
+    pub fn len(&self) -> usize {
+        self.toasts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.toasts.clear();
+    }
+
+    /// Draw toasts centered near the top of the screen (call in screen space)
+    pub fn draw(&self) {
+        let text_size = 24.0;
+        let padding = 10.0;
+        let spacing = 8.0;
+        let mut y = 80.0;
+
+        for toast in &self.toasts {
+            let alpha = (toast.remaining / TOAST_FADE_TIME).clamp(0.0, 1.0);
+            let dims = measure_text(&toast.text, None, text_size as u16, 1.0);
+            let width = dims.width + padding * 2.0;
+            let height = text_size + padding;
+            let x = SafeArea::current().anchor(Anchor::TopCenter, Vec2::new(width, height), Vec2::ZERO).x;
+
+            draw_rectangle(x, y, width, height, Color::new(0.0, 0.0, 0.0, 0.7 * alpha));
+            draw_rectangle_lines(
+                x,
+                y,
+                width,
+                height,
+                2.0,
+                Color::new(toast.color.r, toast.color.g, toast.color.b, alpha),
+            );
+            draw_text(
+                &toast.text,
+                x + padding,
+                y + text_size * 0.8 + padding / 2.0,
+                text_size,
+                Color::new(toast.color.r, toast.color.g, toast.color.b, alpha),
+            );
+
+            y += height + spacing;
+        }
+    }
+}
+
+impl Default for ToastManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_expire() {
+        let mut toasts = ToastManager::new();
+        toasts.push_for("hello", WHITE, 1.0);
+        assert_eq!(toasts.len(), 1);
+
+        toasts.update(0.5);
+        assert_eq!(toasts.len(), 1);
+
+        toasts.update(0.6);
+        assert!(toasts.is_empty());
+    }
+
+    #[test]
+    fn test_oldest_toast_dropped_when_full() {
+        let mut toasts = ToastManager::new();
+        for i in 0..MAX_TOASTS + 2 {
+            toasts.push(format!("toast {}", i), WHITE);
+        }
+        assert_eq!(toasts.len(), MAX_TOASTS);
+        assert_eq!(toasts.toasts[0].text, "toast 2");
+    }
+}
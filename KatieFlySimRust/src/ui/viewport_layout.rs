@@ -0,0 +1,132 @@
+// Viewport Layout - Splits the window into one view per local player
+// Two players sit side by side, three get two views on top and a wide one below, four get quadrants
+
+use macroquad::prelude::*;
+
+/// Most local players the window is split for
+pub const MAX_VIEWPORTS: usize = 4;
+
+const DIVIDER_WIDTH: f32 = 2.0;
+const DIVIDER_COLOR: Color = Color::new(0.6, 0.6, 0.7, 1.0);
+
+/// Screen rects (in pixels, y down) for `count` views of a `screen`-sized window
+pub fn split(count: usize, screen: Vec2) -> Vec<Rect> {
+    let Vec2 { x: w, y: h } = screen;
+    let (half_w, half_h) = (w / 2.0, h / 2.0);
+    match count.clamp(1, MAX_VIEWPORTS) {
+        1 => vec![Rect::new(0.0, 0.0, w, h)],
+        2 => vec![Rect::new(0.0, 0.0, half_w, h), Rect::new(half_w, 0.0, w - half_w, h)],
+        3 => vec![
+            Rect::new(0.0, 0.0, half_w, half_h),
+            Rect::new(half_w, 0.0, w - half_w, half_h),
+            Rect::new(0.0, half_h, w, h - half_h),
+        ],
+        _ => vec![
+            Rect::new(0.0, 0.0, half_w, half_h),
+            Rect::new(half_w, 0.0, w - half_w, half_h),
+            Rect::new(0.0, half_h, half_w, h - half_h),
+            Rect::new(half_w, half_h, w - half_w, h - half_h),
+        ],
+    }
+}
+
+/// One viewport per player, kept in step with the window size
+#[derive(Debug, Clone)]
+pub struct ViewportLayout {
+    count: usize,
+    screen: Vec2,
+    viewports: Vec<Rect>,
+}
+
+impl ViewportLayout {
+    pub fn new(count: usize, screen: Vec2) -> Self {
+        let count = count.clamp(1, MAX_VIEWPORTS);
+        ViewportLayout { count, screen, viewports: split(count, screen) }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn viewports(&self) -> &[Rect] {
+        &self.viewports
+    }
+
+    pub fn viewport(&self, index: usize) -> Rect {
+        self.viewports[index.min(self.count - 1)]
+    }
+
+    /// Lay out again for a resized window; true if anything moved
+    pub fn resize(&mut self, screen: Vec2) -> bool {
+        if screen == self.screen {
+            return false;
+        }
+        self.screen = screen;
+        self.viewports = split(self.count, screen);
+        true
+    }
+
+    /// The view under a screen position (the mouse, for wheel zoom)
+    pub fn viewport_at(&self, point: Vec2) -> Option<usize> {
+        self.viewports.iter().position(|rect| rect.contains(point))
+    }
+
+    /// Screen-space camera clipped to one view (pixel coordinates stay the window's)
+    pub fn screen_camera(&self, index: usize) -> Camera2D {
+        let rect = self.viewport(index);
+        Camera2D {
+            target: rect.center(),
+            zoom: vec2(2.0 / rect.w, 2.0 / rect.h),
+            // GL viewports count up from the bottom of the window
+            viewport: Some((rect.x as i32, (self.screen.y - rect.bottom()) as i32, rect.w as i32, rect.h as i32)),
+            ..Default::default()
+        }
+    }
+
+    /// Lines between the views (call in screen space)
+    pub fn draw_dividers(&self) {
+        for rect in &self.viewports {
+            if rect.x > 0.0 {
+                draw_line(rect.x, rect.y, rect.x, rect.bottom(), DIVIDER_WIDTH, DIVIDER_COLOR);
+            }
+            if rect.y > 0.0 {
+                draw_line(rect.x, rect.y, rect.right(), rect.y, DIVIDER_WIDTH, DIVIDER_COLOR);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_views_tile_the_window() {
+        let screen = Vec2::new(1920.0, 1080.0);
+        for count in 1..=MAX_VIEWPORTS {
+            let views = split(count, screen);
+            assert_eq!(views.len(), count);
+            let area: f32 = views.iter().map(|rect| rect.w * rect.h).sum();
+            assert!((area - screen.x * screen.y).abs() < 1.0);
+            for (i, a) in views.iter().enumerate() {
+                for b in &views[i + 1..] {
+                    assert!(a.intersect(*b).is_none_or(|overlap| overlap.w * overlap.h < 1.0));
+                }
+            }
+        }
+        // Three players: the third gets the full width below
+        assert_eq!(split(3, screen)[2], Rect::new(0.0, 540.0, 1920.0, 540.0));
+    }
+
+    #[test]
+    fn test_count_is_clamped_and_follows_resizes() {
+        let mut layout = ViewportLayout::new(7, Vec2::new(800.0, 600.0));
+        assert_eq!(layout.count(), MAX_VIEWPORTS);
+        assert_eq!(layout.viewport_at(Vec2::new(700.0, 500.0)), Some(3));
+
+        assert!(!layout.resize(Vec2::new(800.0, 600.0)));
+        assert!(layout.resize(Vec2::new(1600.0, 600.0)));
+        assert_eq!(layout.viewport(1), Rect::new(800.0, 0.0, 800.0, 300.0));
+        assert_eq!(layout.viewport_at(Vec2::new(2000.0, 0.0)), None);
+    }
+}
@@ -0,0 +1,71 @@
+// Waypoint Markers - Diamonds with name and distance readouts for player waypoints
+// Drawn in screen space over the main view and on the network map
+
+use macroquad::prelude::*;
+
+use crate::systems::waypoints::format_distance;
+use crate::systems::Waypoint;
+use crate::ui::Camera;
+
+const OWN_COLOR: Color = Color::new(1.0, 0.85, 0.2, 0.95);
+const SHARED_COLOR: Color = Color::new(0.3, 1.0, 0.9, 0.95);
+
+fn waypoint_color(waypoint: &Waypoint) -> Color {
+    if waypoint.shared {
+        SHARED_COLOR
+    } else {
+        OWN_COLOR
+    }
+}
+
+fn draw_diamond(center: Vec2, size: f32, color: Color) {
+    let corners = [
+        center + vec2(0.0, -size),
+        center + vec2(size, 0.0),
+        center + vec2(0.0, size),
+        center + vec2(-size, 0.0),
+    ];
+    for i in 0..4 {
+        let (a, b) = (corners[i], corners[(i + 1) % 4]);
+        draw_line(a.x, a.y, b.x, b.y, 2.0, color);
+    }
+}
+
+fn label(waypoint: &Waypoint, from: Option<Vec2>) -> String {
+    match from {
+        Some(from) => format!("{} {}", waypoint.name, format_distance(from.distance(waypoint.position))),
+        None => waypoint.name.clone(),
+    }
+}
+
+/// Waypoints in the main view (call in screen space); distances are from `from` (usually our rocket)
+pub fn draw_waypoints(waypoints: &[Waypoint], camera: &Camera, from: Option<Vec2>) {
+    for waypoint in waypoints {
+        let screen = camera.world_to_screen(waypoint.position);
+        if screen.x < -50.0 || screen.y < -50.0 || screen.x > screen_width() + 50.0 || screen.y > screen_height() + 50.0 {
+            continue;
+        }
+        let color = waypoint_color(waypoint);
+        draw_diamond(screen, 9.0, color);
+        draw_text(&label(waypoint, from), screen.x + 13.0, screen.y + 5.0, 16.0, color);
+    }
+}
+
+/// Waypoints on the network map, skipping any outside `bounds`
+pub fn draw_map_waypoints(waypoints: &[Waypoint], world_to_map: impl Fn(Vec2) -> Vec2, bounds: Rect, from: Option<Vec2>) {
+    for waypoint in waypoints {
+        let map_pos = world_to_map(waypoint.position);
+        if !bounds.contains(map_pos) {
+            continue;
+        }
+        let color = waypoint_color(waypoint);
+        draw_diamond(map_pos, 6.0, color);
+        draw_text(&label(waypoint, from), map_pos.x + 9.0, map_pos.y + 4.0, 13.0, color);
+    }
+}
+
+/// Inverse of the network maps' projection (origin at the map center, Y flipped)
+pub fn map_to_world(map_pos: Vec2, map_center: Vec2, map_scale: f32, origin: Vec2) -> Vec2 {
+    let offset = map_pos - map_center;
+    origin + vec2(offset.x, -offset.y) / map_scale
+}
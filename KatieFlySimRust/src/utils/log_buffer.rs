@@ -0,0 +1,208 @@
+// Log Buffer - Ring buffer of recent log lines alongside env_logger's console output
+// Capture levels are per module and adjustable at runtime; bug reports and the log console read it
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::time::Instant;
+
+/// Oldest lines are dropped past this many
+const MAX_LINES: usize = 2000;
+/// Environment variable with the initial capture levels (RUST_LOG syntax, e.g. "info,katie_fly_sim_rust::physics=debug")
+pub const CAPTURE_LEVELS_ENV: &str = "KATIE_LOG";
+
+/// One captured log line
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub seconds: f32, // Since startup
+    pub level: Level,
+    pub target: String, // Module path
+    pub message: String,
+}
+
+impl LogEntry {
+    pub fn format(&self) -> String {
+        format!("[{:9.3} {:<5} {}] {}", self.seconds, self.level, self.target, self.message)
+    }
+}
+
+/// Minimum level captured for each module; the longest matching module prefix wins
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogLevels {
+    pub default: LevelFilter,
+    modules: Vec<(String, LevelFilter)>,
+}
+
+impl Default for LogLevels {
+    fn default() -> Self {
+        LogLevels {
+            default: LevelFilter::Info,
+            modules: Vec::new(),
+        }
+    }
+}
+
+impl LogLevels {
+    /// Parse a RUST_LOG style spec ("warn,katie_fly_sim_rust::physics=debug"); bad parts are skipped
+    pub fn parse(spec: &str) -> Self {
+        let mut levels = LogLevels::default();
+        for part in spec.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+            match part.split_once('=') {
+                Some((module, level)) => {
+                    if let Ok(level) = level.trim().parse() {
+                        levels.set(module.trim(), level);
+                    }
+                }
+                None => {
+                    if let Ok(level) = part.parse() {
+                        levels.default = level;
+                    }
+                }
+            }
+        }
+        levels
+    }
+
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        self.modules
+            .iter()
+            .filter(|(module, _)| target == module || target.starts_with(&format!("{}::", module)))
+            .max_by_key(|(module, _)| module.len())
+            .map_or(self.default, |(_, level)| *level)
+    }
+
+    /// Set a module's level (an empty module sets the default)
+    pub fn set(&mut self, module: &str, level: LevelFilter) {
+        if module.is_empty() {
+            self.default = level;
+        } else if let Some(entry) = self.modules.iter_mut().find(|(m, _)| m == module) {
+            entry.1 = level;
+        } else {
+            self.modules.push((module.to_string(), level));
+        }
+    }
+
+    /// Most verbose level any module captures
+    pub fn max(&self) -> LevelFilter {
+        self.modules.iter().map(|(_, level)| *level).fold(self.default, Ord::max)
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref RECENT: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
+    static ref LEVELS: RwLock<LogLevels> = RwLock::new(LogLevels::default());
+    static ref STARTED: Instant = Instant::now();
+}
+/// Console (RUST_LOG) filter, kept so the global max level can be recomputed
+static CONSOLE_FILTER: OnceLock<LevelFilter> = OnceLock::new();
+
+/// Logger that forwards to env_logger and records lines passing the capture levels
+struct BufferedLogger {
+    console: env_logger::Logger,
+}
+
+impl Log for BufferedLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        captures(metadata.target(), metadata.level()) || self.console.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if captures(record.target(), record.level()) {
+            push(LogEntry {
+                seconds: STARTED.elapsed().as_secs_f32(),
+                level: record.level(),
+                target: record.target().to_string(),
+                message: record.args().to_string(),
+            });
+        }
+        if self.console.matches(record) {
+            self.console.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.console.flush();
+    }
+}
+
+fn captures(target: &str, level: Level) -> bool {
+    let levels = LEVELS.read().unwrap_or_else(|e| e.into_inner());
+    level <= levels.level_for(target)
+}
+
+/// Install the logger (replaces `env_logger::init`; RUST_LOG still controls console output)
+pub fn init() {
+    let console = env_logger::Builder::from_default_env().build();
+    let _ = CONSOLE_FILTER.set(console.filter());
+    if let Ok(spec) = std::env::var(CAPTURE_LEVELS_ENV) {
+        *LEVELS.write().unwrap_or_else(|e| e.into_inner()) = LogLevels::parse(&spec);
+    }
+    lazy_static::initialize(&STARTED);
+    if log::set_boxed_logger(Box::new(BufferedLogger { console })).is_ok() {
+        update_max_level();
+    }
+}
+
+/// Let through everything either the console or the capture levels want
+fn update_max_level() {
+    let console = CONSOLE_FILTER.get().copied().unwrap_or(LevelFilter::Off);
+    log::set_max_level(console.max(levels().max()));
+}
+
+/// Current capture levels
+pub fn levels() -> LogLevels {
+    LEVELS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Change what gets captured for a module (empty module = default) while the game runs
+pub fn set_module_level(module: &str, level: LevelFilter) {
+    LEVELS.write().unwrap_or_else(|e| e.into_inner()).set(module, level);
+    update_max_level();
+    log::info!(
+        "Log capture level for {} set to {}",
+        if module.is_empty() { "all modules" } else { module },
+        level
+    );
+}
+
+fn push(entry: LogEntry) {
+    let mut recent = RECENT.lock().unwrap_or_else(|e| e.into_inner());
+    if recent.len() >= MAX_LINES {
+        recent.pop_front();
+    }
+    recent.push_back(entry);
+}
+
+/// Captured lines, oldest first
+pub fn recent_entries() -> Vec<LogEntry> {
+    let recent = RECENT.lock().unwrap_or_else(|e| e.into_inner());
+    recent.iter().cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_levels() {
+        let levels = LogLevels::parse("warn, katie_fly_sim_rust::physics=debug,bogus=loud");
+        assert_eq!(levels.default, LevelFilter::Warn);
+        assert_eq!(levels.level_for("katie_fly_sim_rust::physics::collision"), LevelFilter::Debug);
+        assert_eq!(levels.level_for("katie_fly_sim_rust::physicsx"), LevelFilter::Warn);
+        assert_eq!(levels.level_for("bogus"), LevelFilter::Warn);
+        assert_eq!(levels.max(), LevelFilter::Debug);
+    }
+
+    #[test]
+    fn test_longest_module_wins() {
+        let mut levels = LogLevels::default();
+        levels.set("katie_fly_sim_rust::systems", LevelFilter::Error);
+        levels.set("katie_fly_sim_rust::systems::world", LevelFilter::Trace);
+        assert_eq!(levels.level_for("katie_fly_sim_rust::systems::world"), LevelFilter::Trace);
+        assert_eq!(levels.level_for("katie_fly_sim_rust::systems::crew"), LevelFilter::Error);
+        assert_eq!(levels.level_for("katie_fly_sim_rust::ui"), LevelFilter::Info);
+
+        levels.set("katie_fly_sim_rust::systems", LevelFilter::Warn);
+        assert_eq!(levels.level_for("katie_fly_sim_rust::systems::crew"), LevelFilter::Warn);
+    }
+}
@@ -1,5 +1,6 @@
 // Utility functions module
 
+pub mod log_buffer;
 pub mod vector_helper;
 
 pub use vector_helper::*;
@@ -100,6 +100,13 @@ pub fn angle_between(a: Vec2, b: Vec2) -> f32 {
     }
 }
 
+/// Signed difference between two angles, wrapped to [-PI, PI)
+#[inline]
+pub fn angle_difference(a: f32, b: f32) -> f32 {
+    use std::f32::consts::{PI, TAU};
+    (a - b + PI).rem_euclid(TAU) - PI
+}
+
 /// Project vector a onto vector b
 #[inline]
 pub fn project(a: Vec2, b: Vec2) -> Vec2 {
@@ -208,4 +215,11 @@ mod tests {
         let v2 = Vec2::new(0.0, 1.0);
         assert_relative_eq!(angle(v2), PI / 2.0, epsilon = 0.0001);
     }
+
+    #[test]
+    fn test_angle_difference_wraps() {
+        use std::f32::consts::PI;
+        assert_relative_eq!(angle_difference(0.1, -0.1), 0.2, epsilon = 0.0001);
+        assert_relative_eq!(angle_difference(PI - 0.1, -PI + 0.1), -0.2, epsilon = 0.0001);
+    }
 }
@@ -0,0 +1,264 @@
+// Netcode Loopback - A host and several clients exchanging real packets over loopback sockets, no window
+// Drives the sessions behind MultiplayerHost and MultiplayerClient headless, with a clock the test steps:
+// joins and leaves, delta-encoded snapshots with acks, checksums, client input, hit events and shot-down notices
+
+use std::cell::Cell;
+use std::net::UdpSocket;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use katie_fly_sim_rust::game_modes::{ClientSession, ClientSessionEvent, HostFuelKeys, HostSession};
+use katie_fly_sim_rust::networking::{ClientInputPacket, Clock};
+use katie_fly_sim_rust::systems::desync::{snapshot_checksum, world_checksum};
+use katie_fly_sim_rust::systems::{CrewRole, EntityId, World};
+use macroquad::prelude::*;
+
+/// Host physics step (seconds)
+const DT: f32 = 1.0 / 60.0;
+/// Longest any scripted wait may take before the test fails
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Seconds the test has stepped, shared by the host and every client
+#[derive(Clone, Default)]
+struct ManualClock(Rc<Cell<f64>>);
+
+impl Clock for ManualClock {
+    fn now(&self) -> f64 {
+        self.0.get()
+    }
+}
+
+/// A client session plus everything it reported, so checks can look back
+struct Client {
+    session: ClientSession,
+    events: Vec<ClientSessionEvent>,
+}
+
+impl Client {
+    fn rocket_players(&self) -> Vec<u32> {
+        let mut players: Vec<u32> = self.session.world().rockets().filter_map(|rocket| rocket.player_id()).collect();
+        players.sort_unstable();
+        players
+    }
+
+    /// Hold these controls (one packet; the host keeps thrust until told otherwise)
+    fn send_input(&mut self, rotation_delta: f32, thrust_level: f32, shoot_bullet: bool) {
+        self.session.send_input(ClientInputPacket {
+            rotation_delta,
+            thrust_level,
+            shoot_bullet,
+            ..ClientInputPacket::idle(self.session.player_id())
+        });
+    }
+}
+
+struct Loopback {
+    clock: ManualClock,
+    host: HostSession,
+    clients: Vec<Client>,
+}
+
+impl Loopback {
+    /// A host with a fresh classic game, and `names` joined in order
+    fn start(names: &[&str]) -> Self {
+        let clock = ManualClock::default();
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let mut host = HostSession::new(socket, "Host".to_string(), Box::new(clock.clone())).unwrap();
+        host.initialize_new_game();
+        let mut loopback = Loopback { clock, host, clients: Vec::new() };
+        for name in names {
+            loopback.join(name);
+        }
+        loopback
+    }
+
+    /// Join one at a time: a client learns its ID from the highest one in its first snapshot
+    fn join(&mut self, name: &str) -> u32 {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let host_addr = self.host.local_addr().unwrap();
+        let mut session =
+            ClientSession::connect(socket, host_addr, name.to_string(), CrewRole::Pilot, Box::new(self.clock.clone())).unwrap();
+        session.prediction_mut().toggle_prediction(); // Hold each snapshot as sent, the way F9 runs the game
+        self.clients.push(Client { session, events: Vec::new() });
+        let index = self.clients.len() - 1;
+        self.pump_until(&format!("{} to get a rocket", name), |loopback| {
+            loopback.clients[index].session.active_rocket_id().is_some()
+        });
+        self.clients[index].session.player_id()
+    }
+
+    /// Step the host, then let every client read what it sent
+    fn pump(&mut self) {
+        self.host.update(DT, HostFuelKeys::default());
+        self.host.take_events();
+        self.clock.0.set(self.clock.0.get() + DT as f64);
+        std::thread::sleep(Duration::from_millis(1));
+        for client in &mut self.clients {
+            client.session.receive(DT);
+            client.session.simulate(DT);
+            client.events.extend(client.session.take_events());
+        }
+    }
+
+    /// Pump until `done` holds, failing the test after `TIMEOUT`
+    fn pump_until(&mut self, what: &str, done: impl Fn(&Loopback) -> bool) {
+        let start = Instant::now();
+        while !done(self) {
+            assert!(start.elapsed() < TIMEOUT, "timed out waiting for {}", what);
+            self.pump();
+        }
+    }
+
+    /// Every client holds the host's latest state
+    fn catch_up(&mut self) {
+        self.pump_until("clients to catch up", |loopback| {
+            loopback.clients.iter().all(|client| client.session.mission_time() == loopback.host.session_time())
+        });
+    }
+
+    /// Fill a player's tank (rockets spawn in orbit with none)
+    fn fuel_up(&mut self, player_id: u32) {
+        let rocket_id = self.rocket_of(player_id);
+        let rocket = self.host.world_mut().get_rocket_mut(rocket_id).unwrap();
+        rocket.set_fuel(rocket.max_fuel());
+    }
+
+    fn rocket_of(&self, player_id: u32) -> EntityId {
+        self.host.world().rockets_with_ids().find(|(_, rocket)| rocket.player_id() == Some(player_id)).map(|(id, _)| id).unwrap()
+    }
+}
+
+#[test]
+fn test_clients_converge_on_the_host_world() {
+    let mut game = Loopback::start(&["Alice", "Bob", "Carol"]);
+    let ids: Vec<u32> = game.clients.iter().map(|client| client.session.player_id()).collect();
+    assert_eq!(ids, vec![1, 2, 3]);
+
+    // Alice burns while turning; Bob and Carol coast
+    game.fuel_up(1);
+    for _ in 0..150 {
+        game.clients[0].send_input(3.0, 1.0, false);
+        game.pump();
+    }
+    game.clients[0].send_input(0.0, 0.0, false);
+    game.catch_up();
+
+    let latest = game.host.create_snapshot();
+    for client in &game.clients {
+        assert_eq!(client.session.desync().mismatch_count(), 0);
+        assert_eq!(world_checksum(client.session.world()), snapshot_checksum(&latest));
+        assert_eq!(client.session.player_names(), game.host.player_names());
+        assert_eq!(client.rocket_players(), vec![0, 1, 2, 3]);
+    }
+
+    // Everyone sees Alice's burn, and nobody else moving under power
+    let alice = game.rocket_of(1);
+    let bob = game.rocket_of(2);
+    let speed_gap = |world: &World| {
+        let velocity = |id| world.get_rocket(id).unwrap().velocity();
+        (velocity(alice) - velocity(bob)).length()
+    };
+    assert!(speed_gap(game.host.world()) > 1.0);
+    for client in &game.clients {
+        assert_eq!(speed_gap(client.session.world()), speed_gap(game.host.world()));
+    }
+}
+
+#[test]
+fn test_join_and_leave_reach_every_client() {
+    let mut game = Loopback::start(&["Alice", "Bob", "Carol"]);
+
+    // Bob leaves: his rocket and name go from the host and from everyone still connected
+    let bob = game.clients.remove(1);
+    bob.session.disconnect();
+    drop(bob);
+    game.pump_until("Bob's rocket to go", |loopback| {
+        loopback.clients.iter().all(|client| client.rocket_players() == vec![0, 1, 3])
+    });
+    assert_eq!(game.host.client_count(), 2);
+    game.catch_up();
+    assert!(game.clients.iter().all(|client| !client.session.player_names().contains_key(&2)));
+
+    // A newcomer gets a fresh player ID and sees the others
+    assert_eq!(game.join("Dave"), 4);
+    game.pump_until("Dave's rocket to reach everyone", |loopback| {
+        loopback.clients.iter().all(|client| client.rocket_players() == vec![0, 1, 3, 4])
+    });
+    game.catch_up();
+    let latest = game.host.create_snapshot();
+    for client in &game.clients {
+        assert_eq!(world_checksum(client.session.world()), snapshot_checksum(&latest));
+        assert_eq!(client.session.player_names().get(&4).map(String::as_str), Some("Dave"));
+    }
+}
+
+#[test]
+fn test_bullet_kill_is_attributed_to_the_shooter() {
+    let mut game = Loopback::start(&["Alice", "Bob"]);
+    game.fuel_up(1);
+
+    // Park Bob in open space a short way to the right of Alice, who faces him
+    let alice = game.rocket_of(1);
+    let bob = game.rocket_of(2);
+    let world = game.host.world_mut();
+    let spot = world.get_rocket(alice).unwrap().position() + Vec2::new(0.0, -3000.0);
+    for (id, position) in [(alice, spot), (bob, spot + Vec2::new(200.0, 0.0))] {
+        let rocket = world.get_rocket_mut(id).unwrap();
+        rocket.take_off();
+        rocket.set_position(position);
+        rocket.set_velocity(Vec2::ZERO);
+    }
+    world.get_rocket_mut(alice).unwrap().set_rotation(std::f32::consts::FRAC_PI_2);
+
+    game.clients[0].send_input(0.0, 0.0, true);
+    let shot_down = |client: &Client| {
+        client.events.iter().find_map(|event| match event {
+            ClientSessionEvent::ShotDown { rocket_id, shot, attacker, .. } => Some((*rocket_id, shot.shooter_id, attacker.clone())),
+            _ => None,
+        })
+    };
+    game.pump_until("Bob to be shot down", |loopback| shot_down(&loopback.clients[1]).is_some());
+    assert_eq!(shot_down(&game.clients[1]), Some((bob, Some(alice), "Alice".to_string())));
+
+    let hit_marker = |client: &Client| client.events.iter().any(|event| matches!(event, ClientSessionEvent::HitLanded(_)));
+    game.pump_until("Alice's hit marker", |loopback| hit_marker(&loopback.clients[0]));
+    assert!(!hit_marker(&game.clients[1]));
+
+    // The wreck and the spent bullet are gone everywhere, and Bob flies a fresh rocket
+    game.catch_up();
+    let latest = game.host.create_snapshot();
+    for client in &game.clients {
+        assert_eq!(client.rocket_players(), vec![0, 1, 2]);
+        assert!(client.session.world().get_rocket(bob).is_none());
+        assert_eq!(client.session.world().bullet_count(), 0);
+        assert_eq!(world_checksum(client.session.world()), snapshot_checksum(&latest));
+    }
+    assert_ne!(game.clients[1].session.active_rocket_id(), Some(bob));
+}
+
+
+#[test]
+fn test_input_from_a_stranger_moves_nothing() {
+    let mut game = Loopback::start(&["Alice"]);
+    game.fuel_up(0);
+    game.fuel_up(1);
+    let rotations = |game: &Loopback| {
+        [0, 1].map(|player_id| game.host.world().get_rocket(game.rocket_of(player_id)).unwrap().rotation())
+    };
+    let before = rotations(&game);
+
+    // Never joined, claims to be the host and then Alice
+    let stranger = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let host_addr = game.host.local_addr().unwrap();
+    for player_id in [0, 1] {
+        let input = ClientInputPacket { rotation_delta: 45.0, shoot_bullet: true, ..ClientInputPacket::idle(player_id) };
+        stranger.send_to(&bincode::serialize(&input).unwrap(), host_addr).unwrap();
+    }
+    for _ in 0..10 {
+        game.pump();
+    }
+
+    assert_eq!(rotations(&game), before);
+    assert_eq!(game.host.world().bullet_count(), 0);
+    assert_eq!(game.host.client_count(), 1);
+}
@@ -22,6 +22,8 @@ enum EditField {
     BodyOrbitalDistance(usize),
     BodyOrbitalPeriod(usize),
     BodyInitialAngle(usize),
+    BodyTerrainSeed(usize),
+    BodyTerrainAmplitude(usize),
     None,
 }
 
@@ -83,6 +85,20 @@ impl App {
             EditField::BodyInitialAngle(i) => {
                 self.current_map.celestial_bodies[*i].initial_angle.to_string()
             }
+            EditField::BodyTerrainSeed(i) => {
+                self.current_map.celestial_bodies[*i]
+                    .terrain
+                    .as_ref()
+                    .map(|t| t.seed.to_string())
+                    .unwrap_or_default()
+            }
+            EditField::BodyTerrainAmplitude(i) => {
+                self.current_map.celestial_bodies[*i]
+                    .terrain
+                    .as_ref()
+                    .map(|t| t.amplitude.to_string())
+                    .unwrap_or_default()
+            }
             EditField::None => String::new(),
         };
     }
@@ -165,6 +181,26 @@ impl App {
                     }
                 }
             }
+            EditField::BodyTerrainSeed(i) => {
+                if i < self.current_map.celestial_bodies.len() {
+                    if let (Ok(val), Some(terrain)) = (
+                        self.text_buffer.parse::<u64>(),
+                        self.current_map.celestial_bodies[i].terrain.as_mut(),
+                    ) {
+                        terrain.seed = val;
+                    }
+                }
+            }
+            EditField::BodyTerrainAmplitude(i) => {
+                if i < self.current_map.celestial_bodies.len() {
+                    if let (Ok(val), Some(terrain)) = (
+                        self.text_buffer.parse::<f32>(),
+                        self.current_map.celestial_bodies[i].terrain.as_mut(),
+                    ) {
+                        terrain.amplitude = val.max(0.0).min(0.5);
+                    }
+                }
+            }
             EditField::None => {}
         }
         self.active_field = EditField::None;
@@ -452,6 +488,7 @@ fn draw_right_panel(app: &mut App, x: f32, width: f32) {
         let body_orbital_distance = app.current_map.celestial_bodies[body_idx].orbital_distance;
         let body_orbital_period = app.current_map.celestial_bodies[body_idx].orbital_period;
         let body_initial_angle = app.current_map.celestial_bodies[body_idx].initial_angle;
+        let body_terrain = app.current_map.celestial_bodies[body_idx].terrain.clone();
 
         draw_text(
             &format!("Editing: {}", body_name),
@@ -537,6 +574,15 @@ fn draw_right_panel(app: &mut App, x: f32, width: f32) {
         ) {
             app.start_editing_field(EditField::BodyColorR(body_idx));
         }
+
+        // Terrain (next to the color fields)
+        let terrain_x = x + 160.0;
+        if draw_checkbox("Terrain", body_terrain.is_some(), terrain_x, y) {
+            app.current_map.celestial_bodies[body_idx].terrain = match body_terrain {
+                Some(_) => None,
+                None => Some(TerrainConfig::default()),
+            };
+        }
         y += 40.0;
 
         // G
@@ -553,6 +599,21 @@ fn draw_right_panel(app: &mut App, x: f32, width: f32) {
         ) {
             app.start_editing_field(EditField::BodyColorG(body_idx));
         }
+        if let Some(terrain) = &body_terrain {
+            draw_text("Seed:", terrain_x, y + 20.0, 20.0, WHITE);
+            if draw_text_field(
+                &terrain.seed.to_string(),
+                terrain_x + 60.0,
+                y,
+                width - 240.0,
+                30.0,
+                &app.active_field,
+                &EditField::BodyTerrainSeed(body_idx),
+        &app.text_buffer,
+            ) {
+                app.start_editing_field(EditField::BodyTerrainSeed(body_idx));
+            }
+        }
         y += 40.0;
 
         // B
@@ -569,6 +630,21 @@ fn draw_right_panel(app: &mut App, x: f32, width: f32) {
         ) {
             app.start_editing_field(EditField::BodyColorB(body_idx));
         }
+        if let Some(terrain) = &body_terrain {
+            draw_text("Height:", terrain_x, y + 20.0, 20.0, WHITE);
+            if draw_text_field(
+                &format!("{:.3}", terrain.amplitude),
+                terrain_x + 60.0,
+                y,
+                width - 240.0,
+                30.0,
+                &app.active_field,
+                &EditField::BodyTerrainAmplitude(body_idx),
+        &app.text_buffer,
+            ) {
+                app.start_editing_field(EditField::BodyTerrainAmplitude(body_idx));
+            }
+        }
         y += 50.0;
 
         // Orbital Settings
@@ -47,6 +47,27 @@ pub struct CelestialBodyConfig {
     pub orbital_period: Option<f32>,
     pub initial_angle: f32,
     pub is_pinned: bool,
+    #[serde(default)]
+    pub terrain: Option<TerrainConfig>,
+}
+
+/// Surface terrain: generated in-game from the seed, or authored heights if any are given
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TerrainConfig {
+    pub seed: u64,
+    pub amplitude: f32, // Tallest peak as a fraction of the radius
+    #[serde(default)]
+    pub heights: Vec<f32>,
+}
+
+impl Default for TerrainConfig {
+    fn default() -> Self {
+        TerrainConfig {
+            seed: 1,
+            amplitude: 0.03,
+            heights: Vec::new(),
+        }
+    }
 }
 
 impl MapConfiguration {
@@ -106,6 +127,11 @@ impl MapConfiguration {
             if body.radius <= 0.0 {
                 return Err(format!("Body '{}' has invalid radius", body.name));
             }
+            if let Some(terrain) = &body.terrain {
+                if !(0.0..=0.5).contains(&terrain.amplitude) {
+                    return Err(format!("Body '{}' terrain amplitude must be 0.0-0.5", body.name));
+                }
+            }
             if let Some(parent_idx) = body.orbital_parent_index {
                 if parent_idx >= self.celestial_bodies.len() {
                     return Err(format!("Body '{}' has invalid parent index", body.name));
@@ -137,6 +163,7 @@ impl CelestialBodyConfig {
             orbital_period: None,
             initial_angle: 0.0,
             is_pinned: true,
+            terrain: None,
         }
     }
 }